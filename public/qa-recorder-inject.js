@@ -14,7 +14,7 @@
   const MAX_TEXT_LENGTH = 160;
 
   let inputTimers = new Map();
-  let scrollTimer = null;
+  let scrollTimers = new Map();
   let resizeTimer = null;
   let lastPointer = null;
   let lastFocusedElement = null;
@@ -28,6 +28,17 @@
 
   console.log('[QA Recorder Inject] Script loaded');
 
+  const DEFAULT_SELECTOR_ATTRIBUTES = ['data-testid', 'data-purpose', 'id', 'name', 'aria-label', 'role'];
+
+  // Attributes selectors are built from, in priority order. The host sets
+  // the session's list on this window, possibly after the script loaded.
+  function selectorAttributeList() {
+    const configured = window.__QA_SELECTOR_ATTRIBUTES__;
+    return Array.isArray(configured) && configured.length > 0
+      ? configured
+      : DEFAULT_SELECTOR_ATTRIBUTES;
+  }
+
   let isReplaying = false;
 
   // Post event to parent window
@@ -42,15 +53,13 @@
 
   // Build CSS selector for element
   function buildSelector(element) {
-    const prioritized = ['data-testid', 'data-purpose', 'id', 'name', 'aria-label', 'role'];
-
-    for (const attr of prioritized) {
+    for (const attr of selectorAttributeList()) {
       const value = element.getAttribute(attr);
       if (!value) continue;
       if (attr === 'id') {
         return `#${CSS.escape(value)}`;
       }
-      return `${element.tagName.toLowerCase()}[${attr}="${CSS.escape(value)}"]`;
+      return `${element.tagName.toLowerCase()}[${CSS.escape(attr)}="${CSS.escape(value)}"]`;
     }
 
     const path = [];
@@ -79,6 +88,36 @@
     return index;
   }
 
+  // Events from inside shadow roots reach document listeners retargeted to
+  // the host; the composed path still starts at the real element
+  function eventTarget(event) {
+    const path = typeof event.composedPath === 'function' ? event.composedPath() : [];
+    return path.length > 0 ? path[0] : event.target;
+  }
+
+  // The selector attributes set on the element, for replay to find it by
+  // another one when the selector's attribute changed
+  function selectorAttributes(element) {
+    const attributes = {};
+    for (const attr of selectorAttributeList()) {
+      const value = element.getAttribute(attr);
+      if (value) attributes[attr] = value;
+    }
+    return Object.keys(attributes).length > 0 ? { attributes } : {};
+  }
+
+  // Selectors of the shadow hosts around `element`, outermost first;
+  // `selector` resolves inside the last one
+  function elementScope(element) {
+    const shadowPath = [];
+    let root = element.getRootNode();
+    while (root instanceof ShadowRoot) {
+      shadowPath.unshift(buildSelector(root.host));
+      root = root.host.getRootNode();
+    }
+    return { shadowPath: shadowPath.length > 0 ? shadowPath : undefined };
+  }
+
   function getElementText(element) {
     if (element instanceof HTMLInputElement || element instanceof HTMLTextAreaElement) {
       return normalizeText(
@@ -159,7 +198,7 @@
   }
 
   function handleClick(event) {
-    const target = event.target;
+    const target = eventTarget(event);
     if (!(target instanceof Element)) return;
     const isEditable =
       target instanceof HTMLInputElement ||
@@ -177,12 +216,14 @@
         type: target instanceof HTMLInputElement ? target.type : undefined,
         isEditable,
         coordinates: getCoordinates(event),
+        ...elementScope(target),
+        ...selectorAttributes(target),
       }),
     });
   }
 
   function handleInput(event) {
-    const target = event.target;
+    const target = eventTarget(event);
     if (!(target instanceof Element)) return;
     if (
       !(
@@ -194,6 +235,9 @@
       return;
     }
 
+    // File inputs are recorded on change, by file name
+    if (target instanceof HTMLInputElement && target.type === 'file') return;
+
     const inputType = event.inputType;
     const previousTimer = inputTimers.get(target);
     if (previousTimer) {
@@ -216,6 +260,8 @@
           inputType,
           type: target instanceof HTMLInputElement ? target.type : undefined,
           coordinates: getCoordinates(event),
+          ...elementScope(target),
+          ...selectorAttributes(target),
         }),
       });
     }, INPUT_DEBOUNCE_MS);
@@ -224,7 +270,7 @@
   }
 
   function handleSubmit(event) {
-    const target = event.target;
+    const target = eventTarget(event);
     if (!(target instanceof Element)) return;
 
     const form = target instanceof HTMLFormElement ? target : target.closest('form');
@@ -240,14 +286,49 @@
         action: form?.action,
         method: form?.method,
         coordinates: getCoordinates(event),
+        ...elementScope(element),
+        ...selectorAttributes(element),
       }),
     });
   }
 
+  // Names, sizes and types of the chosen files; their contents are never read
+  function describeFiles(input) {
+    const files = Array.from(input.files || []).map((file) => ({
+      name: file.name,
+      size: file.size,
+      type: file.type || undefined,
+    }));
+    return {
+      value: files.map((file) => file.name).join(', ') || undefined,
+      files,
+    };
+  }
+
   function handleChange(event) {
-    const target = event.target;
+    const target = eventTarget(event);
     if (!(target instanceof Element)) return;
 
+    if (target instanceof HTMLInputElement && target.type === 'file') {
+      const { value, files } = describeFiles(target);
+      postEventToParent({
+        eventType: 'file_upload',
+        selector: buildSelector(target),
+        elementText: getElementText(target),
+        value,
+        url: window.location.href,
+        metaJson: stringifyMeta({
+          tag: 'input',
+          type: 'file',
+          multiple: target.multiple || undefined,
+          files,
+          ...elementScope(target),
+          ...selectorAttributes(target),
+        }),
+      });
+      return;
+    }
+
     postEventToParent({
       eventType: 'change',
       selector: buildSelector(target),
@@ -258,12 +339,14 @@
         tag: target.tagName.toLowerCase(),
         type: target instanceof HTMLInputElement ? target.type : undefined,
         coordinates: getCoordinates(event),
+        ...elementScope(target),
+        ...selectorAttributes(target),
       }),
     });
   }
 
   function handleDblClick(event) {
-    const target = event.target;
+    const target = eventTarget(event);
     if (!(target instanceof Element)) return;
 
     postEventToParent({
@@ -274,12 +357,14 @@
       metaJson: stringifyMeta({
         tag: target.tagName.toLowerCase(),
         coordinates: getCoordinates(event),
+        ...elementScope(target),
+        ...selectorAttributes(target),
       }),
     });
   }
 
   function handleContextMenu(event) {
-    const target = event.target;
+    const target = eventTarget(event);
     if (!(target instanceof Element)) return;
 
     postEventToParent({
@@ -290,6 +375,8 @@
       metaJson: stringifyMeta({
         tag: target.tagName.toLowerCase(),
         coordinates: getCoordinates(event),
+        ...elementScope(target),
+        ...selectorAttributes(target),
       }),
     });
   }
@@ -297,14 +384,14 @@
   function handleKeyDown(event) {
     // Only capture specific control keys to avoid noise
     const capturedKeys = ['Enter', 'Escape', 'Tab', 'ArrowUp', 'ArrowDown', 'ArrowLeft', 'ArrowRight'];
-    if (!capturedKeys.includes(event.key)) return;
+    if (!capturedKeys.includes(event.key) || event.isComposing) return;
 
-    const target = event.target;
+    const target = eventTarget(event);
     postEventToParent({
       eventType: 'keydown',
       selector: target instanceof Element ? buildSelector(target) : undefined,
       elementText: target instanceof Element ? getElementText(target) : undefined,
-      value: event.key,
+      value: keyCombo(event),
       url: window.location.href,
       metaJson: stringifyMeta({
         key: event.key,
@@ -312,25 +399,79 @@
         ctrlKey: event.ctrlKey,
         shiftKey: event.shiftKey,
         altKey: event.altKey,
+        metaKey: event.metaKey,
+        ...(target instanceof Element
+          ? { ...elementScope(target), ...selectorAttributes(target) }
+          : {}),
       }),
     });
   }
 
-  function handleScroll() {
-    if (scrollTimer) clearTimeout(scrollTimer);
-    scrollTimer = setTimeout(() => {
-      postEventToParent({
-        eventType: 'scroll',
-        url: window.location.href,
-        metaJson: stringifyMeta({
-          scrollX: window.scrollX,
-          scrollY: window.scrollY,
-          timestamp: Date.now(),
-        }),
-      });
-    }, 500);
+  // Modifiers held with the key, in Playwright's `Shift+Tab` form
+  function keyCombo(event) {
+    const modifiers = [
+      event.ctrlKey && 'Control',
+      event.altKey && 'Alt',
+      event.metaKey && 'Meta',
+      event.shiftKey && 'Shift',
+    ].filter(Boolean);
+    return [...modifiers, event.key].join('+');
+  }
+
+  // Window and element scrolls alike, recorded once each settles
+  function handleScroll(event) {
+    const element = event.target instanceof Element ? event.target : null;
+    const source = element || window;
+    const previousTimer = scrollTimers.get(source);
+    if (previousTimer) clearTimeout(previousTimer);
+    scrollTimers.set(
+      source,
+      setTimeout(() => {
+        scrollTimers.delete(source);
+        postEventToParent({
+          eventType: 'scroll',
+          selector: element ? buildSelector(element) : undefined,
+          url: window.location.href,
+          metaJson: stringifyMeta({
+            target: element ? 'element' : 'window',
+            scrollX: Math.round(element ? element.scrollLeft : window.scrollX),
+            scrollY: Math.round(element ? element.scrollTop : window.scrollY),
+            timestamp: Date.now(),
+          }),
+        });
+      }, 500)
+    );
+  }
+
+  // History and hash navigation inside the page; full loads are seen by
+  // the host when the frame loads again
+  let lastNavigationUrl = window.location.href;
+
+  function handleNavigation(trigger) {
+    const url = window.location.href;
+    if (url === lastNavigationUrl) return;
+    lastNavigationUrl = url;
+    postEventToParent({
+      eventType: 'navigation',
+      url,
+      metaJson: stringifyMeta({
+        trigger,
+        pathname: window.location.pathname,
+        search: window.location.search,
+        hash: window.location.hash,
+      }),
+    });
   }
 
+  ['pushState', 'replaceState'].forEach((method) => {
+    const original = history[method];
+    history[method] = function (...args) {
+      const result = original.apply(this, args);
+      handleNavigation(method);
+      return result;
+    };
+  });
+
   function handleResize() {
     if (resizeTimer) clearTimeout(resizeTimer);
     resizeTimer = setTimeout(() => {
@@ -356,21 +497,25 @@
   document.addEventListener('change', handleChange, true);
   document.addEventListener('submit', handleSubmit, true);
   document.addEventListener('keydown', handleKeyDown, true);
-  document.addEventListener('keyup', handleKeyDown, true);
-  window.addEventListener('scroll', handleScroll, { passive: true });
+  document.addEventListener('scroll', handleScroll, { capture: true, passive: true });
+  window.addEventListener('popstate', () => handleNavigation('popstate'));
+  window.addEventListener('hashchange', () => handleNavigation('hashchange'));
   window.addEventListener('resize', handleResize, { passive: true });
   document.addEventListener(
     'focusin',
     (event) => {
-      if (event.target instanceof Element) {
-        lastFocusedElement = event.target;
+      const target = eventTarget(event);
+      if (target instanceof Element) {
+        lastFocusedElement = target;
         postEventToParent({
           eventType: 'focus',
-          selector: buildSelector(event.target),
-          elementText: getElementText(event.target),
+          selector: buildSelector(target),
+          elementText: getElementText(target),
           url: window.location.href,
           metaJson: stringifyMeta({
-            tag: event.target.tagName.toLowerCase(),
+            tag: target.tagName.toLowerCase(),
+            ...elementScope(target),
+            ...selectorAttributes(target),
           }),
         });
       }
@@ -380,14 +525,17 @@
   document.addEventListener(
     'focusout',
     (event) => {
-      if (event.target instanceof Element) {
+      const target = eventTarget(event);
+      if (target instanceof Element) {
         postEventToParent({
           eventType: 'blur',
-          selector: buildSelector(event.target),
-          elementText: getElementText(event.target),
+          selector: buildSelector(target),
+          elementText: getElementText(target),
           url: window.location.href,
           metaJson: stringifyMeta({
-            tag: event.target.tagName.toLowerCase(),
+            tag: target.tagName.toLowerCase(),
+            ...elementScope(target),
+            ...selectorAttributes(target),
           }),
         });
       }
@@ -408,29 +556,120 @@
   }
 
   function dispatchMouseEvent(target, type) {
-    const evt = new MouseEvent(type, {
+    const view = target.ownerDocument?.defaultView ?? window;
+    const evt = new view.MouseEvent(type, {
       bubbles: true,
       cancelable: true,
-      view: window,
+      composed: true,
+      view,
     });
     target.dispatchEvent(evt);
   }
 
   function applyReplayValue(target, value) {
     if (value === undefined || value === null) return;
+    // Elements in a nested frame belong to that frame's globals
+    const view = target.ownerDocument?.defaultView ?? window;
     if (
-      target instanceof HTMLInputElement ||
-      target instanceof HTMLTextAreaElement ||
-      target instanceof HTMLSelectElement
+      target instanceof view.HTMLInputElement ||
+      target instanceof view.HTMLTextAreaElement ||
+      target instanceof view.HTMLSelectElement
     ) {
       target.value = value;
       return;
     }
-    if (target instanceof HTMLElement && target.isContentEditable) {
+    if (target instanceof view.HTMLElement && target.isContentEditable) {
       target.innerText = value;
     }
   }
 
+  // Frame and shadow paths come with the payload or in its recorded
+  // metadata, the element's selector attributes in the metadata
+  function replayScope(payload) {
+    let meta = {};
+    try {
+      meta = payload?.metaJson ? JSON.parse(payload.metaJson) : {};
+    } catch (err) {
+      meta = {};
+    }
+    const list = (value) => (Array.isArray(value) ? value : []);
+    return {
+      framePath: list(payload?.framePath ?? meta?.framePath),
+      shadowPath: list(payload?.shadowPath ?? meta?.shadowPath),
+      attributes: meta?.attributes ?? {},
+    };
+  }
+
+  function findReplayTarget(selector, scope) {
+    let root = document;
+    for (const frameSelector of scope.framePath) {
+      const frameDocument = root.querySelector(frameSelector)?.contentDocument;
+      if (!frameDocument) {
+        throw new Error(`Replay frame not found or not accessible: ${frameSelector}`);
+      }
+      root = frameDocument;
+    }
+    for (const hostSelector of scope.shadowPath) {
+      const shadowRoot = root.querySelector(hostSelector)?.shadowRoot;
+      if (!shadowRoot) {
+        throw new Error(`Replay shadow host not found or closed: ${hostSelector}`);
+      }
+      root = shadowRoot;
+    }
+    return root.querySelector(selector) ?? findByAttributes(root, scope.attributes);
+  }
+
+  // The recorded selector attributes of the element, tried in priority
+  // order when the selector matches nothing
+  function findByAttributes(root, attributes) {
+    for (const attr of selectorAttributeList()) {
+      const value = attributes[attr];
+      if (typeof value !== 'string' || !value) continue;
+      const match = root.querySelector(`[${CSS.escape(attr)}="${CSS.escape(value)}"]`);
+      if (match) return match;
+    }
+    return null;
+  }
+
+  function parseMeta(payload) {
+    try {
+      return payload?.metaJson ? JSON.parse(payload.metaJson) : {};
+    } catch {
+      return {};
+    }
+  }
+
+  // Synthetic key events reach the page's handlers but not the browser's
+  // defaults; a form submitted by Enter has its own recorded submit step
+  function dispatchKey(target, payload) {
+    const meta = parseMeta(payload);
+    const key = meta.key || String(payload?.value || '').split('+').pop();
+    if (!key) {
+      throw new Error('Replay key is missing.');
+    }
+    if (typeof target.focus === 'function') {
+      target.focus({ preventScroll: true });
+    }
+    const init = {
+      key,
+      code: meta.code,
+      ctrlKey: Boolean(meta.ctrlKey),
+      shiftKey: Boolean(meta.shiftKey),
+      altKey: Boolean(meta.altKey),
+      metaKey: Boolean(meta.metaKey),
+      bubbles: true,
+      cancelable: true,
+      composed: true,
+    };
+    isReplaying = true;
+    try {
+      target.dispatchEvent(new KeyboardEvent('keydown', init));
+      target.dispatchEvent(new KeyboardEvent('keyup', init));
+    } finally {
+      isReplaying = false;
+    }
+  }
+
   function replayEvent(payload) {
     const eventType = payload?.eventType;
     const selector = payload?.selector;
@@ -438,19 +677,47 @@
     const url = payload?.url;
 
     if (eventType === 'navigation' && url) {
+      const next = new URL(url, window.location.href);
+      if (next.href === window.location.href) return;
+      const sameDocument =
+        next.origin === window.location.origin &&
+        next.pathname === window.location.pathname &&
+        next.search === window.location.search;
+      if (sameDocument) {
+        window.location.hash = next.hash;
+        return;
+      }
       window.location.assign(url);
       return;
     }
 
+    if (eventType === 'scroll' && !selector) {
+      const meta = parseMeta(payload);
+      window.scrollTo(Number(meta.scrollX) || 0, Number(meta.scrollY) || 0);
+      return;
+    }
+
+    if (eventType === 'file_upload') {
+      throw new Error('Replay of file uploads needs the browser replay; files are not recorded.');
+    }
+
+    if (eventType === 'keydown' && !selector) {
+      dispatchKey(document.activeElement || document.body, payload);
+      return;
+    }
+
     if (!selector) {
       throw new Error('Replay requires a selector.');
     }
 
     let target = null;
     try {
-      target = document.querySelector(selector);
+      target = findReplayTarget(selector, replayScope(payload));
     } catch (err) {
-      throw new Error('Replay selector is invalid.');
+      if (err?.name === 'SyntaxError') {
+        throw new Error('Replay selector is invalid.');
+      }
+      throw err;
     }
 
     if (!target) {
@@ -475,8 +742,8 @@
           target.focus({ preventScroll: true });
         }
         applyReplayValue(target, value);
-        target.dispatchEvent(new Event('input', { bubbles: true }));
-        target.dispatchEvent(new Event('change', { bubbles: true }));
+        target.dispatchEvent(new Event('input', { bubbles: true, composed: true }));
+        target.dispatchEvent(new Event('change', { bubbles: true, composed: true }));
         return;
       }
 
@@ -497,6 +764,17 @@
         return;
       }
 
+      if (eventType === 'keydown') {
+        dispatchKey(target, payload);
+        return;
+      }
+
+      if (eventType === 'scroll') {
+        const meta = parseMeta(payload);
+        target.scrollTo(Number(meta.scrollX) || 0, Number(meta.scrollY) || 0);
+        return;
+      }
+
       if (eventType === 'focus') {
         if (typeof target.focus === 'function') {
           target.focus({ preventScroll: true });
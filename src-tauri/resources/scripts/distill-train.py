@@ -8,6 +8,8 @@ Contract
   - `--config <path>`: JSON file path
   - or `--stdin`: read JSON config from stdin
   - (both can be supported; CLI config takes precedence)
+  - `--resume [<checkpoint dir>]`: continue from a checkpoint under
+    `<run_dir>/checkpoints` (the newest one when no dir is given)
 
 - Output:
   - Writes JSON Lines (one JSON object per line) to stdout.
@@ -31,6 +33,7 @@ import argparse
 import json
 import os
 import random
+import shutil
 import sqlite3
 import sys
 import time
@@ -121,6 +124,7 @@ class TrainConfig:
     dataset_path: Optional[str] = None
     hyperparams: Optional[Dict[str, Any]] = None
     soft_labels_path: Optional[str] = None  # Path to cached soft labels JSONL file (Phase 1 output)
+    resume_from: Optional[str] = None  # Checkpoint dir to continue from (set by --resume)
 
 
 def _parse_config(raw: Dict[str, Any], run_dir_override: Optional[str]) -> TrainConfig:
@@ -408,6 +412,73 @@ def _check_cancel(run_dir: Path) -> None:
         raise CancelledError()
 
 
+def _checkpoints_dir(run_dir: Path) -> Path:
+    return run_dir / "checkpoints"
+
+
+def _latest_checkpoint(run_dir: Path) -> Optional[Path]:
+    root = _checkpoints_dir(run_dir)
+    if not root.is_dir():
+        return None
+    # checkpoint.json is written last, so its presence marks a complete checkpoint
+    done = sorted(p for p in root.iterdir() if p.name.startswith("step-") and (p / "checkpoint.json").is_file())
+    return done[-1] if done else None
+
+
+def _resolve_resume(run_dir: Path, resume: Optional[str]) -> Optional[Path]:
+    if not resume:
+        return None
+    path = _latest_checkpoint(run_dir) if resume == "latest" else Path(resume)
+    if path is None or not (path / "checkpoint.json").is_file():
+        raise ValueError(f"No checkpoint to resume from in {_checkpoints_dir(run_dir)}")
+    return path
+
+
+def _read_checkpoint_meta(checkpoint_dir: Path) -> Dict[str, Any]:
+    return json.loads((checkpoint_dir / "checkpoint.json").read_text(encoding="utf-8"))
+
+
+def _checkpoint_settings(hp: Dict[str, Any], max_steps: int) -> Tuple[int, int]:
+    """(save every N steps, checkpoints kept); N = 0 disables checkpoints."""
+    every_raw = hp.get("checkpoint_every", hp.get("checkpointEvery"))
+    every = max(1, max_steps // 10) if every_raw is None else int(every_raw)
+    keep = int(hp.get("keep_checkpoints", hp.get("keepCheckpoints", 2)) or 1)
+    return max(0, every), max(1, keep)
+
+
+def _write_checkpoint(
+    cfg: TrainConfig,
+    run_dir: Path,
+    step: int,
+    meta: Dict[str, Any],
+    keep: int,
+    save_state: Optional[Any] = None,
+) -> None:
+    """Write `<run_dir>/checkpoints/step-<step>`, report it as an artifact and
+    drop all but the newest `keep` checkpoints. `save_state(dir)` writes the
+    training state files."""
+    root = _checkpoints_dir(run_dir)
+    root.mkdir(parents=True, exist_ok=True)
+    name = f"step-{step:08d}"
+    tmp = root / f".{name}.tmp"
+    shutil.rmtree(tmp, ignore_errors=True)
+    tmp.mkdir(parents=True)
+    if save_state is not None:
+        save_state(tmp)
+    _atomic_write_text(
+        tmp / "checkpoint.json",
+        json.dumps({"run_id": cfg.run_id, "step": step, **meta}, indent=2, ensure_ascii=True),
+    )
+    target = root / name
+    shutil.rmtree(target, ignore_errors=True)
+    tmp.rename(target)
+    _jsonl("artifact", {"run_id": cfg.run_id, "kind": "checkpoint", "path": str(target), "step": step})
+
+    done = sorted(p for p in root.iterdir() if p.name.startswith("step-"))
+    for old in done[:-keep]:
+        shutil.rmtree(old, ignore_errors=True)
+
+
 def _set_seed(seed: Optional[int], deterministic: bool = True) -> None:
     if seed is None:
         return
@@ -646,9 +717,17 @@ def _build_toy_student(samples: List[TrainingSample], max_seq_len: int) -> Tuple
 def _run_stub_training(cfg: TrainConfig, run_dir: Path) -> None:
     start = time.time()
     loss = 2.0
-    for step in range(1, cfg.steps + 1):
+    first_step = 1
+    if cfg.resume_from:
+        meta = _read_checkpoint_meta(Path(cfg.resume_from))
+        first_step = int(meta["step"]) + 1
+        loss = float(meta.get("loss", loss))
+    checkpoint_every, keep_checkpoints = _checkpoint_settings(dict(cfg.hyperparams or {}), cfg.steps)
+    for step in range(first_step, cfg.steps + 1):
         _check_cancel(run_dir)
         loss = max(0.05, loss * 0.985)
+        if checkpoint_every and step % checkpoint_every == 0 and step < cfg.steps:
+            _write_checkpoint(cfg, run_dir, step, {"loss": loss, "stub": True}, keep_checkpoints)
         if step % max(1, cfg.emit_every) == 0:
             _jsonl(
                 "progress",
@@ -1145,8 +1224,55 @@ def _run_training_pipeline(
     total_kd = 0.0
     total_count = 0
 
-    for epoch in range(1, epochs + 1):
+    # Checkpoints are only taken after an optimizer step, so no accumulated
+    # gradients are lost when resuming.
+    checkpoint_every, keep_checkpoints = _checkpoint_settings(hp, max_steps)
+    if checkpoint_every:
+        checkpoint_every = ((checkpoint_every + grad_accum - 1) // grad_accum) * grad_accum
+
+    start_epoch = 1
+    skip_batches = 0
+    resume_gen_state = None
+    if cfg.resume_from:
+        resume_dir = Path(cfg.resume_from)
+        # Loaded on the CPU: RNG states must stay there, and the optimizer
+        # moves its state to the parameters' device itself
+        try:
+            state = torch.load(resume_dir / "trainer_state.pt", map_location="cpu", weights_only=False)
+        except TypeError:  # torch < 1.13 has no weights_only
+            state = torch.load(resume_dir / "trainer_state.pt", map_location="cpu")
+        student_model.load_state_dict(state["model"], strict=False)
+        optimizer.load_state_dict(state["optimizer"])
+        scheduler.load_state_dict(state["scheduler"])
+        global_step = int(state["global_step"])
+        optim_step = int(state["optim_step"])
+        start_epoch = int(state["epoch"])
+        skip_batches = int(state["epoch_batches"])
+        total_loss, total_ce, total_kd, total_count = state["totals"]
+        resume_gen_state = state.get("loader_gen_state")
+        torch.set_rng_state(state["torch_rng"])
+        random.setstate(state["python_rng"])
+        _jsonl(
+            "status",
+            {
+                "level": "info",
+                "message": f"Resumed from checkpoint at step {global_step}",
+                "run_id": cfg.run_id,
+                "checkpoint": str(resume_dir),
+            },
+        )
+
+    for epoch in range(start_epoch, epochs + 1):
+        if loader_gen is not None and resume_gen_state is not None:
+            # Replay the shuffle order of the interrupted epoch
+            loader_gen.set_state(resume_gen_state)
+            resume_gen_state = None
+        epoch_gen_state = loader_gen.get_state() if loader_gen is not None else None
+        epoch_batches = 0
         for batch in train_loader:
+            epoch_batches += 1
+            if epoch_batches <= skip_batches:
+                continue
             _check_cancel(run_dir)
             global_step += 1
             if global_step > max_steps:
@@ -1235,6 +1361,33 @@ def _run_training_pipeline(
             running_ce += float(ce_loss.detach().cpu())
             running_kd += float(kd_loss.detach().cpu())
 
+            if checkpoint_every and global_step % checkpoint_every == 0 and global_step < max_steps:
+                state = {
+                    "model": {
+                        name: p.detach().cpu()
+                        for name, p in student_model.named_parameters()
+                        if p.requires_grad
+                    },
+                    "optimizer": optimizer.state_dict(),
+                    "scheduler": scheduler.state_dict(),
+                    "global_step": global_step,
+                    "optim_step": optim_step,
+                    "epoch": epoch,
+                    "epoch_batches": epoch_batches,
+                    "totals": (total_loss, total_ce, total_kd, total_count),
+                    "loader_gen_state": epoch_gen_state,
+                    "torch_rng": torch.get_rng_state(),
+                    "python_rng": random.getstate(),
+                }
+                _write_checkpoint(
+                    cfg,
+                    run_dir,
+                    global_step,
+                    {"epoch": epoch, "loss": loss_value, "mode": mode},
+                    keep_checkpoints,
+                    lambda d: torch.save(state, d / "trainer_state.pt"),
+                )
+
             if global_step % max(1, cfg.emit_every) == 0:
                 lr_now = float(scheduler.get_last_lr()[0]) if scheduler else lr
                 _jsonl(
@@ -1260,6 +1413,7 @@ def _run_training_pipeline(
                 running_ce = 0.0
                 running_kd = 0.0
 
+        skip_batches = 0
         if global_step > max_steps:
             break
 
@@ -1412,6 +1566,12 @@ def main() -> int:
         "--run-dir",
         help="Override run_dir in config (useful for orchestrator)"
     )
+    parser.add_argument(
+        "--resume",
+        nargs="?",
+        const="latest",
+        help="Continue from a checkpoint dir (default: newest checkpoint of the run)",
+    )
 
     args = parser.parse_args()
 
@@ -1436,6 +1596,9 @@ def main() -> int:
 
         run_dir = Path(cfg.run_dir)
         run_dir.mkdir(parents=True, exist_ok=True)
+        resume_dir = _resolve_resume(run_dir, args.resume)
+        if resume_dir is not None:
+            cfg.resume_from = str(resume_dir)
         config_out = run_dir / "config.json"
         _atomic_write_text(config_out, json.dumps(raw, indent=2, ensure_ascii=True))
 
@@ -1443,7 +1606,7 @@ def main() -> int:
             "status",
             {
                 "level": "info",
-                "message": "trainer started",
+                "message": "trainer resumed" if resume_dir else "trainer started",
                 "run_id": cfg.run_id,
                 "run_dir": str(run_dir),
             },
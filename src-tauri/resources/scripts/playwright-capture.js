@@ -5,6 +5,13 @@
  * Outputs PNG tiles suitable for OCR processing.
  *
  * Usage: node playwright-capture.js <url> <output_dir>
+ *        node playwright-capture.js --batch <job.json>
+ *
+ * A batch job is JSON with `concurrency` and `targets` ([{ url, outputDir }]);
+ * all targets share one browser and every progress line carries `urlIndex`.
+ *
+ * Pages behind a login: GADOGADO_CAPTURE_AUTH may hold JSON with a
+ * `storage_state` file path, `cookies` and extra `headers`.
  *
  * Output:
  * - tiles/tile-001.png, tile-002.png, etc.
@@ -25,7 +32,91 @@ const CONFIG = {
     waitForNetworkIdle: true,
 };
 
+/**
+ * Login state passed by the app; see `CaptureAuth` in the Rust wrapper.
+ */
+function readAuth() {
+    const raw = process.env.GADOGADO_CAPTURE_AUTH;
+    if (!raw) {
+        return null;
+    }
+    const auth = JSON.parse(raw);
+    return {
+        storageState: auth.storage_state || null,
+        headers: auth.headers || {},
+        cookies: auth.cookies || [],
+    };
+}
+
+/** Cookies in Playwright's shape; one without a domain belongs to the captured URL. */
+function toPlaywrightCookies(cookies, url) {
+    return cookies.map((cookie) => {
+        const base = { name: cookie.name, value: cookie.value };
+        if (cookie.secure) base.secure = true;
+        if (cookie.http_only) base.httpOnly = true;
+        if (!cookie.domain) {
+            return { ...base, url: new URL(url).origin };
+        }
+        return { ...base, domain: cookie.domain, path: cookie.path || '/' };
+    });
+}
+
+/** Progress lines are JSON on stdout; `extra` tags them in batch mode. */
+function progressEmitter(extra = {}) {
+    return (event) => console.log(JSON.stringify({ ...extra, ...event }));
+}
+
 async function captureUrl(url, outputDir) {
+    const browser = await chromium.launch({
+        headless: true,
+    });
+    try {
+        return await capturePage(browser, url, outputDir, readAuth(), progressEmitter());
+    } finally {
+        await browser.close();
+    }
+}
+
+/**
+ * Capture the targets of a batch job with at most `concurrency` pages open.
+ * A failed target is recorded in its result and the others carry on.
+ */
+async function captureBatch(jobPath) {
+    const job = JSON.parse(fs.readFileSync(jobPath, 'utf8'));
+    const targets = job.targets || [];
+    const concurrency = Math.max(1, Math.min(job.concurrency || 1, targets.length));
+    const auth = readAuth();
+    const results = new Array(targets.length);
+    let next = 0;
+
+    const browser = await chromium.launch({
+        headless: true,
+    });
+
+    async function worker() {
+        while (next < targets.length) {
+            const urlIndex = next++;
+            const { url, outputDir } = targets[urlIndex];
+            const emit = progressEmitter({ urlIndex });
+            try {
+                const manifest = await capturePage(browser, url, outputDir, auth, emit);
+                results[urlIndex] = { manifest, error: null };
+            } catch (err) {
+                emit({ status: 'failed', error: err.message });
+                results[urlIndex] = { manifest: null, error: err.message };
+            }
+        }
+    }
+
+    try {
+        await Promise.all(Array.from({ length: concurrency }, () => worker()));
+    } finally {
+        await browser.close();
+    }
+    return results;
+}
+
+async function capturePage(browser, url, outputDir, auth, emit) {
     const tilesDir = path.join(outputDir, 'tiles');
 
     // Ensure output directories exist
@@ -36,10 +127,6 @@ async function captureUrl(url, outputDir) {
         fs.mkdirSync(tilesDir, { recursive: true });
     }
 
-    const browser = await chromium.launch({
-        headless: true,
-    });
-
     const context = await browser.newContext({
         viewport: {
             width: CONFIG.viewportWidth,
@@ -47,11 +134,23 @@ async function captureUrl(url, outputDir) {
         },
         deviceScaleFactor: CONFIG.deviceScaleFactor,
         userAgent: 'Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36',
+        ...(auth && auth.storageState ? { storageState: auth.storageState } : {}),
+        ...(auth && Object.keys(auth.headers).length > 0 ? { extraHTTPHeaders: auth.headers } : {}),
     });
+    try {
+        if (auth && auth.cookies.length > 0) {
+            await context.addCookies(toPlaywrightCookies(auth.cookies, url));
+        }
+        return await captureTiles(context, url, outputDir, tilesDir, emit);
+    } finally {
+        await context.close();
+    }
+}
 
+async function captureTiles(context, url, outputDir, tilesDir, emit) {
     const page = await context.newPage();
 
-    console.log(JSON.stringify({ status: 'navigating', url }));
+    emit({ status: 'navigating', url });
 
     try {
         await page.goto(url, {
@@ -61,7 +160,7 @@ async function captureUrl(url, outputDir) {
     } catch (err) {
         // If networkidle times out, try with just load
         if (err.message.includes('timeout')) {
-            console.log(JSON.stringify({ status: 'retrying', reason: 'networkidle timeout' }));
+            emit({ status: 'retrying', reason: 'networkidle timeout' });
             await page.goto(url, {
                 waitUntil: 'load',
                 timeout: CONFIG.timeout,
@@ -96,13 +195,13 @@ async function captureUrl(url, outputDir) {
         };
     });
 
-    console.log(JSON.stringify({ status: 'dimensions', ...dimensions }));
+    emit({ status: 'dimensions', ...dimensions });
 
     const tiles = [];
     const effectiveHeight = CONFIG.viewportHeight - CONFIG.tileOverlap;
     const numTiles = Math.ceil(dimensions.height / effectiveHeight);
 
-    console.log(JSON.stringify({ status: 'capturing', numTiles }));
+    emit({ status: 'capturing', numTiles });
 
     for (let i = 0; i < numTiles; i++) {
         const yOffset = i * effectiveHeight;
@@ -132,15 +231,13 @@ async function captureUrl(url, outputDir) {
             height: CONFIG.viewportHeight * CONFIG.deviceScaleFactor,
         });
 
-        console.log(JSON.stringify({ status: 'tile_captured', index: i, total: numTiles }));
+        emit({ status: 'tile_captured', index: i, total: numTiles });
     }
 
     // Get page title and metadata
     const pageTitle = await page.title();
     const pageUrl = page.url();
 
-    await browser.close();
-
     // Create manifest
     const manifest = {
         engine: 'playwright',
@@ -162,11 +259,11 @@ async function captureUrl(url, outputDir) {
     const manifestPath = path.join(outputDir, 'manifest.json');
     fs.writeFileSync(manifestPath, JSON.stringify(manifest, null, 2));
 
-    console.log(JSON.stringify({
+    emit({
         status: 'complete',
         tilesCount: tiles.length,
         manifestPath: path.relative(outputDir, manifestPath),
-    }));
+    });
 
     return manifest;
 }
@@ -178,18 +275,18 @@ async function main() {
     if (args.length < 2) {
         console.error(JSON.stringify({
             error: 'Missing arguments',
-            usage: 'node playwright-capture.js <url> <output_dir>',
+            usage: 'node playwright-capture.js <url> <output_dir> | --batch <job.json>',
         }));
         process.exit(1);
     }
 
-    const [url, outputDir] = args;
-
     try {
-        const manifest = await captureUrl(url, outputDir);
+        const result = args[0] === '--batch'
+            ? await captureBatch(args[1])
+            : await captureUrl(args[0], args[1]);
         // Final output for parsing
         console.log('---RESULT---');
-        console.log(JSON.stringify(manifest));
+        console.log(JSON.stringify(result));
     } catch (err) {
         console.error(JSON.stringify({
             error: err.message,
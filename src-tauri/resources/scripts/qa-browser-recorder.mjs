@@ -18,6 +18,11 @@ const screenshotDelayMs = screenshotDelayArg ? Number(screenshotDelayArg) : null
 const screenshotDelay = Number.isFinite(screenshotDelayMs)
   ? Math.max(0, screenshotDelayMs)
   : null;
+// Attributes selectors are built from, in priority order
+const selectorAttributes = (getArg("--selector-attributes") ?? "")
+  .split(",")
+  .map((attr) => attr.trim())
+  .filter(Boolean);
 const eventIntervalArg = getArg("--event-interval");
 const eventIntervalMs = eventIntervalArg ? Number(eventIntervalArg) : null;
 const eventInterval = Number.isFinite(eventIntervalMs)
@@ -166,16 +171,42 @@ try {
     }, eventInterval);
   };
 
-  await page.exposeBinding("qaRecordEvent", (_source, payload) => {
+  // Selectors of the iframes from the page down to `frame`, outermost first.
+  // Resolved from the node side so cross-origin frames are covered too.
+  const framePathOf = async (frame) => {
+    const path = [];
+    let current = frame;
+    while (current && current.parentFrame()) {
+      const element = await current.frameElement();
+      const selector = await element
+        .evaluate((el) => window.__qaBuildSelector?.(el) || el.tagName.toLowerCase())
+        .catch(() => "iframe");
+      await element.dispose();
+      path.unshift(selector);
+      current = current.parentFrame();
+    }
+    return path;
+  };
+
+  const recordPayload = (payload) => {
     if (eventInterval === null) {
       queueEmit(payload);
       return;
     }
     eventQueue.push(payload);
     scheduleEventFlush();
+  };
+
+  await page.exposeBinding("qaRecordEvent", async (source, eventPayload) => {
+    let payload = eventPayload;
+    if (source.frame && source.frame.parentFrame()) {
+      const framePath = await framePathOf(source.frame).catch(() => []);
+      payload = { ...payload, meta: { ...payload.meta, framePath } };
+    }
+    recordPayload(payload);
   });
 
-  await page.addInitScript((originValue) => {
+  await page.addInitScript(({ originValue, attributes }) => {
     window.__qaRecorderOrigin = originValue;
     const send = (payload) => {
       if (window.qaRecordEvent) {
@@ -184,7 +215,12 @@ try {
     };
     const buildSelector = (element) => {
       if (!element || element.nodeType !== 1) return null;
-      if (element.id) return `#${CSS.escape(element.id)}`;
+      for (const attr of attributes) {
+        const value = element.getAttribute(attr);
+        if (!value) continue;
+        if (attr === "id") return `#${CSS.escape(value)}`;
+        return `${element.tagName.toLowerCase()}[${CSS.escape(attr)}="${CSS.escape(value)}"]`;
+      }
       const parts = [];
       let current = element;
       while (current && current.nodeType === 1 && parts.length < 5) {
@@ -203,10 +239,35 @@ try {
       }
       return parts.join(" > ");
     };
+    window.__qaBuildSelector = buildSelector;
+    // Selectors of the shadow hosts around `element`, outermost first; the
+    // event selector resolves inside the last one
+    const shadowPathOf = (element) => {
+      const hosts = [];
+      let root = element.getRootNode();
+      while (root instanceof ShadowRoot) {
+        hosts.unshift(buildSelector(root.host));
+        root = root.host.getRootNode();
+      }
+      return hosts;
+    };
     const buildEventPayload = (eventType, target, value, meta = {}) => {
       const selector = target
         ? target.dataset?.qaSelector || buildSelector(target)
         : null;
+      const shadowPath = target ? shadowPathOf(target) : [];
+      if (shadowPath.length > 0) {
+        meta = { ...meta, shadowPath };
+      }
+      // Replay falls back to these when the selector matches nothing
+      const recorded = {};
+      for (const attr of target ? attributes : []) {
+        const attrValue = target.getAttribute(attr);
+        if (attrValue) recorded[attr] = attrValue;
+      }
+      if (Object.keys(recorded).length > 0) {
+        meta = { ...meta, attributes: recorded };
+      }
       const text = target?.innerText ? target.innerText.slice(0, 120) : null;
       const url = window.location.href;
       const payload = {
@@ -221,7 +282,34 @@ try {
       send(payload);
     };
 
-    const safeTarget = (event) => (event.target instanceof Element ? event.target : null);
+    // Events from inside shadow roots are retargeted to the host; the
+    // composed path still starts at the real element
+    const safeTarget = (event) => {
+      const target = event.composedPath()[0] ?? event.target;
+      return target instanceof Element ? target : null;
+    };
+
+    const isSecret = (target) =>
+      (target instanceof HTMLInputElement && target.type === "password") ||
+      ["name", "id", "aria-label"].some((attr) =>
+        (target.getAttribute(attr) || "").toLowerCase().includes("password")
+      );
+
+    const fileInput = (target) =>
+      target instanceof HTMLInputElement && target.type === "file";
+
+    // Typed characters reach replay through input events; only keys that
+    // move focus, confirm or dismiss are steps of their own
+    const capturedKeys = [
+      "Enter",
+      "Escape",
+      "Tab",
+      "ArrowUp",
+      "ArrowDown",
+      "ArrowLeft",
+      "ArrowRight",
+    ];
+    const scrollTimers = new Map();
 
     document.addEventListener(
       "click",
@@ -229,8 +317,18 @@ try {
         const target = safeTarget(event);
         if (!target) return;
         const selector = target.closest("[data-qa-selector]") || target;
+        // Positions let click screenshots be annotated with a highlight
+        const rect = target.getBoundingClientRect();
         buildEventPayload("click", selector, null, {
           tag: target.tagName.toLowerCase(),
+          coordinates: { x: event.clientX, y: event.clientY },
+          rect: {
+            x: rect.left,
+            y: rect.top,
+            width: rect.width,
+            height: rect.height,
+          },
+          viewport: { width: window.innerWidth, height: window.innerHeight },
         });
       },
       true
@@ -240,13 +338,14 @@ try {
       "input",
       (event) => {
         const target = safeTarget(event);
-        if (!target) return;
-        const value =
+        if (!target || fileInput(target)) return;
+        const raw =
           target instanceof HTMLInputElement ||
           target instanceof HTMLTextAreaElement ||
           target instanceof HTMLSelectElement
             ? target.value
             : null;
+        const value = raw && isSecret(target) ? "[masked]" : raw;
         buildEventPayload("input", target, value, {
           tag: target.tagName.toLowerCase(),
         });
@@ -265,7 +364,83 @@ try {
       },
       true
     );
-  }, mode === "ai" ? "ai" : "user");
+
+    // Only names, sizes and types of the chosen files; replay sets files of
+    // the same names
+    document.addEventListener(
+      "change",
+      (event) => {
+        const target = safeTarget(event);
+        if (!target || !fileInput(target)) return;
+        const files = Array.from(target.files || []).map((file) => ({
+          name: file.name,
+          size: file.size,
+          type: file.type || null,
+        }));
+        buildEventPayload(
+          "file_upload",
+          target,
+          files.map((file) => file.name).join(", ") || null,
+          { tag: "input", type: "file", multiple: target.multiple, files }
+        );
+      },
+      true
+    );
+
+    document.addEventListener(
+      "keydown",
+      (event) => {
+        if (!event.isTrusted || event.isComposing) return;
+        if (!capturedKeys.includes(event.key)) return;
+        const target = safeTarget(event);
+        const modifiers = [
+          event.ctrlKey && "Control",
+          event.altKey && "Alt",
+          event.metaKey && "Meta",
+          event.shiftKey && "Shift",
+        ].filter(Boolean);
+        const combo = [...modifiers, event.key].join("+");
+        const focused = target && target !== document.body ? target : null;
+        buildEventPayload("keydown", focused, combo, {
+          key: event.key,
+          tag: focused ? focused.tagName.toLowerCase() : null,
+        });
+      },
+      true
+    );
+
+    // Recorded once the scroll settles, per scrolled element
+    document.addEventListener(
+      "scroll",
+      (event) => {
+        const element =
+          event.target instanceof Element &&
+          event.target !== document.scrollingElement
+            ? event.target
+            : null;
+        const source = element || window;
+        clearTimeout(scrollTimers.get(source));
+        scrollTimers.set(
+          source,
+          setTimeout(() => {
+            scrollTimers.delete(source);
+            buildEventPayload("scroll", element, null, {
+              target: element ? "element" : "window",
+              scrollX: Math.round(element ? element.scrollLeft : window.scrollX),
+              scrollY: Math.round(element ? element.scrollTop : window.scrollY),
+            });
+          }, 400)
+        );
+      },
+      { capture: true, passive: true }
+    );
+  }, {
+    originValue: mode === "ai" ? "ai" : "user",
+    attributes:
+      selectorAttributes.length > 0
+        ? selectorAttributes
+        : ["data-testid", "data-purpose", "id", "name", "aria-label", "role"],
+  });
 
   page.on("requestfinished", async (request) => {
     const response = await request.response();
@@ -297,6 +472,37 @@ try {
   await page.goto(targetUrl, { waitUntil: "domcontentloaded" });
   emit("status", { level: "info", message: `Navigated to ${targetUrl}` });
 
+  // Link follows, redirects and history or hash changes of the page after
+  // the first load; replay waits for these or goes to them
+  let lastNavigatedUrl = page.url();
+  page.on("framenavigated", (frame) => {
+    if (frame !== page.mainFrame()) return;
+    const url = frame.url();
+    if (url === lastNavigatedUrl || url === "about:blank") return;
+    const previous = lastNavigatedUrl;
+    lastNavigatedUrl = url;
+    let parsed = null;
+    try {
+      parsed = new URL(url);
+    } catch {
+      // keep the bare url
+    }
+    recordPayload({
+      eventType: "navigation",
+      selector: null,
+      elementText: null,
+      value: null,
+      url,
+      meta: {
+        from: previous,
+        pathname: parsed?.pathname,
+        search: parsed?.search,
+        hash: parsed?.hash,
+      },
+      origin: mode === "ai" ? "ai" : "user",
+    });
+  });
+
   if (mode === "ai") {
     emit("status", { level: "info", message: "AI exploration started" });
     await page.waitForTimeout(1000);
@@ -1,5 +1,7 @@
 import { chromium } from "playwright";
 import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
 
 const payloadPath = process.argv[2];
 if (!payloadPath) {
@@ -11,6 +13,9 @@ const rawPayload = await fs.readFile(payloadPath, "utf-8");
 const payload = JSON.parse(rawPayload);
 const events = Array.isArray(payload?.events) ? payload.events : [];
 const targetUrl = typeof payload?.targetUrl === "string" ? payload.targetUrl : "";
+const selectorAttributes = Array.isArray(payload?.selectorAttributes)
+  ? payload.selectorAttributes.filter((attr) => typeof attr === "string" && attr)
+  : [];
 
 if (events.length === 0) {
   console.log("No events to replay.");
@@ -36,6 +41,91 @@ const delayBetween = (current, next) => {
 };
 
 const SELECTOR_TIMEOUT_MS = 10000;
+// How long a recorded navigation may take to follow from the step before
+// before replay goes to the URL itself
+const NAVIGATION_WAIT_MS = 3000;
+
+const metaOf = (event) => {
+  if (typeof event.metaJson !== "string" || !event.metaJson) return {};
+  try {
+    return JSON.parse(event.metaJson) ?? {};
+  } catch {
+    return {};
+  }
+};
+
+// Recorded events keep the iframes and shadow hosts around their element in
+// `framePath` / `shadowPath`; the selector resolves inside the innermost one.
+// `attributes` holds the element's selector attributes.
+const scopeOf = (event) => {
+  const meta = metaOf(event);
+  const list = (value) =>
+    Array.isArray(value) ? value.filter((item) => typeof item === "string" && item) : [];
+  const attributes =
+    meta.attributes && typeof meta.attributes === "object" ? meta.attributes : {};
+  return { framePath: list(meta.framePath), shadowPath: list(meta.shadowPath), attributes };
+};
+
+// `[name="value"]`; names may hold `:` or `.`, which CSS needs escaped
+const attributeSelector = (name, value) =>
+  `[${name.replace(/[:.]/g, "\\$&")}="${value.replace(/["\\]/g, "\\$&")}"]`;
+
+// The recorded selector, or when it matches nothing, the first of the
+// element's recorded attributes that does, in the session's priority order
+const locate = async (page, event, selector) => {
+  const { framePath, shadowPath, attributes } = scopeOf(event);
+  let scope = page;
+  for (const frameSelector of framePath) {
+    scope = scope.frameLocator(`${frameSelector} >> nth=0`);
+  }
+  // Locators pierce open shadow roots, so chaining through the hosts keeps
+  // the search inside each one
+  for (const hostSelector of shadowPath) {
+    scope = scope.locator(hostSelector).first();
+  }
+  const primary = scope.locator(selector).first();
+  const fallbacks = selectorAttributes
+    .filter((attr) => typeof attributes[attr] === "string" && attributes[attr])
+    .map((attr) => attributeSelector(attr, attributes[attr]));
+  if (fallbacks.length === 0 || (await primary.count()) > 0) {
+    return primary;
+  }
+  for (const fallback of fallbacks) {
+    const candidate = scope.locator(fallback).first();
+    if ((await candidate.count()) > 0) {
+      console.log(`Selector ${selector} matched nothing, using ${fallback}`);
+      return candidate;
+    }
+  }
+  return primary;
+};
+
+// Recordings keep only the names of chosen files, so replay uploads empty
+// files of the same names
+const placeholderFiles = async (event) => {
+  const files = Array.isArray(metaOf(event).files) ? metaOf(event).files : [];
+  const names = files
+    .map((file) => (typeof file?.name === "string" ? path.basename(file.name) : ""))
+    .filter(Boolean);
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), "qa-replay-upload-"));
+  return Promise.all(
+    names.map(async (name) => {
+      const filePath = path.join(dir, name);
+      await fs.writeFile(filePath, "");
+      return filePath;
+    })
+  );
+};
+
+const sameDocument = (current, next) => {
+  try {
+    const from = new URL(current);
+    const to = new URL(next);
+    return from.origin === to.origin && from.pathname === to.pathname && from.search === to.search;
+  } catch {
+    return false;
+  }
+};
 
 const withStepGuard = async (label, action) => {
   try {
@@ -68,15 +158,69 @@ try {
       const nextUrl = eventUrl || firstUrl;
       console.log(`Navigate -> ${nextUrl}`);
       await withStepGuard("Navigation", async () => {
+        // Usually the result of the step before; only go there when the
+        // page doesn't get there on its own
+        const reached = await page
+          .waitForURL(nextUrl, { timeout: NAVIGATION_WAIT_MS })
+          .then(() => true, () => false);
+        if (reached) return;
+        if (sameDocument(page.url(), nextUrl)) {
+          await page.evaluate((hash) => {
+            window.location.hash = hash;
+          }, new URL(nextUrl).hash);
+          return;
+        }
         await page.goto(nextUrl, { waitUntil: "domcontentloaded" });
       });
+    } else if (eventType === "keydown") {
+      const combo = String(value || metaOf(event).key || "");
+      if (!combo) {
+        console.error("Missing key for keydown.");
+      } else if (!selector) {
+        console.log(`Press -> ${combo}`);
+        await withStepGuard(`Press ${combo}`, async () => {
+          await page.keyboard.press(combo);
+        });
+      } else {
+        console.log(`Press -> ${combo} on ${selector}`);
+        await withStepGuard(`Press ${combo}`, async () => {
+          const locator = await locate(page, event, selector);
+          await locator.press(combo, { timeout: SELECTOR_TIMEOUT_MS });
+        });
+      }
+    } else if (eventType === "scroll") {
+      const meta = metaOf(event);
+      const position = { x: Number(meta.scrollX) || 0, y: Number(meta.scrollY) || 0 };
+      console.log(`Scroll -> ${selector || "window"} (${position.x}, ${position.y})`);
+      await withStepGuard("Scroll", async () => {
+        if (!selector) {
+          await page.evaluate(({ x, y }) => window.scrollTo(x, y), position);
+          return;
+        }
+        const locator = await locate(page, event, selector);
+        await locator.evaluate((el, { x, y }) => el.scrollTo(x, y), position, {
+          timeout: SELECTOR_TIMEOUT_MS,
+        });
+      });
+    } else if (eventType === "file_upload") {
+      if (!selector) {
+        console.error("Missing selector for file upload.");
+      } else {
+        console.log(`Upload -> ${selector} (${value || "no files"})`);
+        await withStepGuard(`Upload ${selector}`, async () => {
+          const locator = await locate(page, event, selector);
+          await locator.setInputFiles(await placeholderFiles(event), {
+            timeout: SELECTOR_TIMEOUT_MS,
+          });
+        });
+      }
     } else if (eventType === "click") {
       if (!selector) {
         console.error("Missing selector for click.");
       } else {
         console.log(`Click -> ${selector}`);
         await withStepGuard(`Click ${selector}`, async () => {
-          const locator = page.locator(selector).first();
+          const locator = await locate(page, event, selector);
           await locator.waitFor({ state: "visible", timeout: SELECTOR_TIMEOUT_MS });
           await locator.click({ timeout: SELECTOR_TIMEOUT_MS });
         });
@@ -87,7 +231,7 @@ try {
       } else {
         console.log(`Fill -> ${selector}`);
         await withStepGuard(`Fill ${selector}`, async () => {
-          const locator = page.locator(selector).first();
+          const locator = await locate(page, event, selector);
           await locator.waitFor({ state: "visible", timeout: SELECTOR_TIMEOUT_MS });
           await locator.fill(String(value ?? ""), { timeout: SELECTOR_TIMEOUT_MS });
         });
@@ -98,7 +242,7 @@ try {
       } else {
         console.log(`Focus -> ${selector}`);
         await withStepGuard(`Focus ${selector}`, async () => {
-          const locator = page.locator(selector).first();
+          const locator = await locate(page, event, selector);
           await locator.waitFor({ state: "visible", timeout: SELECTOR_TIMEOUT_MS });
           await locator.focus({ timeout: SELECTOR_TIMEOUT_MS });
         });
@@ -109,40 +253,52 @@ try {
       } else {
         console.log(`Blur -> ${selector}`);
         await withStepGuard(`Blur ${selector}`, async () => {
-          await page.evaluate((sel) => {
-            const el = document.querySelector(sel);
-            if (el && typeof el.blur === "function") {
-              el.blur();
-            }
-          }, selector);
+          const locator = await locate(page, event, selector);
+          await locator.evaluate(
+            (el) => {
+              if (typeof el.blur === "function") {
+                el.blur();
+              }
+            },
+            undefined,
+            { timeout: SELECTOR_TIMEOUT_MS }
+          );
         });
       }
     } else if (eventType === "submit") {
-      if (!selector) {
+      const previous = sortedEvents[index - 1];
+      const previousType = String(previous?.eventType ?? previous?.event_type ?? "").toLowerCase();
+      if (previousType === "keydown" && String(previous.value ?? "").endsWith("Enter")) {
+        // The replayed Enter press submitted the form already
+        console.log("Submit -> done by Enter");
+      } else if (!selector) {
         console.error("Missing selector for submit.");
       } else {
         console.log(`Submit -> ${selector}`);
         await withStepGuard(`Submit ${selector}`, async () => {
-          await page.evaluate((sel) => {
-            const el = document.querySelector(sel);
-            if (!el) return;
-            if (el instanceof HTMLFormElement) {
-              if (typeof el.requestSubmit === "function") {
-                el.requestSubmit();
-              } else {
-                el.submit();
+          const locator = await locate(page, event, selector);
+          await locator.evaluate(
+            (el) => {
+              if (el instanceof HTMLFormElement) {
+                if (typeof el.requestSubmit === "function") {
+                  el.requestSubmit();
+                } else {
+                  el.submit();
+                }
+                return;
               }
-              return;
-            }
-            const form = el.closest("form");
-            if (form instanceof HTMLFormElement) {
-              if (typeof form.requestSubmit === "function") {
-                form.requestSubmit();
-              } else {
-                form.submit();
+              const form = el.closest("form");
+              if (form instanceof HTMLFormElement) {
+                if (typeof form.requestSubmit === "function") {
+                  form.requestSubmit();
+                } else {
+                  form.submit();
+                }
               }
-            }
-          }, selector);
+            },
+            undefined,
+            { timeout: SELECTOR_TIMEOUT_MS }
+          );
         });
       }
     } else {
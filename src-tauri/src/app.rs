@@ -3,13 +3,19 @@ use std::sync::Arc;
 use tauri::Manager;
 
 pub fn run() {
-    let _ = tracing_subscriber::fmt().with_env_filter("info").try_init();
+    crate::infrastructure::logging::init();
 
     tauri::Builder::default()
+        // Registered first so a second launch exits before anything starts
+        .plugin(tauri_plugin_single_instance::init(|app, argv, cwd| {
+            crate::interfaces::background::on_second_instance(app, argv, cwd)
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(crate::interfaces::background::autostart_plugin())
         .setup(|app| crate::infrastructure::bootstrap::setup(app))
         .invoke_handler(crate::tauri_invoke_handler!())
         .on_window_event(|window, event| {
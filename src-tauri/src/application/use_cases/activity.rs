@@ -0,0 +1,65 @@
+use crate::domain::activity::{ActivityInput, ActivityPage, ActivityQuery};
+use crate::domain::error::Result;
+use crate::infrastructure::db::activity::ActivityRepository;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+/// Entries kept in the table; older ones are pruned as new ones arrive
+const MAX_ENTRIES: i64 = 50_000;
+/// Inserts between two prunes
+const PRUNE_EVERY: u64 = 500;
+
+/// Records what the subsystems do into the activity timeline. Recording
+/// never waits on the database: entries are queued and written in order
+/// by the `ActivityWriter`.
+pub struct ActivityFeed {
+    repository: Arc<ActivityRepository>,
+    sender: mpsc::UnboundedSender<ActivityInput>,
+}
+
+impl ActivityFeed {
+    /// The feed and the writer to spawn for it
+    pub fn new(repository: Arc<ActivityRepository>) -> (Self, ActivityWriter) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let writer = ActivityWriter {
+            repository: repository.clone(),
+            receiver,
+        };
+        (Self { repository, sender }, writer)
+    }
+
+    pub fn record(&self, input: ActivityInput) {
+        if self.sender.send(input).is_err() {
+            warn!("Activity writer has stopped; entry dropped");
+        }
+    }
+
+    pub async fn query(&self, query: &ActivityQuery) -> Result<ActivityPage> {
+        self.repository.query(query).await
+    }
+}
+
+pub struct ActivityWriter {
+    repository: Arc<ActivityRepository>,
+    receiver: mpsc::UnboundedReceiver<ActivityInput>,
+}
+
+impl ActivityWriter {
+    /// Writes queued entries until every `ActivityFeed` handle is dropped
+    pub async fn run(mut self) {
+        let mut written = 0u64;
+        while let Some(input) = self.receiver.recv().await {
+            if let Err(err) = self.repository.insert(&input).await {
+                warn!(error = %err, source = input.source.as_str(), "Failed to record activity");
+                continue;
+            }
+            written += 1;
+            if written.is_multiple_of(PRUNE_EVERY) {
+                if let Err(err) = self.repository.prune(MAX_ENTRIES).await {
+                    warn!(error = %err, "Failed to prune activity");
+                }
+            }
+        }
+    }
+}
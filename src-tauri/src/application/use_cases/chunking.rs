@@ -16,6 +16,10 @@ impl Default for ChunkStrategy {
     }
 }
 
+/// Token budget of a parent section stored at ingestion. Retrieval matches
+/// the small chunks and may return their section as context.
+pub const PARENT_SECTION_MAX_TOKENS: usize = 1500;
+
 pub struct ChunkConfig {
     pub max_chunk_size: usize,
     pub overlap: usize,
@@ -590,6 +594,25 @@ impl ChunkEngine {
     }
 }
 
+/// Group consecutive chunks into parent sections of at most `max_tokens`,
+/// starting a new section at each header. Returns, for every chunk, the
+/// position of the first chunk of its section.
+pub fn parent_sections(chunks: &[Chunk], max_tokens: usize) -> Vec<usize> {
+    let mut parents = Vec::with_capacity(chunks.len());
+    let mut start = 0;
+    let mut tokens = 0;
+    for (position, chunk) in chunks.iter().enumerate() {
+        let is_header = chunk.content_type.as_deref() == Some("header");
+        if position > start && (is_header || tokens + chunk.token_count > max_tokens) {
+            start = position;
+            tokens = 0;
+        }
+        tokens += chunk.token_count;
+        parents.push(start);
+    }
+    parents
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -610,4 +633,33 @@ mod tests {
         let chunks = engine.chunk_text(&text).unwrap();
         assert!(chunks.len() > 1);
     }
+
+    fn chunk(tokens: usize, content_type: Option<&str>) -> Chunk {
+        Chunk {
+            content: String::new(),
+            token_count: tokens,
+            page_number: None,
+            page_offset: None,
+            quality_score: None,
+            content_type: content_type.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parent_sections_split_on_budget_and_headers() {
+        let chunks = vec![
+            chunk(100, Some("paragraph")),
+            chunk(100, Some("paragraph")),
+            chunk(100, Some("paragraph")),
+            chunk(50, Some("header")),
+            chunk(100, Some("paragraph")),
+        ];
+        assert_eq!(parent_sections(&chunks, 250), vec![0, 0, 2, 3, 3]);
+    }
+
+    #[test]
+    fn test_parent_sections_keep_oversized_chunk_alone() {
+        let chunks = vec![chunk(400, None), chunk(400, None)];
+        assert_eq!(parent_sections(&chunks, 250), vec![0, 1]);
+    }
 }
@@ -0,0 +1,437 @@
+//! Syntax-aware chunking of source files. Files in a language with a
+//! tree-sitter grammar are split along function, class and impl boundaries:
+//! a definition that fits the budget stays whole, small neighbours are
+//! grouped, and oversized ones are split along their members, which keep the
+//! signature of what encloses them. Other languages fall back to windows of
+//! whole lines.
+
+use tree_sitter::{Language, Node, Parser};
+
+/// Largest chunk of code, in bytes
+pub const MAX_CODE_CHUNK_CHARS: usize = 1500;
+
+/// Definitions are grouped with their neighbours until a chunk reaches this
+const MIN_CODE_CHUNK_CHARS: usize = 200;
+
+/// Longest enclosing signature kept as context
+const MAX_SIGNATURE_CHARS: usize = 120;
+
+/// Node kinds that start a definition in the supported grammars
+const DEFINITION_KINDS: &[&str] = &[
+    // Rust
+    "function_item",
+    "function_signature_item",
+    "impl_item",
+    "struct_item",
+    "enum_item",
+    "union_item",
+    "trait_item",
+    "mod_item",
+    "macro_definition",
+    "type_item",
+    // Python
+    "function_definition",
+    "class_definition",
+    "decorated_definition",
+    // JavaScript and TypeScript
+    "function_declaration",
+    "generator_function_declaration",
+    "class_declaration",
+    "abstract_class_declaration",
+    "method_definition",
+    "interface_declaration",
+    "type_alias_declaration",
+    "enum_declaration",
+    "export_statement",
+    // Go
+    "method_declaration",
+    "type_declaration",
+    // Java
+    "constructor_declaration",
+    "record_declaration",
+];
+
+/// Directories whose files are generated or vendored
+const IGNORED_DIRS: &[&str] = &[
+    ".git",
+    "node_modules",
+    "vendor",
+    "dist",
+    "build",
+    "target",
+    "out",
+    ".next",
+    "__pycache__",
+    ".venv",
+    "venv",
+];
+
+/// Lock files and other generated files with a source extension
+const IGNORED_FILES: &[&str] = &[
+    "package-lock.json",
+    "pnpm-lock.yaml",
+    "yarn.lock",
+    "Cargo.lock",
+    "poetry.lock",
+    "go.sum",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodeChunk {
+    pub content: String,
+    /// 1-based, inclusive
+    pub start_line: usize,
+    pub end_line: usize,
+    /// Signatures of the definitions enclosing the chunk, outermost first
+    pub scope: Vec<String>,
+    /// Names of the definitions in the chunk
+    pub symbols: Vec<String>,
+}
+
+/// Language of a repository file by its name, or `None` for files that are
+/// not imported (binary formats, images, data)
+pub fn language_for_path(path: &str) -> Option<&'static str> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    match name {
+        "Dockerfile" => return Some("dockerfile"),
+        "Makefile" => return Some("makefile"),
+        _ => {}
+    }
+    let extension = name.rsplit_once('.')?.1.to_lowercase();
+    let language = match extension.as_str() {
+        "rs" => "rust",
+        "py" | "pyi" => "python",
+        "js" | "mjs" | "cjs" | "jsx" => "javascript",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "tsx",
+        "go" => "go",
+        "java" => "java",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hh" | "hpp" => "cpp",
+        "cs" => "csharp",
+        "rb" => "ruby",
+        "php" => "php",
+        "kt" | "kts" => "kotlin",
+        "swift" => "swift",
+        "scala" => "scala",
+        "sh" | "bash" | "zsh" => "shell",
+        "sql" => "sql",
+        "css" | "scss" => "css",
+        "html" | "htm" | "vue" | "svelte" => "html",
+        "toml" => "toml",
+        "yaml" | "yml" => "yaml",
+        "json" => "json",
+        "md" | "markdown" | "mdx" => "markdown",
+        "rst" => "restructuredtext",
+        "txt" => "text",
+        _ => return None,
+    };
+    Some(language)
+}
+
+/// Whether the file is documentation, chunked like any other text
+pub fn is_prose(language: &str) -> bool {
+    matches!(language, "markdown" | "restructuredtext" | "text")
+}
+
+/// Whether a path is vendored, generated or a lock file
+pub fn is_ignored_path(path: &str) -> bool {
+    let mut parts = path.split('/').peekable();
+    while let Some(part) = parts.next() {
+        if parts.peek().is_none() {
+            return IGNORED_FILES.contains(&part)
+                || part.ends_with(".min.js")
+                || part.ends_with(".min.css");
+        }
+        if IGNORED_DIRS.contains(&part) {
+            return true;
+        }
+    }
+    false
+}
+
+fn grammar(language: &str) -> Option<Language> {
+    let grammar = match language {
+        "rust" => tree_sitter_rust::LANGUAGE,
+        "python" => tree_sitter_python::LANGUAGE,
+        "javascript" => tree_sitter_javascript::LANGUAGE,
+        "typescript" => tree_sitter_typescript::LANGUAGE_TYPESCRIPT,
+        "tsx" => tree_sitter_typescript::LANGUAGE_TSX,
+        "go" => tree_sitter_go::LANGUAGE,
+        "java" => tree_sitter_java::LANGUAGE,
+        _ => return None,
+    };
+    Some(grammar.into())
+}
+
+/// Chunks of a source file, along syntax boundaries when the language has
+/// a grammar
+pub fn chunk_source(language: &str, source: &str) -> Vec<CodeChunk> {
+    if source.trim().is_empty() {
+        return Vec::new();
+    }
+    let tree = grammar(language).and_then(|grammar| {
+        let mut parser = Parser::new();
+        parser.set_language(&grammar).ok()?;
+        parser.parse(source, None)
+    });
+    let mut chunker = Chunker {
+        source,
+        chunks: Vec::new(),
+    };
+    match tree {
+        Some(tree) => chunker.chunk_children(tree.root_node(), &[], None),
+        None => chunker.line_windows(0, source.len(), &[]),
+    }
+    chunker.chunks
+}
+
+/// Consecutive sibling nodes collected into one chunk
+struct Group {
+    start: usize,
+    end: usize,
+    has_definition: bool,
+    symbols: Vec<String>,
+}
+
+struct Chunker<'a> {
+    source: &'a str,
+    chunks: Vec<CodeChunk>,
+}
+
+impl Chunker<'_> {
+    /// Chunk the children of `parent`. `carried` is the pending group of the
+    /// level above, so the head of a split node (`impl Parser {`) opens the
+    /// chunk of its first member.
+    fn chunk_children(&mut self, parent: Node, scope: &[String], carried: Option<Group>) {
+        let mut group = carried;
+        let mut cursor = parent.walk();
+        for child in parent.children(&mut cursor) {
+            let text = &self.source[child.byte_range()];
+            // Braces and other punctuation between members
+            if !child.is_named() && !text.chars().any(char::is_alphanumeric) {
+                continue;
+            }
+
+            let definition = DEFINITION_KINDS.contains(&child.kind());
+            let oversized = child.byte_range().len() > MAX_CODE_CHUNK_CHARS;
+            let starts_new = group.as_ref().is_some_and(|group| {
+                (!oversized && child.end_byte() - group.start > MAX_CODE_CHUNK_CHARS)
+                    || (definition
+                        && group.has_definition
+                        && group.end - group.start >= MIN_CODE_CHUNK_CHARS)
+            });
+            if starts_new {
+                self.flush(group.take(), scope);
+            }
+
+            if oversized {
+                if child.named_child_count() > 0 {
+                    let mut inner = scope.to_vec();
+                    if definition {
+                        inner.push(self.signature(child));
+                    }
+                    self.chunk_children(child, &inner, group.take());
+                } else {
+                    self.flush(group.take(), scope);
+                    self.line_windows(child.start_byte(), child.end_byte(), scope);
+                }
+                continue;
+            }
+
+            let group = group.get_or_insert_with(|| Group {
+                start: child.start_byte(),
+                end: child.end_byte(),
+                has_definition: false,
+                symbols: Vec::new(),
+            });
+            group.end = child.end_byte();
+            if definition {
+                group.has_definition = true;
+                if let Some(name) = symbol_name(child, self.source) {
+                    group.symbols.push(name);
+                }
+            }
+        }
+        self.flush(group, scope);
+    }
+
+    fn flush(&mut self, group: Option<Group>, scope: &[String]) {
+        let Some(group) = group else {
+            return;
+        };
+        let text = &self.source[group.start..group.end];
+        if !text.chars().any(char::is_alphanumeric) {
+            return;
+        }
+        self.chunks.push(CodeChunk {
+            content: text.to_string(),
+            start_line: line_of(self.source, group.start),
+            end_line: line_of(self.source, group.end.saturating_sub(1).max(group.start)),
+            scope: scope.to_vec(),
+            symbols: group.symbols,
+        });
+    }
+
+    /// Windows of whole lines, each at most the chunk budget; a longer line
+    /// is cut at character boundaries
+    fn line_windows(&mut self, start: usize, end: usize, scope: &[String]) {
+        let mut window_start = start;
+        let mut window_end = start;
+        let mut offset = start;
+        for line in self.source[start..end].split_inclusive('\n') {
+            let line_end = offset + line.len();
+            if line_end - window_start > MAX_CODE_CHUNK_CHARS && window_end > window_start {
+                self.push_window(window_start, window_end, scope);
+                window_start = window_end;
+            }
+            while line_end - window_start > MAX_CODE_CHUNK_CHARS {
+                let mut cut = window_start + MAX_CODE_CHUNK_CHARS;
+                while !self.source.is_char_boundary(cut) {
+                    cut -= 1;
+                }
+                self.push_window(window_start, cut, scope);
+                window_start = cut;
+            }
+            window_end = line_end;
+            offset = line_end;
+        }
+        if window_end > window_start {
+            self.push_window(window_start, window_end, scope);
+        }
+    }
+
+    fn push_window(&mut self, start: usize, end: usize, scope: &[String]) {
+        let text = &self.source[start..end];
+        if text.trim().is_empty() {
+            return;
+        }
+        self.chunks.push(CodeChunk {
+            content: text.trim_end().to_string(),
+            start_line: line_of(self.source, start),
+            end_line: line_of(self.source, end.saturating_sub(1).max(start)),
+            scope: scope.to_vec(),
+            symbols: Vec::new(),
+        });
+    }
+
+    /// First line of a definition without its opening brace or colon
+    fn signature(&self, node: Node) -> String {
+        let text = &self.source[node.byte_range()];
+        let line = text.lines().next().unwrap_or_default().trim();
+        let line = line.trim_end_matches(['{', ':']).trim_end();
+        line.chars().take(MAX_SIGNATURE_CHARS).collect()
+    }
+}
+
+/// Name of a definition, e.g. `parse` or `Display for Token`
+fn symbol_name(node: Node, source: &str) -> Option<String> {
+    let text = |node: Node| source[node.byte_range()].to_string();
+    match node.kind() {
+        "impl_item" => {
+            let target = node.child_by_field_name("type").map(text)?;
+            Some(match node.child_by_field_name("trait").map(text) {
+                Some(trait_name) => format!("{} for {}", trait_name, target),
+                None => target,
+            })
+        }
+        "export_statement" => node
+            .child_by_field_name("declaration")
+            .and_then(|declaration| symbol_name(declaration, source)),
+        "decorated_definition" => node
+            .child_by_field_name("definition")
+            .and_then(|definition| symbol_name(definition, source)),
+        "type_declaration" => {
+            let mut cursor = node.walk();
+            let spec = node
+                .named_children(&mut cursor)
+                .find(|child| child.kind() == "type_spec")?;
+            spec.child_by_field_name("name").map(text)
+        }
+        _ => node.child_by_field_name("name").map(text),
+    }
+}
+
+/// 1-based line of a byte offset
+fn line_of(source: &str, offset: usize) -> usize {
+    source[..offset].matches('\n').count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn languages_and_ignored_paths() {
+        assert_eq!(language_for_path("src/lib.rs"), Some("rust"));
+        assert_eq!(language_for_path("web/App.TSX"), Some("tsx"));
+        assert_eq!(language_for_path("docker/Dockerfile"), Some("dockerfile"));
+        assert_eq!(language_for_path("assets/logo.png"), None);
+        assert!(is_prose("markdown"));
+        assert!(is_ignored_path("web/node_modules/react/index.js"));
+        assert!(is_ignored_path("Cargo.lock"));
+        assert!(is_ignored_path("static/app.min.js"));
+        assert!(!is_ignored_path("src/build.rs"));
+    }
+
+    #[test]
+    fn small_definitions_are_grouped_with_their_neighbours() {
+        let source = "use std::fmt;\n\nfn a() {}\n\nfn b() {}\n";
+        let chunks = chunk_source("rust", source);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].symbols, vec!["a", "b"]);
+        assert_eq!((chunks[0].start_line, chunks[0].end_line), (1, 5));
+    }
+
+    #[test]
+    fn large_impl_is_split_along_methods_with_its_signature() {
+        let body = "        let value = 1;\n".repeat(40);
+        let source = format!(
+            "/// Parses tokens\npub struct Parser;\n\nimpl Parser {{\n    fn first(&self) {{\n{body}    }}\n\n    fn second(&self) {{\n{body}    }}\n}}\n"
+        );
+        let chunks = chunk_source("rust", &source);
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks
+            .iter()
+            .all(|chunk| chunk.scope == vec!["impl Parser".to_string()]));
+        assert_eq!(chunks[0].symbols, vec!["Parser", "first"]);
+        assert!(chunks[0].content.starts_with("/// Parses tokens"));
+        assert!(chunks[0]
+            .content
+            .contains("impl Parser {\n    fn first(&self)"));
+        assert_eq!(chunks[1].symbols, vec!["second"]);
+        assert!(chunks[1].content.starts_with("fn second(&self)"));
+        assert!(chunks
+            .iter()
+            .all(|chunk| chunk.content.len() <= MAX_CODE_CHUNK_CHARS));
+    }
+
+    #[test]
+    fn python_classes_keep_decorated_methods() {
+        let body = "        total = total + 1\n".repeat(40);
+        let source = format!(
+            "class Cart:\n    @property\n    def total(self):\n{body}\n    def clear(self):\n{body}"
+        );
+        let chunks = chunk_source("python", &source);
+        let symbols: Vec<_> = chunks
+            .iter()
+            .flat_map(|chunk| chunk.symbols.clone())
+            .collect();
+        assert_eq!(symbols, vec!["total", "clear"]);
+        assert!(chunks
+            .iter()
+            .all(|chunk| chunk.scope == vec!["class Cart".to_string()]));
+    }
+
+    #[test]
+    fn languages_without_grammar_use_line_windows() {
+        let source = "SELECT 1;\n".repeat(400);
+        let chunks = chunk_source("sql", &source);
+        assert!(chunks.len() > 1);
+        assert_eq!(chunks[0].start_line, 1);
+        assert_eq!(chunks[1].start_line, chunks[0].end_line + 1);
+        assert!(chunks
+            .iter()
+            .all(|chunk| chunk.content.len() <= MAX_CODE_CHUNK_CHARS));
+    }
+}
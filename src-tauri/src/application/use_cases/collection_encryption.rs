@@ -0,0 +1,172 @@
+//! Per-collection encryption at rest for confidential documents.
+//!
+//! Chunk text and embeddings of an encrypted collection are stored sealed
+//! and only opened in memory when read. The key is derived from a passphrase
+//! the user chooses and kept in the OS keychain, so the collection unlocks
+//! on its own at startup; on another device, or after locking it, the
+//! passphrase is needed again. Keyword (FTS) search does not see encrypted
+//! text, so retrieval in these collections relies on embeddings.
+
+use crate::domain::error::{AppError, Result};
+use crate::infrastructure::config::ConfigService;
+use crate::infrastructure::db::rag::repository::{CollectionEncryption, RagRepository};
+use crate::infrastructure::security::collection_cipher::{
+    derive_key, generate_salt, CollectionCipher, KEY_LEN, PBKDF2_ITERATIONS,
+};
+use serde::Serialize;
+
+const MIN_PASSPHRASE_CHARS: usize = 8;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectionEncryptionStatus {
+    pub collection_id: i64,
+    pub encrypted: bool,
+    /// Chunks can be read and written; always true for plain collections
+    pub unlocked: bool,
+    pub encrypted_at: Option<String>,
+}
+
+pub struct CollectionEncryptionService<'a> {
+    rag_repository: &'a RagRepository,
+    config_service: &'a ConfigService,
+}
+
+impl<'a> CollectionEncryptionService<'a> {
+    pub fn new(rag_repository: &'a RagRepository, config_service: &'a ConfigService) -> Self {
+        Self {
+            rag_repository,
+            config_service,
+        }
+    }
+
+    pub async fn status(&self, collection_id: i64) -> Result<CollectionEncryptionStatus> {
+        let keys = self.rag_repository.collection_keys().await?;
+        let record = self
+            .rag_repository
+            .get_collection_encryption(collection_id)
+            .await?;
+        Ok(CollectionEncryptionStatus {
+            collection_id,
+            encrypted: record.is_some(),
+            unlocked: record.is_none() || keys.is_unlocked(collection_id),
+            encrypted_at: record.and_then(|r| r.created_at),
+        })
+    }
+
+    /// Encrypt the collection's existing chunks and everything ingested
+    /// into it from now on. Returns the number of chunks sealed.
+    pub async fn enable(&self, collection_id: i64, passphrase: &str) -> Result<usize> {
+        if passphrase.chars().count() < MIN_PASSPHRASE_CHARS {
+            return Err(AppError::ValidationError(format!(
+                "Passphrase must be at least {} characters",
+                MIN_PASSPHRASE_CHARS
+            )));
+        }
+        self.rag_repository.get_collection(collection_id).await?;
+
+        let salt = generate_salt()?;
+        let key = derive(passphrase, &salt, PBKDF2_ITERATIONS).await?;
+        let cipher = CollectionCipher::new(collection_id, &key)?;
+        let sealed = self
+            .rag_repository
+            .enable_collection_encryption(collection_id, &salt, PBKDF2_ITERATIONS, cipher)
+            .await?;
+        self.config_service
+            .save_collection_key(collection_id, &key)?;
+
+        // Sealing rewrote every chunk; drop the plaintext left in free pages.
+        // The chunks are sealed either way, so a failure is only reported.
+        if let Err(err) = self.rag_repository.vacuum().await {
+            tracing::warn!(
+                collection_id,
+                error = %err,
+                "Failed to clear plaintext left after sealing"
+            );
+        }
+        Ok(sealed)
+    }
+
+    /// Unlock with the passphrase and remember the key in the keychain
+    pub async fn unlock(&self, collection_id: i64, passphrase: &str) -> Result<()> {
+        let record = self.record(collection_id).await?;
+        let key = derive(passphrase, &record.salt, record.iterations as u32).await?;
+        let cipher = CollectionCipher::new(collection_id, &key)?;
+        if !cipher.verify(&record.key_check) {
+            return Err(AppError::SecurityError("Wrong passphrase".to_string()));
+        }
+
+        self.rag_repository.collection_keys().await?.unlock(cipher);
+        self.config_service.save_collection_key(collection_id, &key)
+    }
+
+    /// Drop the key from memory and the keychain; the passphrase is needed
+    /// to read the collection again
+    pub async fn lock(&self, collection_id: i64) -> Result<()> {
+        self.record(collection_id).await?;
+        self.rag_repository
+            .collection_keys()
+            .await?
+            .set_locked(collection_id);
+        self.config_service.delete_collection_key(collection_id)
+    }
+
+    /// Store the collection's chunks in plaintext again. Returns the number
+    /// of chunks opened.
+    pub async fn disable(&self, collection_id: i64, passphrase: &str) -> Result<usize> {
+        self.unlock(collection_id, passphrase).await?;
+        let opened = self
+            .rag_repository
+            .disable_collection_encryption(collection_id)
+            .await?;
+        self.config_service.delete_collection_key(collection_id)?;
+        Ok(opened)
+    }
+
+    /// Forget a deleted collection's key
+    pub async fn forget(&self, collection_id: i64) -> Result<()> {
+        self.rag_repository
+            .collection_keys()
+            .await?
+            .remove(collection_id);
+        self.config_service.delete_collection_key(collection_id)
+    }
+
+    /// Unlock every encrypted collection whose key is in the keychain.
+    /// Returns the number still locked.
+    pub async fn unlock_from_keychain(&self) -> Result<usize> {
+        let keys = self.rag_repository.collection_keys().await?;
+        let mut locked = 0;
+        for record in self.rag_repository.list_collection_encryption().await? {
+            if keys.is_unlocked(record.collection_id) {
+                continue;
+            }
+            let cipher = self
+                .config_service
+                .find_collection_key(record.collection_id)?
+                .map(|key| CollectionCipher::new(record.collection_id, &key))
+                .transpose()?
+                .filter(|cipher| cipher.verify(&record.key_check));
+            match cipher {
+                Some(cipher) => keys.unlock(cipher),
+                None => locked += 1,
+            }
+        }
+        Ok(locked)
+    }
+
+    async fn record(&self, collection_id: i64) -> Result<CollectionEncryption> {
+        self.rag_repository
+            .get_collection_encryption(collection_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::ValidationError(format!("Collection {} is not encrypted", collection_id))
+            })
+    }
+}
+
+async fn derive(passphrase: &str, salt: &[u8], iterations: u32) -> Result<[u8; KEY_LEN]> {
+    let (passphrase, salt) = (passphrase.to_string(), salt.to_vec());
+    tokio::task::spawn_blocking(move || derive_key(&passphrase, &salt, iterations))
+        .await
+        .map_err(|e| AppError::Internal(format!("Key derivation failed: {}", e)))?
+}
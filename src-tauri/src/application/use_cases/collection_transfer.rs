@@ -0,0 +1,438 @@
+//! Reorganizing file collections without re-importing: moving documents
+//! to another collection, merging one collection into another, and
+//! splitting the documents that match a filter off into a new collection.
+//!
+//! Chunks and embeddings move with their documents (see the repository's
+//! `transfer.rs`). Documents a connector or git repository keeps in sync
+//! stay where they are on a move or split, since the next sync would write
+//! them back into the source's collection; a merge takes the sources along.
+//! Documents never leave an encrypted collection for a plain one.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::rag_ingestion::IngestionPipelineConfig;
+use crate::domain::error::{AppError, Result};
+use crate::domain::rag_entities::{CollectionKind, RagCollection};
+use crate::infrastructure::db::rag::repository::{RagRepository, TransferDocumentRecord};
+
+/// Which documents of a collection a split takes. Every condition set must
+/// hold; at least one has to be set.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DocumentFilter {
+    /// File types such as "pdf" or "md", without the dot
+    pub file_types: Vec<String>,
+    pub languages: Vec<String>,
+    /// Case-insensitive part of the file name
+    pub name_contains: Option<String>,
+    /// Unix seconds, inclusive
+    pub created_after: Option<i64>,
+    pub created_before: Option<i64>,
+    pub min_quality: Option<f64>,
+    pub max_quality: Option<f64>,
+    /// Top-level document metadata fields and the values they must equal
+    pub meta: BTreeMap<String, Value>,
+}
+
+impl DocumentFilter {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.is_empty() {
+            return Err(AppError::ValidationError(
+                "A split needs at least one filter condition".to_string(),
+            ));
+        }
+        for (min, max, what) in [
+            (
+                self.created_after.map(|v| v as f64),
+                self.created_before.map(|v| v as f64),
+                "creation date",
+            ),
+            (self.min_quality, self.max_quality, "quality"),
+        ] {
+            if let (Some(min), Some(max)) = (min, max) {
+                if min > max {
+                    return Err(AppError::ValidationError(format!(
+                        "The {} range is empty",
+                        what
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn matches(&self, doc: &TransferDocumentRecord) -> bool {
+        let listed = |values: &[String], value: &str| {
+            values.is_empty() || values.iter().any(|v| v.eq_ignore_ascii_case(value))
+        };
+        if !listed(&self.file_types, doc.file_type.trim_start_matches('.')) {
+            return false;
+        }
+        if !listed(&self.languages, &doc.language) {
+            return false;
+        }
+        if let Some(part) = self.name_contains.as_deref().map(str::trim) {
+            if !part.is_empty() && !doc.file_name.to_lowercase().contains(&part.to_lowercase()) {
+                return false;
+            }
+        }
+        if self
+            .created_after
+            .is_some_and(|after| doc.created_at < after)
+            || self
+                .created_before
+                .is_some_and(|before| doc.created_at > before)
+        {
+            return false;
+        }
+        if self.min_quality.is_some() || self.max_quality.is_some() {
+            // Documents never scored only match a filter without a quality range
+            let Some(quality) = doc.quality_score else {
+                return false;
+            };
+            if self.min_quality.is_some_and(|min| quality < min)
+                || self.max_quality.is_some_and(|max| quality > max)
+            {
+                return false;
+            }
+        }
+        if !self.meta.is_empty() {
+            let meta: Value = serde_json::from_str(&doc.meta_json).unwrap_or(Value::Null);
+            if !self
+                .meta
+                .iter()
+                .all(|(key, expected)| meta.get(key) == Some(expected))
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Outcome of moving, merging or splitting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionTransferReport {
+    pub target_collection_id: i64,
+    /// Collections documents were taken from
+    pub source_collection_ids: Vec<i64>,
+    pub moved_documents: usize,
+    pub moved_chunks: usize,
+    /// Documents left in place because a connector or git repository syncs them
+    pub skipped_synced: Vec<i64>,
+    /// Connectors and git repositories handed to the target on a merge
+    pub moved_sources: usize,
+    /// Whether the merged collection was deleted afterwards
+    pub source_deleted: bool,
+}
+
+/// A split's new collection and what moved into it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionSplit {
+    pub collection: RagCollection,
+    pub report: CollectionTransferReport,
+}
+
+impl CollectionTransferReport {
+    fn new(target_collection_id: i64) -> Self {
+        Self {
+            target_collection_id,
+            source_collection_ids: Vec::new(),
+            moved_documents: 0,
+            moved_chunks: 0,
+            skipped_synced: Vec::new(),
+            moved_sources: 0,
+            source_deleted: false,
+        }
+    }
+
+    /// Every collection whose content changed
+    pub fn affected_collection_ids(&self) -> Vec<i64> {
+        let mut ids: BTreeSet<i64> = self.source_collection_ids.iter().copied().collect();
+        ids.insert(self.target_collection_id);
+        ids.into_iter().collect()
+    }
+}
+
+pub struct CollectionTransferService<'a> {
+    rag_repository: &'a RagRepository,
+}
+
+impl<'a> CollectionTransferService<'a> {
+    pub fn new(rag_repository: &'a RagRepository) -> Self {
+        Self { rag_repository }
+    }
+
+    /// Move documents, from one collection or several, into the target
+    pub async fn move_documents(
+        &self,
+        doc_ids: &[i64],
+        target_collection_id: i64,
+    ) -> Result<CollectionTransferReport> {
+        if doc_ids.is_empty() {
+            return Err(AppError::ValidationError(
+                "No documents to move".to_string(),
+            ));
+        }
+        let target = self.files_collection(target_collection_id).await?;
+        let docs = self.rag_repository.get_transfer_documents(doc_ids).await?;
+        let found: BTreeSet<i64> = docs.iter().map(|doc| doc.id).collect();
+        if let Some(missing) = doc_ids.iter().find(|id| !found.contains(id)) {
+            return Err(AppError::NotFound(format!(
+                "Document not found: {}",
+                missing
+            )));
+        }
+        let docs = docs
+            .into_iter()
+            .filter(|doc| doc.collection_id != Some(target.id))
+            .collect();
+        self.transfer(docs, &target, false).await
+    }
+
+    /// Move everything in `source_collection_id` into the target, along
+    /// with the connectors and git repositories that sync into it and its
+    /// retrieval history. Deleting the emptied source is left to the caller.
+    pub async fn merge_collections(
+        &self,
+        source_collection_id: i64,
+        target_collection_id: i64,
+    ) -> Result<CollectionTransferReport> {
+        if source_collection_id == target_collection_id {
+            return Err(AppError::ValidationError(
+                "A collection cannot be merged into itself".to_string(),
+            ));
+        }
+        self.files_collection(source_collection_id).await?;
+        let target = self.files_collection(target_collection_id).await?;
+        let docs = self
+            .rag_repository
+            .list_transfer_documents(source_collection_id)
+            .await?;
+        // Checked here as well, since an empty collection moves no documents
+        self.check_encryption(&[source_collection_id], target.id)
+            .await?;
+
+        let mut report = self.transfer(docs, &target, true).await?;
+        if !report.source_collection_ids.contains(&source_collection_id) {
+            report.source_collection_ids.push(source_collection_id);
+        }
+        report.moved_sources = self
+            .rag_repository
+            .reassign_collection_sources(source_collection_id, target.id)
+            .await?;
+        Ok(report)
+    }
+
+    /// Move the documents of `source_collection_id` matching `filter` into
+    /// a new collection with the same settings
+    pub async fn split_collection(
+        &self,
+        source_collection_id: i64,
+        filter: &DocumentFilter,
+        name: &str,
+        description: Option<&str>,
+    ) -> Result<CollectionSplit> {
+        filter.validate()?;
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AppError::ValidationError(
+                "The new collection needs a name".to_string(),
+            ));
+        }
+        let source = self.files_collection(source_collection_id).await?;
+        if self
+            .rag_repository
+            .is_collection_encrypted(source_collection_id)
+            .await?
+        {
+            return Err(AppError::ValidationError(
+                "An encrypted collection cannot be split into a plain one; create an encrypted collection and move the documents instead".to_string(),
+            ));
+        }
+        let docs: Vec<_> = self
+            .rag_repository
+            .list_transfer_documents(source_collection_id)
+            .await?
+            .into_iter()
+            .filter(|doc| filter.matches(doc))
+            .collect();
+        if docs.iter().all(|doc| doc.synced) {
+            return Err(AppError::ValidationError(if docs.is_empty() {
+                "No documents match the filter".to_string()
+            } else {
+                "Every matching document is kept in sync by a connector or git repository"
+                    .to_string()
+            }));
+        }
+
+        let description = description
+            .map(str::to_string)
+            .or(source.description)
+            .unwrap_or_default();
+        let target = self
+            .rag_repository
+            .create_collection_with_config(name, &description, "files", &source.config_json)
+            .await?;
+        let report = self.transfer(docs, &target, false).await?;
+        Ok(CollectionSplit {
+            collection: target,
+            report,
+        })
+    }
+
+    async fn files_collection(&self, collection_id: i64) -> Result<RagCollection> {
+        let collection = self.rag_repository.get_collection(collection_id).await?;
+        if collection.kind != CollectionKind::Files {
+            return Err(AppError::ValidationError(format!(
+                "Collection \"{}\" is not a file collection",
+                collection.name
+            )));
+        }
+        Ok(collection)
+    }
+
+    async fn check_encryption(&self, source_ids: &[i64], target_id: i64) -> Result<()> {
+        let keys = self.rag_repository.collection_keys().await?;
+        if keys.is_encrypted(target_id) {
+            return Ok(());
+        }
+        if source_ids.iter().any(|id| keys.is_encrypted(*id)) {
+            return Err(AppError::ValidationError(
+                "Documents of an encrypted collection can only move to another encrypted collection".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn transfer(
+        &self,
+        docs: Vec<TransferDocumentRecord>,
+        target: &RagCollection,
+        include_synced: bool,
+    ) -> Result<CollectionTransferReport> {
+        let mut report = CollectionTransferReport::new(target.id);
+        let mut moving = Vec::new();
+        let mut sources = BTreeSet::new();
+        for doc in docs {
+            if doc.synced && !include_synced {
+                report.skipped_synced.push(doc.id);
+                continue;
+            }
+            sources.extend(doc.collection_id);
+            moving.push(doc.id);
+        }
+        report.source_collection_ids = sources.into_iter().collect();
+        if moving.is_empty() {
+            return Ok(report);
+        }
+
+        for id in &report.source_collection_ids {
+            self.files_collection(*id).await?;
+        }
+        self.check_encryption(&report.source_collection_ids, target.id)
+            .await?;
+        let quantization = IngestionPipelineConfig::from_collection_config(&target.config_json)?
+            .embed
+            .quantization;
+
+        report.moved_chunks = self
+            .rag_repository
+            .move_documents(&moving, target.id, quantization)
+            .await?;
+        report.moved_documents = moving.len();
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn doc(
+        file_name: &str,
+        file_type: &str,
+        quality: Option<f64>,
+        meta: Value,
+    ) -> TransferDocumentRecord {
+        TransferDocumentRecord {
+            id: 1,
+            collection_id: Some(1),
+            file_name: file_name.to_string(),
+            file_type: file_type.to_string(),
+            language: "en".to_string(),
+            quality_score: quality,
+            meta_json: meta.to_string(),
+            created_at: 1_700_000_000,
+            synced: false,
+        }
+    }
+
+    #[test]
+    fn filter_conditions_must_all_hold() {
+        let report = doc(
+            "Q3 Report.pdf",
+            "pdf",
+            Some(0.8),
+            json!({"team": "finance", "year": 2024}),
+        );
+
+        let filter = DocumentFilter {
+            file_types: vec!["PDF".to_string()],
+            name_contains: Some("report".to_string()),
+            meta: BTreeMap::from([("team".to_string(), json!("finance"))]),
+            ..Default::default()
+        };
+        assert!(filter.matches(&report));
+        assert!(!filter.matches(&doc(
+            "Q3 Report.md",
+            "md",
+            Some(0.8),
+            json!({"team": "finance"})
+        )));
+        assert!(!filter.matches(&doc("Q3 Report.pdf", "pdf", None, json!({"team": "legal"}))));
+
+        let year = DocumentFilter {
+            meta: BTreeMap::from([("year".to_string(), json!(2024))]),
+            created_before: Some(1_700_000_000),
+            ..Default::default()
+        };
+        assert!(year.matches(&report));
+        assert!(!DocumentFilter {
+            created_after: Some(1_700_000_001),
+            ..Default::default()
+        }
+        .matches(&report));
+    }
+
+    #[test]
+    fn quality_range_leaves_out_unscored_documents() {
+        let filter = DocumentFilter {
+            max_quality: Some(0.5),
+            ..Default::default()
+        };
+        assert!(filter.matches(&doc("scan.pdf", "pdf", Some(0.3), json!({}))));
+        assert!(!filter.matches(&doc("scan.pdf", "pdf", Some(0.7), json!({}))));
+        assert!(!filter.matches(&doc("scan.pdf", "pdf", None, json!({}))));
+
+        assert!(DocumentFilter::default().validate().is_err());
+        assert!(DocumentFilter {
+            min_quality: Some(0.9),
+            max_quality: Some(0.1),
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+        assert!(filter.validate().is_ok());
+    }
+}
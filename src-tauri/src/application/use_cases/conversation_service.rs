@@ -98,6 +98,19 @@ impl ConversationService {
             .execute(self.rag_repository.pool())
             .await;
 
+        // Sources of a saved answer count as citations for chunk usage stats
+        if role == "assistant" {
+            let cited: Vec<i64> = sources
+                .and_then(|sources| serde_json::from_str(sources).ok())
+                .unwrap_or_default();
+            if !cited.is_empty() {
+                let _ = self
+                    .rag_repository
+                    .record_chunk_citations(conversation_id, &cited)
+                    .await;
+            }
+        }
+
         Ok(result.last_insert_rowid())
     }
 
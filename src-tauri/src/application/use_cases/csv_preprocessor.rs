@@ -3,12 +3,26 @@
 // ============================================================
 // Orchestrate CSV parsing, content detection, and formatting
 
+use std::fs::File;
+use std::io::{BufWriter, Write};
 use std::path::Path;
 use std::time::Instant;
 
-use crate::domain::csv::{ContentType, CsvRow, PreprocessedCsv, PreprocessingConfig};
+use crate::domain::csv::{
+    ContentType, CsvProcessingPhase, CsvProfile, CsvProgress, CsvRow, PreprocessedCsv,
+    PreprocessingConfig,
+};
 use crate::domain::error::AppError;
-use crate::infrastructure::csv::{ContentAnalyzer, CsvParser};
+use crate::infrastructure::csv::{ColumnProfiler, ContentAnalyzer, CsvParser, CsvStreamReader};
+
+/// Separator between narrative records
+const NARRATIVE_SEPARATOR: &str = "\n---\n";
+/// Triple newline for clear record boundaries between structured records
+const STRUCTURED_SEPARATOR: &str = "\n\n\n";
+/// Rows handled between progress callbacks
+const PROGRESS_INTERVAL_ROWS: usize = 5_000;
+/// Bytes of processed text kept in `processed_text` when streaming to a sink
+const PREVIEW_BYTES: usize = 64 * 1024;
 
 /// CSV preprocessing use case
 pub struct CsvPreprocessor {
@@ -28,6 +42,54 @@ impl CsvPreprocessor {
 
     /// Process a CSV file and return formatted text
     pub async fn preprocess_csv(&self, csv_path: &Path) -> Result<PreprocessedCsv, AppError> {
+        self.preprocess_csv_with_progress(csv_path, |_| {})
+    }
+
+    /// Process a CSV file, streaming rows and reporting progress
+    ///
+    /// Input rows are never held in memory all at once; memory use is
+    /// bounded by the processed text that is returned.
+    pub fn preprocess_csv_with_progress(
+        &self,
+        csv_path: &Path,
+        on_progress: impl FnMut(&CsvProgress),
+    ) -> Result<PreprocessedCsv, AppError> {
+        let mut output = Vec::new();
+        let mut result = self.stream_preprocess(csv_path, &mut output, on_progress)?;
+        result.processed_text = String::from_utf8(output).map_err(|e| {
+            AppError::Internal(format!("Processed CSV text is not valid UTF-8: {}", e))
+        })?;
+        Ok(result)
+    }
+
+    /// Process a CSV file of any size, writing the processed text to `output_path`
+    ///
+    /// `processed_text` in the result only holds a preview of the output.
+    pub fn preprocess_csv_to_file(
+        &self,
+        csv_path: &Path,
+        output_path: &Path,
+        on_progress: impl FnMut(&CsvProgress),
+    ) -> Result<PreprocessedCsv, AppError> {
+        let file = File::create(output_path)
+            .map_err(|e| AppError::IoError(format!("Failed to create output file: {}", e)))?;
+        let mut writer = BufWriter::new(file);
+        let result = self.stream_preprocess(csv_path, &mut writer, on_progress)?;
+        writer
+            .flush()
+            .map_err(|e| AppError::IoError(format!("Failed to write output file: {}", e)))?;
+        Ok(result)
+    }
+
+    /// Two passes over the file: the first profiles columns and samples rows
+    /// for content detection, the second normalizes and formats each row
+    /// straight into `sink`. `processed_text` is filled with a preview.
+    fn stream_preprocess(
+        &self,
+        csv_path: &Path,
+        sink: &mut dyn Write,
+        mut on_progress: impl FnMut(&CsvProgress),
+    ) -> Result<PreprocessedCsv, AppError> {
         let start = Instant::now();
 
         // Validate configuration
@@ -35,96 +97,193 @@ impl CsvPreprocessor {
             AppError::ValidationError(format!("Invalid preprocessing config: {}", e))
         })?;
 
-        // Parse CSV file
-        let parser = CsvParser::parse_file_auto_detect(csv_path)
-            .map_err(|e| AppError::ParseError(format!("Failed to parse CSV file: {}", e)))?;
+        // First pass: profile columns, keep a sample for content detection
+        let FileScan {
+            profile,
+            sample,
+            row_count,
+            headers,
+            encoding,
+            delimiter,
+            total_bytes,
+        } = self.scan_file(csv_path, &mut on_progress)?;
 
         // Check minimum row count
-        if parser.len() < self.config.min_sample_rows {
+        if row_count < self.config.min_sample_rows {
             return Err(AppError::ValidationError(format!(
                 "CSV file has too few rows ({}), minimum required: {}",
-                parser.len(),
-                self.config.min_sample_rows
+                row_count, self.config.min_sample_rows
             )));
         }
 
-        // Detect content type
-        let analyzer = ContentAnalyzer::new(self.config.clone());
-        let content_type = analyzer.detect_content_type(&parser);
-        let analysis = analyzer.analyze(&parser);
-
-        // Extract headers
-        let headers = if let Some(first_row) = parser.first() {
-            first_row.fields.iter().map(|f| f.name.clone()).collect()
+        let sample = if self.config.normalize_columns {
+            ColumnProfiler::normalize_rows(&profile, sample)
         } else {
-            Vec::new()
+            sample
         };
 
-        // Format based on content type
-        let processed_text = match content_type {
-            ContentType::Narrative => self.format_narrative(&parser),
-            ContentType::Structured => self.format_structured(&parser),
+        // Detect content type from the leading sample
+        let analyzer = ContentAnalyzer::new(self.config.clone());
+        let content_type = analyzer.detect_content_type(&sample);
+        let mut analysis = analyzer.analyze(&sample);
+        analysis.row_count = row_count;
+        drop(sample);
+
+        // Second pass: normalize and format rows into the sink
+        let separator = match content_type {
+            ContentType::Narrative => NARRATIVE_SEPARATOR,
+            ContentType::Structured => STRUCTURED_SEPARATOR,
         };
+        let mut reader = CsvStreamReader::open(csv_path)?;
+        let mut preview = String::new();
+        let mut written_any = false;
+        let mut formatted = 0;
+        while let Some(row) = reader.next() {
+            let mut row = row?;
+            if self.config.normalize_columns {
+                row = ColumnProfiler::normalize_row(&profile, row);
+            }
+
+            let text = match content_type {
+                ContentType::Narrative => row.format_narrative(),
+                ContentType::Structured => self.format_structured_row(&row),
+            };
+            if !text.is_empty() {
+                if written_any {
+                    write_text(sink, &mut preview, separator)?;
+                }
+                write_text(sink, &mut preview, &text)?;
+                written_any = true;
+            }
+
+            formatted += 1;
+            if formatted % PROGRESS_INTERVAL_ROWS == 0 {
+                on_progress(&CsvProgress::new(
+                    CsvProcessingPhase::Formatting,
+                    formatted,
+                    reader.bytes_read(),
+                    total_bytes,
+                ));
+            }
+        }
+
+        on_progress(&CsvProgress::new(
+            CsvProcessingPhase::Complete,
+            row_count,
+            total_bytes,
+            total_bytes,
+        ));
 
         let processing_time = start.elapsed();
 
         Ok(PreprocessedCsv {
             content_type,
-            processed_text,
-            row_count: parser.len(),
+            processed_text: preview,
+            row_count,
             analysis,
+            profile,
             headers,
+            encoding,
+            delimiter,
             processing_time_ms: processing_time.as_millis() as u64,
         })
     }
 
+    /// Stream the whole file once, profiling every row and keeping the first
+    /// `max_sample_rows` rows for content type detection
+    fn scan_file(
+        &self,
+        csv_path: &Path,
+        on_progress: &mut impl FnMut(&CsvProgress),
+    ) -> Result<FileScan, AppError> {
+        let mut reader = CsvStreamReader::open(csv_path)?;
+        let total_bytes = reader.total_bytes();
+
+        let mut profiler = ColumnProfiler::new();
+        let mut sample = Vec::new();
+        let mut row_count = 0;
+        while let Some(row) = reader.next() {
+            let row = row?;
+            profiler.observe(&row);
+            if sample.len() < self.config.max_sample_rows {
+                sample.push(row);
+            }
+            row_count += 1;
+            if row_count % PROGRESS_INTERVAL_ROWS == 0 {
+                on_progress(&CsvProgress::new(
+                    CsvProcessingPhase::Analyzing,
+                    row_count,
+                    reader.bytes_read(),
+                    total_bytes,
+                ));
+            }
+        }
+
+        Ok(FileScan {
+            profile: profiler.finish(),
+            sample,
+            row_count,
+            headers: reader.headers(),
+            encoding: reader.encoding_name().to_string(),
+            delimiter: reader.delimiter() as char,
+            total_bytes,
+        })
+    }
+
+    /// Profile columns and, when enabled, normalize values by inferred type
+    fn profile_rows(&self, rows: Vec<CsvRow>) -> (Vec<CsvRow>, CsvProfile) {
+        let profile = ColumnProfiler::new().profile(&rows);
+        let rows = if self.config.normalize_columns {
+            ColumnProfiler::normalize_rows(&profile, rows)
+        } else {
+            rows
+        };
+        (rows, profile)
+    }
+
     /// Format CSV rows as narrative text (plain text style)
     fn format_narrative(&self, rows: &[CsvRow]) -> String {
         rows.iter()
             .map(|row| row.format_narrative())
             .filter(|s| !s.is_empty())
             .collect::<Vec<_>>()
-            .join("\n---\n")
+            .join(NARRATIVE_SEPARATOR)
     }
 
     /// Format CSV rows as structured text
-    /// Uses keyword-dense format optimized for semantic search
-    /// Important keywords like "email" are repeated for better retrieval
     fn format_structured(&self, rows: &[CsvRow]) -> String {
         rows.iter()
-            .enumerate()
-            .map(|(_idx, row)| {
-                let mut parts: Vec<String> = Vec::new();
-                let mut email_parts: Vec<String> = Vec::new();
-
-                // Extract and emphasize important fields
-                for field in row.fields.iter().filter(|f| !f.value.is_empty()) {
-                    let clean_name_lower = field.clean_name.to_lowercase();
-
-                    if clean_name_lower.contains("email") || clean_name_lower.contains("mail") {
-                        // Repeat email keyword multiple times for better matching
-                        parts.push(format!("email: {}", field.value));
-                        parts.push(format!("{} email address", field.value));
-                        email_parts.push(field.value.clone());
-                    } else if clean_name_lower.contains("name") {
-                        parts.push(format!("name: {}", field.value));
-                    } else if clean_name_lower.contains("id") {
-                        parts.push(format!("ID: {}", field.value));
-                    } else {
-                        parts.push(format!("{}: {}", field.clean_name, field.value));
-                    }
-                }
-
-                if parts.is_empty() {
-                    String::new()
-                } else {
-                    // Join all parts with clear separators
-                    parts.join(". ")
-                }
-            })
+            .map(|row| self.format_structured_row(row))
             .filter(|s| !s.is_empty())
             .collect::<Vec<_>>()
-            .join("\n\n\n") // Triple newline for clear record boundaries
+            .join(STRUCTURED_SEPARATOR)
+    }
+
+    /// Format a single row as structured text
+    /// Uses keyword-dense format optimized for semantic search
+    /// Important keywords like "email" are repeated for better retrieval
+    fn format_structured_row(&self, row: &CsvRow) -> String {
+        let mut parts: Vec<String> = Vec::new();
+
+        // Extract and emphasize important fields
+        for field in row.fields.iter().filter(|f| !f.value.is_empty()) {
+            let clean_name_lower = field.clean_name.to_lowercase();
+
+            if clean_name_lower.contains("email") || clean_name_lower.contains("mail") {
+                // Repeat email keyword multiple times for better matching
+                parts.push(format!("email: {}", field.value));
+                parts.push(format!("{} email address", field.value));
+            } else if clean_name_lower.contains("name") {
+                parts.push(format!("name: {}", field.value));
+            } else if clean_name_lower.contains("id") {
+                parts.push(format!("ID: {}", field.value));
+            } else {
+                parts.push(format!("{}: {}", field.clean_name, field.value));
+            }
+        }
+
+        // Join all parts with clear separators
+        parts.join(". ")
     }
 
     /// Process CSV from string content (for testing or in-memory data)
@@ -147,6 +306,9 @@ impl CsvPreprocessor {
             )));
         }
 
+        // Infer column types and normalize values
+        let (rows, profile) = self.profile_rows(rows);
+
         // Detect content type
         let analyzer = ContentAnalyzer::new(self.config.clone());
         let content_type = analyzer.detect_content_type(&rows);
@@ -172,7 +334,10 @@ impl CsvPreprocessor {
             processed_text,
             row_count: rows.len(),
             analysis,
+            profile,
             headers,
+            encoding: "UTF-8".to_string(),
+            delimiter: delimiter as char,
             processing_time_ms: processing_time.as_millis() as u64,
         })
     }
@@ -186,7 +351,40 @@ impl CsvPreprocessor {
             .map_err(|e| AppError::ParseError(format!("Failed to parse CSV content: {}", e)))?;
 
         let analyzer = ContentAnalyzer::new(self.config.clone());
-        Ok(analyzer.get_analysis_report(&rows))
+        let profile = ColumnProfiler::new().profile(&rows);
+        Ok(format!(
+            "{}\n\n{}",
+            analyzer.get_analysis_report(&rows),
+            profile.summary()
+        ))
+    }
+
+    /// Analysis report and column profile for a file of any size
+    ///
+    /// Every row is profiled; content statistics use the first
+    /// `max_sample_rows` rows.
+    pub fn analyze_file(&self, csv_path: &Path) -> Result<(String, CsvProfile), AppError> {
+        let scan = self.scan_file(csv_path, &mut |_| {})?;
+        let analyzer = ContentAnalyzer::new(self.config.clone());
+        let report = format!(
+            "Encoding: {}, delimiter: {:?}\n\n{}\n\n{}",
+            scan.encoding,
+            scan.delimiter,
+            analyzer.get_analysis_report(&scan.sample),
+            scan.profile.summary()
+        );
+        Ok((report, scan.profile))
+    }
+
+    /// Infer column types, null rates, ranges and a suggested schema
+    pub fn profile_csv(&self, content: &str) -> Result<CsvProfile, AppError> {
+        let delimiter = CsvParser::detect_delimiter(content);
+        let parser = CsvParser::new().with_delimiter(delimiter);
+        let rows = parser
+            .parse_content(content)
+            .map_err(|e| AppError::ParseError(format!("Failed to parse CSV content: {}", e)))?;
+
+        Ok(ColumnProfiler::new().profile(&rows))
     }
 
     /// Preview first N rows in processed format
@@ -201,11 +399,27 @@ impl CsvPreprocessor {
             .parse_content(content)
             .map_err(|e| AppError::ParseError(format!("Failed to parse CSV content: {}", e)))?;
 
+        Ok(self.format_preview(&rows, preview_count))
+    }
+
+    /// Preview the first N rows of a file without reading the rest of it
+    pub fn preview_file(
+        &self,
+        csv_path: &Path,
+        preview_count: usize,
+    ) -> Result<Vec<String>, AppError> {
+        let rows = CsvStreamReader::open(csv_path)?
+            .take(preview_count)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(self.format_preview(&rows, preview_count))
+    }
+
+    fn format_preview(&self, rows: &[CsvRow], preview_count: usize) -> Vec<String> {
         let analyzer = ContentAnalyzer::new(self.config.clone());
-        let content_type = analyzer.detect_content_type(&rows);
+        let content_type = analyzer.detect_content_type(rows);
 
-        let preview: Vec<String> = rows
-            .iter()
+        rows.iter()
             .take(preview_count)
             .map(|row| match content_type {
                 ContentType::Narrative => row.format_narrative(),
@@ -214,10 +428,35 @@ impl CsvPreprocessor {
                     format!("## Record #{}\n{}", row.index + 1, formatted)
                 }
             })
-            .collect();
+            .collect()
+    }
+}
+
+/// Result of the profiling pass over a file
+struct FileScan {
+    profile: CsvProfile,
+    sample: Vec<CsvRow>,
+    row_count: usize,
+    headers: Vec<String>,
+    encoding: String,
+    delimiter: char,
+    total_bytes: u64,
+}
+
+/// Write to the sink and keep the first `PREVIEW_BYTES` in `preview`
+fn write_text(sink: &mut dyn Write, preview: &mut String, text: &str) -> Result<(), AppError> {
+    sink.write_all(text.as_bytes())
+        .map_err(|e| AppError::IoError(format!("Failed to write processed text: {}", e)))?;
 
-        Ok(preview)
+    let room = PREVIEW_BYTES.saturating_sub(preview.len());
+    if room > 0 {
+        let mut end = room.min(text.len());
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        preview.push_str(&text[..end]);
     }
+    Ok(())
 }
 
 impl Default for CsvPreprocessor {
@@ -322,5 +561,45 @@ Alice,Johnson,1001";
         assert!(report.contains("Field Analysis"));
         assert!(report.contains("Avg length"));
         assert!(report.contains("Detected Content Type"));
+        assert!(report.contains("Suggested Schema"));
+    }
+
+    #[test]
+    fn test_numeric_columns_normalized_before_formatting() {
+        let csv = "\
+sku,price,in_stock
+A-1,\"1,250.00\",yes
+A-2,99.5,no
+A-3,N/A,yes";
+
+        let preprocessor = CsvPreprocessor::default();
+        let result = preprocessor.preprocess_csv_content(csv).unwrap();
+
+        assert!(result.processed_text.contains("price: 1250"));
+        assert!(result.processed_text.contains("in_stock: true"));
+        assert!(!result.processed_text.contains("N/A"));
+        assert_eq!(result.profile.columns[1].null_count, 1);
+    }
+
+    #[test]
+    fn test_preprocess_file_streams_to_output() {
+        let dir = std::env::temp_dir();
+        let input = dir.join(format!("{}-csv-stream-input.csv", std::process::id()));
+        let output = dir.join(format!("{}-csv-stream-output.txt", std::process::id()));
+        std::fs::write(&input, "id;amount\n1;1,000\n2;25\n3;N/A\n").unwrap();
+
+        let mut phases = Vec::new();
+        let result = CsvPreprocessor::default()
+            .preprocess_csv_to_file(&input, &output, |progress| phases.push(progress.phase))
+            .unwrap();
+        let written = std::fs::read_to_string(&output).unwrap();
+        std::fs::remove_file(&input).ok();
+        std::fs::remove_file(&output).ok();
+
+        assert_eq!(result.row_count, 3);
+        assert_eq!(result.delimiter, ';');
+        assert_eq!(written, result.processed_text);
+        assert!(written.contains("amount: 1000"));
+        assert_eq!(phases.last(), Some(&CsvProcessingPhase::Complete));
     }
 }
@@ -0,0 +1,703 @@
+//! Email parsing for RAG imports: messages of `.eml` files and mbox
+//! archives with their headers decoded, bodies reduced to the text the
+//! sender wrote (quoted replies and signatures removed), and replies grouped
+//! into threads.
+
+use std::collections::HashMap;
+
+use base64::Engine;
+use chrono::DateTime;
+use sha2::{Digest, Sha256};
+
+use crate::application::use_cases::web_crawler::WebCrawler;
+
+/// Prefixes mail clients put before the subject of replies and forwards
+const REPLY_PREFIXES: &[&str] = &["re:", "fw:", "fwd:", "aw:", "wg:", "sv:", "vs:", "tr:"];
+
+/// Lines that start the copy of the message being replied to
+const QUOTE_MARKERS: &[&str] = &[
+    "-----original message-----",
+    "________________________________",
+    "-----ursprüngliche nachricht-----",
+];
+
+/// Footers mobile and web clients add below the text
+const CLIENT_FOOTERS: &[&str] = &["sent from my ", "get outlook for ", "sent from mail for "];
+
+/// A decoded email message
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EmailMessage {
+    /// Without angle brackets; a content hash when the header is missing
+    pub message_id: String,
+    pub in_reply_to: Option<String>,
+    /// Message ids of the thread from its first message on
+    pub references: Vec<String>,
+    pub subject: String,
+    pub from: Option<Mailbox>,
+    pub to: Vec<Mailbox>,
+    pub cc: Vec<Mailbox>,
+    /// Unix seconds
+    pub sent_at: Option<i64>,
+    /// Text the sender wrote, without quoted replies and signature
+    pub body: String,
+    /// File names of attachments
+    pub attachments: Vec<String>,
+}
+
+/// An address with its display name
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mailbox {
+    pub name: Option<String>,
+    /// Lower case
+    pub address: String,
+}
+
+impl std::fmt::Display for Mailbox {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{} <{}>", name, self.address),
+            None => write!(f, "{}", self.address),
+        }
+    }
+}
+
+/// Messages of an `.eml` file or an mbox archive
+pub fn parse_mailbox(bytes: &[u8]) -> Vec<EmailMessage> {
+    let bytes = normalize_newlines(bytes);
+    if bytes.starts_with(b"From ") {
+        split_mbox(&bytes)
+            .iter()
+            .map(|raw| parse_message(raw))
+            .collect()
+    } else {
+        vec![parse_message(&bytes)]
+    }
+}
+
+/// Thread id of each message: the first message id the thread's replies
+/// reference, or for messages without references one before them in
+/// `messages` with the same subject
+pub fn thread_ids(messages: &[EmailMessage]) -> Vec<String> {
+    let mut by_message_id: HashMap<&str, String> = HashMap::new();
+    let mut by_subject: HashMap<String, String> = HashMap::new();
+    let mut threads = Vec::with_capacity(messages.len());
+    for message in messages {
+        let referenced = message.references.first().or(message.in_reply_to.as_ref());
+        let thread = match referenced {
+            Some(parent) => by_message_id
+                .get(parent.as_str())
+                .cloned()
+                .unwrap_or_else(|| parent.clone()),
+            None => by_subject
+                .get(&thread_subject(&message.subject))
+                .filter(|_| is_reply_subject(&message.subject))
+                .cloned()
+                .unwrap_or_else(|| message.message_id.clone()),
+        };
+        by_message_id.insert(&message.message_id, thread.clone());
+        by_subject
+            .entry(thread_subject(&message.subject))
+            .or_insert_with(|| thread.clone());
+        threads.push(thread);
+    }
+    threads
+}
+
+/// Subject without reply and forward prefixes, lower case
+pub fn thread_subject(subject: &str) -> String {
+    let mut rest = subject.trim();
+    while let Some(prefix) = REPLY_PREFIXES
+        .iter()
+        .find(|prefix| starts_with_ignore_case(rest, prefix))
+    {
+        rest = rest[prefix.len()..].trim_start();
+    }
+    rest.to_lowercase()
+}
+
+fn is_reply_subject(subject: &str) -> bool {
+    thread_subject(subject).len() < subject.trim().len()
+}
+
+fn starts_with_ignore_case(text: &str, prefix: &str) -> bool {
+    text.get(..prefix.len())
+        .is_some_and(|head| head.eq_ignore_ascii_case(prefix))
+}
+
+fn normalize_newlines(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len());
+    for (i, byte) in bytes.iter().enumerate() {
+        if *byte == b'\r' && bytes.get(i + 1) == Some(&b'\n') {
+            continue;
+        }
+        out.push(*byte);
+    }
+    out
+}
+
+/// Messages of an mbox archive: each starts with a `From ` line after a
+/// blank line; `>From ` escapes in bodies are undone
+fn split_mbox(bytes: &[u8]) -> Vec<Vec<u8>> {
+    let mut messages = Vec::new();
+    let mut current: Option<Vec<u8>> = None;
+    let mut previous_blank = true;
+    for line in bytes.split_inclusive(|byte| *byte == b'\n') {
+        if previous_blank && line.starts_with(b"From ") {
+            messages.extend(current.take());
+            current = Some(Vec::new());
+            previous_blank = false;
+            continue;
+        }
+        previous_blank = line == b"\n";
+        let Some(message) = current.as_mut() else {
+            continue;
+        };
+        let unescaped = match line.iter().position(|byte| *byte != b'>') {
+            Some(quotes) if quotes > 0 && line[quotes..].starts_with(b"From ") => &line[1..],
+            _ => line,
+        };
+        message.extend_from_slice(unescaped);
+    }
+    messages.extend(current);
+    messages.retain(|message| !message.iter().all(u8::is_ascii_whitespace));
+    messages
+}
+
+fn parse_message(raw: &[u8]) -> EmailMessage {
+    let (headers, body) = split_headers(raw);
+    let header = |name: &str| header_value(&headers, name);
+
+    let mut text = String::new();
+    let mut attachments = Vec::new();
+    collect_text(&headers, body, &mut text, &mut attachments);
+
+    let message_id = header("message-id")
+        .and_then(|value| message_ids(&value).into_iter().next())
+        .unwrap_or_else(|| hex::encode(&Sha256::digest(raw)[..16]));
+    EmailMessage {
+        message_id,
+        in_reply_to: header("in-reply-to").and_then(|value| message_ids(&value).pop()),
+        references: header("references")
+            .map(|value| message_ids(&value))
+            .unwrap_or_default(),
+        subject: header("subject").unwrap_or_default(),
+        from: header("from").and_then(|value| mailboxes(&value).into_iter().next()),
+        to: header("to")
+            .map(|value| mailboxes(&value))
+            .unwrap_or_default(),
+        cc: header("cc")
+            .map(|value| mailboxes(&value))
+            .unwrap_or_default(),
+        sent_at: header("date").and_then(|value| parse_date(&value)),
+        body: strip_replies(&text),
+        attachments,
+    }
+}
+
+/// Unfolded headers with lower-case names, and the body after them
+fn split_headers(raw: &[u8]) -> (Vec<(String, String)>, &[u8]) {
+    if let Some(body) = raw.strip_prefix(b"\n") {
+        return (Vec::new(), body);
+    }
+    let (head, body) = match raw.windows(2).position(|pair| pair == b"\n\n") {
+        Some(end) => (&raw[..end], &raw[end + 2..]),
+        None => (raw, &raw[raw.len()..]),
+    };
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for line in String::from_utf8_lossy(head).lines() {
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_lowercase(), value.trim().to_string()));
+        }
+    }
+    (headers, body)
+}
+
+fn header_value(headers: &[(String, String)], name: &str) -> Option<String> {
+    headers
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| decode_words(value))
+        .filter(|value| !value.is_empty())
+}
+
+/// Append the readable text of a MIME part to `text`: plain text over HTML
+/// in alternatives, every inline text part of a mixed message
+fn collect_text(
+    headers: &[(String, String)],
+    body: &[u8],
+    text: &mut String,
+    attachments: &mut Vec<String>,
+) {
+    let content_type = headers
+        .iter()
+        .find(|(key, _)| key == "content-type")
+        .map(|(_, value)| value.as_str())
+        .unwrap_or("text/plain");
+    let (mime, params) = parse_content_type(content_type);
+    let disposition = headers
+        .iter()
+        .find(|(key, _)| key == "content-disposition")
+        .map(|(_, value)| parse_content_type(value));
+
+    let file_name = disposition
+        .as_ref()
+        .and_then(|(_, params)| params.get("filename"))
+        .or_else(|| params.get("name"))
+        .map(|name| decode_words(name));
+    let is_attachment = disposition
+        .as_ref()
+        .is_some_and(|(kind, _)| kind == "attachment");
+    if is_attachment || (file_name.is_some() && !mime.starts_with("multipart/")) {
+        attachments.extend(file_name);
+        return;
+    }
+
+    if let Some(boundary) = params
+        .get("boundary")
+        .filter(|_| mime.starts_with("multipart/"))
+    {
+        let parts: Vec<_> = split_multipart(body, boundary)
+            .into_iter()
+            .map(split_headers)
+            .collect();
+        if mime == "multipart/alternative" {
+            let preferred = parts
+                .iter()
+                .find(|(headers, _)| part_mime(headers) == "text/plain")
+                .or_else(|| parts.last());
+            if let Some((headers, body)) = preferred {
+                collect_text(headers, body, text, attachments);
+            }
+        } else {
+            for (headers, body) in &parts {
+                collect_text(headers, body, text, attachments);
+            }
+        }
+        return;
+    }
+
+    if mime == "message/rfc822" {
+        let (inner_headers, inner_body) = split_headers(body);
+        collect_text(&inner_headers, inner_body, text, attachments);
+        return;
+    }
+    if !mime.starts_with("text/") {
+        return;
+    }
+
+    let encoding = headers
+        .iter()
+        .find(|(key, _)| key == "content-transfer-encoding")
+        .map(|(_, value)| value.to_lowercase());
+    let decoded = match encoding.as_deref() {
+        Some("base64") => decode_base64(body),
+        Some("quoted-printable") => decode_quoted_printable(body),
+        _ => body.to_vec(),
+    };
+    let decoded = decode_charset(&decoded, params.get("charset").map(String::as_str));
+    let part_text = if mime == "text/html" {
+        WebCrawler::clean_html(&decoded).unwrap_or_default()
+    } else {
+        decoded
+    };
+    if !part_text.trim().is_empty() {
+        if !text.is_empty() {
+            text.push_str("\n\n");
+        }
+        text.push_str(part_text.trim_end());
+    }
+}
+
+fn part_mime(headers: &[(String, String)]) -> String {
+    headers
+        .iter()
+        .find(|(key, _)| key == "content-type")
+        .map(|(_, value)| parse_content_type(value).0)
+        .unwrap_or_else(|| "text/plain".to_string())
+}
+
+/// Lower-case media type and parameters of a `Content-Type` or
+/// `Content-Disposition` value
+fn parse_content_type(value: &str) -> (String, HashMap<String, String>) {
+    let mut fields = split_unquoted(value, ';').into_iter();
+    let mime = fields.next().unwrap_or_default().trim().to_lowercase();
+    let params = fields
+        .filter_map(|field| {
+            let (key, value) = field.split_once('=')?;
+            let key = key.trim().to_lowercase();
+            // RFC 2231 names (`filename*`) carry a charset prefix
+            let key = key.trim_end_matches('*').to_string();
+            let value = value.trim().trim_matches('"');
+            let value = value
+                .split_once("''")
+                .map(|(_, rest)| percent_decode(rest))
+                .unwrap_or_else(|| value.to_string());
+            Some((key, value))
+        })
+        .collect();
+    (mime, params)
+}
+
+fn split_multipart<'a>(body: &'a [u8], boundary: &str) -> Vec<&'a [u8]> {
+    let delimiter = format!("--{}", boundary);
+    let mut parts = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut offset = 0usize;
+    for line in body.split_inclusive(|byte| *byte == b'\n') {
+        let trimmed = line.trim_ascii_end();
+        if trimmed.starts_with(delimiter.as_bytes()) {
+            if let Some(begin) = start {
+                // The newline before a delimiter belongs to it
+                parts.push(&body[begin..offset.saturating_sub(1).max(begin)]);
+            }
+            if trimmed[delimiter.len()..].starts_with(b"--") {
+                return parts;
+            }
+            start = Some(offset + line.len());
+        }
+        offset += line.len();
+    }
+    if let Some(begin) = start.filter(|begin| *begin < body.len()) {
+        parts.push(&body[begin..]);
+    }
+    parts
+}
+
+fn decode_base64(body: &[u8]) -> Vec<u8> {
+    let compact: Vec<u8> = body
+        .iter()
+        .copied()
+        .filter(|byte| !byte.is_ascii_whitespace())
+        .collect();
+    base64::engine::general_purpose::STANDARD
+        .decode(&compact)
+        .or_else(|_| base64::engine::general_purpose::STANDARD_NO_PAD.decode(&compact))
+        .unwrap_or_default()
+}
+
+fn decode_quoted_printable(body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(body.len());
+    let mut i = 0;
+    while i < body.len() {
+        match body[i] {
+            b'=' if body.get(i + 1) == Some(&b'\n') => i += 2,
+            b'=' => match body
+                .get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                Some(byte) => {
+                    out.push(byte);
+                    i += 3;
+                }
+                None => {
+                    out.push(b'=');
+                    i += 1;
+                }
+            },
+            byte => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+fn decode_charset(bytes: &[u8], charset: Option<&str>) -> String {
+    match charset.and_then(|label| encoding_rs::Encoding::for_label(label.trim().as_bytes())) {
+        Some(encoding) => encoding.decode(bytes).0.into_owned(),
+        None => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+/// Decode RFC 2047 encoded words (`=?utf-8?B?...?=`); whitespace between
+/// two encoded words is dropped
+fn decode_words(value: &str) -> String {
+    let mut out = String::new();
+    let mut rest = value;
+    let mut after_word = false;
+    while let Some(start) = rest.find("=?") {
+        let Some((decoded, len)) = decode_word(&rest[start..]) else {
+            out.push_str(&rest[..start + 2]);
+            rest = &rest[start + 2..];
+            after_word = false;
+            continue;
+        };
+        let between = &rest[..start];
+        if !(after_word && between.trim().is_empty()) {
+            out.push_str(between);
+        }
+        out.push_str(&decoded);
+        rest = &rest[start + len..];
+        after_word = true;
+    }
+    out.push_str(rest);
+    out.trim().to_string()
+}
+
+/// One encoded word at the start of `text`, and its length
+fn decode_word(text: &str) -> Option<(String, usize)> {
+    let inner = text.strip_prefix("=?")?;
+    let (charset, inner) = inner.split_once('?')?;
+    let (encoding, inner) = inner.split_once('?')?;
+    let end = inner.find("?=")?;
+    let payload = &inner[..end];
+    let bytes = match encoding.to_ascii_lowercase().as_str() {
+        "b" => decode_base64(payload.as_bytes()),
+        "q" => decode_quoted_printable(payload.replace('_', " ").as_bytes()),
+        _ => return None,
+    };
+    let len = 2 + charset.len() + 1 + encoding.len() + 1 + end + 2;
+    // Language suffixes (`utf-8*en`) are ignored
+    let charset = charset.split('*').next().unwrap_or(charset);
+    Some((decode_charset(&bytes, Some(charset)), len))
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%')
+            .then(|| value.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match hex {
+            Some(byte) => {
+                out.push(byte);
+                i += 3;
+            }
+            None => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Split on `separator` outside of quotes and angle brackets
+fn split_unquoted(value: &str, separator: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let (mut quoted, mut angle) = (false, false);
+    for ch in value.chars() {
+        match ch {
+            '"' => quoted = !quoted,
+            '<' if !quoted => angle = true,
+            '>' if !quoted => angle = false,
+            _ if ch == separator && !quoted && !angle => {
+                fields.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(ch);
+    }
+    fields.push(current);
+    fields
+}
+
+fn mailboxes(value: &str) -> Vec<Mailbox> {
+    split_unquoted(value, ',')
+        .iter()
+        .filter_map(|field| {
+            let field = field.trim();
+            let (name, address) = match (field.rfind('<'), field.rfind('>')) {
+                (Some(open), Some(close)) if open < close => {
+                    (&field[..open], &field[open + 1..close])
+                }
+                _ => ("", field),
+            };
+            let address = address.trim().to_lowercase();
+            if !address.contains('@') {
+                return None;
+            }
+            let name = name.trim().trim_matches('"').trim();
+            Some(Mailbox {
+                name: (!name.is_empty()).then(|| name.to_string()),
+                address,
+            })
+        })
+        .collect()
+}
+
+fn message_ids(value: &str) -> Vec<String> {
+    let ids: Vec<String> = value
+        .split('<')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('>'))
+        .map(|(id, _)| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect();
+    if ids.is_empty() && !value.trim().is_empty() {
+        vec![value.trim().to_string()]
+    } else {
+        ids
+    }
+}
+
+/// RFC 2822 date; trailing comments like `(UTC)` are ignored
+fn parse_date(value: &str) -> Option<i64> {
+    let value = match value.find('(') {
+        Some(comment) => value[..comment].trim(),
+        None => value.trim(),
+    };
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|date| date.timestamp())
+}
+
+/// Cut the quoted message a reply carries and the sender's signature. When
+/// nothing is left (a bare forward), the text is kept whole.
+pub fn strip_replies(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut kept: Vec<&str> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        let lower = trimmed.to_lowercase();
+        if is_reply_header(&lines, i) || QUOTE_MARKERS.iter().any(|m| lower.starts_with(m)) {
+            break;
+        }
+        // Standard signature delimiter
+        if *line == "-- " || trimmed == "--" {
+            break;
+        }
+        if CLIENT_FOOTERS
+            .iter()
+            .any(|footer| lower.starts_with(footer))
+        {
+            continue;
+        }
+        if trimmed.starts_with('>') {
+            continue;
+        }
+        kept.push(line);
+    }
+    let stripped = kept.join("\n").trim().to_string();
+    if stripped.is_empty() {
+        text.trim().to_string()
+    } else {
+        stripped
+    }
+}
+
+/// `On <date>, <name> wrote:`, possibly wrapped onto a second line, or an
+/// Outlook `From:` block followed by `Sent:` or `Date:`
+fn is_reply_header(lines: &[&str], i: usize) -> bool {
+    let line = lines[i].trim();
+    let next = lines.get(i + 1).map(|line| line.trim()).unwrap_or_default();
+    if line.starts_with("On ") {
+        return line.ends_with("wrote:") || next.ends_with("wrote:");
+    }
+    if starts_with_ignore_case(line, "from:") {
+        return starts_with_ignore_case(next, "sent:") || starts_with_ignore_case(next, "date:");
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPLY: &str = "From: \"Ana Putri\" <Ana@Example.com>\r\n\
+To: team@example.com, Budi <budi@example.com>\r\n\
+Subject: =?UTF-8?Q?Re:_Rilis_v2?=\r\n\
+\x20=?UTF-8?B?IOKAkyBqYWR3YWw=?=\r\n\
+Date: Tue, 2 Jan 2024 10:00:00 +0700 (WIB)\r\n\
+Message-ID: <b@example.com>\r\n\
+In-Reply-To: <a@example.com>\r\n\
+References: <a@example.com>\r\n\
+Content-Type: multipart/alternative; boundary=\"xyz\"\r\n\
+\r\n\
+--xyz\r\n\
+Content-Type: text/plain; charset=utf-8\r\n\
+Content-Transfer-Encoding: quoted-printable\r\n\
+\r\n\
+Jadwal rilis mundur ke Jumat, karena =\r\n\
+QA belum selesai.\r\n\
+\r\n\
+On Mon, 1 Jan 2024 at 09:00, Budi <budi@example.com>\r\n\
+wrote:\r\n\
+> Kapan rilis v2?\r\n\
+--xyz\r\n\
+Content-Type: text/html\r\n\
+\r\n\
+<p>Jadwal rilis mundur</p>\r\n\
+--xyz--\r\n";
+
+    #[test]
+    fn reply_headers_and_body_are_decoded() {
+        let messages = parse_mailbox(REPLY.as_bytes());
+        assert_eq!(messages.len(), 1);
+        let message = &messages[0];
+        assert_eq!(message.subject, "Re: Rilis v2 – jadwal");
+        assert_eq!(
+            message.from,
+            Some(Mailbox {
+                name: Some("Ana Putri".to_string()),
+                address: "ana@example.com".to_string(),
+            })
+        );
+        assert_eq!(message.to.len(), 2);
+        assert_eq!(message.sent_at, Some(1704164400));
+        assert_eq!(message.in_reply_to.as_deref(), Some("a@example.com"));
+        assert_eq!(
+            message.body,
+            "Jadwal rilis mundur ke Jumat, karena QA belum selesai."
+        );
+    }
+
+    #[test]
+    fn mbox_messages_split_and_thread() {
+        let mbox = "From a@example.com Mon Jan  1 09:00:00 2024\n\
+Message-ID: <a@example.com>\n\
+Subject: Rilis v2\n\
+\n\
+Kapan rilis v2?\n\
+>From the roadmap, it was planned for Friday.\n\
+\n\
+-- \n\
+Budi\n\
+\n\
+From b@example.com Mon Jan  1 10:00:00 2024\n\
+Message-ID: <b@example.com>\n\
+Subject: RE: Rilis v2\n\
+\n\
+Jumat.\n\
+\n\
+From c@example.com Mon Jan  1 11:00:00 2024\n\
+Message-ID: <c@example.com>\n\
+Subject: Makan siang\n\
+Content-Type: multipart/mixed; boundary=b1\n\
+\n\
+--b1\n\
+Content-Type: text/plain\n\
+\n\
+Menu terlampir.\n\
+--b1\n\
+Content-Type: application/pdf; name=\"menu.pdf\"\n\
+Content-Transfer-Encoding: base64\n\
+\n\
+JVBERi0=\n\
+--b1--\n";
+        let messages = parse_mailbox(mbox.as_bytes());
+        assert_eq!(messages.len(), 3);
+        assert_eq!(
+            messages[0].body,
+            "Kapan rilis v2?\nFrom the roadmap, it was planned for Friday."
+        );
+        assert_eq!(messages[2].body, "Menu terlampir.");
+        assert_eq!(messages[2].attachments, vec!["menu.pdf".to_string()]);
+        assert_eq!(
+            thread_ids(&messages),
+            vec!["a@example.com", "a@example.com", "c@example.com"]
+        );
+    }
+}
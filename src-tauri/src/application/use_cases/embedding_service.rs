@@ -1,13 +1,25 @@
+use crate::domain::embedding_models::{EmbeddingExecutionProvider, EmbeddingProviderBenchmark};
+use crate::domain::embedding_quantization::StoredEmbedding;
 use crate::domain::error::{AppError, Result};
 use crate::domain::llm_config::LLMConfig;
 use crate::domain::llm_config::LLMProvider;
-use fastembed::{EmbeddingModel, InitOptions, TextEmbedding};
+use crate::infrastructure::embedding_models::EmbeddingModelStore;
+use crate::infrastructure::execution_providers;
+use crate::infrastructure::security::redact::redact_secrets;
+use fastembed::{
+    EmbeddingModel, InitOptions, InitOptionsUserDefined, TextEmbedding, TokenizerFiles,
+    UserDefinedEmbeddingModel,
+};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+mod batch;
+
+pub use batch::{BatchEmbeddingOptions, EmbeddingThroughputStats};
+
 // ============================================================
 // EMBEDDING CACHE
 // ============================================================
@@ -200,6 +212,47 @@ struct OllamaEmbeddingResponse {
     embedding: Vec<f32>,
 }
 
+/// Local models the model manager can download, by the id used as model name.
+/// multilingual-e5-large keeps its weights in a separate file and is left
+/// to fastembed's own download.
+pub const LOCAL_EMBEDDING_MODELS: &[&str] = &[
+    "all-minilm-l6-v2",
+    "all-minilm-l12-v2",
+    "bge-small-en-v1.5",
+    "bge-base-en-v1.5",
+    "bge-large-en-v1.5",
+    "nomic-embed-text-v1",
+    "nomic-embed-text-v1.5",
+    "multilingual-e5-small",
+    "multilingual-e5-base",
+];
+
+/// Tokenizer files every local model needs besides its ONNX file
+const TOKENIZER_FILES: [&str; 4] = [
+    "tokenizer.json",
+    "config.json",
+    "special_tokens_map.json",
+    "tokenizer_config.json",
+];
+
+/// What the model manager needs to download and describe a local model
+#[derive(Debug, Clone)]
+pub struct LocalModelSpec {
+    pub id: &'static str,
+    pub model_code: String,
+    pub description: String,
+    pub dimension: usize,
+    pub model_file: String,
+    /// Every file to download, the ONNX file first
+    pub files: Vec<String>,
+}
+
+/// In-process model and the execution provider it was loaded on
+struct LocalEmbedder {
+    model: TextEmbedding,
+    provider: EmbeddingExecutionProvider,
+}
+
 /// Default cache size (1000 embeddings)
 const DEFAULT_CACHE_SIZE: usize = 1000;
 /// Default TTL in seconds (1 hour)
@@ -208,9 +261,15 @@ const DEFAULT_CACHE_TTL_SECS: u64 = 3600;
 pub struct EmbeddingService {
     client: Client,
     config: Arc<Mutex<LLMConfig>>,
-    local_embedder: Arc<Mutex<Option<TextEmbedding>>>,
+    local_embedder: Arc<Mutex<Option<LocalEmbedder>>>,
+    /// Execution providers tried in order when loading a local model
+    execution_providers: Arc<Mutex<Vec<EmbeddingExecutionProvider>>>,
+    /// Models downloaded by the model manager; others are fetched by fastembed
+    model_store: Option<Arc<EmbeddingModelStore>>,
     /// Embedding cache for performance
     cache: Arc<Mutex<EmbeddingCache>>,
+    /// Totals across `embed_batch` runs
+    throughput: Mutex<EmbeddingThroughputStats>,
 }
 
 impl EmbeddingService {
@@ -219,10 +278,13 @@ impl EmbeddingService {
             client: Client::new(),
             config: Arc::new(Mutex::new(config)),
             local_embedder: Arc::new(Mutex::new(None)),
+            execution_providers: Arc::new(Mutex::new(vec![EmbeddingExecutionProvider::Cpu])),
+            model_store: None,
             cache: Arc::new(Mutex::new(EmbeddingCache::new(
                 DEFAULT_CACHE_SIZE,
                 DEFAULT_CACHE_TTL_SECS,
             ))),
+            throughput: Mutex::new(EmbeddingThroughputStats::default()),
         }
     }
 
@@ -232,10 +294,47 @@ impl EmbeddingService {
             client: Client::new(),
             config: Arc::new(Mutex::new(config)),
             local_embedder: Arc::new(Mutex::new(None)),
+            execution_providers: Arc::new(Mutex::new(vec![EmbeddingExecutionProvider::Cpu])),
+            model_store: None,
             cache: Arc::new(Mutex::new(EmbeddingCache::new(cache_size, cache_ttl_secs))),
+            throughput: Mutex::new(EmbeddingThroughputStats::default()),
+        }
+    }
+
+    /// Load downloaded local models from `store`
+    pub fn with_model_store(mut self, store: Arc<EmbeddingModelStore>) -> Self {
+        self.model_store = Some(store);
+        self
+    }
+
+    pub fn model_store(&self) -> Option<&Arc<EmbeddingModelStore>> {
+        self.model_store.as_ref()
+    }
+
+    /// Providers to try, in order, for local models; the loaded model is
+    /// reloaded on the next embedding when they change
+    pub fn set_execution_providers(&self, providers: Vec<EmbeddingExecutionProvider>) {
+        let mut current = self.execution_providers.lock().unwrap();
+        if *current != providers {
+            *current = providers;
+            *self.local_embedder.lock().unwrap() = None;
         }
     }
 
+    /// Provider the local model runs on, once it has been loaded
+    pub fn local_execution_provider(&self) -> Option<EmbeddingExecutionProvider> {
+        self.local_embedder
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|embedder| embedder.provider)
+    }
+
+    /// Current provider and model
+    pub fn config(&self) -> LLMConfig {
+        self.config.lock().unwrap().clone()
+    }
+
     /// Get cache statistics
     pub fn cache_stats(&self) -> CacheStats {
         self.cache.lock().unwrap().stats()
@@ -251,6 +350,22 @@ impl EmbeddingService {
         self.cache.lock().unwrap().cleanup();
     }
 
+    /// Cached embedding for `text`, without generating one on a miss
+    pub fn cached_embedding(&self, text: &str) -> Option<Vec<f32>> {
+        self.cache.lock().unwrap().get(text)
+    }
+
+    /// Add an embedding computed earlier with the current model
+    pub fn seed_cache(&self, text: &str, embedding: Vec<f32>) {
+        self.cache.lock().unwrap().put(text, embedding);
+    }
+
+    /// Provider and model, to tell whether a stored embedding is still comparable
+    pub fn model_id(&self) -> String {
+        let config = self.config.lock().unwrap();
+        format!("{:?}/{}", config.provider, config.model)
+    }
+
     fn resolve_gemini_embedding_model(model: &str) -> String {
         let trimmed = model.trim().trim_start_matches("models/");
         if trimmed.contains("embedding") {
@@ -261,7 +376,12 @@ impl EmbeddingService {
     }
 
     fn resolve_local_embedding_model(model: &str) -> EmbeddingModel {
-        match model.trim().to_lowercase().as_str() {
+        // Default to best model
+        Self::local_embedding_model(model).unwrap_or(EmbeddingModel::NomicEmbedTextV15)
+    }
+
+    fn local_embedding_model(model: &str) -> Option<EmbeddingModel> {
+        let model = match model.trim().to_lowercase().as_str() {
             "all-minilm-l6-v2" => EmbeddingModel::AllMiniLML6V2,
             "nomic-embed-text" | "nomic-embed-text-v1" => EmbeddingModel::NomicEmbedTextV1,
             "nomic-embed-text-v1.5" | "nomic-embed-text-v15" => EmbeddingModel::NomicEmbedTextV15,
@@ -272,8 +392,35 @@ impl EmbeddingService {
             "multilingual-e5-small" => EmbeddingModel::MultilingualE5Small,
             "multilingual-e5-base" => EmbeddingModel::MultilingualE5Base,
             "multilingual-e5-large" => EmbeddingModel::MultilingualE5Large,
-            _ => EmbeddingModel::NomicEmbedTextV15, // Default to best model
-        }
+            _ => return None,
+        };
+        Some(model)
+    }
+
+    /// Manager id of a local model name, so aliases share one download
+    pub fn local_model_id(model: &str) -> Option<&'static str> {
+        let resolved = Self::local_embedding_model(model)?;
+        LOCAL_EMBEDDING_MODELS
+            .iter()
+            .copied()
+            .find(|id| Self::local_embedding_model(id).as_ref() == Some(&resolved))
+    }
+
+    /// Files and metadata of a model in `LOCAL_EMBEDDING_MODELS`
+    pub fn local_model_spec(model: &str) -> Option<LocalModelSpec> {
+        let id = Self::local_model_id(model)?;
+        let embedding_model = Self::local_embedding_model(id)?;
+        let info = TextEmbedding::get_model_info(&embedding_model).ok()?;
+        let mut files = vec![info.model_file.clone()];
+        files.extend(TOKENIZER_FILES.iter().map(|file| file.to_string()));
+        Some(LocalModelSpec {
+            id,
+            model_code: info.model_code.clone(),
+            description: info.description.clone(),
+            dimension: info.dim,
+            model_file: info.model_file.clone(),
+            files,
+        })
     }
 
     /// Get the embedding dimension for a given model
@@ -346,37 +493,229 @@ impl EmbeddingService {
     }
 
     async fn generate_local_embedding(&self, text: &str, config: LLMConfig) -> Result<Vec<f32>> {
-        let model = Self::resolve_local_embedding_model(&config.model);
-        let mut guard = self.local_embedder.lock().unwrap();
+        let providers = self.execution_providers.lock().unwrap().clone();
+        Self::embed_locally(
+            &self.local_embedder,
+            self.model_store.as_deref(),
+            &providers,
+            &config.model,
+            vec![text.to_string()],
+            None,
+        )?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::Internal("Empty embedding response".to_string()))
+    }
+
+    /// Embed `texts` with the in-process model, loading it on first use
+    fn embed_locally(
+        local_embedder: &Mutex<Option<LocalEmbedder>>,
+        model_store: Option<&EmbeddingModelStore>,
+        providers: &[EmbeddingExecutionProvider],
+        model: &str,
+        texts: Vec<String>,
+        batch_size: Option<usize>,
+    ) -> Result<Vec<Vec<f32>>> {
+        let mut guard = local_embedder.lock().unwrap();
         if guard.is_none() {
-            let mut options = InitOptions::default();
-            options.model_name = model;
-            let embedder = TextEmbedding::try_new(options)
-                .map_err(|e| AppError::Internal(format!("Failed to init local embedder: {}", e)))?;
-            *guard = Some(embedder);
+            *guard = Some(Self::load_with_fallback(model_store, providers, model)?);
         }
         let embedder = guard
             .as_mut()
             .ok_or_else(|| AppError::Internal("Local embedder unavailable".to_string()))?;
         let embeddings = embedder
-            .embed(vec![text.to_string()], None)
+            .model
+            .embed(texts, batch_size)
             .map_err(|e| AppError::Internal(format!("Failed to embed text: {}", e)))?;
-        let embedding = embeddings
-            .into_iter()
-            .next()
-            .ok_or_else(|| AppError::Internal("Empty embedding response".to_string()))?;
-        if embedding.is_empty() {
+        if embeddings.iter().any(|embedding| embedding.is_empty()) {
             return Err(AppError::Internal("Empty embedding response".to_string()));
         }
-        Ok(embedding)
+        Ok(embeddings)
     }
 
-    async fn generate_openai_embedding(&self, text: &str, config: LLMConfig) -> Result<Vec<f32>> {
-        let url = if config.base_url.ends_with("/") {
-            format!("{}embeddings", config.base_url)
-        } else {
-            format!("{}/embeddings", config.base_url)
+    /// Load `model` on the first of `providers` that works, the CPU last
+    fn load_with_fallback(
+        model_store: Option<&EmbeddingModelStore>,
+        providers: &[EmbeddingExecutionProvider],
+        model: &str,
+    ) -> Result<LocalEmbedder> {
+        let mut last_error = None;
+        for &provider in providers {
+            if let Err(reason) = execution_providers::check(provider) {
+                tracing::debug!(
+                    "Skipping {} for local embeddings: {}",
+                    provider.label(),
+                    reason
+                );
+                continue;
+            }
+            match Self::load_local_model(model_store, model, provider) {
+                Ok(model) => {
+                    if provider != EmbeddingExecutionProvider::Cpu {
+                        tracing::info!("Local embeddings run on {}", provider.label());
+                    }
+                    return Ok(LocalEmbedder { model, provider });
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        "Loading the local embedding model on {} failed: {}",
+                        provider.label(),
+                        err
+                    );
+                    last_error = Some(err);
+                }
+            }
+        }
+        match last_error {
+            Some(err) => Err(err),
+            // Every GPU provider was skipped and the CPU was not in the list
+            None => Self::load_local_model(model_store, model, EmbeddingExecutionProvider::Cpu)
+                .map(|model| LocalEmbedder {
+                    model,
+                    provider: EmbeddingExecutionProvider::Cpu,
+                }),
+        }
+    }
+
+    /// Load `model` on `provider`, from the model manager's files when it
+    /// downloaded them and through fastembed otherwise
+    fn load_local_model(
+        model_store: Option<&EmbeddingModelStore>,
+        model: &str,
+        provider: EmbeddingExecutionProvider,
+    ) -> Result<TextEmbedding> {
+        let installed = model_store
+            .zip(Self::local_model_spec(model))
+            .filter(|(store, spec)| store.manifest(spec.id).is_some());
+        match installed {
+            Some((store, spec)) => Self::load_installed(store, &spec, provider),
+            None => {
+                let mut options = InitOptions::default();
+                options.model_name = Self::resolve_local_embedding_model(model);
+                options.execution_providers = vec![execution_providers::dispatch(provider)];
+                TextEmbedding::try_new(options).map_err(|e| {
+                    AppError::Internal(format!("Failed to init local embedder: {}", e))
+                })
+            }
+        }
+    }
+
+    /// Build the embedder from files downloaded by the model manager
+    fn load_installed(
+        store: &EmbeddingModelStore,
+        spec: &LocalModelSpec,
+        provider: EmbeddingExecutionProvider,
+    ) -> Result<TextEmbedding> {
+        let embedding_model = Self::resolve_local_embedding_model(spec.id);
+        let read = |path: &str| store.read_file(spec.id, path);
+        let tokenizer_files = TokenizerFiles {
+            tokenizer_file: read("tokenizer.json")?,
+            config_file: read("config.json")?,
+            special_tokens_map_file: read("special_tokens_map.json")?,
+            tokenizer_config_file: read("tokenizer_config.json")?,
         };
+        let mut model = UserDefinedEmbeddingModel::new(read(&spec.model_file)?, tokenizer_files)
+            .with_quantization(TextEmbedding::get_quantization_mode(&embedding_model));
+        if let Some(pooling) = TextEmbedding::get_default_pooling_method(&embedding_model) {
+            model = model.with_pooling(pooling);
+        }
+        let options = InitOptionsUserDefined::default()
+            .with_execution_providers(vec![execution_providers::dispatch(provider)]);
+        TextEmbedding::try_new_from_user_defined(model, options)
+            .map_err(|e| AppError::Internal(format!("Failed to load {}: {}", spec.id, e)))
+    }
+
+    /// Embeddings per second of `model` on each of `providers`, each loaded
+    /// fresh so the running embedder is left alone. Blocks; run it off the
+    /// async runtime.
+    pub fn benchmark_local(
+        &self,
+        model: &str,
+        providers: &[EmbeddingExecutionProvider],
+        texts: &[String],
+        batch_size: usize,
+    ) -> Vec<EmbeddingProviderBenchmark> {
+        providers
+            .iter()
+            .map(|&provider| {
+                let mut result = EmbeddingProviderBenchmark {
+                    provider,
+                    available: false,
+                    load_ms: None,
+                    embeddings_per_sec: None,
+                    error: None,
+                };
+                if let Err(reason) = execution_providers::check(provider) {
+                    result.error = Some(reason);
+                    return result;
+                }
+
+                let started = Instant::now();
+                let mut embedder =
+                    match Self::load_local_model(self.model_store.as_deref(), model, provider) {
+                        Ok(embedder) => embedder,
+                        Err(err) => {
+                            result.error = Some(err.to_string());
+                            return result;
+                        }
+                    };
+                result.available = true;
+                result.load_ms = Some(started.elapsed().as_millis() as u64);
+
+                // The first run compiles kernels and allocates buffers
+                let warm_up: Vec<String> = texts.iter().take(batch_size).cloned().collect();
+                if let Err(err) = embedder.embed(warm_up, Some(batch_size)) {
+                    result.error = Some(format!("Failed to embed text: {}", err));
+                    return result;
+                }
+                let started = Instant::now();
+                match embedder.embed(texts.to_vec(), Some(batch_size)) {
+                    Ok(embeddings) => {
+                        let seconds = started.elapsed().as_secs_f64().max(f64::EPSILON);
+                        result.embeddings_per_sec = Some(embeddings.len() as f64 / seconds);
+                    }
+                    Err(err) => result.error = Some(format!("Failed to embed text: {}", err)),
+                }
+                result
+            })
+            .collect()
+    }
+
+    fn openai_embeddings_url(base_url: &str) -> String {
+        if base_url.ends_with("/") {
+            format!("{}embeddings", base_url)
+        } else {
+            format!("{}/embeddings", base_url)
+        }
+    }
+
+    fn gemini_models_url(base_url: &str) -> String {
+        let base_url = base_url.trim_end_matches('/');
+        if base_url.ends_with("/models") {
+            base_url.to_string()
+        } else {
+            format!("{}/models", base_url)
+        }
+    }
+
+    /// Ollama API endpoint, dropping the OpenAI-compatible `/v1` suffix
+    fn ollama_api_url(base_url: &str, endpoint: &str) -> String {
+        let mut url = base_url.to_string();
+
+        if url.ends_with("/v1") || url.ends_with("/v1/") {
+            url = url.trim_end_matches("/v1").to_string();
+            url = url.trim_end_matches("/").to_string();
+        }
+
+        if url.ends_with("/") {
+            format!("{}api/{}", url, endpoint)
+        } else {
+            format!("{}/api/{}", url, endpoint)
+        }
+    }
+
+    async fn generate_openai_embedding(&self, text: &str, config: LLMConfig) -> Result<Vec<f32>> {
+        let url = Self::openai_embeddings_url(&config.base_url);
 
         let request = OpenAIEmbeddingRequest {
             model: config.model.clone(),
@@ -405,7 +744,9 @@ impl EmbeddingService {
                 .unwrap_or_else(|_| "Unknown error".to_string());
             return Err(AppError::Internal(format!(
                 "Embedding API returned error {} (URL: {}): {}",
-                status, url, error_text
+                status,
+                url,
+                redact_secrets(&error_text)
             )));
         }
 
@@ -431,12 +772,12 @@ impl EmbeddingService {
             .api_key
             .clone()
             .ok_or_else(|| AppError::Internal("Missing API key for Google provider".to_string()))?;
-        let mut base_url = config.base_url.trim_end_matches('/').to_string();
-        if !base_url.ends_with("/models") {
-            base_url = format!("{}/models", base_url);
-        }
         let model_id = Self::resolve_gemini_embedding_model(&config.model);
-        let url = format!("{}/{}:embedContent?key={}", base_url, model_id, api_key);
+        let url = format!(
+            "{}/{}:embedContent",
+            Self::gemini_models_url(&config.base_url),
+            model_id
+        );
 
         let request = GeminiEmbeddingRequest {
             content: GeminiEmbeddingContent {
@@ -446,9 +787,11 @@ impl EmbeddingService {
             },
         };
 
+        // Header instead of `?key=` so the key stays out of the URL in error messages
         let response = self
             .client
             .post(&url)
+            .header("x-goog-api-key", api_key)
             .header("Content-Type", "application/json")
             .json(&request)
             .send()
@@ -465,7 +808,9 @@ impl EmbeddingService {
                 .unwrap_or_else(|_| "Unknown error".to_string());
             return Err(AppError::Internal(format!(
                 "Embedding API returned error {} (URL: {}): {}",
-                status, url, error_text
+                status,
+                url,
+                redact_secrets(&error_text)
             )));
         }
 
@@ -482,18 +827,7 @@ impl EmbeddingService {
     }
 
     async fn generate_ollama_embedding(&self, text: &str, config: LLMConfig) -> Result<Vec<f32>> {
-        let mut url = config.base_url.clone();
-
-        if url.ends_with("/v1") || url.ends_with("/v1/") {
-            url = url.trim_end_matches("/v1").to_string();
-            url = url.trim_end_matches("/").to_string();
-        }
-
-        url = if url.ends_with("/") {
-            format!("{}api/embeddings", url)
-        } else {
-            format!("{}/api/embeddings", url)
-        };
+        let url = Self::ollama_api_url(&config.base_url, "embeddings");
 
         let request = OllamaEmbeddingRequest {
             model: config.model.clone(),
@@ -519,7 +853,9 @@ impl EmbeddingService {
                 .unwrap_or_else(|_| "Unknown error".to_string());
             return Err(AppError::Internal(format!(
                 "Embedding API returned error {} (URL: {}): {}",
-                status, url, error_text
+                status,
+                url,
+                redact_secrets(&error_text)
             )));
         }
 
@@ -536,13 +872,13 @@ impl EmbeddingService {
         Ok(embedding)
     }
 
+    /// Embed `texts` with default batching, failing on the first error
     pub async fn generate_embeddings_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
-        let mut embeddings = Vec::new();
-        for text in texts {
-            let embedding = self.generate_embedding(text).await?;
-            embeddings.push(embedding);
-        }
-        Ok(embeddings)
+        let texts: Vec<String> = texts.iter().map(|text| text.to_string()).collect();
+        let (embeddings, _) = self
+            .embed_batch(&texts, &BatchEmbeddingOptions::default())
+            .await;
+        embeddings.into_iter().collect()
     }
 
     pub fn embedding_to_bytes(embedding: &[f32]) -> Vec<u8> {
@@ -587,6 +923,97 @@ impl EmbeddingService {
     }
 }
 
+/// How query and chunk embeddings are compared. Most embedding models are
+/// trained for cosine similarity; some for the raw dot product, where the
+/// vector length carries meaning.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimilarityMetric {
+    #[default]
+    Cosine,
+    Dot,
+    Euclidean,
+}
+
+impl SimilarityMetric {
+    /// Similarity of two embeddings, higher meaning closer. Euclidean
+    /// distance `d` becomes `1 / (1 + d)`.
+    pub fn similarity(&self, a: &[f32], b: &[f32]) -> f32 {
+        if a.len() != b.len() {
+            return 0.0;
+        }
+        match self {
+            SimilarityMetric::Cosine => EmbeddingService::cosine_similarity(a, b),
+            SimilarityMetric::Dot => a.iter().zip(b).map(|(x, y)| x * y).sum(),
+            SimilarityMetric::Euclidean => {
+                let distance = a
+                    .iter()
+                    .zip(b)
+                    .map(|(x, y)| (x - y) * (x - y))
+                    .sum::<f32>()
+                    .sqrt();
+                1.0 / (1.0 + distance)
+            }
+        }
+    }
+
+    /// Similarity of a query to a stored embedding. Quantized ones are
+    /// scored from their dot product and norm without being expanded.
+    pub fn similarity_stored(&self, query: &[f32], stored: &StoredEmbedding) -> f32 {
+        if let StoredEmbedding::Float32(values) = stored {
+            return self.similarity(query, values);
+        }
+        if query.len() != stored.dimension() {
+            return 0.0;
+        }
+        let dot = stored.dot(query);
+        let query_norm = query.iter().map(|x| x * x).sum::<f32>();
+        match self {
+            SimilarityMetric::Cosine => {
+                let norms = (query_norm * stored.norm_squared()).sqrt();
+                if norms == 0.0 {
+                    0.0
+                } else {
+                    dot / norms
+                }
+            }
+            SimilarityMetric::Dot => dot,
+            SimilarityMetric::Euclidean => {
+                let distance = (query_norm + stored.norm_squared() - 2.0 * dot)
+                    .max(0.0)
+                    .sqrt();
+                1.0 / (1.0 + distance)
+            }
+        }
+    }
+
+    /// A similarity on the scale keyword scores are fused at. Dot products
+    /// are unbounded, so they are squashed into -1..1; the others already
+    /// are in range.
+    pub fn fusion_score(&self, similarity: f32) -> f32 {
+        match self {
+            SimilarityMetric::Dot => similarity.tanh(),
+            SimilarityMetric::Cosine | SimilarityMetric::Euclidean => similarity,
+        }
+    }
+
+    /// Whether embeddings are scaled to unit length before they are stored.
+    /// Cosine ignores length anyway; dot and euclidean keep it.
+    pub fn normalizes_embeddings(&self) -> bool {
+        matches!(self, SimilarityMetric::Cosine)
+    }
+}
+
+/// `embedding` scaled to unit length; a zero vector stays as it is
+pub fn normalize_embedding(embedding: &mut [f32]) {
+    let norm = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for value in embedding.iter_mut() {
+            *value /= norm;
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SearchResult {
     pub chunk_id: i64,
@@ -609,18 +1036,19 @@ impl VectorSearch {
         Self { embedding_service }
     }
 
-    /// Search using chunks with full metadata
+    /// Search using chunks with full metadata, scoring them by `metric`
     pub fn search_with_metadata(
         &self,
         query_embedding: &[f32],
         chunks: &[ChunkWithMetadata],
         top_k: usize,
+        metric: SimilarityMetric,
     ) -> Vec<SearchResult> {
         let mut results: Vec<SearchResult> = chunks
             .iter()
             .filter_map(|chunk| {
                 if let Some(ref embedding) = chunk.embedding {
-                    let score = EmbeddingService::cosine_similarity(query_embedding, embedding);
+                    let score = metric.similarity_stored(query_embedding, embedding);
                     Some(SearchResult {
                         chunk_id: chunk.id,
                         content: chunk.content.clone(),
@@ -705,4 +1133,63 @@ mod tests {
         let similarity = EmbeddingService::cosine_similarity(&a, &c);
         assert!((similarity - 0.0).abs() < 0.001);
     }
+
+    #[test]
+    fn metrics_rank_by_their_own_measure() {
+        let query = [1.0, 0.0];
+        let short = [0.5, 0.0];
+        let long = [3.0, 1.0];
+
+        let cosine = SimilarityMetric::Cosine;
+        assert!(cosine.similarity(&query, &short) > cosine.similarity(&query, &long));
+
+        let dot = SimilarityMetric::Dot;
+        assert_eq!(dot.similarity(&query, &long), 3.0);
+        assert!(dot.similarity(&query, &long) > dot.similarity(&query, &short));
+        assert!(dot.fusion_score(3.0) < 1.0);
+
+        let euclidean = SimilarityMetric::Euclidean;
+        assert_eq!(euclidean.similarity(&query, &query), 1.0);
+        assert!((euclidean.similarity(&query, &short) - 1.0 / 1.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn quantized_embeddings_score_like_float32() {
+        use crate::domain::embedding_quantization::EmbeddingQuantization;
+
+        let query = [0.3, -0.5, 0.8];
+        let chunk = [0.6, -0.2, 0.4];
+        for metric in [
+            SimilarityMetric::Cosine,
+            SimilarityMetric::Dot,
+            SimilarityMetric::Euclidean,
+        ] {
+            let exact = metric.similarity(&query, &chunk);
+            for quantization in [
+                EmbeddingQuantization::Float32,
+                EmbeddingQuantization::Float16,
+                EmbeddingQuantization::Int8,
+            ] {
+                let stored = quantization.quantize(&chunk);
+                let score = metric.similarity_stored(&query, &stored);
+                assert!(
+                    (score - exact).abs() < 0.01,
+                    "{:?} {:?}",
+                    metric,
+                    quantization
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn normalized_embeddings_have_unit_length() {
+        let mut embedding = vec![3.0, 4.0];
+        normalize_embedding(&mut embedding);
+        assert_eq!(embedding, vec![0.6, 0.8]);
+
+        let mut zero = vec![0.0, 0.0];
+        normalize_embedding(&mut zero);
+        assert_eq!(zero, vec![0.0, 0.0]);
+    }
 }
@@ -0,0 +1,615 @@
+//! Batched embedding for ingestion.
+//!
+//! Texts are split into provider-sized batches and sent by a few concurrent
+//! workers. Request starts are spaced to stay under the provider's
+//! per-minute budget, and throttled requests (429/503) are retried after
+//! `Retry-After` or an exponential backoff.
+
+use super::{EmbeddingService, GeminiEmbeddingContent, GeminiEmbeddingPart, GeminiEmbeddingResult};
+use crate::application::use_cases::rag_config::EmbeddingConfig;
+use crate::domain::error::{AppError, Result};
+use crate::domain::llm_config::{LLMConfig, LLMProvider};
+use crate::infrastructure::security::redact::redact_secrets;
+use futures_util::stream::{self, StreamExt};
+use reqwest::header::RETRY_AFTER;
+use reqwest::{RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Retries of a throttled request before its batch fails
+const MAX_RETRIES: u32 = 4;
+const BASE_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub struct BatchEmbeddingOptions {
+    /// Texts per request, capped by the provider's limit
+    pub batch_size: usize,
+    /// Requests in flight at once, capped by the provider's limit
+    pub concurrency: usize,
+    /// Request budget per minute; `None` uses the provider's default
+    pub requests_per_minute: Option<u32>,
+}
+
+impl Default for BatchEmbeddingOptions {
+    fn default() -> Self {
+        Self {
+            batch_size: 32,
+            concurrency: 4,
+            requests_per_minute: None,
+        }
+    }
+}
+
+impl From<&EmbeddingConfig> for BatchEmbeddingOptions {
+    fn from(config: &EmbeddingConfig) -> Self {
+        Self {
+            batch_size: config.batch_size,
+            concurrency: config.concurrency,
+            requests_per_minute: (config.requests_per_minute > 0)
+                .then_some(config.requests_per_minute),
+        }
+    }
+}
+
+/// What a provider accepts per request and how hard it may be pushed
+struct ProviderLimits {
+    max_batch_size: usize,
+    max_concurrency: usize,
+    requests_per_minute: Option<u32>,
+}
+
+impl ProviderLimits {
+    fn for_provider(provider: &LLMProvider) -> Self {
+        match provider {
+            // One in-process model: larger batches help, parallel calls do not
+            LLMProvider::Local => Self {
+                max_batch_size: 256,
+                max_concurrency: 1,
+                requests_per_minute: None,
+            },
+            LLMProvider::OpenAI => Self {
+                max_batch_size: 2048,
+                max_concurrency: 8,
+                requests_per_minute: Some(3000),
+            },
+            LLMProvider::OpenRouter => Self {
+                max_batch_size: 2048,
+                max_concurrency: 4,
+                requests_per_minute: Some(600),
+            },
+            // `batchEmbedContents` takes at most 100 requests
+            LLMProvider::Gemini => Self {
+                max_batch_size: 100,
+                max_concurrency: 4,
+                requests_per_minute: Some(1500),
+            },
+            // Ollama and other local servers share the machine with the app
+            _ => Self {
+                max_batch_size: 64,
+                max_concurrency: 2,
+                requests_per_minute: None,
+            },
+        }
+    }
+
+    /// `options` clamped to these limits
+    fn apply(&self, options: &BatchEmbeddingOptions) -> BatchEmbeddingOptions {
+        BatchEmbeddingOptions {
+            batch_size: options.batch_size.clamp(1, self.max_batch_size),
+            concurrency: options.concurrency.clamp(1, self.max_concurrency),
+            requests_per_minute: options
+                .requests_per_minute
+                .or(self.requests_per_minute)
+                .filter(|rpm| *rpm > 0),
+        }
+    }
+}
+
+/// Result of one `embed_batch` call
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EmbeddingThroughput {
+    pub texts: usize,
+    /// Served from the embedding cache
+    pub cached: usize,
+    /// Newly computed
+    pub embedded: usize,
+    pub failed: usize,
+    pub requests: usize,
+    /// Requests repeated after the provider throttled them
+    pub retries: usize,
+    pub batch_size: usize,
+    pub concurrency: usize,
+    pub elapsed_ms: u64,
+    /// Newly computed embeddings per second
+    pub texts_per_second: f32,
+}
+
+/// Totals across `embed_batch` calls since startup
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EmbeddingThroughputStats {
+    pub runs: usize,
+    pub texts: usize,
+    pub cached: usize,
+    pub embedded: usize,
+    pub failed: usize,
+    pub requests: usize,
+    pub retries: usize,
+    pub busy_ms: u64,
+    pub texts_per_second: f32,
+    pub last_run: Option<EmbeddingThroughput>,
+}
+
+impl EmbeddingThroughputStats {
+    fn record(&mut self, run: &EmbeddingThroughput) {
+        self.runs += 1;
+        self.texts += run.texts;
+        self.cached += run.cached;
+        self.embedded += run.embedded;
+        self.failed += run.failed;
+        self.requests += run.requests;
+        self.retries += run.retries;
+        self.busy_ms += run.elapsed_ms;
+        self.texts_per_second = texts_per_second(self.embedded, self.busy_ms);
+        self.last_run = Some(run.clone());
+    }
+}
+
+fn texts_per_second(texts: usize, elapsed_ms: u64) -> f32 {
+    if elapsed_ms == 0 {
+        0.0
+    } else {
+        texts as f32 * 1000.0 / elapsed_ms as f32
+    }
+}
+
+/// Spaces request starts evenly so a run stays under a per-minute budget
+struct RequestPacer {
+    interval: Option<Duration>,
+    next_slot: tokio::sync::Mutex<tokio::time::Instant>,
+}
+
+impl RequestPacer {
+    fn new(requests_per_minute: Option<u32>) -> Self {
+        Self {
+            interval: requests_per_minute
+                .filter(|rpm| *rpm > 0)
+                .map(|rpm| Duration::from_secs(60) / rpm),
+            next_slot: tokio::sync::Mutex::new(tokio::time::Instant::now()),
+        }
+    }
+
+    async fn wait(&self) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+        let slot = {
+            let mut next_slot = self.next_slot.lock().await;
+            let slot = (*next_slot).max(tokio::time::Instant::now());
+            *next_slot = slot + interval;
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+/// State shared by the workers of one `embed_batch` call
+struct BatchRun {
+    config: LLMConfig,
+    batch_size: usize,
+    pacer: RequestPacer,
+    requests: AtomicUsize,
+    retries: AtomicUsize,
+    /// Ollama older than `/api/embed`: embed one text per request
+    ollama_legacy: AtomicBool,
+}
+
+/// A failed request, with what is needed to decide on a retry
+struct RequestError {
+    message: String,
+    status: Option<StatusCode>,
+    retry_after: Option<Duration>,
+}
+
+impl RequestError {
+    fn new(message: String) -> Self {
+        Self {
+            message,
+            status: None,
+            retry_after: None,
+        }
+    }
+
+    fn is_throttled(&self) -> bool {
+        matches!(
+            self.status,
+            Some(StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE)
+        )
+    }
+}
+
+impl From<AppError> for RequestError {
+    fn from(error: AppError) -> Self {
+        Self::new(error.to_string())
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct OpenAIBatchEmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIBatchEmbeddingResponse {
+    data: Vec<OpenAIBatchEmbeddingData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIBatchEmbeddingData {
+    #[serde(default)]
+    index: Option<usize>,
+    embedding: Vec<f32>,
+}
+
+#[derive(Debug, Serialize)]
+struct GeminiBatchEmbeddingRequest {
+    requests: Vec<GeminiBatchEmbeddingItem>,
+}
+
+#[derive(Debug, Serialize)]
+struct GeminiBatchEmbeddingItem {
+    model: String,
+    content: GeminiEmbeddingContent,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiBatchEmbeddingResponse {
+    embeddings: Vec<GeminiEmbeddingResult>,
+}
+
+#[derive(Debug, Serialize)]
+struct OllamaBatchEmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaBatchEmbeddingResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+impl EmbeddingService {
+    /// Embed many texts with batched, concurrent requests.
+    ///
+    /// Results are in input order, one per text, so a failed batch only
+    /// fails its own texts. Cached embeddings are reused and new ones cached.
+    pub async fn embed_batch(
+        &self,
+        texts: &[String],
+        options: &BatchEmbeddingOptions,
+    ) -> (Vec<Result<Vec<f32>>>, EmbeddingThroughput) {
+        let started = Instant::now();
+        let config = self.config.lock().unwrap().clone();
+        let options = ProviderLimits::for_provider(&config.provider).apply(options);
+
+        let mut results: Vec<Option<Result<Vec<f32>>>> = texts.iter().map(|_| None).collect();
+        let mut pending = Vec::new();
+        {
+            let mut cache = self.cache.lock().unwrap();
+            for (index, text) in texts.iter().enumerate() {
+                match cache.get(text) {
+                    Some(embedding) => results[index] = Some(Ok(embedding)),
+                    None => pending.push(index),
+                }
+            }
+        }
+        let cached = texts.len() - pending.len();
+
+        let run = BatchRun {
+            config,
+            batch_size: options.batch_size,
+            pacer: RequestPacer::new(options.requests_per_minute),
+            requests: AtomicUsize::new(0),
+            retries: AtomicUsize::new(0),
+            ollama_legacy: AtomicBool::new(false),
+        };
+        let run = &run;
+
+        let chunks: Vec<Vec<usize>> = pending
+            .chunks(options.batch_size)
+            .map(|indices| indices.to_vec())
+            .collect();
+        let mut batches = stream::iter(chunks.into_iter().map(|indices| async move {
+            let batch: Vec<String> = indices.iter().map(|&index| texts[index].clone()).collect();
+            let outcome = self.request_with_retry(&batch, run).await;
+            (indices, outcome)
+        }))
+        .buffer_unordered(options.concurrency);
+
+        while let Some((indices, outcome)) = batches.next().await {
+            match outcome {
+                Ok(embeddings) => {
+                    let mut cache = self.cache.lock().unwrap();
+                    for (&index, embedding) in indices.iter().zip(embeddings) {
+                        cache.put(&texts[index], embedding.clone());
+                        results[index] = Some(Ok(embedding));
+                    }
+                }
+                Err(error) => {
+                    let message = error.to_string();
+                    for index in indices {
+                        results[index] = Some(Err(AppError::Internal(message.clone())));
+                    }
+                }
+            }
+        }
+        drop(batches);
+
+        let results: Vec<Result<Vec<f32>>> = results
+            .into_iter()
+            .map(|result| {
+                result
+                    .unwrap_or_else(|| Err(AppError::Internal("Text was not embedded".to_string())))
+            })
+            .collect();
+
+        let failed = results.iter().filter(|result| result.is_err()).count();
+        let embedded = pending.len() - failed;
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+        let throughput = EmbeddingThroughput {
+            texts: texts.len(),
+            cached,
+            embedded,
+            failed,
+            requests: run.requests.load(Ordering::Relaxed),
+            retries: run.retries.load(Ordering::Relaxed),
+            batch_size: options.batch_size,
+            concurrency: options.concurrency,
+            elapsed_ms,
+            texts_per_second: texts_per_second(embedded, elapsed_ms),
+        };
+        self.throughput.lock().unwrap().record(&throughput);
+
+        (results, throughput)
+    }
+
+    /// Totals across `embed_batch` calls since startup
+    pub fn throughput_stats(&self) -> EmbeddingThroughputStats {
+        self.throughput.lock().unwrap().clone()
+    }
+
+    async fn request_with_retry(&self, texts: &[String], run: &BatchRun) -> Result<Vec<Vec<f32>>> {
+        let mut attempt = 0;
+        loop {
+            run.pacer.wait().await;
+            run.requests.fetch_add(1, Ordering::Relaxed);
+            match self.request_batch(texts, run).await {
+                Ok(embeddings) => return Ok(embeddings),
+                Err(error) if error.is_throttled() && attempt < MAX_RETRIES => {
+                    let delay = error
+                        .retry_after
+                        .unwrap_or_else(|| BASE_BACKOFF * 2u32.pow(attempt));
+                    run.retries.fetch_add(1, Ordering::Relaxed);
+                    tokio::time::sleep(delay.min(MAX_BACKOFF)).await;
+                    attempt += 1;
+                }
+                Err(error) => return Err(AppError::Internal(error.message)),
+            }
+        }
+    }
+
+    async fn request_batch(
+        &self,
+        texts: &[String],
+        run: &BatchRun,
+    ) -> std::result::Result<Vec<Vec<f32>>, RequestError> {
+        let config = &run.config;
+        let embeddings = match config.provider {
+            LLMProvider::Local => {
+                let local_embedder = self.local_embedder.clone();
+                let model_store = self.model_store.clone();
+                let providers = self.execution_providers.lock().unwrap().clone();
+                let model = config.model.clone();
+                let texts = texts.to_vec();
+                let batch_size = run.batch_size;
+                tokio::task::spawn_blocking(move || {
+                    Self::embed_locally(
+                        &local_embedder,
+                        model_store.as_deref(),
+                        &providers,
+                        &model,
+                        texts,
+                        Some(batch_size),
+                    )
+                })
+                .await
+                .map_err(|e| RequestError::new(format!("Local embedding task failed: {}", e)))??
+            }
+            LLMProvider::OpenAI | LLMProvider::OpenRouter => {
+                self.request_openai_batch(texts, config).await?
+            }
+            LLMProvider::Gemini => self.request_gemini_batch(texts, config).await?,
+            _ => self.request_ollama_batch(texts, run).await?,
+        };
+
+        if embeddings.len() != texts.len() {
+            return Err(RequestError::new(format!(
+                "Embedding API returned {} embeddings for {} texts",
+                embeddings.len(),
+                texts.len()
+            )));
+        }
+        if embeddings.iter().any(|embedding| embedding.is_empty()) {
+            return Err(RequestError::new("Empty embedding response".to_string()));
+        }
+        Ok(embeddings)
+    }
+
+    async fn request_openai_batch(
+        &self,
+        texts: &[String],
+        config: &LLMConfig,
+    ) -> std::result::Result<Vec<Vec<f32>>, RequestError> {
+        let url = Self::openai_embeddings_url(&config.base_url);
+        let mut request = self.client.post(&url).json(&OpenAIBatchEmbeddingRequest {
+            model: &config.model,
+            input: texts,
+        });
+        if let Some(api_key) = &config.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response: OpenAIBatchEmbeddingResponse = Self::send_batch(request, &url).await?;
+        let mut data = response.data;
+        data.sort_by_key(|item| item.index);
+        Ok(data.into_iter().map(|item| item.embedding).collect())
+    }
+
+    async fn request_gemini_batch(
+        &self,
+        texts: &[String],
+        config: &LLMConfig,
+    ) -> std::result::Result<Vec<Vec<f32>>, RequestError> {
+        let api_key = config
+            .api_key
+            .clone()
+            .ok_or_else(|| RequestError::new("Missing API key for Google provider".to_string()))?;
+        let model_id = Self::resolve_gemini_embedding_model(&config.model);
+        let url = format!(
+            "{}/{}:batchEmbedContents",
+            Self::gemini_models_url(&config.base_url),
+            model_id
+        );
+        let body = GeminiBatchEmbeddingRequest {
+            requests: texts
+                .iter()
+                .map(|text| GeminiBatchEmbeddingItem {
+                    model: format!("models/{}", model_id),
+                    content: GeminiEmbeddingContent {
+                        parts: vec![GeminiEmbeddingPart { text: text.clone() }],
+                    },
+                })
+                .collect(),
+        };
+
+        let request = self
+            .client
+            .post(&url)
+            .header("x-goog-api-key", api_key)
+            .json(&body);
+        let response: GeminiBatchEmbeddingResponse = Self::send_batch(request, &url).await?;
+        Ok(response
+            .embeddings
+            .into_iter()
+            .map(|embedding| embedding.values)
+            .collect())
+    }
+
+    /// `/api/embed` takes a list of inputs; servers without it get one
+    /// `/api/embeddings` request per text
+    async fn request_ollama_batch(
+        &self,
+        texts: &[String],
+        run: &BatchRun,
+    ) -> std::result::Result<Vec<Vec<f32>>, RequestError> {
+        let config = &run.config;
+        if !run.ollama_legacy.load(Ordering::Relaxed) {
+            let url = Self::ollama_api_url(&config.base_url, "embed");
+            let request = self.client.post(&url).json(&OllamaBatchEmbeddingRequest {
+                model: &config.model,
+                input: texts,
+            });
+            match Self::send_batch::<OllamaBatchEmbeddingResponse>(request, &url).await {
+                Ok(response) => return Ok(response.embeddings),
+                Err(error) if error.status == Some(StatusCode::NOT_FOUND) => {
+                    run.ollama_legacy.store(true, Ordering::Relaxed);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for text in texts {
+            embeddings.push(self.generate_ollama_embedding(text, config.clone()).await?);
+        }
+        Ok(embeddings)
+    }
+
+    async fn send_batch<T: DeserializeOwned>(
+        request: RequestBuilder,
+        url: &str,
+    ) -> std::result::Result<T, RequestError> {
+        let response = request
+            .header("Content-Type", "application/json")
+            .send()
+            .await
+            .map_err(|e| {
+                RequestError::new(format!("Failed to call embedding API ({}): {}", url, e))
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .map(Duration::from_secs);
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(RequestError {
+                message: format!(
+                    "Embedding API returned error {} (URL: {}): {}",
+                    status,
+                    url,
+                    redact_secrets(&error_text)
+                ),
+                status: Some(status),
+                retry_after,
+            });
+        }
+
+        response
+            .json::<T>()
+            .await
+            .map_err(|e| RequestError::new(format!("Failed to parse embedding response: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options_clamped_to_provider_limits() {
+        let options = BatchEmbeddingOptions {
+            batch_size: 5000,
+            concurrency: 8,
+            requests_per_minute: None,
+        };
+
+        let local = ProviderLimits::for_provider(&LLMProvider::Local).apply(&options);
+        assert_eq!(local.batch_size, 256);
+        assert_eq!(local.concurrency, 1);
+        assert_eq!(local.requests_per_minute, None);
+
+        let gemini = ProviderLimits::for_provider(&LLMProvider::Gemini).apply(&options);
+        assert_eq!(gemini.batch_size, 100);
+        assert_eq!(gemini.requests_per_minute, Some(1500));
+
+        let configured = BatchEmbeddingOptions {
+            batch_size: 0,
+            concurrency: 0,
+            requests_per_minute: Some(60),
+        };
+        let openai = ProviderLimits::for_provider(&LLMProvider::OpenAI).apply(&configured);
+        assert_eq!(openai.batch_size, 1);
+        assert_eq!(openai.concurrency, 1);
+        assert_eq!(openai.requests_per_minute, Some(60));
+    }
+}
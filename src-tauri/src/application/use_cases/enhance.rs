@@ -1,3 +1,5 @@
+use crate::application::use_cases::output_language::{generate_in, OutputLanguage};
+use crate::domain::enhance_presets::EnhancePreset;
 use crate::domain::error::Result;
 use crate::domain::llm_config::LLMConfig;
 use crate::domain::prompt::Prompt;
@@ -6,6 +8,8 @@ use crate::infrastructure::llm_clients::LLMClient;
 use crate::infrastructure::response::clean_llm_response;
 use std::sync::Arc;
 
+const DEFAULT_SYSTEM_PROMPT: &str = "You are an expert prompt engineer. Improve the following prompt to be more precise, descriptive, and effective for large language models. Ensure clarity and remove ambiguity. Return ONLY the enhanced prompt. Do not include any explanations.";
+
 pub struct EnhanceUseCase {
     llm_client: Arc<dyn LLMClient + Send + Sync>,
     repository: Arc<SqliteRepository>,
@@ -27,16 +31,33 @@ impl EnhanceUseCase {
         config: &LLMConfig,
         content: String,
         custom_system_prompt: Option<String>,
+        output_language: Option<&OutputLanguage>,
     ) -> Result<Prompt> {
-        let default_prompt = "You are an expert prompt engineer. Improve the following prompt to be more precise, descriptive, and effective for large language models. Ensure clarity and remove ambiguity. Return ONLY the enhanced prompt. Do not include any explanations.";
+        self.execute_with_preset(config, content, custom_system_prompt, None, output_language)
+            .await
+    }
 
-        let system_prompt = custom_system_prompt.as_deref().unwrap_or(default_prompt);
+    /// Enhance with a preset's tone, length and format instructions added
+    /// to the system prompt (the custom one when given)
+    pub async fn execute_with_preset(
+        &self,
+        config: &LLMConfig,
+        content: String,
+        custom_system_prompt: Option<String>,
+        preset: Option<&EnhancePreset>,
+        output_language: Option<&OutputLanguage>,
+    ) -> Result<Prompt> {
+        let system_prompt = compose_system_prompt(custom_system_prompt.as_deref(), preset);
         let user_prompt = content.clone();
 
-        let raw_result = self
-            .llm_client
-            .generate(config, system_prompt, &user_prompt)
-            .await?;
+        let raw_result = generate_in(
+            output_language,
+            self.llm_client.as_ref(),
+            config,
+            &system_prompt,
+            &user_prompt,
+        )
+        .await?;
 
         let enhanced_text = clean_llm_response(&raw_result);
 
@@ -48,3 +69,20 @@ impl EnhanceUseCase {
         Ok(prompt)
     }
 }
+
+fn compose_system_prompt(custom: Option<&str>, preset: Option<&EnhancePreset>) -> String {
+    let base = custom
+        .map(str::trim)
+        .filter(|prompt| !prompt.is_empty())
+        .unwrap_or(DEFAULT_SYSTEM_PROMPT);
+    let instructions = preset.map(EnhancePreset::instructions).unwrap_or_default();
+    if instructions.is_empty() {
+        return base.to_string();
+    }
+    let mut prompt = format!("{}\n\nAlso follow these instructions:", base);
+    for line in instructions {
+        prompt.push_str("\n- ");
+        prompt.push_str(&line);
+    }
+    prompt
+}
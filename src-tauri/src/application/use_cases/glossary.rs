@@ -0,0 +1,237 @@
+//! Translation glossary enforcement
+//!
+//! Glossary rules are injected into the translation system prompt and the
+//! model output is checked afterwards:
+//! - Mapped terms must be rendered with their required target term
+//! - Do-not-translate terms must survive verbatim
+//! - Mapped terms the model left untranslated can be auto-corrected
+
+use crate::domain::glossary::{GlossaryEntry, GlossaryViolation, GlossaryViolationKind};
+use regex::Regex;
+
+const AUTO_DETECT: &str = "Auto Detect";
+
+/// Select the glossary entries that apply to a source/target language pair.
+pub fn entries_for_pair<'a>(
+    entries: &'a [GlossaryEntry],
+    source: &str,
+    target: &str,
+) -> Vec<&'a GlossaryEntry> {
+    entries
+        .iter()
+        .filter(|entry| lang_matches(entry.source_lang.as_deref(), source))
+        .filter(|entry| lang_matches(entry.target_lang.as_deref(), target))
+        .collect()
+}
+
+/// Keep only entries whose source term actually occurs in the input text.
+pub fn entries_in_text<'a>(entries: Vec<&'a GlossaryEntry>, text: &str) -> Vec<&'a GlossaryEntry> {
+    entries
+        .into_iter()
+        .filter(|entry| contains_term(text, &entry.source_term, entry.case_sensitive))
+        .collect()
+}
+
+/// Render the glossary section appended to the translation system prompt.
+pub fn build_prompt_section(entries: &[&GlossaryEntry]) -> Option<String> {
+    if entries.is_empty() {
+        return None;
+    }
+
+    let mut mapped = Vec::new();
+    let mut protected = Vec::new();
+    for entry in entries {
+        if entry.do_not_translate {
+            protected.push(format!("- \"{}\"", entry.source_term));
+        } else if let Some(target) = entry.target_term.as_deref() {
+            mapped.push(format!("- \"{}\" => \"{}\"", entry.source_term, target));
+        }
+    }
+
+    let mut section = String::new();
+    if !mapped.is_empty() {
+        section.push_str("\n\nGlossary (you MUST translate these terms exactly as given):\n");
+        section.push_str(&mapped.join("\n"));
+    }
+    if !protected.is_empty() {
+        section.push_str("\n\nDo NOT translate these terms; keep them exactly as written:\n");
+        section.push_str(&protected.join("\n"));
+    }
+
+    if section.is_empty() {
+        None
+    } else {
+        Some(section)
+    }
+}
+
+/// Check a translation against the glossary, optionally rewriting mapped terms
+/// the model left untranslated. Returns the (possibly corrected) output.
+pub fn enforce(
+    entries: &[&GlossaryEntry],
+    output: &str,
+    auto_correct: bool,
+) -> (String, Vec<GlossaryViolation>) {
+    let mut text = output.to_string();
+    let mut violations = Vec::new();
+
+    for entry in entries {
+        if entry.do_not_translate {
+            if !contains_term(&text, &entry.source_term, entry.case_sensitive) {
+                violations.push(GlossaryViolation {
+                    entry_id: entry.id,
+                    source_term: entry.source_term.clone(),
+                    expected: entry.source_term.clone(),
+                    kind: GlossaryViolationKind::ProtectedTermAltered,
+                    corrected: false,
+                });
+            }
+            continue;
+        }
+
+        let Some(target) = entry.target_term.as_deref() else {
+            continue;
+        };
+        if contains_term(&text, target, entry.case_sensitive) {
+            continue;
+        }
+
+        let mut corrected = false;
+        if auto_correct {
+            if let Some(re) = term_regex(&entry.source_term, entry.case_sensitive) {
+                if re.is_match(&text) {
+                    text = re.replace_all(&text, regex::NoExpand(target)).into_owned();
+                    corrected = true;
+                }
+            }
+        }
+
+        violations.push(GlossaryViolation {
+            entry_id: entry.id,
+            source_term: entry.source_term.clone(),
+            expected: target.to_string(),
+            kind: GlossaryViolationKind::MissingTargetTerm,
+            corrected,
+        });
+    }
+
+    (text, violations)
+}
+
+fn lang_matches(rule: Option<&str>, actual: &str) -> bool {
+    match rule.map(str::trim).filter(|value| !value.is_empty()) {
+        None => true,
+        Some(_) if actual == AUTO_DETECT => true,
+        Some(lang) => lang.eq_ignore_ascii_case(actual.trim()),
+    }
+}
+
+fn contains_term(text: &str, term: &str, case_sensitive: bool) -> bool {
+    term_regex(term, case_sensitive)
+        .map(|re| re.is_match(text))
+        .unwrap_or(false)
+}
+
+fn term_regex(term: &str, case_sensitive: bool) -> Option<Regex> {
+    let term = term.trim();
+    if term.is_empty() {
+        return None;
+    }
+
+    // Only anchor on word boundaries where the term itself starts/ends with a
+    // word character, so terms like "C++" or ".NET" still match.
+    let starts_word = term.chars().next().map(is_word_char).unwrap_or(false);
+    let ends_word = term.chars().last().map(is_word_char).unwrap_or(false);
+    let pattern = format!(
+        "{}{}{}{}",
+        if case_sensitive { "" } else { "(?i)" },
+        if starts_word { r"\b" } else { "" },
+        regex::escape(term),
+        if ends_word { r"\b" } else { "" },
+    );
+    Regex::new(&pattern).ok()
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapped(source: &str, target: &str) -> GlossaryEntry {
+        GlossaryEntry {
+            id: Some(1),
+            source_term: source.to_string(),
+            target_term: Some(target.to_string()),
+            source_lang: None,
+            target_lang: Some("Indonesian".to_string()),
+            do_not_translate: false,
+            case_sensitive: false,
+            notes: None,
+            created_at: None,
+        }
+    }
+
+    fn protected(term: &str) -> GlossaryEntry {
+        GlossaryEntry {
+            id: Some(2),
+            source_term: term.to_string(),
+            target_term: None,
+            source_lang: None,
+            target_lang: None,
+            do_not_translate: true,
+            case_sensitive: true,
+            notes: None,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn test_entries_for_pair_filters_by_language() {
+        let entries = vec![mapped("invoice", "faktur"), protected("Gadogado")];
+        assert_eq!(entries_for_pair(&entries, "English", "Indonesian").len(), 2);
+        assert_eq!(entries_for_pair(&entries, "English", "French").len(), 1);
+    }
+
+    #[test]
+    fn test_build_prompt_section() {
+        let entries = vec![mapped("invoice", "faktur"), protected("Gadogado")];
+        let refs: Vec<&GlossaryEntry> = entries.iter().collect();
+        let section = build_prompt_section(&refs).unwrap();
+        assert!(section.contains("\"invoice\" => \"faktur\""));
+        assert!(section.contains("\"Gadogado\""));
+        assert!(build_prompt_section(&[]).is_none());
+    }
+
+    #[test]
+    fn test_enforce_flags_and_corrects_mapped_terms() {
+        let entry = mapped("invoice", "faktur");
+        let (text, violations) = enforce(&[&entry], "Kirim invoice ini", true);
+        assert_eq!(text, "Kirim faktur ini");
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].corrected);
+
+        let (text, violations) = enforce(&[&entry], "Kirim invoice ini", false);
+        assert_eq!(text, "Kirim invoice ini");
+        assert!(!violations[0].corrected);
+    }
+
+    #[test]
+    fn test_enforce_protected_terms() {
+        let entry = protected("Gadogado");
+        let (_, violations) = enforce(&[&entry], "Gadogado adalah aplikasi", true);
+        assert!(violations.is_empty());
+
+        let (_, violations) = enforce(&[&entry], "Gado-gado adalah aplikasi", true);
+        assert_eq!(violations[0].kind, GlossaryViolationKind::ProtectedTermAltered);
+    }
+
+    #[test]
+    fn test_term_matching_respects_word_boundaries() {
+        assert!(contains_term("use C++ daily", "C++", true));
+        assert!(!contains_term("invoices", "invoice", false));
+        assert!(contains_term("INVOICE", "invoice", false));
+    }
+}
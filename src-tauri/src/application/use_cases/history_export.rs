@@ -0,0 +1,101 @@
+//! Prompt history export
+//!
+//! Serializes translation/enhancement history to CSV or JSON so it can be
+//! saved through the file dialog.
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::prompt::Prompt;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryExportFormat {
+    Csv,
+    Json,
+}
+
+pub fn render_history(prompts: &[Prompt], format: HistoryExportFormat) -> Result<String> {
+    match format {
+        HistoryExportFormat::Json => serde_json::to_string_pretty(prompts)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize history: {}", e))),
+        HistoryExportFormat::Csv => render_csv(prompts),
+    }
+}
+
+fn render_csv(prompts: &[Prompt]) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record([
+            "id",
+            "created_at",
+            "source_lang",
+            "target_lang",
+            "detected_lang",
+            "favorite",
+            "tags",
+            "content",
+            "result",
+        ])
+        .map_err(|e| AppError::Internal(format!("Failed to write CSV header: {}", e)))?;
+
+    for prompt in prompts {
+        writer
+            .write_record([
+                prompt.id.map(|id| id.to_string()).unwrap_or_default(),
+                prompt
+                    .created_at
+                    .map(|ts| ts.to_rfc3339())
+                    .unwrap_or_default(),
+                prompt.source_lang.clone(),
+                prompt.target_lang.clone(),
+                prompt.detected_lang.clone().unwrap_or_default(),
+                prompt.is_favorite.to_string(),
+                prompt.tags.join(";"),
+                prompt.content.clone(),
+                prompt.result.clone().unwrap_or_default(),
+            ])
+            .map_err(|e| AppError::Internal(format!("Failed to write CSV row: {}", e)))?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| AppError::Internal(format!("Failed to finish CSV export: {}", e)))?;
+    String::from_utf8(bytes).map_err(|e| AppError::Internal(format!("Invalid CSV output: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Prompt {
+        let mut prompt = Prompt::new(
+            "Hello, \"world\"".to_string(),
+            "English".to_string(),
+            "Indonesian".to_string(),
+        );
+        prompt.id = Some(7);
+        prompt.result = Some("Halo, dunia".to_string());
+        prompt.tags = vec!["greeting".to_string(), "demo".to_string()];
+        prompt.is_favorite = true;
+        prompt
+    }
+
+    #[test]
+    fn test_render_csv_escapes_fields() {
+        let csv = render_history(&[sample()], HistoryExportFormat::Csv).unwrap();
+        let mut lines = csv.lines();
+        assert!(lines.next().unwrap().starts_with("id,created_at"));
+        let row = lines.next().unwrap();
+        assert!(row.starts_with("7,"));
+        assert!(row.contains("greeting;demo"));
+        assert!(row.contains("\"Hello, \"\"world\"\"\""));
+    }
+
+    #[test]
+    fn test_render_json_roundtrip() {
+        let json = render_history(&[sample()], HistoryExportFormat::Json).unwrap();
+        let parsed: Vec<Prompt> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[0].id, Some(7));
+        assert!(parsed[0].is_favorite);
+    }
+}
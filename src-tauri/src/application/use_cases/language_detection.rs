@@ -0,0 +1,76 @@
+//! Source language detection for translation
+//!
+//! Wraps `whatlang` so "Auto Detect" resolves to a concrete language before
+//! the prompt is built. Short or ambiguous inputs fall back to letting the
+//! LLM detect the language itself.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Label the UI sends when the user has not picked a source language.
+pub const AUTO_DETECT: &str = "Auto Detect";
+
+/// Minimum confidence before a detection is trusted over the LLM fallback.
+const MIN_CONFIDENCE: f64 = 0.5;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DetectedLanguage {
+    /// ISO 639-3 code, e.g. `eng`.
+    pub code: String,
+    /// English name matching the UI language labels, e.g. `English`.
+    pub name: String,
+    pub confidence: f64,
+    pub reliable: bool,
+}
+
+pub fn detect_language(text: &str) -> Option<DetectedLanguage> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    let info = whatlang::detect(text)?;
+    Some(DetectedLanguage {
+        code: info.lang().code().to_string(),
+        name: info.lang().eng_name().to_string(),
+        confidence: info.confidence(),
+        reliable: info.is_reliable() || info.confidence() >= MIN_CONFIDENCE,
+    })
+}
+
+pub fn is_auto_detect(source: &str) -> bool {
+    source.trim().is_empty() || source.trim().eq_ignore_ascii_case(AUTO_DETECT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_common_languages() {
+        let english = detect_language(
+            "The quick brown fox jumps over the lazy dog while the children watch from the porch.",
+        )
+        .unwrap();
+        assert_eq!(english.code, "eng");
+        assert_eq!(english.name, "English");
+
+        let indonesian = detect_language(
+            "Saya sedang belajar bahasa pemrograman dan ingin membuat aplikasi yang berguna untuk banyak orang.",
+        )
+        .unwrap();
+        assert_eq!(indonesian.code, "ind");
+    }
+
+    #[test]
+    fn test_empty_input_is_not_detected() {
+        assert!(detect_language("   ").is_none());
+    }
+
+    #[test]
+    fn test_is_auto_detect() {
+        assert!(is_auto_detect("Auto Detect"));
+        assert!(is_auto_detect(""));
+        assert!(!is_auto_detect("English"));
+    }
+}
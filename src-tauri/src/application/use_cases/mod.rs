@@ -1,28 +1,44 @@
 #![allow(dead_code)]
 // TODO: tighten this allow once all use-cases are wired to commands/tests.
 
+pub mod activity;
 pub mod allowlist_validator;
 pub mod audit_service;
 pub mod chunking;
+pub mod code_chunking;
+pub mod collection_encryption;
+pub mod collection_transfer;
 pub mod context_manager;
 pub mod conversation_service;
 pub mod csv_preprocessor;
 pub mod data_protection;
 pub mod db_connection_manager;
+pub mod email_parsing;
 pub mod embedding_service;
 pub mod enhance;
 pub mod few_shot_prompt_builder;
+pub mod glossary;
+pub mod history_export;
+pub mod language_detection;
+pub mod output_language;
+pub mod pii_detector;
 pub mod prompt_engine;
 pub mod qa_ai;
 pub mod query_intent_enricher;
 pub mod qa_api_call;
+pub mod qa_auth;
 pub mod qa_event;
+pub mod qa_redaction;
 pub mod qa_run;
 pub mod qa_session;
+pub mod qa_test_management;
 pub mod rag_analytics;
+pub mod rag_answer_cache;
 pub mod rag_config;
 pub mod rag_ingestion;
 pub mod rag_metrics;
+pub mod rag_pruning;
+pub mod rag_tuning;
 pub mod rag_validation;
 pub mod rate_limiter;
 pub mod reranker_service;
@@ -32,7 +48,9 @@ pub mod sql_compiler;
 pub mod sql_rag_router;
 pub mod structured_row_schema;
 pub mod table_matcher;
+pub mod telemetry;
 pub mod template_matcher;
 pub mod translate;
+pub mod translation_memory;
 pub mod typegen;
 pub mod web_crawler;
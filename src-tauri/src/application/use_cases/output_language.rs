@@ -0,0 +1,261 @@
+//! Output language enforcement for the AI features
+//!
+//! Resolves the language an answer must be written in from the request and
+//! the `outputLanguage` settings, tells the model, and checks the answer with
+//! `whatlang`. An answer detected in another language is asked for again.
+//! Languages `whatlang` doesn't know are still requested but never checked.
+
+use crate::application::use_cases::language_detection::detect_language;
+use crate::domain::error::Result;
+use crate::domain::llm_config::LLMConfig;
+use crate::domain::settings::OutputLanguageSettings;
+use crate::infrastructure::llm_clients::LLMClient;
+use serde_json::Value;
+use tracing::warn;
+
+/// Answers shorter than this are not checked; detection guesses on them
+const MIN_CHECKED_CHARS: usize = 40;
+
+/// Two-letter codes and other names people use for a language
+const ALIASES: &[(&str, &str)] = &[
+    ("en", "eng"),
+    ("id", "ind"),
+    ("indonesia", "ind"),
+    ("bahasa", "ind"),
+    ("bahasa indonesia", "ind"),
+    ("es", "spa"),
+    ("fr", "fra"),
+    ("de", "deu"),
+    ("pt", "por"),
+    ("it", "ita"),
+    ("nl", "nld"),
+    ("ru", "rus"),
+    ("ja", "jpn"),
+    ("ko", "kor"),
+    ("zh", "cmn"),
+    ("chinese", "cmn"),
+    ("ar", "ara"),
+    ("hi", "hin"),
+    ("th", "tha"),
+    ("vi", "vie"),
+    ("tr", "tur"),
+    ("pl", "pol"),
+    ("jv", "jav"),
+];
+
+/// The language one answer has to be in
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputLanguage {
+    /// English name, e.g. `Indonesian`
+    pub language: String,
+    pub verify: bool,
+    pub max_retries: u32,
+}
+
+impl OutputLanguage {
+    /// `requested` wins over the configured language; `None` when neither is set
+    pub fn resolve(settings: &OutputLanguageSettings, requested: Option<&str>) -> Option<Self> {
+        let language = [requested, settings.language.as_deref()]
+            .into_iter()
+            .flatten()
+            .map(str::trim)
+            .find(|language| !language.is_empty())?;
+        Some(Self {
+            language: canonical_language(language),
+            verify: settings.verify,
+            max_retries: settings.max_retries,
+        })
+    }
+
+    pub fn instruction(&self) -> String {
+        format!(
+            "Write your entire answer in {}, whatever language the input or context is in. \
+             Keep code, identifiers, URLs and quoted text unchanged.",
+            self.language
+        )
+    }
+
+    /// `system` with the language instruction appended
+    pub fn system_prompt(&self, system: &str) -> String {
+        format!("{}\n\n{}", system.trim_end(), self.instruction())
+    }
+
+    /// The language `answer` was detected in when it isn't the requested one.
+    /// JSON answers are judged by their string values.
+    pub fn mismatch(&self, answer: &str) -> Option<String> {
+        if !self.verify {
+            return None;
+        }
+        let expected = whatlang::Lang::all()
+            .iter()
+            .find(|lang| lang.eng_name().eq_ignore_ascii_case(&self.language))?;
+        let prose = answer_prose(answer);
+        if prose.chars().count() < MIN_CHECKED_CHARS {
+            return None;
+        }
+        let detected = detect_language(&prose).filter(|detected| detected.reliable)?;
+        (detected.code != expected.code()).then_some(detected.name)
+    }
+
+    /// `user` again, telling the model what went wrong with its last answer
+    pub fn retry_prompt(&self, user: &str, detected: &str) -> String {
+        format!(
+            "{}\n\nYour previous answer was written in {} instead of {}. \
+             Answer again, entirely in {}.",
+            user, detected, self.language, self.language
+        )
+    }
+
+    /// Generates with the language instruction and retries wrong-language answers
+    pub async fn generate(
+        &self,
+        client: &(dyn LLMClient + Send + Sync),
+        config: &LLMConfig,
+        system: &str,
+        user: &str,
+    ) -> Result<String> {
+        let system = self.system_prompt(system);
+        let answer = client.generate(config, &system, user).await?;
+        self.enforce(client, config, &system, user, answer).await
+    }
+
+    /// Retries `answer` while it is in the wrong language and attempts are
+    /// left; the last answer is kept when they run out. `system` should
+    /// already carry the instruction.
+    pub async fn enforce(
+        &self,
+        client: &(dyn LLMClient + Send + Sync),
+        config: &LLMConfig,
+        system: &str,
+        user: &str,
+        mut answer: String,
+    ) -> Result<String> {
+        for _ in 0..self.max_retries {
+            let Some(detected) = self.mismatch(&answer) else {
+                return Ok(answer);
+            };
+            answer = client
+                .generate(config, system, &self.retry_prompt(user, &detected))
+                .await?;
+        }
+        if let Some(detected) = self.mismatch(&answer) {
+            warn!(
+                expected = %self.language,
+                detected = %detected,
+                "Answer is still in the wrong language"
+            );
+        }
+        Ok(answer)
+    }
+}
+
+/// `client.generate`, held to `language` when one is set
+pub async fn generate_in(
+    language: Option<&OutputLanguage>,
+    client: &(dyn LLMClient + Send + Sync),
+    config: &LLMConfig,
+    system: &str,
+    user: &str,
+) -> Result<String> {
+    match language {
+        Some(language) => language.generate(client, config, system, user).await,
+        None => client.generate(config, system, user).await,
+    }
+}
+
+/// The English name of a language given by name or ISO code, e.g. `id` or
+/// `indonesia` -> `Indonesian`; unknown languages are returned as given
+pub fn canonical_language(value: &str) -> String {
+    let value = value.trim();
+    let lower = value.to_lowercase();
+    let code = ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == lower)
+        .map_or(lower.as_str(), |(_, code)| code);
+    whatlang::Lang::from_code(code)
+        .or_else(|| {
+            whatlang::Lang::all()
+                .iter()
+                .copied()
+                .find(|lang| lang.eng_name().eq_ignore_ascii_case(value))
+        })
+        .map_or_else(|| value.to_string(), |lang| lang.eng_name().to_string())
+}
+
+/// The text of an answer to detect the language of: the string values of a
+/// JSON answer, the answer itself otherwise
+fn answer_prose(answer: &str) -> String {
+    let trimmed = answer.trim();
+    let json = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .map(|rest| rest.trim_end_matches("```"))
+        .unwrap_or(trimmed);
+    match serde_json::from_str::<Value>(json) {
+        Ok(value @ (Value::Object(_) | Value::Array(_))) => {
+            let mut strings = Vec::new();
+            collect_strings(&value, &mut strings);
+            strings.join("\n")
+        }
+        _ => trimmed.to_string(),
+    }
+}
+
+fn collect_strings<'a>(value: &'a Value, out: &mut Vec<&'a str>) {
+    match value {
+        // Single words are mostly enum values and keys like `high` or `click`
+        Value::String(text) if text.split_whitespace().count() > 2 => out.push(text),
+        Value::Array(items) => items.iter().for_each(|item| collect_strings(item, out)),
+        Value::Object(map) => map.values().for_each(|item| collect_strings(item, out)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indonesian() -> OutputLanguage {
+        OutputLanguage::resolve(&OutputLanguageSettings::default(), Some("id")).unwrap()
+    }
+
+    #[test]
+    fn requested_language_overrides_the_setting() {
+        let settings = OutputLanguageSettings {
+            language: Some("English".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(indonesian().language, "Indonesian");
+        assert_eq!(
+            OutputLanguage::resolve(&settings, Some("  "))
+                .unwrap()
+                .language,
+            "English"
+        );
+        assert_eq!(canonical_language("indonesia"), "Indonesian");
+        assert_eq!(canonical_language("Klingon"), "Klingon");
+        assert!(OutputLanguage::resolve(&OutputLanguageSettings::default(), None).is_none());
+    }
+
+    #[test]
+    fn answers_in_another_language_are_detected() {
+        let language = indonesian();
+        assert_eq!(
+            language
+                .mismatch(
+                    "The checkout page accepted the order after the user filled in the \
+                     shipping address and confirmed the payment."
+                )
+                .as_deref(),
+            Some("English")
+        );
+        assert_eq!(
+            language.mismatch(
+                r#"{"summaryText":"Pengguna mengisi alamat pengiriman lalu mengonfirmasi pembayaran dan pesanan berhasil dibuat.","risks":["high"]}"#
+            ),
+            None
+        );
+        // Too short to judge
+        assert_eq!(language.mismatch("Okay, done."), None);
+    }
+}
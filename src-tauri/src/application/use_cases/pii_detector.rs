@@ -0,0 +1,307 @@
+//! PII detection for ingested text.
+//!
+//! Finds email addresses, phone numbers, national IDs (Indonesian NIK and
+//! US SSN) and payment card numbers so ingestion can redact them or tag the
+//! chunk as sensitive. Card numbers must pass the Luhn check and a 16-digit
+//! NIK must carry a plausible province code and birth date, which keeps
+//! invoice numbers and other long digit runs from being flagged.
+
+use crate::application::use_cases::rag_config::PiiConfig;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+static EMAIL_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b[A-Z0-9._%+\-]+@[A-Z0-9.\-]+\.[A-Z]{2,}\b").unwrap());
+
+/// 13-19 digits, optionally grouped with spaces or dashes
+static CARD_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b\d(?:[ \-]?\d){12,18}\b").unwrap());
+
+static NIK_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b\d{16}\b").unwrap());
+
+static SSN_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b\d{3}-\d{2}-\d{4}\b").unwrap());
+
+/// International (`+62 ...`), trunk-prefixed (`0812-...`) or area-code
+/// (`(021) ...`) numbers; the digit count is checked separately
+static PHONE_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:\+\d{1,3}[ .\-]?|\(0?\d{1,4}\)[ .\-]?|\b0)\d[\d .\-]{6,16}\d\b").unwrap()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiKind {
+    Email,
+    PhoneNumber,
+    NationalId,
+    CreditCard,
+}
+
+impl PiiKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PiiKind::Email => "email",
+            PiiKind::PhoneNumber => "phone_number",
+            PiiKind::NationalId => "national_id",
+            PiiKind::CreditCard => "credit_card",
+        }
+    }
+
+    /// Replacement text used when redacting
+    pub fn placeholder(&self) -> &'static str {
+        match self {
+            PiiKind::Email => "[EMAIL]",
+            PiiKind::PhoneNumber => "[PHONE]",
+            PiiKind::NationalId => "[NATIONAL_ID]",
+            PiiKind::CreditCard => "[CREDIT_CARD]",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiiMatch {
+    pub kind: PiiKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+pub struct PiiDetector {
+    kinds: Vec<PiiKind>,
+}
+
+impl PiiDetector {
+    pub fn new(kinds: Vec<PiiKind>) -> Self {
+        Self { kinds }
+    }
+
+    /// Detector for the categories enabled in `config`
+    pub fn from_config(config: &PiiConfig) -> Self {
+        let kinds = [
+            (config.detect_emails, PiiKind::Email),
+            (config.detect_credit_cards, PiiKind::CreditCard),
+            (config.detect_national_ids, PiiKind::NationalId),
+            (config.detect_phone_numbers, PiiKind::PhoneNumber),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, kind)| enabled.then_some(kind))
+        .collect();
+        Self::new(kinds)
+    }
+
+    /// Non-overlapping matches in text order. Emails win over digit
+    /// patterns, and card and ID numbers over phone numbers.
+    pub fn detect(&self, text: &str) -> Vec<PiiMatch> {
+        let mut matches: Vec<PiiMatch> = Vec::new();
+        let mut push = |kind: PiiKind, start: usize, end: usize| {
+            if !matches.iter().any(|m| start < m.end && m.start < end) {
+                matches.push(PiiMatch { kind, start, end });
+            }
+        };
+
+        if self.enabled(PiiKind::Email) {
+            for m in EMAIL_PATTERN.find_iter(text) {
+                push(PiiKind::Email, m.start(), m.end());
+            }
+        }
+        if self.enabled(PiiKind::NationalId) {
+            for m in NIK_PATTERN.find_iter(text) {
+                if is_plausible_nik(m.as_str()) {
+                    push(PiiKind::NationalId, m.start(), m.end());
+                }
+            }
+            for m in SSN_PATTERN.find_iter(text) {
+                push(PiiKind::NationalId, m.start(), m.end());
+            }
+        }
+        if self.enabled(PiiKind::CreditCard) {
+            for m in CARD_PATTERN.find_iter(text) {
+                if passes_luhn(m.as_str()) {
+                    push(PiiKind::CreditCard, m.start(), m.end());
+                }
+            }
+        }
+        if self.enabled(PiiKind::PhoneNumber) {
+            for m in PHONE_PATTERN.find_iter(text) {
+                let digits = m.as_str().chars().filter(char::is_ascii_digit).count();
+                if (9..=15).contains(&digits) {
+                    push(PiiKind::PhoneNumber, m.start(), m.end());
+                }
+            }
+        }
+
+        matches.sort_by_key(|m| m.start);
+        matches
+    }
+
+    /// Distinct kinds found in `text`, in a stable order
+    pub fn kinds_in(&self, text: &str) -> Vec<PiiKind> {
+        let found = self.detect(text);
+        self.kinds
+            .iter()
+            .copied()
+            .filter(|kind| found.iter().any(|m| m.kind == *kind))
+            .collect()
+    }
+
+    /// `text` with every match replaced by its placeholder, plus the kinds
+    /// that were replaced
+    pub fn redact(&self, text: &str) -> (String, Vec<PiiKind>) {
+        let found = self.detect(text);
+        let mut redacted = String::with_capacity(text.len());
+        let mut last = 0;
+        for m in &found {
+            redacted.push_str(&text[last..m.start]);
+            redacted.push_str(m.kind.placeholder());
+            last = m.end;
+        }
+        redacted.push_str(&text[last..]);
+
+        let kinds = self
+            .kinds
+            .iter()
+            .copied()
+            .filter(|kind| found.iter().any(|m| m.kind == *kind))
+            .collect();
+        (redacted, kinds)
+    }
+
+    fn enabled(&self, kind: PiiKind) -> bool {
+        self.kinds.contains(&kind)
+    }
+}
+
+/// The ingestion stage built from `PiiConfig`: redacts matches or keeps
+/// them and marks the chunk sensitive
+pub struct PiiStage {
+    detector: PiiDetector,
+    redact: bool,
+}
+
+/// What the stage did to one chunk
+pub struct ScreenedChunk {
+    pub content: String,
+    pub kinds: Vec<PiiKind>,
+    /// PII remains in `content`
+    pub sensitive: bool,
+}
+
+impl ScreenedChunk {
+    /// Comma-separated kinds for the `pii_types` column
+    pub fn pii_types(&self) -> String {
+        self.kinds
+            .iter()
+            .map(PiiKind::as_str)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+impl PiiStage {
+    /// `None` when the mode is "off" (or unknown)
+    pub fn from_config(config: &PiiConfig) -> Option<Self> {
+        let redact = match config.mode.as_str() {
+            "tag" => false,
+            "redact" => true,
+            _ => return None,
+        };
+        Some(Self {
+            detector: PiiDetector::from_config(config),
+            redact,
+        })
+    }
+
+    pub fn screen(&self, text: &str) -> ScreenedChunk {
+        if self.redact {
+            let (content, kinds) = self.detector.redact(text);
+            ScreenedChunk {
+                content,
+                kinds,
+                sensitive: false,
+            }
+        } else {
+            let kinds = self.detector.kinds_in(text);
+            ScreenedChunk {
+                content: text.to_string(),
+                sensitive: !kinds.is_empty(),
+                kinds,
+            }
+        }
+    }
+}
+
+fn passes_luhn(candidate: &str) -> bool {
+    let digits: Vec<u32> = candidate.chars().filter_map(|c| c.to_digit(10)).collect();
+    if !(13..=19).contains(&digits.len()) || digits.iter().all(|d| *d == digits[0]) {
+        return false;
+    }
+
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+/// NIK layout: 2-digit province (11-94), regency and district codes, then
+/// DDMMYY birth date with 40 added to the day for women
+fn is_plausible_nik(candidate: &str) -> bool {
+    let number = |range: std::ops::Range<usize>| candidate[range].parse::<u32>().unwrap_or(0);
+    let province = number(0..2);
+    let day = number(6..8);
+    let month = number(8..10);
+
+    (11..=94).contains(&province)
+        && ((1..=31).contains(&day) || (41..=71).contains(&day))
+        && (1..=12).contains(&month)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detector() -> PiiDetector {
+        PiiDetector::from_config(&PiiConfig::default())
+    }
+
+    #[test]
+    fn test_detects_each_kind() {
+        let text = "Contact budi.santoso@example.co.id or +62 812-3456-7890. \
+                    NIK 3174054508900001, card 4111 1111 1111 1111, SSN 123-45-6789.";
+        let kinds: Vec<PiiKind> = detector().detect(text).iter().map(|m| m.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                PiiKind::Email,
+                PiiKind::PhoneNumber,
+                PiiKind::NationalId,
+                PiiKind::CreditCard,
+                PiiKind::NationalId,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ignores_ordinary_numbers() {
+        let text = "Invoice 1234567890123456 totals 2,500,000 for 2024 (order 20240115).";
+        assert!(detector().detect(text).is_empty());
+    }
+
+    #[test]
+    fn test_redact_replaces_matches() {
+        let (redacted, kinds) = detector().redact("Call 0812 3456 7890 or mail a@b.io");
+        assert_eq!(redacted, "Call [PHONE] or mail [EMAIL]");
+        assert_eq!(kinds, vec![PiiKind::Email, PiiKind::PhoneNumber]);
+    }
+}
@@ -600,6 +600,7 @@ mod tests {
             page_number: Some(3),
             page_offset: Some(150),
             doc_name: Some("test.pdf".to_string()),
+            snippet: None,
         }];
 
         let prompt = PromptEngine::build_prompt("What is X?", &results).unwrap();
@@ -0,0 +1,41 @@
+//! API calls per action for the summary and test case prompts, from the
+//! links between `api_response` events and the clicks/submits that
+//! triggered them (see `domain::qa_api_link`).
+
+use super::QaAiUseCase;
+use crate::domain::error::Result;
+use crate::domain::qa_api_link::{group_by_action, link_api_responses, QaActionApiCalls};
+use crate::domain::qa_checkpoint::QaCheckpointSummary;
+use crate::domain::qa_event::QaEvent;
+
+impl QaAiUseCase {
+    /// The actions among `events` with the calls they triggered. Sessions
+    /// recorded before responses were linked are linked on the fly.
+    pub(super) async fn action_api_calls(
+        &self,
+        session_id: &str,
+        events: &[QaEvent],
+    ) -> Result<Vec<QaActionApiCalls>> {
+        let mut links = self.event_repository.list_api_links(session_id).await?;
+        if links.is_empty() {
+            links = link_api_responses(events);
+        }
+        Ok(group_by_action(events, &links))
+    }
+}
+
+/// Stored with the checkpoint summary; `None` when no action triggered a call
+pub(super) fn actions_json(actions: &[QaActionApiCalls]) -> Option<String> {
+    if actions.is_empty() {
+        return None;
+    }
+    serde_json::to_string(actions).ok()
+}
+
+/// The actions stored with `summary`, if it has them
+pub(super) fn summary_actions(summary: &QaCheckpointSummary) -> Option<Vec<QaActionApiCalls>> {
+    summary
+        .api_links_json
+        .as_deref()
+        .and_then(|json| serde_json::from_str(json).ok())
+}
@@ -0,0 +1,330 @@
+//! Assertion suggestions from repeated API calls. The successful responses
+//! of each endpoint called more than once are compared: values that held in
+//! all of them become equality assertions, values that changed are checked
+//! by type only and listed as changing so they are left out of equality.
+
+use super::grounding::endpoint_key;
+use super::QaAiUseCase;
+use crate::domain::error::{AppError, Result};
+use crate::domain::qa_api_call::QaApiCall;
+use crate::domain::qa_api_test::{JsonValueType, QaApiAssertion, QaEndpointAssertions};
+use crate::domain::qa_event::QaEvent;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Responses an endpoint needs before anything counts as stable
+const MIN_RESPONSES: usize = 2;
+const MAX_ENDPOINTS: usize = 20;
+const MAX_JSON_ASSERTIONS: usize = 25;
+/// Nesting below this depth is checked by type only
+const MAX_DEPTH: usize = 4;
+/// Longer strings are checked by type; they rarely stay the same for long
+const MAX_EQUALS_CHARS: usize = 80;
+const REDACTED: &str = "[REDACTED]";
+
+impl QaAiUseCase {
+    /// Suggestions for the endpoints the session called more than once.
+    /// With a checkpoint, only endpoints called during it are kept, though
+    /// their responses from the whole session are compared.
+    pub async fn suggest_api_assertions(
+        &self,
+        session_id: &str,
+        checkpoint_id: Option<&str>,
+    ) -> Result<Vec<QaEndpointAssertions>> {
+        let calls = self
+            .api_call_repository
+            .list_calls_by_session(session_id)
+            .await?;
+        let mut suggestions = suggest_assertions(&calls);
+        if let Some(checkpoint_id) = checkpoint_id {
+            let checkpoint = self
+                .checkpoint_repository
+                .get_checkpoint(checkpoint_id)
+                .await?;
+            if checkpoint.session_id != session_id {
+                return Err(AppError::ValidationError(
+                    "Checkpoint does not belong to session.".to_string(),
+                ));
+            }
+            let events = self
+                .event_repository
+                .list_events_range(
+                    session_id,
+                    checkpoint.start_event_seq,
+                    checkpoint.end_event_seq,
+                )
+                .await?;
+            retain_called_during(&mut suggestions, &calls_during(&calls, &events));
+        }
+        Ok(suggestions)
+    }
+
+    /// Suggestions for the endpoints called during `events`, serialized for
+    /// the test cases generated from them; `None` when there are none
+    pub(super) async fn assertions_json_for(
+        &self,
+        session_id: &str,
+        events: &[QaEvent],
+    ) -> Result<Option<String>> {
+        let calls = self
+            .api_call_repository
+            .list_calls_by_session(session_id)
+            .await?;
+        let mut suggestions = suggest_assertions(&calls);
+        retain_called_during(&mut suggestions, &calls_during(&calls, events));
+        if suggestions.is_empty() {
+            return Ok(None);
+        }
+        Ok(serde_json::to_string(&suggestions).ok())
+    }
+}
+
+/// API calls carry no event seq, so the events' time span picks them
+pub(super) fn calls_during(calls: &[QaApiCall], events: &[QaEvent]) -> Vec<QaApiCall> {
+    let first_ts = events.first().map(|e| e.ts).unwrap_or(0);
+    let last_ts = events.last().map(|e| e.ts).unwrap_or(0);
+    calls
+        .iter()
+        .filter(|call| call.created_at >= first_ts && call.created_at <= last_ts)
+        .cloned()
+        .collect()
+}
+
+fn retain_called_during(suggestions: &mut Vec<QaEndpointAssertions>, calls: &[QaApiCall]) {
+    let called: BTreeSet<(String, String)> = calls.iter().filter_map(endpoint_key).collect();
+    suggestions.retain(|suggestion| {
+        called.contains(&(suggestion.method.clone(), suggestion.path.clone()))
+    });
+}
+
+/// One suggestion per endpoint with at least two successful responses
+pub(crate) fn suggest_assertions(calls: &[QaApiCall]) -> Vec<QaEndpointAssertions> {
+    let mut grouped: BTreeMap<(String, String), Vec<&QaApiCall>> = BTreeMap::new();
+    for call in calls {
+        if !call
+            .response_status
+            .is_some_and(|status| (200..300).contains(&status))
+        {
+            continue;
+        }
+        if let Some(key) = endpoint_key(call) {
+            grouped.entry(key).or_default().push(call);
+        }
+    }
+
+    grouped
+        .into_iter()
+        .filter(|(_, calls)| calls.len() >= MIN_RESPONSES)
+        .take(MAX_ENDPOINTS)
+        .map(|((method, path), calls)| endpoint_assertions(method, path, &calls))
+        .collect()
+}
+
+fn endpoint_assertions(method: String, path: String, calls: &[&QaApiCall]) -> QaEndpointAssertions {
+    let mut assertions = Vec::new();
+
+    let statuses: BTreeSet<i64> = calls
+        .iter()
+        .filter_map(|call| call.response_status)
+        .collect();
+    if let [status] = statuses.into_iter().collect::<Vec<_>>()[..] {
+        if let Ok(status) = u16::try_from(status) {
+            assertions.push(QaApiAssertion::Status { equals: status });
+        }
+    }
+
+    let content_types: Option<BTreeSet<String>> = calls
+        .iter()
+        .map(|call| content_type(call.response_headers_json.as_deref()?))
+        .collect();
+    if let Some([media_type]) = content_types
+        .map(|types| types.into_iter().collect::<Vec<_>>())
+        .as_deref()
+    {
+        assertions.push(QaApiAssertion::Header {
+            name: "content-type".to_string(),
+            equals: None,
+            contains: Some(media_type.clone()),
+        });
+    }
+
+    // Bodies cut short when stored don't parse and are left out
+    let bodies: Vec<Value> = calls
+        .iter()
+        .filter_map(|call| serde_json::from_str(call.response_body_json.as_deref()?).ok())
+        .collect();
+    let mut changing_paths = Vec::new();
+    if bodies.len() >= MIN_RESPONSES {
+        let mut first = Vec::new();
+        flatten(&bodies[0], "$".to_string(), 0, &mut first);
+        let others: Vec<HashMap<String, &Value>> = bodies[1..]
+            .iter()
+            .map(|body| {
+                let mut leaves = Vec::new();
+                flatten(body, "$".to_string(), 0, &mut leaves);
+                leaves.into_iter().collect()
+            })
+            .collect();
+
+        let mut json_assertions = 0;
+        for (path, value) in first {
+            // Paths missing from some response are optional
+            let Some(values) = others
+                .iter()
+                .map(|leaves| leaves.get(&path).copied())
+                .collect::<Option<Vec<_>>>()
+            else {
+                continue;
+            };
+            let value_type = JsonValueType::of(value);
+            let same_type = values
+                .iter()
+                .all(|other| JsonValueType::of(other) == value_type);
+            let same_value = values.iter().all(|other| *other == value);
+            // A list that grew is no field to ignore
+            if !same_value && !value.is_array() {
+                changing_paths.push(path.clone());
+            }
+            if !same_type || json_assertions == MAX_JSON_ASSERTIONS {
+                continue;
+            }
+            assertions.push(QaApiAssertion::JsonPath {
+                equals: (same_value && comparable(value)).then(|| value.clone()),
+                value_type: (!same_value || !comparable(value)).then_some(value_type),
+                path,
+            });
+            json_assertions += 1;
+        }
+    }
+
+    QaEndpointAssertions {
+        method,
+        path,
+        call_count: calls.len(),
+        assertions,
+        changing_paths,
+    }
+}
+
+/// Leaf values by JSON path, in key order. Arrays are a leaf of their
+/// own and are followed into their first item; keys a path can't spell,
+/// such as ones with dots, are skipped.
+fn flatten<'a>(value: &'a Value, path: String, depth: usize, out: &mut Vec<(String, &'a Value)>) {
+    match value {
+        Value::Object(map) if depth < MAX_DEPTH => {
+            for (key, child) in map {
+                if key.is_empty() || key.contains(['.', '[', ']']) {
+                    continue;
+                }
+                flatten(child, format!("{}.{}", path, key), depth + 1, out);
+            }
+        }
+        Value::Array(items) => {
+            out.push((path.clone(), value));
+            if let Some(first) = items.first().filter(|_| depth < MAX_DEPTH) {
+                flatten(first, format!("{}[0]", path), depth + 1, out);
+            }
+        }
+        _ => out.push((path, value)),
+    }
+}
+
+/// Values worth an equality check; collections, long strings and masked
+/// secrets are checked by type
+fn comparable(value: &Value) -> bool {
+    match value {
+        Value::String(text) => text.chars().count() <= MAX_EQUALS_CHARS && !text.contains(REDACTED),
+        Value::Array(_) | Value::Object(_) => false,
+        _ => true,
+    }
+}
+
+/// The media type of the stored response headers, which are a JSON object
+/// or a list of name and value pairs
+fn content_type(headers_json: &str) -> Option<String> {
+    let headers: Value = serde_json::from_str(headers_json).ok()?;
+    let value = match &headers {
+        Value::Object(map) => map
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            .and_then(|(_, value)| value.as_str()),
+        Value::Array(pairs) => pairs.iter().find_map(|pair| {
+            let name = pair.get(0)?.as_str()?;
+            name.eq_ignore_ascii_case("content-type")
+                .then(|| pair.get(1)?.as_str())
+                .flatten()
+        }),
+        _ => None,
+    }?;
+    let media_type = value.split(';').next()?.trim().to_ascii_lowercase();
+    (!media_type.is_empty()).then_some(media_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(method: &str, url: &str, status: i64, body: &str) -> QaApiCall {
+        QaApiCall {
+            id: url.to_string(),
+            session_id: "session".to_string(),
+            run_id: "run".to_string(),
+            method: method.to_string(),
+            url: url.to_string(),
+            request_headers_json: None,
+            request_body_json: None,
+            request_body_hash: None,
+            response_status: Some(status),
+            response_headers_json: Some(
+                r#"{"content-type":"application/json; charset=utf-8"}"#.to_string(),
+            ),
+            response_body_hash: None,
+            response_body_json: Some(body.to_string()),
+            timing_ms: None,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn stable_values_are_asserted_and_changing_ones_typed() {
+        let suggestions = suggest_assertions(&[
+            call(
+                "GET",
+                "https://api.example.com/orders?page=1",
+                200,
+                r#"{"status":"paid","requestId":"a1","items":[{"sku":"TEA"}],"token":"[REDACTED]"}"#,
+            ),
+            call(
+                "get",
+                "https://api.example.com/orders?page=2",
+                200,
+                r#"{"status":"paid","requestId":"b2","items":[{"sku":"TEA"},{"sku":"MUG"}],"token":"[REDACTED]"}"#,
+            ),
+            call("GET", "https://api.example.com/orders", 500, "{}"),
+            call("GET", "https://api.example.com/me", 200, r#"{"id":1}"#),
+        ]);
+
+        assert_eq!(suggestions.len(), 1);
+        let orders = &suggestions[0];
+        assert_eq!(
+            (orders.method.as_str(), orders.path.as_str()),
+            ("GET", "/orders")
+        );
+        assert_eq!(orders.call_count, 2);
+        assert_eq!(orders.changing_paths, vec!["$.requestId"]);
+
+        let described: Vec<String> = orders.assertions.iter().map(|a| a.describe()).collect();
+        assert_eq!(
+            described,
+            vec![
+                "status = 200",
+                "header content-type contains application/json",
+                "$.items is array",
+                "$.items[0].sku = \"TEA\"",
+                "$.requestId is string",
+                "$.status = \"paid\"",
+                "$.token is string",
+            ]
+        );
+    }
+}
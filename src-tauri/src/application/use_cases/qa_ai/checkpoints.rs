@@ -1,7 +1,12 @@
 use super::QaAiUseCase;
 use crate::domain::error::{AppError, Result};
-use crate::domain::qa_checkpoint::{QaCheckpoint, QaCheckpointSummary, QaLlmRun, QaTestCase};
+use crate::domain::llm_config::LLMConfig;
+use crate::domain::qa_checkpoint::{
+    QaCheckpoint, QaCheckpointEdit, QaCheckpointRegeneration, QaCheckpointSummary, QaLlmRun,
+    QaLlmRunDetail, QaTestCase, QaTestGenerationMode,
+};
 use crate::domain::qa_event::{QaEvent, QaEventSummary};
+use uuid::Uuid;
 
 const IDLE_THRESHOLD_MS: i64 = 15000;
 
@@ -135,4 +140,194 @@ impl QaAiUseCase {
         }
         self.checkpoint_repository.list_llm_runs(session_id).await
     }
+
+    /// A run with its stored prompt and response bodies
+    pub async fn get_llm_run_detail(&self, run_id: &str) -> Result<QaLlmRunDetail> {
+        let run_id = run_id.trim();
+        if run_id.is_empty() {
+            return Err(AppError::ValidationError("Run id is required.".to_string()));
+        }
+        self.checkpoint_repository.get_llm_run_detail(run_id).await
+    }
+
+    /// Rename and annotate a checkpoint; empty values clear the field
+    pub async fn update_checkpoint(
+        &self,
+        checkpoint_id: &str,
+        title: Option<String>,
+        notes: Option<String>,
+    ) -> Result<QaCheckpoint> {
+        let title = title
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+        let notes = notes
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+        self.checkpoint_repository
+            .update_checkpoint_details(checkpoint_id, title.as_deref(), notes.as_deref())
+            .await?;
+        self.checkpoint_repository
+            .get_checkpoint(checkpoint_id)
+            .await
+    }
+
+    /// Merge two adjacent checkpoints into the earlier one
+    pub async fn merge_checkpoints(
+        &self,
+        session_id: &str,
+        first_id: &str,
+        second_id: &str,
+    ) -> Result<QaCheckpointEdit> {
+        let first = self.session_checkpoint(session_id, first_id).await?;
+        let second = self.session_checkpoint(session_id, second_id).await?;
+        let (kept, removed) = if first.seq <= second.seq {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        if removed.seq != kept.seq + 1 {
+            return Err(AppError::ValidationError(
+                "Only adjacent checkpoints can be merged.".to_string(),
+            ));
+        }
+
+        let notes = match (kept.notes.as_deref(), removed.notes.as_deref()) {
+            (Some(first), Some(second)) => Some(format!("{}\n\n{}", first, second)),
+            (first, second) => first.or(second).map(str::to_string),
+        };
+        let updated_at = chrono::Utc::now().timestamp_millis();
+        self.checkpoint_repository
+            .merge_checkpoints(&kept, &removed, notes.as_deref(), updated_at)
+            .await?;
+
+        Ok(QaCheckpointEdit {
+            checkpoints: self
+                .checkpoint_repository
+                .list_checkpoints(session_id)
+                .await?,
+            affected_checkpoint_ids: vec![kept.id],
+        })
+    }
+
+    /// Split a checkpoint so that `at_event_seq` starts a new one
+    pub async fn split_checkpoint(
+        &self,
+        session_id: &str,
+        checkpoint_id: &str,
+        at_event_seq: i64,
+        title: Option<String>,
+    ) -> Result<QaCheckpointEdit> {
+        let checkpoint = self.session_checkpoint(session_id, checkpoint_id).await?;
+        if at_event_seq <= checkpoint.start_event_seq || at_event_seq > checkpoint.end_event_seq {
+            return Err(AppError::ValidationError(format!(
+                "Split point must be after the first event and within events {} to {}.",
+                checkpoint.start_event_seq, checkpoint.end_event_seq
+            )));
+        }
+
+        let title = title
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .or_else(|| {
+                checkpoint
+                    .title
+                    .as_ref()
+                    .map(|title| format!("{} (continued)", title))
+            });
+        let updated_at = chrono::Utc::now().timestamp_millis();
+        let second = self
+            .checkpoint_repository
+            .split_checkpoint(
+                &checkpoint,
+                at_event_seq,
+                Uuid::new_v4().to_string(),
+                title,
+                updated_at,
+            )
+            .await?;
+
+        Ok(QaCheckpointEdit {
+            checkpoints: self
+                .checkpoint_repository
+                .list_checkpoints(session_id)
+                .await?,
+            affected_checkpoint_ids: vec![checkpoint.id, second.id],
+        })
+    }
+
+    /// Checkpoints re-segmented since their latest summary
+    pub async fn list_stale_checkpoints(&self, session_id: &str) -> Result<Vec<QaCheckpoint>> {
+        let mut stale = Vec::new();
+        for checkpoint in self.list_checkpoints(session_id).await? {
+            let summary = self
+                .checkpoint_repository
+                .get_checkpoint_summary(&checkpoint.id)
+                .await?;
+            if checkpoint.needs_regeneration(summary.as_ref()) {
+                stale.push(checkpoint);
+            }
+        }
+        Ok(stale)
+    }
+
+    /// Regenerate summaries and test cases for the given checkpoints, or for
+    /// every checkpoint re-segmented since its latest summary
+    pub async fn regenerate_checkpoints(
+        &self,
+        session_id: &str,
+        checkpoint_ids: Option<Vec<String>>,
+        config: &LLMConfig,
+        output_language: &str,
+    ) -> Result<QaCheckpointRegeneration> {
+        let checkpoint_ids = match checkpoint_ids {
+            Some(ids) => ids,
+            None => self
+                .list_stale_checkpoints(session_id)
+                .await?
+                .into_iter()
+                .map(|checkpoint| checkpoint.id)
+                .collect(),
+        };
+
+        let mut result = QaCheckpointRegeneration {
+            checkpoint_ids: Vec::with_capacity(checkpoint_ids.len()),
+            summaries: Vec::new(),
+            test_cases: Vec::new(),
+        };
+        for checkpoint_id in checkpoint_ids {
+            let summary = self
+                .generate_checkpoint_summary(session_id, &checkpoint_id, config, output_language)
+                .await?;
+            let cases = self
+                .generate_test_cases(
+                    session_id,
+                    &checkpoint_id,
+                    config,
+                    output_language,
+                    QaTestGenerationMode::Standard,
+                )
+                .await?;
+            result.summaries.push(summary);
+            result.test_cases.extend(cases);
+            result.checkpoint_ids.push(checkpoint_id);
+        }
+        Ok(result)
+    }
+
+    async fn session_checkpoint(
+        &self,
+        session_id: &str,
+        checkpoint_id: &str,
+    ) -> Result<QaCheckpoint> {
+        let checkpoint = self
+            .checkpoint_repository
+            .get_checkpoint(checkpoint_id)
+            .await?;
+        if checkpoint.session_id != session_id.trim() {
+            return Err(AppError::ValidationError(
+                "Checkpoint does not belong to session.".to_string(),
+            ));
+        }
+        Ok(checkpoint)
+    }
 }
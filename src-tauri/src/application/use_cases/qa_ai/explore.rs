@@ -1,24 +1,29 @@
-use super::{ExploreResult, QaAiUseCase, PROMPT_VERSION};
+use super::api_links::actions_json;
+use super::{ExploreResult, QaAiUseCase};
 use crate::application::use_cases::qa_ai::event_text::{
     build_chunked_event_text, build_input_summary, preview_text,
 };
-use crate::application::use_cases::qa_ai::hashing::{hash_input, normalize_language};
+use crate::application::use_cases::qa_ai::hashing::hash_input;
 use crate::application::use_cases::qa_ai::llm_output::extract_json_payload;
 use crate::application::use_cases::qa_ai::prompts::{
-    build_explore_system_prompt, build_explore_user_prompt, build_summary_system_prompt,
-    build_summary_user_prompt,
+    build_explore_user_prompt, build_summary_user_prompt, push_action_api_calls,
+    push_visual_findings, ASSERT_AFTER_ACTION, SUMMARIZE_ACTION_API_CALLS,
 };
 use crate::application::use_cases::qa_ai::success_detection::detect_post_submit_success;
-use crate::application::use_cases::qa_ai::types::{ExploreOutput, SummaryOutput};
+use crate::application::use_cases::qa_ai::types::{ExploreOutput, SummaryOutput, VisualOutput};
+use crate::application::use_cases::qa_ai::visual::merge_visual_risks;
 use crate::domain::error::{AppError, Result};
 use crate::domain::llm_config::LLMConfig;
 use crate::domain::qa_checkpoint::QaLlmRun;
+use crate::domain::qa_prompts::QaPromptKind;
 use crate::infrastructure::response::clean_llm_response;
 use uuid::Uuid;
 
 impl QaAiUseCase {
     /// Explore recorded events and generate test cases for a complete positive flow.
     /// Detects post-submit UI confirmation patterns (without requiring URL changes).
+    /// With `qaVision` on, the checkpoint's screenshots are reviewed by a vision
+    /// model first and its findings go into the summary and test case prompts.
     pub async fn explore_and_generate_tests(
         &self,
         session_id: &str,
@@ -69,16 +74,41 @@ impl QaAiUseCase {
 
         let chunked = build_chunked_event_text(&events);
         let input_summary = build_input_summary(&session, &checkpoint, chunked.len());
-        let language = normalize_language(output_language);
+        let output = self.output_language(output_language);
+        let language = output.language.clone();
+
+        let visual = self
+            .analyze_screenshots(
+                &session,
+                &checkpoint,
+                &events,
+                config,
+                &input_summary,
+                &language,
+            )
+            .await;
 
         // Summary generation
-        let summary_system = build_summary_system_prompt(&language);
-        let summary_user = build_summary_user_prompt(&session, &checkpoint, &chunked, &language);
-        let summary_raw = self
-            .llm_client
-            .generate(config, &summary_system, &summary_user)
+        let summary_system = self.system_prompt(QaPromptKind::CheckpointSummary, &language);
+        let mut summary_user =
+            build_summary_user_prompt(&session, &checkpoint, &chunked, &language);
+        let actions = self.action_api_calls(session_id, &events).await?;
+        push_action_api_calls(
+            &mut summary_user,
+            &actions,
+            SUMMARIZE_ACTION_API_CALLS,
+        );
+        if let Some((findings, _)) = visual.as_ref() {
+            push_visual_findings(
+                &mut summary_user,
+                findings,
+                "Mention what the screenshots show in summary_text and list each UI anomaly under risks.",
+            );
+        }
+        let summary_completion = self
+            .complete_in_language(config, &summary_system.text, &summary_user, &output)
             .await?;
-        let summary_cleaned = clean_llm_response(&summary_raw);
+        let summary_cleaned = clean_llm_response(&summary_completion.text);
         let summary_normalized = extract_json_payload(&summary_cleaned);
 
         let summary_parsed = serde_json::from_str::<SummaryOutput>(&summary_normalized).ok();
@@ -90,10 +120,15 @@ impl QaAiUseCase {
             .as_ref()
             .and_then(|o| o.entities.as_ref())
             .and_then(|items| serde_json::to_string(items).ok());
-        let risks_json = summary_parsed
+        let visual_anomalies = visual
             .as_ref()
-            .and_then(|o| o.risks.as_ref())
-            .and_then(|items| serde_json::to_string(items).ok());
+            .map(|(findings, _)| findings.anomalies.clone())
+            .unwrap_or_default();
+        let risks_json = merge_visual_risks(
+            summary_parsed.as_ref().and_then(|o| o.risks.clone()),
+            &visual_anomalies,
+        )
+        .and_then(|items| serde_json::to_string(&items).ok());
 
         let created_at = chrono::Utc::now().timestamp_millis();
         let summary_id = Uuid::new_v4().to_string();
@@ -105,6 +140,7 @@ impl QaAiUseCase {
                 summary_text,
                 entities_json,
                 risks_json,
+                actions_json(&actions),
                 created_at,
             )
             .await?;
@@ -119,31 +155,44 @@ impl QaAiUseCase {
             scope: "checkpoint_summary".to_string(),
             scope_id: checkpoint.id.clone(),
             model: config.model.clone(),
-            prompt_version: Some(PROMPT_VERSION.to_string()),
+            prompt_version: Some(summary_system.version),
             input_digest: Some(hash_input(&input_summary, &config.model)),
             input_summary: Some(input_summary.clone()),
             output_json: summary_output_json,
             created_at,
+            usage: summary_completion.usage,
         };
         self.checkpoint_repository
-            .insert_llm_run(&summary_run)
+            .insert_llm_run(&summary_run, &summary_completion.bodies)
             .await?;
-        let mut generated_llm_runs = vec![summary_run];
+        let mut generated_llm_runs: Vec<QaLlmRun> =
+            visual.map(|(_, run)| run).into_iter().collect();
+        generated_llm_runs.push(summary_run);
 
         // Exploration test generation
-        let explore_system = build_explore_system_prompt(&language);
-        let explore_user = build_explore_user_prompt(
+        let explore_system = self.system_prompt(QaPromptKind::Explore, &language);
+        let mut explore_user = build_explore_user_prompt(
             &session,
             &checkpoint,
             &detected_patterns,
             &chunked,
             &language,
         );
-        let explore_raw = self
-            .llm_client
-            .generate(config, &explore_system, &explore_user)
+        push_action_api_calls(&mut explore_user, &actions, ASSERT_AFTER_ACTION);
+        if !visual_anomalies.is_empty() {
+            push_visual_findings(
+                &mut explore_user,
+                &VisualOutput {
+                    observations: Vec::new(),
+                    anomalies: visual_anomalies.clone(),
+                },
+                "Add negative or exploratory cases that check for these UI anomalies, and expect the positive case to show none of them.",
+            );
+        }
+        let explore_completion = self
+            .complete_in_language(config, &explore_system.text, &explore_user, &output)
             .await?;
-        let explore_cleaned = clean_llm_response(&explore_raw);
+        let explore_cleaned = clean_llm_response(&explore_completion.text);
         let explore_normalized = extract_json_payload(&explore_cleaned);
         let explore_parsed =
             serde_json::from_str::<ExploreOutput>(&explore_normalized).map_err(|err| {
@@ -161,17 +210,20 @@ impl QaAiUseCase {
             scope: "explore_tests".to_string(),
             scope_id: checkpoint.id.clone(),
             model: config.model.clone(),
-            prompt_version: Some(PROMPT_VERSION.to_string()),
+            prompt_version: Some(explore_system.version),
             input_digest: Some(hash_input(&input_summary, &config.model)),
             input_summary: Some(input_summary),
             output_json: explore_output_json,
             created_at,
+            usage: explore_completion.usage,
         };
         self.checkpoint_repository
-            .insert_llm_run(&explore_run)
+            .insert_llm_run(&explore_run, &explore_completion.bodies)
             .await?;
         generated_llm_runs.push(explore_run);
 
+        // Failure cases go without the suggested response assertions
+        let assertions_json = self.assertions_json_for(session_id, &events).await?;
         let mut generated_test_cases = Vec::new();
         if let Some(positive) = explore_parsed.positive_case {
             generated_test_cases.extend(
@@ -180,6 +232,7 @@ impl QaAiUseCase {
                     &session,
                     &[positive],
                     "positive",
+                    assertions_json.as_deref(),
                     created_at,
                 )
                 .await?,
@@ -191,6 +244,7 @@ impl QaAiUseCase {
                 &session,
                 &explore_parsed.negative_cases,
                 "negative",
+                None,
                 created_at,
             )
             .await?,
@@ -201,6 +255,7 @@ impl QaAiUseCase {
                 &session,
                 &explore_parsed.edge_cases,
                 "edge",
+                assertions_json.as_deref(),
                 created_at,
             )
             .await?,
@@ -211,6 +266,7 @@ impl QaAiUseCase {
                 &session,
                 &explore_parsed.exploratory_charters,
                 "exploratory",
+                assertions_json.as_deref(),
                 created_at,
             )
             .await?,
@@ -223,6 +279,7 @@ impl QaAiUseCase {
             llm_runs: generated_llm_runs,
             post_submit_detected: has_submit && !detected_patterns.is_empty(),
             detected_patterns,
+            visual_anomalies,
         })
     }
 }
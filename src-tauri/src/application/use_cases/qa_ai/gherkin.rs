@@ -0,0 +1,468 @@
+use super::QaAiUseCase;
+use crate::domain::error::{AppError, Result};
+use crate::domain::qa_checkpoint::{QaGherkinFeature, QaTestCase};
+use crate::domain::qa_session::QaSession;
+
+/// Longest file name slug, before the `.feature` extension
+const MAX_SLUG_CHARS: usize = 48;
+
+/// Keywords that start a structural line; description text must not
+const STRUCTURAL_KEYWORDS: &[&str] = &[
+    "Feature:",
+    "Rule:",
+    "Background:",
+    "Scenario:",
+    "Scenario Outline:",
+    "Example:",
+    "Examples:",
+];
+
+/// Step openings that set up state rather than act, while no action came yet
+const PRECONDITION_PREFIXES: &[&str] = &[
+    "open ",
+    "navigate",
+    "go to ",
+    "visit ",
+    "log in",
+    "login",
+    "sign in",
+    "the user is ",
+    "user is ",
+    "the user has ",
+    "user has ",
+];
+
+/// Step openings that check an outcome
+const OUTCOME_PREFIXES: &[&str] = &[
+    "verify", "check ", "ensure", "confirm", "assert", "expect", "observe",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StepKeyword {
+    Given,
+    When,
+    Then,
+}
+
+impl StepKeyword {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Given => "Given",
+            Self::When => "When",
+            Self::Then => "Then",
+        }
+    }
+}
+
+/// One feature file before rendering
+struct FeatureSource<'a> {
+    name: String,
+    tags: Vec<String>,
+    description: Option<&'a str>,
+    /// Text of the `Given` step shared by every scenario
+    background: Option<String>,
+    cases: Vec<&'a QaTestCase>,
+}
+
+impl QaAiUseCase {
+    /// Gherkin feature files for the test cases of a session, one per
+    /// checkpoint that has cases. The checkpoint summary becomes the feature
+    /// description and the first page its events visited the background;
+    /// cases outside any checkpoint share one session-wide feature.
+    pub async fn export_gherkin(&self, session_id: &str) -> Result<Vec<QaGherkinFeature>> {
+        let session_id = session_id.trim();
+        if session_id.is_empty() {
+            return Err(AppError::ValidationError(
+                "Session id is required.".to_string(),
+            ));
+        }
+
+        let session = self.session_repository.get_session(session_id).await?;
+        let checkpoints = self
+            .checkpoint_repository
+            .list_checkpoints(session_id)
+            .await?;
+        // Newest first, so the first summary of a checkpoint is its latest
+        let summaries = self
+            .checkpoint_repository
+            .list_checkpoint_summaries(session_id)
+            .await?;
+        let mut cases = self
+            .checkpoint_repository
+            .list_test_cases(session_id)
+            .await?;
+        if cases.is_empty() {
+            return Err(AppError::ValidationError(
+                "Session has no test cases to export.".to_string(),
+            ));
+        }
+        cases.reverse();
+
+        let mut features = Vec::new();
+        for checkpoint in &checkpoints {
+            let checkpoint_cases: Vec<&QaTestCase> = cases
+                .iter()
+                .filter(|case| case.checkpoint_id.as_deref() == Some(checkpoint.id.as_str()))
+                .collect();
+            if checkpoint_cases.is_empty() {
+                continue;
+            }
+
+            let events = self
+                .event_repository
+                .list_events_range(
+                    session_id,
+                    checkpoint.start_event_seq,
+                    checkpoint.end_event_seq,
+                )
+                .await?;
+            let start_url = events
+                .iter()
+                .filter_map(|event| event.url.as_deref())
+                .find(|url| !url.trim().is_empty());
+
+            let name = checkpoint
+                .title
+                .as_deref()
+                .map(str::trim)
+                .filter(|title| !title.is_empty())
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("Checkpoint {}", checkpoint.seq));
+            let source = FeatureSource {
+                tags: vec![
+                    tag(&session.session_type),
+                    format!("@checkpoint-{}", checkpoint.seq),
+                ],
+                description: summaries
+                    .iter()
+                    .find(|summary| summary.checkpoint_id == checkpoint.id)
+                    .map(|summary| summary.summary_text.as_str()),
+                background: background_step(&session, start_url),
+                cases: checkpoint_cases,
+                name: name.clone(),
+            };
+            features.push(QaGherkinFeature {
+                checkpoint_id: Some(checkpoint.id.clone()),
+                file_name: format!("{:02}-{}.feature", checkpoint.seq, slug(&name)),
+                scenario_count: source.cases.len(),
+                content: render_feature(&source),
+            });
+        }
+
+        let orphans: Vec<&QaTestCase> = cases
+            .iter()
+            .filter(|case| {
+                !checkpoints
+                    .iter()
+                    .any(|checkpoint| case.checkpoint_id.as_deref() == Some(checkpoint.id.as_str()))
+            })
+            .collect();
+        if !orphans.is_empty() {
+            let name = if session.title.trim().is_empty() {
+                "Session".to_string()
+            } else {
+                session.title.trim().to_string()
+            };
+            let source = FeatureSource {
+                tags: vec![tag(&session.session_type)],
+                description: Some(session.goal.as_str()),
+                background: background_step(&session, None),
+                cases: orphans,
+                name: name.clone(),
+            };
+            features.push(QaGherkinFeature {
+                checkpoint_id: None,
+                file_name: format!("session-{}.feature", slug(&name)),
+                scenario_count: source.cases.len(),
+                content: render_feature(&source),
+            });
+        }
+
+        Ok(features)
+    }
+}
+
+/// `Given` step text for where the session starts: the API base URL of API
+/// sessions, else the first page visited, else the session's target URL
+fn background_step(session: &QaSession, start_url: Option<&str>) -> Option<String> {
+    let non_empty = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    if session.session_type == "api" {
+        if let Some(base_url) = non_empty(&session.api_base_url) {
+            return Some(format!("the API base URL is \"{}\"", base_url));
+        }
+    }
+    start_url
+        .map(|url| url.trim().to_string())
+        .or_else(|| non_empty(&session.target_url))
+        .map(|url| format!("the user is on \"{}\"", url))
+}
+
+fn render_feature(source: &FeatureSource<'_>) -> String {
+    let mut out = String::new();
+    if !source.tags.is_empty() {
+        out.push_str(&source.tags.join(" "));
+        out.push('\n');
+    }
+    out.push_str(&format!("Feature: {}\n", single_line(&source.name)));
+    if let Some(description) = source.description {
+        for line in description.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            out.push_str(&format!("  {}\n", description_line(line)));
+        }
+    }
+
+    if let Some(background) = source.background.as_deref() {
+        out.push_str("\n  Background:\n");
+        out.push_str(&format!("    Given {}\n", background));
+    }
+
+    for case in &source.cases {
+        out.push('\n');
+        let mut tags = vec![tag(&case.case_type)];
+        if let Some(priority) = case.priority.as_deref().filter(|p| !p.trim().is_empty()) {
+            tags.push(format!("@priority-{}", tag_body(priority)));
+        }
+        out.push_str(&format!("  {}\n", tags.join(" ")));
+        out.push_str(&format!("  Scenario: {}\n", single_line(&case.title)));
+
+        let mut previous = None;
+        for (keyword, text) in scenario_steps(case) {
+            let word = if previous == Some(keyword) {
+                "And"
+            } else {
+                keyword.as_str()
+            };
+            out.push_str(&format!("    {} {}\n", word, continue_sentence(&text)));
+            previous = Some(keyword);
+        }
+    }
+
+    out
+}
+
+/// Keyword and text of each step of `case`. Leading set-up steps become
+/// `Given`, checks and the expected result become `Then`, and everything
+/// else `When`. Keywords already written in a step are kept.
+fn scenario_steps(case: &QaTestCase) -> Vec<(StepKeyword, String)> {
+    let raw: Vec<String> = serde_json::from_str(&case.steps_json).unwrap_or_default();
+
+    let mut steps = Vec::new();
+    let mut acted = false;
+    for step in &raw {
+        let (written, text) = split_keyword(&strip_numbering(&single_line(step)));
+        if text.is_empty() {
+            continue;
+        }
+        let lower = text.to_lowercase();
+        let keyword = match written {
+            Some(keyword) => keyword,
+            None if !acted && starts_with_any(&lower, PRECONDITION_PREFIXES) => StepKeyword::Given,
+            None if starts_with_any(&lower, OUTCOME_PREFIXES) => StepKeyword::Then,
+            None => StepKeyword::When,
+        };
+        if keyword != StepKeyword::Given {
+            acted = true;
+        }
+        steps.push((keyword, text));
+    }
+
+    if let Some(expected) = case.expected.as_deref() {
+        for line in expected.lines() {
+            let (_, text) = split_keyword(&strip_numbering(&single_line(line)));
+            if !text.is_empty() {
+                steps.push((StepKeyword::Then, text));
+            }
+        }
+    }
+
+    steps
+}
+
+/// Leading `Given`/`When`/`Then`/`And`/`But` of a step and the rest of it
+fn split_keyword(step: &str) -> (Option<StepKeyword>, String) {
+    let lower = step.to_lowercase();
+    for (word, keyword) in [
+        ("given ", Some(StepKeyword::Given)),
+        ("when ", Some(StepKeyword::When)),
+        ("then ", Some(StepKeyword::Then)),
+        ("and ", None),
+        ("but ", None),
+    ] {
+        if lower.starts_with(word) {
+            return (keyword, step[word.len()..].trim().to_string());
+        }
+    }
+    (None, step.trim().to_string())
+}
+
+/// Drop list markers such as `1.`, `2)` or `-` from the start of a step
+fn strip_numbering(step: &str) -> String {
+    let trimmed = step.trim_start();
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    let rest = if digits > 0 {
+        trimmed[digits..]
+            .strip_prefix(['.', ')', ':'])
+            .unwrap_or(&trimmed[digits..])
+    } else {
+        trimmed.strip_prefix(['-', '*', '•']).unwrap_or(trimmed)
+    };
+    rest.trim().to_string()
+}
+
+/// `text` with its first letter lowercased, unless the first word is an
+/// acronym or name written in capitals
+fn continue_sentence(text: &str) -> String {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(first), Some(second)) if first.is_uppercase() && second.is_lowercase() => first
+            .to_lowercase()
+            .chain(text[first.len_utf8()..].chars())
+            .collect(),
+        _ => text.to_string(),
+    }
+}
+
+fn starts_with_any(text: &str, prefixes: &[&str]) -> bool {
+    prefixes.iter().any(|prefix| text.starts_with(prefix))
+}
+
+/// Whitespace, line breaks included, collapsed to single spaces
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// A description line that the parser cannot mistake for a keyword,
+/// comment or tag
+fn description_line(line: &str) -> String {
+    let structural = STRUCTURAL_KEYWORDS
+        .iter()
+        .any(|keyword| line.starts_with(keyword));
+    if structural || line.starts_with('#') || line.starts_with('@') {
+        format!("- {}", line)
+    } else {
+        line.to_string()
+    }
+}
+
+fn tag(value: &str) -> String {
+    format!("@{}", tag_body(value))
+}
+
+/// Lowercase letters and digits, with runs of anything else as one `-`
+fn tag_body(value: &str) -> String {
+    let mut body = String::new();
+    for c in value.trim().chars() {
+        if c.is_alphanumeric() {
+            body.extend(c.to_lowercase());
+        } else if !body.is_empty() && !body.ends_with('-') {
+            body.push('-');
+        }
+    }
+    let body = body.trim_end_matches('-');
+    if body.is_empty() {
+        "untagged".to_string()
+    } else {
+        body.to_string()
+    }
+}
+
+fn slug(name: &str) -> String {
+    let body = tag_body(name);
+    let cut: String = body.chars().take(MAX_SLUG_CHARS).collect();
+    cut.trim_end_matches('-').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn case(steps: &[&str], expected: Option<&str>) -> QaTestCase {
+        QaTestCase {
+            id: "case-1".to_string(),
+            session_id: "session-1".to_string(),
+            checkpoint_id: Some("checkpoint-1".to_string()),
+            case_type: "negative".to_string(),
+            title: "Login with a wrong password".to_string(),
+            steps_json: serde_json::to_string(steps).unwrap(),
+            expected: expected.map(str::to_string),
+            priority: Some("High".to_string()),
+            status: None,
+            dedup_hash: String::new(),
+            assertions_json: None,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn steps_are_split_into_given_when_then() {
+        let case = case(
+            &[
+                "1. Open the login page",
+                "2. Enter \"bob\" in the username field",
+                "3) Click Sign in",
+                "Verify the password field is cleared",
+            ],
+            Some("An error message is shown\nThen the user stays on the login page"),
+        );
+
+        let steps = scenario_steps(&case);
+        let keywords: Vec<StepKeyword> = steps.iter().map(|(keyword, _)| *keyword).collect();
+        assert_eq!(
+            keywords,
+            vec![
+                StepKeyword::Given,
+                StepKeyword::When,
+                StepKeyword::When,
+                StepKeyword::Then,
+                StepKeyword::Then,
+                StepKeyword::Then,
+            ]
+        );
+        assert_eq!(steps[0].1, "Open the login page");
+        assert_eq!(steps[5].1, "the user stays on the login page");
+    }
+
+    #[test]
+    fn renders_a_feature_with_background_and_tagged_scenarios() {
+        let case = case(
+            &["Given the user is logged out", "Submit the form"],
+            Some("It fails"),
+        );
+        let source = FeatureSource {
+            name: "Login".to_string(),
+            tags: vec![tag("web"), "@checkpoint-1".to_string()],
+            description: Some("The user signs in.\n# not a comment"),
+            background: Some("the user is on \"https://example.test\"".to_string()),
+            cases: vec![&case],
+        };
+
+        assert_eq!(
+            render_feature(&source),
+            "@web @checkpoint-1\n\
+             Feature: Login\n  \
+               The user signs in.\n  \
+               - # not a comment\n\
+             \n  \
+               Background:\n    \
+                 Given the user is on \"https://example.test\"\n\
+             \n  \
+               @negative @priority-high\n  \
+               Scenario: Login with a wrong password\n    \
+                 Given the user is logged out\n    \
+                 When submit the form\n    \
+                 Then it fails\n"
+        );
+    }
+
+    #[test]
+    fn slugs_are_file_name_safe() {
+        assert_eq!(slug("Checkout / Pay (Visa)!"), "checkout-pay-visa");
+        assert_eq!(tag("api_gap"), "@api-gap");
+        assert_eq!(tag("  "), "@untagged");
+    }
+}
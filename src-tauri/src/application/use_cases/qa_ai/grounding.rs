@@ -0,0 +1,230 @@
+//! What negative test generation is grounded in: the form fields the tester
+//! filled in and the request shapes of the API calls recorded alongside.
+
+use crate::domain::qa_api_call::QaApiCall;
+use crate::domain::qa_event::QaEvent;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+const MAX_FIELDS: usize = 40;
+const MAX_ENDPOINTS: usize = 20;
+const MAX_BODY_FIELDS: usize = 30;
+/// Nesting below this depth is described as `object` or `array`
+const MAX_BODY_DEPTH: usize = 3;
+
+/// A form field the tester typed into
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RecordedField {
+    pub selector: String,
+    pub label: Option<String>,
+    /// The input's `type` attribute, e.g. `email` or `password`
+    pub input_type: Option<String>,
+}
+
+/// One recorded endpoint: its request body fields and the statuses it answered with
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct EndpointShape {
+    pub method: String,
+    pub path: String,
+    /// Dotted paths with JSON types, e.g. `user.email: string`
+    pub body_fields: Vec<String>,
+    pub statuses: BTreeSet<i64>,
+}
+
+/// Fields of the `input` events, once each in the order first typed into
+pub(crate) fn recorded_fields(events: &[QaEvent]) -> Vec<RecordedField> {
+    let mut fields: Vec<RecordedField> = Vec::new();
+    for event in events.iter().filter(|event| event.event_type == "input") {
+        let Some(selector) = event.selector.as_deref().map(str::trim) else {
+            continue;
+        };
+        if selector.is_empty() || fields.iter().any(|field| field.selector == selector) {
+            continue;
+        }
+        let input_type = event
+            .meta_json
+            .as_deref()
+            .and_then(|meta| serde_json::from_str::<Value>(meta).ok())
+            .and_then(|meta| meta.get("type")?.as_str().map(str::to_string));
+        fields.push(RecordedField {
+            selector: selector.to_string(),
+            label: event
+                .element_text
+                .as_deref()
+                .map(str::trim)
+                .filter(|text| !text.is_empty())
+                .map(str::to_string),
+            input_type,
+        });
+        if fields.len() == MAX_FIELDS {
+            break;
+        }
+    }
+    fields
+}
+
+/// Calls grouped by method and path, with the union of their body fields
+pub(crate) fn endpoint_shapes(calls: &[QaApiCall]) -> Vec<EndpointShape> {
+    let mut grouped: BTreeMap<(String, String), (BTreeSet<String>, BTreeSet<i64>)> =
+        BTreeMap::new();
+    for call in calls {
+        let Some(key) = endpoint_key(call) else {
+            continue;
+        };
+        if !grouped.contains_key(&key) && grouped.len() == MAX_ENDPOINTS {
+            continue;
+        }
+        let (fields, statuses) = grouped.entry(key).or_default();
+        if let Some(body) = call
+            .request_body_json
+            .as_deref()
+            .and_then(|body| serde_json::from_str::<Value>(body).ok())
+        {
+            let mut found = Vec::new();
+            describe_fields(&body, "", 0, &mut found);
+            fields.extend(found);
+        }
+        statuses.extend(call.response_status);
+    }
+
+    grouped
+        .into_iter()
+        .map(|((method, path), (fields, statuses))| EndpointShape {
+            method,
+            path,
+            body_fields: fields.into_iter().take(MAX_BODY_FIELDS).collect(),
+            statuses,
+        })
+        .collect()
+}
+
+/// Upper-cased method and URL path, without the query; `None` when the URL
+/// does not parse
+pub(crate) fn endpoint_key(call: &QaApiCall) -> Option<(String, String)> {
+    let url = url::Url::parse(call.url.trim()).ok()?;
+    Some((call.method.trim().to_uppercase(), url.path().to_string()))
+}
+
+fn describe_fields(value: &Value, prefix: &str, depth: usize, out: &mut Vec<String>) {
+    let Value::Object(map) = value else {
+        if !prefix.is_empty() {
+            out.push(format!("{}: {}", prefix, json_type(value)));
+        }
+        return;
+    };
+    for (key, child) in map {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        if child.is_object() && depth + 1 < MAX_BODY_DEPTH {
+            describe_fields(child, &path, depth + 1, out);
+        } else {
+            out.push(format!("{}: {}", path, json_type(child)));
+        }
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_i64() || number.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(seq: i64, selector: &str, label: Option<&str>, input_type: &str) -> QaEvent {
+        QaEvent {
+            id: seq.to_string(),
+            session_id: "session".to_string(),
+            run_id: None,
+            checkpoint_id: None,
+            seq,
+            ts: seq,
+            event_type: "input".to_string(),
+            origin: None,
+            recording_mode: None,
+            selector: Some(selector.to_string()),
+            element_text: label.map(str::to_string),
+            value: Some("***".to_string()),
+            url: None,
+            screenshot_id: None,
+            screenshot_path: None,
+            annotated_screenshot_path: None,
+            meta_json: Some(format!(r#"{{"tag":"input","type":"{}"}}"#, input_type)),
+        }
+    }
+
+    fn call(method: &str, url: &str, body: Option<&str>, status: i64) -> QaApiCall {
+        QaApiCall {
+            id: url.to_string(),
+            session_id: "session".to_string(),
+            run_id: "run".to_string(),
+            method: method.to_string(),
+            url: url.to_string(),
+            request_headers_json: None,
+            request_body_json: body.map(str::to_string),
+            request_body_hash: None,
+            response_status: Some(status),
+            response_headers_json: None,
+            response_body_hash: None,
+            response_body_json: None,
+            timing_ms: None,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn fields_are_listed_once_per_selector() {
+        let fields = recorded_fields(&[
+            input(1, "#email", Some("Email"), "email"),
+            input(2, "#email", Some("Email"), "email"),
+            input(3, "#password", None, "password"),
+        ]);
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].label.as_deref(), Some("Email"));
+        assert_eq!(fields[1].input_type.as_deref(), Some("password"));
+    }
+
+    #[test]
+    fn endpoints_merge_body_fields_and_statuses() {
+        let shapes = endpoint_shapes(&[
+            call(
+                "post",
+                "https://api.example.com/login?next=/",
+                Some(r#"{"email":"a@b.c","password":"x","device":{"id":7}}"#),
+                200,
+            ),
+            call(
+                "POST",
+                "https://api.example.com/login",
+                Some(r#"{"email":"a@b.c","remember":true}"#),
+                401,
+            ),
+            call("GET", "not a url", None, 200),
+        ]);
+        assert_eq!(shapes.len(), 1);
+        let login = &shapes[0];
+        assert_eq!(login.method, "POST");
+        assert_eq!(login.path, "/login");
+        assert_eq!(
+            login.body_fields,
+            vec![
+                "device.id: integer",
+                "email: string",
+                "password: string",
+                "remember: boolean"
+            ]
+        );
+        assert_eq!(login.statuses, BTreeSet::from([200, 401]));
+    }
+}
@@ -11,12 +11,3 @@ pub(crate) fn hash_value(value: &str) -> String {
     value.hash(&mut hasher);
     format!("{:x}", hasher.finish())
 }
-
-pub(crate) fn normalize_language(value: &str) -> String {
-    let trimmed = value.trim();
-    if trimmed.is_empty() {
-        "English".to_string()
-    } else {
-        trimmed.to_string()
-    }
-}
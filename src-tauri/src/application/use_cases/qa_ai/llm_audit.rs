@@ -0,0 +1,155 @@
+use super::QaAiUseCase;
+use crate::application::use_cases::output_language::OutputLanguage;
+use crate::domain::error::Result;
+use crate::domain::llm_config::LLMConfig;
+use crate::domain::qa_checkpoint::{QaLlmRunBodies, QaLlmUsage};
+use crate::domain::qa_llm_audit::QaLlmAuditSettings;
+use crate::domain::settings::OutputLanguageSettings;
+use crate::infrastructure::llm_clients::ImageInput;
+use crate::shared::TokenCounter;
+use std::time::Instant;
+use tracing::warn;
+
+/// Model output together with what the run record keeps about the call
+pub(super) struct AuditedCompletion {
+    pub text: String,
+    pub usage: QaLlmUsage,
+    pub bodies: QaLlmRunBodies,
+}
+
+impl QaAiUseCase {
+    /// The language generated QA text is written in: `requested`, else the
+    /// `outputLanguage` setting, else English
+    pub(super) fn output_language(&self, requested: &str) -> OutputLanguage {
+        let settings = self.settings.get::<OutputLanguageSettings>();
+        OutputLanguage::resolve(&settings, Some(requested)).unwrap_or(OutputLanguage {
+            language: "English".to_string(),
+            verify: settings.verify,
+            max_retries: settings.max_retries,
+        })
+    }
+
+    /// `complete_audited`, asked again while the answer is in the wrong
+    /// language. Usage adds up over the attempts; the bodies are the last
+    /// attempt's.
+    pub(super) async fn complete_in_language(
+        &self,
+        config: &LLMConfig,
+        system: &str,
+        user: &str,
+        language: &OutputLanguage,
+    ) -> Result<AuditedCompletion> {
+        let mut completion = self.complete_audited(config, system, user).await?;
+        for _ in 0..language.max_retries {
+            let Some(detected) = language.mismatch(&completion.text) else {
+                return Ok(completion);
+            };
+            let retry = self
+                .complete_audited(config, system, &language.retry_prompt(user, &detected))
+                .await?;
+            completion = AuditedCompletion {
+                usage: add_usage(&completion.usage, retry.usage),
+                ..retry
+            };
+        }
+        if let Some(detected) = language.mismatch(&completion.text) {
+            warn!(
+                expected = %language.language,
+                detected = %detected,
+                "QA output is still in the wrong language"
+            );
+        }
+        Ok(completion)
+    }
+
+    /// Calls the model, measuring latency, tokens and cost and capturing the
+    /// bodies as configured in `qaLlmAudit`
+    pub(super) async fn complete_audited(
+        &self,
+        config: &LLMConfig,
+        system: &str,
+        user: &str,
+    ) -> Result<AuditedCompletion> {
+        self.complete_audited_with_images(config, system, user, &[])
+            .await
+    }
+
+    /// `complete_audited` for a vision model; the stored user prompt notes
+    /// how many images went with it, not their bytes
+    pub(super) async fn complete_audited_with_images(
+        &self,
+        config: &LLMConfig,
+        system: &str,
+        user: &str,
+        images: &[ImageInput],
+    ) -> Result<AuditedCompletion> {
+        let started = Instant::now();
+        let completion = self
+            .llm_client
+            .complete_with_images(config, system, user, images)
+            .await?;
+        let latency_ms = started.elapsed().as_millis() as i64;
+
+        let audit = self.settings.get::<QaLlmAuditSettings>();
+        let provider = config.provider.to_string();
+        let (prompt_tokens, completion_tokens, tokens_estimated) = match completion.usage {
+            Some(usage) => (usage.prompt_tokens, usage.completion_tokens, false),
+            None => (
+                TokenCounter::estimate_messages_tokens(
+                    &[("system", system), ("user", user)],
+                    &provider,
+                ) as u64,
+                TokenCounter::estimate_tokens(&completion.text, &provider) as u64,
+                true,
+            ),
+        };
+        let usage = QaLlmUsage {
+            prompt_tokens: Some(prompt_tokens as i64),
+            completion_tokens: Some(completion_tokens as i64),
+            tokens_estimated,
+            latency_ms: Some(latency_ms),
+            cost_usd: audit.cost_usd(&config.model, prompt_tokens, completion_tokens),
+        };
+
+        let bodies = if audit.store_bodies {
+            let (system_prompt, system_cut) = audit.cap_body(system);
+            let user_body = if images.is_empty() {
+                user.to_string()
+            } else {
+                format!("{}\n\n[{} images attached]", user, images.len())
+            };
+            let (user_prompt, user_cut) = audit.cap_body(&user_body);
+            let (response_text, response_cut) = audit.cap_body(&completion.text);
+            QaLlmRunBodies {
+                system_prompt: Some(system_prompt),
+                user_prompt: Some(user_prompt),
+                response_text: Some(response_text),
+                truncated: system_cut || user_cut || response_cut,
+            }
+        } else {
+            QaLlmRunBodies::default()
+        };
+
+        Ok(AuditedCompletion {
+            text: completion.text,
+            usage,
+            bodies,
+        })
+    }
+}
+
+fn add_usage(earlier: &QaLlmUsage, later: QaLlmUsage) -> QaLlmUsage {
+    fn sum<T: std::ops::Add<Output = T>>(a: Option<T>, b: Option<T>) -> Option<T> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        }
+    }
+    QaLlmUsage {
+        prompt_tokens: sum(earlier.prompt_tokens, later.prompt_tokens),
+        completion_tokens: sum(earlier.completion_tokens, later.completion_tokens),
+        tokens_estimated: earlier.tokens_estimated || later.tokens_estimated,
+        latency_ms: sum(earlier.latency_ms, later.latency_ms),
+        cost_usd: sum(earlier.cost_usd, later.cost_usd),
+    }
+}
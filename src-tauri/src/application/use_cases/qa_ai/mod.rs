@@ -1,25 +1,36 @@
+mod api_links;
+mod assertions;
 mod checkpoints;
 mod event_text;
 mod explore;
+mod gherkin;
+mod grounding;
 mod hashing;
+mod llm_audit;
 mod llm_output;
+mod prompt_templates;
 mod prompts;
 mod success_detection;
 mod summaries;
 mod tests;
+mod training_examples;
 mod types;
+mod visual;
 
 use crate::domain::error::{AppError, Result};
 use crate::domain::qa_checkpoint::{QaCheckpoint, QaCheckpointSummary, QaLlmRun, QaTestCase};
 use crate::domain::qa_session::QaSession;
 use crate::infrastructure::db::qa_checkpoints::QaCheckpointRepository;
 use crate::infrastructure::db::qa_events::QaEventRepository;
+use crate::infrastructure::config::SettingsStore;
+use crate::infrastructure::db::qa_api_calls::QaApiCallRepository;
 use crate::infrastructure::db::qa_sessions::QaRepository;
 use crate::infrastructure::llm_clients::LLMClient;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use hashing::hash_value;
+pub(crate) use hashing::hash_value;
+pub use prompt_templates::{prompt_template_views, QaPromptTemplateView};
 use types::TestCaseInput;
 
 const PROMPT_VERSION: &str = "v1";
@@ -28,7 +39,9 @@ pub struct QaAiUseCase {
     session_repository: Arc<QaRepository>,
     event_repository: Arc<QaEventRepository>,
     checkpoint_repository: Arc<QaCheckpointRepository>,
+    api_call_repository: Arc<QaApiCallRepository>,
     llm_client: Arc<dyn LLMClient + Send + Sync>,
+    settings: Arc<SettingsStore>,
 }
 
 impl QaAiUseCase {
@@ -36,13 +49,17 @@ impl QaAiUseCase {
         session_repository: Arc<QaRepository>,
         event_repository: Arc<QaEventRepository>,
         checkpoint_repository: Arc<QaCheckpointRepository>,
+        api_call_repository: Arc<QaApiCallRepository>,
         llm_client: Arc<dyn LLMClient + Send + Sync>,
+        settings: Arc<SettingsStore>,
     ) -> Self {
         Self {
             session_repository,
             event_repository,
             checkpoint_repository,
+            api_call_repository,
             llm_client,
+            settings,
         }
     }
 
@@ -86,6 +103,7 @@ impl QaAiUseCase {
         session: &QaSession,
         items: &[TestCaseInput],
         case_type: &str,
+        assertions_json: Option<&str>,
         created_at: i64,
     ) -> Result<Vec<QaTestCase>> {
         let mut stored = Vec::new();
@@ -108,6 +126,7 @@ impl QaAiUseCase {
                 priority: item.priority.clone().filter(|value| !value.trim().is_empty()),
                 status: None,
                 dedup_hash: hash_value(&dedup_source),
+                assertions_json: assertions_json.map(str::to_string),
                 created_at,
             };
             self.checkpoint_repository
@@ -129,4 +148,6 @@ pub struct ExploreResult {
     pub llm_runs: Vec<QaLlmRun>,
     pub post_submit_detected: bool,
     pub detected_patterns: Vec<String>,
+    /// UI anomalies the vision model found in the checkpoint's screenshots
+    pub visual_anomalies: Vec<String>,
 }
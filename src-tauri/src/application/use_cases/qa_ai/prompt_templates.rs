@@ -0,0 +1,74 @@
+use super::{QaAiUseCase, PROMPT_VERSION};
+use crate::application::use_cases::qa_ai::prompts::{
+    build_explore_system_prompt, build_negative_system_prompt, build_summary_system_prompt,
+    build_test_system_prompt, build_visual_system_prompt,
+};
+use crate::domain::qa_prompts::{QaPromptKind, QaPromptSettings, LANGUAGE_PLACEHOLDER};
+use serde::Serialize;
+
+/// A system prompt and the prompt version the runs that use it record
+pub(super) struct SystemPrompt {
+    pub text: String,
+    pub version: String,
+}
+
+/// One prompt kind as the template editor shows it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaPromptTemplateView {
+    pub kind: QaPromptKind,
+    /// The built-in prompt, with `{language}` where the language goes
+    pub default_template: String,
+    /// The team's template; `None` while the built-in one is used
+    pub template: Option<String>,
+    /// 0 until the first edit
+    pub version: u32,
+    pub updated_at: Option<i64>,
+}
+
+/// The built-in system prompt of `kind`
+pub fn default_system_prompt(kind: QaPromptKind, language: &str) -> String {
+    match kind {
+        QaPromptKind::CheckpointSummary => build_summary_system_prompt(language),
+        QaPromptKind::TestCases => build_test_system_prompt(language),
+        QaPromptKind::NegativeCases => build_negative_system_prompt(language),
+        QaPromptKind::Explore => build_explore_system_prompt(language),
+        QaPromptKind::VisualAnalysis => build_visual_system_prompt(language),
+    }
+}
+
+pub fn prompt_template_views(settings: &QaPromptSettings) -> Vec<QaPromptTemplateView> {
+    QaPromptKind::ALL
+        .iter()
+        .map(|&kind| {
+            let stored = settings.templates.get(&kind);
+            QaPromptTemplateView {
+                kind,
+                default_template: default_system_prompt(kind, LANGUAGE_PLACEHOLDER),
+                template: stored.and_then(|t| t.template.clone()),
+                version: stored.map_or(0, |t| t.version),
+                updated_at: stored.map(|t| t.updated_at),
+            }
+        })
+        .collect()
+}
+
+impl QaAiUseCase {
+    /// The team's template for `kind` from `qaPrompts`, or the built-in prompt
+    pub(super) fn system_prompt(&self, kind: QaPromptKind, language: &str) -> SystemPrompt {
+        let settings = self.settings.get::<QaPromptSettings>();
+        match settings
+            .custom(kind)
+            .and_then(|template| Some((template.render(language)?, template.version)))
+        {
+            Some((text, version)) => SystemPrompt {
+                text,
+                version: format!("{}-custom.{}", PROMPT_VERSION, version),
+            },
+            None => SystemPrompt {
+                text: default_system_prompt(kind, language),
+                version: PROMPT_VERSION.to_string(),
+            },
+        }
+    }
+}
@@ -1,7 +1,20 @@
 use crate::application::use_cases::qa_ai::event_text::truncate;
+use crate::application::use_cases::qa_ai::grounding::{EndpointShape, RecordedField};
+use crate::application::use_cases::qa_ai::types::VisualOutput;
+use crate::domain::qa_api_link::QaActionApiCalls;
 use crate::domain::qa_checkpoint::{QaCheckpoint, QaCheckpointSummary, QaTestCase};
 use crate::domain::qa_session::QaSession;
 
+/// Title and notes the tester gave the checkpoint
+fn push_checkpoint_annotations(body: &mut String, checkpoint: &QaCheckpoint) {
+    if let Some(title) = checkpoint.title.as_ref() {
+        body.push_str(&format!("Checkpoint title: {}\n", title));
+    }
+    if let Some(notes) = checkpoint.notes.as_ref() {
+        body.push_str(&format!("Tester notes: {}\n", truncate(notes, 1000)));
+    }
+}
+
 pub(crate) fn build_summary_system_prompt(language: &str) -> String {
     format!(
         "You are a QA automation assistant. Summarize the event chunks into a concise checkpoint summary. Respond in {}. Return JSON with keys: summary_text (bullet list), entities (list of fields/buttons/routes), risks (list of anomalies/errors). Return only JSON.",
@@ -31,6 +44,7 @@ pub(crate) fn build_summary_user_prompt(
         "Checkpoint seq: {} (events {} to {})\n",
         checkpoint.seq, checkpoint.start_event_seq, checkpoint.end_event_seq
     ));
+    push_checkpoint_annotations(&mut body, checkpoint);
 
     body.push_str("\nEvent chunks:\n");
     for (index, chunk) in chunks.iter().enumerate() {
@@ -45,6 +59,50 @@ pub(crate) fn build_summary_user_prompt(
     body
 }
 
+pub(crate) const SUMMARIZE_ACTION_API_CALLS: &str =
+    "Mention in summary_text which API call each action led to.";
+pub(crate) const ASSERT_AFTER_ACTION: &str = "After a step that performs one of these actions, assert on the API call listed under it (method, path and status), not on calls that followed other actions.";
+
+/// Each action with the API calls it triggered, so assertions follow the
+/// action they belong to
+pub(crate) fn push_action_api_calls(
+    body: &mut String,
+    actions: &[QaActionApiCalls],
+    instruction: &str,
+) {
+    if actions.is_empty() {
+        return;
+    }
+    body.push_str("\nAPI calls after each action:\n");
+    for action in actions {
+        body.push_str(&format!(
+            "- #{} {}",
+            action.trigger_seq, action.trigger_type
+        ));
+        if let Some(selector) = action.selector.as_ref() {
+            body.push_str(&format!(" selector={}", truncate(selector, 120)));
+        }
+        if let Some(text) = action.element_text.as_ref() {
+            body.push_str(&format!(" text={}", truncate(text, 80)));
+        }
+        body.push('\n');
+        for call in &action.calls {
+            body.push_str(&format!(
+                "  - #{} {} {}",
+                call.response_seq,
+                call.method.as_deref().unwrap_or("?"),
+                truncate(call.url.as_deref().unwrap_or(""), 140)
+            ));
+            if let Some(status) = call.status {
+                body.push_str(&format!(" -> {}", status));
+            }
+            body.push_str(&format!(" (+{} ms)\n", call.delay_ms));
+        }
+    }
+    body.push_str(instruction);
+    body.push('\n');
+}
+
 pub(crate) fn build_test_system_prompt(language: &str) -> String {
     format!(
         "You are a QA automation assistant. Generate negative, edge, and exploratory test cases from the checkpoint summary and event chunks. Respond in {}. Return JSON with arrays: negative_cases, edge_cases, exploratory_charters, api_gap_checks. Each item: {{title, steps, expected, priority}}. Return only JSON.",
@@ -52,6 +110,54 @@ pub(crate) fn build_test_system_prompt(language: &str) -> String {
     )
 }
 
+pub(crate) fn build_negative_system_prompt(language: &str) -> String {
+    format!(
+        "You are a QA automation assistant. Generate negative test cases that must fail or be rejected: invalid inputs (wrong format, wrong type, missing required values, injection strings), authentication and authorization failures (missing, expired or foreign credentials), and boundary values (empty, too long, minimum and maximum, off by one). Base every case on the recorded form fields and API request fields listed, naming them exactly, and state the expected error in expected. Do not repeat existing cases. Respond in {}. Return JSON with array negative_cases. Each item: {{title, steps, expected, priority}}. Return only JSON.",
+        language
+    )
+}
+
+/// The fields and endpoints negative cases are built on
+pub(crate) fn push_negative_grounding(
+    body: &mut String,
+    fields: &[RecordedField],
+    endpoints: &[EndpointShape],
+) {
+    if !fields.is_empty() {
+        body.push_str("\nRecorded form fields:\n");
+        for field in fields {
+            body.push_str(&format!("- {}", truncate(&field.selector, 120)));
+            if let Some(label) = field.label.as_ref() {
+                body.push_str(&format!(" (label: {})", truncate(label, 80)));
+            }
+            if let Some(input_type) = field.input_type.as_ref() {
+                body.push_str(&format!(" type={}", input_type));
+            }
+            body.push('\n');
+        }
+    }
+    if !endpoints.is_empty() {
+        body.push_str("\nRecorded API requests:\n");
+        for endpoint in endpoints {
+            body.push_str(&format!("- {} {}", endpoint.method, endpoint.path));
+            if !endpoint.statuses.is_empty() {
+                let statuses: Vec<String> =
+                    endpoint.statuses.iter().map(|s| s.to_string()).collect();
+                body.push_str(&format!(" (answered {})", statuses.join(", ")));
+            }
+            body.push('\n');
+            for field in &endpoint.body_fields {
+                body.push_str(&format!("  - body {}\n", field));
+            }
+        }
+    }
+    if fields.is_empty() && endpoints.is_empty() {
+        body.push_str(
+            "\nNo form fields or API requests were recorded; use the fields named in the events.\n",
+        );
+    }
+}
+
 pub(crate) fn build_test_user_prompt(
     session: &QaSession,
     checkpoint: &QaCheckpoint,
@@ -75,6 +181,7 @@ pub(crate) fn build_test_user_prompt(
         "Checkpoint seq: {} (events {} to {})\n",
         checkpoint.seq, checkpoint.start_event_seq, checkpoint.end_event_seq
     ));
+    push_checkpoint_annotations(&mut body, checkpoint);
     if let Some(summary) = summary {
         body.push_str(&format!("Checkpoint summary: {}\n", summary.summary_text));
     }
@@ -177,3 +284,53 @@ pub(crate) fn build_explore_user_prompt(
 
     body
 }
+
+pub(crate) fn build_visual_system_prompt(language: &str) -> String {
+    format!(
+        "You are a QA assistant reviewing screenshots of a recorded browser flow. Describe what each screenshot shows and look for UI anomalies: error toasts or banners, validation messages, broken or overlapping layout, cut-off text, missing images, blank screens, endless spinners. Respond in {}. Return JSON with arrays: observations, anomalies. Each item is one sentence naming its screenshot number. Leave anomalies empty when the screens look right. Return only JSON.",
+        language
+    )
+}
+
+/// `captions` describe the attached screenshots in order
+pub(crate) fn build_visual_user_prompt(
+    session: &QaSession,
+    checkpoint: &QaCheckpoint,
+    captions: &[String],
+    language: &str,
+) -> String {
+    let mut body = String::new();
+    body.push_str(&format!("Session goal: {}\n", session.goal));
+    body.push_str(&format!("Response language: {}\n", language));
+    if let Some(url) = session.target_url.as_ref() {
+        body.push_str(&format!("Target URL: {}\n", url));
+    }
+    body.push_str(&format!(
+        "Checkpoint seq: {} (events {} to {})\n",
+        checkpoint.seq, checkpoint.start_event_seq, checkpoint.end_event_seq
+    ));
+    push_checkpoint_annotations(&mut body, checkpoint);
+
+    body.push_str("\nAttached screenshots, in order:\n");
+    for caption in captions {
+        body.push_str("- ");
+        body.push_str(caption);
+        body.push('\n');
+    }
+
+    body
+}
+
+/// What the vision model saw in the checkpoint's screenshots, followed by
+/// `instruction` on how to use it
+pub(crate) fn push_visual_findings(body: &mut String, visual: &VisualOutput, instruction: &str) {
+    body.push_str("\nScreenshot review:\n");
+    for observation in &visual.observations {
+        body.push_str(&format!("- Observed: {}\n", observation));
+    }
+    for anomaly in &visual.anomalies {
+        body.push_str(&format!("- UI anomaly: {}\n", anomaly));
+    }
+    body.push_str(instruction);
+    body.push('\n');
+}
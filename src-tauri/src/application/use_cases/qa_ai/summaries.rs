@@ -1,16 +1,18 @@
-use super::{QaAiUseCase, PROMPT_VERSION};
+use super::api_links::actions_json;
+use super::QaAiUseCase;
 use crate::application::use_cases::qa_ai::event_text::{
     build_chunked_event_text, build_input_summary,
 };
-use crate::application::use_cases::qa_ai::hashing::{hash_input, normalize_language};
+use crate::application::use_cases::qa_ai::hashing::hash_input;
 use crate::application::use_cases::qa_ai::llm_output::extract_json_payload;
 use crate::application::use_cases::qa_ai::prompts::{
-    build_summary_system_prompt, build_summary_user_prompt,
+    build_summary_user_prompt, push_action_api_calls, SUMMARIZE_ACTION_API_CALLS,
 };
 use crate::application::use_cases::qa_ai::types::SummaryOutput;
 use crate::domain::error::{AppError, Result};
 use crate::domain::llm_config::LLMConfig;
 use crate::domain::qa_checkpoint::{QaCheckpointSummary, QaLlmRun};
+use crate::domain::qa_prompts::QaPromptKind;
 use crate::infrastructure::response::clean_llm_response;
 use uuid::Uuid;
 
@@ -47,15 +49,22 @@ impl QaAiUseCase {
 
         let chunked = build_chunked_event_text(&events);
         let input_summary = build_input_summary(&session, &checkpoint, chunked.len());
-        let language = normalize_language(output_language);
-        let system_prompt = build_summary_system_prompt(&language);
-        let user_prompt = build_summary_user_prompt(&session, &checkpoint, &chunked, &language);
+        let output = self.output_language(output_language);
+        let language = output.language.clone();
+        let system_prompt = self.system_prompt(QaPromptKind::CheckpointSummary, &language);
+        let mut user_prompt =
+            build_summary_user_prompt(&session, &checkpoint, &chunked, &language);
+        let actions = self.action_api_calls(session_id, &events).await?;
+        push_action_api_calls(
+            &mut user_prompt,
+            &actions,
+            SUMMARIZE_ACTION_API_CALLS,
+        );
 
-        let raw_output = self
-            .llm_client
-            .generate(config, &system_prompt, &user_prompt)
+        let completion = self
+            .complete_in_language(config, &system_prompt.text, &user_prompt, &output)
             .await?;
-        let cleaned = clean_llm_response(&raw_output);
+        let cleaned = clean_llm_response(&completion.text);
         let normalized = extract_json_payload(&cleaned);
 
         let parsed = serde_json::from_str::<SummaryOutput>(&normalized).ok();
@@ -87,6 +96,7 @@ impl QaAiUseCase {
                 summary_text,
                 entities_json,
                 risks_json,
+                actions_json(&actions),
                 created_at,
             )
             .await?;
@@ -96,13 +106,16 @@ impl QaAiUseCase {
             scope: "checkpoint_summary".to_string(),
             scope_id: checkpoint_id.to_string(),
             model: config.model.clone(),
-            prompt_version: Some(PROMPT_VERSION.to_string()),
+            prompt_version: Some(system_prompt.version),
             input_digest: Some(hash_input(&input_summary, &config.model)),
             input_summary: Some(input_summary),
             output_json,
             created_at,
+            usage: completion.usage,
         };
-        self.checkpoint_repository.insert_llm_run(&run).await?;
+        self.checkpoint_repository
+            .insert_llm_run(&run, &completion.bodies)
+            .await?;
 
         Ok(summary)
     }
@@ -1,24 +1,35 @@
-use super::{QaAiUseCase, PROMPT_VERSION};
+use super::api_links::summary_actions;
+use super::assertions::calls_during;
+use super::QaAiUseCase;
 use crate::application::use_cases::qa_ai::event_text::{
     build_chunked_event_text, build_input_summary, preview_text,
 };
-use crate::application::use_cases::qa_ai::hashing::{hash_input, normalize_language};
+use crate::application::use_cases::qa_ai::grounding::{endpoint_shapes, recorded_fields};
+use crate::application::use_cases::qa_ai::hashing::hash_input;
 use crate::application::use_cases::qa_ai::llm_output::extract_json_payload;
-use crate::application::use_cases::qa_ai::prompts::{build_test_system_prompt, build_test_user_prompt};
+use crate::application::use_cases::qa_ai::prompts::{
+    build_test_user_prompt, push_action_api_calls, push_negative_grounding, ASSERT_AFTER_ACTION,
+};
 use crate::application::use_cases::qa_ai::types::TestCaseOutput;
 use crate::domain::error::{AppError, Result};
 use crate::domain::llm_config::LLMConfig;
-use crate::domain::qa_checkpoint::{QaLlmRun, QaTestCase};
+use crate::domain::qa_checkpoint::{QaLlmRun, QaTestCase, QaTestGenerationMode};
+use crate::domain::qa_prompts::QaPromptKind;
 use crate::infrastructure::response::clean_llm_response;
 use uuid::Uuid;
 
 impl QaAiUseCase {
+    /// Generate test cases for a checkpoint. In `Negative` mode the model is
+    /// given the recorded form fields and the request shapes of the API
+    /// calls made during the checkpoint, and every case is stored as
+    /// `negative`.
     pub async fn generate_test_cases(
         &self,
         session_id: &str,
         checkpoint_id: &str,
         config: &LLMConfig,
         output_language: &str,
+        mode: QaTestGenerationMode,
     ) -> Result<Vec<QaTestCase>> {
         let session = self.session_repository.get_session(session_id).await?;
         let checkpoint = self
@@ -54,9 +65,14 @@ impl QaAiUseCase {
             .await?;
 
         let input_summary = build_input_summary(&session, &checkpoint, chunked.len());
-        let language = normalize_language(output_language);
-        let system_prompt = build_test_system_prompt(&language);
-        let user_prompt = build_test_user_prompt(
+        let output = self.output_language(output_language);
+        let language = output.language.clone();
+        let prompt_kind = match mode {
+            QaTestGenerationMode::Standard => QaPromptKind::TestCases,
+            QaTestGenerationMode::Negative => QaPromptKind::NegativeCases,
+        };
+        let system_prompt = self.system_prompt(prompt_kind, &language);
+        let mut user_prompt = build_test_user_prompt(
             &session,
             &checkpoint,
             summary.as_ref(),
@@ -64,12 +80,30 @@ impl QaAiUseCase {
             &existing_cases,
             &language,
         );
+        let actions = match summary.as_ref().and_then(summary_actions) {
+            Some(actions) => actions,
+            None => self.action_api_calls(session_id, &events).await?,
+        };
+        push_action_api_calls(&mut user_prompt, &actions, ASSERT_AFTER_ACTION);
+        if mode == QaTestGenerationMode::Negative {
+            let calls = calls_during(
+                &self
+                    .api_call_repository
+                    .list_calls_by_session(session_id)
+                    .await?,
+                &events,
+            );
+            push_negative_grounding(
+                &mut user_prompt,
+                &recorded_fields(&events),
+                &endpoint_shapes(&calls),
+            );
+        }
 
-        let raw_output = self
-            .llm_client
-            .generate(config, &system_prompt, &user_prompt)
+        let completion = self
+            .complete_in_language(config, &system_prompt.text, &user_prompt, &output)
             .await?;
-        let cleaned = clean_llm_response(&raw_output);
+        let cleaned = clean_llm_response(&completion.text);
         let normalized = extract_json_payload(&cleaned);
         let parsed = serde_json::from_str::<TestCaseOutput>(&normalized).map_err(|err| {
             let snippet = preview_text(&normalized, 600);
@@ -82,62 +116,101 @@ impl QaAiUseCase {
         let output_json = serde_json::to_string(&parsed).unwrap_or_else(|_| normalized.clone());
         let created_at = chrono::Utc::now().timestamp_millis();
 
+        // Suggested response assertions describe the flow succeeding, so
+        // failure cases go without them
+        let assertions_json = match mode {
+            QaTestGenerationMode::Standard => self.assertions_json_for(session_id, &events).await?,
+            QaTestGenerationMode::Negative => None,
+        };
+
         let mut stored = Vec::new();
-        stored.extend(
-            self.store_test_cases(
-                checkpoint_id,
-                &session,
-                &parsed.negative_cases,
-                "negative",
-                created_at,
-            )
-            .await?,
-        );
-        stored.extend(
-            self.store_test_cases(
-                checkpoint_id,
-                &session,
-                &parsed.edge_cases,
-                "edge",
-                created_at,
-            )
-            .await?,
-        );
-        stored.extend(
-            self.store_test_cases(
-                checkpoint_id,
-                &session,
-                &parsed.exploratory_charters,
-                "exploratory",
-                created_at,
-            )
-            .await?,
-        );
-        if session.session_type == "api" {
+        if mode == QaTestGenerationMode::Negative {
+            // Edge cases the model adds anyway are failure cases here too
+            let cases: Vec<_> = parsed
+                .negative_cases
+                .iter()
+                .chain(&parsed.edge_cases)
+                .cloned()
+                .collect();
+            stored.extend(
+                self.store_test_cases(
+                    checkpoint_id,
+                    &session,
+                    &cases,
+                    "negative",
+                    None,
+                    created_at,
+                )
+                .await?,
+            );
+        } else {
+            stored.extend(
+                self.store_test_cases(
+                    checkpoint_id,
+                    &session,
+                    &parsed.negative_cases,
+                    "negative",
+                    None,
+                    created_at,
+                )
+                .await?,
+            );
             stored.extend(
                 self.store_test_cases(
                     checkpoint_id,
                     &session,
-                    &parsed.api_gap_checks,
-                    "api_gap",
+                    &parsed.edge_cases,
+                    "edge",
+                    assertions_json.as_deref(),
                     created_at,
                 )
                 .await?,
             );
+            stored.extend(
+                self.store_test_cases(
+                    checkpoint_id,
+                    &session,
+                    &parsed.exploratory_charters,
+                    "exploratory",
+                    assertions_json.as_deref(),
+                    created_at,
+                )
+                .await?,
+            );
+            if session.session_type == "api" {
+                stored.extend(
+                    self.store_test_cases(
+                        checkpoint_id,
+                        &session,
+                        &parsed.api_gap_checks,
+                        "api_gap",
+                        assertions_json.as_deref(),
+                        created_at,
+                    )
+                    .await?,
+                );
+            }
         }
 
+        let scope = match mode {
+            QaTestGenerationMode::Standard => "test_cases",
+            QaTestGenerationMode::Negative => "negative_test_cases",
+        };
         let run = QaLlmRun {
             id: Uuid::new_v4().to_string(),
-            scope: "test_cases".to_string(),
+            scope: scope.to_string(),
             scope_id: checkpoint_id.to_string(),
             model: config.model.clone(),
-            prompt_version: Some(PROMPT_VERSION.to_string()),
+            prompt_version: Some(system_prompt.version),
             input_digest: Some(hash_input(&input_summary, &config.model)),
             input_summary: Some(input_summary),
             output_json,
             created_at,
+            usage: completion.usage,
         };
-        self.checkpoint_repository.insert_llm_run(&run).await?;
+        self.checkpoint_repository
+            .insert_llm_run(&run, &completion.bodies)
+            .await?;
 
         Ok(stored)
     }
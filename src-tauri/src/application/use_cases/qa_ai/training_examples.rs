@@ -0,0 +1,163 @@
+use super::QaAiUseCase;
+use crate::application::use_cases::qa_ai::event_text::build_chunked_event_text;
+use crate::application::use_cases::qa_ai::prompts::build_test_user_prompt;
+use crate::application::use_cases::qa_ai::types::{TestCaseInput, TestCaseOutput};
+use crate::domain::error::{AppError, Result};
+use crate::domain::qa_checkpoint::{QaTestCase, QaTrainingExample};
+use crate::domain::qa_prompts::QaPromptKind;
+
+impl QaAiUseCase {
+    /// Training examples for a student model that writes test cases: one
+    /// per checkpoint of the session with events and test cases. The prompt
+    /// is the one test case generation sends for the checkpoint, and the
+    /// expected output its test cases in the JSON shape that prompt asks for.
+    pub async fn build_training_examples(
+        &self,
+        session_id: &str,
+        output_language: &str,
+    ) -> Result<Vec<QaTrainingExample>> {
+        let session_id = session_id.trim();
+        if session_id.is_empty() {
+            return Err(AppError::ValidationError(
+                "Session id is required.".to_string(),
+            ));
+        }
+
+        let session = self.session_repository.get_session(session_id).await?;
+        let language = self.output_language(output_language).language;
+        let system_prompt = self.system_prompt(QaPromptKind::TestCases, &language).text;
+
+        let mut examples = Vec::new();
+        for checkpoint in self
+            .checkpoint_repository
+            .list_checkpoints(session_id)
+            .await?
+        {
+            let cases = self
+                .checkpoint_repository
+                .list_test_cases_for_checkpoint(&checkpoint.id)
+                .await?;
+            let Some(output) = expected_test_case_output(&cases) else {
+                continue;
+            };
+            let events = self
+                .event_repository
+                .list_events_range(
+                    session_id,
+                    checkpoint.start_event_seq,
+                    checkpoint.end_event_seq,
+                )
+                .await?;
+            if events.is_empty() {
+                continue;
+            }
+
+            let summary = self
+                .checkpoint_repository
+                .get_checkpoint_summary(&checkpoint.id)
+                .await?;
+            let chunked = build_chunked_event_text(&events);
+            // The student learns to write every case at once, so no case is
+            // listed as existing
+            let user_prompt = build_test_user_prompt(
+                &session,
+                &checkpoint,
+                summary.as_ref(),
+                &chunked,
+                &[],
+                &language,
+            );
+            let expected_output = serde_json::to_string(&output).map_err(|err| {
+                AppError::Internal(format!("Failed to serialize test cases: {}", err))
+            })?;
+
+            examples.push(QaTrainingExample {
+                session_id: session.id.clone(),
+                checkpoint_id: checkpoint.id.clone(),
+                prompt: format!("{}\n\n{}", system_prompt, user_prompt),
+                expected_output,
+                test_case_count: test_case_count(&output),
+            });
+        }
+
+        Ok(examples)
+    }
+}
+
+/// The cases grouped by type as test case generation returns them. Positive
+/// cases come from exploring and have no place in that output, so they are
+/// left out; `None` when no case is left.
+fn expected_test_case_output(cases: &[QaTestCase]) -> Option<TestCaseOutput> {
+    let mut output = TestCaseOutput {
+        negative_cases: Vec::new(),
+        edge_cases: Vec::new(),
+        exploratory_charters: Vec::new(),
+        api_gap_checks: Vec::new(),
+    };
+    for case in cases {
+        let group = match case.case_type.as_str() {
+            "negative" => &mut output.negative_cases,
+            "edge" => &mut output.edge_cases,
+            "exploratory" => &mut output.exploratory_charters,
+            "api_gap" => &mut output.api_gap_checks,
+            _ => continue,
+        };
+        group.push(TestCaseInput {
+            title: case.title.clone(),
+            steps: serde_json::from_str(&case.steps_json).unwrap_or_default(),
+            expected: case.expected.clone(),
+            priority: case.priority.clone(),
+        });
+    }
+    (test_case_count(&output) > 0).then_some(output)
+}
+
+fn test_case_count(output: &TestCaseOutput) -> usize {
+    output.negative_cases.len()
+        + output.edge_cases.len()
+        + output.exploratory_charters.len()
+        + output.api_gap_checks.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn case(case_type: &str, title: &str) -> QaTestCase {
+        QaTestCase {
+            id: title.to_string(),
+            session_id: "session".to_string(),
+            checkpoint_id: Some("checkpoint".to_string()),
+            case_type: case_type.to_string(),
+            title: title.to_string(),
+            steps_json: r#"["Open the login page","Submit"]"#.to_string(),
+            expected: Some("An error is shown".to_string()),
+            priority: None,
+            status: None,
+            dedup_hash: String::new(),
+            assertions_json: None,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn cases_are_grouped_like_generated_output() {
+        let output = expected_test_case_output(&[
+            case("positive", "Valid login"),
+            case("negative", "Empty password"),
+            case("edge", "Long username"),
+            case("api_gap", "Missing auth header"),
+        ])
+        .unwrap();
+        assert_eq!(test_case_count(&output), 3);
+        assert_eq!(output.negative_cases[0].title, "Empty password");
+        assert_eq!(output.negative_cases[0].steps.len(), 2);
+        assert_eq!(output.api_gap_checks[0].title, "Missing auth header");
+        assert!(output.exploratory_charters.is_empty());
+    }
+
+    #[test]
+    fn only_positive_cases_give_no_example() {
+        assert!(expected_test_case_output(&[case("positive", "Valid login")]).is_none());
+    }
+}
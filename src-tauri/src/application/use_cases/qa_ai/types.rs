@@ -38,3 +38,13 @@ pub(crate) struct ExploreOutput {
     #[serde(default)]
     pub(crate) exploratory_charters: Vec<TestCaseInput>,
 }
+
+#[derive(Debug, Default, Deserialize, serde::Serialize)]
+pub(crate) struct VisualOutput {
+    /// What the screenshots show, one line each
+    #[serde(default)]
+    pub(crate) observations: Vec<String>,
+    /// UI problems such as error toasts or broken layout
+    #[serde(default)]
+    pub(crate) anomalies: Vec<String>,
+}
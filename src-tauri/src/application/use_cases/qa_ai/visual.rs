@@ -0,0 +1,255 @@
+use super::QaAiUseCase;
+use crate::application::use_cases::qa_ai::hashing::hash_input;
+use crate::application::use_cases::qa_ai::llm_output::extract_json_payload;
+use crate::application::use_cases::qa_ai::prompts::build_visual_user_prompt;
+use crate::application::use_cases::qa_ai::types::VisualOutput;
+use crate::domain::llm_config::LLMConfig;
+use crate::domain::qa_checkpoint::{QaCheckpoint, QaLlmRun};
+use crate::domain::qa_event::QaEvent;
+use crate::domain::qa_prompts::QaPromptKind;
+use crate::domain::qa_session::QaSession;
+use crate::domain::qa_vision::QaVisionSettings;
+use crate::infrastructure::llm_clients::ImageInput;
+use crate::infrastructure::response::clean_llm_response;
+use std::path::Path;
+use tracing::warn;
+use uuid::Uuid;
+
+impl QaAiUseCase {
+    /// Shows the checkpoint's screenshots to a vision model when `qaVision`
+    /// is on and records the call as a `visual_analysis` run. `None` when it
+    /// is off, no screenshot could be read or the model call failed; the
+    /// exploration goes on without visual findings then.
+    pub(super) async fn analyze_screenshots(
+        &self,
+        session: &QaSession,
+        checkpoint: &QaCheckpoint,
+        events: &[QaEvent],
+        config: &LLMConfig,
+        input_summary: &str,
+        language: &str,
+    ) -> Option<(VisualOutput, QaLlmRun)> {
+        let settings = self.settings.get::<QaVisionSettings>();
+        if !settings.enabled {
+            return None;
+        }
+
+        let mut images = Vec::new();
+        let mut captions = Vec::new();
+        for event in pick_screenshots(events, checkpoint, settings.max_screenshots) {
+            let Some(path) = event.screenshot_path.as_deref() else {
+                continue;
+            };
+            if let Some(image) = load_screenshot(Path::new(path), settings.max_image_kb).await {
+                images.push(image);
+                captions.push(screenshot_caption(captions.len() + 1, event));
+            }
+        }
+        if images.is_empty() {
+            return None;
+        }
+
+        let vision_config = LLMConfig {
+            model: settings.model_or(&config.model).to_string(),
+            ..config.clone()
+        };
+        let system = self.system_prompt(QaPromptKind::VisualAnalysis, language);
+        let user = build_visual_user_prompt(session, checkpoint, &captions, language);
+        let completion = match self
+            .complete_audited_with_images(&vision_config, &system.text, &user, &images)
+            .await
+        {
+            Ok(completion) => completion,
+            Err(err) => {
+                warn!(error = %err, checkpoint_id = %checkpoint.id, "Screenshot review failed");
+                return None;
+            }
+        };
+        let normalized = extract_json_payload(&clean_llm_response(&completion.text));
+        let output = match serde_json::from_str::<VisualOutput>(&normalized) {
+            Ok(output) => output,
+            Err(err) => {
+                warn!(error = %err, checkpoint_id = %checkpoint.id, "Unreadable screenshot review");
+                return None;
+            }
+        };
+
+        let input_summary = format!("{} screenshots={}", input_summary, images.len());
+        let run = QaLlmRun {
+            id: Uuid::new_v4().to_string(),
+            scope: "visual_analysis".to_string(),
+            scope_id: checkpoint.id.clone(),
+            model: vision_config.model.clone(),
+            prompt_version: Some(system.version),
+            input_digest: Some(hash_input(&input_summary, &vision_config.model)),
+            input_summary: Some(input_summary),
+            output_json: serde_json::to_string(&output).unwrap_or(normalized),
+            created_at: chrono::Utc::now().timestamp_millis(),
+            usage: completion.usage,
+        };
+        if let Err(err) = self
+            .checkpoint_repository
+            .insert_llm_run(&run, &completion.bodies)
+            .await
+        {
+            warn!(error = %err, checkpoint_id = %checkpoint.id, "Failed to record screenshot review");
+        }
+
+        Some((output, run))
+    }
+}
+
+/// Up to `max` of the checkpoint's screenshot events, spread evenly from
+/// the first to the last so the final screen is always seen
+fn pick_screenshots<'a>(
+    events: &'a [QaEvent],
+    checkpoint: &QaCheckpoint,
+    max: usize,
+) -> Vec<&'a QaEvent> {
+    let shots: Vec<&QaEvent> = events
+        .iter()
+        .filter(|event| {
+            event.seq >= checkpoint.start_event_seq
+                && event.seq <= checkpoint.end_event_seq
+                && event.screenshot_path.is_some()
+        })
+        .collect();
+    if shots.len() <= max {
+        return shots;
+    }
+    match max {
+        0 => Vec::new(),
+        1 => vec![shots[shots.len() - 1]],
+        _ => (0..max)
+            .map(|i| shots[i * (shots.len() - 1) / (max - 1)])
+            .collect(),
+    }
+}
+
+/// `None` when the file is missing, unreadable or over `max_kb`
+async fn load_screenshot(path: &Path, max_kb: u64) -> Option<ImageInput> {
+    let size = tokio::fs::metadata(path).await.ok()?.len();
+    if size > max_kb.saturating_mul(1024) {
+        return None;
+    }
+    let mime_type = match path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("webp") => "image/webp",
+        // Captures are saved as PNG
+        _ => "image/png",
+    };
+    let data = tokio::fs::read(path).await.ok()?;
+    Some(ImageInput {
+        mime_type: mime_type.to_string(),
+        data,
+    })
+}
+
+fn screenshot_caption(number: usize, event: &QaEvent) -> String {
+    let mut caption = format!(
+        "Screenshot {}: after event #{} ({})",
+        number, event.seq, event.event_type
+    );
+    if let Some(url) = event.url.as_ref() {
+        caption.push_str(&format!(" on {}", url));
+    }
+    caption
+}
+
+/// The summary's risks with the anomalies it doesn't already mention
+pub(super) fn merge_visual_risks(
+    risks: Option<Vec<String>>,
+    anomalies: &[String],
+) -> Option<Vec<String>> {
+    let mut risks = risks.unwrap_or_default();
+    for anomaly in anomalies {
+        let anomaly_lower = anomaly.to_lowercase();
+        if !risks
+            .iter()
+            .any(|risk| risk.to_lowercase().contains(&anomaly_lower))
+        {
+            risks.push(format!("Visual: {}", anomaly));
+        }
+    }
+    (!risks.is_empty()).then_some(risks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(seq: i64, screenshot: bool) -> QaEvent {
+        QaEvent {
+            id: seq.to_string(),
+            session_id: "session".to_string(),
+            run_id: None,
+            checkpoint_id: None,
+            seq,
+            ts: seq,
+            event_type: "click".to_string(),
+            origin: None,
+            recording_mode: None,
+            selector: None,
+            element_text: None,
+            value: None,
+            url: None,
+            screenshot_id: screenshot.then(|| format!("shot-{}", seq)),
+            screenshot_path: screenshot.then(|| format!("/tmp/shot-{}.png", seq)),
+            annotated_screenshot_path: None,
+            meta_json: None,
+        }
+    }
+
+    fn checkpoint(start: i64, end: i64) -> QaCheckpoint {
+        QaCheckpoint {
+            id: "checkpoint".to_string(),
+            session_id: "session".to_string(),
+            seq: 1,
+            title: None,
+            notes: None,
+            start_event_seq: start,
+            end_event_seq: end,
+            created_at: 0,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn screenshots_are_spread_over_the_checkpoint() {
+        let events: Vec<QaEvent> = (1..=12).map(|seq| event(seq, seq % 2 == 0)).collect();
+        let seqs = |picked: Vec<&QaEvent>| picked.iter().map(|e| e.seq).collect::<Vec<_>>();
+
+        assert_eq!(
+            seqs(pick_screenshots(&events, &checkpoint(1, 12), 3)),
+            vec![2, 6, 12]
+        );
+        assert_eq!(
+            seqs(pick_screenshots(&events, &checkpoint(1, 12), 1)),
+            vec![12]
+        );
+        assert_eq!(
+            seqs(pick_screenshots(&events, &checkpoint(3, 7), 5)),
+            vec![4, 6]
+        );
+    }
+
+    #[test]
+    fn anomalies_already_in_risks_are_not_repeated() {
+        let risks = merge_visual_risks(
+            Some(vec!["Error toast 'Server error' after submit".to_string()]),
+            &[
+                "error toast 'server error' after submit".to_string(),
+                "Footer overlaps the form".to_string(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(risks.len(), 2);
+        assert_eq!(risks[1], "Visual: Footer overlaps the form");
+        assert_eq!(merge_visual_risks(None, &[]), None);
+    }
+}
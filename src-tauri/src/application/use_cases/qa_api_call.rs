@@ -1,3 +1,4 @@
+use crate::application::use_cases::qa_redaction::QaRedaction;
 use crate::domain::error::{AppError, Result};
 use crate::domain::qa_api_call::QaApiCall;
 use crate::infrastructure::db::qa_api_calls::QaApiCallRepository;
@@ -7,14 +8,20 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 const BODY_PREVIEW_LIMIT: usize = 4000;
+/// Response bodies feed typegen, so keep more of them than request previews.
+const RESPONSE_BODY_LIMIT: usize = 64_000;
 
 pub struct QaApiCallUseCase {
     repository: Arc<QaApiCallRepository>,
+    redaction: Arc<QaRedaction>,
 }
 
 impl QaApiCallUseCase {
-    pub fn new(repository: Arc<QaApiCallRepository>) -> Self {
-        Self { repository }
+    pub fn new(repository: Arc<QaApiCallRepository>, redaction: Arc<QaRedaction>) -> Self {
+        Self {
+            repository,
+            redaction,
+        }
     }
 
     pub async fn record_api_call(
@@ -41,22 +48,36 @@ impl QaApiCallUseCase {
             ));
         }
 
+        // Secrets are masked before anything is hashed or stored
+        let rules = self.redaction.rules();
+        let url = rules.url(url);
+        let request_headers_json = request_headers_json.map(|headers| rules.document(&headers));
+        let response_headers_json = response_headers_json.map(|headers| rules.document(&headers));
+        let request_body = request_body.map(|body| rules.document(&body));
+        let response_body = response_body.map(|body| rules.document(&body));
+
         let request_body_hash = request_body.as_ref().map(|body| hash_body(body));
         let response_body_hash = response_body.as_ref().map(|body| hash_body(body));
-        let request_body_json = request_body.as_ref().map(|body| truncate_body(body));
+        let request_body_json = request_body
+            .as_ref()
+            .map(|body| truncate_body(body, BODY_PREVIEW_LIMIT));
+        let response_body_json = response_body
+            .as_ref()
+            .map(|body| truncate_body(body, RESPONSE_BODY_LIMIT));
 
         let call = QaApiCall {
             id: Uuid::new_v4().to_string(),
             session_id: session_id.to_string(),
             run_id: run_id.to_string(),
             method: method.to_string(),
-            url: url.to_string(),
+            url,
             request_headers_json,
             request_body_json,
             request_body_hash,
             response_status,
             response_headers_json,
             response_body_hash,
+            response_body_json,
             timing_ms,
             created_at: chrono::Utc::now().timestamp_millis(),
         };
@@ -64,6 +85,38 @@ impl QaApiCallUseCase {
         self.repository.insert_call(&call).await?;
         Ok(call)
     }
+
+    /// Apply the current redaction rules to calls recorded before them.
+    /// Returns the number of calls changed.
+    pub async fn redact_session(&self, session_id: &str) -> Result<u64> {
+        let rules = self.redaction.rules();
+        let mut changed = 0;
+        for call in self.repository.list_calls_by_session(session_id).await? {
+            let document = |value: &Option<String>| value.as_deref().map(|v| rules.document(v));
+            let redacted = QaApiCall {
+                url: rules.url(&call.url),
+                request_headers_json: document(&call.request_headers_json),
+                request_body_json: document(&call.request_body_json),
+                response_headers_json: document(&call.response_headers_json),
+                response_body_json: document(&call.response_body_json),
+                ..call.clone()
+            };
+            let unchanged = redacted.url == call.url
+                && redacted.request_headers_json == call.request_headers_json
+                && redacted.request_body_json == call.request_body_json
+                && redacted.response_headers_json == call.response_headers_json
+                && redacted.response_body_json == call.response_body_json;
+            if !unchanged {
+                self.repository.update_call_contents(&redacted).await?;
+                changed += 1;
+            }
+        }
+        Ok(changed)
+    }
+
+    pub async fn list_session_calls(&self, session_id: &str) -> Result<Vec<QaApiCall>> {
+        self.repository.list_calls_by_session(session_id).await
+    }
 }
 
 fn hash_body(body: &str) -> String {
@@ -72,11 +125,15 @@ fn hash_body(body: &str) -> String {
     format!("{:x}", hasher.finish())
 }
 
-fn truncate_body(body: &str) -> String {
+fn truncate_body(body: &str, limit: usize) -> String {
     let trimmed = body.trim();
-    if trimmed.len() <= BODY_PREVIEW_LIMIT {
+    if trimmed.len() <= limit {
         trimmed.to_string()
     } else {
-        format!("{}...", &trimmed[..BODY_PREVIEW_LIMIT])
+        let mut end = limit;
+        while !trimmed.is_char_boundary(end) {
+            end -= 1;
+        }
+        format!("{}...", &trimmed[..end])
     }
 }
@@ -0,0 +1,158 @@
+use crate::domain::error::Result;
+use crate::domain::qa_auth::{
+    OAuthToken, QaAuthProfile, QaAuthProfileStatus, QaAuthSecret, QaAuthSettings,
+};
+use crate::infrastructure::config::{ConfigService, SettingsStore};
+use crate::infrastructure::oauth::OAuthClient;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+/// A token and the profile it was issued for; editing the profile
+/// invalidates it
+struct CachedToken {
+    profile: QaAuthProfile,
+    token: OAuthToken,
+}
+
+/// Tokens of the `qaAuthProfiles` settings, fetched on first use and kept
+/// in memory only. The lock is held while a token is fetched, so requests
+/// running in parallel share one token instead of each fetching their own.
+pub struct QaAuthUseCase {
+    settings: Arc<SettingsStore>,
+    secrets: ConfigService,
+    client: OAuthClient,
+    tokens: Mutex<HashMap<String, CachedToken>>,
+}
+
+impl QaAuthUseCase {
+    pub fn new(settings: Arc<SettingsStore>) -> Self {
+        Self {
+            settings,
+            secrets: ConfigService::new(),
+            client: OAuthClient::new(),
+            tokens: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Header name and value to sign a request with. A cached token close
+    /// to expiry is refreshed, or replaced when the refresh fails.
+    pub async fn authorization(&self, profile_id: &str) -> Result<(String, String)> {
+        let profile = self.profile(profile_id)?;
+        let mut tokens = self.tokens.lock().await;
+        let now = chrono::Utc::now().timestamp_millis();
+        let cached = tokens
+            .remove(&profile.id)
+            .filter(|cached| cached.profile == profile);
+
+        let token = match cached {
+            Some(cached) if cached.token.is_fresh(now) => cached.token,
+            Some(CachedToken {
+                token:
+                    OAuthToken {
+                        refresh_token: Some(refresh_token),
+                        ..
+                    },
+                ..
+            }) => match self.refresh(&profile, &refresh_token).await {
+                Ok(token) => token,
+                Err(err) => {
+                    warn!(profile = %profile.id, error = %err, "Token refresh failed; requesting a new token");
+                    self.request(&profile).await?
+                }
+            },
+            _ => self.request(&profile).await?,
+        };
+
+        let header = (
+            profile.header_name.trim().to_string(),
+            profile.header_value(&token.access_token),
+        );
+        tokens.insert(profile.id.clone(), CachedToken { profile, token });
+        Ok(header)
+    }
+
+    /// Mark the profile's token as expired after the API rejected it, so
+    /// the next request refreshes it
+    pub async fn expire(&self, profile_id: &str) {
+        if let Some(cached) = self.tokens.lock().await.get_mut(profile_id.trim()) {
+            cached.token.expires_at = Some(0);
+        }
+    }
+
+    /// Fetch a new token right away, dropping the cached one. Returns when
+    /// the new token expires.
+    pub async fn test_profile(&self, profile_id: &str) -> Result<Option<i64>> {
+        let profile = self.profile(profile_id)?;
+        let mut tokens = self.tokens.lock().await;
+        tokens.remove(&profile.id);
+        let token = self.request(&profile).await?;
+        let expires_at = token.expires_at;
+        tokens.insert(profile.id.clone(), CachedToken { profile, token });
+        Ok(expires_at)
+    }
+
+    /// Store or, when empty, delete a secret of the profile. Its cached
+    /// token was issued with the old secret and is dropped.
+    pub async fn save_secret(
+        &self,
+        profile_id: &str,
+        secret: QaAuthSecret,
+        value: &str,
+    ) -> Result<()> {
+        let profile = self.profile(profile_id)?;
+        self.secrets
+            .save_qa_auth_secret(&profile.id, secret, value)?;
+        self.tokens.lock().await.remove(&profile.id);
+        Ok(())
+    }
+
+    pub async fn statuses(&self) -> Result<Vec<QaAuthProfileStatus>> {
+        let settings = self.settings.get::<QaAuthSettings>();
+        let tokens = self.tokens.lock().await;
+        settings
+            .profiles
+            .iter()
+            .map(|profile| {
+                let token = tokens
+                    .get(profile.id.trim())
+                    .filter(|cached| &cached.profile == profile)
+                    .map(|cached| &cached.token);
+                Ok(QaAuthProfileStatus {
+                    id: profile.id.clone(),
+                    has_client_secret: self.secret(profile, QaAuthSecret::ClientSecret)?.is_some(),
+                    has_password: self.secret(profile, QaAuthSecret::Password)?.is_some(),
+                    token_expires_at: token.and_then(|token| token.expires_at),
+                    token_cached: token.is_some(),
+                })
+            })
+            .collect()
+    }
+
+    fn profile(&self, profile_id: &str) -> Result<QaAuthProfile> {
+        self.settings
+            .get::<QaAuthSettings>()
+            .profile(profile_id)
+            .cloned()
+    }
+
+    fn secret(&self, profile: &QaAuthProfile, secret: QaAuthSecret) -> Result<Option<String>> {
+        self.secrets.find_qa_auth_secret(&profile.id, secret)
+    }
+
+    async fn request(&self, profile: &QaAuthProfile) -> Result<OAuthToken> {
+        let client_secret = self.secret(profile, QaAuthSecret::ClientSecret)?;
+        let password = self.secret(profile, QaAuthSecret::Password)?;
+        self.client
+            .request_token(profile, client_secret.as_deref(), password.as_deref())
+            .await
+    }
+
+    async fn refresh(&self, profile: &QaAuthProfile, refresh_token: &str) -> Result<OAuthToken> {
+        let client_secret = self.secret(profile, QaAuthSecret::ClientSecret)?;
+        self.client
+            .refresh_token(profile, client_secret.as_deref(), refresh_token)
+            .await
+    }
+}
@@ -1,4 +1,8 @@
+use crate::application::use_cases::qa_redaction::QaRedaction;
 use crate::domain::error::{AppError, Result};
+use crate::domain::qa_api_link::{
+    link_api_response, link_api_responses, link_window_start, QaApiLink,
+};
 use crate::domain::qa_event::{QaEvent, QaEventInput, QaEventPage, QaEventSummary};
 use crate::infrastructure::db::qa_events::QaEventRepository;
 use std::sync::Arc;
@@ -6,11 +10,15 @@ use uuid::Uuid;
 
 pub struct QaEventUseCase {
     repository: Arc<QaEventRepository>,
+    redaction: Arc<QaRedaction>,
 }
 
 impl QaEventUseCase {
-    pub fn new(repository: Arc<QaEventRepository>) -> Self {
-        Self { repository }
+    pub fn new(repository: Arc<QaEventRepository>, redaction: Arc<QaRedaction>) -> Self {
+        Self {
+            repository,
+            redaction,
+        }
     }
 
     pub async fn record_event(&self, session_id: &str, input: QaEventInput) -> Result<QaEvent> {
@@ -42,6 +50,7 @@ impl QaEventUseCase {
                 | "blur"
                 | "scroll"
                 | "resize"
+                | "file_upload"
         ) || event_type.starts_with("curl_")
             || event_type.starts_with("api_");
 
@@ -52,6 +61,7 @@ impl QaEventUseCase {
             )));
         }
 
+        let rules = self.redaction.rules();
         let event = QaEvent {
             id: Uuid::new_v4().to_string(),
             session_id: session_id.to_string(),
@@ -63,15 +73,62 @@ impl QaEventUseCase {
             origin: normalize_optional(input.origin),
             recording_mode: normalize_optional(input.recording_mode),
             selector: normalize_optional(input.selector),
-            element_text: normalize_optional(input.element_text),
-            value: normalize_value(input.value),
-            url: normalize_optional(input.url),
+            element_text: normalize_optional(input.element_text).map(|text| rules.text(&text)),
+            value: normalize_value(input.value).map(|value| rules.text(&value)),
+            url: normalize_optional(input.url).map(|url| rules.url(&url)),
             screenshot_id: None,
             screenshot_path: None,
-            meta_json: normalize_optional(input.meta_json),
+            annotated_screenshot_path: None,
+            meta_json: normalize_optional(input.meta_json).map(|meta| rules.document(&meta)),
         };
 
-        self.repository.insert_event(event).await
+        let event = self.repository.insert_event(event).await?;
+        if event.event_type == "api_response" {
+            // Linking is a best effort; `link_api_calls` redoes it
+            let _ = self.link_api_response(&event).await;
+        }
+        Ok(event)
+    }
+
+    /// Link a response to the action that triggered it, among the events
+    /// recorded shortly before it
+    async fn link_api_response(&self, response: &QaEvent) -> Result<Option<QaApiLink>> {
+        let recent = self
+            .repository
+            .list_events_since(&response.session_id, link_window_start(response))
+            .await?;
+        let link = link_api_response(response, &recent);
+        if let Some(link) = link.as_ref() {
+            self.repository.upsert_api_link(link).await?;
+        }
+        Ok(link)
+    }
+
+    /// Relink every response of the session, e.g. for sessions recorded
+    /// before responses were linked or after events were deleted
+    pub async fn link_api_calls(&self, session_id: &str) -> Result<Vec<QaApiLink>> {
+        let session_id = session_id.trim();
+        if session_id.is_empty() {
+            return Err(AppError::ValidationError(
+                "Session id is required.".to_string(),
+            ));
+        }
+        let events = self.repository.list_events(session_id).await?;
+        let links = link_api_responses(&events);
+        self.repository
+            .replace_api_links(session_id, &links)
+            .await?;
+        Ok(links)
+    }
+
+    pub async fn list_api_links(&self, session_id: &str) -> Result<Vec<QaApiLink>> {
+        let session_id = session_id.trim();
+        if session_id.is_empty() {
+            return Err(AppError::ValidationError(
+                "Session id is required.".to_string(),
+            ));
+        }
+        self.repository.list_api_links(session_id).await
     }
 
     pub async fn list_events(&self, session_id: &str) -> Result<Vec<QaEvent>> {
@@ -84,6 +141,37 @@ impl QaEventUseCase {
         self.repository.list_events(session_id).await
     }
 
+    /// Apply the current redaction rules to events recorded before them.
+    /// Returns the number of events changed.
+    pub async fn redact_session(&self, session_id: &str) -> Result<u64> {
+        let session_id = session_id.trim();
+        if session_id.is_empty() {
+            return Err(AppError::ValidationError(
+                "Session id is required.".to_string(),
+            ));
+        }
+        let rules = self.redaction.rules();
+        let mut changed = 0;
+        for event in self.repository.list_events(session_id).await? {
+            let redacted = QaEvent {
+                element_text: event.element_text.as_deref().map(|text| rules.text(text)),
+                value: event.value.as_deref().map(|value| rules.text(value)),
+                url: event.url.as_deref().map(|url| rules.url(url)),
+                meta_json: event.meta_json.as_deref().map(|meta| rules.document(meta)),
+                ..event.clone()
+            };
+            let unchanged = redacted.element_text == event.element_text
+                && redacted.value == event.value
+                && redacted.url == event.url
+                && redacted.meta_json == event.meta_json;
+            if !unchanged {
+                self.repository.update_event_contents(&redacted).await?;
+                changed += 1;
+            }
+        }
+        Ok(changed)
+    }
+
     pub async fn list_screenshots(&self, session_id: &str) -> Result<Vec<QaEvent>> {
         let session_id = session_id.trim();
         if session_id.is_empty() {
@@ -150,6 +238,16 @@ impl QaEventUseCase {
         self.repository.delete_events(session_id, &cleaned).await
     }
 
+    pub async fn get_event(&self, session_id: &str, event_id: &str) -> Result<Option<QaEvent>> {
+        let session_id = session_id.trim();
+        if session_id.is_empty() {
+            return Err(AppError::ValidationError(
+                "Session id is required.".to_string(),
+            ));
+        }
+        self.repository.get_event(session_id, event_id).await
+    }
+
     pub async fn latest_event_summary(&self, session_id: &str) -> Result<Option<QaEventSummary>> {
         let session_id = session_id.trim();
         if session_id.is_empty() {
@@ -0,0 +1,273 @@
+use crate::domain::qa_redaction::QaRedactionSettings;
+use crate::infrastructure::config::SettingsStore;
+use crate::infrastructure::security::redact::redact_secrets;
+use regex::Regex;
+use serde_json::Value as JsonValue;
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// The `qaRedaction` rules, compiled again only when the settings change
+pub struct QaRedaction {
+    settings: Arc<SettingsStore>,
+    rules: Mutex<Option<Arc<RedactionRules>>>,
+}
+
+impl QaRedaction {
+    pub fn new(settings: Arc<SettingsStore>) -> Self {
+        Self {
+            settings,
+            rules: Mutex::new(None),
+        }
+    }
+
+    pub fn rules(&self) -> Arc<RedactionRules> {
+        let settings = self.settings.get::<QaRedactionSettings>();
+        let mut rules = self.rules.lock().unwrap();
+        match rules.as_ref() {
+            Some(current) if current.settings == settings => current.clone(),
+            _ => {
+                let compiled = Arc::new(RedactionRules::new(settings));
+                *rules = Some(compiled.clone());
+                compiled
+            }
+        }
+    }
+}
+
+pub struct RedactionRules {
+    settings: QaRedactionSettings,
+    header_names: Vec<String>,
+    field_names: Vec<String>,
+    /// `name=value` and `name: value` outside of JSON
+    assignment: Option<Regex>,
+    patterns: Vec<Regex>,
+}
+
+impl RedactionRules {
+    pub fn new(settings: QaRedactionSettings) -> Self {
+        let header_names: Vec<String> = settings
+            .header_names
+            .iter()
+            .map(|name| name.trim().to_lowercase())
+            .collect();
+        let field_names: Vec<String> = settings
+            .field_names
+            .iter()
+            .map(|name| normalize_key(name))
+            .filter(|name| !name.is_empty())
+            .collect();
+
+        let names: Vec<String> = settings
+            .header_names
+            .iter()
+            .chain(&settings.field_names)
+            .map(|name| regex::escape(name.trim()))
+            .filter(|name| !name.is_empty())
+            .collect();
+        let assignment = (!names.is_empty()).then(|| {
+            Regex::new(&format!(
+                r"(?im)((?:^|[?&\s;,])[\w\-]*(?:{})\s*(?:=|:[ \t]*))[^&\r\n;,]+",
+                names.join("|")
+            ))
+            .expect("escaped names form a valid pattern")
+        });
+
+        let patterns = settings
+            .patterns
+            .iter()
+            .filter_map(|pattern| match Regex::new(pattern) {
+                Ok(regex) => Some(regex),
+                Err(err) => {
+                    warn!(pattern = %pattern, error = %err, "Skipping invalid redaction pattern");
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            settings,
+            header_names,
+            field_names,
+            assignment,
+            patterns,
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.settings.enabled
+    }
+
+    /// Free text such as an input value: tokens, keys and custom patterns
+    pub fn text(&self, text: &str) -> String {
+        if !self.enabled() {
+            return text.to_string();
+        }
+        let mut text = redact_secrets(text);
+        for pattern in &self.patterns {
+            text = pattern.replace_all(&text, REDACTED).into_owned();
+        }
+        text
+    }
+
+    /// A body, header list or event metadata: JSON is walked so sensitive
+    /// keys keep their structure, anything else is treated as a form body
+    /// or header block
+    pub fn document(&self, document: &str) -> String {
+        if !self.enabled() {
+            return document.to_string();
+        }
+        match serde_json::from_str::<JsonValue>(document) {
+            Ok(mut json) if json.is_object() || json.is_array() => {
+                self.redact_json(&mut json);
+                json.to_string()
+            }
+            _ => {
+                let text = match &self.assignment {
+                    Some(assignment) => assignment
+                        .replace_all(document, format!("${{1}}{}", REDACTED))
+                        .into_owned(),
+                    None => document.to_string(),
+                };
+                self.text(&text)
+            }
+        }
+    }
+
+    /// Masks the values of sensitive query parameters
+    pub fn url(&self, url: &str) -> String {
+        if !self.enabled() {
+            return url.to_string();
+        }
+        let Ok(mut parsed) = url::Url::parse(url) else {
+            return self.text(url);
+        };
+        if !parsed.query_pairs().any(|(key, _)| self.is_sensitive(&key)) {
+            return self.text(url);
+        }
+        let pairs: Vec<(String, String)> = parsed
+            .query_pairs()
+            .map(|(key, value)| {
+                let value = if self.is_sensitive(&key) {
+                    REDACTED.to_string()
+                } else {
+                    value.into_owned()
+                };
+                (key.into_owned(), value)
+            })
+            .collect();
+        parsed.query_pairs_mut().clear().extend_pairs(pairs);
+        self.text(&parsed.as_str().replace("%5BREDACTED%5D", REDACTED))
+    }
+
+    fn is_sensitive(&self, key: &str) -> bool {
+        let lower = key.trim().to_lowercase();
+        if self.header_names.contains(&lower) {
+            return true;
+        }
+        let key = normalize_key(key);
+        self.field_names.iter().any(|name| key.ends_with(name))
+    }
+
+    fn redact_json(&self, value: &mut JsonValue) {
+        match value {
+            JsonValue::Object(map) => {
+                // `{ "key": "Authorization", "value": "..." }` header rows
+                let named = ["key", "name"]
+                    .iter()
+                    .filter_map(|field| map.get(*field).and_then(JsonValue::as_str))
+                    .any(|name| self.is_sensitive(name));
+                for (key, entry) in map.iter_mut() {
+                    if (named && key == "value") || (self.is_sensitive(key) && !entry.is_null()) {
+                        *entry = JsonValue::String(REDACTED.to_string());
+                    } else {
+                        self.redact_json(entry);
+                    }
+                }
+            }
+            JsonValue::Array(items) => {
+                // `["Authorization", "..."]` header pairs
+                let pair_name = match items.as_slice() {
+                    [JsonValue::String(name), JsonValue::String(_)] => Some(name.clone()),
+                    _ => None,
+                };
+                if pair_name.is_some_and(|name| self.is_sensitive(&name)) {
+                    items[1] = JsonValue::String(REDACTED.to_string());
+                    return;
+                }
+                items.iter_mut().for_each(|item| self.redact_json(item));
+            }
+            JsonValue::String(text) => *text = self.text(text),
+            _ => {}
+        }
+    }
+}
+
+/// Lowercase without `-` and `_`, so `api-key`, `api_key` and `apiKey` match
+fn normalize_key(key: &str) -> String {
+    key.trim()
+        .chars()
+        .filter(|c| *c != '-' && *c != '_')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> RedactionRules {
+        RedactionRules::new(QaRedactionSettings {
+            patterns: vec![r"\b\d{4}-\d{4}-\d{4}-\d{4}\b".to_string()],
+            ..QaRedactionSettings::default()
+        })
+    }
+
+    #[test]
+    fn test_redacts_json_headers_and_fields() {
+        let rules = rules();
+        let headers = rules.document(
+            r#"[{"key":"Authorization","value":"Basic dXNlcjpwYXNz","enabled":true},["Set-Cookie","sid=abc"],["Accept","*/*"]]"#,
+        );
+        assert!(!headers.contains("dXNlcjpwYXNz"));
+        assert!(!headers.contains("sid=abc"));
+        assert!(headers.contains("*/*"));
+
+        let body = rules.document(
+            r#"{"user":{"email":"a@b.c","newPassword":"hunter2","card":"4111-1111-1111-1111"},"tokenType":"Bearer"}"#,
+        );
+        let body: JsonValue = serde_json::from_str(&body).unwrap();
+        assert_eq!(body["user"]["newPassword"], REDACTED);
+        assert_eq!(body["user"]["card"], REDACTED);
+        assert_eq!(body["user"]["email"], "a@b.c");
+        assert_eq!(body["tokenType"], "Bearer");
+    }
+
+    #[test]
+    fn test_redacts_forms_urls_and_text() {
+        let rules = rules();
+        assert_eq!(
+            rules.document("username=ana&password=hunter2&remember=1"),
+            "username=ana&password=[REDACTED]&remember=1"
+        );
+        assert_eq!(
+            rules.document("Cookie: sid=abc\nAccept: */*"),
+            "Cookie: [REDACTED]\nAccept: */*"
+        );
+        assert_eq!(
+            rules.url("https://api.example.com/login?user=ana&access_token=abc123"),
+            "https://api.example.com/login?user=ana&access_token=[REDACTED]"
+        );
+        assert_eq!(
+            rules.text("Bearer abcdefghijkl"),
+            format!("Bearer {}", REDACTED)
+        );
+
+        let disabled = RedactionRules::new(QaRedactionSettings {
+            enabled: false,
+            ..QaRedactionSettings::default()
+        });
+        assert_eq!(disabled.document("password=hunter2"), "password=hunter2");
+    }
+}
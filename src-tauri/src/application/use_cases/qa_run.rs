@@ -1,3 +1,5 @@
+use crate::application::use_cases::activity::ActivityFeed;
+use crate::domain::activity::{ActivityInput, ActivitySource};
 use crate::domain::error::{AppError, Result};
 use crate::domain::qa_run::{QaRunStreamEvent, QaRunStreamInput, QaSessionRun};
 use crate::infrastructure::db::qa_runs::QaRunRepository;
@@ -6,11 +8,15 @@ use uuid::Uuid;
 
 pub struct QaRunUseCase {
     repository: Arc<QaRunRepository>,
+    activity: Arc<ActivityFeed>,
 }
 
 impl QaRunUseCase {
-    pub fn new(repository: Arc<QaRunRepository>) -> Self {
-        Self { repository }
+    pub fn new(repository: Arc<QaRunRepository>, activity: Arc<ActivityFeed>) -> Self {
+        Self {
+            repository,
+            activity,
+        }
     }
 
     pub async fn start_run(
@@ -48,6 +54,18 @@ impl QaRunUseCase {
         };
 
         self.repository.insert_run(&run).await?;
+        self.activity.record(
+            ActivityInput::new(
+                ActivitySource::QaRun,
+                "run_started",
+                format!("{} run started ({})", run.run_type, run.mode),
+            )
+            .subject(run.id.clone())
+            .payload(serde_json::json!({
+                "sessionId": run.session_id,
+                "triggeredBy": run.triggered_by,
+            })),
+        );
         Ok(run)
     }
 
@@ -61,7 +79,26 @@ impl QaRunUseCase {
         self.repository
             .update_run_status(run_id, &status, Some(ended_at))
             .await?;
-        self.repository.get_run(run_id).await
+        let run = self.repository.get_run(run_id).await?;
+        let level = if run.status == "failed" {
+            "error"
+        } else {
+            "info"
+        };
+        self.activity.record(
+            ActivityInput::new(
+                ActivitySource::QaRun,
+                "run_ended",
+                format!("{} run ended: {}", run.run_type, run.status),
+            )
+            .level(level)
+            .subject(run.id.clone())
+            .payload(serde_json::json!({
+                "sessionId": run.session_id,
+                "durationMs": ended_at - run.started_at,
+            })),
+        );
+        Ok(run)
     }
 
     #[allow(dead_code)]
@@ -100,6 +137,13 @@ impl QaRunUseCase {
             payload_json: normalize_optional(input.payload_json),
         };
         self.repository.insert_stream_event(&event).await?;
+        let mut activity =
+            ActivityInput::new(ActivitySource::QaRun, &event.channel, event.message.clone())
+                .level(&event.level)
+                .subject(event.run_id.clone());
+        activity.ts = event.ts;
+        activity.payload_json = event.payload_json.clone();
+        self.activity.record(activity);
         Ok(event)
     }
 
@@ -1,23 +1,30 @@
 use crate::domain::error::{AppError, Result};
+use crate::domain::qa_highlight::annotated_screenshot_path;
+use crate::domain::qa_retention::{
+    screenshots_over_cap, QaCleanupReport, QaExpiredSession, QaRetentionSettings,
+};
+use crate::domain::qa_selectors::{normalize_selector_attributes, QaSelectorSettings};
 use crate::domain::qa_session::QaSession;
+use crate::domain::trash::TrashEntry;
 use crate::infrastructure::db::qa_sessions::QaRepository;
-use crate::infrastructure::storage::ensure_session_dir;
+use crate::infrastructure::storage::{dir_stats, ensure_session_dir, StorageLocations};
+use std::collections::HashSet;
 use std::fs;
-use std::path::PathBuf;
+use std::path::Path;
 use std::sync::Arc;
 use tracing::error;
 use uuid::Uuid;
 
 pub struct QaSessionUseCase {
     repository: Arc<QaRepository>,
-    qa_sessions_dir: PathBuf,
+    storage: Arc<StorageLocations>,
 }
 
 impl QaSessionUseCase {
-    pub fn new(repository: Arc<QaRepository>, qa_sessions_dir: PathBuf) -> Self {
+    pub fn new(repository: Arc<QaRepository>, storage: Arc<StorageLocations>) -> Self {
         Self {
             repository,
-            qa_sessions_dir,
+            storage,
         }
     }
 
@@ -34,6 +41,7 @@ impl QaSessionUseCase {
         app_version: Option<String>,
         os: Option<String>,
         notes: Option<String>,
+        selector_attributes: Option<Vec<String>>,
     ) -> Result<QaSession> {
         if goal.trim().is_empty() {
             return Err(AppError::ValidationError("Goal is required.".to_string()));
@@ -77,6 +85,8 @@ impl QaSessionUseCase {
             }
         });
 
+        let selector_attributes = session_selector_attributes(selector_attributes)?;
+
         if session_type == "api" && api_base_url.is_none() {
             return Err(AppError::ValidationError(
                 "API base URL is required for API sessions.".to_string(),
@@ -84,8 +94,9 @@ impl QaSessionUseCase {
         }
 
         let session_id = Uuid::new_v4().to_string();
-        ensure_session_dir(&self.qa_sessions_dir, &session_id).map_err(|e| {
-            let session_dir = self.qa_sessions_dir.join(&session_id);
+        let qa_sessions_dir = self.storage.qa_sessions_dir();
+        ensure_session_dir(&qa_sessions_dir, &session_id).map_err(|e| {
+            let session_dir = qa_sessions_dir.join(&session_id);
             error!(
                 error = %e,
                 session_id = %session_id,
@@ -111,6 +122,7 @@ impl QaSessionUseCase {
             started_at,
             ended_at: None,
             notes,
+            selector_attributes,
         };
 
         self.repository.insert_session(&session).await?;
@@ -122,6 +134,40 @@ impl QaSessionUseCase {
         self.repository.end_session(session_id, ended_at).await
     }
 
+    /// Set or, with `None` or an empty list, clear the session's own
+    /// selector attributes
+    pub async fn set_selector_attributes(
+        &self,
+        session_id: &str,
+        attributes: Option<Vec<String>>,
+    ) -> Result<QaSession> {
+        let attributes = session_selector_attributes(attributes)?;
+        self.repository
+            .update_selector_attributes(session_id, attributes.as_deref())
+            .await
+    }
+
+    /// Selector attributes the recorders and replay use: the session's own,
+    /// else the `settings` ones. A session that can't be read counts as one
+    /// without its own.
+    pub async fn selector_attributes(
+        &self,
+        settings: &QaSelectorSettings,
+        session_id: Option<&str>,
+    ) -> Vec<String> {
+        let session_id = session_id.map(str::trim).filter(|id| !id.is_empty());
+        let session_attributes = match session_id {
+            Some(session_id) => self
+                .repository
+                .get_session(session_id)
+                .await
+                .ok()
+                .and_then(|session| session.selector_attributes),
+            None => None,
+        };
+        settings.resolve(session_attributes.as_deref())
+    }
+
     pub async fn get_session(&self, session_id: &str) -> Result<QaSession> {
         self.repository.get_session(session_id).await
     }
@@ -134,13 +180,59 @@ impl QaSessionUseCase {
         self.repository.list_sessions(limit).await
     }
 
+    /// Move a session to the trash; its events and files stay until it is
+    /// purged
     pub async fn delete_session(&self, session_id: &str) -> Result<u64> {
-        let session_id = session_id.trim();
-        if session_id.is_empty() {
-            return Err(AppError::ValidationError(
-                "Session id is required.".to_string(),
-            ));
+        let session_id = required_session_id(session_id)?;
+        let trashed = self
+            .repository
+            .trash_session(session_id, chrono::Utc::now().timestamp_millis())
+            .await?;
+        if trashed == 0 {
+            return Err(AppError::NotFound(format!(
+                "QA session not found: {}",
+                session_id
+            )));
+        }
+        Ok(trashed)
+    }
+
+    pub async fn restore_session(&self, session_id: &str) -> Result<u64> {
+        let session_id = required_session_id(session_id)?;
+        let restored = self.repository.restore_session(session_id).await?;
+        if restored == 0 {
+            return Err(AppError::NotFound(format!(
+                "QA session not in the trash: {}",
+                session_id
+            )));
+        }
+        Ok(restored)
+    }
+
+    pub async fn list_trash(&self) -> Result<Vec<TrashEntry>> {
+        self.repository.list_trashed_sessions().await
+    }
+
+    /// Purge the sessions moved to the trash before `deleted_before` (ms).
+    /// Returns how many were purged and the errors of the rest.
+    pub async fn purge_trash(&self, deleted_before: i64) -> Result<(u64, Vec<String>)> {
+        let mut purged = 0;
+        let mut errors = Vec::new();
+        for entry in self.repository.list_trashed_sessions().await? {
+            if entry.deleted_at >= deleted_before {
+                continue;
+            }
+            match self.purge_session(&entry.id).await {
+                Ok(_) => purged += 1,
+                Err(err) => errors.push(format!("QA session {}: {}", entry.name, err)),
+            }
         }
+        Ok((purged, errors))
+    }
+
+    /// Delete a session for good, with its events and files
+    pub async fn purge_session(&self, session_id: &str) -> Result<u64> {
+        let session_id = required_session_id(session_id)?;
         let deleted = self.repository.delete_session_cascade(session_id).await?;
         if deleted == 0 {
             return Err(AppError::NotFound(format!(
@@ -149,7 +241,7 @@ impl QaSessionUseCase {
             )));
         }
 
-        let session_dir = self.qa_sessions_dir.join(session_id);
+        let session_dir = self.storage.qa_sessions_dir().join(session_id);
         if session_dir.exists() {
             fs::remove_dir_all(&session_dir).map_err(|e| {
                 error!(
@@ -164,4 +256,153 @@ impl QaSessionUseCase {
 
         Ok(deleted)
     }
+
+    /// Apply the retention limits: delete expired sessions, then the oldest
+    /// screenshots until the storage cap is met. `active_session_ids` are
+    /// never touched. With `dry_run` the report lists what would go and nothing is
+    /// deleted.
+    pub async fn cleanup_storage(
+        &self,
+        policy: &QaRetentionSettings,
+        active_session_ids: &HashSet<String>,
+        dry_run: bool,
+    ) -> Result<QaCleanupReport> {
+        let mut report = QaCleanupReport::new(dry_run);
+        let qa_sessions_dir = self.storage.qa_sessions_dir();
+        let now = chrono::Utc::now().timestamp_millis();
+
+        if let Some(cutoff) = policy.session_cutoff(now) {
+            for session in self.repository.list_sessions_started_before(cutoff).await? {
+                if active_session_ids.contains(&session.id) {
+                    continue;
+                }
+                let (_, file_bytes) = dir_stats(&qa_sessions_dir.join(&session.id));
+                report.expired_sessions.push(QaExpiredSession {
+                    event_count: self.repository.count_events(&session.id).await?,
+                    session_id: session.id,
+                    title: session.title,
+                    started_at: session.started_at,
+                    file_bytes,
+                });
+            }
+        }
+        let expired: HashSet<&str> = report
+            .expired_sessions
+            .iter()
+            .map(|session| session.session_id.as_str())
+            .collect();
+
+        let screenshots: Vec<_> = self
+            .repository
+            .list_screenshot_artifacts()
+            .await?
+            .into_iter()
+            .map(|artifact| {
+                let file_size = |path: &Path| fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+                let path = Path::new(&artifact.path);
+                // An annotated copy goes with its screenshot
+                let size = file_size(path) + file_size(&annotated_screenshot_path(path));
+                (artifact, size)
+            })
+            .collect();
+        report.screenshot_bytes_before = screenshots.iter().map(|(_, size)| size).sum();
+        let expired_screenshot_bytes: u64 = screenshots
+            .iter()
+            .filter(|(artifact, _)| expired.contains(artifact.session_id.as_str()))
+            .map(|(_, size)| size)
+            .sum();
+        let remaining = report.screenshot_bytes_before - expired_screenshot_bytes;
+
+        let candidates: Vec<_> = screenshots
+            .iter()
+            .filter(|(artifact, _)| {
+                !expired.contains(artifact.session_id.as_str())
+                    && !active_session_ids.contains(&artifact.session_id)
+            })
+            .collect();
+        let prune_count = policy.max_screenshot_bytes().map_or(0, |max_bytes| {
+            let sizes: Vec<u64> = candidates.iter().map(|(_, size)| *size).collect();
+            screenshots_over_cap(&sizes, remaining, max_bytes)
+        });
+        let pruned = &candidates[..prune_count];
+        let pruned_bytes: u64 = pruned.iter().map(|(_, size)| size).sum();
+
+        report.events_removed = report
+            .expired_sessions
+            .iter()
+            .map(|session| session.event_count)
+            .sum();
+        report.screenshots_removed = pruned.len() as u64;
+        report.screenshot_bytes_after = remaining - pruned_bytes;
+        report.bytes_freed = report
+            .expired_sessions
+            .iter()
+            .map(|session| session.file_bytes)
+            .sum::<u64>()
+            + pruned_bytes;
+        if dry_run {
+            return Ok(report);
+        }
+
+        // Anything that could not be removed is taken back out of the totals
+        let mut failures = Vec::new();
+        for session in &report.expired_sessions {
+            if let Err(err) = self.purge_session(&session.session_id).await {
+                error!(
+                    error = %err,
+                    session_id = %session.session_id,
+                    "Failed to delete expired QA session"
+                );
+                report.events_removed -= session.event_count;
+                report.bytes_freed -= session.file_bytes;
+                failures.push(format!("Session {}: {}", session.session_id, err));
+            }
+        }
+
+        let mut removed_ids = Vec::with_capacity(pruned.len());
+        for (artifact, size) in pruned {
+            match fs::remove_file(&artifact.path) {
+                Ok(()) => removed_ids.push(artifact.id.clone()),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    removed_ids.push(artifact.id.clone())
+                }
+                Err(err) => {
+                    report.screenshots_removed -= 1;
+                    report.screenshot_bytes_after += size;
+                    report.bytes_freed -= size;
+                    failures.push(format!("Screenshot {}: {}", artifact.path, err));
+                    continue;
+                }
+            }
+            let annotated = annotated_screenshot_path(Path::new(&artifact.path));
+            if let Err(err) = fs::remove_file(&annotated) {
+                if err.kind() != std::io::ErrorKind::NotFound {
+                    failures.push(format!("Screenshot {}: {}", annotated.display(), err));
+                }
+            }
+        }
+        report.errors = failures;
+        self.repository.delete_artifacts(&removed_ids).await?;
+
+        Ok(report)
+    }
+}
+
+/// A session's own selector attributes; an empty list means the settings apply
+fn session_selector_attributes(attributes: Option<Vec<String>>) -> Result<Option<Vec<String>>> {
+    let Some(attributes) = attributes else {
+        return Ok(None);
+    };
+    let attributes = normalize_selector_attributes(&attributes)?;
+    Ok((!attributes.is_empty()).then_some(attributes))
+}
+
+fn required_session_id(session_id: &str) -> Result<&str> {
+    let session_id = session_id.trim();
+    if session_id.is_empty() {
+        return Err(AppError::ValidationError(
+            "Session id is required.".to_string(),
+        ));
+    }
+    Ok(session_id)
 }
@@ -0,0 +1,401 @@
+use crate::domain::error::{AppError, Result};
+use crate::domain::qa_checkpoint::QaTestCase;
+use crate::domain::qa_test_management::{
+    QaTestManagementSettings, QaTestResultInput, QaTestResultStatus, QaTestSyncEntry,
+    QaTestSyncReport, TestManagementProvider,
+};
+use crate::infrastructure::db::qa_checkpoints::QaCheckpointRepository;
+use crate::infrastructure::db::qa_test_sync::QaTestSyncRepository;
+use crate::infrastructure::test_management::testrail::TestRailClient;
+use crate::infrastructure::test_management::xray::XrayClient;
+use crate::infrastructure::test_management::{ExternalTestCase, ExternalTestResult};
+use serde_json::json;
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const SYNC_LOG_LIMIT_DEFAULT: i64 = 200;
+const SYNC_LOG_LIMIT_MAX: i64 = 1000;
+
+pub struct QaTestManagementUseCase {
+    checkpoint_repository: Arc<QaCheckpointRepository>,
+    sync_repository: Arc<QaTestSyncRepository>,
+}
+
+impl QaTestManagementUseCase {
+    pub fn new(
+        checkpoint_repository: Arc<QaCheckpointRepository>,
+        sync_repository: Arc<QaTestSyncRepository>,
+    ) -> Self {
+        Self {
+            checkpoint_repository,
+            sync_repository,
+        }
+    }
+
+    /// Push the selected test cases of a session; all of them when
+    /// `test_case_ids` is empty. Cases pushed to `provider` before are
+    /// skipped, so pushing twice never duplicates a test.
+    pub async fn push_test_cases(
+        &self,
+        session_id: &str,
+        provider: TestManagementProvider,
+        test_case_ids: &[String],
+        settings: &QaTestManagementSettings,
+        secret: &str,
+    ) -> Result<QaTestSyncReport> {
+        settings.ensure_configured(provider)?;
+        let cases = self.selected_cases(session_id, test_case_ids).await?;
+        let pushed = self
+            .sync_repository
+            .external_ids(session_id, provider)
+            .await?;
+
+        let mut entries = Vec::with_capacity(cases.len());
+        let mut pending = Vec::new();
+        for case in &cases {
+            match pushed.get(&case.id) {
+                Some(external_id) => entries.push(
+                    self.record(
+                        session_id,
+                        provider,
+                        "case",
+                        Some(&case.id),
+                        Some(external_id),
+                        "skipped",
+                        Some("Already pushed".to_string()),
+                    )
+                    .await?,
+                ),
+                None => pending.push(case),
+            }
+        }
+
+        let external_cases: Vec<ExternalTestCase> =
+            pending.iter().map(|case| external_case(case)).collect();
+        let outcomes = match provider {
+            TestManagementProvider::Xray if !external_cases.is_empty() => {
+                XrayClient::new(&settings.xray, secret)
+                    .create_tests(&external_cases)
+                    .await?
+            }
+            TestManagementProvider::Xray => Vec::new(),
+            TestManagementProvider::Testrail => {
+                let client = TestRailClient::new(&settings.testrail, secret);
+                let mut outcomes = Vec::with_capacity(external_cases.len());
+                for case in &external_cases {
+                    outcomes.push(client.add_case(case).await);
+                }
+                outcomes
+            }
+        };
+
+        for (case, outcome) in pending.iter().zip(outcomes) {
+            let entry = match outcome {
+                Ok(external_id) => {
+                    self.record(
+                        session_id,
+                        provider,
+                        "case",
+                        Some(&case.id),
+                        Some(&external_id),
+                        "created",
+                        None,
+                    )
+                    .await?
+                }
+                Err(err) => {
+                    self.record(
+                        session_id,
+                        provider,
+                        "case",
+                        Some(&case.id),
+                        None,
+                        "failed",
+                        Some(err.to_string()),
+                    )
+                    .await?
+                }
+            };
+            entries.push(entry);
+        }
+
+        Ok(QaTestSyncReport::new(provider, None, entries))
+    }
+
+    /// Push one run's results as an Xray test execution or a TestRail run.
+    /// Only cases pushed to `provider` before can be reported on; the rest
+    /// are logged as failed.
+    pub async fn push_test_results(
+        &self,
+        session_id: &str,
+        provider: TestManagementProvider,
+        run_name: &str,
+        results: &[QaTestResultInput],
+        settings: &QaTestManagementSettings,
+        secret: &str,
+    ) -> Result<QaTestSyncReport> {
+        settings.ensure_configured(provider)?;
+        if results.is_empty() {
+            return Err(AppError::ValidationError(
+                "Select at least one test result to push.".to_string(),
+            ));
+        }
+        let run_name = run_name.trim();
+        if run_name.is_empty() {
+            return Err(AppError::ValidationError(
+                "Test run name is required.".to_string(),
+            ));
+        }
+
+        let session_cases: HashSet<String> = self
+            .checkpoint_repository
+            .list_test_cases(session_id)
+            .await?
+            .into_iter()
+            .map(|case| case.id)
+            .collect();
+        if let Some(missing) = results
+            .iter()
+            .find(|result| !session_cases.contains(&result.test_case_id))
+        {
+            return Err(AppError::NotFound(format!(
+                "Test case {} not found in this session.",
+                missing.test_case_id
+            )));
+        }
+        let pushed = self
+            .sync_repository
+            .external_ids(session_id, provider)
+            .await?;
+
+        let run_id = Uuid::new_v4().to_string();
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut entries = Vec::new();
+        let mut reportable = Vec::new();
+        for result in results {
+            let comment = result
+                .comment
+                .as_deref()
+                .map(str::trim)
+                .filter(|c| !c.is_empty());
+            let error = comment.filter(|_| result.status == QaTestResultStatus::Failed);
+            let result_json = comment.map(|comment| json!({ "comment": comment }).to_string());
+            self.sync_repository
+                .insert_test_case_run(
+                    &Uuid::new_v4().to_string(),
+                    &result.test_case_id,
+                    session_id,
+                    &run_id,
+                    result.status.as_str(),
+                    error,
+                    result_json.as_deref(),
+                    now,
+                )
+                .await?;
+
+            match pushed.get(&result.test_case_id) {
+                Some(external_id) => reportable.push((result, external_id.as_str())),
+                None => entries.push(
+                    self.record(
+                        session_id,
+                        provider,
+                        "result",
+                        Some(&result.test_case_id),
+                        None,
+                        "failed",
+                        Some(format!(
+                            "Test case was not pushed to {} yet",
+                            provider.display_name()
+                        )),
+                    )
+                    .await?,
+                ),
+            }
+        }
+
+        if reportable.is_empty() {
+            return Ok(QaTestSyncReport::new(provider, None, entries));
+        }
+
+        let external_results: Vec<ExternalTestResult> = reportable
+            .iter()
+            .map(|(result, external_id)| ExternalTestResult {
+                external_id,
+                status: result.status,
+                comment: result.comment.as_deref(),
+            })
+            .collect();
+        let pushed_run = match provider {
+            TestManagementProvider::Xray => {
+                XrayClient::new(&settings.xray, secret)
+                    .import_execution(run_name, &external_results)
+                    .await
+            }
+            TestManagementProvider::Testrail => {
+                TestRailClient::new(&settings.testrail, secret)
+                    .add_run(run_name, &external_results)
+                    .await
+            }
+        };
+
+        let (status, message) = match &pushed_run {
+            Ok(run_key) => ("pushed", Some(format!("Reported in {}", run_key))),
+            Err(err) => ("failed", Some(err.to_string())),
+        };
+        for (result, external_id) in &reportable {
+            entries.push(
+                self.record(
+                    session_id,
+                    provider,
+                    "result",
+                    Some(&result.test_case_id),
+                    Some(external_id),
+                    status,
+                    message.clone(),
+                )
+                .await?,
+            );
+        }
+
+        let run_key = pushed_run?;
+        Ok(QaTestSyncReport::new(provider, Some(run_key), entries))
+    }
+
+    pub async fn list_sync_log(
+        &self,
+        session_id: &str,
+        limit: Option<i64>,
+    ) -> Result<Vec<QaTestSyncEntry>> {
+        let limit = limit
+            .unwrap_or(SYNC_LOG_LIMIT_DEFAULT)
+            .clamp(1, SYNC_LOG_LIMIT_MAX);
+        self.sync_repository.list_entries(session_id, limit).await
+    }
+
+    /// Store a test case written by the user rather than generated, such as
+    /// a saved API test. A checkpoint it is linked to must be in its session.
+    pub async fn add_test_case(&self, test_case: &QaTestCase) -> Result<()> {
+        if let Some(checkpoint_id) = test_case.checkpoint_id.as_deref() {
+            let checkpoint = self
+                .checkpoint_repository
+                .get_checkpoint(checkpoint_id)
+                .await?;
+            if checkpoint.session_id != test_case.session_id {
+                return Err(AppError::NotFound(format!(
+                    "Checkpoint {} not found in this session.",
+                    checkpoint_id
+                )));
+            }
+        }
+        self.checkpoint_repository.insert_test_case(test_case).await
+    }
+
+    /// The session's test cases of `case_type`, oldest first
+    pub async fn list_test_cases_of_type(
+        &self,
+        session_id: &str,
+        case_type: &str,
+    ) -> Result<Vec<QaTestCase>> {
+        let mut cases = self
+            .checkpoint_repository
+            .list_test_cases(session_id)
+            .await?;
+        cases.retain(|case| case.case_type == case_type);
+        cases.reverse();
+        Ok(cases)
+    }
+
+    /// Record the outcome of running one test case as part of `run_id`
+    pub async fn record_test_case_run(
+        &self,
+        session_id: &str,
+        run_id: &str,
+        test_case_id: &str,
+        status: QaTestResultStatus,
+        error: Option<&str>,
+        result_json: Option<&str>,
+    ) -> Result<()> {
+        self.sync_repository
+            .insert_test_case_run(
+                &Uuid::new_v4().to_string(),
+                test_case_id,
+                session_id,
+                run_id,
+                status.as_str(),
+                error,
+                result_json,
+                chrono::Utc::now().timestamp_millis(),
+            )
+            .await
+    }
+
+    async fn selected_cases(
+        &self,
+        session_id: &str,
+        test_case_ids: &[String],
+    ) -> Result<Vec<QaTestCase>> {
+        let mut cases = self
+            .checkpoint_repository
+            .list_test_cases(session_id)
+            .await?;
+        // Oldest first, so external ids follow the order cases were generated in
+        cases.reverse();
+        if test_case_ids.is_empty() {
+            if cases.is_empty() {
+                return Err(AppError::ValidationError(
+                    "This session has no test cases to push.".to_string(),
+                ));
+            }
+            return Ok(cases);
+        }
+
+        if let Some(missing) = test_case_ids
+            .iter()
+            .find(|id| !cases.iter().any(|case| &case.id == *id))
+        {
+            return Err(AppError::NotFound(format!(
+                "Test case {} not found in this session.",
+                missing
+            )));
+        }
+        cases.retain(|case| test_case_ids.contains(&case.id));
+        Ok(cases)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn record(
+        &self,
+        session_id: &str,
+        provider: TestManagementProvider,
+        action: &str,
+        test_case_id: Option<&str>,
+        external_id: Option<&str>,
+        status: &str,
+        message: Option<String>,
+    ) -> Result<QaTestSyncEntry> {
+        let entry = QaTestSyncEntry {
+            id: Uuid::new_v4().to_string(),
+            session_id: session_id.to_string(),
+            provider,
+            action: action.to_string(),
+            test_case_id: test_case_id.map(str::to_string),
+            external_id: external_id.map(str::to_string),
+            status: status.to_string(),
+            message,
+            created_at: chrono::Utc::now().timestamp_millis(),
+        };
+        self.sync_repository.insert_entry(&entry).await?;
+        Ok(entry)
+    }
+}
+
+fn external_case(case: &QaTestCase) -> ExternalTestCase<'_> {
+    ExternalTestCase {
+        title: &case.title,
+        case_type: &case.case_type,
+        priority: case.priority.as_deref(),
+        steps: serde_json::from_str(&case.steps_json).unwrap_or_default(),
+        expected: case.expected.as_deref(),
+    }
+}
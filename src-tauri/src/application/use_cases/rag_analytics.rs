@@ -4,6 +4,8 @@ use std::hash::{Hash, Hasher};
 use std::sync::{Arc, Mutex};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use super::retrieval_service::RoutingDecision;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum AnalyticsEventType {
@@ -31,6 +33,15 @@ pub struct AnalyticsMetadata {
     pub confidence: Option<f32>,
     pub answer_length: Option<usize>,
     pub feedback: Option<String>,
+    /// Chat answered from the answer cache
+    #[serde(default)]
+    pub cached: Option<bool>,
+    /// Route a retrieval ran on (`semantic`, `keyword` or `structured`)
+    #[serde(default)]
+    pub route: Option<String>,
+    /// The route was forced by the query
+    #[serde(default)]
+    pub route_overridden: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,6 +54,8 @@ pub struct AnalyticsSummary {
     pub avg_retrieval_ms: f32,
     pub avg_chat_ms: f32,
     pub success_rate: f32,
+    /// Chats answered from the answer cache, included in `chat_count`
+    pub answer_cache_hits: usize,
 }
 
 pub struct AnalyticsLogger {
@@ -97,6 +110,33 @@ impl AnalyticsLogger {
         });
     }
 
+    /// A retrieval with the route it ran on
+    pub fn log_routed_retrieval(
+        &mut self,
+        query: &str,
+        collection_id: i64,
+        sources: usize,
+        routing: &RoutingDecision,
+        duration_ms: u64,
+    ) {
+        let metadata = AnalyticsMetadata {
+            collection_id: Some(collection_id),
+            query_hash: Some(hash_query(query)),
+            query_length: Some(query.len()),
+            sources: Some(sources),
+            route: routing.route.map(|route| route.as_str().to_string()),
+            route_overridden: Some(routing.overridden),
+            ..Default::default()
+        };
+        self.push_event(AnalyticsEvent {
+            event_type: AnalyticsEventType::Retrieval,
+            timestamp_ms: now_ms(),
+            success: true,
+            duration_ms,
+            metadata,
+        });
+    }
+
     pub fn log_chat(
         &mut self,
         query: &str,
@@ -122,6 +162,31 @@ impl AnalyticsLogger {
         });
     }
 
+    /// A chat answered from the answer cache
+    pub fn log_cached_chat(
+        &mut self,
+        query: &str,
+        collection_id: i64,
+        answer_length: usize,
+        duration_ms: u64,
+    ) {
+        let metadata = AnalyticsMetadata {
+            collection_id: Some(collection_id),
+            query_hash: Some(hash_query(query)),
+            query_length: Some(query.len()),
+            answer_length: Some(answer_length),
+            cached: Some(true),
+            ..Default::default()
+        };
+        self.push_event(AnalyticsEvent {
+            event_type: AnalyticsEventType::Chat,
+            timestamp_ms: now_ms(),
+            success: true,
+            duration_ms,
+            metadata,
+        });
+    }
+
     pub fn recent_events(&self, limit: usize) -> Vec<AnalyticsEvent> {
         self.events.iter().rev().take(limit).cloned().collect()
     }
@@ -157,6 +222,7 @@ impl AnalyticsLogger {
         let mut retrieval_ms = 0u64;
         let mut chat_ms = 0u64;
         let mut success_count = 0usize;
+        let mut answer_cache_hits = 0usize;
 
         for event in &filtered_events {
             if event.success {
@@ -174,6 +240,9 @@ impl AnalyticsLogger {
                 AnalyticsEventType::Chat => {
                     chat_count += 1;
                     chat_ms += event.duration_ms;
+                    if event.metadata.cached == Some(true) {
+                        answer_cache_hits += 1;
+                    }
                 }
             }
         }
@@ -197,6 +266,7 @@ impl AnalyticsLogger {
             avg_retrieval_ms,
             avg_chat_ms,
             success_rate,
+            answer_cache_hits,
         }
     }
 
@@ -263,6 +333,23 @@ impl SharedAnalyticsLogger {
         );
     }
 
+    pub fn log_routed_retrieval(
+        &self,
+        query: &str,
+        collection_id: i64,
+        sources: usize,
+        routing: &RoutingDecision,
+        duration_ms: u64,
+    ) {
+        self.inner.lock().unwrap().log_routed_retrieval(
+            query,
+            collection_id,
+            sources,
+            routing,
+            duration_ms,
+        );
+    }
+
     pub fn log_chat(
         &self,
         query: &str,
@@ -280,6 +367,21 @@ impl SharedAnalyticsLogger {
         );
     }
 
+    pub fn log_cached_chat(
+        &self,
+        query: &str,
+        collection_id: i64,
+        answer_length: usize,
+        duration_ms: u64,
+    ) {
+        self.inner.lock().unwrap().log_cached_chat(
+            query,
+            collection_id,
+            answer_length,
+            duration_ms,
+        );
+    }
+
     pub fn recent_events(&self, limit: usize) -> Vec<AnalyticsEvent> {
         self.inner.lock().unwrap().recent_events(limit)
     }
@@ -308,7 +410,7 @@ fn now_ms() -> u64 {
         .unwrap_or(0)
 }
 
-fn hash_query(query: &str) -> String {
+pub(crate) fn hash_query(query: &str) -> String {
     let mut hasher = DefaultHasher::new();
     query.hash(&mut hasher);
     format!("{:016x}", hasher.finish())
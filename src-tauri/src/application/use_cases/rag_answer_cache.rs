@@ -0,0 +1,275 @@
+//! Answer cache for RAG chat.
+//!
+//! Identical questions against an unchanged collection get the answer that
+//! was generated the first time, skipping retrieval and the model. Entries
+//! live in `answer_cache` in rag_sense.db and are tied to the collection's
+//! `content_version`, which triggers bump on every document change, so an
+//! edit to the collection invalidates its answers without explicit calls.
+//! Encrypted collections are never cached: answers are stored in plain text.
+
+use crate::application::use_cases::rag_config::SharedConfigManager;
+use crate::application::use_cases::retrieval_service::QueryResult;
+use crate::domain::error::Result;
+use crate::infrastructure::db::rag::repository::{PersistedAnswer, RagRepository};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tracing::warn;
+use utoipa::ToSchema;
+
+/// Everything an answer depends on besides the collection's content
+pub struct AnswerCacheKey<'a> {
+    pub query: &'a str,
+    pub top_k: usize,
+    pub provider: &'a str,
+    pub model: &'a str,
+    pub language: Option<&'a str>,
+    /// Which prompt produced the answer, e.g. `http` or `quick_action`;
+    /// answers built with different prompts are kept apart
+    pub variant: &'a str,
+}
+
+impl AnswerCacheKey<'_> {
+    fn digest(&self) -> String {
+        let query = self
+            .query
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        let mut hasher = Sha256::new();
+        for part in [
+            query.as_str(),
+            &self.top_k.to_string(),
+            self.provider,
+            self.model,
+            &self.language.unwrap_or_default().to_lowercase(),
+            self.variant,
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        hex::encode(hasher.finalize())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CachedRagAnswer {
+    pub answer: String,
+    /// The retrieved context the answer was generated from
+    pub results: Vec<QueryResult>,
+    /// Times the answer was served from the cache, this one included
+    pub hit_count: i64,
+    /// Unix seconds the answer was generated at
+    pub cached_at: i64,
+}
+
+/// Where a freshly generated answer goes. It carries the collection version
+/// read before retrieval, so an answer to content that changed meanwhile is
+/// stored as already stale.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnswerCacheSlot {
+    pub collection_id: i64,
+    pub cache_key: String,
+    pub collection_version: i64,
+    pub query: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnswerCacheLookup {
+    /// The cached answer on a hit
+    pub cached: Option<CachedRagAnswer>,
+    /// Set on a miss or bypass; hand it to `store` with the new answer.
+    /// `None` when the cache is off or the collection is encrypted.
+    pub slot: Option<AnswerCacheSlot>,
+}
+
+pub struct RagAnswerCache {
+    rag_repository: Arc<RagRepository>,
+    config_manager: SharedConfigManager,
+}
+
+impl RagAnswerCache {
+    pub fn new(rag_repository: Arc<RagRepository>, config_manager: SharedConfigManager) -> Self {
+        Self {
+            rag_repository,
+            config_manager,
+        }
+    }
+
+    /// TTL of new entries, or `None` when the answer cache is off
+    fn ttl_secs(&self) -> Option<i64> {
+        let cache = self.config_manager.get_config().cache;
+        (cache.enabled && cache.answer_cache && cache.answer_cache_ttl_secs > 0)
+            .then_some(cache.answer_cache_ttl_secs as i64)
+    }
+
+    /// Find a cached answer. With `bypass` the cache is not read, but the
+    /// returned slot lets the new answer replace the cached one. Cache
+    /// failures only cost a hit, so they are logged rather than returned.
+    pub async fn lookup(
+        &self,
+        collection_id: i64,
+        key: &AnswerCacheKey<'_>,
+        bypass: bool,
+    ) -> AnswerCacheLookup {
+        if self.ttl_secs().is_none() {
+            return AnswerCacheLookup::default();
+        }
+        match self.try_lookup(collection_id, key, bypass).await {
+            Ok(lookup) => lookup,
+            Err(err) => {
+                warn!(collection_id, error = %err, "Answer cache lookup failed");
+                AnswerCacheLookup::default()
+            }
+        }
+    }
+
+    async fn try_lookup(
+        &self,
+        collection_id: i64,
+        key: &AnswerCacheKey<'_>,
+        bypass: bool,
+    ) -> Result<AnswerCacheLookup> {
+        if self
+            .rag_repository
+            .is_collection_encrypted(collection_id)
+            .await?
+        {
+            return Ok(AnswerCacheLookup::default());
+        }
+
+        let cache_key = key.digest();
+        if !bypass {
+            if let Some(entry) = self
+                .rag_repository
+                .take_answer_cache_hit(collection_id, &cache_key)
+                .await?
+            {
+                match serde_json::from_str(&entry.results_json) {
+                    Ok(results) => {
+                        return Ok(AnswerCacheLookup {
+                            cached: Some(CachedRagAnswer {
+                                answer: entry.answer,
+                                results,
+                                hit_count: entry.hit_count,
+                                cached_at: entry.created_at,
+                            }),
+                            slot: None,
+                        })
+                    }
+                    // Written by an older build; regenerate and overwrite it
+                    Err(err) => {
+                        warn!(collection_id, error = %err, "Dropping unreadable cached answer")
+                    }
+                }
+            }
+        }
+
+        let collection_version = self
+            .rag_repository
+            .collection_content_version(collection_id)
+            .await?;
+        Ok(AnswerCacheLookup {
+            cached: None,
+            slot: Some(AnswerCacheSlot {
+                collection_id,
+                cache_key,
+                collection_version,
+                query: key.query.to_string(),
+            }),
+        })
+    }
+
+    /// Store a generated answer in the slot a lookup handed out
+    pub async fn store(&self, slot: &AnswerCacheSlot, answer: &str, results: &[QueryResult]) {
+        let Some(ttl_secs) = self.ttl_secs() else {
+            return;
+        };
+        if answer.trim().is_empty() {
+            return;
+        }
+        let results_json = match serde_json::to_string(results) {
+            Ok(json) => json,
+            Err(err) => {
+                warn!(error = %err, "Failed to encode answer cache results");
+                return;
+            }
+        };
+        let entry = PersistedAnswer {
+            collection_id: slot.collection_id,
+            cache_key: slot.cache_key.clone(),
+            collection_version: slot.collection_version,
+            query_text: slot.query.clone(),
+            answer: answer.to_string(),
+            results_json,
+            hit_count: 0,
+            created_at: chrono::Utc::now().timestamp(),
+        };
+        if let Err(err) = self
+            .rag_repository
+            .save_answer_cache(&entry, ttl_secs)
+            .await
+        {
+            warn!(collection_id = slot.collection_id, error = %err, "Failed to save cached answer");
+        }
+    }
+
+    /// Drop expired and outdated entries; returns how many were removed
+    pub async fn prune(&self) -> Result<u64> {
+        self.rag_repository.prune_answer_cache().await
+    }
+
+    pub async fn clear(&self) -> Result<u64> {
+        self.rag_repository.clear_answer_cache().await
+    }
+
+    /// Drop a collection's answers after a change retrieval depends on
+    /// other than its documents, such as its language settings
+    pub async fn invalidate_collection(&self, collection_id: i64) -> Result<u64> {
+        self.rag_repository
+            .delete_collection_answer_cache(collection_id)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key<'a>(query: &'a str, model: &'a str) -> AnswerCacheKey<'a> {
+        AnswerCacheKey {
+            query,
+            top_k: 5,
+            provider: "openai",
+            model,
+            language: Some("English"),
+            variant: "http",
+        }
+    }
+
+    #[test]
+    fn test_key_ignores_case_and_spacing_only() {
+        let base = key("What is the refund policy?", "gpt-4o");
+        assert_eq!(
+            base.digest(),
+            key("  what is   the REFUND policy? ", "gpt-4o").digest()
+        );
+        assert_ne!(
+            base.digest(),
+            key("What is the refund policy", "gpt-4o").digest()
+        );
+        assert_ne!(
+            base.digest(),
+            key("What is the refund policy?", "gpt-4o-mini").digest()
+        );
+        assert_ne!(
+            base.digest(),
+            AnswerCacheKey {
+                variant: "quick_action",
+                ..key("What is the refund policy?", "gpt-4o")
+            }
+            .digest()
+        );
+    }
+}
@@ -3,6 +3,11 @@ use std::fs;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
+use crate::application::use_cases::chunking::PARENT_SECTION_MAX_TOKENS;
+use crate::application::use_cases::retrieval_service::RoutingThresholds;
+use crate::domain::settings::SettingsSection;
+use crate::infrastructure::config::SettingsStore;
+
 /// RAG system configuration with all tunable parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RagConfig {
@@ -23,6 +28,10 @@ pub struct RagConfig {
 
     // Chat configuration
     pub chat: ChatConfig,
+
+    // PII detection during ingestion
+    #[serde(default)]
+    pub pii: PiiConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -73,6 +82,29 @@ pub struct RetrievalConfig {
 
     /// Whether to use query expansion with synonyms
     pub query_expansion_enabled: bool,
+
+    /// Leave chunks tagged as containing PII out of LLM context
+    #[serde(default)]
+    pub exclude_sensitive_chunks: bool,
+
+    /// Return the parent section around each matched chunk instead of the
+    /// chunk alone
+    #[serde(default = "default_parent_context_enabled")]
+    pub parent_context_enabled: bool,
+
+    /// Token budget of the section returned for each matched chunk
+    #[serde(default = "default_parent_context_max_tokens")]
+    pub parent_context_max_tokens: usize,
+
+    /// Try parameter changes proposed from user feedback on part of the
+    /// queries, and keep them when they get fewer negative ratings
+    #[serde(default)]
+    pub auto_tune: bool,
+
+    /// When queries go to structured rows or keyword search instead of
+    /// semantic retrieval; refit from route corrections
+    #[serde(default)]
+    pub routing: RoutingThresholds,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -89,6 +121,14 @@ pub struct EmbeddingConfig {
     /// Batch size for embedding generation
     pub batch_size: usize,
 
+    /// Embedding requests in flight at once during ingestion
+    #[serde(default = "default_embedding_concurrency")]
+    pub concurrency: usize,
+
+    /// Request budget per minute; 0 uses the provider's default
+    #[serde(default)]
+    pub requests_per_minute: u32,
+
     /// Request timeout in milliseconds
     pub timeout_ms: u64,
 }
@@ -109,6 +149,10 @@ pub struct OcrConfig {
 
     /// Minimum confidence threshold for OCR results (0.0 - 1.0)
     pub min_confidence: f32,
+
+    /// Rebuild reading order across columns and turn aligned cells into markdown tables
+    #[serde(default = "default_ocr_layout_analysis")]
+    pub layout_analysis: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -127,6 +171,22 @@ pub struct CacheConfig {
 
     /// Whether caching is enabled
     pub enabled: bool,
+
+    /// Keep text retrieval results in rag_sense.db across restarts
+    #[serde(default = "default_persist_retrieval_cache")]
+    pub persist_retrieval_cache: bool,
+
+    /// How long persisted retrieval results stay valid, in seconds
+    #[serde(default = "default_persisted_retrieval_cache_ttl_secs")]
+    pub persisted_retrieval_cache_ttl_secs: u64,
+
+    /// Reuse generated answers to repeated questions until the collection changes
+    #[serde(default = "default_answer_cache")]
+    pub answer_cache: bool,
+
+    /// How long a cached answer stays valid, in seconds
+    #[serde(default = "default_answer_cache_ttl_secs")]
+    pub answer_cache_ttl_secs: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -150,6 +210,22 @@ pub struct ChatConfig {
     pub feedback_enabled: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PiiConfig {
+    /// What ingestion does with detected PII: "off", "tag", "redact"
+    pub mode: String,
+
+    pub detect_emails: bool,
+    pub detect_phone_numbers: bool,
+    pub detect_national_ids: bool,
+    pub detect_credit_cards: bool,
+}
+
+impl SettingsSection for RagConfig {
+    const KEY: &'static str = "rag";
+}
+
 impl Default for RagConfig {
     fn default() -> Self {
         Self {
@@ -159,6 +235,7 @@ impl Default for RagConfig {
             ocr: OcrConfig::default(),
             cache: CacheConfig::default(),
             chat: ChatConfig::default(),
+            pii: PiiConfig::default(),
         }
     }
 }
@@ -187,6 +264,11 @@ impl Default for RetrievalConfig {
             rerank_k: default_rerank_k(),
             min_relevance_score: 0.1,
             query_expansion_enabled: true,
+            exclude_sensitive_chunks: false,
+            parent_context_enabled: default_parent_context_enabled(),
+            parent_context_max_tokens: default_parent_context_max_tokens(),
+            auto_tune: false,
+            routing: RoutingThresholds::default(),
         }
     }
 }
@@ -199,13 +281,47 @@ fn default_rerank_k() -> usize {
     75
 }
 
+fn default_parent_context_enabled() -> bool {
+    true
+}
+
+fn default_parent_context_max_tokens() -> usize {
+    600
+}
+
+fn default_ocr_layout_analysis() -> bool {
+    true
+}
+
+fn default_persist_retrieval_cache() -> bool {
+    true
+}
+
+fn default_persisted_retrieval_cache_ttl_secs() -> u64 {
+    24 * 3600
+}
+
+fn default_answer_cache() -> bool {
+    true
+}
+
+fn default_answer_cache_ttl_secs() -> u64 {
+    6 * 3600
+}
+
+fn default_embedding_concurrency() -> usize {
+    4
+}
+
 impl Default for EmbeddingConfig {
     fn default() -> Self {
         Self {
             model: "nomic-embed-text".to_string(),
             dimension: 768,
             api_endpoint: "http://localhost:11434".to_string(),
-            batch_size: 10,
+            batch_size: 32,
+            concurrency: default_embedding_concurrency(),
+            requests_per_minute: 0,
             timeout_ms: 30000,
         }
     }
@@ -219,6 +335,7 @@ impl Default for OcrConfig {
             preprocessing_enabled: true,
             preprocessing_mode: "auto".to_string(),
             min_confidence: 0.6,
+            layout_analysis: true,
         }
     }
 }
@@ -231,6 +348,10 @@ impl Default for CacheConfig {
             retrieval_cache_size: 500,
             retrieval_cache_ttl_secs: 300,
             enabled: true,
+            persist_retrieval_cache: default_persist_retrieval_cache(),
+            persisted_retrieval_cache_ttl_secs: default_persisted_retrieval_cache_ttl_secs(),
+            answer_cache: default_answer_cache(),
+            answer_cache_ttl_secs: default_answer_cache_ttl_secs(),
         }
     }
 }
@@ -248,6 +369,18 @@ impl Default for ChatConfig {
     }
 }
 
+impl Default for PiiConfig {
+    fn default() -> Self {
+        Self {
+            mode: "off".to_string(),
+            detect_emails: true,
+            detect_phone_numbers: true,
+            detect_national_ids: true,
+            detect_credit_cards: true,
+        }
+    }
+}
+
 /// Validation result for configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConfigValidation {
@@ -289,6 +422,20 @@ impl RagConfig {
         if !["vector", "keyword", "hybrid"].contains(&self.retrieval.mode.as_str()) {
             errors.push(format!("Invalid retrieval mode: {}", self.retrieval.mode));
         }
+        if self.retrieval.parent_context_enabled && self.retrieval.parent_context_max_tokens < 50 {
+            errors.push("Parent context budget must be at least 50 tokens".to_string());
+        }
+        if self.retrieval.parent_context_enabled
+            && self.retrieval.parent_context_max_tokens > PARENT_SECTION_MAX_TOKENS
+        {
+            warnings.push(format!(
+                "Parent sections hold at most {} tokens; a larger budget returns them whole",
+                PARENT_SECTION_MAX_TOKENS
+            ));
+        }
+        if self.retrieval.routing.structured_min_signals == 0 {
+            errors.push("Structured routing needs at least 1 signal".to_string());
+        }
         let weight_sum = self.retrieval.vector_weight + self.retrieval.keyword_weight;
         if (weight_sum - 1.0).abs() > 0.01 {
             warnings.push(format!(
@@ -304,6 +451,13 @@ impl RagConfig {
         if self.embedding.batch_size == 0 {
             errors.push("Batch size must be at least 1".to_string());
         }
+        if self.embedding.concurrency == 0 {
+            errors.push("Embedding concurrency must be at least 1".to_string());
+        } else if self.embedding.concurrency > 16 {
+            warnings.push(
+                "Embedding concurrency above 16 is likely to hit provider rate limits".to_string(),
+            );
+        }
         if self.embedding.timeout_ms < 1000 {
             warnings.push("Embedding timeout under 1 second may cause failures".to_string());
         }
@@ -325,6 +479,17 @@ impl RagConfig {
         if self.cache.enabled && self.cache.embedding_cache_size == 0 {
             warnings.push("Cache enabled but embedding cache size is 0".to_string());
         }
+        if self.cache.persist_retrieval_cache && self.cache.persisted_retrieval_cache_ttl_secs == 0
+        {
+            warnings.push(
+                "Retrieval cache persistence enabled but its TTL is 0, nothing will be reused"
+                    .to_string(),
+            );
+        }
+        if self.cache.answer_cache && self.cache.answer_cache_ttl_secs == 0 {
+            warnings
+                .push("Answer cache enabled but its TTL is 0, nothing will be reused".to_string());
+        }
 
         // Validate chat config
         if self.chat.max_history_length == 0 {
@@ -332,6 +497,17 @@ impl RagConfig {
                 .push("Max history length is 0, no conversation context will be used".to_string());
         }
 
+        // Validate PII config
+        if !["off", "tag", "redact"].contains(&self.pii.mode.as_str()) {
+            errors.push(format!("Invalid PII mode: {}", self.pii.mode));
+        }
+        if self.retrieval.exclude_sensitive_chunks && self.pii.mode != "tag" {
+            warnings.push(
+                "Excluding sensitive chunks only applies to chunks tagged while PII mode is \"tag\""
+                    .to_string(),
+            );
+        }
+
         ConfigValidation {
             valid: errors.is_empty(),
             errors,
@@ -344,6 +520,8 @@ impl RagConfig {
 pub struct ConfigManager {
     config: RagConfig,
     config_path: PathBuf,
+    /// Unified settings store; when set it replaces `config_path` for persistence
+    store: Option<Arc<SettingsStore>>,
     dirty: bool,
 }
 
@@ -356,10 +534,29 @@ impl ConfigManager {
         Self {
             config,
             config_path,
+            store: None,
             dirty: false,
         }
     }
 
+    /// Create a config manager backed by the unified settings store
+    pub fn with_store(store: Arc<SettingsStore>) -> Self {
+        Self {
+            config: store.get::<RagConfig>(),
+            config_path: store.path().to_path_buf(),
+            store: Some(store),
+            dirty: false,
+        }
+    }
+
+    /// Re-read the configuration from the settings store, dropping unsaved changes
+    pub fn reload(&mut self) {
+        if let Some(store) = &self.store {
+            self.config = store.get::<RagConfig>();
+            self.dirty = false;
+        }
+    }
+
     /// Load config from file
     fn load_from_file(path: &PathBuf) -> Option<RagConfig> {
         if path.exists() {
@@ -433,6 +630,11 @@ impl ConfigManager {
         self.dirty = true;
     }
 
+    pub fn update_pii(&mut self, config: PiiConfig) {
+        self.config.pii = config;
+        self.dirty = true;
+    }
+
     /// Reset configuration to defaults
     pub fn reset_to_defaults(&mut self) {
         self.config = RagConfig::default();
@@ -445,6 +647,14 @@ impl ConfigManager {
             return Ok(());
         }
 
+        if let Some(store) = &self.store {
+            store
+                .set(&self.config)
+                .map_err(|e| format!("Failed to save config: {}", e))?;
+            self.dirty = false;
+            return Ok(());
+        }
+
         // Ensure parent directory exists
         if let Some(parent) = self.config_path.parent() {
             if !parent.exists() {
@@ -481,6 +691,16 @@ impl SharedConfigManager {
         }
     }
 
+    pub fn with_store(store: Arc<SettingsStore>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(ConfigManager::with_store(store))),
+        }
+    }
+
+    pub fn reload(&self) {
+        self.inner.lock().unwrap().reload();
+    }
+
     pub fn get_config(&self) -> RagConfig {
         self.inner.lock().unwrap().get_config().clone()
     }
@@ -513,6 +733,10 @@ impl SharedConfigManager {
         self.inner.lock().unwrap().update_chat(config);
     }
 
+    pub fn update_pii(&self, config: PiiConfig) {
+        self.inner.lock().unwrap().update_pii(config);
+    }
+
     pub fn reset_to_defaults(&self) {
         self.inner.lock().unwrap().reset_to_defaults();
     }
@@ -1,35 +1,104 @@
-use crate::application::use_cases::chunking::ChunkEngine;
-use crate::application::use_cases::embedding_service::EmbeddingService;
+use crate::application::use_cases::chunking::{
+    parent_sections, Chunk, ChunkEngine, PARENT_SECTION_MAX_TOKENS,
+};
+use crate::application::use_cases::embedding_service::{BatchEmbeddingOptions, EmbeddingService};
+use crate::application::use_cases::pii_detector::PiiStage;
+use crate::application::use_cases::rag_config::SharedConfigManager;
 use crate::domain::error::{AppError, Result};
 use crate::domain::llm_config::LLMConfig;
 use crate::domain::rag_entities::{
-    RagDocument, RagDocumentChunkInput, RagDocumentInput, RagExcelDataInput,
+    IngestionState, RagDocument, RagDocumentChunkInput, RagDocumentInput, RagExcelDataInput,
 };
+use crate::domain::rag_import_templates::ImportTemplate;
 use crate::infrastructure::db::rag::repository::RagRepository;
+use crate::infrastructure::node_runtime::NodeRuntime;
 
+use std::collections::HashSet;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
+mod connectors;
+mod emails;
+mod git_repos;
 mod ocr;
+mod ocr_quality;
 mod parsers;
+mod pipeline;
+mod preview;
+mod resume;
 mod structured_rows;
 mod types;
-
-pub use types::{DocumentQualityAnalysis, ExtractionQuality, OcrPage, OcrResult, ParsedContent};
+mod workbooks;
+mod writing_memory;
+
+pub use pipeline::{IngestionPipelineConfig, IngestionStage};
+pub use types::{
+    DocumentPreview, DocumentQualityAnalysis, ExcelImportOptions, ExtractionQuality,
+    IngestionResumeReport, OcrPage, OcrPageQuality, OcrQueueReport, OcrResult, ParsedContent,
+    WorkbookSheet,
+};
 
 /// Result type for parsing: (pages_content, total_pages, excel_data)
 /// pages_content: Vec of PageContent for documents with page structure (PDF, DOCX)
 /// or plain text for single-page documents (TXT, web)
 type ParseResult = Result<(ParsedContent, i64, Option<Vec<Vec<String>>>)>;
 
+use self::pipeline::PipelineRun;
 use self::structured_rows::{
-    build_row_content, redact_row_for_storage, split_header_and_rows, StructuredRowMapping,
+    build_row_content, match_template, redact_row_for_storage, split_header_and_rows,
+    StructuredRowMapping,
 };
 
 pub struct RagIngestionUseCase {
     rag_repository: Arc<RagRepository>,
     embedding_service: Arc<EmbeddingService>,
     chunk_engine: ChunkEngine,
+    /// Source of embedding batch and PII settings; defaults apply without one
+    config_manager: Option<SharedConfigManager>,
+    /// Playwright install the web OCR capture script runs with
+    node_runtime: Option<Arc<NodeRuntime>>,
+    /// Connectors with a sync in progress
+    connector_syncs: Mutex<HashSet<i64>>,
+    /// Git repositories with a sync in progress
+    git_syncs: Mutex<HashSet<i64>>,
+    /// Held while writing memory entries are checked and stored
+    writing_memory: tokio::sync::Mutex<()>,
+}
+
+/// Log store the ingestion steps report to
+type Logs = std::sync::Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>;
+
+/// Errors of single pages or files kept in a source sync report
+const MAX_REPORTED_ERRORS: usize = 20;
+
+/// Marks a synced source (connector or git repository) as busy until dropped
+struct SyncGuard<'a> {
+    running: &'a Mutex<HashSet<i64>>,
+    id: i64,
+}
+
+impl<'a> SyncGuard<'a> {
+    /// Fails when a sync of the same source is already running
+    fn acquire(running: &'a Mutex<HashSet<i64>>, id: i64, label: &str) -> Result<Self> {
+        let mut ids = running
+            .lock()
+            .map_err(|_| AppError::Internal("Sync state is poisoned".to_string()))?;
+        if !ids.insert(id) {
+            return Err(AppError::ValidationError(format!(
+                "{} is already syncing",
+                label
+            )));
+        }
+        Ok(Self { running, id })
+    }
+}
+
+impl Drop for SyncGuard<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut ids) = self.running.lock() {
+            ids.remove(&self.id);
+        }
+    }
 }
 
 impl RagIngestionUseCase {
@@ -38,6 +107,11 @@ impl RagIngestionUseCase {
             rag_repository,
             embedding_service: Arc::new(EmbeddingService::new(config)),
             chunk_engine: ChunkEngine::default(),
+            config_manager: None,
+            node_runtime: None,
+            connector_syncs: Mutex::new(HashSet::new()),
+            git_syncs: Mutex::new(HashSet::new()),
+            writing_memory: tokio::sync::Mutex::new(()),
         }
     }
 
@@ -49,7 +123,79 @@ impl RagIngestionUseCase {
             rag_repository,
             embedding_service,
             chunk_engine: ChunkEngine::default(),
+            config_manager: None,
+            node_runtime: None,
+            connector_syncs: Mutex::new(HashSet::new()),
+            git_syncs: Mutex::new(HashSet::new()),
+            writing_memory: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn with_config_manager(mut self, config_manager: SharedConfigManager) -> Self {
+        self.config_manager = Some(config_manager);
+        self
+    }
+
+    pub fn with_node_runtime(mut self, node_runtime: Arc<NodeRuntime>) -> Self {
+        self.node_runtime = Some(node_runtime);
+        self
+    }
+
+    /// PII stage for the configured mode; `None` when detection is off
+    fn pii_stage(&self) -> Option<PiiStage> {
+        let manager = self.config_manager.as_ref()?;
+        PiiStage::from_config(&manager.get_config().pii)
+    }
+
+    /// Embed chunk texts in batches, logging the run's throughput
+    async fn embed_chunk_texts(
+        &self,
+        texts: &[String],
+        logs: &std::sync::Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+    ) -> Vec<Result<Vec<f32>>> {
+        use crate::interfaces::http::add_log;
+
+        let options = self
+            .config_manager
+            .as_ref()
+            .map(|manager| BatchEmbeddingOptions::from(&manager.get_config().embedding))
+            .unwrap_or_default();
+        add_log(
+            logs,
+            "INFO",
+            "RAG",
+            &format!("Generating embeddings for {} chunks...", texts.len()),
+        );
+
+        let (embeddings, throughput) = self.embedding_service.embed_batch(texts, &options).await;
+        add_log(
+            logs,
+            "INFO",
+            "RAG",
+            &format!(
+                "Embedded {} chunks ({} cached, {} failed) in {} ms: {:.1} chunks/s, {} requests, batch size {}, {} workers",
+                throughput.embedded,
+                throughput.cached,
+                throughput.failed,
+                throughput.elapsed_ms,
+                throughput.texts_per_second,
+                throughput.requests,
+                throughput.batch_size,
+                throughput.concurrency
+            ),
+        );
+        if throughput.retries > 0 {
+            add_log(
+                logs,
+                "WARN",
+                "RAG",
+                &format!(
+                    "Embedding provider throttled {} requests; they were retried",
+                    throughput.retries
+                ),
+            );
         }
+        embeddings
     }
 
     pub async fn ingest_file(
@@ -104,6 +250,7 @@ impl RagIngestionUseCase {
             "txt" => "txt",
             "md" => "md",
             "web" => "web",
+            "eml" | "mbox" => "email",
             _ => {
                 add_log(
                     &logs,
@@ -125,22 +272,30 @@ impl RagIngestionUseCase {
             &format!("Parsing {} file...", file_type.to_uppercase()),
         );
 
-        let (parsed_content, pages, excel_data) = match file_type {
-            "pdf" => self.parse_pdf(file_path, &logs)?,
-            "docx" => self.parse_docx(file_path, &logs)?,
-            "xlsx" => self.parse_xlsx(file_path, &logs)?,
-            "csv" => self.parse_csv(file_path, &logs)?,
-            "txt" | "md" => self.parse_txt(file_path, &logs)?,
-            "web" => self.parse_web(file_path, &logs).await?,
-            _ => unreachable!(),
-        };
+        if file_type == "email" {
+            return self
+                .ingest_email_file(file_path, &file_name, collection_id, &logs)
+                .await;
+        }
 
-        add_log(
-            &logs,
-            "INFO",
-            "RAG",
-            &format!("Parsed file: {} pages", pages),
-        );
+        // Every visible sheet of a workbook gets its own document; the first
+        // sheet's is returned
+        if file_type == "xlsx" {
+            let documents = self
+                .ingest_workbook(
+                    file_path,
+                    collection_id,
+                    &ExcelImportOptions::default(),
+                    &logs,
+                )
+                .await?;
+            return documents.into_iter().next().ok_or_else(|| {
+                AppError::ValidationError(format!("No sheets imported from {}", file_name))
+            });
+        }
+
+        self.discard_previous_import(collection_id, file_path)
+            .await?;
 
         let document_input = RagDocumentInput {
             collection_id,
@@ -148,7 +303,7 @@ impl RagIngestionUseCase {
             file_path: Some(file_path.to_string()),
             file_type: file_type.to_string(),
             language: Some("auto".to_string()),
-            total_pages: Some(pages),
+            total_pages: None,
         };
 
         add_log(&logs, "INFO", "RAG", "Creating document record...");
@@ -160,103 +315,281 @@ impl RagIngestionUseCase {
             "RAG",
             &format!("Document created with ID: {}", document.id),
         );
+        self.rag_repository
+            .set_ingestion_state(document.id, IngestionState::Extracting)
+            .await?;
+
+        if let Err(e) = self
+            .import_file_content(&document, file_path, false, &logs)
+            .await
+        {
+            self.record_ingestion_failure(document.id, &e, &logs).await;
+            return Err(e);
+        }
+        self.rag_repository.get_document(document.id).await
+    }
+
+    /// Start over a failed import of the same path, or one whose document
+    /// was moved to this collection's trash. Paths are unique across
+    /// collections, so a document trashed elsewhere still blocks the import.
+    async fn discard_previous_import(
+        &self,
+        collection_id: Option<i64>,
+        file_path: &str,
+    ) -> Result<()> {
+        if let Some(failed) = self
+            .rag_repository
+            .list_failed_ingestions(None)
+            .await?
+            .into_iter()
+            .find(|document| document.file_path.as_deref() == Some(file_path))
+        {
+            self.rag_repository.delete_document(failed.id).await?;
+        }
+        self.rag_repository
+            .purge_trashed_document_by_path(collection_id, file_path)
+            .await?;
+        Ok(())
+    }
+
+    /// Extract, chunk and embed the file of a tracked document, recording
+    /// each stage; structured rows are not stored again when `rows_stored`.
+    /// `file_path` is the document's path, which names the sheet of a workbook.
+    async fn import_file_content(
+        &self,
+        document: &RagDocument,
+        file_path: &str,
+        rows_stored: bool,
+        logs: &Logs,
+    ) -> Result<()> {
+        use crate::interfaces::http::add_log;
+
+        let file_type = document.file_type.as_str();
+        let (parsed_content, pages, excel_data) = match file_type {
+            "pdf" => self.parse_pdf(file_path, logs)?,
+            "docx" => self.parse_docx(file_path, logs)?,
+            "xlsx" => self.parse_xlsx(file_path, logs)?,
+            "csv" => self.parse_csv(file_path, logs)?,
+            "txt" | "md" => self.parse_txt(file_path, logs)?,
+            "web" => self.parse_web(file_path, logs).await?,
+            other => {
+                return Err(AppError::ValidationError(format!(
+                    "Unsupported file type: {}",
+                    other
+                )))
+            }
+        };
+
+        add_log(
+            logs,
+            "INFO",
+            "RAG",
+            &format!("Parsed file: {} pages", pages),
+        );
+        self.rag_repository
+            .set_document_total_pages(document.id, pages)
+            .await?;
+        let document = &self.rag_repository.get_document(document.id).await?;
+
+        if let Some(excel_rows) = excel_data.filter(|_| !rows_stored) {
+            self.store_structured_rows(document, &excel_rows, logs)
+                .await?;
+        }
 
-        if let Some(excel_rows) = excel_data {
+        self.store_chunks_for_document(
+            document,
+            parsed_content,
+            &document.file_name,
+            file_type,
+            logs,
+        )
+        .await?;
+        self.store_document_preview(document, logs).await;
+        self.rag_repository
+            .set_ingestion_state(document.id, IngestionState::Complete)
+            .await
+    }
+
+    /// Keep the error of a tracked import on its document, so it can be
+    /// resumed
+    async fn record_ingestion_failure(&self, doc_id: i64, error: &AppError, logs: &Logs) {
+        use crate::interfaces::http::add_log;
+
+        if let Err(e) = self
+            .rag_repository
+            .fail_ingestion(doc_id, &error.to_string())
+            .await
+        {
             add_log(
-                &logs,
-                "INFO",
+                logs,
+                "ERROR",
                 "RAG",
-                &format!("Storing {} structured rows...", excel_rows.len()),
+                &format!(
+                    "Failed to record import failure of document {}: {}",
+                    doc_id, e
+                ),
             );
+        }
+    }
+
+    /// Store the rows of a spreadsheet or CSV file as structured rows
+    async fn store_structured_rows(
+        &self,
+        document: &RagDocument,
+        excel_rows: &[Vec<String>],
+        logs: &Logs,
+    ) -> Result<()> {
+        use crate::interfaces::http::add_log;
 
-            // 1) Keep the old excel_data table for backward compatibility.
-            // 2) Also populate structured_rows so aggregate/list/count queries are accurate.
-            //
-            // Mapping strategy (v1):
-            // - Treat the first row as header if it looks like headers.
-            // - Map category/source/title/created_at by matching header names.
-
-            let (header, data_rows) = split_header_and_rows(&excel_rows);
-            let mapping = StructuredRowMapping::from_header(header.as_deref());
-
-            let mut structured_batch: Vec<(
-                i64,
-                Option<String>,
-                Option<String>,
-                Option<String>,
-                Option<String>,
-                Option<String>,
-                Option<String>,
-                String,
-            )> = Vec::new();
-
-            for (row_index, data) in data_rows.iter().enumerate() {
-                let redacted_row = redact_row_for_storage(header.as_deref(), data);
-                let data_json =
-                    serde_json::to_string::<Vec<String>>(&redacted_row).map_err(|e| {
-                        AppError::Internal(format!("Failed to serialize row data: {}", e))
-                    })?;
-
-                // Legacy excel_data storage (val_a/val_b/val_c) - store redacted cells.
-                let excel_input = RagExcelDataInput {
-                    doc_id: document.id,
-                    row_index: row_index as i64,
-                    data_json: Some(data_json.clone()),
-                    val_a: redacted_row.get(0).cloned(),
-                    val_b: redacted_row.get(1).cloned(),
-                    val_c: redacted_row.get(2).and_then(|s| s.parse::<f64>().ok()),
-                };
+        let doc_id = document.id;
 
-                self.rag_repository
-                    .create_excel_data(&excel_input)
-                    .await
-                    .map_err(|e| {
+        add_log(
+            logs,
+            "INFO",
+            "RAG",
+            &format!("Storing {} structured rows...", excel_rows.len()),
+        );
+
+        // 1) Keep the old excel_data table for backward compatibility.
+        // 2) Also populate structured_rows so aggregate/list/count queries are accurate.
+        //
+        // Mapping strategy (v1):
+        // - Read the rows with a saved import template when one matches.
+        // - Otherwise treat the first row as header if it looks like headers.
+        // - Map category/source/title/created_at by matching header names.
+
+        let templates = self.import_templates(document.collection_id, logs).await;
+        let (header, data_rows, mapping) =
+            match match_template(&templates, document.collection_id, excel_rows) {
+                Some((template, templated)) => {
+                    add_log(
+                        logs,
+                        "INFO",
+                        "RAG",
+                        &format!(
+                            "Reading rows with import template '{}' ({} values kept as text)",
+                            template.name, templated.coercion_failures
+                        ),
+                    );
+                    if let Err(e) = self
+                        .rag_repository
+                        .record_import_template_use(template.id)
+                        .await
+                    {
                         add_log(
-                            &logs,
-                            "ERROR",
+                            logs,
+                            "WARN",
                             "RAG",
-                            &format!("Failed to store Excel row {}: {}", row_index + 1, e),
+                            &format!(
+                                "Failed to record use of import template {}: {}",
+                                template.id, e
+                            ),
                         );
-                        AppError::Internal(format!("Failed to store Excel row: {}", e))
-                    })?;
-
-                let extracted = mapping.extract(data);
-
-                // Structured rows: include a readable content string.
-                let content = Some(build_row_content(header.as_deref(), data));
-
-                structured_batch.push((
-                    row_index as i64,
-                    extracted.category,
-                    extracted.source,
-                    extracted.title,
-                    extracted.created_at_text,
-                    extracted.created_at,
-                    content,
-                    data_json,
-                ));
-            }
+                    }
+                    (Some(templated.header), templated.rows, templated.mapping)
+                }
+                None => {
+                    let (header, data_rows) = split_header_and_rows(excel_rows);
+                    let mapping = StructuredRowMapping::from_header(header.as_deref());
+                    (header, data_rows, mapping)
+                }
+            };
+
+        let mut structured_batch: Vec<(
+            i64,
+            Option<String>,
+            Option<String>,
+            Option<String>,
+            Option<String>,
+            Option<String>,
+            Option<String>,
+            String,
+        )> = Vec::new();
+
+        for (row_index, data) in data_rows.iter().enumerate() {
+            let redacted_row = redact_row_for_storage(header.as_deref(), data);
+            let data_json = serde_json::to_string::<Vec<String>>(&redacted_row)
+                .map_err(|e| AppError::Internal(format!("Failed to serialize row data: {}", e)))?;
+
+            // Legacy excel_data storage (val_a/val_b/val_c) - store redacted cells.
+            let excel_input = RagExcelDataInput {
+                doc_id,
+                row_index: row_index as i64,
+                data_json: Some(data_json.clone()),
+                val_a: redacted_row.get(0).cloned(),
+                val_b: redacted_row.get(1).cloned(),
+                val_c: redacted_row.get(2).and_then(|s| s.parse::<f64>().ok()),
+            };
 
             self.rag_repository
-                .insert_structured_rows(document.id, structured_batch)
+                .create_excel_data(&excel_input)
                 .await
                 .map_err(|e| {
                     add_log(
-                        &logs,
+                        logs,
                         "ERROR",
                         "RAG",
-                        &format!("Failed to store structured rows: {}", e),
+                        &format!("Failed to store Excel row {}: {}", row_index + 1, e),
                     );
-                    AppError::Internal(format!("Failed to store structured rows: {}", e))
+                    AppError::Internal(format!("Failed to store Excel row: {}", e))
                 })?;
 
-            add_log(&logs, "INFO", "RAG", "Structured rows stored");
+            let extracted = mapping.extract(data);
+
+            // Structured rows: include a readable content string.
+            let content = Some(build_row_content(header.as_deref(), data));
+
+            structured_batch.push((
+                row_index as i64,
+                extracted.category,
+                extracted.source,
+                extracted.title,
+                extracted.created_at_text,
+                extracted.created_at,
+                content,
+                data_json,
+            ));
         }
 
-        self.store_chunks_for_document(&document, parsed_content, &file_name, file_type, &logs)
-            .await?;
+        self.rag_repository
+            .insert_structured_rows(doc_id, structured_batch)
+            .await
+            .map_err(|e| {
+                add_log(
+                    logs,
+                    "ERROR",
+                    "RAG",
+                    &format!("Failed to store structured rows: {}", e),
+                );
+                AppError::Internal(format!("Failed to store structured rows: {}", e))
+            })?;
 
-        Ok(document)
+        add_log(logs, "INFO", "RAG", "Structured rows stored");
+
+        Ok(())
+    }
+
+    /// Import templates a file imported into `collection_id` can match; none
+    /// when they can't be loaded, so the rows are still stored
+    async fn import_templates(
+        &self,
+        collection_id: Option<i64>,
+        logs: &Logs,
+    ) -> Vec<ImportTemplate> {
+        use crate::interfaces::http::add_log;
+
+        self.rag_repository
+            .list_import_templates(collection_id)
+            .await
+            .unwrap_or_else(|e| {
+                add_log(
+                    logs,
+                    "WARN",
+                    "RAG",
+                    &format!("Failed to load import templates: {}", e),
+                );
+                Vec::new()
+            })
     }
 
     pub async fn ingest_web_html(
@@ -343,8 +676,25 @@ impl RagIngestionUseCase {
     ) -> Result<()> {
         use crate::interfaces::http::add_log;
 
+        self.rag_repository
+            .advance_ingestion_state(document.id, IngestionState::Chunking)
+            .await?;
+        let pipeline = self.pipeline_run(document).await?;
+        let parsed_content = pipeline
+            .run_content_hooks(IngestionStage::Extract, parsed_content, logs)
+            .await?;
+        let parsed_content = pipeline.config.clean.clean(parsed_content);
+        let parsed_content = pipeline
+            .run_content_hooks(IngestionStage::Clean, parsed_content, logs)
+            .await?;
+        let chunk_engine = pipeline.chunk_engine();
+
         let chunks = match parsed_content {
-            ParsedContent::Pages(ref page_contents) => {
+            ParsedContent::Pages(ref page_contents)
+            | ParsedContent::OcrPages {
+                pages: ref page_contents,
+                ..
+            } => {
                 let total_chars: usize = page_contents.iter().map(|p| p.content.len()).sum();
                 add_log(
                     logs,
@@ -357,7 +707,7 @@ impl RagIngestionUseCase {
                     ),
                 );
 
-                self.chunk_engine
+                chunk_engine
                     .chunk_pages(page_contents)
                     .map_err(|e| AppError::Internal(format!("Failed to chunk pages: {}", e)))?
             }
@@ -369,7 +719,7 @@ impl RagIngestionUseCase {
                     &format!("Chunking plain text ({} chars)...", text_content.len()),
                 );
 
-                self.chunk_engine
+                chunk_engine
                     .chunk_text(text_content)
                     .map_err(|e| AppError::Internal(format!("Failed to chunk text: {}", e)))?
             }
@@ -395,7 +745,106 @@ impl RagIngestionUseCase {
             &format!("Created {} chunks", chunks.len()),
         );
 
-        for (chunk_index, chunk) in chunks.iter().enumerate() {
+        let (chunks, stored) = self.store_chunk_rows(&pipeline, &chunks, 0, logs).await?;
+
+        // Compute and store document quality metrics
+        add_log(logs, "INFO", "RAG", "Computing document quality metrics...");
+
+        // Calculate average chunk quality
+        let chunk_count = chunks.len() as i64;
+        let total_quality: f64 = chunks
+            .iter()
+            .filter_map(|c| c.quality_score.map(|q| q as f64))
+            .sum();
+        let avg_quality = if chunk_count > 0 && total_quality > 0.0 {
+            Some(total_quality / chunk_count as f64)
+        } else {
+            None
+        };
+
+        let (ocr_confidence, warning_count) = match parsed_content {
+            ParsedContent::OcrPages { ref quality, .. } => {
+                self.record_ocr_quality(document.id, quality, logs).await?
+            }
+            _ => (None, 0),
+        };
+
+        // Update document with quality metrics
+        self.rag_repository
+            .update_document_quality(
+                document.id,
+                avg_quality,
+                ocr_confidence,
+                chunk_count,
+                warning_count,
+            )
+            .await
+            .map_err(|e| {
+                add_log(
+                    logs,
+                    "ERROR",
+                    "RAG",
+                    &format!("Failed to update document quality: {}", e),
+                );
+                AppError::Internal(format!("Failed to update document quality: {}", e))
+            })?;
+
+        add_log(
+            logs,
+            "INFO",
+            "RAG",
+            &format!("Document quality: {:.2}", avg_quality.unwrap_or(0.0)),
+        );
+
+        // Embedding comes last so an interrupted import resumes with it alone
+        self.embed_stored_chunks(&pipeline, &stored, logs).await?;
+        add_log(logs, "INFO", "RAG", "All chunks processed successfully");
+
+        add_log(logs, "INFO", "RAG", "Import completed successfully");
+
+        Ok(())
+    }
+
+    /// Run chunks from `first_index` on through the chunk hooks, embed and
+    /// index stages of `pipeline`: each is stored with its quality and PII
+    /// flags and, unless the collection turned embedding off, its embedding.
+    /// Returns the chunks as stored.
+    async fn store_chunks(
+        &self,
+        pipeline: &PipelineRun,
+        chunks: &[Chunk],
+        first_index: i64,
+        logs: &std::sync::Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+    ) -> Result<Vec<Chunk>> {
+        let (chunks, stored) = self
+            .store_chunk_rows(pipeline, chunks, first_index, logs)
+            .await?;
+        self.embed_stored_chunks(pipeline, &stored, logs).await?;
+        Ok(chunks)
+    }
+
+    /// Chunk hooks and index stage of `store_chunks`: stores the chunks
+    /// without embeddings. Returns the chunks as stored along with the ID
+    /// and embedding text of each.
+    async fn store_chunk_rows(
+        &self,
+        pipeline: &PipelineRun,
+        chunks: &[Chunk],
+        first_index: i64,
+        logs: &std::sync::Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+    ) -> Result<(Vec<Chunk>, Vec<(i64, String)>)> {
+        use crate::interfaces::http::add_log;
+
+        let doc_id = pipeline.doc_id();
+        let chunks = pipeline
+            .run_chunk_hooks(self, chunks.to_vec(), logs)
+            .await?;
+
+        let pii_stage = self.pii_stage();
+        let parents = parent_sections(&chunks, PARENT_SECTION_MAX_TOKENS);
+        let mut stored = Vec::with_capacity(chunks.len());
+        let mut pii_chunks = 0;
+        for (position, chunk) in chunks.iter().enumerate() {
             let page_info = chunk
                 .page_number
                 .map(|p| format!(" (page {})", p))
@@ -405,20 +854,25 @@ impl RagIngestionUseCase {
                 "INFO",
                 "RAG",
                 &format!(
-                    "Processing chunk {}/{}{}...",
-                    chunk_index + 1,
+                    "Storing chunk {}/{}{}...",
+                    position + 1,
                     chunks.len(),
                     page_info
                 ),
             );
 
+            let screened = pii_stage.as_ref().map(|stage| stage.screen(&chunk.content));
+            let content = screened
+                .as_ref()
+                .map_or_else(|| chunk.content.clone(), |s| s.content.clone());
             let chunk_input = RagDocumentChunkInput {
-                doc_id: document.id,
-                content: chunk.content.clone(),
+                doc_id,
+                content: content.clone(),
                 page_number: chunk.page_number,
                 page_offset: chunk.page_offset,
-                chunk_index: chunk_index as i64,
+                chunk_index: first_index + position as i64,
                 token_count: Some(chunk.token_count as i64),
+                parent_index: Some(first_index + parents[position] as i64),
             };
 
             let created_chunk = self
@@ -430,7 +884,7 @@ impl RagIngestionUseCase {
                         logs,
                         "ERROR",
                         "RAG",
-                        &format!("Failed to store chunk {}: {}", chunk_index + 1, e),
+                        &format!("Failed to store chunk {}: {}", position + 1, e),
                     );
                     AppError::Internal(format!("Failed to store chunk: {}", e))
                 })?;
@@ -447,22 +901,60 @@ impl RagIngestionUseCase {
                     .await;
             }
 
+            if let Some(screened) = screened.filter(|s| !s.kinds.is_empty()) {
+                pii_chunks += 1;
+                self.rag_repository
+                    .update_chunk_pii(created_chunk.id, &screened.pii_types(), screened.sensitive)
+                    .await?;
+            }
+
+            stored.push((created_chunk.id, content));
+        }
+
+        if pii_chunks > 0 {
             add_log(
                 logs,
                 "INFO",
                 "RAG",
-                &format!("Generating embedding for chunk {}...", chunk_index + 1),
+                &format!("PII found in {} of {} chunks", pii_chunks, chunks.len()),
             );
+        }
 
-            match self
-                .embedding_service
-                .generate_embedding(&chunk.content)
-                .await
-            {
+        Ok((chunks, stored))
+    }
+
+    /// Embed stage of `store_chunks` for chunks already stored, given by ID
+    /// and text. A chunk whose embedding fails is logged and left without
+    /// one.
+    async fn embed_stored_chunks(
+        &self,
+        pipeline: &PipelineRun,
+        stored: &[(i64, String)],
+        logs: &std::sync::Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+    ) -> Result<()> {
+        use crate::interfaces::http::add_log;
+
+        if !pipeline.config.embed.enabled {
+            add_log(
+                logs,
+                "INFO",
+                "RAG",
+                "Embed stage is off for this collection; storing chunks without embeddings",
+            );
+            return Ok(());
+        }
+        self.rag_repository
+            .advance_ingestion_state(pipeline.doc_id(), IngestionState::Embedding)
+            .await?;
+
+        let texts: Vec<String> = stored.iter().map(|(_, text)| text.clone()).collect();
+        let embeddings = self.embed_chunk_texts(&texts, logs).await;
+        for (position, ((chunk_id, _), embedding)) in stored.iter().zip(embeddings).enumerate() {
+            match embedding {
                 Ok(embedding) => {
-                    let embedding_bytes = EmbeddingService::embedding_to_bytes(&embedding);
+                    let embedding_bytes = pipeline.config.embed.stored_bytes(embedding);
                     self.rag_repository
-                        .update_chunk_embedding(created_chunk.id, &embedding_bytes)
+                        .update_chunk_embedding(*chunk_id, &embedding_bytes)
                         .await
                         .map_err(|e| {
                             add_log(
@@ -471,18 +963,12 @@ impl RagIngestionUseCase {
                                 "RAG",
                                 &format!(
                                     "Failed to update chunk embedding {}: {}",
-                                    chunk_index + 1,
+                                    position + 1,
                                     e
                                 ),
                             );
                             AppError::Internal(format!("Failed to update chunk embedding: {}", e))
                         })?;
-                    add_log(
-                        logs,
-                        "INFO",
-                        "RAG",
-                        &format!("Chunk {}/{} processed", chunk_index + 1, chunks.len()),
-                    );
                 }
                 Err(e) => {
                     add_log(
@@ -491,54 +977,13 @@ impl RagIngestionUseCase {
                         "RAG",
                         &format!(
                             "Failed to generate embedding for chunk {}: {}",
-                            chunk_index + 1,
+                            position + 1,
                             e
                         ),
                     );
                 }
             }
         }
-
-        add_log(logs, "INFO", "RAG", "All chunks processed successfully");
-
-        // Compute and store document quality metrics
-        add_log(logs, "INFO", "RAG", "Computing document quality metrics...");
-
-        // Calculate average chunk quality
-        let chunk_count = chunks.len() as i64;
-        let total_quality: f64 = chunks
-            .iter()
-            .filter_map(|c| c.quality_score.map(|q| q as f64))
-            .sum();
-        let avg_quality = if chunk_count > 0 && total_quality > 0.0 {
-            Some(total_quality / chunk_count as f64)
-        } else {
-            None
-        };
-
-        // Update document with quality metrics
-        self.rag_repository
-            .update_document_quality(document.id, avg_quality, None, chunk_count, 0)
-            .await
-            .map_err(|e| {
-                add_log(
-                    logs,
-                    "ERROR",
-                    "RAG",
-                    &format!("Failed to update document quality: {}", e),
-                );
-                AppError::Internal(format!("Failed to update document quality: {}", e))
-            })?;
-
-        add_log(
-            logs,
-            "INFO",
-            "RAG",
-            &format!("Document quality: {:.2}", avg_quality.unwrap_or(0.0)),
-        );
-
-        add_log(logs, "INFO", "RAG", "Import completed successfully");
-
         Ok(())
     }
 
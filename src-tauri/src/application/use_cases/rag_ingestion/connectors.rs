@@ -0,0 +1,242 @@
+//! Sync of Notion and Confluence connectors: pages edited since the last
+//! sync are re-imported, pages gone from the source lose their documents.
+
+use std::collections::{HashMap, HashSet};
+
+use super::{Logs, ParsedContent, RagIngestionUseCase, SyncGuard, MAX_REPORTED_ERRORS};
+use crate::domain::error::{AppError, ErrorCode, Result};
+use crate::domain::rag_connectors::{ConnectorPage, ConnectorSyncReport, RagConnector};
+use crate::domain::rag_entities::RagDocumentInput;
+use crate::infrastructure::rag_connectors::{ConnectorClient, SourcePage};
+use crate::interfaces::http::add_log;
+
+impl RagIngestionUseCase {
+    /// Bring the connector's collection up to date with the source and
+    /// record the outcome on the connector
+    pub async fn sync_connector(
+        &self,
+        connector: &RagConnector,
+        token: &str,
+        logs: Logs,
+    ) -> Result<ConnectorSyncReport> {
+        let _guard = SyncGuard::acquire(
+            &self.connector_syncs,
+            connector.id,
+            &format!("Connector '{}'", connector.name),
+        )?;
+        add_log(
+            &logs,
+            "INFO",
+            "RAG",
+            &format!(
+                "Syncing {} connector '{}'",
+                connector.kind.display_name(),
+                connector.name
+            ),
+        );
+
+        let started_at = chrono::Utc::now().timestamp();
+        match self
+            .run_connector_sync(connector, token, &logs, started_at)
+            .await
+        {
+            Ok(report) => {
+                self.rag_repository
+                    .record_connector_sync(connector.id, &report, None)
+                    .await?;
+                Ok(report)
+            }
+            Err(err) => {
+                let report = ConnectorSyncReport {
+                    connector_id: connector.id,
+                    started_at,
+                    finished_at: chrono::Utc::now().timestamp(),
+                    ..Default::default()
+                };
+                self.rag_repository
+                    .record_connector_sync(connector.id, &report, Some(&err.to_string()))
+                    .await?;
+                Err(err)
+            }
+        }
+    }
+
+    async fn run_connector_sync(
+        &self,
+        connector: &RagConnector,
+        token: &str,
+        logs: &Logs,
+        started_at: i64,
+    ) -> Result<ConnectorSyncReport> {
+        let client = ConnectorClient::new(connector, token);
+        let source_pages = client.list_pages().await?;
+        let stored: HashMap<String, ConnectorPage> = self
+            .rag_repository
+            .list_connector_pages(connector.id)
+            .await?
+            .into_iter()
+            .map(|page| (page.external_id.clone(), page))
+            .collect();
+
+        // An empty listing usually means the token lost access rather than
+        // every page being deleted
+        if source_pages.is_empty() && !stored.is_empty() {
+            return Err(AppError::coded(
+                ErrorCode::ProviderUnauthorized,
+                format!(
+                    "{} returned no pages; keeping the {} imported ones",
+                    connector.kind.display_name(),
+                    stored.len()
+                ),
+            )
+            .with_hint("Check that the pages are still shared with the connector's token"));
+        }
+
+        let mut report = ConnectorSyncReport {
+            connector_id: connector.id,
+            started_at,
+            ..Default::default()
+        };
+
+        for page in &source_pages {
+            let path = page.path();
+            let previous = stored.get(&page.external_id);
+            let mut record = ConnectorPage {
+                connector_id: connector.id,
+                external_id: page.external_id.clone(),
+                parent_external_id: page.parent_id.clone(),
+                title: page.title.clone(),
+                path: path.clone(),
+                url: page.url.clone(),
+                last_edited_at: page.last_edited_at,
+                doc_id: None,
+                synced_at: chrono::Utc::now().timestamp(),
+            };
+
+            if let Some(previous) = previous.filter(|previous| {
+                previous.doc_id.is_some() && previous.last_edited_at >= page.last_edited_at
+            }) {
+                // Moved or renamed pages keep their document
+                let moved = previous.path != record.path
+                    || previous.parent_external_id != record.parent_external_id
+                    || previous.url != record.url;
+                if moved {
+                    record.doc_id = previous.doc_id;
+                    self.rag_repository.upsert_connector_page(&record).await?;
+                }
+                report.unchanged += 1;
+                continue;
+            }
+
+            match self
+                .import_connector_page(connector, &client, page, &path, logs)
+                .await
+            {
+                Ok(doc_id) => {
+                    match previous.and_then(|previous| previous.doc_id) {
+                        Some(old_doc_id) => {
+                            self.rag_repository.delete_document(old_doc_id).await?;
+                            report.updated += 1;
+                        }
+                        None => report.added += 1,
+                    }
+                    record.doc_id = Some(doc_id);
+                    self.rag_repository.upsert_connector_page(&record).await?;
+                }
+                Err(err) => {
+                    add_log(
+                        logs,
+                        "WARN",
+                        "RAG",
+                        &format!("Failed to import '{}': {}", path, err),
+                    );
+                    report.failed += 1;
+                    if report.errors.len() < MAX_REPORTED_ERRORS {
+                        report.errors.push(format!("{}: {}", path, err));
+                    }
+                }
+            }
+        }
+
+        let listed: HashSet<&str> = source_pages
+            .iter()
+            .map(|page| page.external_id.as_str())
+            .collect();
+        for page in stored.values() {
+            if listed.contains(page.external_id.as_str()) {
+                continue;
+            }
+            if let Some(doc_id) = page.doc_id {
+                self.rag_repository.delete_document(doc_id).await?;
+            }
+            self.rag_repository
+                .delete_connector_page(connector.id, &page.external_id)
+                .await?;
+            report.removed += 1;
+        }
+
+        report.finished_at = chrono::Utc::now().timestamp();
+        add_log(
+            logs,
+            if report.failed == 0 { "INFO" } else { "WARN" },
+            "RAG",
+            &format!(
+                "Connector '{}' synced: {} added, {} updated, {} unchanged, {} removed, {} failed",
+                connector.name,
+                report.added,
+                report.updated,
+                report.unchanged,
+                report.removed,
+                report.failed
+            ),
+        );
+        Ok(report)
+    }
+
+    /// Import one page as a document whose text starts with its place in
+    /// the page tree; returns the new document's id
+    async fn import_connector_page(
+        &self,
+        connector: &RagConnector,
+        client: &ConnectorClient<'_>,
+        page: &SourcePage,
+        path: &str,
+        logs: &Logs,
+    ) -> Result<i64> {
+        let body = client.page_text(page).await?;
+        let mut text = format!("{}\nPath: {}\n", page.title, path);
+        if let Some(url) = &page.url {
+            text.push_str(&format!("Source: {}\n", url));
+        }
+        text.push('\n');
+        text.push_str(&body);
+
+        let file_type = connector.kind.as_str();
+        let document = self
+            .rag_repository
+            .create_document(&RagDocumentInput {
+                collection_id: Some(connector.collection_id),
+                file_name: page.title.clone(),
+                file_path: page.url.clone(),
+                file_type: file_type.to_string(),
+                language: Some("auto".to_string()),
+                total_pages: Some(1),
+            })
+            .await?;
+
+        if let Err(err) = self
+            .store_chunks_for_document(
+                &document,
+                ParsedContent::Plain(Some(text)),
+                &page.title,
+                file_type,
+                logs,
+            )
+            .await
+        {
+            let _ = self.rag_repository.delete_document(document.id).await;
+            return Err(err);
+        }
+        Ok(document.id)
+    }
+}
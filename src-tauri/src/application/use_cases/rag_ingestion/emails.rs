@@ -0,0 +1,192 @@
+//! Import of `.eml` files and mbox archives: one document per file, chunked
+//! per message, with each message's sender, date and thread stored for
+//! filtering.
+
+use super::{Logs, RagIngestionUseCase};
+use crate::application::use_cases::chunking::Chunk;
+use crate::application::use_cases::email_parsing::{parse_mailbox, thread_ids, EmailMessage};
+use crate::domain::error::{AppError, Result};
+use crate::domain::rag_email::EmailMessageInput;
+use crate::domain::rag_entities::{RagDocument, RagDocumentInput};
+use crate::interfaces::http::add_log;
+
+impl RagIngestionUseCase {
+    pub(super) async fn ingest_email_file(
+        &self,
+        file_path: &str,
+        file_name: &str,
+        collection_id: Option<i64>,
+        logs: &Logs,
+    ) -> Result<RagDocument> {
+        let bytes = std::fs::read(file_path).map_err(|e| {
+            add_log(
+                logs,
+                "ERROR",
+                "RAG",
+                &format!("Failed to read email file {}: {}", file_path, e),
+            );
+            AppError::Internal(format!("Failed to read email file: {}", e))
+        })?;
+        let messages = parse_mailbox(&bytes);
+        if messages.is_empty() {
+            return Err(AppError::ValidationError(format!(
+                "No email messages found in {}",
+                file_name
+            )));
+        }
+        let threads = thread_ids(&messages);
+        add_log(
+            logs,
+            "INFO",
+            "RAG",
+            &format!(
+                "Parsed {} messages in {} threads",
+                messages.len(),
+                threads
+                    .iter()
+                    .collect::<std::collections::HashSet<_>>()
+                    .len()
+            ),
+        );
+
+        let mut chunks = Vec::new();
+        let mut records = Vec::with_capacity(messages.len());
+        for (i, (message, thread_id)) in messages.iter().zip(threads).enumerate() {
+            let message_index = i as i64 + 1;
+            let message_chunks = self.message_chunks(message, message_index)?;
+            records.push(EmailMessageInput {
+                message_index,
+                message_id: message.message_id.clone(),
+                thread_id,
+                in_reply_to: message.in_reply_to.clone(),
+                subject: message.subject.clone(),
+                from_address: message.from.as_ref().map(|from| from.address.clone()),
+                from_name: message.from.as_ref().and_then(|from| from.name.clone()),
+                recipients: recipients(message),
+                sent_at: message.sent_at,
+                attachments: message.attachments.join(", "),
+                chunk_count: message_chunks.len() as i64,
+            });
+            chunks.extend(message_chunks);
+        }
+
+        let document = self
+            .rag_repository
+            .create_document(&RagDocumentInput {
+                collection_id,
+                file_name: file_name.to_string(),
+                file_path: Some(file_path.to_string()),
+                file_type: "email".to_string(),
+                language: Some("auto".to_string()),
+                total_pages: Some(messages.len() as i64),
+            })
+            .await?;
+        add_log(
+            logs,
+            "INFO",
+            "RAG",
+            &format!(
+                "Document created with ID: {}; storing {} chunks",
+                document.id,
+                chunks.len()
+            ),
+        );
+
+        let stored = async {
+            self.rag_repository
+                .insert_email_messages(document.id, &records)
+                .await?;
+            let pipeline = self.pipeline_run(&document).await?;
+            let chunks = self.store_chunks(&pipeline, &chunks, 0, logs).await?;
+            let scores: Vec<f64> = chunks
+                .iter()
+                .filter_map(|chunk| chunk.quality_score.map(f64::from))
+                .collect();
+            let quality =
+                (!scores.is_empty()).then(|| scores.iter().sum::<f64>() / scores.len() as f64);
+            self.rag_repository
+                .update_document_quality(document.id, quality, None, chunks.len() as i64, 0)
+                .await
+        };
+        if let Err(err) = stored.await {
+            let _ = self.rag_repository.delete_document(document.id).await;
+            return Err(err);
+        }
+
+        add_log(logs, "INFO", "RAG", "Import completed successfully");
+        Ok(document)
+    }
+
+    /// Chunks of one message's text, each led by the message's headers;
+    /// a message without text still gets one chunk so its subject is found
+    fn message_chunks(&self, message: &EmailMessage, message_index: i64) -> Result<Vec<Chunk>> {
+        let header = message_header(message);
+        let body_chunks = if message.body.trim().is_empty() {
+            Vec::new()
+        } else {
+            self.chunk_engine
+                .chunk_text(&message.body)
+                .map_err(|e| AppError::Internal(format!("Failed to chunk text: {}", e)))?
+        };
+        if body_chunks.is_empty() {
+            let content = header.trim_end().to_string();
+            return Ok(vec![Chunk {
+                token_count: (content.len() / 4).max(1),
+                content,
+                page_number: Some(message_index),
+                page_offset: None,
+                quality_score: None,
+                content_type: Some("email".to_string()),
+            }]);
+        }
+        Ok(body_chunks
+            .into_iter()
+            .map(|chunk| Chunk {
+                content: format!("{}{}", header, chunk.content),
+                page_number: Some(message_index),
+                content_type: Some("email".to_string()),
+                ..chunk
+            })
+            .collect())
+    }
+}
+
+fn recipients(message: &EmailMessage) -> String {
+    message
+        .to
+        .iter()
+        .chain(&message.cc)
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Headers repeated on every chunk so retrieval matches on people, dates
+/// and subjects and the chat can cite them
+fn message_header(message: &EmailMessage) -> String {
+    let mut header = String::new();
+    if let Some(from) = &message.from {
+        header.push_str(&format!("From: {}\n", from));
+    }
+    let recipients = recipients(message);
+    if !recipients.is_empty() {
+        header.push_str(&format!("To: {}\n", recipients));
+    }
+    if let Some(sent_at) = message
+        .sent_at
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+    {
+        header.push_str(&format!("Date: {}\n", sent_at.format("%Y-%m-%d %H:%M UTC")));
+    }
+    if !message.subject.is_empty() {
+        header.push_str(&format!("Subject: {}\n", message.subject));
+    }
+    if !message.attachments.is_empty() {
+        header.push_str(&format!(
+            "Attachments: {}\n",
+            message.attachments.join(", ")
+        ));
+    }
+    header.push('\n');
+    header
+}
@@ -0,0 +1,380 @@
+//! Sync of git repositories: files whose blob changed since the last sync
+//! are re-imported, chunked along their syntax, and files gone from the
+//! commit lose their documents.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use super::{Logs, RagIngestionUseCase, SyncGuard, MAX_REPORTED_ERRORS};
+use crate::application::use_cases::chunking::Chunk;
+use crate::application::use_cases::code_chunking::{
+    chunk_source, is_ignored_path, is_prose, language_for_path, CodeChunk,
+};
+use crate::domain::error::{AppError, Result};
+use crate::domain::rag_entities::RagDocumentInput;
+use crate::domain::rag_git::{GitRepoFile, GitRepoSource, GitSyncReport};
+use crate::infrastructure::git_repo::{GitRepository, GitTreeEntry};
+use crate::interfaces::http::add_log;
+
+/// Larger files are mostly generated code or data
+const MAX_FILE_BYTES: u64 = 512 * 1024;
+
+/// Files imported from one repository at most
+const MAX_REPO_FILES: usize = 5000;
+
+/// Characters of the commit id shown in chunk headers
+const SHORT_COMMIT_LEN: usize = 10;
+
+/// A file of the synced commit picked for import
+struct FileToImport<'a> {
+    entry: &'a GitTreeEntry,
+    language: &'static str,
+}
+
+impl RagIngestionUseCase {
+    /// Bring the repository's collection up to date with the tip of its
+    /// branch and record the outcome on the repository source. Remote
+    /// repositories are cloned into `checkout_dir`.
+    pub async fn sync_git_repo(
+        &self,
+        source: &GitRepoSource,
+        checkout_dir: &Path,
+        logs: Logs,
+    ) -> Result<GitSyncReport> {
+        let _guard = SyncGuard::acquire(
+            &self.git_syncs,
+            source.id,
+            &format!("Repository '{}'", source.name),
+        )?;
+        add_log(
+            &logs,
+            "INFO",
+            "RAG",
+            &format!("Syncing git repository '{}' ({})", source.name, source.url),
+        );
+
+        let started_at = chrono::Utc::now().timestamp();
+        match self
+            .run_git_sync(source, checkout_dir, &logs, started_at)
+            .await
+        {
+            Ok(report) => {
+                self.rag_repository
+                    .record_git_sync(source.id, &report, None)
+                    .await?;
+                Ok(report)
+            }
+            Err(err) => {
+                let report = GitSyncReport {
+                    repo_id: source.id,
+                    previous_commit: source.last_commit.clone(),
+                    started_at,
+                    finished_at: chrono::Utc::now().timestamp(),
+                    ..Default::default()
+                };
+                self.rag_repository
+                    .record_git_sync(source.id, &report, Some(&err.to_string()))
+                    .await?;
+                Err(err)
+            }
+        }
+    }
+
+    async fn run_git_sync(
+        &self,
+        source: &GitRepoSource,
+        checkout_dir: &Path,
+        logs: &Logs,
+        started_at: i64,
+    ) -> Result<GitSyncReport> {
+        let branch = source.branch.as_deref();
+        let (repo, commit) = if source.is_remote() {
+            GitRepository::fetch_remote(&source.url, branch, checkout_dir).await?
+        } else {
+            GitRepository::open_local(Path::new(&source.url), branch).await?
+        };
+        let stored: HashMap<String, GitRepoFile> = self
+            .rag_repository
+            .list_git_repo_files(source.id)
+            .await?
+            .into_iter()
+            .map(|file| (file.path.clone(), file))
+            .collect();
+
+        let mut report = GitSyncReport {
+            repo_id: source.id,
+            commit_sha: commit.clone(),
+            previous_commit: source.last_commit.clone(),
+            started_at,
+            ..Default::default()
+        };
+
+        if source.last_commit.as_deref() == Some(commit.as_str()) {
+            add_log(
+                logs,
+                "INFO",
+                "RAG",
+                &format!("Repository '{}' has no new commits", source.name),
+            );
+            report.unchanged = stored.len();
+            report.finished_at = chrono::Utc::now().timestamp();
+            return Ok(report);
+        }
+
+        let tree = repo.tree(&commit).await?;
+        let mut files = Vec::new();
+        for entry in &tree {
+            if !source.includes(&entry.path) || is_ignored_path(&entry.path) {
+                continue;
+            }
+            match language_for_path(&entry.path) {
+                Some(language) if entry.size <= MAX_FILE_BYTES && files.len() < MAX_REPO_FILES => {
+                    files.push(FileToImport { entry, language });
+                }
+                _ => report.skipped += 1,
+            }
+        }
+        if files.len() == MAX_REPO_FILES {
+            add_log(
+                logs,
+                "WARN",
+                "RAG",
+                &format!(
+                    "Repository '{}' has more than {} importable files; the rest are skipped",
+                    source.name, MAX_REPO_FILES
+                ),
+            );
+        }
+
+        let changed: Vec<&FileToImport> = files
+            .iter()
+            .filter(|file| {
+                stored.get(&file.entry.path).is_none_or(|previous| {
+                    previous.doc_id.is_none() || previous.blob_sha != file.entry.blob_sha
+                })
+            })
+            .collect();
+        report.unchanged = files.len() - changed.len();
+        add_log(
+            logs,
+            "INFO",
+            "RAG",
+            &format!(
+                "Repository '{}' at {}: {} files to import, {} unchanged",
+                source.name,
+                short_commit(&commit),
+                changed.len(),
+                report.unchanged
+            ),
+        );
+
+        let shas: Vec<String> = changed
+            .iter()
+            .map(|file| file.entry.blob_sha.clone())
+            .collect();
+        let blobs = repo.read_blobs(&shas).await?;
+
+        let mut kept: HashSet<&str> = files.iter().map(|file| file.entry.path.as_str()).collect();
+        for file in changed {
+            let path = file.entry.path.as_str();
+            // Binary content with a source extension, or another encoding
+            let Some(text) = blobs
+                .get(&file.entry.blob_sha)
+                .filter(|bytes| !bytes.contains(&0))
+                .and_then(|bytes| std::str::from_utf8(bytes).ok())
+                .filter(|text| !text.trim().is_empty())
+            else {
+                report.skipped += 1;
+                kept.remove(path);
+                continue;
+            };
+
+            match self
+                .import_git_file(source, path, file.language, text, &commit, logs)
+                .await
+            {
+                Ok((doc_id, chunk_count)) => {
+                    match stored.get(path).and_then(|previous| previous.doc_id) {
+                        Some(old_doc_id) => {
+                            self.rag_repository.delete_document(old_doc_id).await?;
+                            report.updated += 1;
+                        }
+                        None => report.added += 1,
+                    }
+                    self.rag_repository
+                        .upsert_git_repo_file(&GitRepoFile {
+                            repo_id: source.id,
+                            path: path.to_string(),
+                            language: file.language.to_string(),
+                            blob_sha: file.entry.blob_sha.clone(),
+                            commit_sha: commit.clone(),
+                            doc_id: Some(doc_id),
+                            chunk_count,
+                            synced_at: chrono::Utc::now().timestamp(),
+                        })
+                        .await?;
+                }
+                Err(err) => {
+                    add_log(
+                        logs,
+                        "WARN",
+                        "RAG",
+                        &format!("Failed to import '{}': {}", path, err),
+                    );
+                    report.failed += 1;
+                    if report.errors.len() < MAX_REPORTED_ERRORS {
+                        report.errors.push(format!("{}: {}", path, err));
+                    }
+                }
+            }
+        }
+
+        // Deleted, moved out of scope, or no longer importable
+        for file in stored.values() {
+            if kept.contains(file.path.as_str()) {
+                continue;
+            }
+            if let Some(doc_id) = file.doc_id {
+                self.rag_repository.delete_document(doc_id).await?;
+            }
+            self.rag_repository
+                .delete_git_repo_file(source.id, &file.path)
+                .await?;
+            report.removed += 1;
+        }
+
+        report.finished_at = chrono::Utc::now().timestamp();
+        add_log(
+            logs,
+            if report.failed == 0 { "INFO" } else { "WARN" },
+            "RAG",
+            &format!(
+                "Repository '{}' synced to {}: {} added, {} updated, {} unchanged, {} removed, {} skipped, {} failed",
+                source.name,
+                short_commit(&commit),
+                report.added,
+                report.updated,
+                report.unchanged,
+                report.removed,
+                report.skipped,
+                report.failed
+            ),
+        );
+        Ok(report)
+    }
+
+    /// Import one file as a document; returns the new document's id and its
+    /// chunk count
+    async fn import_git_file(
+        &self,
+        source: &GitRepoSource,
+        path: &str,
+        language: &str,
+        text: &str,
+        commit: &str,
+        logs: &Logs,
+    ) -> Result<(i64, i64)> {
+        let chunks = if is_prose(language) {
+            let header = chunk_header(source, path, language, commit, None);
+            self.chunk_engine
+                .chunk_text(text)
+                .map_err(|e| AppError::Internal(format!("Failed to chunk text: {}", e)))?
+                .into_iter()
+                .map(|chunk| Chunk {
+                    content: format!("{}{}", header, chunk.content),
+                    ..chunk
+                })
+                .collect()
+        } else {
+            chunk_source(language, text)
+                .iter()
+                .map(|chunk| code_chunk(source, path, language, commit, chunk))
+                .collect::<Vec<_>>()
+        };
+
+        let document = self
+            .rag_repository
+            .create_document(&RagDocumentInput {
+                collection_id: Some(source.collection_id),
+                file_name: path.to_string(),
+                file_path: Some(format!("{}/{}", source.url.trim_end_matches('/'), path)),
+                file_type: "git".to_string(),
+                language: Some("auto".to_string()),
+                total_pages: Some(1),
+            })
+            .await?;
+
+        let stored = async {
+            let pipeline = self.pipeline_run(&document).await?;
+            let chunks = self.store_chunks(&pipeline, &chunks, 0, logs).await?;
+            self.rag_repository
+                .update_document_quality(document.id, None, None, chunks.len() as i64, 0)
+                .await
+        };
+        if let Err(err) = stored.await {
+            let _ = self.rag_repository.delete_document(document.id).await;
+            return Err(err);
+        }
+        Ok((document.id, chunks.len() as i64))
+    }
+}
+
+fn short_commit(commit: &str) -> &str {
+    &commit[..commit.len().min(SHORT_COMMIT_LEN)]
+}
+
+/// Where a chunk comes from, so retrieval matches on paths and names and the
+/// chat can cite them
+fn chunk_header(
+    source: &GitRepoSource,
+    path: &str,
+    language: &str,
+    commit: &str,
+    code: Option<&CodeChunk>,
+) -> String {
+    let mut header = match code {
+        Some(chunk) => format!(
+            "File: {} (lines {}-{})\n",
+            path, chunk.start_line, chunk.end_line
+        ),
+        None => format!("File: {}\n", path),
+    };
+    header.push_str(&format!(
+        "Repository: {} @ {}\nLanguage: {}\n",
+        source.name,
+        short_commit(commit),
+        language
+    ));
+    if let Some(chunk) = code {
+        if !chunk.scope.is_empty() {
+            header.push_str(&format!("In: {}\n", chunk.scope.join(" > ")));
+        }
+        if !chunk.symbols.is_empty() {
+            header.push_str(&format!("Defines: {}\n", chunk.symbols.join(", ")));
+        }
+    }
+    header.push('\n');
+    header
+}
+
+fn code_chunk(
+    source: &GitRepoSource,
+    path: &str,
+    language: &str,
+    commit: &str,
+    chunk: &CodeChunk,
+) -> Chunk {
+    let content = format!(
+        "{}{}",
+        chunk_header(source, path, language, commit, Some(chunk)),
+        chunk.content
+    );
+    Chunk {
+        token_count: (content.len() / 4).max(1),
+        content,
+        page_number: None,
+        page_offset: None,
+        quality_score: None,
+        content_type: Some("code".to_string()),
+    }
+}
@@ -4,11 +4,22 @@ use crate::application::use_cases::rag_config::OcrConfig;
 
 use super::{AppError, OcrPage, OcrResult, RagIngestionUseCase, Result};
 
+mod layout;
 mod pdf;
 mod preprocess;
 mod tesseract;
 mod web;
 
+pub(super) use layout::RecognizedPage;
+
+/// Resolution PDFs are rendered at for the first OCR pass
+pub(super) const OCR_DPI: u32 = 300;
+
+/// Renderings tried in turn for pages whose confidence stays below the
+/// configured minimum: (DPI, preprocessing mode)
+pub(super) const OCR_RETRY_STRATEGIES: [(u32, &str); 3] =
+    [(400, "otsu"), (450, "contrast"), (600, "grayscale")];
+
 impl RagIngestionUseCase {
     /// Run OCR on a file without ingestion, using the provided OCR config.
     pub async fn enhanced_ocr(
@@ -0,0 +1,480 @@
+//! Layout analysis over Tesseract's TSV output.
+//!
+//! Words are grouped into visual rows and split into segments at wide gaps.
+//! Runs of rows whose segments line up become either text columns, emitted
+//! one column after the other, or a markdown table, so multi-column reports
+//! and tables keep their reading order instead of being interleaved. The
+//! word confidences in the same output give the page and block confidence.
+
+/// A word box from `tesseract ... tsv` (level 5 rows)
+#[derive(Debug, Clone)]
+pub(super) struct OcrWord {
+    pub block: i32,
+    pub paragraph: i32,
+    pub line: i32,
+    pub left: i32,
+    pub top: i32,
+    pub width: i32,
+    pub height: i32,
+    /// Tesseract's word confidence, 0 - 100
+    pub confidence: f32,
+    pub text: String,
+}
+
+impl OcrWord {
+    fn right(&self) -> i32 {
+        self.left + self.width
+    }
+
+    fn bottom(&self) -> i32 {
+        self.top + self.height
+    }
+}
+
+/// Words separated by less than the page's segment gap
+#[derive(Debug)]
+struct Segment {
+    left: i32,
+    right: i32,
+    words: Vec<OcrWord>,
+}
+
+impl Segment {
+    fn text(&self) -> String {
+        self.words
+            .iter()
+            .map(|word| word.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn center(&self) -> i32 {
+        (self.left + self.right) / 2
+    }
+}
+
+#[derive(Debug)]
+struct Row {
+    top: i32,
+    bottom: i32,
+    segments: Vec<Segment>,
+}
+
+impl Row {
+    fn text(&self) -> String {
+        self.segments
+            .iter()
+            .map(Segment::text)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Two text columns average more words per segment than this; table cells fewer
+const TEXT_COLUMN_MIN_WORDS: f32 = 4.0;
+
+/// Word boxes of a TSV page; structural rows and empty words are skipped
+fn parse_tsv(tsv: &str) -> Vec<OcrWord> {
+    tsv.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.splitn(12, '\t').collect();
+            if fields.len() < 12 || fields[0] != "5" {
+                return None;
+            }
+            let number = |index: usize| fields[index].trim().parse::<i32>().ok();
+            let word = OcrWord {
+                block: number(2)?,
+                paragraph: number(3)?,
+                line: number(4)?,
+                left: number(6)?,
+                top: number(7)?,
+                width: number(8)?,
+                height: number(9)?,
+                confidence: fields[10].trim().parse::<f32>().ok()?,
+                text: fields[11].trim().to_string(),
+            };
+            (!word.text.is_empty() && word.width > 0 && word.height > 0).then_some(word)
+        })
+        .collect()
+}
+
+/// Mean word confidence of a page and of each of its text blocks, 0.0 - 1.0
+#[derive(Debug, Clone, PartialEq)]
+pub(in crate::application::use_cases::rag_ingestion) struct OcrConfidence {
+    pub mean: f32,
+    /// In Tesseract's block order
+    pub blocks: Vec<f32>,
+}
+
+/// Text and confidence of one recognized image
+#[derive(Debug, Clone)]
+pub(in crate::application::use_cases::rag_ingestion) struct RecognizedPage {
+    pub text: String,
+    pub confidence: OcrConfidence,
+}
+
+/// Recognize a TSV page, laid out when `layout` is set and otherwise in
+/// Tesseract's own line order. `None` when it holds no words.
+pub(super) fn recognize(tsv: &str, layout: bool) -> Option<RecognizedPage> {
+    let words = parse_tsv(tsv);
+    let confidence = confidence(&words)?;
+    let text = if layout {
+        layout_text(&words)
+    } else {
+        plain_text(&words)
+    };
+    Some(RecognizedPage { text, confidence })
+}
+
+fn confidence(words: &[OcrWord]) -> Option<OcrConfidence> {
+    let scored: Vec<&OcrWord> = words.iter().filter(|word| word.confidence >= 0.0).collect();
+    if scored.is_empty() {
+        return None;
+    }
+    let mean = |words: &[&OcrWord]| {
+        words.iter().map(|word| word.confidence).sum::<f32>() / words.len() as f32 / 100.0
+    };
+
+    let mut block_ids: Vec<i32> = scored.iter().map(|word| word.block).collect();
+    block_ids.sort_unstable();
+    block_ids.dedup();
+    let blocks = block_ids
+        .into_iter()
+        .map(|block| {
+            let words: Vec<&OcrWord> = scored
+                .iter()
+                .copied()
+                .filter(|word| word.block == block)
+                .collect();
+            mean(&words)
+        })
+        .collect();
+    Some(OcrConfidence {
+        mean: mean(&scored),
+        blocks,
+    })
+}
+
+/// Words in TSV order, one line per Tesseract line and a blank line between paragraphs
+fn plain_text(words: &[OcrWord]) -> String {
+    let mut text = String::new();
+    let mut previous: Option<&OcrWord> = None;
+    for word in words {
+        if let Some(previous) = previous {
+            if (previous.block, previous.paragraph) != (word.block, word.paragraph) {
+                text.push_str("\n\n");
+            } else if previous.line != word.line {
+                text.push('\n');
+            } else {
+                text.push(' ');
+            }
+        }
+        text.push_str(&word.text);
+        previous = Some(word);
+    }
+    text
+}
+
+/// Page text in reading order, with aligned cells rendered as markdown tables
+fn layout_text(words: &[OcrWord]) -> String {
+    if words.is_empty() {
+        return String::new();
+    }
+    let mut heights: Vec<i32> = words.iter().map(|word| word.height).collect();
+    heights.sort_unstable();
+    let line_height = heights[heights.len() / 2].max(1);
+    // Word spacing stays well below a line height; gutters and cell gaps do not
+    let segment_gap = line_height + line_height / 5;
+    let rows = group_rows(words, segment_gap);
+
+    let mut blocks: Vec<String> = Vec::new();
+    let mut paragraph: Vec<String> = Vec::new();
+    let mut previous_bottom: Option<i32> = None;
+    let mut index = 0;
+    while index < rows.len() {
+        let end = grid_end(&rows, index);
+        if end - index >= 2 {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(render_grid(&rows[index..end]));
+            previous_bottom = None;
+            index = end;
+            continue;
+        }
+
+        let row = &rows[index];
+        if previous_bottom.is_some_and(|bottom| row.top - bottom > line_height) {
+            flush(&mut paragraph, &mut blocks);
+        }
+        paragraph.push(row.text());
+        previous_bottom = Some(row.bottom);
+        index += 1;
+    }
+    flush(&mut paragraph, &mut blocks);
+    blocks.join("\n\n")
+}
+
+fn flush(paragraph: &mut Vec<String>, blocks: &mut Vec<String>) {
+    if !paragraph.is_empty() {
+        blocks.push(paragraph.join("\n"));
+        paragraph.clear();
+    }
+}
+
+/// Words grouped into rows by vertical overlap, top to bottom, each split into segments
+fn group_rows(words: &[OcrWord], segment_gap: i32) -> Vec<Row> {
+    let mut sorted: Vec<&OcrWord> = words.iter().collect();
+    sorted.sort_by_key(|word| (word.top, word.left));
+
+    let mut rows: Vec<(i32, i32, Vec<OcrWord>)> = Vec::new();
+    for word in sorted {
+        let existing = rows.iter_mut().rev().find(|(top, bottom, _)| {
+            let overlap = (*bottom).min(word.bottom()) - (*top).max(word.top);
+            overlap * 2 >= word.height.min(*bottom - *top)
+        });
+        match existing {
+            Some((top, bottom, row_words)) => {
+                *top = (*top).min(word.top);
+                *bottom = (*bottom).max(word.bottom());
+                row_words.push(word.clone());
+            }
+            None => rows.push((word.top, word.bottom(), vec![word.clone()])),
+        }
+    }
+
+    rows.sort_by_key(|(top, _, _)| *top);
+    rows.into_iter()
+        .map(|(top, bottom, mut row_words)| {
+            row_words.sort_by_key(|word| word.left);
+            let mut segments: Vec<Segment> = Vec::new();
+            for word in row_words {
+                match segments.last_mut() {
+                    Some(segment) if word.left - segment.right <= segment_gap => {
+                        segment.right = segment.right.max(word.right());
+                        segment.words.push(word);
+                    }
+                    _ => segments.push(Segment {
+                        left: word.left,
+                        right: word.right(),
+                        words: vec![word],
+                    }),
+                }
+            }
+            Row {
+                top,
+                bottom,
+                segments,
+            }
+        })
+        .collect()
+}
+
+/// End of the run of aligned rows starting at `start`. A single-segment row
+/// (an empty cell, a paragraph break in one column) stays in the run when the
+/// next row is split again and the segment fits inside one column.
+fn grid_end(rows: &[Row], start: usize) -> usize {
+    if rows[start].segments.len() < 2 {
+        return start;
+    }
+    let mut end = start + 1;
+    while end < rows.len() {
+        if rows[end].segments.len() >= 2 {
+            end += 1;
+            continue;
+        }
+        let next_is_split = rows
+            .get(end + 1)
+            .is_some_and(|next| next.segments.len() >= 2);
+        let bands = column_bands(&rows[start..end]);
+        let fits = rows[end].segments.iter().all(|segment| {
+            bands
+                .iter()
+                .any(|(left, right)| segment.left >= *left - 1 && segment.right <= *right + 1)
+        });
+        if !(next_is_split && fits && bands.len() >= 2) {
+            break;
+        }
+        end += 1;
+    }
+    end
+}
+
+/// Horizontal extents no gap runs through: the union of overlapping segments
+fn column_bands(rows: &[Row]) -> Vec<(i32, i32)> {
+    let mut spans: Vec<(i32, i32)> = rows
+        .iter()
+        .flat_map(|row| {
+            row.segments
+                .iter()
+                .map(|segment| (segment.left, segment.right))
+        })
+        .collect();
+    spans.sort_unstable();
+
+    let mut bands: Vec<(i32, i32)> = Vec::new();
+    for (left, right) in spans {
+        match bands.last_mut() {
+            Some(band) if left <= band.1 => band.1 = band.1.max(right),
+            _ => bands.push((left, right)),
+        }
+    }
+    bands
+}
+
+fn band_index(bands: &[(i32, i32)], segment: &Segment) -> usize {
+    let center = segment.center();
+    bands
+        .iter()
+        .position(|(left, right)| center >= *left && center <= *right)
+        .unwrap_or_else(|| {
+            bands
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, (left, right))| (left - center).abs().min((right - center).abs()))
+                .map(|(index, _)| index)
+                .unwrap_or(0)
+        })
+}
+
+/// Aligned rows as text columns (read one after the other) or as a markdown table
+fn render_grid(rows: &[Row]) -> String {
+    let bands = column_bands(rows);
+    if bands.len() < 2 {
+        return rows.iter().map(Row::text).collect::<Vec<_>>().join("\n");
+    }
+
+    let mut cells: Vec<Vec<Vec<String>>> = vec![vec![Vec::new(); bands.len()]; rows.len()];
+    let mut segment_count = 0usize;
+    let mut word_count = 0usize;
+    for (row_index, row) in rows.iter().enumerate() {
+        for segment in &row.segments {
+            cells[row_index][band_index(&bands, segment)].push(segment.text());
+            segment_count += 1;
+            word_count += segment.words.len();
+        }
+    }
+
+    let words_per_segment = word_count as f32 / segment_count.max(1) as f32;
+    if bands.len() == 2 && words_per_segment > TEXT_COLUMN_MIN_WORDS {
+        return (0..bands.len())
+            .map(|column| {
+                cells
+                    .iter()
+                    .map(|row| row[column].join(" "))
+                    .filter(|line| !line.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+    }
+
+    let markdown_row = |row: &Vec<Vec<String>>| {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|cell| cell.join(" ").replace('|', "\\|"))
+            .collect();
+        format!("| {} |", cells.join(" | "))
+    };
+    let mut lines = vec![
+        markdown_row(&cells[0]),
+        format!("|{}", " --- |".repeat(bands.len())),
+    ];
+    lines.extend(cells[1..].iter().map(markdown_row));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(left: i32, top: i32, text: &str) -> OcrWord {
+        OcrWord {
+            block: 1,
+            paragraph: 1,
+            line: top / 20,
+            left,
+            top,
+            width: 10 * text.len() as i32,
+            height: 20,
+            confidence: 90.0,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_word_rows_from_tsv() {
+        let tsv = "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext\n\
+                   1\t1\t0\t0\t0\t0\t0\t0\t800\t600\t-1\t\n\
+                   5\t1\t1\t1\t1\t1\t10\t20\t40\t18\t96.5\tRevenue\n\
+                   5\t1\t1\t1\t1\t2\t60\t20\t30\t18\t95\t \n";
+        let words = parse_tsv(tsv);
+        assert_eq!(words.len(), 1);
+        assert_eq!(words[0].text, "Revenue");
+        assert_eq!((words[0].left, words[0].top), (10, 20));
+        assert_eq!(words[0].confidence, 96.5);
+    }
+
+    #[test]
+    fn averages_word_confidence_per_page_and_block() {
+        let tsv = "5\t1\t1\t1\t1\t1\t0\t0\t40\t20\t90\tTotal\n\
+                   5\t1\t1\t1\t1\t2\t50\t0\t40\t20\t80\tsales\n\
+                   5\t1\t2\t1\t1\t1\t0\t60\t40\t20\t40\tsmudge\n";
+        let page = recognize(tsv, false).unwrap();
+        assert_eq!(page.text, "Total sales\n\nsmudge");
+        assert!((page.confidence.mean - 0.7).abs() < 1e-6);
+        assert_eq!(page.confidence.blocks.len(), 2);
+        assert!((page.confidence.blocks[0] - 0.85).abs() < 1e-6);
+        assert!((page.confidence.blocks[1] - 0.4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn renders_aligned_cells_as_a_markdown_table() {
+        let words = vec![
+            word(0, 0, "Quarterly"),
+            word(100, 0, "report"),
+            word(0, 50, "Region"),
+            word(200, 50, "Q1"),
+            word(400, 50, "Q2"),
+            word(0, 80, "North"),
+            word(200, 80, "120"),
+            word(400, 80, "135"),
+            word(0, 110, "South"),
+            word(400, 110, "98"),
+            word(0, 140, "West"),
+            word(200, 140, "77"),
+            word(400, 140, "80"),
+        ];
+        assert_eq!(
+            layout_text(&words),
+            "Quarterly report\n\n\
+             | Region | Q1 | Q2 |\n| --- | --- | --- |\n| North | 120 | 135 |\n\
+             | South |  | 98 |\n| West | 77 | 80 |"
+        );
+    }
+
+    #[test]
+    fn reads_text_columns_one_after_the_other() {
+        let line = |left: i32, top: i32, text: &str| -> Vec<OcrWord> {
+            let mut x = left;
+            text.split(' ')
+                .map(|part| {
+                    let next = word(x, top, part);
+                    x = next.right() + 5;
+                    next
+                })
+                .collect()
+        };
+        let words: Vec<OcrWord> = [
+            line(0, 0, "the first column starts here"),
+            line(500, 0, "the second column starts here"),
+            line(0, 30, "and the first column ends"),
+            line(500, 30, "and the second column ends"),
+        ]
+        .concat();
+        assert_eq!(
+            layout_text(&words),
+            "the first column starts here\nand the first column ends\n\n\
+             the second column starts here\nand the second column ends"
+        );
+    }
+}
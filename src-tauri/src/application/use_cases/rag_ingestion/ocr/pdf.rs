@@ -1,13 +1,14 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use crate::application::use_cases::chunking::PageContent;
 use crate::application::use_cases::rag_config::OcrConfig;
+use crate::application::use_cases::rag_ingestion::OcrPageQuality;
 
-use super::RagIngestionUseCase;
+use super::{RagIngestionUseCase, RecognizedPage, OCR_DPI};
 
-struct OcrTempDir {
+pub(in crate::application::use_cases::rag_ingestion) struct OcrTempDir {
     path: PathBuf,
 }
 
@@ -26,10 +27,12 @@ impl Drop for OcrTempDir {
 }
 
 impl RagIngestionUseCase {
-    fn rasterize_pdf_to_pngs(
+    /// Render every page, or only `page` when given, to PNG files
+    pub(in crate::application::use_cases::rag_ingestion) fn rasterize_pdf_to_pngs(
         &self,
         file_path: &str,
         dpi: u32,
+        page: Option<i64>,
         logs: &std::sync::Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
     ) -> Option<(OcrTempDir, Vec<PathBuf>)> {
         use crate::interfaces::http::add_log;
@@ -56,10 +59,16 @@ impl RagIngestionUseCase {
         };
 
         let output_prefix = temp_dir.path.join("page");
-        let output = Command::new(&pdftoppm_cmd)
-            .arg("-png")
-            .arg("-r")
-            .arg(dpi.to_string())
+        let mut command = Command::new(&pdftoppm_cmd);
+        command.arg("-png").arg("-r").arg(dpi.to_string());
+        if let Some(page) = page {
+            command
+                .arg("-f")
+                .arg(page.to_string())
+                .arg("-l")
+                .arg(page.to_string());
+        }
+        let output = command
             .arg(file_path)
             .arg(output_prefix.to_string_lossy().to_string())
             .output();
@@ -132,7 +141,7 @@ impl RagIngestionUseCase {
             ),
         );
 
-        let (_temp_dir, images) = match self.rasterize_pdf_to_pngs(file_path, 300, logs) {
+        let (_temp_dir, images) = match self.rasterize_pdf_to_pngs(file_path, OCR_DPI, None, logs) {
             Some(v) => v,
             None => {
                 let languages = if config.languages.trim().is_empty() {
@@ -185,17 +194,22 @@ impl RagIngestionUseCase {
         }
     }
 
-    /// OCR PDF pages (legacy method kept for compatibility with the PDF parser).
+    /// OCR PDF pages (legacy method kept for compatibility with the PDF parser),
+    /// with the confidence of every page Tesseract scored.
     pub(in crate::application::use_cases::rag_ingestion) fn ocr_pdf_with_grayscale(
         &self,
         file_path: &str,
         logs: &std::sync::Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
-    ) -> Option<Vec<PageContent>> {
+    ) -> Option<(Vec<PageContent>, Vec<OcrPageQuality>)> {
         use crate::interfaces::http::add_log;
 
-        let (_temp_dir, images) = match self.rasterize_pdf_to_pngs(file_path, 300, logs) {
+        let (_temp_dir, images) = match self.rasterize_pdf_to_pngs(file_path, OCR_DPI, None, logs) {
             Some(v) => v,
-            None => return self.ocr_pdf_with_tesseract_fallback(file_path, logs),
+            None => {
+                return self
+                    .ocr_pdf_with_tesseract_fallback(file_path, logs)
+                    .map(|pages| (pages, Vec::new()))
+            }
         };
         add_log(
             logs,
@@ -205,23 +219,15 @@ impl RagIngestionUseCase {
         );
 
         let mut page_contents: Vec<PageContent> = Vec::new();
+        let mut quality: Vec<OcrPageQuality> = Vec::new();
         for (idx, image_path) in images.iter().enumerate() {
-            let page_number = (idx + 1) as i64;
-            add_log(
+            self.ocr_page_image(
+                image_path,
+                (idx + 1) as i64,
+                &mut page_contents,
+                &mut quality,
                 logs,
-                "INFO",
-                "RAG",
-                &format!("OCR processing page {}...", page_number),
             );
-
-            if let Some(text) = self.ocr_single_image(image_path, logs) {
-                if !text.trim().is_empty() {
-                    page_contents.push(PageContent {
-                        page_number,
-                        content: text.trim().to_string(),
-                    });
-                }
-            }
         }
 
         if page_contents.is_empty() {
@@ -234,10 +240,101 @@ impl RagIngestionUseCase {
                 "RAG",
                 &format!("OCR extracted text from {} pages", page_contents.len()),
             );
-            Some(page_contents)
+            Some((page_contents, quality))
         }
     }
 
+    /// OCR only `page_numbers` of a PDF, rendering each page on its own so
+    /// pages with a text layer are not rasterized
+    pub(in crate::application::use_cases::rag_ingestion) fn ocr_pdf_pages(
+        &self,
+        file_path: &str,
+        page_numbers: &[i64],
+        logs: &std::sync::Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+    ) -> (Vec<PageContent>, Vec<OcrPageQuality>) {
+        let mut page_contents: Vec<PageContent> = Vec::new();
+        let mut quality: Vec<OcrPageQuality> = Vec::new();
+        for &page_number in page_numbers {
+            let Some((_temp_dir, images)) =
+                self.rasterize_pdf_to_pngs(file_path, OCR_DPI, Some(page_number), logs)
+            else {
+                // pdftoppm is missing or broken; the other pages would fail alike
+                break;
+            };
+            if let Some(image_path) = images.first() {
+                self.ocr_page_image(
+                    image_path,
+                    page_number,
+                    &mut page_contents,
+                    &mut quality,
+                    logs,
+                );
+            }
+        }
+        (page_contents, quality)
+    }
+
+    /// OCR the rendering of one page, collecting its text and confidence
+    fn ocr_page_image(
+        &self,
+        image_path: &Path,
+        page_number: i64,
+        page_contents: &mut Vec<PageContent>,
+        quality: &mut Vec<OcrPageQuality>,
+        logs: &std::sync::Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+    ) {
+        use crate::interfaces::http::add_log;
+
+        add_log(
+            logs,
+            "INFO",
+            "RAG",
+            &format!("OCR processing page {}...", page_number),
+        );
+        let Some((text, confidence)) = self.ocr_single_image(image_path, logs) else {
+            return;
+        };
+        if text.trim().is_empty() {
+            return;
+        }
+        page_contents.push(PageContent {
+            page_number,
+            content: text.trim().to_string(),
+        });
+        if let Some(confidence) = confidence {
+            quality.push(OcrPageQuality {
+                page_number,
+                confidence: confidence.mean,
+                block_confidences: confidence.blocks,
+                dpi: OCR_DPI,
+                preprocessing: "auto".to_string(),
+            });
+        }
+    }
+
+    /// Render one PDF page at `dpi`, apply the `preprocessing` mode and OCR it
+    pub(in crate::application::use_cases::rag_ingestion) fn ocr_pdf_page(
+        &self,
+        file_path: &str,
+        page_number: i64,
+        dpi: u32,
+        preprocessing: &str,
+        languages: &str,
+        logs: &std::sync::Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+    ) -> Option<RecognizedPage> {
+        let (_temp_dir, images) =
+            self.rasterize_pdf_to_pngs(file_path, dpi, Some(page_number), logs)?;
+        let image_path = images.first()?;
+        let preprocessed = self.preprocess_image_for_mode(image_path, preprocessing);
+        let layout = self.ocr_config().layout_analysis;
+        self.run_tesseract_tsv(
+            preprocessed.as_deref().unwrap_or(image_path),
+            languages,
+            layout,
+            logs,
+        )
+    }
+
     fn ocr_pdf_with_tesseract_fallback(
         &self,
         file_path: &str,
@@ -296,7 +393,7 @@ impl RagIngestionUseCase {
     ) -> Option<String> {
         use crate::interfaces::http::add_log;
 
-        let (_temp_dir, images) = self.rasterize_pdf_to_pngs(file_path, 300, logs)?;
+        let (_temp_dir, images) = self.rasterize_pdf_to_pngs(file_path, OCR_DPI, None, logs)?;
 
         let mut combined = String::new();
         for image_path in images {
@@ -4,9 +4,18 @@ use std::process::Command;
 
 use crate::application::use_cases::rag_config::OcrConfig;
 
+use super::layout::{self, OcrConfidence, RecognizedPage};
 use super::RagIngestionUseCase;
 
 impl RagIngestionUseCase {
+    /// OCR settings of the RAG config, or the defaults without a config manager
+    pub(in crate::application::use_cases::rag_ingestion) fn ocr_config(&self) -> OcrConfig {
+        self.config_manager
+            .as_ref()
+            .map(|manager| manager.get_config().ocr)
+            .unwrap_or_default()
+    }
+
     pub(super) fn new_tesseract_command() -> Command {
         let tesseract_cmd =
             std::env::var("TESSERACT_CMD").unwrap_or_else(|_| "tesseract".to_string());
@@ -59,6 +68,52 @@ impl RagIngestionUseCase {
         }
     }
 
+    /// OCR an image from Tesseract's word boxes, with their confidence. With
+    /// `layout` columns keep their reading order and tables become markdown.
+    /// `None` when Tesseract fails or finds no words.
+    pub(super) fn run_tesseract_tsv(
+        &self,
+        image_path: &Path,
+        languages: &str,
+        layout: bool,
+        logs: &std::sync::Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+    ) -> Option<RecognizedPage> {
+        use crate::interfaces::http::add_log;
+
+        let output = Self::new_tesseract_command()
+            .arg(image_path.as_os_str())
+            .arg("stdout")
+            .arg("-l")
+            .arg(languages)
+            .arg("tsv")
+            .output();
+
+        match output {
+            Ok(output) if output.status.success() => {
+                layout::recognize(&String::from_utf8_lossy(&output.stdout), layout)
+            }
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                add_log(
+                    logs,
+                    "WARN",
+                    "RAG",
+                    &format!("Tesseract layout analysis failed: {}", stderr.trim()),
+                );
+                None
+            }
+            Err(err) => {
+                add_log(
+                    logs,
+                    "ERROR",
+                    "RAG",
+                    &format!("Tesseract OCR failed to start: {}", err),
+                );
+                None
+            }
+        }
+    }
+
     pub(super) fn ocr_image_with_config(
         &self,
         image_path: &Path,
@@ -100,7 +155,10 @@ impl RagIngestionUseCase {
             }
         }
 
-        let text = self.run_tesseract_on_image(&ocr_path, languages, logs);
+        let text = self
+            .run_tesseract_tsv(&ocr_path, languages, config.layout_analysis, logs)
+            .map(|page| page.text)
+            .or_else(|| self.run_tesseract_on_image(&ocr_path, languages, logs));
         if let Some(path) = preprocessed {
             let _ = fs::remove_file(path);
         }
@@ -110,11 +168,12 @@ impl RagIngestionUseCase {
 
     /// OCR a single image file with Tesseract.
     /// Applies preprocessing automatically if contrast looks poor.
+    /// The confidence is missing when only Tesseract's plain text output worked.
     pub(super) fn ocr_single_image(
         &self,
         image_path: &Path,
         logs: &std::sync::Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
-    ) -> Option<String> {
+    ) -> Option<(String, Option<OcrConfidence>)> {
         use crate::interfaces::http::add_log;
 
         let (ocr_path, preprocessed_path) = if self.needs_preprocessing(image_path) {
@@ -133,6 +192,14 @@ impl RagIngestionUseCase {
             (image_path.to_path_buf(), None)
         };
 
+        let layout = self.ocr_config().layout_analysis;
+        if let Some(page) = self.run_tesseract_tsv(&ocr_path, "eng+ind", layout, logs) {
+            if let Some(preprocessed) = preprocessed_path {
+                let _ = fs::remove_file(preprocessed);
+            }
+            return Some((page.text, Some(page.confidence)));
+        }
+
         let output = Self::new_tesseract_command()
             .arg(ocr_path.as_os_str())
             .arg("stdout")
@@ -147,7 +214,7 @@ impl RagIngestionUseCase {
         match output {
             Ok(output) => {
                 if output.status.success() {
-                    Some(String::from_utf8_lossy(&output.stdout).to_string())
+                    Some((String::from_utf8_lossy(&output.stdout).to_string(), None))
                 } else {
                     let stderr = String::from_utf8_lossy(&output.stderr);
                     add_log(
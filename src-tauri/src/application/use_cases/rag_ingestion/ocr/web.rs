@@ -1,7 +1,9 @@
 use std::path::PathBuf;
 
-use crate::application::use_cases::embedding_service::EmbeddingService;
+use crate::application::use_cases::chunking::{parent_sections, PARENT_SECTION_MAX_TOKENS};
 use crate::application::use_cases::web_crawler::WebOcrCapture;
+use crate::domain::node_runtime::{NodeRuntimeProgress, RuntimeScript};
+use crate::infrastructure::playwright::CaptureAuth;
 
 use super::super::{
     AppError, RagDocument, RagDocumentChunkInput, RagDocumentInput, RagIngestionUseCase, Result,
@@ -9,10 +11,12 @@ use super::super::{
 
 impl RagIngestionUseCase {
     /// Ingest a web page using screenshot OCR mode (Playwright + Tesseract).
+    /// `auth` logs the browser in for pages behind authentication.
     pub async fn ingest_web_ocr(
         &self,
         url: &str,
         collection_id: Option<i64>,
+        auth: Option<&CaptureAuth>,
         logs: std::sync::Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
     ) -> Result<RagDocument> {
         use crate::interfaces::http::add_log;
@@ -24,13 +28,13 @@ impl RagIngestionUseCase {
             &format!("Starting OCR web capture: {}", url),
         );
 
-        let script_path = self.get_playwright_script_path()?;
+        let script_path = self.capture_script_path(&logs).await?;
         let temp_dir = std::env::temp_dir().join("gadogado_web_ocr");
         std::fs::create_dir_all(&temp_dir)
             .map_err(|e| AppError::Internal(format!("Failed to create temp directory: {}", e)))?;
 
         let ocr_capture = WebOcrCapture::new(script_path, temp_dir);
-        let result = ocr_capture.capture_url(url, logs.clone()).await?;
+        let result = ocr_capture.capture_url(url, auth, logs.clone()).await?;
 
         add_log(
             &logs,
@@ -68,41 +72,61 @@ impl RagIngestionUseCase {
             "RAG",
             &format!("Created {} chunks", chunks.len()),
         );
-        add_log(&logs, "INFO", "RAG", "Generating embeddings for chunks...");
+        let pii_stage = self.pii_stage();
+        let screened: Vec<_> = chunks
+            .iter()
+            .map(|chunk| pii_stage.as_ref().map(|stage| stage.screen(&chunk.content)))
+            .collect();
+        let texts: Vec<String> = chunks
+            .iter()
+            .zip(&screened)
+            .map(|(chunk, screened)| {
+                screened
+                    .as_ref()
+                    .map_or_else(|| chunk.content.clone(), |s| s.content.clone())
+            })
+            .collect();
+        let embeddings = self.embed_chunk_texts(&texts, &logs).await;
+        let parents = parent_sections(&chunks, PARENT_SECTION_MAX_TOKENS);
+        let embed = self.embed_config(document.id).await?;
 
         let total_chunks = chunks.len();
-        for (chunk_index, chunk) in chunks.iter().enumerate() {
-            let embedding = self
-                .embedding_service
-                .generate_embedding(&chunk.content)
-                .await
-                .map_err(|e| {
-                    add_log(
-                        &logs,
-                        "WARN",
-                        "RAG",
-                        &format!(
-                            "Failed to generate embedding for chunk {}: {}",
-                            chunk_index, e
-                        ),
-                    );
-                    e
-                })?;
+        for (chunk_index, ((chunk, embedding), screened)) in
+            chunks.iter().zip(embeddings).zip(screened).enumerate()
+        {
+            let embedding = embedding.map_err(|e| {
+                add_log(
+                    &logs,
+                    "WARN",
+                    "RAG",
+                    &format!(
+                        "Failed to generate embedding for chunk {}: {}",
+                        chunk_index, e
+                    ),
+                );
+                e
+            })?;
 
             let chunk_input = RagDocumentChunkInput {
                 doc_id: document.id,
-                content: chunk.content.clone(),
+                content: texts[chunk_index].clone(),
                 page_number: chunk.page_number,
                 page_offset: chunk.page_offset,
                 chunk_index: chunk_index as i64,
                 token_count: Some(chunk.token_count as i64),
+                parent_index: Some(parents[chunk_index] as i64),
             };
 
             let created_chunk = self.rag_repository.create_chunk(&chunk_input).await?;
-            let embedding_bytes = EmbeddingService::embedding_to_bytes(&embedding);
+            let embedding_bytes = embed.stored_bytes(embedding);
             self.rag_repository
                 .update_chunk_embedding(created_chunk.id, &embedding_bytes)
                 .await?;
+            if let Some(screened) = screened.filter(|s| !s.kinds.is_empty()) {
+                self.rag_repository
+                    .update_chunk_pii(created_chunk.id, &screened.pii_types(), screened.sensitive)
+                    .await?;
+            }
 
             if (chunk_index + 1) % 10 == 0 || chunk_index + 1 == total_chunks {
                 add_log(
@@ -136,32 +160,22 @@ impl RagIngestionUseCase {
         Ok(document)
     }
 
-    fn get_playwright_script_path(&self) -> Result<PathBuf> {
-        let possible_paths = vec![
-            PathBuf::from("resources/scripts/playwright-capture.js"),
-            PathBuf::from("../resources/scripts/playwright-capture.js"),
-            std::env::current_dir()
-                .unwrap_or_default()
-                .join("resources/scripts/playwright-capture.js"),
-        ];
-
-        for path in &possible_paths {
-            if path.exists() {
-                return Ok(path.clone());
-            }
-        }
-
-        if let Ok(exe_path) = std::env::current_exe() {
-            if let Some(exe_dir) = exe_path.parent() {
-                let bundled_path = exe_dir.join("resources/scripts/playwright-capture.js");
-                if bundled_path.exists() {
-                    return Ok(bundled_path);
-                }
-            }
-        }
+    /// Capture script in the managed Node runtime, installing Playwright
+    /// and Chromium on first use
+    async fn capture_script_path(
+        &self,
+        logs: &std::sync::Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+    ) -> Result<PathBuf> {
+        use crate::interfaces::http::add_log;
 
-        Err(AppError::Internal(
-            "Playwright capture script not found. Ensure playwright-capture.js is in resources/scripts/".to_string(),
-        ))
+        let runtime = self.node_runtime.as_ref().ok_or_else(|| {
+            AppError::Internal("Node runtime for web capture is not configured".to_string())
+        })?;
+        let mut on_progress = |progress: NodeRuntimeProgress| {
+            add_log(logs, "INFO", "RAG", &progress.message);
+        };
+        runtime
+            .prepare(RuntimeScript::Capture, &mut on_progress)
+            .await
     }
 }
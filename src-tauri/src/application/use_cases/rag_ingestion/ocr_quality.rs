@@ -0,0 +1,352 @@
+//! OCR quality gating: records Tesseract's confidence for every OCR'd page,
+//! warns about pages and documents below the configured minimum and retries
+//! queued pages with the next rendering in `OCR_RETRY_STRATEGIES`.
+
+use crate::application::use_cases::chunking::PageContent;
+use crate::domain::error::{AppError, Result};
+use crate::domain::rag_entities::DocumentWarningInput;
+use crate::infrastructure::db::rag::repository::OcrPageRecord;
+
+use super::ocr::OCR_RETRY_STRATEGIES;
+use super::{OcrPageQuality, OcrQueueReport, RagIngestionUseCase};
+
+const OCR_LOW_CONFIDENCE: &str = "ocr_low_confidence";
+
+const PAGE_OK: &str = "ok";
+const PAGE_QUEUED: &str = "queued";
+const PAGE_IMPROVED: &str = "improved";
+const PAGE_EXHAUSTED: &str = "exhausted";
+
+/// Languages the ingestion pass OCRs with when a document has no hint
+const DEFAULT_OCR_LANGUAGES: &str = "eng+ind";
+
+impl RagIngestionUseCase {
+    /// Store the confidence of OCR'd pages on their chunks and in the OCR
+    /// queue. Returns the document's mean confidence and the warnings raised.
+    pub(super) async fn record_ocr_quality(
+        &self,
+        doc_id: i64,
+        quality: &[OcrPageQuality],
+        logs: &std::sync::Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+    ) -> Result<(Option<f64>, i64)> {
+        use crate::interfaces::http::add_log;
+
+        if quality.is_empty() {
+            return Ok((None, 0));
+        }
+        let min_confidence = self.ocr_config().min_confidence;
+        let now = chrono::Utc::now().timestamp();
+        let mut warnings = 0;
+        for page in quality {
+            self.rag_repository
+                .set_page_chunks_ocr_meta(
+                    doc_id,
+                    page.page_number,
+                    page.confidence,
+                    &page.block_confidences,
+                )
+                .await?;
+
+            let low = page.confidence < min_confidence;
+            self.rag_repository
+                .upsert_ocr_page(&OcrPageRecord {
+                    doc_id,
+                    page_number: page.page_number,
+                    confidence: page.confidence,
+                    block_confidences: page.block_confidences.clone(),
+                    dpi: page.dpi as i64,
+                    preprocessing: page.preprocessing.clone(),
+                    status: if low { PAGE_QUEUED } else { PAGE_OK }.to_string(),
+                    attempts: 0,
+                    last_error: None,
+                    updated_at: now,
+                })
+                .await?;
+            if low {
+                self.rag_repository
+                    .create_warning(&DocumentWarningInput {
+                        doc_id,
+                        warning_type: OCR_LOW_CONFIDENCE.to_string(),
+                        page_number: Some(page.page_number),
+                        chunk_index: None,
+                        severity: "warning".to_string(),
+                        message: format!(
+                            "OCR confidence on page {} is {:.0}%",
+                            page.page_number,
+                            page.confidence * 100.0
+                        ),
+                        suggestion: Some(
+                            "The page is queued to be OCR'd again at a higher resolution; a cleaner scan gives better text".to_string(),
+                        ),
+                    })
+                    .await?;
+                warnings += 1;
+            }
+        }
+
+        let average = quality
+            .iter()
+            .map(|page| page.confidence as f64)
+            .sum::<f64>()
+            / quality.len() as f64;
+        if average < min_confidence as f64 {
+            self.rag_repository
+                .create_warning(&low_document_confidence(doc_id, average))
+                .await?;
+            warnings += 1;
+        }
+        add_log(
+            logs,
+            if warnings > 0 { "WARN" } else { "INFO" },
+            "RAG",
+            &format!(
+                "OCR confidence {:.0}% over {} pages, {} below {:.0}% queued for reprocessing",
+                average * 100.0,
+                quality.len(),
+                quality
+                    .iter()
+                    .filter(|page| page.confidence < min_confidence)
+                    .count(),
+                min_confidence * 100.0
+            ),
+        );
+
+        Ok((Some(average), warnings))
+    }
+
+    /// OCR up to `limit` queued pages again with their next retry strategy.
+    /// A page keeps the better of its old and new text; it leaves the queue
+    /// once it reaches the minimum confidence or runs out of strategies.
+    pub async fn process_ocr_queue(
+        &self,
+        limit: i64,
+        logs: &std::sync::Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+    ) -> Result<OcrQueueReport> {
+        use crate::interfaces::http::add_log;
+
+        let min_confidence = self.ocr_config().min_confidence;
+        let queued = self
+            .rag_repository
+            .list_ocr_pages(None, Some(PAGE_QUEUED), limit)
+            .await?;
+        let mut report = OcrQueueReport::default();
+
+        for mut page in queued {
+            report.processed += 1;
+            let Some(&(dpi, preprocessing)) = OCR_RETRY_STRATEGIES.get(page.attempts as usize)
+            else {
+                page.status = PAGE_EXHAUSTED.to_string();
+                report.exhausted += 1;
+                self.save_ocr_page(page).await?;
+                continue;
+            };
+            page.attempts += 1;
+            add_log(
+                logs,
+                "INFO",
+                "RAG",
+                &format!(
+                    "Re-running OCR on document {} page {} at {} DPI ({})",
+                    page.doc_id, page.page_number, dpi, preprocessing
+                ),
+            );
+
+            let recognized = match self.rag_repository.get_document(page.doc_id).await {
+                Ok(document) => match document
+                    .file_path
+                    .filter(|path| std::path::Path::new(path).is_file())
+                {
+                    Some(file_path) => {
+                        let languages = self
+                            .rag_repository
+                            .get_document_ocr_languages(page.doc_id)
+                            .await?
+                            .unwrap_or_else(|| DEFAULT_OCR_LANGUAGES.to_string());
+                        self.ocr_pdf_page(
+                            &file_path,
+                            page.page_number,
+                            dpi,
+                            preprocessing,
+                            &languages,
+                            logs,
+                        )
+                        .ok_or_else(|| "OCR produced no result".to_string())
+                    }
+                    None => {
+                        // Nothing left to retry with
+                        page.attempts = OCR_RETRY_STRATEGIES.len() as i64;
+                        Err("The source file is no longer available".to_string())
+                    }
+                },
+                Err(err) => Err(err.to_string()),
+            };
+
+            match recognized {
+                Ok(recognized)
+                    if recognized.confidence.mean > page.confidence
+                        && !recognized.text.trim().is_empty() =>
+                {
+                    self.replace_page_chunks(page.doc_id, page.page_number, &recognized.text, logs)
+                        .await?;
+                    self.rag_repository
+                        .set_page_chunks_ocr_meta(
+                            page.doc_id,
+                            page.page_number,
+                            recognized.confidence.mean,
+                            &recognized.confidence.blocks,
+                        )
+                        .await?;
+                    add_log(
+                        logs,
+                        "INFO",
+                        "RAG",
+                        &format!(
+                            "OCR confidence of document {} page {}: {:.0}% -> {:.0}%",
+                            page.doc_id,
+                            page.page_number,
+                            page.confidence * 100.0,
+                            recognized.confidence.mean * 100.0
+                        ),
+                    );
+                    page.confidence = recognized.confidence.mean;
+                    page.block_confidences = recognized.confidence.blocks;
+                    page.dpi = dpi as i64;
+                    page.preprocessing = preprocessing.to_string();
+                    page.last_error = None;
+                    if !report.updated_documents.contains(&page.doc_id) {
+                        report.updated_documents.push(page.doc_id);
+                    }
+                }
+                Ok(_) => page.last_error = None,
+                Err(err) => {
+                    add_log(
+                        logs,
+                        "WARN",
+                        "RAG",
+                        &format!(
+                            "OCR retry of document {} page {} failed: {}",
+                            page.doc_id, page.page_number, err
+                        ),
+                    );
+                    page.last_error = Some(err);
+                    report.failed += 1;
+                }
+            }
+
+            if page.confidence >= min_confidence {
+                page.status = PAGE_IMPROVED.to_string();
+                report.improved += 1;
+                self.rag_repository
+                    .delete_page_warnings(page.doc_id, Some(page.page_number), OCR_LOW_CONFIDENCE)
+                    .await?;
+            } else if page.attempts as usize >= OCR_RETRY_STRATEGIES.len() {
+                page.status = PAGE_EXHAUSTED.to_string();
+                report.exhausted += 1;
+            }
+            self.save_ocr_page(page).await?;
+        }
+
+        report.remaining = self.rag_repository.count_ocr_pages(PAGE_QUEUED).await?;
+        add_log(
+            logs,
+            "INFO",
+            "RAG",
+            &format!(
+                "OCR queue: {} pages processed, {} improved, {} exhausted, {} failed, {} still queued",
+                report.processed,
+                report.improved,
+                report.exhausted,
+                report.failed,
+                report.remaining
+            ),
+        );
+        Ok(report)
+    }
+
+    /// Put a page back in the queue with all retry strategies available again
+    pub async fn requeue_ocr_page(&self, doc_id: i64, page_number: i64) -> Result<OcrPageRecord> {
+        let mut page = self
+            .rag_repository
+            .get_ocr_page(doc_id, page_number)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!(
+                    "Page {} of document {} was not OCR'd",
+                    page_number, doc_id
+                ))
+            })?;
+        page.status = PAGE_QUEUED.to_string();
+        page.attempts = 0;
+        page.last_error = None;
+        page.updated_at = chrono::Utc::now().timestamp();
+        self.rag_repository.upsert_ocr_page(&page).await?;
+        Ok(page)
+    }
+
+    /// Persist a processed page and bring the document's OCR stats and
+    /// document-level warning in line with it
+    async fn save_ocr_page(&self, mut page: OcrPageRecord) -> Result<()> {
+        page.updated_at = chrono::Utc::now().timestamp();
+        self.rag_repository.upsert_ocr_page(&page).await?;
+
+        self.rag_repository
+            .delete_page_warnings(page.doc_id, None, OCR_LOW_CONFIDENCE)
+            .await?;
+        let average = self
+            .rag_repository
+            .refresh_document_ocr_stats(page.doc_id)
+            .await?;
+        if let Some(average) =
+            average.filter(|average| *average < self.ocr_config().min_confidence as f64)
+        {
+            self.rag_repository
+                .create_warning(&low_document_confidence(page.doc_id, average))
+                .await?;
+            self.rag_repository
+                .refresh_document_ocr_stats(page.doc_id)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Swap the chunks of one page for chunks of its new OCR text
+    async fn replace_page_chunks(
+        &self,
+        doc_id: i64,
+        page_number: i64,
+        text: &str,
+        logs: &std::sync::Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+    ) -> Result<()> {
+        let document = self.rag_repository.get_document(doc_id).await?;
+        let pipeline = self.pipeline_run(&document).await?;
+        let chunks = pipeline
+            .chunk_engine()
+            .chunk_pages(&[PageContent {
+                page_number,
+                content: text.trim().to_string(),
+            }])
+            .map_err(|e| AppError::Internal(format!("Failed to chunk pages: {}", e)))?;
+
+        self.rag_repository
+            .delete_page_chunks(doc_id, page_number)
+            .await?;
+        let first_index = self.rag_repository.next_chunk_index(doc_id).await?;
+        self.store_chunks(&pipeline, &chunks, first_index, logs)
+            .await?;
+        Ok(())
+    }
+}
+
+fn low_document_confidence(doc_id: i64, average: f64) -> DocumentWarningInput {
+    DocumentWarningInput {
+        doc_id,
+        warning_type: OCR_LOW_CONFIDENCE.to_string(),
+        page_number: None,
+        chunk_index: None,
+        severity: "warning".to_string(),
+        message: format!("Average OCR confidence is {:.0}%", average * 100.0),
+        suggestion: Some(
+            "Search results from this document may contain misread text; process the OCR queue or re-import a higher quality scan".to_string(),
+        ),
+    }
+}
@@ -1,6 +1,7 @@
 use super::super::{AppError, ParseResult, ParsedContent, RagIngestionUseCase};
 
 use crate::application::use_cases::csv_preprocessor::CsvPreprocessor;
+use crate::infrastructure::csv::CsvParser;
 
 impl RagIngestionUseCase {
     pub(in crate::application::use_cases::rag_ingestion) fn parse_csv(
@@ -15,16 +16,18 @@ impl RagIngestionUseCase {
         // Use the new CSV preprocessor
         let preprocessor = CsvPreprocessor::default();
 
-        // Read file content for preprocessing
-        let content = std::fs::read_to_string(file_path).map_err(|e| {
-            add_log(
-                logs,
-                "ERROR",
-                "RAG",
-                &format!("Failed to read CSV file {}: {}", file_path, e),
-            );
-            AppError::Internal(format!("Failed to read CSV file: {}", e))
-        })?;
+        // Read file content for preprocessing (UTF-16 and ANSI exports are transcoded)
+        let content = CsvParser::new()
+            .read_with_encoding_detection(std::path::Path::new(file_path))
+            .map_err(|e| {
+                add_log(
+                    logs,
+                    "ERROR",
+                    "RAG",
+                    &format!("Failed to read CSV file {}: {}", file_path, e),
+                );
+                AppError::Internal(format!("Failed to read CSV file: {}", e))
+            })?;
 
         // Preprocess the CSV
         let preprocessed = preprocessor.preprocess_csv_content(&content).map_err(|e| {
@@ -47,11 +50,18 @@ impl RagIngestionUseCase {
                  - Rows processed: {}\n\
                  - Avg field length: {:.1} chars\n\
                  - Lexical diversity: {:.2}\n\
+                 - Typed columns: {}\n\
                  - Confidence: {:.2}",
                 preprocessed.content_type,
                 preprocessed.row_count,
                 preprocessed.analysis.avg_value_length,
                 preprocessed.analysis.lexical_diversity,
+                preprocessed
+                    .profile
+                    .columns
+                    .iter()
+                    .filter(|column| column.inferred_type.is_normalized())
+                    .count(),
                 preprocessed.analysis.confidence_score()
             ),
         );
@@ -72,7 +82,6 @@ impl RagIngestionUseCase {
     /// Parse CSV rows for storage in excel_data table
     /// This preserves the original CSV structure for structured queries
     fn parse_csv_rows_for_storage(&self, content: &str) -> Vec<Vec<String>> {
-        use crate::infrastructure::csv::CsvParser;
         use csv::{ReaderBuilder, Trim};
 
         let delimiter = CsvParser::detect_delimiter(content);
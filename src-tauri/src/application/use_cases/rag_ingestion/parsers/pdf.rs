@@ -2,6 +2,12 @@ use super::super::{AppError, ParseResult, ParsedContent, RagIngestionUseCase};
 
 use crate::application::use_cases::chunking::PageContent;
 
+use lopdf::{Dictionary, Document, Object, ObjectId};
+
+/// Letters and digits a text layer needs before a page counts as native text;
+/// scans often carry no more than a page number or a stamp
+const MIN_NATIVE_TEXT_CHARS: usize = 16;
+
 impl RagIngestionUseCase {
     pub(in crate::application::use_cases::rag_ingestion) fn parse_pdf(
         &self,
@@ -9,45 +15,251 @@ impl RagIngestionUseCase {
         logs: &std::sync::Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
     ) -> ParseResult {
         use crate::interfaces::http::add_log;
-        use lopdf::Document;
 
         let document = Document::load(file_path)
             .map_err(|e| AppError::Internal(format!("Failed to load PDF: {}", e)))?;
 
-        let mut page_contents: Vec<PageContent> = Vec::new();
-        let mut total_pages = 0i64;
-        let mut has_text = false;
-
-        // Extract text per page to preserve page boundaries
-        for (page_num, (page_id, _)) in document.get_pages() {
-            total_pages += 1;
-            match document.extract_text(&[page_id]) {
-                Ok(page_text) => {
-                    let trimmed = page_text.trim();
-                    if !trimmed.is_empty() {
-                        has_text = true;
-                        page_contents.push(PageContent {
-                            page_number: page_num as i64,
-                            content: trimmed.to_string(),
-                        });
-                    }
-                }
-                Err(_) => {}
-            }
-        }
+        let total_pages = document.get_pages().len() as i64;
+        let (mut page_contents, image_pages) = split_text_and_image_pages(&document);
 
         // If no text extracted (scanned PDF), fall back to OCR
-        if !has_text {
+        if page_contents.is_empty() {
             add_log(logs, "INFO", "RAG", "No text layer found, running OCR...");
-            if let Some(ocr_pages) = self.ocr_pdf_with_grayscale(file_path, logs) {
-                return Ok((ParsedContent::Pages(ocr_pages), total_pages, None));
+            if let Some((pages, quality)) = self.ocr_pdf_with_grayscale(file_path, logs) {
+                return Ok((
+                    ParsedContent::OcrPages { pages, quality },
+                    total_pages,
+                    None,
+                ));
             }
+            return Ok((ParsedContent::Plain(None), total_pages, None));
         }
 
-        if page_contents.is_empty() {
-            Ok((ParsedContent::Plain(None), total_pages, None))
-        } else {
-            Ok((ParsedContent::Pages(page_contents), total_pages, None))
+        if image_pages.is_empty() {
+            add_log(
+                logs,
+                "INFO",
+                "RAG",
+                &format!("Text layer found on all {} pages, OCR skipped", total_pages),
+            );
+            return Ok((ParsedContent::Pages(page_contents), total_pages, None));
+        }
+
+        add_log(
+            logs,
+            "INFO",
+            "RAG",
+            &format!(
+                "Text layer found on {} of {} pages; running OCR on {} image-only pages",
+                page_contents.len(),
+                total_pages,
+                image_pages.len()
+            ),
+        );
+        let (ocr_pages, quality) = self.ocr_pdf_pages(file_path, &image_pages, logs);
+        if ocr_pages.is_empty() {
+            add_log(
+                logs,
+                "WARN",
+                "RAG",
+                "OCR produced no text from the image-only pages",
+            );
+            return Ok((ParsedContent::Pages(page_contents), total_pages, None));
         }
+        page_contents.extend(ocr_pages);
+        page_contents.sort_by_key(|page| page.page_number);
+        Ok((
+            ParsedContent::OcrPages {
+                pages: page_contents,
+                quality,
+            },
+            total_pages,
+            None,
+        ))
+    }
+}
+
+/// Text of the pages with a text layer, and the numbers of the pages that
+/// are only an image and need OCR. Page boundaries are kept.
+fn split_text_and_image_pages(document: &Document) -> (Vec<PageContent>, Vec<i64>) {
+    let mut page_contents = Vec::new();
+    let mut image_pages = Vec::new();
+    for (page_num, page_id) in document.get_pages() {
+        let page_number = page_num as i64;
+        let text = document.extract_text(&[page_num]).unwrap_or_default();
+        let trimmed = text.trim();
+        if has_native_text(trimmed) {
+            page_contents.push(PageContent {
+                page_number,
+                content: trimmed.to_string(),
+            });
+        } else if page_has_images(document, page_id) {
+            image_pages.push(page_number);
+        } else if !trimmed.is_empty() {
+            page_contents.push(PageContent {
+                page_number,
+                content: trimmed.to_string(),
+            });
+        }
+    }
+    (page_contents, image_pages)
+}
+
+fn has_native_text(text: &str) -> bool {
+    text.chars().filter(|c| c.is_alphanumeric()).count() >= MIN_NATIVE_TEXT_CHARS
+}
+
+/// Whether the page draws an image XObject, directly or through a form
+fn page_has_images(document: &Document, page_id: ObjectId) -> bool {
+    let (resources, resource_ids) = document.get_page_resources(page_id);
+    resources
+        .into_iter()
+        .chain(
+            resource_ids
+                .into_iter()
+                .filter_map(|id| document.get_dictionary(id).ok()),
+        )
+        .any(|resources| resources_have_images(document, resources, 0))
+}
+
+fn resources_have_images(document: &Document, resources: &Dictionary, depth: u8) -> bool {
+    let Ok(xobjects) = resolve_dict(document, resources, b"XObject") else {
+        return false;
+    };
+    xobjects.iter().any(|(_, xobject)| {
+        let Ok(stream) = document
+            .dereference(xobject)
+            .and_then(|(_, object)| object.as_stream())
+        else {
+            return false;
+        };
+        match stream.dict.get(b"Subtype").and_then(Object::as_name) {
+            Ok(b"Image") => true,
+            Ok(b"Form") if depth < 3 => resolve_dict(document, &stream.dict, b"Resources")
+                .map(|resources| resources_have_images(document, resources, depth + 1))
+                .unwrap_or(false),
+            _ => false,
+        }
+    })
+}
+
+/// `key` of `dict` as a dictionary, following a reference
+fn resolve_dict<'a>(
+    document: &'a Document,
+    dict: &'a Dictionary,
+    key: &[u8],
+) -> lopdf::Result<&'a Dictionary> {
+    dict.get(key)
+        .and_then(|object| document.dereference(object))
+        .and_then(|(_, object)| object.as_dict())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::content::{Content, Operation};
+    use lopdf::{dictionary, Stream};
+
+    fn add_page(
+        doc: &mut Document,
+        pages_id: ObjectId,
+        operations: Vec<Operation>,
+        resources: Dictionary,
+    ) -> ObjectId {
+        let content = Content { operations };
+        let content_id = doc.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+        doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Contents" => content_id,
+            "Resources" => resources,
+        })
+    }
+
+    /// Page 1 carries text, page 2 only draws an image, page 3 is blank
+    fn sample_pdf() -> Document {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let font_id = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Courier",
+        });
+        let image_id = doc.add_object(Stream::new(
+            dictionary! {
+                "Type" => "XObject",
+                "Subtype" => "Image",
+                "Width" => 1,
+                "Height" => 1,
+                "ColorSpace" => "DeviceGray",
+                "BitsPerComponent" => 8,
+            },
+            vec![0],
+        ));
+
+        let text = add_page(
+            &mut doc,
+            pages_id,
+            vec![
+                Operation::new("BT", vec![]),
+                Operation::new("Tf", vec!["F1".into(), 12.into()]),
+                Operation::new("Td", vec![72.into(), 700.into()]),
+                Operation::new(
+                    "Tj",
+                    vec![Object::string_literal("Quarterly revenue report")],
+                ),
+                Operation::new("ET", vec![]),
+            ],
+            dictionary! { "Font" => dictionary! { "F1" => font_id } },
+        );
+        let scan = add_page(
+            &mut doc,
+            pages_id,
+            vec![
+                Operation::new(
+                    "cm",
+                    vec![
+                        100.into(),
+                        0.into(),
+                        0.into(),
+                        100.into(),
+                        0.into(),
+                        0.into(),
+                    ],
+                ),
+                Operation::new("Do", vec!["Im1".into()]),
+            ],
+            dictionary! { "XObject" => dictionary! { "Im1" => image_id } },
+        );
+        let blank = add_page(&mut doc, pages_id, vec![], dictionary! {});
+
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => vec![text.into(), scan.into(), blank.into()],
+                "Count" => 3,
+                "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+            }),
+        );
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        doc.trailer.set("Root", catalog_id);
+
+        let mut bytes = Vec::new();
+        doc.save_to(&mut bytes).unwrap();
+        Document::load_mem(&bytes).unwrap()
+    }
+
+    #[test]
+    fn splits_text_pages_from_image_only_pages() {
+        let (pages, image_pages) = split_text_and_image_pages(&sample_pdf());
+
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].page_number, 1);
+        assert!(pages[0].content.contains("Quarterly revenue report"));
+        assert_eq!(image_pages, vec![2]);
     }
 }
@@ -1,8 +1,10 @@
+use super::super::workbooks::{fill_merged_cells, merged_regions, split_sheet_path};
 use super::super::{AppError, ParseResult, ParsedContent, RagIngestionUseCase};
 
 use crate::application::use_cases::csv_preprocessor::CsvPreprocessor;
 
 impl RagIngestionUseCase {
+    /// Parse the sheet of a workbook named by a document path
     pub(in crate::application::use_cases::rag_ingestion) fn parse_xlsx(
         &self,
         file_path: &str,
@@ -13,31 +15,57 @@ impl RagIngestionUseCase {
 
         add_log(logs, "INFO", "RAG", "Parsing XLSX file...");
 
-        let mut workbook: Xlsx<_> = open_workbook(file_path).map_err(|e| {
+        let (workbook_path, sheet_name) = split_sheet_path(file_path);
+        let mut workbook: Xlsx<_> = open_workbook(workbook_path).map_err(|e| {
             add_log(
                 logs,
                 "ERROR",
                 "RAG",
-                &format!("Failed to open Excel file {}: {}", file_path, e),
+                &format!("Failed to open Excel file {}: {}", workbook_path, e),
             );
             AppError::Internal(format!("Failed to open Excel file: {}", e))
         })?;
 
-        let range = workbook
-            .worksheet_range_at(0)
-            .ok_or_else(|| {
+        // Documents imported before each sheet had its own hold the first sheet
+        let sheet_name = match sheet_name {
+            Some(name) => name.to_string(),
+            None => workbook.sheet_names().first().cloned().ok_or_else(|| {
                 add_log(logs, "ERROR", "RAG", "No worksheet found in Excel file");
                 AppError::Internal("No worksheet found".to_string())
-            })?
-            .map_err(|e| {
-                add_log(
-                    logs,
-                    "ERROR",
-                    "RAG",
-                    &format!("Failed to read Excel range {}: {}", file_path, e),
-                );
-                AppError::Internal(format!("Failed to read Excel range: {}", e))
-            })?;
+            })?,
+        };
+
+        // Formula cells hold the value Excel computed when the file was saved
+        let mut range = workbook.worksheet_range(&sheet_name).map_err(|e| {
+            add_log(
+                logs,
+                "ERROR",
+                "RAG",
+                &format!(
+                    "Failed to read sheet {} of {}: {}",
+                    sheet_name, workbook_path, e
+                ),
+            );
+            AppError::Internal(format!("Failed to read Excel range: {}", e))
+        })?;
+        let uncomputed = workbook
+            .worksheet_formula(&sheet_name)
+            .map(|formulas| uncomputed_formula_cells(&range, &formulas))
+            .unwrap_or(0);
+        if uncomputed > 0 {
+            add_log(
+                logs,
+                "WARN",
+                "RAG",
+                &format!(
+                    "{} formula cells of sheet {} have no computed value; open and save the workbook in Excel to compute them",
+                    uncomputed, sheet_name
+                ),
+            );
+        }
+        if let Some(regions) = merged_regions(workbook_path).get(&sheet_name) {
+            fill_merged_cells(&mut range, regions);
+        }
 
         // Convert Excel data to CSV format for preprocessing
         add_log(
@@ -167,3 +195,24 @@ impl RagIngestionUseCase {
         rows
     }
 }
+
+/// Formula cells whose computed value was never saved, as in files written
+/// by tools that do not calculate
+fn uncomputed_formula_cells(
+    range: &calamine::Range<calamine::Data>,
+    formulas: &calamine::Range<String>,
+) -> usize {
+    let Some((first_row, first_column)) = formulas.start() else {
+        return 0;
+    };
+    formulas
+        .used_cells()
+        .filter(|(row, column, _)| {
+            let position = (first_row + *row as u32, first_column + *column as u32);
+            matches!(
+                range.get_value(position),
+                None | Some(calamine::Data::Empty)
+            )
+        })
+        .count()
+}
@@ -0,0 +1,821 @@
+//! Ingestion as a pipeline of stages: extract → clean → chunk → embed →
+//! index. A collection configures the optional stages, and hooks of its own
+//! that run between them, under `pipeline` in its config; without one,
+//! documents go through exactly as before.
+//!
+//! Hooks get the stage output as JSON, on stdin for scripts and as the POST
+//! body for HTTP callbacks, and may answer with a replacement in the same
+//! shape. An empty answer keeps the output as it is.
+
+use std::collections::{HashMap, HashSet};
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::application::use_cases::chunking::{
+    Chunk, ChunkConfig, ChunkEngine, ChunkStrategy, PageContent,
+};
+use crate::application::use_cases::embedding_service::{normalize_embedding, SimilarityMetric};
+use crate::domain::embedding_quantization::EmbeddingQuantization;
+use crate::domain::error::{AppError, Result};
+use crate::domain::rag_entities::RagDocument;
+use crate::interfaces::http::add_log;
+
+use super::types::ParsedContent;
+use super::{Logs, RagIngestionUseCase};
+
+const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 60;
+const MAX_HOOK_TIMEOUT_SECS: u64 = 3600;
+/// Hook stderr or response text kept in error messages
+const MAX_HOOK_ERROR_CHARS: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestionStage {
+    Extract,
+    Clean,
+    Chunk,
+    Embed,
+    Index,
+}
+
+impl IngestionStage {
+    fn as_str(&self) -> &'static str {
+        match self {
+            IngestionStage::Extract => "extract",
+            IngestionStage::Clean => "clean",
+            IngestionStage::Chunk => "chunk",
+            IngestionStage::Embed => "embed",
+            IngestionStage::Index => "index",
+        }
+    }
+}
+
+/// Ingestion settings of one collection
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct IngestionPipelineConfig {
+    pub clean: CleanStageConfig,
+    pub chunk: ChunkStageConfig,
+    pub embed: EmbedStageConfig,
+    /// Run in order after the stage each names
+    pub hooks: Vec<PipelineHook>,
+}
+
+/// Text cleanup between extraction and chunking; every step is off by default
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CleanStageConfig {
+    /// Drop control characters other than tabs
+    pub strip_control_chars: bool,
+    /// Collapse whitespace within lines and squeeze runs of blank lines
+    pub normalize_whitespace: bool,
+    /// Drop lines found on most pages, such as running headers and footers
+    pub remove_repeated_lines: bool,
+    /// Drop lines shorter than this many characters
+    pub min_line_chars: Option<usize>,
+}
+
+/// Overrides of the default chunking settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ChunkStageConfig {
+    /// "fixed_size", "content_aware" or "semantic"
+    pub strategy: Option<String>,
+    pub max_chunk_size: Option<usize>,
+    pub overlap: Option<usize>,
+    pub min_chunk_size: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct EmbedStageConfig {
+    /// Off stores chunks without embeddings, so they are only found by keyword
+    pub enabled: bool,
+    /// Metric the embedding model is trained for. Retrieval compares with
+    /// it; embeddings are stored at unit length when it ignores length.
+    pub metric: SimilarityMetric,
+    /// Precision new embeddings are stored at; existing ones keep theirs
+    /// until the collection is converted
+    pub quantization: EmbeddingQuantization,
+}
+
+impl Default for EmbedStageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            metric: SimilarityMetric::default(),
+            quantization: EmbeddingQuantization::default(),
+        }
+    }
+}
+
+impl EmbedStageConfig {
+    /// `embedding` as stored for a chunk: at unit length when the metric
+    /// ignores length, at the configured precision
+    pub fn stored_bytes(&self, mut embedding: Vec<f32>) -> Vec<u8> {
+        if self.metric.normalizes_embeddings() {
+            normalize_embedding(&mut embedding);
+        }
+        self.quantization.encode(&embedding)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PipelineHook {
+    /// Extract, clean or chunk; embeddings and index writes aren't handed out
+    pub after: IngestionStage,
+    pub action: HookAction,
+    pub timeout_secs: Option<u64>,
+    /// Keep the stage output and go on when the hook fails
+    #[serde(default)]
+    pub continue_on_error: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HookAction {
+    /// Program run with the stage output on stdin, answering on stdout
+    Script {
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+    /// URL the stage output is POSTed to, answering in the response body
+    Http {
+        url: String,
+        #[serde(default)]
+        headers: HashMap<String, String>,
+    },
+}
+
+impl HookAction {
+    fn describe(&self) -> String {
+        match self {
+            HookAction::Script { command, .. } => format!("script '{}'", command),
+            HookAction::Http { url, .. } => format!("HTTP hook {}", url),
+        }
+    }
+}
+
+impl IngestionPipelineConfig {
+    /// The pipeline of a collection config, defaults when it has none
+    pub fn from_collection_config(config_json: &str) -> Result<Self> {
+        if config_json.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        let config: Value = serde_json::from_str(config_json)
+            .map_err(|e| AppError::ParseError(format!("Invalid collection config: {}", e)))?;
+        let pipeline = match config.get("pipeline") {
+            None | Some(Value::Null) => return Ok(Self::default()),
+            Some(pipeline) => serde_json::from_value::<Self>(pipeline.clone()).map_err(|e| {
+                AppError::ParseError(format!("Invalid ingestion pipeline config: {}", e))
+            })?,
+        };
+        pipeline.validate()?;
+        Ok(pipeline)
+    }
+
+    /// `config_json` with its pipeline replaced by this one; other keys stay
+    pub fn merge_into_collection_config(&self, config_json: &str) -> Result<String> {
+        self.validate()?;
+        let mut config: Value = if config_json.trim().is_empty() {
+            Value::Object(Default::default())
+        } else {
+            serde_json::from_str(config_json)
+                .map_err(|e| AppError::ParseError(format!("Invalid collection config: {}", e)))?
+        };
+        let object = config.as_object_mut().ok_or_else(|| {
+            AppError::ValidationError("Collection config is not a JSON object".to_string())
+        })?;
+        let pipeline = serde_json::to_value(self)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize pipeline: {}", e)))?;
+        object.insert("pipeline".to_string(), pipeline);
+        Ok(config.to_string())
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let chunk = self.chunk_config();
+        if let Some(strategy) = &self.chunk.strategy {
+            if !["fixed_size", "content_aware", "semantic"].contains(&strategy.as_str()) {
+                return Err(AppError::ValidationError(format!(
+                    "Unknown chunking strategy: {}",
+                    strategy
+                )));
+            }
+        }
+        if chunk.max_chunk_size == 0 {
+            return Err(AppError::ValidationError(
+                "Chunk size must be greater than 0".to_string(),
+            ));
+        }
+        if chunk.overlap >= chunk.max_chunk_size {
+            return Err(AppError::ValidationError(
+                "Chunk overlap must be smaller than the chunk size".to_string(),
+            ));
+        }
+        if chunk.min_chunk_size > chunk.max_chunk_size {
+            return Err(AppError::ValidationError(
+                "Minimum chunk size cannot exceed the chunk size".to_string(),
+            ));
+        }
+
+        for hook in &self.hooks {
+            if !matches!(
+                hook.after,
+                IngestionStage::Extract | IngestionStage::Clean | IngestionStage::Chunk
+            ) {
+                return Err(AppError::ValidationError(format!(
+                    "Hooks can run after extract, clean or chunk, not after {}",
+                    hook.after.as_str()
+                )));
+            }
+            if hook
+                .timeout_secs
+                .is_some_and(|secs| secs == 0 || secs > MAX_HOOK_TIMEOUT_SECS)
+            {
+                return Err(AppError::ValidationError(format!(
+                    "Hook timeout must be between 1 and {} seconds",
+                    MAX_HOOK_TIMEOUT_SECS
+                )));
+            }
+            match &hook.action {
+                HookAction::Script { command, .. } if command.trim().is_empty() => {
+                    return Err(AppError::ValidationError(
+                        "Script hook needs a command".to_string(),
+                    ));
+                }
+                HookAction::Http { url, .. }
+                    if !url.starts_with("http://") && !url.starts_with("https://") =>
+                {
+                    return Err(AppError::ValidationError(format!(
+                        "HTTP hook URL must start with http:// or https://: {}",
+                        url
+                    )));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Default chunking settings with this pipeline's overrides
+    pub fn chunk_config(&self) -> ChunkConfig {
+        let defaults = ChunkConfig::default();
+        ChunkConfig {
+            max_chunk_size: self.chunk.max_chunk_size.unwrap_or(defaults.max_chunk_size),
+            overlap: self.chunk.overlap.unwrap_or(defaults.overlap),
+            strategy: match self.chunk.strategy.as_deref() {
+                Some("fixed_size") => ChunkStrategy::FixedSize,
+                Some("semantic") => ChunkStrategy::Semantic,
+                Some("content_aware") => ChunkStrategy::ContentAware,
+                _ => defaults.strategy,
+            },
+            min_chunk_size: self.chunk.min_chunk_size.unwrap_or(defaults.min_chunk_size),
+        }
+    }
+
+    fn hooks_after(&self, stage: IngestionStage) -> impl Iterator<Item = &PipelineHook> {
+        self.hooks.iter().filter(move |hook| hook.after == stage)
+    }
+}
+
+impl CleanStageConfig {
+    fn is_noop(&self) -> bool {
+        !self.strip_control_chars
+            && !self.normalize_whitespace
+            && !self.remove_repeated_lines
+            && self.min_line_chars.is_none()
+    }
+
+    pub fn clean(&self, content: ParsedContent) -> ParsedContent {
+        if self.is_noop() {
+            return content;
+        }
+        match content {
+            ParsedContent::Pages(pages) => ParsedContent::Pages(self.clean_pages(pages)),
+            ParsedContent::OcrPages { pages, quality } => ParsedContent::OcrPages {
+                pages: self.clean_pages(pages),
+                quality,
+            },
+            ParsedContent::Plain(Some(text)) => {
+                let text = self.clean_text(&text, &HashSet::new());
+                ParsedContent::Plain((!text.trim().is_empty()).then_some(text))
+            }
+            ParsedContent::Plain(None) => ParsedContent::Plain(None),
+        }
+    }
+
+    fn clean_pages(&self, pages: Vec<PageContent>) -> Vec<PageContent> {
+        let repeated = if self.remove_repeated_lines {
+            repeated_lines(&pages)
+        } else {
+            HashSet::new()
+        };
+        pages
+            .into_iter()
+            .map(|page| PageContent {
+                content: self.clean_text(&page.content, &repeated),
+                ..page
+            })
+            .collect()
+    }
+
+    fn clean_text(&self, text: &str, repeated: &HashSet<String>) -> String {
+        let mut lines = Vec::new();
+        let mut previous_blank = false;
+        for line in text.lines() {
+            let mut line = if self.strip_control_chars {
+                line.chars()
+                    .filter(|c| *c == '\t' || !c.is_control())
+                    .collect()
+            } else {
+                line.to_string()
+            };
+            if self.normalize_whitespace {
+                line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+            }
+
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                if self.normalize_whitespace && previous_blank {
+                    continue;
+                }
+                previous_blank = true;
+            } else {
+                if repeated.contains(trimmed)
+                    || self
+                        .min_line_chars
+                        .is_some_and(|min| trimmed.chars().count() < min)
+                {
+                    continue;
+                }
+                previous_blank = false;
+            }
+            lines.push(line);
+        }
+        lines.join("\n")
+    }
+}
+
+/// Lines on more than half of the pages, for documents of three pages or more
+fn repeated_lines(pages: &[PageContent]) -> HashSet<String> {
+    if pages.len() < 3 {
+        return HashSet::new();
+    }
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for page in pages {
+        let lines: HashSet<&str> = page
+            .content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        for line in lines {
+            *counts.entry(line).or_default() += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .filter(|(_, count)| count * 2 > pages.len())
+        .map(|(line, _)| line.to_string())
+        .collect()
+}
+
+/// The document a pipeline runs for, as hooks see it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HookDocument {
+    id: i64,
+    collection_id: Option<i64>,
+    file_name: String,
+    file_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HookPage {
+    page_number: i64,
+    content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HookChunk {
+    content: String,
+    #[serde(default)]
+    page_number: Option<i64>,
+    #[serde(default)]
+    page_offset: Option<i64>,
+    #[serde(default)]
+    content_type: Option<String>,
+}
+
+/// What a hook may answer; fields it leaves out keep the stage output
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct HookResponse {
+    pages: Option<Vec<HookPage>>,
+    text: Option<String>,
+    chunks: Option<Vec<HookChunk>>,
+}
+
+/// One document's pass through its collection's pipeline
+pub(super) struct PipelineRun {
+    pub config: IngestionPipelineConfig,
+    document: HookDocument,
+}
+
+impl PipelineRun {
+    pub fn doc_id(&self) -> i64 {
+        self.document.id
+    }
+
+    pub fn chunk_engine(&self) -> ChunkEngine {
+        ChunkEngine::new(self.config.chunk_config())
+    }
+
+    /// Extracted or cleaned content after the hooks of `stage`
+    pub async fn run_content_hooks(
+        &self,
+        stage: IngestionStage,
+        mut content: ParsedContent,
+        logs: &Logs,
+    ) -> Result<ParsedContent> {
+        for hook in self.config.hooks_after(stage) {
+            let mut request = serde_json::json!({
+                "stage": stage,
+                "document": self.document,
+            });
+            match &content {
+                ParsedContent::Pages(pages) | ParsedContent::OcrPages { pages, .. } => {
+                    request["pages"] = serde_json::json!(pages
+                        .iter()
+                        .map(|page| HookPage {
+                            page_number: page.page_number,
+                            content: page.content.clone(),
+                        })
+                        .collect::<Vec<_>>());
+                }
+                ParsedContent::Plain(text) => request["text"] = serde_json::json!(text),
+            }
+
+            let Some(response) = self.call_hook(hook, stage, &request, logs).await? else {
+                continue;
+            };
+            content = match (content, response) {
+                (
+                    ParsedContent::Pages(_),
+                    HookResponse {
+                        pages: Some(pages), ..
+                    },
+                ) => ParsedContent::Pages(into_page_contents(pages)),
+                (
+                    ParsedContent::OcrPages { quality, .. },
+                    HookResponse {
+                        pages: Some(pages), ..
+                    },
+                ) => ParsedContent::OcrPages {
+                    pages: into_page_contents(pages),
+                    quality,
+                },
+                (
+                    ParsedContent::Plain(_),
+                    HookResponse {
+                        text: Some(text), ..
+                    },
+                ) => ParsedContent::Plain((!text.trim().is_empty()).then_some(text)),
+                (content, _) => content,
+            };
+        }
+        Ok(content)
+    }
+
+    /// Chunks after the hooks of the chunk stage. Chunks a hook hands back
+    /// are scored again since their content may have changed.
+    pub async fn run_chunk_hooks(
+        &self,
+        ingestion: &RagIngestionUseCase,
+        mut chunks: Vec<Chunk>,
+        logs: &Logs,
+    ) -> Result<Vec<Chunk>> {
+        for hook in self.config.hooks_after(IngestionStage::Chunk) {
+            let request = serde_json::json!({
+                "stage": IngestionStage::Chunk,
+                "document": self.document,
+                "chunks": chunks
+                    .iter()
+                    .map(|chunk| HookChunk {
+                        content: chunk.content.clone(),
+                        page_number: chunk.page_number,
+                        page_offset: chunk.page_offset,
+                        content_type: chunk.content_type.clone(),
+                    })
+                    .collect::<Vec<_>>(),
+            });
+            let Some(returned) = self
+                .call_hook(hook, IngestionStage::Chunk, &request, logs)
+                .await?
+                .and_then(|response| response.chunks)
+            else {
+                continue;
+            };
+            chunks = returned
+                .into_iter()
+                .filter(|chunk| !chunk.content.trim().is_empty())
+                .map(|chunk| Chunk {
+                    // Same estimate the chunk engine makes
+                    token_count: (chunk.content.len() / 4).max(1),
+                    quality_score: Some(ingestion.estimate_chunk_quality(&chunk.content)),
+                    content: chunk.content,
+                    page_number: chunk.page_number,
+                    page_offset: chunk.page_offset,
+                    content_type: chunk.content_type,
+                })
+                .collect();
+        }
+        Ok(chunks)
+    }
+
+    /// The hook's answer, `None` when it kept the output or failed with
+    /// `continue_on_error` set
+    async fn call_hook(
+        &self,
+        hook: &PipelineHook,
+        stage: IngestionStage,
+        request: &Value,
+        logs: &Logs,
+    ) -> Result<Option<HookResponse>> {
+        let label = hook.action.describe();
+        add_log(
+            logs,
+            "INFO",
+            "RAG",
+            &format!("Running {} after {} stage", label, stage.as_str()),
+        );
+
+        let limit = Duration::from_secs(hook.timeout_secs.unwrap_or(DEFAULT_HOOK_TIMEOUT_SECS));
+        let body = match &hook.action {
+            HookAction::Script { command, args } => run_script(command, args, request, limit).await,
+            HookAction::Http { url, headers } => post_http(url, headers, request, limit).await,
+        };
+        let response = body.and_then(|body| {
+            if body.trim().is_empty() {
+                return Ok(None);
+            }
+            serde_json::from_str::<HookResponse>(&body)
+                .map(Some)
+                .map_err(|e| format!("answer is not valid JSON: {}", e))
+        });
+
+        match response {
+            Ok(response) => Ok(response),
+            Err(err) if hook.continue_on_error => {
+                add_log(
+                    logs,
+                    "WARN",
+                    "RAG",
+                    &format!("{} failed, continuing without it: {}", label, err),
+                );
+                Ok(None)
+            }
+            Err(err) => {
+                let message = format!("{} after {} stage failed: {}", label, stage.as_str(), err);
+                add_log(logs, "ERROR", "RAG", &message);
+                Err(AppError::Internal(message))
+            }
+        }
+    }
+}
+
+impl RagIngestionUseCase {
+    /// Start a document's pass through its collection's pipeline
+    /// Embed settings of the collection `doc_id` belongs to, for embeddings
+    /// written outside an import
+    pub async fn embed_config(&self, doc_id: i64) -> Result<EmbedStageConfig> {
+        let document = self.rag_repository.get_document(doc_id).await?;
+        Ok(self.pipeline_run(&document).await?.config.embed)
+    }
+
+    pub(super) async fn pipeline_run(&self, document: &RagDocument) -> Result<PipelineRun> {
+        let config = match document.collection_id {
+            Some(collection_id) => {
+                let collection = self.rag_repository.get_collection(collection_id).await?;
+                IngestionPipelineConfig::from_collection_config(&collection.config_json)?
+            }
+            None => IngestionPipelineConfig::default(),
+        };
+        Ok(PipelineRun {
+            config,
+            document: HookDocument {
+                id: document.id,
+                collection_id: document.collection_id,
+                file_name: document.file_name.clone(),
+                file_type: document.file_type.clone(),
+            },
+        })
+    }
+}
+
+fn into_page_contents(pages: Vec<HookPage>) -> Vec<PageContent> {
+    pages
+        .into_iter()
+        .map(|page| PageContent {
+            page_number: page.page_number,
+            content: page.content,
+        })
+        .collect()
+}
+
+fn truncate(text: &str) -> String {
+    let text = text.trim();
+    match text.char_indices().nth(MAX_HOOK_ERROR_CHARS) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+async fn run_script(
+    command: &str,
+    args: &[String],
+    request: &Value,
+    limit: Duration,
+) -> std::result::Result<String, String> {
+    let mut child = Command::new(command)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("failed to start: {}", e))?;
+
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| "stdin is not available".to_string())?;
+    let input = request.to_string();
+    let run = async move {
+        // A script may exit without reading its input; its exit status tells
+        stdin.write_all(input.as_bytes()).await.ok();
+        drop(stdin);
+        child.wait_with_output().await
+    };
+    let output = timeout(limit, run)
+        .await
+        .map_err(|_| format!("timed out after {}s", limit.as_secs()))?
+        .map_err(|e| format!("failed to run: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "exited with {}: {}",
+            output.status,
+            truncate(&String::from_utf8_lossy(&output.stderr))
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+async fn post_http(
+    url: &str,
+    headers: &HashMap<String, String>,
+    request: &Value,
+    limit: Duration,
+) -> std::result::Result<String, String> {
+    let client = reqwest::Client::builder()
+        .timeout(limit)
+        .build()
+        .map_err(|e| format!("failed to build HTTP client: {}", e))?;
+    let mut builder = client.post(url).json(request);
+    for (name, value) in headers {
+        builder = builder.header(name, value);
+    }
+    let response = builder
+        .send()
+        .await
+        .map_err(|e| format!("request failed: {}", e))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("failed to read response: {}", e))?;
+    if !status.is_success() {
+        return Err(format!("returned {}: {}", status, truncate(&body)));
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(page_number: i64, content: &str) -> PageContent {
+        PageContent {
+            page_number,
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn collections_without_pipeline_get_defaults() {
+        let config = IngestionPipelineConfig::from_collection_config("{}").unwrap();
+        assert_eq!(config, IngestionPipelineConfig::default());
+        assert!(config.embed.enabled);
+        assert!(config.clean.is_noop());
+        assert_eq!(config.chunk_config().max_chunk_size, 500);
+    }
+
+    #[test]
+    fn pipeline_round_trips_through_collection_config() {
+        let config: IngestionPipelineConfig = serde_json::from_value(serde_json::json!({
+            "clean": { "normalizeWhitespace": true },
+            "chunk": { "strategy": "semantic", "maxChunkSize": 800 },
+            "embed": { "metric": "dot" },
+            "hooks": [{
+                "after": "clean",
+                "action": { "type": "script", "command": "python3", "args": ["scrub.py"] },
+                "continueOnError": true
+            }]
+        }))
+        .unwrap();
+
+        let merged = config
+            .merge_into_collection_config(r#"{"owner":"docs"}"#)
+            .unwrap();
+        let stored: Value = serde_json::from_str(&merged).unwrap();
+        assert_eq!(stored["owner"], "docs");
+        let parsed = IngestionPipelineConfig::from_collection_config(&merged).unwrap();
+        assert_eq!(parsed, config);
+        assert_eq!(parsed.chunk_config().strategy, ChunkStrategy::Semantic);
+        assert_eq!(parsed.embed.metric, SimilarityMetric::Dot);
+        assert!(parsed.embed.enabled);
+    }
+
+    #[test]
+    fn hooks_after_embed_or_without_target_are_rejected() {
+        let mut config = IngestionPipelineConfig {
+            hooks: vec![PipelineHook {
+                after: IngestionStage::Embed,
+                action: HookAction::Http {
+                    url: "https://example.com/hook".to_string(),
+                    headers: HashMap::new(),
+                },
+                timeout_secs: None,
+                continue_on_error: false,
+            }],
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        config.hooks[0].after = IngestionStage::Chunk;
+        assert!(config.validate().is_ok());
+
+        config.hooks[0].action = HookAction::Http {
+            url: "example.com/hook".to_string(),
+            headers: HashMap::new(),
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn clean_stage_normalizes_and_drops_running_headers() {
+        let clean = CleanStageConfig {
+            strip_control_chars: true,
+            normalize_whitespace: true,
+            remove_repeated_lines: true,
+            min_line_chars: Some(3),
+        };
+        let pages = vec![
+            page(
+                1,
+                "ACME Annual Report\nRevenue   grew\u{0}  by 12%.\n\n\n\nok\n1",
+            ),
+            page(2, "ACME Annual Report\nCosts fell."),
+            page(3, "ACME Annual Report\nOutlook is stable."),
+        ];
+
+        let ParsedContent::Pages(cleaned) = clean.clean(ParsedContent::Pages(pages)) else {
+            panic!("pages stay pages");
+        };
+        assert_eq!(cleaned[0].content, "Revenue grew by 12%.\n");
+        assert_eq!(cleaned[1].content, "Costs fell.");
+        assert_eq!(cleaned[2].page_number, 3);
+    }
+
+    #[test]
+    fn clean_stage_leaves_content_alone_by_default() {
+        let text = "  indented\tcode\n\n\n";
+        let ParsedContent::Plain(Some(kept)) =
+            CleanStageConfig::default().clean(ParsedContent::Plain(Some(text.to_string())))
+        else {
+            panic!("text stays text");
+        };
+        assert_eq!(kept, text);
+    }
+}
@@ -0,0 +1,287 @@
+//! Preview artifacts for document lists: a thumbnail of a PDF's first page,
+//! the title and author from the file's metadata, and the page count. They
+//! are built once, at ingestion or on the first request for documents
+//! imported earlier, and stored in `document_previews`.
+
+use std::io::Read;
+use std::path::Path;
+
+use base64::Engine as _;
+use image::codecs::jpeg::JpegEncoder;
+use lopdf::{Document, Object};
+
+use crate::domain::rag_entities::RagDocument;
+use crate::infrastructure::db::rag::repository::DocumentPreviewRecord;
+
+use super::{DocumentPreview, Logs, RagIngestionUseCase, Result};
+
+/// Resolution the first page is rendered at before it is scaled down
+const THUMBNAIL_DPI: u32 = 48;
+const THUMBNAIL_MAX_WIDTH: u32 = 240;
+const THUMBNAIL_MAX_HEIGHT: u32 = 320;
+const THUMBNAIL_JPEG_QUALITY: u8 = 80;
+
+/// Longest title or author kept, in characters
+const MAX_METADATA_CHARS: usize = 300;
+
+struct Thumbnail {
+    jpeg: Vec<u8>,
+    width: u32,
+    height: u32,
+}
+
+impl From<DocumentPreviewRecord> for DocumentPreview {
+    fn from(record: DocumentPreviewRecord) -> Self {
+        Self {
+            document_id: record.doc_id,
+            title: record.title,
+            author: record.author,
+            page_count: record.page_count,
+            thumbnail: record.thumbnail.map(|jpeg| {
+                format!(
+                    "data:image/jpeg;base64,{}",
+                    base64::engine::general_purpose::STANDARD.encode(jpeg)
+                )
+            }),
+            thumbnail_width: record.thumbnail_width,
+            thumbnail_height: record.thumbnail_height,
+        }
+    }
+}
+
+impl RagIngestionUseCase {
+    /// The stored preview of a document, built from its file first when the
+    /// document was imported before previews existed
+    pub async fn document_preview(&self, doc_id: i64, logs: &Logs) -> Result<DocumentPreview> {
+        if let Some(record) = self.rag_repository.get_document_preview(doc_id).await? {
+            return Ok(record.into());
+        }
+        let document = self.rag_repository.get_document(doc_id).await?;
+        Ok(self.store_document_preview(&document, logs).await.into())
+    }
+
+    /// Build and store the preview of a document. A missing preview only
+    /// costs the list a thumbnail, so failures are logged, not returned.
+    pub(super) async fn store_document_preview(
+        &self,
+        document: &RagDocument,
+        logs: &Logs,
+    ) -> DocumentPreviewRecord {
+        use crate::interfaces::http::add_log;
+
+        let mut record = DocumentPreviewRecord {
+            doc_id: document.id,
+            title: None,
+            author: None,
+            page_count: document.total_pages,
+            thumbnail: None,
+            thumbnail_width: None,
+            thumbnail_height: None,
+            created_at: chrono::Utc::now().timestamp(),
+        };
+
+        // Previews are stored in plain text, unlike an encrypted collection's chunks
+        let encrypted = match document.collection_id {
+            Some(collection_id) => self
+                .rag_repository
+                .is_collection_encrypted(collection_id)
+                .await
+                .unwrap_or(true),
+            None => false,
+        };
+        let source = document
+            .file_path
+            .as_deref()
+            .map(Path::new)
+            .filter(|path| path.is_file());
+        if let (false, Some(path)) = (encrypted, source) {
+            let file_path = path.to_string_lossy();
+            match document.file_type.as_str() {
+                "pdf" => self.fill_pdf_preview(&mut record, &file_path, logs),
+                "docx" => {
+                    if let Some((title, author)) = docx_metadata(&file_path) {
+                        record.title = title;
+                        record.author = author;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let Err(err) = self.rag_repository.upsert_document_preview(&record).await {
+            add_log(
+                logs,
+                "WARN",
+                "RAG",
+                &format!(
+                    "Failed to save preview of document {}: {}",
+                    document.id, err
+                ),
+            );
+        }
+        record
+    }
+
+    fn fill_pdf_preview(&self, record: &mut DocumentPreviewRecord, file_path: &str, logs: &Logs) {
+        use crate::interfaces::http::add_log;
+
+        match Document::load(file_path) {
+            Ok(pdf) => {
+                record.page_count = pdf.get_pages().len() as i64;
+                record.title = pdf_info_text(&pdf, b"Title");
+                record.author = pdf_info_text(&pdf, b"Author");
+            }
+            Err(err) => add_log(
+                logs,
+                "WARN",
+                "RAG",
+                &format!("Failed to read PDF metadata for preview: {}", err),
+            ),
+        }
+
+        let Some((_temp_dir, images)) =
+            self.rasterize_pdf_to_pngs(file_path, THUMBNAIL_DPI, Some(1), logs)
+        else {
+            return;
+        };
+        match images.first().map(|image| encode_thumbnail(image)) {
+            Some(Ok(thumbnail)) => {
+                record.thumbnail_width = Some(thumbnail.width as i64);
+                record.thumbnail_height = Some(thumbnail.height as i64);
+                record.thumbnail = Some(thumbnail.jpeg);
+            }
+            Some(Err(err)) => add_log(
+                logs,
+                "WARN",
+                "RAG",
+                &format!("Failed to encode thumbnail: {}", err),
+            ),
+            None => {}
+        }
+    }
+}
+
+fn encode_thumbnail(image_path: &Path) -> image::ImageResult<Thumbnail> {
+    let thumbnail = image::open(image_path)?
+        .thumbnail(THUMBNAIL_MAX_WIDTH, THUMBNAIL_MAX_HEIGHT)
+        .to_rgb8();
+    let mut jpeg = Vec::new();
+    thumbnail.write_with_encoder(JpegEncoder::new_with_quality(
+        &mut jpeg,
+        THUMBNAIL_JPEG_QUALITY,
+    ))?;
+    Ok(Thumbnail {
+        jpeg,
+        width: thumbnail.width(),
+        height: thumbnail.height(),
+    })
+}
+
+/// An entry of the PDF's document information dictionary
+fn pdf_info_text(pdf: &Document, key: &[u8]) -> Option<String> {
+    let info = pdf
+        .trailer
+        .get(b"Info")
+        .and_then(|info| pdf.dereference(info))
+        .and_then(|(_, info)| info.as_dict())
+        .ok()?;
+    match info
+        .get(key)
+        .and_then(|value| pdf.dereference(value))
+        .ok()?
+    {
+        (_, Object::String(bytes, _)) => clean_metadata(&decode_pdf_text(bytes)),
+        _ => None,
+    }
+}
+
+/// PDF text strings are UTF-16BE with a byte order mark, UTF-8 with one
+/// (PDF 2.0), or PDFDocEncoding, which matches Latin-1 for printable text
+fn decode_pdf_text(bytes: &[u8]) -> String {
+    if let Some(utf16) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        let units: Vec<u16> = utf16
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect();
+        return String::from_utf16_lossy(&units);
+    }
+    if let Some(utf8) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return String::from_utf8_lossy(utf8).into_owned();
+    }
+    bytes.iter().map(|&byte| byte as char).collect()
+}
+
+/// Title and author from a DOCX's `docProps/core.xml`
+fn docx_metadata(file_path: &str) -> Option<(Option<String>, Option<String>)> {
+    let file = std::fs::File::open(file_path).ok()?;
+    let mut archive = zip::ZipArchive::new(file).ok()?;
+    let mut core = String::new();
+    archive
+        .by_name("docProps/core.xml")
+        .ok()?
+        .read_to_string(&mut core)
+        .ok()?;
+    Some((
+        xml_element_text(&core, "dc:title"),
+        xml_element_text(&core, "dc:creator"),
+    ))
+}
+
+/// Text of the first `<tag>` element; enough for the flat core properties part
+fn xml_element_text(xml: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}", tag);
+    let start = xml
+        .match_indices(&open)
+        .map(|(index, _)| index + open.len())
+        .find(|&index| matches!(xml[index..].chars().next(), Some('>' | ' ' | '/')))?;
+    let content_start = start + xml[start..].find('>')? + 1;
+    if xml[..content_start].ends_with("/>") {
+        return None;
+    }
+    let content_end = content_start + xml[content_start..].find(&format!("</{}>", tag))?;
+    let text = xml[content_start..content_end]
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&");
+    clean_metadata(&text)
+}
+
+/// Trimmed, with control characters dropped; `None` when nothing is left
+fn clean_metadata(text: &str) -> Option<String> {
+    let text: String = text
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(MAX_METADATA_CHARS)
+        .collect();
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_pdf_text_encodings() {
+        assert_eq!(
+            decode_pdf_text(&[0xFE, 0xFF, 0x00, 0x52, 0x00, 0xE9, 0x00, 0x73]),
+            "Rés"
+        );
+        assert_eq!(decode_pdf_text(&[0xEF, 0xBB, 0xBF, b'o', b'k']), "ok");
+        assert_eq!(decode_pdf_text(b"Caf\xe9"), "Café");
+    }
+
+    #[test]
+    fn test_docx_core_properties() {
+        let core = r#"<cp:coreProperties><dc:title>Q3 &amp; Q4 Plan</dc:title><dc:subject/><dc:creator>Ana</dc:creator><dc:creatorTool/></cp:coreProperties>"#;
+        assert_eq!(
+            xml_element_text(core, "dc:title").as_deref(),
+            Some("Q3 & Q4 Plan")
+        );
+        assert_eq!(xml_element_text(core, "dc:creator").as_deref(), Some("Ana"));
+        assert_eq!(xml_element_text(core, "dc:subject"), None);
+        assert_eq!(xml_element_text(core, "dc:description"), None);
+    }
+}
@@ -0,0 +1,102 @@
+//! Resuming imports that failed or were cut short by the app closing. Each
+//! file import records the stage it reached on its document; an import that
+//! got as far as embedding only embeds the chunks still missing one, any
+//! other starts over from the file, keeping structured rows once chunking
+//! began.
+
+use std::path::Path;
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::rag_entities::{IngestionState, RagDocument};
+
+use super::workbooks::source_file_path;
+use super::{IngestionResumeReport, Logs, RagIngestionUseCase};
+
+impl RagIngestionUseCase {
+    /// Resume every failed import, of one collection or of all
+    pub async fn resume_failed_ingestions(
+        &self,
+        collection_id: Option<i64>,
+        logs: &Logs,
+    ) -> Result<IngestionResumeReport> {
+        use crate::interfaces::http::add_log;
+
+        let documents = self
+            .rag_repository
+            .list_failed_ingestions(collection_id)
+            .await?;
+        let mut report = IngestionResumeReport::default();
+        for document in documents {
+            add_log(
+                logs,
+                "INFO",
+                "RAG",
+                &format!(
+                    "Resuming import of {} from {}",
+                    document.file_name,
+                    document
+                        .ingestion_failed_at
+                        .unwrap_or(IngestionState::Pending)
+                        .as_str()
+                ),
+            );
+            match self.resume_ingestion(&document, logs).await {
+                Ok(()) => report.resumed.push(document.id),
+                Err(e) => {
+                    add_log(
+                        logs,
+                        "ERROR",
+                        "RAG",
+                        &format!("Import of {} failed again: {}", document.file_name, e),
+                    );
+                    self.record_ingestion_failure(document.id, &e, logs).await;
+                    report.failed.push(document.id);
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    async fn resume_ingestion(&self, document: &RagDocument, logs: &Logs) -> Result<()> {
+        let stage = document
+            .ingestion_failed_at
+            .unwrap_or(IngestionState::Pending);
+
+        if stage == IngestionState::Embedding {
+            self.rag_repository
+                .set_ingestion_state(document.id, IngestionState::Embedding)
+                .await?;
+            let pipeline = self.pipeline_run(document).await?;
+            let missing = self
+                .rag_repository
+                .get_chunks_missing_embeddings(document.id)
+                .await?;
+            self.embed_stored_chunks(&pipeline, &missing, logs).await?;
+            self.store_document_preview(document, logs).await;
+            return self
+                .rag_repository
+                .set_ingestion_state(document.id, IngestionState::Complete)
+                .await;
+        }
+
+        let file_path = document
+            .file_path
+            .as_deref()
+            .filter(|path| Path::new(source_file_path(path)).is_file())
+            .ok_or_else(|| {
+                AppError::NotFound(format!(
+                    "Source file of {} is no longer available",
+                    document.file_name
+                ))
+            })?;
+        let rows_stored = stage == IngestionState::Chunking;
+        self.rag_repository
+            .clear_document_content(document.id, rows_stored)
+            .await?;
+        self.rag_repository
+            .set_ingestion_state(document.id, IngestionState::Extracting)
+            .await?;
+        self.import_file_content(document, file_path, rows_stored, logs)
+            .await
+    }
+}
@@ -1,6 +1,8 @@
 use crate::application::use_cases::structured_row_schema::{
     detect_field, is_sensitive_header, normalize_header as normalize_header_schema, StructuredField,
 };
+use crate::domain::rag_import_templates::{find_template, ImportTemplate, TemplateField};
+use crate::infrastructure::csv::ColumnProfiler;
 
 use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 
@@ -10,6 +12,8 @@ pub(super) struct StructuredRowMapping {
     source_idx: Option<usize>,
     title_idx: Option<usize>,
     created_at_idx: Option<usize>,
+    /// Guess the columns by position when the header names none
+    positional_fallback: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -30,6 +34,7 @@ impl StructuredRowMapping {
             source_idx: None,
             title_idx: None,
             created_at_idx: None,
+            positional_fallback: true,
         };
 
         let Some(header) = header else {
@@ -64,7 +69,8 @@ impl StructuredRowMapping {
         // fall back to positional mapping for the typical schema:
         // - 6 columns: id, category, source, title, content, created_at
         // - 5 columns: category, source, title, content, created_at
-        let fallback = self.category_idx.is_none()
+        let fallback = self.positional_fallback
+            && self.category_idx.is_none()
             && self.source_idx.is_none()
             && self.title_idx.is_none()
             && self.created_at_idx.is_none();
@@ -109,6 +115,96 @@ impl StructuredRowMapping {
     }
 }
 
+/// Rows of a file read with an import template
+#[derive(Debug, Clone)]
+pub(super) struct TemplatedRows {
+    pub(super) header: Vec<String>,
+    pub(super) rows: Vec<Vec<String>>,
+    pub(super) mapping: StructuredRowMapping,
+    /// Values that did not fit their column's type and were kept as-is
+    pub(super) coercion_failures: usize,
+}
+
+/// The template among `templates` that rows of a file imported into
+/// `collection_id` match, with the rows read by it
+pub(super) fn match_template<'a>(
+    templates: &'a [ImportTemplate],
+    collection_id: Option<i64>,
+    rows: &[Vec<String>],
+) -> Option<(&'a ImportTemplate, TemplatedRows)> {
+    // Spreadsheets keep their empty rows; CSV rows are read without them
+    let rows: Vec<Vec<String>> = rows
+        .iter()
+        .filter(|row| row.iter().any(|cell| !cell.trim().is_empty()))
+        .cloned()
+        .collect();
+    let template = find_template(templates, collection_id, &rows)?;
+    Some((template, apply_template(template, &rows)?))
+}
+
+/// The header and data rows of `rows`, without blank rows, laid out by
+/// `template`, without skipped columns and with values coerced to the
+/// column types; `None` when the rows don't have the template's layout
+fn apply_template(template: &ImportTemplate, rows: &[Vec<String>]) -> Option<TemplatedRows> {
+    let layout = template.split_rows(rows)?;
+
+    // Columns past the template's are kept as text
+    let kept: Vec<usize> = (0..layout.header.len())
+        .filter(|&idx| !template.columns.get(idx).is_some_and(|column| column.skip))
+        .collect();
+    let mut mapping = StructuredRowMapping {
+        category_idx: None,
+        source_idx: None,
+        title_idx: None,
+        created_at_idx: None,
+        positional_fallback: false,
+    };
+    for (position, &idx) in kept.iter().enumerate() {
+        let slot = match template.columns.get(idx).and_then(|column| column.field) {
+            Some(TemplateField::Category) => &mut mapping.category_idx,
+            Some(TemplateField::Source) => &mut mapping.source_idx,
+            Some(TemplateField::Title) => &mut mapping.title_idx,
+            Some(TemplateField::CreatedAt) => &mut mapping.created_at_idx,
+            None => continue,
+        };
+        *slot = Some(position);
+    }
+
+    let mut coercion_failures = 0;
+    let data_rows = layout
+        .data
+        .iter()
+        .map(|row| {
+            kept.iter()
+                .map(|&idx| {
+                    let value = row.get(idx).map(String::as_str).unwrap_or("");
+                    let Some(column) = template.columns.get(idx) else {
+                        return value.to_string();
+                    };
+                    let Some(column_type) = column.coerce else {
+                        return value.to_string();
+                    };
+                    ColumnProfiler::coerce_value(column_type, column.date_format.as_deref(), value)
+                        .unwrap_or_else(|| {
+                            coercion_failures += 1;
+                            value.to_string()
+                        })
+                })
+                .collect()
+        })
+        .collect();
+
+    Some(TemplatedRows {
+        header: kept
+            .iter()
+            .map(|&idx| clean_cell(&layout.header[idx]))
+            .collect(),
+        rows: data_rows,
+        mapping,
+        coercion_failures,
+    })
+}
+
 pub(super) fn split_header_and_rows(
     rows: &[Vec<String>],
 ) -> (Option<Vec<String>>, Vec<Vec<String>>) {
@@ -276,6 +372,11 @@ pub(super) fn parse_datetime_to_iso(raw: &str) -> Option<String> {
         );
     }
 
+    // 2026-01-22T13:45:00 (coerced by an import template)
+    if let Ok(dt) = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S") {
+        return Some(dt.format("%Y-%m-%dT%H:%M:%S").to_string());
+    }
+
     // 2026-01-22 13:45:00
     if let Ok(dt) = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S") {
         return Some(dt.format("%Y-%m-%dT%H:%M:%S").to_string());
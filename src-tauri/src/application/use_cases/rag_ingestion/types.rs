@@ -8,6 +8,74 @@ pub enum ParsedContent {
     Pages(Vec<PageContent>),
     /// Plain text without page structure (TXT, web, single-page docs)
     Plain(Option<String>),
+    /// Pages of which some or all were recognized by OCR, with Tesseract's
+    /// confidence for the OCR'd pages that reported one
+    OcrPages {
+        pages: Vec<PageContent>,
+        quality: Vec<OcrPageQuality>,
+    },
+}
+
+/// Tesseract confidence of one OCR'd page and how it was rendered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OcrPageQuality {
+    pub page_number: i64,
+    /// Mean word confidence, 0.0 - 1.0
+    pub confidence: f32,
+    /// Mean word confidence of each text block, in Tesseract's block order
+    pub block_confidences: Vec<f32>,
+    pub dpi: u32,
+    pub preprocessing: String,
+}
+
+/// Outcome of one run over the OCR reprocessing queue
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OcrQueueReport {
+    pub processed: usize,
+    /// Pages that reached the minimum confidence
+    pub improved: usize,
+    /// Pages that ran out of retry strategies
+    pub exhausted: usize,
+    pub failed: usize,
+    /// Pages still queued after the run
+    pub remaining: i64,
+    /// Documents whose chunks were replaced
+    pub updated_documents: Vec<i64>,
+}
+
+/// Outcome of resuming failed and interrupted imports
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IngestionResumeReport {
+    /// Documents whose import completed
+    pub resumed: Vec<i64>,
+    /// Documents that failed again; each keeps its new error
+    pub failed: Vec<i64>,
+}
+
+/// A worksheet of an Excel workbook, as offered for import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkbookSheet {
+    pub name: String,
+    /// Position among the workbook's sheets, from 0
+    pub index: usize,
+    /// Hidden or very hidden in Excel
+    pub hidden: bool,
+    pub rows: usize,
+    pub columns: usize,
+    /// Merged cell ranges; each is filled with its top-left value
+    pub merged_regions: usize,
+    /// Cells holding a formula; the values Excel computed are imported
+    pub formula_cells: usize,
+}
+
+/// Which sheets of a workbook are imported, each as its own document
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExcelImportOptions {
+    /// Sheet names; empty imports every visible sheet with data
+    pub sheets: Vec<String>,
+    /// Import hidden sheets too when no sheets are named
+    pub include_hidden: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,3 +118,16 @@ pub enum ExtractionQuality {
     Poor,
     Unknown,
 }
+
+/// What a document list shows of a document without opening its file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentPreview {
+    pub document_id: i64,
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub page_count: i64,
+    /// First page as a `data:image/jpeg;base64,` URL; PDFs only
+    pub thumbnail: Option<String>,
+    pub thumbnail_width: Option<i64>,
+    pub thumbnail_height: Option<i64>,
+}
@@ -0,0 +1,497 @@
+//! Import of Excel workbooks: one document per worksheet, named after the
+//! workbook and the sheet, with the sheet's name kept in the document
+//! metadata for filtering. Hidden and empty sheets are skipped unless they
+//! are picked. Cells hold the values Excel computed for formulas, and
+//! merged cells repeat the value of their top-left cell.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+use calamine::{Data, Range};
+
+use super::{ExcelImportOptions, Logs, RagIngestionUseCase, WorkbookSheet};
+use crate::domain::error::{AppError, Result};
+use crate::domain::rag_entities::{IngestionState, RagDocument, RagDocumentInput};
+use crate::interfaces::http::add_log;
+
+/// Separates the workbook path from the sheet name in a sheet document's path
+const SHEET_PATH_MARKER: &str = "#sheet=";
+
+impl RagIngestionUseCase {
+    /// The worksheets of a workbook, in workbook order
+    pub fn list_workbook_sheets(&self, file_path: &str) -> Result<Vec<WorkbookSheet>> {
+        use calamine::{open_workbook, Reader, SheetType, SheetVisible, Xlsx};
+
+        let mut workbook: Xlsx<_> = open_workbook(file_path)
+            .map_err(|e| AppError::Internal(format!("Failed to open Excel file: {}", e)))?;
+        let merged = merged_regions(file_path);
+        let metadata = workbook.sheets_metadata().to_vec();
+
+        let mut sheets = Vec::new();
+        for (index, sheet) in metadata.into_iter().enumerate() {
+            if sheet.typ != SheetType::WorkSheet {
+                continue;
+            }
+            let range = workbook.worksheet_range(&sheet.name).map_err(|e| {
+                AppError::Internal(format!("Failed to read sheet {}: {}", sheet.name, e))
+            })?;
+            let formula_cells = workbook
+                .worksheet_formula(&sheet.name)
+                .map(|formulas| formulas.used_cells().count())
+                .unwrap_or(0);
+            let (rows, columns) = range.get_size();
+            sheets.push(WorkbookSheet {
+                index,
+                hidden: sheet.visible != SheetVisible::Visible,
+                rows,
+                columns,
+                merged_regions: merged.get(&sheet.name).map_or(0, Vec::len),
+                formula_cells,
+                name: sheet.name,
+            });
+        }
+        Ok(sheets)
+    }
+
+    /// Import the sheets of a workbook picked by `options`, each as its own
+    /// document. A sheet that fails keeps its failed document, so it can be
+    /// resumed; the import only fails when every sheet did.
+    pub async fn ingest_workbook(
+        &self,
+        file_path: &str,
+        collection_id: Option<i64>,
+        options: &ExcelImportOptions,
+        logs: &Logs,
+    ) -> Result<Vec<RagDocument>> {
+        let path = Path::new(file_path);
+        if !path.is_file() {
+            return Err(AppError::NotFound(format!("File not found: {}", file_path)));
+        }
+        let file_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| AppError::ValidationError("Invalid file name".to_string()))?
+            .to_string();
+
+        let sheets = self.list_workbook_sheets(file_path)?;
+        let selected = select_sheets(&sheets, options)?;
+        add_log(
+            logs,
+            "INFO",
+            "RAG",
+            &format!(
+                "Importing {} of {} sheets from {}",
+                selected.len(),
+                sheets.len(),
+                file_name
+            ),
+        );
+
+        let mut documents = Vec::with_capacity(selected.len());
+        let mut first_error = None;
+        for sheet in selected {
+            match self
+                .ingest_sheet(file_path, &file_name, sheet, collection_id, logs)
+                .await
+            {
+                Ok(document) => documents.push(document),
+                Err((document, e)) => {
+                    add_log(
+                        logs,
+                        "ERROR",
+                        "RAG",
+                        &format!("Failed to import sheet {}: {}", sheet.name, e),
+                    );
+                    documents.extend(document);
+                    if first_error.is_none() {
+                        first_error = Some(e);
+                    }
+                }
+            }
+        }
+
+        match first_error {
+            Some(e)
+                if documents
+                    .iter()
+                    .all(|document| document.ingestion_state == IngestionState::Failed) =>
+            {
+                Err(e)
+            }
+            _ => Ok(documents),
+        }
+    }
+
+    /// Import one sheet; on failure, the failed document if one was created
+    async fn ingest_sheet(
+        &self,
+        file_path: &str,
+        file_name: &str,
+        sheet: &WorkbookSheet,
+        collection_id: Option<i64>,
+        logs: &Logs,
+    ) -> std::result::Result<RagDocument, (Option<RagDocument>, AppError)> {
+        let sheet_path = sheet_document_path(file_path, &sheet.name);
+        let document = async {
+            self.discard_previous_import(collection_id, &sheet_path)
+                .await?;
+            let document = self
+                .rag_repository
+                .create_document(&RagDocumentInput {
+                    collection_id,
+                    file_name: format!("{} [{}]", file_name, sheet.name),
+                    file_path: Some(sheet_path.clone()),
+                    file_type: "xlsx".to_string(),
+                    language: Some("auto".to_string()),
+                    total_pages: None,
+                })
+                .await?;
+            self.rag_repository
+                .set_document_meta(document.id, &sheet_meta_json(file_name, sheet))
+                .await?;
+            Ok::<_, AppError>(document)
+        }
+        .await
+        .map_err(|e| (None, e))?;
+        add_log(
+            logs,
+            "INFO",
+            "RAG",
+            &format!("Document {} created for sheet {}", document.id, sheet.name),
+        );
+
+        let imported = async {
+            self.rag_repository
+                .set_ingestion_state(document.id, IngestionState::Extracting)
+                .await?;
+            self.import_file_content(&document, &sheet_path, false, logs)
+                .await
+        }
+        .await;
+        if let Err(e) = imported {
+            self.record_ingestion_failure(document.id, &e, logs).await;
+            let failed = self.rag_repository.get_document(document.id).await.ok();
+            return Err((failed, e));
+        }
+        self.rag_repository
+            .get_document(document.id)
+            .await
+            .map_err(|e| (None, e))
+    }
+}
+
+/// The sheets `options` pick, in workbook order. Named sheets are imported
+/// even when hidden or empty; unknown names are rejected.
+fn select_sheets<'a>(
+    sheets: &'a [WorkbookSheet],
+    options: &ExcelImportOptions,
+) -> Result<Vec<&'a WorkbookSheet>> {
+    if let Some(unknown) = options
+        .sheets
+        .iter()
+        .find(|name| !sheets.iter().any(|sheet| sheet.name == **name))
+    {
+        return Err(AppError::ValidationError(format!(
+            "The workbook has no sheet named \"{}\"",
+            unknown
+        )));
+    }
+    let selected: Vec<&WorkbookSheet> = sheets
+        .iter()
+        .filter(|sheet| {
+            if options.sheets.is_empty() {
+                (options.include_hidden || !sheet.hidden) && sheet.rows > 0
+            } else {
+                options.sheets.contains(&sheet.name)
+            }
+        })
+        .collect();
+    if selected.is_empty() {
+        return Err(AppError::ValidationError(
+            "The workbook has no visible sheet with data; pick the sheets to import".to_string(),
+        ));
+    }
+    Ok(selected)
+}
+
+/// Path of the document of one sheet of the workbook at `file_path`
+fn sheet_document_path(file_path: &str, sheet_name: &str) -> String {
+    format!("{}{}{}", file_path, SHEET_PATH_MARKER, sheet_name)
+}
+
+/// The workbook path and sheet name of a document path; documents imported
+/// before sheets had their own have no sheet name and hold the first sheet
+pub(super) fn split_sheet_path(path: &str) -> (&str, Option<&str>) {
+    match path.rfind(SHEET_PATH_MARKER) {
+        Some(at) => (&path[..at], Some(&path[at + SHEET_PATH_MARKER.len()..])),
+        None => (path, None),
+    }
+}
+
+/// The file a document was imported from
+pub(super) fn source_file_path(path: &str) -> &str {
+    split_sheet_path(path).0
+}
+
+fn sheet_meta_json(file_name: &str, sheet: &WorkbookSheet) -> String {
+    serde_json::json!({
+        "workbook": file_name,
+        "sheet_name": sheet.name,
+        "sheet_index": sheet.index,
+        "sheet_hidden": sheet.hidden,
+    })
+    .to_string()
+}
+
+/// A merged cell range, by absolute (row, column) from 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct MergedRegion {
+    first: (u32, u32),
+    last: (u32, u32),
+}
+
+/// Merged cell ranges of each worksheet, by sheet name. calamine does not
+/// report them for XLSX files, so they are read from the sheet parts; a
+/// workbook whose parts cannot be read has none.
+pub(super) fn merged_regions(file_path: &str) -> HashMap<String, Vec<MergedRegion>> {
+    let mut regions = HashMap::new();
+    let Ok(file) = std::fs::File::open(file_path) else {
+        return regions;
+    };
+    let Ok(mut archive) = zip::ZipArchive::new(file) else {
+        return regions;
+    };
+    let (Some(workbook), Some(rels)) = (
+        read_part(&mut archive, "xl/workbook.xml"),
+        read_part(&mut archive, "xl/_rels/workbook.xml.rels"),
+    ) else {
+        return regions;
+    };
+
+    let targets: HashMap<String, String> = xml_elements(&rels, "Relationship")
+        .into_iter()
+        .filter_map(|element| {
+            Some((
+                xml_attribute(element, "Id")?,
+                xml_attribute(element, "Target")?,
+            ))
+        })
+        .collect();
+    for element in xml_elements(&workbook, "sheet") {
+        let (Some(name), Some(target)) = (
+            xml_attribute(element, "name"),
+            xml_attribute(element, "r:id").and_then(|id| targets.get(&id)),
+        ) else {
+            continue;
+        };
+        let part = match target.strip_prefix('/') {
+            Some(absolute) => absolute.to_string(),
+            None => format!("xl/{}", target),
+        };
+        if let Some(sheet) = read_part(&mut archive, &part) {
+            let merged = parse_merged_regions(&sheet);
+            if !merged.is_empty() {
+                regions.insert(name, merged);
+            }
+        }
+    }
+    regions
+}
+
+fn read_part(archive: &mut zip::ZipArchive<std::fs::File>, name: &str) -> Option<String> {
+    let mut content = String::new();
+    archive
+        .by_name(name)
+        .ok()?
+        .read_to_string(&mut content)
+        .ok()?;
+    Some(content)
+}
+
+/// The `<mergeCell ref="A1:C2"/>` ranges of a sheet part
+fn parse_merged_regions(sheet_xml: &str) -> Vec<MergedRegion> {
+    xml_elements(sheet_xml, "mergeCell")
+        .into_iter()
+        .filter_map(|element| {
+            let reference = xml_attribute(element, "ref")?;
+            let (first, last) = reference
+                .split_once(':')
+                .unwrap_or((&reference, &reference));
+            Some(MergedRegion {
+                first: cell_position(first)?,
+                last: cell_position(last)?,
+            })
+        })
+        .collect()
+}
+
+/// Absolute (row, column) from 0 of a reference such as `B12` or `$B$12`
+fn cell_position(reference: &str) -> Option<(u32, u32)> {
+    let reference = reference.replace('$', "");
+    let digits = reference.find(|c: char| c.is_ascii_digit())?;
+    let (letters, row) = reference.split_at(digits);
+    if letters.is_empty() {
+        return None;
+    }
+    let mut column = 0u32;
+    for letter in letters.chars() {
+        if !letter.is_ascii_alphabetic() {
+            return None;
+        }
+        column = column
+            .checked_mul(26)?
+            .checked_add(letter.to_ascii_uppercase() as u32 - 'A' as u32 + 1)?;
+    }
+    let row: u32 = row.parse().ok()?;
+    Some((row.checked_sub(1)?, column - 1))
+}
+
+/// Give every cell of a merged range the value of its top-left cell, within
+/// the used part of the sheet
+pub(super) fn fill_merged_cells(range: &mut Range<Data>, regions: &[MergedRegion]) {
+    let (Some(start), Some(end)) = (range.start(), range.end()) else {
+        return;
+    };
+    for region in regions {
+        let value = match range.get_value(region.first) {
+            Some(value) if *value != Data::Empty => value.clone(),
+            _ => continue,
+        };
+        for row in region.first.0.max(start.0)..=region.last.0.min(end.0) {
+            for column in region.first.1.max(start.1)..=region.last.1.min(end.1) {
+                if (row, column) != region.first {
+                    range.set_value((row, column), value.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Elements named `tag`, from `<tag` up to their closing `>`
+fn xml_elements<'a>(xml: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{}", tag);
+    xml.match_indices(&open)
+        .map(|(index, _)| &xml[index..])
+        .filter(|element| {
+            element[open.len()..]
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_whitespace() || c == '/' || c == '>')
+        })
+        .filter_map(|element| element.find('>').map(|end| &element[..=end]))
+        .collect()
+}
+
+/// Unescaped value of the attribute `name` of an element
+fn xml_attribute(element: &str, name: &str) -> Option<String> {
+    let pattern = format!("{}=\"", name);
+    let start = element
+        .match_indices(&pattern)
+        .find(|(index, _)| {
+            element[..*index]
+                .chars()
+                .next_back()
+                .is_some_and(char::is_whitespace)
+        })
+        .map(|(index, _)| index + pattern.len())?;
+    let end = start + element[start..].find('"')?;
+    Some(
+        element[start..end]
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sheet(name: &str, hidden: bool, rows: usize) -> WorkbookSheet {
+        WorkbookSheet {
+            name: name.to_string(),
+            index: 0,
+            hidden,
+            rows,
+            columns: 3,
+            merged_regions: 0,
+            formula_cells: 0,
+        }
+    }
+
+    #[test]
+    fn sheet_paths_round_trip() {
+        let path = sheet_document_path("/data/Q3 #2.xlsx", "Sales #1");
+        assert_eq!(
+            split_sheet_path(&path),
+            ("/data/Q3 #2.xlsx", Some("Sales #1"))
+        );
+        assert_eq!(split_sheet_path("/data/old.xlsx"), ("/data/old.xlsx", None));
+        assert_eq!(source_file_path(&path), "/data/Q3 #2.xlsx");
+    }
+
+    #[test]
+    fn default_selection_skips_hidden_and_empty_sheets() {
+        let sheets = vec![
+            sheet("Data", false, 10),
+            sheet("Lookup", true, 4),
+            sheet("Notes", false, 0),
+        ];
+        let names = |options: &ExcelImportOptions| {
+            select_sheets(&sheets, options)
+                .map(|picked| picked.iter().map(|s| s.name.clone()).collect::<Vec<_>>())
+        };
+
+        assert_eq!(names(&ExcelImportOptions::default()).unwrap(), ["Data"]);
+        let with_hidden = ExcelImportOptions {
+            include_hidden: true,
+            ..Default::default()
+        };
+        assert_eq!(names(&with_hidden).unwrap(), ["Data", "Lookup"]);
+        let picked = ExcelImportOptions {
+            sheets: vec!["Lookup".to_string(), "Notes".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(names(&picked).unwrap(), ["Lookup", "Notes"]);
+        let unknown = ExcelImportOptions {
+            sheets: vec!["Summary".to_string()],
+            ..Default::default()
+        };
+        assert!(names(&unknown).is_err());
+    }
+
+    #[test]
+    fn merged_cells_take_the_top_left_value() {
+        let xml = r#"<worksheet><mergeCells count="2"><mergeCell ref="A1:B2"/><mergeCell ref="$C$3:C4"/></mergeCells></worksheet>"#;
+        let regions = parse_merged_regions(xml);
+        assert_eq!(
+            regions,
+            [
+                MergedRegion {
+                    first: (0, 0),
+                    last: (1, 1),
+                },
+                MergedRegion {
+                    first: (2, 2),
+                    last: (3, 2),
+                },
+            ]
+        );
+        assert_eq!(cell_position("AB12"), Some((11, 27)));
+        assert_eq!(cell_position("12"), None);
+
+        let mut range = Range::new((0, 0), (3, 2));
+        range.set_value((0, 0), Data::String("Region".to_string()));
+        range.set_value((2, 2), Data::Float(4.5));
+        fill_merged_cells(&mut range, &regions);
+        assert_eq!(
+            range.get_value((1, 1)),
+            Some(&Data::String("Region".to_string()))
+        );
+        assert_eq!(range.get_value((3, 2)), Some(&Data::Float(4.5)));
+        assert_eq!(range.get_value((2, 0)), Some(&Data::Empty));
+    }
+}
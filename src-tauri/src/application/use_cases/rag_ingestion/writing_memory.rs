@@ -0,0 +1,162 @@
+//! Writing memory: accepted translations and enhancements stored one
+//! document each in a dedicated collection, skipping ones already there.
+
+use super::{Logs, ParsedContent, RagIngestionUseCase, MAX_REPORTED_ERRORS};
+use crate::domain::error::{AppError, Result};
+use crate::domain::rag_entities::{
+    CollectionKind, RagCollection, RagCollectionInput, RagDocumentInput,
+};
+use crate::domain::writing_memory::{
+    WritingMemoryEntry, WritingMemorySettings, WritingMemorySyncReport, WRITING_MEMORY_FILE_TYPE,
+};
+use crate::interfaces::http::add_log;
+
+impl RagIngestionUseCase {
+    /// Store one accepted entry; returns false when it was already stored
+    /// or its kind is not kept
+    pub async fn remember_writing(
+        &self,
+        settings: &WritingMemorySettings,
+        entry: &WritingMemoryEntry,
+        logs: &Logs,
+    ) -> Result<bool> {
+        if !settings.accepts(entry.kind) {
+            return Ok(false);
+        }
+        let _guard = self.writing_memory.lock().await;
+        let collection = self.writing_memory_collection(settings).await?;
+        self.store_writing(collection.id, entry, logs).await
+    }
+
+    /// Store the given past entries, e.g. starred history and approved
+    /// translation memory, in the collection
+    pub async fn sync_writing_memory(
+        &self,
+        settings: &WritingMemorySettings,
+        entries: &[WritingMemoryEntry],
+        logs: &Logs,
+    ) -> Result<WritingMemorySyncReport> {
+        if !settings.enabled {
+            return Err(AppError::ValidationError(
+                "Writing memory is turned off in settings".to_string(),
+            ));
+        }
+        let _guard = self.writing_memory.lock().await;
+        let collection = self.writing_memory_collection(settings).await?;
+        let mut report = WritingMemorySyncReport {
+            collection_id: collection.id,
+            ..Default::default()
+        };
+        for entry in entries.iter().filter(|entry| settings.accepts(entry.kind)) {
+            match self.store_writing(collection.id, entry, logs).await {
+                Ok(true) => report.added += 1,
+                Ok(false) => report.skipped += 1,
+                Err(err) => {
+                    report.failed += 1;
+                    if report.errors.len() < MAX_REPORTED_ERRORS {
+                        report.errors.push(format!("{}: {}", entry.title(), err));
+                    }
+                }
+            }
+        }
+        add_log(
+            logs,
+            "INFO",
+            "RAG",
+            &format!(
+                "Writing memory sync into '{}': {} added, {} already stored, {} failed",
+                collection.name, report.added, report.skipped, report.failed
+            ),
+        );
+        Ok(report)
+    }
+
+    /// The configured collection, created when missing. A collection of that
+    /// name holding other documents is not reused.
+    async fn writing_memory_collection(
+        &self,
+        settings: &WritingMemorySettings,
+    ) -> Result<RagCollection> {
+        let name = settings.collection_name();
+        let Some(collection) = self.rag_repository.find_collection_by_name(name).await? else {
+            return self
+                .rag_repository
+                .create_collection(&RagCollectionInput {
+                    name: name.to_string(),
+                    description: Some(
+                        "Accepted translations and enhancements, added automatically".to_string(),
+                    ),
+                })
+                .await;
+        };
+
+        if collection.kind == CollectionKind::Db {
+            return Err(AppError::ValidationError(format!(
+                "Collection '{}' is a Database Collection and can't hold writing memory",
+                collection.name
+            )));
+        }
+        let existing_type = self
+            .rag_repository
+            .get_collection_document_type(collection.id)
+            .await?;
+        if let Some(existing_type) = existing_type {
+            if existing_type != WRITING_MEMORY_FILE_TYPE {
+                return Err(AppError::ValidationError(format!(
+                    "Collection '{}' already contains {} documents. Choose another writing memory collection name.",
+                    collection.name, existing_type
+                )));
+            }
+        }
+        Ok(collection)
+    }
+
+    async fn store_writing(
+        &self,
+        collection_id: i64,
+        entry: &WritingMemoryEntry,
+        logs: &Logs,
+    ) -> Result<bool> {
+        let path = entry.document_path();
+        if self
+            .rag_repository
+            .find_document_by_path(collection_id, &path)
+            .await?
+            .is_some()
+        {
+            return Ok(false);
+        }
+
+        let title = entry.title();
+        let document = self
+            .rag_repository
+            .create_document(&RagDocumentInput {
+                collection_id: Some(collection_id),
+                file_name: title.clone(),
+                file_path: Some(path),
+                file_type: WRITING_MEMORY_FILE_TYPE.to_string(),
+                language: Some("auto".to_string()),
+                total_pages: Some(1),
+            })
+            .await?;
+
+        let stored = async {
+            self.rag_repository
+                .set_document_meta(document.id, &entry.meta_json())
+                .await?;
+            self.store_chunks_for_document(
+                &document,
+                ParsedContent::Plain(Some(entry.document_text())),
+                &title,
+                WRITING_MEMORY_FILE_TYPE,
+                logs,
+            )
+            .await
+        };
+        if let Err(err) = stored.await {
+            let _ = self.rag_repository.delete_document(document.id).await;
+            return Err(err);
+        }
+        Ok(true)
+    }
+}
@@ -0,0 +1,194 @@
+//! Pruning advisor: suggests documents to archive because retrieval never
+//! or no longer returns them.
+//!
+//! Usage is only known from the first recorded retrieval in a collection, so
+//! a document is judged on the time since it was imported or since tracking
+//! began, whichever is later. Documents without text chunks (structured
+//! imports) are not tracked and never suggested.
+
+use crate::infrastructure::db::rag::repository::{CollectionChunkUsage, DocumentUsage};
+use serde::{Deserialize, Serialize};
+
+const SECS_PER_DAY: i64 = 24 * 3600;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PruningOptions {
+    /// Days a document must have been observed without any retrieval
+    pub min_unused_days: i64,
+    /// Days since the last retrieval after which a document counts as stale
+    pub stale_days: i64,
+}
+
+impl Default for PruningOptions {
+    fn default() -> Self {
+        Self {
+            min_unused_days: 30,
+            stale_days: 90,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PruningReason {
+    NeverRetrieved,
+    NotRetrievedRecently,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PruningSuggestion {
+    pub doc_id: i64,
+    pub file_name: String,
+    pub chunk_count: i64,
+    pub reason: PruningReason,
+    pub days_unused: i64,
+    pub last_retrieved_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PruningReport {
+    pub collection_id: i64,
+    pub total_documents: usize,
+    /// First recorded retrieval, in unix seconds; `None` means no usage data yet
+    pub tracking_since: Option<i64>,
+    pub suggestions: Vec<PruningSuggestion>,
+    /// Chunks that archiving every suggested document would remove
+    pub reclaimable_chunks: i64,
+}
+
+pub fn build_pruning_report(
+    collection_id: i64,
+    usage: &CollectionChunkUsage,
+    documents: &[DocumentUsage],
+    options: &PruningOptions,
+    now: i64,
+) -> PruningReport {
+    let mut suggestions: Vec<PruningSuggestion> = match usage.tracking_since {
+        Some(tracking_since) => documents
+            .iter()
+            .filter(|doc| doc.chunk_count > 0)
+            .filter_map(|doc| suggest(doc, tracking_since, options, now))
+            .collect(),
+        None => Vec::new(),
+    };
+    suggestions.sort_by_key(|s| std::cmp::Reverse(s.days_unused));
+
+    PruningReport {
+        collection_id,
+        total_documents: documents.len(),
+        tracking_since: usage.tracking_since,
+        reclaimable_chunks: suggestions.iter().map(|s| s.chunk_count).sum(),
+        suggestions,
+    }
+}
+
+fn suggest(
+    doc: &DocumentUsage,
+    tracking_since: i64,
+    options: &PruningOptions,
+    now: i64,
+) -> Option<PruningSuggestion> {
+    let (reason, days_unused) = match doc.last_retrieved_at {
+        None => {
+            let observed_from = doc.created_at.max(tracking_since);
+            let days = (now - observed_from) / SECS_PER_DAY;
+            (days >= options.min_unused_days).then_some((PruningReason::NeverRetrieved, days))?
+        }
+        Some(last_retrieved_at) => {
+            let days = (now - last_retrieved_at) / SECS_PER_DAY;
+            (days >= options.stale_days).then_some((PruningReason::NotRetrievedRecently, days))?
+        }
+    };
+
+    Some(PruningSuggestion {
+        doc_id: doc.doc_id,
+        file_name: doc.file_name.clone(),
+        chunk_count: doc.chunk_count,
+        reason,
+        days_unused,
+        last_retrieved_at: doc.last_retrieved_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(
+        doc_id: i64,
+        created_days_ago: i64,
+        last_retrieved_days_ago: Option<i64>,
+    ) -> DocumentUsage {
+        let now = 1_000 * SECS_PER_DAY;
+        DocumentUsage {
+            doc_id,
+            file_name: format!("doc-{}.pdf", doc_id),
+            created_at: now - created_days_ago * SECS_PER_DAY,
+            chunk_count: 4,
+            retrieved_chunks: last_retrieved_days_ago.map_or(0, |_| 1),
+            retrieval_count: last_retrieved_days_ago.map_or(0, |_| 3),
+            citation_count: 0,
+            last_retrieved_at: last_retrieved_days_ago.map(|days| now - days * SECS_PER_DAY),
+            last_cited_at: None,
+        }
+    }
+
+    fn usage(tracking_days_ago: Option<i64>) -> CollectionChunkUsage {
+        CollectionChunkUsage {
+            total_chunks: 16,
+            retrieved_chunks: 2,
+            cited_chunks: 0,
+            total_retrievals: 6,
+            total_citations: 0,
+            tracking_since: tracking_days_ago.map(|days| (1_000 - days) * SECS_PER_DAY),
+        }
+    }
+
+    #[test]
+    fn test_suggests_unused_and_stale_documents() {
+        let now = 1_000 * SECS_PER_DAY;
+        let documents = vec![
+            document(1, 200, None),      // never retrieved in 60 tracked days
+            document(2, 200, Some(5)),   // in use
+            document(3, 200, Some(120)), // stale
+            document(4, 10, None),       // too new to judge
+        ];
+
+        let report = build_pruning_report(
+            7,
+            &usage(Some(60)),
+            &documents,
+            &PruningOptions::default(),
+            now,
+        );
+
+        let suggested: Vec<(i64, PruningReason)> = report
+            .suggestions
+            .iter()
+            .map(|s| (s.doc_id, s.reason))
+            .collect();
+        assert_eq!(
+            suggested,
+            vec![
+                (3, PruningReason::NotRetrievedRecently),
+                (1, PruningReason::NeverRetrieved),
+            ]
+        );
+        assert_eq!(report.suggestions[1].days_unused, 60);
+        assert_eq!(report.reclaimable_chunks, 8);
+    }
+
+    #[test]
+    fn test_no_suggestions_without_usage_data() {
+        let documents = vec![document(1, 400, None)];
+        let report = build_pruning_report(
+            7,
+            &usage(None),
+            &documents,
+            &PruningOptions::default(),
+            1_000 * SECS_PER_DAY,
+        );
+        assert!(report.suggestions.is_empty());
+    }
+}
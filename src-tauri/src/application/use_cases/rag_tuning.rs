@@ -0,0 +1,645 @@
+//! Retrieval tuning: correlates rated answers with the retrieval parameters
+//! they were produced with and proposes parameter changes.
+//!
+//! A proposal is tried as an experiment: queries are split between the
+//! current parameters (baseline) and the proposed ones (candidate) by a hash
+//! of the query text, so a repeated question always gets the same arm. The
+//! experiment is promoted when the candidate collects clearly fewer negative
+//! ratings and rolled back when it collects clearly more.
+//!
+//! Collections whose documents scored low on ingestion quality are left out
+//! of the parameter comparison: their answers are poor whatever the
+//! parameters are, and they are reported for re-ingestion instead.
+
+use crate::application::use_cases::rag_config::RetrievalConfig;
+use crate::domain::error::{AppError, Result};
+use crate::infrastructure::db::rag::repository::{
+    RetrievalExperimentRecord, RetrievalFeedbackRecord,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+
+/// Collections whose average document quality is below this are reported
+/// instead of tuned for
+const LOW_COLLECTION_QUALITY: f64 = 0.5;
+
+/// Negative rate above which untried values are proposed to explore
+const HIGH_NEGATIVE_RATE: f32 = 0.4;
+
+const MAX_TUNED_TOP_K: usize = 20;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct TuningOptions {
+    /// Ratings a parameter value or experiment arm needs before it is judged
+    pub min_samples: usize,
+    /// Difference in negative rate that counts as better or worse
+    pub min_improvement: f32,
+}
+
+impl Default for TuningOptions {
+    fn default() -> Self {
+        Self {
+            min_samples: 10,
+            min_improvement: 0.1,
+        }
+    }
+}
+
+/// The retrieval parameters the tuning job adjusts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetrievalParams {
+    pub top_k: usize,
+    pub vector_weight: f32,
+    pub keyword_weight: f32,
+    pub reranking_enabled: bool,
+}
+
+impl RetrievalParams {
+    pub fn from_config(config: &RetrievalConfig) -> Self {
+        Self {
+            top_k: config.top_k,
+            vector_weight: config.vector_weight,
+            keyword_weight: config.keyword_weight,
+            reranking_enabled: config.reranking_enabled,
+        }
+    }
+
+    pub fn apply(&self, config: &mut RetrievalConfig) {
+        config.top_k = self.top_k;
+        config.vector_weight = self.vector_weight;
+        config.keyword_weight = self.keyword_weight;
+        config.reranking_enabled = self.reranking_enabled;
+    }
+
+    fn from_record(record: &RetrievalFeedbackRecord) -> Self {
+        Self {
+            top_k: record.top_k.max(0) as usize,
+            vector_weight: record.vector_weight as f32,
+            keyword_weight: record.keyword_weight as f32,
+            reranking_enabled: record.reranking_enabled,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExperimentArm {
+    Baseline,
+    Candidate,
+}
+
+impl ExperimentArm {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExperimentArm::Baseline => "baseline",
+            ExperimentArm::Candidate => "candidate",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "baseline" => Some(ExperimentArm::Baseline),
+            "candidate" => Some(ExperimentArm::Candidate),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExperimentStatus {
+    Running,
+    Promoted,
+    RolledBack,
+    Stopped,
+}
+
+impl ExperimentStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExperimentStatus::Running => "running",
+            ExperimentStatus::Promoted => "promoted",
+            ExperimentStatus::RolledBack => "rolled_back",
+            ExperimentStatus::Stopped => "stopped",
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "running" => ExperimentStatus::Running,
+            "promoted" => ExperimentStatus::Promoted,
+            "rolled_back" => ExperimentStatus::RolledBack,
+            _ => ExperimentStatus::Stopped,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RetrievalExperiment {
+    pub id: i64,
+    pub status: ExperimentStatus,
+    /// Parameters when the experiment started
+    pub baseline: RetrievalParams,
+    pub candidate: RetrievalParams,
+    pub rationale: String,
+    /// Unix seconds
+    pub created_at: i64,
+    pub ended_at: Option<i64>,
+}
+
+impl RetrievalExperiment {
+    pub fn from_record(record: RetrievalExperimentRecord) -> Result<Self> {
+        let parse = |json: &str| {
+            serde_json::from_str::<RetrievalParams>(json).map_err(|e| {
+                AppError::ParseError(format!(
+                    "Invalid parameters in retrieval experiment {}: {}",
+                    record.id, e
+                ))
+            })
+        };
+        Ok(Self {
+            id: record.id,
+            status: ExperimentStatus::parse(&record.status),
+            baseline: parse(&record.baseline_json)?,
+            candidate: parse(&record.candidate_json)?,
+            rationale: record.rationale.clone().unwrap_or_default(),
+            created_at: record.created_at,
+            ended_at: record.ended_at,
+        })
+    }
+
+    /// Arm a query runs on; stable for the same question
+    pub fn arm_for(&self, query_text: &str) -> ExperimentArm {
+        let mut hasher = DefaultHasher::new();
+        self.id.hash(&mut hasher);
+        query_text.trim().to_lowercase().hash(&mut hasher);
+        if hasher.finish().is_multiple_of(2) {
+            ExperimentArm::Baseline
+        } else {
+            ExperimentArm::Candidate
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FeedbackCounts {
+    pub total: usize,
+    pub negative: usize,
+    pub negative_rate: f32,
+}
+
+impl FeedbackCounts {
+    fn add(&mut self, negative: bool) {
+        self.total += 1;
+        if negative {
+            self.negative += 1;
+        }
+        self.negative_rate = self.negative as f32 / self.total as f32;
+    }
+}
+
+/// Ratings received with one value of one parameter
+#[derive(Debug, Clone, Serialize)]
+pub struct ParameterBucket {
+    pub parameter: TunedParameter,
+    pub value: String,
+    pub is_current: bool,
+    #[serde(flatten)]
+    pub counts: FeedbackCounts,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TunedParameter {
+    TopK,
+    /// `vector_weight` and `keyword_weight`, which move together
+    FusionWeights,
+    Reranking,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectionFeedback {
+    pub collection_id: i64,
+    /// Latest average document quality, when computed
+    pub quality: Option<f64>,
+    /// Quality is too low for parameter changes to help
+    pub needs_reingest: bool,
+    #[serde(flatten)]
+    pub counts: FeedbackCounts,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ParameterChange {
+    pub parameter: TunedParameter,
+    pub from: String,
+    pub to: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TuningProposal {
+    pub candidate: RetrievalParams,
+    pub changes: Vec<ParameterChange>,
+}
+
+impl TuningProposal {
+    pub fn rationale(&self) -> String {
+        self.changes
+            .iter()
+            .map(|change| change.reason.as_str())
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExperimentVerdict {
+    /// Not enough ratings on both arms yet
+    Collecting,
+    Promote,
+    RollBack,
+    /// Both arms rated alike so far
+    NoDifference,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExperimentSummary {
+    pub experiment: RetrievalExperiment,
+    pub baseline: FeedbackCounts,
+    pub candidate: FeedbackCounts,
+    pub verdict: ExperimentVerdict,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TuningReport {
+    pub current: RetrievalParams,
+    #[serde(flatten)]
+    pub overall: FeedbackCounts,
+    pub buckets: Vec<ParameterBucket>,
+    pub collections: Vec<CollectionFeedback>,
+    pub proposal: Option<TuningProposal>,
+    pub experiment: Option<ExperimentSummary>,
+}
+
+pub fn build_tuning_report(
+    feedback: &[RetrievalFeedbackRecord],
+    current: &RetrievalParams,
+    experiment: Option<RetrievalExperiment>,
+    options: &TuningOptions,
+) -> TuningReport {
+    let mut overall = FeedbackCounts::default();
+    let mut collections: BTreeMap<i64, CollectionFeedback> = BTreeMap::new();
+    for record in feedback {
+        let negative = is_negative(record);
+        overall.add(negative);
+        if let Some(collection_id) = record.collection_id {
+            let entry = collections
+                .entry(collection_id)
+                .or_insert_with(|| CollectionFeedback {
+                    collection_id,
+                    quality: None,
+                    needs_reingest: false,
+                    counts: FeedbackCounts::default(),
+                });
+            entry.counts.add(negative);
+            // Records are newest first
+            entry.quality = entry.quality.or(record.collection_quality);
+        }
+    }
+    for collection in collections.values_mut() {
+        collection.needs_reingest = collection
+            .quality
+            .is_some_and(|quality| quality < LOW_COLLECTION_QUALITY)
+            && collection.counts.total >= options.min_samples
+            && collection.counts.negative_rate >= HIGH_NEGATIVE_RATE;
+    }
+
+    let tunable: Vec<&RetrievalFeedbackRecord> = feedback
+        .iter()
+        .filter(|record| {
+            !record
+                .collection_id
+                .and_then(|id| collections.get(&id))
+                .is_some_and(|collection| collection.needs_reingest)
+        })
+        .collect();
+    let buckets = parameter_buckets(&tunable, current);
+    let proposal = propose(&buckets, current, &tunable, options);
+    let experiment =
+        experiment.map(|experiment| summarize_experiment(feedback, experiment, options));
+
+    let mut collections: Vec<CollectionFeedback> = collections.into_values().collect();
+    collections.sort_by(|a, b| b.counts.negative_rate.total_cmp(&a.counts.negative_rate));
+
+    TuningReport {
+        current: current.clone(),
+        overall,
+        buckets,
+        collections,
+        proposal,
+        experiment,
+    }
+}
+
+/// Compare the two arms of an experiment on the ratings given to it
+pub fn summarize_experiment(
+    feedback: &[RetrievalFeedbackRecord],
+    experiment: RetrievalExperiment,
+    options: &TuningOptions,
+) -> ExperimentSummary {
+    let mut baseline = FeedbackCounts::default();
+    let mut candidate = FeedbackCounts::default();
+    for record in feedback
+        .iter()
+        .filter(|record| record.experiment_id == Some(experiment.id))
+    {
+        match record
+            .experiment_arm
+            .as_deref()
+            .and_then(ExperimentArm::parse)
+        {
+            Some(ExperimentArm::Baseline) => baseline.add(is_negative(record)),
+            Some(ExperimentArm::Candidate) => candidate.add(is_negative(record)),
+            None => {}
+        }
+    }
+
+    let verdict = if baseline.total < options.min_samples || candidate.total < options.min_samples {
+        ExperimentVerdict::Collecting
+    } else if candidate.negative_rate + options.min_improvement <= baseline.negative_rate {
+        ExperimentVerdict::Promote
+    } else if candidate.negative_rate >= baseline.negative_rate + options.min_improvement {
+        ExperimentVerdict::RollBack
+    } else {
+        ExperimentVerdict::NoDifference
+    };
+
+    ExperimentSummary {
+        experiment,
+        baseline,
+        candidate,
+        verdict,
+    }
+}
+
+fn is_negative(record: &RetrievalFeedbackRecord) -> bool {
+    record.rating == "negative"
+}
+
+fn parameter_value(parameter: TunedParameter, params: &RetrievalParams) -> String {
+    match parameter {
+        TunedParameter::TopK => params.top_k.to_string(),
+        TunedParameter::FusionWeights => {
+            format!("{:.2}/{:.2}", params.vector_weight, params.keyword_weight)
+        }
+        TunedParameter::Reranking => params.reranking_enabled.to_string(),
+    }
+}
+
+fn parameter_buckets(
+    feedback: &[&RetrievalFeedbackRecord],
+    current: &RetrievalParams,
+) -> Vec<ParameterBucket> {
+    let mut counts: BTreeMap<(TunedParameter, String), FeedbackCounts> = BTreeMap::new();
+    for record in feedback {
+        let params = RetrievalParams::from_record(record);
+        for parameter in [
+            TunedParameter::TopK,
+            TunedParameter::FusionWeights,
+            TunedParameter::Reranking,
+        ] {
+            counts
+                .entry((parameter, parameter_value(parameter, &params)))
+                .or_default()
+                .add(is_negative(record));
+        }
+    }
+    counts
+        .into_iter()
+        .map(|((parameter, value), counts)| ParameterBucket {
+            parameter,
+            is_current: value == parameter_value(parameter, current),
+            value,
+            counts,
+        })
+        .collect()
+}
+
+/// Move each parameter to the value rated clearly better than the current
+/// one. When answers are often rated negative and nothing better has been
+/// seen, try an untested value instead.
+fn propose(
+    buckets: &[ParameterBucket],
+    current: &RetrievalParams,
+    feedback: &[&RetrievalFeedbackRecord],
+    options: &TuningOptions,
+) -> Option<TuningProposal> {
+    let mut candidate = current.clone();
+    let mut changes = Vec::new();
+
+    for parameter in [
+        TunedParameter::TopK,
+        TunedParameter::FusionWeights,
+        TunedParameter::Reranking,
+    ] {
+        let judged: Vec<&ParameterBucket> = buckets
+            .iter()
+            .filter(|bucket| bucket.parameter == parameter)
+            .filter(|bucket| bucket.counts.total >= options.min_samples)
+            .collect();
+        let Some(current_bucket) = judged.iter().find(|bucket| bucket.is_current) else {
+            continue;
+        };
+        let best = judged
+            .iter()
+            .filter(|bucket| !bucket.is_current)
+            .min_by(|a, b| a.counts.negative_rate.total_cmp(&b.counts.negative_rate));
+
+        if let Some(best) = best.filter(|best| {
+            best.counts.negative_rate + options.min_improvement
+                <= current_bucket.counts.negative_rate
+        }) {
+            let Some(params) = feedback
+                .iter()
+                .map(|record| RetrievalParams::from_record(record))
+                .find(|params| parameter_value(parameter, params) == best.value)
+            else {
+                continue;
+            };
+            match parameter {
+                TunedParameter::TopK => candidate.top_k = params.top_k,
+                TunedParameter::FusionWeights => {
+                    candidate.vector_weight = params.vector_weight;
+                    candidate.keyword_weight = params.keyword_weight;
+                }
+                TunedParameter::Reranking => candidate.reranking_enabled = params.reranking_enabled,
+            }
+            changes.push(ParameterChange {
+                parameter,
+                from: current_bucket.value.clone(),
+                to: best.value.clone(),
+                reason: format!(
+                    "{:.0}% negative with {} against {:.0}% now",
+                    best.counts.negative_rate * 100.0,
+                    best.value,
+                    current_bucket.counts.negative_rate * 100.0
+                ),
+            });
+            continue;
+        }
+
+        if current_bucket.counts.negative_rate < HIGH_NEGATIVE_RATE {
+            continue;
+        }
+        let tested = |value: &str| judged.iter().any(|bucket| bucket.value == value);
+        let explored = match parameter {
+            TunedParameter::Reranking if !current.reranking_enabled => {
+                candidate.reranking_enabled = true;
+                Some("reranking has not been tried")
+            }
+            TunedParameter::TopK if current.top_k < MAX_TUNED_TOP_K => {
+                let more = (current.top_k + 2).min(MAX_TUNED_TOP_K);
+                let mut params = current.clone();
+                params.top_k = more;
+                if tested(&parameter_value(parameter, &params)) {
+                    None
+                } else {
+                    candidate.top_k = more;
+                    Some("more context has not been tried")
+                }
+            }
+            _ => None,
+        };
+        if let Some(reason) = explored {
+            changes.push(ParameterChange {
+                parameter,
+                from: current_bucket.value.clone(),
+                to: parameter_value(parameter, &candidate),
+                reason: format!(
+                    "{:.0}% of answers rated negative and {}",
+                    current_bucket.counts.negative_rate * 100.0,
+                    reason
+                ),
+            });
+        }
+    }
+
+    (!changes.is_empty()).then_some(TuningProposal { candidate, changes })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(top_k: usize, reranking_enabled: bool) -> RetrievalParams {
+        RetrievalParams {
+            top_k,
+            vector_weight: 0.7,
+            keyword_weight: 0.3,
+            reranking_enabled,
+        }
+    }
+
+    fn rated(
+        params: &RetrievalParams,
+        collection_id: i64,
+        quality: f64,
+        negatives: usize,
+        total: usize,
+    ) -> Vec<RetrievalFeedbackRecord> {
+        (0..total)
+            .map(|i| RetrievalFeedbackRecord {
+                collection_id: Some(collection_id),
+                rating: if i < negatives {
+                    "negative"
+                } else {
+                    "positive"
+                }
+                .to_string(),
+                top_k: params.top_k as i64,
+                vector_weight: params.vector_weight as f64,
+                keyword_weight: params.keyword_weight as f64,
+                reranking_enabled: params.reranking_enabled,
+                collection_quality: Some(quality),
+                experiment_id: None,
+                experiment_arm: None,
+                created_at: 0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_proposes_the_better_rated_value() {
+        let current = params(5, true);
+        let mut feedback = rated(&current, 1, 0.9, 6, 12);
+        feedback.extend(rated(&params(8, true), 1, 0.9, 1, 12));
+
+        let report = build_tuning_report(&feedback, &current, None, &TuningOptions::default());
+        let proposal = report.proposal.unwrap();
+
+        assert_eq!(proposal.candidate, params(8, true));
+        assert_eq!(proposal.changes.len(), 1);
+        assert_eq!(proposal.changes[0].parameter, TunedParameter::TopK);
+    }
+
+    #[test]
+    fn test_explores_untried_values_when_ratings_are_poor() {
+        let current = params(5, false);
+        let feedback = rated(&current, 1, 0.9, 8, 12);
+
+        let report = build_tuning_report(&feedback, &current, None, &TuningOptions::default());
+
+        assert_eq!(report.proposal.unwrap().candidate, params(7, true));
+    }
+
+    #[test]
+    fn test_low_quality_collections_are_not_tuned_for() {
+        let current = params(5, true);
+        let feedback = rated(&current, 3, 0.2, 10, 12);
+
+        let report = build_tuning_report(&feedback, &current, None, &TuningOptions::default());
+
+        assert!(report.proposal.is_none());
+        assert!(report.collections[0].needs_reingest);
+    }
+
+    #[test]
+    fn test_experiment_verdict() {
+        let experiment = RetrievalExperiment {
+            id: 4,
+            status: ExperimentStatus::Running,
+            baseline: params(5, true),
+            candidate: params(8, true),
+            rationale: String::new(),
+            created_at: 0,
+            ended_at: None,
+        };
+        let arm = |arm: ExperimentArm, negatives, total| {
+            let mut records = rated(&params(5, true), 1, 0.9, negatives, total);
+            for record in &mut records {
+                record.experiment_id = Some(4);
+                record.experiment_arm = Some(arm.as_str().to_string());
+            }
+            records
+        };
+        let options = TuningOptions::default();
+
+        let mut feedback = arm(ExperimentArm::Baseline, 5, 10);
+        feedback.extend(arm(ExperimentArm::Candidate, 1, 10));
+        let summary = summarize_experiment(&feedback, experiment.clone(), &options);
+        assert_eq!(summary.verdict, ExperimentVerdict::Promote);
+
+        let feedback = arm(ExperimentArm::Baseline, 5, 10);
+        let summary = summarize_experiment(&feedback, experiment.clone(), &options);
+        assert_eq!(summary.verdict, ExperimentVerdict::Collecting);
+
+        assert_eq!(
+            experiment.arm_for("What is the refund policy?"),
+            experiment.arm_for("  what is the refund policy?")
+        );
+    }
+}
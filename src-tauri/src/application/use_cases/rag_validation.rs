@@ -1,5 +1,6 @@
 use crate::application::use_cases::rag_ingestion::RagIngestionUseCase;
 use crate::application::use_cases::retrieval_service::{QueryResult, RetrievalService};
+use crate::domain::embedding_quantization::EmbeddingQuantization;
 use crate::domain::error::Result;
 use serde::{Deserialize, Serialize};
 use std::time::Instant;
@@ -18,6 +19,10 @@ pub struct ValidationOptions {
     pub top_k: usize,
     pub use_cache: bool,
     pub optimized: bool,
+    /// Also measure how much of each vector top-k survives storing the
+    /// embeddings at this precision
+    #[serde(default)]
+    pub quantization: Option<EmbeddingQuantization>,
 }
 
 impl Default for ValidationOptions {
@@ -26,6 +31,7 @@ impl Default for ValidationOptions {
             top_k: 5,
             use_cache: true,
             optimized: true,
+            quantization: None,
         }
     }
 }
@@ -40,6 +46,8 @@ pub struct ValidationResult {
     pub extraction_accuracy: f32,
     pub latency_ms: u64,
     pub cache_hit: bool,
+    /// Share of the vector top-k kept at `ValidationOptions::quantization`
+    pub quantization_recall: Option<f32>,
     pub issues: Vec<String>,
 }
 
@@ -51,9 +59,15 @@ pub struct ValidationReport {
     pub avg_chunking_quality: f32,
     pub avg_extraction_accuracy: f32,
     pub avg_latency_ms: f32,
+    pub quantization: Option<EmbeddingQuantization>,
+    /// Over the cases a recall could be measured for
+    pub avg_quantization_recall: Option<f32>,
     pub results: Vec<ValidationResult>,
 }
 
+/// Quantization recall below this is flagged on the case
+const MIN_QUANTIZATION_RECALL: f32 = 0.9;
+
 pub struct RagValidationSuite;
 
 impl RagValidationSuite {
@@ -108,6 +122,18 @@ impl RagValidationSuite {
                 chunking_quality
             };
 
+            let quantization_recall = match options.quantization {
+                Some(quantization) => {
+                    retrieval_service
+                        .quantization_recall(case.collection_id, &case.query, top_k, quantization)
+                        .await?
+                }
+                None => None,
+            };
+            if quantization_recall.is_some_and(|recall| recall < MIN_QUANTIZATION_RECALL) {
+                issues.push("quantization_recall_drop".to_string());
+            }
+
             let result = ValidationResult {
                 query: case.query.clone(),
                 result_count: retrieved.len(),
@@ -117,6 +143,7 @@ impl RagValidationSuite {
                 extraction_accuracy,
                 latency_ms,
                 cache_hit,
+                quantization_recall,
                 issues,
             };
 
@@ -124,7 +151,7 @@ impl RagValidationSuite {
             results.push(result);
         }
 
-        Ok(totals.build_report(results))
+        Ok(totals.build_report(results, options.quantization))
     }
 
     fn score_expected_keywords(
@@ -235,6 +262,8 @@ struct Totals {
     sum_chunking_quality: f32,
     sum_extraction_accuracy: f32,
     sum_latency_ms: f32,
+    recall_count: usize,
+    sum_quantization_recall: f32,
 }
 
 impl Totals {
@@ -245,9 +274,17 @@ impl Totals {
         self.sum_chunking_quality += result.chunking_quality;
         self.sum_extraction_accuracy += result.extraction_accuracy;
         self.sum_latency_ms += result.latency_ms as f32;
+        if let Some(recall) = result.quantization_recall {
+            self.recall_count += 1;
+            self.sum_quantization_recall += recall;
+        }
     }
 
-    fn build_report(self, results: Vec<ValidationResult>) -> ValidationReport {
+    fn build_report(
+        self,
+        results: Vec<ValidationResult>,
+        quantization: Option<EmbeddingQuantization>,
+    ) -> ValidationReport {
         let count = self.count.max(1) as f32;
         ValidationReport {
             total_cases: results.len(),
@@ -256,6 +293,12 @@ impl Totals {
             avg_chunking_quality: self.sum_chunking_quality / count,
             avg_extraction_accuracy: self.sum_extraction_accuracy / count,
             avg_latency_ms: self.sum_latency_ms / count,
+            quantization,
+            avg_quantization_recall: if self.recall_count > 0 {
+                Some(self.sum_quantization_recall / self.recall_count as f32)
+            } else {
+                None
+            },
             results,
         }
     }
@@ -1,4 +1,7 @@
-use super::{NumericQuery, QueryAnalysis, QueryType, RetrievalService, StructuredQueryHints};
+use super::{
+    NumericQuery, QueryAnalysis, QueryRoute, QueryType, RetrievalService, RoutingSignals,
+    RoutingThresholds, StructuredQueryHints,
+};
 use sha2::{Digest, Sha256};
 
 impl RetrievalService {
@@ -11,13 +14,25 @@ impl RetrievalService {
     }
 
     pub(super) fn analyze_query(&self, query: &str) -> QueryAnalysis {
+        self.analyze_query_with(query, &RoutingThresholds::default())
+    }
+
+    pub(super) fn analyze_query_with(
+        &self,
+        query: &str,
+        thresholds: &RoutingThresholds,
+    ) -> QueryAnalysis {
         let lowercase_query = query.to_lowercase();
         let mut numeric_queries = Vec::new();
         let mut has_numeric = false;
         let mut has_text = false;
 
         // Aggregate/list/count queries should be routed to structured_rows.
-        let structured = self.analyze_structured_query(&lowercase_query);
+        let (structured, structured_signals) = self.analyze_structured_query(&lowercase_query);
+        let signals = RoutingSignals {
+            structured: structured_signals,
+            terms: query.split_whitespace().count(),
+        };
 
         let keywords = ["val_a", "val_b", "column", "field", "value", "numeric"];
         for keyword in &keywords {
@@ -51,7 +66,8 @@ impl RetrievalService {
             has_text = true;
         }
 
-        let query_type = if structured.wants_aggregate {
+        let text_route = thresholds.route(&signals);
+        let query_type = if text_route == QueryRoute::Structured {
             QueryType::Structured
         } else if has_numeric && has_text {
             QueryType::Hybrid
@@ -61,14 +77,23 @@ impl RetrievalService {
             QueryType::TextOnly
         };
 
+        let route = match query_type {
+            QueryType::Structured | QueryType::TextOnly => Some(text_route),
+            QueryType::NumericOnly | QueryType::Hybrid => None,
+        };
+
         QueryAnalysis {
             query_type,
+            route,
             numeric_queries,
             structured,
+            signals,
         }
     }
 
-    fn analyze_structured_query(&self, lowercase_query: &str) -> StructuredQueryHints {
+    /// Structured hints and how many structured-intent signals the query
+    /// has; the hints are empty without signals
+    fn analyze_structured_query(&self, lowercase_query: &str) -> (StructuredQueryHints, u32) {
         // Heuristic intent router based on keywords (matches IMPLEMENTATION_PROGRESS_V2.md).
         let agg_keywords = [
             "all",
@@ -106,9 +131,7 @@ impl RetrievalService {
             .split(|c: char| !c.is_ascii_alphanumeric())
             .any(|t| t == "ai");
 
-        let initial_wants_aggregate = agg_keywords.iter().any(|k| lowercase_query.contains(k))
-            || has_explicit_filters
-            || (has_structured_markers && has_ai_token);
+        let has_agg_keyword = agg_keywords.iter().any(|k| lowercase_query.contains(k));
 
         let wants_count = ["count", "total", "berapa"]
             .iter()
@@ -209,17 +232,25 @@ impl RetrievalService {
         }
 
         // If we extracted any structured filters, treat it as structured intent even if the user
-        // didn't use explicit aggregate keywords.
-        let wants_aggregate = initial_wants_aggregate
-            || category.is_some()
-            || source.is_some()
-            || keyword.is_some()
-            || wants_count
-            || wants_sources
-            || wants_titles;
+        // didn't use explicit aggregate keywords. Each marker counts as one signal.
+        let signals = [
+            has_agg_keyword,
+            has_explicit_filters,
+            has_structured_markers && has_ai_token,
+            category.is_some(),
+            source.is_some(),
+            keyword.is_some(),
+            wants_count,
+            wants_sources,
+            wants_titles,
+        ]
+        .iter()
+        .filter(|signal| **signal)
+        .count() as u32;
+        let wants_aggregate = signals > 0;
 
         if !wants_aggregate {
-            return StructuredQueryHints::empty();
+            return (StructuredQueryHints::empty(), 0);
         }
 
         // Default for aggregate queries: if user didn't ask for count/sources/titles explicitly,
@@ -228,7 +259,7 @@ impl RetrievalService {
             wants_titles = true;
         }
 
-        StructuredQueryHints {
+        let hints = StructuredQueryHints {
             wants_aggregate,
             wants_count,
             wants_sources,
@@ -237,6 +268,7 @@ impl RetrievalService {
             source,
             keyword,
             requested_limit,
-        }
+        };
+        (hints, signals)
     }
 }
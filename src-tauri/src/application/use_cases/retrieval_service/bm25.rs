@@ -1,3 +1,4 @@
+use super::TextAnalyzer;
 use std::collections::{HashMap, HashSet};
 
 /// BM25 scoring parameters
@@ -5,7 +6,9 @@ const BM25_K1: f32 = 1.2; // Term frequency saturation
 const BM25_B: f32 = 0.75; // Length normalization
 
 /// Simple BM25 scorer for keyword-based retrieval
-pub struct Bm25Scorer {
+pub struct Bm25Scorer<'a> {
+    /// Turns documents and queries into terms
+    analyzer: &'a TextAnalyzer,
     /// Document frequencies: term -> number of documents containing term
     doc_frequencies: HashMap<String, usize>,
     /// Total number of documents
@@ -14,14 +17,14 @@ pub struct Bm25Scorer {
     avg_doc_len: f32,
 }
 
-impl Bm25Scorer {
+impl<'a> Bm25Scorer<'a> {
     /// Build a BM25 scorer from a collection of documents
-    pub fn from_documents(documents: &[&str]) -> Self {
+    pub fn from_documents(documents: &[&str], analyzer: &'a TextAnalyzer) -> Self {
         let mut doc_frequencies: HashMap<String, usize> = HashMap::new();
         let mut total_length = 0usize;
 
         for doc in documents {
-            let tokens = Self::tokenize(doc);
+            let tokens = analyzer.tokenize(doc);
             let unique_tokens: HashSet<_> = tokens.iter().collect();
 
             for token in unique_tokens {
@@ -38,6 +41,7 @@ impl Bm25Scorer {
         };
 
         Self {
+            analyzer,
             doc_frequencies,
             total_docs: documents.len(),
             avg_doc_len,
@@ -46,8 +50,8 @@ impl Bm25Scorer {
 
     /// Score a document against a query
     pub fn score(&self, query: &str, document: &str) -> f32 {
-        let query_tokens = Self::tokenize(query);
-        let doc_tokens = Self::tokenize(document);
+        let query_tokens = self.analyzer.tokenize(query);
+        let doc_tokens = self.analyzer.tokenize(document);
         let doc_len = doc_tokens.len() as f32;
 
         // Count term frequencies in document
@@ -77,12 +81,8 @@ impl Bm25Scorer {
         score
     }
 
-    /// Tokenize text into lowercase terms
-    fn tokenize(text: &str) -> Vec<String> {
-        text.to_lowercase()
-            .split(|c: char| !c.is_alphanumeric())
-            .filter(|s| s.len() > 2)
-            .map(|s| s.to_string())
-            .collect()
+    /// Tokenize text into lowercase terms, without language settings
+    pub(super) fn tokenize(text: &str) -> Vec<String> {
+        TextAnalyzer::default().tokenize(text)
     }
 }
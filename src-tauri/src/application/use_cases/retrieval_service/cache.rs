@@ -6,6 +6,7 @@ use std::time::{Duration, Instant};
 /// Cache entry for query results
 #[derive(Clone)]
 struct RetrievalCacheEntry {
+    collection_id: i64,
     results: Vec<QueryResult>,
     created_at: Instant,
 }
@@ -98,6 +99,7 @@ impl RetrievalCache {
         self.cache.insert(
             key.clone(),
             RetrievalCacheEntry {
+                collection_id,
                 results,
                 created_at: Instant::now(),
             },
@@ -137,12 +139,11 @@ impl RetrievalCache {
     }
 
     /// Invalidate cache for a specific collection
-    pub fn invalidate_collection(&mut self, _collection_id: i64) {
-        // Since our key includes collection_id hash, we need to track collection->keys
-        // For now, we'll clear all (simpler, safe invalidation)
-        // A more sophisticated approach would track keys per collection
-        self.cache.clear();
-        self.access_order.clear();
+    pub fn invalidate_collection(&mut self, collection_id: i64) {
+        self.cache
+            .retain(|_, entry| entry.collection_id != collection_id);
+        let cache = &self.cache;
+        self.access_order.retain(|key| cache.contains_key(key));
     }
 
     /// Clear expired entries
@@ -183,3 +184,34 @@ pub struct RetrievalCacheStats {
 pub(super) const DEFAULT_RETRIEVAL_CACHE_SIZE: usize = 500;
 /// Default TTL in seconds (5 minutes for retrieval results)
 pub(super) const DEFAULT_RETRIEVAL_CACHE_TTL_SECS: u64 = 300;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(source_id: i64) -> Vec<QueryResult> {
+        vec![QueryResult {
+            content: format!("chunk {}", source_id),
+            source_type: "text_chunk".to_string(),
+            source_id,
+            score: Some(0.9),
+            page_number: None,
+            page_offset: None,
+            doc_name: None,
+            snippet: None,
+        }]
+    }
+
+    #[test]
+    fn test_invalidate_collection_keeps_other_collections() {
+        let mut cache = RetrievalCache::new(10, 60);
+        cache.put(1, "Revenue 2024", 5, result(1));
+        cache.put(2, "revenue 2024", 5, result(2));
+
+        cache.invalidate_collection(1);
+
+        assert!(cache.get(1, "revenue 2024", 5).is_none());
+        assert_eq!(cache.get(2, "REVENUE 2024", 5).unwrap()[0].source_id, 2);
+        assert_eq!(cache.stats().total_entries, 1);
+    }
+}
@@ -43,6 +43,7 @@ impl RetrievalService {
                 page_number: None,
                 page_offset: None,
                 doc_name: None,
+                snippet: None,
             });
         }
 
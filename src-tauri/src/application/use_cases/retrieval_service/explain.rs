@@ -0,0 +1,259 @@
+//! Explain mode: a record of how every candidate of one retrieval scored,
+//! for finding out why an expected chunk didn't surface.
+
+use super::QueryResult;
+use crate::application::use_cases::embedding_service::SimilarityMetric;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Mutex;
+
+/// How one candidate scored on the way to the results
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CandidateExplanation {
+    pub source_type: String,
+    pub source_id: i64,
+    pub doc_name: Option<String>,
+    /// Keyword score on the 0..1 scale it is fused at
+    pub keyword_score: Option<f32>,
+    /// Best vector similarity over the expanded queries, on the fused scale
+    pub vector_similarity: Option<f32>,
+    /// Expanded query that gave `vector_similarity`
+    pub vector_query: Option<String>,
+    pub fused_score: Option<f32>,
+    /// 1-based position after fusion
+    pub fusion_rank: Option<usize>,
+    pub rerank_score: Option<f32>,
+    /// Positions the reranker moved the candidate up; negative when down
+    pub rerank_delta: Option<i64>,
+    /// 1-based position among the returned results; `None` when left out
+    pub final_rank: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RetrievalExplanation {
+    /// Route the query analysis chose, such as `TextOnly` or `Structured`
+    pub route: Option<String>,
+    /// Whether the results came from the retrieval cache; `None` when it
+    /// wasn't consulted. Scores are computed fresh either way.
+    pub cache_hit: Option<bool>,
+    /// `fts` or `bm25`
+    pub keyword_method: Option<String>,
+    /// Queries the vector search ran, the original one included
+    pub expanded_queries: Vec<String>,
+    pub metric: Option<SimilarityMetric>,
+    /// Fusion weights; `None` when the scores are averaged instead
+    pub keyword_weight: Option<f32>,
+    pub vector_weight: Option<f32>,
+    pub reranked: bool,
+    /// Every scored candidate in fusion order, then returned results that
+    /// were never fused, such as structured rows
+    pub candidates: Vec<CandidateExplanation>,
+}
+
+/// Collects a `RetrievalExplanation` while a retrieval runs
+#[derive(Default)]
+pub struct ExplainTrace {
+    state: Mutex<TraceState>,
+}
+
+#[derive(Default)]
+struct TraceState {
+    explanation: RetrievalExplanation,
+    /// Text chunk candidates by chunk id
+    candidates: HashMap<i64, CandidateExplanation>,
+}
+
+impl ExplainTrace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&self, apply: impl FnOnce(&mut TraceState)) {
+        apply(&mut self.state.lock().unwrap());
+    }
+
+    pub(super) fn route(&self, route: &impl Debug) {
+        self.update(|state| state.explanation.route = Some(format!("{:?}", route)));
+    }
+
+    pub(super) fn keyword_method(&self, method: &str) {
+        self.update(|state| state.explanation.keyword_method = Some(method.to_string()));
+    }
+
+    pub(super) fn weights(&self, keyword_weight: f32, vector_weight: f32) {
+        self.update(|state| {
+            state.explanation.keyword_weight = Some(keyword_weight);
+            state.explanation.vector_weight = Some(vector_weight);
+        });
+    }
+
+    pub(super) fn vector_queries(&self, queries: &[String], metric: SimilarityMetric) {
+        self.update(|state| {
+            state.explanation.metric = Some(metric);
+            for query in queries {
+                if !state.explanation.expanded_queries.contains(query) {
+                    state.explanation.expanded_queries.push(query.clone());
+                }
+            }
+        });
+    }
+
+    pub(super) fn keyword(&self, chunk_id: i64, score: f32) {
+        self.update(|state| state.candidate(chunk_id).keyword_score = Some(score));
+    }
+
+    /// Keeps the best similarity a chunk reached over the expanded queries
+    pub(super) fn vector(&self, chunk_id: i64, query: &str, similarity: f32) {
+        self.update(|state| {
+            let candidate = state.candidate(chunk_id);
+            if candidate
+                .vector_similarity
+                .is_none_or(|best| similarity > best)
+            {
+                candidate.vector_similarity = Some(similarity);
+                candidate.vector_query = Some(query.to_string());
+            }
+        });
+    }
+
+    /// Fused scores, best first
+    pub(super) fn fused(&self, fused: &[(i64, f32)]) {
+        self.update(|state| {
+            for (position, (chunk_id, score)) in fused.iter().enumerate() {
+                let candidate = state.candidate(*chunk_id);
+                candidate.fused_score = Some(*score);
+                candidate.fusion_rank = Some(position + 1);
+            }
+        });
+    }
+
+    /// Candidates in the order the reranker left them, with its scores
+    pub(super) fn reranked(&self, reranked: &[QueryResult]) {
+        self.update(|state| {
+            state.explanation.reranked = true;
+            for (position, result) in reranked.iter().enumerate() {
+                if result.source_type != "text_chunk" {
+                    continue;
+                }
+                let candidate = state.candidate(result.source_id);
+                candidate.rerank_score = result.score;
+                candidate.rerank_delta = candidate
+                    .fusion_rank
+                    .map(|rank| rank as i64 - (position as i64 + 1));
+            }
+        });
+    }
+
+    /// The explanation of a retrieval that returned `results`
+    pub fn finish(self, results: &[QueryResult], cache_hit: Option<bool>) -> RetrievalExplanation {
+        let TraceState {
+            mut explanation,
+            mut candidates,
+        } = self.state.into_inner().unwrap();
+
+        let mut unfused = Vec::new();
+        for (position, result) in results.iter().enumerate() {
+            let known = (result.source_type == "text_chunk")
+                .then(|| candidates.get_mut(&result.source_id))
+                .flatten();
+            let candidate = match known {
+                Some(candidate) => candidate,
+                None => {
+                    unfused.push(CandidateExplanation {
+                        source_type: result.source_type.clone(),
+                        source_id: result.source_id,
+                        fused_score: result.score,
+                        ..Default::default()
+                    });
+                    unfused.last_mut().unwrap()
+                }
+            };
+            candidate.doc_name = result.doc_name.clone();
+            candidate.final_rank = Some(position + 1);
+        }
+
+        let mut fused: Vec<CandidateExplanation> = candidates.into_values().collect();
+        fused.sort_by_key(|candidate| {
+            (
+                candidate.fusion_rank.unwrap_or(usize::MAX),
+                candidate.source_id,
+            )
+        });
+        fused.extend(unfused);
+
+        explanation.cache_hit = cache_hit;
+        explanation.candidates = fused;
+        explanation
+    }
+}
+
+impl TraceState {
+    fn candidate(&mut self, chunk_id: i64) -> &mut CandidateExplanation {
+        self.candidates
+            .entry(chunk_id)
+            .or_insert_with(|| CandidateExplanation {
+                source_type: "text_chunk".to_string(),
+                source_id: chunk_id,
+                ..Default::default()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(source_type: &str, source_id: i64, score: f32) -> QueryResult {
+        QueryResult {
+            content: String::new(),
+            source_type: source_type.to_string(),
+            source_id,
+            score: Some(score),
+            page_number: None,
+            page_offset: None,
+            doc_name: Some("doc.pdf".to_string()),
+            snippet: None,
+        }
+    }
+
+    #[test]
+    fn explanation_follows_candidates_through_fusion_and_rerank() {
+        let trace = ExplainTrace::new();
+        trace.keyword(1, 0.8);
+        trace.vector(2, "refund policy", 0.5);
+        trace.vector(2, "refunds", 0.7);
+        trace.vector(3, "refunds", 0.4);
+        trace.fused(&[(1, 0.6), (2, 0.5), (3, 0.2)]);
+        trace.reranked(&[result("text_chunk", 2, 0.9), result("text_chunk", 1, 0.3)]);
+
+        let explanation = trace.finish(
+            &[
+                result("text_chunk", 2, 0.9),
+                result("text_chunk", 1, 0.3),
+                result("excel_row", 7, 1.0),
+            ],
+            Some(false),
+        );
+
+        let ids: Vec<i64> = explanation.candidates.iter().map(|c| c.source_id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 7]);
+
+        let second = &explanation.candidates[1];
+        assert_eq!(second.vector_similarity, Some(0.7));
+        assert_eq!(second.vector_query.as_deref(), Some("refunds"));
+        assert_eq!(second.rerank_delta, Some(1));
+        assert_eq!(second.final_rank, Some(1));
+        assert_eq!(explanation.candidates[0].rerank_delta, Some(-1));
+
+        // Fused but cut before the results
+        assert_eq!(explanation.candidates[2].final_rank, None);
+
+        let row = &explanation.candidates[3];
+        assert_eq!(row.source_type, "excel_row");
+        assert_eq!(row.fusion_rank, None);
+        assert_eq!(row.final_rank, Some(3));
+        assert!(explanation.reranked);
+        assert_eq!(explanation.cache_hit, Some(false));
+    }
+}
@@ -0,0 +1,547 @@
+//! Language settings of a collection, under `language` in its config: the
+//! stopwords dropped from keyword terms, the stemmer applied to them,
+//! diacritic folding, and the user's synonym groups used at query
+//! expansion. Without settings the keyword search is language neutral, as
+//! before.
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use rust_stemmers::{Algorithm, Stemmer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::domain::error::{AppError, Result};
+
+mod indonesian;
+
+/// Terms a synonym group may hold
+const MAX_SYNONYM_GROUP_TERMS: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalyzerLanguage {
+    English,
+    Indonesian,
+    Spanish,
+    French,
+    German,
+    Portuguese,
+}
+
+impl AnalyzerLanguage {
+    fn pack(&self) -> LanguagePack {
+        match self {
+            AnalyzerLanguage::English => LanguagePack {
+                stopwords: include_str!("../../../resources/rag/stopwords/english.txt"),
+                stemmer: Some(StemmerKind::Snowball(Algorithm::English)),
+                synonyms: &[],
+            },
+            AnalyzerLanguage::Indonesian => LanguagePack {
+                stopwords: include_str!("../../../resources/rag/stopwords/indonesian.txt"),
+                stemmer: Some(StemmerKind::IndonesianAffix),
+                synonyms: indonesian::SYNONYMS,
+            },
+            AnalyzerLanguage::Spanish => LanguagePack {
+                stopwords: include_str!("../../../resources/rag/stopwords/spanish.txt"),
+                stemmer: Some(StemmerKind::Snowball(Algorithm::Spanish)),
+                synonyms: &[],
+            },
+            AnalyzerLanguage::French => LanguagePack {
+                stopwords: include_str!("../../../resources/rag/stopwords/french.txt"),
+                stemmer: Some(StemmerKind::Snowball(Algorithm::French)),
+                synonyms: &[],
+            },
+            AnalyzerLanguage::German => LanguagePack {
+                stopwords: include_str!("../../../resources/rag/stopwords/german.txt"),
+                stemmer: Some(StemmerKind::Snowball(Algorithm::German)),
+                synonyms: &[],
+            },
+            AnalyzerLanguage::Portuguese => LanguagePack {
+                stopwords: include_str!("../../../resources/rag/stopwords/portuguese.txt"),
+                stemmer: Some(StemmerKind::Snowball(Algorithm::Portuguese)),
+                synonyms: &[],
+            },
+        }
+    }
+}
+
+/// What the analyzer knows of a language. Supporting another one takes a
+/// variant of `AnalyzerLanguage`, a stopword list and its pack.
+struct LanguagePack {
+    /// One word per line, `#` starts a comment line
+    stopwords: &'static str,
+    stemmer: Option<StemmerKind>,
+    /// Synonym groups applied after the collection's own
+    synonyms: &'static [&'static [&'static str]],
+}
+
+#[derive(Debug, Clone, Copy)]
+enum StemmerKind {
+    Snowball(Algorithm),
+    /// The affix stripper of `language/indonesian.rs`
+    IndonesianAffix,
+}
+
+enum WordStemmer {
+    Snowball(Stemmer),
+    IndonesianAffix,
+}
+
+impl WordStemmer {
+    fn new(kind: StemmerKind) -> Self {
+        match kind {
+            StemmerKind::Snowball(algorithm) => WordStemmer::Snowball(Stemmer::create(algorithm)),
+            StemmerKind::IndonesianAffix => WordStemmer::IndonesianAffix,
+        }
+    }
+
+    fn stem<'a>(&self, word: &'a str) -> Cow<'a, str> {
+        match self {
+            WordStemmer::Snowball(stemmer) => stemmer.stem(word),
+            WordStemmer::IndonesianAffix => Cow::Owned(indonesian::stem(word)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenAnalyzer {
+    /// Lowercased words
+    #[default]
+    Standard,
+    /// Words reduced to their stem, so `policies` finds `policy` and
+    /// `dikirimkan` finds `kiriman`
+    Stemmed,
+}
+
+/// Language settings of one collection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CollectionLanguageConfig {
+    /// Language of the documents; none keeps keyword search language neutral
+    pub language: Option<AnalyzerLanguage>,
+    pub analyzer: TokenAnalyzer,
+    /// Drop the language's bundled stopwords from keyword terms
+    pub stopwords: bool,
+    /// Stopwords of the collection's own, on top of the bundled ones
+    pub extra_stopwords: Vec<String>,
+    /// Match `resume` and `résumé` alike; only applies with a language set
+    pub fold_diacritics: bool,
+    /// Groups of interchangeable terms or phrases; a query naming one is
+    /// also run with each of the others
+    pub synonyms: Vec<Vec<String>>,
+}
+
+impl Default for CollectionLanguageConfig {
+    fn default() -> Self {
+        Self {
+            language: None,
+            analyzer: TokenAnalyzer::Standard,
+            stopwords: true,
+            extra_stopwords: Vec::new(),
+            fold_diacritics: true,
+            synonyms: Vec::new(),
+        }
+    }
+}
+
+impl CollectionLanguageConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.analyzer == TokenAnalyzer::Stemmed {
+            match self.language {
+                None => {
+                    return Err(AppError::ValidationError(
+                        "Choose a language to use the stemmed analyzer".to_string(),
+                    ))
+                }
+                Some(language) if language.pack().stemmer.is_none() => {
+                    return Err(AppError::ValidationError(format!(
+                        "No stemmer is available for {:?}; use the standard analyzer",
+                        language
+                    )))
+                }
+                _ => {}
+            }
+        }
+        for group in &self.synonyms {
+            let terms = group.iter().filter(|term| !term.trim().is_empty()).count();
+            if terms < 2 {
+                return Err(AppError::ValidationError(
+                    "A synonym group needs at least two terms".to_string(),
+                ));
+            }
+            if group.len() > MAX_SYNONYM_GROUP_TERMS {
+                return Err(AppError::ValidationError(format!(
+                    "A synonym group can hold at most {} terms",
+                    MAX_SYNONYM_GROUP_TERMS
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The language settings of a collection config, defaults when it has none
+    pub fn from_collection_config(config_json: &str) -> Result<Self> {
+        if config_json.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        let config: Value = serde_json::from_str(config_json)
+            .map_err(|e| AppError::ParseError(format!("Invalid collection config: {}", e)))?;
+        match config.get("language") {
+            None | Some(Value::Null) => Ok(Self::default()),
+            Some(language) => serde_json::from_value(language.clone()).map_err(|e| {
+                AppError::ParseError(format!("Invalid collection language config: {}", e))
+            }),
+        }
+    }
+
+    /// `config_json` with its language settings replaced by these; other
+    /// keys stay
+    pub fn merge_into_collection_config(&self, config_json: &str) -> Result<String> {
+        self.validate()?;
+        let mut config: Value = if config_json.trim().is_empty() {
+            Value::Object(Default::default())
+        } else {
+            serde_json::from_str(config_json)
+                .map_err(|e| AppError::ParseError(format!("Invalid collection config: {}", e)))?
+        };
+        let object = config.as_object_mut().ok_or_else(|| {
+            AppError::ValidationError("Collection config is not a JSON object".to_string())
+        })?;
+        let language = serde_json::to_value(self).map_err(|e| {
+            AppError::Internal(format!("Failed to serialize language config: {}", e))
+        })?;
+        object.insert("language".to_string(), language);
+        serde_json::to_string(&config).map_err(|e| {
+            AppError::Internal(format!("Failed to serialize collection config: {}", e))
+        })
+    }
+}
+
+/// Turns text into keyword terms the way a collection's settings ask
+#[derive(Default)]
+pub struct TextAnalyzer {
+    language: Option<AnalyzerLanguage>,
+    fold_diacritics: bool,
+    /// Normalized like the text they are matched against
+    stopwords: HashSet<String>,
+    stemmer: Option<WordStemmer>,
+    /// Normalized synonym groups, the collection's first
+    synonyms: Vec<Vec<String>>,
+}
+
+impl TextAnalyzer {
+    pub fn new(config: &CollectionLanguageConfig) -> Self {
+        let pack = config.language.map(|language| language.pack());
+        let fold = config.language.is_some() && config.fold_diacritics;
+
+        let mut stopwords: HashSet<String> = HashSet::new();
+        if let (Some(pack), true) = (&pack, config.stopwords) {
+            stopwords.extend(
+                pack.stopwords
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(|word| normalize_text(word, fold)),
+            );
+        }
+        stopwords.extend(
+            config
+                .extra_stopwords
+                .iter()
+                .map(|word| normalize_text(word.trim(), fold))
+                .filter(|word| !word.is_empty()),
+        );
+        let stemmer = match config.analyzer {
+            TokenAnalyzer::Stemmed => pack
+                .as_ref()
+                .and_then(|pack| pack.stemmer)
+                .map(WordStemmer::new),
+            TokenAnalyzer::Standard => None,
+        };
+        let built_in = pack.as_ref().map_or(&[][..], |pack| pack.synonyms);
+        let synonyms = config
+            .synonyms
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|term| normalize_phrase(term, fold))
+                    .filter(|term| !term.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .chain(built_in.iter().map(|group| {
+                group
+                    .iter()
+                    .map(|term| normalize_phrase(term, fold))
+                    .collect()
+            }))
+            .filter(|group| group.len() > 1)
+            .collect();
+        Self {
+            language: config.language,
+            fold_diacritics: fold,
+            stopwords,
+            stemmer,
+            synonyms,
+        }
+    }
+
+    pub fn language(&self) -> Option<AnalyzerLanguage> {
+        self.language
+    }
+
+    /// Lowercased, and without diacritics when the settings fold them
+    pub fn normalize(&self, text: &str) -> String {
+        normalize_text(text, self.fold_diacritics)
+    }
+
+    pub fn is_stopword(&self, word: &str) -> bool {
+        if self.fold_diacritics {
+            self.stopwords.contains(&fold_diacritics(word))
+        } else {
+            self.stopwords.contains(word)
+        }
+    }
+
+    /// Normalized terms of more than two bytes, without stopwords, stemmed
+    /// when the analyzer stems
+    pub fn tokenize(&self, text: &str) -> Vec<String> {
+        self.normalize(text)
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| word.len() > 2 && !self.stopwords.contains(*word))
+            .map(|word| match &self.stemmer {
+                Some(stemmer) => stemmer.stem(word).into_owned(),
+                None => word.to_string(),
+            })
+            .collect()
+    }
+
+    /// The query with each synonym group member it names swapped for the
+    /// group's other members, one variant per swap
+    pub fn synonym_variants(&self, query: &str) -> Vec<String> {
+        let query = normalize_phrase(query, self.fold_diacritics);
+        let mut variants = Vec::new();
+        for group in &self.synonyms {
+            let Some(found) = group
+                .iter()
+                .find(|term| find_phrase(&query, term).is_some())
+            else {
+                continue;
+            };
+            for other in group.iter().filter(|term| *term != found) {
+                if let Some(variant) = replace_phrase(&query, found, other) {
+                    push_unique(&mut variants, variant);
+                }
+            }
+        }
+        variants
+    }
+
+    /// FTS5 MATCH expression for a query: any of its terms or their
+    /// synonyms, stems as prefixes when stemming. A stem that dropped a
+    /// prefix of the word no longer starts it, so the word goes in as well.
+    /// `None` without a language, so the query goes to FTS as typed, or
+    /// when no term is left.
+    pub fn fts_query(&self, query: &str) -> Option<String> {
+        self.language?;
+        let normalized = normalize_phrase(query, self.fold_diacritics);
+        let mut clauses: Vec<String> = Vec::new();
+        for word in normalized
+            .split(' ')
+            .filter(|word| word.len() > 2 && !self.stopwords.contains(*word))
+        {
+            let Some(stemmer) = &self.stemmer else {
+                push_unique(&mut clauses, format!("\"{}\"", word));
+                continue;
+            };
+            let stem = stemmer.stem(word);
+            if !word.starts_with(stem.as_ref()) {
+                push_unique(&mut clauses, format!("\"{}\"", word));
+            }
+            push_unique(&mut clauses, format!("\"{}\"*", stem));
+        }
+        if clauses.is_empty() {
+            return None;
+        }
+        for group in &self.synonyms {
+            if group
+                .iter()
+                .any(|term| find_phrase(&normalized, term).is_some())
+            {
+                for term in group {
+                    push_unique(&mut clauses, format!("\"{}\"", term));
+                }
+            }
+        }
+        Some(clauses.join(" OR "))
+    }
+}
+
+fn push_unique(items: &mut Vec<String>, item: String) {
+    if !items.contains(&item) {
+        items.push(item);
+    }
+}
+
+/// Letters stripped of their accents: `é` becomes `e`, the way the FTS
+/// index's unicode61 tokenizer folds them
+fn fold_diacritics(text: &str) -> String {
+    text.nfd().filter(|c| !is_combining_mark(*c)).collect()
+}
+
+fn normalize_text(text: &str, fold: bool) -> String {
+    let lowercase = text.to_lowercase();
+    if fold {
+        fold_diacritics(&lowercase)
+    } else {
+        lowercase
+    }
+}
+
+/// Normalized words joined by single spaces, punctuation dropped
+fn normalize_phrase(text: &str, fold: bool) -> String {
+    normalize_text(text, fold)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Byte offset of `phrase` in `text` as whole words; both normalized
+fn find_phrase(text: &str, phrase: &str) -> Option<usize> {
+    text.match_indices(phrase)
+        .map(|(index, _)| index)
+        .find(|&index| {
+            let end = index + phrase.len();
+            (index == 0 || text.as_bytes()[index - 1] == b' ')
+                && (end == text.len() || text.as_bytes()[end] == b' ')
+        })
+}
+
+fn replace_phrase(text: &str, from: &str, to: &str) -> Option<String> {
+    let index = find_phrase(text, from)?;
+    Some(format!(
+        "{}{}{}",
+        &text[..index],
+        to,
+        &text[index + from.len()..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyzer(language: AnalyzerLanguage, analyzer: TokenAnalyzer) -> TextAnalyzer {
+        TextAnalyzer::new(&CollectionLanguageConfig {
+            language: Some(language),
+            analyzer,
+            synonyms: vec![vec!["invoice".to_string(), "faktur pajak".to_string()]],
+            ..CollectionLanguageConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_tokenize_drops_stopwords_and_stems() {
+        let neutral = TextAnalyzer::default();
+        assert_eq!(
+            neutral.tokenize("What are the refund policies?"),
+            vec!["what", "are", "the", "refund", "policies"]
+        );
+
+        let english = analyzer(AnalyzerLanguage::English, TokenAnalyzer::Stemmed);
+        assert_eq!(
+            english.tokenize("What are the refund policies?"),
+            vec!["refund", "polici"]
+        );
+        assert_eq!(english.tokenize("policy"), vec!["polici"]);
+
+        let indonesian = analyzer(AnalyzerLanguage::Indonesian, TokenAnalyzer::Standard);
+        assert_eq!(
+            indonesian.tokenize("Apa syarat pengembalian dana untuk pesanan ini?"),
+            vec!["syarat", "pengembalian", "dana", "pesanan"]
+        );
+    }
+
+    #[test]
+    fn test_synonyms_expand_queries_and_fts() {
+        let indonesian = analyzer(AnalyzerLanguage::Indonesian, TokenAnalyzer::Standard);
+        assert_eq!(
+            indonesian.synonym_variants("Kapan Faktur Pajak dikirim?"),
+            vec!["kapan invoice dikirim"]
+        );
+        assert!(indonesian.synonym_variants("invoices dikirim").is_empty());
+        assert_eq!(
+            indonesian.fts_query("Kapan invoice dikirim?").as_deref(),
+            Some("\"kapan\" OR \"invoice\" OR \"dikirim\" OR \"faktur pajak\"")
+        );
+
+        let english = analyzer(AnalyzerLanguage::English, TokenAnalyzer::Stemmed);
+        assert_eq!(
+            english.fts_query("the refund policies").as_deref(),
+            Some("\"refund\"* OR \"polici\"*")
+        );
+        assert_eq!(english.fts_query("what is it?"), None);
+        assert_eq!(TextAnalyzer::default().fts_query("refund"), None);
+    }
+
+    #[test]
+    fn test_config_round_trips_through_collection_config() {
+        let config = CollectionLanguageConfig {
+            language: Some(AnalyzerLanguage::German),
+            analyzer: TokenAnalyzer::Stemmed,
+            ..CollectionLanguageConfig::default()
+        };
+        let json = config
+            .merge_into_collection_config(r#"{"pipeline":{"hooks":[]}}"#)
+            .unwrap();
+        assert!(json.contains("pipeline"));
+        assert_eq!(
+            CollectionLanguageConfig::from_collection_config(&json).unwrap(),
+            config
+        );
+
+        let no_language = CollectionLanguageConfig {
+            analyzer: TokenAnalyzer::Stemmed,
+            ..CollectionLanguageConfig::default()
+        };
+        assert!(no_language.validate().is_err());
+        let indonesian = CollectionLanguageConfig {
+            language: Some(AnalyzerLanguage::Indonesian),
+            ..no_language
+        };
+        assert!(indonesian.validate().is_ok());
+    }
+
+    #[test]
+    fn test_indonesian_pack_folds_stems_and_expands() {
+        let indonesian = analyzer(AnalyzerLanguage::Indonesian, TokenAnalyzer::Stemmed);
+        assert_eq!(
+            indonesian.tokenize("Paket dikirimkan ke kafé"),
+            vec!["paket", "kirim", "kafe"]
+        );
+        assert_eq!(indonesian.tokenize("kiriman"), vec!["kirim"]);
+        assert_eq!(
+            indonesian.fts_query("dokumen dikirimkan").as_deref(),
+            Some(
+                "\"dokumen\"* OR \"dikirimkan\" OR \"kirim\"* OR \"dokumen\" OR \"berkas\" OR \"file\""
+            )
+        );
+        assert_eq!(
+            indonesian.synonym_variants("cara unduh laporan"),
+            vec!["cara download laporan"]
+        );
+
+        let unfolded = TextAnalyzer::new(&CollectionLanguageConfig {
+            language: Some(AnalyzerLanguage::French),
+            fold_diacritics: false,
+            ..CollectionLanguageConfig::default()
+        });
+        assert_eq!(unfolded.tokenize("résumé"), vec!["résumé"]);
+        assert_eq!(TextAnalyzer::default().tokenize("résumé"), vec!["résumé"]);
+    }
+}
@@ -0,0 +1,205 @@
+//! Indonesian language pack: a light affix stemmer and built-in synonym
+//! groups. The stemmer follows Tala's rule-based stemmer: with no root word
+//! list it strips particles, possessive pronouns, derivational prefixes and
+//! suffixes by pattern alone, and never leaves a stem of less than two
+//! vowels. It errs on the side of keeping a word whole; `dikirimkan`,
+//! `kiriman` and `terkirim` all become `kirim`, but `mengirim` becomes
+//! `irim` since the dropped `k` can't be told from the prefix.
+
+/// Groups of interchangeable terms, including the English terms common in
+/// Indonesian technical writing
+pub(super) const SYNONYMS: &[&[&str]] = &[
+    &["kesalahan", "galat", "error"],
+    &["masalah", "kendala", "isu"],
+    &["pengaturan", "konfigurasi", "setelan"],
+    &["pengguna", "user", "akun"],
+    &["kata sandi", "password"],
+    &["masuk", "login"],
+    &["daftar", "registrasi"],
+    &["harga", "biaya", "tarif"],
+    &["pembayaran", "transaksi"],
+    &["pengiriman", "ekspedisi"],
+    &["pengembalian dana", "refund"],
+    &["ringkasan", "rangkuman", "ikhtisar"],
+    &["dokumen", "berkas", "file"],
+    &["hapus", "buang"],
+    &["ubah", "edit", "perbarui"],
+    &["unduh", "download"],
+    &["unggah", "upload"],
+];
+
+const PARTICLES: &[&str] = &["kah", "lah", "pun"];
+const POSSESSIVES: &[&str] = &["nya", "ku", "mu"];
+const SUFFIXES: &[&str] = &["kan", "an", "i"];
+
+/// Words ending in `lah` that are not a word plus the particle
+const LAH_WORDS: &[&str] = &["masalah", "sekolah", "istilah"];
+
+/// Reduce a lowercased word to its stem; words it can't safely reduce come
+/// back as they are
+pub(super) fn stem(word: &str) -> String {
+    let mut word = word.to_string();
+    if !LAH_WORDS.contains(&word.as_str()) {
+        strip_ending(&mut word, PARTICLES);
+    }
+    strip_ending(&mut word, POSSESSIVES);
+
+    if let Some(prefix) = strip_first_order_prefix(&mut word) {
+        strip_suffix(&mut word, Some(prefix));
+        strip_second_order_prefix(&mut word);
+    } else {
+        strip_second_order_prefix(&mut word);
+        strip_suffix(&mut word, None);
+    }
+    word
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+/// Stripping an affix must leave at least two syllables, counted by vowel
+fn long_enough(stem: &str) -> bool {
+    stem.chars().filter(|c| is_vowel(*c)).count() >= 2
+}
+
+fn strip_ending(word: &mut String, endings: &[&str]) {
+    for ending in endings {
+        if let Some(stem) = word.strip_suffix(ending) {
+            if long_enough(stem) {
+                word.truncate(stem.len());
+                return;
+            }
+        }
+    }
+}
+
+/// `meN-`, `peN-`, `di-`, `ter-` and `ke-`. The nasal of `meN-` and `peN-`
+/// stands in for a root's first `s`, or `p` before a vowel, which is put
+/// back. Returns the prefix stripped.
+fn strip_first_order_prefix(word: &mut String) -> Option<&'static str> {
+    const PREFIXES: &[&str] = &[
+        "meng", "meny", "men", "mem", "me", "peng", "peny", "pen", "pem", "di", "ter", "ke",
+    ];
+    for prefix in PREFIXES {
+        let Some(rest) = word.strip_prefix(prefix) else {
+            continue;
+        };
+        let before_vowel = rest.starts_with(is_vowel);
+        let stem = match *prefix {
+            "meny" | "peny" if before_vowel => format!("s{}", rest),
+            "meny" | "peny" => continue,
+            "mem" | "pem" if before_vowel => format!("p{}", rest),
+            "me" if !rest.starts_with(['l', 'r', 'w', 'y', 'm', 'n']) => continue,
+            _ => rest.to_string(),
+        };
+        if !long_enough(&stem) {
+            return None;
+        }
+        *word = stem;
+        return Some(prefix);
+    }
+    None
+}
+
+/// `ber-`, `per-` and `pe-`, with `bel-` and `pel-` only before `ajar` and
+/// `be-` only before a consonant and `er`, as in `bekerja`
+fn strip_second_order_prefix(word: &mut String) {
+    const PREFIXES: &[&str] = &["ber", "bel", "be", "per", "pel", "pe"];
+    for prefix in PREFIXES {
+        let Some(rest) = word.strip_prefix(prefix) else {
+            continue;
+        };
+        let applies = match *prefix {
+            "bel" | "pel" => rest.starts_with("ajar"),
+            "be" => {
+                let mut chars = rest.chars();
+                chars.next().is_some_and(|c| !is_vowel(c)) && chars.as_str().starts_with("er")
+            }
+            _ => true,
+        };
+        if applies {
+            if long_enough(rest) {
+                *word = rest.to_string();
+            }
+            return;
+        }
+    }
+}
+
+/// `-kan`, `-an` and `-i`, except where the prefix stripped never pairs with
+/// the suffix. `-i` is only taken after a consonant other than `s`, so
+/// `sampai` and loanwords such as `informasi` stay whole.
+fn strip_suffix(word: &mut String, prefix: Option<&str>) {
+    for suffix in SUFFIXES {
+        let Some(stem) = word.strip_suffix(suffix) else {
+            continue;
+        };
+        let allowed = match (prefix, *suffix) {
+            (Some("meng" | "meny" | "men" | "mem" | "me" | "di" | "ter"), "an") => false,
+            (Some("ke"), "kan" | "i") => false,
+            (_, "i") => stem.ends_with(|c: char| !is_vowel(c) && c != 's'),
+            _ => true,
+        };
+        if allowed && long_enough(stem) {
+            word.truncate(stem.len());
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem_strips_common_affixes() {
+        let cases = [
+            ("dikirimkan", "kirim"),
+            ("kiriman", "kirim"),
+            ("terkirim", "kirim"),
+            ("memperbaiki", "baik"),
+            ("diperbaiki", "baik"),
+            ("menyimpan", "simpan"),
+            ("penyimpanan", "simpan"),
+            ("memakai", "pakai"),
+            ("membaca", "baca"),
+            ("menggunakan", "guna"),
+            ("penggunaan", "guna"),
+            ("belajar", "ajar"),
+            ("pelajaran", "ajar"),
+            ("mempelajari", "ajar"),
+            ("bekerja", "kerja"),
+            ("pekerjaan", "kerja"),
+            ("memerlukan", "perlu"),
+            ("diperlukan", "perlu"),
+            ("keamanan", "aman"),
+            ("kebijakan", "bijak"),
+            ("keberhasilan", "hasil"),
+            ("permasalahan", "masalah"),
+            ("bukunya", "buku"),
+            ("apakah", "apa"),
+        ];
+        for (word, expected) in cases {
+            assert_eq!(stem(word), expected, "stem of {}", word);
+        }
+    }
+
+    #[test]
+    fn test_stem_keeps_short_and_root_words() {
+        for word in [
+            "makan",
+            "masalah",
+            "sekolah",
+            "sampai",
+            "informasi",
+            "beras",
+            "memang",
+            "meja",
+            "buku",
+            "data",
+        ] {
+            assert_eq!(stem(word), word);
+        }
+    }
+}
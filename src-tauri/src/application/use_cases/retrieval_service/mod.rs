@@ -3,20 +3,38 @@ mod bm25;
 mod cache;
 mod context;
 mod excel;
+mod explain;
 mod fusion;
+mod language;
+mod parent_context;
+mod persistent_cache;
 mod query_expansion;
+mod routing;
 mod service;
+mod snippet;
 mod structured;
 mod text;
 mod types;
 
 pub use bm25::Bm25Scorer;
 pub use cache::{RetrievalCache, RetrievalCacheStats};
-pub use types::{NumericQuery, QueryAnalysis, QueryResult, QueryType, StructuredQueryHints};
+pub use explain::{ExplainTrace, RetrievalExplanation};
+pub use language::{AnalyzerLanguage, CollectionLanguageConfig, TextAnalyzer};
+pub use routing::{
+    fit_thresholds, QueryRoute, RoutingDecision, RoutingExample, RoutingFit, RoutingSignals,
+    RoutingThresholds,
+};
+pub use types::{
+    HighlightKind, NumericQuery, QueryAnalysis, QueryResult, QueryType, ResultSnippet,
+    SnippetHighlight, StructuredQueryHints,
+};
 
 use crate::application::use_cases::embedding_service::{EmbeddingService, VectorSearch};
+use crate::application::use_cases::rag_config::SharedConfigManager;
 use crate::application::use_cases::reranker_service::RerankerService;
+use crate::domain::error::Result;
 use crate::infrastructure::db::rag::repository::RagRepository;
+use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex};
 
 pub struct RetrievalService {
@@ -26,10 +44,18 @@ pub struct RetrievalService {
     reranker_service: Arc<RerankerService>,
     /// Retrieval results cache
     cache: Arc<Mutex<RetrievalCache>>,
+    /// Cache settings, including whether results are also kept on disk
+    config_manager: SharedConfigManager,
+    /// Set once the on-disk cache has been loaded into memory
+    cache_warmed: AtomicBool,
 }
 
 impl RetrievalService {
-    pub fn new(rag_repository: Arc<RagRepository>, embedding_service: Arc<EmbeddingService>) -> Self {
+    pub fn new(
+        rag_repository: Arc<RagRepository>,
+        embedding_service: Arc<EmbeddingService>,
+        config_manager: SharedConfigManager,
+    ) -> Self {
         let vector_search = VectorSearch::new(embedding_service.clone());
         let reranker_service = Arc::new(RerankerService::default());
         Self {
@@ -41,6 +67,8 @@ impl RetrievalService {
                 cache::DEFAULT_RETRIEVAL_CACHE_SIZE,
                 cache::DEFAULT_RETRIEVAL_CACHE_TTL_SECS,
             ))),
+            config_manager,
+            cache_warmed: AtomicBool::new(false),
         }
     }
 
@@ -49,17 +77,24 @@ impl RetrievalService {
         self.cache.lock().unwrap().stats()
     }
 
-    /// Clear the retrieval cache
-    pub fn clear_cache(&self) {
+    /// Clear the retrieval cache, in memory and on disk
+    pub async fn clear_cache(&self) -> Result<()> {
         self.cache.lock().unwrap().clear();
+        self.rag_repository.clear_retrieval_cache().await?;
+        Ok(())
     }
 
-    /// Invalidate cache for a specific collection (call after document updates)
-    pub fn invalidate_collection_cache(&self, collection_id: i64) {
+    /// Invalidate cache for a specific collection (call after document updates),
+    /// in memory and on disk
+    pub async fn invalidate_collection_cache(&self, collection_id: i64) -> Result<()> {
         self.cache
             .lock()
             .unwrap()
             .invalidate_collection(collection_id);
+        self.rag_repository
+            .delete_collection_retrieval_cache(collection_id)
+            .await?;
+        Ok(())
     }
 
     /// Clean up expired cache entries
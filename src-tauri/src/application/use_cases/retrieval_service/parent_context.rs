@@ -0,0 +1,105 @@
+use super::{QueryResult, RetrievalService};
+use crate::domain::error::Result;
+use std::collections::HashSet;
+
+/// Neighbours on each side used as the section of chunks stored before
+/// parent sections existed
+const LEGACY_SECTION_WINDOW: i64 = 2;
+
+impl RetrievalService {
+    /// Replace each matched chunk with the part of its parent section around
+    /// it that fits `max_tokens`. A result whose chunk is already inside an
+    /// earlier result's section is dropped.
+    pub(super) async fn expand_parent_context(
+        &self,
+        results: Vec<QueryResult>,
+        max_tokens: usize,
+        skip_sensitive: bool,
+    ) -> Result<Vec<QueryResult>> {
+        let mut covered: HashSet<i64> = HashSet::new();
+        let mut expanded = Vec::with_capacity(results.len());
+
+        for mut result in results {
+            if !result.source_type.ends_with("_chunk") {
+                expanded.push(result);
+                continue;
+            }
+            if covered.contains(&result.source_id) {
+                continue;
+            }
+
+            let section = self
+                .rag_repository
+                .get_parent_section(result.source_id, LEGACY_SECTION_WINDOW, skip_sensitive)
+                .await?;
+            let Some(matched) = section.iter().position(|c| c.id == result.source_id) else {
+                expanded.push(result);
+                continue;
+            };
+            let tokens: Vec<usize> = section
+                .iter()
+                .map(|c| match c.token_count {
+                    Some(count) if count > 0 => count as usize,
+                    _ => (c.content.len() / 4).max(1),
+                })
+                .collect();
+            let (start, end) = section_window(&tokens, matched, max_tokens);
+            let window = &section[start..end];
+
+            covered.extend(window.iter().map(|c| c.id));
+            if window.len() > 1 {
+                result.content = window
+                    .iter()
+                    .map(|c| c.content.trim())
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+            }
+            expanded.push(result);
+        }
+
+        Ok(expanded)
+    }
+}
+
+/// Range `[start, end)` of a section around `matched` whose tokens fit
+/// `max_tokens`, adding the next chunk and then the previous one in turn.
+/// The matched chunk is always kept.
+fn section_window(tokens: &[usize], matched: usize, max_tokens: usize) -> (usize, usize) {
+    let (mut start, mut end) = (matched, matched + 1);
+    let mut total = tokens[matched];
+    loop {
+        let mut grew = false;
+        if end < tokens.len() && total + tokens[end] <= max_tokens {
+            total += tokens[end];
+            end += 1;
+            grew = true;
+        }
+        if start > 0 && total + tokens[start - 1] <= max_tokens {
+            total += tokens[start - 1];
+            start -= 1;
+            grew = true;
+        }
+        if !grew {
+            return (start, end);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_grows_around_the_match_within_budget() {
+        let tokens = [100, 100, 100, 100, 100];
+        assert_eq!(section_window(&tokens, 2, 300), (1, 4));
+        assert_eq!(section_window(&tokens, 0, 250), (0, 2));
+        assert_eq!(section_window(&tokens, 4, 1000), (0, 5));
+    }
+
+    #[test]
+    fn window_keeps_an_oversized_match_alone() {
+        let tokens = [100, 900, 100];
+        assert_eq!(section_window(&tokens, 1, 500), (1, 2));
+    }
+}
@@ -0,0 +1,170 @@
+//! On-disk tier of the retrieval cache.
+//!
+//! Text retrievals are written to `retrieval_cache` in rag_sense.db as chunk
+//! ids plus the query embedding, read back on an in-memory miss and loaded
+//! into memory at startup. Chunk content is always re-read from
+//! `document_chunks`, so an entry whose chunks were deleted is dropped
+//! instead of resurfacing stale text. Structured and Excel results are not
+//! persisted: they are plain SQL lookups and cheap to recompute.
+
+use super::cache::DEFAULT_RETRIEVAL_CACHE_SIZE;
+use super::{QueryResult, RetrievalService};
+use crate::domain::error::Result;
+use crate::infrastructure::db::rag::repository::{CachedChunkHit, PersistedRetrieval};
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+
+const TEXT_CHUNK_SOURCE: &str = "text_chunk";
+
+impl RetrievalService {
+    /// TTL for results written to disk, or `None` when persistence is off
+    fn persisted_cache_ttl(&self) -> Option<i64> {
+        let cache = self.config_manager.get_config().cache;
+        (cache.enabled
+            && cache.persist_retrieval_cache
+            && cache.persisted_retrieval_cache_ttl_secs > 0)
+            .then_some(cache.persisted_retrieval_cache_ttl_secs as i64)
+    }
+
+    /// Look up a query on disk after an in-memory miss
+    pub(super) async fn load_persisted(
+        &self,
+        collection_id: i64,
+        query_text: &str,
+        top_k: usize,
+    ) -> Option<Vec<QueryResult>> {
+        self.persisted_cache_ttl()?;
+        let entry = self
+            .rag_repository
+            .find_retrieval_cache(
+                collection_id,
+                query_text,
+                top_k as i64,
+                &self.embedding_service.model_id(),
+            )
+            .await
+            .ok()??;
+        self.hydrate(&entry).await
+    }
+
+    /// Write text retrieval results through to disk. Failures only cost a
+    /// future cache hit, so they are not reported to the caller.
+    pub(super) async fn persist(
+        &self,
+        collection_id: i64,
+        query_text: &str,
+        top_k: usize,
+        results: &[QueryResult],
+    ) {
+        let Some(ttl_secs) = self.persisted_cache_ttl() else {
+            return;
+        };
+        if results.is_empty() || results.iter().any(|r| r.source_type != TEXT_CHUNK_SOURCE) {
+            return;
+        }
+        // Queries against encrypted collections stay off disk
+        if !matches!(
+            self.rag_repository
+                .is_collection_encrypted(collection_id)
+                .await,
+            Ok(false)
+        ) {
+            return;
+        }
+
+        let entry = PersistedRetrieval {
+            collection_id,
+            query_text: query_text.to_string(),
+            top_k: top_k as i64,
+            embedding_model: self.embedding_service.model_id(),
+            query_embedding: self.embedding_service.cached_embedding(query_text),
+            hits: results
+                .iter()
+                .map(|r| CachedChunkHit {
+                    chunk_id: r.source_id,
+                    score: r.score,
+                })
+                .collect(),
+        };
+        let _ = self
+            .rag_repository
+            .save_retrieval_cache(&entry, ttl_secs)
+            .await;
+    }
+
+    /// Load the newest persisted entries into memory and seed the embedding
+    /// cache with their query embeddings. Runs once; returns the number of
+    /// entries loaded.
+    pub async fn warm_cache(&self) -> Result<usize> {
+        if self.persisted_cache_ttl().is_none() || self.cache_warmed.swap(true, Ordering::SeqCst) {
+            return Ok(0);
+        }
+
+        self.rag_repository.prune_retrieval_cache().await?;
+        let entries = self
+            .rag_repository
+            .recent_retrieval_cache(
+                &self.embedding_service.model_id(),
+                DEFAULT_RETRIEVAL_CACHE_SIZE as i64,
+            )
+            .await?;
+
+        let mut loaded = 0;
+        // Oldest first so the newest entries end up most recently used
+        for entry in entries.iter().rev() {
+            let Some(results) = self.hydrate(entry).await else {
+                continue;
+            };
+            if let Some(embedding) = &entry.query_embedding {
+                self.embedding_service
+                    .seed_cache(&entry.query_text, embedding.clone());
+            }
+            self.cache.lock().unwrap().put(
+                entry.collection_id,
+                &entry.query_text,
+                entry.top_k as usize,
+                results,
+            );
+            loaded += 1;
+        }
+        Ok(loaded)
+    }
+
+    /// Rebuild results from the stored chunk ids. An entry with a missing
+    /// chunk is deleted and treated as a miss.
+    async fn hydrate(&self, entry: &PersistedRetrieval) -> Option<Vec<QueryResult>> {
+        let ids: Vec<i64> = entry.hits.iter().map(|hit| hit.chunk_id).collect();
+        let chunks = self
+            .rag_repository
+            .get_collection_chunks_by_ids(entry.collection_id, &ids)
+            .await
+            .ok()?;
+        let chunks: HashMap<i64, _> = chunks.into_iter().map(|c| (c.id, c)).collect();
+
+        let results: Option<Vec<QueryResult>> = entry
+            .hits
+            .iter()
+            .map(|hit| {
+                let chunk = chunks.get(&hit.chunk_id)?;
+                Some(QueryResult {
+                    content: chunk.content.clone(),
+                    source_type: TEXT_CHUNK_SOURCE.to_string(),
+                    source_id: chunk.id,
+                    score: hit.score,
+                    page_number: chunk.page_number,
+                    page_offset: chunk.page_offset,
+                    doc_name: Some(chunk.doc_name.clone()),
+                    snippet: None,
+                })
+            })
+            .collect();
+
+        if results.is_none() {
+            let _ = self
+                .rag_repository
+                .delete_retrieval_cache_entry(entry.collection_id, &entry.query_text, entry.top_k)
+                .await;
+        }
+        results
+    }
+}
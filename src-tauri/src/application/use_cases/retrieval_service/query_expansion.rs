@@ -1,15 +1,24 @@
-use super::RetrievalService;
+use super::{AnalyzerLanguage, RetrievalService, TextAnalyzer};
 use std::collections::HashSet;
 
 impl RetrievalService {
     /// Expand the query with synonyms and related terms for better retrieval
-    pub(super) fn expand_query(&self, query: &str) -> Vec<String> {
+    pub(super) fn expand_query(&self, query: &str, analyzer: &TextAnalyzer) -> Vec<String> {
         let mut expansions = Vec::new();
         let lowercase_query = query.to_lowercase();
 
         // Original query (normalized)
         expansions.push(lowercase_query.clone());
 
+        // The collection's and its language's synonyms come first so the
+        // limit keeps them
+        for variant in analyzer.synonym_variants(query) {
+            if !expansions.contains(&variant) {
+                expansions.push(variant);
+            }
+        }
+        let english = matches!(analyzer.language(), None | Some(AnalyzerLanguage::English));
+
         // Common technical synonyms mapping
         let synonyms: &[(&[&str], &[&str])] = &[
             // Programming terms
@@ -58,7 +67,8 @@ impl RetrievalService {
             ),
         ];
 
-        // Apply synonym expansion
+        // Apply synonym expansion; the built-in terms are English
+        let synonyms = if english { synonyms } else { &[] };
         for (terms, related) in synonyms {
             for term in *terms {
                 if lowercase_query.contains(term) {
@@ -94,8 +104,12 @@ impl RetrievalService {
         let key_terms: Vec<&str> = lowercase_query
             .split_whitespace()
             .filter(|word| {
-                word.len() > 2
-                    && !stop_words.contains(word.trim_matches(|c: char| !c.is_alphanumeric()))
+                let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+                let stopword = match analyzer.language() {
+                    Some(_) => analyzer.is_stopword(word),
+                    None => stop_words.contains(word),
+                };
+                word.len() > 2 && !stopword
             })
             .collect();
 
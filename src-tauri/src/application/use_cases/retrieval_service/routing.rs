@@ -0,0 +1,253 @@
+//! Query routing: which retriever answers a query.
+//!
+//! `analyze_query` counts the structured-intent signals of a query
+//! (aggregate words, `category:` filters, ...) and its terms. A query with
+//! enough signals goes to structured rows, a short one to keyword search,
+//! and the rest to the semantic (vector + keyword) retriever. Queries can
+//! force a route; the thresholds are refit from the routes users pick
+//! when they correct or override one.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::QueryType;
+use crate::infrastructure::db::rag::repository::RoutingDecisionRecord;
+
+/// Highest threshold values tried when refitting
+const MAX_STRUCTURED_MIN_SIGNALS: u32 = 6;
+const MAX_KEYWORD_MAX_TERMS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryRoute {
+    /// Vector and keyword candidates fused with the configured weights
+    Semantic,
+    /// Keyword (full-text) candidates only
+    Keyword,
+    /// Aggregate/list/filter over structured rows
+    Structured,
+}
+
+impl QueryRoute {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            QueryRoute::Semantic => "semantic",
+            QueryRoute::Keyword => "keyword",
+            QueryRoute::Structured => "structured",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "semantic" => Some(QueryRoute::Semantic),
+            "keyword" => Some(QueryRoute::Keyword),
+            "structured" => Some(QueryRoute::Structured),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoutingThresholds {
+    /// Structured-intent signals a query needs to go to structured rows
+    pub structured_min_signals: u32,
+    /// Queries with at most this many terms go to keyword search; 0 never
+    pub keyword_max_terms: usize,
+}
+
+impl Default for RoutingThresholds {
+    fn default() -> Self {
+        Self {
+            structured_min_signals: 1,
+            keyword_max_terms: 0,
+        }
+    }
+}
+
+impl RoutingThresholds {
+    /// Route of a text or structured query with these signals
+    pub fn route(&self, signals: &RoutingSignals) -> QueryRoute {
+        if signals.structured > 0 && signals.structured >= self.structured_min_signals {
+            QueryRoute::Structured
+        } else if signals.terms <= self.keyword_max_terms {
+            QueryRoute::Keyword
+        } else {
+            QueryRoute::Semantic
+        }
+    }
+}
+
+/// What the routing thresholds are compared against
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoutingSignals {
+    /// Structured-intent markers found in the query
+    pub structured: u32,
+    /// Whitespace-separated terms
+    pub terms: usize,
+}
+
+/// How a query was routed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutingDecision {
+    pub query_type: QueryType,
+    /// Route the analyzer picked; `None` for column/value lookups, which go
+    /// to spreadsheet data instead
+    pub auto_route: Option<QueryRoute>,
+    /// Route the query ran on
+    pub route: Option<QueryRoute>,
+    /// `route` was forced by the request
+    pub overridden: bool,
+    pub signals: RoutingSignals,
+}
+
+/// A query whose right route the user told us, by correcting it afterwards
+/// or forcing it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoutingExample {
+    pub signals: RoutingSignals,
+    pub route: QueryRoute,
+}
+
+impl RoutingExample {
+    /// The corrected route of a decision, or the route it was forced on;
+    /// `None` when neither is known or the analyzer did not route it
+    pub fn from_record(record: &RoutingDecisionRecord) -> Option<Self> {
+        if record.auto_route.is_none() {
+            return None;
+        }
+        let route = match &record.corrected_route {
+            Some(route) => route,
+            None if record.overridden => record.route.as_ref()?,
+            None => return None,
+        };
+        Some(Self {
+            signals: RoutingSignals {
+                structured: record.structured_signals.max(0) as u32,
+                terms: record.term_count.max(0) as usize,
+            },
+            route: QueryRoute::parse(route)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutingFit {
+    pub current: RoutingThresholds,
+    pub proposed: RoutingThresholds,
+    pub examples: usize,
+    /// Examples the current and proposed thresholds route elsewhere
+    pub misrouted_current: usize,
+    pub misrouted_proposed: usize,
+}
+
+impl RoutingFit {
+    /// The proposed thresholds route more examples right
+    pub fn improves(&self) -> bool {
+        self.misrouted_proposed < self.misrouted_current
+    }
+}
+
+/// Thresholds that route the most `examples` right; among equally good
+/// ones, the closest to `current`. `None` below `min_examples`.
+pub fn fit_thresholds(
+    examples: &[RoutingExample],
+    current: RoutingThresholds,
+    min_examples: usize,
+) -> Option<RoutingFit> {
+    if examples.len() < min_examples.max(1) {
+        return None;
+    }
+    let misrouted = |thresholds: &RoutingThresholds| {
+        examples
+            .iter()
+            .filter(|example| thresholds.route(&example.signals) != example.route)
+            .count()
+    };
+    let distance = |thresholds: &RoutingThresholds| {
+        current
+            .structured_min_signals
+            .abs_diff(thresholds.structured_min_signals) as usize
+            + current
+                .keyword_max_terms
+                .abs_diff(thresholds.keyword_max_terms)
+    };
+
+    let mut best = (misrouted(&current), 0, current);
+    for structured_min_signals in 1..=MAX_STRUCTURED_MIN_SIGNALS {
+        for keyword_max_terms in 0..=MAX_KEYWORD_MAX_TERMS {
+            let candidate = RoutingThresholds {
+                structured_min_signals,
+                keyword_max_terms,
+            };
+            let score = (misrouted(&candidate), distance(&candidate), candidate);
+            if (score.0, score.1) < (best.0, best.1) {
+                best = score;
+            }
+        }
+    }
+
+    Some(RoutingFit {
+        current,
+        proposed: best.2,
+        examples: examples.len(),
+        misrouted_current: misrouted(&current),
+        misrouted_proposed: best.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example(structured: u32, terms: usize, route: QueryRoute) -> RoutingExample {
+        RoutingExample {
+            signals: RoutingSignals { structured, terms },
+            route,
+        }
+    }
+
+    #[test]
+    fn default_thresholds_keep_the_heuristic_routes() {
+        let thresholds = RoutingThresholds::default();
+        let route = |structured, terms| thresholds.route(&RoutingSignals { structured, terms });
+        assert_eq!(route(1, 6), QueryRoute::Structured);
+        assert_eq!(route(0, 1), QueryRoute::Semantic);
+        assert_eq!(route(0, 12), QueryRoute::Semantic);
+    }
+
+    #[test]
+    fn corrections_move_the_thresholds() {
+        let examples = [
+            // Single incidental markers ("data", "list") were not structured
+            example(1, 7, QueryRoute::Semantic),
+            example(1, 9, QueryRoute::Semantic),
+            example(1, 6, QueryRoute::Semantic),
+            example(3, 5, QueryRoute::Structured),
+            // Part numbers and error codes
+            example(0, 1, QueryRoute::Keyword),
+            example(0, 2, QueryRoute::Keyword),
+        ];
+        let fit = fit_thresholds(&examples, RoutingThresholds::default(), 5).unwrap();
+        assert!(fit.improves());
+        assert_eq!(fit.misrouted_proposed, 0);
+        assert_eq!(
+            fit.proposed,
+            RoutingThresholds {
+                structured_min_signals: 2,
+                keyword_max_terms: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn fitting_needs_enough_examples_and_keeps_good_thresholds() {
+        let examples = [example(2, 4, QueryRoute::Structured)];
+        assert!(fit_thresholds(&examples, RoutingThresholds::default(), 5).is_none());
+
+        let examples = vec![example(2, 4, QueryRoute::Structured); 5];
+        let fit = fit_thresholds(&examples, RoutingThresholds::default(), 5).unwrap();
+        assert!(!fit.improves());
+        assert_eq!(fit.proposed, RoutingThresholds::default());
+    }
+}
@@ -1,4 +1,7 @@
-use super::{QueryResult, QueryType, RetrievalService};
+use super::{
+    ExplainTrace, QueryAnalysis, QueryResult, QueryRoute, QueryType, RetrievalService,
+    RoutingDecision, StructuredQueryHints,
+};
 use crate::application::use_cases::rag_config::RagConfig;
 use crate::domain::error::Result;
 use crate::interfaces::http::add_log;
@@ -6,7 +9,56 @@ use std::sync::Mutex;
 
 impl RetrievalService {
     pub async fn query(&self, collection_id: i64, query_text: &str, top_k: usize) -> Result<Vec<QueryResult>> {
+        self.query_impl(collection_id, query_text, top_k, None).await
+    }
+
+    /// `query`, recording how each candidate scored in `trace`
+    pub async fn query_explained(
+        &self,
+        collection_id: i64,
+        query_text: &str,
+        top_k: usize,
+        trace: &ExplainTrace,
+    ) -> Result<Vec<QueryResult>> {
+        self.query_impl(collection_id, query_text, top_k, Some(trace))
+            .await
+    }
+
+    async fn query_impl(
+        &self,
+        collection_id: i64,
+        query_text: &str,
+        top_k: usize,
+        explain: Option<&ExplainTrace>,
+    ) -> Result<Vec<QueryResult>> {
+        let mut results = self.retrieve(collection_id, query_text, top_k, explain).await?;
+        self.attach_snippets(&mut results, query_text).await;
+        Ok(results)
+    }
+
+    async fn retrieve(
+        &self,
+        collection_id: i64,
+        query_text: &str,
+        top_k: usize,
+        explain: Option<&ExplainTrace>,
+    ) -> Result<Vec<QueryResult>> {
         let analysis = self.analyze_query(query_text);
+        if let Some(trace) = explain {
+            trace.route(&analysis.query_type);
+        }
+        self.retrieve_analyzed(collection_id, query_text, &analysis, top_k, explain)
+            .await
+    }
+
+    async fn retrieve_analyzed(
+        &self,
+        collection_id: i64,
+        query_text: &str,
+        analysis: &QueryAnalysis,
+        top_k: usize,
+        explain: Option<&ExplainTrace>,
+    ) -> Result<Vec<QueryResult>> {
         let mut results = Vec::new();
 
         match analysis.query_type {
@@ -23,14 +75,18 @@ impl RetrievalService {
                 results.extend(excel_results);
             }
             QueryType::TextOnly => {
-                let text_results = self.retrieve_text_chunks(collection_id, query_text, top_k).await?;
+                let text_results = self
+                    .retrieve_text_chunks(collection_id, query_text, top_k, explain)
+                    .await?;
                 results.extend(text_results);
             }
             QueryType::Hybrid => {
                 let excel_results = self
                     .retrieve_excel_data(collection_id, &analysis.numeric_queries, top_k / 2)
                     .await?;
-                let text_results = self.retrieve_text_chunks(collection_id, query_text, top_k / 2).await?;
+                let text_results = self
+                    .retrieve_text_chunks(collection_id, query_text, top_k / 2, explain)
+                    .await?;
                 results.extend(excel_results);
                 results.extend(text_results);
             }
@@ -47,11 +103,25 @@ impl RetrievalService {
         top_k: usize,
     ) -> Result<(Vec<QueryResult>, bool)> {
         // Check cache first
+        let cached = {
+            let mut cache = self.cache.lock().unwrap();
+            cache.get(collection_id, query_text, top_k)
+        };
+        if let Some(mut cached_results) = cached {
+            // Entries warmed from disk carry no snippets yet
+            self.attach_snippets(&mut cached_results, query_text).await;
+            return Ok((cached_results, true)); // true = cache hit
+        }
+
+        // Then the on-disk cache kept across restarts
+        if let Some(mut persisted_results) = self
+            .load_persisted(collection_id, query_text, top_k)
+            .await
         {
+            self.attach_snippets(&mut persisted_results, query_text).await;
             let mut cache = self.cache.lock().unwrap();
-            if let Some(cached_results) = cache.get(collection_id, query_text, top_k) {
-                return Ok((cached_results, true)); // true = cache hit
-            }
+            cache.put(collection_id, query_text, top_k, persisted_results.clone());
+            return Ok((persisted_results, true));
         }
 
         // Cache miss - perform actual query
@@ -62,6 +132,7 @@ impl RetrievalService {
             let mut cache = self.cache.lock().unwrap();
             cache.put(collection_id, query_text, top_k, results.clone());
         }
+        self.persist(collection_id, query_text, top_k, &results).await;
 
         Ok((results, false)) // false = cache miss
     }
@@ -73,8 +144,9 @@ impl RetrievalService {
         query_text: &str,
         top_k: usize,
     ) -> Result<Vec<QueryResult>> {
-        self.query_optimized_impl(collection_id, query_text, top_k, None, None)
+        self.query_optimized_impl(collection_id, query_text, top_k, None, None, None, None)
             .await
+            .map(|(results, _)| results)
     }
 
     pub async fn query_optimized_with_config(
@@ -91,8 +163,58 @@ impl RetrievalService {
             top_k,
             Some(rag_config),
             Some(logs),
+            None,
+            None,
         )
         .await
+        .map(|(results, _)| results)
+    }
+
+    /// `query_optimized_with_config` on `route` when given instead of the
+    /// analyzer's route, returning how the query was routed
+    pub async fn query_routed(
+        &self,
+        collection_id: i64,
+        query_text: &str,
+        top_k: usize,
+        rag_config: &RagConfig,
+        logs: &Mutex<Vec<crate::interfaces::http::LogEntry>>,
+        route: Option<QueryRoute>,
+    ) -> Result<(Vec<QueryResult>, RoutingDecision)> {
+        self.query_optimized_impl(
+            collection_id,
+            query_text,
+            top_k,
+            Some(rag_config),
+            Some(logs),
+            None,
+            route,
+        )
+        .await
+    }
+
+    /// `query_optimized_with_config`, recording how each candidate scored
+    /// in `trace`
+    pub async fn query_optimized_explained(
+        &self,
+        collection_id: i64,
+        query_text: &str,
+        top_k: usize,
+        rag_config: &RagConfig,
+        logs: &Mutex<Vec<crate::interfaces::http::LogEntry>>,
+        trace: &ExplainTrace,
+    ) -> Result<Vec<QueryResult>> {
+        self.query_optimized_impl(
+            collection_id,
+            query_text,
+            top_k,
+            Some(rag_config),
+            Some(logs),
+            Some(trace),
+            None,
+        )
+        .await
+        .map(|(results, _)| results)
     }
 
     async fn query_optimized_impl(
@@ -102,9 +224,11 @@ impl RetrievalService {
         top_k: usize,
         rag_config: Option<&RagConfig>,
         logs: Option<&Mutex<Vec<crate::interfaces::http::LogEntry>>>,
-    ) -> Result<Vec<QueryResult>> {
+        explain: Option<&ExplainTrace>,
+        route_override: Option<QueryRoute>,
+    ) -> Result<(Vec<QueryResult>, RoutingDecision)> {
         let default_config = RagConfig::default();
-        let cfg = rag_config.unwrap_or(&default_config);
+        let mut cfg = rag_config.unwrap_or(&default_config).clone();
 
         let query_len = query_text.chars().count();
         let query_hash = Self::hash_query(query_text);
@@ -121,7 +245,25 @@ impl RetrievalService {
             );
         }
 
-        let analysis = self.analyze_query(query_text);
+        let mut analysis = self.analyze_query_with(query_text, &cfg.retrieval.routing);
+        let decision = RoutingDecision {
+            query_type: analysis.query_type,
+            auto_route: analysis.route,
+            route: route_override.or(analysis.route),
+            overridden: route_override.is_some(),
+            signals: analysis.signals,
+        };
+        if let Some(route) = route_override {
+            force_route(&mut analysis, route);
+        }
+        if analysis.route == Some(QueryRoute::Keyword) {
+            cfg.retrieval.keyword_weight = 1.0;
+            cfg.retrieval.vector_weight = 0.0;
+        }
+        let cfg = &cfg;
+        if let Some(trace) = explain {
+            trace.route(&analysis.query_type);
+        }
 
         if let Some(logs) = logs {
             add_log(
@@ -129,8 +271,10 @@ impl RetrievalService {
                 "INFO",
                 "RAG",
                 &format!(
-                    "Intent route (query_type={:?}, wants_aggregate={}, wants_count={}, wants_sources={}, wants_titles={}, has_category={}, has_source={}, has_keyword={}, query_hash={})",
+                    "Intent route (query_type={:?}, route={}, overridden={}, wants_aggregate={}, wants_count={}, wants_sources={}, wants_titles={}, has_category={}, has_source={}, has_keyword={}, query_hash={})",
                     analysis.query_type,
+                    analysis.route.map_or("none", QueryRoute::as_str),
+                    decision.overridden,
                     analysis.structured.wants_aggregate,
                     analysis.structured.wants_count,
                     analysis.structured.wants_sources,
@@ -143,10 +287,14 @@ impl RetrievalService {
             );
         }
 
+        // Over-fetch text chunks so dropping sensitive ones still fills top_k.
+        let exclude_sensitive = cfg.retrieval.exclude_sensitive_chunks;
+        let text_k = if exclude_sensitive { top_k * 2 } else { top_k };
+
         // Phase 05 applies multi-way recall + local reranking for QA (TextOnly) mode.
-        let raw_results = match analysis.query_type {
+        let mut raw_results = match analysis.query_type {
             QueryType::TextOnly => {
-                self.retrieve_text_chunks_multiway(collection_id, query_text, top_k, cfg, logs)
+                self.retrieve_text_chunks_multiway(collection_id, query_text, text_k, cfg, logs, explain)
                     .await?
             }
             QueryType::Structured => {
@@ -183,7 +331,7 @@ impl RetrievalService {
                     }
 
                     if structured_count == 0 {
-                        self.retrieve_text_chunks_multiway(collection_id, query_text, top_k, cfg, logs)
+                        self.retrieve_text_chunks_multiway(collection_id, query_text, text_k, cfg, logs, explain)
                             .await?
                     } else {
                         structured_results
@@ -192,14 +340,45 @@ impl RetrievalService {
             }
             _ => {
                 // Preserve existing behavior for structured/numeric/hybrid.
-                self.query(collection_id, query_text, top_k * 2).await?
+                self.retrieve_analyzed(collection_id, query_text, &analysis, top_k * 2, explain)
+                    .await?
             }
         };
 
+        if exclude_sensitive {
+            let removed = self.drop_sensitive_chunks(&mut raw_results).await?;
+            if removed > 0 {
+                if let Some(logs) = logs {
+                    add_log(
+                        logs,
+                        "INFO",
+                        "RAG",
+                        &format!(
+                            "Excluded {} sensitive chunks from context (query_hash={})",
+                            removed, query_hash
+                        ),
+                    );
+                }
+            }
+        }
+
         // Optimize and limit to requested count
         let mut optimized = self.optimize_context(raw_results);
         optimized.truncate(top_k);
 
+        // Matching used the small chunks; answer from their surrounding sections
+        if cfg.retrieval.parent_context_enabled {
+            optimized = self
+                .expand_parent_context(
+                    optimized,
+                    cfg.retrieval.parent_context_max_tokens,
+                    exclude_sensitive,
+                )
+                .await?;
+        }
+
+        self.attach_snippets(&mut optimized, query_text).await;
+
         if let Some(logs) = logs {
             add_log(
                 logs,
@@ -213,6 +392,40 @@ impl RetrievalService {
             );
         }
 
-        Ok(optimized)
+        Ok((optimized, decision))
+    }
+
+    /// Remove text chunks tagged as containing PII; returns how many were removed
+    async fn drop_sensitive_chunks(&self, results: &mut Vec<QueryResult>) -> Result<usize> {
+        let chunk_ids: Vec<i64> = results
+            .iter()
+            .filter(|r| r.source_type == "text_chunk")
+            .map(|r| r.source_id)
+            .collect();
+        if chunk_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let sensitive = self
+            .rag_repository
+            .get_sensitive_chunk_ids(&chunk_ids)
+            .await?;
+        let before = results.len();
+        results.retain(|r| !(r.source_type == "text_chunk" && sensitive.contains(&r.source_id)));
+        Ok(before - results.len())
+    }
+}
+
+/// Send the query down `route` whatever the analyzer found
+fn force_route(analysis: &mut QueryAnalysis, route: QueryRoute) {
+    analysis.route = Some(route);
+    match route {
+        QueryRoute::Structured => {
+            analysis.query_type = QueryType::Structured;
+            if !analysis.structured.wants_aggregate {
+                analysis.structured = StructuredQueryHints::forced();
+            }
+        }
+        QueryRoute::Semantic | QueryRoute::Keyword => analysis.query_type = QueryType::TextOnly,
     }
 }
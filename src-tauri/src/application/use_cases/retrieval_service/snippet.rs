@@ -0,0 +1,358 @@
+use super::{
+    Bm25Scorer, HighlightKind, QueryResult, ResultSnippet, RetrievalService, SnippetHighlight,
+};
+use crate::application::use_cases::embedding_service::EmbeddingService;
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// Longest snippet in characters, not counting the ellipses
+const SNIPPET_MAX_CHARS: usize = 240;
+
+/// Sentences per result compared with the query when no keyword matched
+const MAX_SEMANTIC_SENTENCES: usize = 12;
+
+impl RetrievalService {
+    /// Give every result without a snippet one built around the query's
+    /// keywords. Results sharing no keyword with the query are previewed at
+    /// the sentence closest to it in meaning; when embedding fails they
+    /// start at their first sentence instead.
+    pub(super) async fn attach_snippets(&self, results: &mut [QueryResult], query_text: &str) {
+        let terms: HashSet<String> = Bm25Scorer::tokenize(query_text).into_iter().collect();
+
+        let mut unmatched = Vec::new();
+        for (index, result) in results.iter_mut().enumerate() {
+            if result.snippet.is_some() {
+                continue;
+            }
+            if term_spans(&result.content, &terms).is_empty() {
+                unmatched.push(index);
+            } else {
+                result.snippet = build_snippet(&result.content, &terms, None, SNIPPET_MAX_CHARS);
+            }
+        }
+        if unmatched.is_empty() {
+            return;
+        }
+
+        let focus = self
+            .closest_sentences(results, &unmatched, query_text)
+            .await
+            .unwrap_or_else(|| vec![None; unmatched.len()]);
+        for (index, focus) in unmatched.into_iter().zip(focus) {
+            let result = &mut results[index];
+            result.snippet = build_snippet(&result.content, &terms, focus, SNIPPET_MAX_CHARS);
+        }
+    }
+
+    /// Index of the sentence most similar to the query for each of
+    /// `indices`, or `None` when the embeddings are unavailable
+    async fn closest_sentences(
+        &self,
+        results: &[QueryResult],
+        indices: &[usize],
+        query_text: &str,
+    ) -> Option<Vec<Option<usize>>> {
+        let query_embedding = self
+            .embedding_service
+            .generate_embedding(query_text)
+            .await
+            .ok()?;
+
+        let sentences: Vec<Vec<&str>> = indices
+            .iter()
+            .map(|&index| {
+                let content = &results[index].content;
+                sentence_spans(content)
+                    .into_iter()
+                    .take(MAX_SEMANTIC_SENTENCES)
+                    .map(|span| &content[span])
+                    .collect()
+            })
+            .collect();
+        let flat: Vec<&str> = sentences.iter().flatten().copied().collect();
+        if flat.is_empty() {
+            return None;
+        }
+        let embeddings = self
+            .embedding_service
+            .generate_embeddings_batch(&flat)
+            .await
+            .ok()?;
+
+        let mut embeddings = embeddings.into_iter();
+        Some(
+            sentences
+                .iter()
+                .map(|group| {
+                    group
+                        .iter()
+                        .map(|_| {
+                            embeddings.next().map_or(f32::MIN, |embedding| {
+                                EmbeddingService::cosine_similarity(&query_embedding, &embedding)
+                            })
+                        })
+                        .enumerate()
+                        .max_by(|a, b| a.1.total_cmp(&b.1))
+                        .map(|(index, _)| index)
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Snippet of `content` of at most `max_chars` characters. It starts from
+/// `focus`, the sentence marked as closest to the query, or else from the
+/// sentence with the most `terms`, and takes in following and then
+/// preceding sentences while they fit. Word matches of `terms` are marked.
+fn build_snippet(
+    content: &str,
+    terms: &HashSet<String>,
+    focus: Option<usize>,
+    max_chars: usize,
+) -> Option<ResultSnippet> {
+    let sentences = sentence_spans(content);
+    if sentences.is_empty() {
+        return None;
+    }
+    let hits = term_spans(content, terms);
+    let focus = focus.filter(|&index| index < sentences.len());
+
+    let anchor = focus.unwrap_or_else(|| {
+        let mut best = (0, 0);
+        for (index, sentence) in sentences.iter().enumerate() {
+            let count = hits.iter().filter(|hit| contains(sentence, hit)).count();
+            if count > best.1 {
+                best = (index, count);
+            }
+        }
+        best.0
+    });
+
+    let chars = |span: &Range<usize>| content[span.clone()].chars().count();
+    let (mut first, mut last) = (anchor, anchor);
+    let mut total = chars(&sentences[anchor]);
+    loop {
+        let mut grew = false;
+        if last + 1 < sentences.len() && total + 1 + chars(&sentences[last + 1]) <= max_chars {
+            last += 1;
+            total += 1 + chars(&sentences[last]);
+            grew = true;
+        }
+        if first > 0 && total + 1 + chars(&sentences[first - 1]) <= max_chars {
+            first -= 1;
+            total += 1 + chars(&sentences[first]);
+            grew = true;
+        }
+        if !grew {
+            break;
+        }
+    }
+
+    let mut range = sentences[first].start..sentences[last].end;
+    if total > max_chars {
+        // A single sentence too long to show whole; keep the part around
+        // its first keyword
+        let center = hits
+            .iter()
+            .find(|hit| contains(&sentences[anchor], hit))
+            .map_or(range.start, |hit| hit.start);
+        range = char_window(content, range, center, max_chars);
+    }
+
+    let cut_before = !content[..range.start].trim().is_empty();
+    let cut_after = !content[range.end..].trim().is_empty();
+    let mut text = String::new();
+    if cut_before {
+        text.push('…');
+    }
+    text.push_str(&content[range.clone()]);
+    if cut_after {
+        text.push('…');
+    }
+
+    let offset = if cut_before { '…'.len_utf16() } else { 0 };
+    let position = |byte: usize| offset + utf16_len(&content[range.start..byte]);
+    let mut highlights: Vec<SnippetHighlight> = hits
+        .iter()
+        .filter(|hit| contains(&range, hit))
+        .map(|hit| SnippetHighlight {
+            start: position(hit.start),
+            end: position(hit.end),
+            kind: HighlightKind::Term,
+        })
+        .collect();
+    if let Some(focus) = focus {
+        let sentence = &sentences[focus];
+        let (start, end) = (sentence.start.max(range.start), sentence.end.min(range.end));
+        if start < end {
+            highlights.push(SnippetHighlight {
+                start: position(start),
+                end: position(end),
+                kind: HighlightKind::Sentence,
+            });
+        }
+    }
+    highlights.sort_by_key(|highlight| highlight.start);
+
+    Some(ResultSnippet {
+        text,
+        highlights,
+        truncated: cut_before || cut_after,
+    })
+}
+
+/// Byte ranges of the trimmed sentences in `text`. A sentence ends at a
+/// line break or at `.`, `!` or `?` followed by whitespace.
+fn sentence_spans(text: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut push = |start: usize, end: usize| {
+        let part = &text[start..end];
+        let trimmed = part.trim_start();
+        let start = start + part.len() - trimmed.len();
+        let end = start + trimmed.trim_end().len();
+        if start < end {
+            spans.push(start..end);
+        }
+    };
+
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        let end = match c {
+            '\n' => Some(index),
+            '.' | '!' | '?' => match chars.peek() {
+                Some((_, next)) if next.is_whitespace() => Some(index + c.len_utf8()),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(end) = end {
+            push(start, end);
+            start = end;
+        }
+    }
+    push(start, text.len());
+    spans
+}
+
+/// Byte ranges of the words in `text` that are one of `terms`, compared the
+/// way BM25 tokenizes
+fn term_spans(text: &str, terms: &HashSet<String>) -> Vec<Range<usize>> {
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    let mut spans = Vec::new();
+    let mut word_start = None;
+    for (index, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        match (c.is_alphanumeric(), word_start) {
+            (true, None) => word_start = Some(index),
+            (false, Some(start)) => {
+                if terms.contains(&text[start..index].to_lowercase()) {
+                    spans.push(start..index);
+                }
+                word_start = None;
+            }
+            _ => {}
+        }
+    }
+    spans
+}
+
+/// Part of `range` at most `max_chars` characters long that starts a third
+/// of the way before `center`
+fn char_window(text: &str, range: Range<usize>, center: usize, max_chars: usize) -> Range<usize> {
+    let boundaries: Vec<usize> = text[range.clone()]
+        .char_indices()
+        .map(|(index, _)| range.start + index)
+        .chain(std::iter::once(range.end))
+        .collect();
+    let center = boundaries
+        .iter()
+        .position(|&byte| byte >= center)
+        .unwrap_or(0);
+    let last = boundaries.len() - 1;
+    let start = center
+        .saturating_sub(max_chars / 3)
+        .min(last.saturating_sub(max_chars));
+    let end = (start + max_chars).min(last);
+    boundaries[start]..boundaries[end]
+}
+
+fn contains(outer: &Range<usize>, inner: &Range<usize>) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
+fn utf16_len(text: &str) -> usize {
+    text.chars().map(char::len_utf16).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(words: &[&str]) -> HashSet<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    fn marked(snippet: &ResultSnippet) -> Vec<String> {
+        let units: Vec<u16> = snippet.text.encode_utf16().collect();
+        snippet
+            .highlights
+            .iter()
+            .map(|h| String::from_utf16(&units[h.start..h.end]).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn centers_on_the_sentence_with_most_terms() {
+        let content = "Intro about nothing. Invoices are paid monthly. \
+                       Late invoices incur a fee. Unrelated closing line.";
+        let snippet = build_snippet(content, &terms(&["invoices", "fee"]), None, 60).unwrap();
+
+        assert_eq!(
+            snippet.text,
+            "…Late invoices incur a fee. Unrelated closing line."
+        );
+        assert!(snippet.truncated);
+        assert_eq!(marked(&snippet), vec!["invoices", "fee"]);
+    }
+
+    #[test]
+    fn offsets_count_utf16_units() {
+        let content = "Café 🚀 rocket launch";
+        let snippet = build_snippet(content, &terms(&["launch"]), None, 200).unwrap();
+
+        assert!(!snippet.truncated);
+        assert_eq!(snippet.highlights[0].start, 15);
+        assert_eq!(marked(&snippet), vec!["launch"]);
+    }
+
+    #[test]
+    fn marks_the_focus_sentence_without_terms() {
+        let content = "First point.\nSecond point about costs.\nThird point.";
+        let snippet = build_snippet(content, &HashSet::new(), Some(1), 200).unwrap();
+
+        assert_eq!(snippet.highlights.len(), 1);
+        assert_eq!(snippet.highlights[0].kind, HighlightKind::Sentence);
+        assert_eq!(marked(&snippet), vec!["Second point about costs."]);
+    }
+
+    #[test]
+    fn cuts_a_long_sentence_around_its_first_term() {
+        let content = format!("{} needle {}", "a".repeat(300), "b".repeat(300));
+        let snippet = build_snippet(&content, &terms(&["needle"]), None, 60).unwrap();
+
+        assert!(snippet.text.starts_with('…') && snippet.text.ends_with('…'));
+        assert_eq!(snippet.text.chars().count(), 62);
+        assert_eq!(marked(&snippet), vec!["needle"]);
+    }
+
+    #[test]
+    fn matches_whole_words_only() {
+        let spans = term_spans("Paid, unpaid and PAID", &terms(&["paid"]));
+        assert_eq!(spans, vec![0..4, 17..21]);
+    }
+}
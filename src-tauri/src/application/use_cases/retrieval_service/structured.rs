@@ -53,6 +53,7 @@ impl RetrievalService {
                 page_number: None,
                 page_offset: None,
                 doc_name: None,
+                snippet: None,
             }]);
         }
 
@@ -84,6 +85,7 @@ impl RetrievalService {
                 page_number: None,
                 page_offset: None,
                 doc_name: None,
+                snippet: None,
             });
         }
 
@@ -98,6 +100,7 @@ impl RetrievalService {
                 page_number: None,
                 page_offset: None,
                 doc_name: Some(r.doc_name),
+                snippet: None,
             });
         }
 
@@ -1,5 +1,10 @@
-use super::{Bm25Scorer, QueryResult, RetrievalService};
+use super::{
+    Bm25Scorer, CollectionLanguageConfig, ExplainTrace, QueryResult, RetrievalService, TextAnalyzer,
+};
+use crate::application::use_cases::embedding_service::SimilarityMetric;
 use crate::application::use_cases::rag_config::RagConfig;
+use crate::application::use_cases::rag_ingestion::IngestionPipelineConfig;
+use crate::domain::embedding_quantization::EmbeddingQuantization;
 use crate::domain::error::Result;
 use crate::infrastructure::db::rag::repository::{ChunkWithMetadata, ChunkWithMetadataScore};
 use crate::interfaces::http::add_log;
@@ -14,6 +19,7 @@ impl RetrievalService {
         collection_id: i64,
         query_text: &str,
         top_k: usize,
+        explain: Option<&ExplainTrace>,
     ) -> Result<Vec<QueryResult>> {
         let chunks = self
             .rag_repository
@@ -25,6 +31,7 @@ impl RetrievalService {
         }
 
         let has_embeddings = chunks.iter().any(|chunk| chunk.embedding.is_some());
+        let analyzer = self.collection_analyzer(collection_id).await;
 
         // Build chunk map for lookups
         let chunk_map: HashMap<i64, &ChunkWithMetadata> = chunks.iter().map(|c| (c.id, c)).collect();
@@ -33,14 +40,24 @@ impl RetrievalService {
         let mut all_result_sets: Vec<Vec<(i64, f32)>> = Vec::new();
 
         // 1. BM25 keyword search (always run)
-        let bm25_results = self.bm25_search(&chunks, query_text, top_k * 2);
+        let bm25_results = self.bm25_search(&chunks, query_text, top_k * 2, &analyzer);
+        if let Some(trace) = explain {
+            trace.keyword_method("bm25");
+            for (chunk_id, score) in &bm25_results {
+                trace.keyword(*chunk_id, *score);
+            }
+        }
         if !bm25_results.is_empty() {
             all_result_sets.push(bm25_results);
         }
 
         // 2. Vector search with query expansion (if embeddings available)
         if has_embeddings {
-            let expanded_queries = self.expand_query(query_text);
+            let metric = self.collection_metric(collection_id).await;
+            let expanded_queries = self.expand_query(query_text, &analyzer);
+            if let Some(trace) = explain {
+                trace.vector_queries(&expanded_queries, metric);
+            }
 
             for expanded_query in &expanded_queries {
                 if let Ok(query_embedding) = self.embedding_service.generate_embedding(expanded_query).await {
@@ -48,9 +65,18 @@ impl RetrievalService {
                         &query_embedding,
                         &chunks,
                         top_k * 2,
+                        metric,
                     );
 
-                    let result_set: Vec<(i64, f32)> = search_results.iter().map(|r| (r.chunk_id, r.score)).collect();
+                    let result_set: Vec<(i64, f32)> = search_results
+                        .iter()
+                        .map(|r| (r.chunk_id, metric.fusion_score(r.score)))
+                        .collect();
+                    if let Some(trace) = explain {
+                        for (chunk_id, score) in &result_set {
+                            trace.vector(*chunk_id, expanded_query, *score);
+                        }
+                    }
 
                     if !result_set.is_empty() {
                         all_result_sets.push(result_set);
@@ -65,6 +91,9 @@ impl RetrievalService {
         if all_result_sets.len() > 1 {
             // Multiple result sets - fuse them
             let fused_results = self.weighted_score_fusion(all_result_sets);
+            if let Some(trace) = explain {
+                trace.fused(&fused_results);
+            }
 
             for (chunk_id, score) in fused_results.iter().take(top_k) {
                 if let Some(chunk) = chunk_map.get(chunk_id) {
@@ -76,11 +105,15 @@ impl RetrievalService {
                         page_number: chunk.page_number,
                         page_offset: chunk.page_offset,
                         doc_name: Some(chunk.doc_name.clone()),
+                        snippet: None,
                     });
                 }
             }
         } else if !all_result_sets.is_empty() {
             // Single result set - use directly
+            if let Some(trace) = explain {
+                trace.fused(&all_result_sets[0]);
+            }
             for (chunk_id, score) in all_result_sets[0].iter().take(top_k) {
                 if let Some(chunk) = chunk_map.get(chunk_id) {
                     results.push(QueryResult {
@@ -91,6 +124,7 @@ impl RetrievalService {
                         page_number: chunk.page_number,
                         page_offset: chunk.page_offset,
                         doc_name: Some(chunk.doc_name.clone()),
+                        snippet: None,
                     });
                 }
             }
@@ -98,7 +132,7 @@ impl RetrievalService {
 
         // Fallback to enhanced keyword search if no results
         if results.is_empty() {
-            results = self.keyword_fallback_results(&chunks, query_text, top_k);
+            results = self.keyword_fallback_results(&chunks, query_text, top_k, &analyzer);
         }
 
         Ok(results)
@@ -111,11 +145,21 @@ impl RetrievalService {
         top_k: usize,
         cfg: &RagConfig,
         logs: Option<&Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+        explain: Option<&ExplainTrace>,
     ) -> Result<Vec<QueryResult>> {
         let query_hash = Self::hash_query(query_text);
+        if let Some(trace) = explain {
+            trace.keyword_method("fts");
+            trace.weights(cfg.retrieval.keyword_weight, cfg.retrieval.vector_weight);
+        }
 
         let candidate_k = cfg.retrieval.candidate_k.max(top_k).max(1);
         let rerank_k = cfg.retrieval.rerank_k.clamp(top_k, candidate_k);
+        let analyzer = self.collection_analyzer(collection_id).await;
+        // With language settings FTS gets the analyzed terms and synonyms
+        let fts_query = analyzer
+            .fts_query(query_text)
+            .unwrap_or_else(|| query_text.to_string());
 
         // 1) FTS5 candidates (keyword heavy)
         if let Some(logs) = logs {
@@ -135,7 +179,7 @@ impl RetrievalService {
         let mut fts_meta: HashMap<i64, ChunkWithMetadataScore> = HashMap::new();
         match self
             .rag_repository
-            .search_chunks_fts_by_collection(collection_id, query_text, candidate_k as i64)
+            .search_chunks_fts_by_collection(collection_id, &fts_query, candidate_k as i64)
             .await
         {
             Ok(rows) => {
@@ -156,6 +200,9 @@ impl RetrievalService {
                     let bm25 = if r.score.is_finite() { r.score } else { 0.0 };
                     let bm25_pos = bm25.max(0.0);
                     let normalized = 1.0 / (1.0 + bm25_pos);
+                    if let Some(trace) = explain {
+                        trace.keyword(r.id, normalized);
+                    }
                     keyword_scores.insert(r.id, normalized);
                     fts_meta.insert(r.id, r);
                 }
@@ -212,10 +259,12 @@ impl RetrievalService {
         let mut vector_scores: HashMap<i64, f32> = HashMap::new();
         let has_embeddings = chunks.iter().any(|c| c.embedding.is_some());
 
-        if has_embeddings {
+        // Keyword-routed queries give vectors no weight; skip embedding them
+        if has_embeddings && cfg.retrieval.vector_weight > 0.0 {
+            let metric = self.collection_metric(collection_id).await;
             let vec_start = Instant::now();
             let mut expanded_queries = if cfg.retrieval.query_expansion_enabled {
-                let mut q = self.expand_query(query_text);
+                let mut q = self.expand_query(query_text, &analyzer);
                 if !q.iter().any(|s| s == query_text) {
                     q.insert(0, query_text.to_string());
                 }
@@ -224,6 +273,9 @@ impl RetrievalService {
                 vec![query_text.to_string()]
             };
             expanded_queries.truncate(3);
+            if let Some(trace) = explain {
+                trace.vector_queries(&expanded_queries, metric);
+            }
 
             if let Some(logs) = logs {
                 add_log(
@@ -241,18 +293,25 @@ impl RetrievalService {
 
             for q in expanded_queries {
                 if let Ok(query_embedding) = self.embedding_service.generate_embedding(&q).await {
-                    let res = self
-                        .vector_search
-                        .search_with_metadata(&query_embedding, &chunks, candidate_k);
+                    let res = self.vector_search.search_with_metadata(
+                        &query_embedding,
+                        &chunks,
+                        candidate_k,
+                        metric,
+                    );
                     for r in res {
+                        let score = metric.fusion_score(r.score);
+                        if let Some(trace) = explain {
+                            trace.vector(r.chunk_id, &q, score);
+                        }
                         vector_scores
                             .entry(r.chunk_id)
                             .and_modify(|s| {
-                                if r.score > *s {
-                                    *s = r.score;
+                                if score > *s {
+                                    *s = score;
                                 }
                             })
-                            .or_insert(r.score);
+                            .or_insert(score);
                     }
                 }
             }
@@ -287,6 +346,10 @@ impl RetrievalService {
             }
         }
         fused.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+        if let Some(trace) = explain {
+            // Before the cut, so candidates that just missed it show up too
+            trace.fused(&fused);
+        }
         fused.truncate(candidate_k);
 
         let mut candidates: Vec<QueryResult> = Vec::with_capacity(fused.len());
@@ -300,6 +363,7 @@ impl RetrievalService {
                     page_number: chunk.page_number,
                     page_offset: chunk.page_offset,
                     doc_name: Some(chunk.doc_name.clone()),
+                    snippet: None,
                 });
                 continue;
             }
@@ -312,6 +376,7 @@ impl RetrievalService {
                     page_number: chunk.page_number,
                     page_offset: chunk.page_offset,
                     doc_name: Some(chunk.doc_name.clone()),
+                    snippet: None,
                 });
             }
         }
@@ -373,6 +438,9 @@ impl RetrievalService {
                             .partial_cmp(&a.score.unwrap_or(0.0))
                             .unwrap_or(Ordering::Equal)
                     });
+                    if let Some(trace) = explain {
+                        trace.reranked(&reranked);
+                    }
                     return Ok(reranked);
                 }
                 Err(e) => {
@@ -396,11 +464,90 @@ impl RetrievalService {
         Ok(candidates)
     }
 
+    /// Share of the vector top `top_k` for `query` that stays in it when the
+    /// collection's embeddings are scored at `quantization` instead of the
+    /// precision they are stored at; `None` without embeddings to compare
+    pub async fn quantization_recall(
+        &self,
+        collection_id: i64,
+        query: &str,
+        top_k: usize,
+        quantization: EmbeddingQuantization,
+    ) -> Result<Option<f32>> {
+        let chunks = self
+            .rag_repository
+            .search_chunks_by_collection(collection_id, 2000)
+            .await?;
+        if !chunks.iter().any(|chunk| chunk.embedding.is_some()) {
+            return Ok(None);
+        }
+        let metric = self.collection_metric(collection_id).await;
+        let query_embedding = self.embedding_service.generate_embedding(query).await?;
+
+        let stored: HashSet<i64> = self
+            .vector_search
+            .search_with_metadata(&query_embedding, &chunks, top_k, metric)
+            .into_iter()
+            .map(|result| result.chunk_id)
+            .collect();
+        if stored.is_empty() {
+            return Ok(None);
+        }
+        let quantized: Vec<ChunkWithMetadata> = chunks
+            .into_iter()
+            .map(|mut chunk| {
+                chunk.embedding = chunk
+                    .embedding
+                    .map(|embedding| embedding.requantize(quantization));
+                chunk
+            })
+            .collect();
+        let kept = self
+            .vector_search
+            .search_with_metadata(&query_embedding, &quantized, top_k, metric)
+            .iter()
+            .filter(|result| stored.contains(&result.chunk_id))
+            .count();
+        Ok(Some(kept as f32 / stored.len() as f32))
+    }
+
+    /// Similarity metric set for the collection's embeddings; cosine when
+    /// its config can't be read
+    async fn collection_metric(&self, collection_id: i64) -> SimilarityMetric {
+        match self.rag_repository.get_collection(collection_id).await {
+            Ok(collection) => {
+                IngestionPipelineConfig::from_collection_config(&collection.config_json)
+                    .map(|pipeline| pipeline.embed.metric)
+                    .unwrap_or_default()
+            }
+            Err(_) => SimilarityMetric::default(),
+        }
+    }
+
+    /// Keyword analyzer of the collection's language settings; language
+    /// neutral when they can't be read
+    async fn collection_analyzer(&self, collection_id: i64) -> TextAnalyzer {
+        match self.rag_repository.get_collection(collection_id).await {
+            Ok(collection) => {
+                CollectionLanguageConfig::from_collection_config(&collection.config_json)
+                    .map(|config| TextAnalyzer::new(&config))
+                    .unwrap_or_default()
+            }
+            Err(_) => TextAnalyzer::default(),
+        }
+    }
+
     /// Perform BM25 keyword search on chunks
-    fn bm25_search(&self, chunks: &[ChunkWithMetadata], query: &str, top_k: usize) -> Vec<(i64, f32)> {
+    fn bm25_search(
+        &self,
+        chunks: &[ChunkWithMetadata],
+        query: &str,
+        top_k: usize,
+        analyzer: &TextAnalyzer,
+    ) -> Vec<(i64, f32)> {
         // Build BM25 scorer from all document contents
         let documents: Vec<&str> = chunks.iter().map(|c| c.content.as_str()).collect();
-        let scorer = Bm25Scorer::from_documents(&documents);
+        let scorer = Bm25Scorer::from_documents(&documents, analyzer);
 
         // Score each chunk
         let mut scored: Vec<(i64, f32)> = chunks
@@ -435,21 +582,17 @@ impl RetrievalService {
         chunks: &[ChunkWithMetadata],
         query_text: &str,
         top_k: usize,
+        analyzer: &TextAnalyzer,
     ) -> Vec<QueryResult> {
         // Expand query for better keyword matching
-        let expanded_queries = self.expand_query(query_text);
+        let expanded_queries = self.expand_query(query_text, analyzer);
 
-        // Collect all unique tokens from expanded queries
+        // Collect all unique terms of the expanded queries; a stem usually
+        // occurs in the words it came from, so the substring match below
+        // finds their inflected forms too
         let mut all_tokens: HashSet<String> = HashSet::new();
         for query in &expanded_queries {
-            for token in query.split_whitespace() {
-                let clean_token = token
-                    .trim_matches(|c: char| !c.is_alphanumeric())
-                    .to_lowercase();
-                if clean_token.len() > 2 {
-                    all_tokens.insert(clean_token);
-                }
-            }
+            all_tokens.extend(analyzer.tokenize(query));
         }
 
         let query_tokens: Vec<String> = all_tokens.into_iter().collect();
@@ -457,7 +600,7 @@ impl RetrievalService {
         let mut scored_chunks: Vec<(f32, &ChunkWithMetadata)> = chunks
             .iter()
             .map(|chunk| {
-                let content_lower = chunk.content.to_lowercase();
+                let content_lower = analyzer.normalize(&chunk.content);
                 let score = if query_tokens.is_empty() {
                     0.0
                 } else {
@@ -495,6 +638,7 @@ impl RetrievalService {
                 page_number: chunk.page_number,
                 page_offset: chunk.page_offset,
                 doc_name: Some(chunk.doc_name.clone()),
+                snippet: None,
             })
             .collect()
     }
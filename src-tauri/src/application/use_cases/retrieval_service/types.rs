@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+use super::{QueryRoute, RoutingSignals};
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct QueryResult {
     pub content: String,
     pub source_type: String,
@@ -9,16 +12,50 @@ pub struct QueryResult {
     pub page_number: Option<i64>,
     pub page_offset: Option<i64>,
     pub doc_name: Option<String>,
+    /// Short preview of `content` around what matched the query
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<ResultSnippet>,
+}
+
+/// Preview of a result with the matched parts marked
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct ResultSnippet {
+    pub text: String,
+    pub highlights: Vec<SnippetHighlight>,
+    /// `text` is cut from longer content
+    pub truncated: bool,
+}
+
+/// Range of `ResultSnippet::text` in UTF-16 code units, so it indexes
+/// JavaScript strings directly
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct SnippetHighlight {
+    pub start: usize,
+    pub end: usize,
+    pub kind: HighlightKind,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HighlightKind {
+    /// A query keyword
+    Term,
+    /// The sentence closest in meaning to the query, for results that
+    /// matched without sharing keywords
+    Sentence,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct QueryAnalysis {
     pub query_type: QueryType,
+    /// Route of text and structured queries; `None` for column/value lookups
+    pub route: Option<QueryRoute>,
     pub numeric_queries: Vec<NumericQuery>,
     pub structured: StructuredQueryHints,
+    pub signals: RoutingSignals,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 pub enum QueryType {
     TextOnly,
     NumericOnly,
@@ -52,6 +89,16 @@ impl StructuredQueryHints {
             requested_limit: None,
         }
     }
+
+    /// Hints for a query forced onto structured rows that asks for nothing
+    /// in particular: list the matching rows
+    pub(super) fn forced() -> Self {
+        Self {
+            wants_aggregate: true,
+            wants_titles: true,
+            ..Self::empty()
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
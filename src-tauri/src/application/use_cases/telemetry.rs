@@ -0,0 +1,143 @@
+use crate::domain::error::Result;
+use crate::domain::telemetry::{TelemetrySettings, UsageDashboard, UsageSample, UsageSubsystem};
+use crate::infrastructure::config::SettingsStore;
+use crate::infrastructure::db::telemetry::{day_of, TelemetryRepository};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+/// Calls recorded between two retention prunes
+const PRUNE_EVERY: u64 = 500;
+
+/// Local usage metrics, recorded only while the `telemetry` setting is on.
+/// Like the activity feed, recording never waits on the database.
+pub struct UsageTelemetry {
+    settings: Arc<SettingsStore>,
+    repository: Arc<TelemetryRepository>,
+    sender: mpsc::UnboundedSender<UsageSample>,
+}
+
+impl UsageTelemetry {
+    /// The telemetry and the writer to spawn for it
+    pub fn new(
+        settings: Arc<SettingsStore>,
+        repository: Arc<TelemetryRepository>,
+    ) -> (Self, TelemetryWriter) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let writer = TelemetryWriter {
+            settings: settings.clone(),
+            repository: repository.clone(),
+            receiver,
+        };
+        (
+            Self {
+                settings,
+                repository,
+                sender,
+            },
+            writer,
+        )
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.settings.get::<TelemetrySettings>().enabled
+    }
+
+    /// Counts one finished call; dropped while telemetry is off
+    pub fn record(
+        &self,
+        subsystem: UsageSubsystem,
+        feature: &str,
+        started: Instant,
+        success: bool,
+    ) {
+        if !self.enabled() {
+            return;
+        }
+        let sample = UsageSample {
+            ts: chrono::Utc::now().timestamp_millis(),
+            subsystem,
+            feature: feature.to_string(),
+            latency_ms: started.elapsed().as_millis() as i64,
+            success,
+        };
+        if self.sender.send(sample).is_err() {
+            warn!("Telemetry writer has stopped; usage sample dropped");
+        }
+    }
+
+    /// Runs `work` and records how long it took and whether it failed
+    pub async fn track<T>(
+        &self,
+        subsystem: UsageSubsystem,
+        feature: &str,
+        work: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let started = Instant::now();
+        let result = work.await;
+        self.record(subsystem, feature, started, result.is_ok());
+        result
+    }
+
+    /// Stats of the last `days` UTC days, today included
+    pub async fn dashboard(&self, days: u32) -> Result<UsageDashboard> {
+        let days = days.max(1);
+        let since_day =
+            day_of(chrono::Utc::now().timestamp_millis() - i64::from(days - 1) * DAY_MS);
+        let summary = self.repository.summarize(&since_day).await?;
+        Ok(UsageDashboard {
+            enabled: self.enabled(),
+            days,
+            since_day,
+            totals: summary.totals,
+            subsystems: summary.subsystems,
+            features: summary.features,
+            daily: summary.daily,
+        })
+    }
+
+    /// Deletes every recorded stat. Returns the number of rows removed.
+    pub async fn clear(&self) -> Result<u64> {
+        self.repository.clear().await
+    }
+}
+
+pub struct TelemetryWriter {
+    settings: Arc<SettingsStore>,
+    repository: Arc<TelemetryRepository>,
+    receiver: mpsc::UnboundedReceiver<UsageSample>,
+}
+
+impl TelemetryWriter {
+    /// Writes queued samples until every `UsageTelemetry` handle is dropped
+    pub async fn run(mut self) {
+        self.prune().await;
+        let mut written = 0u64;
+        while let Some(sample) = self.receiver.recv().await {
+            if let Err(err) = self.repository.record(&sample).await {
+                warn!(error = %err, feature = %sample.feature, "Failed to record usage");
+                continue;
+            }
+            written += 1;
+            if written.is_multiple_of(PRUNE_EVERY) {
+                self.prune().await;
+            }
+        }
+    }
+
+    async fn prune(&self) {
+        let retention_days = self
+            .settings
+            .get::<TelemetrySettings>()
+            .retention_days
+            .max(1);
+        let cutoff =
+            day_of(chrono::Utc::now().timestamp_millis() - i64::from(retention_days - 1) * DAY_MS);
+        if let Err(err) = self.repository.prune(&cutoff).await {
+            warn!(error = %err, "Failed to prune usage stats");
+        }
+    }
+}
@@ -1,10 +1,43 @@
+use crate::application::use_cases::glossary;
+use crate::application::use_cases::language_detection::{self, DetectedLanguage};
+use crate::application::use_cases::translation_memory;
 use crate::domain::error::Result;
+use crate::domain::glossary::GlossaryEntry;
 use crate::domain::llm_config::LLMConfig;
 use crate::domain::prompt::Prompt;
+use crate::domain::translation_memory::TranslationMemoryEntry;
 use crate::infrastructure::db::sqlite::SqliteRepository;
 use crate::infrastructure::llm_clients::LLMClient;
 use crate::infrastructure::response::clean_llm_response;
+use serde::Deserialize;
 use std::sync::Arc;
+use utoipa::ToSchema;
+
+/// Maximum translation memory candidates scored per lookup.
+const TM_CANDIDATE_LIMIT: i64 = 200;
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct TranslateOptions {
+    /// Rewrite mapped glossary terms the model left untranslated.
+    #[serde(default)]
+    pub auto_correct_glossary: bool,
+    /// Reuse approved translation memory matches instead of calling the LLM.
+    #[serde(default = "default_true")]
+    pub use_translation_memory: bool,
+}
+
+impl Default for TranslateOptions {
+    fn default() -> Self {
+        Self {
+            auto_correct_glossary: false,
+            use_translation_memory: true,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
 
 pub struct TranslateUseCase {
     llm_client: Arc<dyn LLMClient + Send + Sync>,
@@ -29,16 +62,71 @@ impl TranslateUseCase {
         source: String,
         target: String,
     ) -> Result<Prompt> {
-        let source_instr = if source == "Auto Detect" {
+        self.execute_with_options(
+            config,
+            content,
+            source,
+            target,
+            &TranslateOptions::default(),
+        )
+        .await
+    }
+
+    /// Translate with glossary enforcement and translation memory reuse.
+    pub async fn execute_with_options(
+        &self,
+        config: &LLMConfig,
+        content: String,
+        source: String,
+        target: String,
+        options: &TranslateOptions,
+    ) -> Result<Prompt> {
+        let detected = if language_detection::is_auto_detect(&source) {
+            language_detection::detect_language(&content).filter(|lang| lang.reliable)
+        } else {
+            None
+        };
+        // Rules and memory lookups key on the resolved language when detection succeeds.
+        let effective_source = detected
+            .as_ref()
+            .map(|lang| lang.name.clone())
+            .unwrap_or_else(|| source.clone());
+
+        let source_instr = if language_detection::is_auto_detect(&effective_source) {
             "Detect the source language automatically".to_string()
         } else {
-            source.clone()
+            effective_source.clone()
         };
 
-        let system_prompt = format!(
+        let glossary_entries = self.repository.list_glossary_terms().await?;
+        let applicable = glossary::entries_in_text(
+            glossary::entries_for_pair(&glossary_entries, &effective_source, &target),
+            &content,
+        );
+
+        if options.use_translation_memory {
+            if let Some(prompt) = self
+                .translate_from_memory(
+                    &content,
+                    &source,
+                    &target,
+                    detected.as_ref(),
+                    &applicable,
+                    options,
+                )
+                .await?
+            {
+                return Ok(prompt);
+            }
+        }
+
+        let mut system_prompt = format!(
             "You are a professional translator. Translate the following text from {} to {}. Return ONLY the translated text. Do not include any explanations, notes, or quotation marks around the output unless they are in the original text.",
             source_instr, target
         );
+        if let Some(section) = glossary::build_prompt_section(&applicable) {
+            system_prompt.push_str(&section);
+        }
         let user_prompt = content.clone();
 
         let raw_result = self
@@ -46,13 +134,80 @@ impl TranslateUseCase {
             .generate(config, &system_prompt, &user_prompt)
             .await?;
 
-        let translated_text = clean_llm_response(&raw_result);
+        let (translated_text, violations) = glossary::enforce(
+            &applicable,
+            &clean_llm_response(&raw_result),
+            options.auto_correct_glossary,
+        );
+
+        let mut tm_entry = TranslationMemoryEntry {
+            id: None,
+            source_text: content.clone(),
+            target_text: translated_text.clone(),
+            source_lang: effective_source,
+            target_lang: target.clone(),
+            approved: false,
+            origin: "model".to_string(),
+            use_count: 0,
+            created_at: None,
+            updated_at: None,
+        };
+        if let Err(err) = self.repository.insert_tm_entry(&mut tm_entry).await {
+            tracing::warn!(error = %err, "Failed to record translation memory entry");
+        }
 
         let mut prompt = Prompt::new(content, source, target);
         prompt.result = Some(translated_text);
+        prompt.detected_lang = detected.map(|lang| lang.name);
+        prompt.glossary_violations = violations;
 
         self.repository.save_prompt(&mut prompt).await?;
 
         Ok(prompt)
     }
+
+    async fn translate_from_memory(
+        &self,
+        content: &str,
+        source: &str,
+        target: &str,
+        detected: Option<&DetectedLanguage>,
+        glossary_entries: &[&GlossaryEntry],
+        options: &TranslateOptions,
+    ) -> Result<Option<Prompt>> {
+        let min_similarity = translation_memory::DEFAULT_MIN_SIMILARITY;
+        let (min_len, max_len) = translation_memory::length_window(content, min_similarity);
+        let source_lang = match detected {
+            Some(lang) => Some(lang.name.as_str()),
+            None => (!language_detection::is_auto_detect(source)).then_some(source),
+        };
+
+        let candidates = self
+            .repository
+            .find_tm_candidates(source_lang, target, min_len, max_len, TM_CANDIDATE_LIMIT)
+            .await?;
+        let Some((entry, tm_match)) =
+            translation_memory::best_match(&candidates, content, min_similarity)
+        else {
+            return Ok(None);
+        };
+
+        self.repository.record_tm_use(tm_match.entry_id).await?;
+
+        let (translated_text, violations) = glossary::enforce(
+            glossary_entries,
+            &entry.target_text,
+            options.auto_correct_glossary,
+        );
+
+        let mut prompt = Prompt::new(content.to_string(), source.to_string(), target.to_string());
+        prompt.result = Some(translated_text);
+        prompt.detected_lang = detected.map(|lang| lang.name.clone());
+        prompt.glossary_violations = violations;
+        prompt.tm_match = Some(tm_match);
+
+        self.repository.save_prompt(&mut prompt).await?;
+
+        Ok(Some(prompt))
+    }
 }
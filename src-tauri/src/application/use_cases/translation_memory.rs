@@ -0,0 +1,151 @@
+//! Translation memory lookup
+//!
+//! Matches incoming text against approved translation memory entries:
+//! - Exact matches on whitespace/case-normalized source text
+//! - Fuzzy matches scored by normalized Levenshtein similarity
+
+pub use crate::domain::translation_memory::normalize;
+use crate::domain::translation_memory::{
+    TranslationMatchType, TranslationMemoryEntry, TranslationMemoryMatch,
+};
+
+/// Minimum similarity for a fuzzy match to be reused without calling the LLM.
+pub const DEFAULT_MIN_SIMILARITY: f32 = 0.9;
+
+/// Length window `(min, max)` in chars a candidate may have and still reach
+/// `min_similarity`; used to pre-filter candidates in SQL.
+pub fn length_window(text: &str, min_similarity: f32) -> (i64, i64) {
+    let len = normalize(text).chars().count() as f32;
+    let ratio = min_similarity.clamp(0.01, 1.0);
+    ((len * ratio).floor() as i64, (len / ratio).ceil() as i64)
+}
+
+/// Normalized Levenshtein similarity between two strings (1.0 = identical).
+pub fn similarity(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return 1.0;
+    }
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0usize; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    1.0 - prev[b.len()] as f32 / max_len as f32
+}
+
+/// Pick the best candidate at or above `min_similarity`.
+pub fn best_match<'a>(
+    candidates: &'a [TranslationMemoryEntry],
+    text: &str,
+    min_similarity: f32,
+) -> Option<(&'a TranslationMemoryEntry, TranslationMemoryMatch)> {
+    let normalized = normalize(text);
+    let text_len = normalized.chars().count();
+
+    let mut best: Option<(&TranslationMemoryEntry, f32)> = None;
+    for candidate in candidates {
+        if candidate.id.is_none() {
+            continue;
+        }
+        let other = normalize(&candidate.source_text);
+
+        // Length ratio is an upper bound on similarity; skip the DP when it
+        // cannot beat the threshold or the current best.
+        let other_len = other.chars().count();
+        let bound = text_len.min(other_len) as f32 / text_len.max(other_len).max(1) as f32;
+        let floor = best.map(|(_, score)| score).unwrap_or(min_similarity);
+        if bound < floor {
+            continue;
+        }
+
+        let score = similarity(&normalized, &other);
+        if score >= floor && best.map(|(_, s)| score > s).unwrap_or(true) {
+            best = Some((candidate, score));
+        }
+    }
+
+    best.map(|(entry, score)| {
+        let match_type = if score >= 1.0 {
+            TranslationMatchType::Exact
+        } else {
+            TranslationMatchType::Fuzzy
+        };
+        (
+            entry,
+            TranslationMemoryMatch {
+                entry_id: entry.id.unwrap_or_default(),
+                match_type,
+                similarity: score,
+                source_text: entry.source_text.clone(),
+                origin: entry.origin.clone(),
+            },
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: i64, source: &str, target: &str) -> TranslationMemoryEntry {
+        TranslationMemoryEntry {
+            id: Some(id),
+            source_text: source.to_string(),
+            target_text: target.to_string(),
+            source_lang: "English".to_string(),
+            target_lang: "Indonesian".to_string(),
+            approved: true,
+            origin: "human".to_string(),
+            use_count: 0,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_normalize_collapses_whitespace_and_case() {
+        assert_eq!(normalize("  Hello \n  World "), "hello world");
+    }
+
+    #[test]
+    fn test_similarity_bounds() {
+        assert_eq!(similarity("abc", "abc"), 1.0);
+        assert_eq!(similarity("", ""), 1.0);
+        assert_eq!(similarity("abc", "xyz"), 0.0);
+        assert!((similarity("kitten", "sitting") - (1.0 - 3.0 / 7.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_best_match_prefers_exact() {
+        let candidates = vec![
+            entry(1, "Save the invoice now", "Simpan faktur sekarang"),
+            entry(2, "Save the invoice", "Simpan faktur"),
+        ];
+        let (matched, provenance) = best_match(&candidates, "save the  invoice", 0.8).unwrap();
+        assert_eq!(matched.id, Some(2));
+        assert_eq!(provenance.match_type, TranslationMatchType::Exact);
+    }
+
+    #[test]
+    fn test_best_match_respects_threshold() {
+        let candidates = vec![entry(1, "Save the invoices", "Simpan faktur")];
+        let (_, provenance) = best_match(&candidates, "Save the invoice", 0.9).unwrap();
+        assert_eq!(provenance.match_type, TranslationMatchType::Fuzzy);
+        assert!(best_match(&candidates, "Delete every invoice", 0.9).is_none());
+    }
+
+    #[test]
+    fn test_length_window() {
+        assert_eq!(length_window("abcdefghij", 0.5), (5, 20));
+    }
+}
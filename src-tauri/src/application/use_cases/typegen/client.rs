@@ -0,0 +1,735 @@
+//! API client generation from recorded QA API calls
+//!
+//! Calls are grouped into endpoints by method and templated path (numeric,
+//! UUID and hash-like segments become path parameters). Request and response
+//! bodies of each endpoint are merged into one schema per direction, rendered
+//! with the offline typegen renderer and wrapped in a small client module.
+//! Recorded auth headers only decide which credentials the client accepts;
+//! their values are never copied into the output.
+
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::qa_api_call::QaApiCall;
+use crate::domain::typegen::ApiClientTarget;
+
+use super::language::TargetLanguage;
+use super::naming::{escape_string, sanitize_identifier, sanitize_type_name, unique_name};
+use super::renderer::TypeRenderer;
+use super::schema::{merge_schemas, Schema};
+use super::stream::infer_schema_from_str;
+
+const API_KEY_HEADERS: &[&str] = &["x-api-key", "api-key", "apikey", "x-auth-token"];
+
+#[derive(Default)]
+struct AuthUsage {
+    bearer: bool,
+    api_key_header: Option<String>,
+}
+
+struct Endpoint {
+    method: String,
+    /// Path segments; identifier-like segments are parameters.
+    template: Vec<PathSegment>,
+    query_keys: BTreeSet<String>,
+    request_schema: Option<Schema>,
+    response_schema: Option<Schema>,
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum PathSegment {
+    Literal(String),
+    Param(String),
+}
+
+/// Rendered endpoint names, resolved for the target language.
+struct EndpointNames {
+    function: String,
+    params: Vec<String>,
+    request_type: Option<String>,
+    response_type: Option<String>,
+}
+
+pub fn generate_api_client(calls: &[QaApiCall], target: ApiClientTarget) -> Result<String> {
+    let mut origins: BTreeMap<String, usize> = BTreeMap::new();
+    let mut auth = AuthUsage::default();
+    let mut grouped: BTreeMap<(Vec<PathSegment>, String), Vec<&QaApiCall>> = BTreeMap::new();
+    let mut recorded = 0;
+
+    for call in calls {
+        let Ok(url) = url::Url::parse(call.url.trim()) else {
+            continue;
+        };
+        *origins
+            .entry(url.origin().ascii_serialization())
+            .or_default() += 1;
+        collect_auth(call.request_headers_json.as_deref(), &mut auth);
+        let template = path_template(&url);
+        grouped
+            .entry((template, call.method.trim().to_uppercase()))
+            .or_default()
+            .push(call);
+        recorded += 1;
+    }
+
+    if grouped.is_empty() {
+        return Err(AppError::ValidationError(
+            "No recorded API calls with a valid URL to generate a client from.".to_string(),
+        ));
+    }
+
+    let base_url = origins
+        .into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(origin, _)| origin)
+        .unwrap_or_default();
+
+    let mut endpoints = Vec::with_capacity(grouped.len());
+    for ((template, method), calls) in grouped {
+        let mut query_keys = BTreeSet::new();
+        for call in &calls {
+            if let Ok(url) = url::Url::parse(call.url.trim()) {
+                query_keys.extend(url.query_pairs().map(|(key, _)| key.into_owned()));
+            }
+        }
+        let request_schema = merge_schemas(
+            calls
+                .iter()
+                .filter_map(|call| body_schema(call.request_body_json.as_deref())),
+        );
+        let response_schema = merge_schemas(
+            calls
+                .iter()
+                .filter(|call| {
+                    call.response_status
+                        .map_or(true, |s| (200..300).contains(&s))
+                })
+                .filter_map(|call| body_schema(call.response_body_json.as_deref())),
+        );
+        endpoints.push(Endpoint {
+            method,
+            template,
+            query_keys,
+            request_schema,
+            response_schema,
+        });
+    }
+
+    let language = match target {
+        ApiClientTarget::TypescriptFetch | ApiClientTarget::TypescriptAxios => {
+            TargetLanguage::TypeScript
+        }
+        ApiClientTarget::RustReqwest => TargetLanguage::Rust,
+    };
+
+    let mut renderer = TypeRenderer::new(language, "ApiClient").exported();
+    let mut used_functions = HashSet::new();
+    let mut used_types = HashSet::from(["ApiClient".to_string(), "ApiClientConfig".to_string()]);
+    let mut names = Vec::with_capacity(endpoints.len());
+    for endpoint in &endpoints {
+        let words = endpoint_words(endpoint);
+        let function = unique_name(sanitize_identifier(&words, language), &mut used_functions);
+        let mut used_params = HashSet::from(["body".to_string(), "query".to_string()]);
+        let params = endpoint
+            .template
+            .iter()
+            .filter_map(|segment| match segment {
+                PathSegment::Param(name) => Some(unique_name(
+                    sanitize_identifier(name, language),
+                    &mut used_params,
+                )),
+                PathSegment::Literal(_) => None,
+            })
+            .collect();
+
+        let mut type_for = |schema: &Option<Schema>, suffix: &str| {
+            schema.as_ref().map(|schema| {
+                let name = unique_name(
+                    sanitize_type_name(&format!("{} {}", words, suffix), language),
+                    &mut used_types,
+                );
+                renderer.add_root(schema, &name);
+                name
+            })
+        };
+        let request_type = type_for(&endpoint.request_schema, "request");
+        let response_type = type_for(&endpoint.response_schema, "response");
+        names.push(EndpointNames {
+            function,
+            params,
+            request_type,
+            response_type,
+        });
+    }
+    let types = renderer.finish();
+
+    let header = format!(
+        "// Generated from {} recorded API call(s); review before use.",
+        recorded
+    );
+    let client = match target {
+        ApiClientTarget::TypescriptFetch => {
+            render_ts_client(&endpoints, &names, &auth, &base_url, false)
+        }
+        ApiClientTarget::TypescriptAxios => {
+            render_ts_client(&endpoints, &names, &auth, &base_url, true)
+        }
+        ApiClientTarget::RustReqwest => render_rust_client(&endpoints, &names, &auth, &base_url),
+    };
+
+    Ok([header, types, client]
+        .into_iter()
+        .filter(|part| !part.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n"))
+}
+
+fn body_schema(body: Option<&str>) -> Option<Schema> {
+    let body = body?.trim();
+    if body.is_empty() {
+        return None;
+    }
+    // Truncated previews are not valid JSON and are skipped here.
+    infer_schema_from_str(body).ok()
+}
+
+fn collect_auth(headers_json: Option<&str>, auth: &mut AuthUsage) {
+    let Some(Value::Object(headers)) = headers_json.and_then(|raw| serde_json::from_str(raw).ok())
+    else {
+        return;
+    };
+    for (name, value) in headers {
+        let lower = name.to_ascii_lowercase();
+        if lower == "authorization" {
+            let is_bearer = value
+                .as_str()
+                .map(|v| v.trim_start().to_ascii_lowercase().starts_with("bearer "))
+                .unwrap_or(false);
+            auth.bearer |= is_bearer;
+        } else if API_KEY_HEADERS.contains(&lower.as_str()) && auth.api_key_header.is_none() {
+            auth.api_key_header = Some(name);
+        }
+    }
+}
+
+fn path_template(url: &url::Url) -> Vec<PathSegment> {
+    let mut segments = Vec::new();
+    let mut previous = "id".to_string();
+    for segment in url.path().split('/').filter(|s| !s.is_empty()) {
+        if is_identifier_segment(segment) {
+            let singular = previous.strip_suffix('s').unwrap_or(&previous);
+            segments.push(PathSegment::Param(format!("{} id", singular)));
+        } else {
+            previous = segment.to_string();
+            segments.push(PathSegment::Literal(segment.to_string()));
+        }
+    }
+    segments
+}
+
+/// Segments that look like record identifiers rather than route names.
+fn is_identifier_segment(segment: &str) -> bool {
+    if segment.chars().all(|ch| ch.is_ascii_digit()) {
+        return true;
+    }
+    let is_uuid = segment.len() == 36
+        && segment.char_indices().all(|(idx, ch)| match idx {
+            8 | 13 | 18 | 23 => ch == '-',
+            _ => ch.is_ascii_hexdigit(),
+        });
+    let is_hash = segment.len() >= 12
+        && segment.chars().all(|ch| ch.is_ascii_hexdigit())
+        && segment.chars().any(|ch| ch.is_ascii_digit());
+    is_uuid || is_hash
+}
+
+fn endpoint_words(endpoint: &Endpoint) -> String {
+    let mut words = vec![endpoint.method.to_ascii_lowercase()];
+    for segment in &endpoint.template {
+        match segment {
+            PathSegment::Literal(literal) => words.push(literal.clone()),
+            PathSegment::Param(name) => words.push(format!("by {}", name)),
+        }
+    }
+    if endpoint.template.is_empty() {
+        words.push("root".to_string());
+    }
+    words.join(" ")
+}
+
+fn path_label(endpoint: &Endpoint) -> String {
+    let path: String = endpoint
+        .template
+        .iter()
+        .map(|segment| match segment {
+            PathSegment::Literal(literal) => format!("/{}", literal),
+            PathSegment::Param(name) => format!("/{{{}}}", name.replace(' ', "_")),
+        })
+        .collect();
+    if path.is_empty() {
+        "/".to_string()
+    } else {
+        path
+    }
+}
+
+fn query_note(endpoint: &Endpoint) -> String {
+    if endpoint.query_keys.is_empty() {
+        return String::new();
+    }
+    let keys: Vec<&str> = endpoint.query_keys.iter().map(String::as_str).collect();
+    format!(" (query: {})", keys.join(", "))
+}
+
+/// Path expression with parameters interpolated in the target's syntax.
+fn path_expression(endpoint: &Endpoint, params: &[String], rust: bool) -> String {
+    let mut params = params.iter();
+    let mut out = String::new();
+    for segment in &endpoint.template {
+        out.push('/');
+        match segment {
+            PathSegment::Literal(literal) => out.push_str(literal),
+            PathSegment::Param(_) => {
+                let param = params.next().map(String::as_str).unwrap_or("id");
+                if rust {
+                    out.push_str(&format!("{{{}}}", param));
+                } else {
+                    out.push_str(&format!("${{encodeURIComponent(String({}))}}", param));
+                }
+            }
+        }
+    }
+    if out.is_empty() {
+        out.push('/');
+    }
+    out
+}
+
+fn render_ts_client(
+    endpoints: &[Endpoint],
+    names: &[EndpointNames],
+    auth: &AuthUsage,
+    base_url: &str,
+    axios: bool,
+) -> String {
+    let mut lines = Vec::new();
+    if axios {
+        lines.push("import axios, { AxiosInstance } from \"axios\";".to_string());
+        lines.push(String::new());
+    }
+
+    lines.push("export interface ApiClientConfig {".to_string());
+    lines.push("  baseUrl?: string;".to_string());
+    if auth.bearer {
+        lines.push("  token?: string;".to_string());
+    }
+    if auth.api_key_header.is_some() {
+        lines.push("  apiKey?: string;".to_string());
+    }
+    lines.push("}".to_string());
+    lines.push(String::new());
+
+    lines.push("export class ApiClient {".to_string());
+    if axios {
+        lines.push("  private readonly http: AxiosInstance;".to_string());
+    } else {
+        lines.push("  private readonly baseUrl: string;".to_string());
+    }
+    lines.push(String::new());
+    lines.push("  constructor(private readonly config: ApiClientConfig = {}) {".to_string());
+    let base = format!(
+        "config.baseUrl ?? \"{}\"",
+        escape_string(base_url.trim_end_matches('/'))
+    );
+    if axios {
+        lines.push("    this.http = axios.create({".to_string());
+        lines.push(format!("      baseURL: {},", base));
+        lines.push("      headers: this.headers(),".to_string());
+        lines.push("    });".to_string());
+    } else {
+        lines.push(format!("    this.baseUrl = {};", base));
+    }
+    lines.push("  }".to_string());
+    lines.push(String::new());
+
+    lines.push("  private headers(): Record<string, string> {".to_string());
+    lines.push(
+        "    const headers: Record<string, string> = { \"Content-Type\": \"application/json\" };"
+            .to_string(),
+    );
+    if auth.bearer {
+        lines.push("    if (this.config.token) {".to_string());
+        lines.push("      headers[\"Authorization\"] = `Bearer ${this.config.token}`;".to_string());
+        lines.push("    }".to_string());
+    }
+    if let Some(header) = &auth.api_key_header {
+        lines.push("    if (this.config.apiKey) {".to_string());
+        lines.push(format!(
+            "      headers[\"{}\"] = this.config.apiKey;",
+            escape_string(header)
+        ));
+        lines.push("    }".to_string());
+    }
+    lines.push("    return headers;".to_string());
+    lines.push("  }".to_string());
+    lines.push(String::new());
+
+    lines.push("  private async request<T>(".to_string());
+    lines.push("    method: string,".to_string());
+    lines.push("    path: string,".to_string());
+    lines.push("    body?: unknown,".to_string());
+    lines.push("    query?: Record<string, string>".to_string());
+    lines.push("  ): Promise<T> {".to_string());
+    if axios {
+        lines.push("    const { data } = await this.http.request<T>({".to_string());
+        lines.push("      method,".to_string());
+        lines.push("      url: path,".to_string());
+        lines.push("      data: body,".to_string());
+        lines.push("      params: query,".to_string());
+        lines.push("    });".to_string());
+        lines.push("    return data;".to_string());
+    } else {
+        lines.push(
+            "    const search = query ? `?${new URLSearchParams(query).toString()}` : \"\";"
+                .to_string(),
+        );
+        lines.push(
+            "    const response = await fetch(`${this.baseUrl}${path}${search}`, {".to_string(),
+        );
+        lines.push("      method,".to_string());
+        lines.push("      headers: this.headers(),".to_string());
+        lines
+            .push("      body: body === undefined ? undefined : JSON.stringify(body),".to_string());
+        lines.push("    });".to_string());
+        lines.push("    if (!response.ok) {".to_string());
+        lines.push(
+            "      throw new Error(`${method} ${path} failed with status ${response.status}`);"
+                .to_string(),
+        );
+        lines.push("    }".to_string());
+        lines.push("    const text = await response.text();".to_string());
+        lines.push("    return (text ? JSON.parse(text) : undefined) as T;".to_string());
+    }
+    lines.push("  }".to_string());
+
+    for (endpoint, names) in endpoints.iter().zip(names) {
+        let mut params: Vec<String> = names
+            .params
+            .iter()
+            .map(|param| format!("{}: string | number", param))
+            .collect();
+        let has_body = names.request_type.is_some();
+        if let Some(request_type) = &names.request_type {
+            params.push(format!("body: {}", request_type));
+        }
+        let has_query = !endpoint.query_keys.is_empty();
+        if has_query {
+            params.push("query?: Record<string, string>".to_string());
+        }
+        let response_type = names.response_type.as_deref().unwrap_or("unknown");
+        let args = [
+            Some(format!("\"{}\"", endpoint.method)),
+            Some(format!(
+                "`{}`",
+                path_expression(endpoint, &names.params, false)
+            )),
+            if has_body || has_query {
+                Some(if has_body { "body" } else { "undefined" }.to_string())
+            } else {
+                None
+            },
+            has_query.then(|| "query".to_string()),
+        ];
+
+        lines.push(String::new());
+        lines.push(format!(
+            "  /** {} {}{} */",
+            endpoint.method,
+            path_label(endpoint),
+            query_note(endpoint)
+        ));
+        lines.push(format!(
+            "  {}({}): Promise<{}> {{",
+            names.function,
+            params.join(", "),
+            response_type
+        ));
+        lines.push(format!(
+            "    return this.request<{}>({});",
+            response_type,
+            args.into_iter().flatten().collect::<Vec<_>>().join(", ")
+        ));
+        lines.push("  }".to_string());
+    }
+    lines.push("}".to_string());
+    lines.join("\n")
+}
+
+fn render_rust_client(
+    endpoints: &[Endpoint],
+    names: &[EndpointNames],
+    auth: &AuthUsage,
+    base_url: &str,
+) -> String {
+    let mut lines = vec![
+        "pub struct ApiClient {".to_string(),
+        "    http: reqwest::Client,".to_string(),
+        "    base_url: String,".to_string(),
+    ];
+    if auth.bearer {
+        lines.push("    token: Option<String>,".to_string());
+    }
+    if auth.api_key_header.is_some() {
+        lines.push("    api_key: Option<String>,".to_string());
+    }
+    lines.push("}".to_string());
+    lines.push(String::new());
+
+    lines.push("impl Default for ApiClient {".to_string());
+    lines.push("    fn default() -> Self {".to_string());
+    lines.push(format!(
+        "        Self::new(\"{}\")",
+        escape_string(base_url.trim_end_matches('/'))
+    ));
+    lines.push("    }".to_string());
+    lines.push("}".to_string());
+    lines.push(String::new());
+
+    lines.push("impl ApiClient {".to_string());
+    lines.push("    pub fn new(base_url: impl Into<String>) -> Self {".to_string());
+    lines.push("        Self {".to_string());
+    lines.push("            http: reqwest::Client::new(),".to_string());
+    lines.push("            base_url: base_url.into(),".to_string());
+    if auth.bearer {
+        lines.push("            token: None,".to_string());
+    }
+    if auth.api_key_header.is_some() {
+        lines.push("            api_key: None,".to_string());
+    }
+    lines.push("        }".to_string());
+    lines.push("    }".to_string());
+    if auth.bearer {
+        lines.push(String::new());
+        lines.push(
+            "    pub fn with_token(mut self, token: impl Into<String>) -> Self {".to_string(),
+        );
+        lines.push("        self.token = Some(token.into());".to_string());
+        lines.push("        self".to_string());
+        lines.push("    }".to_string());
+    }
+    if auth.api_key_header.is_some() {
+        lines.push(String::new());
+        lines.push(
+            "    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {".to_string(),
+        );
+        lines.push("        self.api_key = Some(api_key.into());".to_string());
+        lines.push("        self".to_string());
+        lines.push("    }".to_string());
+    }
+
+    lines.push(String::new());
+    lines.push(
+        "    async fn request<B: serde::Serialize + ?Sized, T: serde::de::DeserializeOwned>("
+            .to_string(),
+    );
+    lines.push("        &self,".to_string());
+    lines.push("        method: reqwest::Method,".to_string());
+    lines.push("        path: &str,".to_string());
+    lines.push("        body: Option<&B>,".to_string());
+    lines.push("        query: &[(&str, &str)],".to_string());
+    lines.push("    ) -> reqwest::Result<T> {".to_string());
+    lines.push("        let mut request = self".to_string());
+    lines.push("            .http".to_string());
+    lines.push("            .request(method, format!(\"{}{}\", self.base_url, path))".to_string());
+    lines.push("            .query(query);".to_string());
+    if auth.bearer {
+        lines.push("        if let Some(token) = &self.token {".to_string());
+        lines.push("            request = request.bearer_auth(token);".to_string());
+        lines.push("        }".to_string());
+    }
+    if let Some(header) = &auth.api_key_header {
+        lines.push("        if let Some(api_key) = &self.api_key {".to_string());
+        lines.push(format!(
+            "            request = request.header(\"{}\", api_key);",
+            escape_string(header)
+        ));
+        lines.push("        }".to_string());
+    }
+    lines.push("        if let Some(body) = body {".to_string());
+    lines.push("            request = request.json(body);".to_string());
+    lines.push("        }".to_string());
+    lines.push("        request.send().await?.error_for_status()?.json().await".to_string());
+    lines.push("    }".to_string());
+
+    for (endpoint, names) in endpoints.iter().zip(names) {
+        let mut params = vec!["&self".to_string()];
+        params.extend(
+            names
+                .params
+                .iter()
+                .map(|param| format!("{}: impl std::fmt::Display", param)),
+        );
+        if let Some(request_type) = &names.request_type {
+            params.push(format!("body: &{}", request_type));
+        }
+        let has_query = !endpoint.query_keys.is_empty();
+        if has_query {
+            params.push("query: &[(&str, &str)]".to_string());
+        }
+        let response_type = names
+            .response_type
+            .clone()
+            .unwrap_or_else(|| "serde_json::Value".to_string());
+        let path = path_expression(endpoint, &names.params, true);
+        let path_arg = if names.params.is_empty() {
+            format!("\"{}\"", path)
+        } else {
+            format!("&format!(\"{}\")", path)
+        };
+        let body_arg = if names.request_type.is_some() {
+            "Some(body)"
+        } else {
+            "None::<&()>"
+        };
+        let query_arg = if has_query { "query" } else { "&[]" };
+
+        lines.push(String::new());
+        lines.push(format!(
+            "    /// `{} {}`{}",
+            endpoint.method,
+            path_label(endpoint),
+            query_note(endpoint)
+        ));
+        lines.push(format!(
+            "    pub async fn {}({}) -> reqwest::Result<{}> {{",
+            names.function,
+            params.join(", "),
+            response_type
+        ));
+        lines.push(format!(
+            "        self.request(reqwest::Method::{}, {}, {}, {})",
+            rust_method(&endpoint.method),
+            path_arg,
+            body_arg,
+            query_arg
+        ));
+        lines.push("            .await".to_string());
+        lines.push("    }".to_string());
+    }
+    lines.push("}".to_string());
+    lines.join("\n")
+}
+
+fn rust_method(method: &str) -> String {
+    match method {
+        "GET" | "POST" | "PUT" | "DELETE" | "PATCH" | "HEAD" | "OPTIONS" => method.to_string(),
+        other => format!("from_bytes(b\"{}\").unwrap()", escape_string(other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(method: &str, url: &str, request: Option<&str>, response: Option<&str>) -> QaApiCall {
+        QaApiCall {
+            id: "id".to_string(),
+            session_id: "session".to_string(),
+            run_id: "run".to_string(),
+            method: method.to_string(),
+            url: url.to_string(),
+            request_headers_json: Some(
+                r#"{"Authorization": "Bearer secret-token", "Accept": "application/json"}"#
+                    .to_string(),
+            ),
+            request_body_json: request.map(str::to_string),
+            request_body_hash: None,
+            response_status: Some(200),
+            response_headers_json: None,
+            response_body_hash: None,
+            response_body_json: response.map(str::to_string),
+            timing_ms: None,
+            created_at: 0,
+        }
+    }
+
+    fn sample_calls() -> Vec<QaApiCall> {
+        vec![
+            call(
+                "GET",
+                "https://api.example.com/users/42",
+                None,
+                Some(r#"{"id": 42, "name": "a"}"#),
+            ),
+            call(
+                "GET",
+                "https://api.example.com/users/7",
+                None,
+                Some(r#"{"id": 7, "name": "b", "email": "b@x.io"}"#),
+            ),
+            call(
+                "POST",
+                "https://api.example.com/users?notify=true",
+                Some(r#"{"name": "c"}"#),
+                Some(r#"{"id": 8}"#),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_path_template_detects_identifiers() {
+        let url = url::Url::parse(
+            "https://x.io/orders/123/items/550e8400-e29b-41d4-a716-446655440000/v2",
+        )
+        .unwrap();
+        let template = path_template(&url);
+        assert!(matches!(&template[1], PathSegment::Param(name) if name == "order id"));
+        assert!(matches!(&template[3], PathSegment::Param(name) if name == "item id"));
+        assert!(matches!(&template[4], PathSegment::Literal(name) if name == "v2"));
+    }
+
+    #[test]
+    fn test_typescript_fetch_client() {
+        let out = generate_api_client(&sample_calls(), ApiClientTarget::TypescriptFetch).unwrap();
+        assert!(out.contains("export interface GetUsersByUserIdResponse {"));
+        assert!(out.contains("  email: string | null;"));
+        assert!(out.contains("export interface PostUsersRequest {"));
+        assert!(out.contains("config.baseUrl ?? \"https://api.example.com\""));
+        assert!(out.contains("headers[\"Authorization\"] = `Bearer ${this.config.token}`;"));
+        assert!(out.contains(
+            "  getUsersByUserId(userId: string | number): Promise<GetUsersByUserIdResponse> {"
+        ));
+        assert!(out.contains("`/users/${encodeURIComponent(String(userId))}`"));
+        assert!(out.contains("  /** POST /users (query: notify) */"));
+        assert!(out.contains("  postUsers(body: PostUsersRequest, query?: Record<string, string>)"));
+        assert!(!out.contains("secret-token"));
+    }
+
+    #[test]
+    fn test_typescript_axios_client() {
+        let out = generate_api_client(&sample_calls(), ApiClientTarget::TypescriptAxios).unwrap();
+        assert!(out.contains("import axios, { AxiosInstance } from \"axios\";"));
+        assert!(out.contains("      baseURL: config.baseUrl ?? \"https://api.example.com\","));
+    }
+
+    #[test]
+    fn test_rust_reqwest_client() {
+        let out = generate_api_client(&sample_calls(), ApiClientTarget::RustReqwest).unwrap();
+        assert!(out.contains("pub struct GetUsersByUserIdResponse {"));
+        assert!(out.contains(
+            "    pub async fn get_users_by_user_id(&self, user_id: impl std::fmt::Display) -> reqwest::Result<GetUsersByUserIdResponse> {"
+        ));
+        assert!(
+            out.contains("reqwest::Method::GET, &format!(\"/users/{user_id}\"), None::<&()>, &[])")
+        );
+        assert!(out.contains("            request = request.bearer_auth(token);"));
+        assert!(!out.contains("secret-token"));
+    }
+
+    #[test]
+    fn test_rejects_calls_without_urls() {
+        let calls = vec![call("GET", "not a url", None, None)];
+        assert!(generate_api_client(&calls, ApiClientTarget::TypescriptFetch).is_err());
+    }
+}
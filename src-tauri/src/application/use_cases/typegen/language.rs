@@ -6,6 +6,11 @@ pub(super) enum TargetLanguage {
     Dart,
     Java,
     Php,
+    Kotlin,
+    Swift,
+    CSharp,
+    PythonPydantic,
+    PythonDataclass,
 }
 
 impl TargetLanguage {
@@ -17,6 +22,11 @@ impl TargetLanguage {
             "dart" | "flutter" => Some(Self::Dart),
             "java" => Some(Self::Java),
             "php" => Some(Self::Php),
+            "kotlin" | "kt" => Some(Self::Kotlin),
+            "swift" => Some(Self::Swift),
+            "csharp" | "c#" | "cs" => Some(Self::CSharp),
+            "python" | "py" | "pydantic" => Some(Self::PythonPydantic),
+            "dataclass" | "python-dataclass" | "python_dataclass" => Some(Self::PythonDataclass),
             _ => None,
         }
     }
@@ -29,6 +39,12 @@ pub(super) fn avoid_keyword(name: String, language: TargetLanguage) -> String {
         TargetLanguage::Dart => is_dart_keyword(&name),
         TargetLanguage::Java => is_java_keyword(&name),
         TargetLanguage::Php => is_php_keyword(&name),
+        TargetLanguage::Kotlin => is_kotlin_keyword(&name),
+        TargetLanguage::Swift => is_swift_keyword(&name),
+        TargetLanguage::CSharp => is_csharp_keyword(&name),
+        TargetLanguage::PythonPydantic | TargetLanguage::PythonDataclass => {
+            is_python_keyword(&name)
+        }
         TargetLanguage::TypeScript => false,
     };
     if keyword {
@@ -265,6 +281,144 @@ fn is_java_keyword(name: &str) -> bool {
     )
 }
 
+fn is_kotlin_keyword(name: &str) -> bool {
+    matches!(
+        name,
+        "as" | "break"
+            | "class"
+            | "continue"
+            | "do"
+            | "else"
+            | "false"
+            | "for"
+            | "fun"
+            | "if"
+            | "in"
+            | "interface"
+            | "is"
+            | "null"
+            | "object"
+            | "package"
+            | "return"
+            | "super"
+            | "this"
+            | "throw"
+            | "true"
+            | "try"
+            | "typealias"
+            | "typeof"
+            | "val"
+            | "var"
+            | "when"
+            | "while"
+    )
+}
+
+fn is_swift_keyword(name: &str) -> bool {
+    matches!(
+        name,
+        "associatedtype"
+            | "class"
+            | "deinit"
+            | "enum"
+            | "extension"
+            | "fileprivate"
+            | "func"
+            | "import"
+            | "init"
+            | "inout"
+            | "internal"
+            | "let"
+            | "open"
+            | "operator"
+            | "private"
+            | "protocol"
+            | "public"
+            | "static"
+            | "struct"
+            | "subscript"
+            | "typealias"
+            | "var"
+            | "break"
+            | "case"
+            | "continue"
+            | "default"
+            | "defer"
+            | "do"
+            | "else"
+            | "fallthrough"
+            | "for"
+            | "guard"
+            | "if"
+            | "in"
+            | "repeat"
+            | "return"
+            | "switch"
+            | "where"
+            | "while"
+            | "as"
+            | "catch"
+            | "false"
+            | "is"
+            | "nil"
+            | "self"
+            | "Self"
+            | "super"
+            | "throw"
+            | "throws"
+            | "true"
+            | "try"
+            | "Type"
+    )
+}
+
+fn is_csharp_keyword(name: &str) -> bool {
+    // Properties are PascalCase, so only the contextual keywords that can
+    // clash after casing matter; lowercase keywords never collide.
+    matches!(name, "Equals" | "GetHashCode" | "GetType" | "ToString")
+}
+
+fn is_python_keyword(name: &str) -> bool {
+    matches!(
+        name,
+        "False"
+            | "None"
+            | "True"
+            | "and"
+            | "as"
+            | "assert"
+            | "async"
+            | "await"
+            | "break"
+            | "class"
+            | "continue"
+            | "def"
+            | "del"
+            | "elif"
+            | "else"
+            | "except"
+            | "finally"
+            | "for"
+            | "from"
+            | "global"
+            | "if"
+            | "import"
+            | "in"
+            | "is"
+            | "lambda"
+            | "nonlocal"
+            | "not"
+            | "or"
+            | "pass"
+            | "raise"
+            | "return"
+            | "try"
+            | "while"
+            | "with"
+            | "yield"
+    )
+}
+
 pub(super) fn box_java_primitive(input: &str) -> String {
     match input {
         "boolean" => "Boolean".to_string(),
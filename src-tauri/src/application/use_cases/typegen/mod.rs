@@ -1,22 +1,34 @@
 use crate::domain::error::{AppError, Result};
 use crate::domain::llm_config::LLMConfig;
-use crate::domain::typegen::TypeGenMode;
+use crate::domain::typegen::{
+    MissingFields, NamingConvention, NullableStrategy, TypeGenMode, TypeGenOptions,
+};
 use crate::infrastructure::llm_clients::LLMClient;
 use crate::infrastructure::response::clean_llm_response;
+use futures_util::future::join_all;
 use serde_json::Value;
 use std::sync::Arc;
 
+mod client;
 mod language;
 mod naming;
 mod renderer;
 mod schema;
+mod stream;
 
+pub use client::generate_api_client;
 use language::TargetLanguage;
 use naming::sanitize_type_name;
 use renderer::TypeRenderer;
-use schema::infer_schema;
+use schema::merge_schemas;
+use stream::{infer_schema_from_str, preview_from_str};
 
-const MAX_JSON_CHARS: usize = 16_384;
+/// Offline inference streams the input, so this only bounds request size.
+const MAX_JSON_CHARS: usize = 8 * 1024 * 1024;
+/// Budget for the JSON preview embedded in the LLM prompt.
+const MAX_LLM_JSON_CHARS: usize = 16_384;
+/// Distinct languages accepted by one batch request.
+const MAX_BATCH_LANGUAGES: usize = 16;
 
 pub struct TypeGenUseCase {
     llm_client: Arc<dyn LLMClient + Send + Sync>,
@@ -27,48 +39,138 @@ impl TypeGenUseCase {
         Self { llm_client }
     }
 
+    /// Generate types from `json_input` plus any `extra_samples` of the same
+    /// payload; all samples are merged into one schema before rendering.
     pub async fn execute(
         &self,
         config: &LLMConfig,
         json_input: String,
+        extra_samples: Vec<String>,
         language: String,
         root_name: String,
         mode: TypeGenMode,
+        options: TypeGenOptions,
     ) -> Result<String> {
-        let sanitized = strip_control_chars(&json_input);
-        if sanitized.len() > MAX_JSON_CHARS {
+        let input = PreparedInput::new(json_input, extra_samples, &root_name, mode)?;
+        self.generate(config, &input, language.trim(), mode, options)
+            .await
+    }
+
+    /// Generate every language in `languages` from the same samples. The
+    /// schema and the LLM preview are built once and shared; each distinct
+    /// language is then rendered on its own, concurrently, and fails on its
+    /// own. Results keep the order of first appearance.
+    pub async fn execute_batch(
+        &self,
+        config: &LLMConfig,
+        json_input: String,
+        extra_samples: Vec<String>,
+        languages: Vec<String>,
+        root_name: String,
+        mode: TypeGenMode,
+        options: TypeGenOptions,
+    ) -> Result<Vec<(String, Result<String>)>> {
+        let mut unique: Vec<String> = Vec::with_capacity(languages.len());
+        for language in languages {
+            let language = language.trim();
+            if !language.is_empty() && !unique.iter().any(|l| l == language) {
+                unique.push(language.to_string());
+            }
+        }
+        if unique.is_empty() {
+            return Err(AppError::ValidationError(
+                "No target languages provided.".to_string(),
+            ));
+        }
+        if unique.len() > MAX_BATCH_LANGUAGES {
             return Err(AppError::ValidationError(format!(
-                "JSON exceeds {} characters.",
-                MAX_JSON_CHARS
+                "At most {} languages can be generated at once.",
+                MAX_BATCH_LANGUAGES
             )));
         }
 
-        let parsed: Value = serde_json::from_str(&sanitized)
-            .map_err(|e| AppError::ValidationError(format!("Invalid JSON input: {}", e)))?;
-        let schema = infer_schema(&parsed);
+        let input = PreparedInput::new(json_input, extra_samples, &root_name, mode)?;
+        let outputs = join_all(
+            unique
+                .iter()
+                .map(|language| self.generate(config, &input, language, mode, options)),
+        )
+        .await;
+        Ok(unique.into_iter().zip(outputs).collect())
+    }
+
+    async fn generate(
+        &self,
+        config: &LLMConfig,
+        input: &PreparedInput,
+        language: &str,
+        mode: TypeGenMode,
+        options: TypeGenOptions,
+    ) -> Result<String> {
+        let Some(pretty_json) = input.llm_json.as_deref() else {
+            return generate_offline(&input.schema, &input.root, language, options);
+        };
+
+        let llm_result =
+            generate_llm(self, config, pretty_json, language, &input.root, options).await;
+        match llm_result {
+            Ok(result) => Ok(result),
+            Err(llm_err) if mode == TypeGenMode::Auto => {
+                generate_offline(&input.schema, &input.root, language, options).map_err(|_| llm_err)
+            }
+            Err(llm_err) => Err(llm_err),
+        }
+    }
+}
+
+/// Samples checked and merged once, shared by every language rendered
+/// from them.
+struct PreparedInput {
+    schema: schema::Schema,
+    root: String,
+    /// Prompt preview of the samples; `None` in offline mode
+    llm_json: Option<String>,
+}
+
+impl PreparedInput {
+    fn new(
+        json_input: String,
+        extra_samples: Vec<String>,
+        root_name: &str,
+        mode: TypeGenMode,
+    ) -> Result<Self> {
+        let mut samples = Vec::with_capacity(extra_samples.len() + 1);
+        for (idx, input) in std::iter::once(json_input).chain(extra_samples).enumerate() {
+            if idx > 0 && input.trim().is_empty() {
+                continue;
+            }
+            samples.push(sanitize_sample(&input, idx)?);
+        }
+
+        let mut schemas = Vec::with_capacity(samples.len());
+        for (idx, sample) in samples.iter().enumerate() {
+            let schema = infer_schema_from_str(sample).map_err(|e| invalid_sample(idx, e))?;
+            schemas.push(schema);
+        }
+        let schema = merge_schemas(schemas)
+            .ok_or_else(|| AppError::ValidationError("No JSON samples provided.".to_string()))?;
 
-        let pretty_json = serde_json::to_string_pretty(&parsed).unwrap_or_else(|_| sanitized);
         let root = if root_name.trim().is_empty() {
             "Root".to_string()
         } else {
             root_name.trim().to_string()
         };
-        let language = language.trim().to_string();
-
-        match mode {
-            TypeGenMode::Offline => generate_offline(&schema, &root, &language),
-            TypeGenMode::Llm => generate_llm(self, config, &pretty_json, &language, &root).await,
-            TypeGenMode::Auto => {
-                let llm_result = generate_llm(self, config, &pretty_json, &language, &root).await;
-                match llm_result {
-                    Ok(result) => Ok(result),
-                    Err(llm_err) => match generate_offline(&schema, &root, &language) {
-                        Ok(result) => Ok(result),
-                        Err(_) => Err(llm_err),
-                    },
-                }
-            }
-        }
+
+        let llm_json = match mode {
+            TypeGenMode::Offline => None,
+            TypeGenMode::Llm | TypeGenMode::Auto => Some(build_llm_json(&samples)?),
+        };
+
+        Ok(Self {
+            schema,
+            root,
+            llm_json,
+        })
     }
 }
 
@@ -78,8 +180,9 @@ async fn generate_llm(
     pretty_json: &str,
     language: &str,
     root_name: &str,
+    options: TypeGenOptions,
 ) -> Result<String> {
-    let system_prompt = build_system_prompt(language, root_name);
+    let system_prompt = build_system_prompt(language, root_name, options);
     let user_prompt = format!("JSON:\n{}", pretty_json);
 
     let raw_result = use_case
@@ -90,7 +193,12 @@ async fn generate_llm(
     Ok(clean_llm_response(&raw_result))
 }
 
-fn generate_offline(schema: &schema::Schema, root_name: &str, language: &str) -> Result<String> {
+fn generate_offline(
+    schema: &schema::Schema,
+    root_name: &str,
+    language: &str,
+    options: TypeGenOptions,
+) -> Result<String> {
     let lang = TargetLanguage::parse(language).ok_or_else(|| {
         AppError::ValidationError(format!(
             "Unsupported language for offline mode: {}",
@@ -98,15 +206,110 @@ fn generate_offline(schema: &schema::Schema, root_name: &str, language: &str) ->
         ))
     })?;
     let root_name = sanitize_type_name(root_name, lang);
-    let renderer = TypeRenderer::new(lang, &root_name);
+    let renderer = TypeRenderer::new(lang, &root_name).with_options(options);
     Ok(renderer.render(schema, &root_name))
 }
 
+fn sanitize_sample(input: &str, idx: usize) -> Result<String> {
+    let sanitized = strip_control_chars(input);
+    if sanitized.len() > MAX_JSON_CHARS {
+        return Err(AppError::ValidationError(format!(
+            "JSON sample {} exceeds {} characters.",
+            idx + 1,
+            MAX_JSON_CHARS
+        )));
+    }
+    Ok(sanitized)
+}
+
+fn invalid_sample(idx: usize, err: serde_json::Error) -> AppError {
+    AppError::ValidationError(format!("Invalid JSON input in sample {}: {}", idx + 1, err))
+}
+
+/// Pretty-printed previews of all samples (large arrays and strings sampled),
+/// cut to `MAX_LLM_JSON_CHARS` so big captures still fit the prompt.
+fn build_llm_json(samples: &[String]) -> Result<String> {
+    let mut previews = Vec::with_capacity(samples.len());
+    for (idx, sample) in samples.iter().enumerate() {
+        let preview = preview_from_str(sample).map_err(|e| invalid_sample(idx, e))?;
+        let pretty = serde_json::to_string_pretty(&preview).unwrap_or_default();
+        previews.push(if samples.len() == 1 {
+            pretty
+        } else {
+            format!("// Sample {}\n{}", idx + 1, pretty)
+        });
+    }
+
+    let joined = previews.join("\n\n");
+    if joined.len() <= MAX_LLM_JSON_CHARS {
+        return Ok(joined);
+    }
+    let mut cut = MAX_LLM_JSON_CHARS;
+    while !joined.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    Ok(format!("{}\n// ... truncated", &joined[..cut]))
+}
+
 fn strip_control_chars(input: &str) -> String {
     input.chars().filter(|ch| (*ch as u32) >= 0x20).collect()
 }
 
-fn build_system_prompt(language: &str, root_name: &str) -> String {
+fn build_system_prompt(language: &str, root_name: &str, options: TypeGenOptions) -> String {
+    let helper_rule = if options.types_only() {
+        "- Do not include parsing, constructors, or helper functions.".to_string()
+    } else {
+        let mut helpers = Vec::new();
+        if options.serialization_helpers {
+            helpers.push("Dart: a fromJson factory and a toJson method on every class");
+            helpers.push(
+                "Rust: impl TryFrom<serde_json::Value> and impl From<Type> for serde_json::Value",
+            );
+        }
+        if options.builders {
+            helpers.push(
+                "Java: a no-args constructor, an all-args constructor and a static Builder class",
+            );
+        }
+        format!(
+            "- Include only these helpers for the matching language: {}. No other parsing or helper functions.",
+            helpers.join("; ")
+        )
+    };
+
+    let missing_rule = match options.missing_fields {
+        MissingFields::Optional => "make fields optional when they are missing or null in any sample",
+        MissingFields::Required => {
+            "keep fields missing from some samples required; make fields optional only when they are null in a sample"
+        }
+    };
+    let nullable_rule = match options.nullable {
+        NullableStrategy::Native => "- Use nullable/optional types when JSON values can be null.",
+        NullableStrategy::Default => {
+            "- Use plain (non-nullable) types for values that can be null and default them when absent: #[serde(default)] in Rust, zero values in Go, no `| null` in TypeScript. Other languages use their nullable types."
+        }
+        NullableStrategy::Undefined => {
+            "- Use nullable/optional types when JSON values can be null; in TypeScript, mark such properties optional (`name?: T`) instead of `T | null`."
+        }
+        NullableStrategy::Optional => {
+            "- Use nullable/optional types when JSON values can be null; in Java, use java.util.Optional<T> for such fields."
+        }
+    };
+    let naming_rule = match options.field_naming {
+        None => "- Use idiomatic naming for types in the target language.".to_string(),
+        Some(convention) => {
+            let case = match convention {
+                NamingConvention::CamelCase => "camelCase",
+                NamingConvention::PascalCase => "PascalCase",
+                NamingConvention::SnakeCase => "snake_case",
+            };
+            format!(
+                "- Use idiomatic naming for types in the target language and {} field names, except for Go (exported PascalCase fields) and TypeScript (JSON names).",
+                case
+            )
+        }
+    };
+
     format!(
         "You are a code generator. Generate type definitions from a JSON response.\n\
 Target language: {language}\n\
@@ -114,11 +317,12 @@ Root type name: {root_name}\n\
 Requirements:\n\
 - Output ONLY code. No markdown, no explanations.\n\
 - Define all nested object and array types.\n\
-- Use idiomatic naming for types in the target language.\n\
+- When several samples are given, they describe the same payload: merge them, and {missing_rule}.\n\
+{naming_rule}\n\
 - Preserve JSON field names. If a field name is not a valid identifier, use a safe identifier and add a mapping using the target language's conventions.\n\
-- Use nullable/optional types when JSON values can be null.\n\
+{nullable_rule}\n\
 - Treat all JSON strings as plain string types; do not infer date/time types.\n\
-- Do not include parsing, constructors, or helper functions.\n\
+{helper_rule}\n\
 Language rules:\n\
 - Go: use struct types and json tags.\n\
 - Rust: use struct types with serde derive and serde rename when needed.\n\
@@ -127,6 +331,11 @@ Language rules:\n\
 - Flutter: use Dart class types with typed fields only; if needed, add a comment like: // json: \"original\".\n\
 - Java: use class types with public fields; use List<T> for arrays and boxed types when needed.\n\
 - PHP: use class types with typed public properties; if needed, add a comment like: // json: \"original\".\n\
+- Kotlin: use kotlinx.serialization @Serializable data classes with @SerialName on every property.\n\
+- Swift: use Codable structs with let properties and a CodingKeys enum when names differ.\n\
+- C#: use records with init-only properties and System.Text.Json [JsonPropertyName] attributes.\n\
+- Python (pydantic): use BaseModel classes with Field(alias=...) when names differ.\n\
+- Python (dataclass): use @dataclass classes; if needed, add a comment like: # json: \"original\".\n\
 "
     )
 }
@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use crate::domain::typegen::NamingConvention;
+
 use super::language::{avoid_keyword, TargetLanguage};
 
 pub(super) fn sanitize_type_name(input: &str, language: TargetLanguage) -> String {
@@ -18,13 +20,21 @@ pub(super) fn sanitize_type_name(input: &str, language: TargetLanguage) -> Strin
 }
 
 pub(super) fn sanitize_identifier(input: &str, language: TargetLanguage) -> String {
-    let base = match language {
-        TargetLanguage::Go => to_pascal_case(input),
-        TargetLanguage::Rust => to_snake_case(input),
-        TargetLanguage::TypeScript => to_lower_camel(input),
-        TargetLanguage::Dart => to_lower_camel(input),
-        TargetLanguage::Java => to_lower_camel(input),
-        TargetLanguage::Php => to_lower_camel(input),
+    sanitize_identifier_as(input, language, None)
+}
+
+/// Like `sanitize_identifier`, in `convention` instead of the language's
+/// own when one is given.
+pub(super) fn sanitize_identifier_as(
+    input: &str,
+    language: TargetLanguage,
+    convention: Option<NamingConvention>,
+) -> String {
+    let base = match convention {
+        Some(NamingConvention::CamelCase) => to_lower_camel(input),
+        Some(NamingConvention::PascalCase) => to_pascal_case(input),
+        Some(NamingConvention::SnakeCase) => to_snake_case(input),
+        None => language_case(input, language),
     };
 
     let base = if base.is_empty() {
@@ -40,6 +50,21 @@ pub(super) fn sanitize_identifier(input: &str, language: TargetLanguage) -> Stri
     avoid_keyword(base, language)
 }
 
+fn language_case(input: &str, language: TargetLanguage) -> String {
+    match language {
+        TargetLanguage::Go => to_pascal_case(input),
+        TargetLanguage::Rust => to_snake_case(input),
+        TargetLanguage::TypeScript => to_lower_camel(input),
+        TargetLanguage::Dart => to_lower_camel(input),
+        TargetLanguage::Java => to_lower_camel(input),
+        TargetLanguage::Php => to_lower_camel(input),
+        TargetLanguage::Kotlin => to_lower_camel(input),
+        TargetLanguage::Swift => to_lower_camel(input),
+        TargetLanguage::CSharp => to_pascal_case(input),
+        TargetLanguage::PythonPydantic | TargetLanguage::PythonDataclass => to_snake_case(input),
+    }
+}
+
 pub(super) fn unique_name(base: String, used: &mut HashSet<String>) -> String {
     if !used.contains(&base) {
         used.insert(base.clone());
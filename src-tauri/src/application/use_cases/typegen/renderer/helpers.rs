@@ -0,0 +1,176 @@
+use super::super::schema::{Schema, SchemaKind};
+use super::TypeRenderer;
+
+impl TypeRenderer {
+    pub(super) fn render_rust_value_conversions(&self, name: &str) -> String {
+        [
+            format!("impl TryFrom<Value> for {} {{", name),
+            "    type Error = serde_json::Error;".to_string(),
+            String::new(),
+            "    fn try_from(value: Value) -> Result<Self, Self::Error> {".to_string(),
+            "        serde_json::from_value(value)".to_string(),
+            "    }".to_string(),
+            "}".to_string(),
+            String::new(),
+            format!("impl From<{}> for Value {{", name),
+            format!("    fn from(value: {}) -> Self {{", name),
+            "        serde_json::to_value(value).unwrap_or(Value::Null)".to_string(),
+            "    }".to_string(),
+            "}".to_string(),
+        ]
+        .join("\n")
+    }
+
+    /// `fromJson` factory and `toJson` method for a Dart class whose fields
+    /// are given as `(json key, field name, schema)`.
+    pub(super) fn render_dart_json_methods(
+        &mut self,
+        name: &str,
+        members: &[(&str, String, &Schema)],
+    ) -> Vec<String> {
+        let mut lines = vec![format!("  {}();", name), String::new()];
+
+        if members.is_empty() {
+            lines.push(format!(
+                "  factory {}.fromJson(Map<String, dynamic> json) => {}();",
+                name, name
+            ));
+            lines.push(String::new());
+            lines.push("  Map<String, dynamic> toJson() => <String, dynamic>{};".to_string());
+            return lines;
+        }
+
+        lines.push(format!(
+            "  factory {}.fromJson(Map<String, dynamic> json) => {}()",
+            name, name
+        ));
+        let last = members.len() - 1;
+        for (idx, (key, field_name, schema)) in members.iter().enumerate() {
+            let access = format!("json[{}]", dart_string(key));
+            let value = self.dart_from_json(schema, name, key, false, &access);
+            let end = if idx == last { ";" } else { "" };
+            lines.push(format!("    ..{} = {}{}", field_name, value, end));
+        }
+
+        lines.push(String::new());
+        lines.push("  Map<String, dynamic> toJson() => <String, dynamic>{".to_string());
+        for (key, field_name, schema) in members {
+            let value = dart_to_json(schema, field_name, 0);
+            lines.push(format!("        {}: {},", dart_string(key), value));
+        }
+        lines.push("      };".to_string());
+        lines
+    }
+
+    fn dart_from_json(
+        &mut self,
+        schema: &Schema,
+        parent: &str,
+        key: &str,
+        in_array: bool,
+        expr: &str,
+    ) -> String {
+        let q = if schema.nullable { "?" } else { "" };
+        match &schema.kind {
+            SchemaKind::Bool => format!("{} as bool{}", expr, q),
+            SchemaKind::Int => format!("{} as int{}", expr, q),
+            SchemaKind::Float => format!("({} as num{}){}.toDouble()", expr, q, q),
+            SchemaKind::String => format!("{} as String{}", expr, q),
+            SchemaKind::Dynamic | SchemaKind::Null => expr.to_string(),
+            SchemaKind::Array(inner) => {
+                let item = self.dart_from_json(inner, parent, key, true, "e");
+                format!(
+                    "({} as List<dynamic>{}){}.map((e) => {}).toList()",
+                    expr, q, q, item
+                )
+            }
+            SchemaKind::Object(_) => {
+                let type_name = self
+                    .registry
+                    .name_for_field(parent, key, in_array, self.language);
+                let parsed = format!("{}.fromJson({} as Map<String, dynamic>)", type_name, expr);
+                if schema.nullable {
+                    format!("{} == null ? null : {}", expr, parsed)
+                } else {
+                    parsed
+                }
+            }
+        }
+    }
+}
+
+fn dart_to_json(schema: &Schema, expr: &str, depth: usize) -> String {
+    let access = if schema.nullable { "?." } else { "." };
+    match &schema.kind {
+        SchemaKind::Object(_) => format!("{}{}toJson()", expr, access),
+        SchemaKind::Array(inner) => {
+            let item_var = if depth == 0 {
+                "e".to_string()
+            } else {
+                format!("e{}", depth)
+            };
+            let item = dart_to_json(inner, &item_var, depth + 1);
+            if item == item_var {
+                expr.to_string()
+            } else {
+                format!("{}{}map(({}) => {}).toList()", expr, access, item_var, item)
+            }
+        }
+        _ => expr.to_string(),
+    }
+}
+
+fn dart_string(input: &str) -> String {
+    let escaped = input
+        .replace('\\', "\\\\")
+        .replace('\'', "\\'")
+        .replace('$', "\\$");
+    format!("'{}'", escaped)
+}
+
+/// All-args constructor plus a fluent `Builder` for a Java class whose fields
+/// are given as `(field name, field type)`.
+pub(super) fn render_java_builders(name: &str, members: &[(String, String)]) -> Vec<String> {
+    let mut lines = vec![format!("  public {}() {{}}", name)];
+
+    if !members.is_empty() {
+        let params: Vec<String> = members
+            .iter()
+            .map(|(field, ty)| format!("{} {}", ty, field))
+            .collect();
+        lines.push(String::new());
+        lines.push(format!("  public {}({}) {{", name, params.join(", ")));
+        for (field, _) in members {
+            lines.push(format!("    this.{} = {};", field, field));
+        }
+        lines.push("  }".to_string());
+    }
+
+    lines.push(String::new());
+    lines.push("  public static Builder builder() {".to_string());
+    lines.push("    return new Builder();".to_string());
+    lines.push("  }".to_string());
+    lines.push(String::new());
+    lines.push("  public static class Builder {".to_string());
+    for (field, ty) in members {
+        lines.push(format!("    private {} {};", ty, field));
+    }
+    for (field, ty) in members {
+        lines.push(String::new());
+        lines.push(format!("    public Builder {}({} {}) {{", field, ty, field));
+        lines.push(format!("      this.{} = {};", field, field));
+        lines.push("      return this;".to_string());
+        lines.push("    }".to_string());
+    }
+    let args: Vec<&str> = members.iter().map(|(field, _)| field.as_str()).collect();
+    lines.push(String::new());
+    lines.push(format!("    public {} build() {{", name));
+    if members.is_empty() {
+        lines.push(format!("      return new {}();", name));
+    } else {
+        lines.push(format!("      return new {}({});", name, args.join(", ")));
+    }
+    lines.push("    }".to_string());
+    lines.push("  }".to_string());
+    lines
+}
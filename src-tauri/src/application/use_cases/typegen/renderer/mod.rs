@@ -1,38 +1,112 @@
 use std::collections::{BTreeMap, HashSet};
 
+use crate::domain::typegen::{NullableStrategy, TypeGenOptions};
+
 use super::language::TargetLanguage;
-use super::naming::{escape_string, sanitize_identifier, ts_property_name, unique_name};
-use super::schema::{find_object_schema, root_type_key, Schema, SchemaKind};
+use super::naming::{escape_string, sanitize_identifier_as, ts_property_name, unique_name};
+use super::schema::{apply_missing_fields, find_object_schema, root_type_key, Schema, SchemaKind};
 
+mod helpers;
 mod registry;
 mod type_mapping;
 
+use helpers::render_java_builders;
 use registry::TypeNameRegistry;
 
 pub(super) struct TypeRenderer {
     pub(super) language: TargetLanguage,
+    options: TypeGenOptions,
+    export_types: bool,
     registry: TypeNameRegistry,
     emitted: HashSet<String>,
     definitions: Vec<String>,
     pub(super) uses_dynamic: bool,
     pub(super) uses_structs: bool,
     pub(super) uses_list: bool,
+    pub(super) uses_optional: bool,
+    uses_alias: bool,
 }
 
+const SWIFT_JSON_VALUE: &str = "enum JSONValue: Codable {
+    case string(String)
+    case int(Int)
+    case double(Double)
+    case bool(Bool)
+    case array([JSONValue])
+    case object([String: JSONValue])
+    case null
+
+    init(from decoder: Decoder) throws {
+        let container = try decoder.singleValueContainer()
+        if container.decodeNil() {
+            self = .null
+        } else if let value = try? container.decode(Bool.self) {
+            self = .bool(value)
+        } else if let value = try? container.decode(Int.self) {
+            self = .int(value)
+        } else if let value = try? container.decode(Double.self) {
+            self = .double(value)
+        } else if let value = try? container.decode(String.self) {
+            self = .string(value)
+        } else if let value = try? container.decode([JSONValue].self) {
+            self = .array(value)
+        } else {
+            self = .object(try container.decode([String: JSONValue].self))
+        }
+    }
+
+    func encode(to encoder: Encoder) throws {
+        var container = encoder.singleValueContainer()
+        switch self {
+        case .string(let value): try container.encode(value)
+        case .int(let value): try container.encode(value)
+        case .double(let value): try container.encode(value)
+        case .bool(let value): try container.encode(value)
+        case .array(let value): try container.encode(value)
+        case .object(let value): try container.encode(value)
+        case .null: try container.encodeNil()
+        }
+    }
+}";
+
 impl TypeRenderer {
     pub(super) fn new(language: TargetLanguage, root_name: &str) -> Self {
         Self {
             language,
+            options: TypeGenOptions::default(),
+            export_types: false,
             registry: TypeNameRegistry::new(root_name),
             emitted: HashSet::new(),
             definitions: Vec::new(),
             uses_dynamic: false,
             uses_structs: false,
             uses_list: false,
+            uses_optional: false,
+            uses_alias: false,
         }
     }
 
+    pub(super) fn with_options(mut self, options: TypeGenOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Prefix TypeScript declarations with `export` for use in a module.
+    pub(super) fn exported(mut self) -> Self {
+        self.export_types = true;
+        self
+    }
+
     pub(super) fn render(mut self, schema: &Schema, root_name: &str) -> String {
+        self.add_root(schema, root_name);
+        self.finish()
+    }
+
+    /// Emit the definitions for another root type, so several roots can share
+    /// one output and import header.
+    pub(super) fn add_root(&mut self, schema: &Schema, root_name: &str) {
+        let schema = &apply_missing_fields(schema.clone(), self.options.missing_fields);
+        self.registry.reserve(root_name);
         let type_key = root_type_key(schema);
         match &schema.kind {
             SchemaKind::Object(_) => {
@@ -49,14 +123,16 @@ impl TypeRenderer {
                 self.definitions.push(root_alias);
             }
         }
+    }
 
+    pub(super) fn finish(mut self) -> String {
         let mut output = String::new();
         match self.language {
             TargetLanguage::Rust => {
                 if self.uses_structs {
                     output.push_str("use serde::{Deserialize, Serialize};\n");
                 }
-                if self.uses_dynamic {
+                if self.uses_dynamic || self.options.serialization_helpers {
                     output.push_str("use serde_json::Value;\n");
                 }
                 if !output.is_empty() {
@@ -65,13 +141,73 @@ impl TypeRenderer {
             }
             TargetLanguage::Java => {
                 if self.uses_list {
-                    output.push_str("import java.util.List;\n\n");
+                    output.push_str("import java.util.List;\n");
+                }
+                if self.uses_optional {
+                    output.push_str("import java.util.Optional;\n");
+                }
+                if !output.is_empty() {
+                    output.push('\n');
+                }
+            }
+            TargetLanguage::Kotlin => {
+                if self.uses_structs {
+                    output.push_str("import kotlinx.serialization.SerialName\n");
+                    output.push_str("import kotlinx.serialization.Serializable\n");
+                }
+                if self.uses_dynamic {
+                    output.push_str("import kotlinx.serialization.json.JsonElement\n");
+                }
+                if !output.is_empty() {
+                    output.push('\n');
+                }
+            }
+            TargetLanguage::Swift => {
+                output.push_str("import Foundation\n\n");
+                if self.uses_dynamic {
+                    self.definitions.push(SWIFT_JSON_VALUE.to_string());
+                }
+            }
+            TargetLanguage::CSharp => {
+                if self.uses_list {
+                    output.push_str("using System.Collections.Generic;\n");
+                }
+                if self.uses_dynamic {
+                    output.push_str("using System.Text.Json;\n");
+                }
+                output.push_str("using System.Text.Json.Serialization;\n\n");
+            }
+            TargetLanguage::PythonPydantic | TargetLanguage::PythonDataclass => {
+                output.push_str("from __future__ import annotations\n\n");
+                if matches!(self.language, TargetLanguage::PythonDataclass) && self.uses_structs {
+                    output.push_str("from dataclasses import dataclass\n");
+                }
+                let typing: Vec<&str> = [
+                    (self.uses_dynamic, "Any"),
+                    (self.uses_list, "List"),
+                    (self.uses_optional, "Optional"),
+                ]
+                .into_iter()
+                .filter(|(used, _)| *used)
+                .map(|(_, name)| name)
+                .collect();
+                if !typing.is_empty() {
+                    output.push_str(&format!("from typing import {}\n", typing.join(", ")));
+                }
+                if matches!(self.language, TargetLanguage::PythonPydantic) && self.uses_structs {
+                    if self.uses_alias {
+                        output.push_str("\nfrom pydantic import BaseModel, Field\n");
+                    } else {
+                        output.push_str("\nfrom pydantic import BaseModel\n");
+                    }
                 }
+                output.push('\n');
             }
             _ => {}
         }
 
-        output.push_str(&self.definitions.join("\n\n"));
+        let separator = if self.is_python() { "\n\n\n" } else { "\n\n" };
+        output.push_str(&self.definitions.join(separator));
         output
     }
 
@@ -105,9 +241,49 @@ impl TypeRenderer {
             TargetLanguage::Dart => self.render_dart_class(name, fields),
             TargetLanguage::Java => self.render_java_class(name, fields),
             TargetLanguage::Php => self.render_php_class(name, fields),
+            TargetLanguage::Kotlin => self.render_kotlin_data_class(name, fields),
+            TargetLanguage::Swift => self.render_swift_struct(name, fields),
+            TargetLanguage::CSharp => self.render_csharp_record(name, fields),
+            TargetLanguage::PythonPydantic | TargetLanguage::PythonDataclass => {
+                self.render_python_class(name, fields)
+            }
+        }
+    }
+
+    /// Field name for the JSON `key`, in the requested naming convention
+    fn field_identifier(&self, key: &str) -> String {
+        let convention = self
+            .options
+            .field_naming
+            .filter(|_| !matches!(self.language, TargetLanguage::Go));
+        sanitize_identifier_as(key, self.language, convention)
+    }
+
+    /// How a nullable value is typed; array items keep the native type,
+    /// as a default can't stand in for a null item
+    pub(super) fn nullable_strategy(&self, in_array: bool) -> NullableStrategy {
+        if in_array {
+            NullableStrategy::Native
+        } else {
+            self.options.nullable
         }
     }
 
+    fn ts_export(&self) -> &'static str {
+        if self.export_types {
+            "export "
+        } else {
+            ""
+        }
+    }
+
+    fn is_python(&self) -> bool {
+        matches!(
+            self.language,
+            TargetLanguage::PythonPydantic | TargetLanguage::PythonDataclass
+        )
+    }
+
     fn render_root_alias(&mut self, schema: &Schema, root_name: &str, type_key: &str) -> String {
         match self.language {
             TargetLanguage::Go => {
@@ -120,7 +296,7 @@ impl TypeRenderer {
             }
             TargetLanguage::TypeScript => {
                 let alias_type = self.ts_type(schema, root_name, type_key, false);
-                format!("type {} = {};", root_name, alias_type)
+                format!("{}type {} = {};", self.ts_export(), root_name, alias_type)
             }
             TargetLanguage::Dart => {
                 let mut lines = vec![format!("class {} {{", root_name)];
@@ -148,6 +324,25 @@ impl TypeRenderer {
                 lines.push("}".to_string());
                 lines.join("\n")
             }
+            TargetLanguage::Kotlin => {
+                let alias_type = self.kotlin_type(schema, root_name, type_key, false);
+                format!("typealias {} = {}", root_name, alias_type)
+            }
+            TargetLanguage::Swift => {
+                let alias_type = self.swift_type(schema, root_name, type_key, false);
+                format!("typealias {} = {}", root_name, alias_type)
+            }
+            TargetLanguage::CSharp => {
+                let value_type = self.csharp_type(schema, root_name, type_key, false);
+                format!(
+                    "public record {}\n{{\n    public {} Value {{ get; init; }}\n}}",
+                    root_name, value_type
+                )
+            }
+            TargetLanguage::PythonPydantic | TargetLanguage::PythonDataclass => {
+                let alias_type = self.python_type(schema, root_name, type_key, false);
+                format!("{} = {}", root_name, alias_type)
+            }
         }
     }
 
@@ -155,7 +350,7 @@ impl TypeRenderer {
         let mut lines = vec![format!("type {} struct {{", name)];
         let mut used = HashSet::new();
         for (key, schema) in fields {
-            let field_name = unique_name(sanitize_identifier(key, self.language), &mut used);
+            let field_name = unique_name(self.field_identifier(key), &mut used);
             let field_type = self.go_type(schema, name, key, false);
             let tag = format!("`json:\"{}\"`", escape_string(key));
             lines.push(format!("    {} {} {}", field_name, field_type, tag));
@@ -166,25 +361,35 @@ impl TypeRenderer {
 
     fn render_rust_struct(&mut self, name: &str, fields: &BTreeMap<String, Schema>) -> String {
         self.uses_structs = true;
-        let mut lines = vec![
-            "#[derive(Serialize, Deserialize, Debug, Clone)]".to_string(),
-            format!("pub struct {} {{", name),
-        ];
+        let defaulted = self.options.nullable == NullableStrategy::Default;
+        let derive = if defaulted {
+            "#[derive(Serialize, Deserialize, Debug, Clone, Default)]"
+        } else {
+            "#[derive(Serialize, Deserialize, Debug, Clone)]"
+        };
+        let mut lines = vec![derive.to_string(), format!("pub struct {} {{", name)];
         let mut used = HashSet::new();
         for (key, schema) in fields {
-            let field_name = unique_name(sanitize_identifier(key, self.language), &mut used);
+            let field_name = unique_name(self.field_identifier(key), &mut used);
             if field_name != *key {
                 lines.push(format!("    #[serde(rename = \"{}\")]", escape_string(key)));
             }
+            if defaulted && schema.nullable {
+                lines.push("    #[serde(default)]".to_string());
+            }
             let field_type = self.rust_type(schema, name, key, false);
             lines.push(format!("    pub {}: {},", field_name, field_type));
         }
         lines.push("}".to_string());
+        if self.options.serialization_helpers {
+            lines.push(String::new());
+            lines.push(self.render_rust_value_conversions(name));
+        }
         lines.join("\n")
     }
 
     fn render_ts_interface(&mut self, name: &str, fields: &BTreeMap<String, Schema>) -> String {
-        let mut lines = vec![format!("interface {} {{", name)];
+        let mut lines = vec![format!("{}interface {} {{", self.ts_export(), name)];
         for (key, schema) in fields {
             let (prop_name, quoted) = ts_property_name(key);
             let rendered = if quoted {
@@ -193,7 +398,9 @@ impl TypeRenderer {
                 prop_name
             };
             let field_type = self.ts_type(schema, name, key, false);
-            lines.push(format!("  {}: {};", rendered, field_type));
+            let optional = schema.nullable && self.options.nullable == NullableStrategy::Undefined;
+            let marker = if optional { "?" } else { "" };
+            lines.push(format!("  {}{}: {};", rendered, marker, field_type));
         }
         lines.push("}".to_string());
         lines.join("\n")
@@ -202,8 +409,9 @@ impl TypeRenderer {
     fn render_dart_class(&mut self, name: &str, fields: &BTreeMap<String, Schema>) -> String {
         let mut lines = vec![format!("class {} {{", name)];
         let mut used = HashSet::new();
+        let mut members = Vec::new();
         for (key, schema) in fields {
-            let field_name = unique_name(sanitize_identifier(key, self.language), &mut used);
+            let field_name = unique_name(self.field_identifier(key), &mut used);
             if field_name != *key {
                 lines.push(format!("  // json: \"{}\"", escape_string(key)));
             }
@@ -214,6 +422,11 @@ impl TypeRenderer {
                 format!("  late {} {};", field_type, field_name)
             };
             lines.push(decl);
+            members.push((key.as_str(), field_name, schema));
+        }
+        if self.options.serialization_helpers {
+            lines.push(String::new());
+            lines.extend(self.render_dart_json_methods(name, &members));
         }
         lines.push("}".to_string());
         lines.join("\n")
@@ -222,13 +435,19 @@ impl TypeRenderer {
     fn render_java_class(&mut self, name: &str, fields: &BTreeMap<String, Schema>) -> String {
         let mut lines = vec![format!("class {} {{", name)];
         let mut used = HashSet::new();
+        let mut members = Vec::new();
         for (key, schema) in fields {
-            let field_name = unique_name(sanitize_identifier(key, self.language), &mut used);
+            let field_name = unique_name(self.field_identifier(key), &mut used);
             if field_name != *key {
                 lines.push(format!("  // json: \"{}\"", escape_string(key)));
             }
             let field_type = self.java_type(schema, name, key, false);
             lines.push(format!("  public {} {};", field_type, field_name));
+            members.push((field_name, field_type));
+        }
+        if self.options.builders {
+            lines.push(String::new());
+            lines.extend(render_java_builders(name, &members));
         }
         lines.push("}".to_string());
         lines.join("\n")
@@ -238,7 +457,7 @@ impl TypeRenderer {
         let mut lines = vec![format!("class {} {{", name)];
         let mut used = HashSet::new();
         for (key, schema) in fields {
-            let field_name = unique_name(sanitize_identifier(key, self.language), &mut used);
+            let field_name = unique_name(self.field_identifier(key), &mut used);
             if field_name != *key {
                 lines.push(format!("  // json: \"{}\"", escape_string(key)));
             }
@@ -248,4 +467,350 @@ impl TypeRenderer {
         lines.push("}".to_string());
         lines.join("\n")
     }
+
+    fn render_kotlin_data_class(
+        &mut self,
+        name: &str,
+        fields: &BTreeMap<String, Schema>,
+    ) -> String {
+        self.uses_structs = true;
+        if fields.is_empty() {
+            return format!("@Serializable\nclass {}", name);
+        }
+
+        let mut lines = vec!["@Serializable".to_string(), format!("data class {}(", name)];
+        let mut used = HashSet::new();
+        for (key, schema) in fields {
+            let field_name = unique_name(self.field_identifier(key), &mut used);
+            let field_type = self.kotlin_type(schema, name, key, false);
+            let default = if schema.nullable { " = null" } else { "" };
+            lines.push(format!(
+                "    @SerialName(\"{}\") val {}: {}{},",
+                escape_string(key),
+                field_name,
+                field_type,
+                default
+            ));
+        }
+        lines.push(")".to_string());
+        lines.join("\n")
+    }
+
+    fn render_swift_struct(&mut self, name: &str, fields: &BTreeMap<String, Schema>) -> String {
+        let mut lines = vec![format!("struct {}: Codable {{", name)];
+        let mut used = HashSet::new();
+        let mut coding_keys = Vec::new();
+        let mut renamed = false;
+        for (key, schema) in fields {
+            let field_name = unique_name(self.field_identifier(key), &mut used);
+            let field_type = self.swift_type(schema, name, key, false);
+            lines.push(format!("    let {}: {}", field_name, field_type));
+            if field_name != *key {
+                renamed = true;
+                coding_keys.push(format!(
+                    "        case {} = \"{}\"",
+                    field_name,
+                    escape_string(key)
+                ));
+            } else {
+                coding_keys.push(format!("        case {}", field_name));
+            }
+        }
+        if renamed {
+            lines.push(String::new());
+            lines.push("    enum CodingKeys: String, CodingKey {".to_string());
+            lines.extend(coding_keys);
+            lines.push("    }".to_string());
+        }
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    fn render_csharp_record(&mut self, name: &str, fields: &BTreeMap<String, Schema>) -> String {
+        let mut lines = vec![format!("public record {}", name), "{".to_string()];
+        // C# members cannot share the enclosing type's name.
+        let mut used = HashSet::from([name.to_string()]);
+        let mut first = true;
+        for (key, schema) in fields {
+            let field_name = unique_name(self.field_identifier(key), &mut used);
+            let field_type = self.csharp_type(schema, name, key, false);
+            if !first {
+                lines.push(String::new());
+            }
+            first = false;
+            lines.push(format!(
+                "    [JsonPropertyName(\"{}\")]",
+                escape_string(key)
+            ));
+            lines.push(format!(
+                "    public {} {} {{ get; init; }}",
+                field_type, field_name
+            ));
+        }
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+
+    fn render_python_class(&mut self, name: &str, fields: &BTreeMap<String, Schema>) -> String {
+        self.uses_structs = true;
+        let pydantic = matches!(self.language, TargetLanguage::PythonPydantic);
+        let mut lines = if pydantic {
+            vec![format!("class {}(BaseModel):", name)]
+        } else {
+            vec!["@dataclass".to_string(), format!("class {}:", name)]
+        };
+
+        let mut used = HashSet::new();
+        let mut required = Vec::new();
+        let mut optional = Vec::new();
+        for (key, schema) in fields {
+            let field_name = unique_name(self.field_identifier(key), &mut used);
+            let field_type = self.python_type(schema, name, key, false);
+            let renamed = field_name != *key;
+            let decl = match (pydantic, schema.nullable, renamed) {
+                (true, true, true) => {
+                    self.uses_alias = true;
+                    format!(
+                        "    {}: {} = Field(None, alias=\"{}\")",
+                        field_name,
+                        field_type,
+                        escape_string(key)
+                    )
+                }
+                (true, false, true) => {
+                    self.uses_alias = true;
+                    format!(
+                        "    {}: {} = Field(alias=\"{}\")",
+                        field_name,
+                        field_type,
+                        escape_string(key)
+                    )
+                }
+                (_, true, _) => format!("    {}: {} = None", field_name, field_type),
+                (_, false, _) => format!("    {}: {}", field_name, field_type),
+            };
+            let comment =
+                (!pydantic && renamed).then(|| format!("    # json: \"{}\"", escape_string(key)));
+
+            // Dataclass fields with defaults must follow the required ones.
+            let bucket = if schema.nullable && !pydantic {
+                &mut optional
+            } else {
+                &mut required
+            };
+            bucket.extend(comment);
+            bucket.push(decl);
+        }
+
+        if required.is_empty() && optional.is_empty() {
+            lines.push("    pass".to_string());
+        }
+        lines.extend(required);
+        lines.extend(optional);
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::schema::infer_schema;
+    use super::*;
+
+    fn render(language: TargetLanguage, json: &str) -> String {
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        let schema = infer_schema(&value);
+        TypeRenderer::new(language, "User").render(&schema, "User")
+    }
+
+    const SAMPLE: &str = r#"{"user_id": 1, "display-name": "a", "score": 1.5, "tags": ["x"], "meta": null, "address": {"city": "x"}}"#;
+
+    #[test]
+    fn test_kotlin_data_class() {
+        let out = render(TargetLanguage::Kotlin, SAMPLE);
+        assert!(out.contains("import kotlinx.serialization.Serializable"));
+        assert!(out.contains("data class Useraddress("));
+        assert!(out.contains("@SerialName(\"user_id\") val userId: Long,"));
+        assert!(out.contains("@SerialName(\"meta\") val meta: JsonElement? = null,"));
+        assert!(out.contains("val tags: List<String>,"));
+    }
+
+    #[test]
+    fn test_swift_codable_struct() {
+        let out = render(TargetLanguage::Swift, SAMPLE);
+        assert!(out.contains("struct User: Codable {"));
+        assert!(out.contains("    let displayName: String"));
+        assert!(out.contains("        case displayName = \"display-name\""));
+        assert!(out.contains("        case score\n"));
+        assert!(out.contains("enum JSONValue: Codable"));
+    }
+
+    #[test]
+    fn test_csharp_record() {
+        let out = render(TargetLanguage::CSharp, SAMPLE);
+        assert!(out.contains("using System.Text.Json.Serialization;"));
+        assert!(out.contains("public record User\n{"));
+        assert!(out.contains(
+            "    [JsonPropertyName(\"user_id\")]\n    public long UserId { get; init; }"
+        ));
+        assert!(out.contains("public JsonElement? Meta { get; init; }"));
+        assert!(out.contains("public List<string> Tags { get; init; }"));
+    }
+
+    #[test]
+    fn test_csharp_member_does_not_clash_with_type_name() {
+        let out = render(TargetLanguage::CSharp, r#"{"user": "x"}"#);
+        assert!(out.contains("public string User2 { get; init; }"));
+    }
+
+    #[test]
+    fn test_python_pydantic_model() {
+        let out = render(TargetLanguage::PythonPydantic, SAMPLE);
+        assert!(out.contains("from pydantic import BaseModel, Field"));
+        assert!(out.contains("class User(BaseModel):"));
+        assert!(out.contains("    display_name: str = Field(alias=\"display-name\")"));
+        assert!(out.contains("    meta: Any"));
+        assert!(out.contains("    address: Useraddress"));
+    }
+
+    #[test]
+    fn test_python_dataclass_orders_optional_fields_last() {
+        let out = render(
+            TargetLanguage::PythonDataclass,
+            r#"{"a": null, "b": 1, "c": [1]}"#,
+        );
+        assert!(out.contains("@dataclass\nclass User:"));
+        let b = out.find("    b: int").unwrap();
+        let a = out.find("    a: Any").unwrap();
+        assert!(b < a);
+        assert!(out.contains("    a: Any = None"));
+        assert!(out.contains("    c: List[int]"));
+    }
+
+    #[test]
+    fn test_python_keyword_fields_are_escaped() {
+        let out = render(TargetLanguage::PythonPydantic, r#"{"class": "x"}"#);
+        assert!(out.contains("    class_: str = Field(alias=\"class\")"));
+    }
+
+    #[test]
+    fn test_array_root_aliases() {
+        let json = r#"[{"id": 1}]"#;
+        assert!(render(TargetLanguage::Kotlin, json).contains("typealias User = List<Useritem>"));
+        assert!(render(TargetLanguage::Swift, json).contains("typealias User = [Useritem]"));
+        assert!(render(TargetLanguage::PythonDataclass, json).contains("User = List[Useritem]"));
+    }
+
+    fn render_with(language: TargetLanguage, json: &str, options: TypeGenOptions) -> String {
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        let schema = infer_schema(&value);
+        TypeRenderer::new(language, "User")
+            .with_options(options)
+            .render(&schema, "User")
+    }
+
+    const HELPERS: TypeGenOptions = TypeGenOptions {
+        serialization_helpers: true,
+        builders: true,
+        nullable: NullableStrategy::Native,
+        missing_fields: crate::domain::typegen::MissingFields::Optional,
+        field_naming: None,
+    };
+
+    fn with_nullable(nullable: NullableStrategy) -> TypeGenOptions {
+        TypeGenOptions {
+            nullable,
+            ..TypeGenOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_types_only_by_default() {
+        let out = render(TargetLanguage::Dart, SAMPLE);
+        assert!(!out.contains("fromJson"));
+        let out = render(TargetLanguage::Java, SAMPLE);
+        assert!(!out.contains("Builder"));
+    }
+
+    #[test]
+    fn test_dart_json_methods() {
+        let out = render_with(
+            TargetLanguage::Dart,
+            r#"{"id": 1, "score": 1.5, "tags": ["x"], "address": {"city": "x"}, "items": [{"n": 1}], "note": null}"#,
+            HELPERS,
+        );
+        assert!(out.contains("  factory User.fromJson(Map<String, dynamic> json) => User()"));
+        assert!(out.contains("    ..id = json['id'] as int"));
+        assert!(out.contains("    ..score = (json['score'] as num).toDouble()"));
+        assert!(out.contains(
+            "    ..tags = (json['tags'] as List<dynamic>).map((e) => e as String).toList()"
+        ));
+        assert!(out.contains(
+            "    ..address = Useraddress.fromJson(json['address'] as Map<String, dynamic>)"
+        ));
+        assert!(out.contains("    ..note = json['note']\n"));
+        assert!(out.contains("        'address': address.toJson(),"));
+        assert!(out.contains("        'items': items.map((e) => e.toJson()).toList(),"));
+        assert!(out.contains("        'tags': tags,"));
+    }
+
+    #[test]
+    fn test_java_builders() {
+        let out = render_with(TargetLanguage::Java, r#"{"id": 1, "name": "x"}"#, HELPERS);
+        assert!(out.contains("  public User(long id, String name) {"));
+        assert!(out.contains("    public Builder name(String name) {"));
+        assert!(out.contains("      return new User(id, name);"));
+    }
+
+    #[test]
+    fn test_nullable_strategies() {
+        let json = r#"{"name": null, "tags": [1, null]}"#;
+        let out = render(TargetLanguage::Rust, json);
+        assert!(out.contains("pub name: Option<Value>,"));
+
+        let out = render_with(
+            TargetLanguage::Rust,
+            json,
+            with_nullable(NullableStrategy::Default),
+        );
+        assert!(out.contains("#[derive(Serialize, Deserialize, Debug, Clone, Default)]"));
+        assert!(out.contains("    #[serde(default)]\n    pub name: Value,"));
+        assert!(out.contains("pub tags: Vec<Option<i64>>,"));
+
+        let out = render_with(
+            TargetLanguage::TypeScript,
+            r#"{"name": null, "id": 1}"#,
+            with_nullable(NullableStrategy::Undefined),
+        );
+        assert!(out.contains("  name?: unknown;"));
+        assert!(out.contains("  id: number;"));
+
+        let out = render_with(
+            TargetLanguage::Java,
+            r#"{"count": null, "id": 1}"#,
+            with_nullable(NullableStrategy::Optional),
+        );
+        assert!(out.contains("import java.util.Optional;"));
+        assert!(out.contains("  public Optional<Object> count;"));
+        assert!(out.contains("  public long id;"));
+    }
+
+    #[test]
+    fn test_field_naming_override() {
+        let options = TypeGenOptions {
+            field_naming: Some(crate::domain::typegen::NamingConvention::CamelCase),
+            ..TypeGenOptions::default()
+        };
+        let out = render_with(TargetLanguage::Rust, r#"{"user_id": 1}"#, options);
+        assert!(out.contains("    #[serde(rename = \"user_id\")]\n    pub userId: i64,"));
+        let out = render_with(TargetLanguage::Go, r#"{"user_id": 1}"#, options);
+        assert!(out.contains("UserId int64"));
+    }
+
+    #[test]
+    fn test_rust_value_conversions() {
+        let out = render_with(TargetLanguage::Rust, r#"{"id": 1}"#, HELPERS);
+        assert!(out.contains("use serde_json::Value;"));
+        assert!(out.contains("impl TryFrom<Value> for User {"));
+        assert!(out.contains("impl From<User> for Value {"));
+    }
 }
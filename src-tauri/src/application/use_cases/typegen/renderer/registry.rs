@@ -18,6 +18,10 @@ impl TypeNameRegistry {
         }
     }
 
+    pub(super) fn reserve(&mut self, name: &str) {
+        self.used.insert(name.to_string());
+    }
+
     pub(super) fn name_for_field(
         &mut self,
         parent: &str,
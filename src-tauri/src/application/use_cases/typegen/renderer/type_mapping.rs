@@ -1,3 +1,5 @@
+use crate::domain::typegen::NullableStrategy;
+
 use super::super::language::box_java_primitive;
 use super::super::schema::{Schema, SchemaKind};
 use super::TypeRenderer;
@@ -15,7 +17,7 @@ impl TypeRenderer {
             SchemaKind::Int => "int64".to_string(),
             SchemaKind::Float => "float64".to_string(),
             SchemaKind::String => "string".to_string(),
-            SchemaKind::Dynamic => "interface{}".to_string(),
+            SchemaKind::Dynamic | SchemaKind::Null => "interface{}".to_string(),
             SchemaKind::Array(inner) => {
                 let inner_type = self.go_type(inner, parent, key, true);
                 format!("[]{}", inner_type)
@@ -26,7 +28,8 @@ impl TypeRenderer {
             }
         };
 
-        if schema.nullable && base != "interface{}" {
+        let pointer = self.nullable_strategy(in_array) != NullableStrategy::Default;
+        if schema.nullable && pointer && base != "interface{}" {
             format!("*{}", base)
         } else {
             base
@@ -45,7 +48,7 @@ impl TypeRenderer {
             SchemaKind::Int => "i64".to_string(),
             SchemaKind::Float => "f64".to_string(),
             SchemaKind::String => "String".to_string(),
-            SchemaKind::Dynamic => {
+            SchemaKind::Dynamic | SchemaKind::Null => {
                 self.uses_dynamic = true;
                 "Value".to_string()
             }
@@ -59,7 +62,7 @@ impl TypeRenderer {
             }
         };
 
-        if schema.nullable {
+        if schema.nullable && self.nullable_strategy(in_array) != NullableStrategy::Default {
             format!("Option<{}>", base)
         } else {
             base
@@ -77,7 +80,7 @@ impl TypeRenderer {
             SchemaKind::Bool => "boolean".to_string(),
             SchemaKind::Int | SchemaKind::Float => "number".to_string(),
             SchemaKind::String => "string".to_string(),
-            SchemaKind::Dynamic => "unknown".to_string(),
+            SchemaKind::Dynamic | SchemaKind::Null => "unknown".to_string(),
             SchemaKind::Array(inner) => {
                 let inner_type = self.ts_type(inner, parent, key, true);
                 let inner_type = if inner_type.contains(" | ") {
@@ -93,7 +96,7 @@ impl TypeRenderer {
             }
         };
 
-        if schema.nullable {
+        if schema.nullable && self.nullable_strategy(in_array) == NullableStrategy::Native {
             base = format!("{} | null", base);
         }
         base
@@ -111,7 +114,7 @@ impl TypeRenderer {
             SchemaKind::Int => "int".to_string(),
             SchemaKind::Float => "double".to_string(),
             SchemaKind::String => "String".to_string(),
-            SchemaKind::Dynamic => "dynamic".to_string(),
+            SchemaKind::Dynamic | SchemaKind::Null => "dynamic".to_string(),
             SchemaKind::Array(inner) => {
                 let inner_type = self.dart_type(inner, parent, key, true);
                 format!("List<{}>", inner_type)
@@ -158,7 +161,7 @@ impl TypeRenderer {
                 }
             }
             SchemaKind::String => "String".to_string(),
-            SchemaKind::Dynamic => "Object".to_string(),
+            SchemaKind::Dynamic | SchemaKind::Null => "Object".to_string(),
             SchemaKind::Array(inner) => {
                 self.uses_list = true;
                 let inner_type = self.java_type(inner, parent, key, true);
@@ -172,6 +175,10 @@ impl TypeRenderer {
 
         if schema.nullable {
             base = box_java_primitive(&base);
+            if self.nullable_strategy(in_array) == NullableStrategy::Optional {
+                self.uses_optional = true;
+                base = format!("Optional<{}>", base);
+            }
         }
         base
     }
@@ -188,7 +195,7 @@ impl TypeRenderer {
             SchemaKind::Int => "int".to_string(),
             SchemaKind::Float => "float".to_string(),
             SchemaKind::String => "string".to_string(),
-            SchemaKind::Dynamic => "mixed".to_string(),
+            SchemaKind::Dynamic | SchemaKind::Null => "mixed".to_string(),
             SchemaKind::Array(inner) => {
                 let _ = self.php_type(inner, parent, key, true);
                 "array".to_string()
@@ -205,4 +212,139 @@ impl TypeRenderer {
             base
         }
     }
+
+    pub(super) fn kotlin_type(
+        &mut self,
+        schema: &Schema,
+        parent: &str,
+        key: &str,
+        in_array: bool,
+    ) -> String {
+        let base = match &schema.kind {
+            SchemaKind::Bool => "Boolean".to_string(),
+            SchemaKind::Int => "Long".to_string(),
+            SchemaKind::Float => "Double".to_string(),
+            SchemaKind::String => "String".to_string(),
+            SchemaKind::Dynamic | SchemaKind::Null => {
+                self.uses_dynamic = true;
+                "JsonElement".to_string()
+            }
+            SchemaKind::Array(inner) => {
+                let inner_type = self.kotlin_type(inner, parent, key, true);
+                format!("List<{}>", inner_type)
+            }
+            SchemaKind::Object(_) => {
+                self.registry
+                    .name_for_field(parent, key, in_array, self.language)
+            }
+        };
+
+        if schema.nullable {
+            format!("{}?", base)
+        } else {
+            base
+        }
+    }
+
+    pub(super) fn swift_type(
+        &mut self,
+        schema: &Schema,
+        parent: &str,
+        key: &str,
+        in_array: bool,
+    ) -> String {
+        let base = match &schema.kind {
+            SchemaKind::Bool => "Bool".to_string(),
+            SchemaKind::Int => "Int".to_string(),
+            SchemaKind::Float => "Double".to_string(),
+            SchemaKind::String => "String".to_string(),
+            SchemaKind::Dynamic | SchemaKind::Null => {
+                self.uses_dynamic = true;
+                "JSONValue".to_string()
+            }
+            SchemaKind::Array(inner) => {
+                let inner_type = self.swift_type(inner, parent, key, true);
+                format!("[{}]", inner_type)
+            }
+            SchemaKind::Object(_) => {
+                self.registry
+                    .name_for_field(parent, key, in_array, self.language)
+            }
+        };
+
+        if schema.nullable {
+            format!("{}?", base)
+        } else {
+            base
+        }
+    }
+
+    pub(super) fn csharp_type(
+        &mut self,
+        schema: &Schema,
+        parent: &str,
+        key: &str,
+        in_array: bool,
+    ) -> String {
+        let base = match &schema.kind {
+            SchemaKind::Bool => "bool".to_string(),
+            SchemaKind::Int => "long".to_string(),
+            SchemaKind::Float => "double".to_string(),
+            SchemaKind::String => "string".to_string(),
+            SchemaKind::Dynamic | SchemaKind::Null => {
+                self.uses_dynamic = true;
+                "JsonElement".to_string()
+            }
+            SchemaKind::Array(inner) => {
+                self.uses_list = true;
+                let inner_type = self.csharp_type(inner, parent, key, true);
+                format!("List<{}>", inner_type)
+            }
+            SchemaKind::Object(_) => {
+                self.registry
+                    .name_for_field(parent, key, in_array, self.language)
+            }
+        };
+
+        if schema.nullable {
+            format!("{}?", base)
+        } else {
+            base
+        }
+    }
+
+    pub(super) fn python_type(
+        &mut self,
+        schema: &Schema,
+        parent: &str,
+        key: &str,
+        in_array: bool,
+    ) -> String {
+        let base = match &schema.kind {
+            SchemaKind::Bool => "bool".to_string(),
+            SchemaKind::Int => "int".to_string(),
+            SchemaKind::Float => "float".to_string(),
+            SchemaKind::String => "str".to_string(),
+            SchemaKind::Dynamic | SchemaKind::Null => {
+                self.uses_dynamic = true;
+                "Any".to_string()
+            }
+            SchemaKind::Array(inner) => {
+                self.uses_list = true;
+                let inner_type = self.python_type(inner, parent, key, true);
+                format!("List[{}]", inner_type)
+            }
+            SchemaKind::Object(_) => {
+                self.registry
+                    .name_for_field(parent, key, in_array, self.language)
+            }
+        };
+
+        if schema.nullable && base != "Any" {
+            self.uses_optional = true;
+            format!("Optional[{}]", base)
+        } else {
+            base
+        }
+    }
 }
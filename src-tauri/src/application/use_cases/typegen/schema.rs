@@ -1,10 +1,14 @@
 use serde_json::Value;
 use std::collections::BTreeMap;
 
+use crate::domain::typegen::MissingFields;
+
 #[derive(Clone, Debug)]
 pub(super) struct Schema {
     pub(super) kind: SchemaKind,
     pub(super) nullable: bool,
+    /// Missing from some of the merged samples.
+    pub(super) optional: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -16,17 +20,21 @@ pub(super) enum SchemaKind {
     Array(Box<Schema>),
     Object(BTreeMap<String, Schema>),
     Dynamic,
+    /// Only `null` was observed; merges into whatever type shows up elsewhere.
+    Null,
 }
 
 pub(super) fn infer_schema(value: &Value) -> Schema {
     match value {
         Value::Null => Schema {
-            kind: SchemaKind::Dynamic,
+            kind: SchemaKind::Null,
             nullable: true,
+            optional: false,
         },
         Value::Bool(_) => Schema {
             kind: SchemaKind::Bool,
             nullable: false,
+            optional: false,
         },
         Value::Number(num) => Schema {
             kind: if num.is_i64() || num.is_u64() {
@@ -35,10 +43,12 @@ pub(super) fn infer_schema(value: &Value) -> Schema {
                 SchemaKind::Float
             },
             nullable: false,
+            optional: false,
         },
         Value::String(_) => Schema {
             kind: SchemaKind::String,
             nullable: false,
+            optional: false,
         },
         Value::Array(items) => {
             let mut merged: Option<Schema> = None;
@@ -52,10 +62,12 @@ pub(super) fn infer_schema(value: &Value) -> Schema {
             let element_schema = merged.unwrap_or(Schema {
                 kind: SchemaKind::Dynamic,
                 nullable: false,
+                optional: false,
             });
             Schema {
                 kind: SchemaKind::Array(Box::new(element_schema)),
                 nullable: false,
+                optional: false,
             }
         }
         Value::Object(map) => {
@@ -66,68 +78,120 @@ pub(super) fn infer_schema(value: &Value) -> Schema {
             Schema {
                 kind: SchemaKind::Object(fields),
                 nullable: false,
+                optional: false,
             }
         }
     }
 }
 
-fn merge_schema(a: Schema, b: Schema) -> Schema {
+/// Merge the schemas of several samples of the same payload.
+///
+/// Fields missing from some samples become optional, `null` values adopt the
+/// type seen in other samples, int/float widen to float and any other type
+/// conflict falls back to a dynamic value.
+pub(super) fn merge_schemas(schemas: impl IntoIterator<Item = Schema>) -> Option<Schema> {
+    schemas.into_iter().reduce(merge_schema)
+}
+
+pub(super) fn merge_schema(a: Schema, b: Schema) -> Schema {
     let nullable = a.nullable || b.nullable;
+    let optional = a.optional || b.optional;
     match (a.kind, b.kind) {
+        (SchemaKind::Null, kind) | (kind, SchemaKind::Null) => Schema {
+            kind,
+            nullable: true,
+            optional,
+        },
         (SchemaKind::Dynamic, _) | (_, SchemaKind::Dynamic) => Schema {
             kind: SchemaKind::Dynamic,
             nullable,
+            optional,
         },
         (SchemaKind::Int, SchemaKind::Float) | (SchemaKind::Float, SchemaKind::Int) => Schema {
             kind: SchemaKind::Float,
             nullable,
+            optional,
         },
         (SchemaKind::Array(a_inner), SchemaKind::Array(b_inner)) => Schema {
             kind: SchemaKind::Array(Box::new(merge_schema(*a_inner, *b_inner))),
             nullable,
+            optional,
         },
         (SchemaKind::Object(a_map), SchemaKind::Object(b_map)) => Schema {
             kind: SchemaKind::Object(merge_object_maps(a_map, b_map)),
             nullable,
+            optional,
         },
         (SchemaKind::Bool, SchemaKind::Bool) => Schema {
             kind: SchemaKind::Bool,
             nullable,
+            optional,
         },
         (SchemaKind::Int, SchemaKind::Int) => Schema {
             kind: SchemaKind::Int,
             nullable,
+            optional,
         },
         (SchemaKind::Float, SchemaKind::Float) => Schema {
             kind: SchemaKind::Float,
             nullable,
+            optional,
         },
         (SchemaKind::String, SchemaKind::String) => Schema {
             kind: SchemaKind::String,
             nullable,
+            optional,
         },
         _ => Schema {
             kind: SchemaKind::Dynamic,
             nullable,
+            optional,
         },
     }
 }
 
 fn merge_object_maps(
     mut left: BTreeMap<String, Schema>,
-    right: BTreeMap<String, Schema>,
+    mut right: BTreeMap<String, Schema>,
 ) -> BTreeMap<String, Schema> {
-    for (key, value) in right {
-        if let Some(existing) = left.get_mut(&key) {
-            let merged = merge_schema(existing.clone(), value);
-            *existing = merged;
-        } else {
-            left.insert(key, value);
+    for (key, existing) in left.iter_mut() {
+        match right.remove(key) {
+            Some(value) => {
+                let merged = merge_schema(existing.clone(), value);
+                *existing = merged;
+            }
+            // Absent from one side: the field is optional.
+            None => existing.optional = true,
         }
     }
+    for (key, mut value) in right {
+        value.optional = true;
+        left.insert(key, value);
+    }
     left
 }
 
+/// `schema` with fields missing from some samples made nullable unless
+/// they are `Required`.
+pub(super) fn apply_missing_fields(mut schema: Schema, missing: MissingFields) -> Schema {
+    if schema.optional && missing == MissingFields::Optional {
+        schema.nullable = true;
+    }
+    schema.kind = match schema.kind {
+        SchemaKind::Array(inner) => {
+            SchemaKind::Array(Box::new(apply_missing_fields(*inner, missing)))
+        }
+        SchemaKind::Object(fields) => SchemaKind::Object(
+            fields
+                .into_iter()
+                .map(|(key, field)| (key, apply_missing_fields(field, missing)))
+                .collect(),
+        ),
+        kind => kind,
+    };
+    schema
+}
+
 pub(super) fn find_object_schema<'a>(
     schema: &'a Schema,
     in_array: bool,
@@ -146,3 +210,85 @@ pub(super) fn root_type_key(schema: &Schema) -> &'static str {
         "value"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn infer_merged_schema(samples: &[Value]) -> Option<Schema> {
+        merge_schemas(samples.iter().map(infer_schema))
+    }
+
+    fn fields(schema: &Schema) -> &BTreeMap<String, Schema> {
+        match &schema.kind {
+            SchemaKind::Object(fields) => fields,
+            other => panic!("expected object, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_merge_marks_missing_fields_optional() {
+        let schema = infer_merged_schema(&[
+            json!({"id": 1, "name": "a"}),
+            json!({"id": 2, "email": "b"}),
+        ])
+        .unwrap();
+        let fields = fields(&schema);
+        assert!(!fields["id"].optional);
+        assert!(fields["name"].optional);
+        assert!(fields["email"].optional);
+        assert!(!fields["name"].nullable);
+    }
+
+    #[test]
+    fn test_missing_fields_are_nullable_unless_required() {
+        let schema = infer_merged_schema(&[
+            json!({"items": [{"a": 1, "b": null}]}),
+            json!({"items": [{"a": 2, "c": "x"}]}),
+        ])
+        .unwrap();
+        let optional = apply_missing_fields(schema.clone(), MissingFields::Optional);
+        let required = apply_missing_fields(schema, MissingFields::Required);
+        for (schema, c_nullable) in [(optional, true), (required, false)] {
+            let SchemaKind::Array(item) = &fields(&schema)["items"].kind else {
+                panic!("expected array");
+            };
+            let item_fields = fields(item);
+            assert!(!item_fields["a"].nullable);
+            assert!(item_fields["b"].nullable);
+            assert_eq!(item_fields["c"].nullable, c_nullable);
+        }
+    }
+
+    #[test]
+    fn test_merge_resolves_null_and_numeric_conflicts() {
+        let schema = infer_merged_schema(&[
+            json!({"price": 1, "note": null, "flag": true}),
+            json!({"price": 1.5, "note": "x", "flag": "yes"}),
+        ])
+        .unwrap();
+        let fields = fields(&schema);
+        assert!(matches!(fields["price"].kind, SchemaKind::Float));
+        assert!(matches!(fields["note"].kind, SchemaKind::String));
+        assert!(fields["note"].nullable);
+        assert!(matches!(fields["flag"].kind, SchemaKind::Dynamic));
+    }
+
+    #[test]
+    fn test_merge_nested_arrays_of_objects() {
+        let schema =
+            infer_merged_schema(&[json!({"items": [{"a": 1}, {"a": 2, "b": "x"}]})]).unwrap();
+        let SchemaKind::Array(item) = &fields(&schema)["items"].kind else {
+            panic!("expected array");
+        };
+        let item_fields = fields(item);
+        assert!(!item_fields["a"].optional);
+        assert!(item_fields["b"].optional);
+    }
+
+    #[test]
+    fn test_merge_empty_samples() {
+        assert!(infer_merged_schema(&[]).is_none());
+    }
+}
@@ -0,0 +1,227 @@
+//! Streaming JSON inspection for large payloads
+//!
+//! Both passes drive serde_json's deserializer directly instead of building a
+//! full `Value` tree:
+//! - `infer_schema_from_str` infers the schema, sampling the first
+//!   `MAX_ARRAY_SAMPLE` items of every array and skipping the rest
+//! - `preview_from_str` keeps a small representative `Value` for the LLM prompt
+
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde_json::{Map, Number, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::schema::{merge_schema, Schema, SchemaKind};
+
+/// Array items inspected per array when inferring a schema.
+pub(super) const MAX_ARRAY_SAMPLE: usize = 200;
+/// Array items kept per array in the LLM preview.
+const PREVIEW_ARRAY_ITEMS: usize = 3;
+/// Strings longer than this are shortened in the LLM preview.
+const PREVIEW_STRING_CHARS: usize = 120;
+
+pub(super) fn infer_schema_from_str(input: &str) -> serde_json::Result<Schema> {
+    let mut deserializer = serde_json::Deserializer::from_str(input);
+    let schema = SchemaSeed.deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(schema)
+}
+
+pub(super) fn preview_from_str(input: &str) -> serde_json::Result<Value> {
+    let mut deserializer = serde_json::Deserializer::from_str(input);
+    let value = PreviewSeed.deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+fn leaf(kind: SchemaKind) -> Schema {
+    Schema {
+        kind,
+        nullable: false,
+        optional: false,
+    }
+}
+
+struct SchemaSeed;
+
+impl<'de> DeserializeSeed<'de> for SchemaSeed {
+    type Value = Schema;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Schema, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for SchemaSeed {
+    type Value = Schema;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<Schema, E> {
+        Ok(Schema {
+            kind: SchemaKind::Null,
+            nullable: true,
+            optional: false,
+        })
+    }
+
+    fn visit_bool<E>(self, _: bool) -> Result<Schema, E> {
+        Ok(leaf(SchemaKind::Bool))
+    }
+
+    fn visit_i64<E>(self, _: i64) -> Result<Schema, E> {
+        Ok(leaf(SchemaKind::Int))
+    }
+
+    fn visit_u64<E>(self, _: u64) -> Result<Schema, E> {
+        Ok(leaf(SchemaKind::Int))
+    }
+
+    fn visit_f64<E>(self, _: f64) -> Result<Schema, E> {
+        Ok(leaf(SchemaKind::Float))
+    }
+
+    fn visit_str<E>(self, _: &str) -> Result<Schema, E> {
+        Ok(leaf(SchemaKind::String))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Schema, A::Error> {
+        let mut merged: Option<Schema> = None;
+        let mut seen = 0;
+        while seen < MAX_ARRAY_SAMPLE {
+            let Some(item) = seq.next_element_seed(SchemaSeed)? else {
+                break;
+            };
+            merged = Some(match merged {
+                Some(existing) => merge_schema(existing, item),
+                None => item,
+            });
+            seen += 1;
+        }
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+
+        let element_schema = merged.unwrap_or(leaf(SchemaKind::Dynamic));
+        Ok(leaf(SchemaKind::Array(Box::new(element_schema))))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Schema, A::Error> {
+        let mut fields = BTreeMap::new();
+        while let Some(key) = map.next_key::<String>()? {
+            let value = map.next_value_seed(SchemaSeed)?;
+            fields.insert(key, value);
+        }
+        Ok(leaf(SchemaKind::Object(fields)))
+    }
+}
+
+struct PreviewSeed;
+
+impl<'de> DeserializeSeed<'de> for PreviewSeed {
+    type Value = Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for PreviewSeed {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Value, E> {
+        Ok(Value::Bool(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Value, E> {
+        Ok(Value::Number(value.into()))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Value, E> {
+        Ok(Value::Number(value.into()))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Value, E> {
+        Ok(Number::from_f64(value).map_or(Value::Null, Value::Number))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Value, E> {
+        if value.chars().count() > PREVIEW_STRING_CHARS {
+            let head: String = value.chars().take(PREVIEW_STRING_CHARS).collect();
+            Ok(Value::String(format!("{}...", head)))
+        } else {
+            Ok(Value::String(value.to_string()))
+        }
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut items = Vec::new();
+        while items.len() < PREVIEW_ARRAY_ITEMS {
+            match seq.next_element_seed(PreviewSeed)? {
+                Some(item) => items.push(item),
+                None => break,
+            }
+        }
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+        Ok(Value::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut fields = Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            let value = map.next_value_seed(PreviewSeed)?;
+            fields.insert(key, value);
+        }
+        Ok(Value::Object(fields))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streaming_schema_samples_large_arrays() {
+        let mut items: Vec<String> = (0..MAX_ARRAY_SAMPLE)
+            .map(|idx| format!(r#"{{"id": {}}}"#, idx))
+            .collect();
+        // Past the sample window; must not affect the schema.
+        items.push(r#"{"id": "late", "extra": true}"#.to_string());
+        let input = format!("[{}]", items.join(","));
+
+        let schema = infer_schema_from_str(&input).unwrap();
+        let SchemaKind::Array(item) = schema.kind else {
+            panic!("expected array");
+        };
+        let SchemaKind::Object(fields) = item.kind else {
+            panic!("expected object items");
+        };
+        assert!(matches!(fields["id"].kind, SchemaKind::Int));
+        assert!(!fields.contains_key("extra"));
+    }
+
+    #[test]
+    fn test_streaming_schema_rejects_trailing_garbage() {
+        assert!(infer_schema_from_str(r#"{"a": 1} x"#).is_err());
+    }
+
+    #[test]
+    fn test_preview_truncates_arrays_and_strings() {
+        let long = "x".repeat(PREVIEW_STRING_CHARS + 10);
+        let input = format!(r#"{{"items": [1, 2, 3, 4, 5], "text": "{}"}}"#, long);
+        let preview = preview_from_str(&input).unwrap();
+        assert_eq!(
+            preview["items"].as_array().unwrap().len(),
+            PREVIEW_ARRAY_ITEMS
+        );
+        assert!(preview["text"].as_str().unwrap().ends_with("..."));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::domain::error::{AppError, Result};
-use crate::infrastructure::playwright::{CaptureManifest, PlaywrightCapture};
+use crate::infrastructure::playwright::{CaptureAuth, CaptureManifest, PlaywrightCapture};
 use reqwest::Client;
 use scraper::{Html, Selector};
 use std::collections::HashSet;
@@ -448,10 +448,12 @@ impl WebOcrCapture {
         }
     }
 
-    /// Capture a single URL using screenshots and OCR
+    /// Capture a single URL using screenshots and OCR, logged in with `auth`
+    /// for pages behind authentication
     pub async fn capture_url(
         &self,
         url: &str,
+        auth: Option<&CaptureAuth>,
         logs: Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
     ) -> Result<OcrCrawlResult> {
         use crate::interfaces::http::add_log;
@@ -470,6 +472,24 @@ impl WebOcrCapture {
             &format!("Starting OCR capture: {}", url),
         );
 
+        if let Some(auth) = auth.filter(|auth| !auth.is_empty()) {
+            add_log(
+                &logs,
+                "INFO",
+                "WebOcrCapture",
+                &format!(
+                    "Capturing with login: storage state {}, {} cookies, {} headers",
+                    if auth.storage_state.is_some() {
+                        "yes"
+                    } else {
+                        "no"
+                    },
+                    auth.cookies.len(),
+                    auth.headers.len()
+                ),
+            );
+        }
+
         // Check Node.js availability
         match PlaywrightCapture::check_nodejs() {
             Ok(version) => {
@@ -515,6 +535,9 @@ impl WebOcrCapture {
                     CaptureProgress::Complete { tiles_count, .. } => {
                         format!("Screenshot capture complete: {} tiles", tiles_count)
                     }
+                    CaptureProgress::Failed { error } => {
+                        format!("Screenshot capture failed: {}", error)
+                    }
                 };
                 add_log(&logs_clone, "DEBUG", "WebOcrCapture", &msg);
             }
@@ -523,7 +546,7 @@ impl WebOcrCapture {
         // Capture screenshots
         let manifest = self
             .playwright
-            .capture_url(url, &output_dir, Some(tx))
+            .capture_url(url, &output_dir, auth, Some(tx))
             .await?;
 
         // Wait for progress logging to complete
@@ -0,0 +1,163 @@
+// ============================================================
+// ACTIVITY FEED
+// ============================================================
+// One timeline of what the backend did across subsystems: QA run
+// streams, RAG ingestion, training progress and mock-server hits,
+// stored side by side so they can be read in chronological order.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivitySource {
+    QaRun,
+    RagIngestion,
+    Training,
+    MockServer,
+}
+
+impl ActivitySource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ActivitySource::QaRun => "qa_run",
+            ActivitySource::RagIngestion => "rag_ingestion",
+            ActivitySource::Training => "training",
+            ActivitySource::MockServer => "mock_server",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "qa_run" => Some(ActivitySource::QaRun),
+            "rag_ingestion" => Some(ActivitySource::RagIngestion),
+            "training" => Some(ActivitySource::Training),
+            "mock_server" => Some(ActivitySource::MockServer),
+            _ => None,
+        }
+    }
+}
+
+/// An entry about to be recorded; its time is taken when it is built
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityInput {
+    pub ts: i64,
+    pub source: ActivitySource,
+    /// What happened within the source, e.g. `run_started` or `request`
+    pub kind: String,
+    /// `info`, `warn` or `error`
+    pub level: String,
+    pub message: String,
+    /// The run, document or route the entry is about
+    pub subject_id: Option<String>,
+    pub payload_json: Option<String>,
+}
+
+impl ActivityInput {
+    pub fn new(source: ActivitySource, kind: &str, message: impl Into<String>) -> Self {
+        Self {
+            ts: chrono::Utc::now().timestamp_millis(),
+            source,
+            kind: kind.to_string(),
+            level: "info".to_string(),
+            message: message.into(),
+            subject_id: None,
+            payload_json: None,
+        }
+    }
+
+    pub fn level(mut self, level: &str) -> Self {
+        self.level = level.trim().to_lowercase();
+        self
+    }
+
+    pub fn subject(mut self, subject_id: impl Into<String>) -> Self {
+        self.subject_id = Some(subject_id.into());
+        self
+    }
+
+    pub fn payload(mut self, payload: serde_json::Value) -> Self {
+        self.payload_json = Some(payload.to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityEntry {
+    pub id: i64,
+    /// Unix milliseconds
+    pub ts: i64,
+    pub source: ActivitySource,
+    pub kind: String,
+    pub level: String,
+    pub message: String,
+    pub subject_id: Option<String>,
+    pub payload_json: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityQuery {
+    /// Only these sources; empty means all
+    #[serde(default)]
+    pub sources: Vec<ActivitySource>,
+
+    /// Only these levels (case-insensitive); empty means all
+    #[serde(default)]
+    pub levels: Vec<String>,
+
+    /// Only entries about this run, document or route
+    #[serde(default)]
+    pub subject_id: Option<String>,
+
+    /// Inclusive lower bound in Unix milliseconds
+    #[serde(default)]
+    pub since: Option<i64>,
+
+    /// Exclusive upper bound in Unix milliseconds
+    #[serde(default)]
+    pub until: Option<i64>,
+
+    /// Case-insensitive substring of the message
+    #[serde(default)]
+    pub search: Option<String>,
+
+    /// Entries skipped, counting back from the newest
+    #[serde(default)]
+    pub offset: usize,
+
+    #[serde(default = "default_query_limit")]
+    pub limit: usize,
+}
+
+fn default_query_limit() -> usize {
+    200
+}
+
+impl Default for ActivityQuery {
+    fn default() -> Self {
+        Self {
+            sources: Vec::new(),
+            levels: Vec::new(),
+            subject_id: None,
+            since: None,
+            until: None,
+            search: None,
+            offset: 0,
+            limit: default_query_limit(),
+        }
+    }
+}
+
+/// One page of the timeline. Pages count back from the newest entry;
+/// the entries of a page are in chronological order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityPage {
+    pub entries: Vec<ActivityEntry>,
+
+    /// Number of matching entries across all pages
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
@@ -0,0 +1,394 @@
+// ============================================================
+// AUTOMATION COMMANDS
+// ============================================================
+// Actions scripts and launchers (Raycast, Alfred, AutoHotkey, shell
+// aliases) can trigger in the running app, either as a link such as
+// `gadogado://translate?text=hello&to=German` or as launch arguments
+// such as `gadogado --run translate --text hello --to German`. A later
+// launch hands its arguments to the running instance, so both forms
+// reach the same app. Any web page can open a link, so links may only
+// show the main window; the other actions need launch arguments.
+
+use serde::Serialize;
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::project_profiles::DEEP_LINK_SCHEME;
+
+/// Launch argument followed by the action and its `--key value` parameters
+pub const RUN_ARG: &str = "--run";
+
+/// Host of the profile links, which are not automation commands
+const PROFILE_HOST: &str = "profile";
+
+/// How a command reached the app
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandOrigin {
+    /// A `gadogado://` link, opened by the OS or passed as a bare launch
+    /// argument
+    Link,
+    /// `--run <action> --key value ...` or `--run <link>` typed on a
+    /// command line
+    Arguments,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "action", rename_all = "kebab-case")]
+pub enum AutomationAction {
+    /// Languages default to the ones last picked in the main window
+    Translate {
+        text: String,
+        source: Option<String>,
+        target: Option<String>,
+    },
+    /// `preset` is an enhancement preset id; the default preset otherwise
+    Enhance {
+        text: String,
+        preset: Option<String>,
+    },
+    /// Answer `query` from a collection, given by id or name; the quick
+    /// action's default collection otherwise
+    RagQuery {
+        collection: Option<String>,
+        query: String,
+    },
+    MockServerStart,
+    MockServerStop,
+    /// Show the main window, on `page` (a route such as `/qa`) when given
+    Open {
+        page: Option<String>,
+    },
+}
+
+impl AutomationAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AutomationAction::Translate { .. } => "translate",
+            AutomationAction::Enhance { .. } => "enhance",
+            AutomationAction::RagQuery { .. } => "rag-query",
+            AutomationAction::MockServerStart => "mock-server/start",
+            AutomationAction::MockServerStop => "mock-server/stop",
+            AutomationAction::Open { .. } => "open",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationCommand {
+    #[serde(flatten)]
+    pub action: AutomationAction,
+    /// Copy the result to the clipboard; on by default for `--run`
+    /// arguments unless `--copy false`
+    pub copy: bool,
+    /// File the outcome is written to as JSON (`--out <path>`), for scripts
+    /// that need to read the result back; never taken from links
+    pub output_path: Option<String>,
+}
+
+impl AutomationCommand {
+    /// Build a command from its action (`translate`, `mock-server/start`, ...)
+    /// and parameters. Unknown parameters are rejected so typos do not go
+    /// unnoticed.
+    pub fn parse(action: &str, params: &[(String, String)], origin: CommandOrigin) -> Result<Self> {
+        let mut params = Params::new(params);
+        let copy = match params.take(&["copy"]).as_deref() {
+            None => origin == CommandOrigin::Arguments,
+            Some("1" | "true" | "yes") => true,
+            Some("0" | "false" | "no") => false,
+            Some(other) => {
+                return Err(AppError::ValidationError(format!(
+                    "copy must be true or false, not '{}'",
+                    other
+                )))
+            }
+        };
+        let output_path = params.take(&["out", "output"]);
+        if output_path.is_some() && origin == CommandOrigin::Link {
+            return Err(AppError::ValidationError(format!(
+                "out is only accepted with {} arguments, not in links",
+                RUN_ARG
+            )));
+        }
+
+        let action = match action.trim_matches('/').to_ascii_lowercase().as_str() {
+            "translate" => AutomationAction::Translate {
+                text: params.require(&["text"])?,
+                source: params.take(&["from", "source"]),
+                target: params.take(&["to", "target"]),
+            },
+            "enhance" => AutomationAction::Enhance {
+                text: params.require(&["text"])?,
+                preset: params.take(&["preset"]),
+            },
+            "rag-query" => AutomationAction::RagQuery {
+                collection: params.take(&["collection"]),
+                query: params.require(&["q", "query"])?,
+            },
+            "mock-server/start" => AutomationAction::MockServerStart,
+            "mock-server/stop" => AutomationAction::MockServerStop,
+            "open" => AutomationAction::Open {
+                page: params
+                    .take(&["page"])
+                    .map(|page| format!("/{}", page.trim_start_matches('/'))),
+            },
+            other => {
+                return Err(AppError::ValidationError(format!(
+                    "Unknown automation action '{}'",
+                    other
+                )))
+            }
+        };
+        params.finish(action.name())?;
+        if origin == CommandOrigin::Link && !matches!(action, AutomationAction::Open { .. }) {
+            return Err(AppError::ValidationError(format!(
+                "{} is only accepted with {} arguments, links can only open the app",
+                action.name(),
+                RUN_ARG
+            )));
+        }
+
+        Ok(Self {
+            action,
+            copy,
+            output_path,
+        })
+    }
+
+    /// Command of a `gadogado://<action>?<params>` link; `None` for other
+    /// links, including profile links
+    pub fn from_deep_link(link: &str) -> Option<Result<Self>> {
+        Self::from_link(link, CommandOrigin::Link)
+    }
+
+    fn from_link(link: &str, origin: CommandOrigin) -> Option<Result<Self>> {
+        let url = url::Url::parse(link.trim()).ok()?;
+        if url.scheme() != DEEP_LINK_SCHEME {
+            return None;
+        }
+        let host = url.host_str().unwrap_or_default();
+        if host.is_empty() || host == PROFILE_HOST {
+            return None;
+        }
+        let action = format!("{}{}", host, url.path().trim_end_matches('/'));
+        let params: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        Some(Self::parse(&action, &params, origin))
+    }
+
+    /// Command among launch arguments: `--run <action> --key value ...`,
+    /// `--run <link>`, or a bare link as passed by the OS on Windows and
+    /// Linux, which is treated like any other link
+    pub fn from_args<S: AsRef<str>>(args: &[S]) -> Option<Result<Self>> {
+        let args: Vec<&str> = args.iter().map(AsRef::as_ref).collect();
+        let Some(run) = args.iter().position(|arg| *arg == RUN_ARG) else {
+            return args.iter().find_map(|arg| Self::from_deep_link(arg));
+        };
+        let Some(action) = args.get(run + 1) else {
+            return Some(Err(AppError::ValidationError(format!(
+                "{} needs an action",
+                RUN_ARG
+            ))));
+        };
+        if let Some(command) = Self::from_link(action, CommandOrigin::Arguments) {
+            return Some(command);
+        }
+
+        let mut params = Vec::new();
+        let mut rest = args[run + 2..].iter();
+        while let Some(arg) = rest.next() {
+            let Some(key) = arg.strip_prefix("--") else {
+                break;
+            };
+            match key.split_once('=') {
+                Some((key, value)) => params.push((key.to_string(), value.to_string())),
+                None => match rest.next() {
+                    Some(value) => params.push((key.to_string(), value.to_string())),
+                    None => {
+                        return Some(Err(AppError::ValidationError(format!(
+                            "--{} needs a value",
+                            key
+                        ))))
+                    }
+                },
+            }
+        }
+        Some(Self::parse(action, &params, CommandOrigin::Arguments))
+    }
+}
+
+/// Parameters left to read; each is read once under one of its names
+struct Params {
+    entries: Vec<(String, String)>,
+}
+
+impl Params {
+    fn new(params: &[(String, String)]) -> Self {
+        Self {
+            entries: params
+                .iter()
+                .map(|(key, value)| (key.trim().to_ascii_lowercase(), value.clone()))
+                .collect(),
+        }
+    }
+
+    fn take(&mut self, names: &[&str]) -> Option<String> {
+        let mut found = None;
+        self.entries.retain(|(key, value)| {
+            if names.contains(&key.as_str()) {
+                found.get_or_insert_with(|| value.clone());
+                false
+            } else {
+                true
+            }
+        });
+        found.filter(|value| !value.trim().is_empty())
+    }
+
+    fn require(&mut self, names: &[&str]) -> Result<String> {
+        self.take(names)
+            .ok_or_else(|| AppError::ValidationError(format!("{} is required", names[0])))
+    }
+
+    fn finish(self, action: &str) -> Result<()> {
+        match self.entries.first() {
+            Some((key, _)) => Err(AppError::ValidationError(format!(
+                "Unknown parameter '{}' for {}",
+                key, action
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_become_commands() {
+        let command = AutomationCommand::from_deep_link("gadogado://open?page=qa")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            command.action,
+            AutomationAction::Open {
+                page: Some("/qa".to_string())
+            }
+        );
+        assert!(!command.copy);
+        assert_eq!(command.output_path, None);
+
+        assert_eq!(
+            AutomationCommand::from_deep_link("gadogado://open/")
+                .unwrap()
+                .unwrap()
+                .action,
+            AutomationAction::Open { page: None }
+        );
+        assert!(AutomationCommand::from_deep_link("gadogado://profile/acme").is_none());
+        assert!(AutomationCommand::from_deep_link("https://translate?text=a").is_none());
+    }
+
+    #[test]
+    fn arguments_become_commands() {
+        let command = AutomationCommand::from_args(&[
+            "gadogado",
+            "--background",
+            "--run",
+            "enhance",
+            "--text",
+            "fix this",
+            "--preset=concise",
+        ])
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            command.action,
+            AutomationAction::Enhance {
+                text: "fix this".to_string(),
+                preset: Some("concise".to_string()),
+            }
+        );
+        assert!(command.copy);
+
+        let command = AutomationCommand::from_args(&[
+            "gadogado",
+            "--run",
+            "translate",
+            "--text",
+            "hi",
+            "--out",
+            "result.json",
+            "--copy",
+            "false",
+        ])
+        .unwrap()
+        .unwrap();
+        assert_eq!(command.output_path.as_deref(), Some("result.json"));
+        assert!(!command.copy);
+
+        let command = AutomationCommand::from_args(&["gadogado", "--run", "open", "--page", "qa"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            command.action,
+            AutomationAction::Open {
+                page: Some("/qa".to_string())
+            }
+        );
+
+        let command = AutomationCommand::from_args(&[
+            "gadogado",
+            "--run",
+            "gadogado://rag-query?collection=Handbook&q=leave+policy",
+        ])
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            command.action,
+            AutomationAction::RagQuery {
+                collection: Some("Handbook".to_string()),
+                query: "leave policy".to_string(),
+            }
+        );
+        assert!(command.copy);
+
+        assert!(AutomationCommand::from_args(&["gadogado", "--profile", "acme"]).is_none());
+        assert!(AutomationCommand::from_args(&["gadogado", "--run"])
+            .unwrap()
+            .is_err());
+    }
+
+    #[test]
+    fn links_can_only_open_the_app() {
+        for link in [
+            "gadogado://translate?text=hi",
+            "gadogado://enhance?text=hi&copy=true",
+            "gadogado://rag-query?q=salaries",
+            "gadogado://mock-server/start",
+            "gadogado://mock-server/stop",
+            "gadogado://open?out=/home/user/.bashrc",
+        ] {
+            assert!(AutomationCommand::from_deep_link(link).unwrap().is_err());
+        }
+        let bare_link = ["gadogado", "gadogado://translate?text=hi"];
+        assert!(AutomationCommand::from_args(&bare_link).unwrap().is_err());
+    }
+
+    #[test]
+    fn invalid_commands_are_rejected() {
+        let parse = |action: &str, params: &[(&str, &str)]| {
+            let params: Vec<(String, String)> = params
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect();
+            AutomationCommand::parse(action, &params, CommandOrigin::Arguments)
+        };
+        assert!(parse("translate", &[]).is_err());
+        assert!(parse("translate", &[("text", "  ")]).is_err());
+        assert!(parse("translate", &[("text", "hi"), ("tone", "formal")]).is_err());
+        assert!(parse("rag-query", &[("collection", "3")]).is_err());
+        assert!(parse("mock-server/restart", &[]).is_err());
+        assert!(parse("mock-server/start", &[("copy", "maybe")]).is_err());
+        assert!(parse("Translate", &[("TEXT", "hi")]).is_ok());
+    }
+}
@@ -0,0 +1,155 @@
+// ============================================================
+// BACKUPS
+// ============================================================
+// Settings, manifests and restore requests for the backup
+// manager. One backup is a single archive holding a snapshot of
+// each app database and, optionally, the QA session artifacts.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::settings::SettingsSection;
+
+/// Layout version written into every manifest
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+/// Scheduled backups and how many of them to keep
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BackupSettings {
+    pub scheduled: bool,
+
+    /// Minimum time between two scheduled backups
+    pub interval_hours: u32,
+
+    /// Retention tiers: the newest scheduled backup of each of the last N
+    /// days, ISO weeks and months is kept, the rest are deleted
+    pub keep_daily: u32,
+    pub keep_weekly: u32,
+    pub keep_monthly: u32,
+
+    /// Also archive screenshots and other files under `qa_sessions`
+    pub include_qa_sessions: bool,
+
+    /// Encrypt new backups with a key kept in the OS keychain
+    pub encrypt: bool,
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        Self {
+            scheduled: true,
+            interval_hours: 24,
+            keep_daily: 7,
+            keep_weekly: 4,
+            keep_monthly: 6,
+            include_qa_sessions: true,
+            encrypt: false,
+        }
+    }
+}
+
+impl BackupSettings {
+    pub fn validate(&self) -> Result<()> {
+        if !(1..=24 * 30).contains(&self.interval_hours) {
+            return Err(AppError::ValidationError(
+                "Backup interval must be between 1 hour and 30 days".to_string(),
+            ));
+        }
+        if self.keep_daily == 0 {
+            return Err(AppError::ValidationError(
+                "Keep at least one daily backup".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl SettingsSection for BackupSettings {
+    const KEY: &'static str = "backups";
+}
+
+/// Why a backup was taken. Only scheduled backups are subject to retention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BackupReason {
+    Scheduled,
+    Manual,
+
+    /// Safety snapshot taken right before a restore
+    PreRestore,
+}
+
+impl BackupReason {
+    /// Tag used in backup file names
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackupReason::Scheduled => "scheduled",
+            BackupReason::Manual => "manual",
+            BackupReason::PreRestore => "pre_restore",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackedUpDatabase {
+    /// "app", "qa", "rag" or "training"
+    pub key: String,
+    pub label: String,
+    pub size_bytes: u64,
+}
+
+/// Stored inside the archive and next to it, so backups can be listed
+/// without decrypting them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub id: String,
+
+    /// RFC 3339
+    pub created_at: String,
+    pub reason: BackupReason,
+    pub app_version: String,
+    pub databases: Vec<BackedUpDatabase>,
+    pub includes_qa_sessions: bool,
+    pub qa_session_files: usize,
+    pub encrypted: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupEntry {
+    #[serde(flatten)]
+    pub manifest: BackupManifest,
+    pub path: String,
+
+    /// Size of the archive on disk
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RestoreRequest {
+    pub id: String,
+
+    /// Databases to restore; every database in the backup when empty
+    pub databases: Vec<String>,
+
+    /// Replace the `qa_sessions` folder with the archived one
+    pub qa_sessions: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreReport {
+    pub restored_databases: Vec<String>,
+    pub restored_qa_sessions: bool,
+
+    /// Snapshot of the data the restore replaced
+    pub safety_backup: BackupEntry,
+
+    /// Staged files are swapped in on the next launch; the app restarts itself
+    pub restart_required: bool,
+}
@@ -0,0 +1,169 @@
+// ============================================================
+// COLUMN PROFILE
+// ============================================================
+// Per-column type inference results and the suggested clean schema
+
+use serde::{Deserialize, Serialize};
+
+/// Inferred type of a CSV column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnType {
+    /// Whole numbers (thousand separators allowed)
+    Int,
+
+    /// Decimal numbers
+    Float,
+
+    /// Calendar dates or timestamps in a single consistent format
+    Date,
+
+    /// true/false, yes/no, y/n style flags
+    Bool,
+
+    /// Short values drawn from a small set (status, country, department)
+    Categorical,
+
+    /// Free-form text
+    Text,
+
+    /// Column has no non-null values
+    Empty,
+}
+
+impl ColumnType {
+    /// Whether values of this type are rewritten during preprocessing
+    pub fn is_normalized(&self) -> bool {
+        matches!(
+            self,
+            ColumnType::Int | ColumnType::Float | ColumnType::Date | ColumnType::Bool
+        )
+    }
+}
+
+impl std::fmt::Display for ColumnType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            ColumnType::Int => "int",
+            ColumnType::Float => "float",
+            ColumnType::Date => "date",
+            ColumnType::Bool => "bool",
+            ColumnType::Categorical => "categorical",
+            ColumnType::Text => "text",
+            ColumnType::Empty => "empty",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Statistics for a single CSV column
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnProfile {
+    /// Original header
+    pub name: String,
+
+    /// Cleaned header (same cleaning as `CsvField::clean_name`)
+    pub clean_name: String,
+
+    /// Inferred column type
+    pub inferred_type: ColumnType,
+
+    /// chrono format string used to parse dates (Date columns only)
+    pub date_format: Option<String>,
+
+    /// Number of rows with a null-like value ("", "null", "N/A", ...)
+    pub null_count: usize,
+
+    /// null_count / row_count (0.0 - 1.0)
+    pub null_rate: f32,
+
+    /// Number of distinct non-null values
+    pub distinct_count: usize,
+
+    /// Smallest value (normalized) for numeric and date columns
+    pub min: Option<String>,
+
+    /// Largest value (normalized) for numeric and date columns
+    pub max: Option<String>,
+
+    /// A few distinct example values, in order of appearance
+    pub sample_values: Vec<String>,
+}
+
+/// One column of the suggested cleaned schema
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestedColumn {
+    /// Suggested column name (cleaned header)
+    pub name: String,
+
+    /// Original header the column comes from
+    pub source: String,
+
+    /// Suggested type
+    pub column_type: ColumnType,
+
+    /// Whether the column contains nulls
+    pub nullable: bool,
+
+    /// Allowed values for categorical columns
+    pub categories: Vec<String>,
+}
+
+/// Column-level profile of a CSV file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvProfile {
+    /// Number of rows profiled
+    pub row_count: usize,
+
+    /// Per-column statistics, in header order
+    pub columns: Vec<ColumnProfile>,
+
+    /// Suggested cleaned schema, in header order
+    pub suggested_schema: Vec<SuggestedColumn>,
+}
+
+impl CsvProfile {
+    /// Look up a column profile by original header
+    pub fn column(&self, name: &str) -> Option<&ColumnProfile> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+
+    /// Get human-readable summary
+    pub fn summary(&self) -> String {
+        let mut lines = vec![format!(
+            "Column Profile ({} rows, {} columns):",
+            self.row_count,
+            self.columns.len()
+        )];
+
+        for column in &self.columns {
+            let mut line = format!(
+                "- {} [{}]: {:.0}% null, {} distinct",
+                column.name,
+                column.inferred_type,
+                column.null_rate * 100.0,
+                column.distinct_count
+            );
+            if let (Some(min), Some(max)) = (&column.min, &column.max) {
+                line.push_str(&format!(", range {} .. {}", min, max));
+            }
+            lines.push(line);
+        }
+
+        lines.push(String::new());
+        lines.push("Suggested Schema:".to_string());
+        for column in &self.suggested_schema {
+            lines.push(format!(
+                "- {}: {}{}",
+                column.name,
+                column.column_type,
+                if column.nullable { " (nullable)" } else { "" }
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
@@ -3,7 +3,7 @@
 // ============================================================
 // Data structures representing parsed CSV content
 
-use super::{ContentType, FieldAnalysis};
+use super::{ContentType, CsvProfile, FieldAnalysis};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -164,9 +164,18 @@ pub struct PreprocessedCsv {
     /// Field analysis statistics
     pub analysis: FieldAnalysis,
 
+    /// Per-column types and statistics, plus the suggested schema
+    pub profile: CsvProfile,
+
     /// Original headers
     pub headers: Vec<String>,
 
+    /// Detected source encoding (e.g. "UTF-8", "UTF-16LE")
+    pub encoding: String,
+
+    /// Detected field delimiter
+    pub delimiter: char,
+
     /// Processing time in milliseconds
     pub processing_time_ms: u64,
 }
@@ -7,15 +7,19 @@
 // Core types and value objects for CSV preprocessing
 // No I/O, no async, no external dependencies
 
+mod column_profile;
 mod content_type;
 mod csv_row;
 mod field_analysis;
 mod preprocessing_config;
+mod progress;
 
+pub use column_profile::{ColumnProfile, ColumnType, CsvProfile, SuggestedColumn};
 pub use content_type::ContentType;
 pub use csv_row::{CsvField, CsvRow, PreprocessedCsv};
 pub use field_analysis::FieldAnalysis;
 pub use preprocessing_config::PreprocessingConfig;
+pub use progress::{CsvProcessingPhase, CsvProgress};
 
 // Re-export commonly used types
 pub use std::collections::HashMap;
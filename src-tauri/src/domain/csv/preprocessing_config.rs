@@ -30,6 +30,15 @@ pub struct PreprocessingConfig {
 
     /// Maximum number of rows to analyze for detection (default: 1000)
     pub max_sample_rows: usize,
+
+    /// Rewrite values using the inferred column types before formatting
+    /// (numbers without thousand separators, ISO dates, true/false flags)
+    #[serde(default = "default_normalize_columns")]
+    pub normalize_columns: bool,
+}
+
+fn default_normalize_columns() -> bool {
+    true
 }
 
 impl Default for PreprocessingConfig {
@@ -42,6 +51,7 @@ impl Default for PreprocessingConfig {
             chunk_after_preprocessing: true,
             min_sample_rows: 2, // Reduced from 10 to support small datasets
             max_sample_rows: 1000,
+            normalize_columns: true,
         }
     }
 }
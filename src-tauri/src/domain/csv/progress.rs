@@ -0,0 +1,69 @@
+// ============================================================
+// PREPROCESSING PROGRESS
+// ============================================================
+// Progress snapshots emitted while a large CSV file is streamed
+
+use serde::{Deserialize, Serialize};
+
+/// Stage of a streaming preprocessing run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CsvProcessingPhase {
+    /// First pass: column profiling and content type detection
+    Analyzing,
+
+    /// Second pass: normalizing and formatting rows
+    Formatting,
+
+    /// All rows written
+    Complete,
+}
+
+/// Progress of a streaming preprocessing run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvProgress {
+    /// Current stage
+    pub phase: CsvProcessingPhase,
+
+    /// Rows handled so far in the current stage
+    pub rows_processed: usize,
+
+    /// Raw file bytes consumed so far in the current stage
+    pub bytes_read: u64,
+
+    /// File size in bytes
+    pub total_bytes: u64,
+
+    /// Overall progress across both passes (0.0 - 100.0)
+    pub percent: f32,
+}
+
+impl CsvProgress {
+    /// Create a progress snapshot; each pass accounts for half of the total
+    pub fn new(
+        phase: CsvProcessingPhase,
+        rows_processed: usize,
+        bytes_read: u64,
+        total_bytes: u64,
+    ) -> Self {
+        let pass_ratio = if total_bytes == 0 {
+            1.0
+        } else {
+            (bytes_read as f32 / total_bytes as f32).min(1.0)
+        };
+        let percent = match phase {
+            CsvProcessingPhase::Analyzing => pass_ratio * 50.0,
+            CsvProcessingPhase::Formatting => 50.0 + pass_ratio * 50.0,
+            CsvProcessingPhase::Complete => 100.0,
+        };
+
+        Self {
+            phase,
+            rows_processed,
+            bytes_read,
+            total_bytes,
+            percent,
+        }
+    }
+}
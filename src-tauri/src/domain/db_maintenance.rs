@@ -0,0 +1,85 @@
+// ============================================================
+// DATABASE MAINTENANCE
+// ============================================================
+// Request and result of `db_maintenance`: file sizes and row
+// counts for each SQLite database, plus the optional VACUUM,
+// ANALYZE, WAL checkpoint and integrity check passes.
+
+use serde::{Deserialize, Serialize};
+
+/// Which passes to run. With everything off only sizes and row counts are reported.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DbMaintenanceRequest {
+    /// Limit to these databases ("app", "qa", "rag", "training"); all when empty
+    pub databases: Vec<String>,
+    pub vacuum: bool,
+    pub analyze: bool,
+
+    /// Fold the write-ahead log back into the database and truncate it
+    pub checkpoint: bool,
+    pub integrity_check: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableStats {
+    pub name: String,
+
+    /// `None` when the table could not be counted (e.g. a virtual table whose module is missing)
+    pub row_count: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseReport {
+    pub id: String,
+    pub label: String,
+    pub path: String,
+    pub exists: bool,
+
+    /// Main file plus `-wal` and `-shm`, before any pass ran
+    pub size_before_bytes: u64,
+    pub size_after_bytes: u64,
+    pub wal_size_bytes: u64,
+    pub tables: Vec<TableStats>,
+
+    /// Problems reported by `PRAGMA integrity_check`; empty when healthy,
+    /// absent when the check was not requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrity_problems: Option<Vec<String>>,
+
+    /// Passes that ran, in order
+    pub operations: Vec<String>,
+
+    /// Why the database could not be opened or a pass failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+impl DatabaseReport {
+    pub fn reclaimed_bytes(&self) -> u64 {
+        self.size_before_bytes.saturating_sub(self.size_after_bytes)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbMaintenanceReport {
+    pub generated_at: String,
+    pub total_size_bytes: u64,
+    pub reclaimed_bytes: u64,
+    pub databases: Vec<DatabaseReport>,
+}
+
+impl DbMaintenanceReport {
+    pub fn new(databases: Vec<DatabaseReport>) -> Self {
+        Self {
+            generated_at: chrono::Local::now().to_rfc3339(),
+            total_size_bytes: databases.iter().map(|db| db.size_after_bytes).sum(),
+            reclaimed_bytes: databases.iter().map(DatabaseReport::reclaimed_bytes).sum(),
+            databases,
+        }
+    }
+}
@@ -0,0 +1,111 @@
+// ============================================================
+// SYSTEM DIAGNOSTICS
+// ============================================================
+// Result of `system_diagnostics`: one check per external
+// dependency, database, disk and port the app relies on.
+
+use serde::{Deserialize, Serialize};
+
+/// Outcome of a check, ordered from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticStatus {
+    Ok,
+    /// The app works, but a feature is degraded or unavailable
+    Warning,
+    Error,
+}
+
+/// Area a check belongs to, used to group the troubleshooting page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiagnosticCategory {
+    Runtime,
+    Ocr,
+    Training,
+    Database,
+    Storage,
+    Network,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticCheck {
+    /// Stable identifier, e.g. "node" or "db.rag"
+    pub id: String,
+    pub label: String,
+    pub category: DiagnosticCategory,
+    pub status: DiagnosticStatus,
+
+    /// One-line result ("v20.11.0", "ok", "Port 3001 is used by another process")
+    pub summary: String,
+
+    /// Raw output or error text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+
+    /// How to fix a failing check
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    pub duration_ms: u64,
+}
+
+impl DiagnosticCheck {
+    pub fn new(
+        id: &str,
+        label: &str,
+        category: DiagnosticCategory,
+        status: DiagnosticStatus,
+        summary: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.to_string(),
+            label: label.to_string(),
+            category,
+            status,
+            summary: summary.into(),
+            detail: None,
+            hint: None,
+            duration_ms: 0,
+        }
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsReport {
+    /// Worst status across all checks
+    pub status: DiagnosticStatus,
+    pub generated_at: String,
+    pub app_version: String,
+
+    /// "<os>-<arch>", e.g. "windows-x86_64"
+    pub platform: String,
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+impl DiagnosticsReport {
+    pub fn new(app_version: String, checks: Vec<DiagnosticCheck>) -> Self {
+        Self {
+            status: checks
+                .iter()
+                .map(|check| check.status)
+                .max()
+                .unwrap_or(DiagnosticStatus::Ok),
+            generated_at: chrono::Local::now().to_rfc3339(),
+            app_version,
+            platform: format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH),
+            checks,
+        }
+    }
+}
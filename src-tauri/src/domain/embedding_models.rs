@@ -0,0 +1,221 @@
+// ============================================================
+// LOCAL EMBEDDING MODELS
+// ============================================================
+// Embedding models that run in-process. The model manager downloads
+// them into `<app data>/embedding_models`, checking every file against
+// the checksum the Hugging Face hub publishes for it, and records what
+// was installed in a manifest next to the files. Models run on the
+// CPU unless GPU acceleration is turned on; a GPU provider that fails
+// to load falls back to the CPU.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::settings::SettingsSection;
+
+/// A local embedding model as listed by the model manager
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalEmbeddingModel {
+    /// Id used as the embedding model name, e.g. `all-minilm-l6-v2`
+    pub id: String,
+
+    /// Hugging Face repository the files come from
+    pub model_code: String,
+    pub description: String,
+    pub dimension: usize,
+    pub installed: bool,
+
+    /// Used by the embedding service right now
+    pub active: bool,
+
+    /// Size on disk when installed, otherwise the download size when known
+    pub size_bytes: Option<u64>,
+
+    /// Provider the active model was loaded on; `None` until first use
+    pub execution_provider: Option<EmbeddingExecutionProvider>,
+}
+
+/// One file of a model and the checksum it must match
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingModelFile {
+    /// Path inside the repository, e.g. `onnx/model.onnx`
+    pub path: String,
+    pub size: u64,
+
+    /// SHA-256 of the content, published for files stored with Git LFS
+    pub sha256: Option<String>,
+
+    /// Git blob id (SHA-1 over `blob <size>\0<content>`), published for every file
+    pub git_oid: String,
+}
+
+/// Written once every file of a model has been downloaded and verified
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingModelManifest {
+    pub model_id: String,
+    pub model_code: String,
+    pub files: Vec<EmbeddingModelFile>,
+    pub installed_at: i64,
+}
+
+impl EmbeddingModelManifest {
+    pub fn total_bytes(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+}
+
+/// Payload of the `embedding-model-download-progress` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingModelDownloadProgress {
+    pub model_id: String,
+
+    /// File being downloaded
+    pub file: String,
+    pub downloaded_bytes: u64,
+    pub total_bytes: u64,
+    pub files_done: u64,
+    pub file_count: u64,
+}
+
+/// ONNX Runtime backend a local embedding model runs on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EmbeddingExecutionProvider {
+    Cpu,
+    /// NVIDIA GPUs on Linux and Windows
+    Cuda,
+    /// Any DirectX 12 GPU on Windows
+    DirectMl,
+    /// Apple GPU and Neural Engine on macOS
+    CoreMl,
+}
+
+impl EmbeddingExecutionProvider {
+    pub const ALL: [EmbeddingExecutionProvider; 4] = [
+        EmbeddingExecutionProvider::Cpu,
+        EmbeddingExecutionProvider::Cuda,
+        EmbeddingExecutionProvider::DirectMl,
+        EmbeddingExecutionProvider::CoreMl,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            EmbeddingExecutionProvider::Cpu => "CPU",
+            EmbeddingExecutionProvider::Cuda => "CUDA",
+            EmbeddingExecutionProvider::DirectMl => "DirectML",
+            EmbeddingExecutionProvider::CoreMl => "CoreML",
+        }
+    }
+}
+
+/// How local embedding models are run
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LocalEmbeddingSettings {
+    /// Try a GPU execution provider before the CPU
+    pub gpu_acceleration: bool,
+
+    /// GPU provider to use; `None` tries every one the platform supports
+    pub gpu_provider: Option<EmbeddingExecutionProvider>,
+}
+
+impl LocalEmbeddingSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.gpu_provider == Some(EmbeddingExecutionProvider::Cpu) {
+            return Err(AppError::ValidationError(
+                "Pick a GPU provider, or leave it empty to try each one".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Providers to try in order when loading a model. The CPU is always
+    /// last, so a GPU that fails to load falls back to it.
+    pub fn provider_order(&self) -> Vec<EmbeddingExecutionProvider> {
+        use EmbeddingExecutionProvider::*;
+        let mut order = match (self.gpu_acceleration, self.gpu_provider) {
+            (false, _) => Vec::new(),
+            (true, Some(provider)) => vec![provider],
+            (true, None) => vec![Cuda, DirectMl, CoreMl],
+        };
+        order.retain(|provider| *provider != Cpu);
+        order.push(Cpu);
+        order
+    }
+}
+
+impl SettingsSection for LocalEmbeddingSettings {
+    const KEY: &'static str = "localEmbedding";
+}
+
+/// Throughput of one execution provider in a benchmark run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingProviderBenchmark {
+    pub provider: EmbeddingExecutionProvider,
+
+    /// The provider loaded the model; otherwise `error` says why not
+    pub available: bool,
+    pub load_ms: Option<u64>,
+    pub embeddings_per_sec: Option<f64>,
+    pub error: Option<String>,
+}
+
+/// Result of `rag_benchmark_embedding_providers`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingBenchmarkReport {
+    pub model_id: String,
+    pub sample_count: usize,
+    pub batch_size: usize,
+
+    /// Provider the embedding service runs the active model on, once loaded
+    pub active_provider: Option<EmbeddingExecutionProvider>,
+    pub results: Vec<EmbeddingProviderBenchmark>,
+}
+
+/// Texts of chunk-like length for measuring throughput
+pub fn benchmark_texts(count: usize) -> Vec<String> {
+    const TOPICS: [&str; 5] = [
+        "the quarterly report shows revenue growth in the retail segment",
+        "the deployment guide explains how to rotate database credentials",
+        "customers asked for offline support in the mobile application",
+        "the meeting notes list open questions about the migration plan",
+        "the API returns paginated results sorted by creation date",
+    ];
+    (0..count)
+        .map(|index| {
+            format!(
+                "Passage {}: {}. It is followed by supporting detail so the text is about as long as a typical retrieved chunk.",
+                index + 1,
+                TOPICS[index % TOPICS.len()]
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use EmbeddingExecutionProvider::*;
+
+    #[test]
+    fn provider_order_ends_with_cpu() {
+        let mut settings = LocalEmbeddingSettings::default();
+        assert_eq!(settings.provider_order(), vec![Cpu]);
+
+        settings.gpu_acceleration = true;
+        assert_eq!(settings.provider_order(), vec![Cuda, DirectMl, CoreMl, Cpu]);
+
+        settings.gpu_provider = Some(CoreMl);
+        assert_eq!(settings.provider_order(), vec![CoreMl, Cpu]);
+        settings.validate().unwrap();
+
+        settings.gpu_provider = Some(Cpu);
+        assert!(settings.validate().is_err());
+    }
+}
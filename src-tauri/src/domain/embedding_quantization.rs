@@ -0,0 +1,316 @@
+// ============================================================
+// EMBEDDING STORAGE QUANTIZATION
+// ============================================================
+// Chunk embeddings can be stored at lower precision to keep the RAG
+// database small. Float32 blobs are the little-endian values as they
+// always were; quantized blobs start with a tag byte and the dimension,
+// which keeps their length off a multiple of four so they are never
+// mistaken for one. Float16 halves the size, int8 quarters it with one
+// scale per vector, and both are scored against a query without being
+// expanded back to float32 first.
+
+use half::f16;
+use serde::{Deserialize, Serialize};
+
+const FLOAT16_TAG: u8 = 0x16;
+const INT8_TAG: u8 = 0x08;
+
+/// Tag byte plus the dimension as a little-endian u16
+const HEADER_LEN: usize = 3;
+
+/// Precision chunk embeddings are stored at
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbeddingQuantization {
+    #[default]
+    Float32,
+    Float16,
+    Int8,
+}
+
+impl EmbeddingQuantization {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EmbeddingQuantization::Float32 => "float32",
+            EmbeddingQuantization::Float16 => "float16",
+            EmbeddingQuantization::Int8 => "int8",
+        }
+    }
+
+    /// `embedding` at this precision. Vectors too long for the header stay
+    /// float32.
+    pub fn quantize(&self, embedding: &[f32]) -> StoredEmbedding {
+        if embedding.len() > u16::MAX as usize {
+            return StoredEmbedding::Float32(embedding.to_vec());
+        }
+        match self {
+            EmbeddingQuantization::Float32 => StoredEmbedding::Float32(embedding.to_vec()),
+            EmbeddingQuantization::Float16 => {
+                StoredEmbedding::Float16(embedding.iter().map(|v| f16::from_f32(*v)).collect())
+            }
+            EmbeddingQuantization::Int8 => {
+                let max = embedding.iter().fold(0.0f32, |max, v| max.max(v.abs()));
+                let scale = if max > 0.0 { max / 127.0 } else { 1.0 };
+                let values = embedding
+                    .iter()
+                    .map(|v| (v / scale).round().clamp(-127.0, 127.0) as i8)
+                    .collect();
+                StoredEmbedding::Int8 { scale, values }
+            }
+        }
+    }
+
+    /// `embedding` as the blob stored for a chunk
+    pub fn encode(&self, embedding: &[f32]) -> Vec<u8> {
+        self.quantize(embedding).to_bytes()
+    }
+}
+
+/// A chunk embedding as stored, scored without dequantizing
+#[derive(Debug, Clone, PartialEq)]
+pub enum StoredEmbedding {
+    Float32(Vec<f32>),
+    Float16(Vec<f16>),
+    /// Each value times `scale` approximates the original
+    Int8 {
+        scale: f32,
+        values: Vec<i8>,
+    },
+}
+
+impl StoredEmbedding {
+    /// Decode a stored blob; `None` when it is not a valid embedding
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len().is_multiple_of(4) {
+            return Some(StoredEmbedding::Float32(
+                bytes
+                    .chunks_exact(4)
+                    .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                    .collect(),
+            ));
+        }
+        if bytes.len() < HEADER_LEN {
+            return None;
+        }
+        let dimension = u16::from_le_bytes([bytes[1], bytes[2]]) as usize;
+        let payload = &bytes[HEADER_LEN..];
+        match bytes[0] {
+            FLOAT16_TAG if payload.len() == dimension * 2 => Some(StoredEmbedding::Float16(
+                payload
+                    .chunks_exact(2)
+                    .map(|b| f16::from_le_bytes([b[0], b[1]]))
+                    .collect(),
+            )),
+            INT8_TAG if payload.len() == int8_payload_len(dimension) => {
+                let scale = f32::from_le_bytes([payload[0], payload[1], payload[2], payload[3]]);
+                let values = payload[4..4 + dimension].iter().map(|b| *b as i8).collect();
+                Some(StoredEmbedding::Int8 { scale, values })
+            }
+            _ => None,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            StoredEmbedding::Float32(values) => {
+                values.iter().flat_map(|v| v.to_le_bytes()).collect()
+            }
+            StoredEmbedding::Float16(values) => {
+                let mut bytes = header(FLOAT16_TAG, values.len());
+                bytes.extend(values.iter().flat_map(|v| v.to_le_bytes()));
+                bytes
+            }
+            StoredEmbedding::Int8 { scale, values } => {
+                let mut bytes = header(INT8_TAG, values.len());
+                bytes.extend_from_slice(&scale.to_le_bytes());
+                bytes.extend(values.iter().map(|v| *v as u8));
+                // Padding keeps the length off a multiple of four
+                bytes.resize(HEADER_LEN + int8_payload_len(values.len()), 0);
+                bytes
+            }
+        }
+    }
+
+    pub fn quantization(&self) -> EmbeddingQuantization {
+        match self {
+            StoredEmbedding::Float32(_) => EmbeddingQuantization::Float32,
+            StoredEmbedding::Float16(_) => EmbeddingQuantization::Float16,
+            StoredEmbedding::Int8 { .. } => EmbeddingQuantization::Int8,
+        }
+    }
+
+    pub fn dimension(&self) -> usize {
+        match self {
+            StoredEmbedding::Float32(values) => values.len(),
+            StoredEmbedding::Float16(values) => values.len(),
+            StoredEmbedding::Int8 { values, .. } => values.len(),
+        }
+    }
+
+    /// Values expanded back to float32
+    pub fn to_f32(&self) -> Vec<f32> {
+        match self {
+            StoredEmbedding::Float32(values) => values.clone(),
+            StoredEmbedding::Float16(values) => values.iter().map(|v| v.to_f32()).collect(),
+            StoredEmbedding::Int8 { scale, values } => {
+                values.iter().map(|v| *v as f32 * scale).collect()
+            }
+        }
+    }
+
+    /// Same values at another precision
+    pub fn requantize(&self, quantization: EmbeddingQuantization) -> StoredEmbedding {
+        if self.quantization() == quantization {
+            return self.clone();
+        }
+        quantization.quantize(&self.to_f32())
+    }
+
+    /// Dot product with a float32 query of the same length
+    pub fn dot(&self, query: &[f32]) -> f32 {
+        match self {
+            StoredEmbedding::Float32(values) => values.iter().zip(query).map(|(v, q)| v * q).sum(),
+            StoredEmbedding::Float16(values) => {
+                values.iter().zip(query).map(|(v, q)| v.to_f32() * q).sum()
+            }
+            StoredEmbedding::Int8 { scale, values } => {
+                values
+                    .iter()
+                    .zip(query)
+                    .map(|(v, q)| *v as f32 * q)
+                    .sum::<f32>()
+                    * scale
+            }
+        }
+    }
+
+    pub fn norm_squared(&self) -> f32 {
+        match self {
+            StoredEmbedding::Float32(values) => values.iter().map(|v| v * v).sum(),
+            StoredEmbedding::Float16(values) => {
+                values.iter().map(|v| v.to_f32() * v.to_f32()).sum()
+            }
+            StoredEmbedding::Int8 { scale, values } => {
+                values
+                    .iter()
+                    .map(|v| (*v as i32 * *v as i32) as f32)
+                    .sum::<f32>()
+                    * scale
+                    * scale
+            }
+        }
+    }
+}
+
+/// Outcome of converting a collection's stored embeddings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingQuantizationReport {
+    pub collection_id: i64,
+    pub quantization: EmbeddingQuantization,
+    /// Embeddings rewritten at the new precision
+    pub converted: usize,
+    /// Embeddings already at it
+    pub unchanged: usize,
+    /// Blobs that could not be decoded and were left as they are
+    pub unreadable: usize,
+    /// Stored embedding bytes before and after, encryption included
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    /// Whether the database file was compacted to give the space back
+    pub compacted: bool,
+}
+
+fn header(tag: u8, dimension: usize) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_LEN + dimension * 2);
+    bytes.push(tag);
+    bytes.extend_from_slice(&(dimension as u16).to_le_bytes());
+    bytes
+}
+
+/// Scale plus one byte per value, padded to a multiple of four
+fn int8_payload_len(dimension: usize) -> usize {
+    (4 + dimension).div_ceil(4) * 4
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [EmbeddingQuantization; 3] = [
+        EmbeddingQuantization::Float32,
+        EmbeddingQuantization::Float16,
+        EmbeddingQuantization::Int8,
+    ];
+
+    fn sample(dimension: usize) -> Vec<f32> {
+        (0..dimension)
+            .map(|i| (i as f32 * 0.37).sin() * 0.8)
+            .collect()
+    }
+
+    #[test]
+    fn quantized_blobs_round_trip_and_never_look_like_float32() {
+        for dimension in [1, 2, 3, 4, 5, 383, 384, 768] {
+            let embedding = sample(dimension);
+            for quantization in ALL {
+                let bytes = quantization.encode(&embedding);
+                if quantization != EmbeddingQuantization::Float32 {
+                    assert!(
+                        !bytes.len().is_multiple_of(4),
+                        "{:?} at {}",
+                        quantization,
+                        dimension
+                    );
+                }
+                let stored = StoredEmbedding::from_bytes(&bytes).unwrap();
+                assert_eq!(stored.quantization(), quantization);
+                assert_eq!(stored.dimension(), dimension);
+            }
+        }
+
+        let embedding = sample(384);
+        assert_eq!(
+            StoredEmbedding::from_bytes(&EmbeddingQuantization::Float32.encode(&embedding))
+                .unwrap()
+                .to_f32(),
+            embedding
+        );
+        assert_eq!(
+            EmbeddingQuantization::Float16.encode(&embedding).len(),
+            3 + 768
+        );
+        assert_eq!(
+            EmbeddingQuantization::Int8.encode(&embedding).len(),
+            3 + 388
+        );
+        assert!(StoredEmbedding::from_bytes(&[FLOAT16_TAG, 9, 0, 1, 2]).is_none());
+    }
+
+    #[test]
+    fn quantized_scores_stay_close_to_float32() {
+        let embedding = sample(384);
+        let query: Vec<f32> = sample(384).iter().rev().map(|v| v + 0.1).collect();
+        let exact = StoredEmbedding::Float32(embedding.clone());
+
+        for quantization in [EmbeddingQuantization::Float16, EmbeddingQuantization::Int8] {
+            let stored = quantization.quantize(&embedding);
+            let dot = exact.dot(&query);
+            assert!(
+                (stored.dot(&query) - dot).abs() < dot.abs() * 0.01,
+                "{:?}",
+                quantization
+            );
+            let norm = exact.norm_squared();
+            assert!((stored.norm_squared() - norm).abs() < norm * 0.01);
+            let expanded = stored.to_f32();
+            assert!(embedding
+                .iter()
+                .zip(&expanded)
+                .all(|(a, b)| (a - b).abs() < 0.01));
+        }
+
+        let zero = EmbeddingQuantization::Int8.quantize(&[0.0, 0.0]);
+        assert_eq!(zero.to_f32(), vec![0.0, 0.0]);
+    }
+}
@@ -0,0 +1,295 @@
+// ============================================================
+// ENHANCEMENT PRESETS
+// ============================================================
+// Named tone, length and format choices for prompt enhancement,
+// e.g. "formal email" or "shorter commit message". The enhance
+// use case adds a preset's instructions to the system prompt. The
+// default preset applies where no preset is picked, such as the
+// enhance shortcut.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::settings::SettingsSection;
+
+const MAX_PRESETS: usize = 30;
+const MAX_NAME_CHARS: usize = 60;
+const MAX_INSTRUCTION_CHARS: usize = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EnhanceTone {
+    Formal,
+    Casual,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EnhanceLength {
+    Shorter,
+    Longer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EnhanceFormat {
+    Bullets,
+    Email,
+    CommitMessage,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnhancePreset {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub tone: Option<EnhanceTone>,
+    #[serde(default)]
+    pub length: Option<EnhanceLength>,
+    #[serde(default)]
+    pub format: Option<EnhanceFormat>,
+    /// Free-form instructions added after the structured ones
+    #[serde(default)]
+    pub instructions: Option<String>,
+}
+
+impl EnhancePreset {
+    fn new(
+        id: &str,
+        name: &str,
+        tone: Option<EnhanceTone>,
+        length: Option<EnhanceLength>,
+        format: Option<EnhanceFormat>,
+    ) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            tone,
+            length,
+            format,
+            instructions: None,
+        }
+    }
+
+    /// Lines appended to the enhancement system prompt
+    pub fn instructions(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(tone) = self.tone {
+            lines.push(
+                match tone {
+                    EnhanceTone::Formal => {
+                        "Use a formal, professional tone; avoid slang and contractions."
+                    }
+                    EnhanceTone::Casual => "Use a casual, friendly tone, as in a chat message.",
+                }
+                .to_string(),
+            );
+        }
+        if let Some(length) = self.length {
+            lines.push(
+                match length {
+                    EnhanceLength::Shorter => {
+                        "Make the text noticeably shorter; keep only what matters."
+                    }
+                    EnhanceLength::Longer => {
+                        "Expand the text with relevant detail and context; don't pad it."
+                    }
+                }
+                .to_string(),
+            );
+        }
+        if let Some(format) = self.format {
+            lines.push(
+                match format {
+                    EnhanceFormat::Bullets => {
+                        "Format the result as a bulleted list, one point per line starting with \"- \"."
+                    }
+                    EnhanceFormat::Email => {
+                        "Format the result as an email with a greeting, a short body and a sign-off; no subject line."
+                    }
+                    EnhanceFormat::CommitMessage => {
+                        "Format the result as a git commit message: an imperative summary line of at most 72 characters, a blank line, then a short body if needed."
+                    }
+                }
+                .to_string(),
+            );
+        }
+        if let Some(instructions) = self
+            .instructions
+            .as_deref()
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+        {
+            lines.push(instructions.to_string());
+        }
+        lines
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EnhancePresetSettings {
+    pub presets: Vec<EnhancePreset>,
+
+    /// Preset used when none is picked; `None` enhances without one
+    pub default_preset_id: Option<String>,
+}
+
+impl Default for EnhancePresetSettings {
+    fn default() -> Self {
+        use EnhanceFormat::*;
+        use EnhanceLength::*;
+        use EnhanceTone::*;
+        Self {
+            presets: vec![
+                EnhancePreset::new("formal", "Formal", Some(Formal), None, None),
+                EnhancePreset::new("casual", "Casual", Some(Casual), None, None),
+                EnhancePreset::new("shorter", "Shorter", None, Some(Shorter), None),
+                EnhancePreset::new("longer", "Longer", None, Some(Longer), None),
+                EnhancePreset::new(
+                    "bullets",
+                    "Bullet points",
+                    None,
+                    Some(Shorter),
+                    Some(Bullets),
+                ),
+                EnhancePreset::new("email", "Email", Some(Formal), None, Some(Email)),
+                EnhancePreset::new(
+                    "commit",
+                    "Commit message",
+                    None,
+                    Some(Shorter),
+                    Some(CommitMessage),
+                ),
+            ],
+            default_preset_id: None,
+        }
+    }
+}
+
+impl EnhancePresetSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.presets.len() > MAX_PRESETS {
+            return Err(AppError::ValidationError(format!(
+                "At most {} enhancement presets are allowed",
+                MAX_PRESETS
+            )));
+        }
+        let mut ids: Vec<&str> = Vec::new();
+        for preset in &self.presets {
+            let id = preset.id.trim();
+            if id.is_empty() || ids.contains(&id) {
+                return Err(AppError::ValidationError(format!(
+                    "Enhancement preset ids must be unique and not empty: '{}'",
+                    preset.id
+                )));
+            }
+            ids.push(id);
+
+            let name_chars = preset.name.trim().chars().count();
+            if name_chars == 0 || name_chars > MAX_NAME_CHARS {
+                return Err(AppError::ValidationError(format!(
+                    "Enhancement preset '{}' needs a name of at most {} characters",
+                    id, MAX_NAME_CHARS
+                )));
+            }
+            if preset
+                .instructions
+                .as_deref()
+                .is_some_and(|text| text.chars().count() > MAX_INSTRUCTION_CHARS)
+            {
+                return Err(AppError::ValidationError(format!(
+                    "Instructions of enhancement preset '{}' must be at most {} characters",
+                    id, MAX_INSTRUCTION_CHARS
+                )));
+            }
+            if preset.instructions().is_empty() {
+                return Err(AppError::ValidationError(format!(
+                    "Enhancement preset '{}' sets no tone, length, format or instructions",
+                    id
+                )));
+            }
+        }
+        if let Some(default_id) = &self.default_preset_id {
+            if self.find(default_id).is_none() {
+                return Err(AppError::ValidationError(format!(
+                    "Default enhancement preset '{}' does not exist",
+                    default_id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn find(&self, id: &str) -> Option<&EnhancePreset> {
+        let id = id.trim();
+        self.presets.iter().find(|preset| preset.id.trim() == id)
+    }
+
+    /// The picked preset, else the default one. An unknown id is an error
+    /// rather than a silent plain enhancement.
+    pub fn resolve(&self, preset_id: Option<&str>) -> Result<Option<&EnhancePreset>> {
+        match preset_id.map(str::trim).filter(|id| !id.is_empty()) {
+            Some(id) => self.find(id).map(Some).ok_or_else(|| {
+                AppError::ValidationError(format!("Unknown enhancement preset '{}'", id))
+            }),
+            None => Ok(self
+                .default_preset_id
+                .as_deref()
+                .and_then(|id| self.find(id))),
+        }
+    }
+}
+
+impl SettingsSection for EnhancePresetSettings {
+    const KEY: &'static str = "enhancePresets";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_are_valid_and_compose_instructions() {
+        let settings = EnhancePresetSettings::default();
+        settings.validate().unwrap();
+
+        let commit = settings.find("commit").unwrap();
+        let lines = commit.instructions();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("shorter"));
+        assert!(lines[1].contains("git commit message"));
+    }
+
+    #[test]
+    fn resolve_prefers_picked_then_default_preset() {
+        let mut settings = EnhancePresetSettings::default();
+        assert_eq!(settings.resolve(None).unwrap(), None);
+
+        settings.default_preset_id = Some("email".to_string());
+        assert_eq!(settings.resolve(None).unwrap().unwrap().id, "email");
+        assert_eq!(
+            settings.resolve(Some(" casual ")).unwrap().unwrap().id,
+            "casual"
+        );
+        assert_eq!(settings.resolve(Some("")).unwrap().unwrap().id, "email");
+        assert!(settings.resolve(Some("pirate")).is_err());
+
+        settings.default_preset_id = Some("pirate".to_string());
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn presets_need_unique_ids_and_some_instruction() {
+        let mut settings = EnhancePresetSettings::default();
+        settings.presets[1].id = "formal".to_string();
+        assert!(settings.validate().is_err());
+
+        let empty = EnhancePresetSettings {
+            presets: vec![EnhancePreset::new("plain", "Plain", None, None, None)],
+            default_preset_id: None,
+        };
+        assert!(empty.validate().is_err());
+    }
+}
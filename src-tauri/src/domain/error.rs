@@ -1,7 +1,133 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Broad class of an error, for deciding how to present it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ErrorCategory {
+    /// The input was rejected; fix it and retry
+    Validation,
+    NotFound,
+    /// A setting is missing or wrong
+    Config,
+    /// A server or provider could not be reached
+    Network,
+    /// An external program is missing, too old or failed to install
+    Dependency,
+    Security,
+    Storage,
+    Internal,
+}
+
+/// Stable identifier of an error that the UI and HTTP clients can branch on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    Internal,
+    NotFound,
+    Validation,
+    Parse,
+    Llm,
+    Security,
+    Database,
+    Io,
+    /// An external program is not installed or not on PATH
+    DependencyMissing,
+    /// An external program is installed but too old
+    DependencyOutdated,
+    /// Installing or downloading a dependency failed
+    DependencyInstallFailed,
+    /// A setting the operation needs is empty
+    ConfigMissing,
+    /// A server or provider could not be reached
+    NetworkUnavailable,
+    /// The provider rejected the credentials
+    ProviderUnauthorized,
+    /// The provider asked to slow down
+    ProviderRateLimited,
+}
+
+impl ErrorCode {
+    pub fn category(self) -> ErrorCategory {
+        match self {
+            ErrorCode::Validation | ErrorCode::Parse => ErrorCategory::Validation,
+            ErrorCode::NotFound => ErrorCategory::NotFound,
+            ErrorCode::ConfigMissing | ErrorCode::ProviderUnauthorized => ErrorCategory::Config,
+            ErrorCode::Llm | ErrorCode::NetworkUnavailable | ErrorCode::ProviderRateLimited => {
+                ErrorCategory::Network
+            }
+            ErrorCode::DependencyMissing
+            | ErrorCode::DependencyOutdated
+            | ErrorCode::DependencyInstallFailed => ErrorCategory::Dependency,
+            ErrorCode::Security => ErrorCategory::Security,
+            ErrorCode::Database | ErrorCode::Io => ErrorCategory::Storage,
+            ErrorCode::Internal => ErrorCategory::Internal,
+        }
+    }
+
+    /// What the user can do about the error when the site raising it gave no
+    /// more specific advice
+    pub fn default_hint(self) -> Option<&'static str> {
+        match self {
+            ErrorCode::DependencyMissing => {
+                Some("Install the missing program, make sure it is on PATH and restart the app")
+            }
+            ErrorCode::DependencyOutdated => Some("Update the program and restart the app"),
+            ErrorCode::DependencyInstallFailed => {
+                Some("Check the network connection and retry; the install resumes where it stopped")
+            }
+            ErrorCode::ConfigMissing => Some("Fill in the missing value in Settings"),
+            ErrorCode::NetworkUnavailable => {
+                Some("Check the network connection and that the server or provider is running")
+            }
+            ErrorCode::ProviderUnauthorized => Some("Check the provider's API key in Settings"),
+            ErrorCode::ProviderRateLimited => {
+                Some("Wait a moment before retrying, or switch to another model")
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Advice for a program that could not be started, keyed by its file stem
+const INSTALL_HINTS: &[(&str, &str)] = &[
+    (
+        "node",
+        "Install Node.js 18 or newer from https://nodejs.org and restart the app",
+    ),
+    (
+        "npm",
+        "Install Node.js 18 or newer from https://nodejs.org, which includes npm, and restart the app",
+    ),
+    (
+        "npx",
+        "Install Node.js 18 or newer from https://nodejs.org, which includes npx, and restart the app",
+    ),
+    (
+        "tesseract",
+        "Install Tesseract OCR (https://github.com/tesseract-ocr/tesseract) or set its path in Settings",
+    ),
+    (
+        "pdftoppm",
+        "Install Poppler, which provides pdftoppm, or set its path in Settings",
+    ),
+    (
+        "ollama",
+        "Install Ollama from https://ollama.com/download or set its path in Settings",
+    ),
+    (
+        "python",
+        "Install Python 3 and make sure `python` is on PATH, then restart the app",
+    ),
+    (
+        "python3",
+        "Install Python 3 and make sure `python3` is on PATH, then restart the app",
+    ),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "ErrorPayload", from = "ErrorPayload")]
 pub enum AppError {
     Internal(String),
     NotFound(String),
@@ -11,6 +137,43 @@ pub enum AppError {
     SecurityError(String),
     DatabaseError(String),
     IoError(String),
+    /// Error with a specific code and, usually, advice on how to fix it
+    Coded {
+        code: ErrorCode,
+        message: String,
+        hint: Option<String>,
+    },
+}
+
+/// How an `AppError` reaches the frontend and HTTP clients
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorPayload {
+    pub code: ErrorCode,
+    pub category: ErrorCategory,
+    pub message: String,
+    /// What the user can do about it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl From<AppError> for ErrorPayload {
+    fn from(err: AppError) -> Self {
+        let code = err.code();
+        let hint = err.hint().map(str::to_string);
+        ErrorPayload {
+            code,
+            category: code.category(),
+            message: err.into_message(),
+            hint,
+        }
+    }
+}
+
+impl From<ErrorPayload> for AppError {
+    fn from(payload: ErrorPayload) -> Self {
+        AppError::coded(payload.code, payload.message).with_hint_opt(payload.hint)
+    }
 }
 
 impl fmt::Display for AppError {
@@ -24,6 +187,152 @@ impl fmt::Display for AppError {
             AppError::SecurityError(msg) => write!(f, "Security error: {}", msg),
             AppError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
             AppError::IoError(msg) => write!(f, "IO error: {}", msg),
+            AppError::Coded { message, hint, .. } => match hint {
+                Some(hint) => write!(f, "{}. {}", message, hint),
+                None => write!(f, "{}", message),
+            },
+        }
+    }
+}
+
+impl AppError {
+    /// Error with `code`, using the code's default hint
+    pub fn coded(code: ErrorCode, message: impl Into<String>) -> Self {
+        let message = message.into();
+        match code {
+            ErrorCode::Internal => AppError::Internal(message),
+            ErrorCode::NotFound => AppError::NotFound(message),
+            ErrorCode::Validation => AppError::ValidationError(message),
+            ErrorCode::Parse => AppError::ParseError(message),
+            ErrorCode::Llm => AppError::LLMError(message),
+            ErrorCode::Security => AppError::SecurityError(message),
+            ErrorCode::Database => AppError::DatabaseError(message),
+            ErrorCode::Io => AppError::IoError(message),
+            code => AppError::Coded {
+                code,
+                message,
+                hint: None,
+            },
+        }
+    }
+
+    /// Attach advice on how to fix the error, replacing the code's default
+    pub fn with_hint(self, hint: impl Into<String>) -> Self {
+        self.with_hint_opt(Some(hint.into()))
+    }
+
+    fn with_hint_opt(self, hint: Option<String>) -> Self {
+        let Some(hint) = hint else {
+            return self;
+        };
+        if self.code().default_hint() == Some(hint.as_str()) {
+            return self;
+        }
+        let code = self.code();
+        AppError::Coded {
+            code,
+            message: self.into_message(),
+            hint: Some(hint),
+        }
+    }
+
+    /// Failure to start an external program. A missing program becomes
+    /// `DependencyMissing` with instructions for installing it.
+    pub fn spawn(program: &str, err: std::io::Error) -> Self {
+        if err.kind() != std::io::ErrorKind::NotFound {
+            return AppError::Internal(format!("Failed to start {}: {}", program, err));
+        }
+        let stem = std::path::Path::new(program)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or(program)
+            .to_ascii_lowercase();
+        let error = AppError::coded(
+            ErrorCode::DependencyMissing,
+            format!("`{}` is not installed or not on PATH", program),
+        );
+        match INSTALL_HINTS.iter().find(|(name, _)| *name == stem) {
+            Some((_, hint)) => error.with_hint(*hint),
+            None => error,
+        }
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AppError::Internal(_) => ErrorCode::Internal,
+            AppError::NotFound(_) => ErrorCode::NotFound,
+            AppError::ValidationError(_) => ErrorCode::Validation,
+            AppError::ParseError(_) => ErrorCode::Parse,
+            AppError::LLMError(_) => ErrorCode::Llm,
+            AppError::SecurityError(_) => ErrorCode::Security,
+            AppError::DatabaseError(_) => ErrorCode::Database,
+            AppError::IoError(_) => ErrorCode::Io,
+            AppError::Coded { code, .. } => *code,
+        }
+    }
+
+    pub fn category(&self) -> ErrorCategory {
+        self.code().category()
+    }
+
+    /// The message without the kind prefix `Display` adds
+    pub fn message(&self) -> &str {
+        match self {
+            AppError::Internal(msg)
+            | AppError::NotFound(msg)
+            | AppError::ValidationError(msg)
+            | AppError::ParseError(msg)
+            | AppError::LLMError(msg)
+            | AppError::SecurityError(msg)
+            | AppError::DatabaseError(msg)
+            | AppError::IoError(msg)
+            | AppError::Coded { message: msg, .. } => msg,
+        }
+    }
+
+    pub fn hint(&self) -> Option<&str> {
+        match self {
+            AppError::Coded {
+                hint: Some(hint), ..
+            } => Some(hint),
+            _ => self.code().default_hint(),
+        }
+    }
+
+    fn into_message(self) -> String {
+        match self {
+            AppError::Internal(msg)
+            | AppError::NotFound(msg)
+            | AppError::ValidationError(msg)
+            | AppError::ParseError(msg)
+            | AppError::LLMError(msg)
+            | AppError::SecurityError(msg)
+            | AppError::DatabaseError(msg)
+            | AppError::IoError(msg)
+            | AppError::Coded { message: msg, .. } => msg,
+        }
+    }
+
+    /// Rewrite the message while keeping the error kind
+    pub fn map_message(self, f: impl FnOnce(String) -> String) -> Self {
+        match self {
+            AppError::Internal(msg) => AppError::Internal(f(msg)),
+            AppError::NotFound(msg) => AppError::NotFound(f(msg)),
+            AppError::ValidationError(msg) => AppError::ValidationError(f(msg)),
+            AppError::ParseError(msg) => AppError::ParseError(f(msg)),
+            AppError::LLMError(msg) => AppError::LLMError(f(msg)),
+            AppError::SecurityError(msg) => AppError::SecurityError(f(msg)),
+            AppError::DatabaseError(msg) => AppError::DatabaseError(f(msg)),
+            AppError::IoError(msg) => AppError::IoError(f(msg)),
+            AppError::Coded {
+                code,
+                message,
+                hint,
+            } => AppError::Coded {
+                code,
+                message: f(message),
+                hint,
+            },
         }
     }
 }
@@ -38,3 +347,51 @@ impl From<std::io::Error> for AppError {
 }
 
 pub type Result<T> = std::result::Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_code_category_and_hint() {
+        let err = AppError::spawn(
+            "node",
+            std::io::Error::new(std::io::ErrorKind::NotFound, "No such file or directory"),
+        );
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["code"], "dependency_missing");
+        assert_eq!(json["category"], "dependency");
+        assert!(json["hint"].as_str().unwrap().contains("nodejs.org"));
+
+        let back: AppError = serde_json::from_value(json).unwrap();
+        assert_eq!(back.code(), ErrorCode::DependencyMissing);
+        assert_eq!(back.message(), err.message());
+    }
+
+    #[test]
+    fn legacy_variants_keep_their_message_without_prefix() {
+        let json =
+            serde_json::to_value(AppError::ValidationError("Goal is required.".into())).unwrap();
+        assert_eq!(json["code"], "validation");
+        assert_eq!(json["message"], "Goal is required.");
+        assert!(json.get("hint").is_none());
+
+        let back: AppError = serde_json::from_value(json).unwrap();
+        assert!(matches!(back, AppError::ValidationError(_)));
+    }
+
+    #[test]
+    fn spawn_failures_other_than_missing_stay_internal() {
+        let err = AppError::spawn(
+            "/usr/bin/tesseract",
+            std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied"),
+        );
+        assert_eq!(err.code(), ErrorCode::Internal);
+
+        let err = AppError::spawn(
+            "/usr/bin/tesseract",
+            std::io::Error::new(std::io::ErrorKind::NotFound, "missing"),
+        );
+        assert!(err.hint().unwrap().contains("Tesseract"));
+    }
+}
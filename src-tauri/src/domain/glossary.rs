@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A glossary rule applied to translations.
+///
+/// A term either maps to a required target term, or is marked
+/// `do_not_translate` and must appear verbatim in the output.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GlossaryEntry {
+    pub id: Option<i64>,
+    pub source_term: String,
+    pub target_term: Option<String>,
+    /// Restrict the rule to a source language; `None` applies to any.
+    pub source_lang: Option<String>,
+    /// Restrict the rule to a target language; `None` applies to any.
+    pub target_lang: Option<String>,
+    #[serde(default)]
+    pub do_not_translate: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    pub notes: Option<String>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GlossaryViolationKind {
+    /// A mapped source term was not rendered with its required target term.
+    MissingTargetTerm,
+    /// A protected term was translated or dropped.
+    ProtectedTermAltered,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct GlossaryViolation {
+    pub entry_id: Option<i64>,
+    pub source_term: String,
+    pub expected: String,
+    pub kind: GlossaryViolationKind,
+    /// Whether the output was rewritten to satisfy the rule.
+    pub corrected: bool,
+}
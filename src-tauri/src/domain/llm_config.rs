@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum LLMProvider {
     Local,
@@ -35,7 +36,7 @@ impl fmt::Display for LLMProvider {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, ToSchema)]
 pub struct LLMConfig {
     pub provider: LLMProvider,
     #[serde(alias = "baseUrl")]
@@ -55,6 +56,20 @@ pub struct ChatMessage {
     pub content: String,
 }
 
+// Hand-written so the API key never ends up in `{:?}` output
+impl fmt::Debug for LLMConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LLMConfig")
+            .field("provider", &self.provider)
+            .field("base_url", &self.base_url)
+            .field("model", &self.model)
+            .field("api_key", &self.api_key.as_ref().map(|_| "[REDACTED]"))
+            .field("max_tokens", &self.max_tokens)
+            .field("temperature", &self.temperature)
+            .finish()
+    }
+}
+
 impl Default for LLMConfig {
     fn default() -> Self {
         Self {
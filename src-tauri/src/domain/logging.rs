@@ -0,0 +1,205 @@
+// ============================================================
+// LOGGING
+// ============================================================
+// Persisted log records, query parameters and the log settings
+// section (levels per source and retention).
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+
+use crate::domain::settings::SettingsSection;
+
+/// Log severity, ordered from most to least verbose
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    /// Threshold only: disables a source entirely
+    Off,
+}
+
+impl LogLevel {
+    /// Parse "INFO", "warn", "Warning", ... Unknown labels map to `Info`.
+    pub fn parse(label: &str) -> Self {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "trace" => LogLevel::Trace,
+            "debug" => LogLevel::Debug,
+            "warn" | "warning" => LogLevel::Warn,
+            "error" | "fatal" => LogLevel::Error,
+            "off" | "none" => LogLevel::Off,
+            _ => LogLevel::Info,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Trace => "TRACE",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+            LogLevel::Off => "OFF",
+        }
+    }
+}
+
+/// One line of a log file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogRecord {
+    /// RFC 3339 timestamp with local offset
+    pub timestamp: String,
+    pub level: LogLevel,
+
+    /// `add_log` source ("RAG", "MockServer", ...) or the tracing target
+    pub source: String,
+    pub message: String,
+
+    /// Extra structured fields attached to the event
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub fields: Map<String, JsonValue>,
+}
+
+/// Filters for `logs_query`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogQuery {
+    /// Only these sources (case-insensitive); empty means all
+    #[serde(default)]
+    pub sources: Vec<String>,
+
+    /// Minimum severity
+    #[serde(default)]
+    pub min_level: Option<LogLevel>,
+
+    /// Inclusive RFC 3339 lower bound
+    #[serde(default)]
+    pub since: Option<String>,
+
+    /// Exclusive RFC 3339 upper bound
+    #[serde(default)]
+    pub until: Option<String>,
+
+    /// Case-insensitive substring of the message
+    #[serde(default)]
+    pub search: Option<String>,
+
+    #[serde(default)]
+    pub offset: usize,
+
+    #[serde(default = "default_query_limit")]
+    pub limit: usize,
+}
+
+fn default_query_limit() -> usize {
+    200
+}
+
+impl Default for LogQuery {
+    fn default() -> Self {
+        Self {
+            sources: Vec::new(),
+            min_level: None,
+            since: None,
+            until: None,
+            search: None,
+            offset: 0,
+            limit: default_query_limit(),
+        }
+    }
+}
+
+/// One page of query results, newest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogPage {
+    pub records: Vec<LogRecord>,
+
+    /// Number of matching records across all pages
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+/// Log levels and retention, stored in the settings file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogSettings {
+    /// Threshold for sources without an override
+    pub level: LogLevel,
+
+    /// Per-source thresholds. Keys match a source name exactly
+    /// (case-insensitive) or prefix a tracing target.
+    #[serde(default)]
+    pub source_levels: BTreeMap<String, LogLevel>,
+
+    /// Log files older than this many days are deleted
+    pub retention_days: u32,
+
+    /// Oldest files are deleted once the log directory exceeds this size
+    pub max_total_size_mb: u64,
+
+    /// A new file is started when the current one reaches this size
+    pub max_file_size_mb: u64,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self {
+            level: LogLevel::Info,
+            source_levels: BTreeMap::new(),
+            retention_days: 14,
+            max_total_size_mb: 200,
+            max_file_size_mb: 20,
+        }
+    }
+}
+
+impl LogSettings {
+    /// Threshold that applies to `source`
+    pub fn threshold(&self, source: &str) -> LogLevel {
+        if let Some((_, level)) = self
+            .source_levels
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(source))
+        {
+            return *level;
+        }
+
+        // Longest matching target prefix wins (e.g. "sqlx" for "sqlx::query")
+        self.source_levels
+            .iter()
+            .filter(|(name, _)| {
+                source.starts_with(name.as_str()) && source[name.len()..].starts_with("::")
+            })
+            .max_by_key(|(name, _)| name.len())
+            .map(|(_, level)| *level)
+            .unwrap_or(self.level)
+    }
+
+    /// Whether a record from `source` at `level` should be kept
+    pub fn allows(&self, source: &str, level: LogLevel) -> bool {
+        let threshold = self.threshold(source);
+        threshold != LogLevel::Off && level >= threshold
+    }
+
+    /// Most verbose threshold in use, for cheap pre-filtering
+    pub fn most_verbose(&self) -> LogLevel {
+        self.source_levels
+            .values()
+            .copied()
+            .chain(std::iter::once(self.level))
+            .min()
+            .unwrap_or(self.level)
+    }
+}
+
+impl SettingsSection for LogSettings {
+    const KEY: &'static str = "logging";
+}
@@ -1,14 +1,50 @@
+pub mod activity;
+pub mod automation;
+pub mod backup;
 pub mod context_config;
+pub mod db_maintenance;
+pub mod diagnostics;
+pub mod embedding_models;
+pub mod embedding_quantization;
+pub mod enhance_presets;
 pub mod error;
+pub mod glossary;
 pub mod llm_config;
+pub mod logging;
+pub mod node_runtime;
+pub mod ocr_languages;
+pub mod ollama;
+pub mod project_profiles;
 pub mod prompt;
 pub mod qa_api_call;
+pub mod qa_api_link;
+pub mod qa_api_test;
+pub mod qa_auth;
 pub mod qa_checkpoint;
 pub mod qa_event;
+pub mod qa_highlight;
+pub mod qa_llm_audit;
+pub mod qa_prompts;
+pub mod qa_redaction;
+pub mod qa_retention;
 pub mod qa_run;
+pub mod qa_selectors;
 pub mod qa_session;
+pub mod qa_test_management;
+pub mod qa_vision;
+pub mod rag_connectors;
+pub mod rag_email;
 pub mod rag_entities;
+pub mod rag_git;
+pub mod rag_import_templates;
+pub mod rag_tools;
+pub mod settings;
+pub mod storage;
+pub mod telemetry;
+pub mod translation_memory;
+pub mod trash;
 pub mod typegen;
+pub mod writing_memory;
 
 // CSV preprocessing module
 pub mod csv;
@@ -0,0 +1,104 @@
+// ============================================================
+// NODE RUNTIME
+// ============================================================
+// The QA recorder, browser replay and web OCR capture are Node.js
+// scripts driving Playwright. The app keeps its own copy of the
+// scripts and a pinned Playwright in `<app data>/node_runtime`,
+// installed on first use, so they never depend on the working
+// directory or on packages installed next to the app.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::diagnostics::{DiagnosticCheck, DiagnosticStatus};
+
+/// Oldest Node.js major version Playwright supports
+pub const MIN_NODE_MAJOR: u32 = 18;
+
+/// Playwright release installed into the runtime folder
+pub const PLAYWRIGHT_VERSION: &str = "1.55.0";
+
+/// Bundled script run with the managed runtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeScript {
+    Recorder,
+    Replay,
+    Capture,
+}
+
+impl RuntimeScript {
+    pub const ALL: [RuntimeScript; 3] = [
+        RuntimeScript::Recorder,
+        RuntimeScript::Replay,
+        RuntimeScript::Capture,
+    ];
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            RuntimeScript::Recorder => "qa-browser-recorder.mjs",
+            RuntimeScript::Replay => "qa-browser-replay.mjs",
+            RuntimeScript::Capture => "playwright-capture.js",
+        }
+    }
+}
+
+/// Step of preparing the runtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NodeRuntimeStage {
+    Node,
+    Scripts,
+    Playwright,
+    Browser,
+    Ready,
+}
+
+/// Emitted while the runtime installs Playwright or its browser
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeRuntimeProgress {
+    pub stage: NodeRuntimeStage,
+
+    /// Installer output line or a summary of the step
+    pub message: String,
+}
+
+/// Result of `qa_check_recorder_environment`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecorderEnvironment {
+    /// Everything is installed; recording starts without a download
+    pub ready: bool,
+
+    /// Nothing is missing that the app cannot install by itself
+    pub installable: bool,
+    pub runtime_dir: String,
+    pub playwright_version: String,
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+impl RecorderEnvironment {
+    pub fn new(runtime_dir: String, checks: Vec<DiagnosticCheck>) -> Self {
+        Self {
+            ready: checks
+                .iter()
+                .all(|check| check.status == DiagnosticStatus::Ok),
+            installable: checks
+                .iter()
+                .all(|check| check.status != DiagnosticStatus::Error),
+            runtime_dir,
+            playwright_version: PLAYWRIGHT_VERSION.to_string(),
+            checks,
+        }
+    }
+}
+
+/// Major version of `node --version` output such as `v20.11.0`
+pub fn node_major_version(version: &str) -> Option<u32> {
+    version
+        .trim()
+        .trim_start_matches('v')
+        .split('.')
+        .next()?
+        .parse()
+        .ok()
+}
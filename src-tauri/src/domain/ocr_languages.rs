@@ -0,0 +1,59 @@
+// ============================================================
+// OCR LANGUAGE PACKS
+// ============================================================
+// Tesseract recognizes the languages whose `.traineddata` file is in
+// its tessdata folder. `eng` and `ind` ship with the app; others are
+// downloaded on demand into `<app data>/tessdata`, which then becomes
+// the folder Tesseract reads.
+
+use serde::{Deserialize, Serialize};
+
+/// Languages offered for download, as `(tesseract code, display name)`
+pub const OCR_LANGUAGE_CATALOG: [(&str, &str); 24] = [
+    ("eng", "English"),
+    ("ind", "Indonesian"),
+    ("msa", "Malay"),
+    ("jav", "Javanese"),
+    ("sun", "Sundanese"),
+    ("tgl", "Tagalog"),
+    ("vie", "Vietnamese"),
+    ("tha", "Thai"),
+    ("chi_sim", "Chinese (Simplified)"),
+    ("chi_tra", "Chinese (Traditional)"),
+    ("jpn", "Japanese"),
+    ("kor", "Korean"),
+    ("hin", "Hindi"),
+    ("ara", "Arabic"),
+    ("rus", "Russian"),
+    ("tur", "Turkish"),
+    ("fra", "French"),
+    ("deu", "German"),
+    ("spa", "Spanish"),
+    ("por", "Portuguese"),
+    ("ita", "Italian"),
+    ("nld", "Dutch"),
+    ("pol", "Polish"),
+    ("osd", "Orientation and script detection"),
+];
+
+/// Display name of a tesseract language code, if it is in the catalog
+pub fn ocr_language_name(code: &str) -> Option<&'static str> {
+    OCR_LANGUAGE_CATALOG
+        .iter()
+        .find(|(known, _)| *known == code)
+        .map(|(_, name)| *name)
+}
+
+/// A language Tesseract can use, or one that can be downloaded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OcrLanguage {
+    /// Tesseract code passed to `-l`, e.g. `chi_sim`
+    pub code: String,
+    pub name: Option<String>,
+    pub installed: bool,
+
+    /// Shipped with the app rather than downloaded
+    pub bundled: bool,
+    pub size_bytes: Option<u64>,
+}
@@ -0,0 +1,245 @@
+// ============================================================
+// OLLAMA
+// ============================================================
+// Ollama serves local models over HTTP. The app can find the
+// installed `ollama` binary, start and stop `ollama serve`, pull
+// models with progress, and start the server on demand when a
+// request finds it down. Chat goes through Ollama's OpenAI-compatible
+// `/v1` API; the lifecycle calls use its native `/api` endpoints.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::settings::SettingsSection;
+
+/// Server address when the configured base URL is empty
+pub const DEFAULT_OLLAMA_URL: &str = "http://localhost:11434";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OllamaSettings {
+    /// Start `ollama serve` when a request finds the server down
+    pub auto_start: bool,
+
+    /// `ollama` executable; `None` looks it up on PATH
+    pub binary_path: Option<String>,
+}
+
+impl Default for OllamaSettings {
+    fn default() -> Self {
+        Self {
+            auto_start: true,
+            binary_path: None,
+        }
+    }
+}
+
+impl OllamaSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self
+            .binary_path
+            .as_deref()
+            .is_some_and(|path| path.trim().is_empty())
+        {
+            return Err(AppError::ValidationError(
+                "Ollama binary path must not be blank; clear it to use PATH".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn binary(&self) -> &str {
+        self.binary_path
+            .as_deref()
+            .map(str::trim)
+            .unwrap_or("ollama")
+    }
+}
+
+impl SettingsSection for OllamaSettings {
+    const KEY: &'static str = "ollama";
+}
+
+/// A model the server has pulled, from `/api/tags`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OllamaModel {
+    /// Name with tag, e.g. `llama3:latest`
+    pub name: String,
+    pub size_bytes: u64,
+    pub modified_at: Option<String>,
+    /// e.g. `8.0B`
+    pub parameter_size: Option<String>,
+    /// e.g. `Q4_0`
+    pub quantization: Option<String>,
+}
+
+impl OllamaModel {
+    /// Entry of the `models` array returned by `/api/tags`
+    pub fn from_tag(json: &serde_json::Value) -> Option<Self> {
+        let text = |value: &serde_json::Value| value.as_str().map(str::to_string);
+        Some(Self {
+            name: text(&json["name"]).or_else(|| text(&json["model"]))?,
+            size_bytes: json["size"].as_u64().unwrap_or(0),
+            modified_at: text(&json["modified_at"]),
+            parameter_size: text(&json["details"]["parameter_size"]),
+            quantization: text(&json["details"]["quantization_level"]),
+        })
+    }
+}
+
+/// What the settings page shows about the local Ollama
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OllamaStatus {
+    /// Server address the status was taken from
+    pub base_url: String,
+
+    /// The `ollama` binary answered `--version`
+    pub installed: bool,
+    pub cli_version: Option<String>,
+
+    /// Why the binary could not be used, when it is not installed
+    pub install_error: Option<String>,
+
+    /// The server answered `/api/version`
+    pub running: bool,
+    pub server_version: Option<String>,
+
+    /// The running server was started by this app and can be stopped by it
+    pub managed: bool,
+    pub models: Vec<OllamaModel>,
+}
+
+/// Payload of the `ollama-pull-progress` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OllamaPullProgress {
+    pub model: String,
+
+    /// Step reported by Ollama, e.g. `pulling manifest` or `success`
+    pub status: String,
+
+    /// Layer being downloaded
+    pub digest: Option<String>,
+    pub completed_bytes: Option<u64>,
+    pub total_bytes: Option<u64>,
+}
+
+impl OllamaPullProgress {
+    /// One line of the NDJSON stream `/api/pull` answers with
+    pub fn from_line(model: &str, line: &str) -> Result<Option<Self>> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(None);
+        }
+        let json: serde_json::Value = serde_json::from_str(line).map_err(|e| {
+            AppError::ParseError(format!("Unexpected pull progress from Ollama: {}", e))
+        })?;
+        if let Some(error) = json["error"].as_str() {
+            return Err(AppError::LLMError(format!(
+                "Ollama could not pull {}: {}",
+                model, error
+            )));
+        }
+        Ok(Some(Self {
+            model: model.to_string(),
+            status: json["status"].as_str().unwrap_or_default().to_string(),
+            digest: json["digest"].as_str().map(str::to_string),
+            completed_bytes: json["completed"].as_u64(),
+            total_bytes: json["total"].as_u64(),
+        }))
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.status == "success"
+    }
+}
+
+/// Server root of an Ollama base URL, without the OpenAI-compatible `/v1`
+pub fn ollama_server_url(base_url: &str) -> String {
+    let url = base_url.trim().trim_end_matches('/');
+    let url = url.strip_suffix("/v1").unwrap_or(url).trim_end_matches('/');
+    if url.is_empty() {
+        DEFAULT_OLLAMA_URL.to_string()
+    } else {
+        url.to_string()
+    }
+}
+
+/// OpenAI-compatible endpoint of an Ollama base URL, for chat requests
+pub fn ollama_openai_url(base_url: &str) -> String {
+    format!("{}/v1", ollama_server_url(base_url))
+}
+
+/// `OLLAMA_HOST` value that makes `ollama serve` listen where `base_url` points
+pub fn ollama_host(base_url: &str) -> String {
+    let server = ollama_server_url(base_url);
+    let host = server
+        .split_once("://")
+        .map(|(_, rest)| rest)
+        .unwrap_or(&server);
+    host.split('/').next().unwrap_or(host).to_string()
+}
+
+/// Version from `ollama --version`, e.g. `ollama version is 0.5.7`. With no
+/// server running some releases print `Warning: client version is 0.5.7`.
+pub fn parse_cli_version(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.split_once("version is "))
+        .map(|(_, version)| version.trim().to_string())
+        .filter(|version| !version.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derives_server_urls_from_base_url() {
+        assert_eq!(
+            ollama_server_url("http://localhost:11434/v1/"),
+            "http://localhost:11434"
+        );
+        assert_eq!(ollama_server_url("  "), DEFAULT_OLLAMA_URL);
+        assert_eq!(
+            ollama_openai_url("http://10.0.0.5:11500"),
+            "http://10.0.0.5:11500/v1"
+        );
+        assert_eq!(ollama_host("http://127.0.0.1:11500/v1"), "127.0.0.1:11500");
+    }
+
+    #[test]
+    fn parses_cli_version_with_warning() {
+        let output = "Warning: could not connect to a running Ollama instance\n\
+                      Warning: client version is 0.1.32\n";
+        assert_eq!(parse_cli_version(output).as_deref(), Some("0.1.32"));
+        assert_eq!(
+            parse_cli_version("ollama version is 0.5.7\n").as_deref(),
+            Some("0.5.7")
+        );
+    }
+
+    #[test]
+    fn reads_pull_progress_lines() {
+        let progress = OllamaPullProgress::from_line(
+            "llama3",
+            r#"{"status":"pulling 6a0746a1ec1a","digest":"sha256:6a07","total":4661211424,"completed":1000}"#,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(progress.total_bytes, Some(4661211424));
+        assert_eq!(progress.completed_bytes, Some(1000));
+        assert!(!progress.is_success());
+
+        assert!(OllamaPullProgress::from_line("llama3", "  ")
+            .unwrap()
+            .is_none());
+        assert!(OllamaPullProgress::from_line(
+            "nope",
+            r#"{"error":"pull model manifest: file does not exist"}"#
+        )
+        .is_err());
+    }
+}
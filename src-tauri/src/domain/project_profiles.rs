@@ -0,0 +1,302 @@
+// ============================================================
+// PROJECT PROFILES
+// ============================================================
+// A profile bundles what changes when switching between client
+// projects: where the mock routes live and whether the mock server
+// runs, the QA proxy rewrites and session URLs, and the RAG
+// collection chat starts in. Applying one switches all of them at
+// once; it can be picked at launch with `--profile <id>` or a
+// `gadogado://profile/<id>` link.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::settings::{QaProxySettings, SettingsSection};
+
+/// Launch argument naming the profile to apply
+pub const PROFILE_ARG: &str = "--profile";
+
+/// URL scheme registered for deep links
+pub const DEEP_LINK_SCHEME: &str = "gadogado";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProjectProfile {
+    /// Used in launch arguments and links; letters, digits, '-' or '_'
+    pub id: String,
+    pub name: String,
+
+    /// Routes directory of the project's mock server, as exported by the
+    /// mock server; `None` leaves the mock routes as they are
+    pub mock_routes_dir: Option<String>,
+    /// Start the mock server when the profile is applied, restarting it
+    /// when it was serving other routes
+    pub start_mock_server: bool,
+
+    /// Origin rewrites and headers of the QA recorder proxy; `None` leaves
+    /// the current ones
+    pub qa_proxy: Option<QaProxySettings>,
+    /// Prefilled when starting QA sessions
+    pub qa_target_url: Option<String>,
+    pub qa_api_base_url: Option<String>,
+
+    /// Collection RAG chat selects when the profile is applied
+    pub rag_collection_id: Option<i64>,
+}
+
+impl ProjectProfile {
+    pub fn validate(&self) -> Result<()> {
+        let id = self.id.trim();
+        if id.is_empty()
+            || !id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(AppError::ValidationError(format!(
+                "Profile id '{}' must be letters, digits, '-' or '_'",
+                self.id
+            )));
+        }
+        if self.name.trim().is_empty() {
+            return Err(AppError::ValidationError(format!(
+                "Profile '{}' needs a name",
+                id
+            )));
+        }
+        if self
+            .mock_routes_dir
+            .as_deref()
+            .is_some_and(|dir| dir.trim().is_empty())
+        {
+            return Err(AppError::ValidationError(format!(
+                "Profile '{}' has an empty mock routes directory",
+                id
+            )));
+        }
+        if let Some(proxy) = &self.qa_proxy {
+            proxy.validate()?;
+        }
+        for (label, url) in [
+            ("QA target URL", &self.qa_target_url),
+            ("QA API base URL", &self.qa_api_base_url),
+        ] {
+            let Some(url) = url.as_deref().map(str::trim).filter(|url| !url.is_empty()) else {
+                continue;
+            };
+            let valid = url::Url::parse(url)
+                .map(|parsed| matches!(parsed.scheme(), "http" | "https") && parsed.has_host())
+                .unwrap_or(false);
+            if !valid {
+                return Err(AppError::ValidationError(format!(
+                    "{} '{}' of profile '{}' must be an http(s) URL",
+                    label, url, id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProjectProfileSettings {
+    pub profiles: Vec<ProjectProfile>,
+    /// Profile applied last
+    pub active_profile_id: Option<String>,
+    /// Apply the active profile again on every launch without `--profile`
+    pub apply_on_launch: bool,
+}
+
+impl SettingsSection for ProjectProfileSettings {
+    const KEY: &'static str = "projectProfiles";
+}
+
+impl ProjectProfileSettings {
+    pub fn validate(&self) -> Result<()> {
+        for (index, profile) in self.profiles.iter().enumerate() {
+            profile.validate()?;
+            if self.profiles[..index]
+                .iter()
+                .any(|other| other.id.trim() == profile.id.trim())
+            {
+                return Err(AppError::ValidationError(format!(
+                    "Profile id '{}' is used more than once",
+                    profile.id.trim()
+                )));
+            }
+        }
+        if let Some(active) = &self.active_profile_id {
+            if self.profile(active).is_none() {
+                return Err(AppError::ValidationError(format!(
+                    "Active profile '{}' does not exist",
+                    active
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn profile(&self, id: &str) -> Option<&ProjectProfile> {
+        let id = id.trim();
+        self.profiles.iter().find(|profile| profile.id.trim() == id)
+    }
+
+    /// Profile to apply at launch: the one named in `args`, else the
+    /// active one when it is applied on every launch
+    pub fn launch_profile<I, S>(&self, args: I) -> Option<String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        requested_profile(args).or_else(|| {
+            if self.apply_on_launch {
+                self.active_profile_id.clone()
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// Profile named by `--profile <id>`, `--profile=<id>` or a
+/// `gadogado://profile/<id>` link among launch arguments
+pub fn requested_profile<I, S>(args: I) -> Option<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let arg = arg.as_ref();
+        let id = if arg == PROFILE_ARG {
+            args.next().map(|next| next.as_ref().to_string())
+        } else if let Some(id) = arg
+            .strip_prefix(PROFILE_ARG)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            Some(id.to_string())
+        } else {
+            profile_from_deep_link(arg)
+        };
+        if let Some(id) = id
+            .map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty())
+        {
+            return Some(id);
+        }
+    }
+    None
+}
+
+/// Profile id of a `gadogado://profile/<id>` link
+pub fn profile_from_deep_link(link: &str) -> Option<String> {
+    let url = url::Url::parse(link.trim()).ok()?;
+    if url.scheme() != DEEP_LINK_SCHEME || url.host_str() != Some("profile") {
+        return None;
+    }
+    let id = url.path().trim_matches('/');
+    let decoded = percent_decode(id)?;
+    (!decoded.is_empty() && !decoded.contains('/')).then_some(decoded)
+}
+
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = value.get(index + 1..index + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(id: &str) -> ProjectProfile {
+        ProjectProfile {
+            id: id.to_string(),
+            name: format!("Client {}", id),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn profile_is_found_in_arguments_and_links() {
+        assert_eq!(
+            requested_profile(["gadogado", "--profile", "acme"]),
+            Some("acme".to_string())
+        );
+        assert_eq!(
+            requested_profile(["gadogado", "--background", "--profile=acme-2"]),
+            Some("acme-2".to_string())
+        );
+        assert_eq!(
+            requested_profile(["gadogado", "gadogado://profile/acme_3/"]),
+            Some("acme_3".to_string())
+        );
+        assert_eq!(requested_profile(["gadogado", "--profile"]), None);
+        assert_eq!(requested_profile(["gadogado", "--profile="]), None);
+        assert_eq!(profile_from_deep_link("gadogado://settings/acme"), None);
+        assert_eq!(profile_from_deep_link("https://profile/acme"), None);
+        assert_eq!(
+            profile_from_deep_link("gadogado://profile/client%20a"),
+            Some("client a".to_string())
+        );
+    }
+
+    #[test]
+    fn launch_profile_falls_back_to_active_one_only_when_enabled() {
+        let mut settings = ProjectProfileSettings {
+            profiles: vec![profile("acme"), profile("globex")],
+            active_profile_id: Some("globex".to_string()),
+            apply_on_launch: false,
+        };
+        assert!(settings.validate().is_ok());
+        assert_eq!(settings.launch_profile(["gadogado"]), None);
+        assert_eq!(
+            settings.launch_profile(["gadogado", "--profile", "acme"]),
+            Some("acme".to_string())
+        );
+        settings.apply_on_launch = true;
+        assert_eq!(
+            settings.launch_profile(["gadogado"]),
+            Some("globex".to_string())
+        );
+    }
+
+    #[test]
+    fn invalid_profiles_are_rejected() {
+        let duplicated = ProjectProfileSettings {
+            profiles: vec![profile("acme"), profile("acme")],
+            ..Default::default()
+        };
+        assert!(duplicated.validate().is_err());
+
+        let missing_active = ProjectProfileSettings {
+            profiles: vec![profile("acme")],
+            active_profile_id: Some("globex".to_string()),
+            ..Default::default()
+        };
+        assert!(missing_active.validate().is_err());
+
+        assert!(profile("has space").validate().is_err());
+        let bad_url = ProjectProfile {
+            qa_target_url: Some("localhost:4200".to_string()),
+            ..profile("acme")
+        };
+        assert!(bad_url.validate().is_err());
+        let blank_url = ProjectProfile {
+            qa_target_url: Some(" ".to_string()),
+            ..profile("acme")
+        };
+        assert!(blank_url.validate().is_ok());
+    }
+}
@@ -1,7 +1,10 @@
+use crate::domain::glossary::GlossaryViolation;
+use crate::domain::translation_memory::TranslationMemoryMatch;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use validator::Validate;
 
-#[derive(Debug, Serialize, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
 pub struct Prompt {
     pub id: Option<i64>,
     #[validate(length(min = 1, max = 4096))]
@@ -9,7 +12,20 @@ pub struct Prompt {
     pub source_lang: String,
     pub target_lang: String,
     pub result: Option<String>,
+    /// Language resolved by auto-detection when the source was "Auto Detect".
+    #[serde(default)]
+    pub detected_lang: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub is_favorite: bool,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Glossary rules the translation broke (not persisted).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub glossary_violations: Vec<GlossaryViolation>,
+    /// Set when the result was reused from translation memory instead of the LLM.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tm_match: Option<TranslationMemoryMatch>,
 }
 
 impl Prompt {
@@ -20,7 +36,12 @@ impl Prompt {
             source_lang,
             target_lang,
             result: None,
+            detected_lang: None,
+            tags: Vec::new(),
+            is_favorite: false,
             created_at: Some(chrono::Utc::now()),
+            glossary_violations: Vec::new(),
+            tm_match: None,
         }
     }
 }
@@ -14,6 +14,8 @@ pub struct QaApiCall {
     pub response_status: Option<i64>,
     pub response_headers_json: Option<String>,
     pub response_body_hash: Option<String>,
+    #[serde(default)]
+    pub response_body_json: Option<String>,
     pub timing_ms: Option<i64>,
     pub created_at: i64,
 }
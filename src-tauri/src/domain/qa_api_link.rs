@@ -0,0 +1,282 @@
+// ============================================================
+// QA API LINKS
+// ============================================================
+// API calls and UI events are recorded independently, so nothing
+// says which action caused a call. A recorded `api_response` is
+// linked to the click or submit that most likely triggered it:
+// the latest one made shortly before the request started (the
+// response time minus the call's timing). Among actions in that
+// window, a submit is preferred for writes and an action on a
+// page of the API's own origin over one on another origin.
+// Responses with no action in the window (polling, prefetch)
+// stay unlinked.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::qa_event::QaEvent;
+
+/// How long after an action a request still counts as triggered by it
+const LINK_WINDOW_MS: i64 = 3_000;
+
+/// A request may be stamped slightly before the action that sent it
+const CLOCK_SKEW_MS: i64 = 250;
+
+const TRIGGER_EVENT_TYPES: &[&str] = &["click", "dblclick", "submit"];
+const WRITE_METHODS: &[&str] = &["POST", "PUT", "PATCH", "DELETE"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaApiLink {
+    pub session_id: String,
+    pub response_event_id: String,
+    pub response_seq: i64,
+    pub trigger_event_id: String,
+    pub trigger_seq: i64,
+    /// From the action to the start of the request (ms)
+    pub delay_ms: i64,
+    /// The action was on a page of the API's origin
+    pub same_origin: bool,
+}
+
+/// An action with the API calls it triggered, as given to the summary and
+/// test case prompts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaActionApiCalls {
+    pub trigger_seq: i64,
+    pub trigger_type: String,
+    pub selector: Option<String>,
+    pub element_text: Option<String>,
+    pub calls: Vec<QaLinkedApiCall>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaLinkedApiCall {
+    pub response_seq: i64,
+    pub method: Option<String>,
+    pub url: Option<String>,
+    pub status: Option<i64>,
+    pub delay_ms: i64,
+}
+
+/// The action among `events` that triggered `response`, if any
+pub fn link_api_response(response: &QaEvent, events: &[QaEvent]) -> Option<QaApiLink> {
+    if response.event_type != "api_response" {
+        return None;
+    }
+    let meta = ResponseMeta::parse(response);
+    let started_at = meta.request_started_at(response);
+    let api_origin = response.url.as_deref().and_then(url_origin);
+    let is_write = meta
+        .method
+        .as_deref()
+        .is_some_and(|method| WRITE_METHODS.contains(&method.to_uppercase().as_str()));
+
+    let (trigger, same_origin) = events
+        .iter()
+        .filter(|event| event.session_id == response.session_id && is_trigger(event))
+        .filter(|event| {
+            event.ts <= started_at + CLOCK_SKEW_MS && started_at - event.ts <= LINK_WINDOW_MS
+        })
+        .map(|event| {
+            let same_origin =
+                api_origin.is_some() && event.url.as_deref().and_then(url_origin) == api_origin;
+            (event, same_origin)
+        })
+        .max_by_key(|(event, same_origin)| {
+            let kind_matches = !is_write || event.event_type == "submit";
+            (kind_matches, *same_origin, event.ts, event.seq)
+        })?;
+
+    Some(QaApiLink {
+        session_id: response.session_id.clone(),
+        response_event_id: response.id.clone(),
+        response_seq: response.seq,
+        trigger_event_id: trigger.id.clone(),
+        trigger_seq: trigger.seq,
+        delay_ms: (started_at - trigger.ts).max(0),
+        same_origin,
+    })
+}
+
+/// Earliest time (ms) of an action `response` can be linked to
+pub fn link_window_start(response: &QaEvent) -> i64 {
+    ResponseMeta::parse(response).request_started_at(response) - LINK_WINDOW_MS
+}
+
+/// Links for every `api_response` among `events`
+pub fn link_api_responses(events: &[QaEvent]) -> Vec<QaApiLink> {
+    events
+        .iter()
+        .filter_map(|event| link_api_response(event, events))
+        .collect()
+}
+
+/// The actions among `events` with the calls linked to them, in event order
+pub fn group_by_action(events: &[QaEvent], links: &[QaApiLink]) -> Vec<QaActionApiCalls> {
+    let mut actions: Vec<QaActionApiCalls> = Vec::new();
+    for trigger in events.iter().filter(|event| is_trigger(event)) {
+        let mut calls: Vec<QaLinkedApiCall> = links
+            .iter()
+            .filter(|link| link.trigger_event_id == trigger.id)
+            .filter_map(|link| {
+                let response = events
+                    .iter()
+                    .find(|event| event.id == link.response_event_id)?;
+                let meta = ResponseMeta::parse(response);
+                Some(QaLinkedApiCall {
+                    response_seq: response.seq,
+                    method: meta.method,
+                    url: response.url.clone(),
+                    status: meta.status,
+                    delay_ms: link.delay_ms,
+                })
+            })
+            .collect();
+        if calls.is_empty() {
+            continue;
+        }
+        calls.sort_by_key(|call| call.response_seq);
+        actions.push(QaActionApiCalls {
+            trigger_seq: trigger.seq,
+            trigger_type: trigger.event_type.clone(),
+            selector: trigger.selector.clone(),
+            element_text: trigger.element_text.clone(),
+            calls,
+        });
+    }
+    actions
+}
+
+/// Clicks and submits made by the tester or an agent, not by the recorder
+fn is_trigger(event: &QaEvent) -> bool {
+    TRIGGER_EVENT_TYPES.contains(&event.event_type.as_str())
+        && event.origin.as_deref() != Some("system")
+}
+
+fn url_origin(url: &str) -> Option<String> {
+    let parsed = url::Url::parse(url.trim()).ok()?;
+    match parsed.origin() {
+        origin @ url::Origin::Tuple(..) => Some(origin.ascii_serialization()),
+        url::Origin::Opaque(_) => None,
+    }
+}
+
+/// What the recorders put in the `meta_json` of an `api_response`
+#[derive(Default)]
+struct ResponseMeta {
+    method: Option<String>,
+    status: Option<i64>,
+    timing_ms: Option<i64>,
+}
+
+impl ResponseMeta {
+    fn parse(event: &QaEvent) -> Self {
+        let Some(meta) = event
+            .meta_json
+            .as_deref()
+            .and_then(|meta| serde_json::from_str::<serde_json::Value>(meta).ok())
+        else {
+            return Self::default();
+        };
+        Self {
+            method: meta
+                .get("method")
+                .and_then(|value| value.as_str())
+                .map(str::to_string),
+            status: meta.get("status").and_then(|value| value.as_i64()),
+            timing_ms: meta.get("timing_ms").and_then(|value| value.as_i64()),
+        }
+    }
+
+    /// The response is recorded when it arrives, `timing_ms` after the request
+    fn request_started_at(&self, response: &QaEvent) -> i64 {
+        response.ts - self.timing_ms.unwrap_or(0).max(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(seq: i64, ts: i64, event_type: &str, url: &str) -> QaEvent {
+        QaEvent {
+            id: format!("e{}", seq),
+            session_id: "s1".to_string(),
+            run_id: None,
+            checkpoint_id: None,
+            seq,
+            ts,
+            event_type: event_type.to_string(),
+            origin: Some("user".to_string()),
+            recording_mode: None,
+            selector: Some(format!("#el{}", seq)),
+            element_text: None,
+            value: None,
+            url: Some(url.to_string()),
+            screenshot_id: None,
+            screenshot_path: None,
+            annotated_screenshot_path: None,
+            meta_json: None,
+        }
+    }
+
+    fn response(seq: i64, ts: i64, method: &str, url: &str, timing_ms: i64) -> QaEvent {
+        QaEvent {
+            origin: Some("system".to_string()),
+            selector: None,
+            meta_json: Some(
+                serde_json::json!({
+                    "method": method,
+                    "url": url,
+                    "status": 200,
+                    "timing_ms": timing_ms
+                })
+                .to_string(),
+            ),
+            ..event(seq, ts, "api_response", url)
+        }
+    }
+
+    #[test]
+    fn responses_link_to_the_latest_action_before_the_request() {
+        let events = vec![
+            event(1, 1_000, "click", "https://app.test/cart"),
+            event(2, 2_000, "click", "https://app.test/cart"),
+            // Request started at 2_100, after the second click
+            response(3, 2_500, "GET", "https://app.test/api/cart", 400),
+            // Started 5s after the last action: polling
+            response(4, 8_000, "GET", "https://app.test/api/poll", 0),
+        ];
+        let links = link_api_responses(&events);
+        assert_eq!(link_window_start(&events[2]), 2_100 - LINK_WINDOW_MS);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].response_event_id, "e3");
+        assert_eq!(links[0].trigger_event_id, "e2");
+        assert_eq!(links[0].delay_ms, 100);
+        assert!(links[0].same_origin);
+    }
+
+    #[test]
+    fn writes_prefer_submits_and_actions_on_the_api_origin() {
+        let events = vec![
+            event(1, 1_000, "submit", "https://app.test/login"),
+            event(2, 1_050, "click", "https://app.test/login"),
+            response(3, 1_300, "POST", "https://app.test/api/login", 200),
+            event(4, 5_000, "click", "https://other.test/widget"),
+            event(5, 5_010, "click", "https://app.test/home"),
+            event(6, 5_020, "click", "https://other.test/widget"),
+            response(7, 5_100, "GET", "https://app.test/api/feed", 50),
+        ];
+        let links = link_api_responses(&events);
+        assert_eq!(links[0].trigger_event_id, "e1");
+        assert_eq!(links[1].trigger_event_id, "e5");
+
+        let actions = group_by_action(&events, &links);
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[0].trigger_seq, 1);
+        assert_eq!(actions[0].calls[0].method.as_deref(), Some("POST"));
+        assert_eq!(actions[0].calls[0].status, Some(200));
+    }
+}
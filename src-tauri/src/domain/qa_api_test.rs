@@ -0,0 +1,368 @@
+// ============================================================
+// QA API TESTS
+// ============================================================
+// Assertions checked against a response of the QA API runner, and
+// the report of re-running saved API tests. An API test is a test
+// case of type `api` whose steps hold the request and its
+// assertions, so it lives and syncs with the session's other cases.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::qa_run::QaSessionRun;
+use crate::domain::qa_test_management::QaTestResultStatus;
+
+/// Test case type of saved API tests
+pub const API_TEST_CASE_TYPE: &str = "api";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonValueType {
+    String,
+    Number,
+    Boolean,
+    Object,
+    Array,
+    Null,
+}
+
+impl JsonValueType {
+    pub fn of(value: &JsonValue) -> Self {
+        match value {
+            JsonValue::String(_) => Self::String,
+            JsonValue::Number(_) => Self::Number,
+            JsonValue::Bool(_) => Self::Boolean,
+            JsonValue::Object(_) => Self::Object,
+            JsonValue::Array(_) => Self::Array,
+            JsonValue::Null => Self::Null,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Boolean => "boolean",
+            Self::Object => "object",
+            Self::Array => "array",
+            Self::Null => "null",
+        }
+    }
+}
+
+/// A check on an API response. Header and JSON path assertions without a
+/// value or type only require the header or value to be present.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum QaApiAssertion {
+    #[serde(rename_all = "camelCase")]
+    Status { equals: u16 },
+
+    #[serde(rename_all = "camelCase")]
+    Header {
+        name: String,
+        #[serde(default)]
+        equals: Option<String>,
+        #[serde(default)]
+        contains: Option<String>,
+    },
+
+    /// `path` like `$.data.items[0].id`
+    #[serde(rename_all = "camelCase")]
+    JsonPath {
+        path: String,
+        #[serde(default)]
+        equals: Option<JsonValue>,
+        #[serde(default, rename = "type")]
+        value_type: Option<JsonValueType>,
+    },
+}
+
+impl QaApiAssertion {
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            Self::Status { equals } if !(100..=599).contains(equals) => Err(
+                AppError::ValidationError(format!("{} is not an HTTP status", equals)),
+            ),
+            Self::Header { name, .. } if name.trim().is_empty() => Err(AppError::ValidationError(
+                "Header assertions need a header name".to_string(),
+            )),
+            Self::JsonPath { path, .. } => parse_json_path(path).map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
+    /// e.g. `status = 200` or `$.id is number`
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Status { equals } => format!("status = {}", equals),
+            Self::Header {
+                name,
+                equals,
+                contains,
+            } => match (equals, contains) {
+                (Some(value), _) => format!("header {} = {}", name, value),
+                (None, Some(part)) => format!("header {} contains {}", name, part),
+                (None, None) => format!("header {} is present", name),
+            },
+            Self::JsonPath {
+                path,
+                equals,
+                value_type,
+            } => match (equals, value_type) {
+                (Some(value), _) => format!("{} = {}", path, value),
+                (None, Some(value_type)) => format!("{} is {}", path, value_type.as_str()),
+                (None, None) => format!("{} exists", path),
+            },
+        }
+    }
+
+    /// Checks the response; `headers` as sent by the server, `body` as text
+    pub fn check(
+        &self,
+        status: u16,
+        headers: &[(String, String)],
+        body: &str,
+    ) -> QaApiAssertionResult {
+        let (passed, actual) = match self {
+            Self::Status { equals } => (status == *equals, Some(status.to_string())),
+            Self::Header {
+                name,
+                equals,
+                contains,
+            } => {
+                let value = headers
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case(name.trim()))
+                    .map(|(_, value)| value.clone());
+                let passed = value.as_deref().is_some_and(|value| {
+                    equals.as_deref().is_none_or(|expected| value == expected)
+                        && contains.as_deref().is_none_or(|part| value.contains(part))
+                });
+                (passed, value)
+            }
+            Self::JsonPath {
+                path,
+                equals,
+                value_type,
+            } => {
+                let value = serde_json::from_str::<JsonValue>(body)
+                    .ok()
+                    .and_then(|json| {
+                        let segments = parse_json_path(path).ok()?;
+                        select(&json, &segments).cloned()
+                    });
+                let passed = value.as_ref().is_some_and(|value| {
+                    equals.as_ref().is_none_or(|expected| value == expected)
+                        && value_type.is_none_or(|expected| JsonValueType::of(value) == expected)
+                });
+                (passed, value.map(|value| value.to_string()))
+            }
+        };
+        QaApiAssertionResult {
+            assertion: self.clone(),
+            description: self.describe(),
+            passed,
+            actual,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaApiAssertionResult {
+    pub assertion: QaApiAssertion,
+    pub description: String,
+    pub passed: bool,
+    /// The status, header value or JSON value found; `None` when missing
+    pub actual: Option<String>,
+}
+
+/// Checks every assertion against one response
+pub fn check_assertions(
+    assertions: &[QaApiAssertion],
+    status: u16,
+    headers: &[(String, String)],
+    body: &str,
+) -> Vec<QaApiAssertionResult> {
+    assertions
+        .iter()
+        .map(|assertion| assertion.check(status, headers, body))
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// `$.a.b[0]`, `a.b[0]` or `$` for the whole body
+fn parse_json_path(path: &str) -> Result<Vec<PathSegment>> {
+    let invalid = || AppError::ValidationError(format!("Invalid JSON path '{}'", path));
+    let trimmed = path.trim();
+    let rest = trimmed.strip_prefix('$').unwrap_or(trimmed);
+    let rest = rest.strip_prefix('.').unwrap_or(rest);
+    if rest.is_empty() {
+        return if trimmed.starts_with('$') {
+            Ok(Vec::new())
+        } else {
+            Err(invalid())
+        };
+    }
+
+    let mut segments = Vec::new();
+    for part in rest.split('.') {
+        let (key, mut indices) = part
+            .split_once('[')
+            .map_or((part, ""), |(key, rest)| (key, rest));
+        if key.is_empty() && indices.is_empty() {
+            return Err(invalid());
+        }
+        if !key.is_empty() {
+            segments.push(PathSegment::Key(key.to_string()));
+        }
+        while !indices.is_empty() {
+            let (index, after) = indices.split_once(']').ok_or_else(invalid)?;
+            segments.push(PathSegment::Index(
+                index.trim().parse().map_err(|_| invalid())?,
+            ));
+            indices = match after {
+                "" => "",
+                after => after.strip_prefix('[').ok_or_else(invalid)?,
+            };
+        }
+    }
+    Ok(segments)
+}
+
+fn select<'a>(value: &'a JsonValue, segments: &[PathSegment]) -> Option<&'a JsonValue> {
+    segments
+        .iter()
+        .try_fold(value, |value, segment| match segment {
+            PathSegment::Key(key) => value.get(key),
+            PathSegment::Index(index) => value.get(index),
+        })
+}
+
+/// Outcome of one saved API test in a run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaApiTestResult {
+    pub test_case_id: String,
+    pub title: String,
+    /// `passed`, `failed`, or `blocked` when the request could not be sent
+    pub status: QaTestResultStatus,
+    pub response_status: Option<u16>,
+    pub duration_ms: Option<i64>,
+    pub assertions: Vec<QaApiAssertionResult>,
+    pub error: Option<String>,
+    /// The recorded QA API call of this test
+    pub api_call_id: Option<String>,
+}
+
+/// A regression run over a session's saved API tests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaApiTestRunReport {
+    pub run: QaSessionRun,
+    pub results: Vec<QaApiTestResult>,
+    pub passed: usize,
+    /// Failed or blocked
+    pub failed: usize,
+}
+
+/// Assertions suggested for one endpoint by comparing the responses it
+/// gave during a session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaEndpointAssertions {
+    pub method: String,
+    pub path: String,
+    /// Responses compared
+    pub call_count: usize,
+    /// Status, content type and the JSON values that held in every response;
+    /// values that changed are checked by type only
+    pub assertions: Vec<QaApiAssertion>,
+    /// JSON paths whose values changed between responses, such as ids and
+    /// timestamps, to leave out of equality checks
+    pub changing_paths: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &str = r#"{"data":{"items":[{"id":7,"name":"Tea"}],"total":1},"ok":true}"#;
+
+    fn headers() -> Vec<(String, String)> {
+        vec![(
+            "content-type".to_string(),
+            "application/json; charset=utf-8".to_string(),
+        )]
+    }
+
+    fn passes(assertion: QaApiAssertion) -> bool {
+        assertion.check(200, &headers(), BODY).passed
+    }
+
+    #[test]
+    fn test_status_and_header_assertions() {
+        assert!(passes(QaApiAssertion::Status { equals: 200 }));
+        assert!(!passes(QaApiAssertion::Status { equals: 201 }));
+        assert!(passes(QaApiAssertion::Header {
+            name: "Content-Type".to_string(),
+            equals: None,
+            contains: Some("application/json".to_string()),
+        }));
+        let missing = QaApiAssertion::Header {
+            name: "x-request-id".to_string(),
+            equals: None,
+            contains: None,
+        }
+        .check(200, &headers(), BODY);
+        assert!(!missing.passed);
+        assert_eq!(missing.actual, None);
+    }
+
+    #[test]
+    fn test_json_path_assertions() {
+        let json_path = |path: &str, equals: Option<JsonValue>, value_type| {
+            passes(QaApiAssertion::JsonPath {
+                path: path.to_string(),
+                equals,
+                value_type,
+            })
+        };
+        assert!(json_path(
+            "$.data.items[0].id",
+            Some(serde_json::json!(7)),
+            None
+        ));
+        assert!(json_path(
+            "data.items[0].name",
+            None,
+            Some(JsonValueType::String)
+        ));
+        assert!(json_path("$.ok", Some(serde_json::json!(true)), None));
+        assert!(json_path("$", None, Some(JsonValueType::Object)));
+        assert!(!json_path("$.data.items[1]", None, None));
+        assert!(!json_path(
+            "$.data.total",
+            None,
+            Some(JsonValueType::String)
+        ));
+
+        assert!(parse_json_path("$.a[x]").is_err());
+        assert!(parse_json_path("a..b").is_err());
+        assert_eq!(
+            parse_json_path("$.a[0][1]").unwrap(),
+            vec![
+                PathSegment::Key("a".to_string()),
+                PathSegment::Index(0),
+                PathSegment::Index(1)
+            ]
+        );
+    }
+}
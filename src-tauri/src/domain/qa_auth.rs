@@ -0,0 +1,292 @@
+// ============================================================
+// QA API AUTH PROFILES
+// ============================================================
+// OAuth2 profiles the QA API runner signs requests with. A request
+// names a profile; the runner fetches a token with the profile's
+// grant, caches it until shortly before it expires and refreshes it
+// with the refresh token when the server issued one. Client secrets
+// and passwords live in the OS keychain, never in the settings.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::settings::SettingsSection;
+
+/// A token this close to expiry is fetched again before it is used
+pub const TOKEN_EXPIRY_SKEW_MS: i64 = 30_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OAuthGrant {
+    ClientCredentials,
+    /// Resource owner password; needs a username and a stored password
+    Password,
+}
+
+impl OAuthGrant {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ClientCredentials => "client_credentials",
+            Self::Password => "password",
+        }
+    }
+}
+
+/// How the client id and secret reach the token endpoint
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OAuthClientAuth {
+    /// `client_id` and `client_secret` form fields
+    #[default]
+    Body,
+    /// HTTP Basic authentication
+    Basic,
+}
+
+/// Secrets of a profile, each in its own keychain entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QaAuthSecret {
+    ClientSecret,
+    Password,
+}
+
+impl QaAuthSecret {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ClientSecret => "client-secret",
+            Self::Password => "password",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaAuthProfile {
+    /// Referenced by requests as `authProfileId`
+    pub id: String,
+    pub name: String,
+    pub grant: OAuthGrant,
+    pub token_url: String,
+    pub client_id: String,
+    #[serde(default)]
+    pub client_auth: OAuthClientAuth,
+    #[serde(default)]
+    pub scope: Option<String>,
+    /// Sent as `audience` for providers such as Auth0
+    #[serde(default)]
+    pub audience: Option<String>,
+    /// Password grant only
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Header the token is sent in
+    #[serde(default = "default_header_name")]
+    pub header_name: String,
+    /// Put before the token in the header; empty sends the bare token
+    #[serde(default = "default_token_prefix")]
+    pub token_prefix: String,
+}
+
+fn default_header_name() -> String {
+    "Authorization".to_string()
+}
+
+fn default_token_prefix() -> String {
+    "Bearer".to_string()
+}
+
+impl QaAuthProfile {
+    pub fn validate(&self) -> Result<()> {
+        let id = self.id.trim();
+        if id.is_empty()
+            || !id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(AppError::ValidationError(format!(
+                "Auth profile id '{}' must be letters, digits, '-' or '_'",
+                self.id
+            )));
+        }
+        if self.name.trim().is_empty() {
+            return Err(AppError::ValidationError(format!(
+                "Auth profile '{}' needs a name",
+                id
+            )));
+        }
+        let valid_url = url::Url::parse(self.token_url.trim())
+            .map(|parsed| matches!(parsed.scheme(), "http" | "https") && parsed.has_host())
+            .unwrap_or(false);
+        if !valid_url {
+            return Err(AppError::ValidationError(format!(
+                "Token URL '{}' of auth profile '{}' must be an http(s) URL",
+                self.token_url, id
+            )));
+        }
+        if self.client_id.trim().is_empty() {
+            return Err(AppError::ValidationError(format!(
+                "Auth profile '{}' needs a client id",
+                id
+            )));
+        }
+        if self.grant == OAuthGrant::Password
+            && self
+                .username
+                .as_deref()
+                .is_none_or(|name| name.trim().is_empty())
+        {
+            return Err(AppError::ValidationError(format!(
+                "Auth profile '{}' uses the password grant and needs a username",
+                id
+            )));
+        }
+        if self.header_name.trim().is_empty() {
+            return Err(AppError::ValidationError(format!(
+                "Auth profile '{}' needs a header name",
+                id
+            )));
+        }
+        Ok(())
+    }
+
+    /// The header value carrying `access_token`
+    pub fn header_value(&self, access_token: &str) -> String {
+        match self.token_prefix.trim() {
+            "" => access_token.to_string(),
+            prefix => format!("{} {}", prefix, access_token),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QaAuthSettings {
+    pub profiles: Vec<QaAuthProfile>,
+}
+
+impl SettingsSection for QaAuthSettings {
+    const KEY: &'static str = "qaAuthProfiles";
+}
+
+impl QaAuthSettings {
+    pub fn validate(&self) -> Result<()> {
+        for (index, profile) in self.profiles.iter().enumerate() {
+            profile.validate()?;
+            if self.profiles[..index]
+                .iter()
+                .any(|other| other.id.trim() == profile.id.trim())
+            {
+                return Err(AppError::ValidationError(format!(
+                    "Auth profile id '{}' is used twice",
+                    profile.id.trim()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn profile(&self, id: &str) -> Result<&QaAuthProfile> {
+        self.profiles
+            .iter()
+            .find(|profile| profile.id.trim() == id.trim())
+            .ok_or_else(|| AppError::NotFound(format!("Auth profile '{}' not found", id)))
+    }
+}
+
+/// A token issued for a profile
+#[derive(Debug, Clone, PartialEq)]
+pub struct OAuthToken {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    /// Unix milliseconds; `None` when the server gave no `expires_in`
+    pub expires_at: Option<i64>,
+}
+
+impl OAuthToken {
+    /// Usable at `now` without a refresh. Tokens without an expiry are kept
+    /// until the server rejects them.
+    pub fn is_fresh(&self, now: i64) -> bool {
+        self.expires_at
+            .is_none_or(|expires_at| now + TOKEN_EXPIRY_SKEW_MS < expires_at)
+    }
+}
+
+/// What the settings page shows about a profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaAuthProfileStatus {
+    pub id: String,
+    pub has_client_secret: bool,
+    pub has_password: bool,
+    /// Unix milliseconds the cached token expires at, if one is cached
+    pub token_expires_at: Option<i64>,
+    pub token_cached: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(id: &str, grant: OAuthGrant) -> QaAuthProfile {
+        QaAuthProfile {
+            id: id.to_string(),
+            name: "Staging".to_string(),
+            grant,
+            token_url: "https://auth.example.com/oauth/token".to_string(),
+            client_id: "qa-runner".to_string(),
+            client_auth: OAuthClientAuth::Body,
+            scope: None,
+            audience: None,
+            username: None,
+            header_name: default_header_name(),
+            token_prefix: default_token_prefix(),
+        }
+    }
+
+    #[test]
+    fn test_settings_validation() {
+        let settings = QaAuthSettings {
+            profiles: vec![profile("staging", OAuthGrant::ClientCredentials)],
+        };
+        assert!(settings.validate().is_ok());
+        assert!(settings.profile("staging").is_ok());
+        assert!(settings.profile("prod").is_err());
+
+        let password = QaAuthSettings {
+            profiles: vec![profile("staging", OAuthGrant::Password)],
+        };
+        assert!(password.validate().is_err());
+
+        let duplicate = QaAuthSettings {
+            profiles: vec![
+                profile("staging", OAuthGrant::ClientCredentials),
+                profile("staging", OAuthGrant::ClientCredentials),
+            ],
+        };
+        assert!(duplicate.validate().is_err());
+        assert!(profile("has space", OAuthGrant::ClientCredentials)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_token_freshness_and_header() {
+        let token = OAuthToken {
+            access_token: "abc".to_string(),
+            refresh_token: None,
+            expires_at: Some(100_000),
+        };
+        assert!(token.is_fresh(60_000));
+        assert!(!token.is_fresh(75_000));
+        assert!(OAuthToken {
+            expires_at: None,
+            ..token
+        }
+        .is_fresh(i64::MAX - TOKEN_EXPIRY_SKEW_MS));
+
+        let mut bare = profile("staging", OAuthGrant::ClientCredentials);
+        assert_eq!(bare.header_value("abc"), "Bearer abc");
+        bare.token_prefix = String::new();
+        assert_eq!(bare.header_value("abc"), "abc");
+    }
+}
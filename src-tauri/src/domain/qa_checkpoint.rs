@@ -10,6 +10,46 @@ pub struct QaCheckpoint {
     pub start_event_seq: i64,
     pub end_event_seq: i64,
     pub created_at: i64,
+
+    /// Tester annotation, passed along to the summary and test case prompts
+    #[serde(default)]
+    pub notes: Option<String>,
+
+    /// When the event range last changed through a merge or split
+    #[serde(default)]
+    pub updated_at: Option<i64>,
+}
+
+impl QaCheckpoint {
+    /// Re-segmented since its latest summary was written, so the summary and
+    /// test cases no longer match its events
+    pub fn needs_regeneration(&self, latest_summary: Option<&QaCheckpointSummary>) -> bool {
+        match (self.updated_at, latest_summary) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(updated_at), Some(summary)) => summary.created_at < updated_at,
+        }
+    }
+}
+
+/// Checkpoints of a session after a merge or split
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QaCheckpointEdit {
+    pub checkpoints: Vec<QaCheckpoint>,
+
+    /// Checkpoints whose events changed; their summaries and test cases
+    /// should be regenerated
+    pub affected_checkpoint_ids: Vec<String>,
+}
+
+/// Output of regenerating the affected checkpoints of a session
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QaCheckpointRegeneration {
+    pub checkpoint_ids: Vec<String>,
+    pub summaries: Vec<QaCheckpointSummary>,
+    pub test_cases: Vec<QaTestCase>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -21,6 +61,24 @@ pub struct QaCheckpointSummary {
     pub entities_json: Option<String>,
     pub risks_json: Option<String>,
     pub created_at: i64,
+
+    /// The checkpoint's actions with the API calls they triggered
+    /// (`QaActionApiCalls`), for tests to assert on after each action
+    #[serde(default)]
+    pub api_links_json: Option<String>,
+}
+
+/// What `qa_generate_test_cases` asks the model for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QaTestGenerationMode {
+    /// Negative, edge, exploratory and API gap cases
+    #[default]
+    Standard,
+    /// Only failure cases (invalid inputs, auth failures, boundary values)
+    /// built on the recorded fields and API request shapes, all stored as
+    /// `negative`
+    Negative,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -37,9 +95,37 @@ pub struct QaTestCase {
     pub priority: Option<String>,
     pub status: Option<String>,
     pub dedup_hash: String,
+    /// `QaEndpointAssertions` suggested for the API calls of the flow, as JSON
+    #[serde(default)]
+    pub assertions_json: Option<String>,
     pub created_at: i64,
 }
 
+/// A Cucumber feature file built from the test cases of one checkpoint
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QaGherkinFeature {
+    /// `None` for the feature holding cases outside any checkpoint
+    pub checkpoint_id: Option<String>,
+    pub file_name: String,
+    pub content: String,
+    pub scenario_count: usize,
+}
+
+/// A checkpoint's test case prompt paired with the test cases it has, for
+/// distilling a model that writes test cases
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QaTrainingExample {
+    pub session_id: String,
+    pub checkpoint_id: String,
+    pub prompt: String,
+    /// JSON with the `negative_cases`, `edge_cases`, `exploratory_charters`
+    /// and `api_gap_checks` the prompt asks for
+    pub expected_output: String,
+    pub test_case_count: usize,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct QaLlmRun {
@@ -52,4 +138,42 @@ pub struct QaLlmRun {
     pub input_summary: Option<String>,
     pub output_json: String,
     pub created_at: i64,
+    #[serde(flatten)]
+    pub usage: QaLlmUsage,
+}
+
+/// Token counts, latency and cost of one LLM run
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QaLlmUsage {
+    pub prompt_tokens: Option<i64>,
+    pub completion_tokens: Option<i64>,
+
+    /// The provider reported no usage; counts are a ~4 chars/token estimate
+    pub tokens_estimated: bool,
+    pub latency_ms: Option<i64>,
+
+    /// From the `qaLlmAudit` model prices; `None` when the model has no price
+    pub cost_usd: Option<f64>,
+}
+
+/// Prompt and response text of an LLM run, each capped to `maxBodyChars`
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QaLlmRunBodies {
+    pub system_prompt: Option<String>,
+    pub user_prompt: Option<String>,
+    pub response_text: Option<String>,
+
+    /// At least one body was cut
+    pub truncated: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QaLlmRunDetail {
+    #[serde(flatten)]
+    pub run: QaLlmRun,
+    #[serde(flatten)]
+    pub bodies: QaLlmRunBodies,
 }
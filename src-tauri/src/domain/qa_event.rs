@@ -32,6 +32,9 @@ pub struct QaEvent {
     pub url: Option<String>,
     pub screenshot_id: Option<String>,
     pub screenshot_path: Option<String>,
+    /// Copy of the screenshot with the clicked element highlighted
+    #[serde(default)]
+    pub annotated_screenshot_path: Option<String>,
     pub meta_json: Option<String>,
 }
 
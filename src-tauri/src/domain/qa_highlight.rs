@@ -0,0 +1,202 @@
+// ============================================================
+// QA SCREENSHOT HIGHLIGHTS
+// ============================================================
+// A screenshot taken for a click gets an annotated copy with a
+// rectangle around the clicked element, saved next to the
+// original as `<name>_annotated.png`. The recorders put the
+// element bounds (`rect`), the pointer position (`coordinates`)
+// and the page viewport size (`viewport`) in the event's
+// `meta_json`, all in CSS pixels; the viewport scales them to
+// the screenshot. Without bounds a fixed box is drawn around
+// the pointer. Clicks inside nested frames are not highlighted
+// because their positions are relative to the frame.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+const HIGHLIGHT_EVENT_TYPES: &[&str] = &["click", "dblclick"];
+
+/// Space between the element and the rectangle, in CSS pixels
+const HIGHLIGHT_PADDING: f64 = 4.0;
+
+/// Side of the box drawn around a pointer without element bounds
+const POINTER_BOX_SIZE: f64 = 32.0;
+
+const ANNOTATED_SUFFIX: &str = "_annotated";
+
+/// Area of the screenshot to highlight, in image pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HighlightRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Deserialize)]
+struct MetaPoint {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Deserialize)]
+struct MetaBounds {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+#[derive(Deserialize)]
+struct MetaViewport {
+    width: f64,
+    height: f64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClickMeta {
+    coordinates: Option<MetaPoint>,
+    rect: Option<MetaBounds>,
+    viewport: Option<MetaViewport>,
+    #[serde(default)]
+    frame_path: Vec<serde_json::Value>,
+}
+
+/// Whether screenshots of this event type get an annotated copy
+pub fn is_highlighted_event(event_type: &str) -> bool {
+    HIGHLIGHT_EVENT_TYPES.contains(&event_type)
+}
+
+/// Rectangle to draw on a screenshot of the given size, or `None` when
+/// the event carries no usable position.
+pub fn highlight_rect(
+    event_type: &str,
+    meta_json: Option<&str>,
+    image_width: u32,
+    image_height: u32,
+) -> Option<HighlightRect> {
+    if !is_highlighted_event(event_type) || image_width == 0 || image_height == 0 {
+        return None;
+    }
+    let meta: ClickMeta = serde_json::from_str(meta_json?).ok()?;
+    if !meta.frame_path.is_empty() {
+        return None;
+    }
+
+    let (left, top, right, bottom) = match (meta.rect, meta.coordinates) {
+        (Some(rect), _) if rect.width > 0.0 && rect.height > 0.0 => (
+            rect.x - HIGHLIGHT_PADDING,
+            rect.y - HIGHLIGHT_PADDING,
+            rect.x + rect.width + HIGHLIGHT_PADDING,
+            rect.y + rect.height + HIGHLIGHT_PADDING,
+        ),
+        (_, Some(point)) => {
+            let half = POINTER_BOX_SIZE / 2.0;
+            (
+                point.x - half,
+                point.y - half,
+                point.x + half,
+                point.y + half,
+            )
+        }
+        _ => return None,
+    };
+
+    let (image_w, image_h) = (f64::from(image_width), f64::from(image_height));
+    let (scale_x, scale_y) = match meta.viewport {
+        Some(viewport) if viewport.width > 0.0 && viewport.height > 0.0 => {
+            (image_w / viewport.width, image_h / viewport.height)
+        }
+        _ => (1.0, 1.0),
+    };
+
+    let left = (left * scale_x).floor().clamp(0.0, image_w);
+    let top = (top * scale_y).floor().clamp(0.0, image_h);
+    let right = (right * scale_x).ceil().clamp(0.0, image_w);
+    let bottom = (bottom * scale_y).ceil().clamp(0.0, image_h);
+    if right <= left || bottom <= top {
+        return None;
+    }
+
+    Some(HighlightRect {
+        x: left as u32,
+        y: top as u32,
+        width: (right - left) as u32,
+        height: (bottom - top) as u32,
+    })
+}
+
+/// Where the annotated copy of a screenshot is stored
+pub fn annotated_screenshot_path(screenshot_path: &Path) -> PathBuf {
+    let stem = screenshot_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    screenshot_path.with_file_name(format!("{stem}{ANNOTATED_SUFFIX}.png"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pads_and_scales_element_bounds() {
+        let meta = r#"{"tag":"button","coordinates":{"x":60,"y":30},
+            "rect":{"x":40,"y":20,"width":50,"height":20},
+            "viewport":{"width":400,"height":300}}"#;
+
+        let rect = highlight_rect("click", Some(meta), 800, 600).unwrap();
+
+        assert_eq!(
+            rect,
+            HighlightRect {
+                x: 72,
+                y: 32,
+                width: 116,
+                height: 56,
+            }
+        );
+    }
+
+    #[test]
+    fn boxes_pointer_and_clamps_to_image() {
+        let meta = r#"{"coordinates":{"x":5,"y":290}}"#;
+
+        let rect = highlight_rect("click", Some(meta), 400, 300).unwrap();
+
+        assert_eq!(
+            rect,
+            HighlightRect {
+                x: 0,
+                y: 274,
+                width: 21,
+                height: 26,
+            }
+        );
+    }
+
+    #[test]
+    fn skips_frames_other_events_and_missing_positions() {
+        let framed = r#"{"coordinates":{"x":5,"y":5},"framePath":["iframe#pay"]}"#;
+        assert!(highlight_rect("click", Some(framed), 400, 300).is_none());
+        assert!(
+            highlight_rect("input", Some(r#"{"coordinates":{"x":5,"y":5}}"#), 400, 300).is_none()
+        );
+        assert!(highlight_rect("click", Some(r#"{"tag":"a"}"#), 400, 300).is_none());
+        assert!(highlight_rect("click", None, 400, 300).is_none());
+        let outside = r#"{"coordinates":{"x":900,"y":900}}"#;
+        assert!(highlight_rect("click", Some(outside), 400, 300).is_none());
+    }
+
+    #[test]
+    fn annotated_copy_sits_next_to_screenshot() {
+        let path = Path::new("/qa/s1/screenshots/screenshot_1_abcd.png");
+
+        assert_eq!(
+            annotated_screenshot_path(path),
+            PathBuf::from("/qa/s1/screenshots/screenshot_1_abcd_annotated.png")
+        );
+    }
+}
@@ -0,0 +1,154 @@
+// ============================================================
+// QA LLM AUDIT
+// ============================================================
+// What is kept of each QA LLM call besides its parsed output: the
+// prompt and response bodies (capped), token counts and an estimated
+// cost from per-model prices. Read back through `qa_get_llm_run_detail`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::settings::SettingsSection;
+
+/// Price of one model in USD per million tokens
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaModelPrice {
+    /// Model id as configured, e.g. `gpt-4o-mini`; `*` matches any model
+    pub model: String,
+    pub input_per_million: f64,
+    pub output_per_million: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QaLlmAuditSettings {
+    /// Keep the system prompt, user prompt and raw response of each run
+    pub store_bodies: bool,
+
+    /// Each stored body is cut to this many characters; 0 keeps it whole
+    pub max_body_chars: usize,
+
+    pub model_prices: Vec<QaModelPrice>,
+}
+
+impl Default for QaLlmAuditSettings {
+    fn default() -> Self {
+        Self {
+            store_bodies: true,
+            max_body_chars: 200_000,
+            model_prices: Vec::new(),
+        }
+    }
+}
+
+impl QaLlmAuditSettings {
+    pub fn validate(&self) -> Result<()> {
+        for price in &self.model_prices {
+            if price.model.trim().is_empty() {
+                return Err(AppError::ValidationError(
+                    "Model price needs a model id".to_string(),
+                ));
+            }
+            let valid = |value: f64| value.is_finite() && value >= 0.0;
+            if !valid(price.input_per_million) || !valid(price.output_per_million) {
+                return Err(AppError::ValidationError(format!(
+                    "Prices for '{}' must be zero or positive",
+                    price.model
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Exact model match first, then the `*` fallback
+    pub fn price_for(&self, model: &str) -> Option<&QaModelPrice> {
+        let model = model.trim();
+        self.model_prices
+            .iter()
+            .find(|price| price.model.trim().eq_ignore_ascii_case(model))
+            .or_else(|| {
+                self.model_prices
+                    .iter()
+                    .find(|price| price.model.trim() == "*")
+            })
+    }
+
+    pub fn cost_usd(&self, model: &str, prompt_tokens: u64, completion_tokens: u64) -> Option<f64> {
+        self.price_for(model).map(|price| {
+            (prompt_tokens as f64 * price.input_per_million
+                + completion_tokens as f64 * price.output_per_million)
+                / 1_000_000.0
+        })
+    }
+
+    /// `body` within `max_body_chars`, and whether it was cut
+    pub fn cap_body(&self, body: &str) -> (String, bool) {
+        if self.max_body_chars == 0 {
+            return (body.to_string(), false);
+        }
+        match body.char_indices().nth(self.max_body_chars) {
+            Some((end, _)) => (body[..end].to_string(), true),
+            None => (body.to_string(), false),
+        }
+    }
+}
+
+impl SettingsSection for QaLlmAuditSettings {
+    const KEY: &'static str = "qaLlmAudit";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(model: &str, input: f64, output: f64) -> QaModelPrice {
+        QaModelPrice {
+            model: model.to_string(),
+            input_per_million: input,
+            output_per_million: output,
+        }
+    }
+
+    #[test]
+    fn cost_prefers_exact_model_over_wildcard() {
+        let settings = QaLlmAuditSettings {
+            model_prices: vec![price("*", 1.0, 1.0), price("gpt-4o-mini", 0.15, 0.6)],
+            ..Default::default()
+        };
+        let cost = settings
+            .cost_usd("GPT-4o-mini", 1_000_000, 500_000)
+            .unwrap();
+        assert!((cost - 0.45).abs() < 1e-9);
+        let cost = settings.cost_usd("other", 2_000, 0).unwrap();
+        assert!((cost - 0.002).abs() < 1e-9);
+        assert_eq!(
+            QaLlmAuditSettings::default().cost_usd("other", 10, 10),
+            None
+        );
+    }
+
+    #[test]
+    fn caps_bodies_on_char_boundaries() {
+        let settings = QaLlmAuditSettings {
+            max_body_chars: 3,
+            ..Default::default()
+        };
+        assert_eq!(settings.cap_body("héllo"), ("hél".to_string(), true));
+        assert_eq!(settings.cap_body("hé"), ("hé".to_string(), false));
+        let unlimited = QaLlmAuditSettings {
+            max_body_chars: 0,
+            ..Default::default()
+        };
+        assert_eq!(unlimited.cap_body("hello"), ("hello".to_string(), false));
+    }
+
+    #[test]
+    fn rejects_negative_prices() {
+        let settings = QaLlmAuditSettings {
+            model_prices: vec![price("m", -1.0, 0.0)],
+            ..Default::default()
+        };
+        assert!(settings.validate().is_err());
+    }
+}
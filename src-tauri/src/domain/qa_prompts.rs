@@ -0,0 +1,170 @@
+// ============================================================
+// QA PROMPT TEMPLATES
+// ============================================================
+// System prompts a team edits in place of the built-in ones, so
+// generated summaries and test cases follow its own format, language
+// and severity taxonomy. Each edit bumps the template's version, which
+// the LLM runs that used it record as their prompt version.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::settings::SettingsSection;
+
+/// Replaced by the output language when a template is rendered
+pub const LANGUAGE_PLACEHOLDER: &str = "{language}";
+
+const MAX_TEMPLATE_CHARS: usize = 20_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QaPromptKind {
+    CheckpointSummary,
+    TestCases,
+    NegativeCases,
+    Explore,
+    VisualAnalysis,
+}
+
+impl QaPromptKind {
+    pub const ALL: [QaPromptKind; 5] = [
+        QaPromptKind::CheckpointSummary,
+        QaPromptKind::TestCases,
+        QaPromptKind::NegativeCases,
+        QaPromptKind::Explore,
+        QaPromptKind::VisualAnalysis,
+    ];
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaPromptTemplate {
+    /// `None` once reset, so the built-in prompt is used again
+    pub template: Option<String>,
+    /// Bumped on every edit and reset; never reused for a kind
+    pub version: u32,
+    pub updated_at: i64,
+}
+
+impl QaPromptTemplate {
+    pub fn render(&self, language: &str) -> Option<String> {
+        self.template
+            .as_ref()
+            .map(|template| template.replace(LANGUAGE_PLACEHOLDER, language))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QaPromptSettings {
+    pub templates: BTreeMap<QaPromptKind, QaPromptTemplate>,
+}
+
+impl QaPromptSettings {
+    pub fn validate(&self) -> Result<()> {
+        for template in self.templates.values() {
+            if let Some(text) = template.template.as_deref() {
+                validate_template(text)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The edited template of `kind`, if it hasn't been reset
+    pub fn custom(&self, kind: QaPromptKind) -> Option<&QaPromptTemplate> {
+        self.templates
+            .get(&kind)
+            .filter(|template| template.template.is_some())
+    }
+
+    pub fn set_template(&mut self, kind: QaPromptKind, template: String, now: i64) -> Result<()> {
+        validate_template(&template)?;
+        self.bump(kind, Some(template), now);
+        Ok(())
+    }
+
+    /// Back to the built-in prompt; `false` when it was already in use
+    pub fn reset(&mut self, kind: QaPromptKind, now: i64) -> bool {
+        if self.custom(kind).is_none() {
+            return false;
+        }
+        self.bump(kind, None, now);
+        true
+    }
+
+    fn bump(&mut self, kind: QaPromptKind, template: Option<String>, now: i64) {
+        let version = self.templates.get(&kind).map_or(0, |t| t.version) + 1;
+        self.templates.insert(
+            kind,
+            QaPromptTemplate {
+                template,
+                version,
+                updated_at: now,
+            },
+        );
+    }
+}
+
+fn validate_template(template: &str) -> Result<()> {
+    if template.trim().is_empty() {
+        return Err(AppError::ValidationError(
+            "Prompt template must not be empty".to_string(),
+        ));
+    }
+    if template.chars().count() > MAX_TEMPLATE_CHARS {
+        return Err(AppError::ValidationError(format!(
+            "Prompt template is longer than {} characters",
+            MAX_TEMPLATE_CHARS
+        )));
+    }
+    Ok(())
+}
+
+impl SettingsSection for QaPromptSettings {
+    const KEY: &'static str = "qaPrompts";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions_keep_counting_across_resets() {
+        let mut settings = QaPromptSettings::default();
+        let kind = QaPromptKind::TestCases;
+        settings
+            .set_template(kind, "Write cases in {language}.".to_string(), 1)
+            .unwrap();
+        assert_eq!(settings.custom(kind).unwrap().version, 1);
+        assert_eq!(
+            settings
+                .custom(kind)
+                .unwrap()
+                .render("Indonesian")
+                .as_deref(),
+            Some("Write cases in Indonesian.")
+        );
+
+        assert!(settings.reset(kind, 2));
+        assert!(settings.custom(kind).is_none());
+        assert!(!settings.reset(kind, 3));
+
+        settings.set_template(kind, "Again".to_string(), 4).unwrap();
+        assert_eq!(settings.custom(kind).unwrap().version, 3);
+        assert!(settings.set_template(kind, "  ".to_string(), 5).is_err());
+    }
+
+    #[test]
+    fn templates_round_trip_with_kind_keys() {
+        let mut settings = QaPromptSettings::default();
+        settings
+            .set_template(QaPromptKind::CheckpointSummary, "Summarize".to_string(), 1)
+            .unwrap();
+        let json = serde_json::to_value(&settings).unwrap();
+        assert_eq!(json["templates"]["checkpoint_summary"]["version"], 1);
+        let parsed: QaPromptSettings = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, settings);
+    }
+}
@@ -0,0 +1,81 @@
+// ============================================================
+// QA SECRET REDACTION
+// ============================================================
+// Recorded API calls and events are masked before they are written:
+// values of sensitive headers and fields, bearer tokens and API keys,
+// and anything matching the user's own patterns.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::settings::SettingsSection;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QaRedactionSettings {
+    pub enabled: bool,
+
+    /// Headers whose whole value is masked, matched case-insensitively
+    pub header_names: Vec<String>,
+
+    /// JSON, form and query fields whose value is masked. A field matches
+    /// when its name ends with one of these, ignoring case, `-` and `_`,
+    /// so `password` also covers `newPassword` and `confirm_password`.
+    pub field_names: Vec<String>,
+
+    /// Extra regular expressions; every match is masked
+    pub patterns: Vec<String>,
+}
+
+impl Default for QaRedactionSettings {
+    fn default() -> Self {
+        let names = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        Self {
+            enabled: true,
+            header_names: names(&[
+                "authorization",
+                "proxy-authorization",
+                "cookie",
+                "set-cookie",
+                "x-api-key",
+                "x-auth-token",
+            ]),
+            field_names: names(&["password", "passwd", "secret", "token", "api_key", "apikey"]),
+            patterns: Vec::new(),
+        }
+    }
+}
+
+impl SettingsSection for QaRedactionSettings {
+    const KEY: &'static str = "qaRedaction";
+}
+
+impl QaRedactionSettings {
+    pub fn validate(&self) -> Result<()> {
+        if let Some(name) = self
+            .header_names
+            .iter()
+            .chain(&self.field_names)
+            .find(|name| name.trim().is_empty())
+        {
+            return Err(AppError::ValidationError(format!(
+                "Redacted header and field names cannot be blank ('{}')",
+                name
+            )));
+        }
+        for pattern in &self.patterns {
+            regex::Regex::new(pattern).map_err(|e| {
+                AppError::ValidationError(format!("Invalid redaction pattern '{}': {}", pattern, e))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Rows changed by redacting a session after the fact
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaRedactionReport {
+    pub api_calls: u64,
+    pub events: u64,
+}
@@ -0,0 +1,164 @@
+// ============================================================
+// QA RETENTION
+// ============================================================
+// How long QA sessions are kept and how much screenshot storage
+// they may use, plus the report of `qa_cleanup_storage`. Expired
+// sessions are deleted with their events and files; the storage
+// cap then removes the oldest remaining screenshots.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::settings::SettingsSection;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Limits applied by `qa_cleanup_storage`; a zero limit is not enforced
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QaRetentionSettings {
+    /// Delete sessions started more than this many days ago
+    pub max_session_age_days: u32,
+
+    /// Remove the oldest screenshots once all sessions together hold more
+    pub max_screenshot_mb: u64,
+
+    /// Apply the limits when the app starts
+    pub cleanup_on_startup: bool,
+}
+
+impl Default for QaRetentionSettings {
+    fn default() -> Self {
+        Self {
+            max_session_age_days: 0,
+            max_screenshot_mb: 0,
+            cleanup_on_startup: true,
+        }
+    }
+}
+
+impl QaRetentionSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.max_session_age_days > 365 * 100 {
+            return Err(AppError::ValidationError(
+                "Session age limit must be at most 100 years".to_string(),
+            ));
+        }
+        if self.max_screenshot_mb > 1024 * 1024 * 1024 {
+            return Err(AppError::ValidationError(
+                "Screenshot storage limit is too large".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_session_age_days > 0 || self.max_screenshot_mb > 0
+    }
+
+    /// Sessions started before this timestamp (ms) have expired
+    pub fn session_cutoff(&self, now_ms: i64) -> Option<i64> {
+        (self.max_session_age_days > 0)
+            .then(|| now_ms - i64::from(self.max_session_age_days) * DAY_MS)
+    }
+
+    pub fn max_screenshot_bytes(&self) -> Option<u64> {
+        (self.max_screenshot_mb > 0).then(|| self.max_screenshot_mb.saturating_mul(1024 * 1024))
+    }
+}
+
+impl SettingsSection for QaRetentionSettings {
+    const KEY: &'static str = "qaRetention";
+}
+
+/// A session removed (or, on a dry run, to be removed) for being too old
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaExpiredSession {
+    pub session_id: String,
+    pub title: String,
+    pub started_at: i64,
+    pub event_count: u64,
+
+    /// Size of the session folder (screenshots and other files)
+    pub file_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaCleanupReport {
+    /// Nothing was deleted; the report shows what would be
+    pub dry_run: bool,
+    pub expired_sessions: Vec<QaExpiredSession>,
+    pub events_removed: u64,
+
+    /// Screenshots removed to get under the storage cap, besides those of
+    /// expired sessions
+    pub screenshots_removed: u64,
+    pub screenshot_bytes_before: u64,
+    pub screenshot_bytes_after: u64,
+    pub bytes_freed: u64,
+
+    /// Sessions or files that could not be removed; the rest of the cleanup still ran
+    pub errors: Vec<String>,
+}
+
+impl QaCleanupReport {
+    pub fn new(dry_run: bool) -> Self {
+        Self {
+            dry_run,
+            expired_sessions: Vec::new(),
+            events_removed: 0,
+            screenshots_removed: 0,
+            screenshot_bytes_before: 0,
+            screenshot_bytes_after: 0,
+            bytes_freed: 0,
+            errors: Vec::new(),
+        }
+    }
+}
+
+/// How many of `sizes` (oldest first) to remove so that `total` fits in
+/// `max_bytes`. Returns fewer than needed when the candidates run out.
+pub fn screenshots_over_cap(sizes: &[u64], total: u64, max_bytes: u64) -> usize {
+    let mut remaining = total;
+    let mut count = 0;
+    for size in sizes {
+        if remaining <= max_bytes {
+            break;
+        }
+        remaining = remaining.saturating_sub(*size);
+        count += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removes_oldest_screenshots_until_under_cap() {
+        assert_eq!(screenshots_over_cap(&[10, 10, 10], 30, 30), 0);
+        assert_eq!(screenshots_over_cap(&[10, 10, 10], 30, 15), 2);
+        assert_eq!(screenshots_over_cap(&[10, 10, 10], 30, 0), 3);
+        // Screenshots of the active session count toward the total but are not candidates
+        assert_eq!(screenshots_over_cap(&[5], 50, 20), 1);
+    }
+
+    #[test]
+    fn zero_limits_are_disabled() {
+        let settings = QaRetentionSettings::default();
+        assert!(!settings.is_enabled());
+        assert_eq!(settings.session_cutoff(1_000), None);
+        assert_eq!(settings.max_screenshot_bytes(), None);
+
+        let settings = QaRetentionSettings {
+            max_session_age_days: 2,
+            max_screenshot_mb: 1,
+            ..Default::default()
+        };
+        assert_eq!(settings.session_cutoff(3 * DAY_MS), Some(DAY_MS));
+        assert_eq!(settings.max_screenshot_bytes(), Some(1024 * 1024));
+    }
+}
@@ -0,0 +1,139 @@
+// ============================================================
+// QA SELECTORS
+// ============================================================
+// Which element attributes the recorders build selectors from,
+// in priority order, e.g. `data-qa` or `formcontrolname` first
+// for apps that tag their elements that way. A session may carry
+// its own list; otherwise the `qaSelectors` settings apply. The
+// recorders also keep the listed attributes found on an element,
+// so replay can find it by the next one when the first changed.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::settings::SettingsSection;
+
+pub const DEFAULT_SELECTOR_ATTRIBUTES: &[&str] = &[
+    "data-testid",
+    "data-purpose",
+    "id",
+    "name",
+    "aria-label",
+    "role",
+];
+
+/// Attributes a list may hold at most
+const MAX_SELECTOR_ATTRIBUTES: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QaSelectorSettings {
+    /// Attributes tried in order; the first one set on the element wins
+    pub attributes: Vec<String>,
+}
+
+impl Default for QaSelectorSettings {
+    fn default() -> Self {
+        Self {
+            attributes: DEFAULT_SELECTOR_ATTRIBUTES
+                .iter()
+                .map(|attr| attr.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl QaSelectorSettings {
+    pub fn validate(&self) -> Result<()> {
+        if normalize_selector_attributes(&self.attributes)?.is_empty() {
+            return Err(AppError::ValidationError(
+                "At least one selector attribute is required".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// The session's own list when it has one, else the configured one,
+    /// else the defaults
+    pub fn resolve(&self, session_attributes: Option<&[String]>) -> Vec<String> {
+        let configured = |attributes: &[String]| {
+            normalize_selector_attributes(attributes)
+                .ok()
+                .filter(|attributes| !attributes.is_empty())
+        };
+        session_attributes
+            .and_then(configured)
+            .or_else(|| configured(&self.attributes))
+            .unwrap_or_else(|| Self::default().attributes)
+    }
+}
+
+impl SettingsSection for QaSelectorSettings {
+    const KEY: &'static str = "qaSelectors";
+}
+
+/// Trimmed and lowercased, without blanks and repeats. Names must be plain
+/// HTML attribute names, since the recorders put them in CSS selectors.
+pub fn normalize_selector_attributes(attributes: &[String]) -> Result<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::new();
+    for attr in attributes {
+        let attr = attr.trim().to_ascii_lowercase();
+        if attr.is_empty() || normalized.contains(&attr) {
+            continue;
+        }
+        let valid = attr.starts_with(|c: char| c.is_ascii_alphabetic())
+            && attr
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'));
+        if !valid {
+            return Err(AppError::ValidationError(format!(
+                "Invalid selector attribute '{}': expected a name such as data-qa",
+                attr
+            )));
+        }
+        normalized.push(attr);
+    }
+    if normalized.len() > MAX_SELECTOR_ATTRIBUTES {
+        return Err(AppError::ValidationError(format!(
+            "At most {} selector attributes are allowed",
+            MAX_SELECTOR_ATTRIBUTES
+        )));
+    }
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(attributes: &[&str]) -> Vec<String> {
+        attributes.iter().map(|attr| attr.to_string()).collect()
+    }
+
+    #[test]
+    fn normalize_trims_lowercases_and_dedupes() {
+        let normalized =
+            normalize_selector_attributes(&list(&[" Data-QA ", "", "formcontrolname", "data-qa"]))
+                .unwrap();
+        assert_eq!(normalized, list(&["data-qa", "formcontrolname"]));
+        assert!(normalize_selector_attributes(&list(&["data-qa\"]"])).is_err());
+        assert!(normalize_selector_attributes(&list(&["1st"])).is_err());
+    }
+
+    #[test]
+    fn resolve_prefers_session_then_settings_then_defaults() {
+        let settings = QaSelectorSettings {
+            attributes: list(&["data-qa", "id"]),
+        };
+        let session = list(&["formcontrolname"]);
+        assert_eq!(settings.resolve(Some(&session)), session);
+        assert_eq!(settings.resolve(Some(&[])), list(&["data-qa", "id"]));
+        assert_eq!(settings.resolve(None), list(&["data-qa", "id"]));
+
+        let empty = QaSelectorSettings {
+            attributes: Vec::new(),
+        };
+        assert_eq!(empty.resolve(None), list(DEFAULT_SELECTOR_ATTRIBUTES));
+        assert!(empty.validate().is_err());
+    }
+}
@@ -16,4 +16,18 @@ pub struct QaSession {
     pub started_at: i64,
     pub ended_at: Option<i64>,
     pub notes: Option<String>,
+    /// Selector attributes in priority order, overriding the `qaSelectors`
+    /// settings for this session
+    #[serde(default)]
+    pub selector_attributes: Option<Vec<String>>,
+}
+
+/// Session started and not yet ended, or recorded into, in this app run
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QaActiveSession {
+    pub session_id: String,
+    /// `None` for a session recorded into without being started this run
+    pub session_type: Option<String>,
+    pub recorder_run_id: Option<String>,
+    pub recorder_mode: Option<String>,
 }
@@ -0,0 +1,313 @@
+// ============================================================
+// QA TEST MANAGEMENT
+// ============================================================
+// Pushes generated test cases, and the results of running them,
+// to Xray (Jira) or TestRail. Targets and field mappings live in
+// the `qaTestManagement` settings section; the Xray client secret
+// and the TestRail API key stay in the OS keychain. Every push is
+// written to the session's sync log.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use crate::domain::error::{AppError, ErrorCode, Result};
+use crate::domain::settings::SettingsSection;
+
+/// Xray Cloud API root used unless a regional endpoint is configured
+pub const XRAY_CLOUD_API_URL: &str = "https://xray.cloud.getxray.app";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TestManagementProvider {
+    Xray,
+    Testrail,
+}
+
+impl TestManagementProvider {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Xray => "xray",
+            Self::Testrail => "testrail",
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Xray => "Xray",
+            Self::Testrail => "TestRail",
+        }
+    }
+}
+
+/// Jira project that receives test cases as Xray `Test` issues
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct XrayTarget {
+    /// Xray Cloud API root; change it only for a regional endpoint
+    pub api_url: String,
+
+    /// Client id of an Xray API key; its secret is kept in the keychain
+    pub client_id: String,
+
+    /// Jira project key, e.g. `SHOP`
+    pub project_key: String,
+
+    /// Jira priority name for each generated priority (`high`, `medium`, `low`)
+    pub priority_map: BTreeMap<String, String>,
+
+    /// Jira labels added for each case type (`positive`, `negative`, `edge`,
+    /// `exploratory`, `api_gap`)
+    pub type_labels: BTreeMap<String, Vec<String>>,
+
+    /// Jira fields set on every test, by field id (e.g. `components`)
+    pub extra_fields: BTreeMap<String, JsonValue>,
+}
+
+impl Default for XrayTarget {
+    fn default() -> Self {
+        Self {
+            api_url: XRAY_CLOUD_API_URL.to_string(),
+            client_id: String::new(),
+            project_key: String::new(),
+            priority_map: [("high", "High"), ("medium", "Medium"), ("low", "Low")]
+                .into_iter()
+                .map(|(from, to)| (from.to_string(), to.to_string()))
+                .collect(),
+            type_labels: BTreeMap::new(),
+            extra_fields: BTreeMap::new(),
+        }
+    }
+}
+
+/// TestRail project and section that receive test cases
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TestRailTarget {
+    /// Instance URL, e.g. `https://example.testrail.io`
+    pub base_url: String,
+
+    /// Login email; the API key is kept in the keychain
+    pub username: String,
+
+    pub project_id: Option<i64>,
+
+    /// Suite runs are created in; required by multi-suite projects
+    pub suite_id: Option<i64>,
+
+    /// Section new cases are added to
+    pub section_id: Option<i64>,
+
+    /// `priority_id` for each generated priority
+    pub priority_map: BTreeMap<String, i64>,
+
+    /// `type_id` for each case type; unmapped types use the project default
+    pub type_map: BTreeMap<String, i64>,
+
+    /// Fields set on every case, e.g. `template_id` or `custom_automation_type`
+    pub extra_fields: BTreeMap<String, JsonValue>,
+}
+
+impl Default for TestRailTarget {
+    fn default() -> Self {
+        Self {
+            base_url: String::new(),
+            username: String::new(),
+            project_id: None,
+            suite_id: None,
+            section_id: None,
+            // TestRail's built-in priorities
+            priority_map: [("critical", 4), ("high", 3), ("medium", 2), ("low", 1)]
+                .into_iter()
+                .map(|(from, to)| (from.to_string(), to))
+                .collect(),
+            type_map: BTreeMap::new(),
+            extra_fields: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QaTestManagementSettings {
+    pub xray: XrayTarget,
+    pub testrail: TestRailTarget,
+}
+
+impl SettingsSection for QaTestManagementSettings {
+    const KEY: &'static str = "qaTestManagement";
+}
+
+impl QaTestManagementSettings {
+    pub fn validate(&self) -> Result<()> {
+        for (name, url) in [
+            ("Xray API URL", &self.xray.api_url),
+            ("TestRail URL", &self.testrail.base_url),
+        ] {
+            let url = url.trim();
+            if url.is_empty() {
+                continue;
+            }
+            let valid = url::Url::parse(url)
+                .map(|parsed| matches!(parsed.scheme(), "http" | "https") && parsed.has_host())
+                .unwrap_or(false);
+            if !valid {
+                return Err(AppError::ValidationError(format!(
+                    "{} '{}' must be an http(s) URL",
+                    name, url
+                )));
+            }
+        }
+
+        let key = self.xray.project_key.trim();
+        let valid_key = key.is_empty()
+            || (key.starts_with(|c: char| c.is_ascii_uppercase())
+                && key
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'));
+        if !valid_key {
+            return Err(AppError::ValidationError(format!(
+                "Jira project key '{}' must be uppercase letters, digits or underscores",
+                key
+            )));
+        }
+
+        for (name, id) in [
+            ("project", self.testrail.project_id),
+            ("suite", self.testrail.suite_id),
+            ("section", self.testrail.section_id),
+        ] {
+            if id.is_some_and(|id| id <= 0) {
+                return Err(AppError::ValidationError(format!(
+                    "TestRail {} id must be positive",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Error naming the first setting `provider` still needs before a push
+    pub fn ensure_configured(&self, provider: TestManagementProvider) -> Result<()> {
+        let missing = match provider {
+            TestManagementProvider::Xray => [
+                ("Xray API URL", self.xray.api_url.trim().is_empty()),
+                ("Xray client id", self.xray.client_id.trim().is_empty()),
+                ("Jira project key", self.xray.project_key.trim().is_empty()),
+            ]
+            .into_iter()
+            .find(|(_, missing)| *missing),
+            TestManagementProvider::Testrail => [
+                ("TestRail URL", self.testrail.base_url.trim().is_empty()),
+                (
+                    "TestRail username",
+                    self.testrail.username.trim().is_empty(),
+                ),
+                ("TestRail project id", self.testrail.project_id.is_none()),
+                ("TestRail section id", self.testrail.section_id.is_none()),
+            ]
+            .into_iter()
+            .find(|(_, missing)| *missing),
+        };
+        match missing {
+            Some((name, _)) => Err(AppError::coded(
+                ErrorCode::ConfigMissing,
+                format!("{} is not set", name),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Outcome of running one test case
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QaTestResultStatus {
+    Passed,
+    Failed,
+    Blocked,
+    Skipped,
+}
+
+impl QaTestResultStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Passed => "passed",
+            Self::Failed => "failed",
+            Self::Blocked => "blocked",
+            Self::Skipped => "skipped",
+        }
+    }
+}
+
+/// Result entered for a test case, pushed as part of one run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaTestResultInput {
+    pub test_case_id: String,
+    pub status: QaTestResultStatus,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+/// One line of a session's sync log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaTestSyncEntry {
+    pub id: String,
+    pub session_id: String,
+    pub provider: TestManagementProvider,
+
+    /// `case` for a pushed test case, `result` for a pushed run result
+    pub action: String,
+    pub test_case_id: Option<String>,
+
+    /// Xray issue key or TestRail case id of the test case
+    pub external_id: Option<String>,
+
+    /// `created`, `skipped` (already pushed), `pushed` or `failed`
+    pub status: String,
+    pub message: Option<String>,
+    pub created_at: i64,
+}
+
+/// What one push did
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaTestSyncReport {
+    pub provider: TestManagementProvider,
+    pub created: usize,
+    pub skipped: usize,
+    pub failed: usize,
+
+    /// Xray test execution key or TestRail run id the results went to
+    pub run_key: Option<String>,
+    pub entries: Vec<QaTestSyncEntry>,
+}
+
+impl QaTestSyncReport {
+    pub fn new(
+        provider: TestManagementProvider,
+        run_key: Option<String>,
+        entries: Vec<QaTestSyncEntry>,
+    ) -> Self {
+        let count = |status: &str| entries.iter().filter(|e| e.status == status).count();
+        Self {
+            provider,
+            created: count("created") + count("pushed"),
+            skipped: count("skipped"),
+            failed: count("failed"),
+            run_key,
+            entries,
+        }
+    }
+}
+
+/// Which providers have their secret in the keychain
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaTestManagementCredentials {
+    pub xray: bool,
+    pub testrail: bool,
+}
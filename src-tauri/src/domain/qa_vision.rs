@@ -0,0 +1,67 @@
+// ============================================================
+// QA VISION
+// ============================================================
+// Whether exploration shows the checkpoint's screenshots to a
+// vision-capable model, which reports UI anomalies (error toasts,
+// broken layout) for the summary and the generated test cases.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::settings::SettingsSection;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QaVisionSettings {
+    pub enabled: bool,
+
+    /// Model to send the screenshots to, on the exploration's provider;
+    /// `None` uses the exploration's model
+    pub model: Option<String>,
+
+    /// Screenshots sent per checkpoint, spread over it
+    pub max_screenshots: usize,
+
+    /// Larger screenshot files are skipped
+    pub max_image_kb: u64,
+}
+
+impl Default for QaVisionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            model: None,
+            max_screenshots: 4,
+            max_image_kb: 4096,
+        }
+    }
+}
+
+impl QaVisionSettings {
+    pub fn validate(&self) -> Result<()> {
+        if !(1..=20).contains(&self.max_screenshots) {
+            return Err(AppError::ValidationError(
+                "Screenshots per checkpoint must be between 1 and 20".to_string(),
+            ));
+        }
+        if self.max_image_kb == 0 {
+            return Err(AppError::ValidationError(
+                "Maximum screenshot size must be above 0 KB".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// The configured vision model, or `fallback` when none is set
+    pub fn model_or<'a>(&'a self, fallback: &'a str) -> &'a str {
+        self.model
+            .as_deref()
+            .map(str::trim)
+            .filter(|model| !model.is_empty())
+            .unwrap_or(fallback)
+    }
+}
+
+impl SettingsSection for QaVisionSettings {
+    const KEY: &'static str = "qaVision";
+}
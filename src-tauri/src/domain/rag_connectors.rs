@@ -0,0 +1,211 @@
+// ============================================================
+// RAG SOURCE CONNECTORS
+// ============================================================
+// Import pages from Notion or Confluence into a collection. Each
+// page becomes one document; its place in the page tree is kept
+// in `rag_connector_pages`. A sync only re-imports pages edited
+// since the last one, and removes documents of deleted pages.
+// API tokens stay in the OS keychain.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::{AppError, Result};
+
+/// Shortest automatic sync interval
+pub const MIN_SYNC_INTERVAL_MINUTES: i64 = 15;
+
+/// Longest automatic sync interval (one week)
+pub const MAX_SYNC_INTERVAL_MINUTES: i64 = 7 * 24 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectorKind {
+    Notion,
+    Confluence,
+}
+
+impl ConnectorKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Notion => "notion",
+            Self::Confluence => "confluence",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "notion" => Some(Self::Notion),
+            "confluence" => Some(Self::Confluence),
+            _ => None,
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Notion => "Notion",
+            Self::Confluence => "Confluence",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RagConnector {
+    pub id: i64,
+    pub name: String,
+    pub kind: ConnectorKind,
+    pub collection_id: i64,
+    /// Confluence site, e.g. `https://example.atlassian.net/wiki`
+    pub base_url: Option<String>,
+    /// Confluence Cloud account email; without one the token is sent as a
+    /// bearer token (Data Center personal access tokens)
+    pub username: Option<String>,
+    /// Notion page whose subpages are imported (all shared pages when
+    /// unset), or the Confluence space key
+    pub scope: Option<String>,
+    /// 0 syncs only on demand
+    pub sync_interval_minutes: i64,
+    pub enabled: bool,
+    /// Unix seconds
+    pub last_synced_at: Option<i64>,
+    /// `ok`, `partial` or `failed`
+    pub last_sync_status: Option<String>,
+    pub last_sync_error: Option<String>,
+    pub created_at: i64,
+}
+
+impl RagConnector {
+    /// Whether an automatic sync is due at `now` (unix seconds)
+    pub fn sync_due(&self, now: i64) -> bool {
+        if !self.enabled || self.sync_interval_minutes <= 0 {
+            return false;
+        }
+        match self.last_synced_at {
+            Some(last) => now - last >= self.sync_interval_minutes * 60,
+            None => true,
+        }
+    }
+}
+
+/// Fields of a connector to create or update
+#[derive(Debug, Clone, Deserialize)]
+pub struct RagConnectorInput {
+    pub name: String,
+    pub kind: ConnectorKind,
+    pub collection_id: i64,
+    #[serde(default)]
+    pub base_url: Option<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub scope: Option<String>,
+    #[serde(default)]
+    pub sync_interval_minutes: i64,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Required when creating; an update keeps the stored token without one
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl RagConnectorInput {
+    /// Trim the text fields, dropping empty ones
+    pub fn normalized(mut self) -> Self {
+        let clean = |value: Option<String>| {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        self.name = self.name.trim().to_string();
+        self.base_url = clean(self.base_url).map(|url| url.trim_end_matches('/').to_string());
+        self.username = clean(self.username);
+        self.scope = clean(self.scope);
+        self.token = clean(self.token);
+        self
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(AppError::ValidationError(
+                "Connector name is required".to_string(),
+            ));
+        }
+        if self.sync_interval_minutes != 0
+            && !(MIN_SYNC_INTERVAL_MINUTES..=MAX_SYNC_INTERVAL_MINUTES)
+                .contains(&self.sync_interval_minutes)
+        {
+            return Err(AppError::ValidationError(format!(
+                "Sync interval must be 0 (manual) or between {} and {} minutes",
+                MIN_SYNC_INTERVAL_MINUTES, MAX_SYNC_INTERVAL_MINUTES
+            )));
+        }
+
+        if self.kind == ConnectorKind::Confluence {
+            let base_url = self.base_url.as_deref().ok_or_else(|| {
+                AppError::ValidationError("Confluence site URL is required".to_string())
+            })?;
+            let valid = url::Url::parse(base_url)
+                .map(|parsed| matches!(parsed.scheme(), "http" | "https") && parsed.has_host())
+                .unwrap_or(false);
+            if !valid {
+                return Err(AppError::ValidationError(format!(
+                    "Confluence site URL '{}' must be an http(s) URL",
+                    base_url
+                )));
+            }
+            if self.scope.is_none() {
+                return Err(AppError::ValidationError(
+                    "Confluence space key is required".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An imported page and where it sits in the source's page tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectorPage {
+    pub connector_id: i64,
+    pub external_id: String,
+    pub parent_external_id: Option<String>,
+    pub title: String,
+    /// Titles from the top of the tree down to the page, joined by ` / `
+    pub path: String,
+    pub url: Option<String>,
+    /// Unix seconds, as reported by the source
+    pub last_edited_at: i64,
+    /// `None` while the page has no imported document
+    pub doc_id: Option<i64>,
+    pub synced_at: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConnectorSyncReport {
+    pub connector_id: i64,
+    pub added: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub removed: usize,
+    pub failed: usize,
+    /// First errors of pages that failed to import
+    pub errors: Vec<String>,
+    pub started_at: i64,
+    pub finished_at: i64,
+}
+
+impl ConnectorSyncReport {
+    /// `ok`, `partial` or `failed`, as stored on the connector
+    pub fn status(&self) -> &'static str {
+        if self.failed == 0 {
+            "ok"
+        } else if self.added + self.updated + self.unchanged > 0 {
+            "partial"
+        } else {
+            "failed"
+        }
+    }
+}
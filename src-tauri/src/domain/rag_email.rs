@@ -0,0 +1,74 @@
+// ============================================================
+// RAG EMAIL ARCHIVES
+// ============================================================
+// `.eml` files and mbox archives are imported as one document
+// each, with one chunk per message (more for long messages).
+// Sender, date and thread of every message are kept next to
+// the chunks so searches can be narrowed to them.
+
+use serde::{Deserialize, Serialize};
+
+/// A message of an imported email document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailMessageRecord {
+    pub id: i64,
+    pub doc_id: i64,
+    /// 1-based position in the file; the `page_number` of its chunks
+    pub message_index: i64,
+    pub message_id: String,
+    /// Message id of the first message of the thread
+    pub thread_id: String,
+    pub in_reply_to: Option<String>,
+    pub subject: String,
+    /// Lower case
+    pub from_address: Option<String>,
+    pub from_name: Option<String>,
+    /// Recipients (To and Cc), comma-separated
+    pub recipients: String,
+    /// Unix seconds
+    pub sent_at: Option<i64>,
+    /// Attachment file names, comma-separated
+    pub attachments: String,
+    pub chunk_count: i64,
+}
+
+/// Fields of a message to store
+#[derive(Debug, Clone)]
+pub struct EmailMessageInput {
+    pub message_index: i64,
+    pub message_id: String,
+    pub thread_id: String,
+    pub in_reply_to: Option<String>,
+    pub subject: String,
+    pub from_address: Option<String>,
+    pub from_name: Option<String>,
+    pub recipients: String,
+    pub sent_at: Option<i64>,
+    pub attachments: String,
+    pub chunk_count: i64,
+}
+
+/// Narrows email messages; unset fields match everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EmailFilter {
+    /// Part of the sender's address or name, case-insensitive
+    #[serde(default)]
+    pub sender: Option<String>,
+    /// Sent at or after (unix seconds)
+    #[serde(default)]
+    pub sent_after: Option<i64>,
+    /// Sent at or before (unix seconds)
+    #[serde(default)]
+    pub sent_before: Option<i64>,
+    #[serde(default)]
+    pub thread_id: Option<String>,
+}
+
+impl EmailFilter {
+    pub fn is_empty(&self) -> bool {
+        self.sender.as_deref().is_none_or(|s| s.trim().is_empty())
+            && self.sent_after.is_none()
+            && self.sent_before.is_none()
+            && self.thread_id.is_none()
+    }
+}
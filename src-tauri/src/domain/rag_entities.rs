@@ -1,8 +1,9 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 /// Collection kind for routing between file-based and DB-based RAG
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
 pub enum CollectionKind {
     Files,
     Db,
@@ -41,7 +42,7 @@ impl From<&str> for CollectionKind {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct RagCollection {
     pub id: i64,
     pub name: String,
@@ -51,6 +52,50 @@ pub struct RagCollection {
     pub created_at: DateTime<Utc>,
 }
 
+/// How far the import of a document got. Imports that fail, or are cut
+/// off by the app closing, keep the stage they reached so they can be
+/// resumed from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestionState {
+    /// Waiting to be (re)imported
+    Pending,
+    /// Reading the file, and storing its rows for spreadsheets
+    Extracting,
+    /// Splitting the text and storing the chunks
+    Chunking,
+    /// Chunks are stored; their embeddings are being generated
+    Embedding,
+    Complete,
+    Failed,
+}
+
+impl IngestionState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IngestionState::Pending => "pending",
+            IngestionState::Extracting => "extracting",
+            IngestionState::Chunking => "chunking",
+            IngestionState::Embedding => "embedding",
+            IngestionState::Complete => "complete",
+            IngestionState::Failed => "failed",
+        }
+    }
+
+    /// Unknown values read as complete, like documents imported before
+    /// states were tracked
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "pending" => IngestionState::Pending,
+            "extracting" => IngestionState::Extracting,
+            "chunking" => IngestionState::Chunking,
+            "embedding" => IngestionState::Embedding,
+            "failed" => IngestionState::Failed,
+            _ => IngestionState::Complete,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RagDocument {
     pub id: i64,
@@ -65,6 +110,11 @@ pub struct RagDocument {
     pub chunk_count: i64,           // Total number of chunks
     pub warning_count: i64,         // Number of quality warnings
     pub created_at: DateTime<Utc>,
+    /// `Failed` when the import stopped; `ingestion_failed_at` then holds
+    /// the stage it stopped in
+    pub ingestion_state: IngestionState,
+    pub ingestion_failed_at: Option<IngestionState>,
+    pub ingestion_error: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -115,6 +165,8 @@ pub struct RagDocumentChunkInput {
     pub page_offset: Option<i64>,
     pub chunk_index: i64,
     pub token_count: Option<i64>,
+    /// `chunk_index` of the first chunk of the parent section
+    pub parent_index: Option<i64>,
 }
 
 #[derive(Debug, Deserialize)]
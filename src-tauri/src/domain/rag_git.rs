@@ -0,0 +1,254 @@
+// ============================================================
+// RAG GIT REPOSITORIES
+// ============================================================
+// Import the source files of a git repository into a collection.
+// Each file becomes one document, chunked along function and
+// class boundaries. A sync reads the tip of the tracked branch
+// and only re-imports files whose content changed since the
+// commit imported last.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::rag_connectors::{MAX_SYNC_INTERVAL_MINUTES, MIN_SYNC_INTERVAL_MINUTES};
+
+/// Prefixes of remote repository URLs; anything else is a local path
+const REMOTE_PREFIXES: &[&str] = &["https://", "http://", "ssh://", "git://", "git@"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitRepoSource {
+    pub id: i64,
+    pub name: String,
+    pub collection_id: i64,
+    /// Remote URL (cloned into app data) or path of a local repository
+    pub url: String,
+    /// Branch to import; the remote's default branch or the local HEAD when unset
+    pub branch: Option<String>,
+    /// Only files below this directory are imported
+    pub subdirectory: Option<String>,
+    /// 0 syncs only on demand
+    pub sync_interval_minutes: i64,
+    pub enabled: bool,
+    /// Commit of the last successful sync
+    pub last_commit: Option<String>,
+    /// Unix seconds
+    pub last_synced_at: Option<i64>,
+    /// `ok`, `partial` or `failed`
+    pub last_sync_status: Option<String>,
+    pub last_sync_error: Option<String>,
+    pub created_at: i64,
+}
+
+impl GitRepoSource {
+    pub fn is_remote(&self) -> bool {
+        is_remote_url(&self.url)
+    }
+
+    /// Whether an automatic sync is due at `now` (unix seconds)
+    pub fn sync_due(&self, now: i64) -> bool {
+        if !self.enabled || self.sync_interval_minutes <= 0 {
+            return false;
+        }
+        match self.last_synced_at {
+            Some(last) => now - last >= self.sync_interval_minutes * 60,
+            None => true,
+        }
+    }
+
+    /// Whether a repository path (relative, `/`-separated) is in scope
+    pub fn includes(&self, path: &str) -> bool {
+        match self.subdirectory.as_deref() {
+            Some(dir) => path
+                .strip_prefix(dir)
+                .is_some_and(|rest| rest.starts_with('/')),
+            None => true,
+        }
+    }
+}
+
+pub fn is_remote_url(url: &str) -> bool {
+    REMOTE_PREFIXES.iter().any(|prefix| url.starts_with(prefix))
+}
+
+/// Fields of a git repository source to create or update
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitRepoInput {
+    pub name: String,
+    pub collection_id: i64,
+    pub url: String,
+    #[serde(default)]
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub subdirectory: Option<String>,
+    #[serde(default)]
+    pub sync_interval_minutes: i64,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl GitRepoInput {
+    /// Trim the text fields, dropping empty ones; the subdirectory loses
+    /// its leading and trailing slashes
+    pub fn normalized(mut self) -> Self {
+        let clean = |value: Option<String>| {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        self.name = self.name.trim().to_string();
+        self.url = self.url.trim().to_string();
+        self.branch = clean(self.branch);
+        self.subdirectory = clean(self.subdirectory)
+            .map(|dir| dir.replace('\\', "/").trim_matches('/').to_string())
+            .filter(|dir| !dir.is_empty());
+        self
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(AppError::ValidationError(
+                "Repository name is required".to_string(),
+            ));
+        }
+        if self.url.is_empty() {
+            return Err(AppError::ValidationError(
+                "Repository URL or path is required".to_string(),
+            ));
+        }
+        if !is_remote_url(&self.url) && !std::path::Path::new(&self.url).is_absolute() {
+            return Err(AppError::ValidationError(format!(
+                "'{}' is neither a remote URL nor an absolute path",
+                self.url
+            )));
+        }
+        if let Some(branch) = &self.branch {
+            if branch.starts_with('-') || branch.contains(char::is_whitespace) {
+                return Err(AppError::ValidationError(format!(
+                    "'{}' is not a valid branch name",
+                    branch
+                )));
+            }
+        }
+        if let Some(dir) = &self.subdirectory {
+            if dir.split('/').any(|part| part == "..") {
+                return Err(AppError::ValidationError(
+                    "Subdirectory must stay inside the repository".to_string(),
+                ));
+            }
+        }
+        if self.sync_interval_minutes != 0
+            && !(MIN_SYNC_INTERVAL_MINUTES..=MAX_SYNC_INTERVAL_MINUTES)
+                .contains(&self.sync_interval_minutes)
+        {
+            return Err(AppError::ValidationError(format!(
+                "Sync interval must be 0 (manual) or between {} and {} minutes",
+                MIN_SYNC_INTERVAL_MINUTES, MAX_SYNC_INTERVAL_MINUTES
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// An imported file and the commit its content was read from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitRepoFile {
+    pub repo_id: i64,
+    /// Path in the repository, `/`-separated
+    pub path: String,
+    /// Language the file was chunked as, e.g. `rust` or `markdown`
+    pub language: String,
+    /// Git blob id of the imported content
+    pub blob_sha: String,
+    pub commit_sha: String,
+    /// `None` while the file has no imported document
+    pub doc_id: Option<i64>,
+    pub chunk_count: i64,
+    pub synced_at: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GitSyncReport {
+    pub repo_id: i64,
+    pub commit_sha: String,
+    pub previous_commit: Option<String>,
+    pub added: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub removed: usize,
+    /// Files left out: binary, too large, vendored or of an unknown type
+    pub skipped: usize,
+    pub failed: usize,
+    /// First errors of files that failed to import
+    pub errors: Vec<String>,
+    pub started_at: i64,
+    pub finished_at: i64,
+}
+
+impl GitSyncReport {
+    /// `ok`, `partial` or `failed`, as stored on the repository source
+    pub fn status(&self) -> &'static str {
+        if self.failed == 0 {
+            "ok"
+        } else if self.added + self.updated + self.unchanged > 0 {
+            "partial"
+        } else {
+            "failed"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(url: &str) -> GitRepoInput {
+        GitRepoInput {
+            name: " api ".to_string(),
+            collection_id: 1,
+            url: url.to_string(),
+            branch: Some(" ".to_string()),
+            subdirectory: Some("/src/server/".to_string()),
+            sync_interval_minutes: 0,
+            enabled: true,
+        }
+        .normalized()
+    }
+
+    #[test]
+    fn normalized_input_validates_urls_and_paths() {
+        let remote = input("https://github.com/acme/api.git");
+        assert_eq!(remote.name, "api");
+        assert_eq!(remote.branch, None);
+        assert_eq!(remote.subdirectory.as_deref(), Some("src/server"));
+        assert!(remote.validate().is_ok());
+        assert!(input("git@github.com:acme/api.git").validate().is_ok());
+        assert!(input("relative/checkout").validate().is_err());
+    }
+
+    #[test]
+    fn subdirectory_scopes_paths() {
+        let source = GitRepoSource {
+            id: 1,
+            name: "api".to_string(),
+            collection_id: 1,
+            url: "/repos/api".to_string(),
+            branch: None,
+            subdirectory: Some("src".to_string()),
+            sync_interval_minutes: 0,
+            enabled: true,
+            last_commit: None,
+            last_synced_at: None,
+            last_sync_status: None,
+            last_sync_error: None,
+            created_at: 0,
+        };
+        assert!(!source.is_remote());
+        assert!(source.includes("src/main.rs"));
+        assert!(!source.includes("srcgen/main.rs"));
+        assert!(!source.includes("README.md"));
+    }
+}
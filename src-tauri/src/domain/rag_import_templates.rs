@@ -0,0 +1,312 @@
+// ============================================================
+// RAG IMPORT TEMPLATES
+// ============================================================
+// Recurring CSV/Excel exports arrive with the same layout every
+// time. A template records how to read one: the rows above the
+// header and below the data to skip, the structured field each
+// column fills and the type its values are coerced to. A file
+// matches a template when its header row, after the skipped
+// rows, has the template's columns in the same order; its rows
+// are then stored with the template instead of the header
+// heuristics.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::csv::ColumnType;
+use crate::domain::error::{AppError, Result};
+
+/// Most rows a template can skip above the header or below the data
+pub const MAX_SKIPPED_ROWS: i64 = 1_000;
+
+/// Structured row field a column can fill
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateField {
+    Category,
+    Source,
+    Title,
+    CreatedAt,
+}
+
+/// How one column of the file is read
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportColumnMapping {
+    /// Header of the column, as in the file
+    pub column: String,
+    #[serde(default)]
+    pub field: Option<TemplateField>,
+    /// Type the values are coerced to; values stay text when unset
+    #[serde(default)]
+    pub coerce: Option<ColumnType>,
+    /// chrono format of the dates of a date column; common formats are
+    /// tried when unset
+    #[serde(default)]
+    pub date_format: Option<String>,
+    /// Left out of the stored rows
+    #[serde(default)]
+    pub skip: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportTemplate {
+    pub id: i64,
+    pub name: String,
+    /// Only files imported into this collection match; any collection when unset
+    pub collection_id: Option<i64>,
+    /// Normalized headers of `columns`, which a file's header row must have
+    pub signature: Vec<String>,
+    /// Rows above the header (report titles, export notes); blank rows
+    /// don't count
+    pub skip_rows: i64,
+    /// Rows below the data (totals, footers)
+    pub skip_footer_rows: i64,
+    /// One per column of the file, in file order
+    pub columns: Vec<ImportColumnMapping>,
+    pub applied_count: i64,
+    /// Unix seconds
+    pub last_applied_at: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl ImportTemplate {
+    /// The header row and data rows of `rows`, without blank rows, when
+    /// they have this template's layout
+    pub fn split_rows<'a>(&self, rows: &'a [Vec<String>]) -> Option<TemplateRows<'a>> {
+        let skip = self.skip_rows.max(0) as usize;
+        let header = rows.get(skip)?;
+        if header_signature(header) != self.signature {
+            return None;
+        }
+        let data = &rows[skip + 1..];
+        let footer = (self.skip_footer_rows.max(0) as usize).min(data.len());
+        Some(TemplateRows {
+            header,
+            data: &data[..data.len() - footer],
+        })
+    }
+}
+
+/// A file's rows as laid out by a template
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TemplateRows<'a> {
+    pub header: &'a [String],
+    pub data: &'a [Vec<String>],
+}
+
+/// Fields of an import template to create or update
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImportTemplateInput {
+    pub name: String,
+    #[serde(default)]
+    pub collection_id: Option<i64>,
+    #[serde(default)]
+    pub skip_rows: i64,
+    #[serde(default)]
+    pub skip_footer_rows: i64,
+    pub columns: Vec<ImportColumnMapping>,
+}
+
+impl ImportTemplateInput {
+    /// Trim the name, headers and date formats, dropping empty formats
+    pub fn normalized(mut self) -> Self {
+        self.name = self.name.trim().to_string();
+        for column in &mut self.columns {
+            column.column = column.column.trim().to_string();
+            column.date_format = column
+                .date_format
+                .take()
+                .map(|format| format.trim().to_string())
+                .filter(|format| !format.is_empty());
+        }
+        self
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(AppError::ValidationError(
+                "Template name is required".to_string(),
+            ));
+        }
+        if self.columns.is_empty() || self.columns.iter().all(|c| c.column.is_empty()) {
+            return Err(AppError::ValidationError(
+                "A template needs the columns of the file's header".to_string(),
+            ));
+        }
+        for (what, rows) in [
+            ("above the header", self.skip_rows),
+            ("below the data", self.skip_footer_rows),
+        ] {
+            if !(0..=MAX_SKIPPED_ROWS).contains(&rows) {
+                return Err(AppError::ValidationError(format!(
+                    "Rows to skip {} must be between 0 and {}",
+                    what, MAX_SKIPPED_ROWS
+                )));
+            }
+        }
+        for (index, column) in self.columns.iter().enumerate() {
+            if let Some(field) = column.field {
+                if self.columns[..index].iter().any(|c| c.field == Some(field)) {
+                    return Err(AppError::ValidationError(format!(
+                        "More than one column is mapped to {:?}",
+                        field
+                    )));
+                }
+                if column.skip {
+                    return Err(AppError::ValidationError(format!(
+                        "Column '{}' is skipped but mapped to {:?}",
+                        column.column, field
+                    )));
+                }
+            }
+            if matches!(
+                column.coerce,
+                Some(ColumnType::Categorical | ColumnType::Empty)
+            ) {
+                return Err(AppError::ValidationError(format!(
+                    "Column '{}' can only be coerced to int, float, date, bool or text",
+                    column.column
+                )));
+            }
+            if column.date_format.is_some() && column.coerce != Some(ColumnType::Date) {
+                return Err(AppError::ValidationError(format!(
+                    "Column '{}' has a date format but is not a date column",
+                    column.column
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Normalized headers of the columns, matched against files' header rows
+    pub fn signature(&self) -> Vec<String> {
+        let headers: Vec<String> = self.columns.iter().map(|c| c.column.clone()).collect();
+        header_signature(&headers)
+    }
+}
+
+/// Headers lowercased with runs of spaces, dashes and underscores made one
+/// underscore; empty trailing cells are dropped
+pub fn header_signature(header: &[String]) -> Vec<String> {
+    let mut signature: Vec<String> = header
+        .iter()
+        .map(|cell| {
+            cell.trim()
+                .trim_matches('"')
+                .to_lowercase()
+                .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join("_")
+        })
+        .collect();
+    while signature.last().is_some_and(|cell| cell.is_empty()) {
+        signature.pop();
+    }
+    signature
+}
+
+/// The template `rows` of a file imported into `collection_id` are read
+/// with: one of that collection before one for any collection, then the
+/// most recently updated
+pub fn find_template<'a>(
+    templates: &'a [ImportTemplate],
+    collection_id: Option<i64>,
+    rows: &[Vec<String>],
+) -> Option<&'a ImportTemplate> {
+    templates
+        .iter()
+        .filter(|template| {
+            template.collection_id.is_none() || template.collection_id == collection_id
+        })
+        .filter(|template| template.split_rows(rows).is_some())
+        .max_by_key(|template| {
+            (
+                template.collection_id.is_some(),
+                template.updated_at,
+                template.id,
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(cells: &[&str]) -> Vec<String> {
+        cells.iter().map(|cell| cell.to_string()).collect()
+    }
+
+    fn template(id: i64, collection_id: Option<i64>, skip_rows: i64) -> ImportTemplate {
+        ImportTemplate {
+            id,
+            name: format!("Template {}", id),
+            collection_id,
+            signature: header_signature(&row(&["Order ID", "Region", "Order Date"])),
+            skip_rows,
+            skip_footer_rows: 1,
+            columns: Vec::new(),
+            applied_count: 0,
+            last_applied_at: None,
+            created_at: 0,
+            updated_at: id,
+        }
+    }
+
+    #[test]
+    fn files_match_on_the_header_after_the_skipped_rows() {
+        let rows = vec![
+            row(&["Weekly sales export", "", ""]),
+            row(&["order id", "REGION", "order-date", ""]),
+            row(&["1", "North", "2026-01-05"]),
+            row(&["2", "South", "2026-01-06"]),
+            row(&["Total", "", ""]),
+        ];
+        let layout = template(1, None, 1).split_rows(&rows).unwrap();
+        assert_eq!(layout.header, rows[1].as_slice());
+        assert_eq!(layout.data, &rows[2..4]);
+        assert!(template(1, None, 0).split_rows(&rows).is_none());
+
+        let templates = vec![template(1, None, 1), template(2, Some(7), 1)];
+        assert_eq!(find_template(&templates, Some(7), &rows).unwrap().id, 2);
+        assert_eq!(find_template(&templates, Some(8), &rows).unwrap().id, 1);
+        assert!(find_template(&templates, None, &rows[1..]).is_none());
+    }
+
+    #[test]
+    fn inputs_need_one_column_per_field_and_matching_coercions() {
+        let column = |name: &str, field, coerce, date_format: Option<&str>| ImportColumnMapping {
+            column: name.to_string(),
+            field,
+            coerce,
+            date_format: date_format.map(str::to_string),
+            skip: false,
+        };
+        let mut input = ImportTemplateInput {
+            name: " Weekly sales ".to_string(),
+            collection_id: None,
+            skip_rows: 1,
+            skip_footer_rows: 0,
+            columns: vec![
+                column("Region", Some(TemplateField::Category), None, None),
+                column(
+                    "Order Date",
+                    Some(TemplateField::CreatedAt),
+                    Some(ColumnType::Date),
+                    Some("%d.%m.%Y"),
+                ),
+                column("Amount", None, Some(ColumnType::Float), None),
+            ],
+        }
+        .normalized();
+        assert!(input.validate().is_ok());
+        assert_eq!(input.name, "Weekly sales");
+        assert_eq!(input.signature(), ["region", "order_date", "amount"]);
+
+        input.columns[2].field = Some(TemplateField::Category);
+        assert!(input.validate().is_err());
+        input.columns[2].field = None;
+        input.columns[2].date_format = Some("%Y".to_string());
+        assert!(input.validate().is_err());
+    }
+}
@@ -0,0 +1,202 @@
+// ============================================================
+// RAG CHAT TOOLS
+// ============================================================
+// Tools the RAG chat model may call while answering. The only tool is
+// the QA API request executor, limited to whitelisted origins; every
+// request it sends is recorded as a QA API call in the configured
+// session.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::settings::{normalize_origin, SettingsSection};
+
+/// Name the model uses to request an API call
+pub const QA_API_TOOL: &str = "qa_api_request";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RagToolSettings {
+    pub enabled: bool,
+
+    /// Origins the model may call, e.g. `https://api.example.com`
+    pub allowed_origins: Vec<String>,
+
+    /// QA session the calls are recorded in
+    pub qa_session_id: Option<String>,
+
+    /// API calls allowed while producing one answer
+    pub max_calls_per_answer: u32,
+
+    /// Response bodies are cut to this many characters before the model sees them
+    pub max_response_chars: usize,
+}
+
+impl Default for RagToolSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            allowed_origins: Vec::new(),
+            qa_session_id: None,
+            max_calls_per_answer: 3,
+            max_response_chars: 8_000,
+        }
+    }
+}
+
+impl RagToolSettings {
+    pub fn validate(&self) -> Result<()> {
+        for origin in &self.allowed_origins {
+            if normalize_origin(origin).is_none() {
+                return Err(AppError::ValidationError(format!(
+                    "Invalid origin '{}': expected scheme and host only, such as https://api.example.com",
+                    origin
+                )));
+            }
+        }
+        if self.enabled && self.session_id().is_none() {
+            return Err(AppError::ValidationError(
+                "Choose a QA session to record the chat's API calls in".to_string(),
+            ));
+        }
+        if !(1..=10).contains(&self.max_calls_per_answer) {
+            return Err(AppError::ValidationError(
+                "API calls per answer must be between 1 and 10".to_string(),
+            ));
+        }
+        if self.max_response_chars == 0 {
+            return Err(AppError::ValidationError(
+                "Response size limit must be greater than zero".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn session_id(&self) -> Option<&str> {
+        self.qa_session_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+    }
+
+    /// Whether `url` is an http(s) URL on one of the whitelisted origins
+    pub fn allows(&self, url: &str) -> bool {
+        let Ok(parsed) = url::Url::parse(url.trim()) else {
+            return false;
+        };
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return false;
+        }
+        let origin = parsed.origin().ascii_serialization();
+        self.allowed_origins
+            .iter()
+            .filter_map(|allowed| normalize_origin(allowed))
+            .any(|allowed| allowed == origin)
+    }
+}
+
+impl SettingsSection for RagToolSettings {
+    const KEY: &'static str = "ragTools";
+}
+
+/// API request the model asked for
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RagApiToolCall {
+    #[serde(default = "default_method")]
+    pub method: String,
+    pub url: String,
+    #[serde(default)]
+    pub headers: std::collections::BTreeMap<String, String>,
+    /// JSON request body
+    #[serde(default)]
+    pub body: Option<serde_json::Value>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+/// The tool call in a model reply, when the whole reply is one. Accepts a
+/// bare JSON object or one inside a code fence:
+/// `{"tool": "qa_api_request", "method": "GET", "url": "..."}`
+pub fn parse_tool_call(reply: &str) -> Option<RagApiToolCall> {
+    let trimmed = reply.trim();
+    let unfenced = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.trim_end().strip_suffix("```"))
+        .unwrap_or(trimmed)
+        .trim();
+    if !unfenced.starts_with('{') || !unfenced.ends_with('}') {
+        return None;
+    }
+    let value: serde_json::Value = serde_json::from_str(unfenced).ok()?;
+    if value.get("tool").and_then(|tool| tool.as_str()) != Some(QA_API_TOOL) {
+        return None;
+    }
+    serde_json::from_value(value).ok()
+}
+
+/// One API call made while answering, as shown next to the answer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RagToolCallRecord {
+    pub method: String,
+    pub url: String,
+    pub status: Option<u16>,
+    pub duration_ms: Option<i64>,
+
+    /// Why the call was refused or failed
+    pub error: Option<String>,
+
+    /// The QA API call row that recorded it
+    pub api_call_id: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fenced_and_bare_tool_calls() {
+        let call = parse_tool_call(
+            "```json\n{\"tool\": \"qa_api_request\", \"url\": \"https://api.test/items\"}\n```",
+        )
+        .unwrap();
+        assert_eq!(call.method, "GET");
+        assert_eq!(call.url, "https://api.test/items");
+
+        let call = parse_tool_call(
+            r#"{"tool":"qa_api_request","method":"POST","url":"https://api.test/items","body":{"name":"a"}}"#,
+        )
+        .unwrap();
+        assert_eq!(call.method, "POST");
+        assert_eq!(call.body, Some(serde_json::json!({"name": "a"})));
+
+        assert!(parse_tool_call("The items are listed below.").is_none());
+        assert!(parse_tool_call(r#"{"tool":"other","url":"https://api.test"}"#).is_none());
+        assert!(parse_tool_call(r#"Sure: {"tool":"qa_api_request","url":"x"}"#).is_none());
+    }
+
+    #[test]
+    fn only_whitelisted_origins_are_allowed() {
+        let settings = RagToolSettings {
+            allowed_origins: vec!["https://api.test".to_string()],
+            ..Default::default()
+        };
+        assert!(settings.allows("https://api.test/items?page=2"));
+        assert!(!settings.allows("http://api.test/items"));
+        assert!(!settings.allows("https://api.test.evil.com/items"));
+        assert!(!settings.allows("file:///etc/passwd"));
+    }
+
+    #[test]
+    fn enabling_requires_a_session() {
+        let mut settings = RagToolSettings {
+            enabled: true,
+            ..Default::default()
+        };
+        assert!(settings.validate().is_err());
+        settings.qa_session_id = Some("session-1".to_string());
+        assert!(settings.validate().is_ok());
+    }
+}
@@ -0,0 +1,538 @@
+// ============================================================
+// APPLICATION SETTINGS
+// ============================================================
+// Versioned settings document shared by every subsystem. Each
+// subsystem owns a named section and reads/writes it through a
+// typed `SettingsSection` implementation.
+
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use utoipa::ToSchema;
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::llm_config::{LLMConfig, LLMProvider};
+
+/// Current layout version of the settings document
+pub const SETTINGS_SCHEMA_VERSION: u32 = 1;
+
+/// A typed slice of the settings document stored under `KEY`
+pub trait SettingsSection: Serialize + DeserializeOwned + Default {
+    /// Section name in the settings document (camelCase)
+    const KEY: &'static str;
+}
+
+/// Persisted settings: a schema version plus one JSON value per section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsDocument {
+    pub schema_version: u32,
+
+    /// RFC 3339 timestamp of the last write
+    #[serde(default)]
+    pub updated_at: Option<String>,
+
+    #[serde(default)]
+    pub sections: BTreeMap<String, JsonValue>,
+}
+
+impl Default for SettingsDocument {
+    fn default() -> Self {
+        Self {
+            schema_version: SETTINGS_SCHEMA_VERSION,
+            updated_at: None,
+            sections: BTreeMap::new(),
+        }
+    }
+}
+
+/// Payload of the `settings-changed` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsChange {
+    pub section: String,
+    pub value: JsonValue,
+}
+
+/// LLM and embedding model selection. API keys are never persisted here.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelSettings {
+    #[serde(default)]
+    pub llm: Option<LLMConfig>,
+
+    #[serde(default)]
+    pub embedding: Option<LLMConfig>,
+
+    /// Models used instead of `llm` by individual features
+    #[serde(default)]
+    pub features: BTreeMap<LlmFeature, ModelOverride>,
+}
+
+impl ModelSettings {
+    /// Copy of `config` that is safe to write to disk
+    pub fn without_api_key(config: &LLMConfig) -> LLMConfig {
+        LLMConfig {
+            api_key: None,
+            ..config.clone()
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        for (feature, model) in &self.features {
+            if model.model.trim().is_empty() {
+                return Err(AppError::ValidationError(format!(
+                    "Pick a model for {} or use the default one",
+                    feature.label()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// `config` switched to the model configured for `feature`, keeping its
+    /// token limit and temperature. `None` when the feature has no override.
+    /// The API key is dropped unless the provider stays the same.
+    pub fn config_for(&self, feature: LlmFeature, config: &LLMConfig) -> Option<LLMConfig> {
+        let model = self.features.get(&feature)?;
+        let same_provider = model.provider == config.provider;
+        Some(LLMConfig {
+            provider: model.provider.clone(),
+            base_url: model.base_url.clone(),
+            model: model.model.clone(),
+            api_key: config.api_key.clone().filter(|_| same_provider),
+            max_tokens: config.max_tokens,
+            temperature: config.temperature,
+        })
+    }
+}
+
+/// Feature that can run on its own model instead of the default one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum LlmFeature {
+    Translate,
+    Enhance,
+    /// Exploration, checkpoint summaries and test case generation
+    QaAi,
+    /// Answers and SQL generation in the RAG chat and the quick action "Ask"
+    RagChat,
+    /// Teacher calls of model distillation
+    SoftLabels,
+    Typegen,
+}
+
+impl LlmFeature {
+    pub fn label(self) -> &'static str {
+        match self {
+            LlmFeature::Translate => "translation",
+            LlmFeature::Enhance => "prompt enhancement",
+            LlmFeature::QaAi => "QA AI",
+            LlmFeature::RagChat => "RAG chat",
+            LlmFeature::SoftLabels => "soft labels",
+            LlmFeature::Typegen => "type generation",
+        }
+    }
+}
+
+/// Provider and model of one feature; its key comes from the keychain entry
+/// of the provider
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelOverride {
+    pub provider: LLMProvider,
+    pub base_url: String,
+    pub model: String,
+}
+
+impl SettingsSection for ModelSettings {
+    const KEY: &'static str = "models";
+}
+
+/// Preferred translation languages
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageSettings {
+    pub source: String,
+    pub target: String,
+}
+
+impl Default for LanguageSettings {
+    fn default() -> Self {
+        Self {
+            source: "Auto Detect".to_string(),
+            target: "English".to_string(),
+        }
+    }
+}
+
+impl SettingsSection for LanguageSettings {
+    const KEY: &'static str = "languages";
+}
+
+/// Language the AI features answer in: enhance, RAG chat answers and QA
+/// generation. A request can still name its own language.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OutputLanguageSettings {
+    /// `None` leaves the language to the model, usually the input's
+    pub language: Option<String>,
+
+    /// Detect the language of each answer and ask again when it is another one
+    pub verify: bool,
+
+    /// Extra attempts after an answer in the wrong language
+    pub max_retries: u32,
+}
+
+impl Default for OutputLanguageSettings {
+    fn default() -> Self {
+        Self {
+            language: None,
+            verify: true,
+            max_retries: 1,
+        }
+    }
+}
+
+impl OutputLanguageSettings {
+    pub fn validate(&self) -> Result<()> {
+        if self.max_retries > 3 {
+            return Err(AppError::ValidationError(
+                "Wrong-language retries must be between 0 and 3".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl SettingsSection for OutputLanguageSettings {
+    const KEY: &'static str = "outputLanguage";
+}
+
+/// Global keyboard shortcuts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutSettings {
+    pub enabled: bool,
+    pub translate: String,
+    pub enhance: String,
+    pub popup: String,
+    pub terminal: String,
+}
+
+impl Default for ShortcutSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            translate: "Ctrl + Alt + T".to_string(),
+            enhance: "Ctrl + Alt + E".to_string(),
+            popup: "Ctrl + Alt + P".to_string(),
+            terminal: "Ctrl + Alt + R".to_string(),
+        }
+    }
+}
+
+impl SettingsSection for ShortcutSettings {
+    const KEY: &'static str = "shortcuts";
+}
+
+/// Popup shortcut window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QuickActionSettings {
+    /// Collection the "Ask" action answers from when the popup has none picked
+    pub rag_collection_id: Option<i64>,
+
+    /// Sources retrieved for each answer
+    pub rag_top_k: usize,
+}
+
+impl Default for QuickActionSettings {
+    fn default() -> Self {
+        Self {
+            rag_collection_id: None,
+            rag_top_k: 5,
+        }
+    }
+}
+
+impl QuickActionSettings {
+    pub fn validate(&self) -> Result<()> {
+        if !(1..=20).contains(&self.rag_top_k) {
+            return Err(AppError::ValidationError(
+                "Sources per answer must be between 1 and 20".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl SettingsSection for QuickActionSettings {
+    const KEY: &'static str = "quickAction";
+}
+
+/// Launch at login and what runs before the main window is opened
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StartupSettings {
+    /// Start with the OS session, minimized to the tray
+    pub launch_at_login: bool,
+
+    /// Start the mock server on every launch, including background ones
+    pub start_mock_server: bool,
+}
+
+impl SettingsSection for StartupSettings {
+    const KEY: &'static str = "startup";
+}
+
+/// Port the local HTTP API listens on unless configured otherwise
+pub const DEFAULT_HTTP_PORT: u16 = 3001;
+
+/// Local HTTP API listener and access control
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpServerSettings {
+    pub bind_address: String,
+    pub port: u16,
+
+    /// Pick a free port when `port` is taken and remember the one chosen
+    pub auto_port: bool,
+
+    /// Require `Authorization: Bearer <token>`; the token is kept in the OS keychain
+    pub require_token: bool,
+
+    /// Token buckets per endpoint; requests to other paths are not limited
+    #[serde(default = "default_http_rate_limits")]
+    pub rate_limits: Vec<HttpRateLimit>,
+}
+
+impl Default for HttpServerSettings {
+    fn default() -> Self {
+        Self {
+            bind_address: "127.0.0.1".to_string(),
+            port: DEFAULT_HTTP_PORT,
+            auto_port: true,
+            require_token: false,
+            rate_limits: default_http_rate_limits(),
+        }
+    }
+}
+
+impl HttpServerSettings {
+    pub fn validate(&self) -> Result<IpAddr> {
+        let address: IpAddr = self.bind_address.trim().parse().map_err(|_| {
+            AppError::ValidationError(format!(
+                "Invalid bind address '{}': expected an IP address such as 127.0.0.1",
+                self.bind_address
+            ))
+        })?;
+        if self.port == 0 {
+            return Err(AppError::ValidationError(
+                "HTTP port must be between 1 and 65535".to_string(),
+            ));
+        }
+        // Anything reachable from other machines must not be an open API
+        if !address.is_loopback() && !self.require_token {
+            return Err(AppError::ValidationError(format!(
+                "Binding to {} exposes the API to the network; enable the access token first",
+                address
+            )));
+        }
+        for (index, limit) in self.rate_limits.iter().enumerate() {
+            limit.validate()?;
+            if self.rate_limits[..index]
+                .iter()
+                .any(|other| other.path == limit.path)
+            {
+                return Err(AppError::ValidationError(format!(
+                    "Rate limit for {} is configured twice",
+                    limit.path
+                )));
+            }
+        }
+        Ok(address)
+    }
+}
+
+impl SettingsSection for HttpServerSettings {
+    const KEY: &'static str = "httpServer";
+}
+
+/// Token bucket shared by every client calling one endpoint of the local API
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpRateLimit {
+    /// e.g. `/api/translate`; also covers the paths below it unless they
+    /// have a limit of their own
+    pub path: String,
+
+    /// Rate the bucket refills at
+    pub requests_per_minute: u32,
+
+    /// Requests let through at once after a quiet period
+    pub burst: u32,
+}
+
+impl HttpRateLimit {
+    fn new(path: &str, requests_per_minute: u32, burst: u32) -> Self {
+        Self {
+            path: path.to_string(),
+            requests_per_minute,
+            burst,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if !self.path.starts_with('/') || self.path.len() < 2 || self.path.ends_with('/') {
+            return Err(AppError::ValidationError(format!(
+                "Rate limit path '{}' must start with / and name an endpoint",
+                self.path
+            )));
+        }
+        if !(1..=100_000).contains(&self.requests_per_minute) {
+            return Err(AppError::ValidationError(format!(
+                "Rate limit for {} must allow 1 to 100000 requests per minute",
+                self.path
+            )));
+        }
+        if !(1..=10_000).contains(&self.burst) {
+            return Err(AppError::ValidationError(format!(
+                "Rate limit burst for {} must be between 1 and 10000",
+                self.path
+            )));
+        }
+        Ok(())
+    }
+
+    /// Whether `path` is this endpoint or below it
+    pub fn covers(&self, path: &str) -> bool {
+        path.strip_prefix(self.path.as_str())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    }
+}
+
+/// The endpoints that call an LLM provider
+fn default_http_rate_limits() -> Vec<HttpRateLimit> {
+    vec![
+        HttpRateLimit::new("/api/translate", 30, 10),
+        HttpRateLimit::new("/api/enhance", 30, 10),
+        HttpRateLimit::new("/api/typegen", 30, 10),
+        HttpRateLimit::new("/api/typegen/batch", 10, 3),
+        HttpRateLimit::new("/api/rag/chat", 30, 10),
+    ]
+}
+
+/// Where QA session files and training artifacts live when not under the
+/// app data dir. Changed through `storage_migrate` so existing files move along.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StorageSettings {
+    /// Replaces `<app data>/qa_sessions`
+    pub qa_sessions_dir: Option<String>,
+
+    /// Replaces `<app data>/training`
+    pub training_dir: Option<String>,
+}
+
+impl SettingsSection for StorageSettings {
+    const KEY: &'static str = "storage";
+}
+
+/// Replace one origin with another, e.g. a production API with a local one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OriginRewrite {
+    pub from: String,
+    pub to: String,
+}
+
+/// Header added to every request the QA proxy forwards
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InjectedHeader {
+    pub name: String,
+    pub value: String,
+}
+
+/// How the QA recorder proxy adapts pages so they work inside the preview iframe
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QaProxySettings {
+    /// Route the page's fetch/XHR calls to its own origin and the rewrite
+    /// targets through the proxy, and report them to the recorder
+    pub capture_subresources: bool,
+
+    /// Applied to forwarded URLs and to text responses (HTML, JS, CSS, JSON)
+    pub origin_rewrites: Vec<OriginRewrite>,
+
+    /// Drop `Domain=` from upstream cookies so the browser keeps them for the proxy
+    pub strip_cookie_domain: bool,
+
+    pub inject_headers: Vec<InjectedHeader>,
+}
+
+impl Default for QaProxySettings {
+    fn default() -> Self {
+        Self {
+            capture_subresources: true,
+            origin_rewrites: Vec::new(),
+            strip_cookie_domain: true,
+            inject_headers: Vec::new(),
+        }
+    }
+}
+
+impl QaProxySettings {
+    pub fn validate(&self) -> Result<()> {
+        for rewrite in &self.origin_rewrites {
+            for origin in [&rewrite.from, &rewrite.to] {
+                if normalize_origin(origin).is_none() {
+                    return Err(AppError::ValidationError(format!(
+                        "Invalid origin '{}': expected scheme and host only, such as https://api.example.com",
+                        origin
+                    )));
+                }
+            }
+        }
+        for header in &self.inject_headers {
+            let name = header.name.trim();
+            let valid = !name.is_empty()
+                && name
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b));
+            if !valid {
+                return Err(AppError::ValidationError(format!(
+                    "Invalid header name '{}'",
+                    header.name
+                )));
+            }
+            if header.value.contains(['\r', '\n']) {
+                return Err(AppError::ValidationError(format!(
+                    "Header '{}' must be a single line",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SettingsSection for QaProxySettings {
+    const KEY: &'static str = "qaProxy";
+}
+
+/// `scheme://host[:port]` without a trailing slash, or `None` when `value`
+/// carries a path, query or anything other than an http(s) origin
+pub fn normalize_origin(value: &str) -> Option<String> {
+    let url = url::Url::parse(value.trim()).ok()?;
+    let bare = url.path() == "/" && url.query().is_none() && url.fragment().is_none();
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() || !bare {
+        return None;
+    }
+    Some(url.origin().ascii_serialization())
+}
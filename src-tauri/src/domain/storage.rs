@@ -0,0 +1,99 @@
+// ============================================================
+// STORAGE LOCATIONS
+// ============================================================
+// Relocatable data folders (QA session files, training
+// artifacts) and the plan, progress and result of moving them
+// to another path with `storage_migrate`.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StorageKind {
+    QaSessions,
+    Training,
+}
+
+impl StorageKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            StorageKind::QaSessions => "QA session files",
+            StorageKind::Training => "Training artifacts",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageLocation {
+    pub kind: StorageKind,
+    pub path: String,
+    pub default_path: String,
+    pub is_default: bool,
+    pub size_bytes: u64,
+    pub file_count: u64,
+
+    /// Free space on the drive holding `path`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub available_bytes: Option<u64>,
+}
+
+/// New locations; a field left out keeps its folder where it is and an
+/// empty string moves it back under the app data dir
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StorageMigrationRequest {
+    pub qa_sessions_dir: Option<String>,
+    pub training_dir: Option<String>,
+}
+
+/// What moving one folder involves, checked before anything is touched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageMovePlan {
+    pub kind: StorageKind,
+    pub from: String,
+    pub to: String,
+    pub file_count: u64,
+    pub total_bytes: u64,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub available_bytes: Option<u64>,
+
+    /// Reasons the move cannot run; empty when it is ready
+    pub problems: Vec<String>,
+}
+
+impl StorageMovePlan {
+    pub fn is_ready(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Payload of the `storage-migration-progress` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageMigrationProgress {
+    pub kind: StorageKind,
+    pub copied_bytes: u64,
+    pub total_bytes: u64,
+    pub files_done: u64,
+    pub file_count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageMoveResult {
+    pub kind: StorageKind,
+    pub from: String,
+    pub to: String,
+    pub files_moved: u64,
+    pub bytes_moved: u64,
+
+    /// Stored file paths rewritten to the new location
+    pub rows_updated: u64,
+
+    /// The old folder could not be removed after the move
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cleanup_error: Option<String>,
+}
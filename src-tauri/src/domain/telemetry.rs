@@ -0,0 +1,161 @@
+// ============================================================
+// USAGE TELEMETRY
+// ============================================================
+// Opt-in, local-only usage metrics: how often each feature runs,
+// how long it takes and how often it fails. Calls are summed into
+// one stats row per day and feature; nothing leaves the machine.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::settings::SettingsSection;
+
+/// Off by default; nothing is recorded until the user opts in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TelemetrySettings {
+    pub enabled: bool,
+
+    /// Days of stats kept; older days are dropped as new calls are recorded
+    pub retention_days: u32,
+}
+
+impl Default for TelemetrySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retention_days: 90,
+        }
+    }
+}
+
+impl TelemetrySettings {
+    pub fn validate(&self) -> Result<()> {
+        if !(1..=3650).contains(&self.retention_days) {
+            return Err(AppError::ValidationError(
+                "Usage stats retention must be between 1 and 3650 days".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl SettingsSection for TelemetrySettings {
+    const KEY: &'static str = "telemetry";
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageSubsystem {
+    Llm,
+    Rag,
+    Qa,
+}
+
+impl UsageSubsystem {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UsageSubsystem::Llm => "llm",
+            UsageSubsystem::Rag => "rag",
+            UsageSubsystem::Qa => "qa",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "llm" => Some(UsageSubsystem::Llm),
+            "rag" => Some(UsageSubsystem::Rag),
+            "qa" => Some(UsageSubsystem::Qa),
+            _ => None,
+        }
+    }
+}
+
+/// One finished call of a feature
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageSample {
+    /// Unix milliseconds, when the call finished
+    pub ts: i64,
+    pub subsystem: UsageSubsystem,
+    /// e.g. `enhance` or `checkpoint_summary`
+    pub feature: String,
+    pub latency_ms: i64,
+    pub success: bool,
+}
+
+/// Calls summed over some set of stats rows
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageTotals {
+    pub count: i64,
+    pub error_count: i64,
+    /// Failed share of `count`, 0 to 1
+    pub error_rate: f64,
+    pub avg_latency_ms: f64,
+}
+
+impl UsageTotals {
+    pub fn from_sums(count: i64, error_count: i64, total_latency_ms: i64) -> Self {
+        let (error_rate, avg_latency_ms) = if count > 0 {
+            (
+                error_count as f64 / count as f64,
+                total_latency_ms as f64 / count as f64,
+            )
+        } else {
+            (0.0, 0.0)
+        };
+        Self {
+            count,
+            error_count,
+            error_rate,
+            avg_latency_ms,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubsystemUsage {
+    pub subsystem: UsageSubsystem,
+    #[serde(flatten)]
+    pub totals: UsageTotals,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureUsage {
+    pub subsystem: UsageSubsystem,
+    pub feature: String,
+    #[serde(flatten)]
+    pub totals: UsageTotals,
+    /// Slowest single call
+    pub max_latency_ms: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyUsage {
+    /// UTC day, `YYYY-MM-DD`
+    pub day: String,
+    #[serde(flatten)]
+    pub totals: UsageTotals,
+}
+
+/// What `get_usage_dashboard` shows for the last `days` days
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageDashboard {
+    /// Whether calls are being recorded now; stats from before an opt-out
+    /// are still shown
+    pub enabled: bool,
+    pub days: u32,
+    /// First UTC day included
+    pub since_day: String,
+    pub totals: UsageTotals,
+    /// Busiest first
+    pub subsystems: Vec<SubsystemUsage>,
+    /// Busiest first
+    pub features: Vec<FeatureUsage>,
+    /// Oldest first; days without calls are left out
+    pub daily: Vec<DailyUsage>,
+}
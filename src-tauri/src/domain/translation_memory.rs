@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A stored source/target pair in the translation memory.
+///
+/// Every model translation is recorded unapproved; only approved entries
+/// (human-reviewed or manually added) are reused instead of calling the LLM.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TranslationMemoryEntry {
+    pub id: Option<i64>,
+    pub source_text: String,
+    pub target_text: String,
+    pub source_lang: String,
+    pub target_lang: String,
+    #[serde(default)]
+    pub approved: bool,
+    /// `model` for LLM output, `human` for manual entries and edits.
+    pub origin: String,
+    #[serde(default)]
+    pub use_count: i64,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TranslationMatchType {
+    Exact,
+    Fuzzy,
+}
+
+/// Provenance attached to a translation served from memory.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct TranslationMemoryMatch {
+    pub entry_id: i64,
+    pub match_type: TranslationMatchType,
+    /// Normalized edit-distance similarity in `[0, 1]`.
+    pub similarity: f32,
+    pub source_text: String,
+    pub origin: String,
+}
+
+/// Lowercase and collapse whitespace so trivial differences still match exactly.
+pub fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
@@ -0,0 +1,114 @@
+// ============================================================
+// TRASH
+// ============================================================
+// Deleting a QA session, RAG document or training dataset moves it to
+// the trash: it is stamped with `deleted_at` (unix ms) and hidden from
+// lists and retrieval, but kept with everything that belongs to it.
+// It can be restored until the retention period ends; after that, or
+// on an explicit purge, it is deleted for good.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::{AppError, Result};
+
+/// Days an item stays in the trash before it is purged
+pub const TRASH_RETENTION_DAYS: i64 = 30;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrashKind {
+    QaSession,
+    RagDocument,
+    Dataset,
+}
+
+impl TrashKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TrashKind::QaSession => "QA session",
+            TrashKind::RagDocument => "Document",
+            TrashKind::Dataset => "Dataset",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashEntry {
+    pub kind: TrashKind,
+    /// Session or dataset id, or the document id as a string
+    pub id: String,
+    /// Session title, file name or dataset name
+    pub name: String,
+    pub deleted_at: i64,
+    /// When the entry is purged unless restored first (unix ms)
+    pub purge_at: i64,
+}
+
+impl TrashEntry {
+    pub fn new(kind: TrashKind, id: impl Into<String>, name: String, deleted_at: i64) -> Self {
+        Self {
+            kind,
+            id: id.into(),
+            name,
+            deleted_at,
+            purge_at: deleted_at + TRASH_RETENTION_DAYS * DAY_MS,
+        }
+    }
+
+    /// Numeric id of a RAG document entry
+    pub fn document_id(id: &str) -> Result<i64> {
+        id.trim()
+            .parse()
+            .map_err(|_| AppError::ValidationError(format!("Invalid document id '{}'", id)))
+    }
+}
+
+/// Items deleted before this timestamp (ms) are past the retention period
+pub fn purge_cutoff(now_ms: i64) -> i64 {
+    now_ms - TRASH_RETENTION_DAYS * DAY_MS
+}
+
+/// Items removed for good by a purge
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashPurgeReport {
+    pub qa_sessions: u64,
+    pub rag_documents: u64,
+    pub datasets: u64,
+    /// Items that could not be purged; they stay in the trash
+    pub errors: Vec<String>,
+}
+
+impl TrashPurgeReport {
+    pub fn total(&self) -> u64 {
+        self.qa_sessions + self.rag_documents + self.datasets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_are_purged_after_the_retention_period() {
+        let deleted_at = 1_700_000_000_000;
+        let entry = TrashEntry::new(
+            TrashKind::Dataset,
+            "ds-1",
+            "Support replies".to_string(),
+            deleted_at,
+        );
+        assert_eq!(entry.purge_at - entry.deleted_at, 30 * DAY_MS);
+        assert!(purge_cutoff(entry.purge_at - 1) < deleted_at);
+        assert!(purge_cutoff(entry.purge_at + 1) > deleted_at);
+    }
+
+    #[test]
+    fn document_ids_must_be_numeric() {
+        assert_eq!(TrashEntry::document_id(" 42 ").unwrap(), 42);
+        assert!(TrashEntry::document_id("abc").is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum TypeGenMode {
     Auto,
@@ -13,3 +14,74 @@ impl Default for TypeGenMode {
         Self::Auto
     }
 }
+
+/// Extra code emitted alongside the plain type definitions, and how
+/// nullable values and field names are rendered.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, ToSchema)]
+#[serde(default)]
+pub struct TypeGenOptions {
+    /// Dart `fromJson`/`toJson` methods and Rust `serde_json::Value` conversions.
+    pub serialization_helpers: bool,
+    /// Java all-args constructors and fluent builders.
+    pub builders: bool,
+    /// How fields that can be null are typed.
+    pub nullable: NullableStrategy,
+    /// Whether fields missing from some samples are nullable.
+    pub missing_fields: MissingFields,
+    /// Naming convention for fields instead of the language's own; Go
+    /// fields stay PascalCase so they remain exported and TypeScript keeps
+    /// the JSON names.
+    pub field_naming: Option<NamingConvention>,
+}
+
+impl TypeGenOptions {
+    pub fn types_only(&self) -> bool {
+        !self.serialization_helpers && !self.builders
+    }
+}
+
+/// How a nullable field is typed. Languages a strategy doesn't apply to
+/// use their native nullable type.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NullableStrategy {
+    /// `Option<T>`, `T | null`, `T?`, `*T`, boxed Java types.
+    #[default]
+    Native,
+    /// The plain type, left at its default when the value is null or
+    /// missing: `#[serde(default)]` in Rust, zero values in Go, `T` in
+    /// TypeScript.
+    Default,
+    /// TypeScript optional properties (`name?: T`).
+    Undefined,
+    /// `Optional<T>` in Java.
+    Optional,
+}
+
+/// How a field missing from some of the samples is typed.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MissingFields {
+    /// Nullable, like a field that is null in some sample.
+    #[default]
+    Optional,
+    /// Required; only null values make a field nullable.
+    Required,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NamingConvention {
+    CamelCase,
+    PascalCase,
+    SnakeCase,
+}
+
+/// Flavour of API client generated from recorded QA API calls.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiClientTarget {
+    TypescriptFetch,
+    TypescriptAxios,
+    RustReqwest,
+}
@@ -0,0 +1,316 @@
+// ============================================================
+// WRITING MEMORY
+// ============================================================
+// Accepted translations and enhancements copied into a RAG
+// collection ("My writing memory") so chat and retrieval can draw
+// on past work. A translation counts as accepted once approved in
+// the translation memory, and any history entry once starred.
+// Each entry becomes one document named by a hash of its content,
+// so accepting the same text twice stores it once.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::prompt::Prompt;
+use crate::domain::settings::SettingsSection;
+use crate::domain::translation_memory::{normalize, TranslationMemoryEntry};
+
+pub const DEFAULT_COLLECTION_NAME: &str = "My writing memory";
+
+/// `file_type` of writing memory documents
+pub const WRITING_MEMORY_FILE_TYPE: &str = "writing_memory";
+
+/// Prefix of the document path that carries the content hash
+const PATH_PREFIX: &str = "writing-memory://";
+
+const MAX_COLLECTION_NAME_CHARS: usize = 100;
+
+/// Characters of the source text used in a document's name
+const TITLE_CHARS: usize = 60;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WritingMemorySettings {
+    pub enabled: bool,
+
+    /// Collection the entries go to; created on first use
+    pub collection_name: String,
+
+    pub include_translations: bool,
+
+    pub include_enhancements: bool,
+}
+
+impl Default for WritingMemorySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            collection_name: DEFAULT_COLLECTION_NAME.to_string(),
+            include_translations: true,
+            include_enhancements: true,
+        }
+    }
+}
+
+impl WritingMemorySettings {
+    pub fn validate(&self) -> Result<()> {
+        let name = self.collection_name.trim();
+        if name.is_empty() {
+            return Err(AppError::ValidationError(
+                "Writing memory collection name is required".to_string(),
+            ));
+        }
+        if name.chars().count() > MAX_COLLECTION_NAME_CHARS {
+            return Err(AppError::ValidationError(format!(
+                "Writing memory collection name must be at most {} characters",
+                MAX_COLLECTION_NAME_CHARS
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn collection_name(&self) -> &str {
+        match self.collection_name.trim() {
+            "" => DEFAULT_COLLECTION_NAME,
+            name => name,
+        }
+    }
+
+    /// Whether entries of `kind` are kept
+    pub fn accepts(&self, kind: WritingMemoryKind) -> bool {
+        self.enabled
+            && match kind {
+                WritingMemoryKind::Translation => self.include_translations,
+                WritingMemoryKind::Enhancement => self.include_enhancements,
+            }
+    }
+}
+
+impl SettingsSection for WritingMemorySettings {
+    const KEY: &'static str = "writingMemory";
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WritingMemoryKind {
+    Translation,
+    Enhancement,
+}
+
+impl WritingMemoryKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Translation => "translation",
+            Self::Enhancement => "enhancement",
+        }
+    }
+}
+
+/// One accepted piece of writing
+#[derive(Debug, Clone)]
+pub struct WritingMemoryEntry {
+    pub kind: WritingMemoryKind,
+    pub source_text: String,
+    pub result_text: String,
+    pub source_lang: String,
+    pub target_lang: String,
+    pub date: DateTime<Utc>,
+}
+
+impl WritingMemoryEntry {
+    /// An approved translation memory entry
+    pub fn from_tm_entry(entry: &TranslationMemoryEntry) -> Option<Self> {
+        Self::new(
+            WritingMemoryKind::Translation,
+            &entry.source_text,
+            &entry.target_text,
+            &entry.source_lang,
+            &entry.target_lang,
+            entry.updated_at.or(entry.created_at),
+        )
+    }
+
+    /// A history entry; enhancements keep the same language on both sides
+    pub fn from_prompt(prompt: &Prompt) -> Option<Self> {
+        // Only set when the source was auto-detected
+        let source_lang = prompt
+            .detected_lang
+            .as_deref()
+            .unwrap_or(&prompt.source_lang);
+        let kind = if prompt.source_lang.eq_ignore_ascii_case(&prompt.target_lang) {
+            WritingMemoryKind::Enhancement
+        } else {
+            WritingMemoryKind::Translation
+        };
+        Self::new(
+            kind,
+            &prompt.content,
+            prompt.result.as_deref()?,
+            source_lang,
+            &prompt.target_lang,
+            prompt.created_at,
+        )
+    }
+
+    fn new(
+        kind: WritingMemoryKind,
+        source_text: &str,
+        result_text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        date: Option<DateTime<Utc>>,
+    ) -> Option<Self> {
+        let (source_text, result_text) = (source_text.trim(), result_text.trim());
+        if source_text.is_empty() || result_text.is_empty() {
+            return None;
+        }
+        Some(Self {
+            kind,
+            source_text: source_text.to_string(),
+            result_text: result_text.to_string(),
+            source_lang: source_lang.trim().to_string(),
+            target_lang: target_lang.trim().to_string(),
+            date: date.unwrap_or_else(Utc::now),
+        })
+    }
+
+    /// Hash of the kind, languages and texts; case and spacing are ignored
+    /// so an entry accepted again with trivial edits is not stored twice
+    pub fn content_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        for part in [
+            self.kind.as_str().to_string(),
+            self.source_lang.to_lowercase(),
+            self.target_lang.to_lowercase(),
+            normalize(&self.source_text),
+            normalize(&self.result_text),
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    /// Document path that identifies the entry in its collection
+    pub fn document_path(&self) -> String {
+        format!("{}{}", PATH_PREFIX, self.content_hash())
+    }
+
+    pub fn title(&self) -> String {
+        let text = self
+            .source_text
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut title: String = text.chars().take(TITLE_CHARS).collect();
+        if text.chars().count() > TITLE_CHARS {
+            title.push('…');
+        }
+        match self.kind {
+            WritingMemoryKind::Translation => format!(
+                "Translation {} → {}: {}",
+                self.source_lang, self.target_lang, title
+            ),
+            WritingMemoryKind::Enhancement => format!("Enhancement: {}", title),
+        }
+    }
+
+    /// Text stored in the collection; the header lines keep the languages
+    /// and date searchable alongside the writing itself
+    pub fn document_text(&self) -> String {
+        let date = self.date.format("%Y-%m-%d");
+        match self.kind {
+            WritingMemoryKind::Translation => format!(
+                "Accepted translation ({} → {}), {}\n\nOriginal ({}):\n{}\n\nTranslation ({}):\n{}\n",
+                self.source_lang,
+                self.target_lang,
+                date,
+                self.source_lang,
+                self.source_text,
+                self.target_lang,
+                self.result_text
+            ),
+            WritingMemoryKind::Enhancement => format!(
+                "Accepted enhancement ({}), {}\n\nOriginal:\n{}\n\nEnhanced:\n{}\n",
+                self.target_lang, date, self.source_text, self.result_text
+            ),
+        }
+    }
+
+    /// Metadata stored on the document
+    pub fn meta_json(&self) -> String {
+        serde_json::json!({
+            "kind": self.kind.as_str(),
+            "sourceLang": self.source_lang,
+            "targetLang": self.target_lang,
+            "date": self.date.to_rfc3339(),
+            "contentHash": self.content_hash(),
+        })
+        .to_string()
+    }
+}
+
+/// Result of copying past accepted entries into the collection
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WritingMemorySyncReport {
+    pub collection_id: i64,
+    pub added: usize,
+    /// Entries already in the collection
+    pub skipped: usize,
+    pub failed: usize,
+    pub errors: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt(source_lang: &str, target_lang: &str, content: &str, result: &str) -> Prompt {
+        let mut prompt = Prompt::new(
+            content.to_string(),
+            source_lang.to_string(),
+            target_lang.to_string(),
+        );
+        prompt.result = Some(result.to_string());
+        prompt
+    }
+
+    #[test]
+    fn prompts_map_to_translations_and_enhancements() {
+        let translation =
+            WritingMemoryEntry::from_prompt(&prompt("EN", "ID", "Good morning", "Selamat pagi"))
+                .unwrap();
+        assert_eq!(translation.kind, WritingMemoryKind::Translation);
+        assert!(translation
+            .document_text()
+            .contains("Translation (ID):\nSelamat pagi"));
+
+        let enhancement =
+            WritingMemoryEntry::from_prompt(&prompt("EN", "EN", "pls send it", "Please send it."))
+                .unwrap();
+        assert_eq!(enhancement.kind, WritingMemoryKind::Enhancement);
+        assert!(enhancement.title().starts_with("Enhancement: "));
+
+        let mut detected = prompt("Auto Detect", "EN", "Selamat pagi", "Good morning");
+        detected.detected_lang = Some("ID".to_string());
+        let detected = WritingMemoryEntry::from_prompt(&detected).unwrap();
+        assert_eq!(detected.source_lang, "ID");
+
+        assert!(WritingMemoryEntry::from_prompt(&prompt("EN", "ID", "Hi", "  ")).is_none());
+    }
+
+    #[test]
+    fn hash_ignores_case_and_spacing_but_not_languages() {
+        let entry = |source_lang: &str, text: &str| {
+            WritingMemoryEntry::from_prompt(&prompt(source_lang, "ID", text, "Selamat pagi"))
+                .unwrap()
+        };
+        let hash = entry("EN", "Good morning").content_hash();
+        assert_eq!(entry("en", "good   morning ").content_hash(), hash);
+        assert_ne!(entry("FR", "Good morning").content_hash(), hash);
+        assert!(entry("EN", "Good morning").document_path().ends_with(&hash));
+    }
+}
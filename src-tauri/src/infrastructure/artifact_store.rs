@@ -32,7 +32,12 @@ pub struct TrainingArtifactLayout {
 
 impl TrainingArtifactLayout {
     pub fn new(app_data_dir: &Path) -> Self {
-        let root = app_data_dir.join("training");
+        Self::at(&app_data_dir.join("training"))
+    }
+
+    /// Layout rooted at a relocated training folder
+    pub fn at(root: &Path) -> Self {
+        let root = root.to_path_buf();
         let models_base = root.join("models").join("base");
         let models_versions = root.join("models").join("versions");
         let runs = root.join("runs");
@@ -251,9 +256,12 @@ pub struct CleanupReport {
 /// Backup strategy for training DB.
 ///
 /// Provides:
-/// - Daily rolling backups (keeps last N days)
-/// - On-demand backup before version promotion
+/// - On-demand backups from the distillation tools
+/// - Daily rolling backups (keeps last N days) while scheduled backups are off
+/// - Backup before version promotion
 /// - Automatic cleanup of old backups
+///
+/// Scheduled backups of every database are handled by `infrastructure::backup`.
 #[derive(Debug, Clone)]
 pub struct BackupConfig {
     /// Directory where backups are stored.
@@ -283,7 +291,8 @@ pub struct BackupResult {
 
 /// Ensure there is at least one backup for the current UTC day.
 ///
-/// This is best-effort and is designed to be called at app startup.
+/// This is best-effort and is designed to be called at app startup when
+/// scheduled backups (`infrastructure::backup`) are turned off.
 /// - If a backup already exists for today, no new backup is created.
 /// - Old backups are still cleaned up according to the retention policy.
 pub fn ensure_daily_backup(db_path: &Path, config: &BackupConfig) -> Result<Option<BackupResult>> {
@@ -0,0 +1,229 @@
+// ============================================================
+// BACKUP ENCRYPTION
+// ============================================================
+// Streaming AES-256-GCM for backup archives. The file is split
+// into 1 MiB chunks, each sealed with its own nonce (random file
+// prefix + chunk counter). The last chunk is authenticated as
+// such, so a truncated file fails to decrypt instead of yielding
+// a shorter archive.
+//
+// Layout: MAGIC | nonce prefix (8) | { len (u32 LE) | ciphertext+tag }*
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::domain::error::{AppError, Result};
+
+pub const KEY_LEN: usize = 32;
+
+const MAGIC: &[u8; 8] = b"GDBAK\x00\x00\x01";
+const PREFIX_LEN: usize = 8;
+const CHUNK_LEN: usize = 1024 * 1024;
+
+pub fn generate_key() -> Result<[u8; KEY_LEN]> {
+    let mut key = [0u8; KEY_LEN];
+    SystemRandom::new()
+        .fill(&mut key)
+        .map_err(|_| AppError::Internal("No secure random source available".to_string()))?;
+    Ok(key)
+}
+
+/// Whether `path` starts with the encrypted backup header
+pub fn is_encrypted(path: &Path) -> bool {
+    let mut magic = [0u8; 8];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .is_ok_and(|_| &magic == MAGIC)
+}
+
+fn cipher(key: &[u8; KEY_LEN]) -> Result<LessSafeKey> {
+    UnboundKey::new(&AES_256_GCM, key)
+        .map(LessSafeKey::new)
+        .map_err(|_| AppError::Internal("Invalid backup encryption key".to_string()))
+}
+
+fn chunk_nonce(prefix: &[u8; PREFIX_LEN], counter: u32) -> Nonce {
+    let mut nonce = [0u8; NONCE_LEN];
+    nonce[..PREFIX_LEN].copy_from_slice(prefix);
+    nonce[PREFIX_LEN..].copy_from_slice(&counter.to_be_bytes());
+    Nonce::assume_unique_for_key(nonce)
+}
+
+/// Read up to `CHUNK_LEN` bytes; shorter only at end of input
+fn read_chunk(reader: &mut impl Read) -> std::io::Result<Vec<u8>> {
+    let mut chunk = Vec::with_capacity(CHUNK_LEN);
+    reader.take(CHUNK_LEN as u64).read_to_end(&mut chunk)?;
+    Ok(chunk)
+}
+
+fn io_err(action: &str, path: &Path, err: std::io::Error) -> AppError {
+    AppError::IoError(format!("Failed to {} {}: {}", action, path.display(), err))
+}
+
+pub fn encrypt_file(src: &Path, dst: &Path, key: &[u8; KEY_LEN]) -> Result<()> {
+    let cipher = cipher(key)?;
+    let mut prefix = [0u8; PREFIX_LEN];
+    SystemRandom::new()
+        .fill(&mut prefix)
+        .map_err(|_| AppError::Internal("No secure random source available".to_string()))?;
+
+    let mut reader = BufReader::new(File::open(src).map_err(|e| io_err("open", src, e))?);
+    let mut writer = BufWriter::new(File::create(dst).map_err(|e| io_err("create", dst, e))?);
+    let write_err = |e| io_err("write", dst, e);
+
+    writer.write_all(MAGIC).map_err(write_err)?;
+    writer.write_all(&prefix).map_err(write_err)?;
+
+    let mut current = read_chunk(&mut reader).map_err(|e| io_err("read", src, e))?;
+    let mut counter = 0u32;
+    loop {
+        // Look one chunk ahead so the final one can be flagged
+        let next = if current.len() == CHUNK_LEN {
+            read_chunk(&mut reader).map_err(|e| io_err("read", src, e))?
+        } else {
+            Vec::new()
+        };
+        let last = next.is_empty();
+
+        cipher
+            .seal_in_place_append_tag(
+                chunk_nonce(&prefix, counter),
+                Aad::from([last as u8]),
+                &mut current,
+            )
+            .map_err(|_| AppError::Internal("Failed to encrypt backup".to_string()))?;
+        writer
+            .write_all(&(current.len() as u32).to_le_bytes())
+            .map_err(write_err)?;
+        writer.write_all(&current).map_err(write_err)?;
+
+        if last {
+            break;
+        }
+        current = next;
+        counter = counter.checked_add(1).ok_or_else(|| {
+            AppError::ValidationError("Backup is too large to encrypt".to_string())
+        })?;
+    }
+
+    writer.flush().map_err(write_err)?;
+    writer.get_ref().sync_all().map_err(write_err)?;
+    Ok(())
+}
+
+pub fn decrypt_file(src: &Path, dst: &Path, key: &[u8; KEY_LEN]) -> Result<()> {
+    let cipher = cipher(key)?;
+    let mut reader = BufReader::new(File::open(src).map_err(|e| io_err("open", src, e))?);
+    let mut writer = BufWriter::new(File::create(dst).map_err(|e| io_err("create", dst, e))?);
+    let read_err = |e| io_err("read", src, e);
+    let corrupt = || {
+        AppError::ValidationError(format!(
+            "{} is damaged or was encrypted with a different key",
+            src.display()
+        ))
+    };
+
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic).map_err(|_| corrupt())?;
+    if &magic != MAGIC {
+        return Err(AppError::ValidationError(format!(
+            "{} is not an encrypted backup",
+            src.display()
+        )));
+    }
+    let mut prefix = [0u8; PREFIX_LEN];
+    reader.read_exact(&mut prefix).map_err(|_| corrupt())?;
+
+    let mut counter = 0u32;
+    loop {
+        let mut len = [0u8; 4];
+        reader.read_exact(&mut len).map_err(|_| corrupt())?;
+        let len = u32::from_le_bytes(len) as usize;
+        if len > CHUNK_LEN + AES_256_GCM.tag_len() {
+            return Err(corrupt());
+        }
+        let mut chunk = vec![0u8; len];
+        reader.read_exact(&mut chunk).map_err(|_| corrupt())?;
+        let last = reader.fill_buf().map_err(read_err)?.is_empty();
+
+        let plain = cipher
+            .open_in_place(
+                chunk_nonce(&prefix, counter),
+                Aad::from([last as u8]),
+                &mut chunk,
+            )
+            .map_err(|_| corrupt())?;
+        writer
+            .write_all(plain)
+            .map_err(|e| io_err("write", dst, e))?;
+
+        if last {
+            break;
+        }
+        counter = counter.checked_add(1).ok_or_else(corrupt)?;
+    }
+
+    writer.flush().map_err(|e| io_err("write", dst, e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("backup-crypto-{}-{}", uuid::Uuid::new_v4(), name))
+    }
+
+    fn round_trip(data: &[u8]) -> Vec<u8> {
+        let key = generate_key().unwrap();
+        let (plain, sealed, opened) =
+            (temp_path("plain"), temp_path("sealed"), temp_path("opened"));
+        std::fs::write(&plain, data).unwrap();
+
+        encrypt_file(&plain, &sealed, &key).unwrap();
+        assert!(is_encrypted(&sealed));
+        assert!(!is_encrypted(&plain));
+        decrypt_file(&sealed, &opened, &key).unwrap();
+
+        let result = std::fs::read(&opened).unwrap();
+        for path in [plain, sealed, opened] {
+            let _ = std::fs::remove_file(path);
+        }
+        result
+    }
+
+    #[test]
+    fn test_round_trip_across_chunk_boundaries() {
+        for len in [0, 17, CHUNK_LEN, CHUNK_LEN * 2 + 5] {
+            let data: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+            assert_eq!(round_trip(&data), data, "length {}", len);
+        }
+    }
+
+    #[test]
+    fn test_wrong_key_and_truncation_are_rejected() {
+        let key = generate_key().unwrap();
+        let (plain, sealed, opened) =
+            (temp_path("plain"), temp_path("sealed"), temp_path("opened"));
+        std::fs::write(&plain, vec![7u8; CHUNK_LEN + 100]).unwrap();
+        encrypt_file(&plain, &sealed, &key).unwrap();
+
+        let other_key = generate_key().unwrap();
+        assert!(decrypt_file(&sealed, &opened, &other_key).is_err());
+
+        // Drop the final chunk: the remaining one was not sealed as the last
+        let bytes = std::fs::read(&sealed).unwrap();
+        let first_chunk_end = MAGIC.len() + PREFIX_LEN + 4 + CHUNK_LEN + AES_256_GCM.tag_len();
+        std::fs::write(&sealed, &bytes[..first_chunk_end]).unwrap();
+        assert!(decrypt_file(&sealed, &opened, &key).is_err());
+
+        for path in [plain, sealed, opened] {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
@@ -0,0 +1,587 @@
+// ============================================================
+// BACKUP MANAGER
+// ============================================================
+// Full backups of the four app databases plus, optionally, the
+// QA session artifacts. Databases are snapshotted with
+// `VACUUM INTO` while the app keeps running, packed into one zip
+// archive and optionally encrypted. A manifest is written both
+// into the archive and next to it so backups can be listed
+// without opening them.
+//
+// Restores never touch open databases: the selected files are
+// staged under `backups/pending_restore` and swapped in by
+// `apply_pending_restore` on the next launch, before any pool
+// is opened. QA session files are archived as `qa_sessions/`
+// wherever that folder has been moved to.
+
+pub mod crypto;
+pub mod retention;
+
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::domain::backup::{
+    BackedUpDatabase, BackupEntry, BackupManifest, BackupReason, BackupSettings, RestoreReport,
+    RestoreRequest, BACKUP_FORMAT_VERSION,
+};
+use crate::domain::error::{AppError, Result};
+use crate::infrastructure::artifact_store::atomic_write_bytes;
+use crate::infrastructure::db::maintenance::{sidecar, snapshot_database};
+use crate::infrastructure::storage::{app_databases, move_dir, StorageLocations};
+
+use self::crypto::KEY_LEN;
+
+const MANIFEST_NAME: &str = "manifest.json";
+const PENDING_MARKER: &str = "restore.json";
+const QA_SESSIONS_DIR: &str = "qa_sessions";
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+/// Suffix of live database files moved aside while a restore swaps them
+const PRE_RESTORE_SUFFIX: &str = ".pre-restore";
+
+/// What `apply_pending_restore` will swap in on the next launch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingRestore {
+    pub backup_id: String,
+    pub databases: Vec<String>,
+    pub qa_sessions: bool,
+}
+
+pub struct BackupManager {
+    app_data_dir: PathBuf,
+    backup_dir: PathBuf,
+    storage: Arc<StorageLocations>,
+
+    /// One backup or restore at a time, whether scheduled or requested
+    running: tokio::sync::Mutex<()>,
+}
+
+impl BackupManager {
+    pub fn new(app_data_dir: &Path, storage: Arc<StorageLocations>) -> Self {
+        Self {
+            app_data_dir: app_data_dir.to_path_buf(),
+            backup_dir: backups_root(app_data_dir).join("snapshots"),
+            storage,
+            running: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn backup_dir(&self) -> &Path {
+        &self.backup_dir
+    }
+
+    /// Snapshot every database (and `qa_sessions` when asked) into a new backup
+    pub async fn create(
+        &self,
+        reason: BackupReason,
+        include_qa_sessions: bool,
+        key: Option<[u8; KEY_LEN]>,
+    ) -> Result<BackupEntry> {
+        let _running = self.running.lock().await;
+        self.create_locked(reason, include_qa_sessions, key).await
+    }
+
+    async fn create_locked(
+        &self,
+        reason: BackupReason,
+        include_qa_sessions: bool,
+        key: Option<[u8; KEY_LEN]>,
+    ) -> Result<BackupEntry> {
+        fs::create_dir_all(&self.backup_dir)?;
+
+        let created_at = Local::now();
+        let mut id = format!(
+            "backup_{}_{}",
+            created_at.format("%Y%m%d_%H%M%S"),
+            reason.as_str()
+        );
+        if self.manifest_path(&id).exists() {
+            id = format!("{}_{}", id, &uuid::Uuid::new_v4().simple().to_string()[..6]);
+        }
+
+        let staging = self.backup_dir.join(format!(".{}.tmp", id));
+        fs::create_dir_all(&staging)?;
+
+        let result = self
+            .build(&id, &staging, created_at, reason, include_qa_sessions, key)
+            .await;
+        let _ = fs::remove_dir_all(&staging);
+        result
+    }
+
+    async fn build(
+        &self,
+        id: &str,
+        staging: &Path,
+        created_at: DateTime<Local>,
+        reason: BackupReason,
+        include_qa_sessions: bool,
+        key: Option<[u8; KEY_LEN]>,
+    ) -> Result<BackupEntry> {
+        let mut databases = Vec::new();
+        for db in app_databases(&self.app_data_dir) {
+            if !db.path.exists() {
+                continue;
+            }
+            let snapshot = staging.join(format!("{}.db", db.key));
+            snapshot_database(&db.path, &snapshot).await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to snapshot {}: {}", db.label, e))
+            })?;
+            databases.push(BackedUpDatabase {
+                key: db.key.to_string(),
+                label: db.label.to_string(),
+                size_bytes: fs::metadata(&snapshot)?.len(),
+            });
+        }
+
+        let mut manifest = BackupManifest {
+            format_version: BACKUP_FORMAT_VERSION,
+            id: id.to_string(),
+            created_at: created_at.to_rfc3339(),
+            reason,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            databases,
+            includes_qa_sessions: include_qa_sessions,
+            qa_session_files: 0,
+            encrypted: key.is_some(),
+        };
+
+        let qa_sessions_dir = self.storage.qa_sessions_dir();
+        let archive_path = self.archive_path(id, key.is_some());
+        let staging = staging.to_path_buf();
+        manifest = tokio::task::spawn_blocking(move || {
+            let archive = staging.join("archive.zip");
+            write_archive(&archive, &staging, &mut manifest, &qa_sessions_dir)?;
+            match key {
+                Some(key) => {
+                    let sealed = staging.join("archive.zip.enc");
+                    crypto::encrypt_file(&archive, &sealed, &key)?;
+                    fs::rename(&sealed, &archive_path)?;
+                }
+                None => fs::rename(&archive, &archive_path)?,
+            }
+            Ok::<_, AppError>(manifest)
+        })
+        .await
+        .map_err(|e| AppError::Internal(format!("Backup worker failed: {}", e)))??;
+
+        let manifest_json = serde_json::to_vec_pretty(&manifest)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize manifest: {}", e)))?;
+        atomic_write_bytes(&self.manifest_path(id), &manifest_json)?;
+
+        self.entry(manifest)
+    }
+
+    /// Every backup with a readable manifest, newest first
+    pub fn list(&self) -> Result<Vec<BackupEntry>> {
+        let mut entries = Vec::new();
+        if !self.backup_dir.exists() {
+            return Ok(entries);
+        }
+
+        for item in fs::read_dir(&self.backup_dir)? {
+            let path = item?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let Ok(manifest) = read_manifest(&path) else {
+                continue;
+            };
+            if let Ok(entry) = self.entry(manifest) {
+                entries.push(entry);
+            }
+        }
+
+        entries.sort_by(|a, b| b.manifest.created_at.cmp(&a.manifest.created_at));
+        Ok(entries)
+    }
+
+    pub fn find(&self, id: &str) -> Result<BackupEntry> {
+        let not_found = || AppError::NotFound(format!("Backup '{}' not found", id));
+        if id.is_empty() || id.contains(['/', '\\']) || id.starts_with('.') {
+            return Err(not_found());
+        }
+        let manifest = read_manifest(&self.manifest_path(id)).map_err(|_| not_found())?;
+        self.entry(manifest).map_err(|_| not_found())
+    }
+
+    pub async fn delete(&self, id: &str) -> Result<()> {
+        let _running = self.running.lock().await;
+        let entry = self.find(id)?;
+        fs::remove_file(&entry.path)?;
+        fs::remove_file(self.manifest_path(id))?;
+        Ok(())
+    }
+
+    /// Creation time of the newest scheduled backup
+    pub fn last_scheduled(&self) -> Result<Option<DateTime<Local>>> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .filter(|entry| entry.manifest.reason == BackupReason::Scheduled)
+            .filter_map(|entry| parse_created_at(&entry.manifest))
+            .max())
+    }
+
+    /// Delete scheduled backups outside the retention tiers; returns their ids
+    pub async fn prune(&self, settings: &BackupSettings) -> Result<Vec<String>> {
+        let _running = self.running.lock().await;
+        let scheduled: Vec<(String, DateTime<Local>)> = self
+            .list()?
+            .into_iter()
+            .filter(|entry| entry.manifest.reason == BackupReason::Scheduled)
+            .filter_map(|entry| parse_created_at(&entry.manifest).map(|at| (entry.manifest.id, at)))
+            .collect();
+
+        let mut deleted = Vec::new();
+        for id in retention::expired_backups(&scheduled, settings) {
+            let _ = fs::remove_file(self.archive_path(&id, false));
+            let _ = fs::remove_file(self.archive_path(&id, true));
+            if fs::remove_file(self.manifest_path(&id)).is_ok() {
+                deleted.push(id);
+            }
+        }
+        Ok(deleted)
+    }
+
+    /// Take a safety snapshot, then stage the selected parts of a backup
+    /// for `apply_pending_restore`. `archive_key` decrypts the backup,
+    /// `safety_key` encrypts the safety snapshot.
+    pub async fn restore(
+        &self,
+        request: &RestoreRequest,
+        archive_key: Option<[u8; KEY_LEN]>,
+        safety_key: Option<[u8; KEY_LEN]>,
+    ) -> Result<RestoreReport> {
+        let _running = self.running.lock().await;
+        let entry = self.find(&request.id)?;
+        let manifest = &entry.manifest;
+
+        let databases: Vec<String> = if request.databases.is_empty() {
+            manifest.databases.iter().map(|db| db.key.clone()).collect()
+        } else {
+            for key in &request.databases {
+                if !manifest.databases.iter().any(|db| &db.key == key) {
+                    return Err(AppError::ValidationError(format!(
+                        "Backup '{}' has no '{}' database",
+                        manifest.id, key
+                    )));
+                }
+            }
+            request.databases.clone()
+        };
+        if request.qa_sessions && !manifest.includes_qa_sessions {
+            return Err(AppError::ValidationError(format!(
+                "Backup '{}' does not include QA session files",
+                manifest.id
+            )));
+        }
+        if databases.is_empty() && !request.qa_sessions {
+            return Err(AppError::ValidationError(
+                "Nothing selected to restore".to_string(),
+            ));
+        }
+        if manifest.encrypted && archive_key.is_none() {
+            return Err(AppError::SecurityError(format!(
+                "Backup '{}' is encrypted and no key was provided",
+                manifest.id
+            )));
+        }
+
+        let safety_backup = self
+            .create_locked(BackupReason::PreRestore, request.qa_sessions, safety_key)
+            .await?;
+
+        let pending = PendingRestore {
+            backup_id: manifest.id.clone(),
+            databases,
+            qa_sessions: request.qa_sessions,
+        };
+        let app_data_dir = self.app_data_dir.clone();
+        let archive = PathBuf::from(&entry.path);
+        let key = archive_key.filter(|_| manifest.encrypted);
+        let pending = tokio::task::spawn_blocking(move || {
+            stage_restore(&app_data_dir, &archive, key, &pending).map(|_| pending)
+        })
+        .await
+        .map_err(|e| AppError::Internal(format!("Restore worker failed: {}", e)))??;
+
+        Ok(RestoreReport {
+            restored_databases: pending.databases,
+            restored_qa_sessions: pending.qa_sessions,
+            safety_backup,
+            restart_required: true,
+        })
+    }
+
+    fn archive_path(&self, id: &str, encrypted: bool) -> PathBuf {
+        let ext = if encrypted { "zip.enc" } else { "zip" };
+        self.backup_dir.join(format!("{}.{}", id, ext))
+    }
+
+    fn manifest_path(&self, id: &str) -> PathBuf {
+        self.backup_dir.join(format!("{}.json", id))
+    }
+
+    fn entry(&self, manifest: BackupManifest) -> Result<BackupEntry> {
+        let path = self.archive_path(&manifest.id, manifest.encrypted);
+        let size_bytes = fs::metadata(&path)?.len();
+        Ok(BackupEntry {
+            manifest,
+            path: path.display().to_string(),
+            size_bytes,
+        })
+    }
+}
+
+fn backups_root(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("backups")
+}
+
+fn pending_restore_dir(app_data_dir: &Path) -> PathBuf {
+    backups_root(app_data_dir).join("pending_restore")
+}
+
+fn read_manifest(path: &Path) -> Result<BackupManifest> {
+    let bytes = fs::read(path)?;
+    serde_json::from_slice(&bytes).map_err(|e| {
+        AppError::ParseError(format!("Invalid backup manifest {}: {}", path.display(), e))
+    })
+}
+
+fn parse_created_at(manifest: &BackupManifest) -> Option<DateTime<Local>> {
+    DateTime::parse_from_rfc3339(&manifest.created_at)
+        .ok()
+        .map(|at| at.with_timezone(&Local))
+}
+
+fn zip_err(err: zip::result::ZipError) -> AppError {
+    AppError::IoError(format!("Backup archive error: {}", err))
+}
+
+/// Pack the database snapshots in `staging`, the QA session files and the
+/// manifest into one archive. Fills in `manifest.qa_session_files`.
+fn write_archive(
+    archive: &Path,
+    staging: &Path,
+    manifest: &mut BackupManifest,
+    qa_sessions_dir: &Path,
+) -> Result<()> {
+    let mut zip = ZipWriter::new(File::create(archive)?);
+    let deflated = FileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .large_file(true);
+
+    for db in &manifest.databases {
+        zip.start_file(format!("databases/{}.db", db.key), deflated)
+            .map_err(zip_err)?;
+        std::io::copy(
+            &mut File::open(staging.join(format!("{}.db", db.key)))?,
+            &mut zip,
+        )?;
+    }
+
+    if manifest.includes_qa_sessions && qa_sessions_dir.exists() {
+        // Screenshots are already compressed
+        let stored = FileOptions::default()
+            .compression_method(CompressionMethod::Stored)
+            .large_file(true);
+        manifest.qa_session_files = add_dir(&mut zip, qa_sessions_dir, QA_SESSIONS_DIR, stored)?;
+    }
+
+    let manifest_json = serde_json::to_vec_pretty(manifest)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize manifest: {}", e)))?;
+    zip.start_file(MANIFEST_NAME, deflated).map_err(zip_err)?;
+    zip.write_all(&manifest_json)?;
+
+    zip.finish().map_err(zip_err)?.sync_all()?;
+    Ok(())
+}
+
+fn add_dir(
+    zip: &mut ZipWriter<File>,
+    dir: &Path,
+    prefix: &str,
+    options: FileOptions,
+) -> Result<usize> {
+    let mut count = 0;
+    for item in fs::read_dir(dir)? {
+        let item = item?;
+        let name = format!("{}/{}", prefix, item.file_name().to_string_lossy());
+        let file_type = item.file_type()?;
+        if file_type.is_dir() {
+            count += add_dir(zip, &item.path(), &name, options)?;
+        } else if file_type.is_file() {
+            zip.start_file(name, options).map_err(zip_err)?;
+            std::io::copy(&mut File::open(item.path())?, zip)?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Extract the selected databases and QA files into `backups/pending_restore`.
+/// A restore that was staged but not applied yet is replaced.
+fn stage_restore(
+    app_data_dir: &Path,
+    archive: &Path,
+    key: Option<[u8; KEY_LEN]>,
+    pending: &PendingRestore,
+) -> Result<()> {
+    let target = pending_restore_dir(app_data_dir);
+    let staging = backups_root(app_data_dir).join(".pending_restore.tmp");
+    let _ = fs::remove_dir_all(&staging);
+    fs::create_dir_all(staging.join("databases"))?;
+
+    let result = (|| {
+        let archive = match key {
+            Some(key) => {
+                let opened = staging.join("archive.zip");
+                crypto::decrypt_file(archive, &opened, &key)?;
+                opened
+            }
+            None => archive.to_path_buf(),
+        };
+        let mut zip = ZipArchive::new(File::open(&archive)?).map_err(zip_err)?;
+
+        for db in &pending.databases {
+            let dest = staging.join("databases").join(format!("{}.db", db));
+            let mut file = zip
+                .by_name(&format!("databases/{}.db", db))
+                .map_err(zip_err)?;
+            std::io::copy(&mut file, &mut File::create(&dest)?)?;
+
+            let mut header = [0u8; 16];
+            File::open(&dest)?.read_exact(&mut header)?;
+            if &header != SQLITE_HEADER {
+                return Err(AppError::ValidationError(format!(
+                    "The '{}' database in the backup is not a SQLite file",
+                    db
+                )));
+            }
+        }
+
+        if pending.qa_sessions {
+            fs::create_dir_all(staging.join(QA_SESSIONS_DIR))?;
+            for index in 0..zip.len() {
+                let mut file = zip.by_index(index).map_err(zip_err)?;
+                // Reject names that would escape the staging folder
+                let Some(name) = file.enclosed_name().map(Path::to_path_buf) else {
+                    continue;
+                };
+                if !name.starts_with(QA_SESSIONS_DIR) || file.is_dir() {
+                    continue;
+                }
+                let dest = staging.join(&name);
+                if let Some(parent) = dest.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                std::io::copy(&mut file, &mut File::create(&dest)?)?;
+            }
+        }
+
+        if key.is_some() {
+            fs::remove_file(&archive)?;
+        }
+        let marker = serde_json::to_vec_pretty(pending)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize restore plan: {}", e)))?;
+        fs::write(staging.join(PENDING_MARKER), marker)?;
+
+        if target.exists() {
+            fs::remove_dir_all(&target)?;
+        }
+        fs::rename(&staging, &target)?;
+        Ok(())
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_dir_all(&staging);
+    }
+    result
+}
+
+/// Move files set aside by `apply_pending_restore` back into place
+fn put_back(set_aside: &[(PathBuf, PathBuf)]) {
+    for (path, aside) in set_aside {
+        let _ = fs::rename(aside, path);
+    }
+}
+
+/// Swap in a staged restore. Must run before any database is opened.
+pub fn apply_pending_restore(
+    app_data_dir: &Path,
+    qa_sessions_dir: &Path,
+) -> Result<Option<PendingRestore>> {
+    let pending_dir = pending_restore_dir(app_data_dir);
+    let marker = pending_dir.join(PENDING_MARKER);
+    if !marker.exists() {
+        return Ok(None);
+    }
+    let pending: PendingRestore = serde_json::from_slice(&fs::read(&marker)?).map_err(|e| {
+        AppError::ParseError(format!("Invalid restore plan {}: {}", marker.display(), e))
+    })?;
+
+    let databases = app_databases(app_data_dir);
+    for key in &pending.databases {
+        let staged = pending_dir.join("databases").join(format!("{}.db", key));
+        let Some(db) = databases.iter().find(|db| db.key == key.as_str()) else {
+            continue;
+        };
+        // Already swapped in by an earlier, interrupted attempt
+        if !staged.exists() {
+            continue;
+        }
+        // A leftover WAL would be replayed on top of the restored file, so the
+        // live file goes aside together with its WAL and SHM. They are put
+        // back if the swap fails and deleted once it succeeded.
+        let mut set_aside = Vec::new();
+        for path in [
+            db.path.clone(),
+            sidecar(&db.path, "-wal"),
+            sidecar(&db.path, "-shm"),
+        ] {
+            if !path.exists() {
+                continue;
+            }
+            let aside = sidecar(&path, PRE_RESTORE_SUFFIX);
+            if let Err(err) = fs::rename(&path, &aside) {
+                put_back(&set_aside);
+                return Err(err.into());
+            }
+            set_aside.push((path, aside));
+        }
+        if let Err(err) = fs::rename(&staged, &db.path) {
+            put_back(&set_aside);
+            return Err(err.into());
+        }
+        for (_, aside) in set_aside {
+            let _ = fs::remove_file(aside);
+        }
+    }
+
+    let staged_sessions = pending_dir.join(QA_SESSIONS_DIR);
+    if pending.qa_sessions && staged_sessions.exists() {
+        let live = qa_sessions_dir;
+        let old = live.with_file_name(format!(
+            ".{}.old",
+            live.file_name().unwrap_or_default().to_string_lossy()
+        ));
+        let _ = fs::remove_dir_all(&old);
+        if live.exists() {
+            fs::rename(live, &old)?;
+        }
+        // The QA folder may have been moved to another drive
+        move_dir(&staged_sessions, live)?;
+        let _ = fs::remove_dir_all(&old);
+    }
+
+    fs::remove_dir_all(&pending_dir)?;
+    Ok(Some(pending))
+}
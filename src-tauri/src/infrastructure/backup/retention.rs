@@ -0,0 +1,107 @@
+// ============================================================
+// BACKUP RETENTION
+// ============================================================
+// Grandfather-father-son pruning for scheduled backups: the
+// newest backup of each of the last N days, ISO weeks and months
+// survives. Manual and pre-restore backups are never pruned.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Datelike, Local};
+
+use crate::domain::backup::BackupSettings;
+
+/// Ids of the backups that fall outside every retention tier.
+/// `backups` holds scheduled backups only, in any order.
+pub fn expired_backups(
+    backups: &[(String, DateTime<Local>)],
+    settings: &BackupSettings,
+) -> Vec<String> {
+    let mut sorted: Vec<&(String, DateTime<Local>)> = backups.iter().collect();
+    sorted.sort_by_key(|(_, at)| std::cmp::Reverse(*at));
+
+    let mut keep: HashSet<&str> = HashSet::new();
+    keep_newest_per_period(&sorted, settings.keep_daily, &mut keep, |at| {
+        (at.year(), at.ordinal())
+    });
+    keep_newest_per_period(&sorted, settings.keep_weekly, &mut keep, |at| {
+        let week = at.iso_week();
+        (week.year(), week.week())
+    });
+    keep_newest_per_period(&sorted, settings.keep_monthly, &mut keep, |at| {
+        (at.year(), at.month())
+    });
+
+    sorted
+        .into_iter()
+        .filter(|(id, _)| !keep.contains(id.as_str()))
+        .map(|(id, _)| id.clone())
+        .collect()
+}
+
+/// Walk newest-first and keep the first backup of each new period, up to `limit` periods
+fn keep_newest_per_period<'a>(
+    sorted: &[&'a (String, DateTime<Local>)],
+    limit: u32,
+    keep: &mut HashSet<&'a str>,
+    period: impl Fn(&DateTime<Local>) -> (i32, u32),
+) {
+    let mut seen = HashSet::new();
+    for (id, at) in sorted {
+        if seen.len() >= limit as usize {
+            break;
+        }
+        if seen.insert(period(at)) {
+            keep.insert(id.as_str());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn settings(daily: u32, weekly: u32, monthly: u32) -> BackupSettings {
+        BackupSettings {
+            keep_daily: daily,
+            keep_weekly: weekly,
+            keep_monthly: monthly,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_keeps_newest_backup_per_tier() {
+        let start = Local.with_ymd_and_hms(2026, 1, 1, 3, 0, 0).unwrap();
+        // Two backups a day for 90 days
+        let backups: Vec<(String, DateTime<Local>)> = (0..180)
+            .map(|i| {
+                let at = start + Duration::hours(12 * i);
+                (format!("b{:03}", i), at)
+            })
+            .collect();
+
+        let expired = expired_backups(&backups, &settings(2, 2, 2));
+        let kept: Vec<&str> = backups
+            .iter()
+            .map(|(id, _)| id.as_str())
+            .filter(|id| !expired.iter().any(|e| e == id))
+            .collect();
+
+        // The last backup lands on Tuesday 31 March. Daily keeps 31 and 30
+        // March (same ISO week), weekly adds Sunday 29 March and monthly
+        // adds the last backup of February.
+        assert_eq!(kept, ["b117", "b175", "b177", "b179"]);
+    }
+
+    #[test]
+    fn test_nothing_expires_within_limits() {
+        let now = Local::now();
+        let backups = vec![
+            ("a".to_string(), now),
+            ("b".to_string(), now - Duration::days(1)),
+        ];
+        assert!(expired_backups(&backups, &settings(7, 0, 0)).is_empty());
+    }
+}
@@ -1,20 +1,29 @@
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
-use tauri::Manager;
+use tauri::{Emitter, Manager};
+use tauri_plugin_deep_link::DeepLinkExt;
+use tokio::sync::OnceCell;
 use tracing::error;
 
+use crate::application::use_cases::activity::ActivityFeed;
 use crate::application::use_cases::audit_service::AuditService;
+use crate::application::use_cases::collection_encryption::CollectionEncryptionService;
 use crate::application::use_cases::conversation_service::ConversationService;
 use crate::application::use_cases::data_protection::DataProtectionService;
 use crate::application::use_cases::db_connection_manager::DbConnectionManager;
 use crate::application::use_cases::embedding_service::EmbeddingService;
 use crate::application::use_cases::qa_ai::QaAiUseCase;
+use crate::application::use_cases::qa_auth::QaAuthUseCase;
+use crate::application::use_cases::qa_redaction::QaRedaction;
+use crate::application::use_cases::qa_test_management::QaTestManagementUseCase;
+use crate::application::use_cases::rag_answer_cache::RagAnswerCache;
 use crate::application::use_cases::rate_limiter::RateLimiter;
 use crate::application::use_cases::reranker_service::RerankerService;
 use crate::application::use_cases::retrieval_service::RetrievalService;
+use crate::application::use_cases::telemetry::UsageTelemetry;
 use crate::application::use_cases::rag_analytics::SharedAnalyticsLogger;
 use crate::application::use_cases::rag_config::{SharedConfigManager, SharedFeedbackCollector};
 use crate::application::use_cases::rag_metrics::{SharedExperimentManager, SharedMetricsCollector};
@@ -25,21 +34,47 @@ use crate::application::{
 use crate::infrastructure::artifact_store::{
     ensure_daily_backup, BackupConfig, TrainingArtifactLayout,
 };
-use crate::infrastructure::config::ConfigService;
+use crate::application::use_cases::rag_config::RagConfig;
+use crate::domain::backup::{BackupReason, BackupSettings};
+use crate::domain::embedding_models::LocalEmbeddingSettings;
+use crate::domain::logging::LogSettings;
+use crate::domain::project_profiles::{profile_from_deep_link, ProjectProfileSettings};
+use crate::domain::qa_retention::QaRetentionSettings;
+use crate::domain::settings::{
+    LanguageSettings, ModelSettings, ShortcutSettings, StartupSettings, StorageSettings,
+};
+use crate::domain::trash::purge_cutoff;
+use crate::infrastructure::backup::{apply_pending_restore, BackupManager};
+use crate::infrastructure::config::{ConfigService, LlmConfigResolver, SettingsStore};
+use crate::infrastructure::db::activity::ActivityRepository;
 use crate::infrastructure::db::qa::init_qa_db;
 use crate::infrastructure::db::qa_api_calls::QaApiCallRepository;
 use crate::infrastructure::db::qa_checkpoints::QaCheckpointRepository;
 use crate::infrastructure::db::qa_events::QaEventRepository;
 use crate::infrastructure::db::qa_runs::QaRunRepository;
 use crate::infrastructure::db::qa_sessions::QaRepository;
+use crate::infrastructure::db::qa_test_sync::QaTestSyncRepository;
 use crate::infrastructure::db::rag::connection::init_rag_db;
 use crate::infrastructure::db::rag::repository::RagRepository;
 use crate::infrastructure::db::sqlite::SqliteRepository;
+use crate::infrastructure::db::telemetry::TelemetryRepository;
 use crate::infrastructure::db::training::connection::init_training_db;
+use crate::infrastructure::embedding_models::EmbeddingModelStore;
+use crate::infrastructure::node_runtime::NodeRuntime;
+use crate::infrastructure::ollama::OllamaManager;
+use crate::infrastructure::ocr_languages::OcrLanguageStore;
 use crate::infrastructure::llm_clients::{LLMClient, RouterClient};
-use crate::infrastructure::storage::{ensure_qa_sessions_root, resolve_app_data_dir};
-use crate::interfaces::http::add_log;
-use crate::interfaces::mock_server::MockServerState;
+use crate::infrastructure::logging;
+use crate::infrastructure::storage::{resolve_app_data_dir, StorageLocations};
+use crate::interfaces::{automation, background};
+use crate::interfaces::http::{add_log, HttpServerControl};
+use crate::interfaces::mock_server::{start_mock_server, MockServerConfig, MockServerState};
+use crate::interfaces::tauri::rag_commands::connectors::sync_connector;
+use crate::interfaces::tauri::rag_commands::git_repos::sync_git_repo;
+use crate::interfaces::tauri::mock_server_commands::MOCK_CONFIG_RELOADED_EMIT;
+use crate::interfaces::tauri::profile_commands::{apply_profile, spawn_apply_profile};
+use crate::interfaces::tauri::settings_commands::SETTINGS_CHANGED_EMIT;
+use crate::interfaces::tauri::trash_commands;
 use crate::interfaces::tauri::AppState;
 
 pub fn setup(app: &mut tauri::App) -> Result<(), Box<dyn Error>> {
@@ -52,22 +87,107 @@ pub fn setup(app: &mut tauri::App) -> Result<(), Box<dyn Error>> {
         err
     })?;
 
-    let qa_sessions_dir = ensure_qa_sessions_root(&app_data_dir).map_err(|err| {
-        error!(
-            error = %err,
-            qa_sessions_dir = %app_data_dir.join("qa_sessions").display(),
-            "Failed to create qa_sessions dir"
-        );
-        err
-    })?;
+    let background = background::launched_in_background();
+    if !background {
+        background::create_main_window(&app_handle).map_err(|err| {
+            error!(error = %err, "Failed to create main window");
+            err
+        })?;
+    }
+
+    if let Err(err) = crate::interfaces::tray::init(&app_handle) {
+        error!(error = %err, "Failed to create tray icon");
+    }
+    register_deep_links(app);
 
     configure_ocr(&app_handle, &logs);
-    ensure_training_artifacts(&app_data_dir, &logs);
-    bootstrap_databases_and_state(app_handle, app_data_dir, qa_sessions_dir, logs);
+    bootstrap_databases_and_state(app_handle, app_data_dir, logs, background);
 
     Ok(())
 }
 
+/// Create the QA session folder and let the webview load screenshots from it.
+/// A relocated folder on a missing drive is logged instead of failing startup.
+fn prepare_qa_sessions_dir(
+    app_handle: &tauri::AppHandle,
+    storage: &StorageLocations,
+    logs: &Arc<Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+) {
+    match storage.ensure_qa_sessions_dir() {
+        Ok(dir) => {
+            if let Err(err) = app_handle.asset_protocol_scope().allow_directory(&dir, true) {
+                add_log(
+                    logs,
+                    "WARN",
+                    "QA",
+                    &format!("Screenshots in {} cannot be previewed: {err}", dir.display()),
+                );
+            }
+        }
+        Err(err) => {
+            let dir = storage.qa_sessions_dir();
+            error!(error = %err, qa_sessions_dir = %dir.display(), "Failed to create qa_sessions dir");
+            add_log(
+                logs,
+                "ERROR",
+                "QA",
+                &format!("QA session folder {} is unavailable: {err}", dir.display()),
+            );
+        }
+    }
+}
+
+/// Apply the profiles named by `gadogado://profile/<id>` links opened while
+/// the app runs and run the automation commands of other `gadogado://`
+/// links. A link that launched it is read with the launch arguments.
+fn register_deep_links(app: &tauri::App) {
+    #[cfg(any(windows, target_os = "linux"))]
+    if let Err(err) = app.deep_link().register_all() {
+        error!(error = %err, "Failed to register deep link schemes");
+    }
+    let app_handle = app.handle().clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            if let Some(profile_id) = profile_from_deep_link(url.as_str()) {
+                spawn_apply_profile(app_handle.clone(), profile_id);
+            } else {
+                automation::spawn_from_link(&app_handle, url.as_str());
+            }
+        }
+    });
+}
+
+/// Swap in a restore staged by `backup_restore` before anything opens the databases
+fn restore_staged_backup(
+    storage: &StorageLocations,
+    logs: &Arc<Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+) {
+    match apply_pending_restore(storage.app_data_dir(), &storage.qa_sessions_dir()) {
+        Ok(Some(restore)) => add_log(
+            logs,
+            "INFO",
+            "Backup",
+            &format!(
+                "Restored backup {} ({}{})",
+                restore.backup_id,
+                restore.databases.join(", "),
+                if restore.qa_sessions {
+                    ", QA session files"
+                } else {
+                    ""
+                }
+            ),
+        ),
+        Ok(None) => {}
+        Err(err) => add_log(
+            logs,
+            "ERROR",
+            "Backup",
+            &format!("Failed to apply staged restore: {err}"),
+        ),
+    }
+}
+
 fn configure_ocr(app_handle: &tauri::AppHandle, logs: &Arc<Mutex<Vec<crate::interfaces::http::LogEntry>>>) {
     let os_folder = match std::env::consts::OS {
         "windows" => "windows",
@@ -179,10 +299,9 @@ fn configure_ocr(app_handle: &tauri::AppHandle, logs: &Arc<Mutex<Vec<crate::inte
 }
 
 fn ensure_training_artifacts(
-    app_data_dir: &PathBuf,
+    training_artifacts: &TrainingArtifactLayout,
     logs: &Arc<Mutex<Vec<crate::interfaces::http::LogEntry>>>,
 ) {
-    let training_artifacts = TrainingArtifactLayout::new(app_data_dir);
     if let Err(err) = training_artifacts.ensure() {
         add_log(
             logs,
@@ -203,32 +322,203 @@ fn ensure_training_artifacts(
     }
 }
 
+fn open_settings_store(
+    app_handle: &tauri::AppHandle,
+    app_data_dir: &PathBuf,
+    logs: &Arc<Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+) -> Arc<SettingsStore> {
+    let settings_path = app_data_dir.join("settings.json");
+    let store = SettingsStore::open(settings_path.clone()).unwrap_or_else(|err| {
+        let (store, quarantined) = SettingsStore::open_quarantined(settings_path)
+            .expect("Failed to initialize settings store");
+        add_log(
+            logs,
+            "ERROR",
+            "Settings",
+            &format!(
+                "Failed to load settings ({err}); moved to {} and started from defaults",
+                quarantined.display()
+            ),
+        );
+        store
+    });
+
+    let legacy_rag = store.adopt_legacy_file::<RagConfig>(&app_data_dir.join("rag_config.json"));
+    let legacy_mock =
+        store.adopt_legacy_file::<MockServerConfig>(&app_data_dir.join("mock_server.json"));
+    for (name, result) in [("RAG", legacy_rag), ("mock server", legacy_mock)] {
+        match result {
+            Ok(true) => add_log(
+                logs,
+                "INFO",
+                "Settings",
+                &format!("Migrated legacy {name} config into settings store"),
+            ),
+            Ok(false) => {}
+            Err(err) => add_log(
+                logs,
+                "ERROR",
+                "Settings",
+                &format!("Failed to migrate legacy {name} config: {err}"),
+            ),
+        }
+    }
+
+    let emitter = app_handle.clone();
+    let emit_logs = logs.clone();
+    store.subscribe(move |change| {
+        if let Err(err) = emitter.emit(SETTINGS_CHANGED_EMIT, change) {
+            add_log(
+                &emit_logs,
+                "WARN",
+                "Settings",
+                &format!("Failed to emit settings change: {}", err),
+            );
+        }
+    });
+
+    add_log(
+        logs,
+        "INFO",
+        "Settings",
+        &format!("Settings loaded from {}", store.path().display()),
+    );
+    Arc::new(store)
+}
+
+/// Apply stored log levels and start writing log files under `<app data>/logs`
+fn configure_logging(
+    settings: &SettingsStore,
+    app_data_dir: &PathBuf,
+    logs: &Arc<Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+) {
+    let sink = logging::sink();
+    sink.configure(settings.get::<LogSettings>());
+
+    let log_dir = app_data_dir.join("logs");
+    match sink.attach(&log_dir) {
+        Ok(()) => add_log(
+            logs,
+            "INFO",
+            "Logging",
+            &format!("Writing logs to {}", log_dir.display()),
+        ),
+        Err(err) => add_log(
+            logs,
+            "ERROR",
+            "Logging",
+            &format!("Failed to open log directory {}: {}", log_dir.display(), err),
+        ),
+    }
+}
+
+/// RAG and training databases: schema upgrades, FTS backfill and, while
+/// scheduled backups are off, the daily training backup. Run at startup, or
+/// on first use in background mode.
+pub struct DeferredInit {
+    rag_db_path: PathBuf,
+    training_db_path: PathBuf,
+    app_data_dir: PathBuf,
+    logs: Arc<Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+    daily_training_backup: bool,
+    done: OnceCell<()>,
+}
+
+impl DeferredInit {
+    fn new(
+        app_data_dir: &Path,
+        logs: Arc<Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+        daily_training_backup: bool,
+    ) -> Self {
+        Self {
+            rag_db_path: app_data_dir.join("rag_sense.db"),
+            training_db_path: app_data_dir.join("training.db"),
+            app_data_dir: app_data_dir.to_path_buf(),
+            logs,
+            daily_training_backup,
+            done: OnceCell::new(),
+        }
+    }
+
+    /// Initialize once; later calls return immediately. A failed attempt is retried next time.
+    pub async fn ensure(&self) -> Result<(), String> {
+        self.done
+            .get_or_try_init(|| async {
+                init_rag_db(&self.rag_db_path)
+                    .await
+                    .map_err(|err| format!("Failed to initialize RAG database: {err}"))?;
+                init_training_db(&self.training_db_path)
+                    .await
+                    .map_err(|err| format!("Failed to initialize Training database: {err}"))?;
+
+                if self.daily_training_backup {
+                    spawn_daily_training_backup(
+                        self.app_data_dir.clone(),
+                        self.training_db_path.clone(),
+                        self.logs.clone(),
+                    );
+                }
+                Ok(())
+            })
+            .await
+            .map(|_| ())
+    }
+
+    /// Whether the databases are initialized, without initializing them
+    pub fn is_ready(&self) -> bool {
+        self.done.initialized()
+    }
+}
+
 fn bootstrap_databases_and_state(
     app_handle: tauri::AppHandle,
     app_data_dir: PathBuf,
-    qa_sessions_dir: PathBuf,
     logs: Arc<Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+    background: bool,
 ) {
     let qa_db_path = app_data_dir.join("qa_recorder.db");
     let rag_db_path = app_data_dir.join("rag_sense.db");
-    let training_db_path = app_data_dir.join("training.db");
+
+    let settings = open_settings_store(&app_handle, &app_data_dir, &logs);
+    configure_logging(&settings, &app_data_dir, &logs);
+    // Scheduled backups include the training database
+    let deferred_init = DeferredInit::new(
+        &app_data_dir,
+        logs.clone(),
+        !settings.get::<BackupSettings>().scheduled,
+    );
+
+    let storage = Arc::new(StorageLocations::new(
+        &app_data_dir,
+        &settings.get::<StorageSettings>(),
+    ));
+    restore_staged_backup(&storage, &logs);
+    prepare_qa_sessions_dir(&app_handle, &storage, &logs);
+    ensure_training_artifacts(&storage.training_layout(), &logs);
+
+    let backups = BackupManager::new(&app_data_dir, storage.clone());
 
     let db_path = app_data_dir.join("promptbridge.db");
     let db_path_str = db_path.to_string_lossy().replace('\\', "/");
     let db_url = format!("sqlite://{}", db_path_str);
 
     tauri::async_runtime::block_on(async move {
-        init_rag_db(&rag_db_path)
-            .await
-            .expect("Failed to initialize RAG database");
+        if background {
+            add_log(
+                &logs,
+                "INFO",
+                "System",
+                "Started in background; RAG and training databases load when the main window opens",
+            );
+        } else {
+            deferred_init
+                .ensure()
+                .await
+                .expect("Failed to initialize databases");
+        }
         init_qa_db(&qa_db_path)
             .await
             .expect("Failed to initialize QA database");
-        init_training_db(&training_db_path)
-            .await
-            .expect("Failed to initialize Training database");
-
-        spawn_daily_training_backup(app_data_dir.clone(), training_db_path.clone(), logs.clone());
 
         let qa_repo = QaRepository::connect(&qa_db_path)
             .await
@@ -245,6 +535,9 @@ fn bootstrap_databases_and_state(
         let qa_api_call_repo = QaApiCallRepository::connect(&qa_db_path)
             .await
             .expect("Failed to connect QA API calls database");
+        let qa_test_sync_repo = QaTestSyncRepository::connect(&qa_db_path)
+            .await
+            .expect("Failed to connect QA test sync database");
 
         let rag_repo = RagRepository::connect(&rag_db_path)
             .await
@@ -253,36 +546,76 @@ fn bootstrap_databases_and_state(
         let repository = SqliteRepository::init(&db_url)
             .await
             .expect("Failed to initialize database");
+        let activity_repo = ActivityRepository::init(&db_path)
+            .await
+            .expect("Failed to initialize activity feed");
+        let telemetry_repo = TelemetryRepository::init(&db_path)
+            .await
+            .expect("Failed to initialize usage stats");
 
         let qa_repo_arc = Arc::new(qa_repo);
         let qa_event_repo_arc = Arc::new(qa_event_repo);
         let qa_checkpoint_repo_arc = Arc::new(qa_checkpoint_repo);
         let qa_run_repo_arc = Arc::new(qa_run_repo);
         let qa_api_call_repo_arc = Arc::new(qa_api_call_repo);
+        let qa_test_sync_repo_arc = Arc::new(qa_test_sync_repo);
         let rag_repo_arc = Arc::new(rag_repo);
         let repository_arc = Arc::new(repository);
 
-        let mock_server = Arc::new(MockServerState::new(
-            app_data_dir.join("mock_server.json"),
-            logs.clone(),
-        ));
+        // Entries are queued by the subsystems and written in order in the background
+        let (activity_feed, activity_writer) = ActivityFeed::new(Arc::new(activity_repo));
+        tauri::async_runtime::spawn(activity_writer.run());
+        let activity_feed = Arc::new(activity_feed);
+        let (telemetry, telemetry_writer) =
+            UsageTelemetry::new(settings.clone(), Arc::new(telemetry_repo));
+        tauri::async_runtime::spawn(telemetry_writer.run());
+
+        let mock_emitter = app_handle.clone();
+        let mock_logs = logs.clone();
+        let mock_server = Arc::new(
+            MockServerState::with_store(settings.clone(), logs.clone())
+                .with_activity(activity_feed.clone())
+                .with_reload_listener(move |reload| {
+                    if let Err(err) = mock_emitter.emit(MOCK_CONFIG_RELOADED_EMIT, reload) {
+                        add_log(
+                            &mock_logs,
+                            "WARN",
+                            "MockServer",
+                            &format!("Failed to emit mock config reload: {err}"),
+                        );
+                    }
+                }),
+        );
+        mock_server.watch_config_source();
 
-        let llm_client: Arc<dyn LLMClient + Send + Sync> = Arc::new(RouterClient::new());
+        let ollama = Arc::new(OllamaManager::new(settings.clone()));
+        let llm_client: Arc<dyn LLMClient + Send + Sync> =
+            Arc::new(RouterClient::new().with_ollama_manager(ollama.clone()));
 
         let translate_use_case = TranslateUseCase::new(llm_client.clone(), repository_arc.clone());
         let enhance_use_case = EnhanceUseCase::new(llm_client.clone(), repository_arc.clone());
         let typegen_use_case = TypeGenUseCase::new(llm_client.clone());
 
-        let qa_session_use_case = QaSessionUseCase::new(qa_repo_arc.clone(), qa_sessions_dir);
-        let qa_event_use_case = QaEventUseCase::new(qa_event_repo_arc.clone());
-        let qa_run_use_case = QaRunUseCase::new(qa_run_repo_arc.clone());
-        let qa_api_call_use_case = QaApiCallUseCase::new(qa_api_call_repo_arc.clone());
+        let qa_session_use_case = QaSessionUseCase::new(qa_repo_arc.clone(), storage.clone());
+        let qa_redaction = Arc::new(QaRedaction::new(settings.clone()));
+        let qa_event_use_case =
+            QaEventUseCase::new(qa_event_repo_arc.clone(), qa_redaction.clone());
+        let qa_run_use_case = QaRunUseCase::new(qa_run_repo_arc.clone(), activity_feed.clone());
+        let qa_api_call_use_case =
+            QaApiCallUseCase::new(qa_api_call_repo_arc.clone(), qa_redaction);
         let qa_ai_use_case = QaAiUseCase::new(
             qa_repo_arc.clone(),
             qa_event_repo_arc.clone(),
             qa_checkpoint_repo_arc.clone(),
+            qa_api_call_repo_arc.clone(),
             llm_client.clone(),
+            settings.clone(),
+        );
+        let qa_test_management_use_case = QaTestManagementUseCase::new(
+            qa_checkpoint_repo_arc.clone(),
+            qa_test_sync_repo_arc.clone(),
         );
+        let qa_auth_use_case = QaAuthUseCase::new(settings.clone());
 
         let embedding_config = crate::domain::llm_config::LLMConfig {
             provider: crate::domain::llm_config::LLMProvider::Local,
@@ -292,20 +625,62 @@ fn bootstrap_databases_and_state(
             max_tokens: Some(1024),
             temperature: Some(0.7),
         };
-        let embedding_service = Arc::new(EmbeddingService::new(embedding_config));
+        let models = settings.get::<ModelSettings>();
+        let languages = settings.get::<LanguageSettings>();
+        let embedding_models = Arc::new(EmbeddingModelStore::new(
+            app_data_dir.join("embedding_models"),
+        ));
+        let embedding_service = Arc::new(
+            EmbeddingService::new(models.embedding.unwrap_or(embedding_config))
+                .with_model_store(embedding_models),
+        );
+        embedding_service.set_execution_providers(
+            settings.get::<LocalEmbeddingSettings>().provider_order(),
+        );
+        // Languages downloaded in earlier runs replace the bundled tessdata folder
+        let ocr_languages = Arc::new(OcrLanguageStore::new(
+            app_data_dir.join("tessdata"),
+            std::env::var_os("TESSDATA_PREFIX").map(PathBuf::from),
+        ));
+        match ocr_languages.activate() {
+            Ok(Some(dir)) => add_log(
+                &logs,
+                "INFO",
+                "RAG",
+                &format!("Using downloaded tessdata at {}", dir.display()),
+            ),
+            Ok(None) => {}
+            Err(err) => add_log(
+                &logs,
+                "WARN",
+                "RAG",
+                &format!("Failed to prepare downloaded tessdata: {}", err),
+            ),
+        }
+        let node_runtime = Arc::new(NodeRuntime::new(
+            app_data_dir.join("node_runtime"),
+            app_handle.path().resource_dir().ok(),
+        ));
+        let config_manager = SharedConfigManager::with_store(settings.clone());
         let rag_ingestion_use_case = RagIngestionUseCase::with_embedding_service(
             rag_repo_arc.clone(),
             embedding_service.clone(),
-        );
+        )
+        .with_config_manager(config_manager.clone())
+        .with_node_runtime(node_runtime.clone());
         let retrieval_service = Arc::new(RetrievalService::new(
             rag_repo_arc.clone(),
             embedding_service.clone(),
+            config_manager.clone(),
+        ));
+        let rag_answer_cache = Arc::new(RagAnswerCache::new(
+            rag_repo_arc.clone(),
+            config_manager.clone(),
         ));
 
         let metrics_collector = SharedMetricsCollector::new();
         let experiment_manager = SharedExperimentManager::new();
         let analytics_logger = SharedAnalyticsLogger::new(2000);
-        let config_manager = SharedConfigManager::new(app_data_dir.clone());
         let feedback_collector = SharedFeedbackCollector::new(1000);
 
         let conversation_service = Arc::new(ConversationService::new(rag_repo_arc.clone()));
@@ -327,19 +702,26 @@ fn bootstrap_databases_and_state(
             qa_ai_use_case,
             qa_run_use_case,
             qa_api_call_use_case,
+            qa_test_management_use_case,
+            qa_auth_use_case,
             rag_ingestion_use_case,
             retrieval_service,
+            rag_answer_cache,
             embedding_service,
-            qa_session_id: Mutex::new(None),
-            qa_recorder: Mutex::new(None),
+            qa_sessions: Mutex::new(HashMap::new()),
+            qa_recorders: Mutex::new(HashMap::new()),
             repository: repository_arc,
             rag_repository: rag_repo_arc,
             config_service: ConfigService::new(),
+            model_resolver: LlmConfigResolver::new(settings.clone()),
             llm_client: llm_client.clone(),
             mock_server,
-            last_config: Mutex::new(crate::domain::llm_config::LLMConfig::default()),
-            preferred_source: Mutex::new("Auto Detect".to_string()),
-            preferred_target: Mutex::new("English".to_string()),
+            http_server: HttpServerControl::new(),
+            last_config: Mutex::new(models.llm.unwrap_or_default()),
+            preferred_source: Mutex::new(languages.source),
+            preferred_target: Mutex::new(languages.target),
+            quick_action_capture: Mutex::new(None),
+            settings: settings.clone(),
             logs: logs.clone(),
             distill_trainers: Mutex::new(HashMap::new()),
             distill_trainer_launches: Mutex::new(HashSet::new()),
@@ -354,39 +736,214 @@ fn bootstrap_databases_and_state(
             rate_limiter,
             data_protection,
             reranker_service,
+            deferred_init,
+            backups,
+            storage,
+            ocr_languages,
+            node_runtime,
+            ollama,
+            activity_feed,
+            telemetry: Arc::new(telemetry),
         };
         let state_arc = Arc::new(state);
 
         app_handle.manage(state_arc.clone());
 
         // Start Actix server
-        let logs_for_server = logs.clone();
-        let server = crate::interfaces::http::start_server(state_arc.clone(), logs_for_server)
-            .expect("Failed to start Actix server");
-        tokio::spawn(server);
-
-        add_log(
-            &logs,
-            "INFO",
-            "System",
-            "Backend initialized and HTTP server started on :3001",
-        );
+        match crate::interfaces::http::start_server(state_arc.clone()).await {
+            Ok(address) => add_log(
+                &logs,
+                "INFO",
+                "System",
+                &format!("Backend initialized and HTTP server started on {}", address),
+            ),
+            Err(err) => add_log(
+                &logs,
+                "ERROR",
+                "System",
+                &format!("Backend initialized but HTTP server failed to start: {}", err),
+            ),
+        }
 
+        let shortcuts = settings.get::<ShortcutSettings>();
         if let Err(err) = crate::register_shortcuts(
             &app_handle,
-            true,
-            "Ctrl + Alt + T",
-            "Ctrl + Alt + E",
-            "Ctrl + Alt + P",
-            "Ctrl + Alt + R",
+            shortcuts.enabled,
+            &shortcuts.translate,
+            &shortcuts.enhance,
+            &shortcuts.popup,
+            &shortcuts.terminal,
         ) {
             add_log(
                 &logs,
                 "ERROR",
                 "Shortcut",
-                &format!("Failed to register default shortcuts: {err}"),
+                &format!("Failed to register saved shortcuts: {err}"),
+            );
+        }
+
+        let input = crate::infrastructure::desktop_input::capabilities();
+        if shortcuts.enabled && input.clipboard_only() {
+            add_log(
+                &logs,
+                "WARN",
+                "Shortcut",
+                &format!(
+                    "{}: copy text before using a shortcut and paste results manually",
+                    input.summary()
+                ),
+            );
+        }
+
+        let startup = settings.get::<StartupSettings>();
+        if let Err(err) = background::sync_autostart(&app_handle, startup.launch_at_login) {
+            add_log(
+                &logs,
+                "ERROR",
+                "System",
+                &format!("Failed to update launch at login: {err}"),
             );
         }
+        let mut launch_args: Vec<String> = std::env::args().collect();
+        // On macOS the link that launched the app is not among the arguments
+        if let Ok(Some(urls)) = app_handle.deep_link().get_current() {
+            launch_args.extend(urls.iter().map(|url| url.to_string()));
+        }
+        let launch_profile = settings
+            .get::<ProjectProfileSettings>()
+            .launch_profile(&launch_args);
+        if let Some(profile_id) = launch_profile {
+            // Failures are logged by `apply_profile`
+            let _ = apply_profile(&app_handle, &state_arc, &profile_id).await;
+        }
+        let mock_running = state_arc.mock_server.server.lock().unwrap().is_some();
+        if startup.start_mock_server && !mock_running {
+            if let Err(err) = start_mock_server(state_arc.mock_server.clone()).await {
+                add_log(
+                    &logs,
+                    "ERROR",
+                    "MockServer",
+                    &format!("Failed to start mock server on launch: {err}"),
+                );
+            }
+        }
+
+        if let Ok(cwd) = std::env::current_dir() {
+            automation::spawn_from_args(&app_handle, &launch_args, &cwd);
+        }
+
+        crate::interfaces::tray::refresh(&app_handle);
+        spawn_backup_scheduler(state_arc.clone());
+        spawn_connector_sync_scheduler(state_arc.clone());
+        spawn_qa_retention_cleanup(state_arc.clone());
+        if !background {
+            spawn_retrieval_cache_warmup(state_arc.clone());
+            spawn_trash_purge(app_handle.clone(), state_arc.clone());
+        }
+    });
+}
+
+/// Unlock encrypted collections whose keys are in the keychain, then load
+/// retrieval results persisted by earlier runs into memory. Needs the RAG
+/// database, so in background mode this runs once the main window has
+/// loaded it.
+pub(crate) fn spawn_retrieval_cache_warmup(state: Arc<AppState>) {
+    tauri::async_runtime::spawn(async move {
+        match CollectionEncryptionService::new(&state.rag_repository, &state.config_service)
+            .unlock_from_keychain()
+            .await
+        {
+            Ok(0) => {}
+            Ok(locked) => add_log(
+                &state.logs,
+                "INFO",
+                "RAG",
+                &format!("{locked} encrypted collections need their passphrase to unlock"),
+            ),
+            Err(err) => add_log(
+                &state.logs,
+                "WARN",
+                "RAG",
+                &format!("Failed to unlock encrypted collections: {err}"),
+            ),
+        }
+
+        if let Err(err) = state.rag_answer_cache.prune().await {
+            add_log(
+                &state.logs,
+                "WARN",
+                "RAG",
+                &format!("Failed to prune the answer cache: {err}"),
+            );
+        }
+
+        match state.retrieval_service.warm_cache().await {
+            Ok(0) => {}
+            Ok(loaded) => add_log(
+                &state.logs,
+                "INFO",
+                "RAG",
+                &format!("Retrieval cache warmed with {loaded} persisted queries"),
+            ),
+            Err(err) => add_log(
+                &state.logs,
+                "WARN",
+                "RAG",
+                &format!("Failed to load the persisted retrieval cache: {err}"),
+            ),
+        }
+    });
+}
+
+/// Apply the QA retention limits once at startup when they are set to
+fn spawn_qa_retention_cleanup(state: Arc<AppState>) {
+    let policy = state.settings.get::<QaRetentionSettings>();
+    if !policy.cleanup_on_startup || !policy.is_enabled() {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        match state
+            .qa_session_use_case
+            .cleanup_storage(&policy, &HashSet::new(), false)
+            .await
+        {
+            Ok(report) => {
+                let level = if report.errors.is_empty() {
+                    "INFO"
+                } else {
+                    "WARN"
+                };
+                add_log(
+                    &state.logs,
+                    level,
+                    "QA",
+                    &crate::interfaces::tauri::qa::sessions::cleanup_summary(&report),
+                );
+            }
+            Err(err) => add_log(
+                &state.logs,
+                "ERROR",
+                "QA",
+                &format!("QA storage cleanup failed: {err}"),
+            ),
+        }
+    });
+}
+
+/// Purge the QA sessions, documents and datasets that have been in the
+/// trash longer than the retention period
+fn spawn_trash_purge(app: tauri::AppHandle, state: Arc<AppState>) {
+    tauri::async_runtime::spawn(async move {
+        let cutoff = purge_cutoff(chrono::Utc::now().timestamp_millis());
+        match trash_commands::purge_trash(&app, &state, cutoff).await {
+            Ok(report) => trash_commands::log_purge(&state, &report),
+            Err(err) => add_log(
+                &state.logs,
+                "ERROR",
+                "Trash",
+                &format!("Trash purge failed: {err}"),
+            ),
+        }
     });
 }
 
@@ -434,3 +991,162 @@ fn spawn_daily_training_backup(
         }
     });
 }
+
+/// How often the scheduler checks whether a scheduled backup is due
+const BACKUP_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+
+/// Take a scheduled backup whenever the newest one is older than the
+/// configured interval, then prune by the retention tiers. Settings are
+/// re-read on every check so changes apply without a restart.
+fn spawn_backup_scheduler(state: Arc<AppState>) {
+    tauri::async_runtime::spawn(async move {
+        // Let startup settle before snapshotting
+        tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+        loop {
+            let settings = state.settings.get::<BackupSettings>();
+            if settings.scheduled {
+                run_scheduled_backup(&state, &settings).await;
+            }
+            tokio::time::sleep(BACKUP_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+async fn run_scheduled_backup(state: &Arc<AppState>, settings: &BackupSettings) {
+    let due = match state.backups.last_scheduled() {
+        Ok(Some(last)) => {
+            chrono::Local::now() - last >= chrono::Duration::hours(settings.interval_hours as i64)
+        }
+        Ok(None) => true,
+        Err(err) => {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "Backup",
+                &format!("Failed to list backups: {err}"),
+            );
+            return;
+        }
+    };
+    if !due {
+        return;
+    }
+
+    let key = if settings.encrypt {
+        match state.config_service.backup_encryption_key() {
+            Ok(key) => Some(key),
+            Err(err) => {
+                add_log(
+                    &state.logs,
+                    "ERROR",
+                    "Backup",
+                    &format!("Scheduled backup skipped, encryption key unavailable: {err}"),
+                );
+                return;
+            }
+        }
+    } else {
+        None
+    };
+
+    match state
+        .backups
+        .create(BackupReason::Scheduled, settings.include_qa_sessions, key)
+        .await
+    {
+        Ok(entry) => add_log(
+            &state.logs,
+            "INFO",
+            "Backup",
+            &format!(
+                "Scheduled backup created: {} ({} bytes)",
+                entry.path, entry.size_bytes
+            ),
+        ),
+        Err(err) => {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "Backup",
+                &format!("Scheduled backup failed: {err}"),
+            );
+            return;
+        }
+    }
+
+    match state.backups.prune(settings).await {
+        Ok(deleted) if !deleted.is_empty() => add_log(
+            &state.logs,
+            "INFO",
+            "Backup",
+            &format!("Removed {} expired backup(s)", deleted.len()),
+        ),
+        Ok(_) => {}
+        Err(err) => add_log(
+            &state.logs,
+            "ERROR",
+            "Backup",
+            &format!("Failed to prune old backups: {err}"),
+        ),
+    }
+}
+
+/// How often the scheduler checks whether a connector or git repository sync
+/// is due
+const CONNECTOR_SYNC_CHECK_INTERVAL: std::time::Duration =
+    std::time::Duration::from_secs(5 * 60);
+
+/// Sync RAG connectors and git repositories whose interval has passed, one at
+/// a time. Waits while the RAG database is deferred in background mode.
+fn spawn_connector_sync_scheduler(state: Arc<AppState>) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+        loop {
+            if state.deferred_init.is_ready() {
+                run_due_connector_syncs(&state).await;
+                run_due_git_syncs(&state).await;
+            }
+            tokio::time::sleep(CONNECTOR_SYNC_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+async fn run_due_connector_syncs(state: &Arc<AppState>) {
+    let connectors = match state.rag_repository.list_connectors().await {
+        Ok(connectors) => connectors,
+        Err(err) => {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "RAG",
+                &format!("Failed to list connectors: {err}"),
+            );
+            return;
+        }
+    };
+    let now = chrono::Utc::now().timestamp();
+    for connector in connectors.iter().filter(|c| c.sync_due(now)) {
+        // Failures are logged and recorded on the connector
+        let _ = sync_connector(state, connector).await;
+    }
+}
+
+async fn run_due_git_syncs(state: &Arc<AppState>) {
+    let repos = match state.rag_repository.list_git_repos().await {
+        Ok(repos) => repos,
+        Err(err) => {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "RAG",
+                &format!("Failed to list git repositories: {err}"),
+            );
+            return;
+        }
+    };
+    let now = chrono::Utc::now().timestamp();
+    for repo in repos.iter().filter(|r| r.sync_due(now)) {
+        // Failures are logged and recorded on the repository source
+        let _ = sync_git_repo(state, repo).await;
+    }
+}
@@ -1,6 +1,43 @@
-use crate::domain::error::Result;
+pub mod model_resolver;
+pub mod settings_store;
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::llm_config::LLMConfig;
+use crate::domain::qa_auth::QaAuthSecret;
+use crate::domain::qa_test_management::TestManagementProvider;
+use crate::infrastructure::backup::crypto::{generate_key, KEY_LEN};
+use crate::infrastructure::security::collection_cipher::KEY_LEN as COLLECTION_KEY_LEN;
 use crate::infrastructure::security::keyring::KeyringManager;
 
+pub use model_resolver::LlmConfigResolver;
+pub use settings_store::SettingsStore;
+
+/// Keychain entry holding the local HTTP API bearer token
+const HTTP_API_TOKEN_ENTRY: &str = "http-api-token";
+
+/// Keychain entry holding the hex-encoded backup encryption key
+const BACKUP_KEY_ENTRY: &str = "backup-encryption-key";
+
+/// Keychain entry holding the hex-encoded key of an encrypted RAG collection
+fn collection_key_entry(collection_id: i64) -> String {
+    format!("rag-collection-key-{}", collection_id)
+}
+
+/// Keychain entry holding the Xray client secret or the TestRail API key
+fn test_management_entry(provider: TestManagementProvider) -> String {
+    format!("qa-{}-secret", provider.as_str())
+}
+
+/// Keychain entry holding a client secret or password of a QA auth profile
+fn qa_auth_entry(profile_id: &str, secret: QaAuthSecret) -> String {
+    format!("qa-auth-{}-{}", profile_id.trim(), secret.as_str())
+}
+
+/// Keychain entry holding the API token of a RAG connector
+fn rag_connector_token_entry(connector_id: i64) -> String {
+    format!("rag-connector-token-{}", connector_id)
+}
+
 pub struct ConfigService {
     keyring: KeyringManager,
 }
@@ -12,15 +49,203 @@ impl ConfigService {
         }
     }
 
+    /// Store a provider API key in the OS keychain. An empty key deletes it.
     pub fn save_api_key(&self, provider: &str, key: &str) -> Result<()> {
-        self.keyring.set_secret(provider, key)
+        let key = key.trim();
+        if key.is_empty() {
+            return self.delete_api_key(provider);
+        }
+        self.keyring.set_secret(&Self::entry_name(provider), key)
     }
 
     pub fn get_api_key(&self, provider: &str) -> Result<String> {
-        self.keyring.get_secret(provider)
+        self.keyring.get_secret(&Self::entry_name(provider))
+    }
+
+    /// API key for `provider`, or `None` when the keychain has no entry
+    pub fn find_api_key(&self, provider: &str) -> Result<Option<String>> {
+        self.keyring.find_secret(&Self::entry_name(provider))
     }
 
     pub fn delete_api_key(&self, provider: &str) -> Result<()> {
-        self.keyring.delete_secret(provider)
+        match self.find_api_key(provider)? {
+            Some(_) => self.keyring.delete_secret(&Self::entry_name(provider)),
+            None => Ok(()),
+        }
+    }
+
+    /// Fill in a missing API key from the keychain
+    pub fn with_api_key(&self, config: &LLMConfig) -> Result<LLMConfig> {
+        let mut config = config.clone();
+        let has_key = config
+            .api_key
+            .as_deref()
+            .is_some_and(|key| !key.trim().is_empty());
+        if !has_key {
+            config.api_key = self.find_api_key(&config.provider.to_string())?;
+        }
+        Ok(config)
+    }
+
+    /// Bearer token for the local HTTP API, created on first use
+    pub fn http_api_token(&self) -> Result<String> {
+        match self.keyring.find_secret(HTTP_API_TOKEN_ENTRY)? {
+            Some(token) if !token.trim().is_empty() => Ok(token),
+            _ => self.rotate_http_api_token(),
+        }
+    }
+
+    /// Replace the HTTP API token; clients holding the old one are rejected
+    pub fn rotate_http_api_token(&self) -> Result<String> {
+        let token = format!(
+            "{}{}",
+            uuid::Uuid::new_v4().simple(),
+            uuid::Uuid::new_v4().simple()
+        );
+        self.keyring.set_secret(HTTP_API_TOKEN_ENTRY, &token)?;
+        Ok(token)
+    }
+
+    /// Key for new encrypted backups, created on first use. Losing it makes
+    /// encrypted backups unreadable.
+    pub fn backup_encryption_key(&self) -> Result<[u8; KEY_LEN]> {
+        match self.find_backup_encryption_key()? {
+            Some(key) => Ok(key),
+            None => {
+                let key = generate_key()?;
+                self.keyring
+                    .set_secret(BACKUP_KEY_ENTRY, &hex::encode(key))?;
+                Ok(key)
+            }
+        }
+    }
+
+    /// Backup key from the keychain without creating one
+    pub fn find_backup_encryption_key(&self) -> Result<Option<[u8; KEY_LEN]>> {
+        let Some(encoded) = self.keyring.find_secret(BACKUP_KEY_ENTRY)? else {
+            return Ok(None);
+        };
+        let key = hex::decode(encoded.trim())
+            .ok()
+            .and_then(|bytes| <[u8; KEY_LEN]>::try_from(bytes).ok())
+            .ok_or_else(|| {
+                AppError::SecurityError(
+                    "Backup encryption key in the keychain is malformed".to_string(),
+                )
+            })?;
+        Ok(Some(key))
+    }
+
+    /// Derived key of an encrypted RAG collection, if this device keeps it
+    pub fn find_collection_key(
+        &self,
+        collection_id: i64,
+    ) -> Result<Option<[u8; COLLECTION_KEY_LEN]>> {
+        let Some(encoded) = self
+            .keyring
+            .find_secret(&collection_key_entry(collection_id))?
+        else {
+            return Ok(None);
+        };
+        let key = hex::decode(encoded.trim())
+            .ok()
+            .and_then(|bytes| <[u8; COLLECTION_KEY_LEN]>::try_from(bytes).ok())
+            .ok_or_else(|| {
+                AppError::SecurityError(format!(
+                    "Key for collection {} in the keychain is malformed",
+                    collection_id
+                ))
+            })?;
+        Ok(Some(key))
+    }
+
+    pub fn save_collection_key(
+        &self,
+        collection_id: i64,
+        key: &[u8; COLLECTION_KEY_LEN],
+    ) -> Result<()> {
+        self.keyring
+            .set_secret(&collection_key_entry(collection_id), &hex::encode(key))
+    }
+
+    pub fn delete_collection_key(&self, collection_id: i64) -> Result<()> {
+        let entry = collection_key_entry(collection_id);
+        match self.keyring.find_secret(&entry)? {
+            Some(_) => self.keyring.delete_secret(&entry),
+            None => Ok(()),
+        }
+    }
+
+    /// Store the credential QA test cases are pushed with. An empty secret deletes it.
+    pub fn save_test_management_secret(
+        &self,
+        provider: TestManagementProvider,
+        secret: &str,
+    ) -> Result<()> {
+        let entry = test_management_entry(provider);
+        let secret = secret.trim();
+        if !secret.is_empty() {
+            return self.keyring.set_secret(&entry, secret);
+        }
+        match self.keyring.find_secret(&entry)? {
+            Some(_) => self.keyring.delete_secret(&entry),
+            None => Ok(()),
+        }
+    }
+
+    pub fn find_test_management_secret(
+        &self,
+        provider: TestManagementProvider,
+    ) -> Result<Option<String>> {
+        self.keyring.find_secret(&test_management_entry(provider))
+    }
+
+    /// Store a secret of a QA auth profile. An empty secret deletes it.
+    pub fn save_qa_auth_secret(
+        &self,
+        profile_id: &str,
+        secret: QaAuthSecret,
+        value: &str,
+    ) -> Result<()> {
+        let entry = qa_auth_entry(profile_id, secret);
+        let value = value.trim();
+        if !value.is_empty() {
+            return self.keyring.set_secret(&entry, value);
+        }
+        match self.keyring.find_secret(&entry)? {
+            Some(_) => self.keyring.delete_secret(&entry),
+            None => Ok(()),
+        }
+    }
+
+    pub fn find_qa_auth_secret(
+        &self,
+        profile_id: &str,
+        secret: QaAuthSecret,
+    ) -> Result<Option<String>> {
+        self.keyring.find_secret(&qa_auth_entry(profile_id, secret))
+    }
+
+    pub fn save_connector_token(&self, connector_id: i64, token: &str) -> Result<()> {
+        self.keyring
+            .set_secret(&rag_connector_token_entry(connector_id), token)
+    }
+
+    pub fn find_connector_token(&self, connector_id: i64) -> Result<Option<String>> {
+        self.keyring
+            .find_secret(&rag_connector_token_entry(connector_id))
+    }
+
+    pub fn delete_connector_token(&self, connector_id: i64) -> Result<()> {
+        let entry = rag_connector_token_entry(connector_id);
+        match self.keyring.find_secret(&entry)? {
+            Some(_) => self.keyring.delete_secret(&entry),
+            None => Ok(()),
+        }
+    }
+
+    /// Keychain entries are keyed by the lowercase provider id ("openai", "gemini", ...)
+    fn entry_name(provider: &str) -> String {
+        provider.trim().to_lowercase()
     }
 }
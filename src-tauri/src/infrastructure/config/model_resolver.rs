@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use crate::domain::llm_config::LLMConfig;
+use crate::domain::settings::{LlmFeature, ModelSettings};
+
+use super::{ConfigService, SettingsStore};
+
+/// Picks the model a feature runs on: its override from the `models`
+/// settings section, or the model the caller asked for
+pub struct LlmConfigResolver {
+    settings: Arc<SettingsStore>,
+    config_service: ConfigService,
+}
+
+impl LlmConfigResolver {
+    pub fn new(settings: Arc<SettingsStore>) -> Self {
+        Self {
+            settings,
+            config_service: ConfigService::new(),
+        }
+    }
+
+    /// `requested` with the provider and model configured for `feature`.
+    /// An overridden provider's key is read from the keychain.
+    pub fn resolve(&self, feature: LlmFeature, requested: &LLMConfig) -> LLMConfig {
+        let models = self.settings.get::<ModelSettings>();
+        let Some(config) = models.config_for(feature, requested) else {
+            return requested.clone();
+        };
+        // Without a keychain the provider may still accept keyless calls
+        self.config_service.with_api_key(&config).unwrap_or(config)
+    }
+}
@@ -0,0 +1,399 @@
+// ============================================================
+// SETTINGS STORE
+// ============================================================
+// Single JSON file (settings.json in the app data dir) holding
+// every persisted setting, upgraded through a migration chain
+// when an older schema version is loaded.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde_json::{Map, Value as JsonValue};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::settings::{
+    SettingsChange, SettingsDocument, SettingsSection, SETTINGS_SCHEMA_VERSION,
+};
+
+type ChangeListener = Box<dyn Fn(&SettingsChange) + Send + Sync>;
+
+/// One upgrade step of the settings document
+struct Migration {
+    /// Schema version this step upgrades from (to `from + 1`)
+    from: u32,
+    description: &'static str,
+    apply: fn(Map<String, JsonValue>) -> Map<String, JsonValue>,
+}
+
+/// Upgrade steps, ordered by `from`
+const MIGRATIONS: &[Migration] = &[Migration {
+    from: 0,
+    description: "wrap unversioned top-level keys into sections",
+    apply: wrap_unversioned_sections,
+}];
+
+/// Versioned, file-backed settings shared by all subsystems
+pub struct SettingsStore {
+    path: PathBuf,
+    document: Mutex<SettingsDocument>,
+    listeners: Mutex<Vec<ChangeListener>>,
+}
+
+impl SettingsStore {
+    /// Load the settings file, migrating it to the current schema
+    ///
+    /// A missing file yields empty settings. When a migration runs, the
+    /// original file is kept next to it as `settings.v{N}.json`.
+    pub fn open(path: PathBuf) -> Result<Self> {
+        let document = if path.exists() {
+            let content = fs::read_to_string(&path)?;
+            let (document, from_version) = Self::parse_document(&content)?;
+            if from_version < SETTINGS_SCHEMA_VERSION {
+                fs::copy(&path, versioned_backup_path(&path, from_version))?;
+                write_document(&path, &document)?;
+            }
+            document
+        } else {
+            SettingsDocument::default()
+        };
+
+        Ok(Self {
+            path,
+            document: Mutex::new(document),
+            listeners: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Move an unreadable settings file aside and start from empty settings
+    ///
+    /// Returns the store and where the old file was moved.
+    pub fn open_quarantined(path: PathBuf) -> Result<(Self, PathBuf)> {
+        let stamp = chrono::Utc::now().format("%Y%m%d%H%M%S");
+        let quarantined = path.with_extension(format!("json.broken-{}", stamp));
+        fs::rename(&path, &quarantined)?;
+        Ok((Self::open(path)?, quarantined))
+    }
+
+    /// Parse and migrate a settings document (file contents or an export)
+    ///
+    /// Returns the upgraded document and the schema version it started at.
+    pub fn parse_document(content: &str) -> Result<(SettingsDocument, u32)> {
+        let value: JsonValue = serde_json::from_str(content)
+            .map_err(|e| AppError::ParseError(format!("Invalid settings JSON: {}", e)))?;
+        let JsonValue::Object(mut object) = value else {
+            return Err(AppError::ParseError(
+                "Settings must be a JSON object".to_string(),
+            ));
+        };
+
+        let from_version = match object.get("schemaVersion") {
+            None => 0,
+            Some(version) => version
+                .as_u64()
+                .and_then(|version| u32::try_from(version).ok())
+                .ok_or_else(|| {
+                    AppError::ParseError(format!("Invalid settings schemaVersion: {}", version))
+                })?,
+        };
+        if from_version > SETTINGS_SCHEMA_VERSION {
+            return Err(AppError::ValidationError(format!(
+                "Settings schema version {} is newer than the supported version {}",
+                from_version, SETTINGS_SCHEMA_VERSION
+            )));
+        }
+
+        for migration in MIGRATIONS
+            .iter()
+            .filter(|migration| migration.from >= from_version)
+        {
+            tracing::info!(
+                "Migrating settings v{} -> v{}: {}",
+                migration.from,
+                migration.from + 1,
+                migration.description
+            );
+            object = (migration.apply)(object);
+            object.insert("schemaVersion".to_string(), (migration.from + 1).into());
+        }
+
+        let document = serde_json::from_value(JsonValue::Object(object))
+            .map_err(|e| AppError::ParseError(format!("Invalid settings document: {}", e)))?;
+        Ok((document, from_version))
+    }
+
+    /// Location of the settings file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Register a callback invoked after every section change
+    pub fn subscribe<F>(&self, listener: F)
+    where
+        F: Fn(&SettingsChange) + Send + Sync + 'static,
+    {
+        self.listeners.lock().unwrap().push(Box::new(listener));
+    }
+
+    /// Whether a section has been stored
+    pub fn contains<T: SettingsSection>(&self) -> bool {
+        self.document.lock().unwrap().sections.contains_key(T::KEY)
+    }
+
+    /// Read a typed section, falling back to its default when missing or invalid
+    pub fn get<T: SettingsSection>(&self) -> T {
+        self.get_section(T::KEY)
+            .and_then(|value| match serde_json::from_value(value) {
+                Ok(section) => Some(section),
+                Err(e) => {
+                    tracing::warn!("Ignoring invalid '{}' settings: {}", T::KEY, e);
+                    None
+                }
+            })
+            .unwrap_or_default()
+    }
+
+    /// Write a typed section. Returns false when nothing changed.
+    pub fn set<T: SettingsSection>(&self, section: &T) -> Result<bool> {
+        let value = serde_json::to_value(section).map_err(|e| {
+            AppError::Internal(format!("Failed to serialize '{}' settings: {}", T::KEY, e))
+        })?;
+        self.set_section(T::KEY, value)
+    }
+
+    /// Raw JSON of a section
+    pub fn get_section(&self, key: &str) -> Option<JsonValue> {
+        self.document.lock().unwrap().sections.get(key).cloned()
+    }
+
+    /// Write a raw section. Returns false when nothing changed.
+    pub fn set_section(&self, key: &str, value: JsonValue) -> Result<bool> {
+        let changed = self.merge_sections(vec![(key.to_string(), value)])?;
+        Ok(!changed.is_empty())
+    }
+
+    /// Copy of the whole document
+    pub fn snapshot(&self) -> SettingsDocument {
+        self.document.lock().unwrap().clone()
+    }
+
+    /// Serialize the whole document for moving to another machine
+    pub fn export(&self) -> Result<String> {
+        serde_json::to_string_pretty(&self.snapshot())
+            .map_err(|e| AppError::Internal(format!("Failed to serialize settings: {}", e)))
+    }
+
+    /// Overwrite the sections present in `document`, keeping the others
+    ///
+    /// Returns the keys of the sections whose value changed.
+    pub fn import(&self, document: SettingsDocument) -> Result<Vec<String>> {
+        self.merge_sections(document.sections.into_iter().collect())
+    }
+
+    /// Seed a section from a pre-unification JSON file
+    ///
+    /// Only runs when the section is not stored yet. The legacy file is
+    /// renamed to `<name>.migrated` so it is not picked up again.
+    pub fn adopt_legacy_file<T: SettingsSection>(&self, legacy_path: &Path) -> Result<bool> {
+        if self.contains::<T>() || !legacy_path.exists() {
+            return Ok(false);
+        }
+
+        let content = fs::read_to_string(legacy_path)?;
+        let section: T = serde_json::from_str(&content).map_err(|e| {
+            AppError::ParseError(format!(
+                "Failed to parse legacy settings {}: {}",
+                legacy_path.display(),
+                e
+            ))
+        })?;
+        self.set(&section)?;
+
+        let mut migrated = legacy_path.as_os_str().to_owned();
+        migrated.push(".migrated");
+        fs::rename(legacy_path, migrated)?;
+        Ok(true)
+    }
+
+    fn merge_sections(&self, sections: Vec<(String, JsonValue)>) -> Result<Vec<String>> {
+        let changes: Vec<SettingsChange> = {
+            let mut document = self.document.lock().unwrap();
+            let changes: Vec<SettingsChange> = sections
+                .into_iter()
+                .filter(|(key, value)| document.sections.get(key) != Some(value))
+                .map(|(section, value)| SettingsChange { section, value })
+                .collect();
+            if changes.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            let mut updated = document.clone();
+            for change in &changes {
+                updated
+                    .sections
+                    .insert(change.section.clone(), change.value.clone());
+            }
+            updated.schema_version = SETTINGS_SCHEMA_VERSION;
+            updated.updated_at = Some(chrono::Utc::now().to_rfc3339());
+            write_document(&self.path, &updated)?;
+            *document = updated;
+            changes
+        };
+
+        let listeners = self.listeners.lock().unwrap();
+        for change in &changes {
+            for listener in listeners.iter() {
+                listener(change);
+            }
+        }
+        Ok(changes.into_iter().map(|change| change.section).collect())
+    }
+}
+
+/// Write through a temp file so a crash never leaves half a settings file
+fn write_document(path: &Path, document: &SettingsDocument) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let content = serde_json::to_string_pretty(document)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize settings: {}", e)))?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, content)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+fn versioned_backup_path(path: &Path, version: u32) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "settings".to_string());
+    path.with_file_name(format!("{}.v{}.json", stem, version))
+}
+
+/// v0 -> v1: early files were a flat object of sections
+fn wrap_unversioned_sections(mut object: Map<String, JsonValue>) -> Map<String, JsonValue> {
+    if object.contains_key("sections") {
+        return object;
+    }
+
+    let updated_at = object.remove("updatedAt");
+    let mut wrapped = Map::new();
+    wrapped.insert("sections".to_string(), JsonValue::Object(object));
+    if let Some(updated_at) = updated_at {
+        wrapped.insert("updatedAt".to_string(), updated_at);
+    }
+    wrapped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::settings::{LanguageSettings, ShortcutSettings};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("settings-{}-{}", std::process::id(), name));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_typed_sections_round_trip() {
+        let dir = temp_dir("round-trip");
+        let path = dir.join("settings.json");
+
+        let store = SettingsStore::open(path.clone()).unwrap();
+        assert_eq!(store.get::<ShortcutSettings>(), ShortcutSettings::default());
+
+        let languages = LanguageSettings {
+            source: "Indonesian".to_string(),
+            target: "English".to_string(),
+        };
+        assert!(store.set(&languages).unwrap());
+        assert!(!store.set(&languages).unwrap());
+
+        let reopened = SettingsStore::open(path).unwrap();
+        assert_eq!(reopened.get::<LanguageSettings>(), languages);
+        assert_eq!(reopened.snapshot().schema_version, SETTINGS_SCHEMA_VERSION);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_migrates_unversioned_file_and_keeps_backup() {
+        let dir = temp_dir("migrate");
+        let path = dir.join("settings.json");
+        fs::write(
+            &path,
+            r#"{"languages": {"source": "Japanese", "target": "Indonesian"}}"#,
+        )
+        .unwrap();
+
+        let store = SettingsStore::open(path.clone()).unwrap();
+        assert_eq!(store.get::<LanguageSettings>().source, "Japanese");
+        assert!(dir.join("settings.v0.json").exists());
+
+        let (document, from_version) =
+            SettingsStore::parse_document(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(from_version, SETTINGS_SCHEMA_VERSION);
+        assert!(document.sections.contains_key("languages"));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_rejects_newer_schema() {
+        let err =
+            SettingsStore::parse_document(r#"{"schemaVersion": 99, "sections": {}}"#).unwrap_err();
+        assert!(matches!(err, AppError::ValidationError(_)));
+    }
+
+    #[test]
+    fn test_import_merges_and_notifies() {
+        let dir = temp_dir("import");
+        let store = SettingsStore::open(dir.join("settings.json")).unwrap();
+        store.set(&ShortcutSettings::default()).unwrap();
+
+        let notified = Arc::new(AtomicUsize::new(0));
+        let counter = notified.clone();
+        store.subscribe(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let exported = r#"{
+            "schemaVersion": 1,
+            "sections": {
+                "shortcuts": {"enabled": true, "translate": "Ctrl + Alt + T",
+                    "enhance": "Ctrl + Alt + E", "popup": "Ctrl + Alt + P",
+                    "terminal": "Ctrl + Alt + R"},
+                "languages": {"source": "French", "target": "German"}
+            }
+        }"#;
+        let (document, _) = SettingsStore::parse_document(exported).unwrap();
+        let changed = store.import(document).unwrap();
+
+        assert_eq!(changed, vec!["languages".to_string()]);
+        assert_eq!(notified.load(Ordering::SeqCst), 1);
+        assert_eq!(store.get::<LanguageSettings>().target, "German");
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_adopts_legacy_file_once() {
+        let dir = temp_dir("legacy");
+        let legacy = dir.join("languages.json");
+        fs::write(&legacy, r#"{"source": "Korean", "target": "English"}"#).unwrap();
+
+        let store = SettingsStore::open(dir.join("settings.json")).unwrap();
+        assert!(store
+            .adopt_legacy_file::<LanguageSettings>(&legacy)
+            .unwrap());
+        assert!(!legacy.exists());
+        assert!(dir.join("languages.json.migrated").exists());
+        assert_eq!(store.get::<LanguageSettings>().source, "Korean");
+        assert!(!store
+            .adopt_legacy_file::<LanguageSettings>(&legacy)
+            .unwrap());
+        fs::remove_dir_all(&dir).ok();
+    }
+}
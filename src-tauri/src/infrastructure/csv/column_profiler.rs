@@ -0,0 +1,687 @@
+// ============================================================
+// COLUMN PROFILER
+// ============================================================
+// Infer column types, collect per-column statistics, and normalize
+// values so numeric/date/bool columns are formatted consistently
+
+use crate::domain::csv::{
+    ColumnProfile, ColumnType, CsvField, CsvProfile, CsvRow, SuggestedColumn,
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use std::collections::{BTreeSet, HashSet};
+
+/// Values treated as missing, compared case-insensitively
+const NULL_TOKENS: &[&str] = &["", "null", "nil", "none", "n/a", "na", "nan", "-", "#n/a"];
+
+const TRUE_TOKENS: &[&str] = &["true", "yes", "y", "t"];
+const FALSE_TOKENS: &[&str] = &["false", "no", "n", "f"];
+
+/// Date-only formats, tried in order; the first one that parses every
+/// value in a column wins so day/month order stays consistent per column
+const DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%d", "%Y/%m/%d", "%d/%m/%Y", "%m/%d/%Y", "%d-%m-%Y", "%d.%m.%Y",
+];
+
+const DATETIME_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M",
+];
+
+/// Number of distinct example values kept per column
+const SAMPLE_VALUES: usize = 5;
+
+/// Distinct values tracked per column; past this the distinct count is a
+/// lower bound, which keeps memory bounded on very large files
+const MAX_TRACKED_DISTINCT: usize = 10_000;
+
+/// Column profiler for CSV data
+///
+/// Rows are fed one at a time through `observe`, so a file can be profiled
+/// while it is streamed without keeping its rows in memory.
+pub struct ColumnProfiler {
+    /// Maximum distinct values for a column to be considered categorical
+    max_categories: usize,
+
+    /// Maximum average value length for a categorical column
+    max_category_length: usize,
+
+    columns: Vec<ColumnAccumulator>,
+    row_count: usize,
+}
+
+impl Default for ColumnProfiler {
+    fn default() -> Self {
+        Self {
+            max_categories: 20,
+            max_category_length: 40,
+            columns: Vec::new(),
+            row_count: 0,
+        }
+    }
+}
+
+impl ColumnProfiler {
+    /// Create a new column profiler with default thresholds
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Profile every column of the parsed rows
+    pub fn profile(mut self, rows: &[CsvRow]) -> CsvProfile {
+        for row in rows {
+            self.observe(row);
+        }
+        self.finish()
+    }
+
+    /// Feed one row; the first row observed defines the columns
+    pub fn observe(&mut self, row: &CsvRow) {
+        if self.row_count == 0 {
+            self.columns = row
+                .fields
+                .iter()
+                .map(|field| ColumnAccumulator::new(&field.name, &field.clean_name))
+                .collect();
+        }
+
+        for (idx, column) in self.columns.iter_mut().enumerate() {
+            let value = row.fields.get(idx).map(|f| f.value.as_str()).unwrap_or("");
+            column.observe(value);
+        }
+        self.row_count += 1;
+    }
+
+    /// Infer column types and build the profile from everything observed
+    pub fn finish(self) -> CsvProfile {
+        let mut columns = Vec::with_capacity(self.columns.len());
+        let mut suggested_schema = Vec::with_capacity(self.columns.len());
+        for accumulator in self.columns {
+            let (column, suggestion) = accumulator.finish(
+                self.row_count,
+                self.max_categories,
+                self.max_category_length,
+            );
+            columns.push(column);
+            suggested_schema.push(suggestion);
+        }
+
+        CsvProfile {
+            row_count: self.row_count,
+            columns,
+            suggested_schema,
+        }
+    }
+
+    /// Rewrite values according to the inferred column types
+    ///
+    /// Null-like values become empty, numbers lose thousand separators,
+    /// dates become ISO 8601 and flags become true/false. Values that do
+    /// not fit the inferred type are kept as-is.
+    pub fn normalize_row(profile: &CsvProfile, row: CsvRow) -> CsvRow {
+        let fields = row
+            .fields
+            .into_iter()
+            .enumerate()
+            .map(|(idx, field)| match profile.columns.get(idx) {
+                Some(column) => {
+                    let value = if is_null_value(&field.value) {
+                        String::new()
+                    } else {
+                        normalize_value(column, &field.value).unwrap_or_else(|| field.value.clone())
+                    };
+                    if value == field.value {
+                        field
+                    } else {
+                        CsvField::new(field.name, value)
+                    }
+                }
+                None => field,
+            })
+            .collect();
+        CsvRow::new(row.index, fields)
+    }
+
+    /// Normalize every row, see `normalize_row`
+    pub fn normalize_rows(profile: &CsvProfile, rows: Vec<CsvRow>) -> Vec<CsvRow> {
+        rows.into_iter()
+            .map(|row| Self::normalize_row(profile, row))
+            .collect()
+    }
+
+    /// Coerce one value to `column_type` the way `normalize_row` rewrites
+    /// values of an inferred type; dates without a `date_format` try the
+    /// known formats. Null-like values become empty. `None` when the value
+    /// does not fit the type.
+    pub fn coerce_value(
+        column_type: ColumnType,
+        date_format: Option<&str>,
+        value: &str,
+    ) -> Option<String> {
+        let trimmed = value.trim();
+        match column_type {
+            ColumnType::Categorical | ColumnType::Text => return Some(trimmed.to_string()),
+            ColumnType::Empty => return Some(String::new()),
+            _ if is_null_value(trimmed) => return Some(String::new()),
+            _ => {}
+        }
+        match (column_type, date_format) {
+            (ColumnType::Date, Some(format)) => parse_date(trimmed, format)
+                .map(|(parsed, has_time)| render_datetime(parsed, has_time)),
+            (ColumnType::Date, None) => DATETIME_FORMATS
+                .iter()
+                .chain(DATE_FORMATS)
+                .find_map(|format| parse_date(trimmed, format))
+                .map(|(parsed, has_time)| render_datetime(parsed, has_time)),
+            (ColumnType::Int, _) => parse_int(trimmed).map(|number| number.to_string()),
+            (ColumnType::Float, _) => parse_float(trimmed).map(|number| number.to_string()),
+            (ColumnType::Bool, _) => parse_bool(trimmed).map(|flag| flag.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Running min/max for one date format that has parsed every value so far
+struct DateCandidate {
+    format: &'static str,
+    range: Option<(NaiveDateTime, NaiveDateTime)>,
+}
+
+/// Per-column running statistics
+struct ColumnAccumulator {
+    name: String,
+    clean_name: String,
+    null_count: usize,
+    non_null_count: usize,
+    total_chars: usize,
+    all_bool: bool,
+    all_int: bool,
+    all_float: bool,
+    int_range: Option<(i64, i64)>,
+    float_range: Option<(f64, f64)>,
+    dates: Vec<DateCandidate>,
+    distinct: HashSet<String>,
+    distinct_overflow: bool,
+    samples: Vec<String>,
+}
+
+impl ColumnAccumulator {
+    fn new(name: &str, clean_name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            clean_name: clean_name.to_string(),
+            null_count: 0,
+            non_null_count: 0,
+            total_chars: 0,
+            all_bool: true,
+            all_int: true,
+            all_float: true,
+            int_range: None,
+            float_range: None,
+            dates: DATE_FORMATS
+                .iter()
+                .chain(DATETIME_FORMATS.iter())
+                .map(|format| DateCandidate {
+                    format,
+                    range: None,
+                })
+                .collect(),
+            distinct: HashSet::new(),
+            distinct_overflow: false,
+            samples: Vec::new(),
+        }
+    }
+
+    fn observe(&mut self, raw: &str) {
+        let value = raw.trim();
+        if is_null_value(value) {
+            self.null_count += 1;
+            return;
+        }
+        self.non_null_count += 1;
+        self.total_chars += value.chars().count();
+
+        if self.all_bool && parse_bool(value).is_none() {
+            self.all_bool = false;
+        }
+        if self.all_int {
+            match parse_int(value) {
+                Some(number) => self.int_range = Some(widen(self.int_range, number)),
+                None => self.all_int = false,
+            }
+        }
+        if self.all_float {
+            match parse_float(value) {
+                Some(number) => self.float_range = Some(widen(self.float_range, number)),
+                None => self.all_float = false,
+            }
+        }
+        // Every candidate format must parse every value, so the chosen
+        // format keeps day/month order consistent across the column
+        self.dates
+            .retain_mut(|candidate| match parse_date(value, candidate.format) {
+                Some((parsed, _)) => {
+                    candidate.range = Some(widen(candidate.range, parsed));
+                    true
+                }
+                None => false,
+            });
+
+        if self.distinct.contains(value) {
+            return;
+        }
+        if self.distinct.len() >= MAX_TRACKED_DISTINCT {
+            self.distinct_overflow = true;
+            return;
+        }
+        self.distinct.insert(value.to_string());
+        if self.samples.len() < SAMPLE_VALUES {
+            self.samples.push(value.to_string());
+        }
+    }
+
+    fn infer_type(
+        &self,
+        max_categories: usize,
+        max_category_length: usize,
+    ) -> (ColumnType, Option<&DateCandidate>) {
+        if self.non_null_count == 0 {
+            return (ColumnType::Empty, None);
+        }
+        // "1"/"0" columns are numbers, not flags
+        if self.all_bool && !self.all_float {
+            return (ColumnType::Bool, None);
+        }
+        if self.all_int {
+            return (ColumnType::Int, None);
+        }
+        if self.all_float {
+            return (ColumnType::Float, None);
+        }
+        if let Some(candidate) = self.dates.first() {
+            return (ColumnType::Date, Some(candidate));
+        }
+
+        let avg_len = self.total_chars / self.non_null_count;
+        if !self.distinct_overflow
+            && self.distinct.len() <= max_categories
+            && self.distinct.len() * 2 <= self.non_null_count
+            && avg_len <= max_category_length
+        {
+            return (ColumnType::Categorical, None);
+        }
+
+        (ColumnType::Text, None)
+    }
+
+    fn finish(
+        self,
+        row_count: usize,
+        max_categories: usize,
+        max_category_length: usize,
+    ) -> (ColumnProfile, SuggestedColumn) {
+        let (inferred_type, date) = self.infer_type(max_categories, max_category_length);
+        let date_format = date.map(|candidate| candidate.format);
+        let date_range = date.and_then(|candidate| candidate.range);
+        let has_time = date_format.is_some_and(|format| format.contains("%H"));
+        let (min, max) = match inferred_type {
+            ColumnType::Int => match self.int_range {
+                Some((min, max)) => (Some(min.to_string()), Some(max.to_string())),
+                None => (None, None),
+            },
+            ColumnType::Float => match self.float_range {
+                Some((min, max)) => (Some(min.to_string()), Some(max.to_string())),
+                None => (None, None),
+            },
+            ColumnType::Date => match date_range {
+                Some((min, max)) => (
+                    Some(render_datetime(min, has_time)),
+                    Some(render_datetime(max, has_time)),
+                ),
+                None => (None, None),
+            },
+            _ => (None, None),
+        };
+
+        let mut column = ColumnProfile {
+            name: self.name,
+            clean_name: self.clean_name,
+            inferred_type,
+            date_format: date_format.map(str::to_string),
+            null_count: self.null_count,
+            null_rate: if row_count == 0 {
+                0.0
+            } else {
+                self.null_count as f32 / row_count as f32
+            },
+            distinct_count: 0,
+            min,
+            max,
+            sample_values: Vec::new(),
+        };
+
+        // Distinct values are counted after normalization so "1,000" and
+        // "1000" are the same value
+        let normalized: HashSet<String> = self
+            .distinct
+            .iter()
+            .map(|value| normalize_value(&column, value).unwrap_or_else(|| value.clone()))
+            .collect();
+        column.distinct_count = normalized.len();
+
+        for value in &self.samples {
+            let value = normalize_value(&column, value).unwrap_or_else(|| value.clone());
+            if !column.sample_values.contains(&value) {
+                column.sample_values.push(value);
+            }
+        }
+
+        let categories = if inferred_type == ColumnType::Categorical {
+            self.distinct
+                .into_iter()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let suggestion = SuggestedColumn {
+            name: if column.clean_name.is_empty() {
+                column.name.clone()
+            } else {
+                column.clean_name.clone()
+            },
+            source: column.name.clone(),
+            column_type: match inferred_type {
+                ColumnType::Empty => ColumnType::Text,
+                other => other,
+            },
+            nullable: column.null_count > 0,
+            categories,
+        };
+
+        (column, suggestion)
+    }
+}
+
+fn widen<T: PartialOrd + Copy>(range: Option<(T, T)>, value: T) -> (T, T) {
+    match range {
+        Some((min, max)) => (
+            if value < min { value } else { min },
+            if value > max { value } else { max },
+        ),
+        None => (value, value),
+    }
+}
+
+fn render_datetime(parsed: NaiveDateTime, has_time: bool) -> String {
+    if has_time {
+        parsed.format("%Y-%m-%dT%H:%M:%S").to_string()
+    } else {
+        parsed.format("%Y-%m-%d").to_string()
+    }
+}
+
+/// Whether a raw CSV value should be treated as missing
+pub fn is_null_value(value: &str) -> bool {
+    let trimmed = value.trim();
+    NULL_TOKENS
+        .iter()
+        .any(|token| trimmed.eq_ignore_ascii_case(token))
+}
+
+/// Normalize a single value for the column's inferred type
+///
+/// Returns `None` when the value does not fit the type, or the type is not
+/// one that gets normalized.
+pub fn normalize_value(column: &ColumnProfile, value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    match column.inferred_type {
+        ColumnType::Int => parse_int(trimmed).map(|number| number.to_string()),
+        ColumnType::Float => parse_float(trimmed).map(|number| number.to_string()),
+        ColumnType::Bool => parse_bool(trimmed).map(|flag| flag.to_string()),
+        ColumnType::Date => {
+            let format = column.date_format.as_deref()?;
+            parse_date(trimmed, format).map(|(parsed, has_time)| render_datetime(parsed, has_time))
+        }
+        ColumnType::Categorical | ColumnType::Text | ColumnType::Empty => None,
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    let lower = value.trim().to_ascii_lowercase();
+    if TRUE_TOKENS.contains(&lower.as_str()) || lower == "1" {
+        Some(true)
+    } else if FALSE_TOKENS.contains(&lower.as_str()) || lower == "0" {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn parse_int(value: &str) -> Option<i64> {
+    let digits = strip_thousands(value.trim())?;
+    if has_leading_zero(&digits) || digits.contains('.') {
+        return None;
+    }
+    digits.parse::<i64>().ok()
+}
+
+fn parse_float(value: &str) -> Option<f64> {
+    let digits = strip_thousands(value.trim())?;
+    if has_leading_zero(&digits) {
+        return None;
+    }
+    // Reject "inf", "NaN", exponents without digits and the like
+    if !digits
+        .chars()
+        .all(|ch| ch.is_ascii_digit() || matches!(ch, '.' | '-' | '+' | 'e' | 'E'))
+    {
+        return None;
+    }
+    digits
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+}
+
+/// Remove thousand separators ("1,234,567.89" -> "1234567.89")
+///
+/// Returns `None` when commas are present but not in valid thousand groups,
+/// so "1,5" or "12,34" are not mistaken for numbers.
+fn strip_thousands(value: &str) -> Option<String> {
+    if value.is_empty() {
+        return None;
+    }
+    if !value.contains(',') {
+        return Some(value.to_string());
+    }
+
+    let unsigned = value.trim_start_matches(['-', '+']);
+    let integer_part = unsigned.split('.').next().unwrap_or("");
+    let mut groups = integer_part.split(',');
+    let first = groups.next().unwrap_or("");
+    if first.is_empty() || first.len() > 3 || !first.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    if !groups.all(|group| group.len() == 3 && group.chars().all(|ch| ch.is_ascii_digit())) {
+        return None;
+    }
+    Some(value.replace(',', ""))
+}
+
+/// Identifiers like zip codes ("00123") must stay text
+fn has_leading_zero(digits: &str) -> bool {
+    let unsigned = digits.trim_start_matches(['-', '+']);
+    unsigned.len() > 1 && unsigned.starts_with('0') && !unsigned.starts_with("0.")
+}
+
+fn parse_date(value: &str, format: &str) -> Option<(NaiveDateTime, bool)> {
+    let value = value.trim();
+    if format.contains("%H") {
+        NaiveDateTime::parse_from_str(value, format)
+            .ok()
+            .map(|parsed| (parsed, true))
+    } else {
+        NaiveDate::parse_from_str(value, format)
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|parsed| (parsed, false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(headers: &[&str], data: &[&[&str]]) -> Vec<CsvRow> {
+        data.iter()
+            .enumerate()
+            .map(|(index, values)| {
+                let fields = headers
+                    .iter()
+                    .zip(values.iter())
+                    .map(|(name, value)| CsvField::new(name.to_string(), value.to_string()))
+                    .collect();
+                CsvRow::new(index, fields)
+            })
+            .collect()
+    }
+
+    fn sample() -> Vec<CsvRow> {
+        rows(
+            &["id", "price", "joined", "active", "dept", "zip", "notes"],
+            &[
+                &[
+                    "1",
+                    "1,200.50",
+                    "2024-01-05",
+                    "yes",
+                    "Sales",
+                    "01234",
+                    "first hire",
+                ],
+                &["2", "980", "2024-03-17", "no", "Sales", "02345", ""],
+                &[
+                    "3",
+                    "N/A",
+                    "2023-12-31",
+                    "yes",
+                    "Ops",
+                    "03456",
+                    "moved from the Jakarta office",
+                ],
+                &[
+                    "4",
+                    "15.25",
+                    "2024-02-29",
+                    "Y",
+                    "Sales",
+                    "04567",
+                    "part time",
+                ],
+            ],
+        )
+    }
+
+    #[test]
+    fn test_infers_column_types() {
+        let profile = ColumnProfiler::new().profile(&sample());
+        let types: Vec<ColumnType> = profile
+            .columns
+            .iter()
+            .map(|column| column.inferred_type)
+            .collect();
+
+        assert_eq!(
+            types,
+            vec![
+                ColumnType::Int,
+                ColumnType::Float,
+                ColumnType::Date,
+                ColumnType::Bool,
+                ColumnType::Categorical,
+                ColumnType::Text,
+                ColumnType::Text,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reports_nulls_and_ranges() {
+        let profile = ColumnProfiler::new().profile(&sample());
+
+        let price = profile.column("price").unwrap();
+        assert_eq!(price.null_count, 1);
+        assert!((price.null_rate - 0.25).abs() < f32::EPSILON);
+        assert_eq!(price.min.as_deref(), Some("15.25"));
+        assert_eq!(price.max.as_deref(), Some("1200.5"));
+
+        let joined = profile.column("joined").unwrap();
+        assert_eq!(joined.min.as_deref(), Some("2023-12-31"));
+        assert_eq!(joined.max.as_deref(), Some("2024-03-17"));
+
+        let dept = profile.column("dept").unwrap();
+        assert_eq!(dept.distinct_count, 2);
+        let suggested = &profile.suggested_schema[4];
+        assert_eq!(suggested.categories, vec!["Ops", "Sales"]);
+        assert!(profile.suggested_schema[1].nullable);
+    }
+
+    #[test]
+    fn test_normalizes_values() {
+        let data = sample();
+        let profile = ColumnProfiler::new().profile(&data);
+        let normalized = ColumnProfiler::normalize_rows(&profile, data);
+
+        let first = &normalized[0].field_map;
+        assert_eq!(first["price"], "1200.5");
+        assert_eq!(first["active"], "true");
+        assert_eq!(first["zip"], "01234");
+        assert!(normalized[2].fields[1].is_empty);
+        assert_eq!(normalized[3].field_map["active"], "true");
+    }
+
+    #[test]
+    fn test_day_first_dates_stay_consistent() {
+        let data = rows(&["date"], &[&["05/01/2024"], &["25/12/2024"]]);
+        let profile = ColumnProfiler::new().profile(&data);
+        assert_eq!(profile.columns[0].date_format.as_deref(), Some("%d/%m/%Y"));
+
+        let normalized = ColumnProfiler::normalize_rows(&profile, data);
+        assert_eq!(normalized[0].field_map["date"], "2024-01-05");
+    }
+
+    #[test]
+    fn test_rejects_ambiguous_commas() {
+        assert_eq!(parse_float("1,5"), None);
+        assert_eq!(parse_int("12,345"), Some(12345));
+        assert_eq!(parse_float("-1,234.5"), Some(-1234.5));
+        assert_eq!(parse_float("inf"), None);
+    }
+
+    #[test]
+    fn test_coerces_values_to_a_chosen_type() {
+        let coerce = ColumnProfiler::coerce_value;
+        assert_eq!(
+            coerce(ColumnType::Int, None, " 1,200 ").as_deref(),
+            Some("1200")
+        );
+        assert_eq!(coerce(ColumnType::Int, None, "n/a").as_deref(), Some(""));
+        assert_eq!(coerce(ColumnType::Float, None, "abc"), None);
+        assert_eq!(
+            coerce(ColumnType::Date, Some("%d.%m.%Y"), "05.01.2024").as_deref(),
+            Some("2024-01-05")
+        );
+        assert_eq!(
+            coerce(ColumnType::Date, None, "2024-01-05 13:45").as_deref(),
+            Some("2024-01-05T13:45:00")
+        );
+        assert_eq!(
+            coerce(ColumnType::Text, None, " none ").as_deref(),
+            Some("none")
+        );
+    }
+}
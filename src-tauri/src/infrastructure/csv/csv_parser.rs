@@ -7,6 +7,7 @@ use std::path::Path;
 use csv::{ReaderBuilder, StringRecord, Trim};
 use crate::domain::csv::{CsvRow, CsvField};
 use crate::domain::error::{AppError};
+use super::csv_stream::detect_encoding;
 
 /// CSV parser with encoding detection
 pub struct CsvParser {
@@ -87,38 +88,15 @@ impl CsvParser {
         Ok(rows)
     }
 
-    /// Read file with encoding detection
-    fn read_with_encoding_detection(&self, path: &Path) -> Result<String, AppError> {
-        use std::fs::File;
-        use std::io::Read;
-
-        // Try UTF-8 first
-        if let Ok(mut file) = File::open(path) {
-            let mut buffer = Vec::new();
-            if file.read_to_end(&mut buffer).is_ok() {
-                // Try UTF-8
-                if let Ok(content) = std::str::from_utf8(&buffer) {
-                    return Ok(content.to_string());
-                }
-
-                // Try Latin-1 (fallback)
-                if let Ok(content) = std::str::from_utf8(&buffer) {
-                    return Ok(content.to_string());
-                }
-            }
-        }
-
-        // If encoding detection fails, read as raw bytes and replace invalid UTF-8
-        let mut file = File::open(path).map_err(|e| {
-            AppError::IoError(format!("Failed to open file: {}", e))
-        })?;
-
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer).map_err(|e| {
+    /// Read a whole file as UTF-8, transcoding UTF-16 and Windows-1252 input
+    pub fn read_with_encoding_detection(&self, path: &Path) -> Result<String, AppError> {
+        let buffer = std::fs::read(path).map_err(|e| {
             AppError::IoError(format!("Failed to read file: {}", e))
         })?;
 
-        Ok(String::from_utf8_lossy(&buffer).to_string())
+        let encoding = detect_encoding(&buffer[..buffer.len().min(64 * 1024)]);
+        let (content, _, _) = encoding.decode(&buffer);
+        Ok(content.into_owned())
     }
 
     /// Parse a single CSV row
@@ -128,6 +106,15 @@ impl CsvParser {
         headers: &StringRecord,
         record: &StringRecord,
     ) -> Result<CsvRow, AppError> {
+        Ok(Self::row_from_record(index, headers, record))
+    }
+
+    /// Build a row from a CSV record, filling missing trailing fields with ""
+    pub(super) fn row_from_record(
+        index: usize,
+        headers: &StringRecord,
+        record: &StringRecord,
+    ) -> CsvRow {
         let mut fields = Vec::new();
 
         for (idx, header) in headers.iter().enumerate() {
@@ -136,7 +123,7 @@ impl CsvParser {
             fields.push(field);
         }
 
-        Ok(CsvRow::new(index, fields))
+        CsvRow::new(index, fields)
     }
 
     /// Detect delimiter from content (comma, semicolon, tab, pipe)
@@ -188,13 +175,14 @@ impl CsvParser {
             use std::fs::File;
             use std::io::Read;
 
-            let mut file = File::open(path).map_err(|e| {
+            let file = File::open(path).map_err(|e| {
                 AppError::IoError(format!("Failed to open file: {}", e))
             })?;
 
-            let mut buffer = vec![0u8; 4096];
-            file.read(&mut buffer).ok();
-            String::from_utf8_lossy(&buffer).to_string()
+            let mut buffer = Vec::new();
+            file.take(4096).read_to_end(&mut buffer).ok();
+            let (sample, _, _) = detect_encoding(&buffer).decode(&buffer);
+            sample.into_owned()
         };
 
         let delimiter = Self::detect_delimiter(&content_sample);
@@ -0,0 +1,321 @@
+// ============================================================
+// CSV STREAM READER
+// ============================================================
+// Read CSV files row by row with bounded memory, detecting the
+// text encoding (UTF-8, UTF-16 LE/BE, Windows-1252) and delimiter
+
+use super::CsvParser;
+use crate::domain::csv::CsvRow;
+use crate::domain::error::AppError;
+use csv::{Reader, ReaderBuilder, StringRecord, Trim};
+use encoding_rs::{CoderResult, Decoder, Encoding, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Bytes inspected for encoding and delimiter detection
+const SNIFF_BYTES: usize = 64 * 1024;
+
+/// Size of the raw and decoded buffers used while streaming
+const DECODE_BUFFER_BYTES: usize = 64 * 1024;
+
+/// Guess the text encoding of a file from its first bytes
+///
+/// A byte order mark wins. Without one, ASCII-heavy UTF-16 is recognised by
+/// its zero high bytes; otherwise valid UTF-8 is assumed and anything else is
+/// treated as Windows-1252 (the usual Excel "ANSI" export).
+pub fn detect_encoding(sample: &[u8]) -> &'static Encoding {
+    if let Some((encoding, _)) = Encoding::for_bom(sample) {
+        return encoding;
+    }
+
+    let pairs = sample.len() / 2;
+    if pairs >= 8 {
+        let even_zeros = sample.iter().step_by(2).filter(|b| **b == 0).count();
+        let odd_zeros = sample
+            .iter()
+            .skip(1)
+            .step_by(2)
+            .filter(|b| **b == 0)
+            .count();
+        if odd_zeros * 10 >= pairs * 7 && even_zeros * 10 < pairs {
+            return UTF_16LE;
+        }
+        if even_zeros * 10 >= pairs * 7 && odd_zeros * 10 < pairs {
+            return UTF_16BE;
+        }
+    }
+
+    match std::str::from_utf8(sample) {
+        Ok(_) => UTF_8,
+        // The sample may end in the middle of a multi-byte character
+        Err(e) if e.error_len().is_none() => UTF_8,
+        Err(_) => WINDOWS_1252,
+    }
+}
+
+/// Row-by-row CSV reader over a file of any size
+pub struct CsvStreamReader {
+    reader: Reader<DecodingReader<CountingReader<File>>>,
+    headers: StringRecord,
+    record: StringRecord,
+    next_index: usize,
+    bytes_read: Arc<AtomicU64>,
+    total_bytes: u64,
+    encoding: &'static Encoding,
+    delimiter: u8,
+}
+
+impl CsvStreamReader {
+    /// Open a CSV file, detecting encoding and delimiter from its first 64KB
+    pub fn open(path: &Path) -> Result<Self, AppError> {
+        let mut file = File::open(path)
+            .map_err(|e| AppError::IoError(format!("Failed to open file: {}", e)))?;
+        let total_bytes = file
+            .metadata()
+            .map(|meta| meta.len())
+            .map_err(|e| AppError::IoError(format!("Failed to read file metadata: {}", e)))?;
+
+        let mut sample = Vec::with_capacity(SNIFF_BYTES);
+        (&mut file)
+            .take(SNIFF_BYTES as u64)
+            .read_to_end(&mut sample)
+            .map_err(|e| AppError::IoError(format!("Failed to read file: {}", e)))?;
+
+        let encoding = detect_encoding(&sample);
+        let (decoded, _, _) = encoding.decode(&sample);
+        let delimiter = CsvParser::detect_delimiter(&decoded);
+
+        // Reopen rather than seek so the sniffed bytes are decoded with BOM handling
+        let file = File::open(path)
+            .map_err(|e| AppError::IoError(format!("Failed to open file: {}", e)))?;
+        let bytes_read = Arc::new(AtomicU64::new(0));
+        let counting = CountingReader {
+            inner: file,
+            count: bytes_read.clone(),
+        };
+
+        let mut reader = ReaderBuilder::new()
+            .delimiter(delimiter)
+            .trim(Trim::All)
+            .flexible(true) // Allow rows with different lengths
+            .from_reader(DecodingReader::new(counting, encoding));
+
+        let headers = reader
+            .headers()
+            .map_err(|e| AppError::ParseError(format!("Failed to read CSV headers: {}", e)))?
+            .clone();
+
+        Ok(Self {
+            reader,
+            headers,
+            record: StringRecord::new(),
+            next_index: 0,
+            bytes_read,
+            total_bytes,
+            encoding,
+            delimiter,
+        })
+    }
+
+    /// Header names in file order
+    pub fn headers(&self) -> Vec<String> {
+        self.headers.iter().map(|h| h.to_string()).collect()
+    }
+
+    /// Detected encoding name (e.g. "UTF-8", "UTF-16LE")
+    pub fn encoding_name(&self) -> &'static str {
+        self.encoding.name()
+    }
+
+    /// Detected delimiter
+    pub fn delimiter(&self) -> u8 {
+        self.delimiter
+    }
+
+    /// Raw file bytes consumed so far
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read.load(Ordering::Relaxed)
+    }
+
+    /// File size in bytes
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+}
+
+impl Iterator for CsvStreamReader {
+    type Item = Result<CsvRow, AppError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.next_index;
+        match self.reader.read_record(&mut self.record) {
+            Ok(true) => {
+                self.next_index += 1;
+                Some(Ok(CsvParser::row_from_record(
+                    index,
+                    &self.headers,
+                    &self.record,
+                )))
+            }
+            Ok(false) => None,
+            Err(e) => {
+                self.next_index += 1;
+                Some(Err(AppError::ParseError(format!(
+                    "Failed to parse CSV row {}: {}",
+                    index + 1,
+                    e
+                ))))
+            }
+        }
+    }
+}
+
+/// Counts raw bytes pulled from the underlying file for progress reporting
+struct CountingReader<R> {
+    inner: R,
+    count: Arc<AtomicU64>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
+/// Transcodes any supported encoding to UTF-8 on the fly
+struct DecodingReader<R> {
+    inner: R,
+    decoder: Decoder,
+    input: Vec<u8>,
+    input_pos: usize,
+    input_len: usize,
+    output: Vec<u8>,
+    output_pos: usize,
+    output_len: usize,
+    eof: bool,
+    finished: bool,
+}
+
+impl<R: Read> DecodingReader<R> {
+    fn new(inner: R, encoding: &'static Encoding) -> Self {
+        Self {
+            inner,
+            // BOM sniffing also strips the BOM from the decoded output
+            decoder: encoding.new_decoder(),
+            input: vec![0; DECODE_BUFFER_BYTES],
+            input_pos: 0,
+            input_len: 0,
+            output: vec![0; DECODE_BUFFER_BYTES],
+            output_pos: 0,
+            output_len: 0,
+            eof: false,
+            finished: false,
+        }
+    }
+}
+
+impl<R: Read> Read for DecodingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if self.output_pos < self.output_len {
+                let n = buf.len().min(self.output_len - self.output_pos);
+                buf[..n].copy_from_slice(&self.output[self.output_pos..self.output_pos + n]);
+                self.output_pos += n;
+                return Ok(n);
+            }
+            if self.finished {
+                return Ok(0);
+            }
+
+            if self.input_pos == self.input_len && !self.eof {
+                self.input_len = self.inner.read(&mut self.input)?;
+                self.input_pos = 0;
+                self.eof = self.input_len == 0;
+            }
+
+            let (result, read, written, _) = self.decoder.decode_to_utf8(
+                &self.input[self.input_pos..self.input_len],
+                &mut self.output,
+                self.eof,
+            );
+            self.input_pos += read;
+            self.output_pos = 0;
+            self.output_len = written;
+
+            if self.eof && result == CoderResult::InputEmpty {
+                self.finished = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn utf16le_with_bom(text: &str) -> Vec<u8> {
+        let mut bytes = vec![0xFF, 0xFE];
+        for unit in text.encode_utf16() {
+            bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+        bytes
+    }
+
+    fn write_temp(name: &str, bytes: &[u8]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("{}-{}", std::process::id(), name));
+        let mut file = File::create(&path).unwrap();
+        file.write_all(bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_detect_encoding() {
+        assert_eq!(detect_encoding(b"name,age\nAlice,30"), UTF_8);
+        assert_eq!(detect_encoding(&utf16le_with_bom("a;b\n1;2")), UTF_16LE);
+
+        let bomless: Vec<u8> = "name\tcity\nAlice\tJakarta"
+            .encode_utf16()
+            .flat_map(|unit| unit.to_le_bytes())
+            .collect();
+        assert_eq!(detect_encoding(&bomless), UTF_16LE);
+
+        assert_eq!(detect_encoding(b"caf\xe9,cr\xe8me\n1,2"), WINDOWS_1252);
+    }
+
+    #[test]
+    fn test_streams_utf16_semicolon_file() {
+        let path = write_temp(
+            "stream-utf16.csv",
+            &utf16le_with_bom("name;city\nAlice;Jakarta\nBob;Zürich\n"),
+        );
+        let reader = CsvStreamReader::open(&path).unwrap();
+        assert_eq!(reader.delimiter(), b';');
+        assert_eq!(reader.encoding_name(), "UTF-16LE");
+        assert_eq!(reader.headers(), vec!["name", "city"]);
+
+        let rows: Vec<CsvRow> = reader.map(|row| row.unwrap()).collect();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].field_map["city"], "Zürich");
+    }
+
+    #[test]
+    fn test_streams_tab_file_and_reports_bytes() {
+        let content = "id\tvalue\n1\talpha\n2\tbeta\n";
+        let path = write_temp("stream-tab.csv", content.as_bytes());
+        let mut reader = CsvStreamReader::open(&path).unwrap();
+        assert_eq!(reader.delimiter(), b'\t');
+
+        let count = reader.by_ref().filter_map(|row| row.ok()).count();
+        assert_eq!(count, 2);
+        assert_eq!(reader.bytes_read(), reader.total_bytes());
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -5,6 +5,10 @@
 
 mod csv_parser;
 mod content_analyzer;
+mod column_profiler;
+mod csv_stream;
 
 pub use csv_parser::CsvParser;
 pub use content_analyzer::ContentAnalyzer;
+pub use column_profiler::ColumnProfiler;
+pub use csv_stream::CsvStreamReader;
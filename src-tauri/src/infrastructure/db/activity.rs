@@ -0,0 +1,301 @@
+use crate::domain::activity::{
+    ActivityEntry, ActivityInput, ActivityPage, ActivityQuery, ActivitySource,
+};
+use crate::domain::error::{AppError, Result};
+use sqlx::query::QueryAs;
+use sqlx::sqlite::{
+    SqliteArguments, SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions,
+    SqliteSynchronous,
+};
+use sqlx::Sqlite;
+use std::path::Path;
+use std::time::Duration;
+
+/// Filters shared by the count and the page query, bound by `QueryFilters::bind`
+const FILTER_SQL: &str = "(? IS NULL OR source IN (SELECT value FROM json_each(?)))
+       AND (? IS NULL OR level IN (SELECT lower(value) FROM json_each(?)))
+       AND (? IS NULL OR subject_id = ?)
+       AND (? IS NULL OR ts >= ?)
+       AND (? IS NULL OR ts < ?)
+       AND (? IS NULL OR instr(lower(message), lower(?)) > 0)";
+
+/// The activity timeline in the main database
+pub struct ActivityRepository {
+    pool: SqlitePool,
+}
+
+impl ActivityRepository {
+    pub async fn init(db_path: &Path) -> Result<Self> {
+        let options = SqliteConnectOptions::new()
+            .filename(db_path)
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .synchronous(SqliteSynchronous::Normal)
+            .busy_timeout(Duration::from_secs(5));
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(2)
+            .acquire_timeout(Duration::from_secs(5))
+            .connect_with(options)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to connect activity DB: {e}")))?;
+
+        for statement in [
+            "CREATE TABLE IF NOT EXISTS activity_events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                ts INTEGER NOT NULL,
+                source TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                level TEXT NOT NULL,
+                message TEXT NOT NULL,
+                subject_id TEXT,
+                payload_json TEXT
+            )",
+            "CREATE INDEX IF NOT EXISTS idx_activity_events_ts ON activity_events(ts, id)",
+            "CREATE INDEX IF NOT EXISTS idx_activity_events_source_ts ON activity_events(source, ts)",
+            "CREATE INDEX IF NOT EXISTS idx_activity_events_subject ON activity_events(subject_id)",
+        ] {
+            sqlx::query(statement).execute(&pool).await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to create activity table: {e}"))
+            })?;
+        }
+
+        Ok(Self { pool })
+    }
+
+    pub async fn insert(&self, input: &ActivityInput) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO activity_events (ts, source, kind, level, message, subject_id, payload_json)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(input.ts)
+        .bind(input.source.as_str())
+        .bind(&input.kind)
+        .bind(&input.level)
+        .bind(&input.message)
+        .bind(&input.subject_id)
+        .bind(&input.payload_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to insert activity: {e}")))?;
+
+        Ok(())
+    }
+
+    pub async fn query(&self, query: &ActivityQuery) -> Result<ActivityPage> {
+        let filters = QueryFilters::from_query(query);
+
+        let (total,): (i64,) = filters
+            .bind(sqlx::query_as(&format!(
+                "SELECT COUNT(*) FROM activity_events WHERE {FILTER_SQL}"
+            )))
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to count activity: {e}")))?;
+
+        // The newest page first, then back into chronological order
+        let rows = filters
+            .bind(sqlx::query_as::<_, ActivityEntity>(&format!(
+                "SELECT * FROM (
+                    SELECT id, ts, source, kind, level, message, subject_id, payload_json
+                    FROM activity_events WHERE {FILTER_SQL}
+                    ORDER BY ts DESC, id DESC LIMIT ? OFFSET ?
+                 ) ORDER BY ts ASC, id ASC"
+            )))
+            .bind(query.limit as i64)
+            .bind(query.offset as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to query activity: {e}")))?;
+
+        Ok(ActivityPage {
+            entries: rows
+                .into_iter()
+                .filter_map(ActivityEntity::into_entry)
+                .collect(),
+            total: total.max(0) as usize,
+            offset: query.offset,
+            limit: query.limit,
+        })
+    }
+
+    /// Drops all but the newest `keep` entries. Returns the number removed.
+    pub async fn prune(&self, keep: i64) -> Result<u64> {
+        let result = sqlx::query(
+            "DELETE FROM activity_events WHERE id <= (
+                SELECT id FROM activity_events ORDER BY id DESC LIMIT 1 OFFSET ?
+             )",
+        )
+        .bind(keep)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prune activity: {e}")))?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+/// Filter values, each bound twice: once for the `IS NULL` check, once to match
+struct QueryFilters {
+    sources: Option<String>,
+    levels: Option<String>,
+    subject_id: Option<String>,
+    since: Option<i64>,
+    until: Option<i64>,
+    search: Option<String>,
+}
+
+impl QueryFilters {
+    fn from_query(query: &ActivityQuery) -> Self {
+        let sources: Vec<&str> = query.sources.iter().map(|source| source.as_str()).collect();
+        let levels: Vec<String> = query
+            .levels
+            .iter()
+            .map(|level| level.trim().to_lowercase())
+            .filter(|level| !level.is_empty())
+            .collect();
+        Self {
+            sources: (!sources.is_empty()).then(|| serde_json::json!(sources).to_string()),
+            levels: (!levels.is_empty()).then(|| serde_json::json!(levels).to_string()),
+            subject_id: non_empty(query.subject_id.as_deref()),
+            since: query.since,
+            until: query.until,
+            search: non_empty(query.search.as_deref()),
+        }
+    }
+
+    fn bind<'q, O>(
+        &'q self,
+        query: QueryAs<'q, Sqlite, O, SqliteArguments<'q>>,
+    ) -> QueryAs<'q, Sqlite, O, SqliteArguments<'q>> {
+        query
+            .bind(&self.sources)
+            .bind(&self.sources)
+            .bind(&self.levels)
+            .bind(&self.levels)
+            .bind(&self.subject_id)
+            .bind(&self.subject_id)
+            .bind(self.since)
+            .bind(self.since)
+            .bind(self.until)
+            .bind(self.until)
+            .bind(&self.search)
+            .bind(&self.search)
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+#[derive(sqlx::FromRow)]
+struct ActivityEntity {
+    id: i64,
+    ts: i64,
+    source: String,
+    kind: String,
+    level: String,
+    message: String,
+    subject_id: Option<String>,
+    payload_json: Option<String>,
+}
+
+impl ActivityEntity {
+    /// `None` for rows of a source this build doesn't know
+    fn into_entry(self) -> Option<ActivityEntry> {
+        Some(ActivityEntry {
+            id: self.id,
+            ts: self.ts,
+            source: ActivitySource::parse(&self.source)?,
+            kind: self.kind,
+            level: self.level,
+            message: self.message,
+            subject_id: self.subject_id,
+            payload_json: self.payload_json,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(ts: i64, source: ActivitySource, level: &str, message: &str) -> ActivityInput {
+        ActivityInput {
+            ts,
+            ..ActivityInput::new(source, "test", message).level(level)
+        }
+    }
+
+    #[tokio::test]
+    async fn pages_count_back_from_the_newest_and_read_chronologically() {
+        let dir = std::env::temp_dir().join(format!("activity-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let repo = ActivityRepository::init(&dir.join("activity.db"))
+            .await
+            .unwrap();
+
+        for input in [
+            entry(10, ActivitySource::QaRun, "info", "Run started"),
+            entry(
+                20,
+                ActivitySource::MockServer,
+                "warn",
+                "GET /users not matched",
+            ),
+            entry(
+                30,
+                ActivitySource::RagIngestion,
+                "INFO",
+                "Imported guide.pdf",
+            ),
+            entry(40, ActivitySource::Training, "info", "Epoch 1 step 10"),
+            entry(50, ActivitySource::QaRun, "error", "Run failed"),
+        ] {
+            repo.insert(&input).await.unwrap();
+        }
+
+        let page = repo
+            .query(&ActivityQuery {
+                limit: 2,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(page.total, 5);
+        let ts: Vec<i64> = page.entries.iter().map(|e| e.ts).collect();
+        assert_eq!(ts, vec![40, 50]);
+
+        let qa = repo
+            .query(&ActivityQuery {
+                sources: vec![ActivitySource::QaRun, ActivitySource::RagIngestion],
+                levels: vec!["Info".to_string()],
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let messages: Vec<&str> = qa.entries.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, vec!["Run started", "Imported guide.pdf"]);
+
+        let search = repo
+            .query(&ActivityQuery {
+                search: Some("users".to_string()),
+                since: Some(20),
+                until: Some(50),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(search.total, 1);
+        assert_eq!(search.entries[0].source, ActivitySource::MockServer);
+
+        assert_eq!(repo.prune(2).await.unwrap(), 3);
+        let rest = repo.query(&ActivityQuery::default()).await.unwrap();
+        assert_eq!(rest.entries.first().map(|e| e.ts), Some(40));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,224 @@
+// ============================================================
+// SQLITE MAINTENANCE
+// ============================================================
+// Size and row-count reporting plus the VACUUM, ANALYZE, WAL
+// checkpoint and integrity passes behind `db_maintenance`. Each
+// database gets its own short-lived connection so the app's
+// pools keep running; a failure is recorded on that database's
+// report instead of aborting the others.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{Connection, SqliteConnection};
+
+use crate::domain::db_maintenance::{DatabaseReport, DbMaintenanceRequest, TableStats};
+
+/// VACUUM waits for the app's own writers to finish instead of failing right away
+const BUSY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Path of the `-wal` or `-shm` file that belongs to `path`
+pub fn sidecar(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|meta| meta.len()).unwrap_or(0)
+}
+
+fn total_size(path: &Path) -> u64 {
+    file_size(path) + file_size(&sidecar(path, "-wal")) + file_size(&sidecar(path, "-shm"))
+}
+
+/// Quote an identifier for interpolation into SQL
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+async fn count_tables(conn: &mut SqliteConnection) -> sqlx::Result<Vec<TableStats>> {
+    let names = sqlx::query_scalar::<_, String>(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut tables = Vec::with_capacity(names.len());
+    for name in names {
+        let row_count =
+            sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM {}", quote_ident(&name)))
+                .fetch_one(&mut *conn)
+                .await
+                .ok();
+        tables.push(TableStats { name, row_count });
+    }
+    Ok(tables)
+}
+
+async fn run_passes(
+    conn: &mut SqliteConnection,
+    request: &DbMaintenanceRequest,
+    report: &mut DatabaseReport,
+) -> sqlx::Result<()> {
+    if request.integrity_check {
+        let rows = sqlx::query_scalar::<_, String>("PRAGMA integrity_check")
+            .fetch_all(&mut *conn)
+            .await?;
+        report.integrity_problems = Some(rows.into_iter().filter(|row| row != "ok").collect());
+        report.operations.push("integrity_check".to_string());
+    }
+    if request.checkpoint {
+        sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+            .execute(&mut *conn)
+            .await?;
+        report.operations.push("checkpoint".to_string());
+    }
+    if request.vacuum {
+        sqlx::query("VACUUM").execute(&mut *conn).await?;
+        report.operations.push("vacuum".to_string());
+    }
+    if request.analyze {
+        sqlx::query("ANALYZE").execute(&mut *conn).await?;
+        report.operations.push("analyze".to_string());
+    }
+
+    report.tables = count_tables(conn).await?;
+    Ok(())
+}
+
+/// Report on one database and run the requested passes on it
+pub async fn maintain_database(
+    id: &str,
+    label: &str,
+    path: &Path,
+    request: &DbMaintenanceRequest,
+) -> DatabaseReport {
+    let started = Instant::now();
+    let size_before = total_size(path);
+    let mut report = DatabaseReport {
+        id: id.to_string(),
+        label: label.to_string(),
+        path: path.display().to_string(),
+        exists: path.exists(),
+        size_before_bytes: size_before,
+        size_after_bytes: size_before,
+        wal_size_bytes: file_size(&sidecar(path, "-wal")),
+        tables: Vec::new(),
+        integrity_problems: None,
+        operations: Vec::new(),
+        error: None,
+        duration_ms: 0,
+    };
+    if !report.exists {
+        return report;
+    }
+
+    let options = SqliteConnectOptions::new()
+        .filename(path)
+        .create_if_missing(false)
+        .busy_timeout(BUSY_TIMEOUT);
+    match SqliteConnection::connect_with(&options).await {
+        Ok(mut conn) => {
+            if let Err(err) = run_passes(&mut conn, request, &mut report).await {
+                report.error = Some(err.to_string());
+            }
+            let _ = conn.close().await;
+        }
+        Err(err) => report.error = Some(format!("Cannot open database: {}", err)),
+    }
+
+    report.size_after_bytes = total_size(path);
+    report.wal_size_bytes = file_size(&sidecar(path, "-wal"));
+    report.duration_ms = started.elapsed().as_millis() as u64;
+    report
+}
+
+/// Write a consistent copy of a live database to `dest` with `VACUUM INTO`,
+/// so the app's writers never have to stop
+pub async fn snapshot_database(path: &Path, dest: &Path) -> sqlx::Result<()> {
+    let options = SqliteConnectOptions::new()
+        .filename(path)
+        .create_if_missing(false)
+        .busy_timeout(BUSY_TIMEOUT);
+    let mut conn = SqliteConnection::connect_with(&options).await?;
+    let result = sqlx::query("VACUUM INTO ?")
+        .bind(dest.to_string_lossy().to_string())
+        .execute(&mut conn)
+        .await;
+    let _ = conn.close().await;
+    result.map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn create_db(path: &Path) {
+        let options = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(true);
+        let mut conn = SqliteConnection::connect_with(&options).await.unwrap();
+        sqlx::query("CREATE TABLE \"odd \"\"name\"\"\" (id INTEGER PRIMARY KEY, body TEXT)")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        for _ in 0..200 {
+            sqlx::query("INSERT INTO \"odd \"\"name\"\"\" (body) VALUES (?)")
+                .bind("x".repeat(1024))
+                .execute(&mut conn)
+                .await
+                .unwrap();
+        }
+        sqlx::query("DELETE FROM \"odd \"\"name\"\"\" WHERE id > 10")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        conn.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_vacuum_reclaims_space_and_counts_rows() {
+        let dir = std::env::temp_dir().join(format!("db-maintenance-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("test.db");
+        create_db(&path).await;
+
+        let request = DbMaintenanceRequest {
+            vacuum: true,
+            analyze: true,
+            integrity_check: true,
+            ..Default::default()
+        };
+        let report = maintain_database("test", "Test", &path, &request).await;
+
+        assert_eq!(report.error, None);
+        assert!(report.reclaimed_bytes() > 0);
+        assert_eq!(report.integrity_problems, Some(Vec::new()));
+        assert_eq!(report.operations, ["integrity_check", "vacuum", "analyze"]);
+        let table = report
+            .tables
+            .iter()
+            .find(|table| table.name == "odd \"name\"")
+            .unwrap();
+        assert_eq!(table.row_count, Some(10));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_missing_database_is_reported_not_created() {
+        let path = std::env::temp_dir().join(format!("missing-{}.db", uuid::Uuid::new_v4()));
+        let report = maintain_database(
+            "missing",
+            "Missing",
+            &path,
+            &DbMaintenanceRequest::default(),
+        )
+        .await;
+
+        assert!(!report.exists);
+        assert!(!path.exists());
+    }
+}
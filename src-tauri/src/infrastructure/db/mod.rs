@@ -1,9 +1,14 @@
+pub mod activity;
+pub mod maintenance;
 pub mod qa;
 pub mod qa_api_calls;
 pub mod qa_checkpoints;
 pub mod qa_events;
 pub mod qa_runs;
 pub mod qa_sessions;
+pub mod qa_test_sync;
 pub mod rag;
+pub mod schema;
 pub mod sqlite;
+pub mod telemetry;
 pub mod training;
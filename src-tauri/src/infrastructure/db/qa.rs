@@ -5,6 +5,8 @@ use std::path::Path;
 use std::str::FromStr;
 use std::time::Duration;
 
+use super::schema::ensure_column;
+
 const QA_SCHEMA: &str = include_str!("../../resources/qa/schema.sql");
 
 pub async fn init_qa_db(db_path: &Path) -> Result<(), String> {
@@ -24,6 +26,27 @@ pub async fn init_qa_db(db_path: &Path) -> Result<(), String> {
         .map_err(|e| format!("Failed to connect to QA database: {e}"))?;
 
     apply_schema(&pool).await?;
+    ensure_column(&pool, "sessions", "selector_attributes_json", "TEXT").await?;
+    // Set while the session is in the trash (unix ms)
+    ensure_column(&pool, "sessions", "deleted_at", "INTEGER").await?;
+    ensure_column(&pool, "api_calls", "response_body_json", "TEXT").await?;
+    ensure_column(&pool, "checkpoints", "notes", "TEXT").await?;
+    ensure_column(&pool, "test_cases", "assertions_json", "TEXT").await?;
+    ensure_column(&pool, "checkpoints", "updated_at", "INTEGER").await?;
+    ensure_column(&pool, "checkpoint_summaries", "api_links_json", "TEXT").await?;
+    for (column, definition) in [
+        ("prompt_tokens", "INTEGER"),
+        ("completion_tokens", "INTEGER"),
+        ("tokens_estimated", "INTEGER NOT NULL DEFAULT 0"),
+        ("latency_ms", "INTEGER"),
+        ("cost_usd", "REAL"),
+        ("system_prompt", "TEXT"),
+        ("user_prompt", "TEXT"),
+        ("response_text", "TEXT"),
+        ("bodies_truncated", "INTEGER NOT NULL DEFAULT 0"),
+    ] {
+        ensure_column(&pool, "llm_runs", column, definition).await?;
+    }
 
     sqlx::query("SELECT 1")
         .execute(&pool)
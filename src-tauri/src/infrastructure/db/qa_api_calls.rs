@@ -33,8 +33,8 @@ impl QaApiCallRepository {
 
     pub async fn insert_call(&self, call: &QaApiCall) -> Result<()> {
         sqlx::query(
-            "INSERT INTO api_calls (id, session_id, run_id, event_request_id, event_response_id, method, url, request_headers_json, request_body_json, request_body_hash, response_status, response_headers_json, response_body_hash, timing_ms, created_at)
-             VALUES (?, ?, ?, NULL, NULL, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO api_calls (id, session_id, run_id, event_request_id, event_response_id, method, url, request_headers_json, request_body_json, request_body_hash, response_status, response_headers_json, response_body_hash, response_body_json, timing_ms, created_at)
+             VALUES (?, ?, ?, NULL, NULL, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(&call.id)
         .bind(&call.session_id)
@@ -47,6 +47,7 @@ impl QaApiCallRepository {
         .bind(call.response_status)
         .bind(&call.response_headers_json)
         .bind(&call.response_body_hash)
+        .bind(&call.response_body_json)
         .bind(call.timing_ms)
         .bind(call.created_at)
         .execute(&self.pool)
@@ -55,6 +56,39 @@ impl QaApiCallRepository {
 
         Ok(())
     }
+
+    pub async fn list_calls_by_session(&self, session_id: &str) -> Result<Vec<QaApiCall>> {
+        let calls = sqlx::query_as::<_, QaApiCallEntity>(
+            "SELECT id, session_id, run_id, method, url, request_headers_json, request_body_json, request_body_hash, response_status, response_headers_json, response_body_hash, response_body_json, timing_ms, created_at
+             FROM api_calls WHERE session_id = ? ORDER BY created_at ASC",
+        )
+        .bind(session_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list API calls: {e}")))?;
+
+        Ok(calls.into_iter().map(|call| call.into()).collect())
+    }
+
+    /// Rewrite the recorded URL, headers and bodies of a call
+    pub async fn update_call_contents(&self, call: &QaApiCall) -> Result<()> {
+        sqlx::query(
+            "UPDATE api_calls SET url = ?, request_headers_json = ?, request_body_json = ?, response_headers_json = ?, response_body_json = ?
+             WHERE id = ? AND session_id = ?",
+        )
+        .bind(&call.url)
+        .bind(&call.request_headers_json)
+        .bind(&call.request_body_json)
+        .bind(&call.response_headers_json)
+        .bind(&call.response_body_json)
+        .bind(&call.id)
+        .bind(&call.session_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update API call: {e}")))?;
+
+        Ok(())
+    }
 }
 
 fn db_path_to_url(db_path: &Path) -> Result<String> {
@@ -63,3 +97,42 @@ fn db_path_to_url(db_path: &Path) -> Result<String> {
     })?;
     Ok(format!("sqlite://{}", db_path_str.replace("\\", "/")))
 }
+
+#[derive(sqlx::FromRow)]
+struct QaApiCallEntity {
+    id: String,
+    session_id: String,
+    run_id: String,
+    method: String,
+    url: String,
+    request_headers_json: Option<String>,
+    request_body_json: Option<String>,
+    request_body_hash: Option<String>,
+    response_status: Option<i64>,
+    response_headers_json: Option<String>,
+    response_body_hash: Option<String>,
+    response_body_json: Option<String>,
+    timing_ms: Option<i64>,
+    created_at: i64,
+}
+
+impl From<QaApiCallEntity> for QaApiCall {
+    fn from(entity: QaApiCallEntity) -> Self {
+        Self {
+            id: entity.id,
+            session_id: entity.session_id,
+            run_id: entity.run_id,
+            method: entity.method,
+            url: entity.url,
+            request_headers_json: entity.request_headers_json,
+            request_body_json: entity.request_body_json,
+            request_body_hash: entity.request_body_hash,
+            response_status: entity.response_status,
+            response_headers_json: entity.response_headers_json,
+            response_body_hash: entity.response_body_hash,
+            response_body_json: entity.response_body_json,
+            timing_ms: entity.timing_ms,
+            created_at: entity.created_at,
+        }
+    }
+}
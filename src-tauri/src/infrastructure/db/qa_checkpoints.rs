@@ -1,5 +1,8 @@
 use crate::domain::error::{AppError, Result};
-use crate::domain::qa_checkpoint::{QaCheckpoint, QaCheckpointSummary, QaLlmRun, QaTestCase};
+use crate::domain::qa_checkpoint::{
+    QaCheckpoint, QaCheckpointSummary, QaLlmRun, QaLlmRunBodies, QaLlmRunDetail, QaLlmUsage,
+    QaTestCase,
+};
 use sqlx::sqlite::{
     SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions, SqliteSynchronous,
 };
@@ -71,12 +74,14 @@ impl QaCheckpointRepository {
             start_event_seq,
             end_event_seq,
             created_at,
+            notes: None,
+            updated_at: None,
         })
     }
 
     pub async fn list_checkpoints(&self, session_id: &str) -> Result<Vec<QaCheckpoint>> {
         let checkpoints = sqlx::query_as::<_, QaCheckpointEntity>(
-            "SELECT id, session_id, seq, title, start_event_seq, end_event_seq, created_at
+            "SELECT id, session_id, seq, title, start_event_seq, end_event_seq, created_at, notes, updated_at
              FROM checkpoints WHERE session_id = ? ORDER BY seq ASC",
         )
         .bind(session_id)
@@ -92,7 +97,7 @@ impl QaCheckpointRepository {
 
     pub async fn latest_checkpoint(&self, session_id: &str) -> Result<Option<QaCheckpoint>> {
         let checkpoint = sqlx::query_as::<_, QaCheckpointEntity>(
-            "SELECT id, session_id, seq, title, start_event_seq, end_event_seq, created_at
+            "SELECT id, session_id, seq, title, start_event_seq, end_event_seq, created_at, notes, updated_at
              FROM checkpoints WHERE session_id = ? ORDER BY seq DESC LIMIT 1",
         )
         .bind(session_id)
@@ -105,7 +110,7 @@ impl QaCheckpointRepository {
 
     pub async fn get_checkpoint(&self, checkpoint_id: &str) -> Result<QaCheckpoint> {
         let checkpoint = sqlx::query_as::<_, QaCheckpointEntity>(
-            "SELECT id, session_id, seq, title, start_event_seq, end_event_seq, created_at
+            "SELECT id, session_id, seq, title, start_event_seq, end_event_seq, created_at, notes, updated_at
              FROM checkpoints WHERE id = ?",
         )
         .bind(checkpoint_id)
@@ -118,6 +123,160 @@ impl QaCheckpointRepository {
             .ok_or_else(|| AppError::NotFound("Checkpoint not found.".to_string()))
     }
 
+    pub async fn update_checkpoint_details(
+        &self,
+        checkpoint_id: &str,
+        title: Option<&str>,
+        notes: Option<&str>,
+    ) -> Result<()> {
+        let result = sqlx::query("UPDATE checkpoints SET title = ?, notes = ? WHERE id = ?")
+            .bind(title)
+            .bind(notes)
+            .bind(checkpoint_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to update checkpoint: {e}")))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Checkpoint not found.".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Fold `removed` into `kept`, which takes over its events, test cases and
+    /// LLM runs. The summary of `removed` is dropped and later checkpoints
+    /// move up one seq.
+    pub async fn merge_checkpoints(
+        &self,
+        kept: &QaCheckpoint,
+        removed: &QaCheckpoint,
+        notes: Option<&str>,
+        updated_at: i64,
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to start checkpoint merge txn: {e}"))
+        })?;
+
+        sqlx::query(
+            "UPDATE checkpoints SET start_event_seq = ?, end_event_seq = ?, notes = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(kept.start_event_seq.min(removed.start_event_seq))
+        .bind(kept.end_event_seq.max(removed.end_event_seq))
+        .bind(notes)
+        .bind(updated_at)
+        .bind(&kept.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to extend checkpoint: {e}")))?;
+
+        sqlx::query("UPDATE test_cases SET checkpoint_id = ? WHERE checkpoint_id = ?")
+            .bind(&kept.id)
+            .bind(&removed.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to move test cases: {e}")))?;
+
+        sqlx::query("UPDATE llm_runs SET scope_id = ? WHERE scope_id = ?")
+            .bind(&kept.id)
+            .bind(&removed.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to move LLM runs: {e}")))?;
+
+        sqlx::query("DELETE FROM checkpoint_summaries WHERE checkpoint_id = ?")
+            .bind(&removed.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to delete checkpoint summaries: {e}"))
+            })?;
+
+        sqlx::query("DELETE FROM checkpoints WHERE id = ?")
+            .bind(&removed.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to delete checkpoint: {e}")))?;
+
+        sqlx::query("UPDATE checkpoints SET seq = seq - 1 WHERE session_id = ? AND seq > ?")
+            .bind(&removed.session_id)
+            .bind(removed.seq)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to renumber checkpoints: {e}")))?;
+
+        tx.commit().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to commit checkpoint merge: {e}"))
+        })?;
+
+        Ok(())
+    }
+
+    /// End `checkpoint` just before `at_event_seq` and insert a new checkpoint
+    /// for the rest of its events right after it. Test cases and LLM runs stay
+    /// with the first part.
+    pub async fn split_checkpoint(
+        &self,
+        checkpoint: &QaCheckpoint,
+        at_event_seq: i64,
+        id: String,
+        title: Option<String>,
+        updated_at: i64,
+    ) -> Result<QaCheckpoint> {
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to start checkpoint split txn: {e}"))
+        })?;
+
+        sqlx::query("UPDATE checkpoints SET seq = seq + 1 WHERE session_id = ? AND seq > ?")
+            .bind(&checkpoint.session_id)
+            .bind(checkpoint.seq)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to renumber checkpoints: {e}")))?;
+
+        sqlx::query("UPDATE checkpoints SET end_event_seq = ?, updated_at = ? WHERE id = ?")
+            .bind(at_event_seq - 1)
+            .bind(updated_at)
+            .bind(&checkpoint.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to shorten checkpoint: {e}")))?;
+
+        let second = QaCheckpoint {
+            id,
+            session_id: checkpoint.session_id.clone(),
+            seq: checkpoint.seq + 1,
+            title,
+            start_event_seq: at_event_seq,
+            end_event_seq: checkpoint.end_event_seq,
+            created_at: updated_at,
+            notes: None,
+            updated_at: Some(updated_at),
+        };
+        sqlx::query(
+            "INSERT INTO checkpoints (id, session_id, seq, title, start_event_seq, end_event_seq, created_at, notes, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&second.id)
+        .bind(&second.session_id)
+        .bind(second.seq)
+        .bind(&second.title)
+        .bind(second.start_event_seq)
+        .bind(second.end_event_seq)
+        .bind(second.created_at)
+        .bind(&second.notes)
+        .bind(second.updated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to insert checkpoint: {e}")))?;
+
+        tx.commit().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to commit checkpoint split: {e}"))
+        })?;
+
+        Ok(second)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub async fn insert_checkpoint_summary(
         &self,
         id: String,
@@ -125,17 +284,19 @@ impl QaCheckpointRepository {
         summary_text: String,
         entities_json: Option<String>,
         risks_json: Option<String>,
+        api_links_json: Option<String>,
         created_at: i64,
     ) -> Result<QaCheckpointSummary> {
         sqlx::query(
-            "INSERT INTO checkpoint_summaries (id, checkpoint_id, summary_text, entities_json, risks_json, created_at)
-             VALUES (?, ?, ?, ?, ?, ?)",
+            "INSERT INTO checkpoint_summaries (id, checkpoint_id, summary_text, entities_json, risks_json, api_links_json, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(&id)
         .bind(checkpoint_id)
         .bind(&summary_text)
         .bind(&entities_json)
         .bind(&risks_json)
+        .bind(&api_links_json)
         .bind(created_at)
         .execute(&self.pool)
         .await
@@ -148,6 +309,7 @@ impl QaCheckpointRepository {
             entities_json,
             risks_json,
             created_at,
+            api_links_json,
         })
     }
 
@@ -156,7 +318,7 @@ impl QaCheckpointRepository {
         session_id: &str,
     ) -> Result<Vec<QaCheckpointSummary>> {
         let summaries = sqlx::query_as::<_, QaCheckpointSummaryEntity>(
-            "SELECT s.id, s.checkpoint_id, s.summary_text, s.entities_json, s.risks_json, s.created_at
+            "SELECT s.id, s.checkpoint_id, s.summary_text, s.entities_json, s.risks_json, s.created_at, s.api_links_json
              FROM checkpoint_summaries s
              JOIN checkpoints c ON s.checkpoint_id = c.id
              WHERE c.session_id = ?
@@ -178,7 +340,7 @@ impl QaCheckpointRepository {
         checkpoint_id: &str,
     ) -> Result<Option<QaCheckpointSummary>> {
         let summary = sqlx::query_as::<_, QaCheckpointSummaryEntity>(
-            "SELECT id, checkpoint_id, summary_text, entities_json, risks_json, created_at
+            "SELECT id, checkpoint_id, summary_text, entities_json, risks_json, created_at, api_links_json
              FROM checkpoint_summaries WHERE checkpoint_id = ? ORDER BY created_at DESC LIMIT 1",
         )
         .bind(checkpoint_id)
@@ -191,8 +353,8 @@ impl QaCheckpointRepository {
 
     pub async fn insert_test_case(&self, test_case: &QaTestCase) -> Result<()> {
         sqlx::query(
-            "INSERT INTO test_cases (id, session_id, checkpoint_id, type, title, steps_json, expected, priority, status, dedup_hash, created_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO test_cases (id, session_id, checkpoint_id, type, title, steps_json, expected, priority, status, dedup_hash, assertions_json, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(&test_case.id)
         .bind(&test_case.session_id)
@@ -204,6 +366,7 @@ impl QaCheckpointRepository {
         .bind(&test_case.priority)
         .bind(&test_case.status)
         .bind(&test_case.dedup_hash)
+        .bind(&test_case.assertions_json)
         .bind(test_case.created_at)
         .execute(&self.pool)
         .await
@@ -214,7 +377,7 @@ impl QaCheckpointRepository {
 
     pub async fn list_test_cases(&self, session_id: &str) -> Result<Vec<QaTestCase>> {
         let cases = sqlx::query_as::<_, QaTestCaseEntity>(
-            "SELECT id, session_id, checkpoint_id, type, title, steps_json, expected, priority, status, dedup_hash, created_at
+            "SELECT id, session_id, checkpoint_id, type, title, steps_json, expected, priority, status, dedup_hash, assertions_json, created_at
              FROM test_cases WHERE session_id = ? ORDER BY created_at DESC",
         )
         .bind(session_id)
@@ -230,7 +393,7 @@ impl QaCheckpointRepository {
         checkpoint_id: &str,
     ) -> Result<Vec<QaTestCase>> {
         let cases = sqlx::query_as::<_, QaTestCaseEntity>(
-            "SELECT id, session_id, checkpoint_id, type, title, steps_json, expected, priority, status, dedup_hash, created_at
+            "SELECT id, session_id, checkpoint_id, type, title, steps_json, expected, priority, status, dedup_hash, assertions_json, created_at
              FROM test_cases WHERE checkpoint_id = ? ORDER BY created_at DESC",
         )
         .bind(checkpoint_id)
@@ -241,10 +404,11 @@ impl QaCheckpointRepository {
         Ok(cases.into_iter().map(|case| case.into()).collect())
     }
 
-    pub async fn insert_llm_run(&self, run: &QaLlmRun) -> Result<()> {
+    pub async fn insert_llm_run(&self, run: &QaLlmRun, bodies: &QaLlmRunBodies) -> Result<()> {
         sqlx::query(
-            "INSERT INTO llm_runs (id, scope, scope_id, model, prompt_version, input_digest, input_summary, output_json, created_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO llm_runs (id, scope, scope_id, model, prompt_version, input_digest, input_summary, output_json, created_at,
+                prompt_tokens, completion_tokens, tokens_estimated, latency_ms, cost_usd, system_prompt, user_prompt, response_text, bodies_truncated)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(&run.id)
         .bind(&run.scope)
@@ -255,6 +419,15 @@ impl QaCheckpointRepository {
         .bind(&run.input_summary)
         .bind(&run.output_json)
         .bind(run.created_at)
+        .bind(run.usage.prompt_tokens)
+        .bind(run.usage.completion_tokens)
+        .bind(run.usage.tokens_estimated)
+        .bind(run.usage.latency_ms)
+        .bind(run.usage.cost_usd)
+        .bind(&bodies.system_prompt)
+        .bind(&bodies.user_prompt)
+        .bind(&bodies.response_text)
+        .bind(bodies.truncated)
         .execute(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to insert LLM run: {e}")))?;
@@ -264,7 +437,8 @@ impl QaCheckpointRepository {
 
     pub async fn list_llm_runs(&self, session_id: &str) -> Result<Vec<QaLlmRun>> {
         let runs = sqlx::query_as::<_, QaLlmRunEntity>(
-            "SELECT lr.id, lr.scope, lr.scope_id, lr.model, lr.prompt_version, lr.input_digest, lr.input_summary, lr.output_json, lr.created_at
+            "SELECT lr.id, lr.scope, lr.scope_id, lr.model, lr.prompt_version, lr.input_digest, lr.input_summary, lr.output_json, lr.created_at,
+                lr.prompt_tokens, lr.completion_tokens, lr.tokens_estimated, lr.latency_ms, lr.cost_usd
              FROM llm_runs lr
              JOIN checkpoints c ON lr.scope_id = c.id
              WHERE c.session_id = ?
@@ -277,6 +451,31 @@ impl QaCheckpointRepository {
 
         Ok(runs.into_iter().map(|run| run.into()).collect())
     }
+
+    pub async fn get_llm_run_detail(&self, run_id: &str) -> Result<QaLlmRunDetail> {
+        let row = sqlx::query_as::<_, QaLlmRunDetailEntity>(
+            "SELECT id, scope, scope_id, model, prompt_version, input_digest, input_summary, output_json, created_at,
+                prompt_tokens, completion_tokens, tokens_estimated, latency_ms, cost_usd,
+                system_prompt, user_prompt, response_text, bodies_truncated
+             FROM llm_runs
+             WHERE id = ?",
+        )
+        .bind(run_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load LLM run: {e}")))?;
+
+        row.map(|row| QaLlmRunDetail {
+            bodies: QaLlmRunBodies {
+                system_prompt: row.system_prompt,
+                user_prompt: row.user_prompt,
+                response_text: row.response_text,
+                truncated: row.bodies_truncated != 0,
+            },
+            run: row.run.into(),
+        })
+        .ok_or_else(|| AppError::NotFound("LLM run not found.".to_string()))
+    }
 }
 
 fn db_path_to_url(db_path: &Path) -> Result<String> {
@@ -295,6 +494,8 @@ struct QaCheckpointEntity {
     start_event_seq: i64,
     end_event_seq: i64,
     created_at: i64,
+    notes: Option<String>,
+    updated_at: Option<i64>,
 }
 
 impl From<QaCheckpointEntity> for QaCheckpoint {
@@ -307,6 +508,8 @@ impl From<QaCheckpointEntity> for QaCheckpoint {
             start_event_seq: entity.start_event_seq,
             end_event_seq: entity.end_event_seq,
             created_at: entity.created_at,
+            notes: entity.notes,
+            updated_at: entity.updated_at,
         }
     }
 }
@@ -319,6 +522,7 @@ struct QaCheckpointSummaryEntity {
     entities_json: Option<String>,
     risks_json: Option<String>,
     created_at: i64,
+    api_links_json: Option<String>,
 }
 
 impl From<QaCheckpointSummaryEntity> for QaCheckpointSummary {
@@ -330,6 +534,7 @@ impl From<QaCheckpointSummaryEntity> for QaCheckpointSummary {
             entities_json: entity.entities_json,
             risks_json: entity.risks_json,
             created_at: entity.created_at,
+            api_links_json: entity.api_links_json,
         }
     }
 }
@@ -347,6 +552,7 @@ struct QaTestCaseEntity {
     priority: Option<String>,
     status: Option<String>,
     dedup_hash: String,
+    assertions_json: Option<String>,
     created_at: i64,
 }
 
@@ -363,6 +569,7 @@ impl From<QaTestCaseEntity> for QaTestCase {
             priority: entity.priority,
             status: entity.status,
             dedup_hash: entity.dedup_hash,
+            assertions_json: entity.assertions_json,
             created_at: entity.created_at,
         }
     }
@@ -379,6 +586,21 @@ struct QaLlmRunEntity {
     input_summary: Option<String>,
     output_json: String,
     created_at: i64,
+    prompt_tokens: Option<i64>,
+    completion_tokens: Option<i64>,
+    tokens_estimated: i64,
+    latency_ms: Option<i64>,
+    cost_usd: Option<f64>,
+}
+
+#[derive(sqlx::FromRow)]
+struct QaLlmRunDetailEntity {
+    #[sqlx(flatten)]
+    run: QaLlmRunEntity,
+    system_prompt: Option<String>,
+    user_prompt: Option<String>,
+    response_text: Option<String>,
+    bodies_truncated: i64,
 }
 
 impl From<QaLlmRunEntity> for QaLlmRun {
@@ -393,6 +615,13 @@ impl From<QaLlmRunEntity> for QaLlmRun {
             input_summary: entity.input_summary,
             output_json: entity.output_json,
             created_at: entity.created_at,
+            usage: QaLlmUsage {
+                prompt_tokens: entity.prompt_tokens,
+                completion_tokens: entity.completion_tokens,
+                tokens_estimated: entity.tokens_estimated != 0,
+                latency_ms: entity.latency_ms,
+                cost_usd: entity.cost_usd,
+            },
         }
     }
 }
@@ -1,5 +1,7 @@
 use crate::domain::error::{AppError, Result};
+use crate::domain::qa_api_link::QaApiLink;
 use crate::domain::qa_event::{QaEvent, QaEventSummary};
+use crate::domain::qa_highlight::annotated_screenshot_path;
 use sqlx::sqlite::{
     SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions, SqliteSynchronous,
 };
@@ -107,6 +109,22 @@ impl QaEventRepository {
         Ok(events.into_iter().map(|event| event.into()).collect())
     }
 
+    pub async fn get_event(&self, session_id: &str, event_id: &str) -> Result<Option<QaEvent>> {
+        let event = sqlx::query_as::<_, QaEventEntity>(
+            "SELECT e.id, e.session_id, e.run_id, e.checkpoint_id, e.seq, e.ts, e.event_type, e.origin, e.recording_mode, e.selector, e.element_text, e.value, e.url, e.screenshot_id, e.meta_json, a.path as screenshot_path
+             FROM events e
+             LEFT JOIN artifacts a ON e.screenshot_id = a.id
+             WHERE e.session_id = ? AND e.id = ?",
+        )
+        .bind(session_id)
+        .bind(event_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch QA event: {e}")))?;
+
+        Ok(event.map(|event| event.into()))
+    }
+
     pub async fn latest_event_id(&self, session_id: &str) -> Result<Option<String>> {
         let event_id = sqlx::query_scalar::<_, String>(
             "SELECT id FROM events WHERE session_id = ? ORDER BY seq DESC LIMIT 1",
@@ -153,6 +171,97 @@ impl QaEventRepository {
         Ok(events.into_iter().map(|event| event.into()).collect())
     }
 
+    /// Events recorded from `since_ts` (ms) on, oldest first
+    pub async fn list_events_since(&self, session_id: &str, since_ts: i64) -> Result<Vec<QaEvent>> {
+        let events = sqlx::query_as::<_, QaEventEntity>(
+            "SELECT e.id, e.session_id, e.run_id, e.checkpoint_id, e.seq, e.ts, e.event_type, e.origin, e.recording_mode, e.selector, e.element_text, e.value, e.url, e.screenshot_id, e.meta_json, a.path as screenshot_path
+             FROM events e
+             LEFT JOIN artifacts a ON e.screenshot_id = a.id
+             WHERE e.session_id = ? AND e.ts >= ? ORDER BY e.seq ASC",
+        )
+        .bind(session_id)
+        .bind(since_ts)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list recent QA events: {e}")))?;
+
+        Ok(events.into_iter().map(|event| event.into()).collect())
+    }
+
+    pub async fn upsert_api_link(&self, link: &QaApiLink) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO api_event_links (response_event_id, session_id, response_seq, trigger_event_id, trigger_seq, delay_ms, same_origin)
+             VALUES (?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(response_event_id) DO UPDATE SET
+               trigger_event_id = excluded.trigger_event_id,
+               trigger_seq = excluded.trigger_seq,
+               delay_ms = excluded.delay_ms,
+               same_origin = excluded.same_origin",
+        )
+        .bind(&link.response_event_id)
+        .bind(&link.session_id)
+        .bind(link.response_seq)
+        .bind(&link.trigger_event_id)
+        .bind(link.trigger_seq)
+        .bind(link.delay_ms)
+        .bind(link.same_origin)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to store API link: {e}")))?;
+
+        Ok(())
+    }
+
+    /// Replace every link of the session
+    pub async fn replace_api_links(&self, session_id: &str, links: &[QaApiLink]) -> Result<()> {
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to start API link tx: {e}"))
+            })?;
+
+        sqlx::query("DELETE FROM api_event_links WHERE session_id = ?")
+            .bind(session_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to clear API links: {e}")))?;
+
+        for link in links {
+            sqlx::query(
+                "INSERT INTO api_event_links (response_event_id, session_id, response_seq, trigger_event_id, trigger_seq, delay_ms, same_origin)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&link.response_event_id)
+            .bind(session_id)
+            .bind(link.response_seq)
+            .bind(&link.trigger_event_id)
+            .bind(link.trigger_seq)
+            .bind(link.delay_ms)
+            .bind(link.same_origin)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to store API link: {e}")))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit API link tx: {e}")))?;
+
+        Ok(())
+    }
+
+    pub async fn list_api_links(&self, session_id: &str) -> Result<Vec<QaApiLink>> {
+        let links = sqlx::query_as::<_, QaApiLinkEntity>(
+            "SELECT response_event_id, session_id, response_seq, trigger_event_id, trigger_seq, delay_ms, same_origin
+             FROM api_event_links WHERE session_id = ? ORDER BY response_seq ASC",
+        )
+        .bind(session_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list API links: {e}")))?;
+
+        Ok(links.into_iter().map(|link| link.into()).collect())
+    }
+
     pub async fn attach_screenshot(
         &self,
         session_id: &str,
@@ -243,6 +352,25 @@ impl QaEventRepository {
         Ok(count)
     }
 
+    /// Rewrite the recorded text, value, URL and metadata of an event
+    pub async fn update_event_contents(&self, event: &QaEvent) -> Result<()> {
+        sqlx::query(
+            "UPDATE events SET element_text = ?, value = ?, url = ?, meta_json = ?
+             WHERE id = ? AND session_id = ?",
+        )
+        .bind(&event.element_text)
+        .bind(&event.value)
+        .bind(&event.url)
+        .bind(&event.meta_json)
+        .bind(&event.id)
+        .bind(&event.session_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update QA event: {e}")))?;
+
+        Ok(())
+    }
+
     pub async fn delete_events(&self, session_id: &str, event_ids: &[String]) -> Result<u64> {
         if event_ids.is_empty() {
             return Ok(0);
@@ -264,6 +392,20 @@ impl QaEventRepository {
             .await
             .map_err(|e| AppError::DatabaseError(format!("Failed to delete QA events: {e}")))?;
 
+        // Links of a deleted response or action point at nothing
+        let query = format!(
+            "DELETE FROM api_event_links WHERE session_id = ? AND (response_event_id IN ({0}) OR trigger_event_id IN ({0}))",
+            placeholders
+        );
+        let mut statement = sqlx::query(&query).bind(session_id);
+        for id in event_ids.iter().chain(event_ids) {
+            statement = statement.bind(id);
+        }
+        statement
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to delete API links: {e}")))?;
+
         Ok(result.rows_affected())
     }
 }
@@ -297,6 +439,12 @@ struct QaEventEntity {
 
 impl From<QaEventEntity> for QaEvent {
     fn from(entity: QaEventEntity) -> Self {
+        let annotated_screenshot_path = entity
+            .screenshot_path
+            .as_deref()
+            .map(|path| annotated_screenshot_path(Path::new(path)))
+            .filter(|path| path.exists())
+            .map(|path| path.to_string_lossy().to_string());
         Self {
             id: entity.id,
             session_id: entity.session_id,
@@ -313,6 +461,7 @@ impl From<QaEventEntity> for QaEvent {
             url: entity.url,
             screenshot_id: entity.screenshot_id,
             screenshot_path: entity.screenshot_path,
+            annotated_screenshot_path,
             meta_json: entity.meta_json,
         }
     }
@@ -336,3 +485,28 @@ impl From<QaEventSummaryEntity> for QaEventSummary {
         }
     }
 }
+
+#[derive(sqlx::FromRow)]
+struct QaApiLinkEntity {
+    response_event_id: String,
+    session_id: String,
+    response_seq: i64,
+    trigger_event_id: String,
+    trigger_seq: i64,
+    delay_ms: i64,
+    same_origin: bool,
+}
+
+impl From<QaApiLinkEntity> for QaApiLink {
+    fn from(entity: QaApiLinkEntity) -> Self {
+        Self {
+            session_id: entity.session_id,
+            response_event_id: entity.response_event_id,
+            response_seq: entity.response_seq,
+            trigger_event_id: entity.trigger_event_id,
+            trigger_seq: entity.trigger_seq,
+            delay_ms: entity.delay_ms,
+            same_origin: entity.same_origin,
+        }
+    }
+}
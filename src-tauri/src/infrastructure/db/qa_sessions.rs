@@ -1,5 +1,6 @@
 use crate::domain::error::{AppError, Result};
 use crate::domain::qa_session::QaSession;
+use crate::domain::trash::{TrashEntry, TrashKind};
 use sqlx::sqlite::{
     SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions, SqliteSynchronous,
 };
@@ -33,8 +34,8 @@ impl QaRepository {
 
     pub async fn insert_session(&self, session: &QaSession) -> Result<()> {
         sqlx::query(
-            "INSERT INTO sessions (id, title, goal, session_type, is_positive_case, target_url, api_base_url, auth_profile_json, source_session_id, app_version, os, started_at, ended_at, notes)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO sessions (id, title, goal, session_type, is_positive_case, target_url, api_base_url, auth_profile_json, source_session_id, app_version, os, started_at, ended_at, notes, selector_attributes_json)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(&session.id)
         .bind(&session.title)
@@ -50,6 +51,7 @@ impl QaRepository {
         .bind(session.started_at)
         .bind(session.ended_at)
         .bind(&session.notes)
+        .bind(selector_attributes_json(session.selector_attributes.as_deref()))
         .execute(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to insert QA session: {e}")))?;
@@ -59,7 +61,7 @@ impl QaRepository {
 
     pub async fn get_session(&self, session_id: &str) -> Result<QaSession> {
         let session = sqlx::query_as::<_, QaSessionEntity>(
-            "SELECT id, title, goal, session_type, is_positive_case, target_url, api_base_url, auth_profile_json, source_session_id, app_version, os, started_at, ended_at, notes
+            "SELECT id, title, goal, session_type, is_positive_case, target_url, api_base_url, auth_profile_json, source_session_id, app_version, os, started_at, ended_at, notes, selector_attributes_json
              FROM sessions WHERE id = ?",
         )
         .bind(session_id)
@@ -101,10 +103,33 @@ impl QaRepository {
         }
     }
 
+    pub async fn update_selector_attributes(
+        &self,
+        session_id: &str,
+        attributes: Option<&[String]>,
+    ) -> Result<QaSession> {
+        let result = sqlx::query("UPDATE sessions SET selector_attributes_json = ? WHERE id = ?")
+            .bind(selector_attributes_json(attributes))
+            .bind(session_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to update QA session selectors: {e}"))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "QA session not found: {}",
+                session_id
+            )));
+        }
+        self.get_session(session_id).await
+    }
+
     pub async fn list_sessions(&self, limit: i64) -> Result<Vec<QaSession>> {
         let sessions = sqlx::query_as::<_, QaSessionEntity>(
-            "SELECT id, title, goal, session_type, is_positive_case, target_url, api_base_url, auth_profile_json, source_session_id, app_version, os, started_at, ended_at, notes
-             FROM sessions ORDER BY started_at DESC LIMIT ?",
+            "SELECT id, title, goal, session_type, is_positive_case, target_url, api_base_url, auth_profile_json, source_session_id, app_version, os, started_at, ended_at, notes, selector_attributes_json
+             FROM sessions WHERE deleted_at IS NULL ORDER BY started_at DESC LIMIT ?",
         )
         .bind(limit)
         .fetch_all(&self.pool)
@@ -114,6 +139,113 @@ impl QaRepository {
         Ok(sessions.into_iter().map(|session| session.into()).collect())
     }
 
+    pub async fn list_sessions_started_before(&self, cutoff: i64) -> Result<Vec<QaSession>> {
+        let sessions = sqlx::query_as::<_, QaSessionEntity>(
+            "SELECT id, title, goal, session_type, is_positive_case, target_url, api_base_url, auth_profile_json, source_session_id, app_version, os, started_at, ended_at, notes, selector_attributes_json
+             FROM sessions WHERE started_at < ? ORDER BY started_at ASC",
+        )
+        .bind(cutoff)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list expired QA sessions: {e}")))?;
+
+        Ok(sessions.into_iter().map(|session| session.into()).collect())
+    }
+
+    /// Move a session to the trash; returns 0 when it does not exist or is
+    /// already there
+    pub async fn trash_session(&self, session_id: &str, deleted_at: i64) -> Result<u64> {
+        let result =
+            sqlx::query("UPDATE sessions SET deleted_at = ? WHERE id = ? AND deleted_at IS NULL")
+                .bind(deleted_at)
+                .bind(session_id)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to trash QA session: {e}")))?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn restore_session(&self, session_id: &str) -> Result<u64> {
+        let result = sqlx::query(
+            "UPDATE sessions SET deleted_at = NULL WHERE id = ? AND deleted_at IS NOT NULL",
+        )
+        .bind(session_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to restore QA session: {e}")))?;
+        Ok(result.rows_affected())
+    }
+
+    /// Sessions in the trash, most recently deleted first
+    pub async fn list_trashed_sessions(&self) -> Result<Vec<TrashEntry>> {
+        let rows = sqlx::query_as::<_, (String, String, i64)>(
+            "SELECT id, title, deleted_at FROM sessions
+             WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list trashed QA sessions: {e}")))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(id, title, deleted_at)| {
+                TrashEntry::new(TrashKind::QaSession, id, title, deleted_at)
+            })
+            .collect())
+    }
+
+    pub async fn count_events(&self, session_id: &str) -> Result<u64> {
+        let count =
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM events WHERE session_id = ?")
+                .bind(session_id)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to count QA events: {e}")))?;
+
+        Ok(count.max(0) as u64)
+    }
+
+    /// Screenshot artifacts of every session, oldest first
+    pub async fn list_screenshot_artifacts(&self) -> Result<Vec<QaScreenshotArtifact>> {
+        sqlx::query_as::<_, QaScreenshotArtifact>(
+            "SELECT id, session_id, path, created_at FROM artifacts
+             WHERE type = 'screenshot' ORDER BY created_at ASC",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list QA screenshots: {e}")))
+    }
+
+    /// Drop artifact rows and unlink them from their events
+    pub async fn delete_artifacts(&self, artifact_ids: &[String]) -> Result<u64> {
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to start QA artifact delete txn: {e}"))
+        })?;
+
+        let mut deleted = 0;
+        for artifact_id in artifact_ids {
+            sqlx::query("UPDATE events SET screenshot_id = NULL WHERE screenshot_id = ?")
+                .bind(artifact_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| {
+                    AppError::DatabaseError(format!("Failed to unlink QA screenshot: {e}"))
+                })?;
+            deleted += sqlx::query("DELETE FROM artifacts WHERE id = ?")
+                .bind(artifact_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to delete artifact: {e}")))?
+                .rows_affected();
+        }
+
+        tx.commit().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to commit artifact delete: {e}"))
+        })?;
+
+        Ok(deleted)
+    }
+
     pub async fn delete_session_cascade(&self, session_id: &str) -> Result<u64> {
         let mut tx =
             self.pool.begin().await.map_err(|e| {
@@ -160,6 +292,12 @@ impl QaRepository {
             .await
             .map_err(|e| AppError::DatabaseError(format!("Failed to delete api calls: {e}")))?;
 
+        sqlx::query("DELETE FROM api_event_links WHERE session_id = ?")
+            .bind(session_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to delete api links: {e}")))?;
+
         sqlx::query("DELETE FROM ai_actions WHERE session_id = ?")
             .bind(session_id)
             .execute(&mut *tx)
@@ -174,6 +312,12 @@ impl QaRepository {
                 AppError::DatabaseError(format!("Failed to delete test case runs: {e}"))
             })?;
 
+        sqlx::query("DELETE FROM test_sync_log WHERE session_id = ?")
+            .bind(session_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to delete test sync log: {e}")))?;
+
         sqlx::query("DELETE FROM replay_runs WHERE session_id = ?")
             .bind(session_id)
             .execute(&mut *tx)
@@ -229,6 +373,18 @@ fn db_path_to_url(db_path: &Path) -> Result<String> {
     Ok(format!("sqlite://{}", db_path_str.replace("\\", "/")))
 }
 
+fn selector_attributes_json(attributes: Option<&[String]>) -> Option<String> {
+    attributes.and_then(|attributes| serde_json::to_string(attributes).ok())
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct QaScreenshotArtifact {
+    pub id: String,
+    pub session_id: String,
+    pub path: String,
+    pub created_at: i64,
+}
+
 #[derive(sqlx::FromRow)]
 struct QaSessionEntity {
     id: String,
@@ -245,6 +401,7 @@ struct QaSessionEntity {
     started_at: i64,
     ended_at: Option<i64>,
     notes: Option<String>,
+    selector_attributes_json: Option<String>,
 }
 
 impl From<QaSessionEntity> for QaSession {
@@ -264,6 +421,9 @@ impl From<QaSessionEntity> for QaSession {
             started_at: entity.started_at,
             ended_at: entity.ended_at,
             notes: entity.notes,
+            selector_attributes: entity
+                .selector_attributes_json
+                .and_then(|json| serde_json::from_str(&json).ok()),
         }
     }
 }
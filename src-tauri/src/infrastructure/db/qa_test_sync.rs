@@ -0,0 +1,168 @@
+use crate::domain::error::{AppError, Result};
+use crate::domain::qa_test_management::{QaTestSyncEntry, TestManagementProvider};
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions, SqliteSynchronous,
+};
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+pub struct QaTestSyncRepository {
+    pool: SqlitePool,
+}
+
+impl QaTestSyncRepository {
+    pub async fn connect(db_path: &Path) -> Result<Self> {
+        let db_url = db_path_to_url(db_path)?;
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .map_err(|e| AppError::DatabaseError(format!("Failed to parse QA DB URL: {e}")))?
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .synchronous(SqliteSynchronous::Normal)
+            .busy_timeout(Duration::from_secs(5));
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(2)
+            .acquire_timeout(Duration::from_secs(5))
+            .connect_with(options)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to connect QA DB: {e}")))?;
+
+        Ok(Self { pool })
+    }
+
+    pub async fn insert_entry(&self, entry: &QaTestSyncEntry) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO test_sync_log (id, session_id, provider, action, test_case_id, external_id, status, message, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&entry.id)
+        .bind(&entry.session_id)
+        .bind(entry.provider.as_str())
+        .bind(&entry.action)
+        .bind(&entry.test_case_id)
+        .bind(&entry.external_id)
+        .bind(&entry.status)
+        .bind(&entry.message)
+        .bind(entry.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to insert sync log entry: {e}")))?;
+        Ok(())
+    }
+
+    /// Newest entries first
+    pub async fn list_entries(&self, session_id: &str, limit: i64) -> Result<Vec<QaTestSyncEntry>> {
+        let entries = sqlx::query_as::<_, QaTestSyncEntity>(
+            "SELECT id, session_id, provider, action, test_case_id, external_id, status, message, created_at
+             FROM test_sync_log WHERE session_id = ?
+             ORDER BY created_at DESC, rowid DESC LIMIT ?",
+        )
+        .bind(session_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list sync log: {e}")))?;
+
+        Ok(entries
+            .into_iter()
+            .filter_map(|entry| entry.into_entry())
+            .collect())
+    }
+
+    /// External id of every test case of the session already created in
+    /// `provider`, keyed by test case id
+    pub async fn external_ids(
+        &self,
+        session_id: &str,
+        provider: TestManagementProvider,
+    ) -> Result<HashMap<String, String>> {
+        let rows = sqlx::query_as::<_, (String, String)>(
+            "SELECT test_case_id, external_id FROM test_sync_log
+             WHERE session_id = ? AND provider = ? AND action = 'case' AND status = 'created'
+               AND test_case_id IS NOT NULL AND external_id IS NOT NULL
+             ORDER BY created_at ASC, rowid ASC",
+        )
+        .bind(session_id)
+        .bind(provider.as_str())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load pushed test cases: {e}")))?;
+
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Record a result entered for a test case in `test_case_runs`
+    #[allow(clippy::too_many_arguments)]
+    pub async fn insert_test_case_run(
+        &self,
+        id: &str,
+        test_case_id: &str,
+        session_id: &str,
+        run_id: &str,
+        status: &str,
+        error: Option<&str>,
+        result_json: Option<&str>,
+        recorded_at: i64,
+    ) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO test_case_runs (id, test_case_id, session_id, run_id, status, error, result_json, started_at, ended_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(id)
+        .bind(test_case_id)
+        .bind(session_id)
+        .bind(run_id)
+        .bind(status)
+        .bind(error)
+        .bind(result_json)
+        .bind(recorded_at)
+        .bind(recorded_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to insert test case run: {e}")))?;
+        Ok(())
+    }
+}
+
+fn db_path_to_url(db_path: &Path) -> Result<String> {
+    let db_path_str = db_path.to_str().ok_or_else(|| {
+        AppError::DatabaseError("QA database path is not valid UTF-8".to_string())
+    })?;
+    Ok(format!("sqlite://{}", db_path_str.replace("\\", "/")))
+}
+
+#[derive(sqlx::FromRow)]
+struct QaTestSyncEntity {
+    id: String,
+    session_id: String,
+    provider: String,
+    action: String,
+    test_case_id: Option<String>,
+    external_id: Option<String>,
+    status: String,
+    message: Option<String>,
+    created_at: i64,
+}
+
+impl QaTestSyncEntity {
+    fn into_entry(self) -> Option<QaTestSyncEntry> {
+        let provider = match self.provider.as_str() {
+            "xray" => TestManagementProvider::Xray,
+            "testrail" => TestManagementProvider::Testrail,
+            _ => return None,
+        };
+        Some(QaTestSyncEntry {
+            id: self.id,
+            session_id: self.session_id,
+            provider,
+            action: self.action,
+            test_case_id: self.test_case_id,
+            external_id: self.external_id,
+            status: self.status,
+            message: self.message,
+            created_at: self.created_at,
+        })
+    }
+}
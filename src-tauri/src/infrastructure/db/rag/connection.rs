@@ -1,11 +1,12 @@
 use sqlx::sqlite::{
     SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions, SqliteSynchronous,
 };
-use sqlx::Row;
 use std::path::Path;
 use std::str::FromStr;
 use std::time::Duration;
 
+use crate::infrastructure::db::schema::ensure_column;
+
 // chrono is used for DB backup timestamp
 use chrono;
 
@@ -49,6 +50,8 @@ pub async fn init_rag_db(db_path: &Path) -> Result<(), String> {
     // Backfill FTS index if needed (safe no-op when already synced)
     backfill_chunks_fts(&pool).await?;
 
+    mark_interrupted_ingestions(&pool).await?;
+
     set_user_version(&pool, RAG_SCHEMA_VERSION).await?;
 
     sqlx::query("SELECT 1")
@@ -85,6 +88,25 @@ async fn apply_schema(pool: &SqlitePool) -> Result<(), String> {
         // DB Connector feature migration (v2 -> v3) - must happen before schema applies indexes
         ensure_column(pool, "collections", "kind", "TEXT NOT NULL DEFAULT 'files'").await?;
         ensure_column(pool, "collections", "config_json", "TEXT NOT NULL DEFAULT '{}'").await?;
+        // Read by the answer cache triggers in schema.sql
+        ensure_column(pool, "collections", "content_version", "INTEGER NOT NULL DEFAULT 0").await?;
+    }
+
+    // Earlier FTS sync triggers used the FTS5 'delete' command, which fails on
+    // document_chunks_fts; drop those so the schema recreates the fixed ones.
+    let stale_triggers: Vec<String> = sqlx::query_scalar(
+        "SELECT name FROM sqlite_master WHERE type = 'trigger' \
+         AND name IN ('document_chunks_ad', 'document_chunks_au') \
+         AND sql LIKE '%''delete''%'",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to inspect FTS triggers: {e}"))?;
+    for trigger in stale_triggers {
+        sqlx::query(&format!("DROP TRIGGER IF EXISTS {trigger}"))
+            .execute(pool)
+            .await
+            .map_err(|e| format!("Failed to drop trigger {trigger}: {e}"))?;
     }
 
     // Now apply all schema statements (including CREATE INDEX on collections.kind)
@@ -102,9 +124,18 @@ async fn apply_schema(pool: &SqlitePool) -> Result<(), String> {
 
     // Additive upgrades for existing DBs (columns without dependent indexes).
     ensure_column(pool, "documents", "meta_json", "TEXT NOT NULL DEFAULT '{}'" ).await?;
+    ensure_column(pool, "documents", "ocr_languages", "TEXT").await?;
+    // Documents imported before states were tracked are complete
+    ensure_column(pool, "documents", "ingestion_state", "TEXT NOT NULL DEFAULT 'complete'").await?;
+    ensure_column(pool, "documents", "ingestion_error", "TEXT").await?;
+    // Set while the document is in the trash (unix ms)
+    ensure_column(pool, "documents", "deleted_at", "INTEGER").await?;
     ensure_column(pool, "document_chunks", "meta_json", "TEXT NOT NULL DEFAULT '{}'" ).await?;
     ensure_column(pool, "document_chunks", "content_hash", "TEXT").await?;
     ensure_column(pool, "document_chunks", "page_offset", "INTEGER").await?;
+    ensure_column(pool, "document_chunks", "pii_types", "TEXT DEFAULT NULL").await?;
+    ensure_column(pool, "document_chunks", "sensitive", "INTEGER NOT NULL DEFAULT 0").await?;
+    ensure_column(pool, "document_chunks", "parent_index", "INTEGER DEFAULT NULL").await?;
 
     // Rate limiter feature migration - add blocked_count column
     ensure_column(pool, "db_query_sessions", "blocked_count", "INTEGER NOT NULL DEFAULT 0").await?;
@@ -115,36 +146,16 @@ async fn apply_schema(pool: &SqlitePool) -> Result<(), String> {
     Ok(())
 }
 
-async fn ensure_column(
-    pool: &SqlitePool,
-    table: &str,
-    column: &str,
-    definition: &str,
-) -> Result<(), String> {
-    let pragma_query = format!("PRAGMA table_info({})", table);
-    let rows = sqlx::query(&pragma_query)
-        .fetch_all(pool)
-        .await
-        .map_err(|e| format!("Failed to inspect {table} schema: {e}"))?;
-    let mut exists = false;
-    for row in rows {
-        let name: String = row
-            .try_get("name")
-            .map_err(|e| format!("Failed to read {table} schema: {e}"))?;
-        if name == column {
-            exists = true;
-            break;
-        }
-    }
-
-    if !exists {
-        let alter_stmt = format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition);
-        sqlx::query(&alter_stmt)
-            .execute(pool)
-            .await
-            .map_err(|e| format!("Failed to add {column} column to {table}: {e}"))?;
-    }
-
+/// The database opens before any import starts, so imports still in
+/// progress were cut off by the app closing; mark them failed so they can
+/// be resumed.
+async fn mark_interrupted_ingestions(pool: &SqlitePool) -> Result<(), String> {
+    sqlx::query(
+        "UPDATE documents SET ingestion_error = 'Interrupted when the app closed'\n         WHERE ingestion_state != 'complete' AND ingestion_error IS NULL",
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Failed to mark interrupted imports: {e}"))?;
     Ok(())
 }
 
@@ -0,0 +1,115 @@
+//! Persisted answer cache: generated answers by collection and cache key,
+//! valid only while the collection's `content_version` is unchanged. The
+//! version is bumped by triggers in schema.sql on every document change.
+
+use crate::domain::error::{AppError, Result};
+
+use super::RagRepository;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PersistedAnswer {
+    pub collection_id: i64,
+    pub cache_key: String,
+    pub collection_version: i64,
+    pub query_text: String,
+    pub answer: String,
+    /// Serialized retrieval results the answer was built from
+    pub results_json: String,
+    pub hit_count: i64,
+    pub created_at: i64,
+}
+
+impl RagRepository {
+    /// Current content version of a collection
+    pub async fn collection_content_version(&self, collection_id: i64) -> Result<i64> {
+        sqlx::query_scalar::<_, i64>("SELECT content_version FROM collections WHERE id = ?")
+            .bind(collection_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to read collection version: {}", e))
+            })?
+            .ok_or_else(|| AppError::NotFound(format!("Collection not found: {}", collection_id)))
+    }
+
+    /// Insert or replace an answer, dropping the collection's entries built
+    /// at older versions
+    pub async fn save_answer_cache(&self, entry: &PersistedAnswer, ttl_secs: i64) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+
+        sqlx::query("DELETE FROM answer_cache WHERE collection_id = ? AND collection_version < ?")
+            .bind(entry.collection_id)
+            .bind(entry.collection_version)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to prune answer cache: {}", e)))?;
+
+        sqlx::query(
+            "INSERT OR REPLACE INTO answer_cache\n             (collection_id, cache_key, collection_version, query_text, answer, results_json, hit_count, created_at, expires_at)\n             VALUES (?, ?, ?, ?, ?, ?, 0, ?, ?)",
+        )
+        .bind(entry.collection_id)
+        .bind(&entry.cache_key)
+        .bind(entry.collection_version)
+        .bind(&entry.query_text)
+        .bind(&entry.answer)
+        .bind(&entry.results_json)
+        .bind(now)
+        .bind(now.saturating_add(ttl_secs))
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to save answer cache: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// The unexpired answer for `cache_key` built at the collection's current
+    /// version, counting the hit
+    pub async fn take_answer_cache_hit(
+        &self,
+        collection_id: i64,
+        cache_key: &str,
+    ) -> Result<Option<PersistedAnswer>> {
+        sqlx::query_as::<_, PersistedAnswer>(
+            "UPDATE answer_cache SET hit_count = hit_count + 1\n             WHERE collection_id = ? AND cache_key = ? AND expires_at > ?\n               AND collection_version = (SELECT content_version FROM collections WHERE id = ?)\n             RETURNING collection_id, cache_key, collection_version, query_text, answer, results_json, hit_count, created_at",
+        )
+        .bind(collection_id)
+        .bind(cache_key)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(collection_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to read answer cache: {}", e)))
+    }
+
+    pub async fn clear_answer_cache(&self) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM answer_cache")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to clear answer cache: {}", e)))?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete_collection_answer_cache(&self, collection_id: i64) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM answer_cache WHERE collection_id = ?")
+            .bind(collection_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to clear answer cache: {}", e)))?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Remove expired entries and those built at an older collection version
+    pub async fn prune_answer_cache(&self) -> Result<u64> {
+        let result = sqlx::query(
+            "DELETE FROM answer_cache\n             WHERE expires_at <= ?\n                OR collection_version < (SELECT content_version FROM collections WHERE id = answer_cache.collection_id)",
+        )
+        .bind(chrono::Utc::now().timestamp())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to prune answer cache: {}", e)))?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,137 @@
+//! Chunk usage: how often chunks are retrieved and cited, kept in
+//! `chunk_usage` for usage stats and pruning suggestions.
+
+use crate::domain::error::{AppError, Result};
+use serde::Serialize;
+
+use super::RagRepository;
+
+/// Usage totals for a collection's text chunks
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct CollectionChunkUsage {
+    pub total_chunks: i64,
+    pub retrieved_chunks: i64,
+    pub cited_chunks: i64,
+    pub total_retrievals: i64,
+    pub total_citations: i64,
+    /// First recorded retrieval in the collection, in unix seconds
+    pub tracking_since: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct ChunkUsage {
+    pub chunk_id: i64,
+    pub doc_id: i64,
+    pub doc_name: String,
+    pub chunk_index: i64,
+    pub retrieval_count: i64,
+    pub citation_count: i64,
+    pub last_retrieved_at: Option<i64>,
+    pub last_cited_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct DocumentUsage {
+    pub doc_id: i64,
+    pub file_name: String,
+    /// Import time in unix seconds
+    pub created_at: i64,
+    pub chunk_count: i64,
+    pub retrieved_chunks: i64,
+    pub retrieval_count: i64,
+    pub citation_count: i64,
+    pub last_retrieved_at: Option<i64>,
+    pub last_cited_at: Option<i64>,
+}
+
+fn ids_json(chunk_ids: &[i64]) -> Result<String> {
+    serde_json::to_string(chunk_ids)
+        .map_err(|e| AppError::Internal(format!("Failed to encode chunk ids: {}", e)))
+}
+
+impl RagRepository {
+    /// Count one retrieval for each chunk that belongs to the collection
+    pub async fn record_chunk_retrievals(
+        &self,
+        collection_id: i64,
+        chunk_ids: &[i64],
+    ) -> Result<u64> {
+        let now = chrono::Utc::now().timestamp();
+        let result = sqlx::query(
+            "INSERT INTO chunk_usage (chunk_id, retrieval_count, first_retrieved_at, last_retrieved_at)\n             SELECT dc.id, 1, ?, ?\n             FROM document_chunks dc\n             INNER JOIN documents d ON dc.doc_id = d.id\n             WHERE d.collection_id = ? AND d.deleted_at IS NULL\n               AND dc.id IN (SELECT value FROM json_each(?))\n             ON CONFLICT(chunk_id) DO UPDATE SET\n               retrieval_count = retrieval_count + 1,\n               first_retrieved_at = COALESCE(first_retrieved_at, excluded.first_retrieved_at),\n               last_retrieved_at = excluded.last_retrieved_at",
+        )
+        .bind(now)
+        .bind(now)
+        .bind(collection_id)
+        .bind(ids_json(chunk_ids)?)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to record chunk retrievals: {}", e))
+        })?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Count one citation for each chunk that belongs to the conversation's
+    /// collection
+    pub async fn record_chunk_citations(
+        &self,
+        conversation_id: i64,
+        chunk_ids: &[i64],
+    ) -> Result<u64> {
+        let result = sqlx::query(
+            "INSERT INTO chunk_usage (chunk_id, citation_count, last_cited_at)\n             SELECT dc.id, 1, ?\n             FROM document_chunks dc\n             INNER JOIN documents d ON dc.doc_id = d.id\n             INNER JOIN conversations c ON c.collection_id = d.collection_id\n             WHERE c.id = ?\n               AND dc.id IN (SELECT value FROM json_each(?))\n             ON CONFLICT(chunk_id) DO UPDATE SET\n               citation_count = citation_count + 1,\n               last_cited_at = excluded.last_cited_at",
+        )
+        .bind(chrono::Utc::now().timestamp())
+        .bind(conversation_id)
+        .bind(ids_json(chunk_ids)?)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to record chunk citations: {}", e))
+        })?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn get_collection_chunk_usage(
+        &self,
+        collection_id: i64,
+    ) -> Result<CollectionChunkUsage> {
+        sqlx::query_as::<_, CollectionChunkUsage>(
+            "SELECT COUNT(dc.id) AS total_chunks,\n                    COUNT(CASE WHEN cu.retrieval_count > 0 THEN 1 END) AS retrieved_chunks,\n                    COUNT(CASE WHEN cu.citation_count > 0 THEN 1 END) AS cited_chunks,\n                    COALESCE(SUM(cu.retrieval_count), 0) AS total_retrievals,\n                    COALESCE(SUM(cu.citation_count), 0) AS total_citations,\n                    MIN(cu.first_retrieved_at) AS tracking_since\n             FROM document_chunks dc\n             INNER JOIN documents d ON dc.doc_id = d.id\n             LEFT JOIN chunk_usage cu ON cu.chunk_id = dc.id\n             WHERE d.collection_id = ? AND d.deleted_at IS NULL",
+        )
+        .bind(collection_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch chunk usage: {}", e)))
+    }
+
+    /// Most retrieved chunks of a collection
+    pub async fn get_top_chunk_usage(
+        &self,
+        collection_id: i64,
+        limit: i64,
+    ) -> Result<Vec<ChunkUsage>> {
+        sqlx::query_as::<_, ChunkUsage>(
+            "SELECT cu.chunk_id, dc.doc_id, d.file_name AS doc_name, dc.chunk_index,\n                    cu.retrieval_count, cu.citation_count, cu.last_retrieved_at, cu.last_cited_at\n             FROM chunk_usage cu\n             INNER JOIN document_chunks dc ON dc.id = cu.chunk_id\n             INNER JOIN documents d ON dc.doc_id = d.id\n             WHERE d.collection_id = ? AND d.deleted_at IS NULL\n             ORDER BY cu.retrieval_count DESC, cu.citation_count DESC\n             LIMIT ?",
+        )
+        .bind(collection_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch chunk usage: {}", e)))
+    }
+
+    /// Usage per document of a collection, least used first
+    pub async fn get_document_usage(&self, collection_id: i64) -> Result<Vec<DocumentUsage>> {
+        sqlx::query_as::<_, DocumentUsage>(
+            "SELECT d.id AS doc_id, d.file_name,\n                    COALESCE(CAST(strftime('%s', d.created_at) AS INTEGER), 0) AS created_at,\n                    COUNT(dc.id) AS chunk_count,\n                    COUNT(CASE WHEN cu.retrieval_count > 0 THEN 1 END) AS retrieved_chunks,\n                    COALESCE(SUM(cu.retrieval_count), 0) AS retrieval_count,\n                    COALESCE(SUM(cu.citation_count), 0) AS citation_count,\n                    MAX(cu.last_retrieved_at) AS last_retrieved_at,\n                    MAX(cu.last_cited_at) AS last_cited_at\n             FROM documents d\n             LEFT JOIN document_chunks dc ON dc.doc_id = d.id\n             LEFT JOIN chunk_usage cu ON cu.chunk_id = dc.id\n             WHERE d.collection_id = ? AND d.deleted_at IS NULL\n             GROUP BY d.id\n             ORDER BY retrieval_count ASC, d.created_at ASC",
+        )
+        .bind(collection_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch document usage: {}", e)))
+    }
+}
@@ -1,5 +1,10 @@
+use crate::domain::embedding_quantization::{
+    EmbeddingQuantization, EmbeddingQuantizationReport, StoredEmbedding,
+};
 use crate::domain::error::{AppError, Result};
 use crate::domain::rag_entities::{RagDocumentChunk, RagDocumentChunkInput};
+use crate::infrastructure::security::collection_cipher::CollectionKeys;
+use std::collections::HashSet;
 
 use super::entities::RagDocumentChunkEntity;
 use super::RagRepository;
@@ -11,7 +16,14 @@ pub struct ChunkWithMetadata {
     pub page_number: Option<i64>,
     pub page_offset: Option<i64>,
     pub doc_name: String,
-    pub embedding: Option<Vec<f32>>,
+    pub embedding: Option<StoredEmbedding>,
+}
+
+/// Chunk of the parent section around a matched chunk
+pub struct SectionChunk {
+    pub id: i64,
+    pub content: String,
+    pub token_count: Option<i64>,
 }
 
 /// Chunk search result with a retrieval score.
@@ -23,43 +35,55 @@ pub struct ChunkWithMetadataScore {
     pub page_number: Option<i64>,
     pub page_offset: Option<i64>,
     pub doc_name: String,
-    pub embedding: Option<Vec<f32>>,
+    pub embedding: Option<StoredEmbedding>,
     pub score: f32,
 }
 
-fn bytes_to_embedding(bytes: &[u8]) -> Option<Vec<f32>> {
-    if bytes.len() % 4 != 0 {
-        return None;
-    }
+pub(super) fn bytes_to_embedding(bytes: &[u8]) -> Option<Vec<f32>> {
+    StoredEmbedding::from_bytes(bytes).map(|embedding| embedding.to_f32())
+}
 
-    let mut embedding = Vec::with_capacity(bytes.len() / 4);
-    for chunk in bytes.chunks_exact(4) {
-        let arr = [chunk[0], chunk[1], chunk[2], chunk[3]];
-        embedding.push(f32::from_le_bytes(arr));
+/// Decrypt a stored embedding if sealed, then decode it at the precision
+/// it was stored at
+fn open_embedding(
+    keys: &CollectionKeys,
+    stored: Option<Vec<u8>>,
+) -> Result<Option<StoredEmbedding>> {
+    match stored {
+        Some(bytes) => Ok(StoredEmbedding::from_bytes(&keys.open_bytes(bytes)?)),
+        None => Ok(None),
     }
-
-    Some(embedding)
 }
 
 impl RagRepository {
     pub async fn create_chunk(&self, input: &RagDocumentChunkInput) -> Result<RagDocumentChunk> {
-        let result = sqlx::query_as::<_, RagDocumentChunkEntity>(
-            "INSERT INTO document_chunks (doc_id, content, page_number, page_offset, chunk_index, token_count)\n             VALUES (?, ?, ?, ?, ?, ?) RETURNING *",
+        let content = match self.cipher_for_document(input.doc_id).await? {
+            Some(cipher) => cipher.encrypt_text(&input.content)?,
+            None => input.content.clone(),
+        };
+        let mut result = sqlx::query_as::<_, RagDocumentChunkEntity>(
+            "INSERT INTO document_chunks (doc_id, content, page_number, page_offset, chunk_index, token_count, parent_index)\n             VALUES (?, ?, ?, ?, ?, ?, ?) RETURNING *",
         )
         .bind(input.doc_id)
-        .bind(&input.content)
+        .bind(content)
         .bind(input.page_number)
         .bind(input.page_offset)
         .bind(input.chunk_index)
         .bind(input.token_count)
+        .bind(input.parent_index)
         .fetch_one(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to create chunk: {}", e)))?;
 
+        result.content = input.content.clone();
         Ok(result.into())
     }
 
     pub async fn update_chunk_embedding(&self, chunk_id: i64, embedding: &[u8]) -> Result<()> {
+        let embedding = match self.cipher_for_chunk(chunk_id).await? {
+            Some(cipher) => cipher.encrypt_bytes(embedding)?,
+            None => embedding.to_vec(),
+        };
         sqlx::query("UPDATE document_chunks SET embedding_api = ? WHERE id = ?")
             .bind(embedding)
             .bind(chunk_id)
@@ -72,6 +96,78 @@ impl RagRepository {
         Ok(())
     }
 
+    /// Rewrite every stored embedding of a collection at `quantization`,
+    /// sealing them again when the collection is encrypted. Fails while an
+    /// encrypted collection is locked.
+    pub async fn requantize_collection_embeddings(
+        &self,
+        collection_id: i64,
+        quantization: EmbeddingQuantization,
+    ) -> Result<EmbeddingQuantizationReport> {
+        let keys = self.collection_keys().await?;
+        let cipher = keys.cipher(collection_id)?;
+        let mut report = EmbeddingQuantizationReport {
+            collection_id,
+            quantization,
+            converted: 0,
+            unchanged: 0,
+            unreadable: 0,
+            bytes_before: 0,
+            bytes_after: 0,
+            compacted: false,
+        };
+
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to begin transaction: {}", e))
+            })?;
+        let rows = sqlx::query_as::<_, (i64, Vec<u8>)>(
+            "SELECT dc.id, dc.embedding_api\n             FROM document_chunks dc\n             INNER JOIN documents d ON dc.doc_id = d.id\n             WHERE d.collection_id = ? AND dc.embedding_api IS NOT NULL",
+        )
+        .bind(collection_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch embeddings: {}", e)))?;
+
+        for (chunk_id, stored) in rows {
+            let stored_len = stored.len() as u64;
+            report.bytes_before += stored_len;
+            let embedding = match StoredEmbedding::from_bytes(&keys.open_bytes(stored)?) {
+                Some(embedding) if embedding.quantization() != quantization => embedding,
+                Some(_) => {
+                    report.unchanged += 1;
+                    report.bytes_after += stored_len;
+                    continue;
+                }
+                None => {
+                    report.unreadable += 1;
+                    report.bytes_after += stored_len;
+                    continue;
+                }
+            };
+            let bytes = embedding.requantize(quantization).to_bytes();
+            let bytes = match &cipher {
+                Some(cipher) => cipher.encrypt_bytes(&bytes)?,
+                None => bytes,
+            };
+            report.bytes_after += bytes.len() as u64;
+            sqlx::query("UPDATE document_chunks SET embedding_api = ? WHERE id = ?")
+                .bind(bytes)
+                .bind(chunk_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| {
+                    AppError::DatabaseError(format!("Failed to update chunk embedding: {}", e))
+                })?;
+            report.converted += 1;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit transaction: {}", e)))?;
+        Ok(report)
+    }
+
     pub async fn get_chunks_with_embeddings(
         &self,
         doc_id: i64,
@@ -86,15 +182,14 @@ impl RagRepository {
             AppError::DatabaseError(format!("Failed to fetch chunks with embeddings: {}", e))
         })?;
 
+        let keys = self.collection_keys().await?;
         let mut results = Vec::new();
         for chunk in chunks {
-            let embedding = chunk
-                .embedding_api
-                .as_deref()
-                .and_then(bytes_to_embedding);
+            let embedding = open_embedding(keys, chunk.embedding_api)?
+                .map(|embedding| embedding.to_f32());
             results.push((
                 chunk.id,
-                chunk.content,
+                keys.open_text(chunk.content)?,
                 chunk.page_number,
                 chunk.page_offset,
                 embedding,
@@ -104,6 +199,23 @@ impl RagRepository {
         Ok(results)
     }
 
+    /// IDs and text of a document's chunks stored without an embedding
+    pub async fn get_chunks_missing_embeddings(&self, doc_id: i64) -> Result<Vec<(i64, String)>> {
+        let chunks = sqlx::query_as::<_, (i64, String)>(
+            "SELECT id, content FROM document_chunks\n             WHERE doc_id = ? AND embedding_api IS NULL ORDER BY chunk_index ASC",
+        )
+        .bind(doc_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch chunks: {}", e)))?;
+
+        let keys = self.collection_keys().await?;
+        chunks
+            .into_iter()
+            .map(|(id, content)| Ok((id, keys.open_text(content)?)))
+            .collect()
+    }
+
     pub async fn get_chunks(&self, doc_id: i64, limit: i64) -> Result<Vec<RagDocumentChunk>> {
         let chunks = sqlx::query_as::<_, RagDocumentChunkEntity>(
             "SELECT id, doc_id, content, page_number, page_offset, chunk_index, token_count,\n                    chunk_quality, content_type, embedding_api\n             FROM document_chunks WHERE doc_id = ? ORDER BY chunk_index ASC LIMIT ?",
@@ -114,7 +226,14 @@ impl RagRepository {
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to fetch chunks: {}", e)))?;
 
-        Ok(chunks.into_iter().map(|c| c.into()).collect())
+        let keys = self.collection_keys().await?;
+        chunks
+            .into_iter()
+            .map(|mut chunk| {
+                chunk.content = keys.open_text(chunk.content)?;
+                Ok(chunk.into())
+            })
+            .collect()
     }
 
     /// Search chunks by collection with page metadata and document name
@@ -144,7 +263,7 @@ impl RagRepository {
         }
 
         let chunks = sqlx::query_as::<_, ChunkWithDocEntity>(
-            "SELECT dc.id, dc.doc_id, dc.content, dc.page_number, dc.page_offset, dc.chunk_index,\n                    dc.token_count, dc.chunk_quality, dc.content_type, dc.embedding_api, d.file_name\n             FROM document_chunks dc\n             INNER JOIN documents d ON dc.doc_id = d.id\n             WHERE d.collection_id = ?\n             ORDER BY dc.chunk_index ASC\n             LIMIT ?",
+            "SELECT dc.id, dc.doc_id, dc.content, dc.page_number, dc.page_offset, dc.chunk_index,\n                    dc.token_count, dc.chunk_quality, dc.content_type, dc.embedding_api, d.file_name\n             FROM document_chunks dc\n             INNER JOIN documents d ON dc.doc_id = d.id\n             WHERE d.collection_id = ? AND d.deleted_at IS NULL\n             ORDER BY dc.chunk_index ASC\n             LIMIT ?",
         )
         .bind(collection_id)
         .bind(limit)
@@ -154,15 +273,13 @@ impl RagRepository {
             AppError::DatabaseError(format!("Failed to search chunks by collection: {}", e))
         })?;
 
+        let keys = self.collection_keys().await?;
         let mut results = Vec::new();
         for chunk in chunks {
-            let embedding = chunk
-                .embedding_api
-                .as_deref()
-                .and_then(bytes_to_embedding);
+            let embedding = open_embedding(keys, chunk.embedding_api)?;
             results.push(ChunkWithMetadata {
                 id: chunk.id,
-                content: chunk.content,
+                content: keys.open_text(chunk.content)?,
                 page_number: chunk.page_number,
                 page_offset: chunk.page_offset,
                 doc_name: chunk.file_name,
@@ -176,13 +293,18 @@ impl RagRepository {
     /// FTS5 keyword search over `document_chunks` scoped to a collection.
     ///
     /// This uses `document_chunks_fts` (rowid == document_chunks.id) and orders by
-    /// `bm25(document_chunks_fts)` (ascending = best).
+    /// `bm25(document_chunks_fts)` (ascending = best). Encrypted collections
+    /// only index ciphertext, so they have no keyword matches.
     pub async fn search_chunks_fts_by_collection(
         &self,
         collection_id: i64,
         query: &str,
         limit: i64,
     ) -> Result<Vec<ChunkWithMetadataScore>> {
+        if self.is_collection_encrypted(collection_id).await? {
+            return Ok(Vec::new());
+        }
+
         #[derive(sqlx::FromRow)]
         struct ChunkFtsEntity {
             id: i64,
@@ -195,7 +317,7 @@ impl RagRepository {
         }
 
         let rows = sqlx::query_as::<_, ChunkFtsEntity>(
-            "SELECT dc.id, dc.content, dc.page_number, dc.page_offset, dc.embedding_api, d.file_name,\n                    bm25(document_chunks_fts) AS bm25_score\n             FROM document_chunks_fts\n             INNER JOIN document_chunks dc ON dc.id = document_chunks_fts.rowid\n             INNER JOIN documents d ON dc.doc_id = d.id\n             WHERE d.collection_id = ?\n               AND document_chunks_fts MATCH ?\n             ORDER BY bm25_score ASC\n             LIMIT ?",
+            "SELECT dc.id, dc.content, dc.page_number, dc.page_offset, dc.embedding_api, d.file_name,\n                    bm25(document_chunks_fts) AS bm25_score\n             FROM document_chunks_fts\n             INNER JOIN document_chunks dc ON dc.id = document_chunks_fts.rowid\n             INNER JOIN documents d ON dc.doc_id = d.id\n             WHERE d.collection_id = ? AND d.deleted_at IS NULL\n               AND document_chunks_fts MATCH ?\n             ORDER BY bm25_score ASC\n             LIMIT ?",
         )
         .bind(collection_id)
         .bind(query)
@@ -214,7 +336,7 @@ impl RagRepository {
             let embedding = row
                 .embedding_api
                 .as_deref()
-                .and_then(bytes_to_embedding);
+                .and_then(StoredEmbedding::from_bytes);
 
             results.push(ChunkWithMetadataScore {
                 id: row.id,
@@ -232,7 +354,7 @@ impl RagRepository {
 
     /// Get a single chunk by ID
     pub async fn get_chunk(&self, chunk_id: i64) -> Result<RagDocumentChunk> {
-        let chunk = sqlx::query_as::<_, RagDocumentChunkEntity>(
+        let mut chunk = sqlx::query_as::<_, RagDocumentChunkEntity>(
             "SELECT id, doc_id, content, page_number, page_offset, chunk_index, token_count,\n                    chunk_quality, content_type, embedding_api\n             FROM document_chunks WHERE id = ?",
         )
         .bind(chunk_id)
@@ -240,9 +362,127 @@ impl RagRepository {
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to fetch chunk: {}", e)))?;
 
+        chunk.content = self.collection_keys().await?.open_text(chunk.content)?;
         Ok(chunk.into())
     }
 
+    /// Chunks of a collection by ID, with their document name. IDs that no
+    /// longer exist or belong to another collection are left out.
+    pub async fn get_collection_chunks_by_ids(
+        &self,
+        collection_id: i64,
+        chunk_ids: &[i64],
+    ) -> Result<Vec<ChunkWithMetadata>> {
+        #[derive(sqlx::FromRow)]
+        struct ChunkByIdEntity {
+            id: i64,
+            content: String,
+            page_number: Option<i64>,
+            page_offset: Option<i64>,
+            file_name: String,
+        }
+
+        let ids_json = serde_json::to_string(chunk_ids)
+            .map_err(|e| AppError::Internal(format!("Failed to encode chunk ids: {}", e)))?;
+        let rows = sqlx::query_as::<_, ChunkByIdEntity>(
+            "SELECT dc.id, dc.content, dc.page_number, dc.page_offset, d.file_name\n             FROM document_chunks dc\n             INNER JOIN documents d ON dc.doc_id = d.id\n             WHERE d.collection_id = ? AND d.deleted_at IS NULL\n               AND dc.id IN (SELECT value FROM json_each(?))",
+        )
+        .bind(collection_id)
+        .bind(ids_json)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch chunks by id: {}", e)))?;
+
+        let keys = self.collection_keys().await?;
+        rows.into_iter()
+            .map(|row| {
+                Ok(ChunkWithMetadata {
+                    id: row.id,
+                    content: keys.open_text(row.content)?,
+                    page_number: row.page_number,
+                    page_offset: row.page_offset,
+                    doc_name: row.file_name,
+                    embedding: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Chunks of the parent section of `chunk_id` in document order. Chunks
+    /// stored before sections existed use their `window` nearest neighbours.
+    /// With `skip_sensitive`, siblings tagged as containing PII are left out.
+    pub async fn get_parent_section(
+        &self,
+        chunk_id: i64,
+        window: i64,
+        skip_sensitive: bool,
+    ) -> Result<Vec<SectionChunk>> {
+        #[derive(sqlx::FromRow)]
+        struct SectionChunkEntity {
+            id: i64,
+            content: String,
+            token_count: Option<i64>,
+        }
+
+        let rows = sqlx::query_as::<_, SectionChunkEntity>(
+            "SELECT s.id, s.content, s.token_count\n             FROM document_chunks c\n             INNER JOIN document_chunks s ON s.doc_id = c.doc_id\n             WHERE c.id = ?\n               AND CASE WHEN c.parent_index IS NULL\n                        THEN s.chunk_index BETWEEN c.chunk_index - ? AND c.chunk_index + ?\n                        ELSE s.parent_index = c.parent_index END\n               AND (? = 0 OR s.sensitive = 0 OR s.id = c.id)\n             ORDER BY s.chunk_index ASC",
+        )
+        .bind(chunk_id)
+        .bind(window)
+        .bind(window)
+        .bind(skip_sensitive)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch parent section: {}", e)))?;
+
+        let keys = self.collection_keys().await?;
+        rows.into_iter()
+            .map(|row| {
+                Ok(SectionChunk {
+                    id: row.id,
+                    content: keys.open_text(row.content)?,
+                    token_count: row.token_count,
+                })
+            })
+            .collect()
+    }
+
+    /// Record the PII kinds found in a chunk; `sensitive` marks PII that was
+    /// kept in the content rather than redacted
+    pub async fn update_chunk_pii(
+        &self,
+        chunk_id: i64,
+        pii_types: &str,
+        sensitive: bool,
+    ) -> Result<()> {
+        sqlx::query("UPDATE document_chunks SET pii_types = ?, sensitive = ? WHERE id = ?")
+            .bind(pii_types)
+            .bind(sensitive)
+            .bind(chunk_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to update chunk PII: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// The subset of `chunk_ids` tagged as sensitive
+    pub async fn get_sensitive_chunk_ids(&self, chunk_ids: &[i64]) -> Result<HashSet<i64>> {
+        let ids_json = serde_json::to_string(chunk_ids)
+            .map_err(|e| AppError::Internal(format!("Failed to encode chunk ids: {}", e)))?;
+        let ids = sqlx::query_scalar::<_, i64>(
+            "SELECT id FROM document_chunks\n             WHERE sensitive = 1 AND id IN (SELECT value FROM json_each(?))",
+        )
+        .bind(ids_json)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to fetch sensitive chunks: {}", e))
+        })?;
+
+        Ok(ids.into_iter().collect())
+    }
+
     /// Delete a specific chunk
     pub async fn delete_chunk(&self, chunk_id: i64) -> Result<u64> {
         let result = sqlx::query("DELETE FROM document_chunks WHERE id = ?")
@@ -257,11 +497,15 @@ impl RagRepository {
     /// Update chunk content
     pub async fn update_chunk_content(&self, chunk_id: i64, new_content: &str) -> Result<()> {
         let token_count = (new_content.len() / 4) as i64;
+        let content = match self.cipher_for_chunk(chunk_id).await? {
+            Some(cipher) => cipher.encrypt_text(new_content)?,
+            None => new_content.to_string(),
+        };
 
         sqlx::query(
             "UPDATE document_chunks SET content = ?, token_count = ?, embedding_api = NULL WHERE id = ?",
         )
-        .bind(new_content)
+        .bind(content)
         .bind(token_count)
         .bind(chunk_id)
         .execute(&self.pool)
@@ -59,6 +59,19 @@ impl RagRepository {
         }
     }
 
+    /// The oldest collection named `name`, if any
+    pub async fn find_collection_by_name(&self, name: &str) -> Result<Option<RagCollection>> {
+        let collection = sqlx::query_as::<_, RagCollectionEntity>(
+            "SELECT id, name, description, kind, config_json, created_at FROM collections\n             WHERE name = ? ORDER BY id LIMIT 1",
+        )
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find collection: {}", e)))?;
+
+        Ok(collection.map(Into::into))
+    }
+
     pub async fn list_collections(&self, limit: i64) -> Result<Vec<RagCollection>> {
         let collections = sqlx::query_as::<_, RagCollectionEntity>(
             "SELECT id, name, description, kind, config_json, created_at FROM collections ORDER BY created_at DESC LIMIT ?",
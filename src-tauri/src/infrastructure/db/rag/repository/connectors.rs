@@ -0,0 +1,261 @@
+//! Notion and Confluence connectors in `rag_connectors`, and the pages
+//! each one imported in `rag_connector_pages`.
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::rag_connectors::{
+    ConnectorKind, ConnectorPage, ConnectorSyncReport, RagConnector, RagConnectorInput,
+};
+
+use super::RagRepository;
+
+const CONNECTOR_COLUMNS: &str =
+    "id, name, kind, collection_id, base_url, username, scope, sync_interval_minutes, enabled,
+     last_synced_at, last_sync_status, last_sync_error, created_at";
+
+#[derive(sqlx::FromRow)]
+struct RagConnectorEntity {
+    id: i64,
+    name: String,
+    kind: String,
+    collection_id: i64,
+    base_url: Option<String>,
+    username: Option<String>,
+    scope: Option<String>,
+    sync_interval_minutes: i64,
+    enabled: bool,
+    last_synced_at: Option<i64>,
+    last_sync_status: Option<String>,
+    last_sync_error: Option<String>,
+    created_at: i64,
+}
+
+impl TryFrom<RagConnectorEntity> for RagConnector {
+    type Error = AppError;
+
+    fn try_from(entity: RagConnectorEntity) -> Result<Self> {
+        let kind = ConnectorKind::parse(&entity.kind).ok_or_else(|| {
+            AppError::DatabaseError(format!("Unknown connector kind: {}", entity.kind))
+        })?;
+        Ok(Self {
+            id: entity.id,
+            name: entity.name,
+            kind,
+            collection_id: entity.collection_id,
+            base_url: entity.base_url,
+            username: entity.username,
+            scope: entity.scope,
+            sync_interval_minutes: entity.sync_interval_minutes,
+            enabled: entity.enabled,
+            last_synced_at: entity.last_synced_at,
+            last_sync_status: entity.last_sync_status,
+            last_sync_error: entity.last_sync_error,
+            created_at: entity.created_at,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ConnectorPageEntity {
+    connector_id: i64,
+    external_id: String,
+    parent_external_id: Option<String>,
+    title: String,
+    path: String,
+    url: Option<String>,
+    last_edited_at: i64,
+    doc_id: Option<i64>,
+    synced_at: i64,
+}
+
+impl From<ConnectorPageEntity> for ConnectorPage {
+    fn from(entity: ConnectorPageEntity) -> Self {
+        Self {
+            connector_id: entity.connector_id,
+            external_id: entity.external_id,
+            parent_external_id: entity.parent_external_id,
+            title: entity.title,
+            path: entity.path,
+            url: entity.url,
+            last_edited_at: entity.last_edited_at,
+            doc_id: entity.doc_id,
+            synced_at: entity.synced_at,
+        }
+    }
+}
+
+impl RagRepository {
+    pub async fn create_connector(&self, input: &RagConnectorInput) -> Result<RagConnector> {
+        let entity = sqlx::query_as::<_, RagConnectorEntity>(&format!(
+            "INSERT INTO rag_connectors
+             (name, kind, collection_id, base_url, username, scope, sync_interval_minutes, enabled, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+             RETURNING {}",
+            CONNECTOR_COLUMNS
+        ))
+        .bind(&input.name)
+        .bind(input.kind.as_str())
+        .bind(input.collection_id)
+        .bind(&input.base_url)
+        .bind(&input.username)
+        .bind(&input.scope)
+        .bind(input.sync_interval_minutes)
+        .bind(input.enabled)
+        .bind(chrono::Utc::now().timestamp())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to create connector: {}", e)))?;
+
+        entity.try_into()
+    }
+
+    /// Update the settings of a connector; its kind and sync state stay
+    pub async fn update_connector(
+        &self,
+        id: i64,
+        input: &RagConnectorInput,
+    ) -> Result<RagConnector> {
+        let entity = sqlx::query_as::<_, RagConnectorEntity>(&format!(
+            "UPDATE rag_connectors
+             SET name = ?, collection_id = ?, base_url = ?, username = ?, scope = ?,
+                 sync_interval_minutes = ?, enabled = ?
+             WHERE id = ?
+             RETURNING {}",
+            CONNECTOR_COLUMNS
+        ))
+        .bind(&input.name)
+        .bind(input.collection_id)
+        .bind(&input.base_url)
+        .bind(&input.username)
+        .bind(&input.scope)
+        .bind(input.sync_interval_minutes)
+        .bind(input.enabled)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update connector: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(format!("Connector not found: {}", id)))?;
+
+        entity.try_into()
+    }
+
+    pub async fn get_connector(&self, id: i64) -> Result<RagConnector> {
+        sqlx::query_as::<_, RagConnectorEntity>(&format!(
+            "SELECT {} FROM rag_connectors WHERE id = ?",
+            CONNECTOR_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch connector: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(format!("Connector not found: {}", id)))?
+        .try_into()
+    }
+
+    pub async fn list_connectors(&self) -> Result<Vec<RagConnector>> {
+        sqlx::query_as::<_, RagConnectorEntity>(&format!(
+            "SELECT {} FROM rag_connectors ORDER BY name ASC, id ASC",
+            CONNECTOR_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list connectors: {}", e)))?
+        .into_iter()
+        .map(RagConnector::try_from)
+        .collect()
+    }
+
+    /// Delete a connector and its page records; the imported documents stay
+    pub async fn delete_connector(&self, id: i64) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM rag_connectors WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to delete connector: {}", e)))?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn record_connector_sync(
+        &self,
+        id: i64,
+        report: &ConnectorSyncReport,
+        error: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query(
+            "UPDATE rag_connectors
+             SET last_synced_at = ?, last_sync_status = ?, last_sync_error = ?
+             WHERE id = ?",
+        )
+        .bind(report.finished_at)
+        .bind(if error.is_some() {
+            "failed"
+        } else {
+            report.status()
+        })
+        .bind(error.or_else(|| report.errors.first().map(String::as_str)))
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to record connector sync: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Pages of a connector ordered by their place in the tree
+    pub async fn list_connector_pages(&self, connector_id: i64) -> Result<Vec<ConnectorPage>> {
+        let pages = sqlx::query_as::<_, ConnectorPageEntity>(
+            "SELECT connector_id, external_id, parent_external_id, title, path, url, last_edited_at, doc_id, synced_at
+             FROM rag_connector_pages WHERE connector_id = ?
+             ORDER BY path ASC",
+        )
+        .bind(connector_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list connector pages: {}", e)))?;
+
+        Ok(pages.into_iter().map(ConnectorPage::from).collect())
+    }
+
+    pub async fn upsert_connector_page(&self, page: &ConnectorPage) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO rag_connector_pages
+             (connector_id, external_id, parent_external_id, title, path, url, last_edited_at, doc_id, synced_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(connector_id, external_id) DO UPDATE SET
+               parent_external_id = excluded.parent_external_id,
+               title = excluded.title,
+               path = excluded.path,
+               url = excluded.url,
+               last_edited_at = excluded.last_edited_at,
+               doc_id = excluded.doc_id,
+               synced_at = excluded.synced_at",
+        )
+        .bind(page.connector_id)
+        .bind(&page.external_id)
+        .bind(&page.parent_external_id)
+        .bind(&page.title)
+        .bind(&page.path)
+        .bind(&page.url)
+        .bind(page.last_edited_at)
+        .bind(page.doc_id)
+        .bind(page.synced_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to save connector page: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn delete_connector_page(&self, connector_id: i64, external_id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM rag_connector_pages WHERE connector_id = ? AND external_id = ?")
+            .bind(connector_id)
+            .bind(external_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to delete connector page: {}", e))
+            })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+//! Document previews: thumbnail, title, author and page count of a
+//! document, kept in `document_previews` for document lists.
+
+use crate::domain::error::{AppError, Result};
+
+use super::RagRepository;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DocumentPreviewRecord {
+    pub doc_id: i64,
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub page_count: i64,
+    /// JPEG bytes of the first page
+    pub thumbnail: Option<Vec<u8>>,
+    pub thumbnail_width: Option<i64>,
+    pub thumbnail_height: Option<i64>,
+    /// Unix seconds
+    pub created_at: i64,
+}
+
+impl RagRepository {
+    pub async fn upsert_document_preview(&self, preview: &DocumentPreviewRecord) -> Result<()> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO document_previews\n             (doc_id, title, author, page_count, thumbnail, thumbnail_width, thumbnail_height, created_at)\n             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(preview.doc_id)
+        .bind(&preview.title)
+        .bind(&preview.author)
+        .bind(preview.page_count)
+        .bind(&preview.thumbnail)
+        .bind(preview.thumbnail_width)
+        .bind(preview.thumbnail_height)
+        .bind(preview.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to save document preview: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn get_document_preview(&self, doc_id: i64) -> Result<Option<DocumentPreviewRecord>> {
+        sqlx::query_as::<_, DocumentPreviewRecord>(
+            "SELECT doc_id, title, author, page_count, thumbnail, thumbnail_width, thumbnail_height, created_at\n             FROM document_previews WHERE doc_id = ?",
+        )
+        .bind(doc_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch document preview: {}", e)))
+    }
+}
@@ -1,5 +1,6 @@
 use crate::domain::error::{AppError, Result};
-use crate::domain::rag_entities::{RagDocument, RagDocumentInput};
+use crate::domain::rag_entities::{IngestionState, RagDocument, RagDocumentInput};
+use crate::domain::trash::{TrashEntry, TrashKind};
 
 use super::entities::RagDocumentEntity;
 use super::RagRepository;
@@ -15,6 +16,97 @@ impl RagRepository {
         Ok(result.rows_affected())
     }
 
+    /// Move a document to the trash, hiding it from lists and retrieval;
+    /// returns 0 when it does not exist or is already there
+    pub async fn trash_document(&self, id: i64, deleted_at: i64) -> Result<u64> {
+        self.set_document_deleted_at(id, Some(deleted_at)).await
+    }
+
+    pub async fn restore_document(&self, id: i64) -> Result<u64> {
+        self.set_document_deleted_at(id, None).await
+    }
+
+    async fn set_document_deleted_at(&self, id: i64, deleted_at: Option<i64>) -> Result<u64> {
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to begin transaction: {}", e))
+            })?;
+        let result = sqlx::query(
+            "UPDATE documents SET deleted_at = ?\n             WHERE id = ? AND (deleted_at IS NULL) = (? IS NOT NULL)",
+        )
+        .bind(deleted_at)
+        .bind(id)
+        .bind(deleted_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update document trash: {}", e)))?;
+        if result.rows_affected() == 0 {
+            return Ok(0);
+        }
+        // The collection's content changes, so its cached answers are stale
+        sqlx::query(
+            "UPDATE collections SET content_version = content_version + 1\n             WHERE id = (SELECT collection_id FROM documents WHERE id = ?)",
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update collection: {}", e)))?;
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit transaction: {}", e)))?;
+        Ok(result.rows_affected())
+    }
+
+    /// Documents in the trash, most recently deleted first
+    pub async fn list_trashed_documents(&self) -> Result<Vec<TrashEntry>> {
+        let rows = sqlx::query_as::<_, (i64, String, i64)>(
+            "SELECT id, file_name, deleted_at FROM documents\n             WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list trashed documents: {}", e)))?;
+        Ok(rows
+            .into_iter()
+            .map(|(id, file_name, deleted_at)| {
+                TrashEntry::new(
+                    TrashKind::RagDocument,
+                    id.to_string(),
+                    file_name,
+                    deleted_at,
+                )
+            })
+            .collect())
+    }
+
+    /// Delete the documents moved to the trash before `deleted_before` (ms)
+    /// for good
+    pub async fn purge_trashed_documents(&self, deleted_before: i64) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM documents WHERE deleted_at < ?")
+            .bind(deleted_before)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to purge documents: {}", e)))?;
+        Ok(result.rows_affected())
+    }
+
+    /// Delete the collection's trashed document imported from `file_path`,
+    /// if any, so the file can be imported into it again
+    pub async fn purge_trashed_document_by_path(
+        &self,
+        collection_id: Option<i64>,
+        file_path: &str,
+    ) -> Result<u64> {
+        let result = sqlx::query(
+            "DELETE FROM documents\n             WHERE collection_id IS ? AND file_path = ? AND deleted_at IS NOT NULL",
+        )
+        .bind(collection_id)
+        .bind(file_path)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to purge document: {}", e)))?;
+        Ok(result.rows_affected())
+    }
+
     pub async fn create_document(&self, input: &RagDocumentInput) -> Result<RagDocument> {
         let result = sqlx::query_as::<_, RagDocumentEntity>(
             "INSERT INTO documents (collection_id, file_name, file_path, file_type, language, total_pages)\n             VALUES (?, ?, ?, ?, ?, ?) RETURNING *",
@@ -34,7 +126,7 @@ impl RagRepository {
 
     pub async fn get_document(&self, id: i64) -> Result<RagDocument> {
         let document = sqlx::query_as::<_, RagDocumentEntity>(
-            "SELECT id, collection_id, file_name, file_path, file_type, language, total_pages,\n                    quality_score, ocr_confidence, chunk_count, warning_count, created_at\n             FROM documents WHERE id = ?",
+            "SELECT id, collection_id, file_name, file_path, file_type, language, total_pages,\n                    quality_score, ocr_confidence, chunk_count, warning_count, created_at,\n                    ingestion_state, ingestion_error\n             FROM documents WHERE id = ?",
         )
         .bind(id)
         .fetch_optional(&self.pool)
@@ -54,7 +146,7 @@ impl RagRepository {
     ) -> Result<Vec<RagDocument>> {
         if let Some(collection_id) = collection_id {
             let documents = sqlx::query_as::<_, RagDocumentEntity>(
-                "SELECT id, collection_id, file_name, file_path, file_type, language, total_pages,\n                        quality_score, ocr_confidence, chunk_count, warning_count, created_at\n                 FROM documents WHERE collection_id = ? ORDER BY created_at DESC LIMIT ?",
+                "SELECT id, collection_id, file_name, file_path, file_type, language, total_pages,\n                        quality_score, ocr_confidence, chunk_count, warning_count, created_at,\n                        ingestion_state, ingestion_error\n                 FROM documents WHERE collection_id = ? AND deleted_at IS NULL\n                 ORDER BY created_at DESC LIMIT ?",
             )
             .bind(collection_id)
             .bind(limit)
@@ -64,7 +156,7 @@ impl RagRepository {
             Ok(documents.into_iter().map(|d| d.into()).collect())
         } else {
             let documents = sqlx::query_as::<_, RagDocumentEntity>(
-                "SELECT id, collection_id, file_name, file_path, file_type, language, total_pages,\n                        quality_score, ocr_confidence, chunk_count, warning_count, created_at\n                 FROM documents ORDER BY created_at DESC LIMIT ?",
+                "SELECT id, collection_id, file_name, file_path, file_type, language, total_pages,\n                        quality_score, ocr_confidence, chunk_count, warning_count, created_at,\n                        ingestion_state, ingestion_error\n                 FROM documents WHERE deleted_at IS NULL ORDER BY created_at DESC LIMIT ?",
             )
             .bind(limit)
             .fetch_all(&self.pool)
@@ -81,7 +173,7 @@ impl RagRepository {
         collection_id: i64,
     ) -> Result<Option<String>> {
         let result = sqlx::query_as::<_, (String,)>(
-            "SELECT file_type FROM documents WHERE collection_id = ? LIMIT 1",
+            "SELECT file_type FROM documents WHERE collection_id = ? AND deleted_at IS NULL LIMIT 1",
         )
         .bind(collection_id)
         .fetch_optional(&self.pool)
@@ -90,4 +182,161 @@ impl RagRepository {
 
         Ok(result.map(|(file_type,)| file_type))
     }
+
+    /// OCR language hint of a document, e.g. `eng+jpn`
+    pub async fn get_document_ocr_languages(&self, id: i64) -> Result<Option<String>> {
+        let result = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT ocr_languages FROM documents WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to get OCR languages: {}", e)))?;
+
+        match result {
+            Some((languages,)) => Ok(languages),
+            None => Err(AppError::NotFound(format!("Document not found: {}", id))),
+        }
+    }
+
+    pub async fn set_document_ocr_languages(&self, id: i64, languages: Option<&str>) -> Result<()> {
+        let result = sqlx::query("UPDATE documents SET ocr_languages = ? WHERE id = ?")
+            .bind(languages)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to set OCR languages: {}", e)))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Document not found: {}", id)));
+        }
+        Ok(())
+    }
+
+    /// Id of the collection's document stored from `file_path`, if any
+    pub async fn find_document_by_path(
+        &self,
+        collection_id: i64,
+        file_path: &str,
+    ) -> Result<Option<i64>> {
+        let result = sqlx::query_as::<_, (i64,)>(
+            "SELECT id FROM documents WHERE collection_id = ? AND file_path = ? LIMIT 1",
+        )
+        .bind(collection_id)
+        .bind(file_path)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find document: {}", e)))?;
+
+        Ok(result.map(|(id,)| id))
+    }
+
+    /// Start tracking the import of a document at `state`, clearing an
+    /// earlier failure
+    pub async fn set_ingestion_state(&self, id: i64, state: IngestionState) -> Result<()> {
+        sqlx::query(
+            "UPDATE documents SET ingestion_state = ?, ingestion_error = NULL WHERE id = ?",
+        )
+        .bind(state.as_str())
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to set import state: {}", e)))?;
+        Ok(())
+    }
+
+    /// Move a tracked import on to `state`. Documents whose import is not
+    /// tracked (connector and repository syncs, re-OCR of complete
+    /// documents) stay complete.
+    pub async fn advance_ingestion_state(&self, id: i64, state: IngestionState) -> Result<()> {
+        sqlx::query(
+            "UPDATE documents SET ingestion_state = ?\n             WHERE id = ? AND ingestion_state != 'complete' AND ingestion_error IS NULL",
+        )
+        .bind(state.as_str())
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to set import state: {}", e)))?;
+        Ok(())
+    }
+
+    /// Record why an import stopped; it keeps the stage it reached
+    pub async fn fail_ingestion(&self, id: i64, error: &str) -> Result<()> {
+        sqlx::query("UPDATE documents SET ingestion_error = ? WHERE id = ?")
+            .bind(error)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to record import failure: {}", e))
+            })?;
+        Ok(())
+    }
+
+    /// Documents whose import failed or was interrupted, oldest first
+    pub async fn list_failed_ingestions(
+        &self,
+        collection_id: Option<i64>,
+    ) -> Result<Vec<RagDocument>> {
+        let documents = sqlx::query_as::<_, RagDocumentEntity>(
+            "SELECT id, collection_id, file_name, file_path, file_type, language, total_pages,\n                    quality_score, ocr_confidence, chunk_count, warning_count, created_at,\n                    ingestion_state, ingestion_error\n             FROM documents\n             WHERE ingestion_error IS NOT NULL AND deleted_at IS NULL\n               AND (? IS NULL OR collection_id = ?)\n             ORDER BY id",
+        )
+        .bind(collection_id)
+        .bind(collection_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list failed imports: {}", e)))?;
+        Ok(documents.into_iter().map(|d| d.into()).collect())
+    }
+
+    pub async fn set_document_total_pages(&self, id: i64, total_pages: i64) -> Result<()> {
+        sqlx::query("UPDATE documents SET total_pages = ? WHERE id = ?")
+            .bind(total_pages)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to set page count: {}", e)))?;
+        Ok(())
+    }
+
+    /// Drop what an interrupted import stored, before it runs again.
+    /// Structured rows are kept when `keep_rows`, as they were all stored
+    /// before chunking began.
+    pub async fn clear_document_content(&self, id: i64, keep_rows: bool) -> Result<()> {
+        let mut tables = vec!["document_chunks", "ocr_pages", "document_warnings"];
+        if !keep_rows {
+            tables.extend(["excel_data", "structured_rows"]);
+        }
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to begin transaction: {}", e))
+            })?;
+        for table in tables {
+            sqlx::query(&format!("DELETE FROM {} WHERE doc_id = ?", table))
+                .bind(id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| {
+                    AppError::DatabaseError(format!("Failed to clear {}: {}", table, e))
+                })?;
+        }
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit transaction: {}", e)))?;
+        Ok(())
+    }
+
+    pub async fn set_document_meta(&self, id: i64, meta_json: &str) -> Result<()> {
+        let result = sqlx::query("UPDATE documents SET meta_json = ? WHERE id = ?")
+            .bind(meta_json)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to set document metadata: {}", e)))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Document not found: {}", id)));
+        }
+        Ok(())
+    }
 }
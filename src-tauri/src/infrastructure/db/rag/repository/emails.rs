@@ -0,0 +1,175 @@
+//! Messages of imported email documents in `rag_email_messages`.
+
+use std::collections::HashSet;
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::rag_email::{EmailFilter, EmailMessageInput, EmailMessageRecord};
+
+use super::RagRepository;
+
+/// Conditions of an [`EmailFilter`] on `m`; binds sender twice, then
+/// sent_after, sent_before and thread_id twice each
+const FILTER_CONDITIONS: &str =
+    "(? IS NULL OR LOWER(COALESCE(m.from_address, '') || ' ' || COALESCE(m.from_name, '')) LIKE ?)
+       AND (? IS NULL OR m.sent_at >= ?)
+       AND (? IS NULL OR m.sent_at <= ?)
+       AND (? IS NULL OR m.thread_id = ?)";
+
+#[derive(sqlx::FromRow)]
+struct EmailMessageEntity {
+    id: i64,
+    doc_id: i64,
+    message_index: i64,
+    message_id: String,
+    thread_id: String,
+    in_reply_to: Option<String>,
+    subject: String,
+    from_address: Option<String>,
+    from_name: Option<String>,
+    recipients: String,
+    sent_at: Option<i64>,
+    attachments: String,
+    chunk_count: i64,
+}
+
+impl From<EmailMessageEntity> for EmailMessageRecord {
+    fn from(entity: EmailMessageEntity) -> Self {
+        Self {
+            id: entity.id,
+            doc_id: entity.doc_id,
+            message_index: entity.message_index,
+            message_id: entity.message_id,
+            thread_id: entity.thread_id,
+            in_reply_to: entity.in_reply_to,
+            subject: entity.subject,
+            from_address: entity.from_address,
+            from_name: entity.from_name,
+            recipients: entity.recipients,
+            sent_at: entity.sent_at,
+            attachments: entity.attachments,
+            chunk_count: entity.chunk_count,
+        }
+    }
+}
+
+/// Sender pattern for `LIKE`, lower case
+fn sender_pattern(filter: &EmailFilter) -> Option<String> {
+    filter
+        .sender
+        .as_deref()
+        .map(str::trim)
+        .filter(|sender| !sender.is_empty())
+        .map(|sender| format!("%{}%", sender.to_lowercase()))
+}
+
+impl RagRepository {
+    pub async fn insert_email_messages(
+        &self,
+        doc_id: i64,
+        messages: &[EmailMessageInput],
+    ) -> Result<()> {
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to begin transaction: {}", e))
+            })?;
+
+        for message in messages {
+            sqlx::query(
+                "INSERT INTO rag_email_messages
+                 (doc_id, message_index, message_id, thread_id, in_reply_to, subject, from_address,
+                  from_name, recipients, sent_at, attachments, chunk_count)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(doc_id)
+            .bind(message.message_index)
+            .bind(&message.message_id)
+            .bind(&message.thread_id)
+            .bind(&message.in_reply_to)
+            .bind(&message.subject)
+            .bind(&message.from_address)
+            .bind(&message.from_name)
+            .bind(&message.recipients)
+            .bind(message.sent_at)
+            .bind(&message.attachments)
+            .bind(message.chunk_count)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to store email message: {}", e))
+            })?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit transaction: {}", e)))?;
+        Ok(())
+    }
+
+    /// Messages of a collection's email documents, newest first
+    pub async fn list_email_messages(
+        &self,
+        collection_id: i64,
+        filter: &EmailFilter,
+        limit: i64,
+    ) -> Result<Vec<EmailMessageRecord>> {
+        let sender = sender_pattern(filter);
+        let messages = sqlx::query_as::<_, EmailMessageEntity>(&format!(
+            "SELECT m.id, m.doc_id, m.message_index, m.message_id, m.thread_id, m.in_reply_to,
+                    m.subject, m.from_address, m.from_name, m.recipients, m.sent_at,
+                    m.attachments, m.chunk_count
+             FROM rag_email_messages m
+             INNER JOIN documents d ON m.doc_id = d.id
+             WHERE d.collection_id = ? AND d.deleted_at IS NULL AND {}
+             ORDER BY m.sent_at IS NULL, m.sent_at DESC, m.id DESC
+             LIMIT ?",
+            FILTER_CONDITIONS
+        ))
+        .bind(collection_id)
+        .bind(&sender)
+        .bind(&sender)
+        .bind(filter.sent_after)
+        .bind(filter.sent_after)
+        .bind(filter.sent_before)
+        .bind(filter.sent_before)
+        .bind(&filter.thread_id)
+        .bind(&filter.thread_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list email messages: {}", e)))?;
+
+        Ok(messages.into_iter().map(EmailMessageRecord::from).collect())
+    }
+
+    /// Ids of the chunks of a collection's messages that match `filter`
+    pub async fn email_chunk_ids(
+        &self,
+        collection_id: i64,
+        filter: &EmailFilter,
+    ) -> Result<HashSet<i64>> {
+        let sender = sender_pattern(filter);
+        let ids = sqlx::query_as::<_, (i64,)>(&format!(
+            "SELECT dc.id
+             FROM rag_email_messages m
+             INNER JOIN documents d ON m.doc_id = d.id
+             INNER JOIN document_chunks dc
+               ON dc.doc_id = m.doc_id AND dc.page_number = m.message_index
+             WHERE d.collection_id = ? AND d.deleted_at IS NULL AND {}",
+            FILTER_CONDITIONS
+        ))
+        .bind(collection_id)
+        .bind(&sender)
+        .bind(&sender)
+        .bind(filter.sent_after)
+        .bind(filter.sent_after)
+        .bind(filter.sent_before)
+        .bind(filter.sent_before)
+        .bind(&filter.thread_id)
+        .bind(&filter.thread_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to filter email chunks: {}", e)))?;
+
+        Ok(ids.into_iter().map(|(id,)| id).collect())
+    }
+}
@@ -0,0 +1,400 @@
+//! Collection encryption at rest: key parameters in `collection_encryption`
+//! and bulk sealing/opening of a collection's chunks. Individual chunk reads
+//! and writes go through `chunks.rs`, which uses the ciphers registered here.
+
+use crate::domain::error::{AppError, Result};
+use crate::infrastructure::security::collection_cipher::{
+    sealed_bytes_collection, sealed_text_collection, CollectionCipher, CollectionKeys,
+};
+use std::sync::Arc;
+
+use super::RagRepository;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct CollectionEncryption {
+    pub collection_id: i64,
+    pub salt: Vec<u8>,
+    pub iterations: i64,
+    pub key_check: Vec<u8>,
+    pub created_at: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct StoredChunk {
+    id: i64,
+    content: String,
+    embedding_api: Option<Vec<u8>>,
+}
+
+impl RagRepository {
+    /// Encrypted collections, loaded from the database on first use. Every
+    /// one starts out locked until its key is supplied.
+    pub async fn collection_keys(&self) -> Result<&CollectionKeys> {
+        self.encryption_loaded
+            .get_or_try_init(|| async {
+                let ids =
+                    sqlx::query_scalar::<_, i64>("SELECT collection_id FROM collection_encryption")
+                        .fetch_all(&self.pool)
+                        .await
+                        .map_err(|e| {
+                            AppError::DatabaseError(format!(
+                                "Failed to load encrypted collections: {}",
+                                e
+                            ))
+                        })?;
+                for id in ids {
+                    self.collection_keys.set_locked(id);
+                }
+                Ok::<_, AppError>(())
+            })
+            .await?;
+        Ok(&self.collection_keys)
+    }
+
+    pub async fn is_collection_encrypted(&self, collection_id: i64) -> Result<bool> {
+        Ok(self.collection_keys().await?.is_encrypted(collection_id))
+    }
+
+    /// Cipher for chunks of a document's collection; `None` when the
+    /// collection is not encrypted
+    pub(super) async fn cipher_for_document(
+        &self,
+        doc_id: i64,
+    ) -> Result<Option<Arc<CollectionCipher>>> {
+        let keys = self.collection_keys().await?;
+        if !keys.has_encrypted() {
+            return Ok(None);
+        }
+        let collection_id = sqlx::query_scalar::<_, Option<i64>>(
+            "SELECT collection_id FROM documents WHERE id = ?",
+        )
+        .bind(doc_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to fetch document collection: {}", e))
+        })?
+        .flatten();
+        match collection_id {
+            Some(collection_id) => keys.cipher(collection_id),
+            None => Ok(None),
+        }
+    }
+
+    pub(super) async fn cipher_for_chunk(
+        &self,
+        chunk_id: i64,
+    ) -> Result<Option<Arc<CollectionCipher>>> {
+        if !self.collection_keys().await?.has_encrypted() {
+            return Ok(None);
+        }
+        let doc_id =
+            sqlx::query_scalar::<_, Option<i64>>("SELECT doc_id FROM document_chunks WHERE id = ?")
+                .bind(chunk_id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| {
+                    AppError::DatabaseError(format!("Failed to fetch chunk document: {}", e))
+                })?
+                .flatten();
+        match doc_id {
+            Some(doc_id) => self.cipher_for_document(doc_id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn get_collection_encryption(
+        &self,
+        collection_id: i64,
+    ) -> Result<Option<CollectionEncryption>> {
+        sqlx::query_as::<_, CollectionEncryption>(
+            "SELECT collection_id, salt, iterations, key_check, created_at\n             FROM collection_encryption WHERE collection_id = ?",
+        )
+        .bind(collection_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to fetch collection encryption: {}", e))
+        })
+    }
+
+    pub async fn list_collection_encryption(&self) -> Result<Vec<CollectionEncryption>> {
+        sqlx::query_as::<_, CollectionEncryption>(
+            "SELECT collection_id, salt, iterations, key_check, created_at\n             FROM collection_encryption ORDER BY collection_id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to list collection encryption: {}", e))
+        })
+    }
+
+    /// Record the key parameters and seal every chunk of the collection.
+    /// The cipher is registered first so chunks written meanwhile are
+    /// sealed too. Returns the number of chunks sealed.
+    pub async fn enable_collection_encryption(
+        &self,
+        collection_id: i64,
+        salt: &[u8],
+        iterations: u32,
+        cipher: CollectionCipher,
+    ) -> Result<usize> {
+        let keys = self.collection_keys().await?;
+        if keys.is_encrypted(collection_id) {
+            return Err(AppError::ValidationError(format!(
+                "Collection {} is already encrypted",
+                collection_id
+            )));
+        }
+        let key_check = cipher.key_check()?;
+        keys.unlock(cipher);
+        let cipher = keys
+            .cipher(collection_id)?
+            .ok_or_else(|| AppError::Internal("Collection cipher missing".to_string()))?;
+
+        let result = self
+            .seal_collection_chunks(collection_id, salt, iterations, &key_check, &cipher)
+            .await;
+        if result.is_err() {
+            keys.remove(collection_id);
+        }
+        result
+    }
+
+    async fn seal_collection_chunks(
+        &self,
+        collection_id: i64,
+        salt: &[u8],
+        iterations: u32,
+        key_check: &[u8],
+        cipher: &CollectionCipher,
+    ) -> Result<usize> {
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to begin transaction: {}", e))
+            })?;
+
+        sqlx::query(
+            "INSERT INTO collection_encryption (collection_id, salt, iterations, key_check)\n             VALUES (?, ?, ?, ?)",
+        )
+        .bind(collection_id)
+        .bind(salt)
+        .bind(iterations as i64)
+        .bind(key_check)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to save collection encryption: {}", e))
+        })?;
+
+        let chunks = sqlx::query_as::<_, StoredChunk>(
+            "SELECT dc.id, dc.content, dc.embedding_api\n             FROM document_chunks dc\n             INNER JOIN documents d ON dc.doc_id = d.id\n             WHERE d.collection_id = ?",
+        )
+        .bind(collection_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch chunks: {}", e)))?;
+
+        let mut sealed = 0;
+        for chunk in chunks {
+            let content = match sealed_text_collection(&chunk.content) {
+                Some(_) => chunk.content,
+                None => cipher.encrypt_text(&chunk.content)?,
+            };
+            let embedding = match chunk.embedding_api {
+                Some(bytes) if sealed_bytes_collection(&bytes).is_none() => {
+                    Some(cipher.encrypt_bytes(&bytes)?)
+                }
+                other => other,
+            };
+            sqlx::query("UPDATE document_chunks SET content = ?, embedding_api = ? WHERE id = ?")
+                .bind(content)
+                .bind(embedding)
+                .bind(chunk.id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to seal chunk: {}", e)))?;
+            sealed += 1;
+        }
+
+        // Thumbnails and titles are not sealed; keep only the page counts
+        sqlx::query(
+            "UPDATE document_previews\n             SET title = NULL, author = NULL, thumbnail = NULL, thumbnail_width = NULL, thumbnail_height = NULL\n             WHERE doc_id IN (SELECT id FROM documents WHERE collection_id = ?)",
+        )
+        .bind(collection_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to clear previews: {}", e)))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit transaction: {}", e)))?;
+        Ok(sealed)
+    }
+
+    /// Open every chunk of the collection and drop its key parameters.
+    /// Returns the number of chunks opened.
+    pub async fn disable_collection_encryption(&self, collection_id: i64) -> Result<usize> {
+        let keys = self.collection_keys().await?;
+        let cipher = keys.cipher(collection_id)?.ok_or_else(|| {
+            AppError::ValidationError(format!("Collection {} is not encrypted", collection_id))
+        })?;
+
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to begin transaction: {}", e))
+            })?;
+        let mut opened = open_collection_chunks(&mut tx, collection_id, &cipher).await?;
+        sqlx::query("DELETE FROM collection_encryption WHERE collection_id = ?")
+            .bind(collection_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to delete collection encryption: {}", e))
+            })?;
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit transaction: {}", e)))?;
+
+        // Chunks written while the transaction held the lock were sealed
+        // with the registered cipher; open those as well
+        keys.remove(collection_id);
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to begin transaction: {}", e))
+            })?;
+        opened += open_collection_chunks(&mut tx, collection_id, &cipher).await?;
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(opened)
+    }
+
+    /// Rewrite the database file so plaintext left in free pages after
+    /// sealing is gone. The FTS index keeps replaced rows in its segments
+    /// until they are merged, so it is optimized first; the checkpoint
+    /// clears the copies the WAL still holds.
+    pub async fn vacuum(&self) -> Result<()> {
+        for (statement, action) in [
+            (
+                "INSERT INTO document_chunks_fts(document_chunks_fts) VALUES('optimize')",
+                "optimize the chunk search index",
+            ),
+            ("VACUUM", "vacuum RAG database"),
+            ("PRAGMA wal_checkpoint(TRUNCATE)", "checkpoint RAG database"),
+        ] {
+            sqlx::query(statement)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to {}: {}", action, e)))?;
+        }
+        Ok(())
+    }
+}
+
+async fn open_collection_chunks(
+    tx: &mut sqlx::SqliteConnection,
+    collection_id: i64,
+    cipher: &CollectionCipher,
+) -> Result<usize> {
+    let chunks = sqlx::query_as::<_, StoredChunk>(
+        "SELECT dc.id, dc.content, dc.embedding_api\n         FROM document_chunks dc\n         INNER JOIN documents d ON dc.doc_id = d.id\n         WHERE d.collection_id = ?",
+    )
+    .bind(collection_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| AppError::DatabaseError(format!("Failed to fetch chunks: {}", e)))?;
+
+    let mut opened = 0;
+    for chunk in chunks {
+        let sealed_embedding = chunk
+            .embedding_api
+            .as_deref()
+            .is_some_and(|bytes| sealed_bytes_collection(bytes).is_some());
+        if sealed_text_collection(&chunk.content).is_none() && !sealed_embedding {
+            continue;
+        }
+        let content = match sealed_text_collection(&chunk.content) {
+            Some(_) => cipher.decrypt_text(&chunk.content)?,
+            None => chunk.content,
+        };
+        let embedding = match chunk.embedding_api {
+            Some(bytes) if sealed_embedding => Some(cipher.decrypt_bytes(&bytes)?),
+            other => other,
+        };
+        sqlx::query("UPDATE document_chunks SET content = ?, embedding_api = ? WHERE id = ?")
+            .bind(content)
+            .bind(embedding)
+            .bind(chunk.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to open chunk: {}", e)))?;
+        opened += 1;
+    }
+    Ok(opened)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::db::rag::connection::init_rag_db;
+    use crate::infrastructure::security::collection_cipher::KEY_LEN;
+
+    async fn fts_matches(repo: &RagRepository, term: &str) -> i64 {
+        sqlx::query_scalar(
+            "SELECT COUNT(*) FROM document_chunks_fts WHERE document_chunks_fts MATCH ?",
+        )
+        .bind(term)
+        .fetch_one(&repo.pool)
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn sealing_removes_plaintext_from_the_search_index_and_file() {
+        let dir = std::env::temp_dir().join(format!("rag-seal-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("rag_sense.db");
+        init_rag_db(&db_path).await.unwrap();
+        let repo = RagRepository::connect(&db_path).await.unwrap();
+
+        let collection_id = sqlx::query("INSERT INTO collections (name) VALUES ('payroll')")
+            .execute(&repo.pool)
+            .await
+            .unwrap()
+            .last_insert_rowid();
+        let doc_id = sqlx::query(
+            "INSERT INTO documents (collection_id, file_name, file_type) VALUES (?, 'pay.txt', 'txt')",
+        )
+        .bind(collection_id)
+        .execute(&repo.pool)
+        .await
+        .unwrap()
+        .last_insert_rowid();
+        sqlx::query("INSERT INTO document_chunks (doc_id, content) VALUES (?, ?)")
+            .bind(doc_id)
+            .bind("zanzibar bonus schedule")
+            .execute(&repo.pool)
+            .await
+            .unwrap();
+        assert_eq!(fts_matches(&repo, "zanzibar").await, 1);
+
+        let cipher = CollectionCipher::new(collection_id, &[7; KEY_LEN]).unwrap();
+        let sealed = repo
+            .enable_collection_encryption(collection_id, &[1; 16], 1, cipher)
+            .await
+            .unwrap();
+        repo.vacuum().await.unwrap();
+
+        assert_eq!(sealed, 1);
+        assert_eq!(fts_matches(&repo, "zanzibar").await, 0);
+        for path in [db_path.clone(), dir.join("rag_sense.db-wal")] {
+            let bytes = std::fs::read(&path).unwrap_or_default();
+            assert!(!bytes.windows(8).any(|window| window == b"zanzibar"));
+        }
+
+        repo.pool.close().await;
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::domain::rag_entities::DocumentWarning;
 use crate::domain::rag_entities::{
     CollectionQualityMetrics, ColumnInfo, DbAllowlistProfile, DbConnection, QueryTemplate,
-    RagCollection, RagDocument, RagDocumentChunk, RagExcelData, RetrievalGap,
+    IngestionState, RagCollection, RagDocument, RagDocumentChunk, RagExcelData, RetrievalGap,
 };
 
 #[derive(sqlx::FromRow)]
@@ -43,10 +43,13 @@ pub(super) struct RagDocumentEntity {
     chunk_count: i64,
     warning_count: i64,
     created_at: String,
+    ingestion_state: String,
+    ingestion_error: Option<String>,
 }
 
 impl From<RagDocumentEntity> for RagDocument {
     fn from(entity: RagDocumentEntity) -> Self {
+        let stage = IngestionState::parse(&entity.ingestion_state);
         Self {
             id: entity.id,
             collection_id: entity.collection_id,
@@ -62,6 +65,12 @@ impl From<RagDocumentEntity> for RagDocument {
             created_at: chrono::DateTime::parse_from_rfc3339(&entity.created_at)
                 .map(|dt| dt.with_timezone(&chrono::Utc))
                 .unwrap_or_else(|_| chrono::Utc::now()),
+            ingestion_state: match entity.ingestion_error {
+                Some(_) => IngestionState::Failed,
+                None => stage,
+            },
+            ingestion_failed_at: entity.ingestion_error.is_some().then_some(stage),
+            ingestion_error: entity.ingestion_error,
         }
     }
 }
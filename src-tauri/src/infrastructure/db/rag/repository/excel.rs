@@ -41,7 +41,7 @@ impl RagRepository {
         limit: i64,
     ) -> Result<Vec<RagExcelData>> {
         let data = sqlx::query_as::<_, RagExcelDataEntity>(
-            "SELECT ed.id, ed.doc_id, ed.row_index, ed.data_json, ed.val_a, ed.val_b, ed.val_c\n             FROM excel_data ed\n             INNER JOIN documents d ON ed.doc_id = d.id\n             WHERE d.collection_id = ?\n             ORDER BY ed.row_index ASC\n             LIMIT ?",
+            "SELECT ed.id, ed.doc_id, ed.row_index, ed.data_json, ed.val_a, ed.val_b, ed.val_c\n             FROM excel_data ed\n             INNER JOIN documents d ON ed.doc_id = d.id\n             WHERE d.collection_id = ? AND d.deleted_at IS NULL\n             ORDER BY ed.row_index ASC\n             LIMIT ?",
         )
         .bind(collection_id)
         .bind(limit)
@@ -62,7 +62,7 @@ impl RagRepository {
         limit: i64,
     ) -> Result<Vec<RagExcelData>> {
         let mut query = String::from(
-            "SELECT ed.id, ed.doc_id, ed.row_index, ed.data_json, ed.val_a, ed.val_b, ed.val_c\n             FROM excel_data ed\n             INNER JOIN documents d ON ed.doc_id = d.id\n             WHERE d.collection_id = ?",
+            "SELECT ed.id, ed.doc_id, ed.row_index, ed.data_json, ed.val_a, ed.val_b, ed.val_c\n             FROM excel_data ed\n             INNER JOIN documents d ON ed.doc_id = d.id\n             WHERE d.collection_id = ? AND d.deleted_at IS NULL",
         );
         let mut params: Vec<String> = Vec::new();
         let mut param_count = 1;
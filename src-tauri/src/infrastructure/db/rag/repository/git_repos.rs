@@ -0,0 +1,250 @@
+//! Git repository sources in `rag_git_repos`, and the files each one
+//! imported in `rag_git_files`.
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::rag_git::{GitRepoFile, GitRepoInput, GitRepoSource, GitSyncReport};
+
+use super::RagRepository;
+
+const GIT_REPO_COLUMNS: &str =
+    "id, name, collection_id, url, branch, subdirectory, sync_interval_minutes, enabled,
+     last_commit, last_synced_at, last_sync_status, last_sync_error, created_at";
+
+#[derive(sqlx::FromRow)]
+struct GitRepoEntity {
+    id: i64,
+    name: String,
+    collection_id: i64,
+    url: String,
+    branch: Option<String>,
+    subdirectory: Option<String>,
+    sync_interval_minutes: i64,
+    enabled: bool,
+    last_commit: Option<String>,
+    last_synced_at: Option<i64>,
+    last_sync_status: Option<String>,
+    last_sync_error: Option<String>,
+    created_at: i64,
+}
+
+impl From<GitRepoEntity> for GitRepoSource {
+    fn from(entity: GitRepoEntity) -> Self {
+        Self {
+            id: entity.id,
+            name: entity.name,
+            collection_id: entity.collection_id,
+            url: entity.url,
+            branch: entity.branch,
+            subdirectory: entity.subdirectory,
+            sync_interval_minutes: entity.sync_interval_minutes,
+            enabled: entity.enabled,
+            last_commit: entity.last_commit,
+            last_synced_at: entity.last_synced_at,
+            last_sync_status: entity.last_sync_status,
+            last_sync_error: entity.last_sync_error,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct GitRepoFileEntity {
+    repo_id: i64,
+    path: String,
+    language: String,
+    blob_sha: String,
+    commit_sha: String,
+    doc_id: Option<i64>,
+    chunk_count: i64,
+    synced_at: i64,
+}
+
+impl From<GitRepoFileEntity> for GitRepoFile {
+    fn from(entity: GitRepoFileEntity) -> Self {
+        Self {
+            repo_id: entity.repo_id,
+            path: entity.path,
+            language: entity.language,
+            blob_sha: entity.blob_sha,
+            commit_sha: entity.commit_sha,
+            doc_id: entity.doc_id,
+            chunk_count: entity.chunk_count,
+            synced_at: entity.synced_at,
+        }
+    }
+}
+
+impl RagRepository {
+    pub async fn create_git_repo(&self, input: &GitRepoInput) -> Result<GitRepoSource> {
+        let entity = sqlx::query_as::<_, GitRepoEntity>(&format!(
+            "INSERT INTO rag_git_repos
+             (name, collection_id, url, branch, subdirectory, sync_interval_minutes, enabled, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+             RETURNING {}",
+            GIT_REPO_COLUMNS
+        ))
+        .bind(&input.name)
+        .bind(input.collection_id)
+        .bind(&input.url)
+        .bind(&input.branch)
+        .bind(&input.subdirectory)
+        .bind(input.sync_interval_minutes)
+        .bind(input.enabled)
+        .bind(chrono::Utc::now().timestamp())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to create git repository: {}", e)))?;
+
+        Ok(entity.into())
+    }
+
+    /// Update the settings of a repository source. The last commit is
+    /// cleared so the next sync compares every file against the new scope.
+    pub async fn update_git_repo(&self, id: i64, input: &GitRepoInput) -> Result<GitRepoSource> {
+        let entity = sqlx::query_as::<_, GitRepoEntity>(&format!(
+            "UPDATE rag_git_repos
+             SET name = ?, url = ?, branch = ?, subdirectory = ?, sync_interval_minutes = ?, enabled = ?,
+                 last_commit = NULL
+             WHERE id = ?
+             RETURNING {}",
+            GIT_REPO_COLUMNS
+        ))
+        .bind(&input.name)
+        .bind(&input.url)
+        .bind(&input.branch)
+        .bind(&input.subdirectory)
+        .bind(input.sync_interval_minutes)
+        .bind(input.enabled)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update git repository: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(format!("Git repository not found: {}", id)))?;
+
+        Ok(entity.into())
+    }
+
+    pub async fn get_git_repo(&self, id: i64) -> Result<GitRepoSource> {
+        sqlx::query_as::<_, GitRepoEntity>(&format!(
+            "SELECT {} FROM rag_git_repos WHERE id = ?",
+            GIT_REPO_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch git repository: {}", e)))?
+        .map(GitRepoSource::from)
+        .ok_or_else(|| AppError::NotFound(format!("Git repository not found: {}", id)))
+    }
+
+    pub async fn list_git_repos(&self) -> Result<Vec<GitRepoSource>> {
+        let repos = sqlx::query_as::<_, GitRepoEntity>(&format!(
+            "SELECT {} FROM rag_git_repos ORDER BY name ASC, id ASC",
+            GIT_REPO_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list git repositories: {}", e)))?;
+
+        Ok(repos.into_iter().map(GitRepoSource::from).collect())
+    }
+
+    /// Delete a repository source and its file records; the imported
+    /// documents stay
+    pub async fn delete_git_repo(&self, id: i64) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM rag_git_repos WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to delete git repository: {}", e))
+            })?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Record a sync; `last_commit` only moves on a sync without errors so
+    /// failed files are retried next time
+    pub async fn record_git_sync(
+        &self,
+        id: i64,
+        report: &GitSyncReport,
+        error: Option<&str>,
+    ) -> Result<()> {
+        let status = if error.is_some() {
+            "failed"
+        } else {
+            report.status()
+        };
+        let commit = (status == "ok").then_some(report.commit_sha.as_str());
+        sqlx::query(
+            "UPDATE rag_git_repos
+             SET last_synced_at = ?, last_sync_status = ?, last_sync_error = ?,
+                 last_commit = COALESCE(?, last_commit)
+             WHERE id = ?",
+        )
+        .bind(report.finished_at)
+        .bind(status)
+        .bind(error.or_else(|| report.errors.first().map(String::as_str)))
+        .bind(commit)
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to record git sync: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn list_git_repo_files(&self, repo_id: i64) -> Result<Vec<GitRepoFile>> {
+        let files = sqlx::query_as::<_, GitRepoFileEntity>(
+            "SELECT repo_id, path, language, blob_sha, commit_sha, doc_id, chunk_count, synced_at
+             FROM rag_git_files WHERE repo_id = ?
+             ORDER BY path ASC",
+        )
+        .bind(repo_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list git files: {}", e)))?;
+
+        Ok(files.into_iter().map(GitRepoFile::from).collect())
+    }
+
+    pub async fn upsert_git_repo_file(&self, file: &GitRepoFile) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO rag_git_files
+             (repo_id, path, language, blob_sha, commit_sha, doc_id, chunk_count, synced_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(repo_id, path) DO UPDATE SET
+               language = excluded.language,
+               blob_sha = excluded.blob_sha,
+               commit_sha = excluded.commit_sha,
+               doc_id = excluded.doc_id,
+               chunk_count = excluded.chunk_count,
+               synced_at = excluded.synced_at",
+        )
+        .bind(file.repo_id)
+        .bind(&file.path)
+        .bind(&file.language)
+        .bind(&file.blob_sha)
+        .bind(&file.commit_sha)
+        .bind(file.doc_id)
+        .bind(file.chunk_count)
+        .bind(file.synced_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to save git file: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn delete_git_repo_file(&self, repo_id: i64, path: &str) -> Result<()> {
+        sqlx::query("DELETE FROM rag_git_files WHERE repo_id = ? AND path = ?")
+            .bind(repo_id)
+            .bind(path)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to delete git file: {}", e)))?;
+
+        Ok(())
+    }
+}
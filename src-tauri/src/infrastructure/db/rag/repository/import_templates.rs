@@ -0,0 +1,165 @@
+//! Column mapping templates for recurring CSV/Excel imports, in
+//! `rag_import_templates`.
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::rag_import_templates::{ImportTemplate, ImportTemplateInput};
+
+use super::RagRepository;
+
+const IMPORT_TEMPLATE_COLUMNS: &str =
+    "id, name, collection_id, signature, skip_rows, skip_footer_rows, columns_json,
+     applied_count, last_applied_at, created_at, updated_at";
+
+#[derive(sqlx::FromRow)]
+struct ImportTemplateEntity {
+    id: i64,
+    name: String,
+    collection_id: Option<i64>,
+    signature: String,
+    skip_rows: i64,
+    skip_footer_rows: i64,
+    columns_json: String,
+    applied_count: i64,
+    last_applied_at: Option<i64>,
+    created_at: i64,
+    updated_at: i64,
+}
+
+impl From<ImportTemplateEntity> for ImportTemplate {
+    fn from(entity: ImportTemplateEntity) -> Self {
+        Self {
+            id: entity.id,
+            name: entity.name,
+            collection_id: entity.collection_id,
+            signature: serde_json::from_str(&entity.signature).unwrap_or_default(),
+            skip_rows: entity.skip_rows,
+            skip_footer_rows: entity.skip_footer_rows,
+            columns: serde_json::from_str(&entity.columns_json).unwrap_or_default(),
+            applied_count: entity.applied_count,
+            last_applied_at: entity.last_applied_at,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+        }
+    }
+}
+
+/// The signature and columns of a template, as stored
+fn template_json(input: &ImportTemplateInput) -> Result<(String, String)> {
+    let signature = serde_json::to_string(&input.signature())
+        .map_err(|e| AppError::Internal(format!("Failed to serialize signature: {}", e)))?;
+    let columns = serde_json::to_string(&input.columns)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize columns: {}", e)))?;
+    Ok((signature, columns))
+}
+
+impl RagRepository {
+    pub async fn create_import_template(
+        &self,
+        input: &ImportTemplateInput,
+    ) -> Result<ImportTemplate> {
+        let (signature, columns) = template_json(input)?;
+        let now = chrono::Utc::now().timestamp();
+        let entity = sqlx::query_as::<_, ImportTemplateEntity>(&format!(
+            "INSERT INTO rag_import_templates
+             (name, collection_id, signature, skip_rows, skip_footer_rows, columns_json,
+              created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+             RETURNING {}",
+            IMPORT_TEMPLATE_COLUMNS
+        ))
+        .bind(&input.name)
+        .bind(input.collection_id)
+        .bind(&signature)
+        .bind(input.skip_rows)
+        .bind(input.skip_footer_rows)
+        .bind(&columns)
+        .bind(now)
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to create import template: {}", e)))?;
+
+        Ok(entity.into())
+    }
+
+    pub async fn update_import_template(
+        &self,
+        id: i64,
+        input: &ImportTemplateInput,
+    ) -> Result<ImportTemplate> {
+        let (signature, columns) = template_json(input)?;
+        let entity = sqlx::query_as::<_, ImportTemplateEntity>(&format!(
+            "UPDATE rag_import_templates
+             SET name = ?, collection_id = ?, signature = ?, skip_rows = ?, skip_footer_rows = ?,
+                 columns_json = ?, updated_at = ?
+             WHERE id = ?
+             RETURNING {}",
+            IMPORT_TEMPLATE_COLUMNS
+        ))
+        .bind(&input.name)
+        .bind(input.collection_id)
+        .bind(&signature)
+        .bind(input.skip_rows)
+        .bind(input.skip_footer_rows)
+        .bind(&columns)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update import template: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(format!("Import template not found: {}", id)))?;
+
+        Ok(entity.into())
+    }
+
+    /// Templates of one collection and those for any collection, or all
+    /// templates when `collection_id` is `None`
+    pub async fn list_import_templates(
+        &self,
+        collection_id: Option<i64>,
+    ) -> Result<Vec<ImportTemplate>> {
+        let templates = sqlx::query_as::<_, ImportTemplateEntity>(&format!(
+            "SELECT {} FROM rag_import_templates
+             WHERE ? IS NULL OR collection_id IS NULL OR collection_id = ?
+             ORDER BY name ASC, id ASC",
+            IMPORT_TEMPLATE_COLUMNS
+        ))
+        .bind(collection_id)
+        .bind(collection_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list import templates: {}", e)))?;
+
+        Ok(templates.into_iter().map(ImportTemplate::from).collect())
+    }
+
+    pub async fn delete_import_template(&self, id: i64) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM rag_import_templates WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to delete import template: {}", e))
+            })?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Count an import whose rows were stored with a template
+    pub async fn record_import_template_use(&self, id: i64) -> Result<()> {
+        sqlx::query(
+            "UPDATE rag_import_templates
+             SET applied_count = applied_count + 1, last_applied_at = ?
+             WHERE id = ?",
+        )
+        .bind(chrono::Utc::now().timestamp())
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to record import template use: {}", e))
+        })?;
+
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use crate::domain::error::{AppError, Result};
+use crate::infrastructure::security::collection_cipher::CollectionKeys;
 use sqlx::sqlite::{
     SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions, SqliteSynchronous,
 };
@@ -6,21 +7,46 @@ use std::path::Path;
 use std::str::FromStr;
 use std::time::Duration;
 
+mod answer_cache;
+mod chunk_usage;
 mod chunks;
 mod collections;
+mod connectors;
 mod context_settings;
 mod db_connector;
+mod document_previews;
 mod documents;
+mod emails;
+mod encryption;
 mod entities;
 mod excel;
+mod git_repos;
+mod import_templates;
+mod ocr_pages;
 mod quality;
+mod query_routing;
+mod retrieval_cache;
+mod retrieval_tuning;
 mod structured_rows;
+mod transfer;
 
+pub use answer_cache::PersistedAnswer;
+pub use chunk_usage::{ChunkUsage, CollectionChunkUsage, DocumentUsage};
 pub use chunks::{ChunkWithMetadata, ChunkWithMetadataScore};
+pub use document_previews::DocumentPreviewRecord;
+pub use encryption::CollectionEncryption;
+pub use ocr_pages::OcrPageRecord;
+pub use query_routing::RoutingDecisionRecord;
+pub use retrieval_cache::{CachedChunkHit, PersistedRetrieval};
+pub use retrieval_tuning::{RetrievalExperimentRecord, RetrievalFeedbackRecord};
 pub use structured_rows::StructuredRowWithDoc;
+pub use transfer::TransferDocumentRecord;
 
 pub struct RagRepository {
     pool: SqlitePool,
+    /// Ciphers of encrypted collections; see `encryption.rs`
+    collection_keys: CollectionKeys,
+    encryption_loaded: tokio::sync::OnceCell<()>,
 }
 
 impl RagRepository {
@@ -40,7 +66,11 @@ impl RagRepository {
             .await
             .map_err(|e| AppError::DatabaseError(format!("Failed to connect RAG DB: {}", e)))?;
 
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            collection_keys: CollectionKeys::default(),
+            encryption_loaded: tokio::sync::OnceCell::new(),
+        })
     }
 
     /// Get a reference to the connection pool for direct queries
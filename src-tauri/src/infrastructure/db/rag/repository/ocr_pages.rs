@@ -0,0 +1,231 @@
+//! OCR page quality: Tesseract confidence of every OCR'd page, kept in
+//! `ocr_pages`, which doubles as the queue of pages to OCR again.
+
+use crate::domain::error::{AppError, Result};
+use serde::Serialize;
+
+use super::RagRepository;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OcrPageRecord {
+    pub doc_id: i64,
+    pub page_number: i64,
+    /// Mean word confidence, 0.0-1.0
+    pub confidence: f32,
+    pub block_confidences: Vec<f32>,
+    pub dpi: i64,
+    pub preprocessing: String,
+    /// ok, queued, improved or exhausted
+    pub status: String,
+    pub attempts: i64,
+    pub last_error: Option<String>,
+    /// Last change in unix seconds
+    pub updated_at: i64,
+}
+
+#[derive(sqlx::FromRow)]
+struct OcrPageRow {
+    doc_id: i64,
+    page_number: i64,
+    confidence: f64,
+    block_confidences: String,
+    dpi: i64,
+    preprocessing: String,
+    status: String,
+    attempts: i64,
+    last_error: Option<String>,
+    updated_at: i64,
+}
+
+impl From<OcrPageRow> for OcrPageRecord {
+    fn from(row: OcrPageRow) -> Self {
+        Self {
+            doc_id: row.doc_id,
+            page_number: row.page_number,
+            confidence: row.confidence as f32,
+            block_confidences: serde_json::from_str(&row.block_confidences).unwrap_or_default(),
+            dpi: row.dpi,
+            preprocessing: row.preprocessing,
+            status: row.status,
+            attempts: row.attempts,
+            last_error: row.last_error,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+/// Confidence as stored: four decimals, without the noise of widening an `f32`
+fn stored_confidence(confidence: f32) -> f64 {
+    (confidence as f64 * 10_000.0).round() / 10_000.0
+}
+
+fn confidences_json(confidences: &[f32]) -> Result<String> {
+    let confidences: Vec<f64> = confidences.iter().copied().map(stored_confidence).collect();
+    serde_json::to_string(&confidences)
+        .map_err(|e| AppError::Internal(format!("Failed to encode block confidences: {}", e)))
+}
+
+impl RagRepository {
+    pub async fn upsert_ocr_page(&self, page: &OcrPageRecord) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO ocr_pages (doc_id, page_number, confidence, block_confidences, dpi, preprocessing, status, attempts, last_error, updated_at)\n             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)\n             ON CONFLICT(doc_id, page_number) DO UPDATE SET\n               confidence = excluded.confidence,\n               block_confidences = excluded.block_confidences,\n               dpi = excluded.dpi,\n               preprocessing = excluded.preprocessing,\n               status = excluded.status,\n               attempts = excluded.attempts,\n               last_error = excluded.last_error,\n               updated_at = excluded.updated_at",
+        )
+        .bind(page.doc_id)
+        .bind(page.page_number)
+        .bind(stored_confidence(page.confidence))
+        .bind(confidences_json(&page.block_confidences)?)
+        .bind(page.dpi)
+        .bind(&page.preprocessing)
+        .bind(&page.status)
+        .bind(page.attempts)
+        .bind(&page.last_error)
+        .bind(page.updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to save OCR page: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// OCR'd pages, optionally of one document and/or in one status, in
+    /// queue order: oldest change first
+    pub async fn list_ocr_pages(
+        &self,
+        doc_id: Option<i64>,
+        status: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<OcrPageRecord>> {
+        let rows = sqlx::query_as::<_, OcrPageRow>(
+            "SELECT doc_id, page_number, confidence, block_confidences, dpi, preprocessing,\n                    status, attempts, last_error, updated_at\n             FROM ocr_pages\n             WHERE (? IS NULL OR doc_id = ?) AND (? IS NULL OR status = ?)\n             ORDER BY updated_at ASC, doc_id ASC, page_number ASC\n             LIMIT ?",
+        )
+        .bind(doc_id)
+        .bind(doc_id)
+        .bind(status)
+        .bind(status)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch OCR pages: {}", e)))?;
+
+        Ok(rows.into_iter().map(OcrPageRecord::from).collect())
+    }
+
+    pub async fn get_ocr_page(
+        &self,
+        doc_id: i64,
+        page_number: i64,
+    ) -> Result<Option<OcrPageRecord>> {
+        let row = sqlx::query_as::<_, OcrPageRow>(
+            "SELECT doc_id, page_number, confidence, block_confidences, dpi, preprocessing,\n                    status, attempts, last_error, updated_at\n             FROM ocr_pages WHERE doc_id = ? AND page_number = ?",
+        )
+        .bind(doc_id)
+        .bind(page_number)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch OCR page: {}", e)))?;
+
+        Ok(row.map(OcrPageRecord::from))
+    }
+
+    pub async fn count_ocr_pages(&self, status: &str) -> Result<i64> {
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM ocr_pages WHERE status = ?")
+            .bind(status)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to count OCR pages: {}", e)))
+    }
+
+    /// Drop warnings of `warning_type` for one page, or the document-level
+    /// ones when `page_number` is `None`
+    pub async fn delete_page_warnings(
+        &self,
+        doc_id: i64,
+        page_number: Option<i64>,
+        warning_type: &str,
+    ) -> Result<u64> {
+        let result = sqlx::query(
+            "DELETE FROM document_warnings\n             WHERE doc_id = ? AND warning_type = ? AND page_number IS ?",
+        )
+        .bind(doc_id)
+        .bind(warning_type)
+        .bind(page_number)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to delete warnings: {}", e)))?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Merge the page's OCR confidence into `meta_json` of its chunks
+    pub async fn set_page_chunks_ocr_meta(
+        &self,
+        doc_id: i64,
+        page_number: i64,
+        confidence: f32,
+        block_confidences: &[f32],
+    ) -> Result<u64> {
+        let meta = serde_json::json!({
+            "confidence": stored_confidence(confidence),
+            "block_confidences": block_confidences
+                .iter()
+                .copied()
+                .map(stored_confidence)
+                .collect::<Vec<_>>(),
+        });
+        let result = sqlx::query(
+            "UPDATE document_chunks SET meta_json = json_set(COALESCE(NULLIF(meta_json, ''), '{}'), '$.ocr', json(?))\n             WHERE doc_id = ? AND page_number = ?",
+        )
+        .bind(meta.to_string())
+        .bind(doc_id)
+        .bind(page_number)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to update chunk OCR metadata: {}", e))
+        })?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete_page_chunks(&self, doc_id: i64, page_number: i64) -> Result<u64> {
+        let result =
+            sqlx::query("DELETE FROM document_chunks WHERE doc_id = ? AND page_number = ?")
+                .bind(doc_id)
+                .bind(page_number)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| {
+                    AppError::DatabaseError(format!("Failed to delete page chunks: {}", e))
+                })?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Chunk index that follows the document's last chunk
+    pub async fn next_chunk_index(&self, doc_id: i64) -> Result<i64> {
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COALESCE(MAX(chunk_index) + 1, 0) FROM document_chunks WHERE doc_id = ?",
+        )
+        .bind(doc_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch chunk index: {}", e)))
+    }
+
+    /// Recompute the document's OCR confidence, chunk and warning counts
+    /// after pages were OCR'd again; returns the new OCR confidence
+    pub async fn refresh_document_ocr_stats(&self, doc_id: i64) -> Result<Option<f64>> {
+        sqlx::query_scalar::<_, Option<f64>>(
+            "UPDATE documents SET\n               ocr_confidence = (SELECT AVG(confidence) FROM ocr_pages WHERE doc_id = ?),\n               chunk_count = (SELECT COUNT(*) FROM document_chunks WHERE doc_id = ?),\n               warning_count = (SELECT COUNT(*) FROM document_warnings WHERE doc_id = ?)\n             WHERE id = ?\n             RETURNING ocr_confidence",
+        )
+        .bind(doc_id)
+        .bind(doc_id)
+        .bind(doc_id)
+        .bind(doc_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to update document OCR stats: {}", e))
+        })
+    }
+}
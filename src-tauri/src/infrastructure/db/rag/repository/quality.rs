@@ -118,7 +118,7 @@ impl RagRepository {
 
         // Aggregate metrics from documents
         let stats = sqlx::query_as::<_, CollectionStatsRow>(
-            "SELECT\n                COUNT(*) as total_documents,\n                AVG(quality_score) as avg_quality_score,\n                AVG(ocr_confidence) as avg_ocr_confidence,\n                SUM(chunk_count) as total_chunks,\n                SUM(CASE WHEN warning_count > 0 THEN 1 ELSE 0 END) as documents_with_warnings\n             FROM documents WHERE collection_id = ?",
+            "SELECT\n                COUNT(*) as total_documents,\n                AVG(quality_score) as avg_quality_score,\n                AVG(ocr_confidence) as avg_ocr_confidence,\n                SUM(chunk_count) as total_chunks,\n                SUM(CASE WHEN warning_count > 0 THEN 1 ELSE 0 END) as documents_with_warnings\n             FROM documents WHERE collection_id = ? AND deleted_at IS NULL",
         )
         .bind(collection_id)
         .fetch_one(&self.pool)
@@ -129,7 +129,7 @@ impl RagRepository {
 
         // Compute average chunk quality
         let avg_chunk_quality = sqlx::query_scalar::<_, Option<f64>>(
-            "SELECT AVG(dc.chunk_quality) FROM document_chunks dc\n             INNER JOIN documents d ON dc.doc_id = d.id\n             WHERE d.collection_id = ?",
+            "SELECT AVG(dc.chunk_quality) FROM document_chunks dc\n             INNER JOIN documents d ON dc.doc_id = d.id\n             WHERE d.collection_id = ? AND d.deleted_at IS NULL",
         )
         .bind(collection_id)
         .fetch_one(&self.pool)
@@ -201,7 +201,7 @@ impl RagRepository {
         limit: i64,
     ) -> Result<Vec<RagDocument>> {
         let documents = sqlx::query_as::<_, RagDocumentEntity>(
-            "SELECT id, collection_id, file_name, file_path, file_type, language, total_pages,\n                    quality_score, ocr_confidence, chunk_count, warning_count, created_at\n             FROM documents\n             WHERE collection_id = ? AND (quality_score IS NULL OR quality_score < ?)\n             ORDER BY quality_score ASC NULLS FIRST\n             LIMIT ?",
+            "SELECT id, collection_id, file_name, file_path, file_type, language, total_pages,\n                    quality_score, ocr_confidence, chunk_count, warning_count, created_at,\n                    ingestion_state, ingestion_error\n             FROM documents\n             WHERE collection_id = ? AND deleted_at IS NULL\n               AND (quality_score IS NULL OR quality_score < ?)\n             ORDER BY quality_score ASC NULLS FIRST\n             LIMIT ?",
         )
         .bind(collection_id)
         .bind(threshold)
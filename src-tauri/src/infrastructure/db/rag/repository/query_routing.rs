@@ -0,0 +1,126 @@
+//! Query routing decisions: the route each query was sent down, kept in
+//! `query_routing_decisions` with the route a user corrected it to.
+
+use crate::domain::error::{AppError, Result};
+use serde::Serialize;
+
+use super::RagRepository;
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct RoutingDecisionRecord {
+    pub id: i64,
+    pub collection_id: Option<i64>,
+    pub query_hash: String,
+    pub query_type: String,
+    /// Route picked by the analyzer; `None` for column/value lookups
+    pub auto_route: Option<String>,
+    /// Route the query ran on
+    pub route: Option<String>,
+    pub overridden: bool,
+    pub structured_signals: i64,
+    pub term_count: i64,
+    pub corrected_route: Option<String>,
+    /// Unix seconds
+    pub created_at: i64,
+}
+
+impl RagRepository {
+    /// Record how a query was routed; returns the decision id
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_routing_decision(
+        &self,
+        collection_id: i64,
+        query_hash: &str,
+        query_type: &str,
+        auto_route: Option<&str>,
+        route: Option<&str>,
+        overridden: bool,
+        structured_signals: i64,
+        term_count: i64,
+    ) -> Result<i64> {
+        let result = sqlx::query(
+            "INSERT INTO query_routing_decisions\n             (collection_id, query_hash, query_type, auto_route, route, overridden,\n              structured_signals, term_count, created_at)\n             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(collection_id)
+        .bind(query_hash)
+        .bind(query_type)
+        .bind(auto_route)
+        .bind(route)
+        .bind(overridden)
+        .bind(structured_signals)
+        .bind(term_count)
+        .bind(chrono::Utc::now().timestamp())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to record routing decision: {}", e))
+        })?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    pub async fn get_routing_decision(&self, id: i64) -> Result<RoutingDecisionRecord> {
+        sqlx::query_as::<_, RoutingDecisionRecord>(
+            "SELECT id, collection_id, query_hash, query_type, auto_route, route, overridden,\n                    structured_signals, term_count, corrected_route, created_at\n             FROM query_routing_decisions WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to get routing decision: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(format!("Routing decision {} not found", id)))
+    }
+
+    /// Store the route a user says the query should have taken
+    pub async fn correct_routing_decision(&self, id: i64, route: &str) -> Result<()> {
+        let result =
+            sqlx::query("UPDATE query_routing_decisions SET corrected_route = ? WHERE id = ?")
+                .bind(route)
+                .bind(id)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| {
+                    AppError::DatabaseError(format!("Failed to correct routing decision: {}", e))
+                })?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "Routing decision {} not found",
+                id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Most recent decisions, newest first
+    pub async fn list_routing_decisions(
+        &self,
+        collection_id: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<RoutingDecisionRecord>> {
+        sqlx::query_as::<_, RoutingDecisionRecord>(
+            "SELECT id, collection_id, query_hash, query_type, auto_route, route, overridden,\n                    structured_signals, term_count, corrected_route, created_at\n             FROM query_routing_decisions\n             WHERE ? IS NULL OR collection_id = ?\n             ORDER BY created_at DESC, id DESC\n             LIMIT ?",
+        )
+        .bind(collection_id)
+        .bind(collection_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to list routing decisions: {}", e))
+        })
+    }
+
+    /// Most recent decisions whose right route is known: corrected ones and
+    /// ones whose route was forced, newest first. Lookups the analyzer did
+    /// not route are left out.
+    pub async fn list_routing_examples(&self, limit: i64) -> Result<Vec<RoutingDecisionRecord>> {
+        sqlx::query_as::<_, RoutingDecisionRecord>(
+            "SELECT id, collection_id, query_hash, query_type, auto_route, route, overridden,\n                    structured_signals, term_count, corrected_route, created_at\n             FROM query_routing_decisions\n             WHERE auto_route IS NOT NULL\n               AND (corrected_route IS NOT NULL OR (overridden = 1 AND route IS NOT NULL))\n             ORDER BY created_at DESC, id DESC\n             LIMIT ?",
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to list routing examples: {}", e))
+        })
+    }
+}
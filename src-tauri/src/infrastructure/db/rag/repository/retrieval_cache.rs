@@ -0,0 +1,197 @@
+//! Persisted retrieval cache: text retrieval results by query, kept in
+//! `retrieval_cache` so they survive restarts. Queries are matched
+//! case-insensitively, like the in-memory cache.
+
+use crate::domain::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+
+use super::chunks::bytes_to_embedding;
+use super::RagRepository;
+
+/// One retrieved chunk, in result order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedChunkHit {
+    pub chunk_id: i64,
+    pub score: Option<f32>,
+}
+
+pub struct PersistedRetrieval {
+    pub collection_id: i64,
+    pub query_text: String,
+    pub top_k: i64,
+    pub embedding_model: String,
+    pub query_embedding: Option<Vec<f32>>,
+    pub hits: Vec<CachedChunkHit>,
+}
+
+#[derive(sqlx::FromRow)]
+struct RetrievalCacheRow {
+    collection_id: i64,
+    query_text: String,
+    top_k: i64,
+    embedding_model: String,
+    query_embedding: Option<Vec<u8>>,
+    results_json: String,
+}
+
+impl TryFrom<RetrievalCacheRow> for PersistedRetrieval {
+    type Error = AppError;
+
+    fn try_from(row: RetrievalCacheRow) -> Result<Self> {
+        let hits = serde_json::from_str(&row.results_json).map_err(|e| {
+            AppError::ParseError(format!("Invalid cached retrieval results: {}", e))
+        })?;
+        Ok(Self {
+            collection_id: row.collection_id,
+            query_text: row.query_text,
+            top_k: row.top_k,
+            embedding_model: row.embedding_model,
+            query_embedding: row.query_embedding.as_deref().and_then(bytes_to_embedding),
+            hits,
+        })
+    }
+}
+
+fn query_key(query_text: &str) -> String {
+    query_text.to_lowercase()
+}
+
+impl RagRepository {
+    /// Insert or replace the cached results for a query
+    pub async fn save_retrieval_cache(
+        &self,
+        entry: &PersistedRetrieval,
+        ttl_secs: i64,
+    ) -> Result<()> {
+        let results_json = serde_json::to_string(&entry.hits).map_err(|e| {
+            AppError::Internal(format!("Failed to encode cached retrieval results: {}", e))
+        })?;
+        let query_embedding: Option<Vec<u8>> = entry
+            .query_embedding
+            .as_ref()
+            .map(|embedding| embedding.iter().flat_map(|v| v.to_le_bytes()).collect());
+        let now = chrono::Utc::now().timestamp();
+
+        sqlx::query(
+            "INSERT OR REPLACE INTO retrieval_cache\n             (collection_id, query_key, top_k, query_text, embedding_model, query_embedding, results_json, created_at, expires_at)\n             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(entry.collection_id)
+        .bind(query_key(&entry.query_text))
+        .bind(entry.top_k)
+        .bind(&entry.query_text)
+        .bind(&entry.embedding_model)
+        .bind(query_embedding)
+        .bind(results_json)
+        .bind(now)
+        .bind(now.saturating_add(ttl_secs))
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to save retrieval cache: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Unexpired cached results for a query, computed with `embedding_model`
+    pub async fn find_retrieval_cache(
+        &self,
+        collection_id: i64,
+        query_text: &str,
+        top_k: i64,
+        embedding_model: &str,
+    ) -> Result<Option<PersistedRetrieval>> {
+        let row = sqlx::query_as::<_, RetrievalCacheRow>(
+            "SELECT collection_id, query_text, top_k, embedding_model, query_embedding, results_json\n             FROM retrieval_cache\n             WHERE collection_id = ? AND query_key = ? AND top_k = ?\n               AND embedding_model = ? AND expires_at > ?",
+        )
+        .bind(collection_id)
+        .bind(query_key(query_text))
+        .bind(top_k)
+        .bind(embedding_model)
+        .bind(chrono::Utc::now().timestamp())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to read retrieval cache: {}", e)))?;
+
+        row.map(PersistedRetrieval::try_from).transpose()
+    }
+
+    /// Newest unexpired entries computed with `embedding_model`
+    pub async fn recent_retrieval_cache(
+        &self,
+        embedding_model: &str,
+        limit: i64,
+    ) -> Result<Vec<PersistedRetrieval>> {
+        let rows = sqlx::query_as::<_, RetrievalCacheRow>(
+            "SELECT collection_id, query_text, top_k, embedding_model, query_embedding, results_json\n             FROM retrieval_cache\n             WHERE embedding_model = ? AND expires_at > ?\n             ORDER BY created_at DESC\n             LIMIT ?",
+        )
+        .bind(embedding_model)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to read retrieval cache: {}", e)))?;
+
+        // Skip entries that no longer parse rather than failing the warm-up
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| PersistedRetrieval::try_from(row).ok())
+            .collect())
+    }
+
+    pub async fn delete_retrieval_cache_entry(
+        &self,
+        collection_id: i64,
+        query_text: &str,
+        top_k: i64,
+    ) -> Result<u64> {
+        let result = sqlx::query(
+            "DELETE FROM retrieval_cache WHERE collection_id = ? AND query_key = ? AND top_k = ?",
+        )
+        .bind(collection_id)
+        .bind(query_key(query_text))
+        .bind(top_k)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to delete retrieval cache entry: {}", e))
+        })?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete_collection_retrieval_cache(&self, collection_id: i64) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM retrieval_cache WHERE collection_id = ?")
+            .bind(collection_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to invalidate retrieval cache: {}", e))
+            })?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn clear_retrieval_cache(&self) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM retrieval_cache")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to clear retrieval cache: {}", e))
+            })?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Remove expired entries
+    pub async fn prune_retrieval_cache(&self) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM retrieval_cache WHERE expires_at <= ?")
+            .bind(chrono::Utc::now().timestamp())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to prune retrieval cache: {}", e))
+            })?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,162 @@
+//! Retrieval tuning: rated answers with the parameters they were produced
+//! with, kept in `retrieval_feedback`, and the parameter experiments in
+//! `retrieval_experiments`.
+
+use crate::domain::error::{AppError, Result};
+use serde::Serialize;
+
+use super::RagRepository;
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct RetrievalFeedbackRecord {
+    pub collection_id: Option<i64>,
+    /// `positive`, `negative` or `neutral`
+    pub rating: String,
+    pub top_k: i64,
+    pub vector_weight: f64,
+    pub keyword_weight: f64,
+    pub reranking_enabled: bool,
+    pub collection_quality: Option<f64>,
+    pub experiment_id: Option<i64>,
+    pub experiment_arm: Option<String>,
+    /// Unix seconds
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct RetrievalExperimentRecord {
+    pub id: i64,
+    pub status: String,
+    pub baseline_json: String,
+    pub candidate_json: String,
+    pub rationale: Option<String>,
+    pub created_at: i64,
+    pub ended_at: Option<i64>,
+}
+
+impl RagRepository {
+    pub async fn record_retrieval_feedback(
+        &self,
+        query_text: &str,
+        record: &RetrievalFeedbackRecord,
+    ) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO retrieval_feedback\n             (collection_id, query_text, rating, top_k, vector_weight, keyword_weight, reranking_enabled,\n              collection_quality, experiment_id, experiment_arm, created_at)\n             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(record.collection_id)
+        .bind(query_text)
+        .bind(&record.rating)
+        .bind(record.top_k)
+        .bind(record.vector_weight)
+        .bind(record.keyword_weight)
+        .bind(record.reranking_enabled)
+        .bind(record.collection_quality)
+        .bind(record.experiment_id)
+        .bind(&record.experiment_arm)
+        .bind(record.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to record retrieval feedback: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    /// Most recent rated answers, newest first
+    pub async fn list_retrieval_feedback(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<RetrievalFeedbackRecord>> {
+        sqlx::query_as::<_, RetrievalFeedbackRecord>(
+            "SELECT collection_id, rating, top_k, vector_weight, keyword_weight, reranking_enabled,\n                    collection_quality, experiment_id, experiment_arm, created_at\n             FROM retrieval_feedback\n             ORDER BY created_at DESC, id DESC\n             LIMIT ?",
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to list retrieval feedback: {}", e))
+        })
+    }
+
+    pub async fn clear_retrieval_feedback(&self) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM retrieval_feedback")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to clear retrieval feedback: {}", e))
+            })?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn get_running_retrieval_experiment(
+        &self,
+    ) -> Result<Option<RetrievalExperimentRecord>> {
+        sqlx::query_as::<_, RetrievalExperimentRecord>(
+            "SELECT id, status, baseline_json, candidate_json, rationale, created_at, ended_at\n             FROM retrieval_experiments WHERE status = 'running'\n             ORDER BY id DESC LIMIT 1",
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get retrieval experiment: {}", e))
+        })
+    }
+
+    /// Start an experiment, stopping any that is still running
+    pub async fn start_retrieval_experiment(
+        &self,
+        baseline_json: &str,
+        candidate_json: &str,
+        rationale: &str,
+    ) -> Result<RetrievalExperimentRecord> {
+        let now = chrono::Utc::now().timestamp();
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to start transaction: {}", e))
+            })?;
+
+        sqlx::query(
+            "UPDATE retrieval_experiments SET status = 'stopped', ended_at = ? WHERE status = 'running'",
+        )
+        .bind(now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to stop retrieval experiment: {}", e))
+        })?;
+
+        let record = sqlx::query_as::<_, RetrievalExperimentRecord>(
+            "INSERT INTO retrieval_experiments (status, baseline_json, candidate_json, rationale, created_at)\n             VALUES ('running', ?, ?, ?, ?)\n             RETURNING id, status, baseline_json, candidate_json, rationale, created_at, ended_at",
+        )
+        .bind(baseline_json)
+        .bind(candidate_json)
+        .bind(rationale)
+        .bind(now)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to start retrieval experiment: {}", e))
+        })?;
+
+        tx.commit().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to commit retrieval experiment: {}", e))
+        })?;
+        Ok(record)
+    }
+
+    /// Finish a running experiment with `status`; false when it was not running
+    pub async fn finish_retrieval_experiment(&self, id: i64, status: &str) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE retrieval_experiments SET status = ?, ended_at = ? WHERE id = ? AND status = 'running'",
+        )
+        .bind(status)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to finish retrieval experiment: {}", e))
+        })?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -102,7 +102,7 @@ impl RagRepository {
         }
 
         let mut sql = String::from(
-            "SELECT sr.id, sr.doc_id, sr.row_index, sr.category, sr.source, sr.title,\n                    sr.created_at_text, sr.created_at, sr.content, sr.data_json,\n                    d.file_name\n             FROM structured_rows sr\n             INNER JOIN documents d ON sr.doc_id = d.id\n             WHERE d.collection_id = ?",
+            "SELECT sr.id, sr.doc_id, sr.row_index, sr.category, sr.source, sr.title,\n                    sr.created_at_text, sr.created_at, sr.content, sr.data_json,\n                    d.file_name\n             FROM structured_rows sr\n             INNER JOIN documents d ON sr.doc_id = d.id\n             WHERE d.collection_id = ? AND d.deleted_at IS NULL",
         );
 
         if category.is_some() {
@@ -162,7 +162,7 @@ impl RagRepository {
         keyword: Option<&str>,
     ) -> Result<i64> {
         let mut sql = String::from(
-            "SELECT COUNT(*)\n             FROM structured_rows sr\n             INNER JOIN documents d ON sr.doc_id = d.id\n             WHERE d.collection_id = ?",
+            "SELECT COUNT(*)\n             FROM structured_rows sr\n             INNER JOIN documents d ON sr.doc_id = d.id\n             WHERE d.collection_id = ? AND d.deleted_at IS NULL",
         );
 
         if category.is_some() {
@@ -0,0 +1,204 @@
+//! Moving documents between collections. Chunks travel with their
+//! document; their text and embeddings are opened with the old
+//! collection's key and sealed with the new one's, and embeddings are
+//! stored at the new collection's precision.
+
+use crate::domain::embedding_quantization::{EmbeddingQuantization, StoredEmbedding};
+use crate::domain::error::{AppError, Result};
+
+use super::RagRepository;
+
+/// A document as seen when picking what to move
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct TransferDocumentRecord {
+    pub id: i64,
+    pub collection_id: Option<i64>,
+    pub file_name: String,
+    pub file_type: String,
+    pub language: String,
+    pub quality_score: Option<f64>,
+    pub meta_json: String,
+    /// Unix seconds
+    pub created_at: i64,
+    /// Imported by a connector or git repository, which keeps it in sync
+    pub synced: bool,
+}
+
+#[derive(sqlx::FromRow)]
+struct MovedChunk {
+    id: i64,
+    content: String,
+    embedding_api: Option<Vec<u8>>,
+}
+
+const TRANSFER_DOCUMENT_COLUMNS: &str = "d.id, d.collection_id, d.file_name, d.file_type,\n        COALESCE(d.language, 'auto') AS language, d.quality_score, d.meta_json,\n        CAST(strftime('%s', d.created_at) AS INTEGER) AS created_at,\n        (EXISTS (SELECT 1 FROM rag_connector_pages p WHERE p.doc_id = d.id)\n         OR EXISTS (SELECT 1 FROM rag_git_files g WHERE g.doc_id = d.id)) AS synced";
+
+impl RagRepository {
+    /// The collection's documents, leaving out the ones in the trash
+    pub async fn list_transfer_documents(
+        &self,
+        collection_id: i64,
+    ) -> Result<Vec<TransferDocumentRecord>> {
+        sqlx::query_as::<_, TransferDocumentRecord>(&format!(
+            "SELECT {} FROM documents d\n             WHERE d.collection_id = ? AND d.deleted_at IS NULL ORDER BY d.id",
+            TRANSFER_DOCUMENT_COLUMNS
+        ))
+        .bind(collection_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list documents: {}", e)))
+    }
+
+    /// Documents by ID; IDs that do not exist are left out
+    pub async fn get_transfer_documents(
+        &self,
+        doc_ids: &[i64],
+    ) -> Result<Vec<TransferDocumentRecord>> {
+        let ids_json = serde_json::to_string(doc_ids)
+            .map_err(|e| AppError::Internal(format!("Failed to encode document ids: {}", e)))?;
+        sqlx::query_as::<_, TransferDocumentRecord>(&format!(
+            "SELECT {} FROM documents d\n             WHERE d.id IN (SELECT value FROM json_each(?)) ORDER BY d.id",
+            TRANSFER_DOCUMENT_COLUMNS
+        ))
+        .bind(ids_json)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch documents: {}", e)))
+    }
+
+    /// Move documents and their chunks into `target_collection_id` in one
+    /// transaction, storing embeddings at `quantization`. Fails while the
+    /// target or a sealed source collection is locked. Returns the number
+    /// of chunks moved.
+    pub async fn move_documents(
+        &self,
+        doc_ids: &[i64],
+        target_collection_id: i64,
+        quantization: EmbeddingQuantization,
+    ) -> Result<usize> {
+        let keys = self.collection_keys().await?;
+        let cipher = keys.cipher(target_collection_id)?;
+        let ids_json = serde_json::to_string(doc_ids)
+            .map_err(|e| AppError::Internal(format!("Failed to encode document ids: {}", e)))?;
+
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to begin transaction: {}", e))
+            })?;
+        let chunks = sqlx::query_as::<_, MovedChunk>(
+            "SELECT id, content, embedding_api FROM document_chunks\n             WHERE doc_id IN (SELECT value FROM json_each(?))",
+        )
+        .bind(&ids_json)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch chunks: {}", e)))?;
+
+        let moved = chunks.len();
+        for chunk in chunks {
+            // Sealed chunks name their collection, so they always need the
+            // target's key even when neither text nor precision changes
+            let unchanged = (chunk.content.clone(), chunk.embedding_api.clone());
+            let content = keys.open_text(chunk.content)?;
+            let content = match &cipher {
+                Some(cipher) => cipher.encrypt_text(&content)?,
+                None => content,
+            };
+            let embedding = match chunk.embedding_api {
+                Some(stored) => {
+                    let bytes = keys.open_bytes(stored)?;
+                    let bytes = match StoredEmbedding::from_bytes(&bytes) {
+                        Some(embedding) => embedding.requantize(quantization).to_bytes(),
+                        None => bytes,
+                    };
+                    Some(match &cipher {
+                        Some(cipher) => cipher.encrypt_bytes(&bytes)?,
+                        None => bytes,
+                    })
+                }
+                None => None,
+            };
+            if (&content, &embedding) == (&unchanged.0, &unchanged.1) {
+                continue;
+            }
+            sqlx::query("UPDATE document_chunks SET content = ?, embedding_api = ? WHERE id = ?")
+                .bind(content)
+                .bind(embedding)
+                .bind(chunk.id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to move chunk: {}", e)))?;
+        }
+
+        if cipher.is_some() {
+            // Same as sealing a collection: previews keep only page counts
+            sqlx::query(
+                "UPDATE document_previews\n                 SET title = NULL, author = NULL, thumbnail = NULL, thumbnail_width = NULL, thumbnail_height = NULL\n                 WHERE doc_id IN (SELECT value FROM json_each(?))",
+            )
+            .bind(&ids_json)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to clear previews: {}", e)))?;
+        }
+
+        sqlx::query(
+            "UPDATE documents SET collection_id = ? WHERE id IN (SELECT value FROM json_each(?))",
+        )
+        .bind(target_collection_id)
+        .bind(&ids_json)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to move documents: {}", e)))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit transaction: {}", e)))?;
+        Ok(moved)
+    }
+
+    /// Hand a merged collection's connectors, git repositories, retrieval
+    /// history and conversations to the collection it was merged into.
+    /// Returns the number of connectors and repositories moved.
+    pub async fn reassign_collection_sources(
+        &self,
+        source_collection_id: i64,
+        target_collection_id: i64,
+    ) -> Result<usize> {
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to begin transaction: {}", e))
+            })?;
+        let mut sources = 0;
+        for table in ["rag_connectors", "rag_git_repos"] {
+            sources += sqlx::query(&format!(
+                "UPDATE {} SET collection_id = ? WHERE collection_id = ?",
+                table
+            ))
+            .bind(target_collection_id)
+            .bind(source_collection_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to move {}: {}", table, e)))?
+            .rows_affected() as usize;
+        }
+        for table in [
+            "retrieval_events",
+            "retrieval_gaps",
+            "retrieval_feedback",
+            "conversations",
+        ] {
+            sqlx::query(&format!(
+                "UPDATE {} SET collection_id = ? WHERE collection_id = ?",
+                table
+            ))
+            .bind(target_collection_id)
+            .bind(source_collection_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to move {}: {}", table, e)))?;
+        }
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit transaction: {}", e)))?;
+        Ok(sources)
+    }
+}
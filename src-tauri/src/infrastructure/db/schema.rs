@@ -0,0 +1,30 @@
+use sqlx::sqlite::SqlitePool;
+
+/// Add `column` to `table` unless it is already there. Used for additive
+/// upgrades of databases created by older versions.
+pub async fn ensure_column(
+    pool: &SqlitePool,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<(), String> {
+    let exists: Option<(i64,)> = sqlx::query_as(&format!(
+        "SELECT 1 FROM pragma_table_info('{}') WHERE name = ?",
+        table
+    ))
+    .bind(column)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("Failed to inspect {table} schema: {e}"))?;
+
+    if exists.is_none() {
+        sqlx::query(&format!(
+            "ALTER TABLE {} ADD COLUMN {} {}",
+            table, column, definition
+        ))
+        .execute(pool)
+        .await
+        .map_err(|e| format!("Failed to add {column} column to {table}: {e}"))?;
+    }
+    Ok(())
+}
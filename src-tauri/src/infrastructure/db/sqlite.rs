@@ -1,5 +1,8 @@
 use crate::domain::error::{AppError, Result};
+use crate::domain::glossary::GlossaryEntry;
 use crate::domain::prompt::Prompt;
+use crate::domain::translation_memory::{normalize, TranslationMemoryEntry};
+use crate::infrastructure::db::schema::ensure_column;
 use sqlx::{
     sqlite::{SqliteConnectOptions, SqlitePool},
     Pool, Sqlite,
@@ -36,18 +39,81 @@ impl SqliteRepository {
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to create table: {}", e)))?;
 
+        ensure_column(&pool, "prompts", "detected_lang", "TEXT")
+            .await
+            .map_err(AppError::DatabaseError)?;
+        ensure_column(
+            &pool,
+            "prompts",
+            "is_favorite",
+            "INTEGER NOT NULL DEFAULT 0",
+        )
+        .await
+        .map_err(AppError::DatabaseError)?;
+        create_history_search_schema(&pool).await?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS glossary_terms (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                source_term TEXT NOT NULL,
+                target_term TEXT,
+                source_lang TEXT,
+                target_lang TEXT,
+                do_not_translate INTEGER NOT NULL DEFAULT 0,
+                case_sensitive INTEGER NOT NULL DEFAULT 0,
+                notes TEXT,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            )",
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to create glossary table: {}", e)))?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS translation_memory (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                source_text TEXT NOT NULL,
+                source_normalized TEXT NOT NULL,
+                source_len INTEGER NOT NULL,
+                target_text TEXT NOT NULL,
+                source_lang TEXT NOT NULL,
+                target_lang TEXT NOT NULL,
+                approved INTEGER NOT NULL DEFAULT 0,
+                origin TEXT NOT NULL DEFAULT 'model',
+                use_count INTEGER NOT NULL DEFAULT 0,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            )",
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to create translation memory table: {}", e))
+        })?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_tm_lookup
+             ON translation_memory(target_lang, approved, source_len)",
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to create translation memory index: {}", e))
+        })?;
+
         Ok(Self { pool })
     }
 
     pub async fn save_prompt(&self, prompt: &mut Prompt) -> Result<()> {
         let result = sqlx::query(
-            "INSERT INTO prompts (content, source_lang, target_lang, result)
-             VALUES (?, ?, ?, ?)",
+            "INSERT INTO prompts (content, source_lang, target_lang, result, detected_lang)
+             VALUES (?, ?, ?, ?, ?)",
         )
         .bind(&prompt.content)
         .bind(&prompt.source_lang)
         .bind(&prompt.target_lang)
         .bind(&prompt.result)
+        .bind(&prompt.detected_lang)
         .execute(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to save prompt: {}", e)))?;
@@ -57,15 +123,456 @@ impl SqliteRepository {
     }
 
     pub async fn get_history(&self, limit: i64) -> Result<Vec<Prompt>> {
-        sqlx::query_as::<_, PromptEntity>(
-            "SELECT id, content, source_lang, target_lang, result, created_at FROM prompts ORDER BY created_at DESC LIMIT ?"
-        )
+        sqlx::query_as::<_, PromptEntity>(&format!(
+            "SELECT {} FROM prompts p ORDER BY p.created_at DESC LIMIT ?",
+            PROMPT_COLUMNS
+        ))
         .bind(limit)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to fetch history: {}", e)))
         .map(|entities| entities.into_iter().map(|e| e.into()).collect())
     }
+
+    pub async fn get_prompt(&self, prompt_id: i64) -> Result<Prompt> {
+        sqlx::query_as::<_, PromptEntity>(&format!(
+            "SELECT {} FROM prompts p WHERE p.id = ?",
+            PROMPT_COLUMNS
+        ))
+        .bind(prompt_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch prompt: {}", e)))?
+        .map(|entity| entity.into())
+        .ok_or_else(|| AppError::NotFound(format!("Prompt {} not found", prompt_id)))
+    }
+
+    /// Full-text search over prompt content and results, optionally narrowed
+    /// by tag and favorite flag. An empty query lists by recency.
+    pub async fn search_history(
+        &self,
+        query: Option<&str>,
+        tag: Option<&str>,
+        favorites_only: bool,
+        limit: i64,
+    ) -> Result<Vec<Prompt>> {
+        let fts_query = query.and_then(build_fts_query);
+        sqlx::query_as::<_, PromptEntity>(&format!(
+            "SELECT {} FROM prompts p
+             WHERE (? IS NULL OR p.id IN (SELECT rowid FROM prompts_fts WHERE prompts_fts MATCH ?))
+               AND (? IS NULL OR EXISTS (
+                    SELECT 1 FROM prompt_tags t WHERE t.prompt_id = p.id AND t.tag = ?))
+               AND (? = 0 OR p.is_favorite = 1)
+             ORDER BY p.created_at DESC
+             LIMIT ?",
+            PROMPT_COLUMNS
+        ))
+        .bind(&fts_query)
+        .bind(&fts_query)
+        .bind(tag)
+        .bind(tag)
+        .bind(favorites_only)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to search history: {}", e)))
+        .map(|entities| entities.into_iter().map(|e| e.into()).collect())
+    }
+
+    /// Replace the tags on a prompt.
+    pub async fn set_prompt_tags(&self, prompt_id: i64, tags: &[String]) -> Result<()> {
+        let mut tx =
+            self.pool.begin().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to begin transaction: {}", e))
+            })?;
+
+        sqlx::query("DELETE FROM prompt_tags WHERE prompt_id = ?")
+            .bind(prompt_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to clear prompt tags: {}", e)))?;
+
+        for tag in tags {
+            sqlx::query("INSERT OR IGNORE INTO prompt_tags (prompt_id, tag) VALUES (?, ?)")
+                .bind(prompt_id)
+                .bind(tag)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to tag prompt: {}", e)))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit prompt tags: {}", e)))
+    }
+
+    pub async fn set_prompt_favorite(&self, prompt_id: i64, favorite: bool) -> Result<()> {
+        let result = sqlx::query("UPDATE prompts SET is_favorite = ? WHERE id = ?")
+            .bind(favorite)
+            .bind(prompt_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to update favorite: {}", e)))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "Prompt {} not found",
+                prompt_id
+            )));
+        }
+        Ok(())
+    }
+
+    pub async fn list_prompt_tags(&self) -> Result<Vec<String>> {
+        sqlx::query_scalar("SELECT DISTINCT tag FROM prompt_tags ORDER BY tag ASC")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to list tags: {}", e)))
+    }
+
+    pub async fn list_glossary_terms(&self) -> Result<Vec<GlossaryEntry>> {
+        sqlx::query_as::<_, GlossaryEntity>(
+            "SELECT id, source_term, target_term, source_lang, target_lang, do_not_translate, case_sensitive, notes, created_at
+             FROM glossary_terms ORDER BY source_term COLLATE NOCASE ASC",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch glossary: {}", e)))
+        .map(|entities| entities.into_iter().map(|e| e.into()).collect())
+    }
+
+    pub async fn upsert_glossary_term(&self, entry: &mut GlossaryEntry) -> Result<()> {
+        if let Some(id) = entry.id {
+            let result = sqlx::query(
+                "UPDATE glossary_terms
+                 SET source_term = ?, target_term = ?, source_lang = ?, target_lang = ?,
+                     do_not_translate = ?, case_sensitive = ?, notes = ?
+                 WHERE id = ?",
+            )
+            .bind(&entry.source_term)
+            .bind(&entry.target_term)
+            .bind(&entry.source_lang)
+            .bind(&entry.target_lang)
+            .bind(entry.do_not_translate)
+            .bind(entry.case_sensitive)
+            .bind(&entry.notes)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to update glossary term: {}", e))
+            })?;
+
+            if result.rows_affected() == 0 {
+                return Err(AppError::NotFound(format!(
+                    "Glossary term {} not found",
+                    id
+                )));
+            }
+            return Ok(());
+        }
+
+        let result = sqlx::query(
+            "INSERT INTO glossary_terms (source_term, target_term, source_lang, target_lang, do_not_translate, case_sensitive, notes)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&entry.source_term)
+        .bind(&entry.target_term)
+        .bind(&entry.source_lang)
+        .bind(&entry.target_lang)
+        .bind(entry.do_not_translate)
+        .bind(entry.case_sensitive)
+        .bind(&entry.notes)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to save glossary term: {}", e)))?;
+
+        entry.id = Some(result.last_insert_rowid());
+        Ok(())
+    }
+
+    pub async fn delete_glossary_term(&self, id: i64) -> Result<()> {
+        let result = sqlx::query("DELETE FROM glossary_terms WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to delete glossary term: {}", e))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "Glossary term {} not found",
+                id
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl SqliteRepository {
+    /// Save an entry. An unapproved entry for a source that already has one
+    /// waiting for review replaces that suggestion instead of adding a row.
+    pub async fn insert_tm_entry(&self, entry: &mut TranslationMemoryEntry) -> Result<()> {
+        let normalized = normalize(&entry.source_text);
+        if !entry.approved {
+            let replaced: Option<i64> = sqlx::query_scalar(
+                "UPDATE translation_memory
+                 SET source_text = ?, target_text = ?, origin = ?, updated_at = CURRENT_TIMESTAMP
+                 WHERE id = (SELECT id FROM translation_memory
+                             WHERE approved = 0
+                               AND source_normalized = ?
+                               AND source_lang = ?
+                               AND target_lang = ?
+                             ORDER BY id
+                             LIMIT 1)
+                 RETURNING id",
+            )
+            .bind(&entry.source_text)
+            .bind(&entry.target_text)
+            .bind(&entry.origin)
+            .bind(&normalized)
+            .bind(&entry.source_lang)
+            .bind(&entry.target_lang)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to save translation memory entry: {}", e))
+            })?;
+            if let Some(id) = replaced {
+                entry.id = Some(id);
+                return Ok(());
+            }
+        }
+
+        let result = sqlx::query(
+            "INSERT INTO translation_memory
+             (source_text, source_normalized, source_len, target_text, source_lang, target_lang, approved, origin)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&entry.source_text)
+        .bind(&normalized)
+        .bind(normalized.chars().count() as i64)
+        .bind(&entry.target_text)
+        .bind(&entry.source_lang)
+        .bind(&entry.target_lang)
+        .bind(entry.approved)
+        .bind(&entry.origin)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to save translation memory entry: {}", e))
+        })?;
+
+        entry.id = Some(result.last_insert_rowid());
+        Ok(())
+    }
+
+    /// Approved entries for a language pair whose normalized source length is
+    /// within `[min_len, max_len]`. `source_lang = None` matches any source.
+    pub async fn find_tm_candidates(
+        &self,
+        source_lang: Option<&str>,
+        target_lang: &str,
+        min_len: i64,
+        max_len: i64,
+        limit: i64,
+    ) -> Result<Vec<TranslationMemoryEntry>> {
+        sqlx::query_as::<_, TranslationMemoryEntity>(
+            "SELECT id, source_text, target_text, source_lang, target_lang, approved, origin, use_count, created_at, updated_at
+             FROM translation_memory
+             WHERE approved = 1
+               AND target_lang = ? COLLATE NOCASE
+               AND (? IS NULL OR source_lang = ? COLLATE NOCASE)
+               AND source_len BETWEEN ? AND ?
+             ORDER BY use_count DESC, updated_at DESC
+             LIMIT ?",
+        )
+        .bind(target_lang)
+        .bind(source_lang)
+        .bind(source_lang)
+        .bind(min_len)
+        .bind(max_len)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to query translation memory: {}", e))
+        })
+        .map(|entities| entities.into_iter().map(|e| e.into()).collect())
+    }
+
+    pub async fn search_translation_memory(
+        &self,
+        query: &str,
+        target_lang: Option<&str>,
+        approved_only: bool,
+        limit: i64,
+    ) -> Result<Vec<TranslationMemoryEntry>> {
+        let pattern = format!("%{}%", query.trim());
+        sqlx::query_as::<_, TranslationMemoryEntity>(
+            "SELECT id, source_text, target_text, source_lang, target_lang, approved, origin, use_count, created_at, updated_at
+             FROM translation_memory
+             WHERE (source_text LIKE ? OR target_text LIKE ?)
+               AND (? IS NULL OR target_lang = ? COLLATE NOCASE)
+               AND (? = 0 OR approved = 1)
+             ORDER BY updated_at DESC
+             LIMIT ?",
+        )
+        .bind(&pattern)
+        .bind(&pattern)
+        .bind(target_lang)
+        .bind(target_lang)
+        .bind(approved_only)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to search translation memory: {}", e))
+        })
+        .map(|entities| entities.into_iter().map(|e| e.into()).collect())
+    }
+
+    pub async fn get_tm_entry(&self, id: i64) -> Result<TranslationMemoryEntry> {
+        sqlx::query_as::<_, TranslationMemoryEntity>(
+            "SELECT id, source_text, target_text, source_lang, target_lang, approved, origin, use_count, created_at, updated_at
+             FROM translation_memory
+             WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to fetch translation memory entry: {}", e))
+        })?
+        .map(|entity| entity.into())
+        .ok_or_else(|| AppError::NotFound(format!("Translation memory entry {} not found", id)))
+    }
+
+    /// Approve an entry, optionally replacing its target with a human edit.
+    pub async fn approve_tm_entry(&self, id: i64, target_text: Option<&str>) -> Result<()> {
+        let result = sqlx::query(
+            "UPDATE translation_memory
+             SET approved = 1,
+                 target_text = COALESCE(?, target_text),
+                 origin = CASE WHEN ? IS NULL THEN origin ELSE 'human' END,
+                 updated_at = CURRENT_TIMESTAMP
+             WHERE id = ?",
+        )
+        .bind(target_text)
+        .bind(target_text)
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            AppError::DatabaseError(format!("Failed to approve translation memory entry: {}", e))
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "Translation memory entry {} not found",
+                id
+            )));
+        }
+        Ok(())
+    }
+
+    pub async fn record_tm_use(&self, id: i64) -> Result<()> {
+        sqlx::query("UPDATE translation_memory SET use_count = use_count + 1 WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to update translation memory: {}", e))
+            })?;
+        Ok(())
+    }
+
+    pub async fn delete_tm_entry(&self, id: i64) -> Result<()> {
+        let result = sqlx::query("DELETE FROM translation_memory WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to delete translation memory entry: {}", e))
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "Translation memory entry {} not found",
+                id
+            )));
+        }
+        Ok(())
+    }
+}
+
+const PROMPT_COLUMNS: &str =
+    "p.id, p.content, p.source_lang, p.target_lang, p.result, p.detected_lang, p.is_favorite,
+    (SELECT GROUP_CONCAT(t.tag, ',') FROM prompt_tags t WHERE t.prompt_id = p.id) AS tags,
+    p.created_at";
+
+async fn create_history_search_schema(pool: &SqlitePool) -> Result<()> {
+    let statements = [
+        "CREATE TABLE IF NOT EXISTS prompt_tags (
+            prompt_id INTEGER NOT NULL,
+            tag TEXT NOT NULL,
+            PRIMARY KEY (prompt_id, tag)
+        )",
+        "CREATE INDEX IF NOT EXISTS idx_prompt_tags_tag ON prompt_tags(tag)",
+        "CREATE VIRTUAL TABLE IF NOT EXISTS prompts_fts USING fts5(content, result)",
+        "CREATE TRIGGER IF NOT EXISTS prompts_ai AFTER INSERT ON prompts BEGIN
+            INSERT INTO prompts_fts(rowid, content, result) VALUES (new.id, new.content, new.result);
+        END",
+        // `prompts_fts` stores its own copy of the text, so rows are removed
+        // by rowid; the FTS5 'delete' command only works on external-content
+        // tables. Older databases got triggers using it and are recreated.
+        "DROP TRIGGER IF EXISTS prompts_ad",
+        "DROP TRIGGER IF EXISTS prompts_au",
+        "CREATE TRIGGER prompts_ad AFTER DELETE ON prompts BEGIN
+            DELETE FROM prompts_fts WHERE rowid = old.id;
+            DELETE FROM prompt_tags WHERE prompt_id = old.id;
+        END",
+        "CREATE TRIGGER prompts_au AFTER UPDATE OF content, result ON prompts BEGIN
+            DELETE FROM prompts_fts WHERE rowid = old.id;
+            INSERT INTO prompts_fts(rowid, content, result) VALUES (new.id, new.content, new.result);
+        END",
+        // Index rows written before full-text search existed.
+        "INSERT INTO prompts_fts(rowid, content, result)
+         SELECT id, content, result FROM prompts
+         WHERE id NOT IN (SELECT rowid FROM prompts_fts)",
+    ];
+
+    // One connection, so each statement sees the schema the previous one left
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to begin transaction: {}", e)))?;
+    for statement in statements {
+        sqlx::query(statement)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to create history search schema: {}", e))
+            })?;
+    }
+    tx.commit().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to create history search schema: {}", e))
+    })
+}
+
+/// Turn free text into a safe FTS5 query: every token is quoted and
+/// prefix-matched so user input never hits FTS syntax errors.
+fn build_fts_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|token| format!("\"{}\"*", token.replace('"', "\"\"")))
+        .collect();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
 }
 
 // Internal entity for database mapping
@@ -76,6 +583,9 @@ struct PromptEntity {
     source_lang: String,
     target_lang: String,
     result: Option<String>,
+    detected_lang: Option<String>,
+    is_favorite: bool,
+    tags: Option<String>,
     created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -87,7 +597,193 @@ impl From<PromptEntity> for Prompt {
             source_lang: e.source_lang,
             target_lang: e.target_lang,
             result: e.result,
+            detected_lang: e.detected_lang,
+            tags: e
+                .tags
+                .map(|tags| tags.split(',').map(str::to_string).collect())
+                .unwrap_or_default(),
+            is_favorite: e.is_favorite,
+            created_at: Some(e.created_at),
+            glossary_violations: Vec::new(),
+            tm_match: None,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct GlossaryEntity {
+    id: i64,
+    source_term: String,
+    target_term: Option<String>,
+    source_lang: Option<String>,
+    target_lang: Option<String>,
+    do_not_translate: bool,
+    case_sensitive: bool,
+    notes: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<GlossaryEntity> for GlossaryEntry {
+    fn from(e: GlossaryEntity) -> Self {
+        Self {
+            id: Some(e.id),
+            source_term: e.source_term,
+            target_term: e.target_term,
+            source_lang: e.source_lang,
+            target_lang: e.target_lang,
+            do_not_translate: e.do_not_translate,
+            case_sensitive: e.case_sensitive,
+            notes: e.notes,
             created_at: Some(e.created_at),
         }
     }
 }
+
+#[derive(sqlx::FromRow)]
+struct TranslationMemoryEntity {
+    id: i64,
+    source_text: String,
+    target_text: String,
+    source_lang: String,
+    target_lang: String,
+    approved: bool,
+    origin: String,
+    use_count: i64,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<TranslationMemoryEntity> for TranslationMemoryEntry {
+    fn from(e: TranslationMemoryEntity) -> Self {
+        Self {
+            id: Some(e.id),
+            source_text: e.source_text,
+            target_text: e.target_text,
+            source_lang: e.source_lang,
+            target_lang: e.target_lang,
+            approved: e.approved,
+            origin: e.origin,
+            use_count: e.use_count,
+            created_at: Some(e.created_at),
+            updated_at: Some(e.updated_at),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt(content: &str, result: &str) -> Prompt {
+        Prompt {
+            id: None,
+            content: content.to_string(),
+            source_lang: "English".to_string(),
+            target_lang: "Indonesian".to_string(),
+            result: Some(result.to_string()),
+            detected_lang: None,
+            tags: Vec::new(),
+            is_favorite: false,
+            created_at: None,
+            glossary_violations: Vec::new(),
+            tm_match: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn prompts_can_be_updated_and_deleted_with_search_index() {
+        let dir = std::env::temp_dir().join(format!("prompts-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let url = format!("sqlite://{}", dir.join("prompts.db").display());
+        // Opening twice replaces the triggers of an existing database
+        SqliteRepository::init(&url).await.unwrap();
+        let repo = SqliteRepository::init(&url).await.unwrap();
+
+        let mut saved = prompt("good morning", "selamat pagi");
+        repo.save_prompt(&mut saved).await.unwrap();
+        let id = saved.id.unwrap();
+        repo.set_prompt_tags(id, &["greeting".to_string()])
+            .await
+            .unwrap();
+
+        sqlx::query("UPDATE prompts SET result = ? WHERE id = ?")
+            .bind("pagi")
+            .bind(id)
+            .execute(&repo.pool)
+            .await
+            .unwrap();
+        let found = repo
+            .search_history(Some("pagi"), None, false, 10)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        let stale = repo
+            .search_history(Some("selamat"), None, false, 10)
+            .await
+            .unwrap();
+        assert!(stale.is_empty());
+
+        sqlx::query("DELETE FROM prompts WHERE id = ?")
+            .bind(id)
+            .execute(&repo.pool)
+            .await
+            .unwrap();
+        let found = repo
+            .search_history(Some("pagi"), None, false, 10)
+            .await
+            .unwrap();
+        assert!(found.is_empty());
+        assert!(repo.list_prompt_tags().await.unwrap().is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+    fn model_entry(source: &str, target: &str) -> TranslationMemoryEntry {
+        TranslationMemoryEntry {
+            id: None,
+            source_text: source.to_string(),
+            target_text: target.to_string(),
+            source_lang: "English".to_string(),
+            target_lang: "Indonesian".to_string(),
+            approved: false,
+            origin: "model".to_string(),
+            use_count: 0,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn model_translations_replace_their_pending_entry() {
+        let dir = std::env::temp_dir().join(format!("tm-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let url = format!("sqlite://{}", dir.join("tm.db").display());
+        let repo = SqliteRepository::init(&url).await.unwrap();
+
+        let mut first = model_entry("Good morning", "Selamat pagi");
+        repo.insert_tm_entry(&mut first).await.unwrap();
+        let mut second = model_entry("good  morning", "Pagi");
+        repo.insert_tm_entry(&mut second).await.unwrap();
+        assert_eq!(first.id, second.id);
+
+        let entries = repo
+            .search_translation_memory("morning", None, false, 10)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].target_text, "Pagi");
+
+        let id = first.id.unwrap();
+        repo.approve_tm_entry(id, None).await.unwrap();
+        let mut third = model_entry("Good morning", "Selamat pagi");
+        repo.insert_tm_entry(&mut third).await.unwrap();
+        assert_ne!(third.id, first.id);
+
+        repo.delete_tm_entry(id).await.unwrap();
+        assert!(matches!(
+            repo.delete_tm_entry(id).await,
+            Err(AppError::NotFound(_))
+        ));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
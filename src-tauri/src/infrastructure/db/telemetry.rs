@@ -0,0 +1,248 @@
+use crate::domain::error::{AppError, Result};
+use crate::domain::telemetry::{
+    DailyUsage, FeatureUsage, SubsystemUsage, UsageSample, UsageSubsystem, UsageTotals,
+};
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions, SqliteSynchronous,
+};
+use std::path::Path;
+use std::time::Duration;
+
+/// Per-day usage sums in the main database; one row per day and feature
+pub struct TelemetryRepository {
+    pool: SqlitePool,
+}
+
+/// Summed stats of a range of days, before the dashboard is assembled
+pub struct UsageSummary {
+    pub totals: UsageTotals,
+    pub subsystems: Vec<SubsystemUsage>,
+    pub features: Vec<FeatureUsage>,
+    pub daily: Vec<DailyUsage>,
+}
+
+impl TelemetryRepository {
+    pub async fn init(db_path: &Path) -> Result<Self> {
+        let options = SqliteConnectOptions::new()
+            .filename(db_path)
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .synchronous(SqliteSynchronous::Normal)
+            .busy_timeout(Duration::from_secs(5));
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(2)
+            .acquire_timeout(Duration::from_secs(5))
+            .connect_with(options)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to connect usage stats DB: {e}"))
+            })?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS usage_stats (
+                day TEXT NOT NULL,
+                subsystem TEXT NOT NULL,
+                feature TEXT NOT NULL,
+                count INTEGER NOT NULL DEFAULT 0,
+                error_count INTEGER NOT NULL DEFAULT 0,
+                total_latency_ms INTEGER NOT NULL DEFAULT 0,
+                max_latency_ms INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (day, subsystem, feature)
+            )",
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to create usage stats table: {e}")))?;
+
+        Ok(Self { pool })
+    }
+
+    /// Adds one call to the stats row of its day and feature
+    pub async fn record(&self, sample: &UsageSample) -> Result<()> {
+        let latency_ms = sample.latency_ms.max(0);
+        sqlx::query(
+            "INSERT INTO usage_stats
+                (day, subsystem, feature, count, error_count, total_latency_ms, max_latency_ms)
+             VALUES (?, ?, ?, 1, ?, ?, ?)
+             ON CONFLICT(day, subsystem, feature) DO UPDATE SET
+                count = count + 1,
+                error_count = error_count + excluded.error_count,
+                total_latency_ms = total_latency_ms + excluded.total_latency_ms,
+                max_latency_ms = MAX(max_latency_ms, excluded.max_latency_ms)",
+        )
+        .bind(day_of(sample.ts))
+        .bind(sample.subsystem.as_str())
+        .bind(&sample.feature)
+        .bind(i64::from(!sample.success))
+        .bind(latency_ms)
+        .bind(latency_ms)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to record usage: {e}")))?;
+
+        Ok(())
+    }
+
+    /// Stats from `since_day` (inclusive, `YYYY-MM-DD`) on
+    pub async fn summarize(&self, since_day: &str) -> Result<UsageSummary> {
+        let feature_rows: Vec<(String, String, i64, i64, i64, i64)> = sqlx::query_as(
+            "SELECT subsystem, feature, SUM(count), SUM(error_count), SUM(total_latency_ms),
+                    MAX(max_latency_ms)
+             FROM usage_stats WHERE day >= ?
+             GROUP BY subsystem, feature
+             ORDER BY SUM(count) DESC, subsystem, feature",
+        )
+        .bind(since_day)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to read usage stats: {e}")))?;
+
+        let daily_rows: Vec<(String, i64, i64, i64)> = sqlx::query_as(
+            "SELECT day, SUM(count), SUM(error_count), SUM(total_latency_ms)
+             FROM usage_stats WHERE day >= ?
+             GROUP BY day ORDER BY day",
+        )
+        .bind(since_day)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to read daily usage: {e}")))?;
+
+        let mut subsystem_sums: Vec<(UsageSubsystem, i64, i64, i64)> = Vec::new();
+        let mut features = Vec::new();
+        for (subsystem, feature, count, errors, latency, max_latency) in feature_rows {
+            // Rows of a subsystem this build doesn't know are skipped
+            let Some(subsystem) = UsageSubsystem::parse(&subsystem) else {
+                continue;
+            };
+            match subsystem_sums.iter_mut().find(|sums| sums.0 == subsystem) {
+                Some(sums) => {
+                    sums.1 += count;
+                    sums.2 += errors;
+                    sums.3 += latency;
+                }
+                None => subsystem_sums.push((subsystem, count, errors, latency)),
+            }
+            features.push(FeatureUsage {
+                subsystem,
+                feature,
+                totals: UsageTotals::from_sums(count, errors, latency),
+                max_latency_ms: max_latency,
+            });
+        }
+        subsystem_sums.sort_by_key(|sums| std::cmp::Reverse(sums.1));
+
+        let (count, errors, latency) = subsystem_sums.iter().fold((0, 0, 0), |acc, sums| {
+            (acc.0 + sums.1, acc.1 + sums.2, acc.2 + sums.3)
+        });
+
+        Ok(UsageSummary {
+            totals: UsageTotals::from_sums(count, errors, latency),
+            subsystems: subsystem_sums
+                .into_iter()
+                .map(|(subsystem, count, errors, latency)| SubsystemUsage {
+                    subsystem,
+                    totals: UsageTotals::from_sums(count, errors, latency),
+                })
+                .collect(),
+            features,
+            daily: daily_rows
+                .into_iter()
+                .map(|(day, count, errors, latency)| DailyUsage {
+                    day,
+                    totals: UsageTotals::from_sums(count, errors, latency),
+                })
+                .collect(),
+        })
+    }
+
+    /// Drops the days before `before_day`. Returns the number of rows removed.
+    pub async fn prune(&self, before_day: &str) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM usage_stats WHERE day < ?")
+            .bind(before_day)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to prune usage stats: {e}")))?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn clear(&self) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM usage_stats")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to clear usage stats: {e}")))?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+/// The UTC day of a Unix millisecond timestamp, `YYYY-MM-DD`
+pub fn day_of(ts_ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(ts_ms)
+        .unwrap_or_default()
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+    fn sample(
+        day: i64,
+        subsystem: UsageSubsystem,
+        feature: &str,
+        latency_ms: i64,
+        success: bool,
+    ) -> UsageSample {
+        UsageSample {
+            ts: day * DAY_MS + 1_000,
+            subsystem,
+            feature: feature.to_string(),
+            latency_ms,
+            success,
+        }
+    }
+
+    #[tokio::test]
+    async fn calls_are_summed_per_day_and_feature() {
+        let dir = std::env::temp_dir().join(format!("usage-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let repo = TelemetryRepository::init(&dir.join("usage.db"))
+            .await
+            .unwrap();
+
+        for input in [
+            sample(0, UsageSubsystem::Llm, "enhance", 900, true),
+            sample(1, UsageSubsystem::Llm, "enhance", 100, true),
+            sample(1, UsageSubsystem::Llm, "enhance", 200, false),
+            sample(1, UsageSubsystem::Llm, "translate", 300, true),
+            sample(1, UsageSubsystem::Rag, "query", 50, true),
+        ] {
+            repo.record(&input).await.unwrap();
+        }
+
+        let summary = repo.summarize("1970-01-02").await.unwrap();
+        assert_eq!(summary.totals.count, 4);
+        assert_eq!(summary.totals.error_count, 1);
+        assert_eq!(summary.subsystems[0].subsystem, UsageSubsystem::Llm);
+        assert_eq!(summary.subsystems[0].totals.count, 3);
+
+        let enhance = &summary.features[0];
+        assert_eq!(enhance.feature, "enhance");
+        assert_eq!(enhance.totals.count, 2);
+        assert_eq!(enhance.totals.error_rate, 0.5);
+        assert_eq!(enhance.totals.avg_latency_ms, 150.0);
+        assert_eq!(enhance.max_latency_ms, 200);
+        assert_eq!(summary.daily.len(), 1);
+        assert_eq!(summary.daily[0].day, "1970-01-02");
+
+        assert_eq!(repo.prune("1970-01-02").await.unwrap(), 1);
+        assert_eq!(repo.clear().await.unwrap(), 3);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
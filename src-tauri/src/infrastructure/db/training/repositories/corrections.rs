@@ -2,8 +2,18 @@ use crate::domain::error::{AppError, Result};
 use serde::{Deserialize, Serialize};
 use sqlx::sqlite::SqlitePool;
 
+use super::review::{ReviewCounts, ReviewStatus, ReviewTable};
 use super::TrainingDb;
 
+const COLUMNS: &str = "correction_id, prompt, student_output, corrected_output, accuracy_rating, \
+     relevance_rating, safety_rating, domain_notes, review_status, review_note, reviewed_at, created_at";
+
+const REVIEW: ReviewTable = ReviewTable {
+    table: "corrections",
+    id_column: "correction_id",
+    label: "correction",
+};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Correction {
@@ -15,6 +25,10 @@ pub struct Correction {
     pub relevance_rating: Option<i64>,
     pub safety_rating: Option<i64>,
     pub domain_notes: Option<String>,
+    /// "pending", "approved" or "rejected"
+    pub review_status: String,
+    pub review_note: Option<String>,
+    pub reviewed_at: Option<String>,
     pub created_at: Option<String>,
 }
 
@@ -62,10 +76,9 @@ impl CorrectionRepository {
     }
 
     pub async fn get(&self, correction_id: &str) -> Result<Correction> {
-        let row = sqlx::query_as::<_, CorrectionEntity>(
-            "SELECT correction_id, prompt, student_output, corrected_output, accuracy_rating, relevance_rating, safety_rating, domain_notes, created_at \
-             FROM corrections WHERE correction_id = ?",
-        )
+        let row = sqlx::query_as::<_, CorrectionEntity>(&format!(
+            "SELECT {COLUMNS} FROM corrections WHERE correction_id = ?"
+        ))
         .bind(correction_id)
         .fetch_optional(&self.pool)
         .await
@@ -81,10 +94,9 @@ impl CorrectionRepository {
     }
 
     pub async fn list_recent(&self, limit: i64) -> Result<Vec<Correction>> {
-        let rows = sqlx::query_as::<_, CorrectionEntity>(
-            "SELECT correction_id, prompt, student_output, corrected_output, accuracy_rating, relevance_rating, safety_rating, domain_notes, created_at \
-             FROM corrections ORDER BY created_at DESC LIMIT ?",
-        )
+        let rows = sqlx::query_as::<_, CorrectionEntity>(&format!(
+            "SELECT {COLUMNS} FROM corrections ORDER BY created_at DESC LIMIT ?"
+        ))
         .bind(limit)
         .fetch_all(&self.pool)
         .await
@@ -101,6 +113,96 @@ impl CorrectionRepository {
             .map_err(|e| AppError::DatabaseError(format!("Failed to delete correction: {e}")))?;
         Ok(result.rows_affected())
     }
+
+    /// Review queue, oldest first, optionally narrowed to a status and a tag
+    pub async fn list_for_review(
+        &self,
+        status: Option<ReviewStatus>,
+        tag: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<Correction>> {
+        let rows = sqlx::query_as::<_, CorrectionEntity>(&format!(
+            "SELECT {COLUMNS} FROM corrections \
+             WHERE (? IS NULL OR review_status = ?) \
+             AND (? IS NULL OR correction_id IN ( \
+                 SELECT ct.correction_id FROM correction_tags ct \
+                 JOIN tags t ON t.tag_id = ct.tag_id WHERE t.name = ?)) \
+             ORDER BY created_at ASC LIMIT ?"
+        ))
+        .bind(status.map(|s| s.as_db()))
+        .bind(status.map(|s| s.as_db()))
+        .bind(tag)
+        .bind(tag)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list corrections: {e}")))?;
+
+        Ok(rows.into_iter().map(|e| e.into()).collect())
+    }
+
+    pub async fn set_review_status(
+        &self,
+        correction_ids: &[String],
+        status: ReviewStatus,
+        note: Option<&str>,
+    ) -> Result<u64> {
+        REVIEW
+            .set_status(&self.pool, correction_ids, status, note)
+            .await
+    }
+
+    /// Reviewer's inline edit of the training target
+    pub async fn update_corrected_output(
+        &self,
+        correction_id: &str,
+        corrected_output: &str,
+    ) -> Result<Correction> {
+        let result =
+            sqlx::query("UPDATE corrections SET corrected_output = ? WHERE correction_id = ?")
+                .bind(corrected_output)
+                .bind(correction_id)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| {
+                    AppError::DatabaseError(format!("Failed to update correction: {e}"))
+                })?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "Correction not found: {}",
+                correction_id
+            )));
+        }
+        self.get(correction_id).await
+    }
+
+    /// Approve the pending corrections carrying `tag`; rejected ones stay
+    /// rejected. Returns the ids approved.
+    pub async fn approve_pending_by_tag(&self, tag: &str) -> Result<Vec<String>> {
+        let ids: Vec<String> = sqlx::query_scalar(
+            "SELECT c.correction_id FROM corrections c \
+             JOIN correction_tags ct ON ct.correction_id = c.correction_id \
+             JOIN tags t ON t.tag_id = ct.tag_id \
+             WHERE t.name = ? AND c.review_status = 'pending'",
+        )
+        .bind(tag)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list tagged corrections: {e}")))?;
+
+        self.set_review_status(&ids, ReviewStatus::Approved, None)
+            .await?;
+        Ok(ids)
+    }
+
+    pub async fn review_counts(&self) -> Result<ReviewCounts> {
+        REVIEW.counts(&self.pool).await
+    }
+
+    /// Fail unless all of `correction_ids` are approved
+    pub async fn ensure_approved(&self, correction_ids: &[String]) -> Result<()> {
+        REVIEW.ensure_approved(&self.pool, correction_ids).await
+    }
 }
 
 #[derive(sqlx::FromRow)]
@@ -113,6 +215,9 @@ struct CorrectionEntity {
     relevance_rating: Option<i64>,
     safety_rating: Option<i64>,
     domain_notes: Option<String>,
+    review_status: String,
+    review_note: Option<String>,
+    reviewed_at: Option<String>,
     created_at: String,
 }
 
@@ -127,6 +232,9 @@ impl From<CorrectionEntity> for Correction {
             relevance_rating: entity.relevance_rating,
             safety_rating: entity.safety_rating,
             domain_notes: entity.domain_notes,
+            review_status: entity.review_status,
+            review_note: entity.review_note,
+            reviewed_at: entity.reviewed_at,
             created_at: Some(entity.created_at),
         }
     }
@@ -1,4 +1,5 @@
 use crate::domain::error::{AppError, Result};
+use crate::domain::trash::{TrashEntry, TrashKind};
 use serde::{Deserialize, Serialize};
 use sqlx::sqlite::SqlitePool;
 
@@ -88,7 +89,7 @@ impl DatasetRepository {
     pub async fn list_by_type(&self, dataset_type: &str) -> Result<Vec<Dataset>> {
         let rows = sqlx::query_as::<_, DatasetEntity>(
             "SELECT dataset_id, name, type, description, created_at FROM datasets \
-             WHERE type = ? ORDER BY created_at DESC",
+             WHERE type = ? AND deleted_at IS NULL ORDER BY created_at DESC",
         )
         .bind(dataset_type)
         .fetch_all(&self.pool)
@@ -100,7 +101,8 @@ impl DatasetRepository {
 
     pub async fn list_all(&self) -> Result<Vec<Dataset>> {
         let rows = sqlx::query_as::<_, DatasetEntity>(
-            "SELECT dataset_id, name, type, description, created_at FROM datasets ORDER BY created_at DESC",
+            "SELECT dataset_id, name, type, description, created_at FROM datasets \
+             WHERE deleted_at IS NULL ORDER BY created_at DESC",
         )
         .fetch_all(&self.pool)
         .await
@@ -119,6 +121,61 @@ impl DatasetRepository {
         Ok(result.rows_affected())
     }
 
+    /// Move a dataset to the trash; returns 0 when it does not exist or is
+    /// already there
+    pub async fn trash(&self, dataset_id: &str, deleted_at: i64) -> Result<u64> {
+        let result = sqlx::query(
+            "UPDATE datasets SET deleted_at = ? WHERE dataset_id = ? AND deleted_at IS NULL",
+        )
+        .bind(deleted_at)
+        .bind(dataset_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to trash dataset: {e}")))?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn restore(&self, dataset_id: &str) -> Result<u64> {
+        let result = sqlx::query(
+            "UPDATE datasets SET deleted_at = NULL WHERE dataset_id = ? AND deleted_at IS NOT NULL",
+        )
+        .bind(dataset_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to restore dataset: {e}")))?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Datasets in the trash, most recently deleted first
+    pub async fn list_trashed(&self) -> Result<Vec<TrashEntry>> {
+        let rows = sqlx::query_as::<_, (String, String, i64)>(
+            "SELECT dataset_id, name, deleted_at FROM datasets \
+             WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list trashed datasets: {e}")))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(id, name, deleted_at)| TrashEntry::new(TrashKind::Dataset, id, name, deleted_at))
+            .collect())
+    }
+
+    /// Delete datasets trashed before `deleted_before` (unix ms) for good
+    pub async fn purge_trashed(&self, deleted_before: i64) -> Result<u64> {
+        let result =
+            sqlx::query("DELETE FROM datasets WHERE deleted_at IS NOT NULL AND deleted_at < ?")
+                .bind(deleted_before)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to purge datasets: {e}")))?;
+
+        Ok(result.rows_affected())
+    }
+
     pub async fn insert_item(&self, item: &DatasetItemInput) -> Result<()> {
         sqlx::query(
             "INSERT INTO dataset_items (item_id, dataset_id, prompt, expected_output, metadata_json, source_correction_id) \
@@ -44,7 +44,7 @@ impl TrainingDb {
 
 async fn apply_training_migrations(pool: &SqlitePool) -> Result<()> {
     // Current schema version. We bump this when we add repair logic.
-    const CURRENT_SCHEMA_VERSION: i64 = 4;
+    const CURRENT_SCHEMA_VERSION: i64 = 8;
 
     // Check current schema version
     let version: i64 = sqlx::query_scalar("PRAGMA user_version")
@@ -96,6 +96,20 @@ async fn apply_training_migrations(pool: &SqlitePool) -> Result<()> {
         }
     }
 
+    // v5: review queue columns on corrections and soft labels
+    for table in ["corrections", "soft_labels"] {
+        ensure_review_columns(pool, table).await?;
+    }
+
+    // v6: 'crashed' run status
+    ensure_crashed_run_status(pool).await?;
+
+    // v7: resource samples recorded while a trainer runs
+    ensure_resource_samples_table(pool).await?;
+
+    // v8: datasets are moved to the trash before they are deleted
+    ensure_dataset_trash_column(pool).await?;
+
     // Bump schema version (even if we didn't change the SQL schema, this encodes "repair logic applied").
     if version < CURRENT_SCHEMA_VERSION {
         let pragma = format!("PRAGMA user_version = {}", CURRENT_SCHEMA_VERSION);
@@ -242,7 +256,174 @@ async fn try_repair_corrections_schema(pool: &SqlitePool) -> Result<bool> {
     Ok(true)
 }
 
+/// Add the review status columns to a table created before the review queue.
+///
+/// Rows that already exist are marked approved: they were eligible for
+/// datasets and runs before reviews existed, and runs may reference them.
+async fn ensure_review_columns(pool: &SqlitePool, table: &str) -> Result<()> {
+    use sqlx::Row;
+
+    let rows = sqlx::query(&format!("PRAGMA table_info({})", table))
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to read {} columns: {e}", table)))?;
+    let has_review_status = rows.iter().any(|row| {
+        let name: String = row.try_get("name").unwrap_or_default();
+        name.eq_ignore_ascii_case("review_status")
+    });
+    if has_review_status {
+        return Ok(());
+    }
+
+    tracing::info!("Adding review columns to {} table", table);
+    let statements = [
+        format!(
+            "ALTER TABLE {} ADD COLUMN review_status TEXT NOT NULL DEFAULT 'pending' \
+             CHECK(review_status IN ('pending', 'approved', 'rejected'))",
+            table
+        ),
+        format!("ALTER TABLE {} ADD COLUMN review_note TEXT", table),
+        format!("ALTER TABLE {} ADD COLUMN reviewed_at DATETIME", table),
+        format!("UPDATE {} SET review_status = 'approved'", table),
+        format!(
+            "CREATE INDEX IF NOT EXISTS idx_{}_review_status ON {}(review_status)",
+            table, table
+        ),
+    ];
+    let error = |e: sqlx::Error| {
+        AppError::DatabaseError(format!("Failed to add review columns to {}: {e}", table))
+    };
+    let mut tx = pool.begin().await.map_err(error)?;
+    for statement in &statements {
+        sqlx::query(statement)
+            .execute(&mut *tx)
+            .await
+            .map_err(error)?;
+    }
+    tx.commit().await.map_err(error)
+}
+
+/// Rebuild `training_runs` with the v6 status CHECK, which allows 'crashed'.
+///
+/// SQLite can't alter a CHECK constraint, so the table is copied into a new
+/// one. Foreign keys are off meanwhile so dropping the old table doesn't
+/// cascade into the run's corrections, logs and artifacts.
+async fn ensure_crashed_run_status(pool: &SqlitePool) -> Result<()> {
+    let sql: Option<String> = sqlx::query_scalar(
+        "SELECT sql FROM sqlite_master WHERE type='table' AND name='training_runs'",
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| AppError::DatabaseError(format!("Failed to read training_runs schema: {e}")))?;
+    if sql.is_none_or(|sql| sql.contains("'crashed'")) {
+        return Ok(());
+    }
+
+    tracing::info!("Rebuilding training_runs table to allow the crashed status");
+    let error = |e: sqlx::Error| {
+        AppError::DatabaseError(format!("Failed to migrate training_runs table: {e}"))
+    };
+    let mut conn = pool.acquire().await.map_err(error)?;
+    sqlx::query("PRAGMA foreign_keys = OFF")
+        .execute(&mut *conn)
+        .await
+        .map_err(error)?;
+
+    let statements = [
+        "CREATE TABLE training_runs_v6 (
+          run_id TEXT PRIMARY KEY,
+          student_model_id TEXT NOT NULL REFERENCES models(model_id),
+          base_version_id TEXT REFERENCES model_versions(version_id),
+          teacher_model_id TEXT REFERENCES models(model_id),
+          method TEXT NOT NULL CHECK(method IN ('fine_tune', 'knowledge_distillation', 'hybrid')),
+          status TEXT NOT NULL CHECK(status IN ('queued', 'running', 'completed', 'failed', 'cancelled', 'crashed', 'rolled_back')),
+          start_time DATETIME DEFAULT CURRENT_TIMESTAMP,
+          end_time DATETIME,
+          hyperparams_json TEXT NOT NULL,
+          seed INTEGER,
+          failure_reason TEXT
+        )",
+        "INSERT INTO training_runs_v6 (run_id, student_model_id, base_version_id, teacher_model_id, \
+         method, status, start_time, end_time, hyperparams_json, seed, failure_reason) \
+         SELECT run_id, student_model_id, base_version_id, teacher_model_id, method, status, \
+         start_time, end_time, hyperparams_json, seed, failure_reason FROM training_runs",
+        "DROP TABLE training_runs",
+        "ALTER TABLE training_runs_v6 RENAME TO training_runs",
+        "CREATE INDEX IF NOT EXISTS idx_training_runs_status_start ON training_runs(status, start_time)",
+    ];
+    let migrated = async {
+        let mut tx = sqlx::Connection::begin(&mut *conn).await?;
+        for statement in statements {
+            sqlx::query(statement).execute(&mut *tx).await?;
+        }
+        tx.commit().await
+    }
+    .await;
+
+    // Back on before the connection returns to the pool, even on failure
+    let restored = sqlx::query("PRAGMA foreign_keys = ON")
+        .execute(&mut *conn)
+        .await;
+    migrated.map_err(error)?;
+    restored.map_err(error)?;
+    Ok(())
+}
+
 /// Drop all tables in the training database
+/// Create `run_resource_samples`; the statements are idempotent
+async fn ensure_resource_samples_table(pool: &SqlitePool) -> Result<()> {
+    let statements = [
+        "CREATE TABLE IF NOT EXISTS run_resource_samples (
+          sample_id INTEGER PRIMARY KEY AUTOINCREMENT,
+          run_id TEXT NOT NULL REFERENCES training_runs(run_id) ON DELETE CASCADE,
+          cpu_percent REAL,
+          ram_used_bytes INTEGER,
+          ram_total_bytes INTEGER,
+          process_rss_bytes INTEGER,
+          gpu_util_percent REAL,
+          gpu_mem_used_bytes INTEGER,
+          gpu_mem_total_bytes INTEGER,
+          gpus_json TEXT,
+          warning TEXT,
+          sampled_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        )",
+        "CREATE INDEX IF NOT EXISTS idx_resource_samples_run_time \
+         ON run_resource_samples(run_id, sampled_at)",
+    ];
+    for statement in statements {
+        sqlx::query(statement).execute(pool).await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to create resource samples table: {e}"))
+        })?;
+    }
+    Ok(())
+}
+
+/// Add `datasets.deleted_at`, set while a dataset is in the trash
+async fn ensure_dataset_trash_column(pool: &SqlitePool) -> Result<()> {
+    use sqlx::Row;
+
+    let error =
+        |e: sqlx::Error| AppError::DatabaseError(format!("Failed to add datasets.deleted_at: {e}"));
+    let rows = sqlx::query("PRAGMA table_info(datasets)")
+        .fetch_all(pool)
+        .await
+        .map_err(error)?;
+    let has_deleted_at = rows.iter().any(|row| {
+        let name: String = row.try_get("name").unwrap_or_default();
+        name.eq_ignore_ascii_case("deleted_at")
+    });
+    if has_deleted_at {
+        return Ok(());
+    }
+
+    tracing::info!("Adding deleted_at column to datasets table");
+    sqlx::query("ALTER TABLE datasets ADD COLUMN deleted_at INTEGER")
+        .execute(pool)
+        .await
+        .map_err(error)?;
+    Ok(())
+}
+
 async fn drop_all_tables(pool: &SqlitePool) -> Result<()> {
     let tables = [
         "run_soft_labels",
@@ -251,6 +432,7 @@ async fn drop_all_tables(pool: &SqlitePool) -> Result<()> {
         "soft_labels",
         "run_artifacts",
         "evaluation_metrics",
+        "run_resource_samples",
         "training_logs",
         "model_actives",
         "model_versions",
@@ -5,6 +5,8 @@ mod db;
 mod evaluation_metrics;
 mod model_versions;
 mod models;
+mod resource_samples;
+mod review;
 mod run_artifacts;
 mod run_corrections;
 mod run_datasets;
@@ -20,6 +22,10 @@ pub use db::TrainingDb;
 pub use evaluation_metrics::{EvaluationMetric, EvaluationMetricInput, EvaluationMetricsRepository};
 pub use model_versions::{ModelVersion, ModelVersionInput, ModelVersionRepository};
 pub use models::{Model, ModelInput, ModelRepository};
+pub use resource_samples::{
+    GpuResourceSample, ResourceSample, ResourceSampleInput, ResourceSampleRepository,
+};
+pub use review::{ReviewCounts, ReviewStatus};
 pub use run_artifacts::{RunArtifact, RunArtifactInput, RunArtifactsRepository};
 pub use run_corrections::RunCorrectionsRepository;
 pub use run_datasets::RunDatasetsRepository;
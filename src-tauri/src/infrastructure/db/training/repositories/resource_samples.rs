@@ -0,0 +1,165 @@
+use crate::domain::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+
+use super::TrainingDb;
+
+/// One GPU in a resource sample
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GpuResourceSample {
+    pub index: i64,
+    pub name: String,
+    pub util_percent: Option<f64>,
+    pub mem_used_bytes: Option<i64>,
+    pub mem_total_bytes: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceSampleInput {
+    pub run_id: String,
+    /// Whole-host CPU usage
+    pub cpu_percent: Option<f64>,
+    pub ram_used_bytes: Option<i64>,
+    pub ram_total_bytes: Option<i64>,
+    /// Resident memory of the trainer process
+    pub process_rss_bytes: Option<i64>,
+    pub gpus: Vec<GpuResourceSample>,
+    /// Set when the sample found a resource exhausted
+    pub warning: Option<String>,
+}
+
+impl ResourceSampleInput {
+    /// Busiest GPU's utilization
+    pub fn gpu_util_percent(&self) -> Option<f64> {
+        self.gpus
+            .iter()
+            .filter_map(|gpu| gpu.util_percent)
+            .reduce(f64::max)
+    }
+
+    /// GPU memory summed over all devices, as (used, total)
+    pub fn gpu_mem_bytes(&self) -> (Option<i64>, Option<i64>) {
+        if self.gpus.is_empty() {
+            return (None, None);
+        }
+        (
+            self.gpus.iter().map(|gpu| gpu.mem_used_bytes).sum(),
+            self.gpus.iter().map(|gpu| gpu.mem_total_bytes).sum(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceSample {
+    pub sample_id: i64,
+    pub run_id: String,
+    pub cpu_percent: Option<f64>,
+    pub ram_used_bytes: Option<i64>,
+    pub ram_total_bytes: Option<i64>,
+    pub process_rss_bytes: Option<i64>,
+    pub gpu_util_percent: Option<f64>,
+    pub gpu_mem_used_bytes: Option<i64>,
+    pub gpu_mem_total_bytes: Option<i64>,
+    pub gpus: Vec<GpuResourceSample>,
+    pub warning: Option<String>,
+    pub sampled_at: String,
+}
+
+pub struct ResourceSampleRepository {
+    pool: SqlitePool,
+}
+
+impl ResourceSampleRepository {
+    pub fn new(db: &TrainingDb) -> Self {
+        Self {
+            pool: db.pool().clone(),
+        }
+    }
+
+    pub async fn insert(&self, sample: &ResourceSampleInput) -> Result<()> {
+        let (gpu_mem_used, gpu_mem_total) = sample.gpu_mem_bytes();
+        let gpus_json = if sample.gpus.is_empty() {
+            None
+        } else {
+            serde_json::to_string(&sample.gpus).ok()
+        };
+        sqlx::query(
+            "INSERT INTO run_resource_samples (run_id, cpu_percent, ram_used_bytes, ram_total_bytes, \
+             process_rss_bytes, gpu_util_percent, gpu_mem_used_bytes, gpu_mem_total_bytes, gpus_json, warning) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&sample.run_id)
+        .bind(sample.cpu_percent)
+        .bind(sample.ram_used_bytes)
+        .bind(sample.ram_total_bytes)
+        .bind(sample.process_rss_bytes)
+        .bind(sample.gpu_util_percent())
+        .bind(gpu_mem_used)
+        .bind(gpu_mem_total)
+        .bind(gpus_json)
+        .bind(&sample.warning)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to insert resource sample: {e}")))?;
+
+        Ok(())
+    }
+
+    /// Newest `limit` samples of a run, oldest first
+    pub async fn list_for_run(&self, run_id: &str, limit: i64) -> Result<Vec<ResourceSample>> {
+        let mut rows = sqlx::query_as::<_, ResourceSampleEntity>(
+            "SELECT sample_id, run_id, cpu_percent, ram_used_bytes, ram_total_bytes, process_rss_bytes, \
+             gpu_util_percent, gpu_mem_used_bytes, gpu_mem_total_bytes, gpus_json, warning, sampled_at \
+             FROM run_resource_samples WHERE run_id = ? ORDER BY sample_id DESC LIMIT ?",
+        )
+        .bind(run_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list resource samples: {e}")))?;
+
+        rows.reverse();
+        Ok(rows.into_iter().map(|e| e.into()).collect())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ResourceSampleEntity {
+    sample_id: i64,
+    run_id: String,
+    cpu_percent: Option<f64>,
+    ram_used_bytes: Option<i64>,
+    ram_total_bytes: Option<i64>,
+    process_rss_bytes: Option<i64>,
+    gpu_util_percent: Option<f64>,
+    gpu_mem_used_bytes: Option<i64>,
+    gpu_mem_total_bytes: Option<i64>,
+    gpus_json: Option<String>,
+    warning: Option<String>,
+    sampled_at: String,
+}
+
+impl From<ResourceSampleEntity> for ResourceSample {
+    fn from(entity: ResourceSampleEntity) -> Self {
+        Self {
+            sample_id: entity.sample_id,
+            run_id: entity.run_id,
+            cpu_percent: entity.cpu_percent,
+            ram_used_bytes: entity.ram_used_bytes,
+            ram_total_bytes: entity.ram_total_bytes,
+            process_rss_bytes: entity.process_rss_bytes,
+            gpu_util_percent: entity.gpu_util_percent,
+            gpu_mem_used_bytes: entity.gpu_mem_used_bytes,
+            gpu_mem_total_bytes: entity.gpu_mem_total_bytes,
+            gpus: entity
+                .gpus_json
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
+            warning: entity.warning,
+            sampled_at: entity.sampled_at,
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Review queue shared by corrections and soft labels. Both tables carry a
+//! `review_status`; only approved rows may go into datasets and runs.
+
+use crate::domain::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl ReviewStatus {
+    pub(super) fn as_db(&self) -> &'static str {
+        match self {
+            ReviewStatus::Pending => "pending",
+            ReviewStatus::Approved => "approved",
+            ReviewStatus::Rejected => "rejected",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewCounts {
+    pub pending: i64,
+    pub approved: i64,
+    pub rejected: i64,
+}
+
+/// The review columns differ only in the table and its id column
+#[derive(Clone, Copy)]
+pub(super) struct ReviewTable {
+    pub table: &'static str,
+    pub id_column: &'static str,
+    /// Singular, for error messages
+    pub label: &'static str,
+}
+
+fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
+impl ReviewTable {
+    /// Set the status of `ids`; returns the number of rows changed
+    pub async fn set_status(
+        &self,
+        pool: &SqlitePool,
+        ids: &[String],
+        status: ReviewStatus,
+        note: Option<&str>,
+    ) -> Result<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        let sql = format!(
+            "UPDATE {} SET review_status = ?, review_note = ?, \
+             reviewed_at = CASE WHEN ? = 'pending' THEN NULL ELSE CURRENT_TIMESTAMP END \
+             WHERE {} IN ({})",
+            self.table,
+            self.id_column,
+            placeholders(ids.len())
+        );
+        let mut query = sqlx::query(&sql)
+            .bind(status.as_db())
+            .bind(note)
+            .bind(status.as_db());
+        for id in ids {
+            query = query.bind(id);
+        }
+        let result = query.execute(pool).await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to review {}s: {e}", self.label))
+        })?;
+        Ok(result.rows_affected())
+    }
+
+    /// Those of `ids` that are not approved, including ids that don't exist
+    pub async fn unapproved(&self, pool: &SqlitePool, ids: &[String]) -> Result<Vec<String>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let sql = format!(
+            "SELECT {} FROM {} WHERE review_status = 'approved' AND {} IN ({})",
+            self.id_column,
+            self.table,
+            self.id_column,
+            placeholders(ids.len())
+        );
+        let mut query = sqlx::query_scalar::<_, String>(&sql);
+        for id in ids {
+            query = query.bind(id);
+        }
+        let approved: HashSet<String> = query
+            .fetch_all(pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to check {} reviews: {e}", self.label))
+            })?
+            .into_iter()
+            .collect();
+        Ok(ids
+            .iter()
+            .filter(|id| !approved.contains(*id))
+            .cloned()
+            .collect())
+    }
+
+    /// Fail unless every one of `ids` is approved
+    pub async fn ensure_approved(&self, pool: &SqlitePool, ids: &[String]) -> Result<()> {
+        let unapproved = self.unapproved(pool, ids).await?;
+        if unapproved.is_empty() {
+            return Ok(());
+        }
+        Err(AppError::ValidationError(format!(
+            "Only approved {}s can be used for training; not approved: {}",
+            self.label,
+            unapproved.join(", ")
+        )))
+    }
+
+    pub async fn counts(&self, pool: &SqlitePool) -> Result<ReviewCounts> {
+        let sql = format!(
+            "SELECT review_status, COUNT(*) FROM {} GROUP BY review_status",
+            self.table
+        );
+        let rows = sqlx::query_as::<_, (String, i64)>(&sql)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| {
+                AppError::DatabaseError(format!("Failed to count {} reviews: {e}", self.label))
+            })?;
+        let mut counts = ReviewCounts::default();
+        for (status, count) in rows {
+            match status.as_str() {
+                "pending" => counts.pending = count,
+                "approved" => counts.approved = count,
+                "rejected" => counts.rejected = count,
+                _ => {}
+            }
+        }
+        Ok(counts)
+    }
+}
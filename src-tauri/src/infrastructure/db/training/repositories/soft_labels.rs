@@ -3,8 +3,15 @@ use base64::Engine;
 use serde::{Deserialize, Serialize};
 use sqlx::sqlite::SqlitePool;
 
+use super::review::{ReviewCounts, ReviewStatus, ReviewTable};
 use super::TrainingDb;
 
+const REVIEW: ReviewTable = ReviewTable {
+    table: "soft_labels",
+    id_column: "soft_label_id",
+    label: "soft label",
+};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SoftLabel {
@@ -16,6 +23,9 @@ pub struct SoftLabel {
     pub soft_label_type: String, // "logits", "one_hot", "text_only"
     pub temperature: f64,
     pub metadata_json: Option<String>,
+    pub review_status: String, // "pending", "approved", "rejected"
+    pub review_note: Option<String>,
+    pub reviewed_at: Option<String>,
     pub created_at: Option<String>,
     // Note: soft_labels_blob is handled separately for binary data
 }
@@ -99,7 +109,8 @@ impl SoftLabelRepository {
     pub async fn get(&self, soft_label_id: &str) -> Result<SoftLabel> {
         let row = sqlx::query_as::<_, SoftLabelEntity>(
             "SELECT soft_label_id, prompt, prompt_hash, teacher_model_id, teacher_output, \
-             soft_label_type, temperature, metadata_json, created_at \
+             soft_label_type, temperature, metadata_json, review_status, review_note, reviewed_at, \
+             created_at \
              FROM soft_labels WHERE soft_label_id = ?",
         )
         .bind(soft_label_id)
@@ -119,7 +130,8 @@ impl SoftLabelRepository {
     ) -> Result<Option<SoftLabel>> {
         let row = sqlx::query_as::<_, SoftLabelEntity>(
             "SELECT soft_label_id, prompt, prompt_hash, teacher_model_id, teacher_output, \
-             soft_label_type, temperature, metadata_json, created_at \
+             soft_label_type, temperature, metadata_json, review_status, review_note, reviewed_at, \
+             created_at \
              FROM soft_labels WHERE prompt_hash = ? AND teacher_model_id = ?",
         )
         .bind(prompt_hash)
@@ -135,7 +147,8 @@ impl SoftLabelRepository {
     pub async fn list_for_run(&self, run_id: &str) -> Result<Vec<SoftLabel>> {
         let rows = sqlx::query_as::<_, SoftLabelEntity>(
             "SELECT sl.soft_label_id, sl.prompt, sl.prompt_hash, sl.teacher_model_id, sl.teacher_output, \
-             sl.soft_label_type, sl.temperature, sl.metadata_json, sl.created_at \
+             sl.soft_label_type, sl.temperature, sl.metadata_json, sl.review_status, sl.review_note, \
+             sl.reviewed_at, sl.created_at \
              FROM soft_labels sl \
              JOIN run_soft_labels rsl ON sl.soft_label_id = rsl.soft_label_id \
              WHERE rsl.run_id = ? \
@@ -205,6 +218,108 @@ impl SoftLabelRepository {
         Ok(())
     }
 
+    /// Review queue, oldest first, optionally narrowed to a status and to
+    /// soft labels of corrections carrying `tag`
+    pub async fn list_for_review(
+        &self,
+        status: Option<ReviewStatus>,
+        tag: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<SoftLabel>> {
+        let rows = sqlx::query_as::<_, SoftLabelEntity>(
+            "SELECT soft_label_id, prompt, prompt_hash, teacher_model_id, teacher_output, \
+             soft_label_type, temperature, metadata_json, review_status, review_note, reviewed_at, \
+             created_at \
+             FROM soft_labels \
+             WHERE (? IS NULL OR review_status = ?) \
+             AND (? IS NULL OR soft_label_id IN ( \
+                 SELECT csl.soft_label_id FROM correction_soft_labels csl \
+                 JOIN correction_tags ct ON ct.correction_id = csl.correction_id \
+                 JOIN tags t ON t.tag_id = ct.tag_id WHERE t.name = ?)) \
+             ORDER BY created_at ASC LIMIT ?",
+        )
+        .bind(status.map(|s| s.as_db()))
+        .bind(status.map(|s| s.as_db()))
+        .bind(tag)
+        .bind(tag)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list soft labels: {e}")))?;
+
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+
+    pub async fn set_review_status(
+        &self,
+        soft_label_ids: &[String],
+        status: ReviewStatus,
+        note: Option<&str>,
+    ) -> Result<u64> {
+        REVIEW
+            .set_status(&self.pool, soft_label_ids, status, note)
+            .await
+    }
+
+    /// Reviewer's inline edit of the teacher output. Logits were generated
+    /// for the old text, so an edited label falls back to text only.
+    pub async fn update_teacher_output(
+        &self,
+        soft_label_id: &str,
+        teacher_output: &str,
+    ) -> Result<SoftLabel> {
+        let result = sqlx::query(
+            "UPDATE soft_labels SET \
+             soft_label_type = CASE WHEN teacher_output = ? THEN soft_label_type ELSE 'text_only' END, \
+             soft_labels_blob = CASE WHEN teacher_output = ? THEN soft_labels_blob ELSE NULL END, \
+             teacher_output = ? \
+             WHERE soft_label_id = ?",
+        )
+        .bind(teacher_output)
+        .bind(teacher_output)
+        .bind(teacher_output)
+        .bind(soft_label_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update soft label: {e}")))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "Soft label not found: {soft_label_id}"
+            )));
+        }
+        self.get(soft_label_id).await
+    }
+
+    /// Approve the pending soft labels linked to corrections carrying `tag`;
+    /// rejected ones stay rejected. Returns the ids approved.
+    pub async fn approve_pending_by_tag(&self, tag: &str) -> Result<Vec<String>> {
+        let ids: Vec<String> = sqlx::query_scalar(
+            "SELECT DISTINCT sl.soft_label_id FROM soft_labels sl \
+             JOIN correction_soft_labels csl ON csl.soft_label_id = sl.soft_label_id \
+             JOIN correction_tags ct ON ct.correction_id = csl.correction_id \
+             JOIN tags t ON t.tag_id = ct.tag_id \
+             WHERE t.name = ? AND sl.review_status = 'pending'",
+        )
+        .bind(tag)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list tagged soft labels: {e}")))?;
+
+        self.set_review_status(&ids, ReviewStatus::Approved, None)
+            .await?;
+        Ok(ids)
+    }
+
+    pub async fn review_counts(&self) -> Result<ReviewCounts> {
+        REVIEW.counts(&self.pool).await
+    }
+
+    /// Fail unless all of `soft_label_ids` are approved
+    pub async fn ensure_approved(&self, soft_label_ids: &[String]) -> Result<()> {
+        REVIEW.ensure_approved(&self.pool, soft_label_ids).await
+    }
+
     /// Export soft labels to a JSONL file for training
     pub async fn export_for_training(
         &self,
@@ -257,6 +372,9 @@ pub struct SoftLabelEntity {
     soft_label_type: String,
     temperature: f64,
     metadata_json: Option<String>,
+    review_status: String,
+    review_note: Option<String>,
+    reviewed_at: Option<String>,
     created_at: String,
 }
 
@@ -271,6 +389,9 @@ impl From<SoftLabelEntity> for SoftLabel {
             soft_label_type: entity.soft_label_type,
             temperature: entity.temperature,
             metadata_json: entity.metadata_json,
+            review_status: entity.review_status,
+            review_note: entity.review_note,
+            reviewed_at: entity.reviewed_at,
             created_at: Some(entity.created_at),
         }
     }
@@ -30,6 +30,9 @@ pub enum TrainingStatus {
     Completed,
     Failed,
     Cancelled,
+    /// The trainer died without reporting an error (killed, out of memory,
+    /// app closed mid-run); the run can be resumed from a checkpoint
+    Crashed,
     RolledBack,
 }
 
@@ -41,6 +44,7 @@ impl TrainingStatus {
             TrainingStatus::Completed => "completed",
             TrainingStatus::Failed => "failed",
             TrainingStatus::Cancelled => "cancelled",
+            TrainingStatus::Crashed => "crashed",
             TrainingStatus::RolledBack => "rolled_back",
         }
     }
@@ -124,6 +128,19 @@ impl TrainingRunRepository {
         Ok(())
     }
 
+    /// Back to running for a resumed trainer, clearing the previous outcome
+    pub async fn mark_resumed(&self, run_id: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE training_runs SET status = 'running', end_time = NULL, failure_reason = NULL WHERE run_id = ?",
+        )
+        .bind(run_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update training run: {e}")))?;
+
+        Ok(())
+    }
+
     pub async fn get(&self, run_id: &str) -> Result<TrainingRun> {
         let run = sqlx::query_as::<_, TrainingRunEntity>(
             "SELECT run_id, student_model_id, base_version_id, teacher_model_id, method, status, start_time, end_time, hyperparams_json, seed, failure_reason \
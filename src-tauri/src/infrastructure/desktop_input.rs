@@ -0,0 +1,295 @@
+// ============================================================
+// DESKTOP INPUT CAPABILITIES
+// ============================================================
+// What the global shortcuts can do to the focused app: send the
+// copy/paste chords and read the current selection. Windows and
+// macOS always go through enigo. On Linux, enigo only reaches X11,
+// so Wayland sessions rely on external tools (wtype, ydotool,
+// wl-clipboard) and fall back to clipboard-only mode without them:
+// the user copies before pressing a shortcut and pastes the result.
+
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+/// External tools that do not finish within this are treated as failed
+const TOOL_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DisplayServer {
+    Windows,
+    Macos,
+    X11,
+    Wayland,
+    Unknown,
+}
+
+/// How keystrokes reach the focused app
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeySender {
+    /// enigo (SendInput, CGEvent or libxdo on X11)
+    Native,
+    /// `wtype`, wlroots-based compositors
+    Wtype,
+    /// `ydotool` through the uinput daemon, any compositor
+    Ydotool,
+}
+
+/// Reads the primary selection without touching the clipboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SelectionReader {
+    WlPaste,
+    Xclip,
+    Xsel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputCapabilities {
+    pub display_server: DisplayServer,
+    pub key_sender: Option<KeySender>,
+    pub selection_reader: Option<SelectionReader>,
+}
+
+impl InputCapabilities {
+    /// Copy and paste can't be simulated, only the clipboard is available
+    pub fn clipboard_only(&self) -> bool {
+        self.key_sender.is_none()
+    }
+
+    pub fn summary(&self) -> String {
+        let session = match self.display_server {
+            DisplayServer::Windows => "Windows",
+            DisplayServer::Macos => "macOS",
+            DisplayServer::X11 => "X11",
+            DisplayServer::Wayland => "Wayland",
+            DisplayServer::Unknown => "Unknown session",
+        };
+        match self.key_sender {
+            Some(KeySender::Native) => format!("{}; native copy and paste", session),
+            Some(KeySender::Wtype) => format!("{}; copy and paste via wtype", session),
+            Some(KeySender::Ydotool) => format!("{}; copy and paste via ydotool", session),
+            None if self.selection_reader.is_some() => {
+                format!("{}; selection only, paste manually", session)
+            }
+            None => format!("{}; clipboard only", session),
+        }
+    }
+}
+
+/// Capabilities detected on first use. Installing a tool takes effect after a restart.
+pub fn capabilities() -> &'static InputCapabilities {
+    static CAPABILITIES: OnceLock<InputCapabilities> = OnceLock::new();
+    CAPABILITIES.get_or_init(detect)
+}
+
+pub fn detect() -> InputCapabilities {
+    if cfg!(target_os = "windows") {
+        return native(DisplayServer::Windows);
+    }
+    if cfg!(target_os = "macos") {
+        return native(DisplayServer::Macos);
+    }
+    detect_unix(
+        |name| std::env::var(name).ok().filter(|value| !value.is_empty()),
+        program_on_path,
+    )
+}
+
+fn native(display_server: DisplayServer) -> InputCapabilities {
+    InputCapabilities {
+        display_server,
+        key_sender: Some(KeySender::Native),
+        selection_reader: None,
+    }
+}
+
+fn detect_unix(
+    env: impl Fn(&str) -> Option<String>,
+    has_program: impl Fn(&str) -> bool,
+) -> InputCapabilities {
+    let session_type = env("XDG_SESSION_TYPE").map(|value| value.to_ascii_lowercase());
+    let display_server = match session_type.as_deref() {
+        Some("wayland") => DisplayServer::Wayland,
+        Some("x11") => DisplayServer::X11,
+        _ if env("WAYLAND_DISPLAY").is_some() => DisplayServer::Wayland,
+        _ if env("DISPLAY").is_some() => DisplayServer::X11,
+        _ => DisplayServer::Unknown,
+    };
+
+    let (key_sender, selection_reader) = match display_server {
+        DisplayServer::Wayland => (
+            if has_program("wtype") {
+                Some(KeySender::Wtype)
+            } else if has_program("ydotool") {
+                Some(KeySender::Ydotool)
+            } else {
+                None
+            },
+            has_program("wl-paste").then_some(SelectionReader::WlPaste),
+        ),
+        DisplayServer::X11 => (
+            Some(KeySender::Native),
+            if has_program("xclip") {
+                Some(SelectionReader::Xclip)
+            } else if has_program("xsel") {
+                Some(SelectionReader::Xsel)
+            } else {
+                None
+            },
+        ),
+        _ => (None, None),
+    };
+
+    InputCapabilities {
+        display_server,
+        key_sender,
+        selection_reader,
+    }
+}
+
+fn program_on_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).any(|dir| is_executable(&dir.join(program))))
+        .unwrap_or(false)
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+async fn run_tool(program: &str, args: &[&str]) -> Option<Vec<u8>> {
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true);
+
+    match timeout(TOOL_TIMEOUT, command.output()).await {
+        Ok(Ok(output)) if output.status.success() => Some(output.stdout),
+        _ => None,
+    }
+}
+
+/// Linux input event code for the keys the shortcuts send
+fn evdev_keycode(key: char) -> Option<u16> {
+    match key {
+        'c' => Some(46),
+        'v' => Some(47),
+        _ => None,
+    }
+}
+
+const EVDEV_LEFT_CTRL: u16 = 29;
+
+/// Press Ctrl+`key` through an external `sender`. Returns false when it failed.
+/// `KeySender::Native` is driven through enigo by the caller.
+pub async fn send_ctrl_chord(sender: KeySender, key: char) -> bool {
+    let key_name = key.to_string();
+    match sender {
+        KeySender::Native => false,
+        KeySender::Wtype => run_tool("wtype", &["-M", "ctrl", &key_name, "-m", "ctrl"])
+            .await
+            .is_some(),
+        KeySender::Ydotool => {
+            let Some(code) = evdev_keycode(key) else {
+                return false;
+            };
+            let ctrl_down = format!("{}:1", EVDEV_LEFT_CTRL);
+            let key_down = format!("{}:1", code);
+            let key_up = format!("{}:0", code);
+            let ctrl_up = format!("{}:0", EVDEV_LEFT_CTRL);
+            run_tool(
+                "ydotool",
+                &["key", &ctrl_down, &key_down, &key_up, &ctrl_up],
+            )
+            .await
+            .is_some()
+        }
+    }
+}
+
+/// Text currently selected in any app, if a selection reader is installed
+pub async fn read_selection() -> Option<String> {
+    let output = match capabilities().selection_reader? {
+        SelectionReader::WlPaste => {
+            run_tool("wl-paste", &["--primary", "--no-newline", "--type", "text"]).await
+        }
+        SelectionReader::Xclip => run_tool("xclip", &["-o", "-selection", "primary"]).await,
+        SelectionReader::Xsel => run_tool("xsel", &["--primary", "--output"]).await,
+    }?;
+
+    let text = String::from_utf8_lossy(&output).to_string();
+    (!text.trim().is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect_with(vars: &[(&str, &str)], programs: &[&str]) -> InputCapabilities {
+        detect_unix(
+            |name| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            },
+            |program| programs.contains(&program),
+        )
+    }
+
+    #[test]
+    fn test_wayland_prefers_wtype() {
+        let caps = detect_with(
+            &[("XDG_SESSION_TYPE", "wayland"), ("DISPLAY", ":0")],
+            &["wtype", "ydotool", "wl-paste"],
+        );
+        assert_eq!(caps.display_server, DisplayServer::Wayland);
+        assert_eq!(caps.key_sender, Some(KeySender::Wtype));
+        assert_eq!(caps.selection_reader, Some(SelectionReader::WlPaste));
+        assert!(!caps.clipboard_only());
+    }
+
+    #[test]
+    fn test_wayland_without_tools_is_clipboard_only() {
+        let caps = detect_with(&[("WAYLAND_DISPLAY", "wayland-0")], &["xclip"]);
+        assert_eq!(caps.display_server, DisplayServer::Wayland);
+        assert!(caps.clipboard_only());
+        assert_eq!(caps.selection_reader, None);
+        assert_eq!(caps.summary(), "Wayland; clipboard only");
+    }
+
+    #[test]
+    fn test_x11_uses_enigo_and_xsel() {
+        let caps = detect_with(&[("DISPLAY", ":0")], &["xsel"]);
+        assert_eq!(caps.display_server, DisplayServer::X11);
+        assert_eq!(caps.key_sender, Some(KeySender::Native));
+        assert_eq!(caps.selection_reader, Some(SelectionReader::Xsel));
+    }
+
+    #[test]
+    fn test_headless_session() {
+        let caps = detect_with(&[("XDG_SESSION_TYPE", "tty")], &["wtype", "xclip"]);
+        assert_eq!(caps.display_server, DisplayServer::Unknown);
+        assert!(caps.clipboard_only());
+    }
+}
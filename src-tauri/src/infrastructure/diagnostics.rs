@@ -0,0 +1,620 @@
+// ============================================================
+// DIAGNOSTIC PROBES
+// ============================================================
+// Checks for the external tools, databases, disk and ports the
+// app depends on. Every probe returns a `DiagnosticCheck` rather
+// than an error so one failure never hides the others.
+
+use std::future::Future;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{Connection, SqliteConnection};
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::domain::diagnostics::{DiagnosticCategory, DiagnosticCheck, DiagnosticStatus};
+use crate::infrastructure::desktop_input::{DisplayServer, InputCapabilities};
+
+/// External commands that do not answer within this are reported as hung
+const PROBE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Languages passed to `tesseract -l` by the OCR pipeline
+const OCR_LANGUAGES: [&str; 2] = ["eng", "ind"];
+
+/// Modules `distill-train.py` cannot run without
+const PYTHON_REQUIRED_MODULES: [&str; 3] = ["torch", "transformers", "tokenizers"];
+
+/// Modules that enable optional trainer features (LoRA, metrics)
+const PYTHON_OPTIONAL_MODULES: [&str; 3] = ["peft", "numpy", "psutil"];
+
+const MIN_FREE_BYTES: u64 = 512 * 1024 * 1024;
+const LOW_FREE_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+struct ProbeOutput {
+    success: bool,
+    stdout: String,
+    stderr: String,
+}
+
+impl ProbeOutput {
+    /// First non-empty line of stdout, falling back to stderr
+    fn first_line(&self) -> String {
+        self.stdout
+            .lines()
+            .chain(self.stderr.lines())
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+            .to_string()
+    }
+}
+
+async fn run_probe(program: &str, args: &[&str]) -> std::result::Result<ProbeOutput, String> {
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    match timeout(PROBE_TIMEOUT, command.output()).await {
+        Err(_) => Err(format!(
+            "`{}` did not respond within {}s",
+            program,
+            PROBE_TIMEOUT.as_secs()
+        )),
+        Ok(Err(err)) => Err(format!("Failed to run `{}`: {}", program, err)),
+        Ok(Ok(output)) => Ok(ProbeOutput {
+            success: output.status.success(),
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        }),
+    }
+}
+
+/// Run `probe` and record how long it took
+pub async fn timed<F>(probe: F) -> DiagnosticCheck
+where
+    F: Future<Output = DiagnosticCheck>,
+{
+    let started = Instant::now();
+    let mut check = probe.await;
+    check.duration_ms = started.elapsed().as_millis() as u64;
+    check
+}
+
+fn npx_program() -> &'static str {
+    if cfg!(windows) {
+        "npx.cmd"
+    } else {
+        "npx"
+    }
+}
+
+pub async fn check_node() -> DiagnosticCheck {
+    let check = |status, summary: String| {
+        DiagnosticCheck::new(
+            "node",
+            "Node.js",
+            DiagnosticCategory::Runtime,
+            status,
+            summary,
+        )
+    };
+    match run_probe("node", &["--version"]).await {
+        Ok(output) if output.success => check(DiagnosticStatus::Ok, output.first_line()),
+        Ok(output) => check(
+            DiagnosticStatus::Warning,
+            "Node.js failed to start".to_string(),
+        )
+        .with_detail(output.first_line()),
+        Err(err) => check(DiagnosticStatus::Warning, "Node.js not found".to_string())
+            .with_detail(err)
+            .with_hint("Install Node.js 18+ to use the QA recorder and web OCR capture"),
+    }
+}
+
+/// Default Playwright browser cache, honouring PLAYWRIGHT_BROWSERS_PATH
+fn playwright_browsers_dir() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("PLAYWRIGHT_BROWSERS_PATH") {
+        if path != "0" {
+            return Some(PathBuf::from(path));
+        }
+    }
+    match std::env::consts::OS {
+        "windows" => {
+            std::env::var_os("LOCALAPPDATA").map(|dir| PathBuf::from(dir).join("ms-playwright"))
+        }
+        "macos" => std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join("Library/Caches/ms-playwright")),
+        _ => std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache/ms-playwright")),
+    }
+}
+
+fn installed_chromium_builds(dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut builds: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with("chromium"))
+        .collect();
+    builds.sort();
+    builds
+}
+
+pub async fn check_playwright() -> DiagnosticCheck {
+    let check = |status, summary: String| {
+        DiagnosticCheck::new(
+            "playwright",
+            "Playwright browsers",
+            DiagnosticCategory::Runtime,
+            status,
+            summary,
+        )
+    };
+    let install_hint = "Run `npx playwright install chromium`";
+
+    let version = match run_probe(npx_program(), &["--no-install", "playwright", "--version"]).await
+    {
+        Ok(output) if output.success => output.first_line(),
+        Ok(output) => {
+            return check(
+                DiagnosticStatus::Warning,
+                "Playwright is not installed".to_string(),
+            )
+            .with_detail(output.first_line())
+            .with_hint(install_hint)
+        }
+        Err(err) => {
+            return check(
+                DiagnosticStatus::Warning,
+                "Playwright could not be checked".to_string(),
+            )
+            .with_detail(err)
+            .with_hint("Install Node.js (npx) first")
+        }
+    };
+
+    let Some(dir) = playwright_browsers_dir() else {
+        return check(
+            DiagnosticStatus::Warning,
+            format!("{}; browser cache location unknown", version),
+        );
+    };
+    let builds = installed_chromium_builds(&dir);
+    if builds.is_empty() {
+        check(
+            DiagnosticStatus::Warning,
+            format!("{}; Chromium is not installed", version),
+        )
+        .with_detail(format!("No Chromium build in {}", dir.display()))
+        .with_hint(install_hint)
+    } else {
+        check(
+            DiagnosticStatus::Ok,
+            format!("{}; {}", version, builds.join(", ")),
+        )
+        .with_detail(dir.display().to_string())
+    }
+}
+
+pub async fn check_tesseract() -> DiagnosticCheck {
+    let check = |status, summary: String| {
+        DiagnosticCheck::new(
+            "tesseract",
+            "Tesseract OCR",
+            DiagnosticCategory::Ocr,
+            status,
+            summary,
+        )
+    };
+    let program = std::env::var("TESSERACT_CMD").unwrap_or_else(|_| "tesseract".to_string());
+
+    let version = match run_probe(&program, &["--version"]).await {
+        Ok(output) if output.success => output.first_line(),
+        Ok(output) => {
+            return check(
+                DiagnosticStatus::Warning,
+                "Tesseract failed to start".to_string(),
+            )
+            .with_detail(output.first_line())
+        }
+        Err(err) => {
+            return check(DiagnosticStatus::Warning, "Tesseract not found".to_string())
+                .with_detail(err)
+                .with_hint("Install Tesseract or restore the bundled resources/ocr folder")
+        }
+    };
+
+    let languages = match run_probe(&program, &["--list-langs"]).await {
+        Ok(output) => format!("{}\n{}", output.stdout, output.stderr),
+        Err(err) => {
+            return check(DiagnosticStatus::Warning, version).with_detail(err);
+        }
+    };
+    let missing: Vec<&str> = OCR_LANGUAGES
+        .iter()
+        .copied()
+        .filter(|lang| !languages.lines().any(|line| line.trim() == *lang))
+        .collect();
+
+    if missing.is_empty() {
+        check(DiagnosticStatus::Ok, version)
+    } else {
+        check(
+            DiagnosticStatus::Warning,
+            format!("{}; missing language data: {}", version, missing.join(", ")),
+        )
+        .with_hint("Add the missing .traineddata files to the tessdata folder")
+    }
+}
+
+pub async fn check_pdftoppm() -> DiagnosticCheck {
+    let check = |status, summary: String| {
+        DiagnosticCheck::new(
+            "pdftoppm",
+            "pdftoppm (Poppler)",
+            DiagnosticCategory::Ocr,
+            status,
+            summary,
+        )
+    };
+    let program = std::env::var("PDFTOPPM_CMD").unwrap_or_else(|_| "pdftoppm".to_string());
+
+    // `pdftoppm -v` prints its version to stderr and some builds exit non-zero
+    match run_probe(&program, &["-v"]).await {
+        Ok(output) if output.first_line().contains("pdftoppm") => {
+            check(DiagnosticStatus::Ok, output.first_line())
+        }
+        Ok(output) => check(
+            DiagnosticStatus::Warning,
+            "pdftoppm gave unexpected output".to_string(),
+        )
+        .with_detail(output.first_line()),
+        Err(err) => check(DiagnosticStatus::Warning, "pdftoppm not found".to_string())
+            .with_detail(err)
+            .with_hint("Install Poppler utilities to OCR scanned PDFs"),
+    }
+}
+
+/// Checks the interpreter the trainer is launched with and its modules
+pub async fn check_python(program: &str, train_script: Option<&Path>) -> DiagnosticCheck {
+    let check = |status, summary: String| {
+        DiagnosticCheck::new(
+            "python",
+            "Python training environment",
+            DiagnosticCategory::Training,
+            status,
+            summary,
+        )
+    };
+
+    let version = match run_probe(program, &["--version"]).await {
+        Ok(output) if output.success => output.first_line(),
+        Ok(output) => {
+            return check(
+                DiagnosticStatus::Warning,
+                "Python failed to start".to_string(),
+            )
+            .with_detail(output.first_line())
+        }
+        Err(err) => {
+            return check(DiagnosticStatus::Warning, "Python not found".to_string())
+                .with_detail(err)
+                .with_hint(format!(
+                    "Install Python 3.10+ and make sure `{}` is on PATH",
+                    program
+                ))
+        }
+    };
+
+    let modules: Vec<&str> = PYTHON_REQUIRED_MODULES
+        .iter()
+        .chain(PYTHON_OPTIONAL_MODULES.iter())
+        .copied()
+        .collect();
+    let script = format!(
+        "import importlib.util\nfor name in {:?}:\n    print(name, importlib.util.find_spec(name) is not None)",
+        modules
+    );
+    let found = match run_probe(program, &["-c", &script]).await {
+        Ok(output) if output.success => output.stdout,
+        Ok(output) => {
+            return check(DiagnosticStatus::Warning, version).with_detail(output.first_line())
+        }
+        Err(err) => return check(DiagnosticStatus::Warning, version).with_detail(err),
+    };
+    let missing = |names: &[&str]| -> Vec<String> {
+        names
+            .iter()
+            .filter(|name| {
+                !found
+                    .lines()
+                    .any(|line| line.trim() == format!("{} True", name))
+            })
+            .map(|name| name.to_string())
+            .collect()
+    };
+    let missing_required = missing(&PYTHON_REQUIRED_MODULES);
+    let missing_optional = missing(&PYTHON_OPTIONAL_MODULES);
+
+    let mut result = if !missing_required.is_empty() {
+        check(
+            DiagnosticStatus::Warning,
+            format!("{}; missing {}", version, missing_required.join(", ")),
+        )
+        .with_hint(format!("pip install {}", missing_required.join(" ")))
+    } else if !missing_optional.is_empty() {
+        check(
+            DiagnosticStatus::Ok,
+            format!(
+                "{}; optional modules missing: {}",
+                version,
+                missing_optional.join(", ")
+            ),
+        )
+    } else {
+        check(DiagnosticStatus::Ok, version)
+    };
+
+    if train_script.is_none() {
+        result.status = result.status.max(DiagnosticStatus::Warning);
+        result.detail = Some("distill-train.py was not found in the app resources".to_string());
+    }
+    result
+}
+
+/// `PRAGMA quick_check` on a read-only connection
+pub async fn check_database(id: &str, label: &str, path: &Path) -> DiagnosticCheck {
+    let check = |status, summary: String| {
+        DiagnosticCheck::new(id, label, DiagnosticCategory::Database, status, summary)
+    };
+    if !path.exists() {
+        return check(
+            DiagnosticStatus::Warning,
+            "Database file does not exist".to_string(),
+        )
+        .with_detail(path.display().to_string())
+        .with_hint("Restart the app to recreate it");
+    }
+
+    let options = SqliteConnectOptions::new()
+        .filename(path)
+        .read_only(true)
+        .busy_timeout(Duration::from_secs(5));
+    let mut conn = match SqliteConnection::connect_with(&options).await {
+        Ok(conn) => conn,
+        Err(err) => {
+            return check(DiagnosticStatus::Error, "Cannot open database".to_string())
+                .with_detail(err.to_string())
+        }
+    };
+    let rows = sqlx::query_scalar::<_, String>("PRAGMA quick_check")
+        .fetch_all(&mut conn)
+        .await;
+    let _ = conn.close().await;
+
+    let size = std::fs::metadata(path).map(|meta| meta.len()).unwrap_or(0);
+    match rows {
+        Ok(rows) if rows.len() == 1 && rows[0] == "ok" => {
+            check(DiagnosticStatus::Ok, format!("ok ({})", format_bytes(size)))
+                .with_detail(path.display().to_string())
+        }
+        Ok(rows) => check(
+            DiagnosticStatus::Error,
+            format!("{} integrity problem(s) found", rows.len()),
+        )
+        .with_detail(rows.join("\n"))
+        .with_hint("Restore the database from a backup or export your data and recreate it"),
+        Err(err) => check(
+            DiagnosticStatus::Error,
+            "Integrity check failed".to_string(),
+        )
+        .with_detail(err.to_string()),
+    }
+}
+
+pub fn check_disk_space(dir: &Path) -> DiagnosticCheck {
+    let check = |status, summary: String| {
+        DiagnosticCheck::new(
+            "disk",
+            "Free disk space",
+            DiagnosticCategory::Storage,
+            status,
+            summary,
+        )
+    };
+    let available = match fs2::available_space(dir) {
+        Ok(available) => available,
+        Err(err) => {
+            return check(
+                DiagnosticStatus::Warning,
+                "Could not read free space".to_string(),
+            )
+            .with_detail(err.to_string())
+        }
+    };
+    let total = fs2::total_space(dir).unwrap_or(0);
+    let summary = format!(
+        "{} free of {}",
+        format_bytes(available),
+        format_bytes(total)
+    );
+
+    let result = if available < MIN_FREE_BYTES {
+        check(DiagnosticStatus::Error, summary)
+            .with_hint("Free up disk space; database writes and training may fail")
+    } else if available < LOW_FREE_BYTES {
+        check(DiagnosticStatus::Warning, summary)
+            .with_hint("Disk space is low; prune old training runs or logs")
+    } else {
+        check(DiagnosticStatus::Ok, summary)
+    };
+    result.with_detail(dir.display().to_string())
+}
+
+fn port_is_free(port: u16) -> bool {
+    TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+/// The local HTTP API is expected to be listening on `port`.
+/// `health_url` is requested (with `token` when the API requires one) to tell
+/// our server apart from another process.
+pub async fn check_api_port(port: u16, health_url: &str, token: Option<&str>) -> DiagnosticCheck {
+    let check = |status, summary: String| {
+        DiagnosticCheck::new(
+            "port.api",
+            &format!("Local API port {}", port),
+            DiagnosticCategory::Network,
+            status,
+            summary,
+        )
+    };
+
+    if port_is_free(port) {
+        return check(
+            DiagnosticStatus::Error,
+            "Local API server is not running".to_string(),
+        )
+        .with_hint("Restart the app; check the logs for the HTTP server start error");
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(3))
+        .build()
+        .unwrap_or_default();
+    let mut request = client.get(health_url);
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    match request.send().await {
+        Ok(response) if response.status().is_success() => {
+            check(DiagnosticStatus::Ok, "Served by this app".to_string())
+        }
+        Ok(response) => check(
+            DiagnosticStatus::Error,
+            format!("Port {} is used by another process", port),
+        )
+        .with_detail(format!("{} returned {}", health_url, response.status()))
+        .with_hint("Stop the other process and restart the app"),
+        Err(err) => check(
+            DiagnosticStatus::Error,
+            format!("Port {} is used by another process", port),
+        )
+        .with_detail(err.to_string())
+        .with_hint("Stop the other process and restart the app"),
+    }
+}
+
+/// The mock server should be able to bind `port` when it is not running
+pub fn check_mock_port(port: u16, running: bool) -> DiagnosticCheck {
+    let check = |status, summary: String| {
+        DiagnosticCheck::new(
+            "port.mock",
+            &format!("Mock server port {}", port),
+            DiagnosticCategory::Network,
+            status,
+            summary,
+        )
+    };
+
+    if running {
+        check(DiagnosticStatus::Ok, "Mock server is listening".to_string())
+    } else if port_is_free(port) {
+        check(DiagnosticStatus::Ok, "Available".to_string())
+    } else {
+        check(
+            DiagnosticStatus::Warning,
+            format!("Port {} is used by another process", port),
+        )
+        .with_hint("Pick a different port in the mock server settings")
+    }
+}
+
+/// Whether the global shortcuts can copy the selection and paste results
+pub fn check_desktop_input(capabilities: &InputCapabilities) -> DiagnosticCheck {
+    let check = |status| {
+        DiagnosticCheck::new(
+            "desktop.input",
+            "Shortcut copy and paste",
+            DiagnosticCategory::Runtime,
+            status,
+            capabilities.summary(),
+        )
+    };
+
+    match (capabilities.display_server, capabilities.key_sender) {
+        (_, Some(_)) => check(DiagnosticStatus::Ok),
+        (DisplayServer::Wayland, None) => check(DiagnosticStatus::Warning).with_hint(
+            "Install wtype (wlroots compositors) or ydotool to copy and paste automatically, \
+             and wl-clipboard to read the selection. Until then, copy the text before using a shortcut.",
+        ),
+        _ => check(DiagnosticStatus::Warning)
+            .with_hint("No graphical session was detected; shortcuts only use the clipboard"),
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+
+    #[test]
+    fn test_first_line_prefers_stdout() {
+        let output = ProbeOutput {
+            success: true,
+            stdout: "\nv20.11.0\n".to_string(),
+            stderr: "warning".to_string(),
+        };
+        assert_eq!(output.first_line(), "v20.11.0");
+
+        let output = ProbeOutput {
+            success: true,
+            stdout: String::new(),
+            stderr: "pdftoppm version 24.02.0\nCopyright".to_string(),
+        };
+        assert_eq!(output.first_line(), "pdftoppm version 24.02.0");
+    }
+
+    #[test]
+    fn test_mock_port_in_use() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        assert_eq!(check_mock_port(port, true).status, DiagnosticStatus::Ok);
+        assert_eq!(
+            check_mock_port(port, false).status,
+            DiagnosticStatus::Warning
+        );
+        drop(listener);
+        assert_eq!(check_mock_port(port, false).status, DiagnosticStatus::Ok);
+    }
+}
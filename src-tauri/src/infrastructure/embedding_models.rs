@@ -0,0 +1,357 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::Deserialize;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncWriteExt;
+
+use crate::domain::embedding_models::{
+    EmbeddingModelDownloadProgress, EmbeddingModelFile, EmbeddingModelManifest,
+};
+use crate::domain::error::{AppError, Result};
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Entry of the hub's `api/models/<repo>/tree/main` listing
+#[derive(Debug, Deserialize)]
+struct HubTreeEntry {
+    #[serde(rename = "type")]
+    kind: String,
+    path: String,
+    oid: String,
+    size: u64,
+    lfs: Option<HubLfsInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HubLfsInfo {
+    oid: String,
+    size: u64,
+}
+
+/// Downloaded local embedding models, one folder per model id
+pub struct EmbeddingModelStore {
+    root: PathBuf,
+    client: reqwest::Client,
+    endpoint: String,
+    /// Models being downloaded right now
+    downloads: Mutex<HashSet<String>>,
+}
+
+impl EmbeddingModelStore {
+    pub fn new(root: PathBuf) -> Self {
+        // Same override fastembed honours for its own downloads
+        let endpoint =
+            std::env::var("HF_ENDPOINT").unwrap_or_else(|_| "https://huggingface.co".to_string());
+        Self {
+            root,
+            client: reqwest::Client::new(),
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            downloads: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn model_dir(&self, model_id: &str) -> PathBuf {
+        self.root.join(model_id)
+    }
+
+    /// Manifest of an installed model; `None` when it is not (fully) downloaded
+    pub fn manifest(&self, model_id: &str) -> Option<EmbeddingModelManifest> {
+        let raw = fs::read(self.model_dir(model_id).join(MANIFEST_FILE)).ok()?;
+        serde_json::from_slice(&raw).ok()
+    }
+
+    pub fn read_file(&self, model_id: &str, path: &str) -> Result<Vec<u8>> {
+        let full = self.model_dir(model_id).join(relative_path(path)?);
+        fs::read(&full)
+            .map_err(|e| AppError::IoError(format!("Failed to read {}: {}", full.display(), e)))
+    }
+
+    /// Sizes and checksums the hub publishes for `paths` of `model_code`
+    pub async fn remote_files(
+        &self,
+        model_code: &str,
+        paths: &[String],
+    ) -> Result<Vec<EmbeddingModelFile>> {
+        let url = format!(
+            "{}/api/models/{}/tree/main?recursive=true",
+            self.endpoint, model_code
+        );
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to list {}: {}", model_code, e)))?;
+        if !response.status().is_success() {
+            return Err(AppError::Internal(format!(
+                "Failed to list {}: HTTP {}",
+                model_code,
+                response.status()
+            )));
+        }
+        let entries: Vec<HubTreeEntry> = response.json().await.map_err(|e| {
+            AppError::ParseError(format!("Invalid file listing for {}: {}", model_code, e))
+        })?;
+
+        paths
+            .iter()
+            .map(|path| {
+                let entry = entries
+                    .iter()
+                    .find(|entry| entry.kind == "file" && entry.path == *path)
+                    .ok_or_else(|| {
+                        AppError::NotFound(format!("{} has no file '{}'", model_code, path))
+                    })?;
+                Ok(EmbeddingModelFile {
+                    path: entry.path.clone(),
+                    size: entry.lfs.as_ref().map_or(entry.size, |lfs| lfs.size),
+                    sha256: entry.lfs.as_ref().map(|lfs| lfs.oid.to_lowercase()),
+                    git_oid: entry.oid.to_lowercase(),
+                })
+            })
+            .collect()
+    }
+
+    /// Download `paths` of `model_code` into the folder of `model_id`,
+    /// replacing any earlier copy only once every file has been verified
+    pub async fn download(
+        &self,
+        model_id: &str,
+        model_code: &str,
+        paths: &[String],
+        on_progress: &mut (dyn FnMut(&EmbeddingModelDownloadProgress) + Send),
+    ) -> Result<EmbeddingModelManifest> {
+        let _guard = DownloadGuard::claim(&self.downloads, model_id)?;
+        let files = self.remote_files(model_code, paths).await?;
+
+        fs::create_dir_all(&self.root)?;
+        let staging = self.root.join(format!("{}.partial", model_id));
+        if staging.exists() {
+            fs::remove_dir_all(&staging)?;
+        }
+        fs::create_dir_all(&staging)?;
+
+        let mut progress = EmbeddingModelDownloadProgress {
+            model_id: model_id.to_string(),
+            file: String::new(),
+            downloaded_bytes: 0,
+            total_bytes: files.iter().map(|file| file.size).sum(),
+            files_done: 0,
+            file_count: files.len() as u64,
+        };
+        for file in &files {
+            progress.file = file.path.clone();
+            if let Err(err) = self
+                .download_file(model_code, file, &staging, &mut progress, on_progress)
+                .await
+            {
+                let _ = fs::remove_dir_all(&staging);
+                return Err(err);
+            }
+            progress.files_done += 1;
+            on_progress(&progress);
+        }
+
+        let manifest = EmbeddingModelManifest {
+            model_id: model_id.to_string(),
+            model_code: model_code.to_string(),
+            files,
+            installed_at: chrono::Utc::now().timestamp_millis(),
+        };
+        let json = serde_json::to_vec_pretty(&manifest)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize manifest: {}", e)))?;
+        fs::write(staging.join(MANIFEST_FILE), json)?;
+
+        let target = self.model_dir(model_id);
+        if target.exists() {
+            fs::remove_dir_all(&target)?;
+        }
+        fs::rename(&staging, &target)?;
+        Ok(manifest)
+    }
+
+    async fn download_file(
+        &self,
+        model_code: &str,
+        file: &EmbeddingModelFile,
+        staging: &Path,
+        progress: &mut EmbeddingModelDownloadProgress,
+        on_progress: &mut (dyn FnMut(&EmbeddingModelDownloadProgress) + Send),
+    ) -> Result<()> {
+        let destination = staging.join(relative_path(&file.path)?);
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let url = format!(
+            "{}/{}/resolve/main/{}",
+            self.endpoint, model_code, file.path
+        );
+        let mut response =
+            self.client.get(&url).send().await.map_err(|e| {
+                AppError::Internal(format!("Failed to download {}: {}", file.path, e))
+            })?;
+        if !response.status().is_success() {
+            return Err(AppError::Internal(format!(
+                "Failed to download {}: HTTP {}",
+                file.path,
+                response.status()
+            )));
+        }
+
+        let mut output = tokio::fs::File::create(&destination).await?;
+        let mut checksum = FileChecksum::new(file.size);
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to download {}: {}", file.path, e)))?
+        {
+            output.write_all(&chunk).await?;
+            checksum.update(&chunk);
+            progress.downloaded_bytes += chunk.len() as u64;
+            on_progress(progress);
+        }
+        output.flush().await?;
+        checksum.verify(file)
+    }
+}
+
+/// Hashes a file while it streams in, for both checksums the hub publishes
+struct FileChecksum {
+    sha256: Sha256,
+    git: Sha1,
+    bytes: u64,
+}
+
+impl FileChecksum {
+    fn new(expected_size: u64) -> Self {
+        let mut git = Sha1::new();
+        git.update(format!("blob {}\0", expected_size).as_bytes());
+        Self {
+            sha256: Sha256::new(),
+            git,
+            bytes: 0,
+        }
+    }
+
+    fn update(&mut self, chunk: &[u8]) {
+        self.sha256.update(chunk);
+        self.git.update(chunk);
+        self.bytes += chunk.len() as u64;
+    }
+
+    fn verify(self, file: &EmbeddingModelFile) -> Result<()> {
+        if self.bytes != file.size {
+            return Err(AppError::ValidationError(format!(
+                "{} is {} bytes, expected {}",
+                file.path, self.bytes, file.size
+            )));
+        }
+        let matches = match &file.sha256 {
+            Some(expected) => hex::encode(self.sha256.finalize()) == *expected,
+            None => hex::encode(self.git.finalize()) == file.git_oid,
+        };
+        if !matches {
+            return Err(AppError::ValidationError(format!(
+                "Checksum mismatch for {}",
+                file.path
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Keeps a second download of the same model or language pack from starting
+pub(crate) struct DownloadGuard<'a> {
+    downloads: &'a Mutex<HashSet<String>>,
+    model_id: String,
+}
+
+impl<'a> DownloadGuard<'a> {
+    pub(crate) fn claim(downloads: &'a Mutex<HashSet<String>>, model_id: &str) -> Result<Self> {
+        if !downloads.lock().unwrap().insert(model_id.to_string()) {
+            return Err(AppError::ValidationError(format!(
+                "{} is already being downloaded",
+                model_id
+            )));
+        }
+        Ok(Self {
+            downloads,
+            model_id: model_id.to_string(),
+        })
+    }
+}
+
+impl Drop for DownloadGuard<'_> {
+    fn drop(&mut self) {
+        self.downloads.lock().unwrap().remove(&self.model_id);
+    }
+}
+
+/// `path` as a relative path that stays inside the model folder
+fn relative_path(path: &str) -> Result<PathBuf> {
+    let relative = PathBuf::from(path);
+    if relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        Ok(relative)
+    } else {
+        Err(AppError::SecurityError(format!(
+            "Model file path '{}' leaves the model folder",
+            path
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(content: &[u8], sha256: Option<String>, git_oid: &str) -> EmbeddingModelFile {
+        EmbeddingModelFile {
+            path: "config.json".to_string(),
+            size: content.len() as u64,
+            sha256,
+            git_oid: git_oid.to_string(),
+        }
+    }
+
+    #[test]
+    fn verifies_git_blob_ids_and_lfs_hashes() {
+        // `git hash-object` of "hello\n"
+        let small = file(b"hello\n", None, "ce013625030ba8dba906f756967f9e9ca394464a");
+        let mut checksum = FileChecksum::new(small.size);
+        checksum.update(b"hel");
+        checksum.update(b"lo\n");
+        assert!(checksum.verify(&small).is_ok());
+
+        let lfs = file(
+            b"hello\n",
+            Some(hex::encode(Sha256::digest(b"hello\n"))),
+            "not-checked",
+        );
+        let mut checksum = FileChecksum::new(lfs.size);
+        checksum.update(b"hello\n");
+        assert!(checksum.verify(&lfs).is_ok());
+
+        let mut checksum = FileChecksum::new(lfs.size);
+        checksum.update(b"hellO\n");
+        assert!(checksum.verify(&lfs).is_err());
+    }
+
+    #[test]
+    fn rejects_paths_outside_the_model_folder() {
+        assert!(relative_path("onnx/model.onnx").is_ok());
+        assert!(relative_path("../settings.json").is_err());
+        assert!(relative_path("/etc/passwd").is_err());
+    }
+}
@@ -0,0 +1,43 @@
+//! ONNX Runtime execution providers for the in-process embedding models.
+//! GPU providers are only usable when ONNX Runtime was built with them,
+//! which the `gpu-cuda`, `gpu-directml` and `gpu-coreml` features enable.
+
+use ort::ep::{self, ExecutionProvider, ExecutionProviderDispatch};
+
+use crate::domain::embedding_models::EmbeddingExecutionProvider;
+
+/// Why `provider` can't be tried on this machine, or `Ok` when it can
+pub fn check(provider: EmbeddingExecutionProvider) -> Result<(), String> {
+    match provider {
+        EmbeddingExecutionProvider::Cpu => Ok(()),
+        EmbeddingExecutionProvider::Cuda => probe(&ep::CUDA::default(), "gpu-cuda"),
+        EmbeddingExecutionProvider::DirectMl => probe(&ep::DirectML::default(), "gpu-directml"),
+        EmbeddingExecutionProvider::CoreMl => probe(&ep::CoreML::default(), "gpu-coreml"),
+    }
+}
+
+/// `provider` for a session. Registration errors are returned instead of
+/// ONNX Runtime's silent CPU fallback, so the caller knows what it got.
+pub fn dispatch(provider: EmbeddingExecutionProvider) -> ExecutionProviderDispatch {
+    match provider {
+        EmbeddingExecutionProvider::Cpu => ep::CPU::default().build(),
+        EmbeddingExecutionProvider::Cuda => ep::CUDA::default().build(),
+        EmbeddingExecutionProvider::DirectMl => ep::DirectML::default().build(),
+        EmbeddingExecutionProvider::CoreMl => ep::CoreML::default().build(),
+    }
+    .error_on_failure()
+}
+
+fn probe(provider: &dyn ExecutionProvider, feature: &str) -> Result<(), String> {
+    if !provider.supported_by_platform() {
+        return Err("Not supported on this platform".to_string());
+    }
+    match provider.is_available() {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(format!(
+            "This build of ONNX Runtime does not include it; build the app with the `{}` feature",
+            feature
+        )),
+        Err(err) => Err(err.to_string()),
+    }
+}
@@ -0,0 +1,309 @@
+//! Git command line access for repository imports: shallow clones of remote
+//! repositories, and the files and blobs of a commit. Content is always read
+//! from the commit, never the working tree, so uncommitted edits in a local
+//! repository are not imported.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::domain::error::{AppError, ErrorCode, Result};
+
+/// Clones and fetches of large repositories can take a while
+const NETWORK_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+const LOCAL_TIMEOUT: Duration = Duration::from_secs(2 * 60);
+
+/// Blobs requested from one `git cat-file` process
+const BLOB_BATCH: usize = 200;
+
+const REMOTE_HINT: &str = "Check the URL and that `git clone` works for it from a terminal; \
+     private repositories use git's credential helper or SSH agent";
+
+/// A file in a commit's tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitTreeEntry {
+    /// `/`-separated path from the repository root
+    pub path: String,
+    pub blob_sha: String,
+    pub size: u64,
+}
+
+/// A repository on disk, local or cloned into app data
+pub struct GitRepository {
+    dir: PathBuf,
+}
+
+impl GitRepository {
+    /// A local repository, with the commit at `branch` (HEAD when unset)
+    pub async fn open_local(path: &Path, branch: Option<&str>) -> Result<(Self, String)> {
+        if !path.is_dir() {
+            return Err(AppError::NotFound(format!(
+                "Repository folder not found: {}",
+                path.display()
+            )));
+        }
+        let repo = Self {
+            dir: path.to_path_buf(),
+        };
+        let commit = repo.resolve_commit(branch.unwrap_or("HEAD")).await?;
+        Ok((repo, commit))
+    }
+
+    /// Clone `url` into `dir` (shallow, without a working tree) or fetch
+    /// the newest commit of `branch` into an earlier clone; returns the
+    /// fetched commit
+    pub async fn fetch_remote(
+        url: &str,
+        branch: Option<&str>,
+        dir: &Path,
+    ) -> Result<(Self, String)> {
+        let repo = Self {
+            dir: dir.to_path_buf(),
+        };
+        if dir.join("HEAD").is_file() || dir.join(".git").is_dir() {
+            repo.git(&["remote", "set-url", "origin", url], LOCAL_TIMEOUT)
+                .await?;
+            repo.git(
+                &["fetch", "--depth", "1", "origin", branch.unwrap_or("HEAD")],
+                NETWORK_TIMEOUT,
+            )
+            .await
+            .map_err(remote_error)?;
+            let commit = repo.resolve_commit("FETCH_HEAD").await?;
+            return Ok((repo, commit));
+        }
+
+        if let Some(parent) = dir.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                AppError::Internal(format!("Failed to create {}: {}", parent.display(), e))
+            })?;
+        }
+        let target = dir.to_string_lossy().to_string();
+        let mut args = vec!["clone", "--depth", "1", "--no-checkout", "--single-branch"];
+        if let Some(branch) = branch {
+            args.extend(["--branch", branch]);
+        }
+        args.extend(["--", url, target.as_str()]);
+        if let Err(err) = run_git(None, &args, NETWORK_TIMEOUT).await {
+            // A half-written clone would be mistaken for a finished one
+            let _ = std::fs::remove_dir_all(dir);
+            return Err(remote_error(err));
+        }
+        let commit = repo.resolve_commit("HEAD").await?;
+        Ok((repo, commit))
+    }
+
+    async fn resolve_commit(&self, rev: &str) -> Result<String> {
+        let spec = format!("{}^{{commit}}", rev);
+        let output = self
+            .git(&["rev-parse", "--verify", "--quiet", &spec], LOCAL_TIMEOUT)
+            .await
+            .map_err(|_| {
+                AppError::NotFound(format!(
+                    "'{}' is not a commit in {}",
+                    rev,
+                    self.dir.display()
+                ))
+            })?;
+        Ok(String::from_utf8_lossy(&output).trim().to_string())
+    }
+
+    /// Regular files of a commit; symlinks and submodules are left out
+    pub async fn tree(&self, commit: &str) -> Result<Vec<GitTreeEntry>> {
+        let output = self
+            .git(&["ls-tree", "-r", "-l", "-z", commit], LOCAL_TIMEOUT)
+            .await?;
+        Ok(parse_tree(&output))
+    }
+
+    /// Contents of blobs by id; ids git doesn't have are left out
+    pub async fn read_blobs(&self, blob_shas: &[String]) -> Result<HashMap<String, Vec<u8>>> {
+        let mut blobs = HashMap::with_capacity(blob_shas.len());
+        for batch in blob_shas.chunks(BLOB_BATCH) {
+            let mut child = Command::new("git")
+                .arg("-C")
+                .arg(&self.dir)
+                .args(["cat-file", "--batch"])
+                .env("GIT_TERMINAL_PROMPT", "0")
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .kill_on_drop(true)
+                .spawn()
+                .map_err(spawn_error)?;
+
+            let mut stdin = child
+                .stdin
+                .take()
+                .ok_or_else(|| AppError::Internal("git cat-file has no stdin".to_string()))?;
+            let request = batch
+                .iter()
+                .map(|sha| format!("{}\n", sha))
+                .collect::<String>();
+            // Written concurrently so a full stdout pipe can't block the request
+            let writer = tokio::spawn(async move {
+                let _ = stdin.write_all(request.as_bytes()).await;
+            });
+            let output = timeout(LOCAL_TIMEOUT, child.wait_with_output())
+                .await
+                .map_err(|_| AppError::Internal("git cat-file timed out".to_string()))?
+                .map_err(|e| AppError::Internal(format!("git cat-file failed: {}", e)))?;
+            let _ = writer.await;
+            if !output.status.success() {
+                return Err(AppError::Internal(format!(
+                    "git cat-file failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )));
+            }
+            blobs.extend(parse_batch(&output.stdout));
+        }
+        Ok(blobs)
+    }
+
+    async fn git(&self, args: &[&str], limit: Duration) -> Result<Vec<u8>> {
+        run_git(Some(&self.dir), args, limit).await
+    }
+}
+
+async fn run_git(dir: Option<&Path>, args: &[&str], limit: Duration) -> Result<Vec<u8>> {
+    let mut command = Command::new("git");
+    if let Some(dir) = dir {
+        command.arg("-C").arg(dir);
+    }
+    command
+        .args(args)
+        // Fail instead of waiting for a password nobody can type
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+
+    let output = timeout(limit, command.output())
+        .await
+        .map_err(|_| {
+            AppError::Internal(format!(
+                "git {} timed out after {} s",
+                args[0],
+                limit.as_secs()
+            ))
+        })?
+        .map_err(spawn_error)?;
+    if !output.status.success() {
+        return Err(AppError::Internal(format!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(output.stdout)
+}
+
+fn spawn_error(err: std::io::Error) -> AppError {
+    if err.kind() == std::io::ErrorKind::NotFound {
+        AppError::coded(ErrorCode::DependencyMissing, "git is not installed")
+            .with_hint("Install git from https://git-scm.com and restart the app")
+    } else {
+        AppError::Internal(format!("Failed to run git: {}", err))
+    }
+}
+
+fn remote_error(err: AppError) -> AppError {
+    match err {
+        AppError::Internal(message) => {
+            AppError::coded(ErrorCode::NetworkUnavailable, message).with_hint(REMOTE_HINT)
+        }
+        other => other,
+    }
+}
+
+/// Entries of `git ls-tree -r -l -z`: `<mode> <type> <sha> <size>\t<path>\0`
+fn parse_tree(output: &[u8]) -> Vec<GitTreeEntry> {
+    output
+        .split(|byte| *byte == 0)
+        .filter_map(|record| {
+            let record = std::str::from_utf8(record).ok()?;
+            let (meta, path) = record.split_once('\t')?;
+            let mut fields = meta.split_whitespace();
+            let mode = fields.next()?;
+            let kind = fields.next()?;
+            let sha = fields.next()?;
+            let size = fields.next()?.parse().ok()?;
+            // 120000 is a symlink; submodules are of type `commit`
+            if kind != "blob" || mode == "120000" {
+                return None;
+            }
+            Some(GitTreeEntry {
+                path: path.to_string(),
+                blob_sha: sha.to_string(),
+                size,
+            })
+        })
+        .collect()
+}
+
+/// Output of `git cat-file --batch`: `<sha> blob <size>\n<content>\n` per
+/// object, or `<sha> missing\n`
+fn parse_batch(output: &[u8]) -> HashMap<String, Vec<u8>> {
+    let mut blobs = HashMap::new();
+    let mut rest = output;
+    while let Some(end) = rest.iter().position(|byte| *byte == b'\n') {
+        let header = String::from_utf8_lossy(&rest[..end]).to_string();
+        rest = &rest[end + 1..];
+        let mut fields = header.split_whitespace();
+        let (Some(sha), Some(_kind), Some(size)) = (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        let Ok(size) = size.parse::<usize>() else {
+            continue;
+        };
+        if rest.len() < size {
+            break;
+        }
+        blobs.insert(sha.to_string(), rest[..size].to_vec());
+        rest = rest.get(size + 1..).unwrap_or_default();
+    }
+    blobs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tree_keeps_regular_files() {
+        let output = b"100644 blob aaa 12\tsrc/main.rs\0\
+                       120000 blob bbb 7\tlink\0\
+                       160000 commit ccc -\tvendor/lib\0\
+                       100755 blob ddd 30\tscripts/run me.sh\0";
+        let entries = parse_tree(output);
+        assert_eq!(
+            entries,
+            vec![
+                GitTreeEntry {
+                    path: "src/main.rs".to_string(),
+                    blob_sha: "aaa".to_string(),
+                    size: 12,
+                },
+                GitTreeEntry {
+                    path: "scripts/run me.sh".to_string(),
+                    blob_sha: "ddd".to_string(),
+                    size: 30,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn batch_output_splits_into_blobs() {
+        let output = b"aaa blob 5\nfn a\n\nbbb missing\nccc blob 0\n\n";
+        let blobs = parse_batch(output);
+        assert_eq!(blobs.get("aaa").map(Vec::as_slice), Some(&b"fn a\n"[..]));
+        assert_eq!(blobs.get("ccc").map(Vec::as_slice), Some(&b""[..]));
+        assert!(!blobs.contains_key("bbb"));
+    }
+}
@@ -1,4 +1,4 @@
-use super::LLMClient;
+use super::{api_error, chat_completion, request_error, Completion, LLMClient};
 use crate::domain::error::{AppError, Result};
 use crate::domain::llm_config::LLMConfig;
 use async_trait::async_trait;
@@ -20,6 +20,12 @@ impl CliProxyClient {
 #[async_trait]
 impl LLMClient for CliProxyClient {
     async fn generate(&self, config: &LLMConfig, system: &str, user: &str) -> Result<String> {
+        self.complete(config, system, user)
+            .await
+            .map(|completion| completion.text)
+    }
+
+    async fn complete(&self, config: &LLMConfig, system: &str, user: &str) -> Result<Completion> {
         // CLI Proxy uses /chat/completions endpoint
         let url = if config.base_url.ends_with("/") {
             format!("{}chat/completions", config.base_url)
@@ -50,19 +56,12 @@ impl LLMClient for CliProxyClient {
             "temperature": config.temperature,
         });
 
-        let response = request
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| AppError::LLMError(format!("Request failed: {}", e)))?;
+        let response = request.json(&body).send().await.map_err(request_error)?;
 
         if !response.status().is_success() {
             let status = response.status();
             let text = response.text().await.unwrap_or_default();
-            return Err(AppError::LLMError(format!(
-                "API error ({}): {}",
-                status, text
-            )));
+            return Err(api_error(status, &text));
         }
 
         let json: serde_json::Value = response
@@ -70,10 +69,7 @@ impl LLMClient for CliProxyClient {
             .await
             .map_err(|e| AppError::LLMError(format!("Failed to parse JSON: {}", e)))?;
 
-        json["choices"][0]["message"]["content"]
-            .as_str()
-            .map(|s| s.to_string())
-            .ok_or_else(|| AppError::LLMError("Invalid response format".to_string()))
+        chat_completion(&json)
     }
 
     async fn list_models(&self, config: &LLMConfig) -> Result<Vec<String>> {
@@ -90,10 +86,7 @@ impl LLMClient for CliProxyClient {
             request = request.header("Authorization", api_key);
         }
 
-        let response = request
-            .send()
-            .await
-            .map_err(|e| AppError::LLMError(format!("Request failed: {}", e)))?;
+        let response = request.send().await.map_err(request_error)?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -1,9 +1,12 @@
-use super::LLMClient;
-use crate::domain::error::{AppError, Result};
+use super::{api_error, request_error, Completion, ImageInput, LLMClient, TokenUsage};
+use crate::domain::error::{AppError, ErrorCode, Result};
 use crate::domain::llm_config::LLMConfig;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
+/// Sent as a header rather than `?key=` so the key never appears in URLs or transport errors
+const API_KEY_HEADER: &str = "x-goog-api-key";
+
 #[derive(Serialize)]
 struct GeminiRequest {
     contents: Vec<GeminiContent>,
@@ -27,7 +30,36 @@ struct GeminiContent {
 
 #[derive(Serialize)]
 struct GeminiPart {
-    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(rename = "inlineData", skip_serializing_if = "Option::is_none")]
+    inline_data: Option<GeminiInlineData>,
+}
+
+impl GeminiPart {
+    fn text(text: &str) -> Self {
+        Self {
+            text: Some(text.to_string()),
+            inline_data: None,
+        }
+    }
+
+    fn image(image: &ImageInput) -> Self {
+        Self {
+            text: None,
+            inline_data: Some(GeminiInlineData {
+                mime_type: image.mime_type.clone(),
+                data: image.base64(),
+            }),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct GeminiInlineData {
+    #[serde(rename = "mimeType")]
+    mime_type: String,
+    data: String,
 }
 
 #[derive(Serialize)]
@@ -42,6 +74,16 @@ struct GenerationConfig {
 #[derive(Deserialize)]
 struct GeminiResponse {
     candidates: Vec<GeminiCandidate>,
+    #[serde(rename = "usageMetadata")]
+    usage_metadata: Option<GeminiUsageMetadata>,
+}
+
+#[derive(Deserialize)]
+struct GeminiUsageMetadata {
+    #[serde(rename = "promptTokenCount", default)]
+    prompt_token_count: u64,
+    #[serde(rename = "candidatesTokenCount", default)]
+    candidates_token_count: u64,
 }
 
 #[derive(Deserialize)]
@@ -90,39 +132,53 @@ impl GeminiClient {
     }
 
     fn api_key(config: &LLMConfig) -> Result<String> {
-        config
-            .api_key
-            .clone()
-            .ok_or_else(|| AppError::LLMError("Missing API key for Google provider".to_string()))
+        config.api_key.clone().ok_or_else(|| {
+            AppError::coded(
+                ErrorCode::ConfigMissing,
+                "Missing API key for Google provider",
+            )
+            .with_hint("Add the Google API key in Settings")
+        })
     }
 }
 
 #[async_trait]
 impl LLMClient for GeminiClient {
     async fn generate(&self, config: &LLMConfig, system: &str, user: &str) -> Result<String> {
+        self.complete(config, system, user)
+            .await
+            .map(|completion| completion.text)
+    }
+
+    async fn complete(&self, config: &LLMConfig, system: &str, user: &str) -> Result<Completion> {
+        self.complete_with_images(config, system, user, &[]).await
+    }
+
+    async fn complete_with_images(
+        &self,
+        config: &LLMConfig,
+        system: &str,
+        user: &str,
+        images: &[ImageInput],
+    ) -> Result<Completion> {
         let api_key = Self::api_key(config)?;
         let model_id = Self::normalize_model(&config.model);
         let base_url = config.base_url.trim_end_matches('/');
-        let url = format!("{}/{}:generateContent?key={}", base_url, model_id, api_key);
+        let url = format!("{}/{}:generateContent", base_url, model_id);
 
         // Build system instruction if provided
         let system_instruction = if !system.trim().is_empty() {
             Some(SystemInstruction {
-                parts: vec![GeminiPart {
-                    text: system.to_string(),
-                }],
+                parts: vec![GeminiPart::text(system)],
             })
         } else {
             None
         };
 
-        // User content goes in contents array
-        let contents = vec![GeminiContent {
-            parts: vec![GeminiPart {
-                text: user.to_string(),
-            }],
-            role: None,
-        }];
+        // User content goes in contents array, images after the prompt
+        let mut parts = vec![GeminiPart::text(user)];
+        parts.extend(images.iter().map(GeminiPart::image));
+        let contents = vec![GeminiContent { parts, role: None }];
 
         let body = GeminiRequest {
             contents,
@@ -137,19 +193,17 @@ impl LLMClient for GeminiClient {
         let response = self
             .client
             .post(&url)
+            .header(API_KEY_HEADER, api_key)
             .header("Content-Type", "application/json")
             .json(&body)
             .send()
             .await
-            .map_err(|e| AppError::LLMError(format!("Request failed: {}", e)))?;
+            .map_err(request_error)?;
 
         if !response.status().is_success() {
             let status = response.status();
             let text = response.text().await.unwrap_or_default();
-            return Err(AppError::LLMError(format!(
-                "API error ({}): {}",
-                status, text
-            )));
+            return Err(api_error(status, &text));
         }
 
         let json: GeminiResponse = response
@@ -157,32 +211,35 @@ impl LLMClient for GeminiClient {
             .await
             .map_err(|e| AppError::LLMError(format!("Failed to parse JSON: {}", e)))?;
 
-        json.candidates
+        let text = json
+            .candidates
             .get(0)
             .and_then(|candidate| candidate.content.parts.get(0))
             .map(|part| part.text.clone())
-            .ok_or_else(|| AppError::LLMError("Invalid response format".to_string()))
+            .ok_or_else(|| AppError::LLMError("Invalid response format".to_string()))?;
+        let usage = json.usage_metadata.map(|usage| TokenUsage {
+            prompt_tokens: usage.prompt_token_count,
+            completion_tokens: usage.candidates_token_count,
+        });
+        Ok(Completion { text, usage })
     }
 
     async fn list_models(&self, config: &LLMConfig) -> Result<Vec<String>> {
         let api_key = Self::api_key(config)?;
         let base_url = config.base_url.trim_end_matches('/');
-        let url = format!("{}?key={}", base_url, api_key);
 
         let response = self
             .client
-            .get(&url)
+            .get(base_url)
+            .header(API_KEY_HEADER, api_key)
             .send()
             .await
-            .map_err(|e| AppError::LLMError(format!("Request failed: {}", e)))?;
+            .map_err(request_error)?;
 
         if !response.status().is_success() {
             let status = response.status();
             let text = response.text().await.unwrap_or_default();
-            return Err(AppError::LLMError(format!(
-                "API error ({}): {}",
-                status, text
-            )));
+            return Err(api_error(status, &text));
         }
 
         let json: GeminiModelsResponse = response
@@ -1,20 +1,84 @@
 pub mod cli_proxy;
 pub mod gemini;
+pub mod ollama;
 pub mod openai;
 pub mod openrouter;
 
-use crate::domain::error::Result;
+use crate::domain::error::{AppError, ErrorCode, Result};
 use crate::domain::llm_config::LLMConfig;
 use crate::domain::llm_config::LLMProvider;
+use crate::infrastructure::config::ConfigService;
+use crate::infrastructure::ollama::OllamaManager;
+use crate::infrastructure::security::redact::{redact_key, redact_secrets};
 use async_trait::async_trait;
+use base64::Engine as _;
 use cli_proxy::CliProxyClient;
 use gemini::GeminiClient;
+use ollama::OllamaClient;
 use openai::OpenAIClient;
 use openrouter::OpenRouterClient;
+use std::sync::Arc;
+
+/// Tokens the provider counted for one call
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+}
+
+/// Generated text plus the token usage, when the provider reports it
+#[derive(Debug, Clone)]
+pub struct Completion {
+    pub text: String,
+    pub usage: Option<TokenUsage>,
+}
+
+/// An image sent along with the user prompt
+#[derive(Debug, Clone)]
+pub struct ImageInput {
+    /// e.g. `image/png`
+    pub mime_type: String,
+    pub data: Vec<u8>,
+}
+
+impl ImageInput {
+    pub fn base64(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(&self.data)
+    }
+
+    pub fn data_url(&self) -> String {
+        format!("data:{};base64,{}", self.mime_type, self.base64())
+    }
+}
 
 #[async_trait]
 pub trait LLMClient {
     async fn generate(&self, config: &LLMConfig, system: &str, user: &str) -> Result<String>;
+
+    /// `generate` for callers that also record token usage
+    async fn complete(&self, config: &LLMConfig, system: &str, user: &str) -> Result<Completion> {
+        let text = self.generate(config, system, user).await?;
+        Ok(Completion { text, usage: None })
+    }
+
+    /// `complete` with images for vision-capable models. Providers that
+    /// can't take images fail unless `images` is empty.
+    async fn complete_with_images(
+        &self,
+        config: &LLMConfig,
+        system: &str,
+        user: &str,
+        images: &[ImageInput],
+    ) -> Result<Completion> {
+        if !images.is_empty() {
+            return Err(AppError::LLMError(format!(
+                "The {} provider does not accept images",
+                config.provider
+            )));
+        }
+        self.complete(config, system, user).await
+    }
+
     async fn list_models(&self, config: &LLMConfig) -> Result<Vec<String>>;
 }
 
@@ -23,6 +87,8 @@ pub struct RouterClient {
     gemini: GeminiClient,
     openrouter: OpenRouterClient,
     cli_proxy: CliProxyClient,
+    ollama: OllamaClient,
+    keys: ConfigService,
 }
 
 impl RouterClient {
@@ -32,27 +98,168 @@ impl RouterClient {
             gemini: GeminiClient::new(),
             openrouter: OpenRouterClient::new(),
             cli_proxy: CliProxyClient::new(),
+            ollama: OllamaClient::new(),
+            keys: ConfigService::new(),
+        }
+    }
+
+    /// Let Ollama requests start the local server when it is down
+    pub fn with_ollama_manager(mut self, manager: Arc<OllamaManager>) -> Self {
+        self.ollama = self.ollama.with_manager(manager);
+        self
+    }
+
+    /// Config with the provider key filled in from the OS keychain when the caller sent none
+    fn with_api_key(&self, config: &LLMConfig) -> LLMConfig {
+        self.keys.with_api_key(config).unwrap_or_else(|err| {
+            tracing::warn!("Keychain lookup failed for {}: {}", config.provider, err);
+            config.clone()
+        })
+    }
+}
+
+/// Transport error without the request URL (which may carry a `?key=` parameter)
+pub(crate) fn request_error(err: reqwest::Error) -> AppError {
+    let unreachable = err.is_connect() || err.is_timeout();
+    let message = format!(
+        "Request failed: {}",
+        redact_secrets(&err.without_url().to_string())
+    );
+    if unreachable {
+        AppError::coded(ErrorCode::NetworkUnavailable, message)
+            .with_hint("Check that the provider is running and its base URL in Settings is right")
+    } else {
+        AppError::LLMError(message)
+    }
+}
+
+/// Non-success response; providers sometimes echo the submitted key back
+pub(crate) fn api_error(status: reqwest::StatusCode, body: &str) -> AppError {
+    let message = format!("API error ({}): {}", status, redact_secrets(body));
+    match status {
+        reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+            AppError::coded(ErrorCode::ProviderUnauthorized, message)
+        }
+        reqwest::StatusCode::TOO_MANY_REQUESTS => {
+            AppError::coded(ErrorCode::ProviderRateLimited, message)
         }
+        _ => AppError::LLMError(message),
     }
 }
 
+/// Text and `usage` of an OpenAI-style chat completion response
+pub(crate) fn chat_completion(json: &serde_json::Value) -> Result<Completion> {
+    let text = json["choices"][0]["message"]["content"]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| AppError::LLMError("Invalid response format".to_string()))?;
+    let usage = json["usage"].as_object().map(|usage| TokenUsage {
+        prompt_tokens: usage
+            .get("prompt_tokens")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0),
+        completion_tokens: usage
+            .get("completion_tokens")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0),
+    });
+    Ok(Completion { text, usage })
+}
+
+/// OpenAI-style user message content: the plain prompt, or text and
+/// `image_url` parts when images are attached
+pub(crate) fn chat_user_content(user: &str, images: &[ImageInput]) -> serde_json::Value {
+    if images.is_empty() {
+        return serde_json::Value::String(user.to_string());
+    }
+    let mut parts = vec![serde_json::json!({ "type": "text", "text": user })];
+    parts.extend(images.iter().map(|image| {
+        serde_json::json!({
+            "type": "image_url",
+            "image_url": { "url": image.data_url() }
+        })
+    }));
+    serde_json::Value::Array(parts)
+}
+
+/// Strip the key in use from any error produced while talking to the provider
+fn scrub_error(err: AppError, config: &LLMConfig) -> AppError {
+    err.map_message(|msg| redact_key(&msg, config.api_key.as_deref()))
+}
+
 #[async_trait]
 impl LLMClient for RouterClient {
     async fn generate(&self, config: &LLMConfig, system: &str, user: &str) -> Result<String> {
-        match config.provider {
+        let config = &self.with_api_key(config);
+        let result = match config.provider {
             LLMProvider::OpenRouter => self.openrouter.generate(config, system, user).await,
             LLMProvider::Gemini => self.gemini.generate(config, system, user).await,
             LLMProvider::CliProxy => self.cli_proxy.generate(config, system, user).await,
+            LLMProvider::Ollama => self.ollama.generate(config, system, user).await,
             _ => self.openai.generate(config, system, user).await,
-        }
+        };
+        result.map_err(|err| scrub_error(err, config))
+    }
+
+    async fn complete(&self, config: &LLMConfig, system: &str, user: &str) -> Result<Completion> {
+        let config = &self.with_api_key(config);
+        let result = match config.provider {
+            LLMProvider::OpenRouter => self.openrouter.complete(config, system, user).await,
+            LLMProvider::Gemini => self.gemini.complete(config, system, user).await,
+            LLMProvider::CliProxy => self.cli_proxy.complete(config, system, user).await,
+            LLMProvider::Ollama => self.ollama.complete(config, system, user).await,
+            _ => self.openai.complete(config, system, user).await,
+        };
+        result.map_err(|err| scrub_error(err, config))
+    }
+
+    async fn complete_with_images(
+        &self,
+        config: &LLMConfig,
+        system: &str,
+        user: &str,
+        images: &[ImageInput],
+    ) -> Result<Completion> {
+        let config = &self.with_api_key(config);
+        let result = match config.provider {
+            LLMProvider::OpenRouter => {
+                self.openrouter
+                    .complete_with_images(config, system, user, images)
+                    .await
+            }
+            LLMProvider::Gemini => {
+                self.gemini
+                    .complete_with_images(config, system, user, images)
+                    .await
+            }
+            LLMProvider::CliProxy => {
+                self.cli_proxy
+                    .complete_with_images(config, system, user, images)
+                    .await
+            }
+            LLMProvider::Ollama => {
+                self.ollama
+                    .complete_with_images(config, system, user, images)
+                    .await
+            }
+            _ => {
+                self.openai
+                    .complete_with_images(config, system, user, images)
+                    .await
+            }
+        };
+        result.map_err(|err| scrub_error(err, config))
     }
 
     async fn list_models(&self, config: &LLMConfig) -> Result<Vec<String>> {
-        match config.provider {
+        let config = &self.with_api_key(config);
+        let result = match config.provider {
             LLMProvider::OpenRouter => self.openrouter.list_models(config).await,
             LLMProvider::Gemini => self.gemini.list_models(config).await,
             LLMProvider::CliProxy => self.cli_proxy.list_models(config).await,
+            LLMProvider::Ollama => self.ollama.list_models(config).await,
             _ => self.openai.list_models(config).await,
-        }
+        };
+        result.map_err(|err| scrub_error(err, config))
     }
 }
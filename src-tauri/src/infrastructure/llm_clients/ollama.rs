@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use super::openai::OpenAIClient;
+use super::{Completion, ImageInput, LLMClient};
+use crate::domain::error::{AppError, ErrorCode, Result};
+use crate::domain::llm_config::LLMConfig;
+use crate::domain::ollama::ollama_openai_url;
+use crate::infrastructure::ollama::{list_models, OllamaManager};
+use async_trait::async_trait;
+
+/// Chat through Ollama's OpenAI-compatible API and model listing through
+/// its native one. With a manager attached, a request that finds the
+/// server down starts it (when auto-start is on) and is sent again.
+pub struct OllamaClient {
+    openai: OpenAIClient,
+    client: reqwest::Client,
+    manager: Option<Arc<OllamaManager>>,
+}
+
+impl OllamaClient {
+    pub fn new() -> Self {
+        Self {
+            openai: OpenAIClient::new(),
+            client: reqwest::Client::new(),
+            manager: None,
+        }
+    }
+
+    pub fn with_manager(mut self, manager: Arc<OllamaManager>) -> Self {
+        self.manager = Some(manager);
+        self
+    }
+
+    /// Config pointed at the `/v1` endpoint, whether or not the user typed it
+    fn openai_config(config: &LLMConfig) -> LLMConfig {
+        LLMConfig {
+            base_url: ollama_openai_url(&config.base_url),
+            ..config.clone()
+        }
+    }
+
+    /// Whether `err` means the server was down and it has now been started
+    async fn started_after(&self, config: &LLMConfig, err: &AppError) -> bool {
+        let Some(manager) = &self.manager else {
+            return false;
+        };
+        if err.code() != ErrorCode::NetworkUnavailable {
+            return false;
+        }
+        match manager.ensure_running(&config.base_url).await {
+            Ok(started) => {
+                if started {
+                    tracing::info!("Started Ollama for a request to {}", config.base_url);
+                }
+                started
+            }
+            Err(err) => {
+                tracing::warn!("Could not start Ollama: {}", err);
+                false
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl LLMClient for OllamaClient {
+    async fn generate(&self, config: &LLMConfig, system: &str, user: &str) -> Result<String> {
+        self.complete(config, system, user)
+            .await
+            .map(|completion| completion.text)
+    }
+
+    async fn complete(&self, config: &LLMConfig, system: &str, user: &str) -> Result<Completion> {
+        self.complete_with_images(config, system, user, &[]).await
+    }
+
+    async fn complete_with_images(
+        &self,
+        config: &LLMConfig,
+        system: &str,
+        user: &str,
+        images: &[ImageInput],
+    ) -> Result<Completion> {
+        let openai_config = Self::openai_config(config);
+        match self
+            .openai
+            .complete_with_images(&openai_config, system, user, images)
+            .await
+        {
+            Err(err) if self.started_after(config, &err).await => {
+                self.openai
+                    .complete_with_images(&openai_config, system, user, images)
+                    .await
+            }
+            result => result,
+        }
+    }
+
+    async fn list_models(&self, config: &LLMConfig) -> Result<Vec<String>> {
+        let models = match list_models(&self.client, &config.base_url).await {
+            Err(err) if self.started_after(config, &err).await => {
+                list_models(&self.client, &config.base_url).await?
+            }
+            result => result?,
+        };
+        Ok(models.into_iter().map(|model| model.name).collect())
+    }
+}
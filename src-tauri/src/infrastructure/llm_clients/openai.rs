@@ -1,4 +1,6 @@
-use super::LLMClient;
+use super::{
+    api_error, chat_completion, chat_user_content, request_error, Completion, ImageInput, LLMClient,
+};
 use crate::domain::error::{AppError, Result};
 use crate::domain::llm_config::LLMConfig;
 use async_trait::async_trait;
@@ -19,6 +21,22 @@ impl OpenAIClient {
 #[async_trait]
 impl LLMClient for OpenAIClient {
     async fn generate(&self, config: &LLMConfig, system: &str, user: &str) -> Result<String> {
+        self.complete(config, system, user)
+            .await
+            .map(|completion| completion.text)
+    }
+
+    async fn complete(&self, config: &LLMConfig, system: &str, user: &str) -> Result<Completion> {
+        self.complete_with_images(config, system, user, &[]).await
+    }
+
+    async fn complete_with_images(
+        &self,
+        config: &LLMConfig,
+        system: &str,
+        user: &str,
+        images: &[ImageInput],
+    ) -> Result<Completion> {
         let url = if config.base_url.ends_with("/") {
             format!("{}chat/completions", config.base_url)
         } else {
@@ -40,26 +58,19 @@ impl LLMClient for OpenAIClient {
                 },
                 {
                     "role": "user",
-                    "content": user
+                    "content": chat_user_content(user, images)
                 }
             ],
             "max_tokens": config.max_tokens,
             "temperature": config.temperature,
         });
 
-        let response = request
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| AppError::LLMError(format!("Request failed: {}", e)))?;
+        let response = request.json(&body).send().await.map_err(request_error)?;
 
         if !response.status().is_success() {
             let status = response.status();
             let text = response.text().await.unwrap_or_default();
-            return Err(AppError::LLMError(format!(
-                "API error ({}): {}",
-                status, text
-            )));
+            return Err(api_error(status, &text));
         }
 
         let json: serde_json::Value = response
@@ -67,10 +78,7 @@ impl LLMClient for OpenAIClient {
             .await
             .map_err(|e| AppError::LLMError(format!("Failed to parse JSON: {}", e)))?;
 
-        json["choices"][0]["message"]["content"]
-            .as_str()
-            .map(|s| s.to_string())
-            .ok_or_else(|| AppError::LLMError("Invalid response format".to_string()))
+        chat_completion(&json)
     }
 
     async fn list_models(&self, config: &LLMConfig) -> Result<Vec<String>> {
@@ -86,10 +94,7 @@ impl LLMClient for OpenAIClient {
             request = request.bearer_auth(api_key);
         }
 
-        let response = request
-            .send()
-            .await
-            .map_err(|e| AppError::LLMError(format!("Request failed: {}", e)))?;
+        let response = request.send().await.map_err(request_error)?;
 
         if !response.status().is_success() {
             let status = response.status();
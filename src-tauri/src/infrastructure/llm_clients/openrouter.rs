@@ -1,5 +1,7 @@
-use super::LLMClient;
-use crate::domain::error::{AppError, Result};
+use super::{
+    api_error, chat_completion, chat_user_content, request_error, Completion, ImageInput, LLMClient,
+};
+use crate::domain::error::{AppError, ErrorCode, Result};
 use crate::domain::llm_config::LLMConfig;
 use async_trait::async_trait;
 use serde_json::json;
@@ -16,16 +18,32 @@ impl OpenRouterClient {
     }
 
     fn api_key(config: &LLMConfig) -> Result<String> {
-        config
-            .api_key
-            .clone()
-            .ok_or_else(|| AppError::LLMError("Missing API key for OpenRouter".to_string()))
+        config.api_key.clone().ok_or_else(|| {
+            AppError::coded(ErrorCode::ConfigMissing, "Missing API key for OpenRouter")
+                .with_hint("Add the OpenRouter API key in Settings")
+        })
     }
 }
 
 #[async_trait]
 impl LLMClient for OpenRouterClient {
     async fn generate(&self, config: &LLMConfig, system: &str, user: &str) -> Result<String> {
+        self.complete(config, system, user)
+            .await
+            .map(|completion| completion.text)
+    }
+
+    async fn complete(&self, config: &LLMConfig, system: &str, user: &str) -> Result<Completion> {
+        self.complete_with_images(config, system, user, &[]).await
+    }
+
+    async fn complete_with_images(
+        &self,
+        config: &LLMConfig,
+        system: &str,
+        user: &str,
+        images: &[ImageInput],
+    ) -> Result<Completion> {
         let api_key = Self::api_key(config)?;
         let url = if config.base_url.ends_with('/') {
             format!("{}chat/completions", config.base_url)
@@ -42,7 +60,7 @@ impl LLMClient for OpenRouterClient {
                 },
                 {
                     "role": "user",
-                    "content": user
+                    "content": chat_user_content(user, images)
                 }
             ],
             "max_tokens": config.max_tokens,
@@ -56,15 +74,12 @@ impl LLMClient for OpenRouterClient {
             .json(&body)
             .send()
             .await
-            .map_err(|e| AppError::LLMError(format!("Request failed: {}", e)))?;
+            .map_err(request_error)?;
 
         if !response.status().is_success() {
             let status = response.status();
             let text = response.text().await.unwrap_or_default();
-            return Err(AppError::LLMError(format!(
-                "API error ({}): {}",
-                status, text
-            )));
+            return Err(api_error(status, &text));
         }
 
         let json: serde_json::Value = response
@@ -72,10 +87,7 @@ impl LLMClient for OpenRouterClient {
             .await
             .map_err(|e| AppError::LLMError(format!("Failed to parse JSON: {}", e)))?;
 
-        json["choices"][0]["message"]["content"]
-            .as_str()
-            .map(|s| s.to_string())
-            .ok_or_else(|| AppError::LLMError("Invalid response format".to_string()))
+        chat_completion(&json)
     }
 
     async fn list_models(&self, config: &LLMConfig) -> Result<Vec<String>> {
@@ -92,15 +104,12 @@ impl LLMClient for OpenRouterClient {
             .bearer_auth(api_key)
             .send()
             .await
-            .map_err(|e| AppError::LLMError(format!("Request failed: {}", e)))?;
+            .map_err(request_error)?;
 
         if !response.status().is_success() {
             let status = response.status();
             let text = response.text().await.unwrap_or_default();
-            return Err(AppError::LLMError(format!(
-                "API error ({}): {}",
-                status, text
-            )));
+            return Err(api_error(status, &text));
         }
 
         let json: serde_json::Value = response
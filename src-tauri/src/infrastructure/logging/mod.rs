@@ -0,0 +1,292 @@
+// ============================================================
+// LOG SUBSYSTEM
+// ============================================================
+// Every tracing event (including everything sent through
+// `add_log`) is written as a JSON line to rolling files under
+// `<app data>/logs`, filtered by per-source levels from the
+// `logging` settings section.
+
+mod query;
+mod rolling_file;
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+
+use chrono::Local;
+use once_cell::sync::Lazy;
+use serde_json::{Map, Value as JsonValue};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::logging::{LogLevel, LogPage, LogQuery, LogRecord, LogSettings};
+
+use rolling_file::RollingFile;
+
+/// Target used for records that come from `add_log`
+pub const APP_LOG_TARGET: &str = "gadogado";
+
+/// Target of the sink's own failures. Kept out of the log files, so
+/// reporting a failed write cannot re-enter the sink.
+const SINK_LOG_TARGET: &str = "gadogado::log_sink";
+
+/// Records kept in memory until the log directory is known
+const MAX_PENDING_RECORDS: usize = 1000;
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+static SINK: Lazy<LogSink> = Lazy::new(LogSink::new);
+
+/// Process-wide log sink
+pub fn sink() -> &'static LogSink {
+    &SINK
+}
+
+/// Install the console and file layers. Safe to call more than once.
+pub fn init() {
+    let console = tracing_subscriber::fmt::layer().with_filter(EnvFilter::new("info"));
+    let file = FileLogLayer.with_filter(filter_fn(|metadata| {
+        metadata.target() != SINK_LOG_TARGET
+            && to_log_level(metadata.level()) >= sink().most_verbose()
+    }));
+    let _ = tracing_subscriber::registry()
+        .with(console)
+        .with(file)
+        .try_init();
+}
+
+/// Forward an `add_log` entry to tracing, keeping its source label
+pub fn record(level: &str, source: &str, message: &str) {
+    match LogLevel::parse(level) {
+        LogLevel::Trace => tracing::trace!(target: APP_LOG_TARGET, source, "{}", message),
+        LogLevel::Debug => tracing::debug!(target: APP_LOG_TARGET, source, "{}", message),
+        LogLevel::Info => tracing::info!(target: APP_LOG_TARGET, source, "{}", message),
+        LogLevel::Warn => tracing::warn!(target: APP_LOG_TARGET, source, "{}", message),
+        LogLevel::Error => tracing::error!(target: APP_LOG_TARGET, source, "{}", message),
+        LogLevel::Off => {}
+    }
+}
+
+fn to_log_level(level: &Level) -> LogLevel {
+    match *level {
+        Level::TRACE => LogLevel::Trace,
+        Level::DEBUG => LogLevel::Debug,
+        Level::INFO => LogLevel::Info,
+        Level::WARN => LogLevel::Warn,
+        Level::ERROR => LogLevel::Error,
+    }
+}
+
+struct FileState {
+    dir: Option<PathBuf>,
+    file: Option<RollingFile>,
+    pending: VecDeque<String>,
+}
+
+/// Filters records by level and appends them to the current log file
+pub struct LogSink {
+    settings: RwLock<LogSettings>,
+    state: Mutex<FileState>,
+}
+
+impl LogSink {
+    fn new() -> Self {
+        Self {
+            settings: RwLock::new(LogSettings::default()),
+            state: Mutex::new(FileState {
+                dir: None,
+                file: None,
+                pending: VecDeque::new(),
+            }),
+        }
+    }
+
+    pub fn settings(&self) -> LogSettings {
+        self.settings.read().unwrap().clone()
+    }
+
+    fn most_verbose(&self) -> LogLevel {
+        self.settings.read().unwrap().most_verbose()
+    }
+
+    /// Apply new levels and retention limits
+    pub fn configure(&self, settings: LogSettings) {
+        *self.settings.write().unwrap() = settings;
+        if let Err(err) = self.prune() {
+            tracing::warn!(target: SINK_LOG_TARGET, error = %err, "Failed to prune log files");
+        }
+    }
+
+    /// Start writing to `dir`, flushing records logged before startup finished
+    pub fn attach(&self, dir: &Path) -> Result<()> {
+        let file = RollingFile::open(dir, Local::now().date_naive())?;
+        let pending = {
+            let mut state = self.state.lock().unwrap();
+            state.dir = Some(dir.to_path_buf());
+            state.file = Some(file);
+            std::mem::take(&mut state.pending)
+        };
+        for line in pending {
+            self.write_line(&line);
+        }
+        self.prune()?;
+        Ok(())
+    }
+
+    pub fn log_dir(&self) -> Option<PathBuf> {
+        self.state.lock().unwrap().dir.clone()
+    }
+
+    /// Write `record` if its source and level pass the configured thresholds
+    pub fn write(&self, record: &LogRecord) {
+        if !self
+            .settings
+            .read()
+            .unwrap()
+            .allows(&record.source, record.level)
+        {
+            return;
+        }
+        match serde_json::to_string(record) {
+            Ok(line) => self.write_line(&line),
+            Err(err) => {
+                tracing::warn!(target: SINK_LOG_TARGET, error = %err, "Failed to serialize log record")
+            }
+        }
+    }
+
+    fn write_line(&self, line: &str) {
+        let max_file_bytes = self
+            .settings
+            .read()
+            .unwrap()
+            .max_file_size_mb
+            .max(1)
+            .saturating_mul(BYTES_PER_MB);
+
+        let rotated = {
+            let mut state = self.state.lock().unwrap();
+            let Some(file) = state.file.as_mut() else {
+                if state.pending.len() >= MAX_PENDING_RECORDS {
+                    state.pending.pop_front();
+                }
+                state.pending.push_back(line.to_string());
+                return;
+            };
+            match file.write_line(line, Local::now().date_naive(), max_file_bytes) {
+                Ok(rotated) => rotated,
+                Err(err) => {
+                    tracing::warn!(target: SINK_LOG_TARGET, error = %err, "Failed to write log file");
+                    false
+                }
+            }
+        };
+
+        if rotated {
+            if let Err(err) = self.prune() {
+                tracing::warn!(target: SINK_LOG_TARGET, error = %err, "Failed to prune log files");
+            }
+        }
+    }
+
+    /// Delete files beyond the retention limits. Returns the number removed.
+    pub fn prune(&self) -> Result<usize> {
+        let settings = self.settings();
+        let state = self.state.lock().unwrap();
+        let Some(dir) = state.dir.as_ref() else {
+            return Ok(0);
+        };
+        let active = state.file.as_ref().map(RollingFile::path);
+        let removed =
+            rolling_file::prune(dir, &settings, Local::now().date_naive(), active.as_deref())?;
+        Ok(removed)
+    }
+
+    /// Search the log files, newest first
+    pub fn query(&self, query: &LogQuery) -> Result<LogPage> {
+        let dir = self
+            .log_dir()
+            .ok_or_else(|| AppError::Internal("Log directory is not initialized".to_string()))?;
+        query::query_dir(&dir, query)
+    }
+}
+
+/// Tracing layer that turns events into `LogRecord`s for the sink
+struct FileLogLayer;
+
+impl<S: Subscriber> Layer<S> for FileLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = RecordVisitor::default();
+        event.record(&mut visitor);
+
+        sink().write(&LogRecord {
+            timestamp: Local::now().to_rfc3339(),
+            level: to_log_level(metadata.level()),
+            source: visitor
+                .source
+                .unwrap_or_else(|| metadata.target().to_string()),
+            message: visitor.message.unwrap_or_default(),
+            fields: visitor.fields,
+        });
+    }
+}
+
+#[derive(Default)]
+struct RecordVisitor {
+    message: Option<String>,
+    source: Option<String>,
+    fields: Map<String, JsonValue>,
+}
+
+impl RecordVisitor {
+    fn insert(&mut self, field: &Field, value: JsonValue) {
+        match field.name() {
+            "message" => {
+                self.message = Some(match value {
+                    JsonValue::String(text) => text,
+                    other => other.to_string(),
+                })
+            }
+            "source" => {
+                if let JsonValue::String(text) = value {
+                    self.source = Some(text);
+                }
+            }
+            name => {
+                self.fields.insert(name.to_string(), value);
+            }
+        }
+    }
+}
+
+impl Visit for RecordVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, JsonValue::String(value.to_string()));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, JsonValue::Bool(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, JsonValue::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, JsonValue::from(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, JsonValue::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.insert(field, JsonValue::String(format!("{:?}", value)));
+    }
+}
@@ -0,0 +1,144 @@
+// ============================================================
+// LOG QUERY
+// ============================================================
+// Scans the rolling log files newest-first and returns one page
+// of records matching a `LogQuery`.
+
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Duration, FixedOffset};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::logging::{LogLevel, LogPage, LogQuery, LogRecord};
+
+use super::rolling_file::list_log_files;
+
+/// Upper bound on `LogQuery::limit`
+const MAX_PAGE_SIZE: usize = 1000;
+
+struct Filter {
+    sources: Vec<String>,
+    min_level: Option<LogLevel>,
+    since: Option<DateTime<FixedOffset>>,
+    until: Option<DateTime<FixedOffset>>,
+    search: Option<String>,
+}
+
+impl Filter {
+    fn new(query: &LogQuery) -> Result<Self> {
+        Ok(Self {
+            sources: query
+                .sources
+                .iter()
+                .map(|source| source.trim().to_ascii_lowercase())
+                .filter(|source| !source.is_empty())
+                .collect(),
+            min_level: query.min_level,
+            since: parse_bound("since", query.since.as_deref())?,
+            until: parse_bound("until", query.until.as_deref())?,
+            search: query
+                .search
+                .as_deref()
+                .map(str::trim)
+                .filter(|search| !search.is_empty())
+                .map(str::to_lowercase),
+        })
+    }
+
+    fn matches(&self, record: &LogRecord) -> bool {
+        if !self.sources.is_empty()
+            && !self
+                .sources
+                .iter()
+                .any(|source| source.eq_ignore_ascii_case(&record.source))
+        {
+            return false;
+        }
+        if let Some(min_level) = self.min_level {
+            if record.level < min_level {
+                return false;
+            }
+        }
+        if self.since.is_some() || self.until.is_some() {
+            let Ok(timestamp) = DateTime::parse_from_rfc3339(&record.timestamp) else {
+                return false;
+            };
+            if self.since.is_some_and(|since| timestamp < since)
+                || self.until.is_some_and(|until| timestamp >= until)
+            {
+                return false;
+            }
+        }
+        match &self.search {
+            Some(search) => record.message.to_lowercase().contains(search),
+            None => true,
+        }
+    }
+}
+
+fn parse_bound(name: &str, value: Option<&str>) -> Result<Option<DateTime<FixedOffset>>> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            DateTime::parse_from_rfc3339(value).map_err(|e| {
+                AppError::ValidationError(format!(
+                    "Invalid '{}' timestamp '{}': {}",
+                    name, value, e
+                ))
+            })
+        })
+        .transpose()
+}
+
+/// Run `query` against the log files in `dir`
+pub(super) fn query_dir(dir: &Path, query: &LogQuery) -> Result<LogPage> {
+    let filter = Filter::new(query)?;
+    let limit = query.limit.clamp(1, MAX_PAGE_SIZE);
+    let mut records = Vec::new();
+    let mut total = 0;
+
+    // File dates are local; allow a day of slack for offsets in the bounds
+    let first_day = filter
+        .since
+        .map(|since| (since - Duration::days(1)).date_naive());
+    let last_day = filter
+        .until
+        .map(|until| (until + Duration::days(1)).date_naive());
+
+    for file in list_log_files(dir)?.into_iter().rev() {
+        if first_day.is_some_and(|day| file.date < day)
+            || last_day.is_some_and(|day| file.date > day)
+        {
+            continue;
+        }
+
+        let content = match fs::read_to_string(&file.path) {
+            Ok(content) => content,
+            // Deleted by retention between listing and reading
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err.into()),
+        };
+
+        for line in content.lines().rev() {
+            let Ok(record) = serde_json::from_str::<LogRecord>(line) else {
+                continue;
+            };
+            if !filter.matches(&record) {
+                continue;
+            }
+            if total >= query.offset && records.len() < limit {
+                records.push(record);
+            }
+            total += 1;
+        }
+    }
+
+    Ok(LogPage {
+        records,
+        total,
+        offset: query.offset,
+        limit,
+    })
+}
@@ -0,0 +1,252 @@
+// ============================================================
+// ROLLING LOG FILES
+// ============================================================
+// One JSON-lines file per day (gadogado-YYYY-MM-DD.log), with
+// numbered continuation files (gadogado-YYYY-MM-DD.1.log) once a
+// file reaches its size limit, plus age/size based retention.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+
+use crate::domain::logging::LogSettings;
+
+const FILE_PREFIX: &str = "gadogado-";
+const FILE_EXTENSION: &str = "log";
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// A log file on disk
+#[derive(Debug, Clone)]
+pub(super) struct LogFile {
+    pub(super) path: PathBuf,
+    pub(super) date: NaiveDate,
+    pub(super) index: u32,
+    pub(super) size: u64,
+}
+
+/// Append-only writer that rolls over by date and size
+pub(super) struct RollingFile {
+    dir: PathBuf,
+    date: NaiveDate,
+    index: u32,
+    file: File,
+    size: u64,
+}
+
+impl RollingFile {
+    /// Continue today's newest file, or start a new one
+    pub(super) fn open(dir: &Path, today: NaiveDate) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let index = list_log_files(dir)?
+            .into_iter()
+            .filter(|file| file.date == today)
+            .map(|file| file.index)
+            .max()
+            .unwrap_or(0);
+        Self::open_at(dir, today, index)
+    }
+
+    fn open_at(dir: &Path, date: NaiveDate, index: u32) -> io::Result<Self> {
+        let path = dir.join(file_name(date, index));
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            date,
+            index,
+            file,
+            size,
+        })
+    }
+
+    /// Path of the file currently written to
+    pub(super) fn path(&self) -> PathBuf {
+        self.dir.join(file_name(self.date, self.index))
+    }
+
+    /// Append one line. Returns true when a new file was started first.
+    pub(super) fn write_line(
+        &mut self,
+        line: &str,
+        today: NaiveDate,
+        max_file_bytes: u64,
+    ) -> io::Result<bool> {
+        let rotated = if today != self.date {
+            *self = Self::open_at(&self.dir, today, 0)?;
+            true
+        } else if self.size > 0 && self.size + line.len() as u64 + 1 > max_file_bytes {
+            *self = Self::open_at(&self.dir, today, self.index + 1)?;
+            true
+        } else {
+            false
+        };
+
+        let mut bytes = Vec::with_capacity(line.len() + 1);
+        bytes.extend_from_slice(line.as_bytes());
+        bytes.push(b'\n');
+        self.file.write_all(&bytes)?;
+        self.size += bytes.len() as u64;
+        Ok(rotated)
+    }
+}
+
+fn file_name(date: NaiveDate, index: u32) -> String {
+    if index == 0 {
+        format!(
+            "{}{}.{}",
+            FILE_PREFIX,
+            date.format("%Y-%m-%d"),
+            FILE_EXTENSION
+        )
+    } else {
+        format!(
+            "{}{}.{}.{}",
+            FILE_PREFIX,
+            date.format("%Y-%m-%d"),
+            index,
+            FILE_EXTENSION
+        )
+    }
+}
+
+fn parse_file_name(name: &str) -> Option<(NaiveDate, u32)> {
+    let stem = name
+        .strip_prefix(FILE_PREFIX)?
+        .strip_suffix(FILE_EXTENSION)?
+        .strip_suffix('.')?;
+    let (date, index) = match stem.split_once('.') {
+        Some((date, index)) => (date, index.parse().ok()?),
+        None => (stem, 0),
+    };
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    Some((date, index))
+}
+
+/// Log files in `dir`, oldest first
+pub(super) fn list_log_files(dir: &Path) -> io::Result<Vec<LogFile>> {
+    let mut files = Vec::new();
+    if !dir.exists() {
+        return Ok(files);
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let Some((date, index)) = parse_file_name(&name.to_string_lossy()) else {
+            continue;
+        };
+        files.push(LogFile {
+            path: entry.path(),
+            date,
+            index,
+            size: entry.metadata()?.len(),
+        });
+    }
+
+    files.sort_by_key(|file| (file.date, file.index));
+    Ok(files)
+}
+
+/// Delete files past the retention age, then the oldest files until the
+/// directory fits the size budget. `active` is never deleted.
+/// Returns the number of deleted files.
+pub(super) fn prune(
+    dir: &Path,
+    settings: &LogSettings,
+    today: NaiveDate,
+    active: Option<&Path>,
+) -> io::Result<usize> {
+    let files = list_log_files(dir)?;
+    let cutoff = today - chrono::Duration::days(i64::from(settings.retention_days));
+    let budget = settings.max_total_size_mb.saturating_mul(BYTES_PER_MB);
+    let mut total: u64 = files.iter().map(|file| file.size).sum();
+    let mut removed = 0;
+
+    for file in &files {
+        if active == Some(file.path.as_path()) {
+            continue;
+        }
+        if file.date < cutoff || total > budget {
+            fs::remove_file(&file.path)?;
+            total = total.saturating_sub(file.size);
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("logs-{}-{}", std::process::id(), name));
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    #[test]
+    fn test_file_names_round_trip() {
+        assert_eq!(file_name(date(5), 0), "gadogado-2026-03-05.log");
+        assert_eq!(file_name(date(5), 2), "gadogado-2026-03-05.2.log");
+        assert_eq!(
+            parse_file_name("gadogado-2026-03-05.2.log"),
+            Some((date(5), 2))
+        );
+        assert_eq!(
+            parse_file_name("gadogado-2026-03-05.log"),
+            Some((date(5), 0))
+        );
+        assert_eq!(parse_file_name("settings.json"), None);
+    }
+
+    #[test]
+    fn test_rolls_over_by_size_and_date() {
+        let dir = temp_dir("rollover");
+        let mut file = RollingFile::open(&dir, date(1)).unwrap();
+
+        assert!(!file.write_line("first", date(1), 10).unwrap());
+        assert!(file.write_line("second", date(1), 10).unwrap());
+        assert!(file.path().ends_with("gadogado-2026-03-01.1.log"));
+        assert!(file.write_line("third", date(2), 10).unwrap());
+        assert!(file.path().ends_with("gadogado-2026-03-02.log"));
+
+        let reopened = RollingFile::open(&dir, date(1)).unwrap();
+        assert!(reopened.path().ends_with("gadogado-2026-03-01.1.log"));
+        assert_eq!(list_log_files(&dir).unwrap().len(), 3);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_prune_by_age_and_size() {
+        let dir = temp_dir("prune");
+        for day in 1..=5 {
+            fs::write(dir.join(file_name(date(day), 0)), vec![b'x'; 1024]).unwrap();
+        }
+
+        let settings = LogSettings {
+            retention_days: 3,
+            ..LogSettings::default()
+        };
+        // Days 1 and 2 are older than 3 days before day 6
+        assert_eq!(prune(&dir, &settings, date(6), None).unwrap(), 2);
+
+        let settings = LogSettings {
+            max_total_size_mb: 0,
+            ..LogSettings::default()
+        };
+        let active = dir.join(file_name(date(5), 0));
+        assert_eq!(prune(&dir, &settings, date(6), Some(&active)).unwrap(), 2);
+        let remaining = list_log_files(&dir).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].date, date(5));
+        fs::remove_dir_all(&dir).ok();
+    }
+}
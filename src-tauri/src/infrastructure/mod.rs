@@ -5,12 +5,28 @@
 pub mod config_mod;
 pub use config_mod as config;
 pub mod artifact_store;
+pub mod backup;
 pub mod db;
+pub mod desktop_input;
+pub mod diagnostics;
+pub mod embedding_models;
+pub mod execution_providers;
+pub mod git_repo;
 pub mod llm_clients;
+pub mod logging;
+pub mod node_runtime;
+pub mod oauth;
+pub mod ocr_languages;
+pub mod ollama;
 pub mod playwright;
+pub mod rag_connectors;
+pub mod resource_monitor;
 pub mod response;
+pub mod screenshot_annotation;
 pub mod security;
 pub mod storage;
+pub mod storage_migration;
+pub mod test_management;
 
 pub mod bootstrap;
 
@@ -0,0 +1,466 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::Mutex as AsyncMutex;
+use tokio::time::timeout;
+
+use crate::domain::diagnostics::{DiagnosticCategory, DiagnosticCheck, DiagnosticStatus};
+use crate::domain::error::{AppError, ErrorCode, Result};
+use crate::domain::node_runtime::{
+    node_major_version, NodeRuntimeProgress, NodeRuntimeStage, RecorderEnvironment, RuntimeScript,
+    MIN_NODE_MAJOR, PLAYWRIGHT_VERSION,
+};
+
+/// Version checks that do not answer within this are reported as hung
+const PROBE_TIMEOUT: Duration = Duration::from_secs(15);
+
+const NODE_INSTALL_HINT: &str =
+    "Install Node.js 18 or newer from https://nodejs.org and restart the app";
+
+/// Prints the browser executable the installed Playwright launches
+const CHROMIUM_PATH_PROBE: &str =
+    "import { chromium } from 'playwright'; console.log(chromium.executablePath());";
+
+/// Node.js scripts and the Playwright install they run against. Scripts are
+/// copied from the bundled resources into `dir`, next to its `node_modules`,
+/// because ES modules resolve packages from the script's own folder.
+pub struct NodeRuntime {
+    dir: PathBuf,
+    /// Folders searched for the bundled scripts, in order
+    script_dirs: Vec<PathBuf>,
+    /// Held while installing so concurrent first uses wait for one install
+    install: AsyncMutex<()>,
+}
+
+impl NodeRuntime {
+    pub fn new(dir: PathBuf, resource_dir: Option<PathBuf>) -> Self {
+        // Development: src-tauri/resources/scripts; production: bundled resources
+        let mut script_dirs = vec![PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("resources")
+            .join("scripts")];
+        if let Some(resource_dir) = resource_dir {
+            script_dirs.push(resource_dir.join("scripts"));
+        }
+        Self {
+            dir,
+            script_dirs,
+            install: AsyncMutex::new(()),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Bundled copy of `script`
+    pub fn bundled_script(&self, script: RuntimeScript) -> Result<PathBuf> {
+        self.script_dirs
+            .iter()
+            .map(|dir| dir.join(script.file_name()))
+            .find(|path| path.is_file())
+            .ok_or_else(|| {
+                AppError::NotFound(format!(
+                    "Script {} not found. Searched: {}",
+                    script.file_name(),
+                    self.searched_dirs()
+                ))
+            })
+    }
+
+    /// Install whatever is missing and return the runnable copy of `script`
+    pub async fn prepare(
+        &self,
+        script: RuntimeScript,
+        on_progress: &mut (dyn FnMut(NodeRuntimeProgress) + Send),
+    ) -> Result<PathBuf> {
+        let _install = self.install.lock().await;
+
+        let version = node_version().await.map_err(|err| {
+            AppError::coded(ErrorCode::DependencyMissing, err).with_hint(NODE_INSTALL_HINT)
+        })?;
+        check_node_version(&version).map_err(|err| {
+            AppError::coded(ErrorCode::DependencyOutdated, err).with_hint(NODE_INSTALL_HINT)
+        })?;
+        on_progress(progress(
+            NodeRuntimeStage::Node,
+            format!("Using Node.js {}", version),
+        ));
+
+        self.stage_scripts()?;
+
+        if self.playwright_version().as_deref() != Some(PLAYWRIGHT_VERSION) {
+            on_progress(progress(
+                NodeRuntimeStage::Playwright,
+                format!("Installing Playwright {}", PLAYWRIGHT_VERSION),
+            ));
+            self.write_package_json()?;
+            let mut command = Command::new(npm_program());
+            command.args(["install", "--no-audit", "--no-fund", "--loglevel=error"]);
+            self.run_installer(command, NodeRuntimeStage::Playwright, on_progress)
+                .await
+                .map_err(|err| {
+                    AppError::coded(
+                        ErrorCode::DependencyInstallFailed,
+                        format!(
+                            "Failed to install Playwright {} into {}: {}",
+                            PLAYWRIGHT_VERSION,
+                            self.dir.display(),
+                            err
+                        ),
+                    )
+                    .with_hint("Check the network connection and that npm works from a terminal")
+                })?;
+        }
+
+        if self.chromium_executable().await.is_none() {
+            on_progress(progress(
+                NodeRuntimeStage::Browser,
+                "Downloading Chromium for Playwright".to_string(),
+            ));
+            let mut command = Command::new("node");
+            command
+                .arg(self.playwright_dir().join("cli.js"))
+                .args(["install", "chromium"]);
+            self.run_installer(command, NodeRuntimeStage::Browser, on_progress)
+                .await
+                .map_err(|err| {
+                    AppError::coded(
+                        ErrorCode::DependencyInstallFailed,
+                        format!("Failed to download Chromium: {}", err),
+                    )
+                    .with_hint(format!(
+                        "Check the network connection, or run `npx playwright install chromium` in {}",
+                        self.dir.display()
+                    ))
+                })?;
+            if self.chromium_executable().await.is_none() {
+                return Err(AppError::Internal(
+                    "Chromium was downloaded but Playwright still cannot find it".to_string(),
+                ));
+            }
+        }
+
+        on_progress(progress(
+            NodeRuntimeStage::Ready,
+            "Node runtime ready".to_string(),
+        ));
+        Ok(self.script_path(script))
+    }
+
+    /// State of every piece the scripts need, without installing anything
+    pub async fn check(&self) -> RecorderEnvironment {
+        let check = |id: &str, label: &str, status, summary: String| {
+            DiagnosticCheck::new(id, label, DiagnosticCategory::Runtime, status, summary)
+        };
+        let install_hint =
+            "Installed automatically the first time a recording, replay or web capture starts";
+        let mut checks = Vec::new();
+
+        let node_ok = match node_version().await {
+            Ok(version) => match check_node_version(&version) {
+                Ok(()) => {
+                    checks.push(check("node", "Node.js", DiagnosticStatus::Ok, version));
+                    true
+                }
+                Err(err) => {
+                    checks.push(
+                        check("node", "Node.js", DiagnosticStatus::Error, err)
+                            .with_hint(NODE_INSTALL_HINT),
+                    );
+                    false
+                }
+            },
+            Err(err) => {
+                checks.push(
+                    check(
+                        "node",
+                        "Node.js",
+                        DiagnosticStatus::Error,
+                        "Node.js not found".to_string(),
+                    )
+                    .with_detail(err)
+                    .with_hint(NODE_INSTALL_HINT),
+                );
+                false
+            }
+        };
+
+        let missing: Vec<&str> = RuntimeScript::ALL
+            .iter()
+            .filter(|script| self.bundled_script(**script).is_err())
+            .map(|script| script.file_name())
+            .collect();
+        checks.push(if missing.is_empty() {
+            check(
+                "runtime.scripts",
+                "Recorder scripts",
+                DiagnosticStatus::Ok,
+                format!("{} scripts bundled", RuntimeScript::ALL.len()),
+            )
+        } else {
+            check(
+                "runtime.scripts",
+                "Recorder scripts",
+                DiagnosticStatus::Error,
+                format!("Missing {}", missing.join(", ")),
+            )
+            .with_detail(format!("Searched: {}", self.searched_dirs()))
+            .with_hint("Reinstall the app; its resources/scripts folder is incomplete")
+        });
+
+        checks.push(match self.playwright_version() {
+            Some(version) if version == PLAYWRIGHT_VERSION => check(
+                "runtime.playwright",
+                "Playwright",
+                DiagnosticStatus::Ok,
+                version,
+            ),
+            Some(version) => check(
+                "runtime.playwright",
+                "Playwright",
+                DiagnosticStatus::Warning,
+                format!("{} installed, {} required", version, PLAYWRIGHT_VERSION),
+            )
+            .with_hint(install_hint),
+            None => check(
+                "runtime.playwright",
+                "Playwright",
+                DiagnosticStatus::Warning,
+                "Not installed yet".to_string(),
+            )
+            .with_detail(self.dir.display().to_string())
+            .with_hint(install_hint),
+        });
+
+        let chromium = if node_ok {
+            self.chromium_executable().await
+        } else {
+            None
+        };
+        checks.push(match chromium {
+            Some(path) => check(
+                "runtime.chromium",
+                "Chromium",
+                DiagnosticStatus::Ok,
+                "Installed".to_string(),
+            )
+            .with_detail(path.display().to_string()),
+            None => check(
+                "runtime.chromium",
+                "Chromium",
+                DiagnosticStatus::Warning,
+                "Not downloaded yet".to_string(),
+            )
+            .with_hint(install_hint),
+        });
+
+        RecorderEnvironment::new(self.dir.display().to_string(), checks)
+    }
+
+    fn searched_dirs(&self) -> String {
+        self.script_dirs
+            .iter()
+            .map(|dir| dir.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn script_path(&self, script: RuntimeScript) -> PathBuf {
+        self.dir.join("scripts").join(script.file_name())
+    }
+
+    /// Copy the bundled scripts into the runtime folder when they changed
+    fn stage_scripts(&self) -> Result<()> {
+        fs::create_dir_all(self.dir.join("scripts"))?;
+        for script in RuntimeScript::ALL {
+            let bundled = fs::read(self.bundled_script(script)?)?;
+            let staged = self.script_path(script);
+            if fs::read(&staged).ok().as_deref() != Some(bundled.as_slice()) {
+                fs::write(&staged, bundled)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_package_json(&self) -> Result<()> {
+        let package = serde_json::json!({
+            "name": "gadogado-node-runtime",
+            "private": true,
+            "type": "module",
+            "dependencies": { "playwright": PLAYWRIGHT_VERSION },
+        });
+        fs::create_dir_all(&self.dir)?;
+        fs::write(
+            self.dir.join("package.json"),
+            serde_json::to_string_pretty(&package).unwrap_or_default(),
+        )?;
+        Ok(())
+    }
+
+    fn playwright_dir(&self) -> PathBuf {
+        self.dir.join("node_modules").join("playwright")
+    }
+
+    /// Version of the Playwright package in the runtime folder
+    fn playwright_version(&self) -> Option<String> {
+        let package = fs::read_to_string(self.playwright_dir().join("package.json")).ok()?;
+        serde_json::from_str::<serde_json::Value>(&package)
+            .ok()?
+            .get("version")?
+            .as_str()
+            .map(str::to_string)
+    }
+
+    /// Browser the runtime's Playwright launches, when it is downloaded
+    async fn chromium_executable(&self) -> Option<PathBuf> {
+        // Nothing to ask without the package
+        self.playwright_version()?;
+        let mut command = Command::new("node");
+        command
+            .args(["--input-type=module", "-e", CHROMIUM_PATH_PROBE])
+            .current_dir(&self.dir)
+            .stdin(Stdio::null())
+            .kill_on_drop(true);
+        let output = timeout(PROBE_TIMEOUT, command.output()).await.ok()?.ok()?;
+        let path = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+        (output.status.success() && path.is_file()).then_some(path)
+    }
+
+    /// Run an install command in the runtime folder, reporting its output
+    /// line by line
+    async fn run_installer(
+        &self,
+        mut command: Command,
+        stage: NodeRuntimeStage,
+        on_progress: &mut (dyn FnMut(NodeRuntimeProgress) + Send),
+    ) -> std::result::Result<(), String> {
+        fs::create_dir_all(&self.dir).map_err(|err| err.to_string())?;
+        let mut child = command
+            .current_dir(&self.dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| err.to_string())?;
+
+        let mut stderr = child.stderr.take();
+        let stderr_task = tokio::spawn(async move {
+            let mut buffer = String::new();
+            if let Some(stderr) = stderr.as_mut() {
+                let _ = stderr.read_to_string(&mut buffer).await;
+            }
+            buffer
+        });
+        if let Some(stdout) = child.stdout.take() {
+            let mut lines = BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let line = line.trim();
+                if !line.is_empty() {
+                    on_progress(progress(stage, line.to_string()));
+                }
+            }
+        }
+
+        let status = child.wait().await.map_err(|err| err.to_string())?;
+        let stderr = stderr_task.await.unwrap_or_default();
+        if status.success() {
+            Ok(())
+        } else {
+            // Installers put the cause first and follow it with advice
+            let cause: Vec<&str> = stderr
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .take(4)
+                .collect();
+            Err(if cause.is_empty() {
+                format!("exited with {}", status)
+            } else {
+                cause.join("; ")
+            })
+        }
+    }
+}
+
+fn progress(stage: NodeRuntimeStage, message: String) -> NodeRuntimeProgress {
+    NodeRuntimeProgress { stage, message }
+}
+
+fn npm_program() -> &'static str {
+    if cfg!(windows) {
+        "npm.cmd"
+    } else {
+        "npm"
+    }
+}
+
+/// `node --version`, e.g. `v20.11.0`
+async fn node_version() -> std::result::Result<String, String> {
+    let mut command = Command::new("node");
+    command
+        .arg("--version")
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+    match timeout(PROBE_TIMEOUT, command.output()).await {
+        Err(_) => Err(format!(
+            "`node --version` did not respond within {}s",
+            PROBE_TIMEOUT.as_secs()
+        )),
+        Ok(Err(err)) => Err(format!("Node.js was not found on PATH ({})", err)),
+        Ok(Ok(output)) if output.status.success() => {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        }
+        Ok(Ok(output)) => Err(format!(
+            "Node.js failed to start: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+    }
+}
+
+fn check_node_version(version: &str) -> std::result::Result<(), String> {
+    match node_major_version(version) {
+        Some(major) if major >= MIN_NODE_MAJOR => Ok(()),
+        Some(_) => Err(format!(
+            "Node.js {} is too old; Playwright needs Node.js {} or newer",
+            version, MIN_NODE_MAJOR
+        )),
+        None => Err(format!("Unrecognized Node.js version '{}'", version)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_node_older_than_minimum() {
+        assert!(check_node_version("v20.11.0").is_ok());
+        assert!(check_node_version("v18.0.0").is_ok());
+        assert!(check_node_version("v16.20.2")
+            .unwrap_err()
+            .contains("too old"));
+        assert!(check_node_version("garbage").is_err());
+    }
+
+    #[test]
+    fn stages_changed_scripts_only() {
+        let dir = std::env::temp_dir().join(format!("node_runtime_test_{}", std::process::id()));
+        let runtime = NodeRuntime::new(dir.clone(), None);
+        runtime.stage_scripts().unwrap();
+        let staged = runtime.script_path(RuntimeScript::Recorder);
+        assert_eq!(
+            fs::read(&staged).unwrap(),
+            fs::read(runtime.bundled_script(RuntimeScript::Recorder).unwrap()).unwrap()
+        );
+
+        fs::write(&staged, "stale").unwrap();
+        runtime.stage_scripts().unwrap();
+        assert_ne!(fs::read_to_string(&staged).unwrap(), "stale");
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,192 @@
+//! OAuth2 token endpoint client for the QA API runner's auth profiles
+
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::domain::error::{AppError, ErrorCode, Result};
+use crate::domain::qa_auth::{OAuthClientAuth, OAuthGrant, OAuthToken, QaAuthProfile};
+use crate::infrastructure::security::redact::redact_secrets;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Longest part of an error response quoted in the error message
+const MAX_ERROR_BODY_CHARS: usize = 500;
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    /// Seconds; some servers send it as a string
+    #[serde(default)]
+    expires_in: Option<serde_json::Value>,
+}
+
+pub struct OAuthClient {
+    http: reqwest::Client,
+}
+
+impl Default for OAuthClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OAuthClient {
+    pub fn new() -> Self {
+        Self {
+            http: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// A new token with the profile's grant. `password` is the user's
+    /// password for the password grant.
+    pub async fn request_token(
+        &self,
+        profile: &QaAuthProfile,
+        client_secret: Option<&str>,
+        password: Option<&str>,
+    ) -> Result<OAuthToken> {
+        let mut form = vec![("grant_type", profile.grant.as_str().to_string())];
+        if profile.grant == OAuthGrant::Password {
+            let password = password.ok_or_else(|| {
+                AppError::coded(
+                    ErrorCode::ConfigMissing,
+                    format!("Password of auth profile '{}' is not set", profile.id),
+                )
+                .with_hint("Save it with the auth profile in Settings")
+            })?;
+            form.push((
+                "username",
+                profile
+                    .username
+                    .as_deref()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string(),
+            ));
+            form.push(("password", password.to_string()));
+        }
+        for (key, value) in [("scope", &profile.scope), ("audience", &profile.audience)] {
+            if let Some(value) = value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+                form.push((key, value.to_string()));
+            }
+        }
+        self.send(profile, client_secret, form).await
+    }
+
+    /// Exchange `refresh_token` for a new token. Servers that do not rotate
+    /// refresh tokens omit it, so the old one is kept.
+    pub async fn refresh_token(
+        &self,
+        profile: &QaAuthProfile,
+        client_secret: Option<&str>,
+        refresh_token: &str,
+    ) -> Result<OAuthToken> {
+        let form = vec![
+            ("grant_type", "refresh_token".to_string()),
+            ("refresh_token", refresh_token.to_string()),
+        ];
+        let mut token = self.send(profile, client_secret, form).await?;
+        if token.refresh_token.is_none() {
+            token.refresh_token = Some(refresh_token.to_string());
+        }
+        Ok(token)
+    }
+
+    async fn send(
+        &self,
+        profile: &QaAuthProfile,
+        client_secret: Option<&str>,
+        mut form: Vec<(&'static str, String)>,
+    ) -> Result<OAuthToken> {
+        let client_id = profile.client_id.trim();
+        let mut request = self
+            .http
+            .post(profile.token_url.trim())
+            .header(reqwest::header::ACCEPT, "application/json");
+        match profile.client_auth {
+            OAuthClientAuth::Body => {
+                form.push(("client_id", client_id.to_string()));
+                if let Some(secret) = client_secret {
+                    form.push(("client_secret", secret.to_string()));
+                }
+            }
+            OAuthClientAuth::Basic => {
+                request = request.basic_auth(client_id, client_secret);
+            }
+        }
+
+        let response = request.form(&form).send().await.map_err(|e| {
+            AppError::coded(
+                ErrorCode::NetworkUnavailable,
+                format!(
+                    "Token request of auth profile '{}' failed: {}",
+                    profile.id,
+                    redact_secrets(&e.without_url().to_string())
+                ),
+            )
+        })?;
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            let body: String = body.trim().chars().take(MAX_ERROR_BODY_CHARS).collect();
+            let message = format!(
+                "Token endpoint of auth profile '{}' answered {}: {}",
+                profile.id,
+                status,
+                redact_secrets(&body)
+            );
+            return Err(match status.as_u16() {
+                400 | 401 | 403 => AppError::coded(ErrorCode::ProviderUnauthorized, message)
+                    .with_hint("Check the client id, secret and password of the auth profile"),
+                _ => AppError::coded(ErrorCode::NetworkUnavailable, message),
+            });
+        }
+        parse_token(&body, chrono::Utc::now().timestamp_millis())
+    }
+}
+
+/// The token in a token endpoint response received at `now`
+fn parse_token(body: &str, now: i64) -> Result<OAuthToken> {
+    let response: TokenResponse = serde_json::from_str(body).map_err(|e| {
+        AppError::ParseError(format!("Token endpoint returned no access token: {}", e))
+    })?;
+    let expires_in = response.expires_in.and_then(|value| match value {
+        serde_json::Value::Number(number) => number.as_i64(),
+        serde_json::Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    });
+    Ok(OAuthToken {
+        access_token: response.access_token,
+        refresh_token: response.refresh_token.filter(|token| !token.is_empty()),
+        expires_at: expires_in.map(|seconds| now + seconds.max(0) * 1000),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_token_response() {
+        let token = parse_token(
+            r#"{"access_token":"abc","token_type":"Bearer","expires_in":"3600","refresh_token":"r1"}"#,
+            1_000,
+        )
+        .unwrap();
+        assert_eq!(token.access_token, "abc");
+        assert_eq!(token.refresh_token.as_deref(), Some("r1"));
+        assert_eq!(token.expires_at, Some(3_601_000));
+
+        let no_expiry = parse_token(r#"{"access_token":"abc","refresh_token":""}"#, 0).unwrap();
+        assert_eq!(no_expiry.expires_at, None);
+        assert_eq!(no_expiry.refresh_token, None);
+
+        assert!(parse_token(r#"{"error":"invalid_client"}"#, 0).is_err());
+    }
+}
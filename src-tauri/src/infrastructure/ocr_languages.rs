@@ -0,0 +1,337 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use reqwest::header::USER_AGENT;
+use serde::Deserialize;
+use sha1::{Digest, Sha1};
+use tokio::io::AsyncWriteExt;
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::ocr_languages::{ocr_language_name, OcrLanguage, OCR_LANGUAGE_CATALOG};
+use crate::infrastructure::embedding_models::DownloadGuard;
+
+const TRAINEDDATA_EXTENSION: &str = "traineddata";
+
+/// Fast integer models, the same flavour as the bundled `eng` and `ind`
+const TESSDATA_CONTENTS_URL: &str =
+    "https://api.github.com/repos/tesseract-ocr/tessdata_fast/contents";
+
+/// Entry of the GitHub `contents` API for one file
+#[derive(Debug, Deserialize)]
+struct GithubContent {
+    size: u64,
+
+    /// Git blob id of the file
+    sha: String,
+    download_url: Option<String>,
+}
+
+/// Tesseract language data: the bundled tessdata folder plus the
+/// languages downloaded into a writable one
+pub struct OcrLanguageStore {
+    dir: PathBuf,
+    bundled: Option<PathBuf>,
+    client: reqwest::Client,
+    /// Languages being downloaded right now
+    downloads: Mutex<HashSet<String>>,
+}
+
+impl OcrLanguageStore {
+    pub fn new(dir: PathBuf, bundled: Option<PathBuf>) -> Self {
+        Self {
+            bundled: bundled.filter(|bundled| *bundled != dir),
+            dir,
+            client: reqwest::Client::new(),
+            downloads: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Folder Tesseract reads: the download folder once it holds a language,
+    /// otherwise the bundled one
+    pub fn tessdata_dir(&self) -> Option<PathBuf> {
+        if !traineddata_files(&self.dir).is_empty() {
+            Some(self.dir.clone())
+        } else {
+            self.bundled.clone()
+        }
+    }
+
+    /// Point `TESSDATA_PREFIX` at the download folder when languages were
+    /// downloaded in an earlier run, topping it up with the bundled files
+    pub fn activate(&self) -> Result<Option<PathBuf>> {
+        if traineddata_files(&self.dir).is_empty() {
+            return Ok(None);
+        }
+        if let Some(bundled) = &self.bundled {
+            copy_missing(bundled, &self.dir)?;
+        }
+        std::env::set_var("TESSDATA_PREFIX", &self.dir);
+        Ok(Some(self.dir.clone()))
+    }
+
+    /// Installed languages followed by the catalog languages that can be downloaded
+    pub fn list(&self) -> Vec<OcrLanguage> {
+        let bundled = self
+            .bundled
+            .as_deref()
+            .map(traineddata_files)
+            .unwrap_or_default();
+        let mut installed = traineddata_files(&self.dir);
+        for (code, size) in &bundled {
+            installed.entry(code.clone()).or_insert(*size);
+        }
+
+        let mut languages: Vec<OcrLanguage> = installed
+            .iter()
+            .map(|(code, size)| OcrLanguage {
+                code: code.clone(),
+                name: ocr_language_name(code).map(str::to_string),
+                installed: true,
+                bundled: bundled.contains_key(code),
+                size_bytes: Some(*size),
+            })
+            .collect();
+        languages.extend(
+            OCR_LANGUAGE_CATALOG
+                .iter()
+                .filter(|(code, _)| !installed.contains_key(*code))
+                .map(|(code, name)| OcrLanguage {
+                    code: code.to_string(),
+                    name: Some(name.to_string()),
+                    installed: false,
+                    bundled: false,
+                    size_bytes: None,
+                }),
+        );
+        languages
+    }
+
+    /// `-l` argument for `languages`, checked against the installed language data
+    pub fn language_arg(&self, languages: &[String]) -> Result<String> {
+        let codes = normalize_languages(languages)?;
+        if codes.is_empty() {
+            return Err(AppError::ValidationError(
+                "Pick at least one OCR language".to_string(),
+            ));
+        }
+        // A system Tesseract without a known tessdata folder is trusted as is
+        if let Some(dir) = self.tessdata_dir() {
+            let installed = traineddata_files(&dir);
+            let missing: Vec<&str> = codes
+                .iter()
+                .map(String::as_str)
+                .filter(|code| !installed.contains_key(*code))
+                .collect();
+            if !missing.is_empty() {
+                return Err(AppError::ValidationError(format!(
+                    "OCR language data not installed: {}; download it first",
+                    missing.join(", ")
+                )));
+            }
+        }
+        Ok(codes.join("+"))
+    }
+
+    /// Download the language data for `code`, verify it against the git blob
+    /// id GitHub publishes and make the download folder the active tessdata
+    pub async fn download(&self, code: &str) -> Result<OcrLanguage> {
+        validate_code(code)?;
+        let _guard = DownloadGuard::claim(&self.downloads, code)?;
+        let file_name = format!("{}.{}", code, TRAINEDDATA_EXTENSION);
+
+        let url = format!("{}/{}", TESSDATA_CONTENTS_URL, file_name);
+        let response = self
+            .client
+            .get(&url)
+            .header(USER_AGENT, "gadogado")
+            .send()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to look up {}: {}", file_name, e)))?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(AppError::NotFound(format!(
+                "No OCR language data for '{}'",
+                code
+            )));
+        }
+        if !response.status().is_success() {
+            return Err(AppError::Internal(format!(
+                "Failed to look up {}: HTTP {}",
+                file_name,
+                response.status()
+            )));
+        }
+        let content: GithubContent = response.json().await.map_err(|e| {
+            AppError::ParseError(format!("Invalid listing for {}: {}", file_name, e))
+        })?;
+        let download_url = content
+            .download_url
+            .clone()
+            .ok_or_else(|| AppError::NotFound(format!("{} has no download link", file_name)))?;
+
+        fs::create_dir_all(&self.dir)?;
+        let partial = self.dir.join(format!("{}.partial", file_name));
+        if let Err(err) = self
+            .download_file(&download_url, &partial, &file_name, &content)
+            .await
+        {
+            let _ = fs::remove_file(&partial);
+            return Err(err);
+        }
+
+        // Tesseract reads a single folder, so the bundled languages move along
+        if let Some(bundled) = &self.bundled {
+            copy_missing(bundled, &self.dir)?;
+        }
+        fs::rename(&partial, self.dir.join(&file_name))?;
+        std::env::set_var("TESSDATA_PREFIX", &self.dir);
+
+        Ok(OcrLanguage {
+            code: code.to_string(),
+            name: ocr_language_name(code).map(str::to_string),
+            installed: true,
+            bundled: false,
+            size_bytes: Some(content.size),
+        })
+    }
+
+    async fn download_file(
+        &self,
+        url: &str,
+        destination: &Path,
+        file_name: &str,
+        expected: &GithubContent,
+    ) -> Result<()> {
+        let mut response =
+            self.client.get(url).send().await.map_err(|e| {
+                AppError::Internal(format!("Failed to download {}: {}", file_name, e))
+            })?;
+        if !response.status().is_success() {
+            return Err(AppError::Internal(format!(
+                "Failed to download {}: HTTP {}",
+                file_name,
+                response.status()
+            )));
+        }
+
+        let mut output = tokio::fs::File::create(destination).await?;
+        let mut git = Sha1::new();
+        git.update(format!("blob {}\0", expected.size).as_bytes());
+        let mut bytes = 0u64;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to download {}: {}", file_name, e)))?
+        {
+            output.write_all(&chunk).await?;
+            git.update(&chunk);
+            bytes += chunk.len() as u64;
+        }
+        output.flush().await?;
+
+        if bytes != expected.size {
+            return Err(AppError::ValidationError(format!(
+                "{} is {} bytes, expected {}",
+                file_name, bytes, expected.size
+            )));
+        }
+        if hex::encode(git.finalize()) != expected.sha.to_lowercase() {
+            return Err(AppError::ValidationError(format!(
+                "Checksum mismatch for {}",
+                file_name
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Language codes from `languages`, where each entry may itself be a
+/// `+`, `,` or space separated list; duplicates are dropped
+fn normalize_languages(languages: &[String]) -> Result<Vec<String>> {
+    let mut codes: Vec<String> = Vec::new();
+    for code in languages
+        .iter()
+        .flat_map(|entry| entry.split(['+', ',', ' ']))
+        .map(str::trim)
+        .filter(|code| !code.is_empty())
+    {
+        validate_code(code)?;
+        if !codes.iter().any(|known| known == code) {
+            codes.push(code.to_string());
+        }
+    }
+    Ok(codes)
+}
+
+/// Tesseract codes are short names like `eng` or `chi_sim`; anything else
+/// must not reach a file name or the `-l` argument
+fn validate_code(code: &str) -> Result<()> {
+    let valid = !code.is_empty()
+        && code.len() <= 32
+        && code.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_');
+    if !valid {
+        return Err(AppError::ValidationError(format!(
+            "Invalid OCR language code '{}'",
+            code
+        )));
+    }
+    Ok(())
+}
+
+/// `.traineddata` files directly in `dir`, by language code, with their size
+fn traineddata_files(dir: &Path) -> BTreeMap<String, u64> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return BTreeMap::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != TRAINEDDATA_EXTENSION {
+                return None;
+            }
+            let code = path.file_stem()?.to_str()?.to_string();
+            let size = entry.metadata().ok().filter(|meta| meta.is_file())?.len();
+            Some((code, size))
+        })
+        .collect()
+}
+
+/// Copy what `from` has and `to` lacks, including `configs/` and other subfolders
+fn copy_missing(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_missing(&entry.path(), &target)?;
+        } else if !target.exists() {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn splits_and_deduplicates_language_lists() {
+        let codes = normalize_languages(&strings(&["eng+jpn", "chi_sim, eng", " "])).unwrap();
+        assert_eq!(codes, vec!["eng", "jpn", "chi_sim"]);
+    }
+
+    #[test]
+    fn rejects_codes_that_could_escape_the_tessdata_folder() {
+        assert!(normalize_languages(&strings(&["../eng"])).is_err());
+        assert!(normalize_languages(&strings(&["eng/x"])).is_err());
+        assert!(validate_code("").is_err());
+        assert!(validate_code("chi_tra").is_ok());
+    }
+}
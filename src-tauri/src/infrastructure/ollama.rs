@@ -0,0 +1,304 @@
+use std::collections::HashSet;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::process::{Child, Command};
+use tokio::sync::Mutex as AsyncMutex;
+use tokio::time::{sleep, timeout, Instant};
+
+use crate::domain::error::{AppError, ErrorCode, Result};
+use crate::domain::ollama::{
+    ollama_host, ollama_server_url, parse_cli_version, OllamaModel, OllamaPullProgress,
+    OllamaSettings, OllamaStatus,
+};
+use crate::infrastructure::config::SettingsStore;
+use crate::infrastructure::embedding_models::DownloadGuard;
+use crate::infrastructure::llm_clients::{api_error, request_error};
+
+/// `ollama --version` that does not answer within this is reported as hung
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Health checks fail fast so a stopped server doesn't stall the UI
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// `ollama serve` usually answers within a second; first runs create keys
+const START_TIMEOUT: Duration = Duration::from_secs(20);
+
+const OLLAMA_INSTALL_HINT: &str =
+    "Install Ollama from https://ollama.com/download or set its path in Settings";
+
+/// Finds the local Ollama install, runs `ollama serve` on demand and pulls
+/// models. Only a server started here can be stopped here; one started by
+/// the Ollama app or a system service is used as is.
+pub struct OllamaManager {
+    client: reqwest::Client,
+    settings: Arc<SettingsStore>,
+    /// `ollama serve` started by the app, killed when stopped or dropped
+    server: AsyncMutex<Option<Child>>,
+    pulls: Mutex<HashSet<String>>,
+}
+
+impl OllamaManager {
+    pub fn new(settings: Arc<SettingsStore>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            settings,
+            server: AsyncMutex::new(None),
+            pulls: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub fn auto_start(&self) -> bool {
+        self.settings.get::<OllamaSettings>().auto_start
+    }
+
+    /// Install, server and model state for `base_url`, without starting anything
+    pub async fn status(&self, base_url: &str) -> OllamaStatus {
+        let (cli_version, install_error) = match self.cli_version().await {
+            Ok(version) => (Some(version), None),
+            Err(err) => (None, Some(err.to_string())),
+        };
+        let server_version = self.server_version(base_url).await;
+        let models = match server_version {
+            Some(_) => self.list_models(base_url).await.unwrap_or_default(),
+            None => Vec::new(),
+        };
+        OllamaStatus {
+            base_url: ollama_server_url(base_url),
+            installed: cli_version.is_some(),
+            cli_version,
+            install_error,
+            running: server_version.is_some(),
+            server_version,
+            managed: self.managed().await,
+            models,
+        }
+    }
+
+    /// Version of the configured `ollama` binary
+    pub async fn cli_version(&self) -> Result<String> {
+        let binary = self.settings.get::<OllamaSettings>().binary().to_string();
+        let mut command = Command::new(&binary);
+        command
+            .arg("--version")
+            .stdin(Stdio::null())
+            .kill_on_drop(true);
+        let output = match timeout(PROBE_TIMEOUT, command.output()).await {
+            Err(_) => {
+                return Err(AppError::Internal(format!(
+                    "`{} --version` did not respond within {}s",
+                    binary,
+                    PROBE_TIMEOUT.as_secs()
+                )))
+            }
+            Ok(Err(err)) => return Err(AppError::spawn(&binary, err)),
+            Ok(Ok(output)) => output,
+        };
+        // The version goes to stdout, the "no server running" warning to stderr
+        let text = format!(
+            "{}\n{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        parse_cli_version(&text).ok_or_else(|| {
+            AppError::coded(
+                ErrorCode::DependencyMissing,
+                format!("`{}` did not report an Ollama version", binary),
+            )
+            .with_hint(OLLAMA_INSTALL_HINT)
+        })
+    }
+
+    /// Version the server at `base_url` reports, `None` when it does not answer
+    pub async fn server_version(&self, base_url: &str) -> Option<String> {
+        let url = format!("{}/api/version", ollama_server_url(base_url));
+        let response = self
+            .client
+            .get(&url)
+            .timeout(HEALTH_TIMEOUT)
+            .send()
+            .await
+            .ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        let json: serde_json::Value = response.json().await.ok()?;
+        Some(json["version"].as_str().unwrap_or("unknown").to_string())
+    }
+
+    pub async fn list_models(&self, base_url: &str) -> Result<Vec<OllamaModel>> {
+        list_models(&self.client, base_url).await
+    }
+
+    /// Start `ollama serve` listening on `base_url` unless a server already
+    /// answers there; returns the server version
+    pub async fn start(&self, base_url: &str) -> Result<String> {
+        let mut server = self.server.lock().await;
+        if let Some(version) = self.server_version(base_url).await {
+            return Ok(version);
+        }
+        // A server we started earlier that stopped answering is replaced
+        if let Some(mut child) = server.take() {
+            let _ = child.kill().await;
+        }
+
+        let binary = self.settings.get::<OllamaSettings>().binary().to_string();
+        let mut child = Command::new(&binary)
+            .arg("serve")
+            .env("OLLAMA_HOST", ollama_host(base_url))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| AppError::spawn(&binary, err))?;
+
+        let deadline = Instant::now() + START_TIMEOUT;
+        loop {
+            if let Some(version) = self.server_version(base_url).await {
+                *server = Some(child);
+                return Ok(version);
+            }
+            if let Ok(Some(status)) = child.try_wait() {
+                return Err(AppError::coded(
+                    ErrorCode::NetworkUnavailable,
+                    format!("`{} serve` exited with {}", binary, status),
+                )
+                .with_hint(format!(
+                    "Run `{} serve` in a terminal to see why; another program may be using {}",
+                    binary,
+                    ollama_host(base_url)
+                )));
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill().await;
+                return Err(AppError::coded(
+                    ErrorCode::NetworkUnavailable,
+                    format!(
+                        "Ollama did not answer on {} within {}s of starting",
+                        ollama_server_url(base_url),
+                        START_TIMEOUT.as_secs()
+                    ),
+                ));
+            }
+            sleep(Duration::from_millis(250)).await;
+        }
+    }
+
+    /// Stop the server this app started
+    pub async fn stop(&self) -> Result<()> {
+        let Some(mut child) = self.server.lock().await.take() else {
+            return Err(AppError::ValidationError(
+                "Ollama was not started by this app; stop it from the Ollama app or its service"
+                    .to_string(),
+            ));
+        };
+        child
+            .kill()
+            .await
+            .map_err(|err| AppError::Internal(format!("Failed to stop Ollama: {}", err)))
+    }
+
+    /// Whether a server started by this app is still running
+    pub async fn managed(&self) -> bool {
+        let mut server = self.server.lock().await;
+        match server.as_mut().map(|child| child.try_wait()) {
+            Some(Ok(None)) => true,
+            Some(_) => {
+                *server = None;
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Start the server when auto-start is on and nothing answers on `base_url`
+    pub async fn ensure_running(&self, base_url: &str) -> Result<bool> {
+        if !self.auto_start() || self.server_version(base_url).await.is_some() {
+            return Ok(false);
+        }
+        self.start(base_url).await.map(|_| true)
+    }
+
+    /// Pull `model` through the server, reporting each progress line
+    pub async fn pull(
+        &self,
+        base_url: &str,
+        model: &str,
+        on_progress: &mut (dyn FnMut(&OllamaPullProgress) + Send),
+    ) -> Result<()> {
+        let model = model.trim();
+        if model.is_empty() {
+            return Err(AppError::ValidationError(
+                "Enter the name of the model to pull, e.g. llama3".to_string(),
+            ));
+        }
+        let _guard = DownloadGuard::claim(&self.pulls, model)?;
+
+        let url = format!("{}/api/pull", ollama_server_url(base_url));
+        let mut response = self
+            .client
+            .post(&url)
+            .json(&serde_json::json!({ "model": model, "name": model, "stream": true }))
+            .send()
+            .await
+            .map_err(request_error)?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(api_error(status, &text));
+        }
+
+        // Progress arrives as one JSON object per line, split across chunks
+        let mut pending = Vec::new();
+        let mut succeeded = false;
+        while let Some(chunk) = response.chunk().await.map_err(request_error)? {
+            pending.extend_from_slice(&chunk);
+            while let Some(end) = pending.iter().position(|byte| *byte == b'\n') {
+                let line: Vec<u8> = pending.drain(..=end).collect();
+                if let Some(progress) =
+                    OllamaPullProgress::from_line(model, &String::from_utf8_lossy(&line))?
+                {
+                    succeeded |= progress.is_success();
+                    on_progress(&progress);
+                }
+            }
+        }
+        if let Some(progress) =
+            OllamaPullProgress::from_line(model, &String::from_utf8_lossy(&pending))?
+        {
+            succeeded |= progress.is_success();
+            on_progress(&progress);
+        }
+        if !succeeded {
+            return Err(AppError::LLMError(format!(
+                "Ollama stopped pulling {} before it finished",
+                model
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Models pulled into the server at `base_url`, from its native `/api/tags`
+pub(crate) async fn list_models(
+    client: &reqwest::Client,
+    base_url: &str,
+) -> Result<Vec<OllamaModel>> {
+    let url = format!("{}/api/tags", ollama_server_url(base_url));
+    let response = client.get(&url).send().await.map_err(request_error)?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(api_error(status, &text));
+    }
+    let json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| AppError::LLMError(format!("Failed to parse JSON: {}", e)))?;
+    Ok(json["models"]
+        .as_array()
+        .map(|models| models.iter().filter_map(OllamaModel::from_tag).collect())
+        .unwrap_or_default())
+}
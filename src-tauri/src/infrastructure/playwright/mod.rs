@@ -3,12 +3,24 @@
 //! This module provides a Rust wrapper around the Node.js Playwright script
 //! to capture full-page screenshots suitable for OCR processing.
 
-use crate::domain::error::{AppError, Result};
+use crate::domain::error::{AppError, ErrorCode, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
 use tokio::sync::mpsc;
 
+/// Environment variable the capture script reads `CaptureAuth` from, so
+/// credentials stay out of the process arguments
+const CAPTURE_AUTH_ENV: &str = "GADOGADO_CAPTURE_AUTH";
+
+/// Marker the capture script prints before its result JSON
+const RESULT_MARKER: &str = "---RESULT---";
+
+/// Upper bound for pages captured at once by one browser in a batch
+pub const MAX_BATCH_CONCURRENCY: usize = 8;
+
 /// Configuration for Playwright capture
 #[derive(Debug, Clone)]
 pub struct PlaywrightConfig {
@@ -33,6 +45,40 @@ impl Default for PlaywrightConfig {
     }
 }
 
+/// Cookie added to the browser context before the page is opened
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureCookie {
+    pub name: String,
+    pub value: String,
+    /// Defaults to the captured URL's host
+    #[serde(default)]
+    pub domain: Option<String>,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub secure: bool,
+    #[serde(default)]
+    pub http_only: bool,
+}
+
+/// Login state for capturing pages behind authentication
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CaptureAuth {
+    /// Playwright storage-state file, e.g. the one the QA recorder saves
+    pub storage_state: Option<PathBuf>,
+    #[serde(default)]
+    pub cookies: Vec<CaptureCookie>,
+    /// Sent with every request, e.g. `Authorization`
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
+impl CaptureAuth {
+    pub fn is_empty(&self) -> bool {
+        self.storage_state.is_none() && self.cookies.is_empty() && self.headers.is_empty()
+    }
+}
+
 /// Tile information from capture
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CapturedTile {
@@ -100,6 +146,36 @@ pub enum CaptureProgress {
         #[serde(rename = "manifestPath")]
         manifest_path: String,
     },
+    /// Only sent by batch captures, which carry on with the other URLs
+    #[serde(rename = "failed")]
+    Failed { error: String },
+}
+
+/// Progress of one URL of a batch capture
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchCaptureProgress {
+    /// Position of the URL in the batch
+    #[serde(rename = "urlIndex")]
+    pub url_index: usize,
+    #[serde(flatten)]
+    pub progress: CaptureProgress,
+}
+
+/// Outcome of one URL of a batch capture; exactly one of `manifest` and
+/// `error` is set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchCaptureResult {
+    pub url: String,
+    pub output_dir: PathBuf,
+    pub manifest: Option<CaptureManifest>,
+    pub error: Option<String>,
+}
+
+/// Per-URL entry of the batch script's result array
+#[derive(Debug, Deserialize)]
+struct BatchScriptResult {
+    manifest: Option<CaptureManifest>,
+    error: Option<String>,
 }
 
 /// Playwright capture service
@@ -128,12 +204,7 @@ impl PlaywrightCapture {
         let output = Command::new("node")
             .arg("--version")
             .output()
-            .map_err(|e| {
-                AppError::Internal(format!(
-                    "Node.js not found. Please install Node.js to use web OCR capture: {}",
-                    e
-                ))
-            })?;
+            .map_err(|e| AppError::spawn("node", e))?;
 
         if !output.status.success() {
             return Err(AppError::Internal(
@@ -162,16 +233,14 @@ impl PlaywrightCapture {
                 let install = Command::new("npx")
                     .args(["playwright", "install", "chromium"])
                     .output()
-                    .map_err(|e| {
-                        AppError::Internal(format!("Failed to install Playwright: {}", e))
-                    })?;
+                    .map_err(|e| AppError::spawn("npx", e))?;
 
                 if !install.status.success() {
                     let stderr = String::from_utf8_lossy(&install.stderr);
-                    return Err(AppError::Internal(format!(
-                        "Failed to install Playwright chromium: {}",
-                        stderr
-                    )));
+                    return Err(AppError::coded(
+                        ErrorCode::DependencyInstallFailed,
+                        format!("Failed to install Playwright chromium: {}", stderr),
+                    ));
                 }
 
                 Ok(())
@@ -179,32 +248,30 @@ impl PlaywrightCapture {
         }
     }
 
-    /// Capture a URL and return tile paths
+    /// Capture a URL and return tile paths, logged in with `auth` when given
     pub async fn capture_url(
         &self,
         url: &str,
         output_dir: &Path,
+        auth: Option<&CaptureAuth>,
         progress_tx: Option<mpsc::Sender<CaptureProgress>>,
     ) -> Result<CaptureManifest> {
-        // Verify script exists
-        if !self.config.script_path.exists() {
-            return Err(AppError::Internal(format!(
-                "Playwright capture script not found at: {}",
-                self.config.script_path.display()
-            )));
-        }
+        self.ensure_script()?;
 
         // Ensure output directory exists
         std::fs::create_dir_all(output_dir)
             .map_err(|e| AppError::Internal(format!("Failed to create output directory: {}", e)))?;
 
         // Run the capture script
-        let output = Command::new("node")
+        let mut command = Command::new("node");
+        command
             .arg(&self.config.script_path)
             .arg(url)
-            .arg(output_dir)
-            .output()
-            .map_err(|e| AppError::Internal(format!("Failed to run playwright capture: {}", e)))?;
+            .arg(output_dir);
+        if let Some(auth_json) = encode_auth(auth)? {
+            command.env(CAPTURE_AUTH_ENV, auth_json);
+        }
+        let output = command.output().map_err(|e| AppError::spawn("node", e))?;
 
         let stdout = String::from_utf8_lossy(&output.stdout);
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -212,7 +279,7 @@ impl PlaywrightCapture {
         // Parse progress messages and send them
         if let Some(tx) = progress_tx {
             for line in stdout.lines() {
-                if line.starts_with('{') && !line.contains("---RESULT---") {
+                if line.starts_with('{') && !line.contains(RESULT_MARKER) {
                     if let Ok(progress) = serde_json::from_str::<CaptureProgress>(line) {
                         let _ = tx.send(progress).await;
                     }
@@ -235,7 +302,7 @@ impl PlaywrightCapture {
 
         // Find the result JSON after ---RESULT--- marker
         let result_json = stdout
-            .split("---RESULT---")
+            .split(RESULT_MARKER)
             .nth(1)
             .map(|s| s.trim())
             .ok_or_else(|| {
@@ -248,6 +315,143 @@ impl PlaywrightCapture {
         Ok(manifest)
     }
 
+    /// Capture several URLs in one browser, at most `concurrency` pages at a
+    /// time. Each URL gets its own `url-NNN` directory under `output_root`;
+    /// a URL that fails is reported in its result without stopping the rest.
+    pub async fn capture_batch(
+        &self,
+        urls: &[String],
+        output_root: &Path,
+        concurrency: usize,
+        auth: Option<&CaptureAuth>,
+        progress_tx: Option<mpsc::Sender<BatchCaptureProgress>>,
+    ) -> Result<Vec<BatchCaptureResult>> {
+        if urls.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.ensure_script()?;
+
+        std::fs::create_dir_all(output_root)
+            .map_err(|e| AppError::Internal(format!("Failed to create output directory: {}", e)))?;
+
+        let output_dirs: Vec<PathBuf> = (1..=urls.len())
+            .map(|n| output_root.join(format!("url-{:03}", n)))
+            .collect();
+        let job = serde_json::json!({
+            "concurrency": concurrency.clamp(1, MAX_BATCH_CONCURRENCY),
+            "targets": urls
+                .iter()
+                .zip(&output_dirs)
+                .map(|(url, dir)| serde_json::json!({ "url": url, "outputDir": dir }))
+                .collect::<Vec<_>>(),
+        });
+        let job_path = output_root.join("batch.json");
+        std::fs::write(&job_path, job.to_string())
+            .map_err(|e| AppError::Internal(format!("Failed to write capture batch: {}", e)))?;
+
+        let mut command = tokio::process::Command::new("node");
+        command
+            .arg(&self.config.script_path)
+            .arg("--batch")
+            .arg(&job_path)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        if let Some(auth_json) = encode_auth(auth)? {
+            command.env(CAPTURE_AUTH_ENV, auth_json);
+        }
+        let mut child = command.spawn().map_err(|e| AppError::spawn("node", e))?;
+
+        // Drain stderr alongside stdout so a chatty browser can't block the pipe
+        let mut stderr = child.stderr.take();
+        let stderr_task = tokio::spawn(async move {
+            let mut buffer = String::new();
+            if let Some(stderr) = stderr.as_mut() {
+                let _ = stderr.read_to_string(&mut buffer).await;
+            }
+            buffer
+        });
+
+        // Forward progress as each line arrives; everything after the marker
+        // is the result
+        let mut result_json: Option<String> = None;
+        if let Some(stdout) = child.stdout.take() {
+            let mut lines = BufReader::new(stdout).lines();
+            while let Some(line) = lines
+                .next_line()
+                .await
+                .map_err(|e| AppError::Internal(format!("Failed to read capture output: {}", e)))?
+            {
+                if let Some(result) = result_json.as_mut() {
+                    result.push_str(&line);
+                } else if line.trim() == RESULT_MARKER {
+                    result_json = Some(String::new());
+                } else if line.starts_with('{') {
+                    if let (Some(tx), Ok(progress)) = (
+                        progress_tx.as_ref(),
+                        serde_json::from_str::<BatchCaptureProgress>(&line),
+                    ) {
+                        let _ = tx.send(progress).await;
+                    }
+                }
+            }
+        }
+
+        let status = child
+            .wait()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to run playwright capture: {}", e)))?;
+        let stderr = stderr_task.await.unwrap_or_default();
+        let _ = std::fs::remove_file(&job_path);
+
+        if !status.success() {
+            return Err(AppError::Internal(format!(
+                "Playwright batch capture failed: {}",
+                stderr.trim()
+            )));
+        }
+        let results: Vec<BatchScriptResult> = result_json
+            .as_deref()
+            .map(serde_json::from_str)
+            .transpose()
+            .map_err(|e| AppError::Internal(format!("Failed to parse capture manifests: {}", e)))?
+            .ok_or_else(|| {
+                AppError::Internal("No result JSON found in playwright output".to_string())
+            })?;
+        if results.len() != urls.len() {
+            return Err(AppError::Internal(format!(
+                "Playwright batch returned {} results for {} URLs",
+                results.len(),
+                urls.len()
+            )));
+        }
+
+        Ok(urls
+            .iter()
+            .zip(output_dirs)
+            .zip(results)
+            .map(|((url, output_dir), result)| BatchCaptureResult {
+                url: url.clone(),
+                output_dir,
+                error: match (&result.manifest, result.error) {
+                    (None, None) => Some("No manifest was produced".to_string()),
+                    (_, error) => error,
+                },
+                manifest: result.manifest,
+            })
+            .collect())
+    }
+
+    fn ensure_script(&self) -> Result<()> {
+        if !self.config.script_path.exists() {
+            return Err(AppError::Internal(format!(
+                "Playwright capture script not found at: {}",
+                self.config.script_path.display()
+            )));
+        }
+        Ok(())
+    }
+
     /// Get full paths to all captured tiles
     pub fn get_tile_paths(&self, output_dir: &Path, manifest: &CaptureManifest) -> Vec<PathBuf> {
         manifest
@@ -258,6 +462,16 @@ impl PlaywrightCapture {
     }
 }
 
+/// `auth` as the JSON the capture script reads, unless it carries no login
+fn encode_auth(auth: Option<&CaptureAuth>) -> Result<Option<String>> {
+    auth.filter(|auth| !auth.is_empty())
+        .map(|auth| {
+            serde_json::to_string(auth)
+                .map_err(|e| AppError::Internal(format!("Failed to encode capture login: {}", e)))
+        })
+        .transpose()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -269,4 +483,29 @@ mod tests {
         assert_eq!(config.viewport_height, 2000);
         assert_eq!(config.device_scale, 2.0);
     }
+
+    #[test]
+    fn capture_auth_is_empty_without_any_credential() {
+        let mut auth = CaptureAuth::default();
+        assert!(auth.is_empty());
+        auth.headers
+            .insert("Authorization".to_string(), "Bearer token".to_string());
+        assert!(!auth.is_empty());
+    }
+
+    #[test]
+    fn batch_progress_carries_url_index() {
+        let progress: BatchCaptureProgress =
+            serde_json::from_str(r#"{"urlIndex":2,"status":"tile_captured","index":0,"total":3}"#)
+                .unwrap();
+        assert_eq!(progress.url_index, 2);
+        assert!(matches!(
+            progress.progress,
+            CaptureProgress::TileCaptured { index: 0, total: 3 }
+        ));
+
+        let failed: BatchCaptureProgress =
+            serde_json::from_str(r#"{"urlIndex":0,"status":"failed","error":"timeout"}"#).unwrap();
+        assert!(matches!(failed.progress, CaptureProgress::Failed { error } if error == "timeout"));
+    }
 }
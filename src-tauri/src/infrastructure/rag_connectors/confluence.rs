@@ -0,0 +1,252 @@
+//! Confluence REST API: the pages of one space with their ancestors, and
+//! each page's storage format body converted to text.
+
+use scraper::{ElementRef, Html, Node};
+use serde_json::Value as JsonValue;
+
+use super::{http_client, parse_timestamp, read_json, request_error, SourcePage};
+use crate::domain::error::{AppError, ErrorCode, Result};
+use crate::domain::rag_connectors::{ConnectorKind, RagConnector};
+
+const KIND: ConnectorKind = ConnectorKind::Confluence;
+const PAGE_SIZE: usize = 50;
+
+/// Elements whose text starts a line of its own
+const BLOCK_TAGS: &[&str] = &[
+    "p",
+    "div",
+    "br",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "li",
+    "tr",
+    "pre",
+    "blockquote",
+];
+
+pub struct ConfluenceClient<'a> {
+    http: reqwest::Client,
+    connector: &'a RagConnector,
+    token: &'a str,
+}
+
+impl<'a> ConfluenceClient<'a> {
+    pub fn new(connector: &'a RagConnector, token: &'a str) -> Self {
+        Self {
+            http: http_client(),
+            connector,
+            token,
+        }
+    }
+
+    /// Current pages of the connector's space
+    pub async fn list_pages(&self) -> Result<Vec<SourcePage>> {
+        let space_key = self.connector.scope.as_deref().ok_or_else(|| {
+            AppError::coded(ErrorCode::ConfigMissing, "Confluence space key is not set")
+        })?;
+
+        let mut pages = Vec::new();
+        let mut start = 0usize;
+        loop {
+            let response = self
+                .request(self.http.get(self.url("rest/api/content")?))
+                .query(&[
+                    ("spaceKey", space_key.to_string()),
+                    ("type", "page".to_string()),
+                    ("status", "current".to_string()),
+                    ("expand", "version,ancestors".to_string()),
+                    ("limit", PAGE_SIZE.to_string()),
+                    ("start", start.to_string()),
+                ])
+                .send()
+                .await
+                .map_err(|e| request_error(KIND, e))?;
+            let body = read_json(KIND, response).await?;
+            let site = body
+                .pointer("/_links/base")
+                .and_then(JsonValue::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| self.base_url().to_string());
+            let results = body
+                .get("results")
+                .and_then(JsonValue::as_array)
+                .cloned()
+                .unwrap_or_default();
+            pages.extend(results.iter().filter_map(|item| listed_page(item, &site)));
+
+            let has_next = body.pointer("/_links/next").is_some();
+            if !has_next || results.len() < PAGE_SIZE {
+                return Ok(pages);
+            }
+            start += results.len();
+        }
+    }
+
+    /// Text of a page's storage format (XHTML) body
+    pub async fn page_text(&self, page_id: &str) -> Result<String> {
+        let response = self
+            .request(
+                self.http
+                    .get(self.url(&format!("rest/api/content/{}", page_id))?),
+            )
+            .query(&[("expand", "body.storage")])
+            .send()
+            .await
+            .map_err(|e| request_error(KIND, e))?;
+        let body = read_json(KIND, response).await?;
+        let storage = body
+            .pointer("/body/storage/value")
+            .and_then(JsonValue::as_str)
+            .unwrap_or_default();
+        Ok(storage_text(storage))
+    }
+
+    fn base_url(&self) -> &str {
+        self.connector
+            .base_url
+            .as_deref()
+            .unwrap_or_default()
+            .trim_end_matches('/')
+    }
+
+    fn url(&self, path: &str) -> Result<String> {
+        if self.base_url().is_empty() {
+            return Err(AppError::coded(
+                ErrorCode::ConfigMissing,
+                "Confluence site URL is not set",
+            ));
+        }
+        Ok(format!("{}/{}", self.base_url(), path))
+    }
+
+    /// Cloud accounts sign in with email and API token; without an email
+    /// the token is a Data Center personal access token
+    fn request(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self.connector.username.as_deref() {
+            Some(username) => builder.basic_auth(username, Some(self.token)),
+            None => builder.bearer_auth(self.token),
+        }
+    }
+}
+
+fn listed_page(item: &JsonValue, site: &str) -> Option<SourcePage> {
+    let id = item.get("id").and_then(JsonValue::as_str)?;
+    let title = item
+        .get("title")
+        .and_then(JsonValue::as_str)
+        .map(str::trim)
+        .filter(|title| !title.is_empty())
+        .unwrap_or("Untitled");
+    let ancestors: Vec<&JsonValue> = item
+        .get("ancestors")
+        .and_then(JsonValue::as_array)
+        .map(|ancestors| ancestors.iter().collect())
+        .unwrap_or_default();
+    Some(SourcePage {
+        external_id: id.to_string(),
+        parent_id: ancestors
+            .last()
+            .and_then(|parent| parent.get("id"))
+            .and_then(JsonValue::as_str)
+            .map(str::to_string),
+        title: title.to_string(),
+        ancestors: ancestors
+            .iter()
+            .filter_map(|ancestor| ancestor.get("title").and_then(JsonValue::as_str))
+            .map(str::to_string)
+            .collect(),
+        url: item
+            .pointer("/_links/webui")
+            .and_then(JsonValue::as_str)
+            .map(|path| format!("{}{}", site.trim_end_matches('/'), path)),
+        last_edited_at: parse_timestamp(item.pointer("/version/when")),
+    })
+}
+
+/// Text of a storage format body, a line per paragraph, heading, list item
+/// or table row; table cells are separated by ` | `
+fn storage_text(storage: &str) -> String {
+    let fragment = Html::parse_fragment(storage);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    collect_text(fragment.root_element(), &mut lines, &mut line);
+    end_line(&mut lines, &mut line);
+    lines.join("\n")
+}
+
+fn collect_text(element: ElementRef<'_>, lines: &mut Vec<String>, line: &mut String) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => line.push_str(text),
+            Node::Element(child_element) => {
+                let name = child_element.name();
+                let block = BLOCK_TAGS.contains(&name);
+                if block {
+                    end_line(lines, line);
+                } else if matches!(name, "td" | "th") && !line.trim().is_empty() {
+                    line.push_str(" | ");
+                }
+                if let Some(child) = ElementRef::wrap(child) {
+                    collect_text(child, lines, line);
+                }
+                if block {
+                    end_line(lines, line);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn end_line(lines: &mut Vec<String>, line: &mut String) {
+    let text = line.split_whitespace().collect::<Vec<_>>().join(" ");
+    if !text.is_empty() {
+        lines.push(text);
+    }
+    line.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn listed_page_keeps_ancestors_and_link() {
+        let item = json!({
+            "id": "42",
+            "title": "Refunds",
+            "version": { "when": "2024-05-01T08:30:00.000Z" },
+            "ancestors": [
+                { "id": "1", "title": "Handbook" },
+                { "id": "7", "title": "Payments" }
+            ],
+            "_links": { "webui": "/spaces/OPS/pages/42/Refunds" }
+        });
+        let page = listed_page(&item, "https://example.atlassian.net/wiki").unwrap();
+        assert_eq!(page.parent_id.as_deref(), Some("7"));
+        assert_eq!(page.path(), "Handbook / Payments / Refunds");
+        assert_eq!(
+            page.url.as_deref(),
+            Some("https://example.atlassian.net/wiki/spaces/OPS/pages/42/Refunds")
+        );
+        assert_eq!(page.last_edited_at, 1_714_552_200);
+    }
+
+    #[test]
+    fn storage_body_becomes_lines_of_text() {
+        let storage = "<h2>Limits</h2><p>Refunds up to <strong>30 days</strong>.</p>\
+                       <ac:structured-macro ac:name=\"info\"><ac:rich-text-body><p>Ask finance</p>\
+                       </ac:rich-text-body></ac:structured-macro>\
+                       <table><tbody><tr><th>Plan</th><th>Days</th></tr>\
+                       <tr><td>Pro</td><td>60</td></tr></tbody></table>";
+        assert_eq!(
+            storage_text(storage),
+            "Limits\nRefunds up to 30 days.\nAsk finance\nPlan | Days\nPro | 60"
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! API clients for the sources RAG connectors import pages from
+
+pub mod confluence;
+pub mod notion;
+
+use std::time::Duration;
+
+use serde_json::Value as JsonValue;
+
+use crate::domain::error::{AppError, ErrorCode, Result};
+use crate::domain::rag_connectors::{ConnectorKind, RagConnector};
+use crate::infrastructure::security::redact::redact_secrets;
+
+use self::confluence::ConfluenceClient;
+use self::notion::NotionClient;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Longest part of an error response quoted in the error message
+const MAX_ERROR_BODY_CHARS: usize = 500;
+
+/// A page as listed by the source, before its content is fetched
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourcePage {
+    pub external_id: String,
+    pub parent_id: Option<String>,
+    pub title: String,
+    /// Titles of the pages above this one, top first
+    pub ancestors: Vec<String>,
+    pub url: Option<String>,
+    /// Unix seconds
+    pub last_edited_at: i64,
+}
+
+impl SourcePage {
+    /// Ancestor titles and the page's own, joined by ` / `
+    pub fn path(&self) -> String {
+        self.ancestors
+            .iter()
+            .chain(std::iter::once(&self.title))
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+}
+
+pub enum ConnectorClient<'a> {
+    Notion(NotionClient<'a>),
+    Confluence(ConfluenceClient<'a>),
+}
+
+impl<'a> ConnectorClient<'a> {
+    pub fn new(connector: &'a RagConnector, token: &'a str) -> Self {
+        match connector.kind {
+            ConnectorKind::Notion => Self::Notion(NotionClient::new(connector, token)),
+            ConnectorKind::Confluence => Self::Confluence(ConfluenceClient::new(connector, token)),
+        }
+    }
+
+    /// Every page in the connector's scope
+    pub async fn list_pages(&self) -> Result<Vec<SourcePage>> {
+        match self {
+            Self::Notion(client) => client.list_pages().await,
+            Self::Confluence(client) => client.list_pages().await,
+        }
+    }
+
+    /// Plain text body of a page
+    pub async fn page_text(&self, page: &SourcePage) -> Result<String> {
+        match self {
+            Self::Notion(client) => client.page_text(&page.external_id).await,
+            Self::Confluence(client) => client.page_text(&page.external_id).await,
+        }
+    }
+}
+
+fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+}
+
+/// Unix seconds of an RFC 3339 timestamp, e.g. `2024-05-01T08:30:00.000Z`
+fn parse_timestamp(value: Option<&JsonValue>) -> i64 {
+    value
+        .and_then(JsonValue::as_str)
+        .and_then(|value| chrono::DateTime::parse_from_rfc3339(value).ok())
+        .map(|time| time.timestamp())
+        .unwrap_or(0)
+}
+
+/// JSON body of a successful response, or the error it reports
+async fn read_json(kind: ConnectorKind, response: reqwest::Response) -> Result<JsonValue> {
+    let status = response.status();
+    let body = response.text().await.map_err(|e| request_error(kind, e))?;
+    if !status.is_success() {
+        return Err(api_error(kind, status, &body));
+    }
+    serde_json::from_str(&body).map_err(|e| {
+        AppError::ParseError(format!(
+            "{} returned an unreadable response: {}",
+            kind.display_name(),
+            e
+        ))
+    })
+}
+
+fn request_error(kind: ConnectorKind, err: reqwest::Error) -> AppError {
+    AppError::coded(
+        ErrorCode::NetworkUnavailable,
+        format!(
+            "{} request failed: {}",
+            kind.display_name(),
+            redact_secrets(&err.without_url().to_string())
+        ),
+    )
+}
+
+fn api_error(kind: ConnectorKind, status: reqwest::StatusCode, body: &str) -> AppError {
+    let body: String = body.trim().chars().take(MAX_ERROR_BODY_CHARS).collect();
+    let message = format!(
+        "{} API error ({}): {}",
+        kind.display_name(),
+        status,
+        redact_secrets(&body)
+    );
+    match status {
+        reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+            AppError::coded(ErrorCode::ProviderUnauthorized, message)
+                .with_hint("Check the connector's API token and that it can read these pages")
+        }
+        reqwest::StatusCode::TOO_MANY_REQUESTS => {
+            AppError::coded(ErrorCode::ProviderRateLimited, message)
+        }
+        reqwest::StatusCode::NOT_FOUND => AppError::NotFound(message),
+        status if status.is_client_error() => AppError::ValidationError(message),
+        _ => AppError::coded(ErrorCode::NetworkUnavailable, message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_joins_ancestors_and_title() {
+        let page = SourcePage {
+            external_id: "3".to_string(),
+            parent_id: Some("2".to_string()),
+            title: "Refunds".to_string(),
+            ancestors: vec!["Handbook".to_string(), "Payments".to_string()],
+            url: None,
+            last_edited_at: 0,
+        };
+        assert_eq!(page.path(), "Handbook / Payments / Refunds");
+    }
+
+    #[test]
+    fn timestamps_parse_to_unix_seconds() {
+        let value = JsonValue::from("2024-05-01T08:30:00.000Z");
+        assert_eq!(parse_timestamp(Some(&value)), 1_714_552_200);
+        assert_eq!(parse_timestamp(None), 0);
+    }
+}
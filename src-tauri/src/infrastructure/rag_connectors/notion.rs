@@ -0,0 +1,375 @@
+//! Notion API: pages shared with the integration are listed through search,
+//! and their text is read block by block.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use serde_json::{json, Value as JsonValue};
+
+use super::{http_client, parse_timestamp, read_json, request_error, SourcePage};
+use crate::domain::error::Result;
+use crate::domain::rag_connectors::{ConnectorKind, RagConnector};
+
+const KIND: ConnectorKind = ConnectorKind::Notion;
+const API_URL: &str = "https://api.notion.com/v1";
+const API_VERSION: &str = "2022-06-28";
+const PAGE_SIZE: usize = 100;
+
+/// Notion allows about three requests a second per integration
+const REQUEST_SPACING: Duration = Duration::from_millis(350);
+
+/// How deep nested blocks (toggles, list items) are followed
+const MAX_BLOCK_DEPTH: usize = 3;
+
+pub struct NotionClient<'a> {
+    http: reqwest::Client,
+    connector: &'a RagConnector,
+    token: &'a str,
+}
+
+impl<'a> NotionClient<'a> {
+    pub fn new(connector: &'a RagConnector, token: &'a str) -> Self {
+        Self {
+            http: http_client(),
+            connector,
+            token,
+        }
+    }
+
+    /// Pages shared with the integration; with a scope, only that page and
+    /// the pages below it
+    pub async fn list_pages(&self) -> Result<Vec<SourcePage>> {
+        let mut results = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut body = json!({
+                "filter": { "property": "object", "value": "page" },
+                "page_size": PAGE_SIZE,
+            });
+            if let Some(cursor) = &cursor {
+                body["start_cursor"] = json!(cursor);
+            }
+            let response = self
+                .request(self.http.post(format!("{}/search", API_URL)))
+                .json(&body)
+                .send()
+                .await
+                .map_err(|e| request_error(KIND, e))?;
+            let page = read_json(KIND, response).await?;
+            if let Some(items) = page.get("results").and_then(JsonValue::as_array) {
+                results.extend(items.iter().filter_map(listed_page));
+            }
+            cursor = page
+                .get("next_cursor")
+                .and_then(JsonValue::as_str)
+                .map(str::to_string);
+            let has_more = page
+                .get("has_more")
+                .and_then(JsonValue::as_bool)
+                .unwrap_or(false);
+            if !has_more || cursor.is_none() {
+                break;
+            }
+            tokio::time::sleep(REQUEST_SPACING).await;
+        }
+        Ok(build_tree(results, self.connector.scope.as_deref()))
+    }
+
+    /// Text of a page's blocks, one block per line
+    pub async fn page_text(&self, page_id: &str) -> Result<String> {
+        let mut lines = Vec::new();
+        self.collect_blocks(page_id.to_string(), 0, &mut lines)
+            .await?;
+        Ok(lines.join("\n"))
+    }
+
+    /// Append the text of a block's children, each followed by its own
+    /// nested blocks
+    fn collect_blocks<'b>(
+        &'b self,
+        block_id: String,
+        depth: usize,
+        lines: &'b mut Vec<String>,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'b>> {
+        Box::pin(async move {
+            for block in self.block_children(&block_id).await? {
+                if let Some(line) = block_text(&block) {
+                    lines.push(format!("{}{}", "  ".repeat(depth), line));
+                }
+                let has_children = block
+                    .get("has_children")
+                    .and_then(JsonValue::as_bool)
+                    .unwrap_or(false);
+                let kind = block.get("type").and_then(JsonValue::as_str).unwrap_or("");
+                // Subpages are imported as pages of their own
+                if !has_children
+                    || depth + 1 >= MAX_BLOCK_DEPTH
+                    || matches!(kind, "child_page" | "child_database")
+                {
+                    continue;
+                }
+                if let Some(id) = block.get("id").and_then(JsonValue::as_str) {
+                    self.collect_blocks(id.to_string(), depth + 1, lines)
+                        .await?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    async fn block_children(&self, block_id: &str) -> Result<Vec<JsonValue>> {
+        let mut blocks = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            tokio::time::sleep(REQUEST_SPACING).await;
+            let mut request = self
+                .request(
+                    self.http
+                        .get(format!("{}/blocks/{}/children", API_URL, block_id)),
+                )
+                .query(&[("page_size", PAGE_SIZE.to_string())]);
+            if let Some(cursor) = &cursor {
+                request = request.query(&[("start_cursor", cursor)]);
+            }
+            let response = request.send().await.map_err(|e| request_error(KIND, e))?;
+            let page = read_json(KIND, response).await?;
+            if let Some(items) = page.get("results").and_then(JsonValue::as_array) {
+                blocks.extend(items.iter().cloned());
+            }
+            cursor = page
+                .get("next_cursor")
+                .and_then(JsonValue::as_str)
+                .map(str::to_string);
+            let has_more = page
+                .get("has_more")
+                .and_then(JsonValue::as_bool)
+                .unwrap_or(false);
+            if !has_more || cursor.is_none() {
+                return Ok(blocks);
+            }
+        }
+    }
+
+    fn request(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        builder
+            .bearer_auth(self.token)
+            .header("Notion-Version", API_VERSION)
+    }
+}
+
+/// Notion ids with or without dashes, in one form
+fn normalize_id(id: &str) -> String {
+    id.trim().replace('-', "").to_lowercase()
+}
+
+/// A search result as a page without ancestors yet; archived pages are left out
+fn listed_page(item: &JsonValue) -> Option<SourcePage> {
+    let archived = ["archived", "in_trash"].iter().any(|flag| {
+        item.get(*flag)
+            .and_then(JsonValue::as_bool)
+            .unwrap_or(false)
+    });
+    if archived {
+        return None;
+    }
+    let id = item.get("id").and_then(JsonValue::as_str)?;
+    let parent = item.get("parent");
+    let parent_id = parent
+        .and_then(|p| p.get("type"))
+        .and_then(JsonValue::as_str)
+        .and_then(|kind| parent?.get(kind))
+        .and_then(JsonValue::as_str)
+        .map(normalize_id);
+    Some(SourcePage {
+        external_id: normalize_id(id),
+        parent_id,
+        title: page_title(item),
+        ancestors: Vec::new(),
+        url: item
+            .get("url")
+            .and_then(JsonValue::as_str)
+            .map(str::to_string),
+        last_edited_at: parse_timestamp(item.get("last_edited_time")),
+    })
+}
+
+/// Plain text of the page's `title` property
+fn page_title(item: &JsonValue) -> String {
+    let title = item
+        .get("properties")
+        .and_then(JsonValue::as_object)
+        .and_then(|properties| {
+            properties
+                .values()
+                .find(|property| property.get("type").and_then(JsonValue::as_str) == Some("title"))
+        })
+        .and_then(|property| property.get("title"))
+        .map(rich_text)
+        .unwrap_or_default();
+    if title.trim().is_empty() {
+        "Untitled".to_string()
+    } else {
+        title.trim().to_string()
+    }
+}
+
+/// Fill in each page's ancestors from the listing and keep the pages in
+/// scope: the scope page and everything below it
+fn build_tree(pages: Vec<SourcePage>, scope: Option<&str>) -> Vec<SourcePage> {
+    let by_id: HashMap<&str, &SourcePage> = pages
+        .iter()
+        .map(|page| (page.external_id.as_str(), page))
+        .collect();
+    let scope = scope.map(normalize_id);
+
+    let mut tree = Vec::new();
+    for page in &pages {
+        let mut chain = Vec::new();
+        let mut in_scope = scope.as_deref().is_none_or(|root| page.external_id == root);
+        let mut parent = page.parent_id.as_deref();
+        while let Some(id) = parent {
+            let Some(ancestor) = by_id.get(id) else {
+                break;
+            };
+            // Guards against a cycle in malformed data
+            if chain.len() > pages.len() {
+                break;
+            }
+            if scope.as_deref() == Some(id) {
+                in_scope = true;
+            }
+            chain.push(ancestor.title.clone());
+            parent = ancestor.parent_id.as_deref();
+        }
+        if in_scope {
+            chain.reverse();
+            tree.push(SourcePage {
+                ancestors: chain,
+                ..page.clone()
+            });
+        }
+    }
+    tree
+}
+
+fn rich_text(value: &JsonValue) -> String {
+    value
+        .as_array()
+        .map(|parts| {
+            parts
+                .iter()
+                .filter_map(|part| part.get("plain_text").and_then(JsonValue::as_str))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// One line of text for a block, marked up like Markdown
+fn block_text(block: &JsonValue) -> Option<String> {
+    let kind = block.get("type").and_then(JsonValue::as_str)?;
+    let content = block.get(kind)?;
+    let text = content.get("rich_text").map(rich_text).unwrap_or_default();
+    let line = match kind {
+        "heading_1" => format!("# {}", text),
+        "heading_2" => format!("## {}", text),
+        "heading_3" => format!("### {}", text),
+        "bulleted_list_item" | "toggle" => format!("- {}", text),
+        "numbered_list_item" => format!("1. {}", text),
+        "to_do" => {
+            let checked = content
+                .get("checked")
+                .and_then(JsonValue::as_bool)
+                .unwrap_or(false);
+            format!("[{}] {}", if checked { "x" } else { " " }, text)
+        }
+        "quote" => format!("> {}", text),
+        "code" => format!("```\n{}\n```", text),
+        "table_row" => content
+            .get("cells")
+            .and_then(JsonValue::as_array)
+            .map(|cells| cells.iter().map(rich_text).collect::<Vec<_>>().join(" | "))
+            .unwrap_or_default(),
+        "child_page" => format!(
+            "Subpage: {}",
+            content
+                .get("title")
+                .and_then(JsonValue::as_str)
+                .unwrap_or("")
+        ),
+        _ => text,
+    };
+    let line = line.trim_end().to_string();
+    (!line.trim().is_empty()).then_some(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(id: &str, parent: Option<&str>, title: &str) -> SourcePage {
+        SourcePage {
+            external_id: id.to_string(),
+            parent_id: parent.map(str::to_string),
+            title: title.to_string(),
+            ancestors: Vec::new(),
+            url: None,
+            last_edited_at: 0,
+        }
+    }
+
+    #[test]
+    fn tree_resolves_ancestors_and_scope() {
+        let pages = vec![
+            page("a", None, "Handbook"),
+            page("b", Some("a"), "Payments"),
+            page("c", Some("b"), "Refunds"),
+            page("d", None, "Other"),
+        ];
+
+        let all = build_tree(pages.clone(), None);
+        assert_eq!(all.len(), 4);
+        assert_eq!(all[2].path(), "Handbook / Payments / Refunds");
+
+        let scoped = build_tree(pages, Some("B"));
+        let ids: Vec<&str> = scoped.iter().map(|p| p.external_id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "c"]);
+    }
+
+    #[test]
+    fn listed_page_reads_title_parent_and_edit_time() {
+        let item = json!({
+            "id": "1A2B-3C",
+            "url": "https://www.notion.so/Refunds-1a2b3c",
+            "last_edited_time": "2024-05-01T08:30:00.000Z",
+            "parent": { "type": "page_id", "page_id": "9F-8E" },
+            "properties": {
+                "title": { "type": "title", "title": [{ "plain_text": "Refunds" }] }
+            }
+        });
+        let page = listed_page(&item).unwrap();
+        assert_eq!(page.external_id, "1a2b3c");
+        assert_eq!(page.parent_id.as_deref(), Some("9f8e"));
+        assert_eq!(page.title, "Refunds");
+        assert_eq!(page.last_edited_at, 1_714_552_200);
+
+        assert!(listed_page(&json!({ "id": "x", "archived": true })).is_none());
+    }
+
+    #[test]
+    fn blocks_render_as_markdown_lines() {
+        let heading = json!({
+            "type": "heading_2",
+            "heading_2": { "rich_text": [{ "plain_text": "Limits" }] }
+        });
+        let todo = json!({
+            "type": "to_do",
+            "to_do": { "rich_text": [{ "plain_text": "Ship" }], "checked": true }
+        });
+        let divider = json!({ "type": "divider", "divider": {} });
+        assert_eq!(block_text(&heading).as_deref(), Some("## Limits"));
+        assert_eq!(block_text(&todo).as_deref(), Some("[x] Ship"));
+        assert_eq!(block_text(&divider), None);
+    }
+}
@@ -0,0 +1,206 @@
+//! Host resource sampling for long-running child processes: CPU and RAM
+//! through the platform APIs, GPUs through `nvidia-smi` when it is installed.
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use tokio::process::Command;
+use tokio::time::timeout;
+
+/// Share of RAM or GPU memory in use at which a resource counts as exhausted
+const EXHAUSTED_FRACTION: f64 = 0.95;
+
+const NVIDIA_SMI_TIMEOUT: Duration = Duration::from_secs(5);
+
+const MIB: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpuUsage {
+    pub index: u32,
+    pub name: String,
+    pub util_percent: Option<f64>,
+    pub mem_used_bytes: Option<u64>,
+    pub mem_total_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HostUsage {
+    pub cpu_percent: f64,
+    pub ram_used_bytes: u64,
+    pub ram_total_bytes: u64,
+    /// Resident memory of the watched process, when it is still alive
+    pub process_rss_bytes: Option<u64>,
+    pub gpus: Vec<GpuUsage>,
+}
+
+impl HostUsage {
+    /// Describes the resources that are nearly used up, if any
+    pub fn exhaustion_warning(&self) -> Option<String> {
+        let mut exhausted = Vec::new();
+        if let Some(percent) = used_percent(self.ram_used_bytes, self.ram_total_bytes) {
+            exhausted.push(format!(
+                "RAM {:.0}% used ({} of {} MiB)",
+                percent,
+                self.ram_used_bytes / MIB,
+                self.ram_total_bytes / MIB
+            ));
+        }
+        for gpu in &self.gpus {
+            let (Some(used), Some(total)) = (gpu.mem_used_bytes, gpu.mem_total_bytes) else {
+                continue;
+            };
+            if let Some(percent) = used_percent(used, total) {
+                exhausted.push(format!(
+                    "GPU {} ({}) memory {:.0}% used ({} of {} MiB)",
+                    gpu.index,
+                    gpu.name,
+                    percent,
+                    used / MIB,
+                    total / MIB
+                ));
+            }
+        }
+        (!exhausted.is_empty()).then(|| format!("Resources exhausted: {}", exhausted.join("; ")))
+    }
+}
+
+/// Percentage used when it is at or above the exhaustion threshold
+fn used_percent(used: u64, total: u64) -> Option<f64> {
+    if total == 0 {
+        return None;
+    }
+    let fraction = used as f64 / total as f64;
+    (fraction >= EXHAUSTED_FRACTION).then_some(fraction * 100.0)
+}
+
+/// Samples the host and one process. CPU usage is measured between calls,
+/// so the first sample reports it as 0.
+pub struct ResourceSampler {
+    system: System,
+    pid: Option<Pid>,
+    /// Cleared once `nvidia-smi` fails, so hosts without it aren't asked again
+    query_gpus: bool,
+}
+
+impl ResourceSampler {
+    pub fn new(pid: Option<u32>) -> Self {
+        Self {
+            system: System::new(),
+            pid: pid.map(Pid::from_u32),
+            query_gpus: true,
+        }
+    }
+
+    pub async fn sample(&mut self) -> HostUsage {
+        self.system.refresh_cpu_usage();
+        self.system.refresh_memory();
+        let process_rss_bytes = self.pid.and_then(|pid| {
+            self.system
+                .refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+            self.system.process(pid).map(|process| process.memory())
+        });
+
+        let gpus = if self.query_gpus {
+            match query_nvidia_smi().await {
+                Some(gpus) => gpus,
+                None => {
+                    self.query_gpus = false;
+                    Vec::new()
+                }
+            }
+        } else {
+            Vec::new()
+        };
+
+        HostUsage {
+            cpu_percent: f64::from(self.system.global_cpu_usage()),
+            ram_used_bytes: self.system.used_memory(),
+            ram_total_bytes: self.system.total_memory(),
+            process_rss_bytes,
+            gpus,
+        }
+    }
+}
+
+/// `None` when `nvidia-smi` is missing or fails, e.g. on hosts without an NVIDIA driver
+async fn query_nvidia_smi() -> Option<Vec<GpuUsage>> {
+    let output = Command::new("nvidia-smi")
+        .args([
+            "--query-gpu=index,name,utilization.gpu,memory.used,memory.total",
+            "--format=csv,noheader,nounits",
+        ])
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let output = timeout(NVIDIA_SMI_TIMEOUT, output).await.ok()?.ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(parse_nvidia_smi(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Lines of `index, name, utilization %, memory used MiB, memory total MiB`.
+/// Fields the GPU doesn't support read `[N/A]` or `[Not Supported]`.
+fn parse_nvidia_smi(output: &str) -> Vec<GpuUsage> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            if fields.len() < 5 {
+                return None;
+            }
+            // GPU names may contain commas, so the numbers are read from the end
+            let count = fields.len();
+            let mib = |field: &str| field.parse::<u64>().ok().map(|value| value * MIB);
+            Some(GpuUsage {
+                index: fields[0].parse().ok()?,
+                name: fields[1..count - 3].join(", "),
+                util_percent: fields[count - 3].parse().ok(),
+                mem_used_bytes: mib(fields[count - 2]),
+                mem_total_bytes: mib(fields[count - 1]),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nvidia_smi_rows_parse() {
+        let output = "0, NVIDIA GeForce RTX 3090, 87, 23000, 24576\n\
+                      1, Tesla T4, [N/A], [N/A], 15360\n";
+        let gpus = parse_nvidia_smi(output);
+        assert_eq!(gpus.len(), 2);
+        assert_eq!(gpus[0].name, "NVIDIA GeForce RTX 3090");
+        assert_eq!(gpus[0].util_percent, Some(87.0));
+        assert_eq!(gpus[0].mem_used_bytes, Some(23000 * MIB));
+        assert_eq!(gpus[1].util_percent, None);
+        assert_eq!(gpus[1].mem_used_bytes, None);
+        assert_eq!(gpus[1].mem_total_bytes, Some(15360 * MIB));
+    }
+
+    #[test]
+    fn warns_only_when_nearly_full() {
+        let mut usage = HostUsage {
+            ram_used_bytes: 8 * 1024 * MIB,
+            ram_total_bytes: 16 * 1024 * MIB,
+            gpus: vec![GpuUsage {
+                index: 0,
+                name: "RTX".to_string(),
+                util_percent: Some(100.0),
+                mem_used_bytes: Some(10 * 1024 * MIB),
+                mem_total_bytes: Some(12 * 1024 * MIB),
+            }],
+            ..HostUsage::default()
+        };
+        assert_eq!(usage.exhaustion_warning(), None);
+
+        usage.gpus[0].mem_used_bytes = Some(12 * 1024 * MIB - 100 * MIB);
+        let warning = usage.exhaustion_warning().unwrap();
+        assert!(warning.contains("GPU 0 (RTX) memory 99% used"), "{warning}");
+        assert!(!warning.contains("RAM"));
+    }
+}
@@ -0,0 +1,55 @@
+use crate::domain::error::{AppError, Result};
+use crate::domain::qa_highlight::{annotated_screenshot_path, highlight_rect, HighlightRect};
+use image::{ImageFormat, Rgba, RgbaImage};
+use std::path::{Path, PathBuf};
+
+const HIGHLIGHT_COLOR: [u8; 3] = [239, 68, 68];
+const BORDER_WIDTH: u32 = 3;
+const FILL_ALPHA: u8 = 40;
+
+/// Writes a copy of a click screenshot with the clicked element outlined.
+/// Returns `None` when the event has nothing to highlight.
+pub fn write_annotated_screenshot(
+    png_bytes: &[u8],
+    screenshot_path: &Path,
+    event_type: &str,
+    meta_json: Option<&str>,
+) -> Result<Option<PathBuf>> {
+    let mut image = image::load_from_memory(png_bytes)
+        .map_err(|e| AppError::Internal(format!("Failed to decode screenshot: {e}")))?
+        .to_rgba8();
+    let Some(rect) = highlight_rect(event_type, meta_json, image.width(), image.height()) else {
+        return Ok(None);
+    };
+
+    draw_highlight(&mut image, rect);
+
+    let target = annotated_screenshot_path(screenshot_path);
+    image
+        .save_with_format(&target, ImageFormat::Png)
+        .map_err(|e| AppError::Internal(format!("Failed to save annotated screenshot: {e}")))?;
+    Ok(Some(target))
+}
+
+fn draw_highlight(image: &mut RgbaImage, rect: HighlightRect) {
+    let right = rect.x + rect.width;
+    let bottom = rect.y + rect.height;
+    for y in rect.y..bottom {
+        for x in rect.x..right {
+            let on_border = x < rect.x + BORDER_WIDTH
+                || x + BORDER_WIDTH >= right
+                || y < rect.y + BORDER_WIDTH
+                || y + BORDER_WIDTH >= bottom;
+            let alpha = if on_border { 255 } else { FILL_ALPHA };
+            blend(image.get_pixel_mut(x, y), alpha);
+        }
+    }
+}
+
+fn blend(pixel: &mut Rgba<u8>, alpha: u8) {
+    let alpha = u16::from(alpha);
+    for (channel, color) in pixel.0.iter_mut().zip(HIGHLIGHT_COLOR) {
+        *channel = ((u16::from(*channel) * (255 - alpha) + u16::from(color) * alpha) / 255) as u8;
+    }
+    pixel.0[3] = 255;
+}
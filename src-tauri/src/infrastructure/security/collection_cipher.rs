@@ -0,0 +1,323 @@
+// ============================================================
+// COLLECTION ENCRYPTION
+// ============================================================
+// Chunk text and embeddings of an encrypted collection are sealed
+// with AES-256-GCM under a key derived from the user's passphrase
+// (PBKDF2-HMAC-SHA256). Every value gets a random nonce, and the
+// collection id is bound in as associated data so a value copied
+// into another collection fails to open.
+//
+// Sealed values name their collection, so readers can pick the key
+// without knowing where a chunk came from:
+//   text:  "enc:v1:" collection_id ":" base64(nonce | ciphertext+tag)
+//   bytes: MAGIC | collection_id (i64 LE) | nonce | ciphertext+tag
+
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::sync::{Arc, RwLock};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::domain::error::{AppError, Result};
+
+pub const KEY_LEN: usize = 32;
+pub const SALT_LEN: usize = 16;
+pub const PBKDF2_ITERATIONS: u32 = 600_000;
+
+const TEXT_PREFIX: &str = "enc:v1:";
+const BYTES_MAGIC: &[u8; 8] = b"GDENC\x00\x00\x01";
+const KEY_CHECK: &[u8] = b"gadogado collection key";
+
+fn random_bytes<const N: usize>() -> Result<[u8; N]> {
+    let mut bytes = [0u8; N];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| AppError::Internal("No secure random source available".to_string()))?;
+    Ok(bytes)
+}
+
+pub fn generate_salt() -> Result<[u8; SALT_LEN]> {
+    random_bytes()
+}
+
+/// Slow by design; run it off the async runtime
+pub fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Result<[u8; KEY_LEN]> {
+    let iterations = NonZeroU32::new(iterations).ok_or_else(|| {
+        AppError::ValidationError("Key derivation needs at least one iteration".to_string())
+    })?;
+    let mut key = [0u8; KEY_LEN];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    Ok(key)
+}
+
+/// Collection a sealed text value belongs to; `None` for plaintext
+pub fn sealed_text_collection(stored: &str) -> Option<i64> {
+    let (id, _) = stored.strip_prefix(TEXT_PREFIX)?.split_once(':')?;
+    id.parse().ok()
+}
+
+/// Collection a sealed byte value belongs to; `None` for plaintext
+pub fn sealed_bytes_collection(stored: &[u8]) -> Option<i64> {
+    let id = stored.strip_prefix(BYTES_MAGIC.as_slice())?.get(..8)?;
+    Some(i64::from_le_bytes(id.try_into().ok()?))
+}
+
+pub struct CollectionCipher {
+    collection_id: i64,
+    key: LessSafeKey,
+}
+
+impl CollectionCipher {
+    pub fn new(collection_id: i64, key: &[u8; KEY_LEN]) -> Result<Self> {
+        let key = UnboundKey::new(&AES_256_GCM, key)
+            .map(LessSafeKey::new)
+            .map_err(|_| AppError::Internal("Invalid collection encryption key".to_string()))?;
+        Ok(Self { collection_id, key })
+    }
+
+    pub fn collection_id(&self) -> i64 {
+        self.collection_id
+    }
+
+    /// nonce | ciphertext+tag
+    fn seal(&self, plain: &[u8]) -> Result<Vec<u8>> {
+        let nonce: [u8; NONCE_LEN] = random_bytes()?;
+        let mut body = plain.to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(self.collection_id.to_le_bytes()),
+                &mut body,
+            )
+            .map_err(|_| AppError::Internal("Failed to encrypt chunk".to_string()))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&body);
+        Ok(sealed)
+    }
+
+    fn open(&self, sealed: &[u8]) -> Result<Vec<u8>> {
+        let corrupt = || {
+            AppError::SecurityError(format!(
+                "Encrypted chunk in collection {} is damaged or uses a different key",
+                self.collection_id
+            ))
+        };
+        if sealed.len() < NONCE_LEN {
+            return Err(corrupt());
+        }
+        let (nonce, body) = sealed.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| corrupt())?;
+        let mut body = body.to_vec();
+        let plain_len = self
+            .key
+            .open_in_place(
+                nonce,
+                Aad::from(self.collection_id.to_le_bytes()),
+                &mut body,
+            )
+            .map_err(|_| corrupt())?
+            .len();
+        body.truncate(plain_len);
+        Ok(body)
+    }
+
+    pub fn encrypt_text(&self, text: &str) -> Result<String> {
+        let sealed = self.seal(text.as_bytes())?;
+        Ok(format!(
+            "{}{}:{}",
+            TEXT_PREFIX,
+            self.collection_id,
+            BASE64.encode(sealed)
+        ))
+    }
+
+    pub fn decrypt_text(&self, stored: &str) -> Result<String> {
+        let sealed = stored
+            .strip_prefix(TEXT_PREFIX)
+            .and_then(|rest| rest.split_once(':'))
+            .filter(|(id, _)| id.parse() == Ok(self.collection_id))
+            .and_then(|(_, encoded)| BASE64.decode(encoded).ok())
+            .ok_or_else(|| {
+                AppError::SecurityError(format!(
+                    "Chunk is not sealed for collection {}",
+                    self.collection_id
+                ))
+            })?;
+        String::from_utf8(self.open(&sealed)?)
+            .map_err(|_| AppError::SecurityError("Decrypted chunk is not valid UTF-8".to_string()))
+    }
+
+    pub fn encrypt_bytes(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        let mut stored = BYTES_MAGIC.to_vec();
+        stored.extend_from_slice(&self.collection_id.to_le_bytes());
+        stored.extend_from_slice(&self.seal(bytes)?);
+        Ok(stored)
+    }
+
+    pub fn decrypt_bytes(&self, stored: &[u8]) -> Result<Vec<u8>> {
+        if sealed_bytes_collection(stored) != Some(self.collection_id) {
+            return Err(AppError::SecurityError(format!(
+                "Embedding is not sealed for collection {}",
+                self.collection_id
+            )));
+        }
+        self.open(&stored[BYTES_MAGIC.len() + 8..])
+    }
+
+    /// Value stored with the collection to recognise the right passphrase
+    pub fn key_check(&self) -> Result<Vec<u8>> {
+        self.encrypt_bytes(KEY_CHECK)
+    }
+
+    pub fn verify(&self, key_check: &[u8]) -> bool {
+        self.decrypt_bytes(key_check)
+            .is_ok_and(|plain| plain == KEY_CHECK)
+    }
+}
+
+/// Encrypted collections known to this process. A collection without a
+/// cipher is locked: its chunks can be neither read nor written.
+#[derive(Default)]
+pub struct CollectionKeys {
+    entries: RwLock<HashMap<i64, Option<Arc<CollectionCipher>>>>,
+}
+
+impl CollectionKeys {
+    pub fn set_locked(&self, collection_id: i64) {
+        self.entries.write().unwrap().insert(collection_id, None);
+    }
+
+    pub fn unlock(&self, cipher: CollectionCipher) {
+        self.entries
+            .write()
+            .unwrap()
+            .insert(cipher.collection_id(), Some(Arc::new(cipher)));
+    }
+
+    /// Forget a collection that is no longer encrypted
+    pub fn remove(&self, collection_id: i64) {
+        self.entries.write().unwrap().remove(&collection_id);
+    }
+
+    pub fn has_encrypted(&self) -> bool {
+        !self.entries.read().unwrap().is_empty()
+    }
+
+    pub fn is_encrypted(&self, collection_id: i64) -> bool {
+        self.entries.read().unwrap().contains_key(&collection_id)
+    }
+
+    pub fn is_unlocked(&self, collection_id: i64) -> bool {
+        matches!(
+            self.entries.read().unwrap().get(&collection_id),
+            Some(Some(_))
+        )
+    }
+
+    /// Cipher for writing into a collection: `None` when it is not
+    /// encrypted, an error when it is locked
+    pub fn cipher(&self, collection_id: i64) -> Result<Option<Arc<CollectionCipher>>> {
+        match self.entries.read().unwrap().get(&collection_id) {
+            None => Ok(None),
+            Some(Some(cipher)) => Ok(Some(cipher.clone())),
+            Some(None) => Err(locked(collection_id)),
+        }
+    }
+
+    fn cipher_for_sealed(&self, collection_id: i64) -> Result<Arc<CollectionCipher>> {
+        self.cipher(collection_id)?.ok_or_else(|| {
+            AppError::SecurityError(format!(
+                "No key for encrypted data of collection {}",
+                collection_id
+            ))
+        })
+    }
+
+    /// Plaintext of a stored chunk; plaintext input passes through
+    pub fn open_text(&self, stored: String) -> Result<String> {
+        match sealed_text_collection(&stored) {
+            Some(collection_id) => self.cipher_for_sealed(collection_id)?.decrypt_text(&stored),
+            None => Ok(stored),
+        }
+    }
+
+    /// Plaintext of a stored embedding; plaintext input passes through
+    pub fn open_bytes(&self, stored: Vec<u8>) -> Result<Vec<u8>> {
+        match sealed_bytes_collection(&stored) {
+            Some(collection_id) => self
+                .cipher_for_sealed(collection_id)?
+                .decrypt_bytes(&stored),
+            None => Ok(stored),
+        }
+    }
+}
+
+fn locked(collection_id: i64) -> AppError {
+    AppError::SecurityError(format!(
+        "Collection {} is encrypted and locked; unlock it with its passphrase",
+        collection_id
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cipher(collection_id: i64, passphrase: &str) -> CollectionCipher {
+        let key = derive_key(passphrase, b"0123456789abcdef", 1_000).unwrap();
+        CollectionCipher::new(collection_id, &key).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_and_passthrough() {
+        let keys = CollectionKeys::default();
+        keys.unlock(cipher(3, "correct horse"));
+        let cipher = keys.cipher(3).unwrap().unwrap();
+
+        let sealed = cipher.encrypt_text("Gaji pokok Rp 12.000.000").unwrap();
+        assert_eq!(sealed_text_collection(&sealed), Some(3));
+        assert!(!sealed.contains("Gaji"));
+        assert_eq!(keys.open_text(sealed).unwrap(), "Gaji pokok Rp 12.000.000");
+
+        let embedding: Vec<u8> = [0.25f32, -1.5]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let sealed = cipher.encrypt_bytes(&embedding).unwrap();
+        assert_eq!(keys.open_bytes(sealed).unwrap(), embedding);
+
+        assert_eq!(keys.open_text("plain".to_string()).unwrap(), "plain");
+        assert_eq!(keys.open_bytes(embedding.clone()).unwrap(), embedding);
+    }
+
+    #[test]
+    fn test_wrong_key_collection_and_locked_are_rejected() {
+        let right = cipher(3, "correct horse");
+        let sealed = right.encrypt_text("secret").unwrap();
+
+        assert!(right.verify(&right.key_check().unwrap()));
+        assert!(!cipher(3, "wrong horse").verify(&right.key_check().unwrap()));
+        assert!(cipher(3, "wrong horse").decrypt_text(&sealed).is_err());
+
+        // Moved into collection 4: the id no longer matches the bound data
+        let moved = sealed.replacen("enc:v1:3:", "enc:v1:4:", 1);
+        assert!(cipher(4, "correct horse").decrypt_text(&moved).is_err());
+
+        let keys = CollectionKeys::default();
+        keys.set_locked(3);
+        assert!(keys.cipher(3).is_err());
+        assert!(keys.open_text(sealed).is_err());
+        assert!(keys.cipher(5).unwrap().is_none());
+    }
+}
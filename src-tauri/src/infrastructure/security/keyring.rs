@@ -32,6 +32,21 @@ impl KeyringManager {
             .map_err(|e| AppError::SecurityError(format!("Failed to get password: {}", e)))
     }
 
+    /// Like `get_secret`, but a missing entry is `Ok(None)` instead of an error
+    pub fn find_secret(&self, key: &str) -> Result<Option<String>> {
+        let entry = Entry::new(&self.service, key)
+            .map_err(|e| AppError::SecurityError(format!("Failed to create entry: {}", e)))?;
+
+        match entry.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(AppError::SecurityError(format!(
+                "Failed to get password: {}",
+                e
+            ))),
+        }
+    }
+
     pub fn delete_secret(&self, key: &str) -> Result<()> {
         let entry = Entry::new(&self.service, key)
             .map_err(|e| AppError::SecurityError(format!("Failed to create entry: {}", e)))?;
@@ -1 +1,3 @@
+pub mod collection_cipher;
 pub mod keyring;
+pub mod redact;
@@ -0,0 +1,79 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Well-known provider key formats (OpenAI/OpenRouter `sk-...`, Google `AIza...`)
+static PROVIDER_KEY_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:sk-[A-Za-z0-9_\-]{16,}|AIza[0-9A-Za-z_\-]{30,})").unwrap());
+
+/// `Authorization: Bearer <token>` style credentials
+static BEARER_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b(bearer\s+)[A-Za-z0-9._~+/=\-]{8,}").unwrap());
+
+/// `?key=...`, `api_key: "..."`, `"apiKey": "..."` and similar assignments
+static KEY_ASSIGNMENT_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?i)(\b(?:api[_\-]?key|x-goog-api-key|access[_\-]?token)["']?\s*[:=]\s*["']?|[?&]key=)[^"'\s&,}]{6,}"#,
+    )
+    .unwrap()
+});
+
+/// Mask anything that looks like an API key or bearer token
+pub fn redact_secrets(text: &str) -> String {
+    let text = PROVIDER_KEY_PATTERN.replace_all(text, REDACTED);
+    let text = BEARER_PATTERN.replace_all(&text, format!("${{1}}{}", REDACTED));
+    KEY_ASSIGNMENT_PATTERN
+        .replace_all(&text, format!("${{1}}{}", REDACTED))
+        .into_owned()
+}
+
+/// Mask a specific secret plus any generic key patterns
+pub fn redact_key(text: &str, key: Option<&str>) -> String {
+    let text = match key.map(str::trim).filter(|key| key.len() >= 4) {
+        Some(key) => text.replace(key, REDACTED),
+        None => text.to_string(),
+    };
+    redact_secrets(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacts_known_key_formats() {
+        let text =
+            "401 for sk-proj-abcdefghijklmnop1234 and AIzaSyA1234567890abcdefghijklmnopqrstu";
+        let redacted = redact_secrets(text);
+        assert!(!redacted.contains("sk-proj"));
+        assert!(!redacted.contains("AIzaSy"));
+        assert_eq!(redacted.matches(REDACTED).count(), 2);
+    }
+
+    #[test]
+    fn test_redacts_headers_and_query_params() {
+        assert_eq!(
+            redact_secrets("Authorization: Bearer abc.def.ghi123"),
+            "Authorization: Bearer [REDACTED]"
+        );
+        assert_eq!(
+            redact_secrets("GET https://host/v1/models?key=secret123456&alt=json"),
+            "GET https://host/v1/models?key=[REDACTED]&alt=json"
+        );
+        assert_eq!(
+            redact_secrets(r#"{"api_key": "local-secret-value"}"#),
+            r#"{"api_key": "[REDACTED]"}"#
+        );
+    }
+
+    #[test]
+    fn test_redact_key_masks_custom_tokens() {
+        let redacted = redact_key(
+            "proxy rejected token zz-custom-9f8e",
+            Some("zz-custom-9f8e"),
+        );
+        assert_eq!(redacted, "proxy rejected token [REDACTED]");
+        assert_eq!(redact_key("no secrets here", None), "no secrets here");
+    }
+}
@@ -1,7 +1,12 @@
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::RwLock;
 use tauri::{AppHandle, Manager};
 
+use crate::domain::settings::StorageSettings;
+use crate::domain::storage::StorageKind;
+use crate::infrastructure::artifact_store::TrainingArtifactLayout;
+
 pub fn resolve_app_data_dir(app_handle: &AppHandle) -> std::io::Result<PathBuf> {
     let app_data_dir = app_handle
         .path()
@@ -11,10 +16,114 @@ pub fn resolve_app_data_dir(app_handle: &AppHandle) -> std::io::Result<PathBuf>
     Ok(app_data_dir)
 }
 
-pub fn ensure_qa_sessions_root(app_data_dir: &Path) -> std::io::Result<PathBuf> {
-    let qa_sessions_dir = app_data_dir.join("qa_sessions");
-    ensure_dir(&qa_sessions_dir)?;
-    Ok(qa_sessions_dir)
+/// A SQLite database the app keeps under its data dir
+pub struct AppDatabase {
+    /// Short identifier: "app", "qa", "rag" or "training"
+    pub key: &'static str,
+    pub label: &'static str,
+    pub path: PathBuf,
+}
+
+pub fn app_databases(app_data_dir: &Path) -> [AppDatabase; 4] {
+    let database = |key: &'static str, label: &'static str, file: &str| AppDatabase {
+        key,
+        label,
+        path: app_data_dir.join(file),
+    };
+    [
+        database("app", "App database", "promptbridge.db"),
+        database("qa", "QA recorder database", "qa_recorder.db"),
+        database("rag", "RAG database", "rag_sense.db"),
+        database("training", "Training database", "training.db"),
+    ]
+}
+
+/// QA session files and training artifacts. Both default to folders under
+/// the app data dir and can be moved elsewhere with `storage_migrate`.
+pub struct StorageLocations {
+    app_data_dir: PathBuf,
+    qa_sessions_dir: RwLock<PathBuf>,
+    training_dir: RwLock<PathBuf>,
+}
+
+impl StorageLocations {
+    pub fn new(app_data_dir: &Path, settings: &StorageSettings) -> Self {
+        let configured = |dir: &Option<String>, default: PathBuf| {
+            dir.as_deref()
+                .map(str::trim)
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from)
+                .unwrap_or(default)
+        };
+        Self {
+            app_data_dir: app_data_dir.to_path_buf(),
+            qa_sessions_dir: RwLock::new(configured(
+                &settings.qa_sessions_dir,
+                Self::default_dir(app_data_dir, StorageKind::QaSessions),
+            )),
+            training_dir: RwLock::new(configured(
+                &settings.training_dir,
+                Self::default_dir(app_data_dir, StorageKind::Training),
+            )),
+        }
+    }
+
+    pub fn default_dir(app_data_dir: &Path, kind: StorageKind) -> PathBuf {
+        match kind {
+            StorageKind::QaSessions => app_data_dir.join("qa_sessions"),
+            StorageKind::Training => app_data_dir.join("training"),
+        }
+    }
+
+    pub fn app_data_dir(&self) -> &Path {
+        &self.app_data_dir
+    }
+
+    pub fn dir(&self, kind: StorageKind) -> PathBuf {
+        match kind {
+            StorageKind::QaSessions => self.qa_sessions_dir.read().unwrap().clone(),
+            StorageKind::Training => self.training_dir.read().unwrap().clone(),
+        }
+    }
+
+    pub fn set_dir(&self, kind: StorageKind, dir: PathBuf) {
+        match kind {
+            StorageKind::QaSessions => *self.qa_sessions_dir.write().unwrap() = dir,
+            StorageKind::Training => *self.training_dir.write().unwrap() = dir,
+        }
+    }
+
+    pub fn qa_sessions_dir(&self) -> PathBuf {
+        self.dir(StorageKind::QaSessions)
+    }
+
+    pub fn ensure_qa_sessions_dir(&self) -> std::io::Result<PathBuf> {
+        let qa_sessions_dir = self.qa_sessions_dir();
+        ensure_dir(&qa_sessions_dir)?;
+        Ok(qa_sessions_dir)
+    }
+
+    pub fn training_layout(&self) -> TrainingArtifactLayout {
+        TrainingArtifactLayout::at(&self.dir(StorageKind::Training))
+    }
+
+    /// Clone of a remote git repository imported into RAG
+    pub fn git_checkout_dir(&self, repo_id: i64) -> PathBuf {
+        self.app_data_dir.join("rag_git").join(repo_id.to_string())
+    }
+
+    /// Settings section for the current locations; defaults are stored as `None`
+    pub fn settings(&self) -> StorageSettings {
+        let custom = |kind: StorageKind| {
+            let dir = self.dir(kind);
+            (dir != Self::default_dir(&self.app_data_dir, kind))
+                .then(|| dir.to_string_lossy().to_string())
+        };
+        StorageSettings {
+            qa_sessions_dir: custom(StorageKind::QaSessions),
+            training_dir: custom(StorageKind::Training),
+        }
+    }
 }
 
 pub fn ensure_session_dir(qa_sessions_dir: &Path, session_id: &str) -> std::io::Result<PathBuf> {
@@ -23,6 +132,27 @@ pub fn ensure_session_dir(qa_sessions_dir: &Path, session_id: &str) -> std::io::
     Ok(session_dir)
 }
 
+/// Playwright storage state (cookies and local storage) the QA recorder
+/// saves for a run, so later browser runs start logged in
+pub fn session_storage_state_path(session_dir: &Path, run_id: &str) -> PathBuf {
+    session_dir.join(format!("storage_state_{}.json", run_id))
+}
+
+/// Most recently saved storage state of any run in the session
+pub fn latest_session_storage_state(session_dir: &Path) -> Option<PathBuf> {
+    fs::read_dir(session_dir)
+        .ok()?
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with("storage_state_") && name.ends_with(".json")
+        })
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
 pub fn ensure_session_screenshots_dir(session_dir: &Path) -> std::io::Result<PathBuf> {
     let screenshots_dir = session_dir.join("screenshots");
     ensure_dir(&screenshots_dir)?;
@@ -35,3 +165,63 @@ fn ensure_dir(path: &Path) -> std::io::Result<()> {
     }
     Ok(())
 }
+
+/// Number of files and their total size under `path`; unreadable entries are skipped
+pub fn dir_stats(path: &Path) -> (u64, u64) {
+    let Ok(entries) = fs::read_dir(path) else {
+        return (0, 0);
+    };
+    let (mut files, mut bytes) = (0, 0);
+    for entry in entries.flatten() {
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if meta.is_dir() {
+            let (sub_files, sub_bytes) = dir_stats(&entry.path());
+            files += sub_files;
+            bytes += sub_bytes;
+        } else if meta.is_file() {
+            files += 1;
+            bytes += meta.len();
+        }
+    }
+    (files, bytes)
+}
+
+/// Free space on the drive that holds `path`, or would hold it once created
+pub fn available_space(path: &Path) -> Option<u64> {
+    path.ancestors()
+        .find(|dir| dir.exists())
+        .and_then(|dir| fs2::available_space(dir).ok())
+}
+
+/// Copy a folder tree, calling `on_file` with the size of each copied file
+pub fn copy_dir(src: &Path, dst: &Path, on_file: &mut dyn FnMut(u64)) -> std::io::Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let target = dst.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            copy_dir(&entry.path(), &target, on_file)?;
+        } else if file_type.is_file() {
+            on_file(fs::copy(entry.path(), &target)?);
+        }
+    }
+    Ok(())
+}
+
+/// Move a folder, copying it when `dst` is on another drive
+pub fn move_dir(src: &Path, dst: &Path) -> std::io::Result<()> {
+    if let Some(parent) = dst.parent() {
+        ensure_dir(parent)?;
+    }
+    if fs::rename(src, dst).is_ok() {
+        return Ok(());
+    }
+    if let Err(err) = copy_dir(src, dst, &mut |_| {}) {
+        let _ = fs::remove_dir_all(dst);
+        return Err(err);
+    }
+    fs::remove_dir_all(src)
+}
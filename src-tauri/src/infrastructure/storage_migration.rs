@@ -0,0 +1,304 @@
+// ============================================================
+// STORAGE MIGRATION
+// ============================================================
+// Moves a data folder to a new location in three steps so a
+// failure never leaves the app pointing at a half-copied tree:
+// transfer (rename, or copy when the target is on another
+// drive), rewrite the absolute paths stored in the databases,
+// then remove the old folder. `rollback` undoes a transfer whose
+// path rewrite failed.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{Connection, SqliteConnection};
+
+use crate::domain::storage::{StorageKind, StorageMovePlan};
+use crate::infrastructure::storage::{available_space, copy_dir, dir_stats};
+
+/// Tables and columns holding absolute paths into each folder
+pub fn path_columns(kind: StorageKind) -> &'static [(&'static str, &'static str)] {
+    match kind {
+        StorageKind::QaSessions => &[("artifacts", "path")],
+        StorageKind::Training => &[
+            ("models", "default_artifact_path"),
+            ("model_versions", "artifact_path"),
+            ("run_artifacts", "path"),
+        ],
+    }
+}
+
+/// Check a move without touching any file except a probe in the target
+pub fn plan_move(kind: StorageKind, from: &Path, to: &Path) -> StorageMovePlan {
+    let (file_count, total_bytes) = dir_stats(from);
+    let mut plan = StorageMovePlan {
+        kind,
+        from: from.display().to_string(),
+        to: to.display().to_string(),
+        file_count,
+        total_bytes,
+        available_bytes: available_space(to),
+        problems: Vec::new(),
+    };
+
+    if !to.is_absolute() {
+        plan.problems
+            .push(format!("{} is not an absolute path", to.display()));
+        return plan;
+    }
+    if to.starts_with(from) || from.starts_with(to) {
+        plan.problems.push(format!(
+            "{} and {} must not be inside one another",
+            from.display(),
+            to.display()
+        ));
+        return plan;
+    }
+    if to.is_file() {
+        plan.problems
+            .push(format!("{} is a file, not a folder", to.display()));
+        return plan;
+    }
+    if fs::read_dir(to).is_ok_and(|mut entries| entries.next().is_some()) {
+        plan.problems
+            .push(format!("{} must be empty or not exist yet", to.display()));
+    }
+    if plan
+        .available_bytes
+        .is_some_and(|available| available < total_bytes)
+    {
+        plan.problems.push(format!(
+            "Not enough free space at {}: {} bytes needed",
+            to.display(),
+            total_bytes
+        ));
+    }
+    if let Err(err) = probe_writable(to) {
+        plan.problems
+            .push(format!("Cannot write to {}: {}", to.display(), err));
+    }
+    plan
+}
+
+fn probe_writable(dir: &Path) -> std::io::Result<()> {
+    let existed = dir.exists();
+    fs::create_dir_all(dir)?;
+    let probe = dir.join(format!(".write-test-{}", uuid::Uuid::new_v4()));
+    let result = fs::write(&probe, b"").and_then(|_| fs::remove_file(&probe));
+    if !existed {
+        let _ = fs::remove_dir(dir);
+    }
+    result
+}
+
+/// How the files reached the target, which decides how to undo or finish the move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transfer {
+    Renamed,
+    Copied,
+}
+
+/// Put the folder's files at `to`. `on_file` gets the size of every copied file.
+pub fn transfer(from: &Path, to: &Path, on_file: &mut dyn FnMut(u64)) -> std::io::Result<Transfer> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // An empty target left by the plan's probe would make the rename fail
+    if to.exists() {
+        fs::remove_dir(to)?;
+    }
+    if !from.exists() {
+        fs::create_dir_all(to)?;
+        return Ok(Transfer::Copied);
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(Transfer::Renamed);
+    }
+
+    let expected = dir_stats(from);
+    if let Err(err) = copy_dir(from, to, on_file) {
+        let _ = fs::remove_dir_all(to);
+        return Err(err);
+    }
+    if dir_stats(to) != expected {
+        let _ = fs::remove_dir_all(to);
+        return Err(std::io::Error::other(format!(
+            "Copy of {} is incomplete",
+            from.display()
+        )));
+    }
+    Ok(Transfer::Copied)
+}
+
+pub fn rollback(from: &Path, to: &Path, transfer: Transfer) -> std::io::Result<()> {
+    match transfer {
+        Transfer::Renamed => fs::rename(to, from),
+        Transfer::Copied => fs::remove_dir_all(to),
+    }
+}
+
+/// Remove the old folder once the copy is in use
+pub fn finish(from: &Path, transfer: Transfer) -> std::io::Result<()> {
+    match transfer {
+        Transfer::Copied if from.exists() => fs::remove_dir_all(from),
+        _ => Ok(()),
+    }
+}
+
+/// `dir` with a trailing separator, so `/data/qa` does not match `/data/qa2`
+fn dir_prefix(dir: &Path) -> String {
+    let mut prefix = dir.to_string_lossy().to_string();
+    if !prefix.ends_with(std::path::MAIN_SEPARATOR) {
+        prefix.push(std::path::MAIN_SEPARATOR);
+    }
+    prefix
+}
+
+/// Point stored paths under `from` at `to` in one transaction. Returns the
+/// number of rows changed; a missing database has nothing to rewrite.
+pub async fn rewrite_path_prefix(
+    db_path: &Path,
+    columns: &[(&str, &str)],
+    from: &Path,
+    to: &Path,
+) -> sqlx::Result<u64> {
+    if !db_path.exists() {
+        return Ok(0);
+    }
+    let options = SqliteConnectOptions::new()
+        .filename(db_path)
+        .create_if_missing(false)
+        .busy_timeout(Duration::from_secs(30));
+    let mut conn = SqliteConnection::connect_with(&options).await?;
+    let (old_prefix, new_prefix) = (dir_prefix(from), dir_prefix(to));
+
+    let mut tx = conn.begin().await?;
+    let mut updated = 0;
+    for (table, column) in columns {
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?)",
+        )
+        .bind(*table)
+        .fetch_one(&mut *tx)
+        .await?;
+        if !exists {
+            continue;
+        }
+        let sql = format!(
+            "UPDATE {table} SET {column} = ?1 || substr({column}, length(?2) + 1) \
+             WHERE substr({column}, 1, length(?2)) = ?2"
+        );
+        updated += sqlx::query(&sql)
+            .bind(&new_prefix)
+            .bind(&old_prefix)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+    }
+    tx.commit().await?;
+    let _ = conn.close().await;
+    Ok(updated)
+}
+
+/// Resolve a requested location: empty means the default folder
+pub fn requested_dir(requested: &str, default: PathBuf) -> PathBuf {
+    let requested = requested.trim();
+    if requested.is_empty() {
+        default
+    } else {
+        PathBuf::from(requested)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "storage-migration-{}-{}",
+            name,
+            uuid::Uuid::new_v4()
+        ))
+    }
+
+    #[test]
+    fn test_plan_rejects_nested_and_non_empty_targets() {
+        let from = temp_dir("from");
+        fs::create_dir_all(from.join("session")).unwrap();
+        fs::write(from.join("session").join("a.png"), b"12345").unwrap();
+
+        let plan = plan_move(StorageKind::QaSessions, &from, &from.join("inner"));
+        assert!(!plan.is_ready());
+
+        let busy = temp_dir("busy");
+        fs::create_dir_all(&busy).unwrap();
+        fs::write(busy.join("other.txt"), b"x").unwrap();
+        assert!(!plan_move(StorageKind::QaSessions, &from, &busy).is_ready());
+
+        let target = temp_dir("target");
+        let plan = plan_move(StorageKind::QaSessions, &from, &target);
+        assert!(plan.is_ready(), "{:?}", plan.problems);
+        assert_eq!((plan.file_count, plan.total_bytes), (1, 5));
+        assert!(!target.exists());
+
+        for dir in [from, busy] {
+            fs::remove_dir_all(dir).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rewrites_only_paths_under_the_old_folder() {
+        let dir = temp_dir("db");
+        fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("qa.db");
+        let options = SqliteConnectOptions::new()
+            .filename(&db_path)
+            .create_if_missing(true);
+        let mut conn = SqliteConnection::connect_with(&options).await.unwrap();
+        sqlx::query("CREATE TABLE artifacts (path TEXT NOT NULL)")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let old_root = dir.join("qa");
+        let new_root = dir.join("elsewhere").join("qa");
+        let inside = old_root.join("s1").join("a.png");
+        let sibling = dir.join("qa2").join("b.png");
+        for path in [&inside, &sibling] {
+            sqlx::query("INSERT INTO artifacts (path) VALUES (?)")
+                .bind(path.to_string_lossy().to_string())
+                .execute(&mut conn)
+                .await
+                .unwrap();
+        }
+        conn.close().await.unwrap();
+
+        let updated = rewrite_path_prefix(&db_path, &[("artifacts", "path")], &old_root, &new_root)
+            .await
+            .unwrap();
+        assert_eq!(updated, 1);
+
+        let mut conn = SqliteConnection::connect_with(&options).await.unwrap();
+        let paths: Vec<String> = sqlx::query_scalar("SELECT path FROM artifacts ORDER BY rowid")
+            .fetch_all(&mut conn)
+            .await
+            .unwrap();
+        conn.close().await.unwrap();
+        assert_eq!(
+            paths,
+            [
+                new_root
+                    .join("s1")
+                    .join("a.png")
+                    .to_string_lossy()
+                    .to_string(),
+                sibling.to_string_lossy().to_string(),
+            ]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,126 @@
+//! Clients for the test management systems QA test cases are pushed to
+
+pub mod testrail;
+pub mod xray;
+
+use std::time::Duration;
+
+use serde_json::Value as JsonValue;
+
+use crate::domain::error::{AppError, ErrorCode, Result};
+use crate::domain::qa_test_management::{QaTestResultStatus, TestManagementProvider};
+use crate::infrastructure::security::redact::redact_secrets;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Longest part of an error response quoted in the error message
+const MAX_ERROR_BODY_CHARS: usize = 500;
+
+/// A generated test case as sent to a test management system
+pub struct ExternalTestCase<'a> {
+    pub title: &'a str,
+    /// `positive`, `negative`, `edge`, `exploratory` or `api_gap`
+    pub case_type: &'a str,
+    pub priority: Option<&'a str>,
+    pub steps: Vec<String>,
+    pub expected: Option<&'a str>,
+}
+
+impl ExternalTestCase<'_> {
+    /// Steps paired with their expected result; the case's expected result
+    /// belongs to the last step. A case without steps becomes one step
+    /// named after its title.
+    fn steps_with_expected(&self) -> Vec<(String, String)> {
+        let mut steps: Vec<(String, String)> = self
+            .steps
+            .iter()
+            .map(|step| step.trim())
+            .filter(|step| !step.is_empty())
+            .map(|step| (step.to_string(), String::new()))
+            .collect();
+        if steps.is_empty() {
+            steps.push((self.title.trim().to_string(), String::new()));
+        }
+        if let Some(expected) = self.expected.map(str::trim) {
+            if let Some(last) = steps.last_mut() {
+                last.1 = expected.to_string();
+            }
+        }
+        steps
+    }
+}
+
+/// A run result for a test case pushed earlier
+pub struct ExternalTestResult<'a> {
+    /// Xray issue key or TestRail case id
+    pub external_id: &'a str,
+    pub status: QaTestResultStatus,
+    pub comment: Option<&'a str>,
+}
+
+fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+}
+
+/// JSON body of a successful response, or the error it reports
+async fn read_json(
+    provider: TestManagementProvider,
+    response: reqwest::Response,
+) -> Result<JsonValue> {
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| request_error(provider, e))?;
+    if !status.is_success() {
+        return Err(api_error(provider, status, &body));
+    }
+    serde_json::from_str(&body).map_err(|e| {
+        AppError::ParseError(format!(
+            "{} returned an unreadable response: {}",
+            provider.display_name(),
+            e
+        ))
+    })
+}
+
+fn request_error(provider: TestManagementProvider, err: reqwest::Error) -> AppError {
+    AppError::coded(
+        ErrorCode::NetworkUnavailable,
+        format!(
+            "{} request failed: {}",
+            provider.display_name(),
+            redact_secrets(&err.without_url().to_string())
+        ),
+    )
+}
+
+fn api_error(
+    provider: TestManagementProvider,
+    status: reqwest::StatusCode,
+    body: &str,
+) -> AppError {
+    let body: String = body.trim().chars().take(MAX_ERROR_BODY_CHARS).collect();
+    let message = format!(
+        "{} API error ({}): {}",
+        provider.display_name(),
+        status,
+        redact_secrets(&body)
+    );
+    match status {
+        reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+            AppError::coded(ErrorCode::ProviderUnauthorized, message).with_hint(format!(
+                "Check the {} credentials in Settings",
+                provider.display_name()
+            ))
+        }
+        reqwest::StatusCode::TOO_MANY_REQUESTS => {
+            AppError::coded(ErrorCode::ProviderRateLimited, message)
+        }
+        status if status.is_client_error() => AppError::ValidationError(message),
+        _ => AppError::coded(ErrorCode::NetworkUnavailable, message),
+    }
+}
@@ -0,0 +1,189 @@
+//! TestRail REST API v2: test cases are added to one section, and run
+//! results go to a new run holding just the reported cases.
+
+use serde_json::{json, Map, Value as JsonValue};
+
+use super::{http_client, read_json, request_error, ExternalTestCase, ExternalTestResult};
+use crate::domain::error::{AppError, ErrorCode, Result};
+use crate::domain::qa_test_management::{
+    QaTestResultStatus, TestManagementProvider, TestRailTarget,
+};
+
+const PROVIDER: TestManagementProvider = TestManagementProvider::Testrail;
+
+pub struct TestRailClient<'a> {
+    http: reqwest::Client,
+    target: &'a TestRailTarget,
+    api_key: &'a str,
+}
+
+impl<'a> TestRailClient<'a> {
+    pub fn new(target: &'a TestRailTarget, api_key: &'a str) -> Self {
+        Self {
+            http: http_client(),
+            target,
+            api_key,
+        }
+    }
+
+    /// Add a case to the configured section; returns its id, e.g. `C123`
+    pub async fn add_case(&self, case: &ExternalTestCase<'_>) -> Result<String> {
+        let section_id = required(self.target.section_id, "TestRail section id")?;
+        let created = self
+            .post(
+                &format!("add_case/{}", section_id),
+                &case_payload(self.target, case),
+            )
+            .await?;
+        created
+            .get("id")
+            .and_then(JsonValue::as_i64)
+            .map(|id| format!("C{}", id))
+            .ok_or_else(|| AppError::ParseError("TestRail did not return a case id".to_string()))
+    }
+
+    /// Create a run named `name` holding the reported cases and add their
+    /// results; returns the run id, e.g. `R45`
+    pub async fn add_run(&self, name: &str, results: &[ExternalTestResult<'_>]) -> Result<String> {
+        let project_id = required(self.target.project_id, "TestRail project id")?;
+        let mut case_results = Vec::with_capacity(results.len());
+        for result in results {
+            let case_id = parse_case_id(result.external_id).ok_or_else(|| {
+                AppError::ValidationError(format!(
+                    "'{}' is not a TestRail case id",
+                    result.external_id
+                ))
+            })?;
+            let mut entry = json!({ "case_id": case_id, "status_id": status_id(result.status) });
+            if let Some(comment) = result.comment.filter(|c| !c.trim().is_empty()) {
+                entry["comment"] = json!(comment.trim());
+            }
+            case_results.push(entry);
+        }
+
+        let case_ids: Vec<&JsonValue> =
+            case_results.iter().map(|entry| &entry["case_id"]).collect();
+        let mut run = json!({ "name": name, "include_all": false, "case_ids": case_ids });
+        if let Some(suite_id) = self.target.suite_id {
+            run["suite_id"] = json!(suite_id);
+        }
+        let created = self.post(&format!("add_run/{}", project_id), &run).await?;
+        let run_id = created
+            .get("id")
+            .and_then(JsonValue::as_i64)
+            .ok_or_else(|| AppError::ParseError("TestRail did not return a run id".to_string()))?;
+
+        self.post(
+            &format!("add_results_for_cases/{}", run_id),
+            &json!({ "results": case_results }),
+        )
+        .await?;
+        Ok(format!("R{}", run_id))
+    }
+
+    async fn post(&self, endpoint: &str, body: &JsonValue) -> Result<JsonValue> {
+        let url = format!(
+            "{}/index.php?/api/v2/{}",
+            self.target.base_url.trim().trim_end_matches('/'),
+            endpoint
+        );
+        let response = self
+            .http
+            .post(url)
+            .basic_auth(self.target.username.trim(), Some(self.api_key))
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| request_error(PROVIDER, e))?;
+        read_json(PROVIDER, response).await
+    }
+}
+
+/// `add_case` body: mapped type and priority, extra fields, and the steps
+/// as separated steps with the expected result on the last one
+fn case_payload(target: &TestRailTarget, case: &ExternalTestCase<'_>) -> JsonValue {
+    let mut body: Map<String, JsonValue> = target
+        .extra_fields
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    body.insert("title".to_string(), json!(case.title.trim()));
+    if let Some(type_id) = target.type_map.get(case.case_type) {
+        body.insert("type_id".to_string(), json!(type_id));
+    }
+    let priority = case
+        .priority
+        .and_then(|priority| target.priority_map.get(&priority.trim().to_lowercase()));
+    if let Some(priority_id) = priority {
+        body.insert("priority_id".to_string(), json!(priority_id));
+    }
+    let steps: Vec<JsonValue> = case
+        .steps_with_expected()
+        .into_iter()
+        .map(|(content, expected)| json!({ "content": content, "expected": expected }))
+        .collect();
+    body.insert("custom_steps_separated".to_string(), json!(steps));
+    JsonValue::Object(body)
+}
+
+/// Numeric id of `C123` or `123`
+fn parse_case_id(external_id: &str) -> Option<i64> {
+    let id = external_id.trim();
+    id.strip_prefix(['C', 'c'])
+        .unwrap_or(id)
+        .parse()
+        .ok()
+        .filter(|&id: &i64| id > 0)
+}
+
+/// TestRail's built-in statuses; skipped cases are marked for retest
+fn status_id(status: QaTestResultStatus) -> i64 {
+    match status {
+        QaTestResultStatus::Passed => 1,
+        QaTestResultStatus::Blocked => 2,
+        QaTestResultStatus::Skipped => 4,
+        QaTestResultStatus::Failed => 5,
+    }
+}
+
+fn required(value: Option<i64>, name: &str) -> Result<i64> {
+    value.ok_or_else(|| AppError::coded(ErrorCode::ConfigMissing, format!("{} is not set", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_maps_type_priority_and_steps() {
+        let mut target = TestRailTarget::default();
+        target.type_map.insert("edge".to_string(), 6);
+        target
+            .extra_fields
+            .insert("template_id".to_string(), json!(2));
+        let case = ExternalTestCase {
+            title: "Quantity of zero",
+            case_type: "edge",
+            priority: Some("medium"),
+            steps: Vec::new(),
+            expected: Some("The cart rejects it"),
+        };
+
+        let payload = case_payload(&target, &case);
+        assert_eq!(payload["type_id"], 6);
+        assert_eq!(payload["priority_id"], 2);
+        assert_eq!(payload["template_id"], 2);
+        assert_eq!(
+            payload["custom_steps_separated"],
+            json!([{ "content": "Quantity of zero", "expected": "The cart rejects it" }])
+        );
+    }
+
+    #[test]
+    fn case_ids_accept_the_c_prefix() {
+        assert_eq!(parse_case_id("C123"), Some(123));
+        assert_eq!(parse_case_id(" 45 "), Some(45));
+        assert_eq!(parse_case_id("SHOP-1"), None);
+        assert_eq!(parse_case_id("C0"), None);
+    }
+}
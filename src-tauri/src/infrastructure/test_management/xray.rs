@@ -0,0 +1,309 @@
+//! Xray Cloud REST API v2: test cases become Jira `Test` issues through a
+//! bulk import job, and run results a new test execution.
+
+use std::time::Duration;
+
+use serde_json::{json, Map, Value as JsonValue};
+
+use super::{http_client, read_json, request_error, ExternalTestCase, ExternalTestResult};
+use crate::domain::error::{AppError, ErrorCode, Result};
+use crate::domain::qa_test_management::{QaTestResultStatus, TestManagementProvider, XrayTarget};
+
+const PROVIDER: TestManagementProvider = TestManagementProvider::Xray;
+
+/// Bulk imports run as jobs; how often and how many times to ask about one
+const JOB_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const JOB_POLL_ATTEMPTS: usize = 60;
+
+pub struct XrayClient<'a> {
+    http: reqwest::Client,
+    target: &'a XrayTarget,
+    client_secret: &'a str,
+}
+
+impl<'a> XrayClient<'a> {
+    pub fn new(target: &'a XrayTarget, client_secret: &'a str) -> Self {
+        Self {
+            http: http_client(),
+            target,
+            client_secret,
+        }
+    }
+
+    /// Create a `Test` issue per case, in order. The outer error fails the
+    /// whole import; the inner ones belong to single cases.
+    pub async fn create_tests(
+        &self,
+        cases: &[ExternalTestCase<'_>],
+    ) -> Result<Vec<Result<String>>> {
+        let token = self.authenticate().await?;
+        let payload: Vec<JsonValue> = cases
+            .iter()
+            .map(|case| test_payload(self.target, case))
+            .collect();
+
+        let response = self
+            .http
+            .post(self.url("import/test/bulk"))
+            .bearer_auth(&token)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| request_error(PROVIDER, e))?;
+        let job = read_json(PROVIDER, response).await?;
+        let job_id = job
+            .get("jobId")
+            .and_then(JsonValue::as_str)
+            .ok_or_else(|| {
+                AppError::ParseError("Xray did not return an import job id".to_string())
+            })?;
+
+        for _ in 0..JOB_POLL_ATTEMPTS {
+            tokio::time::sleep(JOB_POLL_INTERVAL).await;
+            let response = self
+                .http
+                .get(self.url(&format!("import/test/bulk/{}/status", job_id)))
+                .bearer_auth(&token)
+                .send()
+                .await
+                .map_err(|e| request_error(PROVIDER, e))?;
+            let status = read_json(PROVIDER, response).await?;
+            match status.get("status").and_then(JsonValue::as_str) {
+                Some("successful" | "partially_successful" | "failed") => {
+                    return Ok(job_outcome(&status, cases.len()));
+                }
+                _ => continue,
+            }
+        }
+        Err(AppError::coded(
+            ErrorCode::NetworkUnavailable,
+            format!("Xray import job {} did not finish in time", job_id),
+        ))
+    }
+
+    /// Record `results` as a new test execution; returns its issue key
+    pub async fn import_execution(
+        &self,
+        summary: &str,
+        results: &[ExternalTestResult<'_>],
+    ) -> Result<String> {
+        let token = self.authenticate().await?;
+        let tests: Vec<JsonValue> = results
+            .iter()
+            .map(|result| {
+                let mut test = json!({
+                    "testKey": result.external_id,
+                    "status": execution_status(result.status),
+                });
+                if let Some(comment) = result.comment.filter(|c| !c.trim().is_empty()) {
+                    test["comment"] = json!(comment.trim());
+                }
+                test
+            })
+            .collect();
+        let payload = json!({
+            "info": {
+                "project": self.target.project_key.trim(),
+                "summary": summary,
+            },
+            "tests": tests,
+        });
+
+        let response = self
+            .http
+            .post(self.url("import/execution"))
+            .bearer_auth(&token)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| request_error(PROVIDER, e))?;
+        let execution = read_json(PROVIDER, response).await?;
+        execution
+            .get("key")
+            .and_then(JsonValue::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| {
+                AppError::ParseError("Xray did not return a test execution key".to_string())
+            })
+    }
+
+    async fn authenticate(&self) -> Result<String> {
+        let response = self
+            .http
+            .post(self.url("authenticate"))
+            .json(&json!({
+                "client_id": self.target.client_id.trim(),
+                "client_secret": self.client_secret,
+            }))
+            .send()
+            .await
+            .map_err(|e| request_error(PROVIDER, e))?;
+        read_json(PROVIDER, response)
+            .await?
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| AppError::ParseError("Xray did not return a token".to_string()))
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!(
+            "{}/api/v2/{}",
+            self.target.api_url.trim().trim_end_matches('/'),
+            path
+        )
+    }
+}
+
+/// Bulk import entry for one case: a manual test with the mapped priority,
+/// labels and extra fields
+fn test_payload(target: &XrayTarget, case: &ExternalTestCase<'_>) -> JsonValue {
+    let mut fields: Map<String, JsonValue> = target
+        .extra_fields
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    fields.insert(
+        "project".to_string(),
+        json!({ "key": target.project_key.trim() }),
+    );
+    fields.insert("summary".to_string(), json!(case.title.trim()));
+    let priority = case
+        .priority
+        .and_then(|priority| target.priority_map.get(&priority.trim().to_lowercase()));
+    if let Some(name) = priority {
+        fields.insert("priority".to_string(), json!({ "name": name }));
+    }
+    if let Some(labels) = target
+        .type_labels
+        .get(case.case_type)
+        .filter(|l| !l.is_empty())
+    {
+        fields.insert("labels".to_string(), json!(labels));
+    }
+
+    let steps: Vec<JsonValue> = case
+        .steps_with_expected()
+        .into_iter()
+        .map(|(action, result)| json!({ "action": action, "data": "", "result": result }))
+        .collect();
+    json!({ "testtype": "Manual", "fields": fields, "steps": steps })
+}
+
+/// Issue key or error of each case from a finished bulk import job
+fn job_outcome(status: &JsonValue, count: usize) -> Vec<Result<String>> {
+    let mut outcomes: Vec<Result<String>> = (0..count)
+        .map(|_| {
+            Err(AppError::Internal(
+                "Xray did not report on this test".to_string(),
+            ))
+        })
+        .collect();
+    let result = status.get("result");
+    let element = |entry: &JsonValue| {
+        entry
+            .get("elementNumber")
+            .and_then(JsonValue::as_u64)
+            .map(|n| n as usize)
+            .filter(|&n| n < count)
+    };
+
+    let issues = result
+        .and_then(|r| r.get("issues"))
+        .and_then(JsonValue::as_array);
+    for issue in issues.into_iter().flatten() {
+        if let (Some(index), Some(key)) =
+            (element(issue), issue.get("key").and_then(JsonValue::as_str))
+        {
+            outcomes[index] = Ok(key.to_string());
+        }
+    }
+
+    let errors = result
+        .and_then(|r| r.get("errors"))
+        .and_then(JsonValue::as_array);
+    for error in errors.into_iter().flatten() {
+        let Some(index) = element(error) else {
+            continue;
+        };
+        let detail = match error.get("errors") {
+            Some(JsonValue::Object(fields)) => fields
+                .iter()
+                .map(|(field, message)| match message.as_str() {
+                    Some(message) => format!("{}: {}", field, message),
+                    None => format!("{}: {}", field, message),
+                })
+                .collect::<Vec<_>>()
+                .join("; "),
+            Some(other) => other.to_string(),
+            None => "rejected".to_string(),
+        };
+        outcomes[index] = Err(AppError::ValidationError(format!(
+            "Xray rejected the test: {}",
+            detail
+        )));
+    }
+    outcomes
+}
+
+fn execution_status(status: QaTestResultStatus) -> &'static str {
+    match status {
+        QaTestResultStatus::Passed => "PASSED",
+        QaTestResultStatus::Failed => "FAILED",
+        QaTestResultStatus::Blocked => "ABORTED",
+        QaTestResultStatus::Skipped => "TODO",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_maps_priority_labels_and_expected_result() {
+        let mut target = XrayTarget {
+            project_key: "SHOP".to_string(),
+            ..XrayTarget::default()
+        };
+        target
+            .type_labels
+            .insert("negative".to_string(), vec!["negative-path".to_string()]);
+        target
+            .extra_fields
+            .insert("components".to_string(), json!([{ "name": "Checkout" }]));
+        let case = ExternalTestCase {
+            title: "Pay with an expired card",
+            case_type: "negative",
+            priority: Some("High"),
+            steps: vec!["Add an item".to_string(), "Pay with 01/20".to_string()],
+            expected: Some("Payment is declined"),
+        };
+
+        let payload = test_payload(&target, &case);
+        assert_eq!(payload["fields"]["project"]["key"], "SHOP");
+        assert_eq!(payload["fields"]["priority"]["name"], "High");
+        assert_eq!(payload["fields"]["labels"], json!(["negative-path"]));
+        assert_eq!(payload["fields"]["components"][0]["name"], "Checkout");
+        assert_eq!(payload["steps"][0]["result"], "");
+        assert_eq!(payload["steps"][1]["result"], "Payment is declined");
+    }
+
+    #[test]
+    fn job_outcome_matches_issues_and_errors_by_element() {
+        let status = json!({
+            "status": "partially_successful",
+            "result": {
+                "issues": [{ "elementNumber": 0, "key": "SHOP-7" }],
+                "errors": [{ "elementNumber": 2, "errors": { "summary": "too long" } }],
+            },
+        });
+
+        let outcomes = job_outcome(&status, 3);
+        assert_eq!(outcomes[0].as_ref().unwrap(), "SHOP-7");
+        assert!(outcomes[1].is_err());
+        assert!(outcomes[2]
+            .as_ref()
+            .unwrap_err()
+            .to_string()
+            .contains("summary: too long"));
+    }
+}
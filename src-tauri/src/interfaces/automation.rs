@@ -0,0 +1,251 @@
+//! Automation commands
+//!
+//! Runs the commands of `gadogado://<action>` links and `--run <action>`
+//! launch arguments (see `domain::automation`) in the running app. Text
+//! results are copied to the clipboard when the command allows it and, for
+//! `--run` commands that ask, written to a file as JSON; every outcome is
+//! logged and emitted to the windows.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::domain::automation::{AutomationAction, AutomationCommand};
+use crate::domain::error::{AppError, Result};
+use crate::interfaces::background::open_main_window;
+use crate::interfaces::http::add_log;
+use crate::interfaces::mock_server::{
+    build_status as build_mock_status, start_mock_server, stop_mock_server, MockServerStatus,
+};
+use crate::interfaces::tauri::quick_action_commands::{
+    run_quick_action, QuickAction, QuickActionCitation, QuickActionOptions,
+};
+use crate::interfaces::tauri::AppState;
+use crate::interfaces::tray::NAVIGATE_EVENT;
+
+/// Emitted with an `AutomationOutcome` payload after each command
+pub(crate) const AUTOMATION_OUTCOME_EMIT: &str = "automation-outcome";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AutomationOutcome {
+    pub command: AutomationCommand,
+    /// Translation, enhanced text or answer
+    pub output: Option<String>,
+    /// Sources behind a `rag-query` answer
+    pub citations: Vec<QuickActionCitation>,
+    /// Server status after `mock-server/*`
+    pub mock_server: Option<MockServerStatus>,
+    pub error: Option<String>,
+}
+
+/// Run the command among `args`, if any. `cwd` is the directory the
+/// launch happened in, which a relative `out` path is resolved against.
+/// Returns whether the arguments held a command.
+pub(crate) fn spawn_from_args(app: &AppHandle, args: &[String], cwd: &Path) -> bool {
+    let Some(command) = AutomationCommand::from_args(args) else {
+        return false;
+    };
+    spawn_parsed(app.clone(), command, Some(cwd.to_path_buf()));
+    true
+}
+
+/// Run the command of a link opened while the app runs. Returns whether
+/// the link was an automation command.
+pub(crate) fn spawn_from_link(app: &AppHandle, link: &str) -> bool {
+    let Some(command) = AutomationCommand::from_deep_link(link) else {
+        return false;
+    };
+    spawn_parsed(app.clone(), command, None);
+    true
+}
+
+fn spawn_parsed(app: AppHandle, command: Result<AutomationCommand>, cwd: Option<PathBuf>) {
+    tauri::async_runtime::spawn(async move {
+        let Some(state) = app.try_state::<Arc<AppState>>() else {
+            return;
+        };
+        let state = state.inner().clone();
+        match command {
+            Ok(command) => run(&app, &state, command, cwd.as_deref()).await,
+            Err(err) => add_log(
+                &state.logs,
+                "ERROR",
+                "Automation",
+                &format!("Ignored automation command: {}", err),
+            ),
+        }
+    });
+}
+
+pub(crate) async fn run(
+    app: &AppHandle,
+    state: &Arc<AppState>,
+    command: AutomationCommand,
+    cwd: Option<&Path>,
+) {
+    let name = command.action.name();
+    add_log(
+        &state.logs,
+        "INFO",
+        "Automation",
+        &format!("Running {}", name),
+    );
+
+    let mut outcome = AutomationOutcome {
+        command: command.clone(),
+        output: None,
+        citations: Vec::new(),
+        mock_server: None,
+        error: None,
+    };
+    if let Err(err) = execute(app, state, &command.action, &mut outcome).await {
+        add_log(
+            &state.logs,
+            "ERROR",
+            "Automation",
+            &format!("{} failed: {}", name, err),
+        );
+        outcome.error = Some(err.to_string());
+    }
+
+    if command.copy {
+        if let Some(output) = &outcome.output {
+            if let Err(err) = app.clipboard().write_text(output.clone()) {
+                add_log(
+                    &state.logs,
+                    "WARN",
+                    "Automation",
+                    &format!("Failed to copy the {} result: {}", name, err),
+                );
+            }
+        }
+    }
+    if let Some(path) = &command.output_path {
+        let path = match cwd {
+            Some(cwd) => cwd.join(path),
+            None => PathBuf::from(path),
+        };
+        if let Err(err) = write_outcome(&path, &outcome) {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "Automation",
+                &format!("Failed to write the {} result: {}", name, err),
+            );
+        }
+    }
+    if outcome.error.is_none() {
+        add_log(
+            &state.logs,
+            "INFO",
+            "Automation",
+            &format!("Finished {}", name),
+        );
+    }
+    let _ = app.emit(AUTOMATION_OUTCOME_EMIT, &outcome);
+}
+
+async fn execute(
+    app: &AppHandle,
+    state: &Arc<AppState>,
+    action: &AutomationAction,
+    outcome: &mut AutomationOutcome,
+) -> Result<()> {
+    match action {
+        AutomationAction::Translate {
+            text,
+            source,
+            target,
+        } => {
+            let result = run_quick_action(
+                state,
+                QuickAction::Translate,
+                text.clone(),
+                QuickActionOptions {
+                    source_lang: source.clone(),
+                    target_lang: target.clone(),
+                    ..Default::default()
+                },
+            )
+            .await?;
+            outcome.output = Some(result.output);
+        }
+        AutomationAction::Enhance { text, preset } => {
+            let result = run_quick_action(
+                state,
+                QuickAction::Enhance,
+                text.clone(),
+                QuickActionOptions {
+                    preset_id: preset.clone(),
+                    ..Default::default()
+                },
+            )
+            .await?;
+            outcome.output = Some(result.output);
+        }
+        AutomationAction::RagQuery { collection, query } => {
+            let collection_id = match collection {
+                Some(collection) => Some(resolve_collection(state, collection).await?),
+                None => None,
+            };
+            let result = run_quick_action(
+                state,
+                QuickAction::RagChat,
+                query.clone(),
+                QuickActionOptions {
+                    collection_id,
+                    ..Default::default()
+                },
+            )
+            .await?;
+            outcome.output = Some(result.output);
+            outcome.citations = result.citations;
+        }
+        AutomationAction::MockServerStart | AutomationAction::MockServerStop => {
+            let running = build_mock_status(&state.mock_server).running;
+            let start = matches!(action, AutomationAction::MockServerStart);
+            if start && !running {
+                start_mock_server(state.mock_server.clone()).await?;
+            } else if !start && running {
+                stop_mock_server(state.mock_server.clone()).await?;
+            }
+            outcome.mock_server = Some(build_mock_status(&state.mock_server));
+            crate::interfaces::tray::refresh(app);
+        }
+        AutomationAction::Open { page } => {
+            open_main_window(app).await;
+            if let Some(page) = page {
+                let _ = app.emit_to("main", NAVIGATE_EVENT, page);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Collection ID from an ID or a collection name
+async fn resolve_collection(state: &AppState, collection: &str) -> Result<i64> {
+    if let Ok(id) = collection.trim().parse::<i64>() {
+        return Ok(id);
+    }
+    state
+        .deferred_init
+        .ensure()
+        .await
+        .map_err(AppError::DatabaseError)?;
+    state
+        .rag_repository
+        .find_collection_by_name(collection.trim())
+        .await?
+        .map(|found| found.id)
+        .ok_or_else(|| AppError::NotFound(format!("Collection not found: {}", collection)))
+}
+
+fn write_outcome(path: &Path, outcome: &AutomationOutcome) -> Result<()> {
+    let json = serde_json::to_vec_pretty(outcome)
+        .map_err(|e| AppError::Internal(format!("Failed to encode result: {}", e)))?;
+    std::fs::write(path, json).map_err(|e| AppError::IoError(e.to_string()))
+}
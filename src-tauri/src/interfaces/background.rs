@@ -0,0 +1,110 @@
+//! Autostart and background mode
+//!
+//! When the OS starts the app at login it passes `--background`: the main
+//! window is not created and only the shortcuts, tray, HTTP API and (if
+//! enabled) the mock server run. The RAG and training databases are
+//! initialized the first time the main window is opened.
+//!
+//! Only one instance runs; a later launch hands its arguments to it, so
+//! `--profile` and `--run` reach the running app.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use tauri::plugin::TauriPlugin;
+use tauri::{AppHandle, Manager, WebviewWindow, WebviewWindowBuilder, Wry};
+use tauri_plugin_autostart::{MacosLauncher, ManagerExt};
+
+use crate::domain::project_profiles::requested_profile;
+use crate::infrastructure::bootstrap::spawn_retrieval_cache_warmup;
+use crate::interfaces::automation;
+use crate::interfaces::http::add_log;
+use crate::interfaces::tauri::profile_commands::spawn_apply_profile;
+use crate::interfaces::tauri::AppState;
+
+/// Passed by the autostart entry to start without the main window
+pub(crate) const BACKGROUND_ARG: &str = "--background";
+
+const MAIN_WINDOW_LABEL: &str = "main";
+
+pub(crate) fn autostart_plugin() -> TauriPlugin<Wry> {
+    tauri_plugin_autostart::init(MacosLauncher::LaunchAgent, Some(vec![BACKGROUND_ARG]))
+}
+
+pub(crate) fn launched_in_background() -> bool {
+    std::env::args().any(|arg| arg == BACKGROUND_ARG)
+}
+
+/// Arguments of a later launch: apply the profile they name (including a
+/// `gadogado://` link, passed as an argument on Windows and Linux) or run
+/// their automation command, and show the window unless that launch was a
+/// background one or a command, which should not steal focus
+pub(crate) fn on_second_instance(app: &AppHandle, argv: Vec<String>, cwd: String) {
+    if let Some(profile_id) = requested_profile(&argv) {
+        spawn_apply_profile(app.clone(), profile_id);
+    }
+    let ran_command = automation::spawn_from_args(app, &argv, Path::new(&cwd));
+    if !ran_command && !argv.iter().any(|arg| arg == BACKGROUND_ARG) {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move { open_main_window(&app).await });
+    }
+}
+
+/// Register or remove the login item so it matches `enabled`
+pub(crate) fn sync_autostart(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let autolaunch = app.autolaunch();
+    let registered = autolaunch.is_enabled().map_err(|e| e.to_string())?;
+    match (enabled, registered) {
+        (true, false) => autolaunch.enable().map_err(|e| e.to_string()),
+        (false, true) => autolaunch.disable().map_err(|e| e.to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// Build the main window from its entry in tauri.conf.json, where it is not
+/// created automatically so background launches never load the webview
+pub(crate) fn create_main_window(app: &AppHandle) -> tauri::Result<WebviewWindow> {
+    let config = app
+        .config()
+        .app
+        .windows
+        .iter()
+        .find(|window| window.label == MAIN_WINDOW_LABEL)
+        .cloned()
+        .ok_or(tauri::Error::WindowNotFound)?;
+    WebviewWindowBuilder::from_config(app, &config)?.build()
+}
+
+/// Show the main window, creating it (and loading the deferred databases)
+/// when the app was started in the background
+pub(crate) async fn open_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+        return;
+    }
+
+    if let Some(state) = app.try_state::<Arc<AppState>>() {
+        match state.deferred_init.ensure().await {
+            Ok(()) => spawn_retrieval_cache_warmup(state.inner().clone()),
+            Err(err) => add_log(&state.logs, "ERROR", "System", &err),
+        }
+    }
+
+    match create_main_window(app) {
+        Ok(window) => {
+            let _ = window.set_focus();
+        }
+        Err(err) => {
+            if let Some(state) = app.try_state::<Arc<AppState>>() {
+                add_log(
+                    &state.logs,
+                    "ERROR",
+                    "System",
+                    &format!("Failed to open the main window: {}", err),
+                );
+            }
+        }
+    }
+}
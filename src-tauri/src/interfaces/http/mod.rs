@@ -1,13 +1,36 @@
+use crate::application::use_cases::language_detection::{self, DetectedLanguage};
+use crate::application::use_cases::translate::TranslateOptions;
+use crate::domain::error::{AppError, ErrorCategory, ErrorCode, ErrorPayload, Result};
 use crate::domain::llm_config::LLMConfig;
-use crate::domain::typegen::TypeGenMode;
+use crate::domain::prompt::Prompt;
+use crate::domain::settings::{HttpServerSettings, LlmFeature};
+use crate::domain::typegen::{TypeGenMode, TypeGenOptions};
+use crate::infrastructure::llm_clients::{api_error, request_error};
+use crate::infrastructure::security::redact::redact_secrets;
 use crate::interfaces::tauri::AppState;
 use actix_cors::Cors;
+use actix_web::http::StatusCode;
+use actix_web::middleware::from_fn;
 use actix_web::{dev::Server, get, post, web, App, HttpResponse, HttpServer, Responder};
 use chrono::Local;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::{SocketAddr, TcpListener};
 use std::sync::{Arc, Mutex};
+use utoipa::{OpenApi, ToSchema};
+use utoipa_swagger_ui::SwaggerUi;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+mod openapi;
+mod qa_proxy;
+mod rag;
+mod rate_limit;
+mod server_control;
+
+pub use openapi::ApiDoc;
+pub use rate_limit::RateLimitMetrics;
+pub use server_control::HttpServerControl;
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct LogEntry {
     pub time: String,
     pub level: String,
@@ -20,57 +43,118 @@ pub struct HttpState {
     pub logs: Arc<Mutex<Vec<LogEntry>>>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct TranslateRequest {
     pub config: LLMConfig,
     pub content: String,
     pub source: String,
     pub target: String,
+    #[serde(flatten)]
+    pub options: TranslateOptions,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct EnhanceRequest {
     pub config: LLMConfig,
     pub content: String,
     #[serde(default)]
     pub system_prompt: Option<String>,
+    /// Feature whose model override applies, `enhance` when omitted. The RAG
+    /// chat answers through this endpoint as `ragChat`.
+    #[serde(default)]
+    pub feature: Option<LlmFeature>,
+    /// Language the answer must be in, over the `outputLanguage` setting
+    #[serde(default)]
+    pub output_language: Option<String>,
+    /// Enhancement preset id, over the default `enhancePresets` preset
+    #[serde(default)]
+    pub preset_id: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct TypeGenRequest {
     pub config: LLMConfig,
     pub json: String,
+    /// Additional samples of the same payload, merged with `json`.
+    #[serde(default)]
+    pub samples: Vec<String>,
     pub language: String,
     pub root_name: String,
     #[serde(default)]
     pub mode: TypeGenMode,
+    #[serde(default)]
+    pub options: TypeGenOptions,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct TypeGenResponse {
     pub result: String,
 }
 
+#[derive(Deserialize, ToSchema)]
+pub struct TypeGenBatchRequest {
+    pub config: LLMConfig,
+    pub json: String,
+    /// Additional samples of the same payload, merged with `json`.
+    #[serde(default)]
+    pub samples: Vec<String>,
+    /// Target languages; repeats are generated once.
+    pub languages: Vec<String>,
+    pub root_name: String,
+    #[serde(default)]
+    pub mode: TypeGenMode,
+    #[serde(default)]
+    pub options: TypeGenOptions,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct TypeGenBatchResponse {
+    /// Generated source code by language, as named in the request.
+    pub results: BTreeMap<String, String>,
+    /// Languages that could not be generated, with the reason.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub errors: BTreeMap<String, ErrorPayload>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/translate",
+    tag = "translate",
+    summary = "Translate text, reusing translation memory and checking glossary rules",
+    request_body = TranslateRequest,
+    responses(
+        (status = 200, description = "Translated prompt", body = Prompt),
+        (status = 400, description = "Invalid input or missing provider settings", body = ErrorPayload),
+        (status = "5XX", description = "Provider or database error", body = ErrorPayload),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 429, description = "Rate limit reached; see the Retry-After header")
+    )
+)]
 #[post("/translate")]
 async fn translate(data: web::Data<HttpState>, req: web::Json<TranslateRequest>) -> impl Responder {
+    let config = data
+        .tauri_state
+        .model_resolver
+        .resolve(LlmFeature::Translate, &req.config);
     add_log(
         &data.logs,
         "INFO",
         "HttpApi",
         &format!(
             "Translating: {} -> {} (provider={:?} base_url={})",
-            req.source, req.target, req.config.provider, req.config.base_url
+            req.source, req.target, config.provider, config.base_url
         ),
     );
 
     match data
         .tauri_state
         .translate_use_case
-        .execute(
-            &req.req_data().config,
+        .execute_with_options(
+            &config,
             req.req_data().content.clone(),
             req.req_data().source.clone(),
             req.req_data().target.clone(),
+            &req.req_data().options,
         )
         .await
     {
@@ -82,30 +166,82 @@ async fn translate(data: web::Data<HttpState>, req: web::Json<TranslateRequest>)
                 "HttpApi",
                 &format!("Translation failed: {}", e),
             );
-            HttpResponse::InternalServerError().body(e.to_string())
+            error_response(&e)
         }
     }
 }
 
+#[derive(Deserialize, ToSchema)]
+pub struct DetectLanguageRequest {
+    pub text: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/detect-language",
+    tag = "translate",
+    summary = "Detect the language of a text offline",
+    request_body = DetectLanguageRequest,
+    responses(
+        (status = 200, description = "Detected language, or null when the text is too short or ambiguous", body = Option<DetectedLanguage>),
+        (status = 401, description = "Missing or invalid API token")
+    )
+)]
+#[post("/detect-language")]
+async fn detect_language(req: web::Json<DetectLanguageRequest>) -> impl Responder {
+    HttpResponse::Ok().json(language_detection::detect_language(&req.text))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/enhance",
+    tag = "enhance",
+    summary = "Rewrite a prompt with the configured model",
+    request_body = EnhanceRequest,
+    responses(
+        (status = 200, description = "Enhanced prompt", body = Prompt),
+        (status = 400, description = "Invalid input or missing provider settings", body = ErrorPayload),
+        (status = "5XX", description = "Provider error", body = ErrorPayload),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 429, description = "Rate limit reached; see the Retry-After header")
+    )
+)]
 #[post("/enhance")]
 async fn enhance(data: web::Data<HttpState>, req: web::Json<EnhanceRequest>) -> impl Responder {
+    let config = data
+        .tauri_state
+        .model_resolver
+        .resolve(req.feature.unwrap_or(LlmFeature::Enhance), &req.config);
     add_log(
         &data.logs,
         "INFO",
         "HttpApi",
         &format!(
             "Enhancing prompt (provider={:?} base_url={})",
-            req.config.provider, req.config.base_url
+            config.provider, config.base_url
         ),
     );
 
+    // Other features answer through this endpoint and never get the default preset
+    let preset = match data.tauri_state.enhance_preset(
+        req.preset_id.as_deref(),
+        req.system_prompt.is_some() || req.feature.is_some_and(|f| f != LlmFeature::Enhance),
+    ) {
+        Ok(preset) => preset,
+        Err(e) => return error_response(&e),
+    };
+
     match data
         .tauri_state
         .enhance_use_case
-        .execute(
-            &req.req_data().config,
+        .execute_with_preset(
+            &config,
             req.req_data().content.clone(),
             req.req_data().system_prompt.clone(),
+            preset.as_ref(),
+            data.tauri_state
+                .output_language(req.req_data().output_language.as_deref())
+                .as_ref(),
         )
         .await
     {
@@ -117,20 +253,38 @@ async fn enhance(data: web::Data<HttpState>, req: web::Json<EnhanceRequest>) ->
                 "HttpApi",
                 &format!("Enhancement failed: {}", e),
             );
-            HttpResponse::InternalServerError().body(e.to_string())
+            error_response(&e)
         }
     }
 }
 
-#[post("/typegen")]
+#[utoipa::path(
+    post,
+    path = "/api/typegen",
+    tag = "typegen",
+    summary = "Generate type definitions from JSON samples",
+    request_body = TypeGenRequest,
+    responses(
+        (status = 200, description = "Generated source code", body = TypeGenResponse),
+        (status = 400, description = "Invalid input or missing provider settings", body = ErrorPayload),
+        (status = "5XX", description = "Invalid JSON or provider error", body = ErrorPayload),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 429, description = "Rate limit reached; see the Retry-After header")
+    )
+)]
+#[post("")]
 async fn typegen(data: web::Data<HttpState>, req: web::Json<TypeGenRequest>) -> impl Responder {
+    let config = data
+        .tauri_state
+        .model_resolver
+        .resolve(LlmFeature::Typegen, &req.config);
     add_log(
         &data.logs,
         "INFO",
         "HttpApi",
         &format!(
             "Generating types (language={} mode={:?} provider={:?} base_url={})",
-            req.language, req.mode, req.config.provider, req.config.base_url
+            req.language, req.mode, config.provider, config.base_url
         ),
     );
 
@@ -138,11 +292,13 @@ async fn typegen(data: web::Data<HttpState>, req: web::Json<TypeGenRequest>) ->
         .tauri_state
         .typegen_use_case
         .execute(
-            &req.req_data().config,
+            &config,
             req.req_data().json.clone(),
+            req.req_data().samples.clone(),
             req.req_data().language.clone(),
             req.req_data().root_name.clone(),
             req.req_data().mode,
+            req.req_data().options,
         )
         .await
     {
@@ -154,11 +310,116 @@ async fn typegen(data: web::Data<HttpState>, req: web::Json<TypeGenRequest>) ->
                 "HttpApi",
                 &format!("Type generation failed: {}", e),
             );
-            HttpResponse::InternalServerError().body(e.to_string())
+            error_response(&e)
         }
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/typegen/batch",
+    tag = "typegen",
+    summary = "Generate type definitions for several languages from the same JSON samples",
+    request_body = TypeGenBatchRequest,
+    responses(
+        (status = 200, description = "Generated source code per language, plus the languages that failed", body = TypeGenBatchResponse),
+        (status = 400, description = "Invalid input or missing provider settings", body = ErrorPayload),
+        (status = "5XX", description = "Invalid JSON or provider error for every language", body = ErrorPayload),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 429, description = "Rate limit reached; see the Retry-After header")
+    )
+)]
+#[post("/batch")]
+async fn typegen_batch(
+    data: web::Data<HttpState>,
+    req: web::Json<TypeGenBatchRequest>,
+) -> impl Responder {
+    let config = data
+        .tauri_state
+        .model_resolver
+        .resolve(LlmFeature::Typegen, &req.config);
+    add_log(
+        &data.logs,
+        "INFO",
+        "HttpApi",
+        &format!(
+            "Generating types (languages={} mode={:?} provider={:?} base_url={})",
+            req.languages.join(","),
+            req.mode,
+            config.provider,
+            config.base_url
+        ),
+    );
+
+    let outputs = match data
+        .tauri_state
+        .typegen_use_case
+        .execute_batch(
+            &config,
+            req.req_data().json.clone(),
+            req.req_data().samples.clone(),
+            req.req_data().languages.clone(),
+            req.req_data().root_name.clone(),
+            req.req_data().mode,
+            req.req_data().options,
+        )
+        .await
+    {
+        Ok(outputs) => outputs,
+        Err(e) => {
+            add_log(
+                &data.logs,
+                "ERROR",
+                "HttpApi",
+                &format!("Type generation failed: {}", e),
+            );
+            return error_response(&e);
+        }
+    };
+
+    let mut results = BTreeMap::new();
+    let mut errors = BTreeMap::new();
+    let mut first_error = None;
+    for (language, output) in outputs {
+        match output {
+            Ok(code) => {
+                results.insert(language, code);
+            }
+            Err(e) => {
+                add_log(
+                    &data.logs,
+                    "ERROR",
+                    "HttpApi",
+                    &format!("Type generation failed for {}: {}", language, e),
+                );
+                errors.insert(language, ErrorPayload::from(e.clone()));
+                if first_error.is_none() {
+                    first_error = Some(e);
+                }
+            }
+        }
+    }
+
+    // Nothing generated: answer like the single-language endpoint would
+    match first_error {
+        Some(e) if results.is_empty() => error_response(&e),
+        _ => HttpResponse::Ok().json(TypeGenBatchResponse { results, errors }),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/models",
+    tag = "models",
+    summary = "List the models offered by a provider",
+    request_body = LLMConfig,
+    responses(
+        (status = 200, description = "Model identifiers", body = Vec<String>),
+        (status = 400, description = "Invalid input or missing provider settings", body = ErrorPayload),
+        (status = "5XX", description = "Provider error", body = ErrorPayload),
+        (status = 401, description = "Missing or invalid API token")
+    )
+)]
 #[post("/models")]
 async fn list_models(data: web::Data<HttpState>, config: web::Json<LLMConfig>) -> impl Responder {
     add_log(
@@ -180,18 +441,18 @@ async fn list_models(data: web::Data<HttpState>, config: web::Json<LLMConfig>) -
                 "HttpApi",
                 &format!("Failed to list models: {}", e),
             );
-            HttpResponse::InternalServerError().body(e.to_string())
+            error_response(&e)
         }
     }
 }
 
-async fn fetch_openrouter_list(
-    config: &LLMConfig,
-    path: &str,
-) -> std::result::Result<Vec<serde_json::Value>, String> {
+async fn fetch_openrouter_list(config: &LLMConfig, path: &str) -> Result<Vec<serde_json::Value>> {
     let base_url = config.base_url.trim_end_matches('/');
     if base_url.is_empty() {
-        return Err("OpenRouter base_url is empty".to_string());
+        return Err(AppError::coded(
+            ErrorCode::ConfigMissing,
+            "OpenRouter base_url is empty",
+        ));
     }
     let url = format!("{}/{}", base_url, path);
 
@@ -200,29 +461,39 @@ async fn fetch_openrouter_list(
         request = request.bearer_auth(api_key);
     }
 
-    let response = request
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
+    let response = request.send().await.map_err(request_error)?;
 
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
-        return Err(format!("API error ({}): {}", status, text));
+        return Err(api_error(status, &text));
     }
 
     let json: serde_json::Value = response
         .json()
         .await
-        .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+        .map_err(|e| AppError::ParseError(format!("Failed to parse JSON: {}", e)))?;
 
-    let data = json["data"]
-        .as_array()
-        .ok_or_else(|| "Invalid response format: missing data array".to_string())?;
+    let data = json["data"].as_array().ok_or_else(|| {
+        AppError::ParseError("Invalid response format: missing data array".to_string())
+    })?;
 
     Ok(data.iter().cloned().collect())
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/openrouter/providers",
+    tag = "models",
+    summary = "List OpenRouter providers",
+    request_body = LLMConfig,
+    responses(
+        (status = 200, description = "Raw OpenRouter provider objects", body = Vec<serde_json::Value>),
+        (status = 400, description = "Invalid input or missing provider settings", body = ErrorPayload),
+        (status = "5XX", description = "OpenRouter error", body = ErrorPayload),
+        (status = 401, description = "Missing or invalid API token")
+    )
+)]
 #[post("/openrouter/providers")]
 async fn openrouter_providers(
     data: web::Data<HttpState>,
@@ -239,11 +510,24 @@ async fn openrouter_providers(
                 "OpenRouter",
                 &format!("Failed to fetch providers: {}", err),
             );
-            HttpResponse::InternalServerError().body(err)
+            error_response(&err)
         }
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/openrouter/models",
+    tag = "models",
+    summary = "List OpenRouter models",
+    request_body = LLMConfig,
+    responses(
+        (status = 200, description = "Raw OpenRouter model objects", body = Vec<serde_json::Value>),
+        (status = 400, description = "Invalid input or missing provider settings", body = ErrorPayload),
+        (status = "5XX", description = "OpenRouter error", body = ErrorPayload),
+        (status = 401, description = "Missing or invalid API token")
+    )
+)]
 #[post("/openrouter/models")]
 async fn openrouter_models(
     data: web::Data<HttpState>,
@@ -260,459 +544,38 @@ async fn openrouter_models(
                 "OpenRouter",
                 &format!("Failed to fetch models: {}", err),
             );
-            HttpResponse::InternalServerError().body(err)
+            error_response(&err)
         }
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/logs",
+    tag = "logs",
+    summary = "Recent in-memory log entries",
+    responses(
+        (status = 200, description = "Log entries, oldest first", body = Vec<LogEntry>),
+        (status = 401, description = "Missing or invalid API token")
+    )
+)]
 #[get("/logs")]
 async fn get_logs(data: web::Data<HttpState>) -> impl Responder {
     let logs = data.logs.lock().unwrap();
     HttpResponse::Ok().json(&*logs)
 }
 
-#[derive(Deserialize)]
-struct ProxyQuery {
-    url: String,
-}
-
-#[get("/qa/proxy")]
-async fn qa_proxy(data: web::Data<HttpState>, query: web::Query<ProxyQuery>) -> impl Responder {
-    let target_url = &query.url;
-
-    add_log(
-        &data.logs,
-        "INFO",
-        "QA Proxy",
-        &format!("Proxying request to: {}", target_url),
-    );
-
-    // Fetch the HTML from target URL
-    let client = reqwest::Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
-        .danger_accept_invalid_certs(true) // For localhost development
-        .build()
-        .unwrap();
-
-    match client.get(target_url).send().await {
-        Ok(response) => {
-            if !response.status().is_success() {
-                add_log(
-                    &data.logs,
-                    "ERROR",
-                    "QA Proxy",
-                    &format!("Failed to fetch URL: HTTP {}", response.status()),
-                );
-                return HttpResponse::BadGateway()
-                    .insert_header(("Access-Control-Allow-Origin", "*"))
-                    .body(format!("Failed to fetch URL: HTTP {}", response.status()));
-            }
-
-            match response.text().await {
-                Ok(html) => {
-                    // Inject the recorder script into the HTML
-                    let injected_html = inject_recorder_script(&html, target_url);
-
-                    add_log(
-                        &data.logs,
-                        "INFO",
-                        "QA Proxy",
-                        "Successfully proxied and injected recorder script",
-                    );
-
-                    HttpResponse::Ok()
-                        .content_type("text/html; charset=utf-8")
-                        .insert_header(("Access-Control-Allow-Origin", "*"))
-                        .insert_header(("Access-Control-Allow-Methods", "GET, POST, OPTIONS"))
-                        .insert_header(("Access-Control-Allow-Headers", "*"))
-                        .insert_header(("X-Frame-Options", "ALLOWALL"))
-                        .body(injected_html)
-                }
-                Err(e) => {
-                    add_log(
-                        &data.logs,
-                        "ERROR",
-                        "QA Proxy",
-                        &format!("Failed to read response body: {}", e),
-                    );
-                    HttpResponse::InternalServerError()
-                        .insert_header(("Access-Control-Allow-Origin", "*"))
-                        .body(format!("Failed to read response: {}", e))
-                }
-            }
-        }
-        Err(e) => {
-            add_log(
-                &data.logs,
-                "ERROR",
-                "QA Proxy",
-                &format!("Failed to fetch URL: {}", e),
-            );
-            HttpResponse::BadGateway()
-                .insert_header(("Access-Control-Allow-Origin", "*"))
-                .body(format!("Failed to fetch URL: {}", e))
-        }
-    }
-}
-
-fn inject_recorder_script(html: &str, base_url: &str) -> String {
-    // Parse base URL to extract origin for proper script/resource loading
-    let _origin = if let Ok(url) = url::Url::parse(base_url) {
-        format!("{}://{}", url.scheme(), url.host_str().unwrap_or(""))
-    } else {
-        "http://localhost:1420".to_string()
+/// JSON `ErrorPayload` with the status matching the error's category
+pub(crate) fn error_response(err: &AppError) -> HttpResponse {
+    let status = match err.category() {
+        ErrorCategory::Validation | ErrorCategory::Config => StatusCode::BAD_REQUEST,
+        ErrorCategory::NotFound => StatusCode::NOT_FOUND,
+        ErrorCategory::Security => StatusCode::FORBIDDEN,
+        ErrorCategory::Network => StatusCode::BAD_GATEWAY,
+        ErrorCategory::Dependency => StatusCode::SERVICE_UNAVAILABLE,
+        ErrorCategory::Storage | ErrorCategory::Internal => StatusCode::INTERNAL_SERVER_ERROR,
     };
-
-    // Use the origin as base href for proper resource loading
-    // let base_href = format!("{}/", origin);
-
-    // Inject recorder script and base tag with CSP bypass
-    let injection = format!(
-        r#"<base href=\"{}\">
-<meta http-equiv=\"Content-Security-Policy\" content=\"default-src * 'unsafe-inline' 'unsafe-eval' data: blob:;\">
-<script>
-// QA Recorder Injectable Script - Injected by Proxy
-(function() {{
-  'use strict';
-
-  const INPUT_DEBOUNCE_MS = 350;
-  const MAX_TEXT_LENGTH = 160;
-
-  let inputTimers = new Map();
-  let lastPointer = null;
-  let lastFocusedElement = null;
-
-  if (window.__QA_RECORDER_INJECTED__) {{
-    return;
-  }}
-  window.__QA_RECORDER_INJECTED__ = true;
-
-  console.log('[QA Recorder Inject] Script loaded via proxy');
-
-  function postEventToParent(payload) {{
-    window.parent.postMessage({{
-      type: 'qa-recorder-event',
-      payload: payload
-    }}, '*');
-  }}
-
-  function buildSelector(element) {{
-    const prioritized = ['data-testid', 'data-purpose', 'id', 'name', 'aria-label', 'role'];
-    for (const attr of prioritized) {{
-      const value = element.getAttribute(attr);
-      if (!value) continue;
-      if (attr === 'id') return `#${{CSS.escape(value)}}`;
-      return `${{element.tagName.toLowerCase()}}[${{attr}}=\"${{CSS.escape(value)}}\"]`;
-    }}
-    const path = [];
-    let current = element;
-    const rootBody = element.ownerDocument?.body;
-    for (let depth = 0; current && current !== rootBody && depth < 4; depth++) {{
-      const tagName = current.tagName.toLowerCase();
-      const index = nthOfType(current);
-      path.unshift(`${{tagName}}:nth-of-type(${{index}})`);
-      current = current.parentElement;
-    }}
-    return path.length > 0 ? path.join(' > ') : undefined;
-  }}
-
-  function nthOfType(element) {{
-    let index = 1;
-    let sibling = element.previousElementSibling;
-    while (sibling) {{
-      if (sibling.tagName === element.tagName) index++;
-      sibling = sibling.previousElementSibling;
-    }}
-    return index;
-  }}
-
-  function getElementText(element) {{
-    if (element instanceof HTMLInputElement || element instanceof HTMLTextAreaElement) {{
-      return normalizeText(element.getAttribute('aria-label') || element.placeholder || element.name);
-    }}
-    if (element instanceof HTMLSelectElement) {{
-      const selected = element.selectedOptions?.[0]?.textContent;
-      return normalizeText(selected || element.getAttribute('aria-label') || element.name);
-    }}
-    return normalizeText(element.textContent);
-  }}
-
-  function getElementValue(element) {{
-    if (element instanceof HTMLInputElement) return element.value;
-    if (element instanceof HTMLTextAreaElement) return element.value;
-    if (element instanceof HTMLSelectElement) return element.value;
-    if (element instanceof HTMLElement && element.isContentEditable) return element.innerText;
-    return undefined;
-  }}
-
-  function maskValue(element, value) {{
-    if (!value || value.trim().length === 0) return undefined;
-    if (element instanceof HTMLInputElement && element.type === 'password') return '[masked]';
-    const label = [element.getAttribute('name'), element.getAttribute('id'), element.getAttribute('aria-label')]
-      .filter(Boolean).join(' ').toLowerCase();
-    if (label.includes('password')) return '[masked]';
-    return value;
-  }}
-
-  function normalizeText(value) {{
-    if (!value) return undefined;
-    const trimmed = value.trim();
-    if (!trimmed) return undefined;
-    return trimmed.length > MAX_TEXT_LENGTH ? trimmed.slice(0, MAX_TEXT_LENGTH) : trimmed;
-  }}
-
-  function stringifyMeta(meta) {{
-    const cleaned = {{}};
-    Object.entries(meta).forEach(([key, value]) => {{
-      if (value === undefined || value === null || value === '') return;
-      cleaned[key] = value;
-    }});
-    return Object.keys(cleaned).length > 0 ? JSON.stringify(cleaned) : undefined;
-  }}
-
-  function getCoordinates(event) {{
-    if (event instanceof MouseEvent) return {{ x: event.clientX, y: event.clientY }};
-    return lastPointer ?? undefined;
-  }}
-
-  function handlePointerDown(event) {{
-    const coords = getCoordinates(event);
-    if (coords) lastPointer = coords;
-  }}
-
-  function handleClick(event) {{
-    const target = event.target;
-    if (!(target instanceof Element)) return;
-    const isEditable =
-      target instanceof HTMLInputElement ||
-      target instanceof HTMLTextAreaElement ||
-      target instanceof HTMLSelectElement ||
-      (target instanceof HTMLElement && target.isContentEditable);
-    postEventToParent({{
-      eventType: 'click',
-      selector: buildSelector(target),
-      elementText: getElementText(target),
-      url: window.location.href,
-      metaJson: stringifyMeta({{
-        tag: target.tagName.toLowerCase(),
-        type: target instanceof HTMLInputElement ? target.type : undefined,
-        isEditable,
-        coordinates: getCoordinates(event)
-      }}),
-    }});
-  }}
-
-  function handleInput(event) {{
-    const target = event.target;
-    if (!(target instanceof Element)) return;
-    if (!(target instanceof HTMLInputElement || target instanceof HTMLTextAreaElement || target instanceof HTMLSelectElement)) return;
-    const inputType = event.inputType;
-    const previousTimer = inputTimers.get(target);
-    if (previousTimer) clearTimeout(previousTimer);
-    const nextTimer = setTimeout(() => {{
-      inputTimers.delete(target);
-      const rawValue = getElementValue(target);
-      const maskedValue = maskValue(target, rawValue);
-      postEventToParent({{
-        eventType: 'input',
-        selector: buildSelector(target),
-        elementText: getElementText(target),
-        value: maskedValue,
-        url: window.location.href,
-        metaJson: stringifyMeta({{
-          tag: target.tagName.toLowerCase(),
-          inputType,
-          type: target instanceof HTMLInputElement ? target.type : undefined,
-          coordinates: getCoordinates(event),
-        }}),
-      }});
-    }}, INPUT_DEBOUNCE_MS);
-    inputTimers.set(target, nextTimer);
-  }}
-
-  function handleSubmit(event) {{
-    const target = event.target;
-    if (!(target instanceof Element)) return;
-    const form = target instanceof HTMLFormElement ? target : target.closest('form');
-    const element = form ?? target;
-    postEventToParent({{
-      eventType: 'submit',
-      selector: buildSelector(element),
-      elementText: getElementText(element),
-      url: window.location.href,
-      metaJson: stringifyMeta({{
-        tag: element.tagName.toLowerCase(),
-        action: form?.action,
-        method: form?.method,
-        coordinates: getCoordinates(event),
-      }}),
-    }});
-  }}
-
-  document.addEventListener('pointerdown', handlePointerDown, true);
-  document.addEventListener('click', handleClick, true);
-  document.addEventListener('input', handleInput, true);
-  document.addEventListener('submit', handleSubmit, true);
-  document.addEventListener(
-    'focusin',
-    (event) => {{
-      if (event.target instanceof Element) {{
-        lastFocusedElement = event.target;
-      }}
-    }},
-    true
-  );
-
-  function handleParentCommand(event) {{
-    if (event.source !== window.parent) return;
-    if (!event.data || event.data.type !== 'qa-recorder-command') return;
-
-    const action = event.data.action;
-    if (action === 'back') {{
-      window.history.back();
-      return;
-    }}
-    if (action === 'refocus') {{
-      if (lastFocusedElement && document.contains(lastFocusedElement)) {{
-        if (typeof lastFocusedElement.focus === 'function') {{
-          lastFocusedElement.focus({{ preventScroll: true }});
-        }}
-      }}
-      return;
-    }}
-    if (action === 'capture') {{
-      const requestId = event.data.requestId;
-      captureDocumentAsDataUrl()
-        .then((dataUrl) => {{
-          window.parent.postMessage(
-            {{ type: 'qa-recorder-capture', requestId, dataUrl }},
-            '*'
-          );
-        }})
-        .catch((err) => {{
-          window.parent.postMessage(
-            {{
-              type: 'qa-recorder-capture-error',
-              requestId,
-              error: err?.message || 'Failed to capture preview.',
-            }},
-            '*'
-          );
-        }});
-    }}
-  }}
-
-  async function captureDocumentAsDataUrl() {{
-    try {{
-      return await renderDocumentToDataUrl(document.documentElement);
-    }} catch (err) {{
-      if (isTaintedCanvasError(err)) {{
-        const sanitized = sanitizeDocumentElement(document.documentElement);
-        return renderDocumentToDataUrl(sanitized);
-      }}
-      throw err;
-    }}
-  }}
-
-  function isTaintedCanvasError(err) {{
-    const message = err?.message || '';
-    return (
-      message.includes('Tainted canvases') ||
-      message.includes('SecurityError')
-    );
-  }}
-
-  function sanitizeDocumentElement(root) {{
-    const clone = root.cloneNode(true);
-    const stripSelectors = [
-      'img',
-      'picture',
-      'source',
-      'video',
-      'audio',
-      'canvas',
-      'iframe',
-      'svg',
-      'link[rel="stylesheet"]',
-    ];
-    clone.querySelectorAll(stripSelectors.join(',')).forEach((el) => el.remove());
-
-    clone.querySelectorAll('style').forEach((style) => {{
-      if (!style.textContent) return;
-      let text = style.textContent;
-      text = text.replace(/@font-face\s*\{{[\s\S]*?\}}/g, '');
-      text = text.replace(/url\(([^)]+)\)/g, 'none');
-      style.textContent = text;
-    }});
-
-    clone.querySelectorAll('[style]').forEach((el) => {{
-      const inline = el.getAttribute('style');
-      if (!inline || !inline.includes('url(')) return;
-      const cleaned = inline.replace(/url\(([^)]+)\)/g, 'none');
-      el.setAttribute('style', cleaned);
-    }});
-
-    return clone;
-  }}
-
-  async function renderDocumentToDataUrl(root) {{
-    const safeWidth = Math.max(1, Math.floor(window.innerWidth));
-    const safeHeight = Math.max(1, Math.floor(window.innerHeight));
-    const serialized = new XMLSerializer().serializeToString(root);
-    const wrapped = `<div xmlns=\"http://www.w3.org/1999/xhtml\">${{serialized}}</div>`;
-    const svg = `<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"${{safeWidth}}\" height=\"${{safeHeight}}\"><foreignObject width=\"100%\" height=\"100%\">${{wrapped}}</foreignObject></svg>`;
-    const blob = new Blob([svg], {{ type: 'image/svg+xml;charset=utf-8' }});
-    const url = URL.createObjectURL(blob);
-
-    try {{
-      const img = await new Promise((resolve, reject) => {{
-        const image = new Image();
-        image.onload = () => resolve(image);
-        image.onerror = () => reject(new Error('Failed to render preview snapshot.'));
-        image.src = url;
-      }});
-
-      const canvas = document.createElement('canvas');
-      canvas.width = safeWidth;
-      canvas.height = safeHeight;
-      const ctx = canvas.getContext('2d');
-      if (!ctx) {{
-        throw new Error('Canvas is not available for screenshot.');
-      }}
-      ctx.drawImage(img, 0, 0, safeWidth, safeHeight);
-      return canvas.toDataURL('image/png');
-    }} finally {{
-      URL.revokeObjectURL(url);
-    }}
-  }}
-
-  window.addEventListener('message', handleParentCommand);
-
-  window.parent.postMessage({{ type: 'qa-recorder-ready' }}, '*');
-  console.log('[QA Recorder Inject] Ready');
-}})();
-</script>
-"#,
-        base_url
-    );
-
-    // Try to inject right after <head> tag, fallback to before </head>, finally before <body>
-    if let Some(pos) = html.find("<head>") {
-        let insert_pos = pos + "<head>".len();
-        format!(
-            "{}{}{}",
-            &html[..insert_pos],
-            injection,
-            &html[insert_pos..]
-        )
-    } else if let Some(pos) = html.find("</head>") {
-        format!("{}{}{}", &html[..pos], injection, &html[pos..])
-    } else if let Some(pos) = html.find("<body") {
-        format!("{}{}{}", &html[..pos], injection, &html[pos..])
-    } else {
-        // Fallback: prepend to entire HTML
-        format!("{}{}", injection, html)
-    }
+    HttpResponse::build(status).json(ErrorPayload::from(err.clone()))
 }
 
 pub fn add_log_entry(
@@ -725,8 +588,11 @@ pub fn add_log_entry(
         time: Local::now().format("%H:%M:%S").to_string(),
         level: level.to_string(),
         source: source.to_string(),
-        message: message.to_string(),
+        message: redact_secrets(message),
     };
+    crate::infrastructure::logging::record(&entry.level, &entry.source, &entry.message);
+
+    // Recent entries stay in memory for `get_logs`; history lives in the log files
     let mut logs = logs.lock().unwrap();
     logs.push(entry.clone());
     if logs.len() > 100 {
@@ -739,28 +605,100 @@ pub fn add_log(logs: &Mutex<Vec<LogEntry>>, level: &str, source: &str, message:
     add_log_entry(logs, level, source, message);
 }
 
-pub fn start_server(
-    tauri_state: Arc<AppState>,
-    logs: Arc<Mutex<Vec<LogEntry>>>,
-) -> std::io::Result<Server> {
+/// Start the local HTTP API from the `httpServer` settings, replacing a running
+/// instance. A port picked because the configured one was taken is persisted.
+pub async fn start_server(tauri_state: Arc<AppState>) -> Result<SocketAddr> {
+    let mut settings = tauri_state.settings.get::<HttpServerSettings>();
+    let address = settings.validate()?;
+    let token = if settings.require_token {
+        Some(tauri_state.config_service.http_api_token()?)
+    } else {
+        None
+    };
+
+    let control = &tauri_state.http_server;
+    // The running server keeps serving until the new listener is bound, so a
+    // failed bind leaves it up. Only a restart on its own port frees it first.
+    if control
+        .address()
+        .is_some_and(|running| running.port() == settings.port)
+    {
+        control.stop().await;
+    }
+
+    let listener = server_control::bind_listener(address, settings.port, settings.auto_port)?;
+    let bound = listener.local_addr()?;
+    let logs = tauri_state.logs.clone();
+    let server = build_server(tauri_state.clone(), listener)?;
+
+    control.stop().await;
+    control.set_token(token);
+    control.rate_limiter().configure(&settings.rate_limits);
+    control.set_running(server.handle(), bound);
+    tokio::spawn(server);
+
+    if bound.port() != settings.port {
+        add_log(
+            &logs,
+            "WARN",
+            "HttpApi",
+            &format!(
+                "Port {} is in use; HTTP API moved to port {}",
+                settings.port,
+                bound.port()
+            ),
+        );
+        settings.port = bound.port();
+        if let Err(err) = tauri_state.settings.set(&settings) {
+            add_log(
+                &logs,
+                "ERROR",
+                "HttpApi",
+                &format!("Failed to persist HTTP port: {}", err),
+            );
+        }
+    }
+    Ok(bound)
+}
+
+fn build_server(tauri_state: Arc<AppState>, listener: TcpListener) -> std::io::Result<Server> {
+    let logs = tauri_state.logs.clone();
     let state = web::Data::new(HttpState { tauri_state, logs });
 
     let server = HttpServer::new(move || {
         let cors = Cors::permissive(); // Allow all origins for local tool
 
-        App::new().wrap(cors).app_data(state.clone()).service(
-            web::scope("/api")
-                .service(translate)
-                .service(enhance)
-                .service(typegen)
-                .service(list_models)
-                .service(openrouter_providers)
-                .service(openrouter_models)
-                .service(get_logs)
-                .service(qa_proxy),
-        )
+        // Registered before CORS so rejected requests still carry CORS headers;
+        // rate limits sit inside the token check so only valid clients count
+        App::new()
+            .wrap(from_fn(rate_limit::enforce_rate_limits))
+            .wrap(from_fn(server_control::require_api_token))
+            .wrap(cors)
+            .app_data(state.clone())
+            // Must precede the `/api` scope, which would otherwise answer 404
+            .service(SwaggerUi::new("/api/docs/{_:.*}").url(openapi::SPEC_PATH, ApiDoc::openapi()))
+            .service(
+                web::scope("/api")
+                    .service(translate)
+                    .service(detect_language)
+                    .service(enhance)
+                    .service(
+                        // Typegen accepts multi-megabyte API captures; actix
+                        // defaults to 2 MiB.
+                        web::scope("/typegen")
+                            .app_data(web::JsonConfig::default().limit(32 * 1024 * 1024))
+                            .service(typegen)
+                            .service(typegen_batch),
+                    )
+                    .service(list_models)
+                    .service(openrouter_providers)
+                    .service(openrouter_models)
+                    .service(get_logs)
+                    .configure(qa_proxy::configure)
+                    .configure(rag::configure),
+            )
     })
-    .bind(("127.0.0.1", 3001))?
+    .listen(listener)?
     .run();
 
     Ok(server)
@@ -0,0 +1,98 @@
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+/// Where the generated spec is served
+pub(super) const SPEC_PATH: &str = "/api/openapi.json";
+
+/// Swagger UI mount point
+pub(super) const DOCS_PATH: &str = "/api/docs";
+
+/// OpenAPI document for the local HTTP API, generated from the handler annotations
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Gadogado local API",
+        description = "HTTP API served by the desktop app for translation, prompt enhancement, type generation and the QA recorder."
+    ),
+    paths(
+        super::translate,
+        super::detect_language,
+        super::enhance,
+        super::typegen,
+        super::typegen_batch,
+        super::list_models,
+        super::openrouter_providers,
+        super::openrouter_models,
+        super::get_logs,
+        super::qa_proxy::qa_proxy,
+        super::qa_proxy::qa_proxy_resource,
+        super::rag::rag_collections,
+        super::rag::rag_query,
+        super::rag::rag_chat,
+    ),
+    modifiers(&ApiTokenAddon),
+    // The token is only enforced when `httpServer.requireToken` is on
+    security((), ("api_token" = [])),
+    tags(
+        (name = "translate", description = "Translation and language detection"),
+        (name = "enhance", description = "Prompt enhancement"),
+        (name = "typegen", description = "Type generation from JSON"),
+        (name = "models", description = "Provider model listings"),
+        (name = "logs", description = "In-memory application log"),
+        (name = "qa", description = "QA recorder proxy"),
+        (name = "rag", description = "Knowledge base retrieval and chat"),
+    )
+)]
+pub struct ApiDoc;
+
+struct ApiTokenAddon;
+
+impl Modify for ApiTokenAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "api_token",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .description(Some(
+                        "Token from Settings > HTTP API. The QA proxy instead accepts its own token as the `access_token` query parameter.",
+                    ))
+                    .build(),
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_covers_every_route() {
+        let spec = ApiDoc::openapi();
+        for path in [
+            "/api/translate",
+            "/api/detect-language",
+            "/api/enhance",
+            "/api/typegen",
+            "/api/typegen/batch",
+            "/api/models",
+            "/api/openrouter/providers",
+            "/api/openrouter/models",
+            "/api/logs",
+            "/api/qa/proxy",
+            "/api/qa/proxy/resource",
+            "/api/rag/collections",
+            "/api/rag/query",
+            "/api/rag/chat",
+        ] {
+            assert!(spec.paths.paths.contains_key(path), "missing {}", path);
+        }
+
+        let json = spec.to_json().unwrap();
+        assert!(json.contains("\"TranslateRequest\""));
+        assert!(json.contains("\"ErrorPayload\""));
+        assert!(json.contains("\"api_token\""));
+    }
+}
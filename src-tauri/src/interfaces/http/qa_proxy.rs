@@ -0,0 +1,1110 @@
+//! QA recorder proxy
+//!
+//! Serves the page under test to the preview iframe with the recorder script
+//! injected. The `qaProxy` settings adapt it to its new origin: origins are
+//! rewritten in forwarded URLs and text responses, upstream cookies lose their
+//! `Domain` so the browser keeps them, and configured headers are added. The
+//! page's fetch/XHR calls go through `/api/qa/proxy/resource` so they avoid
+//! CORS and show up in the recording, and its iframes from captured origins
+//! load through the proxy so events inside them are recorded as well.
+
+use actix_web::http::header;
+use actix_web::http::StatusCode;
+use actix_web::{get, route, web, HttpRequest, HttpResponse, HttpResponseBuilder, Responder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::IntoParams;
+
+use crate::domain::qa_selectors::QaSelectorSettings;
+use crate::domain::settings::{normalize_origin, QaProxySettings};
+
+use super::{add_log, HttpState};
+
+/// Endpoint nested frames of a captured origin are loaded through
+const PAGE_PATH: &str = "/api/qa/proxy";
+
+/// Endpoint the injected script sends captured calls to
+const RESOURCE_PATH: &str = "/api/qa/proxy/resource";
+
+/// Request headers passed on to the upstream server
+const FORWARDED_REQUEST_HEADERS: &[&str] = &[
+    "accept",
+    "accept-language",
+    "authorization",
+    "content-type",
+    "cookie",
+    "x-requested-with",
+];
+
+/// Response headers passed back besides `Content-Type` and `Set-Cookie`
+const FORWARDED_RESPONSE_HEADERS: &[&str] = &["cache-control", "etag", "last-modified"];
+
+#[derive(Deserialize, IntoParams)]
+struct ProxyQuery {
+    /// Page to load into the QA recorder
+    url: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+struct ProxyPageQuery {
+    /// Page to load into the QA recorder
+    url: String,
+    /// QA session whose selector attributes the recorder uses; the
+    /// `qaSelectors` settings apply without one
+    session_id: Option<String>,
+}
+
+/// Settings handed to the injected script
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CaptureConfig {
+    enabled: bool,
+    origins: Vec<String>,
+    page_url: String,
+    resource_url: String,
+    access_token: Option<String>,
+    /// Passed on to nested frames, which load through the proxy too
+    session_id: Option<String>,
+    /// Priority of the attributes selectors are built from
+    selector_attributes: Vec<String>,
+}
+
+/// `qaProxy` settings with their origins normalized
+struct ProxyRules {
+    settings: QaProxySettings,
+    rewrites: Vec<(String, String)>,
+}
+
+impl ProxyRules {
+    fn new(settings: QaProxySettings) -> Self {
+        let rewrites = settings
+            .origin_rewrites
+            .iter()
+            .filter_map(|rule| Some((normalize_origin(&rule.from)?, normalize_origin(&rule.to)?)))
+            .collect();
+        Self { settings, rewrites }
+    }
+
+    fn rewrite_url(&self, url: &str) -> String {
+        for (from, to) in &self.rewrites {
+            if let Some(rest) = url.strip_prefix(from.as_str()) {
+                if rest.is_empty() || rest.starts_with(['/', '?', '#']) {
+                    return format!("{}{}", to, rest);
+                }
+            }
+        }
+        url.to_string()
+    }
+
+    fn rewrite_body(&self, body: String) -> String {
+        self.rewrites
+            .iter()
+            .fold(body, |body, (from, to)| body.replace(from.as_str(), to))
+    }
+
+    /// Adapt an upstream `Set-Cookie` value to the proxy origin
+    fn rewrite_set_cookie(&self, cookie: &str) -> String {
+        if !self.settings.strip_cookie_domain {
+            return cookie.to_string();
+        }
+        cookie
+            .split(';')
+            .filter(|attr| {
+                !attr
+                    .trim_start()
+                    .to_ascii_lowercase()
+                    .starts_with("domain=")
+            })
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+
+    /// The page's own origin plus both sides of every rewrite
+    fn capture_origins(&self, page_url: &str) -> Vec<String> {
+        let mut origins: Vec<String> = url::Url::parse(page_url)
+            .ok()
+            .and_then(|url| normalize_origin(&url.origin().ascii_serialization()))
+            .into_iter()
+            .collect();
+        for (from, to) in &self.rewrites {
+            for origin in [from, to] {
+                if !origins.contains(origin) {
+                    origins.push(origin.clone());
+                }
+            }
+        }
+        origins
+    }
+
+    fn capture_config(
+        &self,
+        req: &HttpRequest,
+        page_url: &str,
+        selector_attributes: Vec<String>,
+    ) -> CaptureConfig {
+        let info = req.connection_info();
+        CaptureConfig {
+            enabled: self.settings.capture_subresources,
+            origins: self.capture_origins(page_url),
+            page_url: format!("{}://{}{}", info.scheme(), info.host(), PAGE_PATH),
+            resource_url: format!("{}://{}{}", info.scheme(), info.host(), RESOURCE_PATH),
+            access_token: query_param(req, "access_token"),
+            session_id: query_param(req, "session_id"),
+            selector_attributes,
+        }
+    }
+
+    /// Send `req` on to `url` with the forwarded and injected headers
+    async fn forward(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        req: &HttpRequest,
+        body: Option<web::Bytes>,
+    ) -> reqwest::Result<reqwest::Response> {
+        let client = reqwest::Client::builder()
+            .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
+            .danger_accept_invalid_certs(true) // For localhost development
+            .build()?;
+
+        let mut request = client.request(method, self.rewrite_url(url));
+        for name in FORWARDED_REQUEST_HEADERS {
+            for value in req.headers().get_all(*name) {
+                request = request.header(*name, value.as_bytes());
+            }
+        }
+        for injected in &self.settings.inject_headers {
+            request = request.header(injected.name.trim(), injected.value.as_str());
+        }
+        if let Some(body) = body.filter(|body| !body.is_empty()) {
+            request = request.body(body.to_vec());
+        }
+        request.send().await
+    }
+
+    /// Response builder with the upstream cookies and cacheable headers
+    fn relay_headers(
+        &self,
+        status: StatusCode,
+        upstream: &reqwest::Response,
+    ) -> HttpResponseBuilder {
+        let mut response = HttpResponse::build(status);
+        response
+            .insert_header(("Access-Control-Allow-Origin", "*"))
+            .insert_header(("Access-Control-Allow-Methods", "GET, POST, OPTIONS"))
+            .insert_header(("Access-Control-Allow-Headers", "*"));
+        for name in FORWARDED_RESPONSE_HEADERS {
+            if let Some(value) = upstream.headers().get(*name).and_then(|v| v.to_str().ok()) {
+                response.insert_header((*name, value));
+            }
+        }
+        for cookie in upstream.headers().get_all("set-cookie") {
+            if let Ok(cookie) = cookie.to_str() {
+                response.append_header((header::SET_COOKIE, self.rewrite_set_cookie(cookie)));
+            }
+        }
+        response
+    }
+}
+
+fn load_rules(data: &HttpState) -> ProxyRules {
+    ProxyRules::new(data.tauri_state.settings.get::<QaProxySettings>())
+}
+
+fn query_param(req: &HttpRequest, name: &str) -> Option<String> {
+    web::Query::<HashMap<String, String>>::from_query(req.query_string())
+        .ok()
+        .and_then(|query| query.get(name).cloned())
+}
+
+fn is_text(content_type: &str) -> bool {
+    let content_type = content_type.to_ascii_lowercase();
+    content_type.starts_with("text/")
+        || ["javascript", "json", "xml"]
+            .iter()
+            .any(|kind| content_type.contains(kind))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/qa/proxy",
+    tag = "qa",
+    summary = "Fetch a page and inject the QA recorder script",
+    params(ProxyPageQuery),
+    responses(
+        (status = 200, description = "Page HTML with the recorder injected", body = String, content_type = "text/html"),
+        (status = 502, description = "Target URL unreachable or returned an error", body = String, content_type = "text/plain"),
+        (status = 500, description = "Failed to read the upstream response", body = String, content_type = "text/plain"),
+        (status = 401, description = "Missing or invalid API token")
+    )
+)]
+#[get("/qa/proxy")]
+pub(super) async fn qa_proxy(
+    data: web::Data<HttpState>,
+    req: HttpRequest,
+    query: web::Query<ProxyPageQuery>,
+) -> impl Responder {
+    let target_url = &query.url;
+    let rules = load_rules(&data);
+
+    add_log(
+        &data.logs,
+        "INFO",
+        "QA Proxy",
+        &format!("Proxying request to: {}", target_url),
+    );
+
+    match rules
+        .forward(reqwest::Method::GET, target_url, &req, None)
+        .await
+    {
+        Ok(response) => {
+            if !response.status().is_success() {
+                add_log(
+                    &data.logs,
+                    "ERROR",
+                    "QA Proxy",
+                    &format!("Failed to fetch URL: HTTP {}", response.status()),
+                );
+                return HttpResponse::BadGateway()
+                    .insert_header(("Access-Control-Allow-Origin", "*"))
+                    .body(format!("Failed to fetch URL: HTTP {}", response.status()));
+            }
+
+            let mut relayed = rules.relay_headers(StatusCode::OK, &response);
+            match response.text().await {
+                Ok(html) => {
+                    // Inject the recorder script into the HTML
+                    let selector_attributes = data
+                        .tauri_state
+                        .qa_session_use_case
+                        .selector_attributes(
+                            &data.tauri_state.settings.get::<QaSelectorSettings>(),
+                            query.session_id.as_deref(),
+                        )
+                        .await;
+                    let capture = rules.capture_config(&req, target_url, selector_attributes);
+                    let injected_html =
+                        inject_recorder_script(&rules.rewrite_body(html), target_url, &capture);
+
+                    add_log(
+                        &data.logs,
+                        "INFO",
+                        "QA Proxy",
+                        "Successfully proxied and injected recorder script",
+                    );
+
+                    relayed
+                        .content_type("text/html; charset=utf-8")
+                        .insert_header(("X-Frame-Options", "ALLOWALL"))
+                        .body(injected_html)
+                }
+                Err(e) => {
+                    add_log(
+                        &data.logs,
+                        "ERROR",
+                        "QA Proxy",
+                        &format!("Failed to read response body: {}", e),
+                    );
+                    HttpResponse::InternalServerError()
+                        .insert_header(("Access-Control-Allow-Origin", "*"))
+                        .body(format!("Failed to read response: {}", e))
+                }
+            }
+        }
+        Err(e) => {
+            add_log(
+                &data.logs,
+                "ERROR",
+                "QA Proxy",
+                &format!("Failed to fetch URL: {}", e),
+            );
+            HttpResponse::BadGateway()
+                .insert_header(("Access-Control-Allow-Origin", "*"))
+                .body(format!("Failed to fetch URL: {}", e))
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/qa/proxy/resource",
+    tag = "qa",
+    summary = "Forward a request made by a proxied page (also POST, PUT, PATCH, DELETE)",
+    params(ProxyQuery),
+    request_body(content = String, description = "Forwarded as is", content_type = "application/octet-stream"),
+    responses(
+        (status = 200, description = "Upstream response with origins and cookies rewritten; upstream error statuses are passed through"),
+        (status = 502, description = "Target URL unreachable", body = String, content_type = "text/plain"),
+        (status = 401, description = "Missing or invalid API token")
+    )
+)]
+#[route(
+    "/qa/proxy/resource",
+    method = "GET",
+    method = "POST",
+    method = "PUT",
+    method = "PATCH",
+    method = "DELETE"
+)]
+pub(super) async fn qa_proxy_resource(
+    data: web::Data<HttpState>,
+    req: HttpRequest,
+    query: web::Query<ProxyQuery>,
+    body: web::Bytes,
+) -> impl Responder {
+    let rules = load_rules(&data);
+    let method = reqwest::Method::from_bytes(req.method().as_str().as_bytes())
+        .unwrap_or(reqwest::Method::GET);
+
+    let response = match rules.forward(method, &query.url, &req, Some(body)).await {
+        Ok(response) => response,
+        Err(e) => {
+            add_log(
+                &data.logs,
+                "WARN",
+                "QA Proxy",
+                &format!("Failed to forward request to {}: {}", query.url, e),
+            );
+            return HttpResponse::BadGateway()
+                .insert_header(("Access-Control-Allow-Origin", "*"))
+                .body(format!("Failed to fetch URL: {}", e));
+        }
+    };
+
+    let status =
+        StatusCode::from_u16(response.status().as_u16()).unwrap_or(StatusCode::BAD_GATEWAY);
+    let content_type = response
+        .headers()
+        .get("content-type")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let mut relayed = rules.relay_headers(status, &response);
+    if let Some(content_type) = &content_type {
+        relayed.content_type(content_type.as_str());
+    }
+
+    let body = match response.bytes().await {
+        Ok(body) => body,
+        Err(e) => {
+            return HttpResponse::BadGateway()
+                .insert_header(("Access-Control-Allow-Origin", "*"))
+                .body(format!("Failed to read response: {}", e));
+        }
+    };
+    match content_type.filter(|content_type| is_text(content_type)) {
+        Some(_) => match String::from_utf8(body.to_vec()) {
+            Ok(text) => relayed.body(rules.rewrite_body(text)),
+            Err(e) => relayed.body(e.into_bytes()),
+        },
+        None => relayed.body(body.to_vec()),
+    }
+}
+
+pub(super) fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(qa_proxy).service(qa_proxy_resource);
+}
+
+fn inject_recorder_script(html: &str, base_url: &str, capture: &CaptureConfig) -> String {
+    // `</` would end the script element early
+    let capture_json = serde_json::to_string(capture)
+        .unwrap_or_else(|_| "{}".to_string())
+        .replace("</", "<\\/");
+
+    // Parse base URL to extract origin for proper script/resource loading
+    let _origin = if let Ok(url) = url::Url::parse(base_url) {
+        format!("{}://{}", url.scheme(), url.host_str().unwrap_or(""))
+    } else {
+        "http://localhost:1420".to_string()
+    };
+
+    // Use the origin as base href for proper resource loading
+    // let base_href = format!("{}/", origin);
+
+    // Inject recorder script and base tag with CSP bypass
+    let injection = format!(
+        r#"<base href="{}">
+<meta http-equiv="Content-Security-Policy" content="default-src * 'unsafe-inline' 'unsafe-eval' data: blob:;">
+<script>
+// QA Recorder Injectable Script - Injected by Proxy
+(function() {{
+  'use strict';
+
+  const INPUT_DEBOUNCE_MS = 350;
+  const MAX_TEXT_LENGTH = 160;
+
+  let inputTimers = new Map();
+  let scrollTimers = new Map();
+  let lastPointer = null;
+  let lastFocusedElement = null;
+
+  if (window.__QA_RECORDER_INJECTED__) {{
+    return;
+  }}
+  window.__QA_RECORDER_INJECTED__ = true;
+
+  console.log('[QA Recorder Inject] Script loaded via proxy');
+
+  // Frames nested in a recorded page load through the proxy too and report
+  // to the same window as the outermost one, along with the iframes that
+  // lead to them
+  function findRecorderHost() {{
+    const frames = [];
+    let current = window;
+    while (current !== current.parent) {{
+      let frameElement = null;
+      try {{
+        frameElement = current.parent.__QA_RECORDER_INJECTED__ ? current.frameElement : null;
+      }} catch (err) {{
+        frameElement = null;
+      }}
+      if (!frameElement) break;
+      frames.unshift(frameElement);
+      current = current.parent;
+    }}
+    return {{ window: current.parent, frames }};
+  }}
+
+  // Calls to these origins go through the proxy, which applies the rewrite
+  // rules and avoids CORS, and are reported as network events. Comes before
+  // FRAME_PATH, whose selectors are built from its selector attributes.
+  const CAPTURE = {capture_json};
+
+  const RECORDER_HOST = findRecorderHost();
+  const FRAME_PATH = RECORDER_HOST.frames.map((frame) => buildSelector(frame));
+
+  function postEventToParent(payload) {{
+    RECORDER_HOST.window.postMessage({{
+      type: 'qa-recorder-event',
+      payload: payload
+    }}, '*');
+  }}
+
+  function resolveUrl(raw) {{
+    try {{
+      return new URL(String(raw), document.baseURI);
+    }} catch (err) {{
+      return null;
+    }}
+  }}
+
+  function proxiedUrl(url) {{
+    if (!url || !CAPTURE.origins.includes(url.origin)) return null;
+    const params = new URLSearchParams({{ url: url.href }});
+    if (CAPTURE.accessToken) params.set('access_token', CAPTURE.accessToken);
+    return `${{CAPTURE.resourceUrl}}?${{params}}`;
+  }}
+
+  function proxyFrame(frame) {{
+    const url = resolveUrl(frame.getAttribute('src'));
+    if (!url || !CAPTURE.origins.includes(url.origin)) return;
+    const params = new URLSearchParams({{ url: url.href }});
+    if (CAPTURE.accessToken) params.set('access_token', CAPTURE.accessToken);
+    if (CAPTURE.sessionId) params.set('session_id', CAPTURE.sessionId);
+    frame.setAttribute('src', `${{CAPTURE.pageUrl}}?${{params}}`);
+  }}
+
+  function postNetworkToParent(method, url, status, startedAt) {{
+    RECORDER_HOST.window.postMessage({{
+      type: 'qa-recorder-network',
+      payload: {{
+        method: (method || 'GET').toUpperCase(),
+        url,
+        status,
+        timingMs: Math.round(performance.now() - startedAt),
+      }}
+    }}, '*');
+  }}
+
+  function installNetworkCapture() {{
+    const originalFetch = window.fetch;
+    window.fetch = function(input, init) {{
+      const isRequest = input instanceof Request;
+      const url = resolveUrl(isRequest ? input.url : input);
+      const proxied = proxiedUrl(url);
+      if (!proxied) return originalFetch.call(this, input, init);
+
+      const method = init?.method || (isRequest ? input.method : 'GET');
+      const startedAt = performance.now();
+      const request = isRequest ? new Request(proxied, input) : proxied;
+      return originalFetch.call(this, request, init).then(
+        (response) => {{
+          postNetworkToParent(method, url.href, response.status, startedAt);
+          return response;
+        }},
+        (err) => {{
+          postNetworkToParent(method, url.href, undefined, startedAt);
+          throw err;
+        }}
+      );
+    }};
+
+    const originalOpen = XMLHttpRequest.prototype.open;
+    const originalSend = XMLHttpRequest.prototype.send;
+    XMLHttpRequest.prototype.open = function(method, rawUrl, ...rest) {{
+      const url = resolveUrl(rawUrl);
+      const proxied = proxiedUrl(url);
+      this.__qaCapture = proxied ? {{ method, url: url.href }} : null;
+      return originalOpen.call(this, method, proxied || rawUrl, ...rest);
+    }};
+    XMLHttpRequest.prototype.send = function(body) {{
+      const capture = this.__qaCapture;
+      if (capture) {{
+        const startedAt = performance.now();
+        this.addEventListener(
+          'loadend',
+          () => postNetworkToParent(capture.method, capture.url, this.status || undefined, startedAt),
+          {{ once: true }}
+        );
+      }}
+      return originalSend.call(this, body);
+    }};
+  }}
+
+  function installFrameCapture() {{
+    const visit = (node) => {{
+      if (!(node instanceof Element)) return;
+      if (node instanceof HTMLIFrameElement) proxyFrame(node);
+      node.querySelectorAll('iframe[src]').forEach(proxyFrame);
+    }};
+    new MutationObserver((mutations) => {{
+      for (const mutation of mutations) {{
+        if (mutation.type === 'attributes') {{
+          visit(mutation.target);
+        }} else {{
+          mutation.addedNodes.forEach(visit);
+        }}
+      }}
+    }}).observe(document.documentElement, {{
+      childList: true,
+      subtree: true,
+      attributes: true,
+      attributeFilter: ['src'],
+    }});
+  }}
+
+  if (CAPTURE.enabled) {{
+    installNetworkCapture();
+    installFrameCapture();
+  }}
+
+  function buildSelector(element) {{
+    for (const attr of CAPTURE.selectorAttributes) {{
+      const value = element.getAttribute(attr);
+      if (!value) continue;
+      if (attr === 'id') return `#${{CSS.escape(value)}}`;
+      return `${{element.tagName.toLowerCase()}}[${{CSS.escape(attr)}}=\"${{CSS.escape(value)}}\"]`;
+    }}
+    const path = [];
+    let current = element;
+    const rootBody = element.ownerDocument?.body;
+    for (let depth = 0; current && current !== rootBody && depth < 4; depth++) {{
+      const tagName = current.tagName.toLowerCase();
+      const index = nthOfType(current);
+      path.unshift(`${{tagName}}:nth-of-type(${{index}})`);
+      current = current.parentElement;
+    }}
+    return path.length > 0 ? path.join(' > ') : undefined;
+  }}
+
+  function nthOfType(element) {{
+    let index = 1;
+    let sibling = element.previousElementSibling;
+    while (sibling) {{
+      if (sibling.tagName === element.tagName) index++;
+      sibling = sibling.previousElementSibling;
+    }}
+    return index;
+  }}
+
+  // The selector attributes set on the element, for replay to find it by
+  // another one when the selector's attribute changed
+  function selectorAttributes(element) {{
+    const attributes = {{}};
+    for (const attr of CAPTURE.selectorAttributes) {{
+      const value = element.getAttribute(attr);
+      if (value) attributes[attr] = value;
+    }}
+    return Object.keys(attributes).length > 0 ? {{ attributes }} : {{}};
+  }}
+
+  // Events from inside shadow roots reach document listeners retargeted to
+  // the host; the composed path still starts at the real element
+  function eventTarget(event) {{
+    const path = typeof event.composedPath === 'function' ? event.composedPath() : [];
+    return path.length > 0 ? path[0] : event.target;
+  }}
+
+  // `selector` resolves inside the last shadow root of `shadowPath`, in the
+  // document of the last iframe of `framePath`
+  function elementScope(element) {{
+    const shadowPath = [];
+    let root = element.getRootNode();
+    while (root instanceof ShadowRoot) {{
+      shadowPath.unshift(buildSelector(root.host));
+      root = root.host.getRootNode();
+    }}
+    return {{
+      framePath: FRAME_PATH.length > 0 ? FRAME_PATH : undefined,
+      shadowPath: shadowPath.length > 0 ? shadowPath : undefined,
+    }};
+  }}
+
+  function getElementText(element) {{
+    if (element instanceof HTMLInputElement || element instanceof HTMLTextAreaElement) {{
+      return normalizeText(element.getAttribute('aria-label') || element.placeholder || element.name);
+    }}
+    if (element instanceof HTMLSelectElement) {{
+      const selected = element.selectedOptions?.[0]?.textContent;
+      return normalizeText(selected || element.getAttribute('aria-label') || element.name);
+    }}
+    return normalizeText(element.textContent);
+  }}
+
+  function getElementValue(element) {{
+    if (element instanceof HTMLInputElement) return element.value;
+    if (element instanceof HTMLTextAreaElement) return element.value;
+    if (element instanceof HTMLSelectElement) return element.value;
+    if (element instanceof HTMLElement && element.isContentEditable) return element.innerText;
+    return undefined;
+  }}
+
+  function maskValue(element, value) {{
+    if (!value || value.trim().length === 0) return undefined;
+    if (element instanceof HTMLInputElement && element.type === 'password') return '[masked]';
+    const label = [element.getAttribute('name'), element.getAttribute('id'), element.getAttribute('aria-label')]
+      .filter(Boolean).join(' ').toLowerCase();
+    if (label.includes('password')) return '[masked]';
+    return value;
+  }}
+
+  function normalizeText(value) {{
+    if (!value) return undefined;
+    const trimmed = value.trim();
+    if (!trimmed) return undefined;
+    return trimmed.length > MAX_TEXT_LENGTH ? trimmed.slice(0, MAX_TEXT_LENGTH) : trimmed;
+  }}
+
+  function stringifyMeta(meta) {{
+    const cleaned = {{}};
+    Object.entries(meta).forEach(([key, value]) => {{
+      if (value === undefined || value === null || value === '') return;
+      cleaned[key] = value;
+    }});
+    return Object.keys(cleaned).length > 0 ? JSON.stringify(cleaned) : undefined;
+  }}
+
+  function getCoordinates(event) {{
+    if (event instanceof MouseEvent) return {{ x: event.clientX, y: event.clientY }};
+    return lastPointer ?? undefined;
+  }}
+
+  function getElementRect(element) {{
+    const rect = element.getBoundingClientRect();
+    return {{ x: rect.left, y: rect.top, width: rect.width, height: rect.height }};
+  }}
+
+  function handlePointerDown(event) {{
+    const coords = getCoordinates(event);
+    if (coords) lastPointer = coords;
+  }}
+
+  function handleClick(event) {{
+    const target = eventTarget(event);
+    if (!(target instanceof Element)) return;
+    const isEditable =
+      target instanceof HTMLInputElement ||
+      target instanceof HTMLTextAreaElement ||
+      target instanceof HTMLSelectElement ||
+      (target instanceof HTMLElement && target.isContentEditable);
+    postEventToParent({{
+      eventType: 'click',
+      selector: buildSelector(target),
+      elementText: getElementText(target),
+      url: window.location.href,
+      metaJson: stringifyMeta({{
+        tag: target.tagName.toLowerCase(),
+        type: target instanceof HTMLInputElement ? target.type : undefined,
+        isEditable,
+        coordinates: getCoordinates(event),
+        rect: getElementRect(target),
+        viewport: {{ width: window.innerWidth, height: window.innerHeight }},
+        ...elementScope(target),
+        ...selectorAttributes(target),
+      }}),
+    }});
+  }}
+
+  function handleInput(event) {{
+    const target = eventTarget(event);
+    if (!(target instanceof Element)) return;
+    if (!(target instanceof HTMLInputElement || target instanceof HTMLTextAreaElement || target instanceof HTMLSelectElement)) return;
+    if (target instanceof HTMLInputElement && target.type === 'file') return;
+    const inputType = event.inputType;
+    const previousTimer = inputTimers.get(target);
+    if (previousTimer) clearTimeout(previousTimer);
+    const nextTimer = setTimeout(() => {{
+      inputTimers.delete(target);
+      const rawValue = getElementValue(target);
+      const maskedValue = maskValue(target, rawValue);
+      postEventToParent({{
+        eventType: 'input',
+        selector: buildSelector(target),
+        elementText: getElementText(target),
+        value: maskedValue,
+        url: window.location.href,
+        metaJson: stringifyMeta({{
+          tag: target.tagName.toLowerCase(),
+          inputType,
+          type: target instanceof HTMLInputElement ? target.type : undefined,
+          coordinates: getCoordinates(event),
+          ...elementScope(target),
+          ...selectorAttributes(target),
+        }}),
+      }});
+    }}, INPUT_DEBOUNCE_MS);
+    inputTimers.set(target, nextTimer);
+  }}
+
+  function handleSubmit(event) {{
+    const target = eventTarget(event);
+    if (!(target instanceof Element)) return;
+    const form = target instanceof HTMLFormElement ? target : target.closest('form');
+    const element = form ?? target;
+    postEventToParent({{
+      eventType: 'submit',
+      selector: buildSelector(element),
+      elementText: getElementText(element),
+      url: window.location.href,
+      metaJson: stringifyMeta({{
+        tag: element.tagName.toLowerCase(),
+        action: form?.action,
+        method: form?.method,
+        coordinates: getCoordinates(event),
+        ...elementScope(element),
+        ...selectorAttributes(element),
+      }}),
+    }});
+  }}
+
+  // Names, sizes and types of the chosen files; their contents are never read
+  function handleChange(event) {{
+    const target = eventTarget(event);
+    if (!(target instanceof HTMLInputElement) || target.type !== 'file') return;
+    const files = Array.from(target.files || []).map((file) => ({{
+      name: file.name,
+      size: file.size,
+      type: file.type || undefined,
+    }}));
+    postEventToParent({{
+      eventType: 'file_upload',
+      selector: buildSelector(target),
+      elementText: getElementText(target),
+      value: files.map((file) => file.name).join(', ') || undefined,
+      url: window.location.href,
+      metaJson: stringifyMeta({{
+        tag: 'input',
+        type: 'file',
+        multiple: target.multiple || undefined,
+        files,
+        ...elementScope(target),
+        ...selectorAttributes(target),
+      }}),
+    }});
+  }}
+
+  const CAPTURED_KEYS = ['Enter', 'Escape', 'Tab', 'ArrowUp', 'ArrowDown', 'ArrowLeft', 'ArrowRight'];
+
+  function handleKeyDown(event) {{
+    if (!CAPTURED_KEYS.includes(event.key) || event.isComposing) return;
+    const target = eventTarget(event);
+    const modifiers = [
+      event.ctrlKey && 'Control',
+      event.altKey && 'Alt',
+      event.metaKey && 'Meta',
+      event.shiftKey && 'Shift',
+    ].filter(Boolean);
+    postEventToParent({{
+      eventType: 'keydown',
+      selector: target instanceof Element ? buildSelector(target) : undefined,
+      elementText: target instanceof Element ? getElementText(target) : undefined,
+      value: [...modifiers, event.key].join('+'),
+      url: window.location.href,
+      metaJson: stringifyMeta({{
+        key: event.key,
+        code: event.code,
+        ctrlKey: event.ctrlKey,
+        shiftKey: event.shiftKey,
+        altKey: event.altKey,
+        metaKey: event.metaKey,
+        ...(target instanceof Element
+          ? {{ ...elementScope(target), ...selectorAttributes(target) }}
+          : {{}}),
+      }}),
+    }});
+  }}
+
+  function handleScroll(event) {{
+    const element = event.target instanceof Element ? event.target : null;
+    const source = element || window;
+    const previousTimer = scrollTimers.get(source);
+    if (previousTimer) clearTimeout(previousTimer);
+    scrollTimers.set(source, setTimeout(() => {{
+      scrollTimers.delete(source);
+      postEventToParent({{
+        eventType: 'scroll',
+        selector: element ? buildSelector(element) : undefined,
+        url: window.location.href,
+        metaJson: stringifyMeta({{
+          target: element ? 'element' : 'window',
+          scrollX: Math.round(element ? element.scrollLeft : window.scrollX),
+          scrollY: Math.round(element ? element.scrollTop : window.scrollY),
+        }}),
+      }});
+    }}, 500));
+  }}
+
+  // The proxied page's own address, with the hash it moved to
+  function handleHashChange() {{
+    const page = resolveUrl(new URLSearchParams(window.location.search).get('url') || window.location.href);
+    if (!page) return;
+    page.hash = window.location.hash;
+    postEventToParent({{
+      eventType: 'navigation',
+      url: page.href,
+      metaJson: stringifyMeta({{
+        trigger: 'hashchange',
+        pathname: page.pathname,
+        search: page.search,
+        hash: page.hash,
+      }}),
+    }});
+  }}
+
+  document.addEventListener('pointerdown', handlePointerDown, true);
+  document.addEventListener('click', handleClick, true);
+  document.addEventListener('input', handleInput, true);
+  document.addEventListener('change', handleChange, true);
+  document.addEventListener('submit', handleSubmit, true);
+  document.addEventListener('keydown', handleKeyDown, true);
+  document.addEventListener('scroll', handleScroll, {{ capture: true, passive: true }});
+  window.addEventListener('hashchange', handleHashChange);
+  document.addEventListener(
+    'focusin',
+    (event) => {{
+      const target = eventTarget(event);
+      if (target instanceof Element) {{
+        lastFocusedElement = target;
+      }}
+    }},
+    true
+  );
+
+  function handleParentCommand(event) {{
+    if (event.source !== window.parent) return;
+    if (!event.data || event.data.type !== 'qa-recorder-command') return;
+
+    const action = event.data.action;
+    if (action === 'back') {{
+      window.history.back();
+      return;
+    }}
+    if (action === 'refocus') {{
+      if (lastFocusedElement && document.contains(lastFocusedElement)) {{
+        if (typeof lastFocusedElement.focus === 'function') {{
+          lastFocusedElement.focus({{ preventScroll: true }});
+        }}
+      }}
+      return;
+    }}
+    if (action === 'capture') {{
+      const requestId = event.data.requestId;
+      captureDocumentAsDataUrl()
+        .then((dataUrl) => {{
+          window.parent.postMessage(
+            {{ type: 'qa-recorder-capture', requestId, dataUrl }},
+            '*'
+          );
+        }})
+        .catch((err) => {{
+          window.parent.postMessage(
+            {{
+              type: 'qa-recorder-capture-error',
+              requestId,
+              error: err?.message || 'Failed to capture preview.',
+            }},
+            '*'
+          );
+        }});
+    }}
+  }}
+
+  async function captureDocumentAsDataUrl() {{
+    try {{
+      return await renderDocumentToDataUrl(document.documentElement);
+    }} catch (err) {{
+      if (isTaintedCanvasError(err)) {{
+        const sanitized = sanitizeDocumentElement(document.documentElement);
+        return renderDocumentToDataUrl(sanitized);
+      }}
+      throw err;
+    }}
+  }}
+
+  function isTaintedCanvasError(err) {{
+    const message = err?.message || '';
+    return (
+      message.includes('Tainted canvases') ||
+      message.includes('SecurityError')
+    );
+  }}
+
+  function sanitizeDocumentElement(root) {{
+    const clone = root.cloneNode(true);
+    const stripSelectors = [
+      'img',
+      'picture',
+      'source',
+      'video',
+      'audio',
+      'canvas',
+      'iframe',
+      'svg',
+      'link[rel="stylesheet"]',
+    ];
+    clone.querySelectorAll(stripSelectors.join(',')).forEach((el) => el.remove());
+
+    clone.querySelectorAll('style').forEach((style) => {{
+      if (!style.textContent) return;
+      let text = style.textContent;
+      text = text.replace(/@font-face\s*\{{[\s\S]*?\}}/g, '');
+      text = text.replace(/url\(([^)]+)\)/g, 'none');
+      style.textContent = text;
+    }});
+
+    clone.querySelectorAll('[style]').forEach((el) => {{
+      const inline = el.getAttribute('style');
+      if (!inline || !inline.includes('url(')) return;
+      const cleaned = inline.replace(/url\(([^)]+)\)/g, 'none');
+      el.setAttribute('style', cleaned);
+    }});
+
+    return clone;
+  }}
+
+  async function renderDocumentToDataUrl(root) {{
+    const safeWidth = Math.max(1, Math.floor(window.innerWidth));
+    const safeHeight = Math.max(1, Math.floor(window.innerHeight));
+    const serialized = new XMLSerializer().serializeToString(root);
+    const wrapped = `<div xmlns=\"http://www.w3.org/1999/xhtml\">${{serialized}}</div>`;
+    const svg = `<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"${{safeWidth}}\" height=\"${{safeHeight}}\"><foreignObject width=\"100%\" height=\"100%\">${{wrapped}}</foreignObject></svg>`;
+    const blob = new Blob([svg], {{ type: 'image/svg+xml;charset=utf-8' }});
+    const url = URL.createObjectURL(blob);
+
+    try {{
+      const img = await new Promise((resolve, reject) => {{
+        const image = new Image();
+        image.onload = () => resolve(image);
+        image.onerror = () => reject(new Error('Failed to render preview snapshot.'));
+        image.src = url;
+      }});
+
+      const canvas = document.createElement('canvas');
+      canvas.width = safeWidth;
+      canvas.height = safeHeight;
+      const ctx = canvas.getContext('2d');
+      if (!ctx) {{
+        throw new Error('Canvas is not available for screenshot.');
+      }}
+      ctx.drawImage(img, 0, 0, safeWidth, safeHeight);
+      return canvas.toDataURL('image/png');
+    }} finally {{
+      URL.revokeObjectURL(url);
+    }}
+  }}
+
+  window.addEventListener('message', handleParentCommand);
+
+  window.parent.postMessage({{ type: 'qa-recorder-ready' }}, '*');
+  console.log('[QA Recorder Inject] Ready');
+}})();
+</script>
+"#,
+        base_url
+    );
+
+    // Try to inject right after <head> tag, fallback to before </head>, finally before <body>
+    if let Some(pos) = html.find("<head>") {
+        let insert_pos = pos + "<head>".len();
+        format!(
+            "{}{}{}",
+            &html[..insert_pos],
+            injection,
+            &html[insert_pos..]
+        )
+    } else if let Some(pos) = html.find("</head>") {
+        format!("{}{}{}", &html[..pos], injection, &html[pos..])
+    } else if let Some(pos) = html.find("<body") {
+        format!("{}{}{}", &html[..pos], injection, &html[pos..])
+    } else {
+        // Fallback: prepend to entire HTML
+        format!("{}{}", injection, html)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::settings::OriginRewrite;
+
+    fn rules() -> ProxyRules {
+        ProxyRules::new(QaProxySettings {
+            origin_rewrites: vec![OriginRewrite {
+                from: "https://api.example.com/".to_string(),
+                to: "http://localhost:8080".to_string(),
+            }],
+            ..QaProxySettings::default()
+        })
+    }
+
+    #[test]
+    fn test_origin_rewrites() {
+        let rules = rules();
+        assert_eq!(
+            rules.rewrite_url("https://api.example.com/v1/users?page=2"),
+            "http://localhost:8080/v1/users?page=2"
+        );
+        // Another host that merely starts the same way is left alone
+        assert_eq!(
+            rules.rewrite_url("https://api.example.com.evil.test/"),
+            "https://api.example.com.evil.test/"
+        );
+        assert_eq!(
+            rules.rewrite_body(r#"fetch("https://api.example.com/v1")"#.to_string()),
+            r#"fetch("http://localhost:8080/v1")"#
+        );
+        assert_eq!(
+            rules.capture_origins("https://app.example.com/login"),
+            [
+                "https://app.example.com",
+                "https://api.example.com",
+                "http://localhost:8080"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_cookie_domain_is_stripped() {
+        let cookie = "sid=abc; Path=/; Domain=.example.com; HttpOnly";
+        assert_eq!(
+            rules().rewrite_set_cookie(cookie),
+            "sid=abc; Path=/; HttpOnly"
+        );
+
+        let keep = ProxyRules::new(QaProxySettings {
+            strip_cookie_domain: false,
+            ..QaProxySettings::default()
+        });
+        assert_eq!(keep.rewrite_set_cookie(cookie), cookie);
+    }
+}
@@ -0,0 +1,306 @@
+//! RAG endpoints
+//!
+//! Mirror the `rag_list_collections`, `rag_query` and `rag_chat_with_context`
+//! Tauri commands so the knowledge base can be scripted over HTTP.
+
+use std::time::Instant;
+
+use actix_web::{get, post, web, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::application::use_cases::output_language::generate_in;
+use crate::application::use_cases::rag_answer_cache::{AnswerCacheKey, AnswerCacheLookup};
+use crate::domain::error::{AppError, ErrorPayload, Result};
+use crate::domain::llm_config::LLMConfig;
+use crate::domain::rag_entities::RagCollection;
+use crate::domain::settings::LlmFeature;
+use crate::infrastructure::response::clean_llm_response;
+use crate::interfaces::tauri::rag_commands::search_chat::{
+    log_cached_answer, run_chat_with_context, run_rag_query, RAG_ANSWER_SYSTEM_PROMPT,
+};
+use crate::interfaces::tauri::rag_commands::types::{
+    ChatMessage, ChatWithContextRequest, ChatWithContextResponse, RagQueryRequest, RagQueryResponse,
+};
+use crate::interfaces::tauri::AppState;
+
+use super::{add_log, error_response, HttpState};
+
+/// History loaded from the conversation store when the request sends none
+const HISTORY_LIMIT: i64 = 50;
+
+/// Keeps answers to this endpoint's prompt apart in the answer cache
+const ANSWER_CACHE_VARIANT: &str = "http";
+
+/// Chunks retrieved when the request sets no `top_k`, as in `run_chat_with_context`
+const DEFAULT_TOP_K: usize = 5;
+
+#[derive(Deserialize, IntoParams)]
+struct CollectionsQuery {
+    /// Maximum number of collections to return (default 50)
+    limit: Option<i64>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct RagChatRequest {
+    #[serde(flatten)]
+    pub chat: ChatWithContextRequest,
+
+    /// Generate the answer with this model and store the exchange in the
+    /// conversation. Without it only the prompt is returned, like the Tauri command.
+    #[serde(default)]
+    pub config: Option<LLMConfig>,
+
+    /// Generate a new answer even when the same question was answered before
+    /// against the unchanged collection; the new answer replaces the cached one
+    #[serde(default)]
+    pub bypass_cache: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct RagChatResponse {
+    #[serde(flatten)]
+    pub context: ChatWithContextResponse,
+    pub answer: Option<String>,
+    /// The answer came from the answer cache; `prompt` is then empty
+    pub cached: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/rag/collections",
+    tag = "rag",
+    summary = "List knowledge base collections",
+    params(CollectionsQuery),
+    responses(
+        (status = 200, description = "Collections, newest first", body = Vec<RagCollection>),
+        (status = "5XX", description = "Database error", body = ErrorPayload),
+        (status = 401, description = "Missing or invalid API token")
+    )
+)]
+#[get("/rag/collections")]
+async fn rag_collections(
+    data: web::Data<HttpState>,
+    query: web::Query<CollectionsQuery>,
+) -> impl Responder {
+    if let Err(e) = data.tauri_state.deferred_init.ensure().await {
+        return error_response(&AppError::DatabaseError(e));
+    }
+    match data
+        .tauri_state
+        .rag_repository
+        .list_collections(query.limit.unwrap_or(50))
+        .await
+    {
+        Ok(collections) => HttpResponse::Ok().json(collections),
+        Err(e) => {
+            add_log(
+                &data.logs,
+                "ERROR",
+                "HttpApi",
+                &format!("Failed to list collections: {}", e),
+            );
+            error_response(&e)
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/rag/query",
+    tag = "rag",
+    summary = "Retrieve context from a collection and build the answer prompt",
+    request_body = RagQueryRequest,
+    responses(
+        (status = 200, description = "Prompt and the retrieved chunks", body = RagQueryResponse),
+        (status = 400, description = "Invalid request", body = ErrorPayload),
+        (status = "5XX", description = "Retrieval or prompt building failed", body = ErrorPayload),
+        (status = 401, description = "Missing or invalid API token")
+    )
+)]
+#[post("/rag/query")]
+async fn rag_query(data: web::Data<HttpState>, req: web::Json<RagQueryRequest>) -> impl Responder {
+    match run_rag_query(&data.tauri_state, req.into_inner()).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => error_response(&e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/rag/chat",
+    tag = "rag",
+    summary = "Chat with a collection, optionally generating and storing the answer",
+    request_body = RagChatRequest,
+    responses(
+        (status = 200, description = "Prompt, retrieved chunks and the answer when `config` was given", body = RagChatResponse),
+        (status = 400, description = "Invalid request", body = ErrorPayload),
+        (status = "5XX", description = "Retrieval, generation or conversation storage failed", body = ErrorPayload),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 429, description = "Rate limit reached; see the Retry-After header")
+    )
+)]
+#[post("/rag/chat")]
+async fn rag_chat(data: web::Data<HttpState>, req: web::Json<RagChatRequest>) -> impl Responder {
+    match chat(&data.tauri_state, req.into_inner()).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            add_log(
+                &data.logs,
+                "ERROR",
+                "HttpApi",
+                &format!("RAG chat failed: {}", e),
+            );
+            error_response(&e)
+        }
+    }
+}
+
+async fn chat(state: &AppState, request: RagChatRequest) -> Result<RagChatResponse> {
+    let RagChatRequest {
+        mut chat,
+        config,
+        bypass_cache,
+    } = request;
+
+    if chat.messages.is_none() {
+        if let Some(conversation_id) = chat.conversation_id {
+            let history = state
+                .conversation_service
+                .get_messages(conversation_id, HISTORY_LIMIT)
+                .await?;
+            if !history.is_empty() {
+                chat.messages = Some(
+                    history
+                        .into_iter()
+                        .map(|m| ChatMessage {
+                            role: m.role,
+                            content: m.content,
+                        })
+                        .collect(),
+                );
+            }
+        }
+    }
+
+    let start = Instant::now();
+    let collection_id = chat.collection_id;
+    let query = chat.query.clone();
+    let language = state.output_language(chat.language.as_deref());
+    let config = config.map(|config| state.model_resolver.resolve(LlmFeature::RagChat, &config));
+
+    // Answers that depend on earlier messages are not cached
+    let lookup = match &config {
+        Some(config) if chat.messages.is_none() => {
+            state
+                .deferred_init
+                .ensure()
+                .await
+                .map_err(AppError::DatabaseError)?;
+            let provider = config.provider.to_string();
+            let key = AnswerCacheKey {
+                query: &query,
+                top_k: chat.top_k.unwrap_or(DEFAULT_TOP_K),
+                provider: &provider,
+                model: &config.model,
+                language: language.as_ref().map(|language| language.language.as_str()),
+                variant: ANSWER_CACHE_VARIANT,
+            };
+            state
+                .rag_answer_cache
+                .lookup(collection_id, &key, bypass_cache)
+                .await
+        }
+        _ => AnswerCacheLookup::default(),
+    };
+
+    let cached = lookup.cached.is_some();
+    let (mut context, answer) = match lookup.cached {
+        Some(cached) => {
+            log_cached_answer(
+                state,
+                collection_id,
+                &query,
+                cached.answer.len(),
+                cached.hit_count,
+                start,
+            );
+            let context = ChatWithContextResponse {
+                prompt: String::new(),
+                results: cached.results,
+                conversation_id: chat.conversation_id,
+                context_summary: None,
+                verified: false,
+                context_managed: None,
+            };
+            (context, cached.answer)
+        }
+        None => {
+            let context = run_chat_with_context(state, chat).await?;
+            let Some(config) = config else {
+                return Ok(RagChatResponse {
+                    context,
+                    answer: None,
+                    cached: false,
+                });
+            };
+            let raw = generate_in(
+                language.as_ref(),
+                state.llm_client.as_ref(),
+                &config,
+                RAG_ANSWER_SYSTEM_PROMPT,
+                &context.prompt,
+            )
+            .await?;
+            let answer = clean_llm_response(&raw);
+            if let Some(slot) = &lookup.slot {
+                state
+                    .rag_answer_cache
+                    .store(slot, &answer, &context.results)
+                    .await;
+            }
+            (context, answer)
+        }
+    };
+
+    let conversation_id = match context.conversation_id {
+        Some(id) => id,
+        None => {
+            let title: String = query.chars().take(60).collect();
+            state
+                .conversation_service
+                .create_conversation(Some(collection_id), Some(&title))
+                .await?
+        }
+    };
+    let sources: Vec<i64> = context.results.iter().map(|r| r.source_id).collect();
+    let sources_json =
+        (!sources.is_empty()).then(|| serde_json::to_string(&sources).unwrap_or_default());
+
+    state
+        .conversation_service
+        .add_message(conversation_id, "user", &query, None)
+        .await?;
+    state
+        .conversation_service
+        .add_message(
+            conversation_id,
+            "assistant",
+            &answer,
+            sources_json.as_deref(),
+        )
+        .await?;
+
+    context.conversation_id = Some(conversation_id);
+    Ok(RagChatResponse {
+        context,
+        answer: Some(answer),
+        cached,
+    })
+}
+
+pub(super) fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(rag_collections)
+        .service(rag_query)
+        .service(rag_chat);
+}
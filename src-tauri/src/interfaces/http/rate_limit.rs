@@ -0,0 +1,224 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::{header, Method};
+use actix_web::middleware::Next;
+use actix_web::{web, Error, HttpResponse};
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::domain::settings::HttpRateLimit;
+
+use super::{add_log, HttpState};
+
+/// Requests let through and turned away for one rate-limited endpoint
+/// since the server last started
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitMetrics {
+    pub path: String,
+    pub requests_per_minute: u32,
+    pub burst: u32,
+    pub allowed: u64,
+    /// Answered with 429
+    pub limited: u64,
+    /// Unix milliseconds of the last 429
+    pub last_limited_at: Option<i64>,
+}
+
+/// A request the bucket of `path` had no token for
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Limited {
+    pub path: String,
+    pub retry_after: Duration,
+    /// The previous request to this endpoint was let through
+    pub first: bool,
+}
+
+struct Bucket {
+    limit: HttpRateLimit,
+    tokens: f64,
+    refilled_at: Instant,
+    /// The last request was answered with 429
+    limiting: bool,
+    metrics: RateLimitMetrics,
+}
+
+impl Bucket {
+    fn new(limit: HttpRateLimit, now: Instant) -> Self {
+        Self {
+            tokens: f64::from(limit.burst),
+            refilled_at: now,
+            limiting: false,
+            metrics: RateLimitMetrics {
+                path: limit.path.clone(),
+                requests_per_minute: limit.requests_per_minute,
+                burst: limit.burst,
+                allowed: 0,
+                limited: 0,
+                last_limited_at: None,
+            },
+            limit,
+        }
+    }
+
+    fn per_second(&self) -> f64 {
+        f64::from(self.limit.requests_per_minute) / 60.0
+    }
+
+    fn take(&mut self, now: Instant) -> Option<Limited> {
+        let elapsed = now
+            .saturating_duration_since(self.refilled_at)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_second()).min(f64::from(self.limit.burst));
+        self.refilled_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            self.limiting = false;
+            self.metrics.allowed += 1;
+            return None;
+        }
+        let first = !std::mem::replace(&mut self.limiting, true);
+        self.metrics.limited += 1;
+        self.metrics.last_limited_at = Some(Utc::now().timestamp_millis());
+        Some(Limited {
+            path: self.limit.path.clone(),
+            retry_after: Duration::from_secs_f64((1.0 - self.tokens) / self.per_second()),
+            first,
+        })
+    }
+}
+
+/// Token buckets of the local HTTP API, one per configured endpoint
+#[derive(Default)]
+pub struct HttpRateLimiter {
+    buckets: Mutex<Vec<Bucket>>,
+}
+
+impl HttpRateLimiter {
+    /// Replace the limits; every bucket starts full and counts from zero
+    pub fn configure(&self, limits: &[HttpRateLimit]) {
+        let now = Instant::now();
+        *self.buckets.lock().unwrap() = limits
+            .iter()
+            .map(|limit| Bucket::new(limit.clone(), now))
+            .collect();
+    }
+
+    pub fn metrics(&self) -> Vec<RateLimitMetrics> {
+        self.buckets
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|bucket| bucket.metrics.clone())
+            .collect()
+    }
+
+    /// Takes a token for `path` from the most specific bucket covering it
+    pub(super) fn check(&self, path: &str, now: Instant) -> Option<Limited> {
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets
+            .iter_mut()
+            .filter(|bucket| bucket.limit.covers(path))
+            .max_by_key(|bucket| bucket.limit.path.len())?;
+        bucket.take(now)
+    }
+}
+
+/// Answer 429 once an endpoint's bucket is empty. Runs after the token
+/// check, so rejected clients do not use up the bucket.
+pub(super) async fn enforce_rate_limits(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    let limited = if req.method() == Method::OPTIONS {
+        None
+    } else {
+        req.app_data::<web::Data<HttpState>>().and_then(|state| {
+            let limited = state
+                .tauri_state
+                .http_server
+                .rate_limiter()
+                .check(req.path(), Instant::now())?;
+            if limited.first {
+                add_log(
+                    &state.logs,
+                    "WARN",
+                    "HttpApi",
+                    &format!("Rate limit reached for {}; answering 429", limited.path),
+                );
+            }
+            Some(limited)
+        })
+    };
+
+    if let Some(limited) = limited {
+        let retry_after = limited.retry_after.as_secs().max(1);
+        let response = HttpResponse::TooManyRequests()
+            .insert_header((header::RETRY_AFTER, retry_after.to_string()))
+            .body(format!(
+                "Rate limit exceeded for {}; retry in {} s",
+                limited.path, retry_after
+            ));
+        return Ok(req.into_response(response).map_into_right_body());
+    }
+
+    next.call(req)
+        .await
+        .map(ServiceResponse::map_into_left_body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limit(path: &str, requests_per_minute: u32, burst: u32) -> HttpRateLimit {
+        HttpRateLimit {
+            path: path.to_string(),
+            requests_per_minute,
+            burst,
+        }
+    }
+
+    #[test]
+    fn test_bucket_allows_burst_then_refills() {
+        let limiter = HttpRateLimiter::default();
+        limiter.configure(&[limit("/api/translate", 60, 2)]);
+        let start = Instant::now();
+
+        assert!(limiter.check("/api/translate", start).is_none());
+        assert!(limiter.check("/api/translate", start).is_none());
+        let limited = limiter.check("/api/translate", start).unwrap();
+        assert_eq!(limited.retry_after, Duration::from_secs(1));
+        assert!(limited.first);
+        assert!(!limiter.check("/api/translate", start).unwrap().first);
+
+        // One token a second at 60 per minute
+        assert!(limiter
+            .check("/api/translate", start + Duration::from_secs(1))
+            .is_none());
+        assert!(limiter.check("/api/detect-language", start).is_none());
+
+        let metrics = &limiter.metrics()[0];
+        assert_eq!((metrics.allowed, metrics.limited), (3, 2));
+    }
+
+    #[test]
+    fn test_most_specific_limit_applies() {
+        let limiter = HttpRateLimiter::default();
+        limiter.configure(&[
+            limit("/api/typegen", 60, 5),
+            limit("/api/typegen/batch", 60, 1),
+        ]);
+        let now = Instant::now();
+
+        assert!(limiter.check("/api/typegen/batch", now).is_none());
+        assert!(limiter.check("/api/typegen/batch", now).is_some());
+        assert!(limiter.check("/api/typegen", now).is_none());
+        assert!(limiter.check("/api/typegenerator", now).is_none());
+        assert_eq!(limiter.metrics()[0].allowed, 1);
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::io;
+use std::net::{IpAddr, SocketAddr, TcpListener};
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{ServerHandle, ServiceRequest, ServiceResponse};
+use actix_web::http::{header, Method};
+use actix_web::middleware::Next;
+use actix_web::{web, Error, HttpResponse};
+use tokio::time::timeout;
+
+use super::rate_limit::HttpRateLimiter;
+use super::HttpState;
+
+/// Runtime state of the local HTTP API: where it listens and which token it expects
+pub struct HttpServerControl {
+    handle: Mutex<Option<ServerHandle>>,
+    address: Mutex<Option<SocketAddr>>,
+    token: RwLock<Option<String>>,
+    proxy_token: RwLock<Option<String>>,
+    rate_limiter: HttpRateLimiter,
+}
+
+impl HttpServerControl {
+    pub fn new() -> Self {
+        Self {
+            handle: Mutex::new(None),
+            address: Mutex::new(None),
+            token: RwLock::new(None),
+            proxy_token: RwLock::new(None),
+            rate_limiter: HttpRateLimiter::default(),
+        }
+    }
+
+    /// Address the server is currently bound to
+    pub fn address(&self) -> Option<SocketAddr> {
+        *self.address.lock().unwrap()
+    }
+
+    /// Require `token` on every request; `None` disables the check. A new
+    /// QA proxy token is issued along with it.
+    pub fn set_token(&self, token: Option<String>) {
+        *self.proxy_token.write().unwrap() = token
+            .as_ref()
+            .map(|_| uuid::Uuid::new_v4().simple().to_string());
+        *self.token.write().unwrap() = token;
+    }
+
+    pub fn token(&self) -> Option<String> {
+        self.token.read().unwrap().clone()
+    }
+
+    /// Token the QA proxy accepts as its `access_token` query parameter. It
+    /// is only valid for `/api/qa/proxy`, so pages loaded through the proxy
+    /// never see the API token.
+    pub fn proxy_token(&self) -> Option<String> {
+        self.proxy_token.read().unwrap().clone()
+    }
+
+    /// Per-endpoint token buckets, reset whenever the server starts
+    pub fn rate_limiter(&self) -> &HttpRateLimiter {
+        &self.rate_limiter
+    }
+
+    fn accepts(&self, candidate: Option<&str>) -> bool {
+        match self.token.read().unwrap().as_deref() {
+            None => true,
+            Some(expected) => candidate.is_some_and(|candidate| tokens_match(candidate, expected)),
+        }
+    }
+
+    fn accepts_proxy(&self, candidate: &str) -> bool {
+        match self.proxy_token.read().unwrap().as_deref() {
+            None => true,
+            Some(expected) => tokens_match(candidate, expected),
+        }
+    }
+
+    pub(super) fn set_running(&self, handle: ServerHandle, address: SocketAddr) {
+        *self.handle.lock().unwrap() = Some(handle);
+        *self.address.lock().unwrap() = Some(address);
+    }
+
+    /// Stop the running server, if any
+    pub async fn stop(&self) {
+        let handle = self.handle.lock().unwrap().take();
+        *self.address.lock().unwrap() = None;
+        if let Some(handle) = handle {
+            if timeout(Duration::from_secs(2), handle.stop(true))
+                .await
+                .is_err()
+            {
+                handle.stop(false).await;
+            }
+        }
+    }
+}
+
+impl Default for HttpServerControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Compare without short-circuiting on the first differing byte
+fn tokens_match(candidate: &str, expected: &str) -> bool {
+    candidate.len() == expected.len()
+        && candidate
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Bind `address:port`, falling back to an OS-assigned port when allowed
+pub(super) fn bind_listener(
+    address: IpAddr,
+    port: u16,
+    auto_port: bool,
+) -> io::Result<TcpListener> {
+    match TcpListener::bind((address, port)) {
+        Ok(listener) => Ok(listener),
+        Err(err) if auto_port && err.kind() == io::ErrorKind::AddrInUse => {
+            TcpListener::bind((address, 0))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// The QA preview loads the proxy in an iframe, which cannot send headers, so
+/// the proxy takes its own token from the query string. Checked before the
+/// header because proxied apps send their own `Authorization`.
+fn proxy_query_token(req: &ServiceRequest) -> Option<String> {
+    if !req.path().starts_with("/api/qa/proxy") {
+        return None;
+    }
+    web::Query::<HashMap<String, String>>::from_query(req.query_string())
+        .ok()
+        .and_then(|query| query.get("access_token").cloned())
+}
+
+fn bearer_token(req: &ServiceRequest) -> Option<String> {
+    req.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| {
+            value
+                .strip_prefix("Bearer ")
+                .or_else(|| value.strip_prefix("bearer "))
+        })
+        .map(|token| token.trim().to_string())
+}
+
+/// The spec and Swagger UI describe the API but expose no data
+fn is_public_docs(req: &ServiceRequest) -> bool {
+    req.method() == Method::GET
+        && (req.path() == super::openapi::SPEC_PATH
+            || req.path().starts_with(super::openapi::DOCS_PATH))
+}
+
+/// Reject requests without the configured bearer token
+pub(super) async fn require_api_token(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    let allowed = req.method() == Method::OPTIONS
+        || is_public_docs(&req)
+        || req.app_data::<web::Data<HttpState>>().is_some_and(|state| {
+            let control = &state.tauri_state.http_server;
+            match proxy_query_token(&req) {
+                Some(token) => control.accepts_proxy(&token),
+                None => control.accepts(bearer_token(&req).as_deref()),
+            }
+        });
+
+    if !allowed {
+        let response = HttpResponse::Unauthorized()
+            .insert_header((header::WWW_AUTHENTICATE, "Bearer"))
+            .body("Missing or invalid API token");
+        return Ok(req.into_response(response).map_into_right_body());
+    }
+
+    next.call(req)
+        .await
+        .map(ServiceResponse::map_into_left_body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("abc123", "abc123"));
+        assert!(!tokens_match("abc124", "abc123"));
+        assert!(!tokens_match("abc12", "abc123"));
+    }
+
+    #[test]
+    fn test_proxy_token_is_separate_from_api_token() {
+        let control = HttpServerControl::new();
+        control.set_token(Some("api-token".to_string()));
+        let proxy_token = control.proxy_token().unwrap();
+
+        assert_ne!(proxy_token, "api-token");
+        assert!(control.accepts_proxy(&proxy_token));
+        assert!(!control.accepts_proxy("api-token"));
+        assert!(!control.accepts(Some(&proxy_token)));
+
+        control.set_token(None);
+        assert!(control.proxy_token().is_none());
+    }
+
+    #[test]
+    fn test_bind_listener_falls_back_when_port_taken() {
+        let taken = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = taken.local_addr().unwrap().port();
+        let address: IpAddr = "127.0.0.1".parse().unwrap();
+
+        assert!(bind_listener(address, port, false).is_err());
+        let listener = bind_listener(address, port, true).unwrap();
+        assert_ne!(listener.local_addr().unwrap().port(), port);
+    }
+}
@@ -5,21 +5,44 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use std::collections::HashMap;
+use std::convert::Infallible;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::time::{sleep, timeout};
 
+use crate::application::use_cases::activity::ActivityFeed;
+use crate::domain::activity::{ActivityInput, ActivitySource};
 use crate::domain::error::{AppError, Result};
+use crate::domain::settings::SettingsSection;
+use crate::infrastructure::config::SettingsStore;
 use crate::interfaces::http::{add_log, LogEntry};
 
+mod route_files;
+mod watch;
+
+pub use route_files::{
+    export_routes_dir, load_routes_dir, MockRoutesExport, MANIFEST_FILE as ROUTES_MANIFEST_FILE,
+};
+pub use watch::{ConfigWatcher, MockConfigReload};
+
+type ReloadListener = Arc<dyn Fn(&MockConfigReload) + Send + Sync>;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MockServerConfig {
     pub port: u16,
     #[serde(default)]
     pub routes: Vec<MockRoute>,
+    /// Directory the routes are kept in, one file each, instead of the
+    /// settings; see `route_files`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub routes_dir: Option<String>,
+}
+
+impl SettingsSection for MockServerConfig {
+    const KEY: &'static str = "mockServer";
 }
 
 impl Default for MockServerConfig {
@@ -27,6 +50,7 @@ impl Default for MockServerConfig {
         Self {
             port: 4010,
             routes: Vec::new(),
+            routes_dir: None,
         }
     }
 }
@@ -228,6 +252,50 @@ pub struct MockResponse {
     #[serde(default)]
     pub body: String,
     pub delay_ms: Option<u64>,
+    /// Send the body as timed parts instead of `body`
+    #[serde(default)]
+    pub stream: Option<MockStream>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StreamMode {
+    /// `text/event-stream` events
+    Sse,
+    /// Raw body segments with chunked transfer encoding
+    Chunked,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MockStream {
+    pub mode: StreamMode,
+    #[serde(default)]
+    pub parts: Vec<StreamPart>,
+    /// Wait before each part after the first that sets no delay of its own
+    #[serde(default)]
+    pub interval_ms: u64,
+    /// Times the parts are sent in order; 0 repeats until the client disconnects
+    #[serde(default = "default_stream_repeat")]
+    pub repeat: u32,
+}
+
+fn default_stream_repeat() -> u32 {
+    1
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamPart {
+    #[serde(default)]
+    pub data: String,
+    /// SSE event name; ignored for chunked streams
+    #[serde(default)]
+    pub event: Option<String>,
+    /// SSE event id; ignored for chunked streams
+    #[serde(default)]
+    pub id: Option<String>,
+    pub delay_ms: Option<u64>,
 }
 
 impl Default for MockResponse {
@@ -241,6 +309,7 @@ impl Default for MockResponse {
             form_data: Vec::new(),
             form_urlencode: Vec::new(),
             delay_ms: None,
+            stream: None,
         }
     }
 }
@@ -259,7 +328,15 @@ pub struct MockServerState {
     pub config: Arc<Mutex<MockServerConfig>>,
     pub server: Arc<Mutex<Option<ServerHandle>>>,
     pub config_path: PathBuf,
+    /// Unified settings store; when set it replaces `config_path` for persistence
+    pub settings: Option<Arc<SettingsStore>>,
     pub logs: Arc<Mutex<Vec<LogEntry>>>,
+    /// Where served and unmatched requests are recorded, when set
+    pub activity: Option<Arc<ActivityFeed>>,
+    /// Watch of the routes' files, when they live in files
+    pub watcher: Arc<Mutex<Option<ConfigWatcher>>>,
+    /// Told of every reload from the routes' files
+    pub reload_listener: Option<ReloadListener>,
 }
 
 impl MockServerState {
@@ -280,12 +357,76 @@ impl MockServerState {
             config: Arc::new(Mutex::new(config)),
             server: Arc::new(Mutex::new(None)),
             config_path,
+            settings: None,
+            logs,
+            activity: None,
+            watcher: Arc::new(Mutex::new(None)),
+            reload_listener: None,
+        }
+    }
+
+    pub fn with_store(settings: Arc<SettingsStore>, logs: Arc<Mutex<Vec<LogEntry>>>) -> Self {
+        Self {
+            config: Arc::new(Mutex::new(stored_config(&settings, &logs))),
+            server: Arc::new(Mutex::new(None)),
+            config_path: settings.path().to_path_buf(),
+            settings: Some(settings),
             logs,
+            activity: None,
+            watcher: Arc::new(Mutex::new(None)),
+            reload_listener: None,
+        }
+    }
+
+    pub fn with_activity(mut self, activity: Arc<ActivityFeed>) -> Self {
+        self.activity = Some(activity);
+        self
+    }
+
+    pub fn with_reload_listener<F>(mut self, listener: F) -> Self
+    where
+        F: Fn(&MockConfigReload) + Send + Sync + 'static,
+    {
+        self.reload_listener = Some(Arc::new(listener));
+        self
+    }
+
+    /// Re-read routes from the settings store (e.g. after an import)
+    pub fn reload(self: &Arc<Self>) {
+        if let Some(settings) = &self.settings {
+            *self.config.lock().unwrap() = stored_config(settings, &self.logs);
+            self.watch_config_source();
+        }
+    }
+}
+
+/// The config in the settings, with its port and routes read from its
+/// routes directory when it has one
+fn stored_config(settings: &SettingsStore, logs: &Arc<Mutex<Vec<LogEntry>>>) -> MockServerConfig {
+    let mut config = settings.get::<MockServerConfig>();
+    if let Some(dir) = config.routes_dir.clone() {
+        match load_routes_dir(Path::new(&dir)) {
+            Ok(loaded) => {
+                config.port = loaded.port;
+                config.routes = loaded.routes;
+            }
+            Err(err) => add_log(
+                logs,
+                "ERROR",
+                "MockServer",
+                &format!("Failed to load mock routes from {}: {}", dir, err),
+            ),
         }
     }
+    config
 }
 
+/// Load a config from a single JSON file, or from a directory written by
+/// `export_routes_dir`
 pub fn load_config_from_path(path: &PathBuf) -> Result<MockServerConfig> {
+    if path.is_dir() {
+        return load_routes_dir(path);
+    }
     if !path.exists() {
         return Ok(MockServerConfig::default());
     }
@@ -297,6 +438,32 @@ pub fn load_config_from_path(path: &PathBuf) -> Result<MockServerConfig> {
 
 pub fn save_config(state: &MockServerState) -> Result<()> {
     let config = state.config.lock().unwrap();
+    if let Some(settings) = &state.settings {
+        if let Some(dir) = &config.routes_dir {
+            export_routes_dir(&config, Path::new(dir))?;
+            // The directory is the copy of record; the settings only point to it
+            let pointer = MockServerConfig {
+                routes: Vec::new(),
+                ..config.clone()
+            };
+            settings.set(&pointer)?;
+            add_log(
+                &state.logs,
+                "INFO",
+                "MockServer",
+                &format!("Mock server routes saved in {}", dir),
+            );
+            return Ok(());
+        }
+        settings.set(&*config)?;
+        add_log(
+            &state.logs,
+            "INFO",
+            "MockServer",
+            &format!("Mock server config saved at {}", settings.path().display()),
+        );
+        return Ok(());
+    }
     let serialized = serde_json::to_string_pretty(&*config).map_err(|err| {
         AppError::Internal(format!("Failed to serialize mock server config: {}", err))
     })?;
@@ -484,6 +651,7 @@ async fn handle_mock_request(
                                 route.name
                             ),
                         );
+                        record_hit(&data, "warn", 400, &method, &path, Some(&route.name));
                         return HttpResponse::BadRequest().json(serde_json::json!({
                             "error": "Body validation failed",
                             "route": route.name,
@@ -520,6 +688,7 @@ async fn handle_mock_request(
                                     route.name, route.multi_response_match_mode
                                 ),
                             );
+                            record_hit(&data, "warn", 400, &method, &path, Some(&route.name));
                             return HttpResponse::BadRequest().json(serde_json::json!({
                                 "error": "No matching payload found for this route",
                                 "route": route.name,
@@ -547,6 +716,14 @@ async fn handle_mock_request(
                 method, path, route.name
             ),
         );
+        record_hit(
+            &data,
+            "info",
+            response.status,
+            &method,
+            &path,
+            Some(&route.name),
+        );
 
         let mut http_response = HttpResponse::build(
             actix_web::http::StatusCode::from_u16(response.status)
@@ -564,6 +741,18 @@ async fn handle_mock_request(
             }
         }
 
+        if let Some(stream) = response.stream.as_ref().filter(|s| !s.parts.is_empty()) {
+            if stream.mode == StreamMode::Sse {
+                if !has_content_type {
+                    http_response.append_header(("Content-Type", "text/event-stream"));
+                }
+                http_response.append_header(("Cache-Control", "no-cache"));
+            } else if !has_content_type {
+                http_response.append_header(("Content-Type", "text/plain"));
+            }
+            return http_response.streaming(stream_body(Arc::new(stream.clone())));
+        }
+
         if !has_content_type {
             if response.body.trim_start().starts_with('{')
                 || response.body.trim_start().starts_with('[')
@@ -589,6 +778,8 @@ async fn handle_mock_request(
         ),
     );
 
+    record_hit(&data, "warn", 404, &method, &path, None);
+
     HttpResponse::NotFound().json(serde_json::json!({
         "error": "No mock route matched.",
         "method": method,
@@ -596,6 +787,80 @@ async fn handle_mock_request(
     }))
 }
 
+/// Adds a handled request to the activity feed, when the state has one
+fn record_hit(
+    state: &MockServerState,
+    level: &str,
+    status: u16,
+    method: &str,
+    path: &str,
+    route: Option<&str>,
+) {
+    let Some(activity) = state.activity.as_ref() else {
+        return;
+    };
+    let message = match route {
+        Some(route) => format!("{} {} -> {} ({})", method, path, status, route),
+        None => format!("{} {} -> {} (no route matched)", method, path, status),
+    };
+    let mut input = ActivityInput::new(ActivitySource::MockServer, "request", message)
+        .level(level)
+        .payload(serde_json::json!({
+            "method": method,
+            "path": path,
+            "status": status,
+        }));
+    if let Some(route) = route {
+        input = input.subject(route);
+    }
+    activity.record(input);
+}
+
+/// Parts of `stream` as body chunks, each sent after its delay
+fn stream_body(
+    stream: Arc<MockStream>,
+) -> impl futures_util::Stream<Item = std::result::Result<web::Bytes, Infallible>> {
+    let total = (stream.repeat > 0).then(|| stream.repeat as usize * stream.parts.len());
+    futures_util::stream::unfold(0usize, move |sent| {
+        let stream = stream.clone();
+        async move {
+            if total.is_some_and(|total| sent >= total) {
+                return None;
+            }
+            let part = &stream.parts[sent % stream.parts.len()];
+            let delay_ms = match part.delay_ms {
+                Some(delay_ms) => delay_ms,
+                None if sent > 0 => stream.interval_ms,
+                None => 0,
+            };
+            if delay_ms > 0 {
+                sleep(Duration::from_millis(delay_ms)).await;
+            }
+            let bytes = match stream.mode {
+                StreamMode::Sse => sse_event(part),
+                StreamMode::Chunked => part.data.clone(),
+            };
+            Some((Ok(web::Bytes::from(bytes)), sent + 1))
+        }
+    })
+}
+
+/// One Server-Sent Event; multi-line data becomes one `data:` field per line
+fn sse_event(part: &StreamPart) -> String {
+    let mut event = String::new();
+    if let Some(id) = part.id.as_deref().filter(|id| !id.is_empty()) {
+        event.push_str(&format!("id: {}\n", id));
+    }
+    if let Some(name) = part.event.as_deref().filter(|name| !name.is_empty()) {
+        event.push_str(&format!("event: {}\n", name));
+    }
+    for line in part.data.split('\n') {
+        event.push_str(&format!("data: {}\n", line.trim_end_matches('\r')));
+    }
+    event.push('\n');
+    event
+}
+
 fn method_matches(route: &MockRoute, method: &str) -> bool {
     route.method.trim().eq_ignore_ascii_case(method)
 }
@@ -1023,3 +1288,30 @@ fn match_payload_exact(expected: &str, actual: &str) -> bool {
         _ => false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sse_event_splits_data_lines() {
+        let part = StreamPart {
+            data: "{\"step\": 1}\r\ndone".to_string(),
+            event: Some("progress".to_string()),
+            id: Some("7".to_string()),
+            delay_ms: None,
+        };
+        assert_eq!(
+            sse_event(&part),
+            "id: 7\nevent: progress\ndata: {\"step\": 1}\ndata: done\n\n"
+        );
+
+        let empty = StreamPart {
+            data: String::new(),
+            event: None,
+            id: None,
+            delay_ms: None,
+        };
+        assert_eq!(sse_event(&empty), "data: \n\n");
+    }
+}
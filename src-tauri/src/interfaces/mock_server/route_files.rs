@@ -0,0 +1,320 @@
+//! Mock server config as a directory for version control: one file per
+//! route under `routes/`, and `mock-server.json` holding the port and the
+//! route order. Files are pretty printed with a fixed key order and named
+//! after the route's method, path and id, so a change to one route shows
+//! up as a diff of one file.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::domain::error::{AppError, Result};
+
+use super::{MockRoute, MockServerConfig};
+
+pub const MANIFEST_FILE: &str = "mock-server.json";
+const ROUTES_DIR: &str = "routes";
+
+/// Characters of the route id kept in its file name
+const FILE_ID_CHARS: usize = 8;
+/// Characters of the path slug kept in a file name
+const FILE_SLUG_CHARS: usize = 60;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RoutesManifest {
+    port: u16,
+    /// Route ids in matching order
+    #[serde(default)]
+    routes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MockRoutesExport {
+    pub directory: String,
+    pub written: usize,
+    /// Route files of routes that no longer exist
+    pub removed: usize,
+}
+
+/// Write the config into `dir`, replacing the route files of an earlier
+/// export. Routes without an id get one from their method and path.
+pub fn export_routes_dir(config: &MockServerConfig, dir: &Path) -> Result<MockRoutesExport> {
+    let routes = with_stable_ids(&config.routes);
+    let routes_dir = dir.join(ROUTES_DIR);
+    fs::create_dir_all(&routes_dir).map_err(|err| {
+        AppError::Internal(format!(
+            "Failed to create {}: {}",
+            routes_dir.display(),
+            err
+        ))
+    })?;
+
+    let mut written = HashSet::new();
+    for route in &routes {
+        let mut file_name = route_file_name(route, false);
+        if written.contains(&file_name) {
+            file_name = route_file_name(route, true);
+        }
+        write_json(&routes_dir.join(&file_name), route)?;
+        written.insert(file_name);
+    }
+
+    let mut removed = 0;
+    for file_name in json_file_names(&routes_dir)? {
+        if !written.contains(&file_name) {
+            fs::remove_file(routes_dir.join(&file_name)).map_err(|err| {
+                AppError::Internal(format!(
+                    "Failed to remove route file {}: {}",
+                    file_name, err
+                ))
+            })?;
+            removed += 1;
+        }
+    }
+
+    let manifest = RoutesManifest {
+        port: config.port,
+        routes: routes.iter().map(|route| route.id.clone()).collect(),
+    };
+    write_json(&dir.join(MANIFEST_FILE), &manifest)?;
+
+    Ok(MockRoutesExport {
+        directory: dir.display().to_string(),
+        written: written.len(),
+        removed,
+    })
+}
+
+/// Read a config written by `export_routes_dir`. Route files missing from
+/// the manifest's order come last, by file name; a route file without an
+/// id takes its file name as id.
+pub fn load_routes_dir(dir: &Path) -> Result<MockServerConfig> {
+    let manifest_path = dir.join(MANIFEST_FILE);
+    let manifest: RoutesManifest = if manifest_path.exists() {
+        let content = fs::read_to_string(&manifest_path).map_err(|err| {
+            AppError::Internal(format!(
+                "Failed to read {}: {}",
+                manifest_path.display(),
+                err
+            ))
+        })?;
+        serde_json::from_str(&content).map_err(|err| {
+            AppError::ParseError(format!("Invalid {}: {}", manifest_path.display(), err))
+        })?
+    } else {
+        RoutesManifest {
+            port: MockServerConfig::default().port,
+            routes: Vec::new(),
+        }
+    };
+
+    let routes_dir = dir.join(ROUTES_DIR);
+    let mut routes: Vec<MockRoute> = Vec::new();
+    if routes_dir.is_dir() {
+        for file_name in json_file_names(&routes_dir)? {
+            let route = read_route(&routes_dir.join(&file_name), &file_name)?;
+            if routes.iter().any(|existing| existing.id == route.id) {
+                return Err(AppError::ValidationError(format!(
+                    "Route id '{}' in {} is used by another route file",
+                    route.id, file_name
+                )));
+            }
+            routes.push(route);
+        }
+    }
+
+    let position: HashMap<&str, usize> = manifest
+        .routes
+        .iter()
+        .enumerate()
+        .map(|(index, id)| (id.as_str(), index))
+        .collect();
+    // Stable, so unlisted routes keep their file name order
+    let mut ordered: Vec<(usize, MockRoute)> = routes
+        .into_iter()
+        .map(|route| {
+            let index = position
+                .get(route.id.as_str())
+                .copied()
+                .unwrap_or(usize::MAX);
+            (index, route)
+        })
+        .collect();
+    ordered.sort_by_key(|(index, _)| *index);
+
+    Ok(MockServerConfig {
+        port: manifest.port,
+        routes: ordered.into_iter().map(|(_, route)| route).collect(),
+        routes_dir: None,
+    })
+}
+
+fn read_route(path: &Path, file_name: &str) -> Result<MockRoute> {
+    let content = fs::read_to_string(path).map_err(|err| {
+        AppError::Internal(format!("Failed to read route file {}: {}", file_name, err))
+    })?;
+    let mut value: JsonValue = serde_json::from_str(&content).map_err(|err| {
+        AppError::ParseError(format!("Invalid route file {}: {}", file_name, err))
+    })?;
+    if let Some(object) = value.as_object_mut() {
+        let has_id = object
+            .get("id")
+            .and_then(JsonValue::as_str)
+            .is_some_and(|id| !id.trim().is_empty());
+        if !has_id {
+            let stem = file_name.trim_end_matches(".json");
+            object.insert("id".to_string(), JsonValue::String(stem.to_string()));
+        }
+    }
+    serde_json::from_value(value)
+        .map_err(|err| AppError::ParseError(format!("Invalid route file {}: {}", file_name, err)))
+}
+
+/// Routes with a blank or repeated id get `<method>-<path>` as id, with a
+/// counter when that is taken too
+fn with_stable_ids(routes: &[MockRoute]) -> Vec<MockRoute> {
+    let mut taken: HashSet<String> = HashSet::new();
+    routes
+        .iter()
+        .map(|route| {
+            let mut route = route.clone();
+            if route.id.trim().is_empty() || taken.contains(&route.id) {
+                let base = format!("{}-{}", route.method.to_lowercase(), slug(&route.path));
+                let mut id = base.clone();
+                let mut counter = 2;
+                while taken.contains(&id) {
+                    id = format!("{}-{}", base, counter);
+                    counter += 1;
+                }
+                route.id = id;
+            }
+            taken.insert(route.id.clone());
+            route
+        })
+        .collect()
+}
+
+/// `get-api-v1-users-1f2c3d4e.json` for `GET /api/v1/users` with an id
+/// starting `1f2c3d4e`; ids given by `with_stable_ids` are used whole
+fn route_file_name(route: &MockRoute, full_id: bool) -> String {
+    let path: String = slug(&route.path).chars().take(FILE_SLUG_CHARS).collect();
+    let prefix = format!(
+        "{}-{}",
+        route.method.to_lowercase(),
+        path.trim_end_matches('-')
+    );
+    let id = slug(&route.id);
+    if id.starts_with(&prefix) {
+        return format!("{}.json", id);
+    }
+    let id: String = if full_id {
+        id
+    } else {
+        id.chars().take(FILE_ID_CHARS).collect()
+    };
+    format!("{}-{}.json", prefix, id.trim_end_matches('-'))
+}
+
+/// Lowercase ASCII letters and digits, other runs turned into one `-`
+fn slug(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "root".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+fn json_file_names(dir: &Path) -> Result<Vec<String>> {
+    let entries = fs::read_dir(dir)
+        .map_err(|err| AppError::Internal(format!("Failed to read {}: {}", dir.display(), err)))?;
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name.ends_with(".json"))
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let mut content = serde_json::to_string_pretty(value).map_err(|err| {
+        AppError::Internal(format!("Failed to serialize {}: {}", path.display(), err))
+    })?;
+    content.push('\n');
+    // Unchanged files are left alone so their modification time stays
+    if fs::read_to_string(path).is_ok_and(|existing| existing == content) {
+        return Ok(());
+    }
+    fs::write(path, content)
+        .map_err(|err| AppError::Internal(format!("Failed to write {}: {}", path.display(), err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(id: &str, method: &str, path: &str) -> MockRoute {
+        MockRoute {
+            id: id.to_string(),
+            name: path.to_string(),
+            enabled: true,
+            method: method.to_string(),
+            path: path.to_string(),
+            matchers: Default::default(),
+            response_strategy: Default::default(),
+            multi_response_match_mode: Default::default(),
+            response: Default::default(),
+            multi_responses: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn export_and_load_round_trip_in_order() {
+        let dir = std::env::temp_dir().join(format!("mock-routes-{}", uuid::Uuid::new_v4()));
+        let config = MockServerConfig {
+            port: 4020,
+            routes: vec![
+                route("f3a9c2d1-77", "POST", "/api/v1/orders"),
+                route("", "GET", "/api/v1/users/:id"),
+                route("0b1e", "GET", "/"),
+            ],
+            routes_dir: None,
+        };
+
+        let export = export_routes_dir(&config, &dir).unwrap();
+        assert_eq!(export.written, 3);
+        assert!(dir
+            .join(ROUTES_DIR)
+            .join("post-api-v1-orders-f3a9c2d1.json")
+            .is_file());
+        assert!(dir.join(ROUTES_DIR).join("get-root-0b1e.json").is_file());
+
+        let loaded = load_routes_dir(&dir).unwrap();
+        assert_eq!(loaded.port, 4020);
+        let ids: Vec<&str> = loaded.routes.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["f3a9c2d1-77", "get-api-v1-users-id", "0b1e"]);
+
+        let trimmed = MockServerConfig {
+            routes: loaded.routes[..1].to_vec(),
+            ..loaded
+        };
+        let export = export_routes_dir(&trimmed, &dir).unwrap();
+        assert_eq!(export.removed, 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,262 @@
+//! Hot reload of mock routes kept in files: the routes directory, or the
+//! config file of a state without a settings store. Changes are debounced,
+//! then loaded and validated off the request path and swapped in whole. A
+//! config that fails validation is reported and the running one is kept.
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+use crate::interfaces::http::add_log;
+
+use super::{load_config_from_path, load_routes_dir, MatchMode, MockServerConfig, MockServerState};
+
+/// Quiet time after the last change before reloading, so a checkout that
+/// rewrites many route files reloads once
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Keeps the file watch alive; dropping it ends the watch
+pub struct ConfigWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+/// Outcome of one reload, sent to the reload listener
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MockConfigReload {
+    pub source: String,
+    /// The new config is live; false when it failed to load or validate
+    pub applied: bool,
+    pub route_count: usize,
+    pub errors: Vec<String>,
+    /// The port changed while the server runs; it moves on the next start
+    pub restart_required: bool,
+}
+
+impl MockServerState {
+    /// Where the routes are read from when they live in files, and whether
+    /// that is a routes directory
+    fn file_source(&self) -> Option<(PathBuf, bool)> {
+        if let Some(dir) = self.config.lock().unwrap().routes_dir.clone() {
+            return Some((PathBuf::from(dir), true));
+        }
+        if self.settings.is_none() {
+            return Some((self.config_path.clone(), false));
+        }
+        None
+    }
+
+    /// Watch the config source for changes, replacing an earlier watch.
+    /// Routes kept in the settings need no watch; their edits go through
+    /// `save_config`.
+    pub fn watch_config_source(self: &Arc<Self>) {
+        let mut slot = self.watcher.lock().unwrap();
+        *slot = None;
+        let Some((source, is_dir)) = self.file_source() else {
+            return;
+        };
+        match start_watch(Arc::downgrade(self), &source, is_dir) {
+            Ok(watcher) => {
+                *slot = Some(watcher);
+                add_log(
+                    &self.logs,
+                    "INFO",
+                    "MockServer",
+                    &format!("Watching {} for mock route changes", source.display()),
+                );
+            }
+            Err(err) => add_log(
+                &self.logs,
+                "WARN",
+                "MockServer",
+                &format!(
+                    "Mock routes in {} won't reload on change: {}",
+                    source.display(),
+                    err
+                ),
+            ),
+        }
+    }
+
+    /// Load the config source, validate it and swap it in. `None` when the
+    /// routes live in the settings or the files match the running config.
+    pub fn reload_from_source(&self) -> Option<MockConfigReload> {
+        let (source, is_dir) = self.file_source()?;
+        let loaded = if is_dir {
+            load_routes_dir(&source)
+        } else {
+            load_config_from_path(&source)
+        };
+        let running = self.server.lock().unwrap().is_some();
+
+        let mut reload = MockConfigReload {
+            source: source.display().to_string(),
+            applied: false,
+            route_count: 0,
+            errors: Vec::new(),
+            restart_required: false,
+        };
+        match loaded {
+            Err(err) => reload.errors.push(err.to_string()),
+            Ok(loaded) => {
+                reload.route_count = loaded.routes.len();
+                reload.errors = validate_config(&loaded);
+                if reload.errors.is_empty() {
+                    let mut current = self.config.lock().unwrap();
+                    if same_routes(&current, &loaded) {
+                        return None;
+                    }
+                    reload.restart_required = running && current.port != loaded.port;
+                    current.port = loaded.port;
+                    current.routes = loaded.routes;
+                    reload.applied = true;
+                }
+            }
+        }
+
+        if reload.applied {
+            add_log(
+                &self.logs,
+                "INFO",
+                "MockServer",
+                &format!(
+                    "config-reloaded: {} routes from {}{}",
+                    reload.route_count,
+                    reload.source,
+                    if reload.restart_required {
+                        "; restart the server to use the new port"
+                    } else {
+                        ""
+                    }
+                ),
+            );
+        } else {
+            add_log(
+                &self.logs,
+                "ERROR",
+                "MockServer",
+                &format!(
+                    "Kept the running mock config; {} failed to reload: {}",
+                    reload.source,
+                    reload.errors.join("; ")
+                ),
+            );
+        }
+        if let Some(listener) = &self.reload_listener {
+            listener(&reload);
+        }
+        Some(reload)
+    }
+}
+
+/// Problems that would make routes of the config misbehave: repeated ids,
+/// and enabled routes without a method, with a relative path or with a
+/// body regex that does not compile
+pub fn validate_config(config: &MockServerConfig) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut ids = HashSet::new();
+    for route in &config.routes {
+        let label = format!("Route '{}' ({} {})", route.name, route.method, route.path);
+        if !ids.insert(route.id.as_str()) {
+            errors.push(format!(
+                "{}: id '{}' is used by another route",
+                label, route.id
+            ));
+        }
+        if !route.enabled {
+            continue;
+        }
+        if route.method.trim().is_empty() {
+            errors.push(format!("{}: method is empty", label));
+        }
+        if !route.path.trim().starts_with('/') {
+            errors.push(format!("{}: path must start with '/'", label));
+        }
+        if let Some(body) = &route.matchers.body {
+            if body.mode == MatchMode::Regex {
+                if let Err(err) = Regex::new(body.value.trim()) {
+                    errors.push(format!("{}: invalid body regex: {}", label, err));
+                }
+            }
+        }
+    }
+    errors
+}
+
+fn same_routes(current: &MockServerConfig, loaded: &MockServerConfig) -> bool {
+    current.port == loaded.port
+        && serde_json::to_value(&current.routes).ok() == serde_json::to_value(&loaded.routes).ok()
+}
+
+fn start_watch(
+    state: std::sync::Weak<MockServerState>,
+    source: &Path,
+    is_dir: bool,
+) -> notify::Result<ConfigWatcher> {
+    let (sender, receiver) = mpsc::channel::<()>();
+    let file = (!is_dir).then(|| source.to_path_buf());
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        if matches!(event.kind, EventKind::Access(_)) {
+            return;
+        }
+        if let Some(file) = &file {
+            if !event.paths.iter().any(|path| path == file) {
+                return;
+            }
+        }
+        let _ = sender.send(());
+    })?;
+    // Editors save a file by replacing it, which ends a watch on the file
+    // itself; watch its directory instead
+    match source.parent().filter(|_| !is_dir) {
+        Some(parent) => watcher.watch(parent, RecursiveMode::NonRecursive)?,
+        None => watcher.watch(source, RecursiveMode::Recursive)?,
+    }
+
+    // Ends when the watcher, and with it the sender, is dropped
+    std::thread::spawn(move || {
+        while receiver.recv().is_ok() {
+            while receiver.recv_timeout(RELOAD_DEBOUNCE).is_ok() {}
+            let Some(state) = state.upgrade() else {
+                break;
+            };
+            state.reload_from_source();
+        }
+    });
+
+    Ok(ConfigWatcher { _watcher: watcher })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_config_reports_broken_enabled_routes() {
+        let config: MockServerConfig = serde_json::from_value(serde_json::json!({
+            "port": 4010,
+            "routes": [
+                { "id": "a", "name": "ok", "enabled": true, "method": "GET", "path": "/users" },
+                { "id": "a", "name": "copy", "enabled": false, "method": "", "path": "users" },
+                {
+                    "id": "b", "name": "regex", "enabled": true, "method": "POST", "path": "orders",
+                    "matchers": { "body": { "mode": "regex", "value": "(" } }
+                }
+            ]
+        }))
+        .unwrap();
+
+        let errors = validate_config(&config);
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+        assert!(errors[0].contains("id 'a' is used by another route"));
+        assert!(errors[1].contains("path must start with '/'"));
+        assert!(errors[2].contains("invalid body regex"));
+    }
+}
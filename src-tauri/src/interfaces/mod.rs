@@ -1,4 +1,8 @@
+pub mod automation;
+pub mod background;
 pub mod http;
 pub mod mock_server;
+pub mod quick_action;
 pub mod shortcuts;
 pub mod tauri;
+pub mod tray;
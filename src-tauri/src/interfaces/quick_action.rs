@@ -0,0 +1,146 @@
+//! Quick-action popup window
+//!
+//! A small frameless, always-on-top window the popup shortcut opens next to
+//! the cursor. It is created on first use and hidden, not closed, afterwards
+//! so later shortcuts show it instantly.
+
+use std::sync::Arc;
+
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+use crate::interfaces::http::add_log;
+use crate::interfaces::tauri::AppState;
+
+pub(crate) const WINDOW_LABEL: &str = "quick-action";
+
+/// Emitted to the window with the newly captured text
+pub(crate) const CAPTURE_EVENT: &str = "quick-action-capture";
+
+const WIDTH: f64 = 440.0;
+const HEIGHT: f64 = 360.0;
+
+/// Gap between the cursor and the nearest window corner, in physical pixels
+const CURSOR_OFFSET: i32 = 12;
+
+fn get_or_create(app: &AppHandle) -> tauri::Result<WebviewWindow> {
+    if let Some(window) = app.get_webview_window(WINDOW_LABEL) {
+        return Ok(window);
+    }
+
+    WebviewWindowBuilder::new(
+        app,
+        WINDOW_LABEL,
+        WebviewUrl::App("index.html?label=quick-action".into()),
+    )
+    .title("gadogado Quick Action")
+    .inner_size(WIDTH, HEIGHT)
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .resizable(false)
+    .visible(false)
+    .build()
+}
+
+/// Top-left corner for a window of `size` beside `cursor`, kept inside the work area.
+/// The window opens below-right of the cursor and flips to the other side near an edge.
+pub(crate) fn position_near_cursor(
+    cursor: (i32, i32),
+    size: (i32, i32),
+    area_origin: (i32, i32),
+    area_size: (i32, i32),
+) -> (i32, i32) {
+    let place = |cursor: i32, size: i32, origin: i32, extent: i32| {
+        let max = (origin + extent - size).max(origin);
+        let after = cursor + CURSOR_OFFSET;
+        let start = if after <= max {
+            after
+        } else {
+            cursor - CURSOR_OFFSET - size
+        };
+        start.clamp(origin, max)
+    };
+
+    (
+        place(cursor.0, size.0, area_origin.0, area_size.0),
+        place(cursor.1, size.1, area_origin.1, area_size.1),
+    )
+}
+
+fn place_near_cursor(app: &AppHandle, window: &WebviewWindow) -> tauri::Result<()> {
+    let cursor = app.cursor_position()?;
+    let monitor = match app.monitor_from_point(cursor.x, cursor.y)? {
+        Some(monitor) => Some(monitor),
+        None => app.primary_monitor()?,
+    };
+    let Some(monitor) = monitor else {
+        return window.center();
+    };
+
+    let area = monitor.work_area();
+    let size = window.outer_size()?;
+    let (x, y) = position_near_cursor(
+        (cursor.x as i32, cursor.y as i32),
+        (size.width as i32, size.height as i32),
+        (area.position.x, area.position.y),
+        (area.size.width as i32, area.size.height as i32),
+    );
+    window.set_position(tauri::PhysicalPosition::new(x, y))
+}
+
+/// Show the quick-action window near the cursor with `captured` as its input
+pub(crate) fn show(app: &AppHandle, captured: Option<String>) -> tauri::Result<()> {
+    let state = app.state::<Arc<AppState>>();
+    *state.quick_action_capture.lock().unwrap() = captured.clone();
+
+    let window = get_or_create(app)?;
+    if let Err(e) = place_near_cursor(app, &window) {
+        add_log(
+            &state.logs,
+            "WARN",
+            "QuickAction",
+            &format!("Failed to position quick action window: {}", e),
+        );
+        let _ = window.center();
+    }
+    window.show()?;
+    window.set_focus()?;
+
+    // A freshly created window reads the capture through `quick_action_take_capture`
+    // once it has loaded; an existing one is listening already.
+    window.emit(CAPTURE_EVENT, captured.unwrap_or_default())
+}
+
+pub(crate) fn hide(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(WINDOW_LABEL) {
+        let _ = window.hide();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_opens_below_right_of_cursor() {
+        let pos = position_near_cursor((100, 100), (400, 300), (0, 0), (1920, 1040));
+        assert_eq!(pos, (112, 112));
+    }
+
+    #[test]
+    fn test_position_flips_near_edges() {
+        let pos = position_near_cursor((1900, 1000), (400, 300), (0, 0), (1920, 1040));
+        assert_eq!(pos, (1900 - 12 - 400, 1000 - 12 - 300));
+    }
+
+    #[test]
+    fn test_position_stays_on_secondary_monitor() {
+        // Monitor left of the primary one, cursor in its top-left corner
+        let pos = position_near_cursor((-1915, 2), (400, 300), (-1920, 0), (1920, 1080));
+        assert_eq!(pos, (-1903, 14));
+
+        // Window larger than the work area is pinned to its origin
+        let pos = position_near_cursor((10, 10), (800, 600), (0, 0), (640, 480));
+        assert_eq!(pos, (0, 0));
+    }
+}
@@ -7,6 +7,9 @@ use tauri::{Emitter, Manager};
 use tauri_plugin_clipboard_manager::{Clipboard, ClipboardExt};
 use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
 
+use crate::domain::settings::LlmFeature;
+#[cfg(target_os = "linux")]
+use crate::infrastructure::desktop_input::{self, KeySender};
 use crate::interfaces::tauri::AppState;
 
 fn log_shortcut(state: &tauri::State<'_, Arc<AppState>>, level: &str, message: &str) {
@@ -210,8 +213,11 @@ fn hide_loading_window(app: &tauri::AppHandle, restore_main_window: bool) {
     }
 }
 
-fn current_config(state: &tauri::State<'_, Arc<AppState>>) -> crate::domain::llm_config::LLMConfig {
-    state.last_config.lock().unwrap().clone()
+fn current_config(
+    state: &tauri::State<'_, Arc<AppState>>,
+    feature: LlmFeature,
+) -> crate::domain::llm_config::LLMConfig {
+    state.feature_config(feature)
 }
 
 fn current_languages(state: &tauri::State<'_, Arc<AppState>>) -> (String, String) {
@@ -230,7 +236,8 @@ fn current_languages(state: &tauri::State<'_, Arc<AppState>>) -> (String, String
     (source, target)
 }
 
-async fn perform_robust_copy(enigo: &mut Enigo) {
+#[cfg(not(target_os = "linux"))]
+async fn perform_robust_copy(enigo: &mut Enigo) -> bool {
     #[cfg(target_os = "windows")]
     {
         enigo.key_up(Key::Alt);
@@ -260,14 +267,77 @@ async fn perform_robust_copy(enigo: &mut Enigo) {
         tokio::time::sleep(Duration::from_millis(100)).await;
         enigo.key_up(Key::Meta);
     }
+
+    true
+}
+
+#[cfg(target_os = "linux")]
+async fn perform_robust_copy(enigo: &mut Enigo) -> bool {
+    send_ctrl_chord(enigo, 'c').await
+}
+
+/// Ctrl+`key` through enigo on X11 or the detected tool on Wayland.
+/// Returns false in clipboard-only mode or when the tool failed.
+#[cfg(target_os = "linux")]
+async fn send_ctrl_chord(enigo: &mut Enigo, key: char) -> bool {
+    match desktop_input::capabilities().key_sender {
+        Some(KeySender::Native) => {
+            enigo.key_up(Key::Alt);
+            enigo.key_up(Key::Control);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+
+            enigo.key_down(Key::Control);
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            enigo.key_click(Key::Layout(key));
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            enigo.key_up(Key::Control);
+            true
+        }
+        Some(sender) => {
+            // The virtual keyboard shares modifier state with the physical one,
+            // so give the user time to let go of the shortcut first
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            desktop_input::send_ctrl_chord(sender, key).await
+        }
+        None => false,
+    }
+}
+
+/// Capture without simulated keystrokes: the current selection when it can be
+/// read, otherwise whatever the user copied before pressing the shortcut
+#[cfg(target_os = "linux")]
+async fn capture_without_keys<R: tauri::Runtime>(
+    clipboard: &Clipboard<R>,
+) -> Result<String, String> {
+    if let Some(text) = desktop_input::read_selection().await {
+        return Ok(text);
+    }
+    match clipboard.read_text() {
+        Ok(content) if !content.trim().is_empty() => Ok(content),
+        _ => Err(
+            "Nothing selected or copied. Automatic copy is unavailable in this session, copy the text before using the shortcut."
+                .to_string(),
+        ),
+    }
 }
 
 async fn capture_selection<R: tauri::Runtime>(
     clipboard: &Clipboard<R>,
     enigo: &mut Enigo,
 ) -> Result<String, String> {
+    #[cfg(target_os = "linux")]
+    if desktop_input::capabilities().clipboard_only() {
+        return capture_without_keys(clipboard).await;
+    }
+
     let _ = clipboard.write_text("");
-    perform_robust_copy(enigo).await;
+    if !perform_robust_copy(enigo).await {
+        #[cfg(target_os = "linux")]
+        if let Some(text) = desktop_input::read_selection().await {
+            return Ok(text);
+        }
+        return Err("Failed to send the copy shortcut.".to_string());
+    }
 
     for _ in 0..40 {
         tokio::time::sleep(Duration::from_millis(50)).await;
@@ -281,7 +351,10 @@ async fn capture_selection<R: tauri::Runtime>(
     Err("Clipboard is empty after auto-copy retries.".to_string())
 }
 
-async fn auto_paste(enigo: &mut Enigo) {
+/// Paste into the focused app. Returns false when the result is left in the
+/// clipboard for the user to paste (clipboard-only mode on Linux).
+#[cfg(not(target_os = "linux"))]
+pub(crate) async fn auto_paste(enigo: &mut Enigo) -> bool {
     #[cfg(target_os = "windows")]
     {
         enigo.key_down(Key::Control);
@@ -294,6 +367,13 @@ async fn auto_paste(enigo: &mut Enigo) {
         enigo.key_click(Key::Layout('v'));
         enigo.key_up(Key::Meta);
     }
+
+    true
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) async fn auto_paste(enigo: &mut Enigo) -> bool {
+    send_ctrl_chord(enigo, 'v').await
 }
 
 async fn handle_global_translate(app: tauri::AppHandle) -> std::result::Result<(), String> {
@@ -318,7 +398,7 @@ async fn handle_global_translate(app: tauri::AppHandle) -> std::result::Result<(
         }
     };
 
-    let config = current_config(&state);
+    let config = current_config(&state, LlmFeature::Translate);
     let (source_lang, target_lang) = current_languages(&state);
 
     let result = match state
@@ -341,8 +421,15 @@ async fn handle_global_translate(app: tauri::AppHandle) -> std::result::Result<(
             .write_text(translated)
             .map_err(|e| e.to_string())?;
 
-        auto_paste(&mut enigo).await;
-        log_shortcut(&state, "INFO", "Translation complete.");
+        if auto_paste(&mut enigo).await {
+            log_shortcut(&state, "INFO", "Translation complete.");
+        } else {
+            log_shortcut(
+                &state,
+                "INFO",
+                "Translation complete (copied to clipboard, paste manually).",
+            );
+        }
     }
 
     emit_shortcut_event(&app, "shortcut-end", "success");
@@ -372,8 +459,15 @@ async fn handle_global_enhance(app: tauri::AppHandle) -> std::result::Result<(),
         }
     };
 
-    let config = current_config(&state);
-    let result = match state.enhance_use_case.execute(&config, text, None).await {
+    let config = current_config(&state, LlmFeature::Enhance);
+    let language = state.output_language(None);
+    // The default preset; the popup offers the others
+    let preset = state.enhance_preset(None, false).unwrap_or(None);
+    let result = match state
+        .enhance_use_case
+        .execute_with_preset(&config, text, None, preset.as_ref(), language.as_ref())
+        .await
+    {
         Ok(result) => result,
         Err(e) => {
             let message = e.to_string();
@@ -386,8 +480,15 @@ async fn handle_global_enhance(app: tauri::AppHandle) -> std::result::Result<(),
 
     if let Some(enhanced) = result.result {
         clipboard.write_text(enhanced).map_err(|e| e.to_string())?;
-        auto_paste(&mut enigo).await;
-        log_shortcut(&state, "INFO", "Enhancement complete.");
+        if auto_paste(&mut enigo).await {
+            log_shortcut(&state, "INFO", "Enhancement complete.");
+        } else {
+            log_shortcut(
+                &state,
+                "INFO",
+                "Enhancement complete (copied to clipboard, paste manually).",
+            );
+        }
     }
 
     emit_shortcut_event(&app, "shortcut-end", "success");
@@ -400,24 +501,25 @@ async fn handle_global_popup(app: tauri::AppHandle) -> std::result::Result<(), S
     let clipboard = app.clipboard();
 
     log_shortcut(&state, "INFO", "Processing popup shortcut...");
-    let restore_main_window = should_restore_main_window(&app);
     emit_shortcut_event(&app, "shortcut-start", "popup");
 
     let mut enigo = Enigo::new();
     let text = capture_selection(&clipboard, &mut enigo).await.ok();
 
-    if let Some(text) = text {
+    if text.is_some() {
         log_shortcut(&state, "INFO", "Captured text for popup.");
-        emit_shortcut_event(&app, "shortcut-capture", &text);
     } else {
         log_shortcut(&state, "WARN", "No text captured for popup.");
     }
 
-    if restore_main_window {
-        if let Some(window) = app.get_webview_window("main") {
-            let _ = window.unminimize();
-            let _ = window.set_focus();
-        }
+    if let Err(e) = crate::interfaces::quick_action::show(&app, text) {
+        log_shortcut(
+            &state,
+            "ERROR",
+            &format!("Failed to open quick action window: {}", e),
+        );
+        emit_shortcut_event(&app, "shortcut-end", "error");
+        return Ok(());
     }
 
     emit_shortcut_event(&app, "shortcut-end", "success");
@@ -445,7 +547,7 @@ async fn handle_global_terminal(app: tauri::AppHandle) -> std::result::Result<()
         }
     };
 
-    let config = current_config(&state);
+    let config = current_config(&state, LlmFeature::Translate);
     let (source_lang, target_lang) = current_languages(&state);
 
     let result = match state
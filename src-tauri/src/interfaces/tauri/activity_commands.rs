@@ -0,0 +1,20 @@
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::domain::activity::{ActivityPage, ActivityQuery};
+use crate::domain::error::Result;
+
+use super::state::AppState;
+
+/// The activity timeline across QA runs, RAG ingestion, training and the
+/// mock server. Pages count back from the newest entry; each page reads
+/// oldest first.
+#[tauri::command]
+pub async fn get_activity_feed(
+    state: State<'_, Arc<AppState>>,
+    filter: Option<ActivityQuery>,
+) -> Result<ActivityPage> {
+    let filter = filter.unwrap_or_default();
+    state.activity_feed.query(&filter).await
+}
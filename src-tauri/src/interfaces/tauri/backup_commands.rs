@@ -0,0 +1,111 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tauri::State;
+
+use crate::domain::backup::{
+    BackupEntry, BackupReason, BackupSettings, RestoreReport, RestoreRequest,
+};
+use crate::domain::error::{AppError, Result};
+use crate::infrastructure::backup::crypto::KEY_LEN;
+use crate::interfaces::http::add_log;
+
+use super::state::AppState;
+
+/// Encryption key for a new backup when the settings ask for one
+fn new_backup_key(state: &AppState, settings: &BackupSettings) -> Result<Option<[u8; KEY_LEN]>> {
+    if settings.encrypt {
+        state.config_service.backup_encryption_key().map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Back up every database now, following the current backup settings
+#[tauri::command]
+pub async fn backup_create(state: State<'_, Arc<AppState>>) -> Result<BackupEntry> {
+    let settings = state.settings.get::<BackupSettings>();
+    let key = new_backup_key(&state, &settings)?;
+    let entry = state
+        .backups
+        .create(BackupReason::Manual, settings.include_qa_sessions, key)
+        .await?;
+
+    add_log(
+        &state.logs,
+        "INFO",
+        "Backup",
+        &format!(
+            "Backup created: {} ({} bytes)",
+            entry.path, entry.size_bytes
+        ),
+    );
+    Ok(entry)
+}
+
+#[tauri::command]
+pub async fn backup_list(state: State<'_, Arc<AppState>>) -> Result<Vec<BackupEntry>> {
+    state.backups.list()
+}
+
+#[tauri::command]
+pub async fn backup_delete(state: State<'_, Arc<AppState>>, id: String) -> Result<()> {
+    state.backups.delete(&id).await?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "Backup",
+        &format!("Backup deleted: {}", id),
+    );
+    Ok(())
+}
+
+/// Restore databases and QA session files from a backup. A safety snapshot
+/// of the current data is taken first; the restored files are swapped in
+/// when the app restarts, which happens right after this returns.
+#[tauri::command]
+pub async fn backup_restore(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<AppState>>,
+    request: RestoreRequest,
+) -> Result<RestoreReport> {
+    let entry = state.backups.find(&request.id)?;
+    let archive_key = if entry.manifest.encrypted {
+        let key = state
+            .config_service
+            .find_backup_encryption_key()?
+            .ok_or_else(|| {
+                AppError::SecurityError(
+                    "This backup is encrypted but no backup key is in the keychain".to_string(),
+                )
+            })?;
+        Some(key)
+    } else {
+        None
+    };
+    let settings = state.settings.get::<BackupSettings>();
+    let safety_key = new_backup_key(&state, &settings)?;
+
+    let report = state
+        .backups
+        .restore(&request, archive_key, safety_key)
+        .await?;
+
+    add_log(
+        &state.logs,
+        "INFO",
+        "Backup",
+        &format!(
+            "Restore of {} staged; current data saved to {}. Restarting",
+            request.id, report.safety_backup.path
+        ),
+    );
+
+    // Give the response time to reach the window before restarting
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(750)).await;
+        app.request_restart();
+    });
+
+    Ok(report)
+}
@@ -7,24 +7,79 @@ macro_rules! tauri_invoke_handler {
         tauri::generate_handler![
             // Core app commands
             crate::interfaces::tauri::core_commands::translate_prompt,
+            crate::interfaces::tauri::core_commands::detect_language,
             crate::interfaces::tauri::core_commands::enhance_prompt,
+            crate::interfaces::tauri::core_commands::generate_types,
             crate::interfaces::tauri::core_commands::llm_chat,
             crate::interfaces::tauri::core_commands::get_translation_history,
+            crate::interfaces::tauri::history_commands::search_history,
+            crate::interfaces::tauri::history_commands::tag_prompt,
+            crate::interfaces::tauri::history_commands::set_prompt_favorite,
+            crate::interfaces::tauri::history_commands::list_favorites,
+            crate::interfaces::tauri::history_commands::list_history_tags,
+            crate::interfaces::tauri::history_commands::export_history,
             crate::interfaces::tauri::core_commands::save_api_key,
             crate::interfaces::tauri::core_commands::get_api_key,
             crate::interfaces::tauri::core_commands::delete_api_key,
             crate::interfaces::tauri::core_commands::get_llm_models,
             crate::interfaces::tauri::core_commands::sync_config,
+            crate::interfaces::tauri::core_commands::sync_feature_model,
             crate::interfaces::tauri::core_commands::sync_embedding_config,
             crate::interfaces::tauri::core_commands::sync_languages,
             crate::interfaces::tauri::core_commands::sync_shortcuts,
+            crate::interfaces::tauri::settings_commands::settings_get,
+            crate::interfaces::tauri::settings_commands::settings_update_section,
+            crate::interfaces::tauri::settings_commands::settings_export,
+            crate::interfaces::tauri::settings_commands::settings_import,
             crate::interfaces::tauri::core_commands::get_logs,
             crate::interfaces::tauri::core_commands::add_log_message,
+            crate::interfaces::tauri::log_commands::logs_query,
+            crate::interfaces::tauri::log_commands::logs_get_settings,
+            crate::interfaces::tauri::log_commands::logs_update_settings,
+            crate::interfaces::tauri::log_commands::logs_prune,
+            crate::interfaces::tauri::activity_commands::get_activity_feed,
+            crate::interfaces::tauri::telemetry_commands::get_usage_dashboard,
+            crate::interfaces::tauri::telemetry_commands::clear_usage_stats,
+            crate::interfaces::tauri::diagnostics_commands::system_diagnostics,
+            crate::interfaces::tauri::db_maintenance_commands::db_maintenance,
+            crate::interfaces::tauri::backup_commands::backup_create,
+            crate::interfaces::tauri::backup_commands::backup_list,
+            crate::interfaces::tauri::backup_commands::backup_delete,
+            crate::interfaces::tauri::backup_commands::backup_restore,
+            crate::interfaces::tauri::storage_commands::storage_locations,
+            crate::interfaces::tauri::storage_commands::storage_plan_migration,
+            crate::interfaces::tauri::storage_commands::storage_migrate,
+            crate::interfaces::tauri::ollama_commands::ollama_status,
+            crate::interfaces::tauri::ollama_commands::ollama_list_models,
+            crate::interfaces::tauri::ollama_commands::ollama_start,
+            crate::interfaces::tauri::ollama_commands::ollama_stop,
+            crate::interfaces::tauri::ollama_commands::ollama_pull_model,
+            crate::interfaces::tauri::http_server_commands::http_server_info,
+            crate::interfaces::tauri::http_server_commands::http_server_update_settings,
+            crate::interfaces::tauri::http_server_commands::http_server_rotate_token,
+            crate::interfaces::tauri::quick_action_commands::quick_action_take_capture,
+            crate::interfaces::tauri::quick_action_commands::quick_action_submit,
+            crate::interfaces::tauri::quick_action_commands::quick_action_apply,
+            crate::interfaces::tauri::quick_action_commands::quick_action_close,
+            crate::interfaces::tauri::glossary_commands::glossary_list_terms,
+            crate::interfaces::tauri::glossary_commands::glossary_upsert_term,
+            crate::interfaces::tauri::glossary_commands::glossary_delete_term,
+            crate::interfaces::tauri::translation_memory_commands::tm_search,
+            crate::interfaces::tauri::translation_memory_commands::tm_add_entry,
+            crate::interfaces::tauri::translation_memory_commands::tm_approve_entry,
+            crate::interfaces::tauri::translation_memory_commands::tm_delete_entry,
+            crate::interfaces::tauri::trash_commands::trash_list,
+            crate::interfaces::tauri::trash_commands::trash_restore,
+            crate::interfaces::tauri::trash_commands::trash_purge,
             crate::interfaces::tauri::mock_server_commands::mock_server_get_config,
             crate::interfaces::tauri::mock_server_commands::mock_server_update_config,
             crate::interfaces::tauri::mock_server_commands::mock_server_start,
             crate::interfaces::tauri::mock_server_commands::mock_server_stop,
             crate::interfaces::tauri::mock_server_commands::mock_server_status,
+            crate::interfaces::tauri::mock_server_commands::mock_server_export_routes,
+            crate::interfaces::tauri::mock_server_commands::mock_server_import_routes,
+            crate::interfaces::tauri::mock_server_commands::mock_server_set_routes_dir,
+            crate::interfaces::tauri::profile_commands::project_profile_apply,
             // QA commands
             crate::interfaces::tauri::qa::sessions::qa_start_session,
             crate::interfaces::tauri::qa::sessions::qa_end_session,
@@ -35,7 +90,22 @@ macro_rules! tauri_invoke_handler {
             crate::interfaces::tauri::qa::runs::qa_append_run_stream_event,
             crate::interfaces::tauri::qa::runs::qa_list_run_stream_events,
             crate::interfaces::tauri::qa::api::qa_execute_api_request,
+            crate::interfaces::tauri::qa::api_tests::qa_save_api_test,
+            crate::interfaces::tauri::qa::api_tests::qa_list_api_tests,
+            crate::interfaces::tauri::qa::api_tests::qa_run_api_tests,
+            crate::interfaces::tauri::qa::api_tests::qa_suggest_api_assertions,
+            crate::interfaces::tauri::qa::auth::qa_get_auth_profile_statuses,
+            crate::interfaces::tauri::qa::auth::qa_save_auth_secret,
+            crate::interfaces::tauri::qa::auth::qa_test_auth_profile,
+            crate::interfaces::tauri::qa::codegen::qa_generate_api_client,
+            crate::interfaces::tauri::qa::codegen::qa_export_gherkin,
+            crate::interfaces::tauri::qa::test_management::qa_push_test_cases,
+            crate::interfaces::tauri::qa::test_management::qa_push_test_results,
+            crate::interfaces::tauri::qa::test_management::qa_list_test_sync_log,
+            crate::interfaces::tauri::qa::test_management::qa_save_test_management_secret,
+            crate::interfaces::tauri::qa::test_management::qa_get_test_management_credentials,
             crate::interfaces::tauri::qa::replay::qa_replay_browser,
+            crate::interfaces::tauri::qa::runtime::qa_check_recorder_environment,
             crate::interfaces::tauri::qa::events::qa_record_event,
             crate::interfaces::tauri::qa::devtools::qa_open_devtools,
             crate::interfaces::tauri::qa::sessions::qa_list_sessions,
@@ -45,26 +115,70 @@ macro_rules! tauri_invoke_handler {
             crate::interfaces::tauri::qa::screenshots::qa_capture_native_screenshot,
             crate::interfaces::tauri::qa::events::qa_list_events_page,
             crate::interfaces::tauri::qa::events::qa_delete_events,
+            crate::interfaces::tauri::qa::events::qa_link_api_calls,
+            crate::interfaces::tauri::qa::events::qa_list_api_links,
+            crate::interfaces::tauri::qa::redaction::qa_redact_session,
             crate::interfaces::tauri::qa::sessions::qa_delete_session,
+            crate::interfaces::tauri::qa::sessions::qa_list_active_sessions,
+            crate::interfaces::tauri::qa::sessions::qa_cleanup_storage,
             crate::interfaces::tauri::qa::sessions::qa_get_session,
+            crate::interfaces::tauri::qa::sessions::qa_set_session_selectors,
+            crate::interfaces::tauri::qa::sessions::qa_get_selector_attributes,
             crate::interfaces::tauri::qa::checkpoints::qa_create_checkpoint,
             crate::interfaces::tauri::qa::checkpoints::qa_list_checkpoints,
+            crate::interfaces::tauri::qa::checkpoints::qa_update_checkpoint,
+            crate::interfaces::tauri::qa::checkpoints::qa_merge_checkpoints,
+            crate::interfaces::tauri::qa::checkpoints::qa_split_checkpoint,
+            crate::interfaces::tauri::qa::checkpoints::qa_regenerate_checkpoints,
             crate::interfaces::tauri::qa::checkpoints::qa_generate_checkpoint_summary,
             crate::interfaces::tauri::qa::checkpoints::qa_generate_test_cases,
             crate::interfaces::tauri::qa::checkpoints::qa_list_checkpoint_summaries,
             crate::interfaces::tauri::qa::checkpoints::qa_list_test_cases,
             crate::interfaces::tauri::qa::checkpoints::qa_list_llm_runs,
+            crate::interfaces::tauri::qa::checkpoints::qa_get_llm_run_detail,
+            crate::interfaces::tauri::qa::prompts::qa_get_prompt_templates,
+            crate::interfaces::tauri::qa::prompts::qa_set_prompt_template,
+            crate::interfaces::tauri::qa::prompts::qa_reset_prompt_template,
             crate::interfaces::tauri::qa::explore::qa_explore_session,
             // RAG commands
             crate::interfaces::tauri::rag_commands::collections::rag_create_collection,
             crate::interfaces::tauri::rag_commands::collections::rag_get_collection,
             crate::interfaces::tauri::rag_commands::collections::rag_list_collections,
             crate::interfaces::tauri::rag_commands::collections::rag_delete_collection,
+            crate::interfaces::tauri::rag_commands::collections::rag_get_collection_encryption,
+            crate::interfaces::tauri::rag_commands::collections::rag_enable_collection_encryption,
+            crate::interfaces::tauri::rag_commands::collections::rag_unlock_collection,
+            crate::interfaces::tauri::rag_commands::collections::rag_lock_collection,
+            crate::interfaces::tauri::rag_commands::collections::rag_disable_collection_encryption,
+            crate::interfaces::tauri::rag_commands::collections::rag_get_collection_pipeline,
+            crate::interfaces::tauri::rag_commands::collections::rag_set_collection_pipeline,
+            crate::interfaces::tauri::rag_commands::collections::rag_quantize_collection_embeddings,
+            crate::interfaces::tauri::rag_commands::collections::rag_get_collection_language,
+            crate::interfaces::tauri::rag_commands::collections::rag_set_collection_language,
+            crate::interfaces::tauri::rag_commands::collections::rag_move_documents,
+            crate::interfaces::tauri::rag_commands::collections::rag_merge_collections,
+            crate::interfaces::tauri::rag_commands::collections::rag_split_collection,
             crate::interfaces::tauri::rag_commands::documents::rag_get_document,
+            crate::interfaces::tauri::rag_commands::documents::rag_get_document_preview,
             crate::interfaces::tauri::rag_commands::documents::rag_delete_document,
             crate::interfaces::tauri::rag_commands::documents::rag_list_documents,
             crate::interfaces::tauri::rag_commands::documents::rag_import_file,
+            crate::interfaces::tauri::rag_commands::documents::rag_list_workbook_sheets,
+            crate::interfaces::tauri::rag_commands::documents::rag_import_workbook,
+            crate::interfaces::tauri::rag_commands::documents::rag_resume_failed_ingestions,
             crate::interfaces::tauri::rag_commands::documents::rag_get_collection_document_type,
+            crate::interfaces::tauri::rag_commands::embedding_models::rag_list_embedding_models,
+            crate::interfaces::tauri::rag_commands::embedding_models::rag_get_embedding_model_info,
+            crate::interfaces::tauri::rag_commands::embedding_models::rag_download_embedding_model,
+            crate::interfaces::tauri::rag_commands::embedding_models::rag_set_active_embedding_model,
+            crate::interfaces::tauri::rag_commands::embedding_models::rag_benchmark_embedding_providers,
+            crate::interfaces::tauri::rag_commands::ocr_languages::rag_list_ocr_languages,
+            crate::interfaces::tauri::rag_commands::ocr_languages::rag_download_ocr_language,
+            crate::interfaces::tauri::rag_commands::ocr_languages::rag_get_document_ocr_languages,
+            crate::interfaces::tauri::rag_commands::ocr_languages::rag_set_document_ocr_languages,
+            crate::interfaces::tauri::rag_commands::ocr_queue::rag_list_ocr_pages,
+            crate::interfaces::tauri::rag_commands::ocr_queue::rag_process_ocr_queue,
+            crate::interfaces::tauri::rag_commands::ocr_queue::rag_requeue_ocr_page,
             crate::interfaces::tauri::rag_commands::chunks::rag_list_chunks,
             crate::interfaces::tauri::rag_commands::chunks::rag_list_excel_data,
             crate::interfaces::tauri::rag_commands::search_chat::rag_hybrid_search,
@@ -74,10 +188,32 @@ macro_rules! tauri_invoke_handler {
             crate::interfaces::tauri::rag_commands::web_ocr_validation::rag_smart_chunking,
             crate::interfaces::tauri::rag_commands::web_ocr_validation::rag_hybrid_retrieval,
             crate::interfaces::tauri::rag_commands::web_ocr_validation::rag_run_validation_suite,
+            crate::interfaces::tauri::rag_commands::connectors::rag_list_connectors,
+            crate::interfaces::tauri::rag_commands::connectors::rag_create_connector,
+            crate::interfaces::tauri::rag_commands::connectors::rag_update_connector,
+            crate::interfaces::tauri::rag_commands::connectors::rag_delete_connector,
+            crate::interfaces::tauri::rag_commands::connectors::rag_list_connector_pages,
+            crate::interfaces::tauri::rag_commands::connectors::rag_sync_connector,
+            crate::interfaces::tauri::rag_commands::writing_memory::rag_sync_writing_memory,
+            crate::interfaces::tauri::rag_commands::git_repos::rag_list_git_repos,
+            crate::interfaces::tauri::rag_commands::git_repos::rag_create_git_repo,
+            crate::interfaces::tauri::rag_commands::git_repos::rag_update_git_repo,
+            crate::interfaces::tauri::rag_commands::git_repos::rag_delete_git_repo,
+            crate::interfaces::tauri::rag_commands::git_repos::rag_list_git_repo_files,
+            crate::interfaces::tauri::rag_commands::git_repos::rag_sync_git_repo,
+            crate::interfaces::tauri::rag_commands::import_templates::rag_list_import_templates,
+            crate::interfaces::tauri::rag_commands::import_templates::rag_create_import_template,
+            crate::interfaces::tauri::rag_commands::import_templates::rag_update_import_template,
+            crate::interfaces::tauri::rag_commands::import_templates::rag_delete_import_template,
+            crate::interfaces::tauri::rag_commands::emails::rag_list_email_messages,
+            crate::interfaces::tauri::rag_commands::emails::rag_search_emails,
             crate::interfaces::tauri::rag_commands::analytics_cache_metrics::rag_get_analytics_summary,
             crate::interfaces::tauri::rag_commands::analytics_cache_metrics::rag_get_recent_analytics,
             crate::interfaces::tauri::rag_commands::analytics_cache_metrics::rag_clear_analytics,
             crate::interfaces::tauri::rag_commands::search_chat::rag_chat_with_context,
+            crate::interfaces::tauri::rag_commands::search_chat::rag_find_cached_answer,
+            crate::interfaces::tauri::rag_commands::search_chat::rag_cache_answer,
+            crate::interfaces::tauri::rag_commands::tools::rag_answer_with_tools,
             crate::interfaces::tauri::rag_commands::search_chat::rag_build_verification_prompt,
             crate::interfaces::tauri::rag_commands::search_chat::rag_build_correction_prompt,
             crate::interfaces::tauri::rag_commands::analytics_cache_metrics::rag_health_check,
@@ -88,14 +224,18 @@ macro_rules! tauri_invoke_handler {
             crate::interfaces::tauri::rag_commands::analytics_cache_metrics::rag_get_document_quality_summary,
             crate::interfaces::tauri::rag_commands::analytics_cache_metrics::rag_clear_metrics,
             crate::interfaces::tauri::rag_commands::analytics_cache_metrics::rag_get_retrieval_cache_stats,
+            crate::interfaces::tauri::rag_commands::analytics_cache_metrics::rag_get_embedding_throughput,
             crate::interfaces::tauri::rag_commands::analytics_cache_metrics::rag_clear_retrieval_cache,
             crate::interfaces::tauri::rag_commands::analytics_cache_metrics::rag_invalidate_collection_cache,
+            crate::interfaces::tauri::rag_commands::analytics_cache_metrics::rag_clear_answer_cache,
             crate::interfaces::tauri::rag_commands::analytics_cache_metrics::rag_register_experiment,
             crate::interfaces::tauri::rag_commands::analytics_cache_metrics::rag_list_experiments,
             crate::interfaces::tauri::rag_commands::analytics_cache_metrics::rag_assign_experiment_variant,
             crate::interfaces::tauri::rag_commands::analytics_cache_metrics::rag_deactivate_experiment,
             crate::interfaces::tauri::rag_commands::analytics_cache_metrics::rag_get_system_stats,
             crate::interfaces::tauri::rag_commands::analytics_cache_metrics::rag_analyze_document_quality,
+            crate::interfaces::tauri::rag_commands::analytics_cache_metrics::rag_get_chunk_usage_stats,
+            crate::interfaces::tauri::rag_commands::analytics_cache_metrics::rag_get_pruning_suggestions,
             // Phase 5: configuration management
             crate::interfaces::tauri::rag_commands::config_feedback::rag_get_config,
             crate::interfaces::tauri::rag_commands::config_feedback::rag_update_config,
@@ -105,6 +245,7 @@ macro_rules! tauri_invoke_handler {
             crate::interfaces::tauri::rag_commands::config_feedback::rag_update_ocr_config,
             crate::interfaces::tauri::rag_commands::config_feedback::rag_update_cache_config,
             crate::interfaces::tauri::rag_commands::config_feedback::rag_update_chat_config,
+            crate::interfaces::tauri::rag_commands::config_feedback::rag_update_pii_config,
             crate::interfaces::tauri::rag_commands::config_feedback::rag_reset_config,
             crate::interfaces::tauri::rag_commands::config_feedback::rag_validate_config,
             // Phase 5: user feedback
@@ -112,6 +253,11 @@ macro_rules! tauri_invoke_handler {
             crate::interfaces::tauri::rag_commands::config_feedback::rag_get_feedback_stats,
             crate::interfaces::tauri::rag_commands::config_feedback::rag_get_recent_feedback,
             crate::interfaces::tauri::rag_commands::config_feedback::rag_clear_feedback,
+            crate::interfaces::tauri::rag_commands::config_feedback::rag_get_tuning_report,
+            crate::interfaces::tauri::rag_commands::config_feedback::rag_start_tuning_experiment,
+            crate::interfaces::tauri::rag_commands::config_feedback::rag_finish_tuning_experiment,
+            crate::interfaces::tauri::rag_commands::config_feedback::rag_list_routing_decisions,
+            crate::interfaces::tauri::rag_commands::config_feedback::rag_correct_query_route,
             // Phase 5: chunk management
             crate::interfaces::tauri::rag_commands::chunks::rag_get_chunks_with_quality,
             crate::interfaces::tauri::rag_commands::chunks::rag_delete_chunk,
@@ -151,6 +297,14 @@ macro_rules! tauri_invoke_handler {
             crate::interfaces::tauri::distillation::distill_delete_correction,
             crate::interfaces::tauri::distillation::distill_update_correction_tags,
             crate::interfaces::tauri::distillation::distill_list_tags,
+            crate::interfaces::tauri::distillation::distill_get_review_summary,
+            crate::interfaces::tauri::distillation::distill_list_review_corrections,
+            crate::interfaces::tauri::distillation::distill_list_review_soft_labels,
+            crate::interfaces::tauri::distillation::distill_review_corrections,
+            crate::interfaces::tauri::distillation::distill_review_soft_labels,
+            crate::interfaces::tauri::distillation::distill_edit_correction_target,
+            crate::interfaces::tauri::distillation::distill_edit_soft_label_target,
+            crate::interfaces::tauri::distillation::distill_approve_by_tag,
             crate::interfaces::tauri::distillation::distill_create_dataset,
             crate::interfaces::tauri::distillation::distill_get_dataset,
             crate::interfaces::tauri::distillation::distill_list_datasets,
@@ -158,6 +312,7 @@ macro_rules! tauri_invoke_handler {
             crate::interfaces::tauri::distillation::distill_add_dataset_item,
             crate::interfaces::tauri::distillation::distill_list_dataset_items,
             crate::interfaces::tauri::distillation::distill_import_dataset_jsonl,
+            crate::interfaces::tauri::distillation::distill_build_dataset_from_qa_sessions,
             crate::interfaces::tauri::distillation::distill_register_model,
             crate::interfaces::tauri::distillation::distill_list_base_models,
             crate::interfaces::tauri::distillation::distill_import_base_model,
@@ -170,6 +325,7 @@ macro_rules! tauri_invoke_handler {
             crate::interfaces::tauri::distillation::distill_list_training_runs,
             crate::interfaces::tauri::distillation::distill_log_training_step,
             crate::interfaces::tauri::distillation::distill_list_training_logs,
+            crate::interfaces::tauri::distillation::distill_list_resource_samples,
             crate::interfaces::tauri::distillation::distill_create_model_version,
             crate::interfaces::tauri::distillation::distill_list_model_versions,
             crate::interfaces::tauri::distillation::distill_get_model_version,
@@ -188,6 +344,7 @@ macro_rules! tauri_invoke_handler {
             crate::interfaces::tauri::distillation::distill_get_artifact_layout,
             crate::interfaces::tauri::distillation::distill_start_python_training,
             crate::interfaces::tauri::distillation::distill_cancel_python_training,
+            crate::interfaces::tauri::distillation::distill_resume_training_run,
             crate::interfaces::tauri::distillation::distill_evaluate_version,
             // Soft labels
             crate::interfaces::tauri::distillation::distill_generate_soft_labels,
@@ -2,11 +2,19 @@ use std::sync::Arc;
 
 use tauri::State;
 
+use crate::application::use_cases::language_detection::{self, DetectedLanguage};
+use crate::application::use_cases::translate::TranslateOptions;
 use crate::domain::error::Result;
 use crate::domain::llm_config::{ChatMessage, LLMConfig};
 use crate::domain::prompt::Prompt;
+use crate::domain::settings::{
+    LanguageSettings, LlmFeature, ModelOverride, ModelSettings, ShortcutSettings,
+};
+use crate::domain::telemetry::UsageSubsystem;
+use crate::domain::typegen::{TypeGenMode, TypeGenOptions};
 use crate::interfaces::http::{add_log, LogEntry};
 
+use super::settings_commands::persist_settings;
 use super::state::AppState;
 
 
@@ -17,23 +25,81 @@ pub async fn translate_prompt(
     content: String,
     source: String,
     target: String,
+    auto_correct_glossary: Option<bool>,
+    use_translation_memory: Option<bool>,
 ) -> Result<Prompt> {
-    state
+    let options = TranslateOptions {
+        auto_correct_glossary: auto_correct_glossary.unwrap_or(false),
+        use_translation_memory: use_translation_memory.unwrap_or(true),
+    };
+    let config = state.model_resolver.resolve(LlmFeature::Translate, &config);
+    let translate = state
         .translate_use_case
-        .execute(&config, content, source, target)
+        .execute_with_options(&config, content, source, target, &options);
+    state
+        .telemetry
+        .track(UsageSubsystem::Llm, "translate", translate)
         .await
 }
 
+/// Detect the language of `text` so the UI can show (and override) it before translating.
+#[tauri::command]
+pub async fn detect_language(text: String) -> Result<Option<DetectedLanguage>> {
+    Ok(language_detection::detect_language(&text))
+}
+
 #[tauri::command]
 pub async fn enhance_prompt(
     state: State<'_, Arc<AppState>>,
     config: LLMConfig,
     content: String,
     system_prompt: Option<String>,
+    output_language: Option<String>,
+    preset_id: Option<String>,
 ) -> Result<Prompt> {
+    let config = state.model_resolver.resolve(LlmFeature::Enhance, &config);
+    let language = state.output_language(output_language.as_deref());
+    let preset = state.enhance_preset(preset_id.as_deref(), system_prompt.is_some())?;
+    let enhance = state.enhance_use_case.execute_with_preset(
+        &config,
+        content,
+        system_prompt,
+        preset.as_ref(),
+        language.as_ref(),
+    );
     state
-        .enhance_use_case
-        .execute(&config, content, system_prompt)
+        .telemetry
+        .track(UsageSubsystem::Llm, "enhance", enhance)
+        .await
+}
+
+/// Generate type definitions for `language` from `json` plus any extra
+/// `samples` of the same payload, as the `/api/typegen` endpoint does.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn generate_types(
+    state: State<'_, Arc<AppState>>,
+    config: LLMConfig,
+    json: String,
+    samples: Option<Vec<String>>,
+    language: String,
+    root_name: String,
+    mode: Option<TypeGenMode>,
+    options: Option<TypeGenOptions>,
+) -> Result<String> {
+    let config = state.model_resolver.resolve(LlmFeature::Typegen, &config);
+    let typegen = state.typegen_use_case.execute(
+        &config,
+        json,
+        samples.unwrap_or_default(),
+        language,
+        root_name,
+        mode.unwrap_or_default(),
+        options.unwrap_or_default(),
+    );
+    state
+        .telemetry
+        .track(UsageSubsystem::Llm, "typegen", typegen)
         .await
 }
 
@@ -96,9 +162,13 @@ pub async fn save_api_key(
     state.config_service.save_api_key(&provider, &key)
 }
 
+/// Stored key for `provider`, or `None` when the keychain has no entry.
 #[tauri::command]
-pub async fn get_api_key(state: State<'_, Arc<AppState>>, provider: String) -> Result<String> {
-    state.config_service.get_api_key(&provider)
+pub async fn get_api_key(
+    state: State<'_, Arc<AppState>>,
+    provider: String,
+) -> Result<Option<String>> {
+    state.config_service.find_api_key(&provider)
 }
 
 #[tauri::command]
@@ -125,8 +195,41 @@ pub async fn get_llm_models(
     state.llm_client.list_models(&config).await
 }
 
+/// Keep provider keys in the OS keychain: a key sent by the UI is stored there,
+/// a missing one is filled in from it.
+fn sync_api_key(state: &AppState, config: LLMConfig) -> LLMConfig {
+    let provider = config.provider.to_string();
+    let sent_key = config
+        .api_key
+        .as_deref()
+        .map(str::trim)
+        .filter(|key| !key.is_empty());
+
+    let result = match sent_key {
+        Some(key) => match state.config_service.find_api_key(&provider) {
+            Ok(Some(stored)) if stored == key => Ok(config.clone()),
+            _ => state
+                .config_service
+                .save_api_key(&provider, key)
+                .map(|_| config.clone()),
+        },
+        None => state.config_service.with_api_key(&config),
+    };
+
+    result.unwrap_or_else(|err| {
+        add_log(
+            &state.logs,
+            "WARN",
+            "Config",
+            &format!("Keychain unavailable for {}: {}", provider, err),
+        );
+        config
+    })
+}
+
 #[tauri::command]
 pub async fn sync_config(state: State<'_, Arc<AppState>>, config: LLMConfig) -> Result<()> {
+    let config = sync_api_key(&state, config);
     let mut last_config = state.last_config.lock().unwrap();
     *last_config = config.clone();
     add_log(
@@ -138,6 +241,39 @@ pub async fn sync_config(state: State<'_, Arc<AppState>>, config: LLMConfig) ->
             last_config.provider, last_config.base_url, last_config.model
         ),
     );
+
+    let mut models = state.settings.get::<ModelSettings>();
+    models.llm = Some(ModelSettings::without_api_key(&config));
+    persist_settings(&state, &models);
+    Ok(())
+}
+
+/// Run `feature` on its own model, or on the default one again when `model`
+/// is `None`. The provider's API key is read from the keychain.
+#[tauri::command]
+pub async fn sync_feature_model(
+    state: State<'_, Arc<AppState>>,
+    feature: LlmFeature,
+    model: Option<ModelOverride>,
+) -> Result<()> {
+    let message = match &model {
+        Some(model) => format!(
+            "Using provider={} model={} for {}",
+            model.provider,
+            model.model,
+            feature.label()
+        ),
+        None => format!("Using the default model for {}", feature.label()),
+    };
+
+    let mut models = state.settings.get::<ModelSettings>();
+    match model {
+        Some(model) => models.features.insert(feature, model),
+        None => models.features.remove(&feature),
+    };
+    models.validate()?;
+    state.settings.set(&models)?;
+    add_log(&state.logs, "INFO", "Config", &message);
     Ok(())
 }
 
@@ -146,6 +282,7 @@ pub async fn sync_embedding_config(
     state: State<'_, Arc<AppState>>,
     config: LLMConfig,
 ) -> Result<()> {
+    let config = sync_api_key(&state, config);
     state.embedding_service.update_config(config.clone());
     add_log(
         &state.logs,
@@ -156,6 +293,10 @@ pub async fn sync_embedding_config(
             config.provider, config.model
         ),
     );
+
+    let mut models = state.settings.get::<ModelSettings>();
+    models.embedding = Some(ModelSettings::without_api_key(&config));
+    persist_settings(&state, &models);
     Ok(())
 }
 
@@ -165,21 +306,34 @@ pub async fn sync_languages(
     source: String,
     target: String,
 ) -> Result<()> {
-    *state.preferred_source.lock().unwrap() = source;
-    *state.preferred_target.lock().unwrap() = target;
+    *state.preferred_source.lock().unwrap() = source.clone();
+    *state.preferred_target.lock().unwrap() = target.clone();
+    persist_settings(&state, &LanguageSettings { source, target });
     Ok(())
 }
 
 #[tauri::command]
 pub fn sync_shortcuts(
     app: tauri::AppHandle,
+    state: State<'_, Arc<AppState>>,
     enabled: bool,
     translate: String,
     enhance: String,
     popup: String,
     terminal: String,
 ) -> std::result::Result<(), String> {
-    crate::register_shortcuts(&app, enabled, &translate, &enhance, &popup, &terminal)
+    crate::register_shortcuts(&app, enabled, &translate, &enhance, &popup, &terminal)?;
+    persist_settings(
+        &state,
+        &ShortcutSettings {
+            enabled,
+            translate,
+            enhance,
+            popup,
+            terminal,
+        },
+    );
+    Ok(())
 }
 
 #[tauri::command]
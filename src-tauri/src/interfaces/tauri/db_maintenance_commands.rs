@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::domain::db_maintenance::{DbMaintenanceReport, DbMaintenanceRequest};
+use crate::domain::error::{AppError, Result};
+use crate::infrastructure::db::maintenance::maintain_database;
+use crate::infrastructure::storage::{app_databases, resolve_app_data_dir};
+use crate::interfaces::http::add_log;
+
+use super::state::AppState;
+
+/// Report database sizes and row counts, running the requested VACUUM,
+/// ANALYZE, checkpoint and integrity passes. Databases are handled one at a
+/// time so a long VACUUM doesn't hold several of the app's pools at once.
+#[tauri::command]
+pub async fn db_maintenance(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<AppState>>,
+    request: Option<DbMaintenanceRequest>,
+) -> Result<DbMaintenanceReport> {
+    let request = request.unwrap_or_default();
+    let app_data_dir = resolve_app_data_dir(&app)?;
+    let databases = app_databases(&app_data_dir);
+
+    if let Some(unknown) = request
+        .databases
+        .iter()
+        .find(|key| !databases.iter().any(|db| db.key == key.as_str()))
+    {
+        return Err(AppError::ValidationError(format!(
+            "Unknown database '{}'; expected one of app, qa, rag, training",
+            unknown
+        )));
+    }
+
+    let mut reports = Vec::new();
+    for db in &databases {
+        if !request.databases.is_empty() && !request.databases.iter().any(|key| key == db.key) {
+            continue;
+        }
+        let report = maintain_database(db.key, db.label, &db.path, &request).await;
+        if let Some(err) = &report.error {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "Database",
+                &format!("Maintenance of {} failed: {}", db.label, err),
+            );
+        } else if !report.operations.is_empty() {
+            add_log(
+                &state.logs,
+                "INFO",
+                "Database",
+                &format!(
+                    "{}: {} done, {} bytes reclaimed",
+                    db.label,
+                    report.operations.join(", "),
+                    report.reclaimed_bytes()
+                ),
+            );
+        }
+        if let Some(problems) = report.integrity_problems.as_ref().filter(|p| !p.is_empty()) {
+            add_log(
+                &state.logs,
+                "WARN",
+                "Database",
+                &format!(
+                    "{} failed its integrity check: {}",
+                    db.label,
+                    problems.join("; ")
+                ),
+            );
+        }
+        reports.push(report);
+    }
+
+    Ok(DbMaintenanceReport::new(reports))
+}
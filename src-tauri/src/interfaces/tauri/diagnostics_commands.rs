@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::domain::diagnostics::{DiagnosticStatus, DiagnosticsReport};
+use crate::domain::error::Result;
+use crate::domain::settings::HttpServerSettings;
+use crate::infrastructure::desktop_input;
+use crate::infrastructure::diagnostics::{self as probes, timed};
+use crate::infrastructure::storage::{app_databases, resolve_app_data_dir};
+use crate::interfaces::http::add_log;
+use crate::interfaces::mock_server::build_status as build_mock_status;
+
+use super::distillation::python_orchestrator::{resolve_train_script_path, PYTHON_PROGRAM};
+use super::state::AppState;
+
+/// Check every external dependency, database, disk and port the app uses.
+/// Individual failures are reported in the returned checks, not as errors.
+#[tauri::command]
+pub async fn system_diagnostics(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<AppState>>,
+) -> Result<DiagnosticsReport> {
+    add_log(
+        &state.logs,
+        "INFO",
+        "Diagnostics",
+        "Running system diagnostics",
+    );
+
+    let app_data_dir = resolve_app_data_dir(&app)?;
+    let train_script = resolve_train_script_path(&app)
+        .ok()
+        .filter(|path| path.exists());
+    let mock_status = build_mock_status(&state.mock_server);
+    let api_port = state
+        .http_server
+        .address()
+        .map(|address| address.port())
+        .unwrap_or_else(|| state.settings.get::<HttpServerSettings>().port);
+    let api_token = state.http_server.token();
+    let health_url = format!("http://127.0.0.1:{}/api/logs", api_port);
+
+    let [app_db, qa_db, rag_db, training_db] =
+        app_databases(&app_data_dir).map(|db| (format!("db.{}", db.key), db.label, db.path));
+
+    let (node, playwright, tesseract, pdftoppm, python) = tokio::join!(
+        timed(probes::check_node()),
+        timed(probes::check_playwright()),
+        timed(probes::check_tesseract()),
+        timed(probes::check_pdftoppm()),
+        timed(probes::check_python(
+            PYTHON_PROGRAM,
+            train_script.as_deref()
+        )),
+    );
+    let (app_db, qa_db, rag_db, training_db) = tokio::join!(
+        timed(probes::check_database(&app_db.0, app_db.1, &app_db.2)),
+        timed(probes::check_database(&qa_db.0, qa_db.1, &qa_db.2)),
+        timed(probes::check_database(&rag_db.0, rag_db.1, &rag_db.2)),
+        timed(probes::check_database(
+            &training_db.0,
+            training_db.1,
+            &training_db.2
+        )),
+    );
+    let api_port_check = timed(probes::check_api_port(
+        api_port,
+        &health_url,
+        api_token.as_deref(),
+    ))
+    .await;
+    let disk = probes::check_disk_space(&app_data_dir);
+    let mock_port = probes::check_mock_port(mock_status.port, mock_status.running);
+    let desktop_input = probes::check_desktop_input(desktop_input::capabilities());
+
+    let report = DiagnosticsReport::new(
+        app.package_info().version.to_string(),
+        vec![
+            node,
+            playwright,
+            tesseract,
+            pdftoppm,
+            python,
+            app_db,
+            qa_db,
+            rag_db,
+            training_db,
+            disk,
+            api_port_check,
+            mock_port,
+            desktop_input,
+        ],
+    );
+
+    let failing: Vec<&str> = report
+        .checks
+        .iter()
+        .filter(|check| check.status != DiagnosticStatus::Ok)
+        .map(|check| check.id.as_str())
+        .collect();
+    add_log(
+        &state.logs,
+        if failing.is_empty() { "INFO" } else { "WARN" },
+        "Diagnostics",
+        &if failing.is_empty() {
+            "All diagnostic checks passed".to_string()
+        } else {
+            format!(
+                "Diagnostic checks needing attention: {}",
+                failing.join(", ")
+            )
+        },
+    );
+    Ok(report)
+}
@@ -6,7 +6,7 @@
 use crate::domain::error::Result;
 use crate::infrastructure::artifact_store::{
     backup_training_db, cleanup_old_backups, list_backups, restore_from_backup, BackupConfig,
-    BackupInfo, TrainingArtifactLayout,
+    BackupInfo,
 };
 use crate::infrastructure::db::training::repositories::{
     EvaluationMetric, EvaluationMetricInput, EvaluationMetricsRepository, RunArtifact,
@@ -260,11 +260,9 @@ pub struct ArtifactLayoutInfo {
 
 #[tauri::command]
 pub async fn distill_get_artifact_layout(
-    app: AppHandle,
-    _state: State<'_, Arc<AppState>>,
+    state: State<'_, Arc<AppState>>,
 ) -> Result<ArtifactLayoutInfo> {
-    let app_data_dir = resolve_app_data_dir(&app)?;
-    let layout = TrainingArtifactLayout::new(&app_data_dir);
+    let layout = state.storage.training_layout();
 
     Ok(ArtifactLayoutInfo {
         root: layout.root().to_string_lossy().to_string(),
@@ -1,5 +1,8 @@
 //! Common utilities and types for distillation commands
+use crate::application::use_cases::activity::ActivityFeed;
+use crate::domain::activity::{ActivityInput, ActivitySource};
 use crate::domain::error::Result;
+use crate::infrastructure::db::training::repositories::TrainingLogInput;
 use crate::infrastructure::storage::resolve_app_data_dir;
 use std::path::PathBuf;
 use tauri::AppHandle;
@@ -9,3 +12,16 @@ pub fn training_db_path(app: &AppHandle) -> Result<PathBuf> {
     let app_data_dir = resolve_app_data_dir(app)?;
     Ok(app_data_dir.join("training.db"))
 }
+
+/// Add a training step to the activity feed
+pub(crate) fn record_training_step(activity: &ActivityFeed, log: &TrainingLogInput) {
+    let mut message = format!("Epoch {} step {}", log.epoch, log.step);
+    if let Some(loss) = log.loss {
+        message.push_str(&format!(", loss {:.4}", loss));
+    }
+    activity.record(
+        ActivityInput::new(ActivitySource::Training, "step", message)
+            .subject(log.run_id.clone())
+            .payload(serde_json::json!(log)),
+    );
+}
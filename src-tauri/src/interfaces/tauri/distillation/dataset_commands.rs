@@ -1,7 +1,8 @@
 //! Dataset Commands (Flow B - Prepare Training Dataset)
-use crate::domain::error::Result;
+use crate::domain::error::{AppError, Result};
 use crate::infrastructure::db::training::repositories::{
-    Dataset, DatasetInput, DatasetItem, DatasetItemInput, DatasetRepository, TrainingDb,
+    CorrectionRepository, Dataset, DatasetInput, DatasetItem, DatasetItemInput, DatasetRepository,
+    TrainingDb,
 };
 use crate::interfaces::http::add_log;
 use crate::interfaces::tauri::AppState;
@@ -75,14 +76,23 @@ pub async fn distill_delete_dataset(
         &state.logs,
         "INFO",
         "Distillation",
-        &format!("Deleting dataset: {}", dataset_id),
+        &format!("Moving dataset to trash: {}", dataset_id),
     );
 
     let db_path = training_db_path(&app)?;
     let db = TrainingDb::connect(&db_path).await?;
     let repo = DatasetRepository::new(&db);
 
-    repo.delete(&dataset_id).await
+    let trashed = repo
+        .trash(&dataset_id, chrono::Utc::now().timestamp_millis())
+        .await?;
+    if trashed == 0 {
+        return Err(AppError::NotFound(format!(
+            "Dataset not found: {}",
+            dataset_id
+        )));
+    }
+    Ok(trashed)
 }
 
 #[tauri::command]
@@ -95,6 +105,11 @@ pub async fn distill_add_dataset_item(
     let db = TrainingDb::connect(&db_path).await?;
     let repo = DatasetRepository::new(&db);
 
+    if let Some(correction_id) = &item.source_correction_id {
+        CorrectionRepository::new(&db)
+            .ensure_approved(std::slice::from_ref(correction_id))
+            .await?;
+    }
     repo.insert_item(&item).await
 }
 
@@ -205,3 +220,116 @@ pub async fn distill_import_dataset_jsonl(
 
     repo.get(&dataset_id).await
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaSessionDatasetInput {
+    pub dataset_id: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub session_ids: Vec<String>,
+    /// Language the test case prompts ask for; English when unset
+    pub output_language: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QaSessionDatasetResult {
+    pub dataset: Dataset,
+    pub item_count: usize,
+    pub test_case_count: usize,
+    /// Selected sessions without a checkpoint that has events and test cases
+    pub skipped_session_ids: Vec<String>,
+}
+
+/// Build a synthetic dataset from recorded QA sessions: one item per
+/// checkpoint, prompting for test cases and expecting the checkpoint's
+/// generated cases, for distilling a QA test generation model
+#[tauri::command]
+pub async fn distill_build_dataset_from_qa_sessions(
+    app: AppHandle,
+    state: State<'_, Arc<AppState>>,
+    input: QaSessionDatasetInput,
+) -> Result<QaSessionDatasetResult> {
+    if input.name.trim().is_empty() {
+        return Err(AppError::ValidationError(
+            "Dataset name cannot be empty".to_string(),
+        ));
+    }
+    if input.session_ids.is_empty() {
+        return Err(AppError::ValidationError(
+            "Select at least one QA session".to_string(),
+        ));
+    }
+    add_log(
+        &state.logs,
+        "INFO",
+        "Distillation",
+        &format!(
+            "Building dataset '{}' from {} QA sessions",
+            input.name,
+            input.session_ids.len()
+        ),
+    );
+
+    let language = input.output_language.as_deref().unwrap_or_default();
+    let mut examples = Vec::new();
+    let mut skipped_session_ids = Vec::new();
+    for session_id in &input.session_ids {
+        let session_examples = state
+            .qa_ai_use_case
+            .build_training_examples(session_id, language)
+            .await?;
+        if session_examples.is_empty() {
+            skipped_session_ids.push(session_id.clone());
+        }
+        examples.extend(session_examples);
+    }
+    if examples.is_empty() {
+        return Err(AppError::ValidationError(
+            "None of the selected QA sessions has a checkpoint with test cases".to_string(),
+        ));
+    }
+
+    let dataset_id = input
+        .dataset_id
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+    let db_path = training_db_path(&app)?;
+    let db = TrainingDb::connect(&db_path).await?;
+    let repo = DatasetRepository::new(&db);
+
+    repo.insert(&DatasetInput {
+        dataset_id: dataset_id.clone(),
+        name: input.name.trim().to_string(),
+        dataset_type: "synthetic".to_string(),
+        description: input.description.clone(),
+    })
+    .await?;
+
+    let mut test_case_count = 0;
+    for example in &examples {
+        test_case_count += example.test_case_count;
+        let metadata = serde_json::json!({
+            "source": "qa_session",
+            "sessionId": example.session_id,
+            "checkpointId": example.checkpoint_id,
+            "testCaseCount": example.test_case_count,
+        });
+        repo.insert_item(&DatasetItemInput {
+            item_id: Uuid::new_v4().to_string(),
+            dataset_id: dataset_id.clone(),
+            prompt: example.prompt.clone(),
+            expected_output: Some(example.expected_output.clone()),
+            metadata_json: Some(metadata.to_string()),
+            source_correction_id: None,
+        })
+        .await?;
+    }
+
+    Ok(QaSessionDatasetResult {
+        dataset: repo.get(&dataset_id).await?,
+        item_count: examples.len(),
+        test_case_count,
+        skipped_session_ids,
+    })
+}
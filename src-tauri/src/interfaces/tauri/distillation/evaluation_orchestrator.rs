@@ -1,10 +1,8 @@
 //! Evaluation Orchestrator (Rust -> Python evaluator)
 use crate::domain::error::Result;
-use crate::infrastructure::artifact_store::TrainingArtifactLayout;
 use crate::infrastructure::db::training::repositories::{
     EvaluationMetricInput, EvaluationMetricsRepository, TrainingDb,
 };
-use crate::infrastructure::storage::resolve_app_data_dir;
 use crate::interfaces::http::add_log;
 use crate::interfaces::tauri::AppState;
 use serde::{Deserialize, Serialize};
@@ -61,8 +59,7 @@ pub async fn distill_evaluate_version(
         &format!("Starting evaluation {} for version {}", eval_id, version_id),
     );
 
-    let app_data_dir = resolve_app_data_dir(&app)?;
-    let layout = TrainingArtifactLayout::new(&app_data_dir);
+    let layout = state.storage.training_layout();
     layout.ensure()?;
     let eval_dir = layout.evaluation_dir(&eval_id);
     std::fs::create_dir_all(&eval_dir).map_err(|e| {
@@ -106,7 +103,7 @@ pub async fn distill_evaluate_version(
     let _ = std::fs::OpenOptions::new().create(true).append(true).open(&stderr_log_path);
 
     // Spawn python evaluator
-    let mut cmd = TokioCommand::new("python");
+    let mut cmd = TokioCommand::new(super::python_orchestrator::PYTHON_PROGRAM);
     let script_path_str = script_path.to_string_lossy().to_string();
     let config_path_str = config_path.to_string_lossy().to_string();
 
@@ -118,7 +115,7 @@ pub async fn distill_evaluate_version(
         .stderr(Stdio::piped());
 
     let mut child = cmd.spawn().map_err(|e| {
-        crate::domain::error::AppError::Internal(format!("Failed to spawn evaluator: {e}"))
+        crate::domain::error::AppError::spawn(super::python_orchestrator::PYTHON_PROGRAM, e)
     })?;
 
     let stdout = child.stdout.take().ok_or_else(|| {
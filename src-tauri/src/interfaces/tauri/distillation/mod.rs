@@ -2,6 +2,7 @@
 //!
 //! This module contains all Tauri commands for model distillation:
 //! - Correction Commands (Flow A - Collect Corrections)
+//! - Review Queue Commands (approve, reject and edit before training)
 //! - Dataset Commands (Flow B - Prepare Training Dataset)
 //! - Model Commands
 //! - Training Run Commands (Flow C - Run Training)
@@ -20,6 +21,7 @@ pub mod model_version_commands;
 pub mod artifact_commands;
 pub mod evaluation_orchestrator;
 pub mod python_orchestrator;
+pub mod review_commands;
 pub mod soft_label_commands;
 
 // Re-export all commands for easier imports
@@ -31,4 +33,5 @@ pub use model_version_commands::*;
 pub use artifact_commands::*;
 pub use evaluation_orchestrator::*;
 pub use python_orchestrator::*;
+pub use review_commands::*;
 pub use soft_label_commands::*;
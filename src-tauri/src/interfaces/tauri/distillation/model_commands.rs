@@ -1,10 +1,8 @@
 //! Model Commands - Model registration and base model management
 use crate::domain::error::Result;
-use crate::infrastructure::artifact_store::TrainingArtifactLayout;
 use crate::infrastructure::db::training::repositories::{
     Model, ModelInput, ModelRepository, TrainingDb,
 };
-use crate::infrastructure::storage::resolve_app_data_dir;
 use crate::interfaces::http::add_log;
 use crate::interfaces::tauri::AppState;
 use serde::{Deserialize, Serialize};
@@ -100,8 +98,7 @@ pub async fn distill_list_base_models(
 
     let mut entries: Vec<BaseModelEntry> = Vec::new();
 
-    let app_data_dir = resolve_app_data_dir(&app)?;
-    let layout = TrainingArtifactLayout::new(&app_data_dir);
+    let layout = state.storage.training_layout();
     layout.ensure()?;
 
     // Try to resolve the resource directory
@@ -218,8 +215,7 @@ pub async fn distill_import_base_model(
         ))
     })?;
 
-    let app_data_dir = resolve_app_data_dir(&app)?;
-    let layout = TrainingArtifactLayout::new(&app_data_dir);
+    let layout = state.storage.training_layout();
     layout.ensure()?;
 
     let display_name = input.display_name.clone().unwrap_or_else(|| {
@@ -309,8 +305,7 @@ pub async fn distill_download_default_model(
         "Downloading default TinyLlama model",
     );
 
-    let app_data_dir = resolve_app_data_dir(&app)?;
-    let layout = TrainingArtifactLayout::new(&app_data_dir);
+    let layout = state.storage.training_layout();
     layout.ensure()?;
 
     let model_name = "tinyllama";
@@ -1,17 +1,20 @@
 //! Python Orchestrator (Rust -> Python runner)
+use crate::application::use_cases::activity::ActivityFeed;
+use crate::domain::activity::{ActivityInput, ActivitySource};
 use crate::domain::error::Result;
-use crate::infrastructure::artifact_store::TrainingArtifactLayout;
 use crate::infrastructure::db::training::repositories::{
-    ModelVersionInput, ModelVersionRepository, RunArtifact, RunArtifactInput, RunArtifactsRepository,
-    TrainingDb, TrainingLogInput, TrainingLogRepository, TrainingRunRepository, TrainingStatus,
+    GpuResourceSample, ModelVersionInput, ModelVersionRepository, ResourceSampleInput, ResourceSampleRepository,
+    RunArtifact, RunArtifactInput, RunArtifactsRepository, TrainingDb, TrainingLogInput, TrainingLogRepository,
+    TrainingRunRepository, TrainingStatus,
 };
-use crate::infrastructure::storage::resolve_app_data_dir;
+use crate::infrastructure::resource_monitor::ResourceSampler;
 use crate::interfaces::http::add_log;
 use crate::interfaces::tauri::{AppState, DistillTrainerHandle};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tauri::{path::BaseDirectory, AppHandle, Emitter, Manager, State};
 use tokio::fs::OpenOptions as TokioOpenOptions;
@@ -21,7 +24,13 @@ use tokio::sync::Mutex as AsyncMutex;
 use tokio::time::{sleep, Duration, Instant};
 use uuid::Uuid;
 
-use super::common::training_db_path;
+use super::common::{record_training_step, training_db_path};
+
+/// Interpreter used to launch the trainer
+pub(crate) const PYTHON_PROGRAM: &str = "python";
+
+/// How often host resources are sampled while a trainer runs
+const RESOURCE_SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -106,12 +115,11 @@ pub async fn distill_start_python_training(
         ));
     }
 
-    let mut launch_guard = DistillTrainerLaunchGuard::reserve(state.inner().clone(), &run_id)?;
+    let launch_guard = DistillTrainerLaunchGuard::reserve(state.inner().clone(), &run_id)?;
 
     add_log(&state.logs, "INFO", "Distillation", &format!("Starting python trainer for run {}", run_id));
 
-    let app_data_dir = resolve_app_data_dir(&app)?;
-    let layout = TrainingArtifactLayout::new(&app_data_dir);
+    let layout = state.storage.training_layout();
     layout.ensure()?;
 
     let run_dir = if config.run_dir.trim().is_empty() {
@@ -125,6 +133,8 @@ pub async fn distill_start_python_training(
 
     let cancel_flag_path = run_dir.join("cancel.flag");
     let _ = std::fs::remove_file(&cancel_flag_path);
+    // A fresh start must not be mistaken for progress of an earlier attempt
+    let _ = std::fs::remove_dir_all(run_dir.join("checkpoints"));
 
     let script_path = resolve_train_script_path(&app)?;
 
@@ -153,31 +163,132 @@ pub async fn distill_start_python_training(
         return Err(err);
     }
 
+    launch_trainer(&app, state.inner(), launch_guard, &db, &run_id, &run_dir, &script_path, &config_path, None).await
+}
+
+/// Relaunch the trainer of a crashed, failed or cancelled run from its newest
+/// checkpoint. A run still marked running without a live trainer (the app
+/// was closed mid-run) counts as crashed.
+#[tauri::command]
+pub async fn distill_resume_training_run(
+    app: AppHandle,
+    state: State<'_, Arc<AppState>>,
+    run_id: String,
+) -> Result<String> {
+    let run_id = run_id.trim().to_string();
+    if run_id.is_empty() {
+        return Err(crate::domain::error::AppError::ValidationError("run_id is required".to_string()));
+    }
+
+    let launch_guard = DistillTrainerLaunchGuard::reserve(state.inner().clone(), &run_id)?;
+
+    let db_path = training_db_path(&app)?;
+    let db = TrainingDb::connect(&db_path).await?;
+    let run_repo = TrainingRunRepository::new(&db);
+    let run = run_repo.get(&run_id).await?;
+    match run.status.as_str() {
+        "crashed" | "failed" | "cancelled" | "running" => {}
+        "queued" => {
+            return Err(crate::domain::error::AppError::ValidationError(format!(
+                "Run {} has not started yet; start it instead of resuming", run_id
+            )));
+        }
+        other => {
+            return Err(crate::domain::error::AppError::ValidationError(format!(
+                "Run {} is {} and cannot be resumed", run_id, other
+            )));
+        }
+    }
+
+    let artifacts_repo = RunArtifactsRepository::new(&db);
+    let run_dir = artifacts_repo
+        .list_by_kind(&run_id, "config")
+        .await?
+        .iter()
+        .map(|a| PathBuf::from(&a.path))
+        .find(|p| p.file_name().is_some_and(|n| n == "trainer_config.json") && p.exists())
+        .and_then(|p| p.parent().map(Path::to_path_buf))
+        .unwrap_or_else(|| state.storage.training_layout().run_dir(&run_id));
+    let config_path = run_dir.join("trainer_config.json");
+    if !config_path.exists() {
+        return Err(crate::domain::error::AppError::NotFound(format!(
+            "Trainer config of run {} not found at {}", run_id, config_path.display()
+        )));
+    }
+
+    // Old checkpoints are pruned by the trainer, so only some records still exist on disk
+    let checkpoint = artifacts_repo
+        .list_by_kind(&run_id, "checkpoint")
+        .await?
+        .into_iter()
+        .map(|a| PathBuf::from(a.path))
+        .filter(|p| p.join("checkpoint.json").is_file())
+        .max()
+        .ok_or_else(|| {
+            crate::domain::error::AppError::ValidationError(format!(
+                "Run {} has no checkpoint to resume from", run_id
+            ))
+        })?;
+
+    let _ = std::fs::remove_file(run_dir.join("cancel.flag"));
+    let script_path = resolve_train_script_path(&app)?;
+    run_repo.mark_resumed(&run_id).await?;
+
+    add_log(&state.logs, "INFO", "Distillation", &format!(
+        "Resuming run {} (was {}) from checkpoint {}", run_id, run.status, checkpoint.display()
+    ));
+
+    launch_trainer(&app, state.inner(), launch_guard, &db, &run_id, &run_dir, &script_path, &config_path, Some(&checkpoint)).await
+}
+
+/// Spawn the trainer for a run whose config is written, and track it until it exits
+#[allow(clippy::too_many_arguments)]
+async fn launch_trainer(
+    app: &AppHandle,
+    state: &Arc<AppState>,
+    mut launch_guard: DistillTrainerLaunchGuard,
+    db: &TrainingDb,
+    run_id: &str,
+    run_dir: &Path,
+    script_path: &Path,
+    config_path: &Path,
+    resume_from: Option<&Path>,
+) -> Result<String> {
+    let run_id = run_id.to_string();
+    let run_dir = run_dir.to_path_buf();
+    let db_path = training_db_path(app)?;
+    let run_repo = TrainingRunRepository::new(db);
+
     let stdout_log_path = run_dir.join("trainer_stdout.log");
     let stderr_log_path = run_dir.join("trainer_stderr.log");
     let metrics_log_path = run_dir.join("trainer_metrics.jsonl");
     let _ = std::fs::OpenOptions::new().create(true).append(true).open(&stdout_log_path);
     let _ = std::fs::OpenOptions::new().create(true).append(true).open(&stderr_log_path);
 
-    // Record baseline artifacts
-    record_initial_artifacts(&db, &run_id, &config_path, &stdout_log_path, &stderr_log_path).await;
+    // Record baseline artifacts; a resumed run already has them
+    if resume_from.is_none() {
+        record_initial_artifacts(db, &run_id, config_path, &stdout_log_path, &stderr_log_path).await;
+    }
 
     // Spawn Python process
-    let mut cmd = TokioCommand::new("python");
+    let mut cmd = TokioCommand::new(PYTHON_PROGRAM);
     let script_path_str = script_path.to_string_lossy().to_string();
     let config_path_str = config_path.to_string_lossy().to_string();
 
     cmd.arg(&script_path_str)
         .arg("--config")
-        .arg(&config_path_str)
-        .stdin(Stdio::null())
+        .arg(&config_path_str);
+    if let Some(checkpoint) = resume_from {
+        cmd.arg("--resume").arg(checkpoint);
+    }
+    cmd.stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
 
     let mut child = match cmd.spawn() {
         Ok(child) => child,
         Err(e) => {
-            let err = crate::domain::error::AppError::Internal(format!("Failed to spawn python: {e}"));
+            let err = crate::domain::error::AppError::spawn(PYTHON_PROGRAM, e);
             let _ = run_repo.set_status(&run_id, TrainingStatus::Failed, Some(chrono::Utc::now().to_rfc3339()), Some(err.to_string())).await;
             return Err(err);
         }
@@ -190,6 +301,7 @@ pub async fn distill_start_python_training(
         crate::domain::error::AppError::Internal("Trainer stderr unavailable".to_string())
     })?;
 
+    let pid = child.id();
     let child = Arc::new(AsyncMutex::new(child));
 
     if let Err(e) = launch_guard.insert_handle(DistillTrainerHandle {
@@ -210,18 +322,26 @@ pub async fn distill_start_python_training(
     }
 
     let last_error: Arc<std::sync::Mutex<Option<String>>> = Arc::new(std::sync::Mutex::new(None));
+    // Set when the trainer reports its own failure; an exit without a report is a crash
+    let reported_error = Arc::new(AtomicBool::new(false));
+    let trainer_done = Arc::new(AtomicBool::new(false));
+    let resource_warning: Arc<std::sync::Mutex<Option<String>>> = Arc::new(std::sync::Mutex::new(None));
 
     // Spawn handlers
     spawn_train_stdout_handler(
-        app.clone(), state.logs.clone(), db_path.clone(), run_id.clone(),
-        run_dir.clone(), stdout_log_path, metrics_log_path, stdout, last_error.clone()
+        app.clone(), state.logs.clone(), state.activity_feed.clone(), db_path.clone(), run_id.clone(),
+        run_dir.clone(), stdout_log_path, metrics_log_path, stdout, last_error.clone(), reported_error.clone()
     );
     spawn_train_stderr_handler(
         app.clone(), state.logs.clone(), stderr_log_path, stderr, last_error.clone()
     );
+    spawn_resource_monitor(
+        app.clone(), state.logs.clone(), db_path.clone(), run_id.clone(),
+        pid, trainer_done.clone(), resource_warning.clone()
+    );
     spawn_train_exit_monitor(
-        app.clone(), state.inner().clone(), db_path, run_id.clone(),
-        run_dir.clone(), child.clone(), last_error
+        app.clone(), state.clone(), db_path, run_id.clone(),
+        run_dir.clone(), child.clone(), last_error, reported_error, trainer_done, resource_warning
     );
 
     let token = format!("{}:{}", run_id, Uuid::new_v4());
@@ -282,7 +402,7 @@ pub async fn distill_cancel_python_training(
     Ok(())
 }
 
-fn resolve_train_script_path(app: &AppHandle) -> Result<PathBuf> {
+pub(crate) fn resolve_train_script_path(app: &AppHandle) -> Result<PathBuf> {
     let script_path: PathBuf = app
         .path()
         .resolve("resources/scripts/distill-train.py", BaseDirectory::Resource)
@@ -320,9 +440,9 @@ fn resolve_train_script_path(app: &AppHandle) -> Result<PathBuf> {
 async fn record_initial_artifacts(
     db: &TrainingDb,
     run_id: &str,
-    config_path: &PathBuf,
-    stdout_log_path: &PathBuf,
-    stderr_log_path: &PathBuf,
+    config_path: &Path,
+    stdout_log_path: &Path,
+    stderr_log_path: &Path,
 ) {
     let repo = RunArtifactsRepository::new(db);
     for (kind, path) in [
@@ -344,6 +464,7 @@ async fn record_initial_artifacts(
 fn spawn_train_stdout_handler(
     app: AppHandle,
     _logs: Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+    activity: Arc<ActivityFeed>,
     db_path: PathBuf,
     run_id: String,
     run_dir: PathBuf,
@@ -351,6 +472,7 @@ fn spawn_train_stdout_handler(
     metrics_log_path: PathBuf,
     stdout: tokio::process::ChildStdout,
     last_error: Arc<std::sync::Mutex<Option<String>>>,
+    reported_error: Arc<AtomicBool>,
 ) {
     tauri::async_runtime::spawn(async move {
         let db = match TrainingDb::connect(&db_path).await { Ok(db) => db, Err(_) => return };
@@ -385,9 +507,12 @@ fn spawn_train_stdout_handler(
                     let ram_usage_mb = resources.and_then(|o| o.get("ram_rss_bytes")).and_then(|v| v.as_i64()).map(|b| b / (1024 * 1024));
                     let gpu_util = resources.and_then(|o| o.get("gpu_util_percent")).and_then(|v| v.as_f64());
 
-                    let _ = log_repo.insert(&TrainingLogInput {
+                    let log = TrainingLogInput {
                         run_id: run_id.clone(), epoch, step, loss, lr, temperature, cpu_util, ram_usage_mb, gpu_util
-                    }).await;
+                    };
+                    if log_repo.insert(&log).await.is_ok() {
+                        record_training_step(&activity, &log);
+                    }
                 }
                 "metric" => {
                     if let Some(ref mut file) = metrics_log {
@@ -420,7 +545,15 @@ fn spawn_train_stdout_handler(
                     let payload = &msg.payload;
                     let level = payload.get("level").and_then(|v| v.as_str()).unwrap_or("");
                     let message = payload.get("message").and_then(|v| v.as_str()).unwrap_or("").to_string();
+                    if !message.is_empty() {
+                        activity.record(
+                            ActivityInput::new(ActivitySource::Training, "status", message.clone())
+                                .level(if level.is_empty() { "info" } else { level })
+                                .subject(run_id.clone()),
+                        );
+                    }
                     if level.eq_ignore_ascii_case("error") {
+                        reported_error.store(true, Ordering::SeqCst);
                         { let mut guard = last_error.lock().unwrap(); *guard = Some(message.clone()); }
                         if let Some(trace) = payload.get("trace").and_then(|v| v.as_str()) {
                             let trace_path = run_dir.join("trainer_error.trace");
@@ -466,6 +599,80 @@ fn spawn_train_stderr_handler(
     });
 }
 
+/// Sample CPU, RAM and GPUs until the trainer exits, storing each sample on
+/// the run and warning once each time resources become exhausted
+fn spawn_resource_monitor(
+    app: AppHandle,
+    logs: Arc<std::sync::Mutex<Vec<crate::interfaces::http::LogEntry>>>,
+    db_path: PathBuf,
+    run_id: String,
+    pid: Option<u32>,
+    trainer_done: Arc<AtomicBool>,
+    resource_warning: Arc<std::sync::Mutex<Option<String>>>,
+) {
+    tauri::async_runtime::spawn(async move {
+        let db = match TrainingDb::connect(&db_path).await { Ok(db) => db, Err(_) => return };
+        let sample_repo = ResourceSampleRepository::new(&db);
+        let mut sampler = ResourceSampler::new(pid);
+        let mut exhausted = false;
+
+        loop {
+            sleep(RESOURCE_SAMPLE_INTERVAL).await;
+            if trainer_done.load(Ordering::SeqCst) { break; }
+
+            let usage = sampler.sample().await;
+            let warning = usage.exhaustion_warning();
+            if let Some(message) = &warning {
+                if !exhausted {
+                    add_log(&logs, "WARN", "Distillation", &format!("Run {}: {}", run_id, message));
+                    let _ = app.emit("distill-train-stream", DistillPythonMessage {
+                        kind: "status".to_string(),
+                        payload: serde_json::json!({
+                            "level": "warn", "message": message, "run_id": run_id, "resource_warning": true,
+                        }),
+                    });
+                }
+                *resource_warning.lock().unwrap() = Some(message.clone());
+            }
+            exhausted = warning.is_some();
+
+            let to_i64 = |bytes: u64| i64::try_from(bytes).ok();
+            let sample = ResourceSampleInput {
+                run_id: run_id.clone(),
+                cpu_percent: Some(usage.cpu_percent),
+                ram_used_bytes: to_i64(usage.ram_used_bytes),
+                ram_total_bytes: to_i64(usage.ram_total_bytes),
+                process_rss_bytes: usage.process_rss_bytes.and_then(to_i64),
+                gpus: usage.gpus.iter().map(|gpu| GpuResourceSample {
+                    index: i64::from(gpu.index),
+                    name: gpu.name.clone(),
+                    util_percent: gpu.util_percent,
+                    mem_used_bytes: gpu.mem_used_bytes.and_then(to_i64),
+                    mem_total_bytes: gpu.mem_total_bytes.and_then(to_i64),
+                }).collect(),
+                warning,
+            };
+            let _ = sample_repo.insert(&sample).await;
+
+            let (gpu_mem_used, gpu_mem_total) = sample.gpu_mem_bytes();
+            let _ = app.emit("distill-train-stream", DistillPythonMessage {
+                kind: "resources".to_string(),
+                payload: serde_json::json!({
+                    "run_id": run_id,
+                    "cpu_percent": sample.cpu_percent,
+                    "ram_used_bytes": sample.ram_used_bytes,
+                    "ram_total_bytes": sample.ram_total_bytes,
+                    "ram_rss_bytes": sample.process_rss_bytes,
+                    "gpu_util_percent": sample.gpu_util_percent(),
+                    "gpu_mem_used_bytes": gpu_mem_used,
+                    "gpu_mem_total_bytes": gpu_mem_total,
+                }),
+            });
+        }
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
 fn spawn_train_exit_monitor(
     app: AppHandle,
     state: Arc<AppState>,
@@ -474,6 +681,9 @@ fn spawn_train_exit_monitor(
     run_dir: PathBuf,
     child: Arc<AsyncMutex<tokio::process::Child>>,
     last_error: Arc<std::sync::Mutex<Option<String>>>,
+    reported_error: Arc<AtomicBool>,
+    trainer_done: Arc<AtomicBool>,
+    resource_warning: Arc<std::sync::Mutex<Option<String>>>,
 ) {
     tauri::async_runtime::spawn(async move {
         let db = match TrainingDb::connect(&db_path).await { Ok(db) => db, Err(_) => return };
@@ -494,42 +704,67 @@ fn spawn_train_exit_monitor(
                 Some(v) => v,
             };
 
+            trainer_done.store(true, Ordering::SeqCst);
             let cancelled = run_dir.join("cancel.flag").exists();
             let end_time = Some(chrono::Utc::now().to_rfc3339());
 
-            match outcome {
+            let (final_status, failure_reason) = match outcome {
                 Ok(status) => {
                     let final_status = if cancelled {
                         TrainingStatus::Cancelled
                     } else if status.success() {
                         TrainingStatus::Completed
-                    } else {
+                    } else if reported_error.load(Ordering::SeqCst) {
                         TrainingStatus::Failed
+                    } else {
+                        // Killed, out of memory, or died in native code
+                        TrainingStatus::Crashed
                     };
 
-                    let failure_reason = if matches!(final_status, TrainingStatus::Failed) {
-                        let guard = last_error.lock().unwrap();
-                        guard.clone().or_else(|| Some(format!("Trainer exited: {}", status)))
-                    } else { None };
-
-                    let is_completed = matches!(final_status, TrainingStatus::Completed);
-                    let _ = run_repo.set_status(&run_id, final_status, end_time, failure_reason).await;
-
-                    if is_completed {
-                        auto_create_model_version(&db, &run_id, &state.logs).await;
-                    }
+                    let failure_reason = match final_status {
+                        TrainingStatus::Failed => {
+                            let guard = last_error.lock().unwrap();
+                            guard.clone().or_else(|| Some(format!("Trainer exited: {}", status)))
+                        }
+                        TrainingStatus::Crashed => {
+                            let guard = last_error.lock().unwrap();
+                            Some(match guard.as_deref() {
+                                Some(last) => format!("Trainer crashed ({}): {}", status, last),
+                                None => format!("Trainer crashed ({})", status),
+                            })
+                        }
+                        _ => None,
+                    };
+                    (final_status, failure_reason)
                 }
                 Err(err) => {
                     let failure = { let guard = last_error.lock().unwrap(); guard.clone().unwrap_or_else(|| format!("Trainer wait failed: {}", err)) };
-                    let _ = run_repo.set_status(&run_id, TrainingStatus::Failed, end_time, Some(failure)).await;
+                    (TrainingStatus::Crashed, Some(failure))
                 }
+            };
+
+            // Exhausted memory is the usual cause of a crash, so it goes with the reason
+            let warning = resource_warning.lock().unwrap().clone();
+            let failure_reason = match (failure_reason, warning) {
+                (Some(reason), Some(warning)) => Some(format!("{} ({})", reason, warning)),
+                (reason, _) => reason,
+            };
+
+            let status_name = serde_json::to_value(&final_status).unwrap_or_default();
+            let is_completed = matches!(final_status, TrainingStatus::Completed);
+            let _ = run_repo.set_status(&run_id, final_status, end_time, failure_reason.clone()).await;
+            if is_completed {
+                auto_create_model_version(&db, &run_id, &state.logs).await;
             }
 
             { let mut guard = state.distill_trainers.lock().unwrap(); guard.remove(&run_id); }
 
             let _ = app.emit("distill-train-stream", DistillPythonMessage {
                 kind: "status".to_string(),
-                payload: serde_json::json!({"level": "info", "message": "trainer exited", "run_id": run_id, "cancelled": cancelled}),
+                payload: serde_json::json!({
+                    "level": "info", "message": "trainer exited", "run_id": run_id, "cancelled": cancelled,
+                    "status": status_name, "failure_reason": failure_reason,
+                }),
             });
             break;
         }
@@ -0,0 +1,225 @@
+//! Review Queue Commands (Flow A - Review Corrections and Soft Labels)
+use crate::domain::error::{AppError, Result};
+use crate::infrastructure::db::training::repositories::{
+    Correction, CorrectionRepository, ReviewCounts, ReviewStatus, SoftLabel, SoftLabelRepository,
+    TrainingDb,
+};
+use crate::interfaces::http::add_log;
+use crate::interfaces::tauri::AppState;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+
+use super::common::training_db_path;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewSummary {
+    pub corrections: ReviewCounts,
+    pub soft_labels: ReviewCounts,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkApproveResult {
+    pub correction_ids: Vec<String>,
+    pub soft_label_ids: Vec<String>,
+}
+
+fn non_empty(text: &str, what: &str) -> Result<()> {
+    if text.trim().is_empty() {
+        return Err(AppError::ValidationError(format!(
+            "{} cannot be empty",
+            what
+        )));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn distill_get_review_summary(
+    app: AppHandle,
+    _state: State<'_, Arc<AppState>>,
+) -> Result<ReviewSummary> {
+    let db_path = training_db_path(&app)?;
+    let db = TrainingDb::connect(&db_path).await?;
+
+    Ok(ReviewSummary {
+        corrections: CorrectionRepository::new(&db).review_counts().await?,
+        soft_labels: SoftLabelRepository::new(&db).review_counts().await?,
+    })
+}
+
+#[tauri::command]
+pub async fn distill_list_review_corrections(
+    app: AppHandle,
+    _state: State<'_, Arc<AppState>>,
+    status: Option<ReviewStatus>,
+    tag: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<Correction>> {
+    let db_path = training_db_path(&app)?;
+    let db = TrainingDb::connect(&db_path).await?;
+    let repo = CorrectionRepository::new(&db);
+
+    repo.list_for_review(status, tag.as_deref(), limit.unwrap_or(100))
+        .await
+}
+
+#[tauri::command]
+pub async fn distill_list_review_soft_labels(
+    app: AppHandle,
+    _state: State<'_, Arc<AppState>>,
+    status: Option<ReviewStatus>,
+    tag: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<SoftLabel>> {
+    let db_path = training_db_path(&app)?;
+    let db = TrainingDb::connect(&db_path).await?;
+    let repo = SoftLabelRepository::new(&db);
+
+    repo.list_for_review(status, tag.as_deref(), limit.unwrap_or(100))
+        .await
+}
+
+#[tauri::command]
+pub async fn distill_review_corrections(
+    app: AppHandle,
+    state: State<'_, Arc<AppState>>,
+    correction_ids: Vec<String>,
+    status: ReviewStatus,
+    note: Option<String>,
+) -> Result<u64> {
+    add_log(
+        &state.logs,
+        "INFO",
+        "Distillation",
+        &format!(
+            "Marking {} corrections as {:?}",
+            correction_ids.len(),
+            status
+        ),
+    );
+
+    let db_path = training_db_path(&app)?;
+    let db = TrainingDb::connect(&db_path).await?;
+    let repo = CorrectionRepository::new(&db);
+
+    repo.set_review_status(&correction_ids, status, note.as_deref())
+        .await
+}
+
+#[tauri::command]
+pub async fn distill_review_soft_labels(
+    app: AppHandle,
+    state: State<'_, Arc<AppState>>,
+    soft_label_ids: Vec<String>,
+    status: ReviewStatus,
+    note: Option<String>,
+) -> Result<u64> {
+    add_log(
+        &state.logs,
+        "INFO",
+        "Distillation",
+        &format!(
+            "Marking {} soft labels as {:?}",
+            soft_label_ids.len(),
+            status
+        ),
+    );
+
+    let db_path = training_db_path(&app)?;
+    let db = TrainingDb::connect(&db_path).await?;
+    let repo = SoftLabelRepository::new(&db);
+
+    repo.set_review_status(&soft_label_ids, status, note.as_deref())
+        .await
+}
+
+#[tauri::command]
+pub async fn distill_edit_correction_target(
+    app: AppHandle,
+    state: State<'_, Arc<AppState>>,
+    correction_id: String,
+    corrected_output: String,
+) -> Result<Correction> {
+    non_empty(&corrected_output, "Corrected output")?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "Distillation",
+        &format!("Editing target of correction: {}", correction_id),
+    );
+
+    let db_path = training_db_path(&app)?;
+    let db = TrainingDb::connect(&db_path).await?;
+    let repo = CorrectionRepository::new(&db);
+
+    repo.update_corrected_output(&correction_id, &corrected_output)
+        .await
+}
+
+#[tauri::command]
+pub async fn distill_edit_soft_label_target(
+    app: AppHandle,
+    state: State<'_, Arc<AppState>>,
+    soft_label_id: String,
+    teacher_output: String,
+) -> Result<SoftLabel> {
+    non_empty(&teacher_output, "Teacher output")?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "Distillation",
+        &format!("Editing target of soft label: {}", soft_label_id),
+    );
+
+    let db_path = training_db_path(&app)?;
+    let db = TrainingDb::connect(&db_path).await?;
+    let repo = SoftLabelRepository::new(&db);
+
+    repo.update_teacher_output(&soft_label_id, &teacher_output)
+        .await
+}
+
+/// Approve every pending correction carrying `tag`, and unless
+/// `include_soft_labels` is false, the pending soft labels linked to them
+#[tauri::command]
+pub async fn distill_approve_by_tag(
+    app: AppHandle,
+    state: State<'_, Arc<AppState>>,
+    tag: String,
+    include_soft_labels: Option<bool>,
+) -> Result<BulkApproveResult> {
+    non_empty(&tag, "Tag")?;
+    let db_path = training_db_path(&app)?;
+    let db = TrainingDb::connect(&db_path).await?;
+
+    let correction_ids = CorrectionRepository::new(&db)
+        .approve_pending_by_tag(&tag)
+        .await?;
+    let soft_label_ids = if include_soft_labels.unwrap_or(true) {
+        SoftLabelRepository::new(&db)
+            .approve_pending_by_tag(&tag)
+            .await?
+    } else {
+        Vec::new()
+    };
+
+    add_log(
+        &state.logs,
+        "INFO",
+        "Distillation",
+        &format!(
+            "Approved {} corrections and {} soft labels tagged '{}'",
+            correction_ids.len(),
+            soft_label_ids.len(),
+            tag
+        ),
+    );
+
+    Ok(BulkApproveResult {
+        correction_ids,
+        soft_label_ids,
+    })
+}
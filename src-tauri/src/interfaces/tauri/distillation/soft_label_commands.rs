@@ -1,5 +1,6 @@
 //! Soft Labels Commands (Phase 1: Data Preparation)
 use crate::domain::error::Result;
+use crate::domain::settings::LlmFeature;
 use crate::infrastructure::db::training::repositories::{
     Model, ModelRepository, SoftLabel, SoftLabelGenerationInput, SoftLabelGenerationResult,
     SoftLabelInput, SoftLabelRepository, TrainingDb,
@@ -13,6 +14,9 @@ use uuid::Uuid;
 
 use super::common::training_db_path;
 
+/// Teacher answers become the training targets, so they get no extra framing
+const TEACHER_SYSTEM_PROMPT: &str = "You are a helpful assistant. Answer the prompt directly.";
+
 #[tauri::command]
 pub async fn distill_generate_soft_labels(
     app: AppHandle,
@@ -177,18 +181,20 @@ pub async fn distill_generate_soft_labels(
     })
 }
 
-/// Helper function to call teacher API (OpenAI, Gemini, etc.)
+/// Helper function to call teacher API (OpenAI, Gemini, etc.) through the
+/// model configured for soft labels
 async fn call_teacher_api(
-    _state: &Arc<AppState>,
+    state: &Arc<AppState>,
     _teacher_model: &Model,
     prompt: &str,
-    _temperature: f64,
+    temperature: f64,
 ) -> Result<String> {
-    // TODO: Implement actual API call using state.llm_client
-    Ok(format!(
-        "[Teacher API Response for: {}]",
-        &prompt.chars().take(30).collect::<String>()
-    ))
+    let mut config = state.feature_config(LlmFeature::SoftLabels);
+    config.temperature = Some(temperature as f32);
+    state
+        .llm_client
+        .generate(&config, TEACHER_SYSTEM_PROMPT, prompt)
+        .await
 }
 
 /// Helper function to call local teacher model
@@ -221,7 +227,8 @@ pub async fn distill_list_soft_labels(
             crate::infrastructure::db::training::repositories::SoftLabelEntity,
         >(
             "SELECT soft_label_id, prompt, prompt_hash, teacher_model_id, teacher_output, \
-             soft_label_type, temperature, metadata_json, created_at \
+             soft_label_type, temperature, metadata_json, review_status, review_note, reviewed_at, \
+             created_at \
              FROM soft_labels WHERE teacher_model_id = ? \
              ORDER BY created_at DESC LIMIT ?",
         )
@@ -242,7 +249,8 @@ pub async fn distill_list_soft_labels(
             crate::infrastructure::db::training::repositories::SoftLabelEntity,
         >(
             "SELECT soft_label_id, prompt, prompt_hash, teacher_model_id, teacher_output, \
-             soft_label_type, temperature, metadata_json, created_at \
+             soft_label_type, temperature, metadata_json, review_status, review_note, reviewed_at, \
+             created_at \
              FROM soft_labels \
              ORDER BY created_at DESC LIMIT ?",
         )
@@ -314,6 +322,7 @@ pub async fn distill_link_soft_labels_to_run(
     let db = TrainingDb::connect(&db_path).await?;
     let sl_repo = SoftLabelRepository::new(&db);
 
+    sl_repo.ensure_approved(&soft_label_ids).await?;
     for soft_label_id in soft_label_ids {
         sl_repo.link_to_run(&run_id, &soft_label_id).await?;
     }
@@ -1,15 +1,16 @@
 //! Training Run Commands (Flow C - Run Training)
 use crate::domain::error::Result;
 use crate::infrastructure::db::training::repositories::{
-    RunCorrectionsRepository, RunDatasetsRepository, TrainingDb, TrainingLog, TrainingLogInput,
-    TrainingLogRepository, TrainingRun, TrainingRunInput, TrainingRunRepository, TrainingStatus,
+    CorrectionRepository, ResourceSample, ResourceSampleRepository, RunCorrectionsRepository,
+    RunDatasetsRepository, TrainingDb, TrainingLog, TrainingLogInput, TrainingLogRepository,
+    TrainingRun, TrainingRunInput, TrainingRunRepository, TrainingStatus,
 };
 use crate::interfaces::http::add_log;
 use crate::interfaces::tauri::AppState;
 use std::sync::Arc;
 use tauri::{AppHandle, State};
 
-use super::common::training_db_path;
+use super::common::{record_training_step, training_db_path};
 
 #[tauri::command]
 pub async fn distill_create_training_run(
@@ -77,6 +78,18 @@ pub async fn distill_create_training_run(
     let corrections_repo = RunCorrectionsRepository::new(&db);
     let datasets_repo = RunDatasetsRepository::new(&db);
 
+    // Only corrections that passed review may train a model
+    let ids: Vec<String> = correction_ids.iter().map(|(id, _, _)| id.clone()).collect();
+    if let Err(e) = CorrectionRepository::new(&db).ensure_approved(&ids).await {
+        add_log(
+            &state.logs,
+            "ERROR",
+            "Distillation",
+            &format!("Rejected training run {}: {}", input.run_id, e),
+        );
+        return Err(e);
+    }
+
     // Create the run
     add_log(
         &state.logs,
@@ -200,13 +213,17 @@ pub async fn distill_update_run_status(
         "completed" => TrainingStatus::Completed,
         "failed" => TrainingStatus::Failed,
         "cancelled" => TrainingStatus::Cancelled,
+        "crashed" => TrainingStatus::Crashed,
         "rolled_back" => TrainingStatus::RolledBack,
         _ => TrainingStatus::Failed,
     };
 
     let end_time = if matches!(
         training_status,
-        TrainingStatus::Completed | TrainingStatus::Failed | TrainingStatus::Cancelled
+        TrainingStatus::Completed
+            | TrainingStatus::Failed
+            | TrainingStatus::Cancelled
+            | TrainingStatus::Crashed
     ) {
         Some(chrono::Utc::now().to_rfc3339())
     } else {
@@ -248,14 +265,16 @@ pub async fn distill_list_training_runs(
 #[tauri::command]
 pub async fn distill_log_training_step(
     app: AppHandle,
-    _state: State<'_, Arc<AppState>>,
+    state: State<'_, Arc<AppState>>,
     log: TrainingLogInput,
 ) -> Result<()> {
     let db_path = training_db_path(&app)?;
     let db = TrainingDb::connect(&db_path).await?;
     let repo = TrainingLogRepository::new(&db);
 
-    repo.insert(&log).await
+    repo.insert(&log).await?;
+    record_training_step(&state.activity_feed, &log);
+    Ok(())
 }
 
 #[tauri::command]
@@ -271,3 +290,18 @@ pub async fn distill_list_training_logs(
 
     repo.list_for_run(&run_id, limit.unwrap_or(200)).await
 }
+
+/// CPU, RAM and GPU samples taken while the run's trainer was active
+#[tauri::command]
+pub async fn distill_list_resource_samples(
+    app: AppHandle,
+    _state: State<'_, Arc<AppState>>,
+    run_id: String,
+    limit: Option<i64>,
+) -> Result<Vec<ResourceSample>> {
+    let db_path = training_db_path(&app)?;
+    let db = TrainingDb::connect(&db_path).await?;
+    let repo = ResourceSampleRepository::new(&db);
+
+    repo.list_for_run(&run_id, limit.unwrap_or(500)).await
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::glossary::GlossaryEntry;
+use crate::interfaces::http::add_log;
+
+use super::state::AppState;
+
+#[tauri::command]
+pub async fn glossary_list_terms(state: State<'_, Arc<AppState>>) -> Result<Vec<GlossaryEntry>> {
+    state.repository.list_glossary_terms().await
+}
+
+#[tauri::command]
+pub async fn glossary_upsert_term(
+    state: State<'_, Arc<AppState>>,
+    mut entry: GlossaryEntry,
+) -> Result<GlossaryEntry> {
+    entry.source_term = entry.source_term.trim().to_string();
+    entry.target_term = entry
+        .target_term
+        .map(|term| term.trim().to_string())
+        .filter(|term| !term.is_empty());
+
+    if entry.source_term.is_empty() {
+        return Err(AppError::ValidationError(
+            "Glossary source term cannot be empty".to_string(),
+        ));
+    }
+    if !entry.do_not_translate && entry.target_term.is_none() {
+        return Err(AppError::ValidationError(format!(
+            "Glossary term '{}' needs a target term or must be marked do-not-translate",
+            entry.source_term
+        )));
+    }
+
+    state.repository.upsert_glossary_term(&mut entry).await?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "Glossary",
+        &format!("Saved glossary term '{}'", entry.source_term),
+    );
+    Ok(entry)
+}
+
+#[tauri::command]
+pub async fn glossary_delete_term(state: State<'_, Arc<AppState>>, id: i64) -> Result<()> {
+    state.repository.delete_glossary_term(id).await?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "Glossary",
+        &format!("Deleted glossary term {}", id),
+    );
+    Ok(())
+}
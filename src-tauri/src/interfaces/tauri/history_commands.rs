@@ -0,0 +1,139 @@
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::application::use_cases::history_export::{render_history, HistoryExportFormat};
+use crate::domain::error::{AppError, Result};
+use crate::domain::prompt::Prompt;
+use crate::domain::writing_memory::WritingMemoryEntry;
+use crate::interfaces::http::add_log;
+
+use super::rag_commands::writing_memory::remember_writing;
+use super::state::AppState;
+
+const DEFAULT_HISTORY_LIMIT: i64 = 100;
+const MAX_EXPORT_ROWS: i64 = 100_000;
+
+#[tauri::command]
+pub async fn search_history(
+    state: State<'_, Arc<AppState>>,
+    query: Option<String>,
+    tag: Option<String>,
+    favorites_only: Option<bool>,
+    limit: Option<i64>,
+) -> Result<Vec<Prompt>> {
+    state
+        .repository
+        .search_history(
+            query.as_deref(),
+            tag.as_deref().map(str::trim).filter(|tag| !tag.is_empty()),
+            favorites_only.unwrap_or(false),
+            limit.unwrap_or(DEFAULT_HISTORY_LIMIT).clamp(1, 1000),
+        )
+        .await
+}
+
+/// Replace the tags on a history entry. Tags are trimmed, lowercased and
+/// de-duplicated; commas are not allowed inside a tag.
+#[tauri::command]
+pub async fn tag_prompt(
+    state: State<'_, Arc<AppState>>,
+    prompt_id: i64,
+    tags: Vec<String>,
+) -> Result<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() {
+            continue;
+        }
+        if tag.contains(',') {
+            return Err(AppError::ValidationError(format!(
+                "Tag '{}' must not contain commas",
+                tag
+            )));
+        }
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+
+    state
+        .repository
+        .set_prompt_tags(prompt_id, &normalized)
+        .await?;
+    Ok(normalized)
+}
+
+#[tauri::command]
+pub async fn set_prompt_favorite(
+    state: State<'_, Arc<AppState>>,
+    prompt_id: i64,
+    favorite: bool,
+) -> Result<()> {
+    state
+        .repository
+        .set_prompt_favorite(prompt_id, favorite)
+        .await?;
+    // Starring an entry accepts it
+    if favorite {
+        if let Ok(prompt) = state.repository.get_prompt(prompt_id).await {
+            remember_writing(state.inner(), WritingMemoryEntry::from_prompt(&prompt));
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_favorites(
+    state: State<'_, Arc<AppState>>,
+    limit: Option<i64>,
+) -> Result<Vec<Prompt>> {
+    state
+        .repository
+        .search_history(
+            None,
+            None,
+            true,
+            limit.unwrap_or(DEFAULT_HISTORY_LIMIT).clamp(1, 1000),
+        )
+        .await
+}
+
+#[tauri::command]
+pub async fn list_history_tags(state: State<'_, Arc<AppState>>) -> Result<Vec<String>> {
+    state.repository.list_prompt_tags().await
+}
+
+/// Export history matching the given filters to `output_path`.
+/// Returns the number of exported rows.
+#[tauri::command]
+pub async fn export_history(
+    state: State<'_, Arc<AppState>>,
+    output_path: String,
+    format: HistoryExportFormat,
+    query: Option<String>,
+    tag: Option<String>,
+    favorites_only: Option<bool>,
+) -> Result<usize> {
+    let prompts = state
+        .repository
+        .search_history(
+            query.as_deref(),
+            tag.as_deref().map(str::trim).filter(|tag| !tag.is_empty()),
+            favorites_only.unwrap_or(false),
+            MAX_EXPORT_ROWS,
+        )
+        .await?;
+
+    let rendered = render_history(&prompts, format)?;
+    tokio::fs::write(&output_path, rendered).await?;
+
+    add_log(
+        &state.logs,
+        "INFO",
+        "History",
+        &format!("Exported {} history entries to {}", prompts.len(), output_path),
+    );
+    Ok(prompts.len())
+}
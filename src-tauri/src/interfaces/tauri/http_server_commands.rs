@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::domain::error::Result;
+use crate::domain::settings::HttpServerSettings;
+use crate::interfaces::http::{add_log, start_server, RateLimitMetrics};
+
+use super::state::AppState;
+
+/// Where the local HTTP API is reachable and how to authenticate
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpServerInfo {
+    pub settings: HttpServerSettings,
+    pub running: bool,
+
+    /// Base URL of the `/api` scope, e.g. "http://127.0.0.1:3001/api"
+    pub base_url: Option<String>,
+
+    /// Bearer token to send when `settings.require_token` is on
+    pub token: Option<String>,
+
+    /// `access_token` query parameter for QA proxy URLs, which iframes load
+    /// without headers. Only valid for `/api/qa/proxy`.
+    pub proxy_token: Option<String>,
+
+    /// Requests let through and answered with 429 per limited endpoint
+    pub rate_limits: Vec<RateLimitMetrics>,
+}
+
+fn server_info(state: &AppState) -> HttpServerInfo {
+    let address = state.http_server.address();
+    HttpServerInfo {
+        settings: state.settings.get::<HttpServerSettings>(),
+        running: address.is_some(),
+        base_url: address.map(|address| {
+            // A wildcard bind is reachable from this machine through loopback
+            let host = if address.ip().is_unspecified() {
+                "127.0.0.1".to_string()
+            } else {
+                address.ip().to_string()
+            };
+            format!("http://{}:{}/api", host, address.port())
+        }),
+        token: state.http_server.token(),
+        proxy_token: state.http_server.proxy_token(),
+        rate_limits: state.http_server.rate_limiter().metrics(),
+    }
+}
+
+#[tauri::command]
+pub async fn http_server_info(state: State<'_, Arc<AppState>>) -> Result<HttpServerInfo> {
+    Ok(server_info(&state))
+}
+
+/// Persist the listener settings and restart the server with them
+#[tauri::command]
+pub async fn http_server_update_settings(
+    state: State<'_, Arc<AppState>>,
+    settings: HttpServerSettings,
+) -> Result<HttpServerInfo> {
+    settings.validate()?;
+    state.settings.set(&settings)?;
+    restart(&state).await?;
+    Ok(server_info(&state))
+}
+
+/// Issue a new API token. Clients using the old one get 401 from now on.
+#[tauri::command]
+pub async fn http_server_rotate_token(state: State<'_, Arc<AppState>>) -> Result<HttpServerInfo> {
+    let token = state.config_service.rotate_http_api_token()?;
+    if state.settings.get::<HttpServerSettings>().require_token {
+        state.http_server.set_token(Some(token));
+    }
+    add_log(&state.logs, "INFO", "HttpApi", "HTTP API token rotated");
+    Ok(server_info(&state))
+}
+
+pub(crate) async fn restart(state: &Arc<AppState>) -> Result<()> {
+    match start_server(state.clone()).await {
+        Ok(address) => {
+            add_log(
+                &state.logs,
+                "INFO",
+                "HttpApi",
+                &format!("HTTP server restarted on {}", address),
+            );
+            Ok(())
+        }
+        Err(err) => {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "HttpApi",
+                &format!("HTTP server failed to restart: {}", err),
+            );
+            Err(err)
+        }
+    }
+}
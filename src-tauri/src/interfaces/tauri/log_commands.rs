@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::logging::{LogPage, LogQuery, LogSettings};
+use crate::infrastructure::logging;
+use crate::interfaces::http::add_log;
+
+use super::state::AppState;
+
+/// Search the log files. Records are returned newest first.
+#[tauri::command]
+pub async fn logs_query(query: Option<LogQuery>) -> Result<LogPage> {
+    let query = query.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || logging::sink().query(&query))
+        .await
+        .map_err(|e| AppError::Internal(format!("Log query task failed: {}", e)))?
+}
+
+#[tauri::command]
+pub async fn logs_get_settings(state: State<'_, Arc<AppState>>) -> Result<LogSettings> {
+    Ok(state.settings.get::<LogSettings>())
+}
+
+/// Persist new levels/retention and apply them immediately
+#[tauri::command]
+pub async fn logs_update_settings(
+    state: State<'_, Arc<AppState>>,
+    settings: LogSettings,
+) -> Result<LogSettings> {
+    if settings.max_file_size_mb == 0 {
+        return Err(AppError::ValidationError(
+            "Maximum log file size must be at least 1 MB".to_string(),
+        ));
+    }
+
+    state.settings.set(&settings)?;
+    logging::sink().configure(settings.clone());
+    add_log(
+        &state.logs,
+        "INFO",
+        "Logging",
+        &format!(
+            "Log settings updated: level {}, {} source override(s), {} day retention",
+            settings.level.as_str(),
+            settings.source_levels.len(),
+            settings.retention_days
+        ),
+    );
+    Ok(settings)
+}
+
+/// Apply retention limits now. Returns the number of deleted files.
+#[tauri::command]
+pub async fn logs_prune(state: State<'_, Arc<AppState>>) -> Result<usize> {
+    let removed = tauri::async_runtime::spawn_blocking(|| logging::sink().prune())
+        .await
+        .map_err(|e| AppError::Internal(format!("Log prune task failed: {}", e)))??;
+    add_log(
+        &state.logs,
+        "INFO",
+        "Logging",
+        &format!("Removed {} old log file(s)", removed),
+    );
+    Ok(removed)
+}
@@ -1,16 +1,21 @@
+use std::path::Path;
 use std::sync::Arc;
 
 use tauri::State;
 
-use crate::domain::error::Result;
+use crate::domain::error::{AppError, Result};
 use crate::interfaces::http::add_log;
 use crate::interfaces::mock_server::{
-    build_status as build_mock_status, save_config as save_mock_server_config, start_mock_server,
-    stop_mock_server, MockServerConfig, MockServerStatus,
+    build_status as build_mock_status, export_routes_dir, load_routes_dir,
+    save_config as save_mock_server_config, start_mock_server, stop_mock_server, MockRoutesExport,
+    MockServerConfig, MockServerStatus, ROUTES_MANIFEST_FILE,
 };
 
 use super::state::AppState;
 
+/// Emitted with a `MockConfigReload` when route files changed on disk
+pub(crate) const MOCK_CONFIG_RELOADED_EMIT: &str = "mock-server-config-reloaded";
+
 
 #[tauri::command]
 pub async fn mock_server_get_config(state: State<'_, Arc<AppState>>) -> Result<MockServerConfig> {
@@ -35,10 +40,16 @@ pub async fn mock_server_update_config(
         "MockServer",
         "Mock server config updating...",
     );
-    {
+    let config = {
         let mut current = state.mock_server.config.lock().unwrap();
+        // The routes directory changes through mock_server_set_routes_dir only
+        let config = MockServerConfig {
+            routes_dir: current.routes_dir.clone(),
+            ..config
+        };
         *current = config.clone();
-    } // Release the lock before saving to avoid deadlock
+        config
+    }; // Release the lock before saving to avoid deadlock
     save_mock_server_config(&state.mock_server)?;
     add_log(
         &state.logs,
@@ -84,3 +95,90 @@ pub async fn mock_server_status(state: State<'_, Arc<AppState>>) -> Result<MockS
     Ok(build_mock_status(&state.mock_server))
 }
 
+
+/// Write the routes into `dir`, one file each, for version control
+#[tauri::command]
+pub async fn mock_server_export_routes(
+    state: State<'_, Arc<AppState>>,
+    dir: String,
+) -> Result<MockRoutesExport> {
+    let config = state.mock_server.config.lock().unwrap().clone();
+    let export = export_routes_dir(&config, Path::new(&dir))?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "MockServer",
+        &format!(
+            "Exported {} mock routes to {} ({} stale files removed)",
+            export.written, export.directory, export.removed
+        ),
+    );
+    Ok(export)
+}
+
+/// Replace the port and routes with those of a directory written by
+/// `mock_server_export_routes`
+#[tauri::command]
+pub async fn mock_server_import_routes(
+    state: State<'_, Arc<AppState>>,
+    dir: String,
+) -> Result<MockServerConfig> {
+    if !Path::new(&dir).is_dir() {
+        return Err(AppError::ValidationError(format!(
+            "{} is not a directory",
+            dir
+        )));
+    }
+    let loaded = load_routes_dir(Path::new(&dir))?;
+    let config = {
+        let mut current = state.mock_server.config.lock().unwrap();
+        current.port = loaded.port;
+        current.routes = loaded.routes;
+        current.clone()
+    };
+    save_mock_server_config(&state.mock_server)?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "MockServer",
+        &format!("Imported {} mock routes from {}", config.routes.len(), dir),
+    );
+    Ok(config)
+}
+
+/// Keep the routes in `dir` instead of the settings, or back in the
+/// settings when `dir` is `None`. A directory that already holds an
+/// export is loaded; otherwise the current routes are written into it.
+#[tauri::command]
+pub async fn mock_server_set_routes_dir(
+    state: State<'_, Arc<AppState>>,
+    dir: Option<String>,
+) -> Result<MockServerConfig> {
+    let loaded = match &dir {
+        Some(dir) if Path::new(dir).join(ROUTES_MANIFEST_FILE).is_file() => {
+            Some(load_routes_dir(Path::new(dir))?)
+        }
+        _ => None,
+    };
+    let config = {
+        let mut current = state.mock_server.config.lock().unwrap();
+        if let Some(loaded) = loaded {
+            current.port = loaded.port;
+            current.routes = loaded.routes;
+        }
+        current.routes_dir = dir.clone();
+        current.clone()
+    };
+    save_mock_server_config(&state.mock_server)?;
+    state.mock_server.watch_config_source();
+    add_log(
+        &state.logs,
+        "INFO",
+        "MockServer",
+        &match &dir {
+            Some(dir) => format!("Mock server routes are now kept in {}", dir),
+            None => "Mock server routes are now kept in the settings".to_string(),
+        },
+    );
+    Ok(config)
+}
@@ -5,10 +5,26 @@ pub mod commands_registry;
 pub mod distillation;
 pub mod rag_commands;
 
+pub(crate) mod activity_commands;
+pub(crate) mod backup_commands;
 pub(crate) mod core_commands;
+pub(crate) mod db_maintenance_commands;
+pub(crate) mod diagnostics_commands;
+pub(crate) mod glossary_commands;
+pub(crate) mod history_commands;
+pub(crate) mod http_server_commands;
+pub(crate) mod log_commands;
 pub(crate) mod mock_server_commands;
+pub(crate) mod ollama_commands;
+pub(crate) mod profile_commands;
 pub mod qa;
+pub(crate) mod quick_action_commands;
+pub(crate) mod settings_commands;
 pub(crate) mod state;
+pub(crate) mod storage_commands;
+pub(crate) mod telemetry_commands;
+pub(crate) mod translation_memory_commands;
+pub(crate) mod trash_commands;
 
 pub use state::{cleanup_child_processes, AppState};
 
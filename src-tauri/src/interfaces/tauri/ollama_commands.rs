@@ -0,0 +1,131 @@
+//! Ollama lifecycle: install and server status, starting and stopping the
+//! server the app manages, and pulling models with progress events.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Emitter, State};
+
+use crate::domain::error::Result;
+use crate::domain::llm_config::LLMProvider;
+use crate::domain::ollama::{OllamaModel, OllamaPullProgress, OllamaStatus, DEFAULT_OLLAMA_URL};
+use crate::interfaces::http::add_log;
+
+use super::state::AppState;
+
+/// Emitted with an `OllamaPullProgress` payload while a model is pulled
+pub(crate) const OLLAMA_PULL_PROGRESS_EMIT: &str = "ollama-pull-progress";
+
+/// Server the UI asked about, else the one the current model runs on
+fn resolve_base_url(state: &AppState, requested: Option<String>) -> String {
+    if let Some(url) = requested.filter(|url| !url.trim().is_empty()) {
+        return url;
+    }
+    let config = state.last_config.lock().unwrap();
+    if matches!(config.provider, LLMProvider::Ollama) && !config.base_url.trim().is_empty() {
+        config.base_url.clone()
+    } else {
+        DEFAULT_OLLAMA_URL.to_string()
+    }
+}
+
+#[tauri::command]
+pub async fn ollama_status(
+    state: State<'_, Arc<AppState>>,
+    base_url: Option<String>,
+) -> Result<OllamaStatus> {
+    let base_url = resolve_base_url(&state, base_url);
+    Ok(state.ollama.status(&base_url).await)
+}
+
+#[tauri::command]
+pub async fn ollama_list_models(
+    state: State<'_, Arc<AppState>>,
+    base_url: Option<String>,
+) -> Result<Vec<OllamaModel>> {
+    let base_url = resolve_base_url(&state, base_url);
+    state.ollama.list_models(&base_url).await
+}
+
+#[tauri::command]
+pub async fn ollama_start(
+    state: State<'_, Arc<AppState>>,
+    base_url: Option<String>,
+) -> Result<OllamaStatus> {
+    let base_url = resolve_base_url(&state, base_url);
+    match state.ollama.start(&base_url).await {
+        Ok(version) => add_log(
+            &state.logs,
+            "INFO",
+            "Ollama",
+            &format!("Ollama {} running on {}", version, base_url),
+        ),
+        Err(err) => {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "Ollama",
+                &format!("Failed to start Ollama: {}", err),
+            );
+            return Err(err);
+        }
+    }
+    Ok(state.ollama.status(&base_url).await)
+}
+
+#[tauri::command]
+pub async fn ollama_stop(
+    state: State<'_, Arc<AppState>>,
+    base_url: Option<String>,
+) -> Result<OllamaStatus> {
+    let base_url = resolve_base_url(&state, base_url);
+    state.ollama.stop().await?;
+    add_log(&state.logs, "INFO", "Ollama", "Stopped the Ollama server");
+    Ok(state.ollama.status(&base_url).await)
+}
+
+/// Pull `model` into the server, starting it first when auto-start is on
+#[tauri::command]
+pub async fn ollama_pull_model(
+    app: AppHandle,
+    state: State<'_, Arc<AppState>>,
+    model: String,
+    base_url: Option<String>,
+) -> Result<Vec<OllamaModel>> {
+    let base_url = resolve_base_url(&state, base_url);
+    state.ollama.ensure_running(&base_url).await?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "Ollama",
+        &format!("Pulling {} into {}", model.trim(), base_url),
+    );
+
+    let mut last_emit = Instant::now();
+    let mut on_progress = |progress: &OllamaPullProgress| {
+        // Status changes always go out; byte counts are throttled
+        if progress.completed_bytes.is_none()
+            || progress.is_success()
+            || last_emit.elapsed() >= Duration::from_millis(250)
+        {
+            last_emit = Instant::now();
+            let _ = app.emit(OLLAMA_PULL_PROGRESS_EMIT, progress);
+        }
+    };
+    if let Err(err) = state.ollama.pull(&base_url, &model, &mut on_progress).await {
+        add_log(
+            &state.logs,
+            "ERROR",
+            "Ollama",
+            &format!("Pulling {} failed: {}", model.trim(), err),
+        );
+        return Err(err);
+    }
+    add_log(
+        &state.logs,
+        "INFO",
+        "Ollama",
+        &format!("Pulled {}", model.trim()),
+    );
+    state.ollama.list_models(&base_url).await
+}
@@ -0,0 +1,158 @@
+//! Project profiles: applying one switches the mock server's routes (and
+//! starts it), the QA proxy settings and the RAG chat collection in one go.
+//! Profiles themselves are edited through the `projectProfiles` settings
+//! section.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::project_profiles::{ProjectProfile, ProjectProfileSettings};
+use crate::interfaces::http::add_log;
+use crate::interfaces::mock_server::{
+    build_status as build_mock_status, load_routes_dir, save_config as save_mock_server_config,
+    start_mock_server, stop_mock_server, MockServerStatus, ROUTES_MANIFEST_FILE,
+};
+
+use super::state::AppState;
+
+/// Emitted with a `ProjectProfileApplied` payload after a profile is applied,
+/// so the windows can pick up its RAG collection and QA URLs
+pub(crate) const PROJECT_PROFILE_APPLIED_EMIT: &str = "project-profile-applied";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectProfileApplied {
+    pub profile: ProjectProfile,
+    pub mock_server: MockServerStatus,
+    /// Parts that could not be applied; the rest still were
+    pub warnings: Vec<String>,
+}
+
+#[tauri::command]
+pub async fn project_profile_apply(
+    app: AppHandle,
+    state: State<'_, Arc<AppState>>,
+    profile_id: String,
+) -> Result<ProjectProfileApplied> {
+    apply_profile(&app, &state, &profile_id).await
+}
+
+/// Apply a profile picked outside the UI, from a launch argument or a link
+pub(crate) fn spawn_apply_profile(app: AppHandle, profile_id: String) {
+    tauri::async_runtime::spawn(async move {
+        let Some(state) = app.try_state::<Arc<AppState>>() else {
+            return;
+        };
+        let state = state.inner().clone();
+        // Failures are logged by `apply_profile`
+        let _ = apply_profile(&app, &state, &profile_id).await;
+    });
+}
+
+pub(crate) async fn apply_profile(
+    app: &AppHandle,
+    state: &Arc<AppState>,
+    profile_id: &str,
+) -> Result<ProjectProfileApplied> {
+    let mut settings = state.settings.get::<ProjectProfileSettings>();
+    let Some(profile) = settings.profile(profile_id).cloned() else {
+        add_log(
+            &state.logs,
+            "ERROR",
+            "Profiles",
+            &format!("Profile '{}' does not exist", profile_id.trim()),
+        );
+        return Err(AppError::NotFound(format!(
+            "Profile not found: {}",
+            profile_id.trim()
+        )));
+    };
+    let mut warnings = Vec::new();
+
+    let served_port = state.mock_server.config.lock().unwrap().port;
+    if let Some(dir) = profile.mock_routes_dir.as_deref().map(str::trim) {
+        if let Err(err) = switch_mock_routes(state, dir) {
+            warnings.push(format!("Mock routes from {}: {}", dir, err));
+        }
+    }
+    if profile.start_mock_server {
+        if let Err(err) = ensure_mock_server(state, served_port).await {
+            warnings.push(format!("Mock server: {}", err));
+        }
+    }
+    if let Some(proxy) = &profile.qa_proxy {
+        if let Err(err) = state.settings.set(proxy) {
+            warnings.push(format!("QA proxy settings: {}", err));
+        }
+    }
+
+    settings.active_profile_id = Some(profile.id.clone());
+    state.settings.set(&settings)?;
+
+    for warning in &warnings {
+        add_log(&state.logs, "WARN", "Profiles", warning);
+    }
+    add_log(
+        &state.logs,
+        "INFO",
+        "Profiles",
+        &format!(
+            "Applied profile '{}'{}",
+            profile.name,
+            if warnings.is_empty() {
+                String::new()
+            } else {
+                format!(" with {} warnings", warnings.len())
+            }
+        ),
+    );
+
+    let applied = ProjectProfileApplied {
+        profile,
+        mock_server: build_mock_status(&state.mock_server),
+        warnings,
+    };
+    let _ = app.emit(PROJECT_PROFILE_APPLIED_EMIT, &applied);
+    crate::interfaces::tray::refresh(app);
+    Ok(applied)
+}
+
+/// Serve the routes exported into `dir`, the way `mock_server_set_routes_dir`
+/// does, but without writing the current routes into a directory that has
+/// none: those belong to another project.
+fn switch_mock_routes(state: &AppState, dir: &str) -> Result<()> {
+    if !Path::new(dir).join(ROUTES_MANIFEST_FILE).is_file() {
+        return Err(AppError::ValidationError(format!(
+            "no {} found; export the project's routes there first",
+            ROUTES_MANIFEST_FILE
+        )));
+    }
+    let loaded = load_routes_dir(Path::new(dir))?;
+    {
+        let mut current = state.mock_server.config.lock().unwrap();
+        current.port = loaded.port;
+        current.routes = loaded.routes;
+        current.routes_dir = Some(dir.to_string());
+    }
+    save_mock_server_config(&state.mock_server)?;
+    state.mock_server.watch_config_source();
+    Ok(())
+}
+
+/// Start the mock server, restarting it when it is serving on
+/// `served_port` and the routes now ask for another one
+async fn ensure_mock_server(state: &AppState, served_port: u16) -> Result<()> {
+    let running = state.mock_server.server.lock().unwrap().is_some();
+    let port = state.mock_server.config.lock().unwrap().port;
+    if running && port == served_port {
+        return Ok(());
+    }
+    if running {
+        stop_mock_server(state.mock_server.clone()).await?;
+    }
+    start_mock_server(state.mock_server.clone()).await
+}
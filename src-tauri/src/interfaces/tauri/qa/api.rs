@@ -3,10 +3,11 @@ use std::sync::Arc;
 use base64::Engine as _;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use reqwest::multipart::{Form, Part};
-use reqwest::Method;
+use reqwest::{Method, RequestBuilder};
 use tauri::State;
 
 use crate::domain::error::{AppError, Result};
+use crate::domain::qa_api_test::check_assertions;
 use crate::interfaces::http::add_log;
 use crate::interfaces::tauri::AppState;
 
@@ -42,88 +43,12 @@ pub async fn qa_execute_api_request(
         ),
     );
 
-    let method =
-        Method::from_bytes(request.method.trim().to_uppercase().as_bytes()).map_err(|_| {
-            AppError::ValidationError("Invalid HTTP method for API request.".to_string())
-        })?;
-    let mut url = url::Url::parse(request.url.trim())
-        .map_err(|_| AppError::ValidationError("Invalid URL for API request.".to_string()))?;
-
-    if !request.query_params.is_empty() {
-        let mut pairs = url.query_pairs_mut();
-        for param in request.query_params.into_iter().filter(|item| item.enabled) {
-            if param.key.trim().is_empty() {
-                continue;
-            }
-            pairs.append_pair(param.key.trim(), param.value.trim());
-        }
+    for assertion in &request.assertions {
+        assertion.validate()?;
     }
-
-    let mut header_map = HeaderMap::new();
-    for header in request.headers.into_iter().filter(|item| item.enabled) {
-        if header.key.trim().is_empty() {
-            continue;
-        }
-        let name = HeaderName::from_bytes(header.key.trim().as_bytes()).map_err(|_| {
-            AppError::ValidationError("Invalid header name for API request.".to_string())
-        })?;
-        let value = HeaderValue::from_str(header.value.trim()).map_err(|_| {
-            AppError::ValidationError("Invalid header value for API request.".to_string())
-        })?;
-        header_map.insert(name, value);
-    }
-
     let client = reqwest::Client::new();
-    let mut builder = client
-        .request(method, url.clone())
-        .headers(header_map.clone());
-
-    if let Some(body_type) = request.body_type.as_deref() {
-        if body_type == "json" {
-            if let Some(body_json) = request.body_json.as_ref() {
-                if !body_json.trim().is_empty() {
-                    if !header_map.contains_key(reqwest::header::CONTENT_TYPE) {
-                        builder = builder.header(reqwest::header::CONTENT_TYPE, "application/json");
-                    }
-                    builder = builder.body(body_json.trim().to_string());
-                }
-            }
-        } else if body_type == "form" {
-            let mut form = Form::new();
-            for field in request.form_data.into_iter().filter(|item| item.enabled) {
-                if field.key.trim().is_empty() {
-                    continue;
-                }
-                if let Some(file_base64) = field.file_base64.as_ref() {
-                    let decoded = base64::engine::general_purpose::STANDARD
-                        .decode(file_base64)
-                        .map_err(|_| {
-                            AppError::ValidationError(
-                                "Failed to decode form-data file payload.".to_string(),
-                            )
-                        })?;
-                    let mut part = Part::bytes(decoded).file_name(
-                        field
-                            .file_name
-                            .clone()
-                            .unwrap_or_else(|| "upload".to_string()),
-                    );
-                    if let Some(content_type) = field.content_type.as_ref() {
-                        part = part.mime_str(content_type).map_err(|_| {
-                            AppError::ValidationError("Invalid form-data content type.".to_string())
-                        })?;
-                    }
-                    form = form.part(field.key.trim().to_string(), part);
-                } else if let Some(value) = field.value.as_ref() {
-                    form = form.text(field.key.trim().to_string(), value.clone());
-                }
-            }
-            builder = builder.multipart(form);
-        }
-    }
-
     let start = std::time::Instant::now();
-    let response = match builder.send().await {
+    let response = match send_api_request(&state, &client, &request).await {
         Ok(response) => response,
         Err(err) => {
             emit_status_log(
@@ -144,7 +69,7 @@ pub async fn qa_execute_api_request(
                     latency_ms: None,
                 }),
             );
-            return Err(AppError::Internal(format!("API request failed: {}", err)));
+            return Err(err);
         }
     };
 
@@ -222,11 +147,156 @@ pub async fn qa_execute_api_request(
         }),
     );
 
+    let header_pairs: Vec<(String, String)> = headers
+        .iter()
+        .map(|header| (header.key.clone(), header.value.clone()))
+        .collect();
+    let assertion_results = check_assertions(&request.assertions, status, &header_pairs, &body);
+
     Ok(QaApiResponse {
         status,
         duration_ms: start.elapsed().as_millis() as i64,
         headers,
         body,
         content_type,
+        assertion_results,
     })
 }
+
+/// Send `request`, signed with a token of its auth profile if it names one.
+/// When the API answers 401 the token is expired and the request is sent
+/// once more with a refreshed one.
+pub(crate) async fn send_api_request(
+    state: &AppState,
+    client: &reqwest::Client,
+    request: &QaApiRequest,
+) -> Result<reqwest::Response> {
+    let profile_id = request
+        .auth_profile_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|id| !id.is_empty());
+    let Some(profile_id) = profile_id else {
+        return send(build_api_request(client, request)?).await;
+    };
+
+    let response = send_signed(state, client, request, profile_id).await?;
+    if response.status() != reqwest::StatusCode::UNAUTHORIZED {
+        return Ok(response);
+    }
+    state.qa_auth_use_case.expire(profile_id).await;
+    send_signed(state, client, request, profile_id).await
+}
+
+async fn send_signed(
+    state: &AppState,
+    client: &reqwest::Client,
+    request: &QaApiRequest,
+    profile_id: &str,
+) -> Result<reqwest::Response> {
+    let (name, value) = state.qa_auth_use_case.authorization(profile_id).await?;
+    let mut signed = request.clone();
+    signed
+        .headers
+        .retain(|header| !header.key.trim().eq_ignore_ascii_case(&name));
+    signed.headers.push(QaApiKeyValue {
+        key: name,
+        value,
+        enabled: true,
+    });
+    send(build_api_request(client, &signed)?).await
+}
+
+async fn send(builder: RequestBuilder) -> Result<reqwest::Response> {
+    builder
+        .send()
+        .await
+        .map_err(|err| AppError::Internal(format!("API request failed: {}", err)))
+}
+
+/// Validate `request` and turn it into a reqwest request on `client`
+pub(crate) fn build_api_request(
+    client: &reqwest::Client,
+    request: &QaApiRequest,
+) -> Result<RequestBuilder> {
+    let method =
+        Method::from_bytes(request.method.trim().to_uppercase().as_bytes()).map_err(|_| {
+            AppError::ValidationError("Invalid HTTP method for API request.".to_string())
+        })?;
+    let mut url = url::Url::parse(request.url.trim())
+        .map_err(|_| AppError::ValidationError("Invalid URL for API request.".to_string()))?;
+
+    if !request.query_params.is_empty() {
+        let mut pairs = url.query_pairs_mut();
+        for param in request.query_params.iter().filter(|item| item.enabled) {
+            if param.key.trim().is_empty() {
+                continue;
+            }
+            pairs.append_pair(param.key.trim(), param.value.trim());
+        }
+    }
+
+    let mut header_map = HeaderMap::new();
+    for header in request.headers.iter().filter(|item| item.enabled) {
+        if header.key.trim().is_empty() {
+            continue;
+        }
+        let name = HeaderName::from_bytes(header.key.trim().as_bytes()).map_err(|_| {
+            AppError::ValidationError("Invalid header name for API request.".to_string())
+        })?;
+        let value = HeaderValue::from_str(header.value.trim()).map_err(|_| {
+            AppError::ValidationError("Invalid header value for API request.".to_string())
+        })?;
+        header_map.insert(name, value);
+    }
+
+    let mut builder = client
+        .request(method, url.clone())
+        .headers(header_map.clone());
+
+    if let Some(body_type) = request.body_type.as_deref() {
+        if body_type == "json" {
+            if let Some(body_json) = request.body_json.as_ref() {
+                if !body_json.trim().is_empty() {
+                    if !header_map.contains_key(reqwest::header::CONTENT_TYPE) {
+                        builder = builder.header(reqwest::header::CONTENT_TYPE, "application/json");
+                    }
+                    builder = builder.body(body_json.trim().to_string());
+                }
+            }
+        } else if body_type == "form" {
+            let mut form = Form::new();
+            for field in request.form_data.iter().filter(|item| item.enabled) {
+                if field.key.trim().is_empty() {
+                    continue;
+                }
+                if let Some(file_base64) = field.file_base64.as_ref() {
+                    let decoded = base64::engine::general_purpose::STANDARD
+                        .decode(file_base64)
+                        .map_err(|_| {
+                            AppError::ValidationError(
+                                "Failed to decode form-data file payload.".to_string(),
+                            )
+                        })?;
+                    let mut part = Part::bytes(decoded).file_name(
+                        field
+                            .file_name
+                            .clone()
+                            .unwrap_or_else(|| "upload".to_string()),
+                    );
+                    if let Some(content_type) = field.content_type.as_ref() {
+                        part = part.mime_str(content_type).map_err(|_| {
+                            AppError::ValidationError("Invalid form-data content type.".to_string())
+                        })?;
+                    }
+                    form = form.part(field.key.trim().to_string(), part);
+                } else if let Some(value) = field.value.as_ref() {
+                    form = form.text(field.key.trim().to_string(), value.clone());
+                }
+            }
+            builder = builder.multipart(form);
+        }
+    }
+
+    Ok(builder)
+}
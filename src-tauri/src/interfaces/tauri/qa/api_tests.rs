@@ -0,0 +1,430 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde_json::json;
+use tauri::{Emitter, State};
+use uuid::Uuid;
+
+use crate::application::use_cases::qa_ai::hash_value;
+use crate::domain::error::{AppError, Result};
+use crate::domain::qa_api_test::{
+    check_assertions, QaApiTestResult, QaApiTestRunReport, QaEndpointAssertions, API_TEST_CASE_TYPE,
+};
+use crate::domain::qa_auth::QaAuthSettings;
+use crate::domain::qa_checkpoint::QaTestCase;
+use crate::domain::qa_run::{QaRunStreamInput, QaSessionRun};
+use crate::domain::qa_test_management::QaTestResultStatus;
+use crate::domain::telemetry::UsageSubsystem;
+use crate::interfaces::http::add_log;
+use crate::interfaces::tauri::AppState;
+
+use super::api::{build_api_request, send_api_request};
+use super::logging::{QA_RUN_STREAM_EMIT, QA_RUN_UPDATED_EMIT};
+use super::types::QaApiRequest;
+
+const API_TEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Save a request and its assertions as an API test case of the session
+#[tauri::command]
+pub async fn qa_save_api_test(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+    title: String,
+    request: QaApiRequest,
+    checkpoint_id: Option<String>,
+    priority: Option<String>,
+) -> Result<QaTestCase> {
+    let session = state.qa_session_use_case.get_session(&session_id).await?;
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(AppError::ValidationError(
+            "API test title is required.".to_string(),
+        ));
+    }
+    if request.assertions.is_empty() {
+        return Err(AppError::ValidationError(
+            "Add at least one assertion before saving the request as a test.".to_string(),
+        ));
+    }
+    for assertion in &request.assertions {
+        assertion.validate()?;
+    }
+    if request
+        .form_data
+        .iter()
+        .any(|field| field.enabled && field.file_base64.is_some())
+    {
+        return Err(AppError::ValidationError(
+            "Requests with file uploads cannot be saved as API tests.".to_string(),
+        ));
+    }
+    // Fails now rather than on every later run
+    build_api_request(&reqwest::Client::new(), &request)?;
+    if let Some(profile_id) = request.auth_profile_id.as_deref() {
+        state.settings.get::<QaAuthSettings>().profile(profile_id)?;
+    }
+
+    let request = QaApiRequest {
+        source: Some("api_test".to_string()),
+        ..request
+    };
+    let steps_json = serde_json::to_string(&request)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize API test: {}", e)))?;
+    let expected = request
+        .assertions
+        .iter()
+        .map(|assertion| assertion.describe())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let test_case = QaTestCase {
+        id: Uuid::new_v4().to_string(),
+        session_id: session.id.clone(),
+        checkpoint_id: checkpoint_id.filter(|value| !value.trim().is_empty()),
+        case_type: API_TEST_CASE_TYPE.to_string(),
+        title: title.to_string(),
+        dedup_hash: hash_value(&format!("{}:{}:{}", API_TEST_CASE_TYPE, title, steps_json)),
+        steps_json,
+        expected: Some(expected),
+        priority: priority.filter(|value| !value.trim().is_empty()),
+        status: None,
+        assertions_json: None,
+        created_at: chrono::Utc::now().timestamp_millis(),
+    };
+    state
+        .qa_test_management_use_case
+        .add_test_case(&test_case)
+        .await?;
+
+    add_log(
+        &state.logs,
+        "INFO",
+        "QA",
+        &format!(
+            "Saved API test '{}' ({} {}, session_id={})",
+            test_case.title, request.method, request.url, session.id
+        ),
+    );
+    Ok(test_case)
+}
+
+#[tauri::command]
+pub async fn qa_list_api_tests(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+) -> Result<Vec<QaTestCase>> {
+    state
+        .qa_test_management_use_case
+        .list_test_cases_of_type(&session_id, API_TEST_CASE_TYPE)
+        .await
+}
+
+/// Assertions for the endpoints the session called more than once, from
+/// what their responses had in common; with a checkpoint, for the endpoints
+/// called during it
+#[tauri::command]
+pub async fn qa_suggest_api_assertions(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+    checkpoint_id: Option<String>,
+) -> Result<Vec<QaEndpointAssertions>> {
+    state
+        .qa_ai_use_case
+        .suggest_api_assertions(&session_id, checkpoint_id.as_deref())
+        .await
+}
+
+/// Re-send the session's saved API tests, all of them when `test_case_ids`
+/// is empty, as one `api_regression` run. Each response is recorded as an
+/// API call of the run and each outcome as a test case run.
+#[tauri::command]
+pub async fn qa_run_api_tests(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+    test_case_ids: Option<Vec<String>>,
+) -> Result<QaApiTestRunReport> {
+    let started = Instant::now();
+    let result = run_api_tests(&app, &state, &session_id, test_case_ids.unwrap_or_default()).await;
+    state
+        .telemetry
+        .record(UsageSubsystem::Qa, "api_tests", started, result.is_ok());
+    result
+}
+
+async fn run_api_tests(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    session_id: &str,
+    test_case_ids: Vec<String>,
+) -> Result<QaApiTestRunReport> {
+    let mut tests = state
+        .qa_test_management_use_case
+        .list_test_cases_of_type(session_id, API_TEST_CASE_TYPE)
+        .await?;
+    if let Some(missing) = test_case_ids
+        .iter()
+        .find(|id| !tests.iter().any(|test| &test.id == *id))
+    {
+        return Err(AppError::NotFound(format!(
+            "API test {} not found in this session.",
+            missing
+        )));
+    }
+    if !test_case_ids.is_empty() {
+        tests.retain(|test| test_case_ids.contains(&test.id));
+    }
+    if tests.is_empty() {
+        return Err(AppError::ValidationError(
+            "This session has no API tests to run.".to_string(),
+        ));
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(API_TEST_TIMEOUT)
+        .build()
+        .map_err(|e| AppError::Internal(format!("Failed to build HTTP client: {}", e)))?;
+
+    let run = state
+        .qa_run_use_case
+        .start_run(
+            session_id,
+            "api_regression",
+            "api",
+            "user",
+            None,
+            None,
+            Some(
+                json!({ "testCaseIds": tests.iter().map(|test| &test.id).collect::<Vec<_>>() })
+                    .to_string(),
+            ),
+        )
+        .await?;
+    emit_run_update(app, state, &run);
+    add_log(
+        &state.logs,
+        "INFO",
+        "QA",
+        &format!(
+            "Running {} API test(s) (session_id={}, run_id={})",
+            tests.len(),
+            session_id,
+            run.id
+        ),
+    );
+
+    let mut results = Vec::new();
+    for test in &tests {
+        let result = run_api_test(state, &client, session_id, &run.id, test).await;
+        let result_json = serde_json::to_string(&result).ok();
+        if let Err(err) = state
+            .qa_test_management_use_case
+            .record_test_case_run(
+                session_id,
+                &run.id,
+                &test.id,
+                result.status,
+                result.error.as_deref(),
+                result_json.as_deref(),
+            )
+            .await
+        {
+            add_log(
+                &state.logs,
+                "WARN",
+                "QA",
+                &format!("Failed to record run of API test {}: {}", test.id, err),
+            );
+        }
+
+        let (level, label) = match result.status {
+            QaTestResultStatus::Passed => ("info", "PASS"),
+            QaTestResultStatus::Failed => ("error", "FAIL"),
+            _ => ("error", "BLOCKED"),
+        };
+        append_stream_event(
+            app,
+            state,
+            &run.id,
+            QaRunStreamInput {
+                channel: "api".to_string(),
+                level: level.to_string(),
+                message: format!("{} {}", label, test.title),
+                payload_json: result_json,
+            },
+        )
+        .await;
+        results.push(result);
+    }
+
+    let passed = results
+        .iter()
+        .filter(|result| result.status == QaTestResultStatus::Passed)
+        .count();
+    let failed = results.len() - passed;
+    append_stream_event(
+        app,
+        state,
+        &run.id,
+        QaRunStreamInput {
+            channel: "system".to_string(),
+            level: if failed == 0 { "info" } else { "error" }.to_string(),
+            message: format!("API tests: {} passed, {} failed", passed, failed),
+            payload_json: None,
+        },
+    )
+    .await;
+    let run = state
+        .qa_run_use_case
+        .end_run(&run.id, if failed == 0 { "completed" } else { "failed" })
+        .await?;
+    emit_run_update(app, state, &run);
+
+    Ok(QaApiTestRunReport {
+        run,
+        results,
+        passed,
+        failed,
+    })
+}
+
+/// Send one saved test and check its assertions. A test that cannot be
+/// sent is `blocked` rather than failing the whole run.
+async fn run_api_test(
+    state: &AppState,
+    client: &reqwest::Client,
+    session_id: &str,
+    run_id: &str,
+    test: &QaTestCase,
+) -> QaApiTestResult {
+    let mut result = QaApiTestResult {
+        test_case_id: test.id.clone(),
+        title: test.title.clone(),
+        status: QaTestResultStatus::Blocked,
+        response_status: None,
+        duration_ms: None,
+        assertions: Vec::new(),
+        error: None,
+        api_call_id: None,
+    };
+    let request = match serde_json::from_str::<QaApiRequest>(&test.steps_json) {
+        Ok(request) => request,
+        Err(err) => {
+            result.error = Some(format!("Saved request is unreadable: {}", err));
+            return result;
+        }
+    };
+    let started = Instant::now();
+    let response = match send_api_request(state, client, &request).await {
+        Ok(response) => response,
+        Err(err) => {
+            result.error = Some(err.to_string());
+            return result;
+        }
+    };
+    let status = response.status().as_u16();
+    let headers: Vec<(String, String)> = response
+        .headers()
+        .iter()
+        .filter_map(|(key, value)| Some((key.to_string(), value.to_str().ok()?.to_string())))
+        .collect();
+    let body = match response.text().await {
+        Ok(body) => body,
+        Err(err) => {
+            result.error = Some(format!("Failed to read response body: {}", err));
+            return result;
+        }
+    };
+    let duration_ms = started.elapsed().as_millis() as i64;
+
+    result.response_status = Some(status);
+    result.duration_ms = Some(duration_ms);
+    result.assertions = check_assertions(&request.assertions, status, &headers, &body);
+    let failed = result
+        .assertions
+        .iter()
+        .filter(|assertion| !assertion.passed)
+        .count();
+    if failed == 0 {
+        result.status = QaTestResultStatus::Passed;
+    } else {
+        result.status = QaTestResultStatus::Failed;
+        result.error = Some(format!(
+            "{} of {} assertion(s) failed",
+            failed,
+            result.assertions.len()
+        ));
+    }
+
+    let request_headers: Vec<_> = request
+        .headers
+        .iter()
+        .filter(|header| header.enabled)
+        .map(|header| (header.key.as_str(), header.value.as_str()))
+        .collect();
+    match state
+        .qa_api_call_use_case
+        .record_api_call(
+            session_id,
+            run_id,
+            &request.method,
+            &request.url,
+            serde_json::to_string(&request_headers).ok(),
+            request.body_json.clone(),
+            Some(i64::from(status)),
+            serde_json::to_string(&headers).ok(),
+            Some(body),
+            Some(duration_ms),
+        )
+        .await
+    {
+        Ok(api_call) => result.api_call_id = Some(api_call.id),
+        Err(err) => add_log(
+            &state.logs,
+            "WARN",
+            "QA",
+            &format!("Failed to record API call of test {}: {}", test.id, err),
+        ),
+    }
+    result
+}
+
+fn emit_run_update(app: &tauri::AppHandle, state: &AppState, run: &QaSessionRun) {
+    if let Err(err) = app.emit(QA_RUN_UPDATED_EMIT, run) {
+        add_log(
+            &state.logs,
+            "ERROR",
+            "QA",
+            &format!("Failed to emit run update: {}", err),
+        );
+    }
+}
+
+async fn append_stream_event(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    run_id: &str,
+    input: QaRunStreamInput,
+) {
+    match state
+        .qa_run_use_case
+        .append_stream_event(run_id, input)
+        .await
+    {
+        Ok(stream_event) => {
+            if let Err(err) = app.emit(QA_RUN_STREAM_EMIT, &stream_event) {
+                add_log(
+                    &state.logs,
+                    "ERROR",
+                    "QA",
+                    &format!("Failed to emit run stream: {}", err),
+                );
+            }
+        }
+        Err(err) => add_log(
+            &state.logs,
+            "WARN",
+            "QA",
+            &format!("Failed to append run stream event: {}", err),
+        ),
+    }
+}
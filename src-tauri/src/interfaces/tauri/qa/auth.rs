@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::domain::error::Result;
+use crate::domain::qa_auth::{QaAuthProfileStatus, QaAuthSecret};
+use crate::interfaces::http::add_log;
+use crate::interfaces::tauri::AppState;
+
+/// Stored secrets and cached token of every auth profile
+#[tauri::command]
+pub async fn qa_get_auth_profile_statuses(
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<QaAuthProfileStatus>> {
+    state.qa_auth_use_case.statuses().await
+}
+
+/// Store a client secret or password of an auth profile; an empty value
+/// deletes it
+#[tauri::command]
+pub async fn qa_save_auth_secret(
+    state: State<'_, Arc<AppState>>,
+    profile_id: String,
+    secret: QaAuthSecret,
+    value: String,
+) -> Result<()> {
+    state
+        .qa_auth_use_case
+        .save_secret(&profile_id, secret, &value)
+        .await
+}
+
+/// Fetch a new token for the profile to check its settings. Returns when
+/// the token expires, if the server said.
+#[tauri::command]
+pub async fn qa_test_auth_profile(
+    state: State<'_, Arc<AppState>>,
+    profile_id: String,
+) -> Result<Option<i64>> {
+    let result = state.qa_auth_use_case.test_profile(&profile_id).await;
+    let message = match &result {
+        Ok(_) => format!("Auth profile '{}' issued a token", profile_id),
+        Err(err) => format!("Auth profile '{}' failed: {}", profile_id, err),
+    };
+    let level = if result.is_ok() { "INFO" } else { "WARN" };
+    add_log(&state.logs, level, "QA", &message);
+    result
+}
@@ -4,7 +4,12 @@ use tauri::State;
 
 use crate::domain::error::Result;
 use crate::domain::llm_config::LLMConfig;
-use crate::domain::qa_checkpoint::{QaCheckpoint, QaCheckpointSummary, QaLlmRun, QaTestCase};
+use crate::domain::qa_checkpoint::{
+    QaCheckpoint, QaCheckpointEdit, QaCheckpointRegeneration, QaCheckpointSummary, QaLlmRun,
+    QaLlmRunDetail, QaTestCase, QaTestGenerationMode,
+};
+use crate::domain::settings::LlmFeature;
+use crate::domain::telemetry::UsageSubsystem;
 use crate::interfaces::http::add_log;
 use crate::interfaces::tauri::AppState;
 
@@ -105,6 +110,7 @@ pub async fn qa_generate_checkpoint_summary(
     config: LLMConfig,
     output_language: String,
 ) -> Result<QaCheckpointSummary> {
+    let config = state.model_resolver.resolve(LlmFeature::QaAi, &config);
     add_log(
         &state.logs,
         "INFO",
@@ -114,9 +120,15 @@ pub async fn qa_generate_checkpoint_summary(
             session_id, checkpoint_id, config.model, output_language
         ),
     );
+    let summary = state.qa_ai_use_case.generate_checkpoint_summary(
+        &session_id,
+        &checkpoint_id,
+        &config,
+        &output_language,
+    );
     match state
-        .qa_ai_use_case
-        .generate_checkpoint_summary(&session_id, &checkpoint_id, &config, &output_language)
+        .telemetry
+        .track(UsageSubsystem::Qa, "checkpoint_summary", summary)
         .await
     {
         Ok(summary) => {
@@ -154,19 +166,29 @@ pub async fn qa_generate_test_cases(
     checkpoint_id: String,
     config: LLMConfig,
     output_language: String,
+    mode: Option<QaTestGenerationMode>,
 ) -> Result<Vec<QaTestCase>> {
+    let config = state.model_resolver.resolve(LlmFeature::QaAi, &config);
+    let mode = mode.unwrap_or_default();
     add_log(
         &state.logs,
         "INFO",
         "QA",
         &format!(
-            "QA generate test cases requested (session_id={} checkpoint_id={} model={} language={})",
-            session_id, checkpoint_id, config.model, output_language
+            "QA generate test cases requested (session_id={} checkpoint_id={} model={} language={} mode={:?})",
+            session_id, checkpoint_id, config.model, output_language, mode
         ),
     );
+    let generate = state.qa_ai_use_case.generate_test_cases(
+        &session_id,
+        &checkpoint_id,
+        &config,
+        &output_language,
+        mode,
+    );
     match state
-        .qa_ai_use_case
-        .generate_test_cases(&session_id, &checkpoint_id, &config, &output_language)
+        .telemetry
+        .track(UsageSubsystem::Qa, "test_cases", generate)
         .await
     {
         Ok(cases) => {
@@ -359,3 +381,208 @@ pub async fn qa_list_llm_runs(
         }
     }
 }
+
+/// Full prompt and response of one LLM run, for auditing its output
+#[tauri::command]
+pub async fn qa_get_llm_run_detail(
+    state: State<'_, Arc<AppState>>,
+    run_id: String,
+) -> Result<QaLlmRunDetail> {
+    match state.qa_ai_use_case.get_llm_run_detail(&run_id).await {
+        Ok(detail) => {
+            add_log(
+                &state.logs,
+                "INFO",
+                "QA",
+                &format!(
+                    "QA LLM run detail loaded (run_id={} truncated={})",
+                    run_id, detail.bodies.truncated
+                ),
+            );
+            Ok(detail)
+        }
+        Err(err) => {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "QA",
+                &format!("Failed to load LLM run detail (run_id={}): {}", run_id, err),
+            );
+            Err(err)
+        }
+    }
+}
+
+/// Rename a checkpoint and set its notes; empty values clear them
+#[tauri::command]
+pub async fn qa_update_checkpoint(
+    state: State<'_, Arc<AppState>>,
+    checkpoint_id: String,
+    title: Option<String>,
+    notes: Option<String>,
+) -> Result<QaCheckpoint> {
+    match state
+        .qa_ai_use_case
+        .update_checkpoint(&checkpoint_id, title, notes)
+        .await
+    {
+        Ok(checkpoint) => {
+            add_log(
+                &state.logs,
+                "INFO",
+                "QA",
+                &format!("QA checkpoint updated (checkpoint_id={})", checkpoint_id),
+            );
+            Ok(checkpoint)
+        }
+        Err(err) => {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "QA",
+                &format!(
+                    "Failed to update checkpoint (checkpoint_id={}): {}",
+                    checkpoint_id, err
+                ),
+            );
+            Err(err)
+        }
+    }
+}
+
+/// Merge two adjacent checkpoints; the earlier one keeps its id
+#[tauri::command]
+pub async fn qa_merge_checkpoints(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+    first_checkpoint_id: String,
+    second_checkpoint_id: String,
+) -> Result<QaCheckpointEdit> {
+    add_log(
+        &state.logs,
+        "INFO",
+        "QA",
+        &format!(
+            "QA merge checkpoints requested (session_id={} first={} second={})",
+            session_id, first_checkpoint_id, second_checkpoint_id
+        ),
+    );
+    match state
+        .qa_ai_use_case
+        .merge_checkpoints(&session_id, &first_checkpoint_id, &second_checkpoint_id)
+        .await
+    {
+        Ok(edit) => Ok(edit),
+        Err(err) => {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "QA",
+                &format!(
+                    "Failed to merge checkpoints (session_id={}): {}",
+                    session_id, err
+                ),
+            );
+            Err(err)
+        }
+    }
+}
+
+/// Split a checkpoint so that `at_event_seq` starts a new one
+#[tauri::command]
+pub async fn qa_split_checkpoint(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+    checkpoint_id: String,
+    at_event_seq: i64,
+    title: Option<String>,
+) -> Result<QaCheckpointEdit> {
+    add_log(
+        &state.logs,
+        "INFO",
+        "QA",
+        &format!(
+            "QA split checkpoint requested (checkpoint_id={} at_event_seq={})",
+            checkpoint_id, at_event_seq
+        ),
+    );
+    match state
+        .qa_ai_use_case
+        .split_checkpoint(&session_id, &checkpoint_id, at_event_seq, title)
+        .await
+    {
+        Ok(edit) => Ok(edit),
+        Err(err) => {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "QA",
+                &format!(
+                    "Failed to split checkpoint (checkpoint_id={}): {}",
+                    checkpoint_id, err
+                ),
+            );
+            Err(err)
+        }
+    }
+}
+
+/// Regenerate summaries and test cases for `checkpoint_ids`, or for every
+/// checkpoint merged or split since its latest summary when none are given
+#[tauri::command]
+pub async fn qa_regenerate_checkpoints(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+    checkpoint_ids: Option<Vec<String>>,
+    config: LLMConfig,
+    output_language: String,
+) -> Result<QaCheckpointRegeneration> {
+    let config = state.model_resolver.resolve(LlmFeature::QaAi, &config);
+    add_log(
+        &state.logs,
+        "INFO",
+        "QA",
+        &format!(
+            "QA regenerate checkpoints requested (session_id={} model={} language={})",
+            session_id, config.model, output_language
+        ),
+    );
+    let regenerate = state.qa_ai_use_case.regenerate_checkpoints(
+        &session_id,
+        checkpoint_ids,
+        &config,
+        &output_language,
+    );
+    match state
+        .telemetry
+        .track(UsageSubsystem::Qa, "regenerate_checkpoints", regenerate)
+        .await
+    {
+        Ok(result) => {
+            add_log(
+                &state.logs,
+                "INFO",
+                "QA",
+                &format!(
+                    "QA checkpoints regenerated (session_id={} checkpoints={} test_cases={})",
+                    session_id,
+                    result.checkpoint_ids.len(),
+                    result.test_cases.len()
+                ),
+            );
+            Ok(result)
+        }
+        Err(err) => {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "QA",
+                &format!(
+                    "Failed to regenerate checkpoints (session_id={}): {}",
+                    session_id, err
+                ),
+            );
+            Err(err)
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::application::use_cases::typegen::generate_api_client;
+use crate::domain::error::{AppError, Result};
+use crate::domain::qa_checkpoint::QaGherkinFeature;
+use crate::domain::typegen::ApiClientTarget;
+use crate::interfaces::http::add_log;
+use crate::interfaces::tauri::AppState;
+
+/// Generate a typed API client module from the API calls recorded in a session.
+#[tauri::command]
+pub async fn qa_generate_api_client(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+    target: ApiClientTarget,
+) -> Result<String> {
+    let calls = state
+        .qa_api_call_use_case
+        .list_session_calls(session_id.trim())
+        .await?;
+    if calls.is_empty() {
+        return Err(AppError::ValidationError(format!(
+            "QA session {} has no recorded API calls.",
+            session_id
+        )));
+    }
+
+    let client = generate_api_client(&calls, target).map_err(|err| {
+        add_log(
+            &state.logs,
+            "ERROR",
+            "QA",
+            &format!(
+                "API client generation failed (session_id={}): {}",
+                session_id, err
+            ),
+        );
+        err
+    })?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "QA",
+        &format!(
+            "Generated {:?} API client from {} call(s) (session_id={})",
+            target,
+            calls.len(),
+            session_id
+        ),
+    );
+    Ok(client)
+}
+
+/// Export the test cases of a session as Gherkin feature files, one per
+/// checkpoint. With `output_dir` the files are also written there.
+#[tauri::command]
+pub async fn qa_export_gherkin(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+    output_dir: Option<String>,
+) -> Result<Vec<QaGherkinFeature>> {
+    let features = state.qa_ai_use_case.export_gherkin(&session_id).await?;
+
+    let output_dir = output_dir
+        .as_deref()
+        .map(str::trim)
+        .filter(|dir| !dir.is_empty());
+    if let Some(dir) = output_dir {
+        tokio::fs::create_dir_all(dir).await?;
+        for feature in &features {
+            tokio::fs::write(
+                std::path::Path::new(dir).join(&feature.file_name),
+                &feature.content,
+            )
+            .await?;
+        }
+    }
+
+    add_log(
+        &state.logs,
+        "INFO",
+        "QA",
+        &format!(
+            "Exported {} Gherkin feature(s) with {} scenario(s) (session_id={}{})",
+            features.len(),
+            features.iter().map(|f| f.scenario_count).sum::<usize>(),
+            session_id,
+            output_dir
+                .map(|dir| format!(" dir={}", dir))
+                .unwrap_or_default()
+        ),
+    );
+    Ok(features)
+}
@@ -1,4 +1,5 @@
 use crate::domain::error::{AppError, Result};
+use crate::domain::qa_api_link::QaApiLink;
 use crate::domain::qa_event::{QaEvent, QaEventInput, QaEventPage};
 use crate::domain::qa_run::QaRunStreamInput;
 use std::sync::Arc;
@@ -26,19 +27,16 @@ pub async fn qa_record_event(
         "QA",
         &format!("QA record event requested (type={})", event.event_type),
     );
-    let active_id = {
-        let current = state.qa_session_id.lock().unwrap();
-        session_id.or_else(|| current.clone())
-    };
-
-    let session_id = active_id.ok_or_else(|| {
+    let session_id = state.qa_session_scope(session_id).ok_or_else(|| {
         add_log(
             &state.logs,
             "ERROR",
             "QA",
-            "Failed to record QA event: no active session",
+            "Failed to record QA event: no session given",
         );
-        AppError::ValidationError("No active QA session for event.".to_string())
+        AppError::ValidationError(
+            "No QA session for event. Pass the session id when several are active.".to_string(),
+        )
     })?;
 
     let previous_event = match state
@@ -331,3 +329,47 @@ pub async fn qa_delete_events(
         }
     }
 }
+
+/// Link every `api_response` of the session to the click/submit that
+/// triggered it, replacing the links made while recording
+#[tauri::command]
+pub async fn qa_link_api_calls(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+) -> Result<Vec<QaApiLink>> {
+    match state.qa_event_use_case.link_api_calls(&session_id).await {
+        Ok(links) => {
+            add_log(
+                &state.logs,
+                "INFO",
+                "QA",
+                &format!(
+                    "QA API calls linked (session_id={} links={})",
+                    session_id,
+                    links.len()
+                ),
+            );
+            Ok(links)
+        }
+        Err(err) => {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "QA",
+                &format!(
+                    "Failed to link QA API calls (session_id={}): {}",
+                    session_id, err
+                ),
+            );
+            Err(err)
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn qa_list_api_links(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+) -> Result<Vec<QaApiLink>> {
+    state.qa_event_use_case.list_api_links(&session_id).await
+}
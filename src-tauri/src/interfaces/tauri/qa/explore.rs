@@ -1,6 +1,8 @@
 use crate::application::use_cases::qa_ai::ExploreResult;
 use crate::domain::error::Result;
 use crate::domain::llm_config::LLMConfig;
+use crate::domain::settings::LlmFeature;
+use crate::domain::telemetry::UsageSubsystem;
 use std::sync::Arc;
 use tauri::State;
 
@@ -19,6 +21,7 @@ pub async fn qa_explore_session(
     config: LLMConfig,
     output_language: String,
 ) -> Result<ExploreResult> {
+    let config = state.model_resolver.resolve(LlmFeature::QaAi, &config);
     add_log(
         &state.logs,
         "INFO",
@@ -47,9 +50,12 @@ pub async fn qa_explore_session(
         }),
     );
 
-    match state
+    let explore = state
         .qa_ai_use_case
-        .explore_and_generate_tests(&session_id, &config, &output_language)
+        .explore_and_generate_tests(&session_id, &config, &output_language);
+    match state
+        .telemetry
+        .track(UsageSubsystem::Qa, "explore", explore)
         .await
     {
         Ok(result) => {
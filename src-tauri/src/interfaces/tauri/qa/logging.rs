@@ -9,6 +9,8 @@ pub(crate) const QA_EVENT_EMIT: &str = "qa-event-recorded";
 pub(crate) const QA_RUN_STREAM_EMIT: &str = "qa-run-stream";
 pub(crate) const QA_RUN_UPDATED_EMIT: &str = "qa-run-updated";
 pub(crate) const QA_LOG_EMIT: &str = "qa-log";
+/// Emitted with a `NodeRuntimeProgress` payload while Playwright or Chromium installs
+pub(crate) const QA_RUNTIME_PROGRESS_EMIT: &str = "qa-runtime-progress";
 
 #[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -1,15 +1,22 @@
 pub(crate) mod api;
+pub(crate) mod api_tests;
+pub(crate) mod auth;
 pub(crate) mod checkpoints;
+pub(crate) mod codegen;
 pub(crate) mod devtools;
 pub(crate) mod events;
 pub(crate) mod explore;
 pub(crate) mod logging;
+pub(crate) mod prompts;
 pub(crate) mod recorder;
 pub(crate) mod recorder_internal;
+pub(crate) mod redaction;
 pub(crate) mod replay;
 pub(crate) mod runs;
+pub(crate) mod runtime;
 pub(crate) mod screenshots;
 pub(crate) mod sessions;
+pub(crate) mod test_management;
 
 pub mod types;
 
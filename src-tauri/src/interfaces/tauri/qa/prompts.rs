@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::application::use_cases::qa_ai::{prompt_template_views, QaPromptTemplateView};
+use crate::domain::error::Result;
+use crate::domain::qa_prompts::{QaPromptKind, QaPromptSettings};
+use crate::interfaces::http::add_log;
+use crate::interfaces::tauri::AppState;
+
+/// Every QA system prompt with its built-in text and the team's template
+#[tauri::command]
+pub async fn qa_get_prompt_templates(
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<QaPromptTemplateView>> {
+    Ok(prompt_template_views(
+        &state.settings.get::<QaPromptSettings>(),
+    ))
+}
+
+/// Replace a system prompt with `template`; `{language}` in it becomes the
+/// output language. Bumps the template's version.
+#[tauri::command]
+pub async fn qa_set_prompt_template(
+    state: State<'_, Arc<AppState>>,
+    kind: QaPromptKind,
+    template: String,
+) -> Result<Vec<QaPromptTemplateView>> {
+    let mut settings = state.settings.get::<QaPromptSettings>();
+    settings.set_template(kind, template, chrono::Utc::now().timestamp_millis())?;
+    state.settings.set(&settings)?;
+
+    let version = settings.templates.get(&kind).map_or(0, |t| t.version);
+    add_log(
+        &state.logs,
+        "INFO",
+        "QA",
+        &format!("QA prompt template {:?} saved as version {}", kind, version),
+    );
+    Ok(prompt_template_views(&settings))
+}
+
+/// Go back to the built-in system prompt
+#[tauri::command]
+pub async fn qa_reset_prompt_template(
+    state: State<'_, Arc<AppState>>,
+    kind: QaPromptKind,
+) -> Result<Vec<QaPromptTemplateView>> {
+    let mut settings = state.settings.get::<QaPromptSettings>();
+    if settings.reset(kind, chrono::Utc::now().timestamp_millis()) {
+        state.settings.set(&settings)?;
+        add_log(
+            &state.logs,
+            "INFO",
+            "QA",
+            &format!("QA prompt template {:?} reset to default", kind),
+        );
+    }
+    Ok(prompt_template_views(&settings))
+}
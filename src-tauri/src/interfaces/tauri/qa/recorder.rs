@@ -1,12 +1,9 @@
 use crate::domain::error::{AppError, Result};
-use crate::infrastructure::storage::{
-    ensure_qa_sessions_root, ensure_session_dir,
-    resolve_app_data_dir,
-};
-use std::path::PathBuf;
+use crate::domain::node_runtime::RuntimeScript;
+use crate::infrastructure::storage::{ensure_session_dir, session_storage_state_path};
 use std::process::Stdio;
 use std::sync::Arc;
-use tauri::{Manager, State};
+use tauri::State;
 
 use crate::interfaces::http::add_log;
 
@@ -19,6 +16,8 @@ use crate::interfaces::tauri::AppState;
 use crate::interfaces::tauri::QaRecorderHandle;
 
 use super::logging::{emit_status_log, QaLogContext};
+use super::runtime::prepare_script;
+use super::sessions::selector_attributes;
 
 use super::recorder_internal::{
     record_recorder_event, record_recorder_network, RecorderAuthPayload, RecorderEventPayload,
@@ -63,57 +62,29 @@ pub async fn qa_start_browser_recorder(
         ));
     }
 
-    let mut recorder_guard = state.qa_recorder.lock().unwrap();
-    if recorder_guard.is_some() {
+    if state.qa_recorders.lock().unwrap().contains_key(&session_id) {
+        return Err(AppError::ValidationError(
+            "A browser recorder is already running for this session.".to_string(),
+        ));
+    }
+    // Installs Playwright and Chromium on first use
+    let script_path = prepare_script(&app, &state, RuntimeScript::Recorder).await?;
+    let selector_attributes = selector_attributes(&state, Some(session_id.as_str())).await;
+
+    let mut recorders = state.qa_recorders.lock().unwrap();
+    if recorders.contains_key(&session_id) {
         return Err(AppError::ValidationError(
-            "A browser recorder is already running.".to_string(),
+            "A browser recorder is already running for this session.".to_string(),
         ));
     }
 
-    let app_data_dir = resolve_app_data_dir(&app)
-        .map_err(|err| AppError::Internal(format!("Failed to resolve app data dir: {}", err)))?;
-    let qa_sessions_dir = ensure_qa_sessions_root(&app_data_dir)
+    let qa_sessions_dir = state
+        .storage
+        .ensure_qa_sessions_dir()
         .map_err(|err| AppError::Internal(format!("Failed to ensure QA session root: {}", err)))?;
     let session_dir = ensure_session_dir(&qa_sessions_dir, &session_id)
         .map_err(|err| AppError::Internal(format!("Failed to ensure QA session dir: {}", err)))?;
-    let storage_state_path = session_dir.join(format!("storage_state_{}.json", run_id));
-
-    // Resolve script path - try multiple locations for dev and production
-    let cargo_manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    let resource_dir = app.path().resource_dir().ok();
-
-    let mut script_candidates: Vec<PathBuf> = vec![
-        // Development: script in src-tauri/resources/scripts folder
-        cargo_manifest_dir.join("resources").join("scripts").join("qa-browser-recorder.mjs"),
-    ];
-
-    // Production: bundled resources
-    if let Some(ref res_dir) = resource_dir {
-        script_candidates.push(res_dir.join("scripts").join("qa-browser-recorder.mjs"));
-    }
-
-    let script_path = script_candidates
-        .iter()
-        .find(|path| path.exists())
-        .cloned()
-        .ok_or_else(|| {
-            let candidates_str = script_candidates
-                .iter()
-                .map(|p| p.display().to_string())
-                .collect::<Vec<_>>()
-                .join(", ");
-            AppError::NotFound(format!(
-                "QA recorder script not found. Searched: {}",
-                candidates_str
-            ))
-        })?;
-
-    add_log(
-        &state.logs,
-        "INFO",
-        "QA",
-        &format!("Using recorder script at: {}", script_path.display()),
-    );
+    let storage_state_path = session_storage_state_path(&session_dir, &run_id);
 
     let mut command = TokioCommand::new("node");
     command
@@ -123,7 +94,9 @@ pub async fn qa_start_browser_recorder(
         .arg("--mode")
         .arg(&mode)
         .arg("--storage")
-        .arg(&storage_state_path);
+        .arg(&storage_state_path)
+        .arg("--selector-attributes")
+        .arg(selector_attributes.join(","));
     if let Some(delay_ms) = screenshot_delay_ms {
         command.arg("--screenshot-delay").arg(delay_ms.to_string());
     }
@@ -134,7 +107,7 @@ pub async fn qa_start_browser_recorder(
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
-        .map_err(|err| AppError::Internal(format!("Failed to launch recorder: {}", err)))?;
+        .map_err(|err| AppError::spawn("node", err))?;
 
     let stdout = child
         .stdout
@@ -146,12 +119,16 @@ pub async fn qa_start_browser_recorder(
         .ok_or_else(|| AppError::Internal("Recorder stderr unavailable".to_string()))?;
 
     let child = Arc::new(AsyncMutex::new(child));
-    *recorder_guard = Some(QaRecorderHandle {
-        child: child.clone(),
-        session_id: session_id.clone(),
-        run_id: run_id.clone(),
-        mode: mode.clone(),
-    });
+    recorders.insert(
+        session_id.clone(),
+        QaRecorderHandle {
+            child: child.clone(),
+            session_id: session_id.clone(),
+            run_id: run_id.clone(),
+            mode: mode.clone(),
+        },
+    );
+    drop(recorders);
 
     let state_clone = state.inner().clone();
     let app_clone = app.clone();
@@ -293,12 +270,21 @@ pub async fn qa_start_browser_recorder(
 
     let state_clone = state.inner().clone();
     let app_clone = app.clone();
+    let session_clone = session_id.clone();
     let run_clone = run_id.clone();
     let child_clone = child.clone();
     tauri::async_runtime::spawn(async move {
         let status = child_clone.lock().await.wait().await;
-        let mut guard = state_clone.qa_recorder.lock().unwrap();
-        guard.take();
+        {
+            // A newer run may already record into the same session
+            let mut recorders = state_clone.qa_recorders.lock().unwrap();
+            if recorders
+                .get(&session_clone)
+                .is_some_and(|handle| handle.run_id == run_clone)
+            {
+                recorders.remove(&session_clone);
+            }
+        }
         let message = match status {
             Ok(exit) if exit.success() => "Recorder stopped",
             Ok(exit) => {
@@ -329,7 +315,7 @@ pub async fn qa_start_browser_recorder(
             "success",
             None,
             Some(QaLogContext {
-                session_id: None,
+                session_id: Some(session_clone.clone()),
                 run_id: Some(run_clone.clone()),
                 run_type: None,
                 mode: Some("browser".to_string()),
@@ -362,10 +348,13 @@ pub async fn qa_start_browser_recorder(
     Ok(())
 }
 
+/// Stop the recorder of `session_id`, or the one running `run_id`. With
+/// neither, the only running recorder is stopped.
 #[tauri::command]
 pub async fn qa_stop_browser_recorder(
     app: tauri::AppHandle,
     state: State<'_, Arc<AppState>>,
+    session_id: Option<String>,
     run_id: Option<String>,
 ) -> Result<()> {
     add_log(
@@ -375,26 +364,36 @@ pub async fn qa_stop_browser_recorder(
         "QA stop browser recorder requested",
     );
     let handle = {
-        let mut recorder_guard = state.qa_recorder.lock().unwrap();
-        recorder_guard.take()
-    };
-
-    let handle = match handle {
-        Some(handle) => handle,
-        None => {
-            return Err(AppError::ValidationError(
-                "No active browser recorder.".to_string(),
-            ));
-        }
-    };
-
-    if let Some(expected) = run_id.as_ref() {
-        if expected != &handle.run_id {
+        let mut recorders = state.qa_recorders.lock().unwrap();
+        let key = match (&session_id, &run_id) {
+            (Some(session_id), _) => Some(session_id.clone()),
+            (None, Some(run_id)) => recorders
+                .iter()
+                .find(|(_, handle)| &handle.run_id == run_id)
+                .map(|(key, _)| key.clone()),
+            (None, None) if recorders.len() == 1 => recorders.keys().next().cloned(),
+            (None, None) if recorders.len() > 1 => {
+                return Err(AppError::ValidationError(
+                    "Several browser recorders are running. Pass the session id to stop."
+                        .to_string(),
+                ));
+            }
+            (None, None) => None,
+        };
+        let handle = key
+            .and_then(|key| recorders.remove(&key))
+            .ok_or_else(|| AppError::ValidationError("No active browser recorder.".to_string()))?;
+        if run_id
+            .as_ref()
+            .is_some_and(|expected| expected != &handle.run_id)
+        {
+            recorders.insert(handle.session_id.clone(), handle);
             return Err(AppError::ValidationError(
                 "Recorder run id mismatch.".to_string(),
             ));
         }
-    }
+        handle
+    };
 
     {
         let mut child = handle.child.lock().await;
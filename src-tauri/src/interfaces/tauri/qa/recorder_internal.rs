@@ -14,6 +14,19 @@ use crate::interfaces::tauri::AppState;
 use super::logging::{QA_EVENT_EMIT, QA_RUN_STREAM_EMIT};
 use super::screenshots::persist_screenshot_data_url;
 
+/// Browser recorder events kept as steps; focus, blur and the like only add
+/// noise to a replay. Key presses are limited to navigation keys by the
+/// recorder, and file choices carry the file names and sizes, never contents.
+const RECORDED_EVENT_TYPES: &[&str] = &[
+    "click",
+    "input",
+    "submit",
+    "keydown",
+    "scroll",
+    "navigation",
+    "file_upload",
+];
+
 pub(crate) async fn record_recorder_event(
     app: &tauri::AppHandle,
     state: &Arc<AppState>,
@@ -23,7 +36,7 @@ pub(crate) async fn record_recorder_event(
     payload: RecorderEventPayload,
 ) -> Result<()> {
     let event_type = payload.event_type.to_lowercase();
-    if !matches!(event_type.as_str(), "click" | "input" | "submit") {
+    if !RECORDED_EVENT_TYPES.contains(&event_type.as_str()) {
         return Ok(());
     }
 
@@ -66,7 +79,7 @@ pub(crate) async fn record_recorder_event(
             ),
         );
         if let Err(err) =
-            persist_screenshot_data_url(app, state, session_id, data_url, Some(&recorded.id)).await
+            persist_screenshot_data_url(state, session_id, data_url, Some(&recorded.id)).await
         {
             add_log(
                 &state.logs,
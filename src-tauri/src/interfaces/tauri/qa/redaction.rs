@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::domain::error::Result;
+use crate::domain::qa_redaction::QaRedactionReport;
+use crate::interfaces::http::add_log;
+use crate::interfaces::tauri::AppState;
+
+/// Mask secrets in a session recorded before the current redaction rules
+#[tauri::command]
+pub async fn qa_redact_session(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+) -> Result<QaRedactionReport> {
+    let report = QaRedactionReport {
+        api_calls: state
+            .qa_api_call_use_case
+            .redact_session(&session_id)
+            .await?,
+        events: state.qa_event_use_case.redact_session(&session_id).await?,
+    };
+    add_log(
+        &state.logs,
+        "INFO",
+        "QA",
+        &format!(
+            "Redacted session {}: {} API calls, {} events changed",
+            session_id.trim(),
+            report.api_calls,
+            report.events
+        ),
+    );
+    Ok(report)
+}
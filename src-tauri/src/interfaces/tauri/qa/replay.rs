@@ -1,10 +1,10 @@
 use crate::domain::error::{AppError, Result};
+use crate::domain::node_runtime::RuntimeScript;
 use std::fs;
 use std::io::{BufRead, BufReader};
-use std::path::PathBuf;
 use std::process::{Command as StdCommand, Stdio};
 use std::sync::Arc;
-use tauri::{Manager, State};
+use tauri::State;
 use uuid::Uuid;
 
 use crate::interfaces::http::add_log;
@@ -14,6 +14,8 @@ use serde_json::{self};
 
 use crate::interfaces::tauri::AppState;
 
+use super::runtime::prepare_script;
+use super::sessions::selector_attributes;
 use super::types::{QaBrowserReplayEvent, QaBrowserReplayPayload};
 
 #[tauri::command]
@@ -22,6 +24,7 @@ pub async fn qa_replay_browser(
     state: State<'_, Arc<AppState>>,
     target_url: String,
     events: Vec<QaBrowserReplayEvent>,
+    session_id: Option<String>,
 ) -> Result<()> {
     add_log(
         &state.logs,
@@ -34,7 +37,13 @@ pub async fn qa_replay_browser(
         ),
     );
 
-    let payload = QaBrowserReplayPayload { target_url, events };
+    let script_path = prepare_script(&app, &state, RuntimeScript::Replay).await?;
+
+    let payload = QaBrowserReplayPayload {
+        target_url,
+        events,
+        selector_attributes: selector_attributes(&state, session_id.as_deref()).await,
+    };
     let payload_json = serde_json::to_string(&payload)
         .map_err(|err| AppError::Internal(format!("Replay payload failed: {}", err)))?;
 
@@ -42,35 +51,6 @@ pub async fn qa_replay_browser(
     fs::write(&temp_path, payload_json)
         .map_err(|err| AppError::Internal(format!("Failed to write replay payload: {}", err)))?;
 
-    let cargo_manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    let resource_dir = app.path().resource_dir().ok();
-
-    let mut script_candidates: Vec<PathBuf> = vec![
-        // Development: script in src-tauri/resources/scripts folder
-        cargo_manifest_dir.join("resources").join("scripts").join("qa-browser-replay.mjs"),
-    ];
-
-    // Production: bundled resources
-    if let Some(ref res_dir) = resource_dir {
-        script_candidates.push(res_dir.join("scripts").join("qa-browser-replay.mjs"));
-    }
-
-    let script_path = script_candidates
-        .iter()
-        .find(|path| path.exists())
-        .cloned()
-        .ok_or_else(|| {
-            let candidates_str = script_candidates
-                .iter()
-                .map(|p| p.display().to_string())
-                .collect::<Vec<_>>()
-                .join(", ");
-            AppError::NotFound(format!(
-                "QA replay script not found. Searched: {}",
-                candidates_str
-            ))
-        })?;
-
     let logs = state.logs.clone();
     tauri::async_runtime::spawn_blocking(move || -> Result<()> {
         let mut child = StdCommand::new("node")
@@ -79,7 +59,7 @@ pub async fn qa_replay_browser(
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()
-            .map_err(|err| AppError::Internal(format!("Failed to launch replay: {}", err)))?;
+            .map_err(|err| AppError::spawn("node", err))?;
 
         if let Some(stdout) = child.stdout.take() {
             let reader = BufReader::new(stdout);
@@ -0,0 +1,56 @@
+//! Node.js runtime behind the recorder, replay and web capture scripts:
+//! reports what is installed and prepares the scripts before they run.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tauri::{AppHandle, Emitter, State};
+
+use crate::domain::error::Result;
+use crate::domain::node_runtime::{NodeRuntimeProgress, RecorderEnvironment, RuntimeScript};
+use crate::interfaces::http::add_log;
+use crate::interfaces::tauri::AppState;
+
+use super::logging::QA_RUNTIME_PROGRESS_EMIT;
+
+/// Node.js, Playwright, Chromium and the bundled scripts, each with a hint
+/// on how to fix it when missing
+#[tauri::command]
+pub async fn qa_check_recorder_environment(
+    state: State<'_, Arc<AppState>>,
+) -> Result<RecorderEnvironment> {
+    Ok(state.node_runtime.check().await)
+}
+
+/// Runnable copy of `script`, installing Playwright and Chromium first when
+/// this is their first use
+pub(crate) async fn prepare_script(
+    app: &AppHandle,
+    state: &AppState,
+    script: RuntimeScript,
+) -> Result<PathBuf> {
+    let mut on_progress = |progress: NodeRuntimeProgress| {
+        add_log(&state.logs, "INFO", "QA", &progress.message);
+        let _ = app.emit(QA_RUNTIME_PROGRESS_EMIT, &progress);
+    };
+    let script_path = state
+        .node_runtime
+        .prepare(script, &mut on_progress)
+        .await
+        .map_err(|err| {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "QA",
+                &format!("Node runtime is not ready: {}", err),
+            );
+            err
+        })?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "QA",
+        &format!("Using {} at {}", script.file_name(), script_path.display()),
+    );
+    Ok(script_path)
+}
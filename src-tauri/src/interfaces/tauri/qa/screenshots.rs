@@ -1,9 +1,9 @@
 use crate::domain::error::{AppError, Result};
 use crate::domain::qa_event::QaEvent;
-use crate::infrastructure::storage::{
-    ensure_qa_sessions_root, ensure_session_dir, ensure_session_screenshots_dir,
-    resolve_app_data_dir,
-};
+use crate::domain::qa_highlight::is_highlighted_event;
+use crate::infrastructure::screenshot_annotation::write_annotated_screenshot;
+use crate::infrastructure::storage::{ensure_session_dir, ensure_session_screenshots_dir};
+use std::path::Path;
 use std::sync::Arc;
 use tauri::State;
 use tracing::error;
@@ -49,7 +49,6 @@ pub async fn qa_list_screenshots(
 }
 
 pub(crate) async fn persist_screenshot_data_url(
-    app: &tauri::AppHandle,
     state: &Arc<AppState>,
     session_id: &str,
     data_url: &str,
@@ -68,16 +67,7 @@ pub(crate) async fn persist_screenshot_data_url(
         }
     };
 
-    let app_data_dir = resolve_app_data_dir(app).map_err(|err| {
-        add_log(
-            &state.logs,
-            "ERROR",
-            "QA",
-            &format!("Failed to resolve app data dir: {}", err),
-        );
-        AppError::Internal(err.to_string())
-    })?;
-    let qa_sessions_dir = ensure_qa_sessions_root(&app_data_dir).map_err(|err| {
+    let qa_sessions_dir = state.storage.ensure_qa_sessions_dir().map_err(|err| {
         add_log(
             &state.logs,
             "ERROR",
@@ -109,7 +99,7 @@ pub(crate) async fn persist_screenshot_data_url(
     let artifact_id = Uuid::new_v4().to_string();
     let filename = format!("screenshot_{}_{}.png", now, &artifact_id[..8]);
     let path = screenshots_dir.join(filename);
-    std::fs::write(&path, bytes).map_err(|err| {
+    std::fs::write(&path, &bytes).map_err(|err| {
         add_log(
             &state.logs,
             "ERROR",
@@ -160,6 +150,7 @@ pub(crate) async fn persist_screenshot_data_url(
             path.display()
         ),
     );
+    annotate_screenshot(state, session_id, &resolved_event_id, &bytes, &path).await;
 
     Ok(QaScreenshotResult {
         path: path.to_string_lossy().to_string(),
@@ -168,9 +159,62 @@ pub(crate) async fn persist_screenshot_data_url(
     })
 }
 
+/// Saves the highlighted copy of a click screenshot. Page captures share
+/// the recorded viewport, so the click metadata maps onto the image; a
+/// failure only costs the annotation.
+async fn annotate_screenshot(
+    state: &Arc<AppState>,
+    session_id: &str,
+    event_id: &str,
+    bytes: &[u8],
+    path: &Path,
+) {
+    let event = match state
+        .qa_event_use_case
+        .get_event(session_id, event_id)
+        .await
+    {
+        Ok(Some(event)) if is_highlighted_event(&event.event_type) => event,
+        Ok(_) => return,
+        Err(err) => {
+            add_log(
+                &state.logs,
+                "WARN",
+                "QA",
+                &format!(
+                    "Screenshot annotation skipped (event_id={}): {}",
+                    event_id, err
+                ),
+            );
+            return;
+        }
+    };
+    match write_annotated_screenshot(bytes, path, &event.event_type, event.meta_json.as_deref()) {
+        Ok(Some(annotated)) => add_log(
+            &state.logs,
+            "INFO",
+            "QA",
+            &format!(
+                "QA screenshot annotated: event_id={} path={}",
+                event_id,
+                annotated.display()
+            ),
+        ),
+        Ok(None) => {}
+        Err(err) => add_log(
+            &state.logs,
+            "WARN",
+            "QA",
+            &format!(
+                "Failed to annotate screenshot (event_id={}): {}",
+                event_id, err
+            ),
+        ),
+    }
+}
+
 #[tauri::command]
 pub async fn qa_capture_screenshot(
-    app: tauri::AppHandle,
     state: State<'_, Arc<AppState>>,
     session_id: String,
     data_url: String,
@@ -200,7 +244,7 @@ pub async fn qa_capture_screenshot(
         ));
     }
 
-    persist_screenshot_data_url(&app, &state, &session_id, &data_url, event_id.as_deref()).await
+    persist_screenshot_data_url(&state, &session_id, &data_url, event_id.as_deref()).await
 }
 
 fn decode_data_url(data_url: &str) -> std::result::Result<(String, Vec<u8>), String> {
@@ -282,7 +326,6 @@ fn intersection_area(rect: QaCaptureRect, bounds: QaCaptureRect) -> u64 {
 /// Coordinates are in screen coordinates (not window-relative).
 #[tauri::command]
 pub async fn qa_capture_native_screenshot(
-    app: tauri::AppHandle,
     state: State<'_, Arc<AppState>>,
     session_id: String,
     x: i32,
@@ -517,16 +560,7 @@ pub async fn qa_capture_native_screenshot(
     }
 
     // Save to file
-    let app_data_dir = resolve_app_data_dir(&app).map_err(|err| {
-        add_log(
-            &state.logs,
-            "ERROR",
-            "QA",
-            &format!("Failed to resolve app data dir: {}", err),
-        );
-        AppError::Internal(err.to_string())
-    })?;
-    let qa_sessions_dir = ensure_qa_sessions_root(&app_data_dir).map_err(|err| {
+    let qa_sessions_dir = state.storage.ensure_qa_sessions_dir().map_err(|err| {
         add_log(
             &state.logs,
             "ERROR",
@@ -1,5 +1,8 @@
 use crate::domain::error::{AppError, Result};
-use crate::domain::qa_session::QaSession;
+use crate::domain::qa_retention::{QaCleanupReport, QaRetentionSettings};
+use crate::domain::qa_selectors::QaSelectorSettings;
+use crate::domain::qa_session::{QaActiveSession, QaSession};
+use std::collections::{BTreeSet, HashSet};
 use std::sync::Arc;
 use tauri::State;
 use tracing::error;
@@ -11,6 +14,9 @@ use crate::interfaces::tauri::AppState;
 
 use super::logging::{emit_status_log, QaLogContext};
 
+const NO_SESSION_TO_STOP: &str =
+    "No QA session to stop. Pass the session id when several are active.";
+
 #[tauri::command]
 pub async fn qa_start_session(
     app: tauri::AppHandle,
@@ -24,29 +30,9 @@ pub async fn qa_start_session(
     auth_profile_json: Option<String>,
     source_session_id: Option<String>,
     notes: Option<String>,
+    selector_attributes: Option<Vec<String>>,
 ) -> Result<QaSession> {
     add_log(&state.logs, "INFO", "QA", "QA start session requested");
-    if state.qa_session_id.lock().unwrap().is_some() {
-        add_log(
-            &state.logs,
-            "ERROR",
-            "QA",
-            "Failed to start QA session: session already active",
-        );
-        emit_status_log(
-            &app,
-            &state.logs,
-            "ERROR",
-            "QA",
-            "Failed to start QA session",
-            "failed",
-            Some("A QA session is already active."),
-            None,
-        );
-        return Err(AppError::ValidationError(
-            "A QA session is already active.".to_string(),
-        ));
-    }
 
     let app_version = Some(app.package_info().version.to_string());
     let os = Some(std::env::consts::OS.to_string());
@@ -65,6 +51,7 @@ pub async fn qa_start_session(
             app_version,
             os,
             notes,
+            selector_attributes,
         )
         .await
     {
@@ -85,7 +72,11 @@ pub async fn qa_start_session(
         }
     };
 
-    *state.qa_session_id.lock().unwrap() = Some(session.id.clone());
+    state
+        .qa_sessions
+        .lock()
+        .unwrap()
+        .insert(session.id.clone(), session.clone());
     add_log(
         &state.logs,
         "INFO",
@@ -124,17 +115,12 @@ pub async fn qa_end_session(
     session_id: Option<String>,
 ) -> Result<QaSession> {
     add_log(&state.logs, "INFO", "QA", "QA end session requested");
-    let active_id = {
-        let current = state.qa_session_id.lock().unwrap();
-        session_id.or_else(|| current.clone())
-    };
-
-    let session_id = active_id.ok_or_else(|| {
+    let session_id = state.qa_session_scope(session_id).ok_or_else(|| {
         add_log(
             &state.logs,
             "ERROR",
             "QA",
-            "Failed to end QA session: no active session",
+            "Failed to end QA session: no session given",
         );
         emit_status_log(
             &app,
@@ -143,10 +129,10 @@ pub async fn qa_end_session(
             "QA",
             "Failed to end QA session",
             "failed",
-            Some("No active QA session to stop."),
+            Some(NO_SESSION_TO_STOP),
             None,
         );
-        AppError::ValidationError("No active QA session to stop.".to_string())
+        AppError::ValidationError(NO_SESSION_TO_STOP.to_string())
     })?;
 
     let session = match state.qa_session_use_case.end_session(&session_id).await {
@@ -174,7 +160,7 @@ pub async fn qa_end_session(
             return Err(err);
         }
     };
-    *state.qa_session_id.lock().unwrap() = None;
+    state.qa_sessions.lock().unwrap().remove(&session.id);
     add_log(
         &state.logs,
         "INFO",
@@ -255,6 +241,55 @@ pub async fn qa_get_session(
     }
 }
 
+/// Give the session its own selector attributes, in priority order; `None`
+/// or an empty list goes back to the `qaSelectors` settings
+#[tauri::command]
+pub async fn qa_set_session_selectors(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+    attributes: Option<Vec<String>>,
+) -> Result<QaSession> {
+    let session = state
+        .qa_session_use_case
+        .set_selector_attributes(session_id.trim(), attributes)
+        .await?;
+    if let Some(active) = state.qa_sessions.lock().unwrap().get_mut(&session.id) {
+        active.selector_attributes = session.selector_attributes.clone();
+    }
+    add_log(
+        &state.logs,
+        "INFO",
+        "QA",
+        &format!(
+            "QA session selectors set (id={}): {}",
+            session.id,
+            session
+                .selector_attributes
+                .as_ref()
+                .map(|attributes| attributes.join(", "))
+                .unwrap_or_else(|| "from settings".to_string())
+        ),
+    );
+    Ok(session)
+}
+
+/// Selector attributes the recorders and replay use for the session, or
+/// the configured ones without a session
+#[tauri::command]
+pub async fn qa_get_selector_attributes(
+    state: State<'_, Arc<AppState>>,
+    session_id: Option<String>,
+) -> Result<Vec<String>> {
+    Ok(selector_attributes(&state, session_id.as_deref()).await)
+}
+
+pub(super) async fn selector_attributes(state: &AppState, session_id: Option<&str>) -> Vec<String> {
+    state
+        .qa_session_use_case
+        .selector_attributes(&state.settings.get::<QaSelectorSettings>(), session_id)
+        .await
+}
+
 #[tauri::command]
 pub async fn qa_delete_session(state: State<'_, Arc<AppState>>, session_id: String) -> Result<u64> {
     add_log(
@@ -269,10 +304,7 @@ pub async fn qa_delete_session(state: State<'_, Arc<AppState>>, session_id: Stri
                 &state.logs,
                 "INFO",
                 "QA",
-                &format!(
-                    "QA delete session success (session_id={} deleted={})",
-                    session_id, deleted
-                ),
+                &format!("QA session moved to trash (session_id={})", session_id),
             );
             Ok(deleted)
         }
@@ -290,3 +322,78 @@ pub async fn qa_delete_session(state: State<'_, Arc<AppState>>, session_id: Stri
         }
     }
 }
+
+/// Sessions started and not yet ended, plus any session a browser recorder
+/// is writing into
+#[tauri::command]
+pub async fn qa_list_active_sessions(
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<QaActiveSession>> {
+    let sessions = state.qa_sessions.lock().unwrap();
+    let recorders = state.qa_recorders.lock().unwrap();
+    let ids: BTreeSet<&String> = sessions.keys().chain(recorders.keys()).collect();
+    Ok(ids
+        .into_iter()
+        .map(|id| {
+            let recorder = recorders.get(id);
+            QaActiveSession {
+                session_id: id.clone(),
+                session_type: sessions.get(id).map(|s| s.session_type.clone()),
+                recorder_run_id: recorder.map(|r| r.run_id.clone()),
+                recorder_mode: recorder.map(|r| r.mode.clone()),
+            }
+        })
+        .collect())
+}
+
+/// Apply the `qaRetention` limits now. Active sessions are left alone.
+/// With `dry_run` nothing is deleted and the report shows what would be.
+#[tauri::command]
+pub async fn qa_cleanup_storage(
+    state: State<'_, Arc<AppState>>,
+    dry_run: bool,
+) -> Result<QaCleanupReport> {
+    let policy = state.settings.get::<QaRetentionSettings>();
+    let active_session_ids: HashSet<String> = {
+        let sessions = state.qa_sessions.lock().unwrap();
+        let recorders = state.qa_recorders.lock().unwrap();
+        sessions.keys().chain(recorders.keys()).cloned().collect()
+    };
+    match state
+        .qa_session_use_case
+        .cleanup_storage(&policy, &active_session_ids, dry_run)
+        .await
+    {
+        Ok(report) => {
+            let level = if report.errors.is_empty() {
+                "INFO"
+            } else {
+                "WARN"
+            };
+            add_log(&state.logs, level, "QA", &cleanup_summary(&report));
+            Ok(report)
+        }
+        Err(err) => {
+            error!(error = %err, "QA storage cleanup failed");
+            add_log(
+                &state.logs,
+                "ERROR",
+                "QA",
+                &format!("QA storage cleanup failed: {}", err),
+            );
+            Err(err)
+        }
+    }
+}
+
+pub(crate) fn cleanup_summary(report: &QaCleanupReport) -> String {
+    format!(
+        "QA storage cleanup{}: {} expired sessions, {} events, {} screenshots over the cap, {} bytes freed, {} errors",
+        if report.dry_run { " (dry run)" } else { "" },
+        report.expired_sessions.len(),
+        report.events_removed,
+        report.screenshots_removed,
+        report.bytes_freed,
+        report.errors.len()
+    )
+}
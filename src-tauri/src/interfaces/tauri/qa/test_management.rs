@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::domain::error::{AppError, ErrorCode, Result};
+use crate::domain::qa_test_management::{
+    QaTestManagementCredentials, QaTestManagementSettings, QaTestResultInput, QaTestSyncEntry,
+    QaTestSyncReport, TestManagementProvider,
+};
+use crate::interfaces::http::add_log;
+use crate::interfaces::tauri::AppState;
+
+/// Push test cases of a session to Xray or TestRail; all of them when
+/// `test_case_ids` is empty.
+#[tauri::command]
+pub async fn qa_push_test_cases(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+    provider: TestManagementProvider,
+    test_case_ids: Option<Vec<String>>,
+) -> Result<QaTestSyncReport> {
+    let settings = state.settings.get::<QaTestManagementSettings>();
+    let secret = provider_secret(&state, provider)?;
+    let report = state
+        .qa_test_management_use_case
+        .push_test_cases(
+            &session_id,
+            provider,
+            &test_case_ids.unwrap_or_default(),
+            &settings,
+            &secret,
+        )
+        .await?;
+
+    add_log(
+        &state.logs,
+        "INFO",
+        "QA",
+        &format!(
+            "Pushed test cases to {} (session_id={} created={} skipped={} failed={})",
+            provider.display_name(),
+            session_id,
+            report.created,
+            report.skipped,
+            report.failed
+        ),
+    );
+    Ok(report)
+}
+
+/// Push the results of one test run to Xray or TestRail
+#[tauri::command]
+pub async fn qa_push_test_results(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+    provider: TestManagementProvider,
+    run_name: String,
+    results: Vec<QaTestResultInput>,
+) -> Result<QaTestSyncReport> {
+    let settings = state.settings.get::<QaTestManagementSettings>();
+    let secret = provider_secret(&state, provider)?;
+    let report = state
+        .qa_test_management_use_case
+        .push_test_results(
+            &session_id,
+            provider,
+            &run_name,
+            &results,
+            &settings,
+            &secret,
+        )
+        .await?;
+
+    add_log(
+        &state.logs,
+        "INFO",
+        "QA",
+        &format!(
+            "Pushed {} test result(s) to {} (session_id={} run={})",
+            report.created,
+            provider.display_name(),
+            session_id,
+            report.run_key.as_deref().unwrap_or("-")
+        ),
+    );
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn qa_list_test_sync_log(
+    state: State<'_, Arc<AppState>>,
+    session_id: String,
+    limit: Option<i64>,
+) -> Result<Vec<QaTestSyncEntry>> {
+    state
+        .qa_test_management_use_case
+        .list_sync_log(&session_id, limit)
+        .await
+}
+
+/// Store the Xray client secret or TestRail API key; an empty one removes it
+#[tauri::command]
+pub fn qa_save_test_management_secret(
+    state: State<'_, Arc<AppState>>,
+    provider: TestManagementProvider,
+    secret: String,
+) -> Result<()> {
+    state
+        .config_service
+        .save_test_management_secret(provider, &secret)
+}
+
+#[tauri::command]
+pub fn qa_get_test_management_credentials(
+    state: State<'_, Arc<AppState>>,
+) -> Result<QaTestManagementCredentials> {
+    let stored = |provider| {
+        state
+            .config_service
+            .find_test_management_secret(provider)
+            .map(|secret| secret.is_some())
+    };
+    Ok(QaTestManagementCredentials {
+        xray: stored(TestManagementProvider::Xray)?,
+        testrail: stored(TestManagementProvider::Testrail)?,
+    })
+}
+
+fn provider_secret(state: &AppState, provider: TestManagementProvider) -> Result<String> {
+    state
+        .config_service
+        .find_test_management_secret(provider)?
+        .ok_or_else(|| {
+            let name = match provider {
+                TestManagementProvider::Xray => "Xray client secret",
+                TestManagementProvider::Testrail => "TestRail API key",
+            };
+            AppError::coded(ErrorCode::ConfigMissing, format!("{} is not set", name)).with_hint(
+                format!("Save it under {} in Settings", provider.display_name()),
+            )
+        })
+}
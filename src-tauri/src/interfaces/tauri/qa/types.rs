@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::domain::qa_api_test::{QaApiAssertion, QaApiAssertionResult};
+
 #[derive(serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QaScreenshotResult {
@@ -8,7 +10,7 @@ pub struct QaScreenshotResult {
     pub artifact_id: String,
 }
 
-#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QaApiKeyValue {
     pub key: String,
@@ -16,7 +18,7 @@ pub struct QaApiKeyValue {
     pub enabled: bool,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QaApiFormField {
     pub key: String,
@@ -27,7 +29,7 @@ pub struct QaApiFormField {
     pub enabled: bool,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QaApiRequest {
     pub method: String,
@@ -38,6 +40,12 @@ pub struct QaApiRequest {
     pub body_json: Option<String>,
     pub form_data: Vec<QaApiFormField>,
     pub source: Option<String>,
+    /// Auth profile whose OAuth2 token signs the request
+    #[serde(default)]
+    pub auth_profile_id: Option<String>,
+    /// Checked against the response; kept when the request is saved as a test
+    #[serde(default)]
+    pub assertions: Vec<QaApiAssertion>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -49,6 +57,10 @@ pub struct QaBrowserReplayEvent {
     pub url: Option<String>,
     pub ts: i64,
     pub seq: i64,
+    /// Recorded event metadata; its frame and shadow paths locate `selector`,
+    /// and its selector attributes stand in when `selector` matches nothing
+    #[serde(default)]
+    pub meta_json: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -56,6 +68,8 @@ pub struct QaBrowserReplayEvent {
 pub struct QaBrowserReplayPayload {
     pub target_url: String,
     pub events: Vec<QaBrowserReplayEvent>,
+    /// Priority of the recorded attributes to find an element by
+    pub selector_attributes: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -66,4 +80,6 @@ pub struct QaApiResponse {
     pub headers: Vec<QaApiKeyValue>,
     pub body: String,
     pub content_type: Option<String>,
+    /// One per assertion of the request, in order
+    pub assertion_results: Vec<QaApiAssertionResult>,
 }
@@ -0,0 +1,386 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use enigo::Enigo;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::application::use_cases::output_language::generate_in;
+use crate::application::use_cases::rag_answer_cache::AnswerCacheKey;
+use crate::application::use_cases::translate::TranslateOptions;
+use crate::application::QueryResult;
+use crate::domain::error::{AppError, Result};
+use crate::domain::settings::{LlmFeature, QuickActionSettings};
+use crate::infrastructure::response::clean_llm_response;
+use crate::interfaces::http::add_log;
+use crate::interfaces::quick_action;
+use crate::interfaces::shortcuts::auto_paste;
+
+use super::rag_commands::search_chat::{log_cached_answer, run_chat_with_context};
+use super::rag_commands::types::ChatWithContextRequest;
+use super::state::AppState;
+
+/// Time for the previously focused app to regain focus before pasting
+const REFOCUS_DELAY: Duration = Duration::from_millis(150);
+
+const RAG_CITED_SYSTEM_PROMPT: &str = "You are a helpful assistant answering questions based on the provided context. If the context is insufficient, say so instead of guessing. Keep the answer short and cite the sources you use in [Source: type_id] format.";
+
+/// Keeps cited popup answers apart from other answers in the answer cache
+const ANSWER_CACHE_VARIANT: &str = "quick_action";
+
+/// `[Source: text_chunk_12]`, or several ids separated by commas
+static SOURCE_MARKER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s*\[Source:\s*([^\]]+)\]").unwrap());
+
+/// Characters of a source shown under a popup answer
+const SNIPPET_CHARS: usize = 240;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuickAction {
+    Translate,
+    Enhance,
+    RagChat,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickActionResult {
+    pub action: QuickAction,
+    pub output: String,
+
+    /// Source language picked by auto-detection, for `translate`
+    pub detected_lang: Option<String>,
+
+    /// Sources behind a `rag_chat` answer, numbered as cited in `output`
+    pub citations: Vec<QuickActionCitation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickActionCitation {
+    /// The `[n]` marker used in the answer
+    pub index: usize,
+    pub doc_name: Option<String>,
+    pub page_number: Option<i64>,
+    pub source_type: String,
+    pub source_id: i64,
+    pub snippet: String,
+    pub score: Option<f32>,
+}
+
+/// Take the text captured by the popup shortcut. Returns it once.
+#[tauri::command]
+pub async fn quick_action_take_capture(state: State<'_, Arc<AppState>>) -> Result<Option<String>> {
+    Ok(state.quick_action_capture.lock().unwrap().take())
+}
+
+/// Choices the popup makes per request; unset ones come from Settings and
+/// the main window
+#[derive(Debug, Clone, Default)]
+pub(crate) struct QuickActionOptions {
+    pub collection_id: Option<i64>,
+    pub preset_id: Option<String>,
+    /// Languages for `translate` instead of the ones last synced from the main window
+    pub source_lang: Option<String>,
+    pub target_lang: Option<String>,
+}
+
+/// Run `action` on `text` with the model and languages last synced from the main window
+#[tauri::command]
+pub async fn quick_action_submit(
+    state: State<'_, Arc<AppState>>,
+    action: QuickAction,
+    text: String,
+    collection_id: Option<i64>,
+    preset_id: Option<String>,
+) -> Result<QuickActionResult> {
+    run_quick_action(
+        &state,
+        action,
+        text,
+        QuickActionOptions {
+            collection_id,
+            preset_id,
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+/// Shared by the popup and automation commands
+pub(crate) async fn run_quick_action(
+    state: &AppState,
+    action: QuickAction,
+    text: String,
+    options: QuickActionOptions,
+) -> Result<QuickActionResult> {
+    let QuickActionOptions {
+        collection_id,
+        preset_id,
+        source_lang,
+        target_lang,
+    } = options;
+    if text.trim().is_empty() {
+        return Err(AppError::ValidationError("Nothing to process".to_string()));
+    }
+
+    add_log(
+        &state.logs,
+        "INFO",
+        "QuickAction",
+        &format!("Running {:?} on {} chars", action, text.chars().count()),
+    );
+
+    let mut citations = Vec::new();
+    let (output, detected_lang) = match action {
+        QuickAction::Translate => {
+            let config = state.feature_config(LlmFeature::Translate);
+            let source =
+                source_lang.unwrap_or_else(|| state.preferred_source.lock().unwrap().clone());
+            let target =
+                target_lang.unwrap_or_else(|| state.preferred_target.lock().unwrap().clone());
+            let prompt = state
+                .translate_use_case
+                .execute_with_options(&config, text, source, target, &TranslateOptions::default())
+                .await?;
+            (prompt.result.unwrap_or_default(), prompt.detected_lang)
+        }
+        QuickAction::Enhance => {
+            let config = state.feature_config(LlmFeature::Enhance);
+            let language = state.output_language(None);
+            let preset = state.enhance_preset(preset_id.as_deref(), false)?;
+            let prompt = state
+                .enhance_use_case
+                .execute_with_preset(&config, text, None, preset.as_ref(), language.as_ref())
+                .await?;
+            (prompt.result.unwrap_or_default(), None)
+        }
+        QuickAction::RagChat => {
+            let config = state.feature_config(LlmFeature::RagChat);
+            let settings = state.settings.get::<QuickActionSettings>();
+            let collection_id = collection_id
+                .or(settings.rag_collection_id)
+                .ok_or_else(|| {
+                    AppError::ValidationError(
+                        "Pick a collection to ask or set a default one in settings".to_string(),
+                    )
+                })?;
+            let start = Instant::now();
+            let language = state.output_language(None);
+            let provider = config.provider.to_string();
+            state
+                .deferred_init
+                .ensure()
+                .await
+                .map_err(AppError::DatabaseError)?;
+            let key = AnswerCacheKey {
+                query: &text,
+                top_k: settings.rag_top_k,
+                provider: &provider,
+                model: &config.model,
+                language: language.as_ref().map(|language| language.language.as_str()),
+                variant: ANSWER_CACHE_VARIANT,
+            };
+            let lookup = state
+                .rag_answer_cache
+                .lookup(collection_id, &key, false)
+                .await;
+
+            let (answer, results) = match lookup.cached {
+                Some(cached) => {
+                    log_cached_answer(
+                        state,
+                        collection_id,
+                        &text,
+                        cached.answer.len(),
+                        cached.hit_count,
+                        start,
+                    );
+                    (cached.answer, cached.results)
+                }
+                None => {
+                    let chat = run_chat_with_context(
+                        state,
+                        ChatWithContextRequest {
+                            collection_id,
+                            query: text.clone(),
+                            conversation_id: None,
+                            messages: None,
+                            top_k: Some(settings.rag_top_k),
+                            enable_verification: false,
+                            language: None,
+                            provider: Some(provider.clone()),
+                            model: Some(config.model.clone()),
+                        },
+                    )
+                    .await?;
+                    let raw = generate_in(
+                        language.as_ref(),
+                        state.llm_client.as_ref(),
+                        &config,
+                        RAG_CITED_SYSTEM_PROMPT,
+                        &chat.prompt,
+                    )
+                    .await?;
+                    let answer = clean_llm_response(&raw);
+                    if let Some(slot) = &lookup.slot {
+                        state
+                            .rag_answer_cache
+                            .store(slot, &answer, &chat.results)
+                            .await;
+                    }
+                    (answer, chat.results)
+                }
+            };
+            let (answer, cited) = number_citations(&answer, &results);
+            citations = cited;
+            (answer, None)
+        }
+    };
+
+    Ok(QuickActionResult {
+        action,
+        output,
+        detected_lang,
+        citations,
+    })
+}
+
+/// Replace the `[Source: type_id]` markers in `answer` with `[n]` and list
+/// the cited sources in that order. Markers naming no retrieved source are
+/// dropped; an answer citing nothing lists every retrieved source.
+fn number_citations(answer: &str, results: &[QueryResult]) -> (String, Vec<QuickActionCitation>) {
+    let mut cited: Vec<usize> = Vec::new();
+    let numbered = SOURCE_MARKER.replace_all(answer, |caps: &regex::Captures| {
+        let mut numbers = Vec::new();
+        for id in caps[1].split(',').map(str::trim) {
+            let Some(position) = results.iter().position(|result| {
+                format!("{}_{}", result.source_type, result.source_id).eq_ignore_ascii_case(id)
+            }) else {
+                continue;
+            };
+            let number = match cited.iter().position(|&p| p == position) {
+                Some(existing) => existing + 1,
+                None => {
+                    cited.push(position);
+                    cited.len()
+                }
+            };
+            if !numbers.contains(&number) {
+                numbers.push(number);
+            }
+        }
+        numbers
+            .iter()
+            .map(|number| format!(" [{}]", number))
+            .collect::<String>()
+    });
+
+    if cited.is_empty() {
+        cited = (0..results.len()).collect();
+    }
+    let citations = cited
+        .into_iter()
+        .enumerate()
+        .map(|(idx, position)| {
+            let result = &results[position];
+            QuickActionCitation {
+                index: idx + 1,
+                doc_name: result.doc_name.clone(),
+                page_number: result.page_number,
+                source_type: result.source_type.clone(),
+                source_id: result.source_id,
+                snippet: result.content.chars().take(SNIPPET_CHARS).collect(),
+                score: result.score,
+            }
+        })
+        .collect();
+    (numbered.into_owned(), citations)
+}
+
+/// Copy `text` to the clipboard, hide the window and optionally paste into the app below
+#[tauri::command]
+pub async fn quick_action_apply(
+    app: AppHandle,
+    state: State<'_, Arc<AppState>>,
+    text: String,
+    paste: bool,
+) -> Result<()> {
+    app.clipboard()
+        .write_text(text)
+        .map_err(|e| AppError::Internal(format!("Failed to write clipboard: {}", e)))?;
+    quick_action::hide(&app);
+
+    let pasted = if paste {
+        tokio::time::sleep(REFOCUS_DELAY).await;
+        let mut enigo = Enigo::new();
+        auto_paste(&mut enigo).await
+    } else {
+        false
+    };
+
+    add_log(
+        &state.logs,
+        "INFO",
+        "QuickAction",
+        if pasted {
+            "Result pasted"
+        } else {
+            "Result copied"
+        },
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn quick_action_close(app: AppHandle) -> Result<()> {
+    quick_action::hide(&app);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(source_type: &str, source_id: i64, content: &str) -> QueryResult {
+        QueryResult {
+            content: content.to_string(),
+            source_type: source_type.to_string(),
+            source_id,
+            score: Some(0.8),
+            page_number: Some(2),
+            page_offset: None,
+            doc_name: Some("guide.pdf".to_string()),
+            snippet: None,
+        }
+    }
+
+    #[test]
+    fn numbers_cited_sources_in_order_of_first_use() {
+        let results = vec![
+            result("pdf_chunk", 1, "first"),
+            result("pdf_chunk", 2, "second"),
+        ];
+        let (answer, citations) = number_citations(
+            "Tokens expire [Source: pdf_chunk_2]. Refresh them [Source: pdf_chunk_2, PDF_CHUNK_1] or ask [Source: pdf_chunk_9].",
+            &results,
+        );
+        // Numbered by first citation, not by retrieval rank
+        assert_eq!(answer, "Tokens expire [1]. Refresh them [1] [2] or ask.");
+        assert_eq!(citations.len(), 2);
+        assert_eq!((citations[0].index, citations[0].source_id), (1, 2));
+        assert_eq!((citations[1].index, citations[1].source_id), (2, 1));
+    }
+
+    #[test]
+    fn lists_every_source_when_nothing_is_cited() {
+        let results = vec![result("text_chunk", 5, &"x".repeat(500))];
+        let (answer, citations) = number_citations("No markers here.", &results);
+        assert_eq!(answer, "No markers here.");
+        assert_eq!(citations.len(), 1);
+        assert_eq!(citations[0].snippet.chars().count(), SNIPPET_CHARS);
+    }
+}
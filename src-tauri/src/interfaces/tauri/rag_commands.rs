@@ -5,15 +5,24 @@ pub(crate) mod analytics_cache_metrics;
 pub(crate) mod chunks;
 pub(crate) mod collections;
 pub(crate) mod config_feedback;
+pub(crate) mod connectors;
 pub(crate) mod conversation;
 pub(crate) mod csv;
 pub(crate) mod db_connections;
 pub(crate) mod db_query_rag;
 pub(crate) mod documents;
+pub(crate) mod emails;
+pub(crate) mod embedding_models;
+pub(crate) mod git_repos;
+pub(crate) mod import_templates;
+pub(crate) mod ocr_languages;
+pub(crate) mod ocr_queue;
 pub(crate) mod quality_warnings_gaps;
 pub(crate) mod query_templates;
 pub(crate) mod rag_settings;
 pub(crate) mod search_chat;
+pub(crate) mod tools;
 pub(crate) mod types;
 pub(crate) mod web_ocr_validation;
+pub(crate) mod writing_memory;
 
@@ -7,14 +7,21 @@
 //! - Document quality analysis
 //! - System statistics
 
+use crate::application::use_cases::embedding_service::EmbeddingThroughputStats;
 use crate::application::use_cases::rag_analytics::{AnalyticsEvent, AnalyticsSummary};
 use crate::application::use_cases::rag_ingestion::DocumentQualityAnalysis;
 use crate::application::use_cases::rag_metrics::{
     AggregatedMetrics, DocumentQualityMetrics, DocumentQualitySummary, ExperimentConfig,
     RagOperationMetrics,
 };
+use crate::application::use_cases::rag_pruning::{
+    build_pruning_report, PruningOptions, PruningReport,
+};
 use crate::application::use_cases::retrieval_service::RetrievalCacheStats;
-use crate::domain::error::Result;
+use crate::domain::error::{AppError, Result};
+use crate::infrastructure::db::rag::repository::{
+    ChunkUsage, CollectionChunkUsage, DocumentUsage,
+};
 use crate::interfaces::http::add_log;
 use serde::Serialize;
 use std::sync::Arc;
@@ -214,15 +221,33 @@ pub async fn rag_get_retrieval_cache_stats(
     Ok(stats)
 }
 
+/// Embedding throughput across batched runs since startup
+
+#[tauri::command]
+pub async fn rag_get_embedding_throughput(
+    state: State<'_, Arc<super::AppState>>,
+) -> Result<EmbeddingThroughputStats> {
+    Ok(state.embedding_service.throughput_stats())
+}
+
 /// Clear retrieval cache
 
 #[tauri::command]
 pub async fn rag_clear_retrieval_cache(state: State<'_, Arc<super::AppState>>) -> Result<String> {
     add_log(&state.logs, "INFO", "RAG", "Clearing retrieval cache");
-    state.retrieval_service.clear_cache();
+    state.retrieval_service.clear_cache().await?;
     Ok("Retrieval cache cleared successfully".to_string())
 }
 
+/// Clear cached RAG answers
+
+#[tauri::command]
+pub async fn rag_clear_answer_cache(state: State<'_, Arc<super::AppState>>) -> Result<String> {
+    add_log(&state.logs, "INFO", "RAG", "Clearing answer cache");
+    let removed = state.rag_answer_cache.clear().await?;
+    Ok(format!("Answer cache cleared ({} answers)", removed))
+}
+
 /// Invalidate retrieval cache for a specific collection
 
 #[tauri::command]
@@ -238,7 +263,8 @@ pub async fn rag_invalidate_collection_cache(
     );
     state
         .retrieval_service
-        .invalidate_collection_cache(collection_id);
+        .invalidate_collection_cache(collection_id)
+        .await?;
     Ok(format!(
         "Cache invalidated for collection {}",
         collection_id
@@ -387,6 +413,89 @@ pub async fn rag_analyze_document_quality(
     Ok(analysis)
 }
 
+// ============================================================
+// CHUNK USAGE
+// ============================================================
+
+/// How often a collection's chunks are retrieved and cited
+
+#[tauri::command]
+pub async fn rag_get_chunk_usage_stats(
+    state: State<'_, Arc<super::AppState>>,
+    collection_id: i64,
+    limit: Option<i64>,
+) -> Result<ChunkUsageStats> {
+    let usage = state
+        .rag_repository
+        .get_collection_chunk_usage(collection_id)
+        .await?;
+    let top_chunks = state
+        .rag_repository
+        .get_top_chunk_usage(collection_id, limit.unwrap_or(20).clamp(1, 500))
+        .await?;
+    let documents = state.rag_repository.get_document_usage(collection_id).await?;
+
+    Ok(ChunkUsageStats {
+        collection_id,
+        usage,
+        top_chunks,
+        documents,
+    })
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChunkUsageStats {
+    pub collection_id: i64,
+    #[serde(flatten)]
+    pub usage: CollectionChunkUsage,
+    pub top_chunks: Vec<ChunkUsage>,
+    /// Least used first
+    pub documents: Vec<DocumentUsage>,
+}
+
+/// Suggest documents to archive because retrieval never or no longer returns them
+
+#[tauri::command]
+pub async fn rag_get_pruning_suggestions(
+    state: State<'_, Arc<super::AppState>>,
+    collection_id: i64,
+    options: Option<PruningOptions>,
+) -> Result<PruningReport> {
+    let options = options.unwrap_or_default();
+    if options.min_unused_days < 1 || options.stale_days < 1 {
+        return Err(AppError::ValidationError(
+            "Pruning thresholds must be at least 1 day".to_string(),
+        ));
+    }
+
+    let usage = state
+        .rag_repository
+        .get_collection_chunk_usage(collection_id)
+        .await?;
+    let documents = state.rag_repository.get_document_usage(collection_id).await?;
+    let report = build_pruning_report(
+        collection_id,
+        &usage,
+        &documents,
+        &options,
+        chrono::Utc::now().timestamp(),
+    );
+
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "Pruning advisor: {} of {} documents in collection {} suggested for archiving",
+            report.suggestions.len(),
+            report.total_documents,
+            collection_id
+        ),
+    );
+
+    Ok(report)
+}
+
 // ============================================================
 // CONFIGURATION MANAGEMENT
 // ============================================================
@@ -156,10 +156,9 @@ pub async fn rag_update_chunk_content(
         .generate_embedding(&new_content)
         .await
     {
-        let embedding_bytes =
-            crate::application::use_cases::embedding_service::EmbeddingService::embedding_to_bytes(
-                &embedding,
-            );
+        let doc_id = state.rag_repository.get_chunk(chunk_id).await?.doc_id;
+        let embed = state.rag_ingestion_use_case.embed_config(doc_id).await?;
+        let embedding_bytes = embed.stored_bytes(embedding);
         let _ = state
             .rag_repository
             .update_chunk_embedding(chunk_id, &embedding_bytes)
@@ -194,10 +193,11 @@ pub async fn rag_reembed_chunk(
             crate::domain::error::AppError::Internal(format!("Embedding failed: {}", e))
         })?;
 
-    let embedding_bytes =
-        crate::application::use_cases::embedding_service::EmbeddingService::embedding_to_bytes(
-            &embedding,
-        );
+    let embed = state
+        .rag_ingestion_use_case
+        .embed_config(chunk.doc_id)
+        .await?;
+    let embedding_bytes = embed.stored_bytes(embedding);
     state
         .rag_repository
         .update_chunk_embedding(chunk_id, &embedding_bytes)
@@ -292,3 +292,33 @@ pub(crate) fn average_score(results: &[crate::application::QueryResult]) -> Opti
     }
 }
 
+
+/// Count the text chunks in `results` as retrieved. Usage stats are best
+/// effort, so a failure is only logged.
+pub(crate) async fn record_chunk_retrievals(
+    state: &super::AppState,
+    collection_id: i64,
+    results: &[crate::application::QueryResult],
+) {
+    let chunk_ids: Vec<i64> = results
+        .iter()
+        .filter(|result| result.source_type == "text_chunk")
+        .map(|result| result.source_id)
+        .collect();
+    if chunk_ids.is_empty() {
+        return;
+    }
+
+    if let Err(e) = state
+        .rag_repository
+        .record_chunk_retrievals(collection_id, &chunk_ids)
+        .await
+    {
+        add_log(
+            &state.logs,
+            "WARN",
+            "RAG",
+            &format!("Failed to record chunk usage: {}", e),
+        );
+    }
+}
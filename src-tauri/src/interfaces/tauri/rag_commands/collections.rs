@@ -3,9 +3,23 @@
 //! This module provides Tauri commands for:
 //! - Creating, reading, listing, and deleting RAG collections
 //! - Collection quality metrics computation
+//! - Collection encryption at rest (enable, unlock, lock, disable)
+//! - Per-collection ingestion pipeline stages and hooks
+//! - Converting stored embeddings to a smaller precision
+//! - Per-collection language settings for keyword search
+//! - Moving documents between collections, merging and splitting collections
 
-use crate::domain::error::Result;
-use crate::domain::rag_entities::{RagCollection, RagCollectionInput};
+use crate::application::use_cases::collection_encryption::{
+    CollectionEncryptionService, CollectionEncryptionStatus,
+};
+use crate::application::use_cases::collection_transfer::{
+    CollectionSplit, CollectionTransferReport, CollectionTransferService, DocumentFilter,
+};
+use crate::application::use_cases::rag_ingestion::IngestionPipelineConfig;
+use crate::application::use_cases::retrieval_service::CollectionLanguageConfig;
+use crate::domain::embedding_quantization::{EmbeddingQuantization, EmbeddingQuantizationReport};
+use crate::domain::error::{AppError, Result};
+use crate::domain::rag_entities::{CollectionKind, RagCollection, RagCollectionInput};
 use crate::interfaces::http::add_log;
 use std::sync::Arc;
 use tauri::State;
@@ -96,7 +110,7 @@ pub async fn rag_delete_collection(state: State<'_, Arc<super::AppState>>, id: i
         &format!("Deleting collection: {}", id),
     );
 
-    state
+    let deleted = state
         .rag_repository
         .delete_collection(id)
         .await
@@ -108,7 +122,20 @@ pub async fn rag_delete_collection(state: State<'_, Arc<super::AppState>>, id: i
                 &format!("Failed to delete collection: {}", e),
             );
             e
-        })
+        })?;
+
+    if let Err(e) = CollectionEncryptionService::new(&state.rag_repository, &state.config_service)
+        .forget(id)
+        .await
+    {
+        add_log(
+            &state.logs,
+            "WARN",
+            "RAG",
+            &format!("Failed to remove the key of collection {}: {}", id, e),
+        );
+    }
+    Ok(deleted)
 }
 
 
@@ -168,5 +195,491 @@ pub async fn rag_compute_collection_quality(
         })
 }
 
+#[tauri::command]
+pub async fn rag_get_collection_encryption(
+    state: State<'_, Arc<super::AppState>>,
+    collection_id: i64,
+) -> Result<CollectionEncryptionStatus> {
+    CollectionEncryptionService::new(&state.rag_repository, &state.config_service)
+        .status(collection_id)
+        .await
+}
+
+/// Encrypt a collection's chunks and embeddings with a key derived from
+/// `passphrase`; returns the number of chunks encrypted
+#[tauri::command]
+pub async fn rag_enable_collection_encryption(
+    state: State<'_, Arc<super::AppState>>,
+    collection_id: i64,
+    passphrase: String,
+) -> Result<usize> {
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!("Encrypting collection {}", collection_id),
+    );
+
+    let sealed = CollectionEncryptionService::new(&state.rag_repository, &state.config_service)
+        .enable(collection_id, &passphrase)
+        .await
+        .map_err(|e| {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "RAG",
+                &format!("Failed to encrypt collection {}: {}", collection_id, e),
+            );
+            e
+        })?;
+    state
+        .retrieval_service
+        .invalidate_collection_cache(collection_id)
+        .await?;
+
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!("Collection {} encrypted ({} chunks)", collection_id, sealed),
+    );
+    Ok(sealed)
+}
+
+#[tauri::command]
+pub async fn rag_unlock_collection(
+    state: State<'_, Arc<super::AppState>>,
+    collection_id: i64,
+    passphrase: String,
+) -> Result<CollectionEncryptionStatus> {
+    let service = CollectionEncryptionService::new(&state.rag_repository, &state.config_service);
+    service
+        .unlock(collection_id, &passphrase)
+        .await
+        .map_err(|e| {
+            add_log(
+                &state.logs,
+                "WARN",
+                "RAG",
+                &format!("Failed to unlock collection {}: {}", collection_id, e),
+            );
+            e
+        })?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!("Collection {} unlocked", collection_id),
+    );
+    service.status(collection_id).await
+}
+
+/// Forget the key of an encrypted collection on this device
+#[tauri::command]
+pub async fn rag_lock_collection(
+    state: State<'_, Arc<super::AppState>>,
+    collection_id: i64,
+) -> Result<CollectionEncryptionStatus> {
+    let service = CollectionEncryptionService::new(&state.rag_repository, &state.config_service);
+    service.lock(collection_id).await?;
+    state
+        .retrieval_service
+        .invalidate_collection_cache(collection_id)
+        .await?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!("Collection {} locked", collection_id),
+    );
+    service.status(collection_id).await
+}
+
+/// Store an encrypted collection in plaintext again; returns the number of
+/// chunks decrypted
+#[tauri::command]
+pub async fn rag_disable_collection_encryption(
+    state: State<'_, Arc<super::AppState>>,
+    collection_id: i64,
+    passphrase: String,
+) -> Result<usize> {
+    let opened = CollectionEncryptionService::new(&state.rag_repository, &state.config_service)
+        .disable(collection_id, &passphrase)
+        .await
+        .map_err(|e| {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "RAG",
+                &format!("Failed to decrypt collection {}: {}", collection_id, e),
+            );
+            e
+        })?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!("Collection {} decrypted ({} chunks)", collection_id, opened),
+    );
+    Ok(opened)
+}
+
+/// Ingestion pipeline of a collection; the defaults when it has none
+#[tauri::command]
+pub async fn rag_get_collection_pipeline(
+    state: State<'_, Arc<super::AppState>>,
+    collection_id: i64,
+) -> Result<IngestionPipelineConfig> {
+    let collection = state.rag_repository.get_collection(collection_id).await?;
+    IngestionPipelineConfig::from_collection_config(&collection.config_json)
+}
+
+/// Replace the ingestion pipeline of a files collection. Documents already
+/// imported keep their chunks until they are imported again; a new
+/// similarity metric applies to searches right away.
+#[tauri::command]
+pub async fn rag_set_collection_pipeline(
+    state: State<'_, Arc<super::AppState>>,
+    collection_id: i64,
+    pipeline: IngestionPipelineConfig,
+) -> Result<IngestionPipelineConfig> {
+    let collection = state.rag_repository.get_collection(collection_id).await?;
+    if collection.kind != CollectionKind::Files {
+        return Err(AppError::ValidationError(
+            "Only file collections have an ingestion pipeline".to_string(),
+        ));
+    }
+
+    let config_json = pipeline.merge_into_collection_config(&collection.config_json)?;
+    state
+        .rag_repository
+        .update_collection_config(collection_id, &config_json)
+        .await?;
+    // Cached results were ranked with the old metric
+    let previous = IngestionPipelineConfig::from_collection_config(&collection.config_json)
+        .map(|previous| previous.embed.metric)
+        .unwrap_or_default();
+    if previous != pipeline.embed.metric {
+        state
+            .retrieval_service
+            .invalidate_collection_cache(collection_id)
+            .await?;
+    }
+
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "Updated ingestion pipeline of collection {} ({} hooks)",
+            collection_id,
+            pipeline.hooks.len()
+        ),
+    );
+    Ok(pipeline)
+}
+
+/// Store a collection's embeddings at `quantization` from now on and
+/// convert the ones it has. The database is compacted afterwards unless
+/// `compact` is false, since SQLite keeps freed pages otherwise.
+#[tauri::command]
+pub async fn rag_quantize_collection_embeddings(
+    state: State<'_, Arc<super::AppState>>,
+    collection_id: i64,
+    quantization: EmbeddingQuantization,
+    compact: Option<bool>,
+) -> Result<EmbeddingQuantizationReport> {
+    let collection = state.rag_repository.get_collection(collection_id).await?;
+    if collection.kind != CollectionKind::Files {
+        return Err(AppError::ValidationError(
+            "Only file collections have an ingestion pipeline".to_string(),
+        ));
+    }
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "Converting embeddings of collection {} to {}",
+            collection_id,
+            quantization.label()
+        ),
+    );
+
+    // New chunks follow the new precision while the old ones are converted
+    let mut pipeline = IngestionPipelineConfig::from_collection_config(&collection.config_json)?;
+    pipeline.embed.quantization = quantization;
+    let config_json = pipeline.merge_into_collection_config(&collection.config_json)?;
+    state
+        .rag_repository
+        .update_collection_config(collection_id, &config_json)
+        .await?;
+
+    let mut report = state
+        .rag_repository
+        .requantize_collection_embeddings(collection_id, quantization)
+        .await
+        .map_err(|e| {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "RAG",
+                &format!(
+                    "Failed to convert embeddings of collection {}: {}",
+                    collection_id, e
+                ),
+            );
+            e
+        })?;
+    // Scores shift slightly at a new precision
+    state
+        .retrieval_service
+        .invalidate_collection_cache(collection_id)
+        .await?;
+    if compact.unwrap_or(true) && report.converted > 0 {
+        state.rag_repository.vacuum().await?;
+        report.compacted = true;
+    }
+
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "Collection {} embeddings stored as {} ({} converted, {} unchanged, {} unreadable; {} -> {} bytes)",
+            collection_id,
+            quantization.label(),
+            report.converted,
+            report.unchanged,
+            report.unreadable,
+            report.bytes_before,
+            report.bytes_after
+        ),
+    );
+    Ok(report)
+}
+
+/// Language settings of a collection's keyword search; the defaults when
+/// it has none
+#[tauri::command]
+pub async fn rag_get_collection_language(
+    state: State<'_, Arc<super::AppState>>,
+    collection_id: i64,
+) -> Result<CollectionLanguageConfig> {
+    let collection = state.rag_repository.get_collection(collection_id).await?;
+    CollectionLanguageConfig::from_collection_config(&collection.config_json)
+}
+
+/// Replace the language settings of a collection. They apply to searches
+/// right away, so results and answers cached under the old ones are dropped.
+#[tauri::command]
+pub async fn rag_set_collection_language(
+    state: State<'_, Arc<super::AppState>>,
+    collection_id: i64,
+    language: CollectionLanguageConfig,
+) -> Result<CollectionLanguageConfig> {
+    let collection = state.rag_repository.get_collection(collection_id).await?;
+    let config_json = language.merge_into_collection_config(&collection.config_json)?;
+    state
+        .rag_repository
+        .update_collection_config(collection_id, &config_json)
+        .await?;
+    state
+        .retrieval_service
+        .invalidate_collection_cache(collection_id)
+        .await?;
+    state
+        .rag_answer_cache
+        .invalidate_collection(collection_id)
+        .await?;
+
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "Updated language settings of collection {} ({:?}, {:?} analyzer, {} synonym groups)",
+            collection_id,
+            language.language,
+            language.analyzer,
+            language.synonyms.len()
+        ),
+    );
+    Ok(language)
+}
+
 // Get document warnings
 
+/// Drop cached retrievals and answers of collections whose documents
+/// changed, and score them again. The target of a merge also takes over the
+/// source's history, so every collection that still exists is refreshed.
+async fn refresh_after_transfer(state: &super::AppState, report: &CollectionTransferReport) {
+    for collection_id in report.affected_collection_ids() {
+        if report.source_deleted && collection_id != report.target_collection_id {
+            continue;
+        }
+        let refreshed = async {
+            state
+                .retrieval_service
+                .invalidate_collection_cache(collection_id)
+                .await?;
+            state
+                .rag_answer_cache
+                .invalidate_collection(collection_id)
+                .await?;
+            state
+                .rag_repository
+                .compute_collection_quality_metrics(collection_id)
+                .await
+        }
+        .await;
+        if let Err(e) = refreshed {
+            add_log(
+                &state.logs,
+                "WARN",
+                "RAG",
+                &format!(
+                    "Failed to refresh collection {} after moving documents: {}",
+                    collection_id, e
+                ),
+            );
+        }
+    }
+}
+
+fn log_transfer(state: &super::AppState, action: &str, report: &CollectionTransferReport) {
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "{}: {} documents ({} chunks) from collections {:?} into {}; {} synced documents left in place, {} sources moved",
+            action,
+            report.moved_documents,
+            report.moved_chunks,
+            report.source_collection_ids,
+            report.target_collection_id,
+            report.skipped_synced.len(),
+            report.moved_sources
+        ),
+    );
+}
+
+/// Move documents with their chunks into another file collection. Documents
+/// a connector or git repository keeps in sync are left where they are.
+#[tauri::command]
+pub async fn rag_move_documents(
+    state: State<'_, Arc<super::AppState>>,
+    doc_ids: Vec<i64>,
+    target_collection_id: i64,
+) -> Result<CollectionTransferReport> {
+    let report = CollectionTransferService::new(&state.rag_repository)
+        .move_documents(&doc_ids, target_collection_id)
+        .await
+        .map_err(|e| {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "RAG",
+                &format!("Failed to move documents: {}", e),
+            );
+            e
+        })?;
+    refresh_after_transfer(&state, &report).await;
+    log_transfer(&state, "Moved documents", &report);
+    Ok(report)
+}
+
+/// Move everything in one file collection into another, including the
+/// connectors and git repositories syncing into it. The emptied source is
+/// deleted unless `delete_source` is false.
+#[tauri::command]
+pub async fn rag_merge_collections(
+    state: State<'_, Arc<super::AppState>>,
+    source_collection_id: i64,
+    target_collection_id: i64,
+    delete_source: Option<bool>,
+) -> Result<CollectionTransferReport> {
+    let mut report = CollectionTransferService::new(&state.rag_repository)
+        .merge_collections(source_collection_id, target_collection_id)
+        .await
+        .map_err(|e| {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "RAG",
+                &format!(
+                    "Failed to merge collection {} into {}: {}",
+                    source_collection_id, target_collection_id, e
+                ),
+            );
+            e
+        })?;
+
+    if delete_source.unwrap_or(true) {
+        state
+            .rag_repository
+            .delete_collection(source_collection_id)
+            .await?;
+        report.source_deleted = true;
+        if let Err(e) =
+            CollectionEncryptionService::new(&state.rag_repository, &state.config_service)
+                .forget(source_collection_id)
+                .await
+        {
+            add_log(
+                &state.logs,
+                "WARN",
+                "RAG",
+                &format!(
+                    "Failed to remove the key of collection {}: {}",
+                    source_collection_id, e
+                ),
+            );
+        }
+    }
+    refresh_after_transfer(&state, &report).await;
+    log_transfer(
+        &state,
+        &format!(
+            "Merged collection {} into {}",
+            source_collection_id, target_collection_id
+        ),
+        &report,
+    );
+    Ok(report)
+}
+
+/// Move the documents of a file collection that match `filter` into a new
+/// collection with the same settings
+#[tauri::command]
+pub async fn rag_split_collection(
+    state: State<'_, Arc<super::AppState>>,
+    collection_id: i64,
+    filter: DocumentFilter,
+    name: String,
+    description: Option<String>,
+) -> Result<CollectionSplit> {
+    let split = CollectionTransferService::new(&state.rag_repository)
+        .split_collection(collection_id, &filter, &name, description.as_deref())
+        .await
+        .map_err(|e| {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "RAG",
+                &format!("Failed to split collection {}: {}", collection_id, e),
+            );
+            e
+        })?;
+    refresh_after_transfer(&state, &split.report).await;
+    log_transfer(
+        &state,
+        &format!(
+            "Split collection {} into \"{}\"",
+            collection_id, split.collection.name
+        ),
+        &split.report,
+    );
+    Ok(split)
+}
@@ -3,17 +3,28 @@
 //! This module provides Tauri commands for:
 //! - RAG configuration management (get, update, reset, validate)
 //! - User feedback collection and statistics
+//! - Feedback-driven retrieval tuning and its experiments
+//! - Query route corrections, which refit the routing thresholds
 
 use crate::application::use_cases::rag_config::{
     CacheConfig, ChatConfig, ChunkingConfig, ConfigValidation, EmbeddingConfig, FeedbackRating,
-    FeedbackStats, OcrConfig, RagConfig, RetrievalConfig, UserFeedback,
+    FeedbackStats, OcrConfig, PiiConfig, RagConfig, RetrievalConfig, UserFeedback,
 };
-use crate::domain::error::Result;
+use crate::application::use_cases::rag_tuning::{
+    build_tuning_report, ExperimentArm, ExperimentStatus, ExperimentVerdict, RetrievalExperiment,
+    RetrievalParams, TuningOptions, TuningReport,
+};
+use crate::application::use_cases::retrieval_service::{
+    fit_thresholds, QueryRoute, RoutingExample,
+};
+use crate::domain::error::{AppError, Result};
+use crate::infrastructure::db::rag::repository::{RetrievalFeedbackRecord, RoutingDecisionRecord};
 use crate::interfaces::http::add_log;
 use std::sync::Arc;
 use tauri::State;
 
 use super::analytics_cache_metrics::truncate_message;
+use super::types::RouteCorrectionReport;
 
 #[tauri::command]
 pub async fn rag_get_config(state: State<'_, Arc<super::AppState>>) -> Result<RagConfig> {
@@ -154,6 +165,25 @@ pub async fn rag_update_chat_config(
     Ok("Chat configuration updated".to_string())
 }
 
+/// Update PII detection configuration
+
+#[tauri::command]
+pub async fn rag_update_pii_config(
+    state: State<'_, Arc<super::AppState>>,
+    config: PiiConfig,
+) -> Result<String> {
+    if !["off", "tag", "redact"].contains(&config.mode.as_str()) {
+        return Err(AppError::ValidationError(format!(
+            "Invalid PII mode: {}",
+            config.mode
+        )));
+    }
+    add_log(&state.logs, "INFO", "RAG", "Updating PII configuration");
+    state.config_manager.update_pii(config);
+    let _ = state.config_manager.save();
+    Ok("PII configuration updated".to_string())
+}
+
 /// Reset RAG configuration to defaults
 
 #[tauri::command]
@@ -219,6 +249,25 @@ pub async fn rag_submit_feedback(
         Some(rating_str.to_string()),
         0,
     );
+
+    if let Err(e) = record_tuning_feedback(&state, &query_text, collection_id, rating_str).await {
+        add_log(
+            &state.logs,
+            "WARN",
+            "RAG",
+            &format!("Failed to record feedback for tuning: {}", e),
+        );
+    }
+    if state.config_manager.get_config().retrieval.auto_tune {
+        if let Err(e) = run_auto_tuning(&state).await {
+            add_log(
+                &state.logs,
+                "WARN",
+                "RAG",
+                &format!("Retrieval tuning failed: {}", e),
+            );
+        }
+    }
     Ok("Feedback submitted successfully".to_string())
 }
 
@@ -251,6 +300,354 @@ pub async fn rag_get_recent_feedback(
 pub async fn rag_clear_feedback(state: State<'_, Arc<super::AppState>>) -> Result<String> {
     add_log(&state.logs, "INFO", "RAG", "Clearing user feedback");
     state.feedback_collector.clear();
+    state.rag_repository.clear_retrieval_feedback().await?;
     Ok("Feedback cleared successfully".to_string())
 }
 
+// ============================================================
+// RETRIEVAL TUNING
+// ============================================================
+
+/// Most recent ratings the tuning job looks at
+const TUNING_FEEDBACK_WINDOW: i64 = 2000;
+
+async fn running_experiment(state: &super::AppState) -> Result<Option<RetrievalExperiment>> {
+    state
+        .rag_repository
+        .get_running_retrieval_experiment()
+        .await?
+        .map(RetrievalExperiment::from_record)
+        .transpose()
+}
+
+/// Switch `config` to the running experiment's candidate parameters when
+/// `query_text` falls on the candidate arm
+pub(crate) async fn apply_retrieval_experiment(
+    state: &super::AppState,
+    query_text: &str,
+    config: &mut RagConfig,
+) -> Option<(i64, ExperimentArm)> {
+    let experiment = running_experiment(state).await.ok().flatten()?;
+    let arm = experiment.arm_for(query_text);
+    if arm == ExperimentArm::Candidate {
+        experiment.candidate.apply(&mut config.retrieval);
+    }
+    Some((experiment.id, arm))
+}
+
+/// Store a rating with the retrieval parameters its query ran with
+async fn record_tuning_feedback(
+    state: &super::AppState,
+    query_text: &str,
+    collection_id: Option<i64>,
+    rating: &str,
+) -> Result<()> {
+    let mut config = state.config_manager.get_config();
+    let experiment = apply_retrieval_experiment(state, query_text, &mut config).await;
+    let params = RetrievalParams::from_config(&config.retrieval);
+    let collection_quality = match collection_id {
+        Some(id) => state
+            .rag_repository
+            .get_collection_quality_metrics(id)
+            .await?
+            .and_then(|metrics| metrics.avg_quality_score),
+        None => None,
+    };
+
+    let record = RetrievalFeedbackRecord {
+        collection_id,
+        rating: rating.to_string(),
+        top_k: params.top_k as i64,
+        vector_weight: params.vector_weight as f64,
+        keyword_weight: params.keyword_weight as f64,
+        reranking_enabled: params.reranking_enabled,
+        collection_quality,
+        experiment_id: experiment.map(|(id, _)| id),
+        experiment_arm: experiment.map(|(_, arm)| arm.as_str().to_string()),
+        created_at: chrono::Utc::now().timestamp(),
+    };
+    state
+        .rag_repository
+        .record_retrieval_feedback(query_text, &record)
+        .await
+}
+
+async fn tuning_report(state: &super::AppState, options: &TuningOptions) -> Result<TuningReport> {
+    let feedback = state
+        .rag_repository
+        .list_retrieval_feedback(TUNING_FEEDBACK_WINDOW)
+        .await?;
+    let experiment = running_experiment(state).await?;
+    let current = RetrievalParams::from_config(&state.config_manager.get_config().retrieval);
+    Ok(build_tuning_report(
+        &feedback, &current, experiment, options,
+    ))
+}
+
+async fn start_experiment(
+    state: &super::AppState,
+    candidate: &RetrievalParams,
+    rationale: &str,
+) -> Result<RetrievalExperiment> {
+    let encode = |params: &RetrievalParams| {
+        serde_json::to_string(params).map_err(|e| {
+            AppError::Internal(format!("Failed to encode retrieval parameters: {}", e))
+        })
+    };
+    let baseline = RetrievalParams::from_config(&state.config_manager.get_config().retrieval);
+    let record = state
+        .rag_repository
+        .start_retrieval_experiment(&encode(&baseline)?, &encode(candidate)?, rationale)
+        .await?;
+    let experiment = RetrievalExperiment::from_record(record)?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "Started retrieval experiment {} ({})",
+            experiment.id, rationale
+        ),
+    );
+    Ok(experiment)
+}
+
+/// End an experiment; promoting it makes its candidate parameters the
+/// configured ones
+async fn finish_experiment(
+    state: &super::AppState,
+    experiment: &RetrievalExperiment,
+    status: ExperimentStatus,
+) -> Result<()> {
+    if !state
+        .rag_repository
+        .finish_retrieval_experiment(experiment.id, status.as_str())
+        .await?
+    {
+        return Ok(());
+    }
+    if status == ExperimentStatus::Promoted {
+        let mut retrieval = state.config_manager.get_config().retrieval;
+        experiment.candidate.apply(&mut retrieval);
+        state.config_manager.update_retrieval(retrieval);
+        state
+            .config_manager
+            .save()
+            .map_err(|e| AppError::Internal(format!("Failed to save config: {}", e)))?;
+    }
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "Retrieval experiment {} ended: {}",
+            experiment.id,
+            status.as_str()
+        ),
+    );
+    Ok(())
+}
+
+/// The tuning job: settle the running experiment once its arms differ, or
+/// start one with the current proposal
+async fn run_auto_tuning(state: &super::AppState) -> Result<()> {
+    let options = TuningOptions::default();
+    let report = tuning_report(state, &options).await?;
+    match report.experiment {
+        Some(summary) => {
+            let status = match summary.verdict {
+                ExperimentVerdict::Promote => ExperimentStatus::Promoted,
+                ExperimentVerdict::RollBack => ExperimentStatus::RolledBack,
+                // Rated alike long enough; the current parameters stay
+                ExperimentVerdict::NoDifference
+                    if summary.baseline.total.min(summary.candidate.total)
+                        >= 3 * options.min_samples =>
+                {
+                    ExperimentStatus::Stopped
+                }
+                _ => return Ok(()),
+            };
+            finish_experiment(state, &summary.experiment, status).await
+        }
+        None => match report.proposal {
+            Some(proposal) => start_experiment(state, &proposal.candidate, &proposal.rationale())
+                .await
+                .map(|_| ()),
+            None => Ok(()),
+        },
+    }
+}
+
+/// Feedback correlated with retrieval parameters and collection quality,
+/// with the proposed parameter changes and the running experiment
+
+#[tauri::command]
+pub async fn rag_get_tuning_report(
+    state: State<'_, Arc<super::AppState>>,
+    options: Option<TuningOptions>,
+) -> Result<TuningReport> {
+    let options = options.unwrap_or_default();
+    if options.min_samples == 0 {
+        return Err(AppError::ValidationError(
+            "min_samples must be at least 1".to_string(),
+        ));
+    }
+    tuning_report(&state, &options).await
+}
+
+/// Try `candidate`, or the current proposal, on half of the queries
+
+#[tauri::command]
+pub async fn rag_start_tuning_experiment(
+    state: State<'_, Arc<super::AppState>>,
+    candidate: Option<RetrievalParams>,
+) -> Result<RetrievalExperiment> {
+    let (candidate, rationale) = match candidate {
+        Some(candidate) => (candidate, "Started manually".to_string()),
+        None => {
+            let proposal = tuning_report(&state, &TuningOptions::default())
+                .await?
+                .proposal
+                .ok_or_else(|| {
+                    AppError::ValidationError(
+                        "Not enough feedback to propose retrieval changes yet".to_string(),
+                    )
+                })?;
+            let rationale = proposal.rationale();
+            (proposal.candidate, rationale)
+        }
+    };
+
+    let mut config = state.config_manager.get_config();
+    candidate.apply(&mut config.retrieval);
+    let validation = config.validate();
+    if !validation.valid {
+        return Err(AppError::ValidationError(validation.errors.join("; ")));
+    }
+
+    start_experiment(&state, &candidate, &rationale).await
+}
+
+/// End the running experiment, applying its candidate parameters when
+/// `promote` is set
+
+#[tauri::command]
+pub async fn rag_finish_tuning_experiment(
+    state: State<'_, Arc<super::AppState>>,
+    promote: bool,
+) -> Result<String> {
+    let experiment = running_experiment(&state)
+        .await?
+        .ok_or_else(|| AppError::NotFound("No retrieval experiment is running".to_string()))?;
+    let status = if promote {
+        ExperimentStatus::Promoted
+    } else {
+        ExperimentStatus::Stopped
+    };
+    finish_experiment(&state, &experiment, status).await?;
+    Ok(format!(
+        "Retrieval experiment {} {}",
+        experiment.id,
+        if promote { "applied" } else { "stopped" }
+    ))
+}
+
+// ============================================================
+// QUERY ROUTING
+// ============================================================
+
+/// Most recent corrected or forced routes the thresholds are fit on
+const ROUTING_EXAMPLE_WINDOW: i64 = 500;
+/// Corrections needed before the thresholds are refit
+const MIN_ROUTING_EXAMPLES: usize = 5;
+
+/// Recent routing decisions, newest first
+
+#[tauri::command]
+pub async fn rag_list_routing_decisions(
+    state: State<'_, Arc<super::AppState>>,
+    collection_id: Option<i64>,
+    limit: Option<i64>,
+) -> Result<Vec<RoutingDecisionRecord>> {
+    state
+        .rag_repository
+        .list_routing_decisions(collection_id, limit.unwrap_or(100).clamp(1, 1000))
+        .await
+}
+
+/// Record the route a query should have taken, then refit the routing
+/// thresholds on the corrections so far and save them when they route
+/// more of them right
+
+#[tauri::command]
+pub async fn rag_correct_query_route(
+    state: State<'_, Arc<super::AppState>>,
+    decision_id: i64,
+    route: QueryRoute,
+) -> Result<RouteCorrectionReport> {
+    let decision = state
+        .rag_repository
+        .get_routing_decision(decision_id)
+        .await?;
+    if decision.auto_route.is_none() {
+        return Err(AppError::ValidationError(format!(
+            "Query {} was a {} lookup, which has no route to correct",
+            decision_id, decision.query_type
+        )));
+    }
+    state
+        .rag_repository
+        .correct_routing_decision(decision_id, route.as_str())
+        .await?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "Query {} route corrected: {} -> {}",
+            decision_id,
+            decision.route.as_deref().unwrap_or("none"),
+            route.as_str()
+        ),
+    );
+
+    let examples: Vec<RoutingExample> = state
+        .rag_repository
+        .list_routing_examples(ROUTING_EXAMPLE_WINDOW)
+        .await?
+        .iter()
+        .filter_map(RoutingExample::from_record)
+        .collect();
+    let mut retrieval = state.config_manager.get_config().retrieval;
+    let fit = fit_thresholds(&examples, retrieval.routing, MIN_ROUTING_EXAMPLES);
+
+    let improved = fit.as_ref().filter(|fit| fit.improves());
+    let applied = improved.is_some();
+    if let Some(fit) = improved {
+        retrieval.routing = fit.proposed;
+        state.config_manager.update_retrieval(retrieval);
+        state
+            .config_manager
+            .save()
+            .map_err(|e| AppError::Internal(format!("Failed to save config: {}", e)))?;
+        add_log(
+            &state.logs,
+            "INFO",
+            "RAG",
+            &format!(
+                "Routing thresholds refit from {} corrections: structured at {} signals, keyword up to {} terms ({} -> {} misrouted)",
+                fit.examples,
+                fit.proposed.structured_min_signals,
+                fit.proposed.keyword_max_terms,
+                fit.misrouted_current,
+                fit.misrouted_proposed
+            ),
+        );
+    }
+
+    Ok(RouteCorrectionReport {
+        decision_id,
+        fit,
+        applied,
+    })
+}
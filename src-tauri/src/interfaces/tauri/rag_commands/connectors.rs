@@ -0,0 +1,220 @@
+//! Notion and Confluence connectors: settings, imported pages and syncs.
+//! Tokens are kept in the OS keychain, never in the RAG database.
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::domain::error::{AppError, ErrorCode, Result};
+use crate::domain::rag_connectors::{
+    ConnectorPage, ConnectorSyncReport, RagConnector, RagConnectorInput,
+};
+use crate::domain::rag_entities::CollectionKind;
+use crate::interfaces::http::add_log;
+
+use super::AppState;
+
+#[tauri::command]
+pub async fn rag_list_connectors(
+    state: State<'_, Arc<super::AppState>>,
+) -> Result<Vec<RagConnector>> {
+    state.rag_repository.list_connectors().await
+}
+
+/// Create a connector; its first sync runs on demand or when the scheduler
+/// next checks
+#[tauri::command]
+pub async fn rag_create_connector(
+    state: State<'_, Arc<super::AppState>>,
+    input: RagConnectorInput,
+) -> Result<RagConnector> {
+    let input = input.normalized();
+    input.validate()?;
+    let token = input.token.clone().ok_or_else(|| {
+        AppError::ValidationError(format!(
+            "{} API token is required",
+            input.kind.display_name()
+        ))
+    })?;
+    check_collection(&state, &input).await?;
+
+    let connector = state.rag_repository.create_connector(&input).await?;
+    if let Err(err) = state
+        .config_service
+        .save_connector_token(connector.id, &token)
+    {
+        let _ = state.rag_repository.delete_connector(connector.id).await;
+        return Err(err);
+    }
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "Created {} connector '{}'",
+            connector.kind.display_name(),
+            connector.name
+        ),
+    );
+    Ok(connector)
+}
+
+/// Update a connector's settings; a token in the input replaces the stored one
+#[tauri::command]
+pub async fn rag_update_connector(
+    state: State<'_, Arc<super::AppState>>,
+    id: i64,
+    input: RagConnectorInput,
+) -> Result<RagConnector> {
+    let input = input.normalized();
+    input.validate()?;
+    let existing = state.rag_repository.get_connector(id).await?;
+    if input.kind != existing.kind {
+        return Err(AppError::ValidationError(
+            "A connector's source can't be changed; create a new connector instead".to_string(),
+        ));
+    }
+    if input.collection_id != existing.collection_id {
+        return Err(AppError::ValidationError(
+            "A connector's collection can't be changed; create a new connector instead".to_string(),
+        ));
+    }
+
+    if let Some(token) = &input.token {
+        state.config_service.save_connector_token(id, token)?;
+    }
+    state.rag_repository.update_connector(id, &input).await
+}
+
+/// Delete a connector and its token; the documents it imported are kept
+/// unless `delete_documents` is set
+#[tauri::command]
+pub async fn rag_delete_connector(
+    state: State<'_, Arc<super::AppState>>,
+    id: i64,
+    delete_documents: Option<bool>,
+) -> Result<()> {
+    let connector = state.rag_repository.get_connector(id).await?;
+    if delete_documents.unwrap_or(false) {
+        for page in state.rag_repository.list_connector_pages(id).await? {
+            if let Some(doc_id) = page.doc_id {
+                state.rag_repository.delete_document(doc_id).await?;
+            }
+        }
+    }
+    state.rag_repository.delete_connector(id).await?;
+    if let Err(err) = state.config_service.delete_connector_token(id) {
+        add_log(
+            &state.logs,
+            "WARN",
+            "RAG",
+            &format!(
+                "Failed to remove the token of connector '{}': {}",
+                connector.name, err
+            ),
+        );
+    }
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!("Deleted connector '{}'", connector.name),
+    );
+    Ok(())
+}
+
+/// Imported pages with their place in the source's page tree
+#[tauri::command]
+pub async fn rag_list_connector_pages(
+    state: State<'_, Arc<super::AppState>>,
+    connector_id: i64,
+) -> Result<Vec<ConnectorPage>> {
+    state
+        .rag_repository
+        .list_connector_pages(connector_id)
+        .await
+}
+
+#[tauri::command]
+pub async fn rag_sync_connector(
+    state: State<'_, Arc<super::AppState>>,
+    id: i64,
+) -> Result<ConnectorSyncReport> {
+    let connector = state.rag_repository.get_connector(id).await?;
+    sync_connector(&state, &connector).await
+}
+
+/// Sync a connector with its stored token. Shared with the sync scheduler;
+/// a missing token is recorded on the connector as a failed sync.
+pub(crate) async fn sync_connector(
+    state: &AppState,
+    connector: &RagConnector,
+) -> Result<ConnectorSyncReport> {
+    let token = match state.config_service.find_connector_token(connector.id)? {
+        Some(token) => token,
+        None => {
+            let err = AppError::coded(
+                ErrorCode::ConfigMissing,
+                format!("Connector '{}' has no API token", connector.name),
+            )
+            .with_hint("Edit the connector and enter its API token again");
+            add_log(&state.logs, "WARN", "RAG", &err.to_string());
+            let now = chrono::Utc::now().timestamp();
+            let report = ConnectorSyncReport {
+                connector_id: connector.id,
+                started_at: now,
+                finished_at: now,
+                ..Default::default()
+            };
+            state
+                .rag_repository
+                .record_connector_sync(connector.id, &report, Some(&err.to_string()))
+                .await?;
+            return Err(err);
+        }
+    };
+
+    state
+        .rag_ingestion_use_case
+        .sync_connector(connector, &token, state.logs.clone())
+        .await
+        .inspect_err(|err| {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "RAG",
+                &format!("Connector '{}' sync failed: {}", connector.name, err),
+            );
+        })
+}
+
+/// The target collection can't be a database collection or hold other document types
+async fn check_collection(state: &AppState, input: &RagConnectorInput) -> Result<()> {
+    let collection = state
+        .rag_repository
+        .get_collection(input.collection_id)
+        .await?;
+    if collection.kind == CollectionKind::Db {
+        return Err(AppError::ValidationError(format!(
+            "Collection '{}' is a Database Collection and can't hold imported pages",
+            collection.name
+        )));
+    }
+
+    // One collection holds one document type
+    let existing_type = state
+        .rag_repository
+        .get_collection_document_type(input.collection_id)
+        .await?;
+    if let Some(existing_type) = existing_type {
+        if existing_type.to_lowercase() != input.kind.as_str() {
+            return Err(AppError::ValidationError(format!(
+                "Collection '{}' already contains {} documents. Please create a new collection for {} pages.",
+                collection.name,
+                existing_type,
+                input.kind.display_name()
+            )));
+        }
+    }
+    Ok(())
+}
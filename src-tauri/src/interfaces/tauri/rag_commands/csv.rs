@@ -1,21 +1,24 @@
 //! CSV Preprocessing Commands
 //!
 //! This module provides Tauri commands for:
-//! - CSV file preprocessing and analysis
+//! - CSV file preprocessing and analysis (streamed, with progress events)
 //! - Row preview functionality
 
 use crate::application::use_cases::csv_preprocessor::CsvPreprocessor;
-use crate::domain::csv::PreprocessingConfig;
-use crate::domain::error::Result;
+use crate::domain::csv::{CsvProgress, PreprocessingConfig};
+use crate::domain::error::{AppError, Result};
 use crate::interfaces::http::add_log;
 use std::path::Path;
 use std::sync::Arc;
-use tauri::State;
+use tauri::{Emitter, State};
 
 use super::types::*;
 
+const CSV_PROGRESS_EMIT: &str = "csv-preprocess-progress";
+
 #[tauri::command]
 pub async fn csv_preprocess_file(
+    app: tauri::AppHandle,
     state: State<'_, Arc<super::AppState>>,
     request: CsvPreprocessingRequest,
 ) -> Result<CsvPreprocessingResponse> {
@@ -43,6 +46,9 @@ pub async fn csv_preprocess_file(
             max_sample_rows: req_config
                 .max_sample_rows
                 .unwrap_or(PreprocessingConfig::default().max_sample_rows),
+            normalize_columns: req_config
+                .normalize_columns
+                .unwrap_or(PreprocessingConfig::default().normalize_columns),
             ..PreprocessingConfig::default()
         }
     } else {
@@ -50,9 +56,37 @@ pub async fn csv_preprocess_file(
     };
 
     let preprocessor = CsvPreprocessor::new(config);
-    let path = Path::new(&request.file_path);
-
-    let preprocessed = preprocessor.preprocess_csv(path).await.map_err(|e| {
+    let file_path = request.file_path.clone();
+    let output_path = request.output_path.clone();
+    let logs = state.logs.clone();
+
+    // Streaming keeps memory bounded but is still blocking file I/O
+    let preprocessed = tauri::async_runtime::spawn_blocking(move || {
+        let path = Path::new(&file_path);
+        let on_progress = |progress: &CsvProgress| {
+            let payload = CsvProgressEvent {
+                file_path: file_path.clone(),
+                progress: progress.clone(),
+            };
+            if let Err(err) = app.emit(CSV_PROGRESS_EMIT, payload) {
+                add_log(
+                    &logs,
+                    "WARN",
+                    "CSV",
+                    &format!("Failed to emit CSV progress: {}", err),
+                );
+            }
+        };
+        match output_path {
+            Some(output) => {
+                preprocessor.preprocess_csv_to_file(path, Path::new(&output), on_progress)
+            }
+            None => preprocessor.preprocess_csv_with_progress(path, on_progress),
+        }
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("CSV preprocessing task failed: {}", e)))?
+    .map_err(|e| {
         add_log(
             &state.logs,
             "ERROR",
@@ -67,9 +101,11 @@ pub async fn csv_preprocess_file(
         "INFO",
         "CSV",
         &format!(
-            "Preprocessing complete: {} rows, {:?} type, {:.2} confidence",
+            "Preprocessing complete: {} rows, {:?} type, {} encoding, {:?} delimiter, {:.2} confidence",
             preprocessed.row_count,
             preprocessed.content_type,
+            preprocessed.encoding,
+            preprocessed.delimiter,
             preprocessed.analysis.confidence_score()
         ),
     );
@@ -89,7 +125,11 @@ pub async fn csv_preprocess_file(
             min_value_length: preprocessed.analysis.min_value_length,
             confidence_score: preprocessed.analysis.confidence_score(),
         },
+        profile: preprocessed.profile,
         headers: preprocessed.headers,
+        encoding: preprocessed.encoding,
+        delimiter: preprocessed.delimiter.to_string(),
+        output_path: request.output_path,
         processing_time_ms: preprocessed.processing_time_ms,
     })
 }
@@ -110,18 +150,9 @@ pub async fn csv_preview_rows(
     );
 
     let preprocessor = CsvPreprocessor::default();
-    let content = std::fs::read_to_string(&file_path).map_err(|e| {
-        add_log(
-            &state.logs,
-            "ERROR",
-            "CSV",
-            &format!("Failed to read file: {}", e),
-        );
-        crate::domain::error::AppError::IoError(format!("Failed to read file: {}", e))
-    })?;
 
     let preview = preprocessor
-        .preview_rows(&content, preview_count)
+        .preview_file(Path::new(&file_path), preview_count)
         .map_err(|e| {
             add_log(
                 &state.logs,
@@ -140,12 +171,13 @@ pub async fn csv_preview_rows(
 }
 
 /// Analyze CSV without full preprocessing
+/// Returns the text report plus per-column types, statistics and a suggested schema
 
 #[tauri::command]
 pub async fn csv_analyze(
     state: State<'_, Arc<super::AppState>>,
     file_path: String,
-) -> Result<String> {
+) -> Result<CsvAnalysisResponse> {
     add_log(
         &state.logs,
         "INFO",
@@ -154,17 +186,14 @@ pub async fn csv_analyze(
     );
 
     let preprocessor = CsvPreprocessor::default();
-    let content = std::fs::read_to_string(&file_path).map_err(|e| {
-        add_log(
-            &state.logs,
-            "ERROR",
-            "CSV",
-            &format!("Failed to read file: {}", e),
-        );
-        crate::domain::error::AppError::IoError(format!("Failed to read file: {}", e))
-    })?;
 
-    let report = preprocessor.analyze_csv(&content).map_err(|e| {
+    // Profiling reads every row, so keep it off the async runtime
+    let (report, profile) = tauri::async_runtime::spawn_blocking(move || {
+        preprocessor.analyze_file(Path::new(&file_path))
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("CSV analysis task failed: {}", e)))?
+    .map_err(|e| {
         add_log(
             &state.logs,
             "ERROR",
@@ -174,7 +203,7 @@ pub async fn csv_analyze(
         e
     })?;
 
-    Ok(report)
+    Ok(CsvAnalysisResponse { report, profile })
 }
 
 // ============================================================
@@ -1,5 +1,6 @@
 use crate::domain::error::Result;
 use crate::domain::rag_entities::DbConnectionConfig;
+use crate::domain::settings::LlmFeature;
 use crate::interfaces::http::add_log;
 use std::sync::Arc;
 use std::time::Instant;
@@ -93,7 +94,7 @@ pub async fn db_query_rag_with_template_impl(
         .limit
         .unwrap_or(conn_config.default_limit.unwrap_or(DEFAULT_LIMIT));
     let final_k = request.final_k.unwrap_or(FINAL_K);
-    let llm_config = state.last_config.lock().unwrap().clone();
+    let llm_config = state.feature_config(LlmFeature::RagChat);
 
     let template_match = TemplateMatch {
         template: template.clone(),
@@ -1,4 +1,5 @@
 use crate::domain::error::Result;
+use crate::domain::settings::LlmFeature;
 use crate::interfaces::http::add_log;
 use std::sync::Arc;
 
@@ -128,7 +129,7 @@ pub async fn execute_and_build_response(
     let final_count = final_rows.len();
 
     let results_context = format_sql_results_for_llm(&final_rows, &resolved.final_plan.table);
-    let llm_config = state.last_config.lock().unwrap().clone();
+    let llm_config = state.feature_config(LlmFeature::RagChat);
 
     let answer = if !resolved.matched_templates.is_empty() {
         let nl_examples = build_nl_few_shot_examples(&request.query, &resolved.matched_templates);
@@ -4,6 +4,7 @@ use crate::application::use_cases::sql_rag_router::SqlRagRouter;
 use crate::application::use_cases::template_matcher::TemplateMatch;
 use crate::domain::error::Result;
 use crate::domain::rag_entities::{DbAllowlistProfile, DbConnectionConfig, QueryPlan};
+use crate::domain::settings::LlmFeature;
 use crate::interfaces::http::add_log;
 use std::sync::Arc;
 
@@ -73,7 +74,7 @@ pub async fn resolve_sql_and_plan(
     }
 
     let detected_tables = collection_config.selected_tables.clone();
-    let llm_config = state.last_config.lock().unwrap().clone();
+    let llm_config = state.feature_config(LlmFeature::RagChat);
 
     // =========================================================================
     // STEP 1: Query Intent Enrichment (LLM-based, with silent fallback)
@@ -184,7 +185,7 @@ pub async fn resolve_sql_and_plan(
         );
     }
 
-    let llm_config = state.last_config.lock().unwrap().clone();
+    let llm_config = state.feature_config(LlmFeature::RagChat);
 
     let (sql_to_execute, sql_description, final_plan, sql_params) = if use_template_first {
         if preferred_template_id.is_some() {
@@ -21,6 +21,7 @@ pub fn convert_db_rows_to_candidates(
                 page_number: None,
                 page_offset: None,
                 doc_name: Some(table_name.to_string()),
+                snippet: None,
             }
         })
         .collect()
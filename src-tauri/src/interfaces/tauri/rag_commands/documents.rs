@@ -2,11 +2,18 @@
 //!
 //! This module provides Tauri commands for:
 //! - Getting, listing, and deleting documents
-//! - Importing files into collections
+//! - Document previews for list views
+//! - Importing files into collections and resuming interrupted imports
 //! - Reindexing documents and collections
 
-use crate::domain::error::Result;
+use crate::application::use_cases::embedding_service::BatchEmbeddingOptions;
+use crate::application::use_cases::rag_ingestion::{
+    DocumentPreview, ExcelImportOptions, IngestionResumeReport, WorkbookSheet,
+};
+use crate::domain::activity::{ActivityInput, ActivitySource};
+use crate::domain::error::{AppError, Result};
 use crate::domain::rag_entities::RagDocument;
+use crate::domain::telemetry::UsageSubsystem;
 use crate::interfaces::http::add_log;
 use serde::Serialize;
 use std::path::Path;
@@ -37,6 +44,27 @@ pub async fn rag_get_document(
     })
 }
 
+/// Thumbnail, title, author and page count of a document for list views
+#[tauri::command]
+pub async fn rag_get_document_preview(
+    state: State<'_, Arc<super::AppState>>,
+    id: i64,
+) -> Result<DocumentPreview> {
+    state
+        .rag_ingestion_use_case
+        .document_preview(id, &state.logs)
+        .await
+        .map_err(|e| {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "RAG",
+                &format!("Failed to get preview of document {}: {}", id, e),
+            );
+            e
+        })
+}
+
 
 #[tauri::command]
 pub async fn rag_delete_document(state: State<'_, Arc<super::AppState>>, id: i64) -> Result<u64> {
@@ -44,12 +72,13 @@ pub async fn rag_delete_document(state: State<'_, Arc<super::AppState>>, id: i64
         &state.logs,
         "INFO",
         "RAG",
-        &format!("Deleting document: {}", id),
+        &format!("Moving document to trash: {}", id),
     );
 
+    let document = state.rag_repository.get_document(id).await?;
     let rows = state
         .rag_repository
-        .delete_document(id)
+        .trash_document(id, chrono::Utc::now().timestamp_millis())
         .await
         .map_err(|e| {
             add_log(
@@ -60,12 +89,24 @@ pub async fn rag_delete_document(state: State<'_, Arc<super::AppState>>, id: i64
             );
             e
         })?;
+    if rows == 0 {
+        return Err(AppError::NotFound(format!(
+            "Document {} is already in the trash",
+            id
+        )));
+    }
+    if let Some(collection_id) = document.collection_id {
+        state
+            .retrieval_service
+            .invalidate_collection_cache(collection_id)
+            .await?;
+    }
 
     add_log(
         &state.logs,
         "INFO",
         "RAG",
-        &format!("Deleted document: {} (rows {})", id, rows),
+        &format!("Document moved to trash: {} ({})", id, document.file_name),
     );
 
     Ok(rows)
@@ -137,6 +178,48 @@ pub async fn rag_import_file(
         &format!("Importing file: {}", file_path),
     );
 
+    check_import_target(&state, &file_path, collection_id).await?;
+
+    record_ingestion_started(&state, &file_path, collection_id);
+    let start = Instant::now();
+    let result = state
+        .rag_ingestion_use_case
+        .ingest_file(&file_path, collection_id, state.logs.clone())
+        .await
+        .map_err(|e| {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "RAG",
+                &format!("Failed to import file: {}", e),
+            );
+            e
+        });
+
+    let doc_type = Path::new(&file_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("unknown");
+    state.analytics_logger.log_extraction(
+        doc_type,
+        result.is_ok(),
+        start.elapsed().as_millis() as u64,
+    );
+    record_ingestion_finished(&state, &file_path, &result, start);
+    state
+        .telemetry
+        .record(UsageSubsystem::Rag, "import_file", start, result.is_ok());
+
+    result
+}
+
+/// Rejects a file import into a DB collection or into a collection holding
+/// another type of document
+async fn check_import_target(
+    state: &super::AppState,
+    file_path: &str,
+    collection_id: Option<i64>,
+) -> Result<()> {
     // CRITICAL SECURITY CHECK: Block file imports for DB collections
     if let Some(coll_id) = collection_id {
         let collection = state.rag_repository.get_collection(coll_id).await?;
@@ -151,11 +234,16 @@ pub async fn rag_import_file(
         }
 
         // DOCUMENT TYPE VALIDATION: Enforce 1 collection = 1 document type
-        let new_file_type = Path::new(&file_path)
+        let new_file_type = Path::new(file_path)
             .extension()
             .and_then(|ext| ext.to_str())
             .unwrap_or("unknown")
             .to_lowercase();
+        // Email files are stored under one type whatever their format
+        let new_file_type = match new_file_type.as_str() {
+            "eml" | "mbox" => "email".to_string(),
+            _ => new_file_type,
+        };
 
         if let Ok(Some(existing_type)) = state.rag_repository.get_collection_document_type(coll_id).await {
             // Normalize web type to lowercase for comparison
@@ -170,6 +258,10 @@ pub async fn rag_import_file(
                     "txt" => "Text",
                     "md" => "Markdown",
                     "web" => "Web content",
+                    "notion" => "Notion pages",
+                    "confluence" => "Confluence pages",
+                    "git" => "Git repository files",
+                    "email" => "Email",
                     _ => &existing_type_normalized,
                 };
                 let new_type_name = match new_file_type.as_str() {
@@ -179,6 +271,7 @@ pub async fn rag_import_file(
                     "csv" => "CSV",
                     "txt" => "Text",
                     "md" => "Markdown",
+                    "email" => "Email",
                     _ => &new_file_type,
                 };
                 let err_msg = format!(
@@ -190,35 +283,182 @@ pub async fn rag_import_file(
             }
         }
     }
+    Ok(())
+}
+
+/// The worksheets of an Excel workbook, to pick the ones to import
+#[tauri::command]
+pub async fn rag_list_workbook_sheets(
+    state: State<'_, Arc<super::AppState>>,
+    file_path: String,
+) -> Result<Vec<WorkbookSheet>> {
+    state
+        .rag_ingestion_use_case
+        .list_workbook_sheets(&file_path)
+        .map_err(|e| {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "RAG",
+                &format!("Failed to list sheets of {}: {}", file_path, e),
+            );
+            e
+        })
+}
 
+/// Import the sheets of an Excel workbook, each as its own document; every
+/// visible sheet with data when `options` names none
+#[tauri::command]
+pub async fn rag_import_workbook(
+    state: State<'_, Arc<super::AppState>>,
+    file_path: String,
+    collection_id: Option<i64>,
+    options: Option<ExcelImportOptions>,
+) -> Result<Vec<RagDocument>> {
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!("Importing workbook: {}", file_path),
+    );
+    check_import_target(&state, &file_path, collection_id).await?;
+
+    record_ingestion_started(&state, &file_path, collection_id);
     let start = Instant::now();
     let result = state
         .rag_ingestion_use_case
-        .ingest_file(&file_path, collection_id, state.logs.clone())
+        .ingest_workbook(
+            &file_path,
+            collection_id,
+            &options.unwrap_or_default(),
+            &state.logs,
+        )
         .await
         .map_err(|e| {
             add_log(
                 &state.logs,
                 "ERROR",
                 "RAG",
-                &format!("Failed to import file: {}", e),
+                &format!("Failed to import workbook: {}", e),
             );
             e
         });
 
-    let doc_type = Path::new(&file_path)
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .unwrap_or("unknown");
     state.analytics_logger.log_extraction(
-        doc_type,
+        "xlsx",
         result.is_ok(),
         start.elapsed().as_millis() as u64,
     );
+    match &result {
+        Ok(documents) => {
+            for document in documents {
+                let source = document.file_path.as_deref().unwrap_or(&file_path);
+                record_ingestion_finished(&state, source, &Ok(document.clone()), start);
+            }
+        }
+        Err(e) => record_ingestion_finished(&state, &file_path, &Err(e.clone()), start),
+    }
+    state.telemetry.record(
+        UsageSubsystem::Rag,
+        "import_workbook",
+        start,
+        result.is_ok(),
+    );
 
     result
 }
 
+/// Adds the start of an import to the activity feed; `source` is the file or URL
+pub(super) fn record_ingestion_started(
+    state: &super::AppState,
+    source: &str,
+    collection_id: Option<i64>,
+) {
+    state.activity_feed.record(
+        ActivityInput::new(
+            ActivitySource::RagIngestion,
+            "ingestion_started",
+            format!("Importing {}", source),
+        )
+        .subject(source)
+        .payload(serde_json::json!({ "collectionId": collection_id })),
+    );
+}
+
+pub(super) fn record_ingestion_finished(
+    state: &super::AppState,
+    source: &str,
+    result: &Result<RagDocument>,
+    start: Instant,
+) {
+    let duration_ms = start.elapsed().as_millis() as u64;
+    let input = match result {
+        Ok(document) => ActivityInput::new(
+            ActivitySource::RagIngestion,
+            "ingestion_completed",
+            format!(
+                "Imported {} ({} chunks)",
+                document.file_name, document.chunk_count
+            ),
+        )
+        .payload(serde_json::json!({
+            "documentId": document.id,
+            "collectionId": document.collection_id,
+            "chunkCount": document.chunk_count,
+            "warningCount": document.warning_count,
+            "durationMs": duration_ms,
+        })),
+        Err(err) => ActivityInput::new(
+            ActivitySource::RagIngestion,
+            "ingestion_failed",
+            format!("Failed to import {}: {}", source, err),
+        )
+        .level("error")
+        .payload(serde_json::json!({ "durationMs": duration_ms })),
+    };
+    state.activity_feed.record(input.subject(source));
+}
+
+/// Resume imports that failed or were interrupted when the app closed, of
+/// one collection or of all, each from the last stage it completed
+#[tauri::command]
+pub async fn rag_resume_failed_ingestions(
+    state: State<'_, Arc<super::AppState>>,
+    collection_id: Option<i64>,
+) -> Result<IngestionResumeReport> {
+    let start = Instant::now();
+    let report = state
+        .rag_ingestion_use_case
+        .resume_failed_ingestions(collection_id, &state.logs)
+        .await
+        .map_err(|e| {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "RAG",
+                &format!("Failed to resume imports: {}", e),
+            );
+            e
+        })?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "Resumed {} imports, {} failed again",
+            report.resumed.len(),
+            report.failed.len()
+        ),
+    );
+    state.telemetry.record(
+        UsageSubsystem::Rag,
+        "resume_imports",
+        start,
+        report.failed.is_empty(),
+    );
+    Ok(report)
+}
+
 
 #[tauri::command]
 pub async fn rag_reindex_document(
@@ -249,16 +489,18 @@ pub async fn rag_reindex_document(
         ),
     );
 
-    for (index, chunk) in chunks.iter().enumerate() {
-        match state
-            .embedding_service
-            .generate_embedding(&chunk.content)
-            .await
-        {
+    let texts: Vec<String> = chunks.iter().map(|chunk| chunk.content.clone()).collect();
+    let options = BatchEmbeddingOptions::from(&state.config_manager.get_config().embedding);
+    let (embeddings, throughput) = state.embedding_service.embed_batch(&texts, &options).await;
+    let embed = state
+        .rag_ingestion_use_case
+        .embed_config(document_id)
+        .await?;
+
+    for (chunk, embedding) in chunks.iter().zip(embeddings) {
+        match embedding {
             Ok(embedding) => {
-                let embedding_bytes = crate::application::use_cases::embedding_service::EmbeddingService::embedding_to_bytes(
-                    &embedding,
-                );
+                let embedding_bytes = embed.stored_bytes(embedding);
                 if state
                     .rag_repository
                     .update_chunk_embedding(chunk.id, &embedding_bytes)
@@ -280,22 +522,18 @@ pub async fn rag_reindex_document(
                 failed_count += 1;
             }
         }
-
-        // Log progress every 10 chunks
-        if (index + 1) % 10 == 0 {
-            add_log(
-                &state.logs,
-                "INFO",
-                "RAG",
-                &format!(
-                    "Re-indexing progress: {}/{} chunks processed",
-                    index + 1,
-                    total_chunks
-                ),
-            );
-        }
     }
 
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "Re-index embedding throughput: {:.1} chunks/s over {} requests ({} retried)",
+            throughput.texts_per_second, throughput.requests, throughput.retries
+        ),
+    );
+
     add_log(
         &state.logs,
         "INFO",
@@ -0,0 +1,95 @@
+//! Messages of imported email archives, and searches narrowed to a sender,
+//! a date range or a thread.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use tauri::State;
+
+use crate::application::QueryResult;
+use crate::domain::error::Result;
+use crate::domain::rag_email::{EmailFilter, EmailMessageRecord};
+use crate::interfaces::http::add_log;
+
+use super::chunks::{average_score, record_chunk_retrievals};
+
+const DEFAULT_MESSAGE_LIMIT: i64 = 200;
+
+/// Candidates retrieved per requested result before the filter drops
+/// chunks of other messages
+const FILTERED_SEARCH_OVERFETCH: usize = 5;
+
+/// Messages of the collection's email documents, newest first
+#[tauri::command]
+pub async fn rag_list_email_messages(
+    state: State<'_, Arc<super::AppState>>,
+    collection_id: i64,
+    filter: Option<EmailFilter>,
+    limit: Option<i64>,
+) -> Result<Vec<EmailMessageRecord>> {
+    state
+        .rag_repository
+        .list_email_messages(
+            collection_id,
+            &filter.unwrap_or_default(),
+            limit.unwrap_or(DEFAULT_MESSAGE_LIMIT).max(1),
+        )
+        .await
+}
+
+/// Hybrid search over the chunks of messages that match `filter`
+#[tauri::command]
+pub async fn rag_search_emails(
+    state: State<'_, Arc<super::AppState>>,
+    collection_id: i64,
+    query: String,
+    filter: Option<EmailFilter>,
+    top_k: Option<usize>,
+) -> Result<Vec<QueryResult>> {
+    let filter = filter.unwrap_or_default();
+    let top_k = top_k.unwrap_or(5);
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "Email search in collection {}: {} ({:?})",
+            collection_id, query, filter
+        ),
+    );
+
+    let start = Instant::now();
+    let results = if filter.is_empty() {
+        state
+            .retrieval_service
+            .query(collection_id, &query, top_k)
+            .await?
+    } else {
+        let allowed = state
+            .rag_repository
+            .email_chunk_ids(collection_id, &filter)
+            .await?;
+        if allowed.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut results = state
+            .retrieval_service
+            .query(collection_id, &query, top_k * FILTERED_SEARCH_OVERFETCH)
+            .await?;
+        results.retain(|result| {
+            result.source_type == "text_chunk" && allowed.contains(&result.source_id)
+        });
+        results.truncate(top_k);
+        results
+    };
+
+    state.analytics_logger.log_retrieval(
+        &query,
+        collection_id,
+        results.len(),
+        average_score(&results),
+        start.elapsed().as_millis() as u64,
+    );
+    record_chunk_retrievals(&state, collection_id, &results).await;
+    Ok(results)
+}
@@ -0,0 +1,287 @@
+//! Local Embedding Model Commands
+//!
+//! Lists the models that can run in-process, downloads them into the app
+//! data dir with checksum verification and progress events, switches the
+//! active embedding model, reports model dimension and size, and measures
+//! embedding throughput on each execution provider.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Emitter, State};
+
+use crate::application::use_cases::embedding_service::{
+    EmbeddingService, LocalModelSpec, LOCAL_EMBEDDING_MODELS,
+};
+use crate::domain::embedding_models::{
+    benchmark_texts, EmbeddingBenchmarkReport, EmbeddingExecutionProvider,
+    EmbeddingModelDownloadProgress, LocalEmbeddingModel,
+};
+use crate::domain::error::{AppError, Result};
+use crate::domain::llm_config::{LLMConfig, LLMProvider};
+use crate::domain::settings::ModelSettings;
+use crate::infrastructure::embedding_models::EmbeddingModelStore;
+use crate::interfaces::http::add_log;
+use crate::interfaces::tauri::settings_commands::persist_settings;
+
+/// Texts embedded per provider by the benchmark unless the UI asks for another count
+const DEFAULT_BENCHMARK_SAMPLES: usize = 256;
+const MAX_BENCHMARK_SAMPLES: usize = 4_096;
+const BENCHMARK_BATCH_SIZE: usize = 32;
+
+/// Emitted with an `EmbeddingModelDownloadProgress` payload while a model downloads
+pub(crate) const EMBEDDING_MODEL_DOWNLOAD_PROGRESS_EMIT: &str = "embedding-model-download-progress";
+
+fn model_store(state: &super::AppState) -> Result<&Arc<EmbeddingModelStore>> {
+    state
+        .embedding_service
+        .model_store()
+        .ok_or_else(|| AppError::Internal("Embedding model store is not configured".to_string()))
+}
+
+fn model_spec(model_id: &str) -> Result<LocalModelSpec> {
+    EmbeddingService::local_model_spec(model_id)
+        .ok_or_else(|| AppError::NotFound(format!("Unknown local embedding model '{}'", model_id)))
+}
+
+fn describe(
+    state: &super::AppState,
+    store: &EmbeddingModelStore,
+    spec: &LocalModelSpec,
+) -> LocalEmbeddingModel {
+    let config = state.embedding_service.config();
+    let manifest = store.manifest(spec.id);
+    let active = matches!(config.provider, LLMProvider::Local)
+        && EmbeddingService::local_model_id(&config.model) == Some(spec.id);
+    LocalEmbeddingModel {
+        id: spec.id.to_string(),
+        model_code: spec.model_code.clone(),
+        description: spec.description.clone(),
+        dimension: spec.dimension,
+        installed: manifest.is_some(),
+        active,
+        size_bytes: manifest.map(|manifest| manifest.total_bytes()),
+        execution_provider: active
+            .then(|| state.embedding_service.local_execution_provider())
+            .flatten(),
+    }
+}
+
+#[tauri::command]
+pub async fn rag_list_embedding_models(
+    state: State<'_, Arc<super::AppState>>,
+) -> Result<Vec<LocalEmbeddingModel>> {
+    let store = model_store(&state)?;
+    Ok(LOCAL_EMBEDDING_MODELS
+        .iter()
+        .filter_map(|id| EmbeddingService::local_model_spec(id))
+        .map(|spec| describe(&state, store, &spec))
+        .collect())
+}
+
+/// Dimension and size of one model; the download size is looked up on the
+/// hub when the model is not installed
+#[tauri::command]
+pub async fn rag_get_embedding_model_info(
+    state: State<'_, Arc<super::AppState>>,
+    model_id: String,
+) -> Result<LocalEmbeddingModel> {
+    let store = model_store(&state)?;
+    let spec = model_spec(&model_id)?;
+    let mut model = describe(&state, store, &spec);
+    if !model.installed {
+        match store.remote_files(&spec.model_code, &spec.files).await {
+            Ok(files) => model.size_bytes = Some(files.iter().map(|file| file.size).sum()),
+            Err(err) => add_log(
+                &state.logs,
+                "WARN",
+                "RAG",
+                &format!("Could not get the download size of {}: {}", spec.id, err),
+            ),
+        }
+    }
+    Ok(model)
+}
+
+#[tauri::command]
+pub async fn rag_download_embedding_model(
+    app: AppHandle,
+    state: State<'_, Arc<super::AppState>>,
+    model_id: String,
+) -> Result<LocalEmbeddingModel> {
+    let store = model_store(&state)?;
+    let spec = model_spec(&model_id)?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "Downloading embedding model {} from {}",
+            spec.id, spec.model_code
+        ),
+    );
+
+    let mut last_emit = Instant::now();
+    let mut on_progress = |progress: &EmbeddingModelDownloadProgress| {
+        if last_emit.elapsed() >= Duration::from_millis(250) {
+            last_emit = Instant::now();
+            let _ = app.emit(EMBEDDING_MODEL_DOWNLOAD_PROGRESS_EMIT, progress);
+        }
+    };
+    match store
+        .download(spec.id, &spec.model_code, &spec.files, &mut on_progress)
+        .await
+    {
+        Ok(manifest) => {
+            // The last event may have been throttled away
+            let _ = app.emit(
+                EMBEDDING_MODEL_DOWNLOAD_PROGRESS_EMIT,
+                EmbeddingModelDownloadProgress {
+                    model_id: spec.id.to_string(),
+                    file: String::new(),
+                    downloaded_bytes: manifest.total_bytes(),
+                    total_bytes: manifest.total_bytes(),
+                    files_done: manifest.files.len() as u64,
+                    file_count: manifest.files.len() as u64,
+                },
+            );
+            add_log(
+                &state.logs,
+                "INFO",
+                "RAG",
+                &format!(
+                    "Embedding model {} installed ({} bytes, checksums verified)",
+                    spec.id,
+                    manifest.total_bytes()
+                ),
+            );
+        }
+        Err(err) => {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "RAG",
+                &format!("Embedding model {} download failed: {}", spec.id, err),
+            );
+            return Err(err);
+        }
+    }
+
+    // Reload the active model from the verified files
+    let model = describe(&state, store, &spec);
+    if model.active {
+        state
+            .embedding_service
+            .update_config(state.embedding_service.config());
+    }
+    Ok(model)
+}
+
+/// Make an installed model the one used for new embeddings. Collections
+/// embedded with another model need re-indexing to be searched with it.
+#[tauri::command]
+pub async fn rag_set_active_embedding_model(
+    state: State<'_, Arc<super::AppState>>,
+    model_id: String,
+) -> Result<LocalEmbeddingModel> {
+    let store = model_store(&state)?;
+    let spec = model_spec(&model_id)?;
+    if store.manifest(spec.id).is_none() {
+        return Err(AppError::ValidationError(format!(
+            "Download {} before switching to it",
+            spec.id
+        )));
+    }
+
+    let previous = state.embedding_service.config();
+    let previous_dimension = state.embedding_service.get_current_dimension();
+    let config = LLMConfig {
+        provider: LLMProvider::Local,
+        base_url: String::new(),
+        model: spec.id.to_string(),
+        api_key: None,
+        max_tokens: previous.max_tokens,
+        temperature: previous.temperature,
+    };
+    state.embedding_service.update_config(config.clone());
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "Active embedding model: {:?}/{} ({} dims) -> {} ({} dims)",
+            previous.provider, previous.model, previous_dimension, spec.id, spec.dimension
+        ),
+    );
+
+    let mut models = state.settings.get::<ModelSettings>();
+    models.embedding = Some(ModelSettings::without_api_key(&config));
+    persist_settings(&state, &models);
+    Ok(describe(&state, store, &spec))
+}
+
+/// Embeddings per second of a local model on the CPU and every GPU
+/// provider. Defaults to the active model, else the smallest one.
+#[tauri::command]
+pub async fn rag_benchmark_embedding_providers(
+    state: State<'_, Arc<super::AppState>>,
+    model_id: Option<String>,
+    sample_count: Option<usize>,
+) -> Result<EmbeddingBenchmarkReport> {
+    let config = state.embedding_service.config();
+    let model_id = model_id.unwrap_or_else(|| match config.provider {
+        LLMProvider::Local => config.model.clone(),
+        _ => LOCAL_EMBEDDING_MODELS[0].to_string(),
+    });
+    let spec = model_spec(&model_id)?;
+    let sample_count = sample_count
+        .unwrap_or(DEFAULT_BENCHMARK_SAMPLES)
+        .clamp(1, MAX_BENCHMARK_SAMPLES);
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "Benchmarking {} on each execution provider ({} texts)",
+            spec.id, sample_count
+        ),
+    );
+
+    let service = state.embedding_service.clone();
+    let model = spec.id.to_string();
+    let results = tokio::task::spawn_blocking(move || {
+        service.benchmark_local(
+            &model,
+            &EmbeddingExecutionProvider::ALL,
+            &benchmark_texts(sample_count),
+            BENCHMARK_BATCH_SIZE,
+        )
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Embedding benchmark failed: {}", e)))?;
+
+    let summary: Vec<String> = results
+        .iter()
+        .map(|result| match result.embeddings_per_sec {
+            Some(rate) => format!("{} {:.1}/s", result.provider.label(), rate),
+            None => format!("{} unavailable", result.provider.label()),
+        })
+        .collect();
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "Embedding benchmark for {}: {}",
+            spec.id,
+            summary.join(", ")
+        ),
+    );
+    Ok(EmbeddingBenchmarkReport {
+        model_id: spec.id.to_string(),
+        sample_count,
+        batch_size: BENCHMARK_BATCH_SIZE,
+        active_provider: state.embedding_service.local_execution_provider(),
+        results,
+    })
+}
@@ -0,0 +1,183 @@
+//! Git repository sources: settings, imported files and syncs.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::rag_entities::CollectionKind;
+use crate::domain::rag_git::{
+    is_remote_url, GitRepoFile, GitRepoInput, GitRepoSource, GitSyncReport,
+};
+use crate::interfaces::http::add_log;
+
+use super::AppState;
+
+/// File type of documents imported from git repositories
+const GIT_FILE_TYPE: &str = "git";
+
+#[tauri::command]
+pub async fn rag_list_git_repos(
+    state: State<'_, Arc<super::AppState>>,
+) -> Result<Vec<GitRepoSource>> {
+    state.rag_repository.list_git_repos().await
+}
+
+/// Add a repository source; its first sync runs on demand or when the
+/// scheduler next checks
+#[tauri::command]
+pub async fn rag_create_git_repo(
+    state: State<'_, Arc<super::AppState>>,
+    input: GitRepoInput,
+) -> Result<GitRepoSource> {
+    let input = input.normalized();
+    validate_input(&input)?;
+    check_collection(&state, input.collection_id).await?;
+
+    let repo = state.rag_repository.create_git_repo(&input).await?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!("Added git repository '{}' ({})", repo.name, repo.url),
+    );
+    Ok(repo)
+}
+
+/// Update a repository source; the next sync compares every file again
+#[tauri::command]
+pub async fn rag_update_git_repo(
+    state: State<'_, Arc<super::AppState>>,
+    id: i64,
+    input: GitRepoInput,
+) -> Result<GitRepoSource> {
+    let input = input.normalized();
+    validate_input(&input)?;
+    let existing = state.rag_repository.get_git_repo(id).await?;
+    if input.collection_id != existing.collection_id {
+        return Err(AppError::ValidationError(
+            "A repository's collection can't be changed; add the repository again instead"
+                .to_string(),
+        ));
+    }
+    state.rag_repository.update_git_repo(id, &input).await
+}
+
+/// Delete a repository source and its clone; the documents it imported are
+/// kept unless `delete_documents` is set
+#[tauri::command]
+pub async fn rag_delete_git_repo(
+    state: State<'_, Arc<super::AppState>>,
+    id: i64,
+    delete_documents: Option<bool>,
+) -> Result<()> {
+    let repo = state.rag_repository.get_git_repo(id).await?;
+    if delete_documents.unwrap_or(false) {
+        for file in state.rag_repository.list_git_repo_files(id).await? {
+            if let Some(doc_id) = file.doc_id {
+                state.rag_repository.delete_document(doc_id).await?;
+            }
+        }
+    }
+    state.rag_repository.delete_git_repo(id).await?;
+
+    let checkout_dir = state.storage.git_checkout_dir(id);
+    if checkout_dir.exists() {
+        if let Err(err) = std::fs::remove_dir_all(&checkout_dir) {
+            add_log(
+                &state.logs,
+                "WARN",
+                "RAG",
+                &format!(
+                    "Failed to remove the clone of '{}' at {}: {}",
+                    repo.name,
+                    checkout_dir.display(),
+                    err
+                ),
+            );
+        }
+    }
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!("Deleted git repository '{}'", repo.name),
+    );
+    Ok(())
+}
+
+/// Imported files with the commit and blob each was read from
+#[tauri::command]
+pub async fn rag_list_git_repo_files(
+    state: State<'_, Arc<super::AppState>>,
+    repo_id: i64,
+) -> Result<Vec<GitRepoFile>> {
+    state.rag_repository.list_git_repo_files(repo_id).await
+}
+
+#[tauri::command]
+pub async fn rag_sync_git_repo(
+    state: State<'_, Arc<super::AppState>>,
+    id: i64,
+) -> Result<GitSyncReport> {
+    let repo = state.rag_repository.get_git_repo(id).await?;
+    sync_git_repo(&state, &repo).await
+}
+
+/// Sync a repository source. Shared with the sync scheduler.
+pub(crate) async fn sync_git_repo(state: &AppState, repo: &GitRepoSource) -> Result<GitSyncReport> {
+    state
+        .rag_ingestion_use_case
+        .sync_git_repo(
+            repo,
+            &state.storage.git_checkout_dir(repo.id),
+            state.logs.clone(),
+        )
+        .await
+        .inspect_err(|err| {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "RAG",
+                &format!("Git repository '{}' sync failed: {}", repo.name, err),
+            );
+        })
+}
+
+fn validate_input(input: &GitRepoInput) -> Result<()> {
+    input.validate()?;
+    if !is_remote_url(&input.url) && !Path::new(&input.url).is_dir() {
+        return Err(AppError::ValidationError(format!(
+            "Repository folder not found: {}",
+            input.url
+        )));
+    }
+    Ok(())
+}
+
+/// The target collection can't be a database collection or hold other document types
+async fn check_collection(state: &AppState, collection_id: i64) -> Result<()> {
+    let collection = state.rag_repository.get_collection(collection_id).await?;
+    if collection.kind == CollectionKind::Db {
+        return Err(AppError::ValidationError(format!(
+            "Collection '{}' is a Database Collection and can't hold repository files",
+            collection.name
+        )));
+    }
+
+    // One collection holds one document type
+    let existing_type = state
+        .rag_repository
+        .get_collection_document_type(collection_id)
+        .await?;
+    if let Some(existing_type) = existing_type {
+        if existing_type.to_lowercase() != GIT_FILE_TYPE {
+            return Err(AppError::ValidationError(format!(
+                "Collection '{}' already contains {} documents. Please create a new collection for repository files.",
+                collection.name, existing_type
+            )));
+        }
+    }
+    Ok(())
+}
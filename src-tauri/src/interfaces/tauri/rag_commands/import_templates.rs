@@ -0,0 +1,79 @@
+//! Column mapping templates for recurring CSV/Excel imports. A file whose
+//! header matches a template has its rows stored with it on import.
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::rag_import_templates::{ImportTemplate, ImportTemplateInput};
+use crate::interfaces::http::add_log;
+
+/// Templates of a collection and those for any collection, or all templates
+#[tauri::command]
+pub async fn rag_list_import_templates(
+    state: State<'_, Arc<super::AppState>>,
+    collection_id: Option<i64>,
+) -> Result<Vec<ImportTemplate>> {
+    state
+        .rag_repository
+        .list_import_templates(collection_id)
+        .await
+}
+
+#[tauri::command]
+pub async fn rag_create_import_template(
+    state: State<'_, Arc<super::AppState>>,
+    input: ImportTemplateInput,
+) -> Result<ImportTemplate> {
+    let input = input.normalized();
+    input.validate()?;
+    if let Some(collection_id) = input.collection_id {
+        state.rag_repository.get_collection(collection_id).await?;
+    }
+
+    let template = state.rag_repository.create_import_template(&input).await?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "Added import template '{}' ({} columns)",
+            template.name,
+            template.columns.len()
+        ),
+    );
+    Ok(template)
+}
+
+/// Update a template; files already imported keep their rows
+#[tauri::command]
+pub async fn rag_update_import_template(
+    state: State<'_, Arc<super::AppState>>,
+    id: i64,
+    input: ImportTemplateInput,
+) -> Result<ImportTemplate> {
+    let input = input.normalized();
+    input.validate()?;
+    if let Some(collection_id) = input.collection_id {
+        state.rag_repository.get_collection(collection_id).await?;
+    }
+    state
+        .rag_repository
+        .update_import_template(id, &input)
+        .await
+}
+
+#[tauri::command]
+pub async fn rag_delete_import_template(
+    state: State<'_, Arc<super::AppState>>,
+    id: i64,
+) -> Result<()> {
+    if state.rag_repository.delete_import_template(id).await? == 0 {
+        return Err(AppError::NotFound(format!(
+            "Import template not found: {}",
+            id
+        )));
+    }
+    Ok(())
+}
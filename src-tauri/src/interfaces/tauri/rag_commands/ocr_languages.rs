@@ -0,0 +1,139 @@
+//! OCR Language Commands
+//!
+//! Lists the Tesseract languages that are installed or can be downloaded,
+//! downloads additional language data on demand and keeps the per-document
+//! language hint used by enhanced OCR.
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::domain::error::Result;
+use crate::domain::ocr_languages::OcrLanguage;
+use crate::interfaces::http::add_log;
+
+/// `languages` as the `-l` argument, or the document's stored hint when none
+/// are given. Explicit languages become the document's new hint.
+pub(crate) async fn resolve_ocr_languages(
+    state: &super::AppState,
+    languages: Option<&[String]>,
+    document_id: Option<i64>,
+) -> Result<Option<String>> {
+    match (
+        languages.filter(|languages| !languages.is_empty()),
+        document_id,
+    ) {
+        (Some(languages), document_id) => {
+            let languages = state.ocr_languages.language_arg(languages)?;
+            if let Some(document_id) = document_id {
+                state
+                    .rag_repository
+                    .set_document_ocr_languages(document_id, Some(&languages))
+                    .await?;
+            }
+            Ok(Some(languages))
+        }
+        (None, Some(document_id)) => {
+            match state
+                .rag_repository
+                .get_document_ocr_languages(document_id)
+                .await?
+            {
+                Some(hint) => Ok(Some(state.ocr_languages.language_arg(&[hint])?)),
+                None => Ok(None),
+            }
+        }
+        (None, None) => Ok(None),
+    }
+}
+
+#[tauri::command]
+pub async fn rag_list_ocr_languages(
+    state: State<'_, Arc<super::AppState>>,
+) -> Result<Vec<OcrLanguage>> {
+    Ok(state.ocr_languages.list())
+}
+
+#[tauri::command]
+pub async fn rag_download_ocr_language(
+    state: State<'_, Arc<super::AppState>>,
+    code: String,
+) -> Result<OcrLanguage> {
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!("Downloading OCR language data: {}", code),
+    );
+
+    match state.ocr_languages.download(&code).await {
+        Ok(language) => {
+            add_log(
+                &state.logs,
+                "INFO",
+                "RAG",
+                &format!(
+                    "OCR language {} installed ({} bytes, checksum verified)",
+                    language.code,
+                    language.size_bytes.unwrap_or_default()
+                ),
+            );
+            Ok(language)
+        }
+        Err(err) => {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "RAG",
+                &format!("OCR language {} download failed: {}", code, err),
+            );
+            Err(err)
+        }
+    }
+}
+
+/// Language hint of a document as tesseract codes; empty when it has none
+#[tauri::command]
+pub async fn rag_get_document_ocr_languages(
+    state: State<'_, Arc<super::AppState>>,
+    document_id: i64,
+) -> Result<Vec<String>> {
+    let hint = state
+        .rag_repository
+        .get_document_ocr_languages(document_id)
+        .await?;
+    Ok(hint
+        .map(|hint| hint.split('+').map(str::to_string).collect())
+        .unwrap_or_default())
+}
+
+/// Store the languages a document is written in; an empty list clears the hint
+#[tauri::command]
+pub async fn rag_set_document_ocr_languages(
+    state: State<'_, Arc<super::AppState>>,
+    document_id: i64,
+    languages: Vec<String>,
+) -> Result<Vec<String>> {
+    let hint = if languages.is_empty() {
+        None
+    } else {
+        Some(state.ocr_languages.language_arg(&languages)?)
+    };
+    state
+        .rag_repository
+        .set_document_ocr_languages(document_id, hint.as_deref())
+        .await?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "OCR languages for document {}: {}",
+            document_id,
+            hint.as_deref().unwrap_or("default")
+        ),
+    );
+    Ok(hint
+        .map(|hint| hint.split('+').map(str::to_string).collect())
+        .unwrap_or_default())
+}
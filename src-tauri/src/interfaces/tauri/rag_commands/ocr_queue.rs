@@ -0,0 +1,98 @@
+//! OCR Reprocessing Queue Commands
+//!
+//! Lists the confidence Tesseract reported for OCR'd pages, runs the queue
+//! of low-confidence pages through the retry renderings and puts pages back
+//! in the queue on request.
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::application::use_cases::rag_ingestion::OcrQueueReport;
+use crate::domain::error::Result;
+use crate::infrastructure::db::rag::repository::OcrPageRecord;
+use crate::interfaces::http::add_log;
+
+/// Pages OCR'd per queue run unless the caller asks for another amount
+const DEFAULT_OCR_QUEUE_BATCH: i64 = 5;
+
+/// OCR'd pages with their confidence, optionally of one document or in one
+/// status (ok, queued, improved, exhausted)
+#[tauri::command]
+pub async fn rag_list_ocr_pages(
+    state: State<'_, Arc<super::AppState>>,
+    document_id: Option<i64>,
+    status: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<OcrPageRecord>> {
+    state
+        .rag_repository
+        .list_ocr_pages(document_id, status.as_deref(), limit.unwrap_or(500))
+        .await
+}
+
+#[tauri::command]
+pub async fn rag_process_ocr_queue(
+    state: State<'_, Arc<super::AppState>>,
+    limit: Option<i64>,
+) -> Result<OcrQueueReport> {
+    let limit = limit.unwrap_or(DEFAULT_OCR_QUEUE_BATCH).max(1);
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!("Processing up to {} queued OCR pages", limit),
+    );
+
+    let report = state
+        .rag_ingestion_use_case
+        .process_ocr_queue(limit, &state.logs)
+        .await
+        .map_err(|e| {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "RAG",
+                &format!("OCR queue run failed: {}", e),
+            );
+            e
+        })?;
+
+    // Replaced chunks must not be served from cached retrievals
+    for document_id in &report.updated_documents {
+        if let Some(collection_id) = state
+            .rag_repository
+            .get_document(*document_id)
+            .await?
+            .collection_id
+        {
+            state
+                .retrieval_service
+                .invalidate_collection_cache(collection_id)
+                .await?;
+        }
+    }
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn rag_requeue_ocr_page(
+    state: State<'_, Arc<super::AppState>>,
+    document_id: i64,
+    page_number: i64,
+) -> Result<OcrPageRecord> {
+    let page = state
+        .rag_ingestion_use_case
+        .requeue_ocr_page(document_id, page_number)
+        .await?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "Page {} of document {} queued for OCR reprocessing",
+            page_number, document_id
+        ),
+    );
+    Ok(page)
+}
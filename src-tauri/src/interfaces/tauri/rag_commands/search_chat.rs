@@ -9,16 +9,26 @@
 use crate::application::use_cases::prompt_engine::{PromptEngine, VerificationResult};
 use crate::application::use_cases::context_manager::{ContextManager, BuildContext};
 use crate::application::use_cases::conversation_service::ConversationMessage;
-use crate::domain::error::Result;
+use crate::application::use_cases::rag_analytics::hash_query;
+use crate::application::use_cases::rag_answer_cache::{
+    AnswerCacheKey, AnswerCacheLookup, AnswerCacheSlot,
+};
+use crate::application::use_cases::retrieval_service::{QueryRoute, RoutingDecision};
+use crate::domain::error::{AppError, Result};
+use crate::domain::telemetry::UsageSubsystem;
 use crate::interfaces::http::add_log;
 use std::sync::Arc;
 use std::time::Instant;
 use tauri::State;
 
 use super::analytics_cache_metrics::truncate_message;
-use super::chunks::average_score;
+use super::chunks::{average_score, record_chunk_retrievals};
+use super::config_feedback::apply_retrieval_experiment;
 use super::types::*;
 
+/// System prompt for answering from a built RAG prompt outside the chat UI
+pub(crate) const RAG_ANSWER_SYSTEM_PROMPT: &str = "You are a helpful assistant answering questions based on the provided context. If the context is insufficient, say so instead of guessing. Respond with only the final answer and do not include source citations.";
+
 #[tauri::command]
 pub async fn rag_hybrid_search(
     state: State<'_, Arc<super::AppState>>,
@@ -55,6 +65,7 @@ pub async fn rag_hybrid_search(
         average_score(&results),
         start.elapsed().as_millis() as u64,
     );
+    record_chunk_retrievals(&state, collection_id, &results).await;
 
     Ok(results)
 }
@@ -65,6 +76,24 @@ pub async fn rag_query(
     state: State<'_, Arc<super::AppState>>,
     request: RagQueryRequest,
 ) -> Result<RagQueryResponse> {
+    state
+        .telemetry
+        .track(UsageSubsystem::Rag, "query", run_rag_query(&state, request))
+        .await
+}
+
+/// Retrieve context for `request.query` and build the answer prompt
+pub(crate) async fn run_rag_query(
+    state: &super::AppState,
+    request: RagQueryRequest,
+) -> Result<RagQueryResponse> {
+    // Reachable over HTTP and from the quick-action window before the main
+    // window has loaded the RAG database in background mode
+    state
+        .deferred_init
+        .ensure()
+        .await
+        .map_err(AppError::DatabaseError)?;
     add_log(
         &state.logs,
         "INFO",
@@ -119,6 +148,7 @@ pub async fn rag_query(
     }
 
     let mut config = state.config_manager.get_config();
+    apply_retrieval_experiment(&state, &request.query, &mut config).await;
     if let Some(k) = request.candidate_k {
         config.retrieval.candidate_k = k;
     }
@@ -127,14 +157,15 @@ pub async fn rag_query(
     }
 
     let top_k = request.top_k.unwrap_or(config.retrieval.top_k);
-    let results = state
+    let (results, routing) = state
         .retrieval_service
-        .query_optimized_with_config(
+        .query_routed(
             request.collection_id,
             &request.query,
             top_k,
             &config,
             &state.logs,
+            request.route,
         )
         .await
         .map_err(|e| {
@@ -147,15 +178,19 @@ pub async fn rag_query(
             e
         })?;
 
-    state.analytics_logger.log_retrieval(
+    state.analytics_logger.log_routed_retrieval(
         &request.query,
         request.collection_id,
         results.len(),
-        average_score(&results),
+        &routing,
         start.elapsed().as_millis() as u64,
     );
+    record_chunk_retrievals(state, request.collection_id, &results).await;
+    let routing_decision_id =
+        record_routing_decision(state, request.collection_id, &request.query, &routing).await;
 
-    let language = request.language.as_deref();
+    let language = prompt_language(state, request.language.as_deref());
+    let language = language.as_deref();
     let prompt = if request.enable_few_shot.unwrap_or(false) {
         PromptEngine::build_conversational_nl_prompt_with_few_shot(
             &request.query,
@@ -182,7 +217,48 @@ pub async fn rag_query(
         &format!("Built prompt with {} results", results.len()),
     );
 
-    Ok(RagQueryResponse { prompt, results })
+    Ok(RagQueryResponse {
+        prompt,
+        results,
+        route: routing.route,
+        route_overridden: routing.overridden,
+        routing_decision_id,
+    })
+}
+
+/// Keep how the query was routed so the route can be corrected later.
+/// Failures are logged; the query is answered either way.
+async fn record_routing_decision(
+    state: &super::AppState,
+    collection_id: i64,
+    query: &str,
+    routing: &RoutingDecision,
+) -> Option<i64> {
+    let recorded = state
+        .rag_repository
+        .record_routing_decision(
+            collection_id,
+            &hash_query(query),
+            &format!("{:?}", routing.query_type),
+            routing.auto_route.map(QueryRoute::as_str),
+            routing.route.map(QueryRoute::as_str),
+            routing.overridden,
+            routing.signals.structured as i64,
+            routing.signals.terms as i64,
+        )
+        .await;
+    match recorded {
+        Ok(id) => Some(id),
+        Err(err) => {
+            add_log(
+                &state.logs,
+                "WARN",
+                "RAG",
+                &format!("Failed to record routing decision: {}", err),
+            );
+            None
+        }
+    }
 }
 
 
@@ -191,6 +267,24 @@ pub async fn rag_chat_with_context(
     state: State<'_, Arc<super::AppState>>,
     request: ChatWithContextRequest,
 ) -> Result<ChatWithContextResponse> {
+    state
+        .telemetry
+        .track(UsageSubsystem::Rag, "chat", run_chat_with_context(&state, request))
+        .await
+}
+
+/// Build a chat prompt from retrieved context and the (compacted) message history
+pub(crate) async fn run_chat_with_context(
+    state: &super::AppState,
+    request: ChatWithContextRequest,
+) -> Result<ChatWithContextResponse> {
+    // Reachable over HTTP and from the quick-action window before the main
+    // window has loaded the RAG database in background mode
+    state
+        .deferred_init
+        .ensure()
+        .await
+        .map_err(AppError::DatabaseError)?;
     add_log(
         &state.logs,
         "INFO",
@@ -205,7 +299,8 @@ pub async fn rag_chat_with_context(
     let top_k = request.top_k.unwrap_or(5);
 
     // Use optimized query for better results
-    let config = state.config_manager.get_config();
+    let mut config = state.config_manager.get_config();
+    apply_retrieval_experiment(&state, &request.query, &mut config).await;
     let results = state
         .retrieval_service
         .query_optimized_with_config(
@@ -227,7 +322,8 @@ pub async fn rag_chat_with_context(
         })?;
 
     // Extract language for conversational responses
-    let language = request.language.as_deref();
+    let language = prompt_language(state, request.language.as_deref());
+    let language = language.as_deref();
 
     // Build RAG context string from results
     let rag_context = results
@@ -407,6 +503,7 @@ pub async fn rag_chat_with_context(
         average_score(&results),
         duration_ms,
     );
+    record_chunk_retrievals(state, request.collection_id, &results).await;
     state.analytics_logger.log_chat(
         &request.query,
         Some(request.collection_id),
@@ -425,6 +522,91 @@ pub async fn rag_chat_with_context(
     })
 }
 
+/// Look up a question in the answer cache before retrieving context for it.
+/// A hit is logged to analytics as a cached chat; on a miss the returned
+/// slot is passed to `rag_cache_answer` with the generated answer.
+#[tauri::command]
+pub async fn rag_find_cached_answer(
+    state: State<'_, Arc<super::AppState>>,
+    request: AnswerCacheRequest,
+) -> Result<AnswerCacheLookup> {
+    state
+        .deferred_init
+        .ensure()
+        .await
+        .map_err(AppError::DatabaseError)?;
+    let start = Instant::now();
+    let key = AnswerCacheKey {
+        query: &request.query,
+        top_k: request.top_k,
+        provider: &request.provider,
+        model: &request.model,
+        language: request.language.as_deref(),
+        variant: &request.variant,
+    };
+    let lookup = state
+        .rag_answer_cache
+        .lookup(request.collection_id, &key, request.bypass_cache)
+        .await;
+    if let Some(cached) = &lookup.cached {
+        log_cached_answer(
+            &state,
+            request.collection_id,
+            &request.query,
+            cached.answer.len(),
+            cached.hit_count,
+            start,
+        );
+    }
+    Ok(lookup)
+}
+
+/// Store an answer generated after an answer cache miss
+#[tauri::command]
+pub async fn rag_cache_answer(
+    state: State<'_, Arc<super::AppState>>,
+    slot: AnswerCacheSlot,
+    answer: String,
+    results: Vec<crate::application::QueryResult>,
+) -> Result<()> {
+    state.rag_answer_cache.store(&slot, &answer, &results).await;
+    Ok(())
+}
+
+/// Log and record an answer served from the answer cache
+pub(crate) fn log_cached_answer(
+    state: &super::AppState,
+    collection_id: i64,
+    query: &str,
+    answer_length: usize,
+    hit_count: i64,
+    start: Instant,
+) {
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!(
+            "Answered from the answer cache in collection {} (hit {})",
+            collection_id, hit_count
+        ),
+    );
+    state.analytics_logger.log_cached_chat(
+        query,
+        collection_id,
+        answer_length,
+        start.elapsed().as_millis() as u64,
+    );
+}
+
+/// Answer language for the prompt builders: the requested one or the
+/// `outputLanguage` setting, lowercased the way they match it
+fn prompt_language(state: &super::AppState, requested: Option<&str>) -> Option<String> {
+    state
+        .output_language(requested)
+        .map(|language| language.language.to_lowercase())
+}
+
 /// Build verification prompt for self-correcting RAG
 
 #[tauri::command]
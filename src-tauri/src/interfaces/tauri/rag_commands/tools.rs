@@ -0,0 +1,336 @@
+//! Tool Calling for RAG Chat
+//!
+//! Lets the chat model call the QA API request executor while answering.
+//! Calls are limited to the whitelisted origins in the `ragTools` settings
+//! and recorded as QA API calls in one run of the configured QA session.
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::rag_tools::{
+    parse_tool_call, RagApiToolCall, RagToolCallRecord, RagToolSettings, QA_API_TOOL,
+};
+use crate::domain::settings::LlmFeature;
+use crate::domain::telemetry::UsageSubsystem;
+use crate::interfaces::http::add_log;
+use crate::interfaces::tauri::qa::api::build_api_request;
+use crate::interfaces::tauri::qa::types::{QaApiKeyValue, QaApiRequest};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::State;
+
+use super::types::{RagToolAnswerRequest, RagToolAnswerResponse};
+
+const TOOL_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[tauri::command]
+pub async fn rag_answer_with_tools(
+    state: State<'_, Arc<super::AppState>>,
+    mut request: RagToolAnswerRequest,
+) -> Result<RagToolAnswerResponse> {
+    request.config = state
+        .model_resolver
+        .resolve(LlmFeature::RagChat, &request.config);
+    let settings = state.settings.get::<RagToolSettings>();
+    if !settings.enabled {
+        return Err(AppError::ValidationError(
+            "RAG chat tools are disabled".to_string(),
+        ));
+    }
+    settings.validate()?;
+    let session_id = settings.session_id().unwrap_or_default().to_string();
+    state.qa_session_use_case.get_session(&session_id).await?;
+
+    let mut tools = ToolRun {
+        state: &state,
+        settings: &settings,
+        session_id,
+        run_id: None,
+        calls: Vec::new(),
+    };
+    let started = Instant::now();
+    let result = tools.answer(&request).await;
+    state
+        .telemetry
+        .record(UsageSubsystem::Rag, "tool_answer", started, result.is_ok());
+
+    if let Some(run_id) = &tools.run_id {
+        let status = if result.is_ok() {
+            "completed"
+        } else {
+            "failed"
+        };
+        if let Err(err) = state.qa_run_use_case.end_run(run_id, status).await {
+            add_log(
+                &state.logs,
+                "WARN",
+                "RAG",
+                &format!("Failed to end tool run {}: {}", run_id, err),
+            );
+        }
+    }
+
+    let answer = result?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "RAG",
+        &format!("Answered with {} tool call(s)", tools.calls.len()),
+    );
+    Ok(RagToolAnswerResponse {
+        answer,
+        tool_calls: tools.calls,
+        qa_run_id: tools.run_id,
+    })
+}
+
+fn tool_instructions(settings: &RagToolSettings) -> String {
+    [
+        "You can call an HTTP API when the question needs live data that the context does not contain.".to_string(),
+        "To call it, reply with only this JSON object and nothing else:".to_string(),
+        format!(
+            r#"{{"tool": "{QA_API_TOOL}", "method": "GET", "url": "https://...", "headers": {{}}, "body": null}}"#
+        ),
+        format!(
+            "Only these origins are allowed: {}.",
+            settings.allowed_origins.join(", ")
+        ),
+        "The response is sent back to you; then answer the question using it.".to_string(),
+        format!(
+            "You may make at most {} call(s) per answer.",
+            settings.max_calls_per_answer
+        ),
+    ]
+    .join("\n")
+}
+
+/// Tool calls made while producing one answer
+struct ToolRun<'a> {
+    state: &'a super::AppState,
+    settings: &'a RagToolSettings,
+    session_id: String,
+    /// QA run holding the calls, started with the first one
+    run_id: Option<String>,
+    calls: Vec<RagToolCallRecord>,
+}
+
+impl ToolRun<'_> {
+    async fn answer(&mut self, request: &RagToolAnswerRequest) -> Result<String> {
+        let language = self
+            .state
+            .output_language(request.output_language.as_deref());
+        let mut system = format!(
+            "{}\n\n{}",
+            request.system_prompt.trim(),
+            tool_instructions(self.settings)
+        );
+        if let Some(language) = language.as_ref() {
+            system = language.system_prompt(&system);
+        }
+        let max_calls = self.settings.max_calls_per_answer as usize;
+        let mut transcript = request.prompt.clone();
+
+        loop {
+            let reply = self
+                .state
+                .llm_client
+                .generate(&request.config, &system, &transcript)
+                .await?;
+            let Some(call) = parse_tool_call(&reply) else {
+                let reply = match language.as_ref() {
+                    Some(language) => {
+                        let client = self.state.llm_client.as_ref();
+                        language
+                            .enforce(client, &request.config, &system, &transcript, reply)
+                            .await?
+                    }
+                    None => reply,
+                };
+                return Ok(reply.trim().to_string());
+            };
+            if self.calls.len() >= max_calls {
+                return Err(AppError::LLMError(format!(
+                    "The model asked for more than {} API call(s)",
+                    max_calls
+                )));
+            }
+
+            let (record, result) = self.call(call).await;
+            transcript.push_str(&format!(
+                "\n\nTool call: {} {}\nTool result:\n{}",
+                record.method, record.url, result
+            ));
+            self.calls.push(record);
+            if self.calls.len() >= max_calls {
+                transcript.push_str(
+                    "\n\nNo more API calls are allowed. Answer now from the information above.",
+                );
+            }
+        }
+    }
+
+    /// Run one call and describe its outcome for the model
+    async fn call(&mut self, call: RagApiToolCall) -> (RagToolCallRecord, String) {
+        let mut record = RagToolCallRecord {
+            method: call.method.trim().to_uppercase(),
+            url: call.url.trim().to_string(),
+            status: None,
+            duration_ms: None,
+            error: None,
+            api_call_id: None,
+        };
+        if !self.settings.allows(&record.url) {
+            let message = format!("{} is not on an allowed origin", record.url);
+            add_log(
+                &self.state.logs,
+                "WARN",
+                "RAG",
+                &format!("[Tool] refused: {}", message),
+            );
+            record.error = Some(message.clone());
+            return (record, format!("Refused: {}", message));
+        }
+
+        match self.send(&call, &mut record).await {
+            Ok(result) => (record, result),
+            Err(err) => {
+                add_log(
+                    &self.state.logs,
+                    "ERROR",
+                    "RAG",
+                    &format!("[Tool] {} {} failed: {}", record.method, record.url, err),
+                );
+                record.error = Some(err.to_string());
+                (record, format!("Request failed: {}", err))
+            }
+        }
+    }
+
+    async fn send(
+        &mut self,
+        call: &RagApiToolCall,
+        record: &mut RagToolCallRecord,
+    ) -> Result<String> {
+        let run_id = match &self.run_id {
+            Some(run_id) => run_id.clone(),
+            None => {
+                let run = self
+                    .state
+                    .qa_run_use_case
+                    .start_run(
+                        &self.session_id,
+                        "rag_tool",
+                        "api",
+                        "rag_chat",
+                        None,
+                        None,
+                        None,
+                    )
+                    .await?;
+                self.run_id = Some(run.id.clone());
+                run.id
+            }
+        };
+
+        let request = QaApiRequest {
+            method: record.method.clone(),
+            url: record.url.clone(),
+            headers: call
+                .headers
+                .iter()
+                .map(|(key, value)| QaApiKeyValue {
+                    key: key.clone(),
+                    value: value.clone(),
+                    enabled: true,
+                })
+                .collect(),
+            query_params: Vec::new(),
+            body_type: call.body.as_ref().map(|_| "json".to_string()),
+            body_json: call.body.as_ref().map(|body| body.to_string()),
+            form_data: Vec::new(),
+            source: Some("rag_chat".to_string()),
+            auth_profile_id: None,
+            assertions: Vec::new(),
+        };
+        // Redirects could leave the whitelisted origin
+        let client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .timeout(TOOL_REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| AppError::Internal(format!("Failed to build HTTP client: {}", e)))?;
+        let builder = build_api_request(&client, &request)?;
+        add_log(
+            &self.state.logs,
+            "INFO",
+            "RAG",
+            &format!("[Tool] {} {}", record.method, record.url),
+        );
+
+        let started = Instant::now();
+        let outcome = match builder.send().await {
+            Ok(response) => {
+                let status = response.status().as_u16();
+                let headers: BTreeMap<String, String> = response
+                    .headers()
+                    .iter()
+                    .filter_map(|(key, value)| {
+                        value
+                            .to_str()
+                            .ok()
+                            .map(|v| (key.to_string(), v.to_string()))
+                    })
+                    .collect();
+                response
+                    .text()
+                    .await
+                    .map(|body| (status, headers, body))
+                    .map_err(|e| format!("Failed to read API response body: {}", e))
+            }
+            Err(err) => Err(format!("API request failed: {}", err)),
+        };
+        let timing_ms = started.elapsed().as_millis() as i64;
+        record.duration_ms = Some(timing_ms);
+
+        let (status, response_headers_json, body) = match &outcome {
+            Ok((status, headers, body)) => (
+                Some(*status as i64),
+                serde_json::to_string(headers).ok(),
+                Some(body.clone()),
+            ),
+            Err(_) => (None, None, None),
+        };
+        let api_call = self
+            .state
+            .qa_api_call_use_case
+            .record_api_call(
+                &self.session_id,
+                &run_id,
+                &record.method,
+                &record.url,
+                serde_json::to_string(&call.headers).ok(),
+                request.body_json.clone(),
+                status,
+                response_headers_json,
+                body,
+                Some(timing_ms),
+            )
+            .await?;
+        record.api_call_id = Some(api_call.id);
+
+        let (status, _, body) = outcome.map_err(AppError::Internal)?;
+        record.status = Some(status);
+        add_log(
+            &self.state.logs,
+            "INFO",
+            "RAG",
+            &format!("[Tool] status={} body_len={}", status, body.len()),
+        );
+
+        let limit = self.settings.max_response_chars;
+        let mut result = format!("Status: {}\n", status);
+        result.extend(body.chars().take(limit));
+        if body.chars().nth(limit).is_some() {
+            result.push_str("\n[response truncated]");
+        }
+        Ok(result)
+    }
+}
@@ -5,8 +5,15 @@
 
 use crate::application::use_cases::rag_config::{ChunkingConfig, OcrConfig};
 use crate::application::use_cases::rag_validation::{ValidationCase, ValidationOptions};
+use crate::application::use_cases::retrieval_service::{
+    QueryRoute, RetrievalExplanation, RoutingFit,
+};
+use crate::domain::csv::{CsvProfile, CsvProgress};
 use crate::domain::rag_entities::RagDocumentChunk;
+use crate::infrastructure::playwright::CaptureCookie;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
 
 // Re-export quality analytics types
 pub use crate::domain::rag_entities::{
@@ -14,7 +21,7 @@ pub use crate::domain::rag_entities::{
     RetrievalGapInput,
 };
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct RagQueryRequest {
     pub collection_id: i64,
     pub query: String,
@@ -29,12 +36,32 @@ pub struct RagQueryRequest {
     /// Language for conversational responses ('id', 'en', 'indonesia', 'english', etc.)
     #[serde(default)]
     pub language: Option<String>,
+    /// Run on this route instead of the one the query analyzer picks
+    #[serde(default)]
+    pub route: Option<QueryRoute>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct RagQueryResponse {
     pub prompt: String,
     pub results: Vec<crate::application::QueryResult>,
+    /// Route the query ran on; `None` for column/value lookups
+    pub route: Option<QueryRoute>,
+    /// `route` was forced by the request
+    pub route_overridden: bool,
+    /// Id to correct the route with (`rag_correct_query_route`); `None`
+    /// when the decision could not be recorded
+    pub routing_decision_id: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RouteCorrectionReport {
+    pub decision_id: i64,
+    /// Thresholds refit from the corrections so far; `None` until there
+    /// are enough of them
+    pub fit: Option<RoutingFit>,
+    /// The proposed thresholds were saved to the retrieval config
+    pub applied: bool,
 }
 
 // ============================================================
@@ -45,6 +72,13 @@ pub struct RagQueryResponse {
 pub struct EnhancedOcrRequest {
     pub file_path: String,
     pub config: Option<OcrConfig>,
+    /// Tesseract codes to recognize together, e.g. `["eng", "jpn"]`; stored
+    /// as the document's hint when `document_id` is set
+    #[serde(default)]
+    pub languages: Option<Vec<String>>,
+    /// Document whose language hint applies when `languages` is not given
+    #[serde(default)]
+    pub document_id: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -67,12 +101,17 @@ pub struct HybridRetrievalOptions {
     pub top_k: Option<usize>,
     pub use_cache: Option<bool>,
     pub optimized: Option<bool>,
+    /// Also return how each candidate scored
+    #[serde(default)]
+    pub explain: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct HybridRetrievalResponse {
     pub results: Vec<crate::application::QueryResult>,
     pub cache_hit: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explain: Option<RetrievalExplanation>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -99,19 +138,38 @@ pub struct RagWebImportRequest {
     /// Crawl mode: "html" (default) or "ocr" (Playwright + Tesseract)
     #[serde(default)]
     pub mode: WebCrawlMode,
+    /// Login for OCR captures of pages behind authentication
+    #[serde(default)]
+    pub auth: Option<WebCaptureAuth>,
+}
+
+/// Where the OCR capture's browser gets its login from; all given sources
+/// are combined
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WebCaptureAuth {
+    /// Reuse the login the QA recorder saved in this session
+    pub qa_session_id: Option<String>,
+    /// Recorder run whose login to use; the latest one when omitted
+    pub qa_run_id: Option<String>,
+    /// Any other Playwright storage-state file
+    pub storage_state_path: Option<String>,
+    #[serde(default)]
+    pub cookies: Vec<CaptureCookie>,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
 }
 
 // ============================================================
 // CHAT WITH CONTEXT TYPES
 // ============================================================
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ChatMessage {
     pub role: String,
     pub content: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ChatWithContextRequest {
     pub collection_id: i64,
     pub query: String,
@@ -131,7 +189,7 @@ pub struct ChatWithContextRequest {
     pub model: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ChatWithContextResponse {
     pub prompt: String,
     pub results: Vec<crate::application::QueryResult>,
@@ -142,8 +200,45 @@ pub struct ChatWithContextResponse {
     pub context_managed: Option<ContextManagedInfo>,
 }
 
+/// A question looked up in the answer cache before retrieval
+#[derive(Debug, Deserialize)]
+pub struct AnswerCacheRequest {
+    pub collection_id: i64,
+    pub query: String,
+    pub top_k: usize,
+    pub provider: String,
+    pub model: String,
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Other options the answer depends on, such as strict mode
+    #[serde(default)]
+    pub variant: String,
+    /// Skip the cached answer; the new one replaces it
+    #[serde(default)]
+    pub bypass_cache: bool,
+}
+
+/// Answer a built chat prompt, letting the model call the whitelisted QA API tool
+#[derive(Debug, Deserialize)]
+pub struct RagToolAnswerRequest {
+    pub config: crate::domain::llm_config::LLMConfig,
+    pub system_prompt: String,
+    pub prompt: String,
+    /// Language the answer must be in, over the `outputLanguage` setting
+    #[serde(default)]
+    pub output_language: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RagToolAnswerResponse {
+    pub answer: String,
+    pub tool_calls: Vec<crate::domain::rag_tools::RagToolCallRecord>,
+    /// QA run holding the recorded API calls, when any call was sent
+    pub qa_run_id: Option<String>,
+}
+
 /// Information about how context was managed
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ContextManagedInfo {
     pub was_compacted: bool,
     pub strategy_used: String,
@@ -204,6 +299,10 @@ pub struct ChunkWithQuality {
 pub struct CsvPreprocessingRequest {
     pub file_path: String,
     pub config: Option<CsvPreprocessingRequestConfig>,
+    /// Write processed text to this file instead of returning it whole;
+    /// `processed_text` in the response is then only a preview
+    #[serde(default)]
+    pub output_path: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -213,6 +312,7 @@ pub struct CsvPreprocessingRequestConfig {
     pub max_numeric_ratio: Option<f32>,
     pub min_sample_rows: Option<usize>,
     pub max_sample_rows: Option<usize>,
+    pub normalize_columns: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -221,7 +321,11 @@ pub struct CsvPreprocessingResponse {
     pub processed_text: String,
     pub row_count: usize,
     pub analysis: CsvFieldAnalysis,
+    pub profile: CsvProfile,
     pub headers: Vec<String>,
+    pub encoding: String,
+    pub delimiter: String,
+    pub output_path: Option<String>,
     pub processing_time_ms: u64,
 }
 
@@ -244,6 +348,20 @@ pub struct CsvPreviewRow {
     pub content: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvProgressEvent {
+    pub file_path: String,
+    #[serde(flatten)]
+    pub progress: CsvProgress,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CsvAnalysisResponse {
+    pub report: String,
+    pub profile: CsvProfile,
+}
+
 // ============================================================
 // SQL-RAG TYPES
 // ============================================================
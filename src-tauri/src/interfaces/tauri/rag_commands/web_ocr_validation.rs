@@ -10,8 +10,11 @@
 use crate::application::use_cases::chunking::{ChunkConfig, ChunkEngine, ChunkStrategy};
 use crate::application::use_cases::rag_ingestion::OcrResult;
 use crate::application::use_cases::rag_validation::{RagValidationSuite, ValidationReport};
-use crate::domain::error::Result;
+use crate::application::use_cases::retrieval_service::ExplainTrace;
+use crate::domain::error::{AppError, Result};
 use crate::domain::rag_entities::RagDocument;
+use crate::infrastructure::playwright::CaptureAuth;
+use crate::infrastructure::storage::{latest_session_storage_state, session_storage_state_path};
 use crate::interfaces::http::add_log;
 use std::path::Path;
 use std::sync::Arc;
@@ -19,6 +22,8 @@ use std::time::Instant;
 use tauri::State;
 
 use super::chunks::average_score;
+use super::documents::{record_ingestion_finished, record_ingestion_started};
+use super::ocr_languages::resolve_ocr_languages;
 use super::types::*;
 
 #[tauri::command]
@@ -63,6 +68,10 @@ pub async fn rag_import_web(
                     "csv" => "CSV",
                     "txt" => "Text",
                     "md" => "Markdown",
+                    "notion" => "Notion pages",
+                    "confluence" => "Confluence pages",
+                    "git" => "Git repository files",
+                    "email" => "Email",
                     _ => &existing_type_normalized,
                 };
                 let err_msg = format!(
@@ -75,6 +84,17 @@ pub async fn rag_import_web(
         }
     }
 
+    let auth = match (request.auth, &request.mode) {
+        (Some(auth), WebCrawlMode::Ocr) => Some(resolve_capture_auth(&state, auth)?),
+        (Some(_), WebCrawlMode::Html) => {
+            return Err(AppError::ValidationError(
+                "Logging in is only supported for OCR captures".to_string(),
+            ))
+        }
+        (None, _) => None,
+    };
+
+    record_ingestion_started(&state, &request.url, request.collection_id);
     let start = Instant::now();
     match request.mode {
         WebCrawlMode::Html => {
@@ -104,13 +124,19 @@ pub async fn rag_import_web(
                 result.is_ok(),
                 start.elapsed().as_millis() as u64,
             );
+            record_ingestion_finished(&state, &request.url, &result, start);
             result
         }
         WebCrawlMode::Ocr => {
             // OCR mode using Playwright + Tesseract
             let result = state
                 .rag_ingestion_use_case
-                .ingest_web_ocr(&request.url, request.collection_id, state.logs.clone())
+                .ingest_web_ocr(
+                    &request.url,
+                    request.collection_id,
+                    auth.as_ref(),
+                    state.logs.clone(),
+                )
                 .await
                 .map_err(|e| {
                     add_log(
@@ -127,11 +153,81 @@ pub async fn rag_import_web(
                 result.is_ok(),
                 start.elapsed().as_millis() as u64,
             );
+            record_ingestion_finished(&state, &request.url, &result, start);
             result
         }
     }
 }
 
+/// Turn the request's login sources into what the capture script takes,
+/// finding the QA recorder's saved storage state when a session is named
+fn resolve_capture_auth(state: &super::AppState, auth: WebCaptureAuth) -> Result<CaptureAuth> {
+    let storage_state = match (auth.storage_state_path, auth.qa_session_id) {
+        (Some(path), _) => {
+            let path = std::path::PathBuf::from(path);
+            if !path.is_file() {
+                return Err(AppError::NotFound(format!(
+                    "Storage state file not found: {}",
+                    path.display()
+                )));
+            }
+            Some(path)
+        }
+        (None, Some(session_id)) => {
+            let valid_id = |id: &str| {
+                !id.is_empty()
+                    && id
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            };
+            let run_id = auth.qa_run_id.as_deref();
+            if !valid_id(&session_id) || !run_id.map(valid_id).unwrap_or(true) {
+                return Err(AppError::ValidationError(
+                    "Invalid QA session or run id".to_string(),
+                ));
+            }
+            let session_dir = state.storage.qa_sessions_dir().join(&session_id);
+            let path = match run_id {
+                Some(run_id) => Some(session_storage_state_path(&session_dir, run_id))
+                    .filter(|path| path.is_file()),
+                None => latest_session_storage_state(&session_dir),
+            };
+            Some(path.ok_or_else(|| {
+                AppError::NotFound(format!(
+                    "QA session {} has no saved login; record a browser run that signs in first",
+                    session_id
+                ))
+            })?)
+        }
+        (None, None) => None,
+    };
+
+    if auth
+        .headers
+        .iter()
+        .any(|(name, value)| name.trim().is_empty() || value.contains(['\r', '\n']))
+    {
+        return Err(AppError::ValidationError(
+            "Capture headers need a name and a single-line value".to_string(),
+        ));
+    }
+    if auth
+        .cookies
+        .iter()
+        .any(|cookie| cookie.name.trim().is_empty())
+    {
+        return Err(AppError::ValidationError(
+            "Capture cookies need a name".to_string(),
+        ));
+    }
+
+    Ok(CaptureAuth {
+        storage_state,
+        cookies: auth.cookies,
+        headers: auth.headers,
+    })
+}
+
 // ============================================================
 // PHASE 6: BACKEND API EXTENSIONS
 // ============================================================
@@ -150,9 +246,14 @@ pub async fn rag_enhanced_ocr(
     );
 
     let start = Instant::now();
-    let config = request
+    let mut config = request
         .config
         .unwrap_or_else(|| state.config_manager.get_config().ocr);
+    if let Some(languages) =
+        resolve_ocr_languages(&state, request.languages.as_deref(), request.document_id).await?
+    {
+        config.languages = languages;
+    }
 
     let result = state
         .rag_ingestion_use_case
@@ -259,6 +360,7 @@ pub async fn rag_hybrid_retrieval(
         .and_then(|o| o.use_cache)
         .unwrap_or(config.cache.enabled);
     let optimized = options.as_ref().and_then(|o| o.optimized).unwrap_or(true);
+    let explain = options.as_ref().and_then(|o| o.explain).unwrap_or(false);
 
     if use_cache {
         let (mut results, cache_hit) = state
@@ -287,37 +389,75 @@ pub async fn rag_hybrid_retrieval(
             start.elapsed().as_millis() as u64,
         );
 
-        return Ok(HybridRetrievalResponse { results, cache_hit });
+        // A cache hit carries no scores, so they are computed again the way
+        // a miss would have
+        let explain = if explain {
+            let trace = ExplainTrace::new();
+            state
+                .retrieval_service
+                .query_explained(collection_id, &query, top_k, &trace)
+                .await?;
+            Some(trace.finish(&results, Some(cache_hit)))
+        } else {
+            None
+        };
+
+        return Ok(HybridRetrievalResponse {
+            results,
+            cache_hit,
+            explain,
+        });
     }
 
+    let trace = explain.then(ExplainTrace::new);
+    let retrieval = &state.retrieval_service;
     let results = if optimized {
-        state
-            .retrieval_service
-            .query_optimized_with_config(collection_id, &query, top_k, &config, &state.logs)
-            .await
-            .map_err(|e| {
-                add_log(
-                    &state.logs,
-                    "ERROR",
-                    "RAG",
-                    &format!("Hybrid retrieval (optimized) failed: {}", e),
-                );
-                e
-            })?
+        match &trace {
+            Some(trace) => {
+                retrieval
+                    .query_optimized_explained(
+                        collection_id,
+                        &query,
+                        top_k,
+                        &config,
+                        &state.logs,
+                        trace,
+                    )
+                    .await
+            }
+            None => {
+                retrieval
+                    .query_optimized_with_config(collection_id, &query, top_k, &config, &state.logs)
+                    .await
+            }
+        }
+        .map_err(|e| {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "RAG",
+                &format!("Hybrid retrieval (optimized) failed: {}", e),
+            );
+            e
+        })?
     } else {
-        state
-            .retrieval_service
-            .query(collection_id, &query, top_k)
-            .await
-            .map_err(|e| {
-                add_log(
-                    &state.logs,
-                    "ERROR",
-                    "RAG",
-                    &format!("Hybrid retrieval failed: {}", e),
-                );
-                e
-            })?
+        match &trace {
+            Some(trace) => {
+                retrieval
+                    .query_explained(collection_id, &query, top_k, trace)
+                    .await
+            }
+            None => retrieval.query(collection_id, &query, top_k).await,
+        }
+        .map_err(|e| {
+            add_log(
+                &state.logs,
+                "ERROR",
+                "RAG",
+                &format!("Hybrid retrieval failed: {}", e),
+            );
+            e
+        })?
     };
 
     state.analytics_logger.log_retrieval(
@@ -328,9 +468,11 @@ pub async fn rag_hybrid_retrieval(
         start.elapsed().as_millis() as u64,
     );
 
+    let explain = trace.map(|trace| trace.finish(&results, None));
     Ok(HybridRetrievalResponse {
         results,
         cache_hit: false,
+        explain,
     })
 }
 
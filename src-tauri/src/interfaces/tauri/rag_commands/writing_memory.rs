@@ -0,0 +1,79 @@
+//! Writing memory: accepted translations and enhancements copied into a
+//! RAG collection as they are approved or starred, plus a sync for the
+//! ones accepted before the option was turned on.
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::domain::error::Result;
+use crate::domain::writing_memory::{
+    WritingMemoryEntry, WritingMemorySettings, WritingMemorySyncReport,
+};
+use crate::interfaces::http::add_log;
+
+use super::AppState;
+
+/// Entries of each source read by one sync
+const MAX_SYNC_ENTRIES: i64 = 10_000;
+
+#[tauri::command]
+pub async fn rag_sync_writing_memory(
+    state: State<'_, Arc<super::AppState>>,
+) -> Result<WritingMemorySyncReport> {
+    let settings = state.settings.get::<WritingMemorySettings>();
+    let translations = state
+        .repository
+        .search_translation_memory("", None, true, MAX_SYNC_ENTRIES)
+        .await?;
+    let starred = state
+        .repository
+        .search_history(None, None, true, MAX_SYNC_ENTRIES)
+        .await?;
+    let entries: Vec<WritingMemoryEntry> = translations
+        .iter()
+        .filter_map(WritingMemoryEntry::from_tm_entry)
+        .chain(starred.iter().filter_map(WritingMemoryEntry::from_prompt))
+        .collect();
+
+    state
+        .rag_ingestion_use_case
+        .sync_writing_memory(&settings, &entries, &state.logs)
+        .await
+}
+
+/// Store an accepted entry when writing memory is on. Runs in the
+/// background so approving or starring doesn't wait on embeddings; a
+/// failure is only logged.
+pub(crate) fn remember_writing(state: &Arc<AppState>, entry: Option<WritingMemoryEntry>) {
+    let settings = state.settings.get::<WritingMemorySettings>();
+    let Some(entry) = entry.filter(|entry| settings.accepts(entry.kind)) else {
+        return;
+    };
+    let state = Arc::clone(state);
+    tauri::async_runtime::spawn(async move {
+        let result = state
+            .rag_ingestion_use_case
+            .remember_writing(&settings, &entry, &state.logs)
+            .await;
+        match result {
+            Ok(true) => add_log(
+                &state.logs,
+                "INFO",
+                "RAG",
+                &format!("Added '{}' to writing memory", entry.title()),
+            ),
+            Ok(false) => {}
+            Err(err) => add_log(
+                &state.logs,
+                "WARN",
+                "RAG",
+                &format!(
+                    "Failed to add '{}' to writing memory: {}",
+                    entry.title(),
+                    err
+                ),
+            ),
+        }
+    });
+}
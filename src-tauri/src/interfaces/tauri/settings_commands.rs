@@ -0,0 +1,309 @@
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value as JsonValue;
+use tauri::State;
+
+use crate::application::use_cases::rag_config::RagConfig;
+use crate::domain::backup::BackupSettings;
+use crate::domain::embedding_models::LocalEmbeddingSettings;
+use crate::domain::enhance_presets::EnhancePresetSettings;
+use crate::domain::error::{AppError, Result};
+use crate::domain::logging::LogSettings;
+use crate::domain::ollama::OllamaSettings;
+use crate::domain::project_profiles::ProjectProfileSettings;
+use crate::domain::qa_auth::QaAuthSettings;
+use crate::domain::qa_llm_audit::QaLlmAuditSettings;
+use crate::domain::qa_prompts::QaPromptSettings;
+use crate::domain::qa_redaction::QaRedactionSettings;
+use crate::domain::qa_retention::QaRetentionSettings;
+use crate::domain::qa_selectors::QaSelectorSettings;
+use crate::domain::qa_test_management::QaTestManagementSettings;
+use crate::domain::qa_vision::QaVisionSettings;
+use crate::domain::rag_tools::RagToolSettings;
+use crate::domain::settings::{
+    HttpServerSettings, LanguageSettings, ModelSettings, OutputLanguageSettings, QaProxySettings,
+    QuickActionSettings, SettingsDocument, SettingsSection, ShortcutSettings, StartupSettings,
+    StorageSettings,
+};
+use crate::domain::telemetry::TelemetrySettings;
+use crate::domain::writing_memory::WritingMemorySettings;
+use crate::infrastructure::config::SettingsStore;
+use crate::infrastructure::logging;
+use crate::interfaces::http::add_log;
+use crate::interfaces::mock_server::MockServerConfig;
+
+use super::state::AppState;
+
+/// Emitted with a `SettingsChange` payload whenever a section is written
+pub(crate) const SETTINGS_CHANGED_EMIT: &str = "settings-changed";
+
+/// Write a section, logging instead of failing the caller when the disk write fails.
+pub(crate) fn persist_settings<T: SettingsSection>(state: &AppState, section: &T) {
+    if let Err(err) = state.settings.set(section) {
+        add_log(
+            &state.logs,
+            "ERROR",
+            "Settings",
+            &format!("Failed to persist '{}' settings: {}", T::KEY, err),
+        );
+    }
+}
+
+#[tauri::command]
+pub async fn settings_get(state: State<'_, Arc<AppState>>) -> Result<SettingsDocument> {
+    Ok(state.settings.snapshot())
+}
+
+/// Replace one section and apply it to the running app.
+/// Returns false when the stored value was already identical.
+#[tauri::command]
+pub async fn settings_update_section(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<AppState>>,
+    section: String,
+    value: JsonValue,
+) -> Result<bool> {
+    let value = validate_section(&section, value)?;
+    let changed = state.settings.set_section(&section, value)?;
+    if changed {
+        apply_sections(&app, &state, &[section]);
+    }
+    Ok(changed)
+}
+
+/// Write all settings to `output_path`. Returns the number of exported sections.
+#[tauri::command]
+pub async fn settings_export(
+    state: State<'_, Arc<AppState>>,
+    output_path: String,
+) -> Result<usize> {
+    let exported = state.settings.export()?;
+    tokio::fs::write(&output_path, exported).await?;
+
+    let count = state.settings.snapshot().sections.len();
+    add_log(
+        &state.logs,
+        "INFO",
+        "Settings",
+        &format!("Exported {} settings sections to {}", count, output_path),
+    );
+    Ok(count)
+}
+
+/// Load settings exported on another machine (older schema versions are
+/// migrated first). Sections missing from the file are left untouched.
+/// Returns the names of the sections that changed.
+#[tauri::command]
+pub async fn settings_import(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<AppState>>,
+    input_path: String,
+) -> Result<Vec<String>> {
+    let content = tokio::fs::read_to_string(&input_path).await?;
+    let (mut document, from_version) = SettingsStore::parse_document(&content)?;
+    // Folder locations belong to the machine they were set on
+    document.sections.remove(StorageSettings::KEY);
+    for (section, value) in document.sections.iter_mut() {
+        *value = validate_section(section, value.take())?;
+    }
+
+    let changed = state.settings.import(document)?;
+    apply_sections(&app, &state, &changed);
+
+    add_log(
+        &state.logs,
+        "INFO",
+        "Settings",
+        &format!(
+            "Imported settings v{} from {}: {} section(s) changed",
+            from_version,
+            input_path,
+            changed.len()
+        ),
+    );
+    Ok(changed)
+}
+
+/// Check a section against its typed schema. API keys are dropped from
+/// model settings so they never reach the settings file.
+fn validate_section(section: &str, value: JsonValue) -> Result<JsonValue> {
+    if section == ModelSettings::KEY {
+        let models = parse_section::<ModelSettings>(section, &value)?;
+        models.validate()?;
+        let models = ModelSettings {
+            llm: models.llm.as_ref().map(ModelSettings::without_api_key),
+            embedding: models
+                .embedding
+                .as_ref()
+                .map(ModelSettings::without_api_key),
+            features: models.features,
+        };
+        serde_json::to_value(models)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize model settings: {}", e)))
+    } else if section == LanguageSettings::KEY {
+        parse_section::<LanguageSettings>(section, &value).map(|_| value)
+    } else if section == OutputLanguageSettings::KEY {
+        parse_section::<OutputLanguageSettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == ShortcutSettings::KEY {
+        parse_section::<ShortcutSettings>(section, &value).map(|_| value)
+    } else if section == QuickActionSettings::KEY {
+        parse_section::<QuickActionSettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == EnhancePresetSettings::KEY {
+        parse_section::<EnhancePresetSettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == LocalEmbeddingSettings::KEY {
+        parse_section::<LocalEmbeddingSettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == OllamaSettings::KEY {
+        parse_section::<OllamaSettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == MockServerConfig::KEY {
+        parse_section::<MockServerConfig>(section, &value).map(|_| value)
+    } else if section == LogSettings::KEY {
+        parse_section::<LogSettings>(section, &value).map(|_| value)
+    } else if section == HttpServerSettings::KEY {
+        parse_section::<HttpServerSettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == StartupSettings::KEY {
+        parse_section::<StartupSettings>(section, &value).map(|_| value)
+    } else if section == ProjectProfileSettings::KEY {
+        parse_section::<ProjectProfileSettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == BackupSettings::KEY {
+        parse_section::<BackupSettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == QaProxySettings::KEY {
+        parse_section::<QaProxySettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == QaSelectorSettings::KEY {
+        parse_section::<QaSelectorSettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == QaRetentionSettings::KEY {
+        parse_section::<QaRetentionSettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == QaRedactionSettings::KEY {
+        parse_section::<QaRedactionSettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == QaLlmAuditSettings::KEY {
+        parse_section::<QaLlmAuditSettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == QaPromptSettings::KEY {
+        parse_section::<QaPromptSettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == QaVisionSettings::KEY {
+        parse_section::<QaVisionSettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == QaAuthSettings::KEY {
+        parse_section::<QaAuthSettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == QaTestManagementSettings::KEY {
+        parse_section::<QaTestManagementSettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == RagToolSettings::KEY {
+        parse_section::<RagToolSettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == WritingMemorySettings::KEY {
+        parse_section::<WritingMemorySettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == TelemetrySettings::KEY {
+        parse_section::<TelemetrySettings>(section, &value)?.validate()?;
+        Ok(value)
+    } else if section == StorageSettings::KEY {
+        Err(AppError::ValidationError(
+            "Storage locations change through the storage migration, which moves the files"
+                .to_string(),
+        ))
+    } else if section == RagConfig::KEY {
+        let validation = parse_section::<RagConfig>(section, &value)?.validate();
+        if validation.valid {
+            Ok(value)
+        } else {
+            Err(AppError::ValidationError(format!(
+                "Invalid RAG settings: {}",
+                validation.errors.join("; ")
+            )))
+        }
+    } else {
+        Err(AppError::ValidationError(format!(
+            "Unknown settings section '{}'",
+            section
+        )))
+    }
+}
+
+fn parse_section<T: DeserializeOwned>(section: &str, value: &JsonValue) -> Result<T> {
+    serde_json::from_value(value.clone())
+        .map_err(|e| AppError::ValidationError(format!("Invalid '{}' settings: {}", section, e)))
+}
+
+/// Push stored sections into the in-memory state that mirrors them
+fn apply_sections(app: &tauri::AppHandle, state: &Arc<AppState>, sections: &[String]) {
+    for section in sections {
+        if section == ModelSettings::KEY {
+            let models = state.settings.get::<ModelSettings>();
+            if let Some(llm) = models.llm {
+                let mut last_config = state.last_config.lock().unwrap();
+                // Keys are not part of the settings file; keep the one in use
+                let api_key = last_config.api_key.take();
+                *last_config = llm;
+                last_config.api_key = api_key;
+            }
+            if let Some(embedding) = models.embedding {
+                state.embedding_service.update_config(embedding);
+            }
+        } else if section == LocalEmbeddingSettings::KEY {
+            let local = state.settings.get::<LocalEmbeddingSettings>();
+            state
+                .embedding_service
+                .set_execution_providers(local.provider_order());
+        } else if section == LanguageSettings::KEY {
+            let languages = state.settings.get::<LanguageSettings>();
+            *state.preferred_source.lock().unwrap() = languages.source;
+            *state.preferred_target.lock().unwrap() = languages.target;
+        } else if section == ShortcutSettings::KEY {
+            let shortcuts = state.settings.get::<ShortcutSettings>();
+            if let Err(err) = crate::register_shortcuts(
+                app,
+                shortcuts.enabled,
+                &shortcuts.translate,
+                &shortcuts.enhance,
+                &shortcuts.popup,
+                &shortcuts.terminal,
+            ) {
+                add_log(
+                    &state.logs,
+                    "ERROR",
+                    "Shortcut",
+                    &format!("Failed to register imported shortcuts: {err}"),
+                );
+            }
+        } else if section == RagConfig::KEY {
+            state.config_manager.reload();
+        } else if section == MockServerConfig::KEY {
+            state.mock_server.reload();
+        } else if section == LogSettings::KEY {
+            logging::sink().configure(state.settings.get::<LogSettings>());
+        } else if section == StartupSettings::KEY {
+            let startup = state.settings.get::<StartupSettings>();
+            if let Err(err) =
+                crate::interfaces::background::sync_autostart(app, startup.launch_at_login)
+            {
+                add_log(
+                    &state.logs,
+                    "ERROR",
+                    "System",
+                    &format!("Failed to update launch at login: {err}"),
+                );
+            }
+        } else if section == HttpServerSettings::KEY {
+            let state = state.clone();
+            tauri::async_runtime::spawn(async move {
+                // Failures are logged by `restart`
+                let _ = super::http_server_commands::restart(&state).await;
+            });
+        }
+    }
+}
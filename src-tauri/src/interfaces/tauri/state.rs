@@ -1,19 +1,29 @@
+use crate::application::use_cases::activity::ActivityFeed;
 use crate::application::use_cases::audit_service::AuditService;
 use crate::application::use_cases::data_protection::DataProtectionService;
 use crate::application::use_cases::db_connection_manager::DbConnectionManager;
 use crate::application::use_cases::reranker_service::RerankerService;
 use crate::application::use_cases::enhance::EnhanceUseCase;
+use crate::application::use_cases::output_language::OutputLanguage;
 use crate::application::use_cases::qa_ai::QaAiUseCase;
 use crate::application::use_cases::qa_api_call::QaApiCallUseCase;
+use crate::application::use_cases::qa_auth::QaAuthUseCase;
 use crate::application::use_cases::qa_event::QaEventUseCase;
 use crate::application::use_cases::qa_run::QaRunUseCase;
 use crate::application::use_cases::qa_session::QaSessionUseCase;
+use crate::application::use_cases::qa_test_management::QaTestManagementUseCase;
+use crate::application::use_cases::rag_answer_cache::RagAnswerCache;
 use crate::application::use_cases::rag_ingestion::RagIngestionUseCase;
 use crate::application::use_cases::rate_limiter::RateLimiter;
 use crate::application::use_cases::retrieval_service::RetrievalService;
 use crate::application::use_cases::translate::TranslateUseCase;
+use crate::application::use_cases::telemetry::UsageTelemetry;
 use crate::application::use_cases::typegen::TypeGenUseCase;
+use crate::domain::enhance_presets::{EnhancePreset, EnhancePresetSettings};
+use crate::domain::error::Result;
 use crate::domain::llm_config::LLMConfig;
+use crate::domain::qa_session::QaSession;
+use crate::domain::settings::{LlmFeature, OutputLanguageSettings};
 use crate::infrastructure::db::rag::repository::RagRepository;
 use crate::infrastructure::db::sqlite::SqliteRepository;
 use std::collections::{HashMap, HashSet};
@@ -25,8 +35,15 @@ use crate::application::use_cases::embedding_service::EmbeddingService;
 use crate::application::use_cases::rag_analytics::SharedAnalyticsLogger;
 use crate::application::use_cases::rag_config::{SharedConfigManager, SharedFeedbackCollector};
 use crate::application::use_cases::rag_metrics::{SharedExperimentManager, SharedMetricsCollector};
-use crate::infrastructure::config::ConfigService;
+use crate::infrastructure::backup::BackupManager;
+use crate::infrastructure::bootstrap::DeferredInit;
+use crate::infrastructure::config::{ConfigService, LlmConfigResolver, SettingsStore};
 use crate::infrastructure::llm_clients::LLMClient;
+use crate::infrastructure::node_runtime::NodeRuntime;
+use crate::infrastructure::ocr_languages::OcrLanguageStore;
+use crate::infrastructure::ollama::OllamaManager;
+use crate::infrastructure::storage::StorageLocations;
+use crate::interfaces::http::HttpServerControl;
 use crate::interfaces::mock_server::MockServerState;
 
 use tokio::process::Child;
@@ -42,19 +59,33 @@ pub struct AppState {
     pub qa_ai_use_case: QaAiUseCase,
     pub qa_run_use_case: QaRunUseCase,
     pub qa_api_call_use_case: QaApiCallUseCase,
+    pub qa_test_management_use_case: QaTestManagementUseCase,
+    pub qa_auth_use_case: QaAuthUseCase,
     pub rag_ingestion_use_case: RagIngestionUseCase,
     pub retrieval_service: Arc<RetrievalService>,
+    /// Generated RAG answers reused until their collection changes
+    pub rag_answer_cache: Arc<RagAnswerCache>,
     pub embedding_service: Arc<EmbeddingService>,
-    pub qa_session_id: Mutex<Option<String>>,
-    pub qa_recorder: Mutex<Option<QaRecorderHandle>>,
+    /// QA sessions started and not yet ended, keyed by session id
+    pub qa_sessions: Mutex<HashMap<String, QaSession>>,
+    /// Running browser recorders, keyed by the session they record into
+    pub qa_recorders: Mutex<HashMap<String, QaRecorderHandle>>,
     pub repository: Arc<SqliteRepository>,
     pub rag_repository: Arc<RagRepository>,
     pub config_service: ConfigService,
+    /// Per-feature model overrides from Settings
+    pub model_resolver: LlmConfigResolver,
     pub llm_client: Arc<dyn LLMClient + Send + Sync>,
     pub mock_server: Arc<MockServerState>,
+    /// Local HTTP API listener and its access token
+    pub http_server: HttpServerControl,
     pub last_config: Mutex<LLMConfig>,
     pub preferred_source: Mutex<String>,
     pub preferred_target: Mutex<String>,
+    /// Text captured by the popup shortcut, until the quick-action window takes it
+    pub quick_action_capture: Mutex<Option<String>>,
+    /// Unified, versioned settings persisted in settings.json
+    pub settings: Arc<SettingsStore>,
     pub logs: Arc<Mutex<Vec<crate::interfaces::http::LogEntry>>>,
     pub distill_trainers: Mutex<HashMap<String, DistillTrainerHandle>>,
     pub distill_trainer_launches: Mutex<HashSet<String>>,
@@ -80,6 +111,65 @@ pub struct AppState {
     pub data_protection: Arc<DataProtectionService>,
     /// Reranker service for SQL-RAG row relevance scoring
     pub reranker_service: Arc<RerankerService>,
+    /// RAG and training database setup, postponed in background mode
+    pub deferred_init: DeferredInit,
+    /// Scheduled and on-demand backups of every database
+    pub backups: BackupManager,
+    /// QA session and training artifact folders, relocatable at runtime
+    pub storage: Arc<StorageLocations>,
+    /// Bundled and downloaded Tesseract language data
+    pub ocr_languages: Arc<OcrLanguageStore>,
+    /// Playwright install and staged scripts for the recorder, replay and web capture
+    pub node_runtime: Arc<NodeRuntime>,
+    /// Local Ollama install, the server the app started and model pulls
+    pub ollama: Arc<OllamaManager>,
+    /// Timeline of QA runs, RAG ingestion, training and mock-server hits
+    pub activity_feed: Arc<ActivityFeed>,
+    /// Opt-in local usage metrics behind the usage dashboard
+    pub telemetry: Arc<UsageTelemetry>,
+}
+
+impl AppState {
+    /// Model `feature` runs on when the UI did not send one
+    pub fn feature_config(&self, feature: LlmFeature) -> LLMConfig {
+        let config = self.last_config.lock().unwrap().clone();
+        self.model_resolver.resolve(feature, &config)
+    }
+
+    /// Language an AI answer must be in: `requested`, or the one from Settings
+    pub fn output_language(&self, requested: Option<&str>) -> Option<OutputLanguage> {
+        OutputLanguage::resolve(&self.settings.get::<OutputLanguageSettings>(), requested)
+    }
+
+    /// Enhancement preset `requested` by id, or the default one from Settings.
+    /// Callers with their own system prompt get the default only when asked.
+    pub fn enhance_preset(
+        &self,
+        requested: Option<&str>,
+        custom_system_prompt: bool,
+    ) -> Result<Option<EnhancePreset>> {
+        if custom_system_prompt && requested.is_none() {
+            return Ok(None);
+        }
+        Ok(self
+            .settings
+            .get::<EnhancePresetSettings>()
+            .resolve(requested)?
+            .cloned())
+    }
+
+    /// Session a QA command applies to: `requested`, or the only active one.
+    /// `None` when no session is named and not exactly one is active.
+    pub fn qa_session_scope(&self, requested: Option<String>) -> Option<String> {
+        requested.or_else(|| {
+            let sessions = self.qa_sessions.lock().unwrap();
+            if sessions.len() == 1 {
+                sessions.keys().next().cloned()
+            } else {
+                None
+            }
+        })
+    }
 }
 
 pub(crate) struct QaRecorderHandle {
@@ -96,10 +186,15 @@ pub(crate) struct DistillTrainerHandle {
 }
 
 /// Cleanup all child processes when the app is closing.
-/// This function kills the QA browser recorder and all distill trainers.
+/// This function kills the QA browser recorders and all distill trainers.
 pub async fn cleanup_child_processes(state: &AppState) {
-    // Kill QA recorder if running
-    if let Some(handle) = state.qa_recorder.lock().unwrap().take() {
+    // Kill running QA recorders
+    let recorders: Vec<_> = {
+        let mut recorders = state.qa_recorders.lock().unwrap();
+        recorders.drain().map(|(_, handle)| handle).collect()
+    };
+
+    for handle in recorders {
         let mut child = handle.child.lock().await;
         let _ = child.kill().await;
         let _ = child.wait().await;
@@ -118,4 +213,9 @@ pub async fn cleanup_child_processes(state: &AppState) {
         let _ = child.wait().await;
         tracing::info!("Killed distill trainer (run_id: {})", run_id);
     }
+
+    // Stop the Ollama server this app started, if any
+    if state.ollama.stop().await.is_ok() {
+        tracing::info!("Stopped the Ollama server started by the app");
+    }
 }
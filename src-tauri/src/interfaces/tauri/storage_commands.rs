@@ -0,0 +1,294 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tauri::{Emitter, Manager, State};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::storage::{
+    StorageKind, StorageLocation, StorageMigrationProgress, StorageMigrationRequest,
+    StorageMovePlan, StorageMoveResult,
+};
+use crate::infrastructure::storage::{app_databases, available_space, dir_stats, StorageLocations};
+use crate::infrastructure::storage_migration::{
+    finish, path_columns, plan_move, requested_dir, rewrite_path_prefix, rollback, transfer,
+};
+use crate::interfaces::http::add_log;
+
+use super::settings_commands::persist_settings;
+use super::state::AppState;
+
+/// Emitted with a `StorageMigrationProgress` payload while files are copied
+pub(crate) const STORAGE_MIGRATION_PROGRESS_EMIT: &str = "storage-migration-progress";
+
+const KINDS: [StorageKind; 2] = [StorageKind::QaSessions, StorageKind::Training];
+
+/// Database whose stored paths point into the folder
+fn owning_database(kind: StorageKind) -> &'static str {
+    match kind {
+        StorageKind::QaSessions => "qa",
+        StorageKind::Training => "training",
+    }
+}
+
+/// Folders the request moves, with their current and new paths
+fn requested_moves(
+    storage: &StorageLocations,
+    request: &StorageMigrationRequest,
+) -> Vec<(StorageKind, PathBuf, PathBuf)> {
+    KINDS
+        .into_iter()
+        .filter_map(|kind| {
+            let requested = match kind {
+                StorageKind::QaSessions => request.qa_sessions_dir.as_deref(),
+                StorageKind::Training => request.training_dir.as_deref(),
+            }?;
+            let from = storage.dir(kind);
+            let to = requested_dir(
+                requested,
+                StorageLocations::default_dir(storage.app_data_dir(), kind),
+            );
+            (from != to).then_some((kind, from, to))
+        })
+        .collect()
+}
+
+/// Current folders with their size and the free space left on their drive
+#[tauri::command]
+pub async fn storage_locations(state: State<'_, Arc<AppState>>) -> Result<Vec<StorageLocation>> {
+    let storage = state.storage.clone();
+    tokio::task::spawn_blocking(move || {
+        KINDS
+            .into_iter()
+            .map(|kind| {
+                let path = storage.dir(kind);
+                let default_path = StorageLocations::default_dir(storage.app_data_dir(), kind);
+                let (file_count, size_bytes) = dir_stats(&path);
+                StorageLocation {
+                    kind,
+                    is_default: path == default_path,
+                    path: path.display().to_string(),
+                    default_path: default_path.display().to_string(),
+                    size_bytes,
+                    file_count,
+                    available_bytes: available_space(&path),
+                }
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Storage scan failed: {}", e)))
+}
+
+/// Check a relocation before running it: sizes, free space and target problems
+#[tauri::command]
+pub async fn storage_plan_migration(
+    state: State<'_, Arc<AppState>>,
+    request: StorageMigrationRequest,
+) -> Result<Vec<StorageMovePlan>> {
+    let moves = requested_moves(&state.storage, &request);
+    tokio::task::spawn_blocking(move || {
+        moves
+            .iter()
+            .map(|(kind, from, to)| plan_move(*kind, from, to))
+            .collect()
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Storage scan failed: {}", e)))
+}
+
+/// Move QA session files and/or training artifacts to new folders, rewrite
+/// the file paths stored in the QA and training databases, and remember the
+/// new locations. Each folder is moved on its own; a failure rolls that
+/// folder back and leaves the ones already moved in place.
+#[tauri::command]
+pub async fn storage_migrate(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<AppState>>,
+    request: StorageMigrationRequest,
+) -> Result<Vec<StorageMoveResult>> {
+    if !state.qa_sessions.lock().unwrap().is_empty()
+        || !state.qa_recorders.lock().unwrap().is_empty()
+    {
+        return Err(AppError::ValidationError(
+            "End the active QA sessions before moving their files".to_string(),
+        ));
+    }
+    if !state.distill_trainers.lock().unwrap().is_empty()
+        || !state.distill_trainer_launches.lock().unwrap().is_empty()
+    {
+        return Err(AppError::ValidationError(
+            "Wait for training to finish before moving its artifacts".to_string(),
+        ));
+    }
+    // Training paths live in a database that background mode opens lazily
+    state
+        .deferred_init
+        .ensure()
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+    let moves = requested_moves(&state.storage, &request);
+    let plans = {
+        let moves = moves.clone();
+        tokio::task::spawn_blocking(move || {
+            moves
+                .iter()
+                .map(|(kind, from, to)| plan_move(*kind, from, to))
+                .collect::<Vec<_>>()
+        })
+        .await
+        .map_err(|e| AppError::Internal(format!("Storage scan failed: {}", e)))?
+    };
+    let problems: Vec<String> = plans
+        .iter()
+        .flat_map(|plan| plan.problems.iter().cloned())
+        .collect();
+    if !problems.is_empty() {
+        return Err(AppError::ValidationError(problems.join("; ")));
+    }
+
+    let databases = app_databases(state.storage.app_data_dir());
+    let mut results = Vec::new();
+    for ((kind, from, to), plan) in moves.into_iter().zip(plans) {
+        add_log(
+            &state.logs,
+            "INFO",
+            "Storage",
+            &format!(
+                "Moving {} from {} to {} ({} files, {} bytes)",
+                kind.label(),
+                from.display(),
+                to.display(),
+                plan.file_count,
+                plan.total_bytes
+            ),
+        );
+
+        let moved = {
+            let (from, to, app) = (from.clone(), to.clone(), app.clone());
+            tokio::task::spawn_blocking(move || {
+                let mut progress = StorageMigrationProgress {
+                    kind,
+                    copied_bytes: 0,
+                    total_bytes: plan.total_bytes,
+                    files_done: 0,
+                    file_count: plan.file_count,
+                };
+                let mut last_emit = Instant::now();
+                let moved = transfer(&from, &to, &mut |bytes| {
+                    progress.copied_bytes += bytes;
+                    progress.files_done += 1;
+                    if last_emit.elapsed() >= Duration::from_millis(250) {
+                        last_emit = Instant::now();
+                        let _ = app.emit(STORAGE_MIGRATION_PROGRESS_EMIT, &progress);
+                    }
+                });
+                progress.copied_bytes = progress.total_bytes;
+                progress.files_done = progress.file_count;
+                let _ = app.emit(STORAGE_MIGRATION_PROGRESS_EMIT, &progress);
+                moved
+            })
+            .await
+            .map_err(|e| AppError::Internal(format!("Storage move failed: {}", e)))?
+            .map_err(|e| {
+                AppError::IoError(format!(
+                    "Failed to move {} to {}: {}",
+                    kind.label(),
+                    to.display(),
+                    e
+                ))
+            })?
+        };
+
+        let db = databases
+            .iter()
+            .find(|db| db.key == owning_database(kind))
+            .expect("every storage kind has a database");
+        let rows_updated = match rewrite_path_prefix(&db.path, path_columns(kind), &from, &to).await
+        {
+            Ok(rows) => rows,
+            Err(err) => {
+                if let Err(rollback_err) = rollback(&from, &to, moved) {
+                    add_log(
+                        &state.logs,
+                        "ERROR",
+                        "Storage",
+                        &format!(
+                            "Failed to undo the move of {}: {}",
+                            kind.label(),
+                            rollback_err
+                        ),
+                    );
+                }
+                return Err(AppError::DatabaseError(format!(
+                    "Failed to update stored paths for {}: {}",
+                    kind.label(),
+                    err
+                )));
+            }
+        };
+
+        state.storage.set_dir(kind, to.clone());
+        persist_settings(&state, &state.storage.settings());
+        if kind == StorageKind::QaSessions {
+            if let Err(err) = app.asset_protocol_scope().allow_directory(&to, true) {
+                add_log(
+                    &state.logs,
+                    "WARN",
+                    "Storage",
+                    &format!(
+                        "Screenshots in {} cannot be previewed: {}",
+                        to.display(),
+                        err
+                    ),
+                );
+            }
+        }
+
+        let cleanup_error = {
+            let from = from.clone();
+            tokio::task::spawn_blocking(move || finish(&from, moved))
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|result| result.map_err(|e| e.to_string()))
+                .err()
+        };
+        if let Some(err) = &cleanup_error {
+            add_log(
+                &state.logs,
+                "WARN",
+                "Storage",
+                &format!(
+                    "{} moved, but {} could not be removed: {}",
+                    kind.label(),
+                    from.display(),
+                    err
+                ),
+            );
+        }
+
+        add_log(
+            &state.logs,
+            "INFO",
+            "Storage",
+            &format!(
+                "{} now stored in {} ({} stored paths updated)",
+                kind.label(),
+                to.display(),
+                rows_updated
+            ),
+        );
+        results.push(StorageMoveResult {
+            kind,
+            from: from.display().to_string(),
+            to: to.display().to_string(),
+            files_moved: plan.file_count,
+            bytes_moved: plan.total_bytes,
+            rows_updated,
+            cleanup_error,
+        });
+    }
+
+    Ok(results)
+}
@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::domain::error::Result;
+use crate::domain::telemetry::UsageDashboard;
+
+use super::state::AppState;
+
+/// Feature usage, latencies and error rates of the last `days` days
+/// (default 30), recorded locally while telemetry is on
+#[tauri::command]
+pub async fn get_usage_dashboard(
+    state: State<'_, Arc<AppState>>,
+    days: Option<u32>,
+) -> Result<UsageDashboard> {
+    state.telemetry.dashboard(days.unwrap_or(30)).await
+}
+
+/// Deletes all recorded usage stats. Returns the number of rows removed.
+#[tauri::command]
+pub async fn clear_usage_stats(state: State<'_, Arc<AppState>>) -> Result<u64> {
+    state.telemetry.clear().await
+}
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::translation_memory::TranslationMemoryEntry;
+use crate::domain::writing_memory::WritingMemoryEntry;
+use crate::interfaces::http::add_log;
+
+use super::rag_commands::writing_memory::remember_writing;
+use super::state::AppState;
+
+#[tauri::command]
+pub async fn tm_search(
+    state: State<'_, Arc<AppState>>,
+    query: String,
+    target_lang: Option<String>,
+    approved_only: Option<bool>,
+    limit: Option<i64>,
+) -> Result<Vec<TranslationMemoryEntry>> {
+    state
+        .repository
+        .search_translation_memory(
+            &query,
+            target_lang.as_deref(),
+            approved_only.unwrap_or(false),
+            limit.unwrap_or(50).clamp(1, 500),
+        )
+        .await
+}
+
+/// Add a human translation; manual entries are approved immediately.
+#[tauri::command]
+pub async fn tm_add_entry(
+    state: State<'_, Arc<AppState>>,
+    mut entry: TranslationMemoryEntry,
+) -> Result<TranslationMemoryEntry> {
+    if entry.source_text.trim().is_empty() || entry.target_text.trim().is_empty() {
+        return Err(AppError::ValidationError(
+            "Translation memory entries need both source and target text".to_string(),
+        ));
+    }
+
+    entry.id = None;
+    entry.approved = true;
+    entry.origin = "human".to_string();
+    state.repository.insert_tm_entry(&mut entry).await?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "TranslationMemory",
+        &format!(
+            "Added translation memory entry {} ({} -> {})",
+            entry.id.unwrap_or_default(),
+            entry.source_lang,
+            entry.target_lang
+        ),
+    );
+    remember_writing(state.inner(), WritingMemoryEntry::from_tm_entry(&entry));
+    Ok(entry)
+}
+
+/// Approve a recorded translation, optionally with a corrected target text.
+#[tauri::command]
+pub async fn tm_approve_entry(
+    state: State<'_, Arc<AppState>>,
+    id: i64,
+    target_text: Option<String>,
+) -> Result<()> {
+    let target_text = target_text.filter(|text| !text.trim().is_empty());
+    state
+        .repository
+        .approve_tm_entry(id, target_text.as_deref())
+        .await?;
+    add_log(
+        &state.logs,
+        "INFO",
+        "TranslationMemory",
+        &format!("Approved translation memory entry {}", id),
+    );
+    if let Ok(entry) = state.repository.get_tm_entry(id).await {
+        remember_writing(state.inner(), WritingMemoryEntry::from_tm_entry(&entry));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn tm_delete_entry(state: State<'_, Arc<AppState>>, id: i64) -> Result<()> {
+    state.repository.delete_tm_entry(id).await
+}
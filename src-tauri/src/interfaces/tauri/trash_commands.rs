@@ -0,0 +1,158 @@
+//! Trash Commands
+//!
+//! Lists, restores and purges the QA sessions, RAG documents and training
+//! datasets that were deleted (see `domain::trash`). Items are purged for
+//! good once the retention period ends, at startup or on request.
+
+use std::sync::Arc;
+
+use tauri::{AppHandle, State};
+
+use crate::domain::error::{AppError, Result};
+use crate::domain::trash::{purge_cutoff, TrashEntry, TrashKind, TrashPurgeReport};
+use crate::infrastructure::db::training::repositories::{DatasetRepository, TrainingDb};
+use crate::interfaces::http::add_log;
+use crate::interfaces::tauri::distillation::common::training_db_path;
+use crate::interfaces::tauri::AppState;
+
+/// Everything in the trash, most recently deleted first
+#[tauri::command]
+pub async fn trash_list(
+    app: AppHandle,
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<TrashEntry>> {
+    ensure_databases(&state).await?;
+    let mut entries = state.qa_session_use_case.list_trash().await?;
+    entries.extend(state.rag_repository.list_trashed_documents().await?);
+    entries.extend(datasets(&app).await?.list_trashed().await?);
+    entries.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    Ok(entries)
+}
+
+#[tauri::command]
+pub async fn trash_restore(
+    app: AppHandle,
+    state: State<'_, Arc<AppState>>,
+    kind: TrashKind,
+    id: String,
+) -> Result<()> {
+    ensure_databases(&state).await?;
+    let restored = match kind {
+        TrashKind::QaSession => state.qa_session_use_case.restore_session(&id).await?,
+        TrashKind::RagDocument => {
+            let document_id = TrashEntry::document_id(&id)?;
+            let restored = state.rag_repository.restore_document(document_id).await?;
+            if restored > 0 {
+                let document = state.rag_repository.get_document(document_id).await?;
+                if let Some(collection_id) = document.collection_id {
+                    state
+                        .retrieval_service
+                        .invalidate_collection_cache(collection_id)
+                        .await?;
+                }
+            }
+            restored
+        }
+        TrashKind::Dataset => datasets(&app).await?.restore(&id).await?,
+    };
+    if restored == 0 {
+        return Err(AppError::NotFound(format!(
+            "{} {} is not in the trash",
+            kind.label(),
+            id
+        )));
+    }
+
+    add_log(
+        &state.logs,
+        "INFO",
+        "Trash",
+        &format!("Restored {} {} from the trash", kind.label(), id),
+    );
+    Ok(())
+}
+
+/// Purge the items past the retention period, or everything with `all`
+#[tauri::command]
+pub async fn trash_purge(
+    app: AppHandle,
+    state: State<'_, Arc<AppState>>,
+    all: Option<bool>,
+) -> Result<TrashPurgeReport> {
+    let deleted_before = if all.unwrap_or(false) {
+        i64::MAX
+    } else {
+        purge_cutoff(chrono::Utc::now().timestamp_millis())
+    };
+    let report = purge_trash(&app, &state, deleted_before).await?;
+    log_purge(&state, &report);
+    Ok(report)
+}
+
+/// Delete the items moved to the trash before `deleted_before` (ms) for
+/// good. A store that fails is reported and the others are still purged.
+pub(crate) async fn purge_trash(
+    app: &AppHandle,
+    state: &AppState,
+    deleted_before: i64,
+) -> Result<TrashPurgeReport> {
+    ensure_databases(state).await?;
+    let mut report = TrashPurgeReport::default();
+
+    match state.qa_session_use_case.purge_trash(deleted_before).await {
+        Ok((purged, errors)) => {
+            report.qa_sessions = purged;
+            report.errors.extend(errors);
+        }
+        Err(err) => report.errors.push(format!("QA sessions: {}", err)),
+    }
+    match state
+        .rag_repository
+        .purge_trashed_documents(deleted_before)
+        .await
+    {
+        Ok(purged) => report.rag_documents = purged,
+        Err(err) => report.errors.push(format!("RAG documents: {}", err)),
+    }
+    let datasets = match datasets(app).await {
+        Ok(repo) => repo.purge_trashed(deleted_before).await,
+        Err(err) => Err(err),
+    };
+    match datasets {
+        Ok(purged) => report.datasets = purged,
+        Err(err) => report.errors.push(format!("Datasets: {}", err)),
+    }
+
+    Ok(report)
+}
+
+pub(crate) fn log_purge(state: &AppState, report: &TrashPurgeReport) {
+    if report.total() == 0 && report.errors.is_empty() {
+        return;
+    }
+    let mut message = format!(
+        "Purged {} QA sessions, {} documents and {} datasets from the trash",
+        report.qa_sessions, report.rag_documents, report.datasets
+    );
+    let level = if report.errors.is_empty() {
+        "INFO"
+    } else {
+        message.push_str(&format!("; failed: {}", report.errors.join("; ")));
+        "WARN"
+    };
+    add_log(&state.logs, level, "Trash", &message);
+}
+
+/// The RAG and training databases are set up after startup
+async fn ensure_databases(state: &AppState) -> Result<()> {
+    state
+        .deferred_init
+        .ensure()
+        .await
+        .map_err(AppError::DatabaseError)
+}
+
+async fn datasets(app: &AppHandle) -> Result<DatasetRepository> {
+    let db = TrainingDb::connect(&training_db_path(app)?).await?;
+    Ok(DatasetRepository::new(&db))
+}
@@ -0,0 +1,231 @@
+//! System tray icon
+//!
+//! Keeps the core controls (mock server, QA recorder, logs, global shortcuts)
+//! reachable while the main window is minimized or hidden. Item states are
+//! refreshed when the pointer enters the icon, right before the menu opens.
+
+use std::sync::Arc;
+
+use tauri::menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem};
+use tauri::tray::{TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Emitter, Manager, Wry};
+use tauri_plugin_opener::OpenerExt;
+
+use crate::domain::settings::ShortcutSettings;
+use crate::infrastructure::logging;
+use crate::interfaces::background::open_main_window;
+use crate::interfaces::http::add_log;
+use crate::interfaces::mock_server::{build_status, start_mock_server, stop_mock_server};
+use crate::interfaces::tauri::settings_commands::persist_settings;
+use crate::interfaces::tauri::AppState;
+
+const OPEN_ID: &str = "tray.open";
+const MOCK_SERVER_ID: &str = "tray.mock_server";
+const QA_RECORDER_ID: &str = "tray.qa_recorder";
+const OPEN_LOGS_ID: &str = "tray.open_logs";
+const SHORTCUTS_ID: &str = "tray.shortcuts";
+const QUIT_ID: &str = "tray.quit";
+
+/// Emitted to the main window with a route to open, e.g. "/qa"
+pub(crate) const NAVIGATE_EVENT: &str = "tray-navigate";
+
+/// Menu items whose label or check mark follows app state
+struct TrayItems {
+    mock_server: CheckMenuItem<Wry>,
+    qa_recorder: MenuItem<Wry>,
+    shortcuts: CheckMenuItem<Wry>,
+}
+
+pub(crate) fn init(app: &AppHandle) -> tauri::Result<()> {
+    let items = TrayItems {
+        mock_server: CheckMenuItem::with_id(
+            app,
+            MOCK_SERVER_ID,
+            "Mock server",
+            true,
+            false,
+            None::<&str>,
+        )?,
+        qa_recorder: MenuItem::with_id(
+            app,
+            QA_RECORDER_ID,
+            "Start QA recorder...",
+            true,
+            None::<&str>,
+        )?,
+        shortcuts: CheckMenuItem::with_id(
+            app,
+            SHORTCUTS_ID,
+            "Global shortcuts",
+            true,
+            false,
+            None::<&str>,
+        )?,
+    };
+
+    let menu = Menu::with_items(
+        app,
+        &[
+            &MenuItem::with_id(app, OPEN_ID, "Open gadogado", true, None::<&str>)?,
+            &PredefinedMenuItem::separator(app)?,
+            &items.mock_server,
+            &items.qa_recorder,
+            &items.shortcuts,
+            &MenuItem::with_id(app, OPEN_LOGS_ID, "Open logs folder", true, None::<&str>)?,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, QUIT_ID, "Quit", true, None::<&str>)?,
+        ],
+    )?;
+
+    let mut builder = TrayIconBuilder::with_id("main")
+        .tooltip("gadogado")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app, event| {
+            let app = app.clone();
+            let id = event.id().as_ref().to_string();
+            tauri::async_runtime::spawn(async move {
+                handle_menu_event(&app, &id).await;
+                refresh(&app);
+            });
+        })
+        .on_tray_icon_event(|tray, event| match event {
+            TrayIconEvent::Enter { .. } => refresh(tray.app_handle()),
+            TrayIconEvent::DoubleClick { .. } => {
+                let app = tray.app_handle().clone();
+                tauri::async_runtime::spawn(async move { open_main_window(&app).await });
+            }
+            _ => {}
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+
+    app.manage(items);
+    refresh(app);
+    Ok(())
+}
+
+/// Sync check marks and labels with the current state
+pub(crate) fn refresh(app: &AppHandle) {
+    let (Some(items), Some(state)) = (
+        app.try_state::<TrayItems>(),
+        app.try_state::<Arc<AppState>>(),
+    ) else {
+        return;
+    };
+
+    let mock_running = build_status(&state.mock_server).running;
+    let recorders = state.qa_recorders.lock().unwrap().len();
+    let shortcuts_enabled = state.settings.get::<ShortcutSettings>().enabled;
+
+    let _ = items.mock_server.set_checked(mock_running);
+    let _ = items.qa_recorder.set_text(match recorders {
+        0 => "Start QA recorder...",
+        1 => "Stop QA recorder",
+        _ => "Stop QA recorders",
+    });
+    let _ = items.shortcuts.set_checked(shortcuts_enabled);
+}
+
+async fn handle_menu_event(app: &AppHandle, id: &str) {
+    // State is managed once the databases are open, shortly after startup
+    let Some(state) = app.try_state::<Arc<AppState>>() else {
+        if id == QUIT_ID {
+            app.exit(0);
+        }
+        return;
+    };
+
+    match id {
+        OPEN_ID => open_main_window(app).await,
+        MOCK_SERVER_ID => {
+            let result = if build_status(&state.mock_server).running {
+                stop_mock_server(state.mock_server.clone()).await
+            } else {
+                start_mock_server(state.mock_server.clone()).await
+            };
+            if let Err(e) = result {
+                add_log(
+                    &state.logs,
+                    "ERROR",
+                    "Tray",
+                    &format!("Failed to toggle mock server: {}", e),
+                );
+            }
+        }
+        QA_RECORDER_ID => {
+            let sessions: Vec<String> =
+                state.qa_recorders.lock().unwrap().keys().cloned().collect();
+            if !sessions.is_empty() {
+                for session_id in sessions {
+                    let result = crate::interfaces::tauri::qa::recorder::qa_stop_browser_recorder(
+                        app.clone(),
+                        app.state(),
+                        Some(session_id),
+                        None,
+                    )
+                    .await;
+                    if let Err(e) = result {
+                        add_log(
+                            &state.logs,
+                            "ERROR",
+                            "Tray",
+                            &format!("Failed to stop QA recorder: {}", e),
+                        );
+                    }
+                }
+            } else {
+                // A recording needs a session and target URL, which only the QA page collects
+                open_main_window(app).await;
+                let _ = app.emit_to("main", NAVIGATE_EVENT, "/qa");
+            }
+        }
+        OPEN_LOGS_ID => match logging::sink().log_dir() {
+            Some(dir) => {
+                if let Err(e) = app.opener().open_path(dir.to_string_lossy(), None::<&str>) {
+                    add_log(
+                        &state.logs,
+                        "ERROR",
+                        "Tray",
+                        &format!("Failed to open logs folder: {}", e),
+                    );
+                }
+            }
+            None => add_log(
+                &state.logs,
+                "WARN",
+                "Tray",
+                "File logging is not active; no logs folder to open",
+            ),
+        },
+        SHORTCUTS_ID => {
+            let mut shortcuts = state.settings.get::<ShortcutSettings>();
+            shortcuts.enabled = !shortcuts.enabled;
+            if let Err(e) = crate::register_shortcuts(
+                app,
+                shortcuts.enabled,
+                &shortcuts.translate,
+                &shortcuts.enhance,
+                &shortcuts.popup,
+                &shortcuts.terminal,
+            ) {
+                add_log(
+                    &state.logs,
+                    "ERROR",
+                    "Tray",
+                    &format!("Failed to toggle shortcuts: {}", e),
+                );
+                return;
+            }
+            // Persisting emits `settings-changed`, which updates the settings page
+            persist_settings(&state, &shortcuts);
+        }
+        QUIT_ID => {
+            crate::interfaces::tauri::cleanup_child_processes(&state).await;
+            app.exit(0);
+        }
+        _ => {}
+    }
+}
@@ -12,7 +12,8 @@ CREATE TABLE IF NOT EXISTS sessions (
   os TEXT,
   started_at INTEGER NOT NULL,
   ended_at INTEGER,
-  notes TEXT
+  notes TEXT,
+  selector_attributes_json TEXT
 );
 
 CREATE INDEX IF NOT EXISTS idx_sessions_started_at
@@ -82,6 +83,7 @@ CREATE TABLE IF NOT EXISTS api_calls (
   response_status INTEGER,
   response_headers_json TEXT,
   response_body_hash TEXT,
+  response_body_json TEXT,
   timing_ms INTEGER,
   created_at INTEGER NOT NULL
 );
@@ -92,6 +94,20 @@ CREATE INDEX IF NOT EXISTS idx_api_calls_session
 CREATE INDEX IF NOT EXISTS idx_api_calls_run
   ON api_calls(run_id);
 
+-- api_response events linked to the click/submit that triggered them
+CREATE TABLE IF NOT EXISTS api_event_links (
+  response_event_id TEXT PRIMARY KEY,
+  session_id TEXT NOT NULL,
+  response_seq INTEGER NOT NULL,
+  trigger_event_id TEXT NOT NULL,
+  trigger_seq INTEGER NOT NULL,
+  delay_ms INTEGER NOT NULL,
+  same_origin INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_event_links_session
+  ON api_event_links(session_id, trigger_seq);
+
 CREATE TABLE IF NOT EXISTS ai_actions (
   id TEXT PRIMARY KEY,
   session_id TEXT NOT NULL,
@@ -135,7 +151,9 @@ CREATE TABLE IF NOT EXISTS checkpoints (
   title TEXT,
   start_event_seq INTEGER NOT NULL,
   end_event_seq INTEGER NOT NULL,
-  created_at INTEGER NOT NULL
+  created_at INTEGER NOT NULL,
+  notes TEXT,
+  updated_at INTEGER
 );
 
 CREATE INDEX IF NOT EXISTS idx_checkpoints_session_seq
@@ -147,7 +165,8 @@ CREATE TABLE IF NOT EXISTS checkpoint_summaries (
   summary_text TEXT NOT NULL,
   entities_json TEXT,
   risks_json TEXT,
-  created_at INTEGER NOT NULL
+  created_at INTEGER NOT NULL,
+  api_links_json TEXT
 );
 
 CREATE INDEX IF NOT EXISTS idx_checkpoint_summaries_checkpoint
@@ -164,6 +183,7 @@ CREATE TABLE IF NOT EXISTS test_cases (
   priority TEXT,
   status TEXT,
   dedup_hash TEXT NOT NULL,
+  assertions_json TEXT,
   created_at INTEGER NOT NULL
 );
 
@@ -194,6 +214,21 @@ CREATE INDEX IF NOT EXISTS idx_test_case_runs_session
 CREATE INDEX IF NOT EXISTS idx_test_case_runs_case
   ON test_case_runs(test_case_id);
 
+CREATE TABLE IF NOT EXISTS test_sync_log (
+  id TEXT PRIMARY KEY,
+  session_id TEXT NOT NULL,
+  provider TEXT NOT NULL,
+  action TEXT NOT NULL,
+  test_case_id TEXT,
+  external_id TEXT,
+  status TEXT NOT NULL,
+  message TEXT,
+  created_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_test_sync_log_session
+  ON test_sync_log(session_id, created_at);
+
 CREATE TABLE IF NOT EXISTS replay_runs (
   id TEXT PRIMARY KEY,
   session_id TEXT NOT NULL,
@@ -222,7 +257,16 @@ CREATE TABLE IF NOT EXISTS llm_runs (
   input_digest TEXT,
   input_summary TEXT,
   output_json TEXT NOT NULL,
-  created_at INTEGER NOT NULL
+  created_at INTEGER NOT NULL,
+  prompt_tokens INTEGER,
+  completion_tokens INTEGER,
+  tokens_estimated INTEGER NOT NULL DEFAULT 0,
+  latency_ms INTEGER,
+  cost_usd REAL,
+  system_prompt TEXT,
+  user_prompt TEXT,
+  response_text TEXT,
+  bodies_truncated INTEGER NOT NULL DEFAULT 0
 );
 
 CREATE INDEX IF NOT EXISTS idx_llm_runs_scope
@@ -16,6 +16,10 @@ kind TEXT NOT NULL DEFAULT 'files',
 -- db: { db_conn_id, allowlist_profile_id, selected_tables, default_limit, max_limit, external_llm_policy }
 config_json TEXT NOT NULL DEFAULT '{}',
 
+-- Bumped by triggers whenever a document, chunk or structured row of the
+-- collection changes; cached answers are only reused at the same version
+content_version INTEGER NOT NULL DEFAULT 0,
+
 created_at DATETIME DEFAULT CURRENT_TIMESTAMP
 );
 
@@ -55,10 +59,15 @@ chunk_index INTEGER,
 token_count INTEGER,
 chunk_quality REAL DEFAULT NULL,          -- Chunk quality score (0.0-1.0)
 content_type TEXT DEFAULT NULL,           -- Detected content type (header, paragraph, list, table, code)
+parent_index INTEGER DEFAULT NULL,        -- chunk_index of the first chunk of its parent section
 
 -- NEW: chunk-scoped metadata (JSON)
 meta_json TEXT NOT NULL DEFAULT '{}',
 
+-- PII found at ingestion (comma-separated kinds); sensitive = PII kept in content
+pii_types TEXT DEFAULT NULL,
+sensitive INTEGER NOT NULL DEFAULT 0,
+
 embedding_api BLOB,
 created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
 FOREIGN KEY (doc_id) REFERENCES documents(id) ON DELETE CASCADE
@@ -143,14 +152,14 @@ CREATE TRIGGER IF NOT EXISTS document_chunks_ai AFTER INSERT ON document_chunks
   VALUES (new.id, new.content, new.doc_id);
 END;
 
+-- document_chunks_fts stores its own content, so rows are removed with a
+-- plain DELETE (the 'delete' command only exists for external-content tables)
 CREATE TRIGGER IF NOT EXISTS document_chunks_ad AFTER DELETE ON document_chunks BEGIN
-  INSERT INTO document_chunks_fts(document_chunks_fts, rowid, content, doc_id)
-  VALUES('delete', old.id, old.content, old.doc_id);
+  DELETE FROM document_chunks_fts WHERE rowid = old.id;
 END;
 
 CREATE TRIGGER IF NOT EXISTS document_chunks_au AFTER UPDATE OF content, doc_id ON document_chunks BEGIN
-  INSERT INTO document_chunks_fts(document_chunks_fts, rowid, content, doc_id)
-  VALUES('delete', old.id, old.content, old.doc_id);
+  DELETE FROM document_chunks_fts WHERE rowid = old.id;
   INSERT INTO document_chunks_fts(rowid, content, doc_id)
   VALUES (new.id, new.content, new.doc_id);
 END;
@@ -234,6 +243,39 @@ CREATE TABLE IF NOT EXISTS document_warnings (
 -- Index for warning retrieval by document
 CREATE INDEX IF NOT EXISTS idx_document_warnings_doc_id ON document_warnings(doc_id);
 
+-- Tesseract confidence per OCR'd page; pages below the configured minimum
+-- are queued to be retried with a higher DPI or other preprocessing
+CREATE TABLE IF NOT EXISTS ocr_pages (
+    doc_id INTEGER NOT NULL,
+    page_number INTEGER NOT NULL,
+    confidence REAL NOT NULL,             -- Mean word confidence (0.0-1.0)
+    block_confidences TEXT NOT NULL DEFAULT '[]',  -- JSON array, one mean per text block
+    dpi INTEGER NOT NULL,
+    preprocessing TEXT NOT NULL,          -- auto, otsu, contrast, grayscale, ...
+    status TEXT NOT NULL DEFAULT 'ok',    -- ok, queued, improved, exhausted
+    attempts INTEGER NOT NULL DEFAULT 0,  -- Reprocessing runs so far
+    last_error TEXT,
+    updated_at INTEGER NOT NULL,          -- unix seconds
+    PRIMARY KEY (doc_id, page_number),
+    FOREIGN KEY (doc_id) REFERENCES documents(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_ocr_pages_status ON ocr_pages(status);
+
+-- Preview artifacts built at ingestion, so document lists render without
+-- opening the source files. Encrypted collections keep only the page count.
+CREATE TABLE IF NOT EXISTS document_previews (
+    doc_id INTEGER PRIMARY KEY,
+    title TEXT,                           -- From the file's metadata
+    author TEXT,
+    page_count INTEGER NOT NULL DEFAULT 0,
+    thumbnail BLOB,                       -- JPEG of the first page (PDF only)
+    thumbnail_width INTEGER,
+    thumbnail_height INTEGER,
+    created_at INTEGER NOT NULL,          -- unix seconds
+    FOREIGN KEY (doc_id) REFERENCES documents(id) ON DELETE CASCADE
+);
+
 -- Collection-level quality metrics (aggregated for analytics)
 CREATE TABLE IF NOT EXISTS collection_quality_metrics (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -505,6 +547,331 @@ CREATE INDEX IF NOT EXISTS idx_model_context_limits_provider_model ON model_cont
 CREATE INDEX IF NOT EXISTS idx_token_usage_logs_conversation ON token_usage_logs(conversation_id);
 CREATE INDEX IF NOT EXISTS idx_token_usage_logs_created_at ON token_usage_logs(created_at);
 
+-- ============================================================
+-- PERSISTED RETRIEVAL CACHE
+-- ============================================================
+
+-- Text retrieval results kept across restarts. Only chunk ids and scores
+-- are stored; content is read back from document_chunks on a hit.
+CREATE TABLE IF NOT EXISTS retrieval_cache (
+  collection_id INTEGER NOT NULL,
+  query_key TEXT NOT NULL,                      -- lowercased query text
+  top_k INTEGER NOT NULL,
+
+  query_text TEXT NOT NULL,
+  embedding_model TEXT NOT NULL,                -- results are only reused with the same model
+  query_embedding BLOB,                         -- little-endian f32, seeds the embedding cache
+  results_json TEXT NOT NULL,                   -- [{"chunkId": 1, "score": 0.8}, ...]
+
+  created_at INTEGER NOT NULL,                  -- unix seconds
+  expires_at INTEGER NOT NULL,
+
+  PRIMARY KEY (collection_id, query_key, top_k),
+  FOREIGN KEY (collection_id) REFERENCES collections(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_retrieval_cache_expires_at ON retrieval_cache(expires_at);
+
+-- ============================================================
+-- ANSWER CACHE
+-- ============================================================
+
+-- Generated answers to repeated questions. An entry is reused only while
+-- the collection's content_version still matches the one it was built at.
+CREATE TABLE IF NOT EXISTS answer_cache (
+  collection_id INTEGER NOT NULL,
+  cache_key TEXT NOT NULL,                      -- hash of the normalized query, model, language, top_k and variant
+  collection_version INTEGER NOT NULL,
+
+  query_text TEXT NOT NULL,
+  answer TEXT NOT NULL,
+  results_json TEXT NOT NULL,                   -- the retrieved QueryResults the answer was built from
+  hit_count INTEGER NOT NULL DEFAULT 0,
+
+  created_at INTEGER NOT NULL,                  -- unix seconds
+  expires_at INTEGER NOT NULL,
+
+  PRIMARY KEY (collection_id, cache_key),
+  FOREIGN KEY (collection_id) REFERENCES collections(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_answer_cache_expires_at ON answer_cache(expires_at);
+
+CREATE TRIGGER IF NOT EXISTS documents_version_ai AFTER INSERT ON documents BEGIN
+  UPDATE collections SET content_version = content_version + 1 WHERE id = new.collection_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS documents_version_ad AFTER DELETE ON documents BEGIN
+  UPDATE collections SET content_version = content_version + 1 WHERE id = old.collection_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS documents_version_au AFTER UPDATE OF collection_id, file_path ON documents BEGIN
+  UPDATE collections SET content_version = content_version + 1
+  WHERE id IN (old.collection_id, new.collection_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS document_chunks_version_ai AFTER INSERT ON document_chunks BEGIN
+  UPDATE collections SET content_version = content_version + 1
+  WHERE id = (SELECT collection_id FROM documents WHERE id = new.doc_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS document_chunks_version_ad AFTER DELETE ON document_chunks BEGIN
+  UPDATE collections SET content_version = content_version + 1
+  WHERE id = (SELECT collection_id FROM documents WHERE id = old.doc_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS document_chunks_version_au AFTER UPDATE OF content, doc_id ON document_chunks BEGIN
+  UPDATE collections SET content_version = content_version + 1
+  WHERE id IN (
+    SELECT collection_id FROM documents WHERE id IN (old.doc_id, new.doc_id)
+  );
+END;
+
+CREATE TRIGGER IF NOT EXISTS structured_rows_version_ai AFTER INSERT ON structured_rows BEGIN
+  UPDATE collections SET content_version = content_version + 1
+  WHERE id = (SELECT collection_id FROM documents WHERE id = new.doc_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS structured_rows_version_ad AFTER DELETE ON structured_rows BEGIN
+  UPDATE collections SET content_version = content_version + 1
+  WHERE id = (SELECT collection_id FROM documents WHERE id = old.doc_id);
+END;
+
+-- ============================================================
+-- CHUNK USAGE
+-- ============================================================
+
+-- How often each chunk is returned by retrieval and cited in a saved
+-- answer. Rows are created on first use; a chunk without one was never used.
+CREATE TABLE IF NOT EXISTS chunk_usage (
+  chunk_id INTEGER PRIMARY KEY,
+  retrieval_count INTEGER NOT NULL DEFAULT 0,
+  citation_count INTEGER NOT NULL DEFAULT 0,
+
+  first_retrieved_at INTEGER,                   -- unix seconds
+  last_retrieved_at INTEGER,
+  last_cited_at INTEGER,
+
+  FOREIGN KEY (chunk_id) REFERENCES document_chunks(id) ON DELETE CASCADE
+);
+
+-- ============================================================
+-- RETRIEVAL TUNING
+-- ============================================================
+
+-- Rated answers with the retrieval parameters they were produced with,
+-- correlated by the tuning job. Kept after feedback is cleared in memory.
+CREATE TABLE IF NOT EXISTS retrieval_feedback (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  collection_id INTEGER,
+  query_text TEXT NOT NULL,
+  rating TEXT NOT NULL,                         -- positive | negative | neutral
+
+  top_k INTEGER NOT NULL,
+  vector_weight REAL NOT NULL,
+  keyword_weight REAL NOT NULL,
+  reranking_enabled INTEGER NOT NULL,
+  collection_quality REAL,                      -- average document quality when rated
+
+  experiment_id INTEGER,                        -- experiment the query ran under
+  experiment_arm TEXT,                          -- baseline | candidate
+
+  created_at INTEGER NOT NULL,                  -- unix seconds
+
+  FOREIGN KEY (collection_id) REFERENCES collections(id) ON DELETE CASCADE,
+  FOREIGN KEY (experiment_id) REFERENCES retrieval_experiments(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_retrieval_feedback_created_at ON retrieval_feedback(created_at);
+
+-- Proposed parameters tried on part of the queries. At most one is running.
+CREATE TABLE IF NOT EXISTS retrieval_experiments (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  status TEXT NOT NULL DEFAULT 'running',       -- running | promoted | rolled_back | stopped
+  baseline_json TEXT NOT NULL,                  -- parameters when it started
+  candidate_json TEXT NOT NULL,
+  rationale TEXT,
+
+  created_at INTEGER NOT NULL,                  -- unix seconds
+  ended_at INTEGER
+);
+
+-- How queries were routed (semantic | keyword | structured), with the route
+-- the user corrected it to. Corrections and forced routes refit the
+-- routing thresholds.
+CREATE TABLE IF NOT EXISTS query_routing_decisions (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  collection_id INTEGER,
+  query_hash TEXT NOT NULL,
+  query_type TEXT NOT NULL,
+  auto_route TEXT,                              -- route picked by the analyzer
+  route TEXT,                                   -- route the query ran on
+  overridden INTEGER NOT NULL DEFAULT 0,        -- route forced by the request
+  structured_signals INTEGER NOT NULL,
+  term_count INTEGER NOT NULL,
+  corrected_route TEXT,
+
+  created_at INTEGER NOT NULL,                  -- unix seconds
+
+  FOREIGN KEY (collection_id) REFERENCES collections(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_query_routing_decisions_created_at ON query_routing_decisions(created_at);
+
+-- ============================================================
+-- COLLECTION ENCRYPTION
+-- ============================================================
+
+-- Collections whose chunk text and embeddings are encrypted at rest. The
+-- key is derived from a user passphrase with `salt` and `iterations`;
+-- `key_check` is a known value sealed with it to recognise the passphrase.
+CREATE TABLE IF NOT EXISTS collection_encryption (
+  collection_id INTEGER PRIMARY KEY,
+  salt BLOB NOT NULL,
+  iterations INTEGER NOT NULL,
+  key_check BLOB NOT NULL,
+  created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+  FOREIGN KEY (collection_id) REFERENCES collections(id) ON DELETE CASCADE
+);
+
+-- ============================================================
+-- SOURCE CONNECTORS
+-- ============================================================
+
+-- Notion or Confluence sources synced into a collection. The API token
+-- is kept in the keychain under `rag-connector-token-<id>`.
+CREATE TABLE IF NOT EXISTS rag_connectors (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  name TEXT NOT NULL,
+  kind TEXT NOT NULL,                           -- notion | confluence
+  collection_id INTEGER NOT NULL,
+  base_url TEXT,
+  username TEXT,
+  scope TEXT,                                   -- Notion root page id | Confluence space key
+  sync_interval_minutes INTEGER NOT NULL DEFAULT 0,
+  enabled INTEGER NOT NULL DEFAULT 1,
+  last_synced_at INTEGER,                       -- unix seconds
+  last_sync_status TEXT,                        -- ok | partial | failed
+  last_sync_error TEXT,
+  created_at INTEGER NOT NULL,
+
+  FOREIGN KEY (collection_id) REFERENCES collections(id) ON DELETE CASCADE
+);
+
+-- Imported pages with their place in the source's page tree
+CREATE TABLE IF NOT EXISTS rag_connector_pages (
+  connector_id INTEGER NOT NULL,
+  external_id TEXT NOT NULL,
+  parent_external_id TEXT,
+  title TEXT NOT NULL,
+  path TEXT NOT NULL,                           -- ancestor titles joined by ' / '
+  url TEXT,
+  last_edited_at INTEGER NOT NULL,              -- unix seconds, from the source
+  doc_id INTEGER,
+  synced_at INTEGER NOT NULL,
+
+  PRIMARY KEY (connector_id, external_id),
+  FOREIGN KEY (connector_id) REFERENCES rag_connectors(id) ON DELETE CASCADE,
+  FOREIGN KEY (doc_id) REFERENCES documents(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_rag_connector_pages_doc ON rag_connector_pages(doc_id);
+
+-- ============================================================
+-- GIT REPOSITORIES
+-- ============================================================
+
+-- Git repositories whose source files are synced into a collection.
+-- Remote repositories are cloned under `rag_git/<id>` in app data.
+CREATE TABLE IF NOT EXISTS rag_git_repos (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  name TEXT NOT NULL,
+  collection_id INTEGER NOT NULL,
+  url TEXT NOT NULL,                            -- remote URL | local path
+  branch TEXT,
+  subdirectory TEXT,
+  sync_interval_minutes INTEGER NOT NULL DEFAULT 0,
+  enabled INTEGER NOT NULL DEFAULT 1,
+  last_commit TEXT,
+  last_synced_at INTEGER,                       -- unix seconds
+  last_sync_status TEXT,                        -- ok | partial | failed
+  last_sync_error TEXT,
+  created_at INTEGER NOT NULL,
+
+  FOREIGN KEY (collection_id) REFERENCES collections(id) ON DELETE CASCADE
+);
+
+-- Imported files with the blob and commit their content came from
+CREATE TABLE IF NOT EXISTS rag_git_files (
+  repo_id INTEGER NOT NULL,
+  path TEXT NOT NULL,
+  language TEXT NOT NULL,
+  blob_sha TEXT NOT NULL,
+  commit_sha TEXT NOT NULL,
+  doc_id INTEGER,
+  chunk_count INTEGER NOT NULL DEFAULT 0,
+  synced_at INTEGER NOT NULL,
+
+  PRIMARY KEY (repo_id, path),
+  FOREIGN KEY (repo_id) REFERENCES rag_git_repos(id) ON DELETE CASCADE,
+  FOREIGN KEY (doc_id) REFERENCES documents(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_rag_git_files_doc ON rag_git_files(doc_id);
+
+-- ============================================================
+-- EMAIL ARCHIVES
+-- ============================================================
+
+-- Messages of imported .eml / .mbox documents. The chunks of a message
+-- have its message_index as page_number.
+CREATE TABLE IF NOT EXISTS rag_email_messages (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  doc_id INTEGER NOT NULL,
+  message_index INTEGER NOT NULL,
+  message_id TEXT NOT NULL,
+  thread_id TEXT NOT NULL,
+  in_reply_to TEXT,
+  subject TEXT NOT NULL DEFAULT '',
+  from_address TEXT,
+  from_name TEXT,
+  recipients TEXT NOT NULL DEFAULT '',         -- To and Cc, comma-separated
+  sent_at INTEGER,                              -- unix seconds
+  attachments TEXT NOT NULL DEFAULT '',        -- file names, comma-separated
+  chunk_count INTEGER NOT NULL DEFAULT 0,
+
+  UNIQUE (doc_id, message_index),
+  FOREIGN KEY (doc_id) REFERENCES documents(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_rag_email_messages_from ON rag_email_messages(from_address);
+CREATE INDEX IF NOT EXISTS idx_rag_email_messages_sent ON rag_email_messages(sent_at);
+CREATE INDEX IF NOT EXISTS idx_rag_email_messages_thread ON rag_email_messages(thread_id);
+
+-- ============================================================
+-- IMPORT TEMPLATES
+-- ============================================================
+
+-- Column mappings for recurring CSV/Excel exports. A file whose header row,
+-- after `skip_rows`, has the normalized headers of `signature` is stored
+-- with the template's field mapping and type coercions.
+CREATE TABLE IF NOT EXISTS rag_import_templates (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  name TEXT NOT NULL,
+  collection_id INTEGER,                        -- NULL: any collection
+  signature TEXT NOT NULL,                      -- JSON: ["order_id", "region"]
+  skip_rows INTEGER NOT NULL DEFAULT 0,
+  skip_footer_rows INTEGER NOT NULL DEFAULT 0,
+  columns_json TEXT NOT NULL DEFAULT '[]',
+  applied_count INTEGER NOT NULL DEFAULT 0,
+  last_applied_at INTEGER,                      -- unix seconds
+  created_at INTEGER NOT NULL,
+  updated_at INTEGER NOT NULL,
+
+  FOREIGN KEY (collection_id) REFERENCES collections(id) ON DELETE CASCADE
+);
+
 -- ============================================================
 -- SEED DATA: Model Context Limits
 -- ============================================================
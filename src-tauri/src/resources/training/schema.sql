@@ -16,7 +16,9 @@ CREATE TABLE IF NOT EXISTS datasets (
   name TEXT NOT NULL,
   type TEXT NOT NULL CHECK(type IN ('corrections', 'golden', 'synthetic')),
   description TEXT,
-  created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+  created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+  -- Set while the dataset is in the trash (unix ms)
+  deleted_at INTEGER
 );
 
 -- Dataset items for golden/synthetic (and optionally corrections-derived snapshots).
@@ -40,6 +42,10 @@ CREATE TABLE IF NOT EXISTS corrections (
   relevance_rating INTEGER CHECK(relevance_rating BETWEEN 1 AND 5),
   safety_rating INTEGER CHECK(safety_rating BETWEEN 1 AND 5),
   domain_notes TEXT,
+  -- Review queue: only approved rows may go into datasets and runs
+  review_status TEXT NOT NULL DEFAULT 'pending' CHECK(review_status IN ('pending', 'approved', 'rejected')),
+  review_note TEXT,
+  reviewed_at DATETIME,
   created_at DATETIME DEFAULT CURRENT_TIMESTAMP
 );
 
@@ -63,7 +69,7 @@ CREATE TABLE IF NOT EXISTS training_runs (
   base_version_id TEXT REFERENCES model_versions(version_id),
   teacher_model_id TEXT REFERENCES models(model_id),
   method TEXT NOT NULL CHECK(method IN ('fine_tune', 'knowledge_distillation', 'hybrid')),
-  status TEXT NOT NULL CHECK(status IN ('queued', 'running', 'completed', 'failed', 'cancelled', 'rolled_back')),
+  status TEXT NOT NULL CHECK(status IN ('queued', 'running', 'completed', 'failed', 'cancelled', 'crashed', 'rolled_back')),
   start_time DATETIME DEFAULT CURRENT_TIMESTAMP,
   end_time DATETIME,
   hyperparams_json TEXT NOT NULL,
@@ -126,6 +132,22 @@ CREATE TABLE IF NOT EXISTS training_logs (
   timestamp DATETIME DEFAULT CURRENT_TIMESTAMP
 );
 
+-- Host resources sampled while a trainer runs, for diagnosing failed runs.
+CREATE TABLE IF NOT EXISTS run_resource_samples (
+  sample_id INTEGER PRIMARY KEY AUTOINCREMENT,
+  run_id TEXT NOT NULL REFERENCES training_runs(run_id) ON DELETE CASCADE,
+  cpu_percent REAL,
+  ram_used_bytes INTEGER,
+  ram_total_bytes INTEGER,
+  process_rss_bytes INTEGER,
+  gpu_util_percent REAL,
+  gpu_mem_used_bytes INTEGER,
+  gpu_mem_total_bytes INTEGER,
+  gpus_json TEXT,
+  warning TEXT,
+  sampled_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
 -- Evaluation metrics.
 CREATE TABLE IF NOT EXISTS evaluation_metrics (
   metric_id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -164,6 +186,9 @@ CREATE TABLE IF NOT EXISTS soft_labels (
   soft_labels_blob BLOB,  -- Binary: Float32 array [seq_len, vocab_size] or NULL
   temperature REAL NOT NULL DEFAULT 1.0,
   metadata_json TEXT,  -- Additional metadata (generation params, token timestamps, etc.)
+  review_status TEXT NOT NULL DEFAULT 'pending' CHECK(review_status IN ('pending', 'approved', 'rejected')),
+  review_note TEXT,
+  reviewed_at DATETIME,
   created_at DATETIME DEFAULT CURRENT_TIMESTAMP
 );
 
@@ -197,9 +222,12 @@ CREATE INDEX IF NOT EXISTS idx_run_corrections_correction ON run_corrections(cor
 CREATE INDEX IF NOT EXISTS idx_versions_model_created ON model_versions(model_id, created_at);
 CREATE INDEX IF NOT EXISTS idx_eval_version ON evaluation_metrics(version_id);
 CREATE INDEX IF NOT EXISTS idx_logs_run_time ON training_logs(run_id, timestamp);
+CREATE INDEX IF NOT EXISTS idx_resource_samples_run_time ON run_resource_samples(run_id, sampled_at);
 CREATE INDEX IF NOT EXISTS idx_soft_labels_prompt_hash ON soft_labels(prompt_hash);
 CREATE INDEX IF NOT EXISTS idx_soft_labels_teacher_model ON soft_labels(teacher_model_id);
 CREATE INDEX IF NOT EXISTS idx_soft_labels_teacher_hash ON soft_labels(teacher_model_id, prompt_hash);
+CREATE INDEX IF NOT EXISTS idx_corrections_review_status ON corrections(review_status);
+CREATE INDEX IF NOT EXISTS idx_soft_labels_review_status ON soft_labels(review_status);
 
 -- Deferred foreign keys (added after all tables exist to avoid circular dependencies)
 -- These are created without actual foreign key constraints to avoid creation order issues
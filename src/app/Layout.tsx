@@ -2,17 +2,21 @@ import { useCallback, useEffect, useMemo, useState } from "react";
 import { Outlet, useLocation, useNavigate } from "react-router";
 import { Activity, ChevronRight, Settings } from "lucide-react";
 import { useShallow } from "zustand/shallow";
+import { listen } from "@tauri-apps/api/event";
 import { useSettingsStore } from "../store/settings";
-import PopupTranslateDialog from "../shared/components/PopupTranslateDialog";
 import { ToastContainer } from "../shared/components/ToastContainer";
 import TerminalPanel from "../shared/components/TerminalPanel";
 import { NAV_SECTIONS, type NavItem } from "./navigation";
 import { cn } from "../utils/cn";
+import { isTauri } from "../utils/tauri";
 import { useShortcutEvents } from "../hooks/useShortcutEvents";
+import { useAutomationEvents } from "../hooks/useAutomationEvents";
 import { useQaEventRecorder } from "../hooks/useQaEventRecorder";
+import { useSyncApiKeys } from "../hooks/useSyncApiKeys";
 import { useSyncConfig } from "../hooks/useSyncConfig";
 import { useSyncEmbeddingConfig } from "../hooks/useSyncEmbeddingConfig";
 import { useSyncLanguages } from "../hooks/useSyncLanguages";
+import { useSyncProjectProfile } from "../hooks/useSyncProjectProfile";
 import { useSyncShortcuts } from "../hooks/useSyncShortcuts";
 import "./App.css";
 import ThemeManager from "../features/theme";
@@ -20,8 +24,6 @@ import ThemeManager from "../features/theme";
 export default function Layout() {
   const location = useLocation();
   const navigate = useNavigate();
-  const [capturedText, setCapturedText] = useState<string | null>(null);
-  const [popupOpen, setPopupOpen] = useState(false);
   const [sidebarCollapsed, setSidebarCollapsed] = useState(false);
   const { provider, model, shortcutsEnabled, navSettings, sectionSettings } = useSettingsStore(
     useShallow((state) => ({
@@ -33,18 +35,37 @@ export default function Layout() {
     })),
   );
 
+  useSyncApiKeys();
   useSyncConfig();
   useSyncEmbeddingConfig();
   useSyncLanguages();
+  useSyncProjectProfile();
   useSyncShortcuts();
   useQaEventRecorder();
 
-  const handleCapture = useCallback((payload: string) => {
-    setCapturedText(payload);
-    setPopupOpen(true);
-  }, []);
+  useShortcutEvents();
+  useAutomationEvents();
 
-  useShortcutEvents({ onCapture: handleCapture });
+  useEffect(() => {
+    if (!isTauri()) return;
+    let disposed = false;
+    let unlisten: (() => void) | undefined;
+
+    listen<string>("tray-navigate", (event) => navigate(event.payload))
+      .then((fn) => {
+        if (disposed) {
+          fn();
+          return;
+        }
+        unlisten = fn;
+      })
+      .catch(console.error);
+
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, [navigate]);
 
   const visibleSections = useMemo(
     () =>
@@ -251,14 +272,6 @@ export default function Layout() {
       <TerminalPanel />
       <br />
 
-      <PopupTranslateDialog
-        open={popupOpen}
-        initialText={capturedText}
-        onClose={() => {
-          setPopupOpen(false);
-          setCapturedText(null);
-        }}
-      />
       <ToastContainer />
     </main>
   );
@@ -4,8 +4,11 @@
 // =============================================================================
 
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import type {
+  MockConfigReload,
   MockServerConfig,
+  MockRoutesExport,
   MockServerStatus,
   LogEntry,
 } from "../types";
@@ -29,6 +32,27 @@ export const mockServerApi = {
     return invoke<MockServerConfig>("mock_server_update_config", { config });
   },
 
+  /**
+   * Write each route to its own file under `dir`, for version control
+   */
+  async exportRoutes(dir: string): Promise<MockRoutesExport> {
+    return invoke<MockRoutesExport>("mock_server_export_routes", { dir });
+  },
+
+  /**
+   * Replace the port and routes with those exported to `dir`
+   */
+  async importRoutes(dir: string): Promise<MockServerConfig> {
+    return invoke<MockServerConfig>("mock_server_import_routes", { dir });
+  },
+
+  /**
+   * Keep the routes in `dir` instead of the settings; null moves them back
+   */
+  async setRoutesDir(dir: string | null): Promise<MockServerConfig> {
+    return invoke<MockServerConfig>("mock_server_set_routes_dir", { dir });
+  },
+
   /**
    * Get the current status of the mock server
    */
@@ -58,6 +82,17 @@ export const mockServerApi = {
   },
 };
 
+/**
+ * Subscribe to reloads of routes kept in files
+ */
+export async function onMockConfigReloaded(
+  handler: (reload: MockConfigReload) => void,
+): Promise<UnlistenFn> {
+  return await listen<MockConfigReload>("mock-server-config-reloaded", (event) =>
+    handler(event.payload),
+  );
+}
+
 /**
  * Helper function to get only MockServer logs
  */
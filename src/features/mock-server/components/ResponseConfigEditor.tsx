@@ -1,6 +1,6 @@
 // =============================================================================
 // Response Config Editor Component
-// Shared response configuration UI (status, content-type, body)
+// Shared response configuration UI (status, content-type, body, streaming)
 // =============================================================================
 
 import { Input } from "../../../shared/components/Input";
@@ -9,6 +9,7 @@ import { TextArea } from "../../../shared/components/TextArea";
 import { Button } from "../../../shared/components/Button";
 import { KeyValueEditor } from "./KeyValueEditor";
 import { FormDataEditor } from "./FormDataEditor";
+import { StreamEditor } from "./StreamEditor";
 import type { MockResponse, ResponseBodyType, RawSubType } from "../types";
 import { createKeyValue, getPlaceholderForRawType } from "../types";
 
@@ -167,6 +168,11 @@ export function ResponseConfigEditor({
           </div>
         )}
       </div>
+
+      <StreamEditor
+        stream={response.stream}
+        onChange={(stream) => onChange({ ...response, stream })}
+      />
     </div>
   );
 }
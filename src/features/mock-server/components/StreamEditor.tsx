@@ -0,0 +1,160 @@
+// =============================================================================
+// Stream Editor Component
+// Streaming response parts: Server-Sent Events or chunked body segments
+// =============================================================================
+
+import { Trash2 } from "lucide-react";
+import { Input } from "../../../shared/components/Input";
+import { Select } from "../../../shared/components/Select";
+import { TextArea } from "../../../shared/components/TextArea";
+import { Button } from "../../../shared/components/Button";
+import type { MockStream, StreamMode, StreamPart } from "../types";
+import { createStream } from "../types";
+
+export interface StreamEditorProps {
+  stream: MockStream | null | undefined;
+  onChange: (stream: MockStream | null) => void;
+}
+
+const parseDelay = (value: string): number | null => {
+  const parsed = parseInt(value);
+  return Number.isNaN(parsed) ? null : Math.max(0, parsed);
+};
+
+export function StreamEditor({ stream, onChange }: StreamEditorProps) {
+  const updatePart = (index: number, patch: Partial<StreamPart>) => {
+    if (!stream) return;
+    const parts = [...stream.parts];
+    parts[index] = { ...parts[index], ...patch };
+    onChange({ ...stream, parts });
+  };
+
+  return (
+    <div className="space-y-3">
+      <div className="grid grid-cols-3 gap-3">
+        <div className="space-y-2">
+          <label className="text-[10px] font-bold text-app-subtext uppercase tracking-widest px-1">
+            Streaming
+          </label>
+          <Select
+            options={[
+              { label: "Off", value: "off" },
+              { label: "Server-Sent Events", value: "sse" },
+              { label: "Chunked Body", value: "chunked" },
+            ]}
+            value={stream?.mode ?? "off"}
+            onChange={(v) => {
+              const value = typeof v === "string" ? v : v[0];
+              if (value === "off") onChange(null);
+              else if (!stream) onChange(createStream(value as StreamMode));
+              else onChange({ ...stream, mode: value as StreamMode });
+            }}
+            className="h-11 bg-app-card border-app-border rounded-xl text-xs w-full font-bold"
+            searchable={false}
+          />
+        </div>
+        {stream && (
+          <>
+            <div className="space-y-2">
+              <label className="text-[10px] font-bold text-app-subtext uppercase tracking-widest px-1">
+                Interval (ms)
+              </label>
+              <Input
+                type="number"
+                min={0}
+                className="h-11 bg-app-card border-app-border rounded-xl font-mono text-xs"
+                value={stream.intervalMs}
+                onChange={(e) =>
+                  onChange({ ...stream, intervalMs: parseDelay(e.target.value) ?? 0 })
+                }
+              />
+            </div>
+            <div className="space-y-2">
+              <label className="text-[10px] font-bold text-app-subtext uppercase tracking-widest px-1">
+                Repeat (0 = forever)
+              </label>
+              <Input
+                type="number"
+                min={0}
+                className="h-11 bg-app-card border-app-border rounded-xl font-mono text-xs"
+                value={stream.repeat}
+                onChange={(e) =>
+                  onChange({ ...stream, repeat: parseDelay(e.target.value) ?? 1 })
+                }
+              />
+            </div>
+          </>
+        )}
+      </div>
+
+      {stream && (
+        <div className="space-y-2">
+          {stream.parts.map((part, idx) => (
+            <div
+              key={idx}
+              className="p-3 bg-app-card rounded-xl border border-app-border space-y-2">
+              <div className="flex gap-2">
+                {stream.mode === "sse" && (
+                  <>
+                    <Input
+                      placeholder="Event (optional)"
+                      value={part.event ?? ""}
+                      onChange={(e) => updatePart(idx, { event: e.target.value || null })}
+                      className="bg-app-bg border-app-border text-xs"
+                    />
+                    <Input
+                      placeholder="Id (optional)"
+                      value={part.id ?? ""}
+                      onChange={(e) => updatePart(idx, { id: e.target.value || null })}
+                      className="bg-app-bg border-app-border text-xs"
+                    />
+                  </>
+                )}
+                <Input
+                  type="number"
+                  min={0}
+                  placeholder={`Delay (${stream.intervalMs} ms)`}
+                  value={part.delayMs ?? ""}
+                  onChange={(e) => updatePart(idx, { delayMs: parseDelay(e.target.value) })}
+                  className="bg-app-bg border-app-border text-xs font-mono w-36"
+                />
+                <Button
+                  size="icon"
+                  variant="ghost"
+                  onClick={() =>
+                    onChange({ ...stream, parts: stream.parts.filter((_, i) => i !== idx) })
+                  }
+                  className="text-app-subtext hover:text-red-400">
+                  <Trash2 className="w-3.5 h-3.5" />
+                </Button>
+              </div>
+              <TextArea
+                className="font-mono text-xs min-h-[60px] bg-app-bg border-app-border text-app-text"
+                value={part.data}
+                onChange={(e) => updatePart(idx, { data: e.target.value })}
+                placeholder={stream.mode === "sse" ? "Event data" : "Body segment"}
+              />
+            </div>
+          ))}
+          {stream.parts.length === 0 && (
+            <div className="text-center py-4 border border-dashed border-app-border rounded-lg text-xs text-app-subtext">
+              No parts defined; the static body is sent instead.
+            </div>
+          )}
+          <Button
+            size="sm"
+            variant="ghost"
+            className="h-6 text-[10px] text-app-accent"
+            onClick={() =>
+              onChange({
+                ...stream,
+                parts: [...stream.parts, { data: "", event: null, id: null, delayMs: null }],
+              })
+            }>
+            ADD PART
+          </Button>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -13,6 +13,7 @@ export { EndpointDefinitionSection } from "./EndpointDefinitionSection";
 export { IncomingRequestSection } from "./IncomingRequestSection";
 export { MockResponseSection } from "./MockResponseSection";
 export { ResponseConfigEditor } from "./ResponseConfigEditor";
+export { StreamEditor } from "./StreamEditor";
 export { MultiResponseEditor } from "./MultiResponseEditor";
 export { PayloadMappingEditor } from "./PayloadMappingEditor";
 export { CurlCommandSection } from "./CurlCommandSection";
@@ -30,6 +31,7 @@ export type { EndpointDefinitionSectionProps } from "./EndpointDefinitionSection
 export type { IncomingRequestSectionProps } from "./IncomingRequestSection";
 export type { MockResponseSectionProps } from "./MockResponseSection";
 export type { ResponseConfigEditorProps } from "./ResponseConfigEditor";
+export type { StreamEditorProps } from "./StreamEditor";
 export type { MultiResponseEditorProps } from "./MultiResponseEditor";
 export type { PayloadMappingEditorProps } from "./PayloadMappingEditor";
 export type { CurlCommandSectionProps } from "./CurlCommandSection";
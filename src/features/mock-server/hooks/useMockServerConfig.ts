@@ -12,7 +12,7 @@ import {
   type SetStateAction,
 } from "react";
 import { isTauri } from "../../../utils/tauri";
-import { mockServerApi } from "../api/client";
+import { mockServerApi, onMockConfigReloaded } from "../api/client";
 import type { MockServerConfig, MockServerStatus } from "../types";
 import { createRoute, cloneConfig } from "../types";
 
@@ -85,6 +85,27 @@ export function useMockServerConfig(): UseMockServerConfigReturn {
     loadConfig();
   }, [loadConfig]);
 
+  // Route files changed on disk: show the new routes, or why they were refused
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten: (() => void) | undefined;
+    let disposed = false;
+    onMockConfigReloaded((reload) => {
+      if (reload.applied) {
+        loadConfig();
+      } else {
+        setError(`Route files not reloaded: ${reload.errors.join("; ")}`);
+      }
+    }).then((fn) => {
+      if (disposed) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, [loadConfig]);
+
   // Save configuration
   const saveConfigWithConfig = useCallback(
     async (nextConfig: MockServerConfig) => {
@@ -123,6 +144,7 @@ export function useMockServerConfig(): UseMockServerConfigReturn {
               formData: r.response.formData ?? [],
               formUrlencode: r.response.formUrlencode ?? [],
               delayMs: r.response.delayMs ?? null,
+              stream: r.response.stream ?? null,
             },
           }));
 
@@ -55,6 +55,28 @@ export interface MockResponse {
   formData?: FormDataItem[];
   formUrlencode?: MockKeyValue[];
   delayMs?: number | null;
+  /** Send the body as timed parts instead of `body` */
+  stream?: MockStream | null;
+}
+
+export type StreamMode = "sse" | "chunked";
+
+export interface StreamPart {
+  data: string;
+  /** SSE event name; ignored for chunked streams */
+  event?: string | null;
+  /** SSE event id; ignored for chunked streams */
+  id?: string | null;
+  delayMs?: number | null;
+}
+
+export interface MockStream {
+  mode: StreamMode;
+  parts: StreamPart[];
+  /** Wait before each part after the first that sets no delay of its own */
+  intervalMs: number;
+  /** Times the parts are sent; 0 repeats until the client disconnects */
+  repeat: number;
 }
 
 export type MultiResponseMatchMode = "exact" | "key_match";
@@ -82,6 +104,26 @@ export interface MockRoute {
 export interface MockServerConfig {
   port: number;
   routes: MockRoute[];
+  /** Directory the routes are kept in, one file per route */
+  routesDir?: string | null;
+}
+
+/** Payload of the `mock-server-config-reloaded` event */
+export interface MockConfigReload {
+  source: string;
+  /** False when the files failed to load or validate; the old config stays */
+  applied: boolean;
+  routeCount: number;
+  errors: string[];
+  /** The port changed while running; it moves on the next start */
+  restartRequired: boolean;
+}
+
+export interface MockRoutesExport {
+  directory: string;
+  written: number;
+  /** Route files of routes that no longer exist */
+  removed: number;
 }
 
 export interface MockServerStatus {
@@ -137,6 +179,22 @@ export const createRoute = (): MockRoute => ({
   multiResponses: [],
 });
 
+export const createStream = (mode: StreamMode): MockStream => ({
+  mode,
+  parts:
+    mode === "sse"
+      ? [
+          { data: '{ "progress": 50 }', event: "progress", id: null, delayMs: null },
+          { data: '{ "progress": 100 }', event: "done", id: null, delayMs: null },
+        ]
+      : [
+          { data: "Hello", delayMs: null },
+          { data: ", world", delayMs: null },
+        ],
+  intervalMs: 500,
+  repeat: 1,
+});
+
 export const createPayloadResponseMapping = (): PayloadResponseMapping => ({
   id:
     typeof crypto !== "undefined" && typeof crypto.randomUUID === "function"
@@ -209,6 +267,7 @@ export const normalizeConfig = (config: MockServerConfig): MockServerConfig => {
         formData: route.response.formData ?? [],
         formUrlencode: route.response.formUrlencode ?? [],
         delayMs: route.response.delayMs ?? null,
+        stream: route.response.stream ?? null,
       },
     })),
   };
@@ -3,11 +3,16 @@ import type {
   Correction,
   CorrectionWithTags,
   Tag,
+  ReviewStatus,
+  ReviewSummary,
+  BulkApproveResult,
   TrainingRun,
   TrainingLog,
+  ResourceSample,
   ModelVersion,
   EvaluationMetric,
   Dataset,
+  QaSessionDatasetResult,
   RunArtifact,
   SoftLabel,
   SoftLabelGenerationInput,
@@ -294,6 +299,67 @@ export class ModelDistillationAPI {
     return await invoke("distill_list_tags");
   }
 
+  // -------------------------------------------------------------------------
+  // Review Queue
+  // -------------------------------------------------------------------------
+
+  static async getReviewSummary(): Promise<ReviewSummary> {
+    return await invoke("distill_get_review_summary");
+  }
+
+  static async listReviewCorrections(
+    status?: ReviewStatus,
+    tag?: string,
+    limit?: number
+  ): Promise<Correction[]> {
+    return await invoke("distill_list_review_corrections", { status, tag, limit });
+  }
+
+  static async listReviewSoftLabels(
+    status?: ReviewStatus,
+    tag?: string,
+    limit?: number
+  ): Promise<SoftLabel[]> {
+    return await invoke("distill_list_review_soft_labels", { status, tag, limit });
+  }
+
+  static async reviewCorrections(
+    correctionIds: string[],
+    status: ReviewStatus,
+    note?: string
+  ): Promise<number> {
+    return await invoke("distill_review_corrections", { correctionIds, status, note });
+  }
+
+  static async reviewSoftLabels(
+    softLabelIds: string[],
+    status: ReviewStatus,
+    note?: string
+  ): Promise<number> {
+    return await invoke("distill_review_soft_labels", { softLabelIds, status, note });
+  }
+
+  static async editCorrectionTarget(
+    correctionId: string,
+    correctedOutput: string
+  ): Promise<Correction> {
+    return await invoke("distill_edit_correction_target", { correctionId, correctedOutput });
+  }
+
+  static async editSoftLabelTarget(
+    softLabelId: string,
+    teacherOutput: string
+  ): Promise<SoftLabel> {
+    return await invoke("distill_edit_soft_label_target", { softLabelId, teacherOutput });
+  }
+
+  static async approveByTag(
+    tag: string,
+    includeSoftLabels?: boolean
+  ): Promise<BulkApproveResult> {
+    return await invoke("distill_approve_by_tag", { tag, includeSoftLabels });
+  }
+
   // -------------------------------------------------------------------------
   // Datasets (Flow B)
   // -------------------------------------------------------------------------
@@ -394,6 +460,10 @@ export class ModelDistillationAPI {
     return await invoke("distill_list_training_logs", { runId, limit });
   }
 
+  static async listResourceSamples(runId: string, limit?: number): Promise<ResourceSample[]> {
+    return await invoke("distill_list_resource_samples", { runId, limit });
+  }
+
   static async startPythonTraining(config: DistillTrainConfig): Promise<string> {
     return await invoke("distill_start_python_training", { config });
   }
@@ -402,6 +472,11 @@ export class ModelDistillationAPI {
     await invoke("distill_cancel_python_training", { runId });
   }
 
+  /** Restart a crashed, failed or cancelled run from its newest checkpoint */
+  static async resumeTrainingRun(runId: string): Promise<string> {
+    return await invoke("distill_resume_training_run", { runId });
+  }
+
   // -------------------------------------------------------------------------
   // Model Versions (Flow E)
   // -------------------------------------------------------------------------
@@ -504,6 +579,17 @@ export class ModelDistillationAPI {
     return await invoke("distill_import_dataset_jsonl", { input });
   }
 
+  /** One item per QA checkpoint with test cases, for a QA test generation student */
+  static async buildDatasetFromQaSessions(input: {
+    datasetId?: string;
+    name: string;
+    description?: string;
+    sessionIds: string[];
+    outputLanguage?: string;
+  }): Promise<QaSessionDatasetResult> {
+    return await invoke("distill_build_dataset_from_qa_sessions", { input });
+  }
+
   // -------------------------------------------------------------------------
   // Soft Labels (Phase 1: Data Preparation)
   // -------------------------------------------------------------------------
@@ -542,11 +628,18 @@ export {
   type Correction,
   type CorrectionWithTags,
   type Tag,
+  type ReviewStatus,
+  type ReviewCounts,
+  type ReviewSummary,
+  type BulkApproveResult,
   type TrainingRun,
   type TrainingLog,
+  type ResourceSample,
+  type GpuResourceSample,
   type ModelVersion,
   type EvaluationMetric,
   type Dataset,
+  type QaSessionDatasetResult,
   type RunArtifact,
   type SoftLabel,
   type SoftLabelType,
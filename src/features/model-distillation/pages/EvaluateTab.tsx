@@ -44,6 +44,7 @@ import {
   type ModelVersion,
 } from "../api";
 import type { EvaluationMetric } from "../types";
+import { formatAppError } from "../../../utils/appError";
 
 function downloadBlob(blob: Blob, fileName: string) {
   const url = URL.createObjectURL(blob);
@@ -314,7 +315,7 @@ export default function EvaluateTab() {
       setActiveEvalId(evalId);
     } catch (err) {
       setEvalStatus("error");
-      setEvalError(err instanceof Error ? err.message : String(err));
+      setEvalError(formatAppError(err));
     }
   };
 
@@ -339,7 +340,7 @@ export default function EvaluateTab() {
       setDatasetDescription("");
       await loadDatasets();
     } catch (err) {
-      setEvalError(err instanceof Error ? err.message : String(err));
+      setEvalError(formatAppError(err));
     } finally {
       setDatasetImporting(false);
     }
@@ -30,6 +30,7 @@ import {
   type PromotionResult,
   type RollbackResult,
 } from "../api";
+import { formatAppError } from "../../../utils/appError";
 
 interface ReportFormat {
   id: string;
@@ -183,7 +184,7 @@ export default function ExportTab() {
         await loadVersions(selectedModelId);
       }
     } catch (err) {
-      setPromotionError(err instanceof Error ? err.message : String(err));
+      setPromotionError(formatAppError(err));
     } finally {
       setPromotionInProgress(false);
     }
@@ -205,7 +206,7 @@ export default function ExportTab() {
       await loadActiveVersion(selectedModelId);
       await loadVersions(selectedModelId);
     } catch (err) {
-      setRollbackError(err instanceof Error ? err.message : String(err));
+      setRollbackError(formatAppError(err));
     } finally {
       setRollbackInProgress(false);
     }
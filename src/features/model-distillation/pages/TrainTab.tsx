@@ -11,6 +11,7 @@ import {
   Zap,
   Terminal,
   AlertCircle,
+  RotateCcw,
 } from "lucide-react";
 import {
   Card,
@@ -31,6 +32,7 @@ import {
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import { ModelDistillationAPI, type DistillTrainConfig } from "../api";
 import { useModelDistillationStore } from "../../../store/modelDistillation";
+import { formatAppError } from "../../../utils/appError";
 
 interface TrainingLog {
   timestamp: string;
@@ -55,7 +57,7 @@ interface DistillPythonMessage {
   payload: Record<string, unknown>;
 }
 
-type TrainingStatus = "idle" | "queued" | "running" | "paused" | "completed" | "failed" | "cancelled";
+type TrainingStatus = "idle" | "queued" | "running" | "paused" | "completed" | "failed" | "cancelled" | "crashed";
 
 export default function TrainTab() {
   const store = useModelDistillationStore();
@@ -70,6 +72,7 @@ export default function TrainTab() {
   const [ramUsage, setRamUsage] = useState(0);
   const [gpuUsage, setGpuUsage] = useState<number | null>(null);
   const [error, setError] = useState<string | null>(null);
+  const [resourceWarning, setResourceWarning] = useState<string | null>(null);
   const [startTime, setStartTime] = useState<number | null>(null);
 
   const logsEndRef = useRef<HTMLDivElement>(null);
@@ -143,6 +146,8 @@ export default function TrainTab() {
           if (message === "trainer started") {
             setStatus("running");
             setStartTime(Date.now());
+          } else if (message === "trainer resumed") {
+            setStatus("running");
           } else if (message === "trainer completed") {
             setStatus("completed");
           } else if (message === "trainer cancelled") {
@@ -151,6 +156,9 @@ export default function TrainTab() {
             const cancelled = payload.cancelled as boolean;
             if (cancelled) {
               setStatus("cancelled");
+            } else if (payload.status === "crashed") {
+              setStatus("crashed");
+              setError((payload.failure_reason as string) ?? null);
             }
           }
         } else if (level === "error") {
@@ -159,10 +167,25 @@ export default function TrainTab() {
         } else if (level === "warn") {
           // Log warnings but don't change status
           console.warn("[Trainer]", message);
+          if (payload.resource_warning) {
+            setResourceWarning(message);
+          }
         }
         break;
       }
 
+      case "resources": {
+        // Host samples taken by the app; they keep coming when the trainer lacks psutil
+        const cpu = payload.cpu_percent as number | undefined;
+        const ramBytes = payload.ram_rss_bytes as number | undefined;
+        const gpu = payload.gpu_util_percent as number | undefined;
+
+        if (cpu != null) setCpuUsage(cpu);
+        if (ramBytes != null) setRamUsage(ramBytes / (1024 * 1024 * 1024));
+        if (gpu != null) setGpuUsage(gpu);
+        break;
+      }
+
       case "progress": {
         const epoch = (payload.epoch as number) ?? 0;
         const step = (payload.step as number) ?? 0;
@@ -294,6 +317,7 @@ export default function TrainTab() {
 
     try {
       setError(null);
+      setResourceWarning(null);
       setStatus("queued");
       setLogs([]);
       setCurrentStep(0);
@@ -320,14 +344,7 @@ export default function TrainTab() {
     } catch (err) {
       setStatus("failed");
       // Handle Tauri invoke errors which come as objects
-      let errorMessage: string;
-      if (err instanceof Error) {
-        errorMessage = err.message;
-      } else if (typeof err === "object" && err !== null) {
-        errorMessage = JSON.stringify(err);
-      } else {
-        errorMessage = String(err);
-      }
+      const errorMessage = formatAppError(err);
       console.error("Start training error:", err);
       setError(errorMessage);
     }
@@ -341,19 +358,28 @@ export default function TrainTab() {
       setStatus("cancelled");
     } catch (err) {
       // Handle Tauri invoke errors which come as objects
-      let errorMessage: string;
-      if (err instanceof Error) {
-        errorMessage = err.message;
-      } else if (typeof err === "object" && err !== null) {
-        errorMessage = JSON.stringify(err);
-      } else {
-        errorMessage = String(err);
-      }
+      const errorMessage = formatAppError(err);
       console.error("Cancel error:", err);
       setError(errorMessage);
     }
   };
 
+  const handleResume = async () => {
+    if (!store.activeRunId) return;
+
+    try {
+      setStatus("queued");
+      setError(null);
+      setResourceWarning(null);
+      await ModelDistillationAPI.resumeTrainingRun(store.activeRunId);
+    } catch (err) {
+      setStatus("failed");
+      const errorMessage = formatAppError(err);
+      console.error("Resume training error:", err);
+      setError(errorMessage);
+    }
+  };
+
   const handleReset = () => {
     setStatus("idle");
     setLogs([]);
@@ -365,6 +391,7 @@ export default function TrainTab() {
     setRamUsage(0);
     setGpuUsage(null);
     setError(null);
+    setResourceWarning(null);
     setStartTime(null);
   };
 
@@ -416,11 +443,23 @@ export default function TrainTab() {
           </motion.div>
         )}
 
+        {resourceWarning && (
+          <motion.div
+            initial={{ opacity: 0, y: -10 }}
+            animate={{ opacity: 1, y: 0 }}
+            className="mb-4">
+            <InfoBox type="warning" icon={AlertCircle}>
+              <div className="font-semibold mb-1">Resource Warning</div>
+              <div>{resourceWarning}</div>
+            </InfoBox>
+          </motion.div>
+        )}
+
         <div className="grid grid-cols-4 gap-4">
           <MetricCard
             icon={Activity}
             label="Status"
-            value={status === "running" ? "Running" : status === "queued" ? "Starting" : status === "completed" ? "Done" : status === "failed" ? "Failed" : status === "cancelled" ? "Cancelled" : "Idle"}
+            value={status === "running" ? "Running" : status === "queued" ? "Starting" : status === "completed" ? "Done" : status === "failed" ? "Failed" : status === "cancelled" ? "Cancelled" : status === "crashed" ? "Crashed" : "Idle"}
             delay={0}
           />
           <MetricCard
@@ -458,7 +497,7 @@ export default function TrainTab() {
               </div>
 
               <div className="flex gap-2">
-                {status === "idle" || status === "completed" || status === "failed" || status === "cancelled" ? (
+                {status === "idle" || status === "completed" || status === "failed" || status === "cancelled" || status === "crashed" ? (
                   <Button
                     variant="primary"
                     size="md"
@@ -473,7 +512,13 @@ export default function TrainTab() {
                   </Button>
                 ) : null}
 
-                {(status === "completed" || status === "failed" || status === "cancelled") && (
+                {(status === "failed" || status === "cancelled" || status === "crashed") && (
+                  <Button variant="default" size="md" onClick={handleResume} icon={RotateCcw}>
+                    Resume
+                  </Button>
+                )}
+
+                {(status === "completed" || status === "failed" || status === "cancelled" || status === "crashed") && (
                   <Button variant="ghost" size="md" onClick={handleReset}>
                     Reset
                   </Button>
@@ -628,7 +673,7 @@ export default function TrainTab() {
               <div className={`p-3 rounded-xl transition-colors ${
                 status === "running" || status === "queued" ? 'bg-blue-500/10 text-blue-400' :
                 status === "completed" ? 'bg-green-500/10 text-green-400' :
-                status === "failed" ? 'bg-red-500/10 text-red-400' :
+                status === "failed" || status === "crashed" ? 'bg-red-500/10 text-red-400' :
                 status === "cancelled" ? 'bg-orange-500/10 text-orange-400' : 
                 'bg-app-card text-app-subtext'
               }`}>
@@ -636,7 +681,7 @@ export default function TrainTab() {
                   <Loader2 className="w-6 h-6 animate-spin" />
                 ) : status === "completed" ? (
                   <CheckCircle className="w-6 h-6" />
-                ) : status === "failed" ? (
+                ) : status === "failed" || status === "crashed" ? (
                   <AlertCircle className="w-6 h-6" />
                 ) : status === "cancelled" ? (
                   <Pause className="w-6 h-6" />
@@ -650,6 +695,7 @@ export default function TrainTab() {
                    status === "queued" ? "Starting Training..." :
                    status === "completed" ? "Training Completed" :
                    status === "failed" ? "Training Failed" :
+                   status === "crashed" ? "Training Crashed" :
                    status === "cancelled" ? "Training Cancelled" : "Ready to Start"}
                 </div>
                 <div className="text-xs text-app-subtext">
@@ -657,6 +703,7 @@ export default function TrainTab() {
                    status === "queued" ? "Initializing Python trainer..." :
                    status === "completed" ? "Training completed successfully! Check the Export tab for artifacts." :
                    status === "failed" ? error || "An error occurred during training." :
+                   status === "crashed" ? `${error || "The trainer stopped unexpectedly."} Resume continues from the last checkpoint.` :
                    status === "cancelled" ? "Training was cancelled." :
                    store.activeRunId ? "Click Start to begin training." : "Create a training run in Setup first."}
                 </div>
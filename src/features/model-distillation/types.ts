@@ -3,6 +3,25 @@ export interface Tag {
   name: string;
 }
 
+// Only approved corrections and soft labels can go into datasets and runs
+export type ReviewStatus = "pending" | "approved" | "rejected";
+
+export interface ReviewCounts {
+  pending: number;
+  approved: number;
+  rejected: number;
+}
+
+export interface ReviewSummary {
+  corrections: ReviewCounts;
+  softLabels: ReviewCounts;
+}
+
+export interface BulkApproveResult {
+  correctionIds: string[];
+  softLabelIds: string[];
+}
+
 export interface Correction {
   correctionId: string;
   prompt: string;
@@ -12,6 +31,9 @@ export interface Correction {
   relevanceRating?: number;
   safetyRating?: number;
   domainNotes?: string;
+  reviewStatus: ReviewStatus;
+  reviewNote?: string;
+  reviewedAt?: string;
   createdAt?: string;
 }
 
@@ -25,7 +47,7 @@ export interface TrainingRun {
   baseVersionId?: string;
   teacherModelId?: string;
   method: "fine_tune" | "knowledge_distillation" | "hybrid";
-  status: "queued" | "running" | "completed" | "failed" | "cancelled" | "rolled_back";
+  status: "queued" | "running" | "completed" | "failed" | "cancelled" | "crashed" | "rolled_back";
   startTime: string;
   endTime?: string;
   hyperparams: {
@@ -76,6 +98,31 @@ export interface TrainingLog {
   timestamp: string;
 }
 
+export interface GpuResourceSample {
+  index: number;
+  name: string;
+  utilPercent?: number;
+  memUsedBytes?: number;
+  memTotalBytes?: number;
+}
+
+/** Host resources sampled while a run's trainer was active */
+export interface ResourceSample {
+  sampleId: number;
+  runId: string;
+  cpuPercent?: number;
+  ramUsedBytes?: number;
+  ramTotalBytes?: number;
+  processRssBytes?: number;
+  gpuUtilPercent?: number;
+  gpuMemUsedBytes?: number;
+  gpuMemTotalBytes?: number;
+  gpus: GpuResourceSample[];
+  /** Set when the sample found RAM or GPU memory exhausted */
+  warning?: string;
+  sampledAt: string;
+}
+
 export interface Dataset {
   datasetId: string;
   name: string;
@@ -84,6 +131,14 @@ export interface Dataset {
   createdAt: string;
 }
 
+/** Dataset built from QA session checkpoints and their test cases */
+export interface QaSessionDatasetResult {
+  dataset: Dataset;
+  itemCount: number;
+  testCaseCount: number;
+  skippedSessionIds: string[];
+}
+
 export interface RunArtifact {
   artifactId: string;
   runId: string;
@@ -109,6 +164,9 @@ export interface SoftLabel {
   softLabelType: SoftLabelType;
   temperature: number;
   metadataJson?: string;
+  reviewStatus: ReviewStatus;
+  reviewNote?: string;
+  reviewedAt?: string;
   createdAt?: string;
 }
 
@@ -57,11 +57,13 @@ export default function EventTimeline({
                   "submit",
                   "focus",
                   "blur",
+                  "keydown",
+                  "scroll",
                   "navigation",
                   "api_request",
                   "api_response",
                 ].includes(event.event_type) &&
-                (event.event_type === "navigation" ||
+                (["navigation", "keydown", "scroll"].includes(event.event_type) ||
                   Boolean(event.selector) ||
                   event.event_type.startsWith("api_"));
               return (
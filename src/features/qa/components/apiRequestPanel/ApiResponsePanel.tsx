@@ -38,6 +38,23 @@ export function ApiResponsePanel({
             </div>
           </div>
 
+          {response.assertionResults.length > 0 && (
+            <div>
+              <div className="text-[10px] text-gray-500 mb-1">Assertions</div>
+              <div className="space-y-1 text-[10px]">
+                {response.assertionResults.map((result, index) => (
+                  <div
+                    key={`${result.description}-${index}`}
+                    className={result.passed ? "text-emerald-300" : "text-red-200"}
+                  >
+                    {result.passed ? "PASS" : "FAIL"} {result.description}
+                    {!result.passed && ` (actual: ${result.actual ?? "missing"})`}
+                  </div>
+                ))}
+              </div>
+            </div>
+          )}
+
           <div>
             <div className="text-[10px] text-gray-500 mb-1">Response Headers</div>
             <div className="max-h-[160px] overflow-y-auto space-y-1 text-[10px]">
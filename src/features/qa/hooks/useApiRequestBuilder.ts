@@ -1,8 +1,15 @@
 import { useEffect, useMemo, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import type { QaSession } from "../../../types/qa/types";
+import type {
+  QaApiAssertion,
+  QaApiAssertionResult,
+  QaEndpointAssertions,
+  QaSession,
+  QaTestCase,
+} from "../../../types/qa/types";
 import { useQaSessionStore } from "../../../store/qaSession";
 import { parseCurlCommand } from "../utils/curlParser";
+import { findEndpointAssertions } from "../utils/apiAssertions";
 
 export const API_METHODS = [
   "GET",
@@ -51,6 +58,7 @@ export type ApiResponsePayload = {
   headers: ApiKeyValueRow[];
   body: string;
   contentType: string | null;
+  assertionResults: QaApiAssertionResult[];
 };
 
 type AuthHeader = {
@@ -127,6 +135,11 @@ export type ApiRequestBuilderState = {
   apiResponse: ApiResponsePayload | null;
   apiResponseError: string | null;
   apiSending: boolean;
+  apiAssertions: QaApiAssertion[];
+  /** OAuth2 auth profile signing the request, from the qaAuthProfiles settings */
+  apiAuthProfileId: string | null;
+  /** Set once a request was sent; it can then be saved as an API test */
+  canSaveApiTest: boolean;
   supportsBody: boolean;
   requestUrlPreview: string;
   formattedResponseBody: string;
@@ -143,6 +156,11 @@ export type ApiRequestBuilderActions = {
   setApiHeaders: (updater: (rows: ApiKeyValueRow[]) => ApiKeyValueRow[]) => void;
   setApiParams: (updater: (rows: ApiKeyValueRow[]) => ApiKeyValueRow[]) => void;
   handleSendApiRequest: () => Promise<void>;
+  setApiAuthProfileId: (value: string | null) => void;
+  setApiAssertions: (updater: (items: QaApiAssertion[]) => QaApiAssertion[]) => void;
+  saveApiTest: (title: string) => Promise<QaTestCase | null>;
+  /** Fill the assertions from earlier responses of this endpoint in the session */
+  suggestApiAssertions: () => Promise<void>;
 
   resetApiState: () => void;
   importCurl: (command: string) => boolean;
@@ -178,6 +196,9 @@ export function useApiRequestBuilder({
   const [apiResponse, setApiResponse] = useState<ApiResponsePayload | null>(null);
   const [apiResponseError, setApiResponseError] = useState<string | null>(null);
   const [apiSending, setApiSending] = useState(false);
+  const [apiAssertions, setApiAssertions] = useState<QaApiAssertion[]>([]);
+  const [apiAuthProfileId, setApiAuthProfileId] = useState<string | null>(null);
+  const [lastRequest, setLastRequest] = useState<Record<string, unknown> | null>(null);
   const { activeRunId, setActiveRunId } = useQaSessionStore();
 
   const supportsBody = BODY_METHODS.has(apiMethod);
@@ -250,6 +271,8 @@ export function useApiRequestBuilder({
     setApiActiveTab("params");
     setApiResponse(null);
     setApiResponseError(null);
+    setApiAssertions([]);
+    setLastRequest(null);
   };
 
   useEffect(() => {
@@ -341,24 +364,28 @@ export function useApiRequestBuilder({
         enabled,
       }));
 
+      const request = {
+        method: apiMethod,
+        url: resolveApiUrl(),
+        headers: requestHeaders,
+        queryParams: requestParams,
+        bodyType: supportsBody ? apiBodyTab : null,
+        bodyJson: supportsBody && apiBodyTab === "json" ? apiBodyJson : null,
+        formData: supportsBody && apiBodyTab === "form" ? formDataPayload : [],
+        source: "manual",
+        authProfileId: apiAuthProfileId,
+      };
       const response = await invoke<{
         status: number;
         durationMs: number;
         headers: ApiKeyValueRow[];
         body: string;
         contentType: string | null;
+        assertionResults: QaApiAssertionResult[];
       }>("qa_execute_api_request", {
-        request: {
-          method: apiMethod,
-          url: resolveApiUrl(),
-          headers: requestHeaders,
-          queryParams: requestParams,
-          bodyType: supportsBody ? apiBodyTab : null,
-          bodyJson: supportsBody && apiBodyTab === "json" ? apiBodyJson : null,
-          formData: supportsBody && apiBodyTab === "form" ? formDataPayload : [],
-          source: "manual",
-        },
+        request: { ...request, assertions: apiAssertions },
       });
+      setLastRequest(request);
 
       setApiResponse({
         status: response.status,
@@ -372,6 +399,7 @@ export function useApiRequestBuilder({
         ),
         body: response.body,
         contentType: response.contentType,
+        assertionResults: response.assertionResults,
       });
       setApiActiveTab("response");
 
@@ -408,6 +436,59 @@ export function useApiRequestBuilder({
     }
   };
 
+  const saveApiTest = async (title: string) => {
+    if (!session || !lastRequest) return null;
+    if (!apiAssertions.length) {
+      addToast("Add at least one assertion first", "error");
+      return null;
+    }
+    try {
+      const testCase = await invoke<QaTestCase>("qa_save_api_test", {
+        sessionId: session.id,
+        title,
+        request: { ...lastRequest, assertions: apiAssertions },
+        checkpointId: null,
+        priority: null,
+      });
+      addToast("Saved as API test", "success");
+      return testCase;
+    } catch (err) {
+      console.error(err);
+      addToast("Failed to save API test", "error");
+      return null;
+    }
+  };
+
+  const suggestApiAssertions = async () => {
+    if (!session) return;
+    try {
+      const suggestions = await invoke<QaEndpointAssertions[]>(
+        "qa_suggest_api_assertions",
+        { sessionId: session.id, checkpointId: null }
+      );
+      const match = findEndpointAssertions(
+        suggestions,
+        apiMethod,
+        requestUrlPreview
+      );
+      if (!match || match.assertions.length === 0) {
+        addToast(
+          "No suggestions yet; send this request at least twice first",
+          "info"
+        );
+        return;
+      }
+      setApiAssertions(match.assertions);
+      addToast(
+        `Suggested ${match.assertions.length} assertions from ${match.callCount} responses`,
+        "success"
+      );
+    } catch (err) {
+      console.error(err);
+      addToast("Failed to suggest assertions", "error");
+    }
+  };
+
   const importCurl = (command: string) => {
     const parsed = parseCurlCommand(command);
     if (!parsed) return false;
@@ -454,6 +535,9 @@ export function useApiRequestBuilder({
       apiResponse,
       apiResponseError,
       apiSending,
+      apiAssertions,
+      apiAuthProfileId,
+      canSaveApiTest: lastRequest !== null,
       supportsBody,
       requestUrlPreview,
       formattedResponseBody,
@@ -469,6 +553,10 @@ export function useApiRequestBuilder({
       setApiHeaders,
       setApiParams,
       handleSendApiRequest,
+      setApiAuthProfileId,
+      setApiAssertions,
+      saveApiTest,
+      suggestApiAssertions,
       resetApiState,
       importCurl,
     },
@@ -7,6 +7,8 @@ import { isTauri } from "../../../utils/tauri";
 export type ScreenshotItem = {
   id: string;
   src: string;
+  /** Original capture when `src` is the copy with the click highlighted */
+  originalSrc?: string;
   ts: number;
   eventType?: string;
   nodeName?: string;
@@ -40,9 +42,13 @@ export default function useQaScreenshots(sessionId: string) {
           const src = resolveScreenshotSrc(e.screenshot_path, isTauriApp);
           console.log("[useQaScreenshots] Event screenshot processed:", e.id, "path:", e.screenshot_path, "src:", src);
           if (src) {
+              const annotatedSrc = e.annotated_screenshot_path
+                ? resolveScreenshotSrc(e.annotated_screenshot_path, isTauriApp)
+                : null;
               items.push({
                   id: e.id,
-                  src,
+                  src: annotatedSrc || src,
+                  originalSrc: annotatedSrc ? src : undefined,
                   ts: e.ts,
                   eventType: e.event_type,
                   nodeName: (e.element_text || e.selector) ?? undefined
@@ -0,0 +1,35 @@
+import { useCallback, useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import type { NodeRuntimeProgress, RecorderEnvironment } from "../../../types/qa/types";
+
+/**
+ * Node.js / Playwright runtime behind the browser recorder: install progress
+ * streamed while a recording or replay starts, and an on-demand check.
+ */
+export default function useRecorderRuntime({ isTauriApp }: { isTauriApp: boolean }) {
+  const [runtimeProgress, setRuntimeProgress] = useState<NodeRuntimeProgress | null>(null);
+
+  useEffect(() => {
+    if (!isTauriApp) return;
+    let unlisten: (() => void) | null = null;
+    const start = async () => {
+      unlisten = await listen<NodeRuntimeProgress>("qa-runtime-progress", (event) => {
+        setRuntimeProgress(event.payload.stage === "ready" ? null : event.payload);
+      });
+    };
+    void start();
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, [isTauriApp]);
+
+  const checkEnvironment = useCallback(async () => {
+    if (!isTauriApp) return null;
+    return await invoke<RecorderEnvironment>("qa_check_recorder_environment");
+  }, [isTauriApp]);
+
+  return { runtimeProgress, checkEnvironment };
+}
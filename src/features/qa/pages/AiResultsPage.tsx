@@ -14,8 +14,12 @@ import {
   Fingerprint,
   Layers,
   Clock,
+  FileDown,
+  Upload,
+  Braces,
 } from "lucide-react";
 import { invoke } from "@tauri-apps/api/core";
+import { open } from "@tauri-apps/plugin-dialog";
 import { useToastStore } from "../../../store/toast";
 import {
   PROVIDER_MODEL_OPTIONS,
@@ -23,13 +27,22 @@ import {
 } from "../../../store/settings";
 import { useLlmConfigBuilder } from "../../../hooks/useLlmConfig";
 import { useModelsQuery } from "../../../hooks/useLlmApi";
+import { formatAppError } from "../../../utils/appError";
 import { isTauri } from "../../../utils/tauri";
 import useQaSession from "../hooks/useQaSession";
+import {
+  describeAssertion,
+  parseEndpointAssertions,
+} from "../utils/apiAssertions";
 import type {
   QaCheckpoint,
   QaCheckpointSummary,
+  QaGherkinFeature,
   QaLlmRun,
   QaTestCase,
+  QaTestGenerationMode,
+  QaTestSyncReport,
+  TestManagementProvider,
 } from "../../../types/qa/types";
 import SessionDetailHeader from "../components/SessionDetailHeader";
 
@@ -234,7 +247,10 @@ export default function AiResultsPage() {
     }
   };
 
-  const handleGenerateTests = async (checkpointId: string) => {
+  const handleGenerateTests = async (
+    checkpointId: string,
+    mode: QaTestGenerationMode = "standard",
+  ) => {
     if (!sessionId) return;
     if (!canGenerate) {
       addToast("Select a valid model before generating", "error");
@@ -252,8 +268,14 @@ export default function AiResultsPage() {
         checkpointId,
         config,
         outputLanguage: aiOutputLanguage,
+        mode,
       });
-      addToast("AI test cases generated", "success");
+      addToast(
+        mode === "negative"
+          ? "Negative test cases generated"
+          : "AI test cases generated",
+        "success",
+      );
       await loadOutputs();
     } catch (err) {
       console.error(err);
@@ -263,6 +285,54 @@ export default function AiResultsPage() {
     }
   };
 
+  const handleExportGherkin = async () => {
+    if (!sessionId) return;
+    const outputDir = await open({
+      directory: true,
+      multiple: false,
+      title: "Select folder for feature files",
+    });
+    if (typeof outputDir !== "string") return;
+    setActionCheckpoint("export");
+    try {
+      const features = await invoke<QaGherkinFeature[]>("qa_export_gherkin", {
+        sessionId,
+        outputDir,
+      });
+      const scenarios = features.reduce((sum, f) => sum + f.scenarioCount, 0);
+      addToast(
+        `Exported ${features.length} feature file(s) with ${scenarios} scenario(s)`,
+        "success",
+      );
+    } catch (err) {
+      console.error(err);
+      addToast("Failed to export Gherkin features", "error");
+    } finally {
+      setActionCheckpoint(null);
+    }
+  };
+
+  const handlePushTestCases = async (provider: TestManagementProvider) => {
+    if (!sessionId) return;
+    setActionCheckpoint("push");
+    try {
+      const report = await invoke<QaTestSyncReport>("qa_push_test_cases", {
+        sessionId,
+        provider,
+      });
+      const name = provider === "xray" ? "Xray" : "TestRail";
+      addToast(
+        `${name}: ${report.created} created, ${report.skipped} already pushed, ${report.failed} failed`,
+        report.failed > 0 ? "error" : "success",
+      );
+    } catch (err) {
+      console.error(err);
+      addToast(formatAppError(err, "Failed to push test cases"), "error");
+    } finally {
+      setActionCheckpoint(null);
+    }
+  };
+
   const activeCheckpoint = checkpoints.find((c) => c.id === activeCheckpointId);
   const activeSummary = activeCheckpoint
     ? summariesByCheckpoint[activeCheckpoint.id]
@@ -368,6 +438,30 @@ export default function AiResultsPage() {
                 title="Add Checkpoint">
                 <LayoutList className="w-4 h-4" />
               </button>
+              <button
+                onClick={handleExportGherkin}
+                disabled={
+                  actionCheckpoint === "export" || testCases.length === 0
+                }
+                className="p-1.5 bg-app-panel hover:bg-app-panel/80 text-app-subtext hover:text-app-text border border-app-border rounded-md transition disabled:opacity-50"
+                title="Export Gherkin feature files">
+                <FileDown className="w-4 h-4" />
+              </button>
+              <div className="relative" title="Push test cases">
+                <Upload className="absolute left-2 top-2 w-3.5 h-3.5 text-app-subtext pointer-events-none" />
+                <select
+                  value=""
+                  onChange={(e) => {
+                    const provider = e.target.value as TestManagementProvider;
+                    if (provider) void handlePushTestCases(provider);
+                  }}
+                  disabled={actionCheckpoint === "push" || testCases.length === 0}
+                  className="appearance-none bg-app-panel border border-app-border rounded-md py-1.5 pl-7 pr-3 text-xs text-app-subtext hover:text-app-text transition outline-none disabled:opacity-50">
+                  <option value="">Push</option>
+                  <option value="xray">Xray</option>
+                  <option value="testrail">TestRail</option>
+                </select>
+              </div>
               <button
                 onClick={loadOutputs}
                 className="p-1.5 bg-app-panel hover:bg-app-panel/80 text-app-subtext hover:text-app-text border border-app-border rounded-md transition"
@@ -503,6 +597,16 @@ export default function AiResultsPage() {
                       ? "Generating..."
                       : "Generate Tests"}
                   </button>
+                  <button
+                    onClick={() =>
+                      handleGenerateTests(activeCheckpoint.id, "negative")
+                    }
+                    disabled={!!actionCheckpoint || !canGenerate}
+                    title="Invalid inputs, auth failures and boundary values for the recorded fields and API requests"
+                    className="text-xs px-3 py-2 bg-rose-500/10 hover:bg-rose-500/20 border border-rose-500/30 text-rose-400 rounded-lg transition disabled:opacity-50 flex items-center gap-2">
+                    <ShieldAlert className="w-3.5 h-3.5" />
+                    Negative Tests
+                  </button>
                 </div>
               </div>
 
@@ -708,6 +812,10 @@ function SummaryMeta({
 
 function TestCaseCard({ testCase }: { testCase: QaTestCase }) {
   const [expanded, setExpanded] = useState(false);
+  const endpointAssertions = useMemo(
+    () => parseEndpointAssertions(testCase.assertionsJson),
+    [testCase.assertionsJson]
+  );
 
   const typeConfig = useMemo(() => {
     switch (testCase.type) {
@@ -811,6 +919,41 @@ function TestCaseCard({ testCase }: { testCase: QaTestCase }) {
                 </div>
               </div>
             )}
+
+            {endpointAssertions.length > 0 && (
+              <div>
+                <div className="text-[10px] uppercase tracking-wider text-app-subtext mb-2 font-semibold flex items-center gap-2">
+                  <Braces className="w-3 h-3" /> Suggested API Assertions
+                </div>
+                <div className="space-y-2">
+                  {endpointAssertions.map((endpoint) => (
+                    <div
+                      key={`${endpoint.method} ${endpoint.path}`}
+                      className="rounded-lg border border-app-border/40 bg-black/20 p-2.5">
+                      <div className="text-xs font-mono text-app-text">
+                        {endpoint.method} {endpoint.path}
+                        <span className="ml-2 text-app-subtext/70 font-sans">
+                          from {endpoint.callCount} responses
+                        </span>
+                      </div>
+                      <ul className="mt-1.5 space-y-0.5 text-[11px] font-mono text-app-text/80">
+                        {endpoint.assertions.map((assertion, index) => (
+                          <li key={index}>{describeAssertion(assertion)}</li>
+                        ))}
+                      </ul>
+                      {endpoint.changingPaths.length > 0 && (
+                        <div className="mt-1.5 text-[11px] text-app-subtext">
+                          Changes between calls, ignore:{" "}
+                          <span className="font-mono">
+                            {endpoint.changingPaths.join(", ")}
+                          </span>
+                        </div>
+                      )}
+                    </div>
+                  ))}
+                </div>
+              </div>
+            )}
           </div>
         </div>
       )}
@@ -9,6 +9,7 @@ import {
 } from "../../../store/settings";
 import { useLlmConfigBuilder } from "../../../hooks/useLlmConfig";
 import { useModelsQuery } from "../../../hooks/useLlmApi";
+import { formatAppError } from "../../../utils/appError";
 import { isTauri } from "../../../utils/tauri";
 import { extractPreviewUrl, isValidUrl } from "../utils/eventFormatting";
 // import { resolveScreenshotSrc } from "./utils/previewCapture";
@@ -25,7 +26,13 @@ import SessionDetailHeader from "../components/SessionDetailHeader";
 import useQaEvents from "../hooks/useQaEvents";
 import useQaRunStream from "../hooks/useQaRunStream";
 import useQaSession from "../hooks/useQaSession";
-import { ExploreResult, QaEvent, QaSessionRun } from "../../../types/qa/types";
+import useRecorderRuntime from "../hooks/useRecorderRuntime";
+import {
+  ExploreResult,
+  QaActiveSession,
+  QaEvent,
+  QaSessionRun,
+} from "../../../types/qa/types";
 
 const EVENTS_POLL_INTERVAL_MS = 2000;
 
@@ -36,10 +43,12 @@ export default function SessionDetailPage() {
   const { addToast } = useToastStore();
   const {
     recordingSessionId,
+    recorderRuns,
     activeRunId,
     setActiveSessionId,
     setActiveRunId,
     setRecordingSessionId,
+    setRecorderRun,
     recordingMode,
     // recordingDelay,
     screenshotDelay,
@@ -65,6 +74,15 @@ export default function SessionDetailPage() {
       isTauriApp,
     });
 
+  const { runtimeProgress, checkEnvironment } = useRecorderRuntime({ isTauriApp });
+
+  useEffect(() => {
+    // Announce the first-use installs; their per-line output only goes to the logs
+    if (runtimeProgress?.stage === "playwright" || runtimeProgress?.stage === "browser") {
+      addToast(runtimeProgress.message, "info");
+    }
+  }, [runtimeProgress?.stage]);
+
   const {
     screenshots: allScreenshots,
     loading: screenshotsLoading,
@@ -134,7 +152,23 @@ export default function SessionDetailPage() {
 
   useEffect(() => {
     setActiveRunId(null);
-  }, [sessionId, setActiveRunId]);
+    if (!isTauriApp || !sessionId) return;
+    // Recorders keep running while other sessions are open
+    let cancelled = false;
+    invoke<QaActiveSession[]>("qa_list_active_sessions")
+      .then((active) => {
+        if (cancelled) return;
+        const runId =
+          active.find((entry) => entry.session_id === sessionId)
+            ?.recorder_run_id ?? null;
+        setRecorderRun(sessionId, runId);
+        if (runId) setActiveRunId(runId);
+      })
+      .catch(console.error);
+    return () => {
+      cancelled = true;
+    };
+  }, [sessionId, isTauriApp, setActiveRunId, setRecorderRun]);
 
   const previewUrl = useMemo(() => extractPreviewUrl(session), [session]);
   const previewUrlValid = useMemo(
@@ -142,7 +176,8 @@ export default function SessionDetailPage() {
     [isApiSession, previewUrl],
   );
 
-  const isRecording = recordingSessionId === sessionId;
+  const recorderRunId = recorderRuns[sessionId] ?? null;
+  const isRecording = recorderRunId !== null;
   const canStartRecording =
     Boolean(session) &&
     !session?.ended_at &&
@@ -245,8 +280,10 @@ export default function SessionDetailPage() {
       addToast("This session has already ended", "error");
       return;
     }
-    if (recordingSessionId && recordingSessionId !== session.id) {
-      addToast("Another QA session is recording", "error");
+    const environment = await checkEnvironment().catch(() => null);
+    const blocker = environment?.checks.find((check) => check.status === "error");
+    if (blocker) {
+      addToast(`${blocker.label}: ${blocker.summary}. ${blocker.hint ?? ""}`.trim(), "error");
       return;
     }
 
@@ -266,6 +303,7 @@ export default function SessionDetailPage() {
       setActiveRunId(run.id);
       setActiveSessionId(session.id);
       setRecordingSessionId(session.id);
+      setRecorderRun(session.id, run.id);
       await invoke("qa_start_browser_recorder", {
         sessionId: session.id,
         runId: run.id,
@@ -284,31 +322,34 @@ export default function SessionDetailPage() {
           // ignore
         }
         setActiveRunId(null);
-        setRecordingSessionId(null);
+        setRecorderRun(session.id, null);
+        if (recordingSessionId === session.id) setRecordingSessionId(null);
       }
-      addToast("Failed to start browser recorder", "error");
+      addToast(formatAppError(err, "Failed to start browser recorder"), "error");
     }
   };
 
   const handleStopRecording = async () => {
-    if (!isRecording) return;
-    setRecordingSessionId(null);
-    if (activeRunId) {
-      try {
-        await invoke("qa_stop_browser_recorder", { runId: activeRunId });
-      } catch (err) {
-        console.error(err);
-      }
-      try {
-        await invoke("qa_end_run", {
-          runId: activeRunId,
-          status: "stopped",
-        });
-      } catch (err) {
-        console.error(err);
-      }
-      setActiveRunId(null);
+    if (!isRecording || !recorderRunId) return;
+    setRecorderRun(sessionId, null);
+    if (recordingSessionId === sessionId) setRecordingSessionId(null);
+    try {
+      await invoke("qa_stop_browser_recorder", {
+        sessionId,
+        runId: recorderRunId,
+      });
+    } catch (err) {
+      console.error(err);
     }
+    try {
+      await invoke("qa_end_run", {
+        runId: recorderRunId,
+        status: "stopped",
+      });
+    } catch (err) {
+      console.error(err);
+    }
+    setActiveRunId(null);
     // Reload screenshots and events after stopping recording
     await Promise.all([reloadScreenshots(), loadEvents(true, true)]);
     addToast("QA recording stopped", "info");
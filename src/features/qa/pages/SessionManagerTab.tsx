@@ -1,14 +1,26 @@
-import { useMemo, useState } from "react";
+import { useEffect, useMemo, useState } from "react";
 import { ClipboardCheck, Save } from "lucide-react";
 import { invoke } from "@tauri-apps/api/core";
 import { Switch } from "../../../shared/components/Switch";
 import { useToastStore } from "../../../store/toast";
 import { useQaSessionStore } from "../../../store/qaSession";
 import { isTauri } from "../../../utils/tauri";
+import {
+  getActiveProjectProfile,
+  onProjectProfilePickedUp,
+} from "../../settings/api/projectProfiles";
 import { QaSession, QaSessionType } from "../../../types/qa/types";
 
 const DEFAULT_TITLE = "Untitled Session";
 
+function parseAttributeList(value: string): string[] | null {
+  const attributes = value
+    .split(",")
+    .map((attr) => attr.trim())
+    .filter(Boolean);
+  return attributes.length > 0 ? attributes : null;
+}
+
 export default function SessionManagerTab() {
   const { addToast } = useToastStore();
   const { activeSessionId, setActiveSessionId, setRecordingSessionId } =
@@ -19,10 +31,32 @@ export default function SessionManagerTab() {
   const [targetUrl, setTargetUrl] = useState("");
   const [apiBaseUrl, setApiBaseUrl] = useState("");
   const [authProfileJson, setAuthProfileJson] = useState("");
+  const [selectorAttributes, setSelectorAttributes] = useState("");
   const [isPositiveCase, setIsPositiveCase] = useState(true);
   const [activeSession, setActiveSession] = useState<QaSession | null>(null);
   const [isStarting, setIsStarting] = useState(false);
 
+  // Start from the URLs of the active project profile
+  useEffect(() => {
+    if (!isTauri()) return;
+    let disposed = false;
+    getActiveProjectProfile()
+      .then((profile) => {
+        if (disposed || !profile) return;
+        if (profile.qaTargetUrl) setTargetUrl((current) => current || profile.qaTargetUrl!);
+        if (profile.qaApiBaseUrl) setApiBaseUrl((current) => current || profile.qaApiBaseUrl!);
+      })
+      .catch(console.error);
+    const unlisten = onProjectProfilePickedUp((profile) => {
+      if (profile.qaTargetUrl) setTargetUrl(profile.qaTargetUrl);
+      if (profile.qaApiBaseUrl) setApiBaseUrl(profile.qaApiBaseUrl);
+    });
+    return () => {
+      disposed = true;
+      unlisten();
+    };
+  }, []);
+
   const canSave =
     goal.trim().length > 0 &&
     !isStarting &&
@@ -59,6 +93,8 @@ export default function SessionManagerTab() {
         apiBaseUrl: sessionType === "api" ? apiBaseUrl.trim() : null,
         authProfileJson: sessionType === "api" ? authProfileJson.trim() : null,
         notes: null,
+        selectorAttributes:
+          sessionType === "browser" ? parseAttributeList(selectorAttributes) : null,
       });
       setActiveSessionId(session.id);
       setRecordingSessionId(null);
@@ -131,16 +167,32 @@ export default function SessionManagerTab() {
               </div>
             </div>
             {sessionType === "browser" ? (
-              <div>
-                <label className="text-[10px] text-gray-500 block mb-1">
-                  Target URL (optional)
-                </label>
-                <input
-                  className="w-full bg-[#181818] border border-app-border rounded p-2 px-3 text-xs outline-none focus:border-gray-500 transition"
-                  placeholder="https://app.example.com"
-                  value={targetUrl}
-                  onChange={(e) => setTargetUrl(e.currentTarget.value)}
-                />
+              <div className="space-y-3">
+                <div>
+                  <label className="text-[10px] text-gray-500 block mb-1">
+                    Target URL (optional)
+                  </label>
+                  <input
+                    className="w-full bg-[#181818] border border-app-border rounded p-2 px-3 text-xs outline-none focus:border-gray-500 transition"
+                    placeholder="https://app.example.com"
+                    value={targetUrl}
+                    onChange={(e) => setTargetUrl(e.currentTarget.value)}
+                  />
+                </div>
+                <div>
+                  <label className="text-[10px] text-gray-500 block mb-1">
+                    Selector attributes (optional)
+                  </label>
+                  <input
+                    className="w-full bg-[#181818] border border-app-border rounded p-2 px-3 text-xs outline-none focus:border-gray-500 transition"
+                    placeholder="data-qa, formcontrolname, id"
+                    value={selectorAttributes}
+                    onChange={(e) => setSelectorAttributes(e.currentTarget.value)}
+                  />
+                  <div className="mt-1 text-[10px] text-gray-500">
+                    In priority order; empty uses the QA selector settings.
+                  </div>
+                </div>
               </div>
             ) : (
               <div className="space-y-3">
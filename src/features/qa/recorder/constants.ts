@@ -1,2 +1,24 @@
 export const INPUT_DEBOUNCE_MS = 350;
 export const MAX_TEXT_LENGTH = 160;
+export const SCROLL_DEBOUNCE_MS = 250;
+
+/** Keys recorded as steps; typed characters reach replay through input events */
+export const RECORDED_KEYS = [
+  "Enter",
+  "Escape",
+  "Tab",
+  "ArrowUp",
+  "ArrowDown",
+  "ArrowLeft",
+  "ArrowRight",
+];
+
+/** Attributes selectors are built from when the session has no list of its own */
+export const DEFAULT_SELECTOR_ATTRIBUTES = [
+  "data-testid",
+  "data-purpose",
+  "id",
+  "name",
+  "aria-label",
+  "role",
+];
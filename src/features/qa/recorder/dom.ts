@@ -1,23 +1,21 @@
-import { MAX_TEXT_LENGTH } from "./constants";
+import { DEFAULT_SELECTOR_ATTRIBUTES, MAX_TEXT_LENGTH } from "./constants";
 import type { QaEventPayload } from "./types";
 
-export function buildSelector(element: Element): string | undefined {
-  const prioritized = [
-    "data-testid",
-    "data-purpose",
-    "id",
-    "name",
-    "aria-label",
-    "role",
-  ];
-
-  for (const attr of prioritized) {
+/**
+ * Selector from the first of `attributes` set on the element, or its
+ * position under the body when none is.
+ */
+export function buildSelector(
+  element: Element,
+  attributes: readonly string[] = DEFAULT_SELECTOR_ATTRIBUTES,
+): string | undefined {
+  for (const attr of attributes) {
     const value = element.getAttribute(attr);
     if (!value) continue;
     if (attr === "id") {
       return `#${escapeSelector(value)}`;
     }
-    return `${element.tagName.toLowerCase()}[${attr}="${escapeSelector(value)}"]`;
+    return `${element.tagName.toLowerCase()}[${escapeSelector(attr)}="${escapeSelector(value)}"]`;
   }
 
   const path: string[] = [];
@@ -46,6 +44,51 @@ function nthOfType(element: Element): number {
   return index;
 }
 
+/**
+ * Element an event happened on. Document listeners see events from inside a
+ * shadow root retargeted to its host; the composed path starts at the real one.
+ */
+export function eventTarget(event: Event): Element | null {
+  const path = event.composedPath();
+  const target = path.length > 0 ? path[0] : event.target;
+  return target instanceof Element ? target : null;
+}
+
+/**
+ * Selectors of the shadow hosts around `element`, outermost first, for the
+ * event metadata. The event selector resolves inside the last one.
+ */
+export function elementScope(
+  element: Element,
+  attributes: readonly string[] = DEFAULT_SELECTOR_ATTRIBUTES,
+): { shadowPath?: string[] } {
+  const shadowPath: string[] = [];
+  let root = element.getRootNode();
+  while (root instanceof ShadowRoot) {
+    shadowPath.unshift(
+      buildSelector(root.host, attributes) ?? root.host.tagName.toLowerCase(),
+    );
+    root = root.host.getRootNode();
+  }
+  return shadowPath.length > 0 ? { shadowPath } : {};
+}
+
+/**
+ * Which of `attributes` the element has set, for the event metadata. Replay
+ * tries them in priority order when the selector matches nothing.
+ */
+export function selectorAttributes(
+  element: Element,
+  attributes: readonly string[] = DEFAULT_SELECTOR_ATTRIBUTES,
+): { attributes?: Record<string, string> } {
+  const values: Record<string, string> = {};
+  for (const attr of attributes) {
+    const value = element.getAttribute(attr);
+    if (value) values[attr] = value;
+  }
+  return Object.keys(values).length > 0 ? { attributes: values } : {};
+}
+
 export function getElementText(element: Element): string | undefined {
   if (element instanceof HTMLInputElement || element instanceof HTMLTextAreaElement) {
     return normalizeText(
@@ -90,6 +133,30 @@ export function maskValue(element: Element, value?: string): string | undefined
   return value;
 }
 
+/** Names, sizes and types of the chosen files; their contents are never read */
+export function describeFiles(input: HTMLInputElement) {
+  const files = Array.from(input.files ?? []).map((file) => ({
+    name: file.name,
+    size: file.size,
+    type: file.type || undefined,
+  }));
+  return {
+    value: files.map((file) => file.name).join(", ") || undefined,
+    files,
+  };
+}
+
+/** Modifier keys held with a recorded key, in Playwright's `Shift+Tab` form */
+export function keyCombo(event: KeyboardEvent): string {
+  const modifiers = [
+    event.ctrlKey && "Control",
+    event.altKey && "Alt",
+    event.metaKey && "Meta",
+    event.shiftKey && "Shift",
+  ].filter(Boolean);
+  return [...modifiers, event.key].join("+");
+}
+
 function normalizeText(value?: string | null): string | undefined {
   if (!value) return undefined;
   const trimmed = value.trim();
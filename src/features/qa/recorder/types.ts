@@ -9,3 +9,11 @@ export interface QaEventPayload {
   origin?: string;
   recordingMode?: string;
 }
+
+/** Fetch/XHR call captured by the proxy-injected recorder script */
+export interface QaNetworkPayload {
+  method: string;
+  url: string;
+  status?: number;
+  timingMs?: number;
+}
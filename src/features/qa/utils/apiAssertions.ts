@@ -0,0 +1,58 @@
+import type {
+  QaApiAssertion,
+  QaEndpointAssertions,
+} from "../../../types/qa/types";
+
+/** e.g. `status = 200` or `$.id is number`, as the backend describes them */
+export function describeAssertion(assertion: QaApiAssertion): string {
+  switch (assertion.kind) {
+    case "status":
+      return `status = ${assertion.equals}`;
+    case "header":
+      if (assertion.equals != null) {
+        return `header ${assertion.name} = ${assertion.equals}`;
+      }
+      if (assertion.contains != null) {
+        return `header ${assertion.name} contains ${assertion.contains}`;
+      }
+      return `header ${assertion.name} is present`;
+    case "jsonPath":
+      if (assertion.equals !== undefined && assertion.equals !== null) {
+        return `${assertion.path} = ${JSON.stringify(assertion.equals)}`;
+      }
+      if (assertion.type) {
+        return `${assertion.path} is ${assertion.type}`;
+      }
+      return `${assertion.path} exists`;
+  }
+}
+
+export function parseEndpointAssertions(
+  json?: string | null
+): QaEndpointAssertions[] {
+  if (!json) return [];
+  try {
+    const parsed = JSON.parse(json);
+    return Array.isArray(parsed) ? (parsed as QaEndpointAssertions[]) : [];
+  } catch {
+    return [];
+  }
+}
+
+/** The suggestion for the request's method and URL path, if any */
+export function findEndpointAssertions(
+  suggestions: QaEndpointAssertions[],
+  method: string,
+  url: string
+): QaEndpointAssertions | undefined {
+  let path: string;
+  try {
+    path = new URL(url).pathname;
+  } catch {
+    return undefined;
+  }
+  return suggestions.find(
+    (suggestion) =>
+      suggestion.method === method.toUpperCase() && suggestion.path === path
+  );
+}
@@ -1,6 +1,16 @@
+import {
+  DEFAULT_API_BASE_URL,
+  getCachedHttpServerInfo,
+  getHttpServerInfo,
+} from '../../../shared/api/httpServer';
+
+// Warm the cache so proxy URLs use the configured port and token
+void getHttpServerInfo();
+
 export function getProxiedPreviewUrl(
   previewUrl: string | null,
-  previewUrlValid: boolean
+  previewUrlValid: boolean,
+  sessionId?: string | null
 ): string | null {
   if (!previewUrl || !previewUrlValid) return null;
 
@@ -17,9 +27,13 @@ export function getProxiedPreviewUrl(
     console.log(
       `[QA Session] Cross-origin detected (${previewOrigin} !== ${currentOrigin}), using proxy`
     );
-    return `http://localhost:3001/api/qa/proxy?url=${encodeURIComponent(
-      previewUrl
-    )}`;
+    const server = getCachedHttpServerInfo();
+    const params = new URLSearchParams({ url: previewUrl });
+    // Iframes cannot send an Authorization header
+    if (server?.proxyToken) params.set('access_token', server.proxyToken);
+    // The proxy builds selectors from the session's attributes
+    if (sessionId) params.set('session_id', sessionId);
+    return `${server?.baseUrl ?? DEFAULT_API_BASE_URL}/qa/proxy?${params}`;
   } catch {
     return previewUrl;
   }
@@ -0,0 +1,51 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export type QuickAction = "translate" | "enhance" | "rag_chat";
+
+/** A source behind an "Ask" answer, matching the `[index]` marker in the output. */
+export interface QuickActionCitation {
+  index: number;
+  docName: string | null;
+  pageNumber: number | null;
+  sourceType: string;
+  sourceId: number;
+  snippet: string;
+  score: number | null;
+}
+
+export interface QuickActionResult {
+  action: QuickAction;
+  output: string;
+  detectedLang: string | null;
+  citations: QuickActionCitation[];
+}
+
+/** Event carrying newly captured text while the window is already open. */
+export const QUICK_ACTION_CAPTURE_EVENT = "quick-action-capture";
+
+export async function takeQuickActionCapture(): Promise<string | null> {
+  return await invoke<string | null>("quick_action_take_capture");
+}
+
+export async function submitQuickAction(
+  action: QuickAction,
+  text: string,
+  collectionId?: number | null,
+  presetId?: string | null,
+): Promise<QuickActionResult> {
+  return await invoke<QuickActionResult>("quick_action_submit", {
+    action,
+    text,
+    collectionId: collectionId ?? null,
+    presetId: presetId ?? null,
+  });
+}
+
+/** Copy `text`, hide the window and, when `paste` is set, paste it into the app below. */
+export async function applyQuickAction(text: string, paste: boolean): Promise<void> {
+  await invoke("quick_action_apply", { text, paste });
+}
+
+export async function closeQuickAction(): Promise<void> {
+  await invoke("quick_action_close");
+}
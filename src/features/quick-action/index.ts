@@ -0,0 +1,2 @@
+export { default } from "./pages/QuickActionWindow";
+export { default as QuickActionWindow } from "./pages/QuickActionWindow";
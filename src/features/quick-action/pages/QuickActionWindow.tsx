@@ -0,0 +1,335 @@
+import { useCallback, useEffect, useRef, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { ClipboardPaste, Copy, Loader2, X } from "lucide-react";
+import { Button } from "../../../shared/components/Button";
+import { TextArea } from "../../../shared/components/TextArea";
+import { useSettingsStore } from "../../../store/settings";
+import { cn } from "../../../utils/cn";
+import { isTauri } from "../../../utils/tauri";
+import { listRagCollections } from "../../rag/api/collections";
+import {
+  DEFAULT_ENHANCE_PRESET_SETTINGS,
+  DEFAULT_QUICK_ACTION_SETTINGS,
+  getSettings,
+  type EnhancePresetSettings,
+  type QuickActionSettings,
+} from "../../settings/api/settings";
+import type { RagCollection } from "../../rag/types";
+import {
+  QUICK_ACTION_CAPTURE_EVENT,
+  applyQuickAction,
+  closeQuickAction,
+  submitQuickAction,
+  takeQuickActionCapture,
+  type QuickAction,
+  type QuickActionCitation,
+} from "../api/quickAction";
+
+const ACTIONS: { value: QuickAction; label: string }[] = [
+  { value: "translate", label: "Translate" },
+  { value: "enhance", label: "Enhance" },
+  { value: "rag_chat", label: "Ask" },
+];
+
+const COLLECTION_KEY = "quick-action:collection";
+const PRESET_KEY = "quick-action:enhance-preset";
+
+export default function QuickActionWindow() {
+  const autoTranslate = useSettingsStore((state) => state.autoTranslate);
+  const [action, setAction] = useState<QuickAction>("translate");
+  const [input, setInput] = useState("");
+  const [output, setOutput] = useState("");
+  const [citations, setCitations] = useState<QuickActionCitation[]>([]);
+  const [error, setError] = useState<string | null>(null);
+  const [running, setRunning] = useState(false);
+  const [collections, setCollections] = useState<RagCollection[]>([]);
+  const [collectionId, setCollectionId] = useState<number | null>(() => {
+    const stored = Number(localStorage.getItem(COLLECTION_KEY));
+    return Number.isFinite(stored) && stored > 0 ? stored : null;
+  });
+  const [defaultCollectionId, setDefaultCollectionId] = useState<number | null>(null);
+  const [presetSettings, setPresetSettings] = useState<EnhancePresetSettings | null>(null);
+  // `null` leaves the pick to the default preset from Settings
+  const [presetId, setPresetId] = useState<string | null>(() =>
+    localStorage.getItem(PRESET_KEY),
+  );
+  const runningRef = useRef(false);
+
+  const run = useCallback(
+    async (nextAction: QuickAction, text: string) => {
+      if (!text.trim() || runningRef.current) return;
+      runningRef.current = true;
+      setRunning(true);
+      setError(null);
+      try {
+        const result = await submitQuickAction(
+          nextAction,
+          text,
+          collectionId,
+          nextAction === "enhance" ? presetId : null,
+        );
+        setOutput(result.output);
+        setCitations(result.citations);
+      } catch (e: any) {
+        setOutput("");
+        setCitations([]);
+        setError(typeof e === "string" ? e : e?.message || "Quick action failed");
+      } finally {
+        runningRef.current = false;
+        setRunning(false);
+      }
+    },
+    [collectionId, presetId],
+  );
+
+  const receive = useCallback(
+    (text: string | null) => {
+      const nextText = text ?? "";
+      setInput(nextText);
+      setOutput("");
+      setCitations([]);
+      setError(null);
+      if (autoTranslate && nextText.trim()) {
+        setAction("translate");
+        run("translate", nextText);
+      }
+    },
+    [autoTranslate, run],
+  );
+
+  const receiveRef = useRef(receive);
+  useEffect(() => {
+    receiveRef.current = receive;
+  }, [receive]);
+
+  useEffect(() => {
+    if (!isTauri()) return;
+    let disposed = false;
+    let unlisten: (() => void) | undefined;
+
+    takeQuickActionCapture()
+      .then((text) => {
+        if (!disposed && text !== null) receiveRef.current(text);
+      })
+      .catch(console.error);
+
+    listen<string>(QUICK_ACTION_CAPTURE_EVENT, (event) => {
+      // Drain the stored copy so a reload doesn't replay it
+      takeQuickActionCapture().catch(console.error);
+      receiveRef.current(event.payload);
+    })
+      .then((fn) => {
+        if (disposed) {
+          fn();
+          return;
+        }
+        unlisten = fn;
+      })
+      .catch(console.error);
+
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, []);
+
+  useEffect(() => {
+    if (action !== "rag_chat" || collections.length > 0) return;
+    Promise.all([
+      listRagCollections(50),
+      getSettings()
+        .then((document) => ({
+          ...DEFAULT_QUICK_ACTION_SETTINGS,
+          ...(document.sections.quickAction as Partial<QuickActionSettings>),
+        }))
+        .catch(() => DEFAULT_QUICK_ACTION_SETTINGS),
+    ])
+      .then(([items, settings]) => {
+        setCollections(items);
+        setDefaultCollectionId(settings.ragCollectionId);
+        if (!items.some((item) => item.id === collectionId)) {
+          const fallback = items.find((item) => item.id === settings.ragCollectionId);
+          setCollectionId(fallback?.id ?? items[0]?.id ?? null);
+        }
+      })
+      .catch(console.error);
+  }, [action, collections.length, collectionId]);
+
+  useEffect(() => {
+    if (collectionId !== null) {
+      localStorage.setItem(COLLECTION_KEY, String(collectionId));
+    }
+  }, [collectionId]);
+
+  useEffect(() => {
+    if (action !== "enhance" || presetSettings) return;
+    getSettings()
+      .then((document) => ({
+        ...DEFAULT_ENHANCE_PRESET_SETTINGS,
+        ...(document.sections.enhancePresets as Partial<EnhancePresetSettings>),
+      }))
+      .catch(() => DEFAULT_ENHANCE_PRESET_SETTINGS)
+      .then((settings) => {
+        setPresetSettings(settings);
+        setPresetId((current) =>
+          settings.presets.some((preset) => preset.id === current) ? current : null,
+        );
+      });
+  }, [action, presetSettings]);
+
+  useEffect(() => {
+    if (presetId === null) {
+      localStorage.removeItem(PRESET_KEY);
+    } else {
+      localStorage.setItem(PRESET_KEY, presetId);
+    }
+  }, [presetId]);
+
+  const defaultPreset = presetSettings?.presets.find(
+    (preset) => preset.id === presetSettings.defaultPresetId,
+  );
+
+  useEffect(() => {
+    const handleKey = (event: KeyboardEvent) => {
+      if (event.key === "Escape") {
+        closeQuickAction().catch(console.error);
+      } else if (event.key === "Enter" && (event.ctrlKey || event.metaKey)) {
+        event.preventDefault();
+        if (output && event.shiftKey) {
+          applyQuickAction(output, true).catch(console.error);
+        } else {
+          run(action, input);
+        }
+      }
+    };
+    window.addEventListener("keydown", handleKey);
+    return () => window.removeEventListener("keydown", handleKey);
+  }, [action, input, output, run]);
+
+  return (
+    <div className="flex h-screen w-screen flex-col overflow-hidden rounded-xl border border-app-border bg-app-panel text-app-text">
+      <div
+        data-tauri-drag-region
+        className="flex items-center justify-between border-b border-app-border/60 px-3 py-2">
+        <div className="flex items-center gap-1">
+          {ACTIONS.map((item) => (
+            <button
+              key={item.value}
+              className={cn(
+                "rounded-md px-2.5 py-1 text-xs font-medium transition",
+                action === item.value
+                  ? "bg-app-accent/20 text-app-accent"
+                  : "text-app-subtext hover:text-app-text",
+              )}
+              onClick={() => setAction(item.value)}>
+              {item.label}
+            </button>
+          ))}
+        </div>
+        <button
+          className="flex h-6 w-6 items-center justify-center rounded-full text-app-subtext transition hover:text-app-text"
+          onClick={() => closeQuickAction().catch(console.error)}
+          aria-label="Close quick action window">
+          <X className="h-3.5 w-3.5" />
+        </button>
+      </div>
+
+      <div className="flex flex-1 flex-col gap-2 overflow-hidden p-3">
+        {action === "rag_chat" && (
+          <select
+            className="rounded border border-app-border bg-[#18181b] px-2 py-1 text-xs outline-none"
+            value={collectionId ?? ""}
+            onChange={(e) => setCollectionId(Number(e.target.value) || null)}>
+            {collections.length === 0 && <option value="">No collections</option>}
+            {collections.map((collection) => (
+              <option key={collection.id} value={collection.id}>
+                {collection.name}
+                {collection.id === defaultCollectionId ? " (default)" : ""}
+              </option>
+            ))}
+          </select>
+        )}
+        {action === "enhance" && presetSettings && (
+          <select
+            className="rounded border border-app-border bg-[#18181b] px-2 py-1 text-xs outline-none"
+            value={presetId ?? ""}
+            onChange={(e) => setPresetId(e.target.value || null)}>
+            <option value="">
+              {defaultPreset ? `Default (${defaultPreset.name})` : "No preset"}
+            </option>
+            {presetSettings.presets.map((preset) => (
+              <option key={preset.id} value={preset.id}>
+                {preset.name}
+              </option>
+            ))}
+          </select>
+        )}
+        <TextArea
+          autoFocus
+          className="min-h-[90px] flex-1 border-app-border/40 bg-app-panel/60 p-2 text-sm"
+          value={input}
+          onChange={(e) => setInput(e.target.value)}
+          placeholder={action === "rag_chat" ? "Ask your knowledge base..." : "Selected text..."}
+        />
+        <TextArea
+          readOnly
+          className="min-h-[90px] flex-1 border-app-border/20 bg-black/20 p-2 text-sm text-app-text/90"
+          value={output}
+          placeholder="Result will appear here..."
+        />
+        {citations.length > 0 && (
+          <ol className="max-h-28 space-y-1 overflow-y-auto text-[11px] text-app-subtext">
+            {citations.map((citation) => (
+              <li key={citation.index} title={citation.snippet}>
+                <span className="mr-1 font-semibold text-app-accent">[{citation.index}]</span>
+                <span className="text-app-text/90">
+                  {citation.docName ?? `${citation.sourceType} #${citation.sourceId}`}
+                </span>
+                {citation.pageNumber !== null && <span> · p.{citation.pageNumber}</span>}
+                <span className="block truncate">{citation.snippet}</span>
+              </li>
+            ))}
+          </ol>
+        )}
+        {error && <div className="text-[10px] text-red-400">{error}</div>}
+      </div>
+
+      <div className="flex items-center justify-between border-t border-app-border/60 px-3 py-2">
+        <span className="text-[10px] uppercase tracking-widest text-app-subtext">
+          Ctrl+Enter run · Ctrl+Shift+Enter paste
+        </span>
+        <div className="flex items-center gap-2">
+          {output ? (
+            <>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => applyQuickAction(output, false).catch(console.error)}>
+                <Copy className="mr-1 h-3.5 w-3.5" />
+                Copy
+              </Button>
+              <Button
+                size="sm"
+                onClick={() => applyQuickAction(output, true).catch(console.error)}>
+                <ClipboardPaste className="mr-1 h-3.5 w-3.5" />
+                Paste
+              </Button>
+            </>
+          ) : (
+            <Button
+              size="sm"
+              disabled={
+                running ||
+                !input.trim() ||
+                (action === "rag_chat" && !collectionId && !defaultCollectionId)
+              }
+              onClick={() => run(action, input)}>
+              {running && <Loader2 className="mr-1 h-3.5 w-3.5 animate-spin" />}
+              Run
+            </Button>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+}
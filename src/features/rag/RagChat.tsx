@@ -173,6 +173,13 @@ export default function RagChat() {
     inputRef.current?.focus();
   }, []);
 
+  const handleRefreshCached = useCallback(
+    (query: string) => {
+      void sendMessage(query, { bypassCache: true }).finally(() => inputRef.current?.focus());
+    },
+    [sendMessage],
+  );
+
   const handleRegenerateWithTemplate = useCallback(
     (query: string, templateId: number) => {
       void regenerateWithTemplate(query, templateId).finally(() => inputRef.current?.focus());
@@ -288,6 +295,7 @@ export default function RagChat() {
             isLoading={currentIsLoading}
             selectedCollectionId={selectedCollectionId}
             onRegenerate={handleRegenerate}
+            onRefreshCached={chatMode === "free" ? undefined : handleRefreshCached}
             onRegenerateWithTemplate={isDbCollection ? handleRegenerateWithTemplate : undefined}
           />
 
@@ -74,6 +74,8 @@ export default function RagTab() {
             onChangeMaxDepth={c.setMaxDepth}
             webCrawlMode={c.webCrawlMode}
             onChangeWebCrawlMode={c.setWebCrawlMode}
+            webLogin={c.webLogin}
+            onChangeWebLogin={c.setWebLogin}
             isCrawling={c.isCrawling}
             onStart={c.handleWebImport}
             onClose={() => c.setShowWebImport(false)}
@@ -100,7 +102,12 @@ export default function RagTab() {
             <>
               {c.selectedCollection?.kind === "Db" ? <RagTabDbCollectionInfo tables={c.collectionTables} config={c.collectionConfig} /> : null}
               {c.selectedCollection?.kind !== "Db" ? (
-                <RagTabDocumentsGrid documents={c.documents} onDeleteDocument={c.handleDeleteDocument} />
+                <RagTabDocumentsGrid
+                  documents={c.documents}
+                  onDeleteDocument={c.handleDeleteDocument}
+                  onResumeImports={c.handleResumeImports}
+                  isResumingImports={c.isResumingImports}
+                />
               ) : null}
             </>
           )}
@@ -9,3 +9,7 @@ export * from "./api/quality";
 export * from "./api/csv";
 export * from "./api/db";
 export * from "./api/dbTemplates";
+export * from "./api/connectors";
+export * from "./api/gitRepos";
+export * from "./api/importTemplates";
+export * from "./api/emails";
@@ -19,3 +19,86 @@ export async function getRecentAnalytics(
 export async function clearAnalytics(): Promise<string> {
   return await invoke<string>("rag_clear_analytics");
 }
+
+export interface CollectionChunkUsage {
+  total_chunks: number;
+  retrieved_chunks: number;
+  cited_chunks: number;
+  total_retrievals: number;
+  total_citations: number;
+  /** First recorded retrieval, unix seconds */
+  tracking_since: number | null;
+}
+
+export interface ChunkUsage {
+  chunk_id: number;
+  doc_id: number;
+  doc_name: string;
+  chunk_index: number;
+  retrieval_count: number;
+  citation_count: number;
+  last_retrieved_at: number | null;
+  last_cited_at: number | null;
+}
+
+export interface DocumentUsage {
+  doc_id: number;
+  file_name: string;
+  created_at: number;
+  chunk_count: number;
+  retrieved_chunks: number;
+  retrieval_count: number;
+  citation_count: number;
+  last_retrieved_at: number | null;
+  last_cited_at: number | null;
+}
+
+export interface ChunkUsageStats extends CollectionChunkUsage {
+  collection_id: number;
+  top_chunks: ChunkUsage[];
+  /** Least used first */
+  documents: DocumentUsage[];
+}
+
+export interface PruningOptions {
+  min_unused_days?: number;
+  stale_days?: number;
+}
+
+export interface PruningSuggestion {
+  doc_id: number;
+  file_name: string;
+  chunk_count: number;
+  reason: "never_retrieved" | "not_retrieved_recently";
+  days_unused: number;
+  last_retrieved_at: number | null;
+}
+
+export interface PruningReport {
+  collection_id: number;
+  total_documents: number;
+  /** `null` until the collection has usage data */
+  tracking_since: number | null;
+  suggestions: PruningSuggestion[];
+  reclaimable_chunks: number;
+}
+
+export async function getChunkUsageStats(
+  collectionId: number,
+  limit?: number,
+): Promise<ChunkUsageStats> {
+  return await invoke<ChunkUsageStats>("rag_get_chunk_usage_stats", {
+    collectionId,
+    limit,
+  });
+}
+
+export async function getPruningSuggestions(
+  collectionId: number,
+  options?: PruningOptions,
+): Promise<PruningReport> {
+  return await invoke<PruningReport>("rag_get_pruning_suggestions", {
+    collectionId,
+    options,
+  });
+}
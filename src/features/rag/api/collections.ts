@@ -1,5 +1,16 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { RagCollection, RagCollectionInput } from "../types";
+import type {
+  CollectionEncryptionStatus,
+  CollectionLanguageConfig,
+  CollectionSplit,
+  CollectionTransferReport,
+  DocumentFilter,
+  EmbeddingQuantization,
+  EmbeddingQuantizationReport,
+  IngestionPipelineConfig,
+  RagCollection,
+  RagCollectionInput,
+} from "../types";
 
 export async function createRagCollection(
   input: RagCollectionInput,
@@ -20,3 +31,146 @@ export async function listRagCollections(
 export async function deleteRagCollection(id: number): Promise<number> {
   return await invoke<number>("rag_delete_collection", { id });
 }
+
+export async function getCollectionEncryption(
+  collectionId: number,
+): Promise<CollectionEncryptionStatus> {
+  return await invoke<CollectionEncryptionStatus>(
+    "rag_get_collection_encryption",
+    { collectionId },
+  );
+}
+
+/** Encrypt a collection at rest; resolves to the number of chunks encrypted */
+export async function enableCollectionEncryption(
+  collectionId: number,
+  passphrase: string,
+): Promise<number> {
+  return await invoke<number>("rag_enable_collection_encryption", {
+    collectionId,
+    passphrase,
+  });
+}
+
+export async function unlockCollection(
+  collectionId: number,
+  passphrase: string,
+): Promise<CollectionEncryptionStatus> {
+  return await invoke<CollectionEncryptionStatus>("rag_unlock_collection", {
+    collectionId,
+    passphrase,
+  });
+}
+
+export async function lockCollection(
+  collectionId: number,
+): Promise<CollectionEncryptionStatus> {
+  return await invoke<CollectionEncryptionStatus>("rag_lock_collection", {
+    collectionId,
+  });
+}
+
+/** Decrypt a collection; resolves to the number of chunks decrypted */
+export async function disableCollectionEncryption(
+  collectionId: number,
+  passphrase: string,
+): Promise<number> {
+  return await invoke<number>("rag_disable_collection_encryption", {
+    collectionId,
+    passphrase,
+  });
+}
+
+export async function getCollectionPipeline(
+  collectionId: number,
+): Promise<IngestionPipelineConfig> {
+  return await invoke<IngestionPipelineConfig>("rag_get_collection_pipeline", {
+    collectionId,
+  });
+}
+
+/** Applies to documents imported from now on */
+export async function setCollectionPipeline(
+  collectionId: number,
+  pipeline: IngestionPipelineConfig,
+): Promise<IngestionPipelineConfig> {
+  return await invoke<IngestionPipelineConfig>("rag_set_collection_pipeline", {
+    collectionId,
+    pipeline,
+  });
+}
+
+/**
+ * Store a collection's embeddings at `quantization` from now on and convert
+ * the existing ones; the database is compacted afterwards unless `compact`
+ * is false
+ */
+export async function quantizeCollectionEmbeddings(
+  collectionId: number,
+  quantization: EmbeddingQuantization,
+  compact?: boolean,
+): Promise<EmbeddingQuantizationReport> {
+  return await invoke<EmbeddingQuantizationReport>("rag_quantize_collection_embeddings", {
+    collectionId,
+    quantization,
+    compact,
+  });
+}
+
+export async function getCollectionLanguage(
+  collectionId: number,
+): Promise<CollectionLanguageConfig> {
+  return await invoke<CollectionLanguageConfig>("rag_get_collection_language", {
+    collectionId,
+  });
+}
+
+/** Applies to searches right away */
+export async function setCollectionLanguage(
+  collectionId: number,
+  language: CollectionLanguageConfig,
+): Promise<CollectionLanguageConfig> {
+  return await invoke<CollectionLanguageConfig>("rag_set_collection_language", {
+    collectionId,
+    language,
+  });
+}
+
+/** Documents a connector or git repository syncs are left where they are */
+export async function moveDocuments(
+  docIds: number[],
+  targetCollectionId: number,
+): Promise<CollectionTransferReport> {
+  return await invoke<CollectionTransferReport>("rag_move_documents", {
+    docIds,
+    targetCollectionId,
+  });
+}
+
+/** The emptied source is deleted unless `deleteSource` is false */
+export async function mergeCollections(
+  sourceCollectionId: number,
+  targetCollectionId: number,
+  deleteSource?: boolean,
+): Promise<CollectionTransferReport> {
+  return await invoke<CollectionTransferReport>("rag_merge_collections", {
+    sourceCollectionId,
+    targetCollectionId,
+    deleteSource,
+  });
+}
+
+/** Move the documents matching `filter` into a new collection with the same settings */
+export async function splitCollection(
+  collectionId: number,
+  filter: DocumentFilter,
+  name: string,
+  description?: string,
+): Promise<CollectionSplit> {
+  return await invoke<CollectionSplit>("rag_split_collection", {
+    collectionId,
+    filter,
+    name,
+    description,
+  });
+}
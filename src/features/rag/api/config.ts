@@ -5,8 +5,10 @@ import type {
   ChunkingConfig,
   ConfigValidation,
   EmbeddingConfig,
+  EmbeddingThroughputStats,
   LogEntry,
   OcrConfig,
+  PiiConfig,
   RagConfig,
   RetrievalConfig,
   SystemStats,
@@ -48,6 +50,10 @@ export async function updateChatConfig(config: ChatConfig): Promise<string> {
   return await invoke<string>("rag_update_chat_config", { config });
 }
 
+export async function updatePiiConfig(config: PiiConfig): Promise<string> {
+  return await invoke<string>("rag_update_pii_config", { config });
+}
+
 export async function resetRagConfig(): Promise<RagConfig> {
   return await invoke<RagConfig>("rag_reset_config");
 }
@@ -59,3 +65,7 @@ export async function validateRagConfig(): Promise<ConfigValidation> {
 export async function getSystemStats(): Promise<SystemStats> {
   return await invoke<SystemStats>("rag_get_system_stats");
 }
+
+export async function getEmbeddingThroughput(): Promise<EmbeddingThroughputStats> {
+  return await invoke<EmbeddingThroughputStats>("rag_get_embedding_throughput");
+}
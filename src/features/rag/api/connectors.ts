@@ -0,0 +1,45 @@
+import { invoke } from "@tauri-apps/api/core";
+import type {
+  ConnectorPage,
+  ConnectorSyncReport,
+  RagConnector,
+  RagConnectorInput,
+} from "../types";
+
+export async function listRagConnectors(): Promise<RagConnector[]> {
+  return await invoke<RagConnector[]>("rag_list_connectors");
+}
+
+export async function createRagConnector(
+  input: RagConnectorInput,
+): Promise<RagConnector> {
+  return await invoke<RagConnector>("rag_create_connector", { input });
+}
+
+export async function updateRagConnector(
+  id: number,
+  input: RagConnectorInput,
+): Promise<RagConnector> {
+  return await invoke<RagConnector>("rag_update_connector", { id, input });
+}
+
+export async function deleteRagConnector(
+  id: number,
+  deleteDocuments = false,
+): Promise<void> {
+  await invoke("rag_delete_connector", { id, deleteDocuments });
+}
+
+export async function listRagConnectorPages(
+  connectorId: number,
+): Promise<ConnectorPage[]> {
+  return await invoke<ConnectorPage[]>("rag_list_connector_pages", {
+    connectorId,
+  });
+}
+
+export async function syncRagConnector(
+  id: number,
+): Promise<ConnectorSyncReport> {
+  return await invoke<ConnectorSyncReport>("rag_sync_connector", { id });
+}
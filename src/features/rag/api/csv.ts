@@ -1,8 +1,11 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 
 export interface CsvPreprocessingRequest {
   filePath: string;
   config?: CsvPreprocessingRequestConfig;
+  /** Write processed text to this file; the response then carries a preview */
+  outputPath?: string;
 }
 
 export interface CsvPreprocessingRequestConfig {
@@ -11,6 +14,7 @@ export interface CsvPreprocessingRequestConfig {
   maxNumericRatio?: number;
   minSampleRows?: number;
   maxSampleRows?: number;
+  normalizeColumns?: boolean;
 }
 
 export interface CsvFieldAnalysis {
@@ -25,15 +29,66 @@ export interface CsvFieldAnalysis {
   confidenceScore: number;
 }
 
+export type CsvColumnType =
+  | "int"
+  | "float"
+  | "date"
+  | "bool"
+  | "categorical"
+  | "text"
+  | "empty";
+
+export interface CsvColumnProfile {
+  name: string;
+  cleanName: string;
+  inferredType: CsvColumnType;
+  dateFormat: string | null;
+  nullCount: number;
+  nullRate: number;
+  distinctCount: number;
+  min: string | null;
+  max: string | null;
+  sampleValues: string[];
+}
+
+export interface CsvSuggestedColumn {
+  name: string;
+  source: string;
+  columnType: CsvColumnType;
+  nullable: boolean;
+  categories: string[];
+}
+
+export interface CsvProfile {
+  rowCount: number;
+  columns: CsvColumnProfile[];
+  suggestedSchema: CsvSuggestedColumn[];
+}
+
 export interface CsvPreprocessingResponse {
   contentType: string;
   processedText: string;
   rowCount: number;
   analysis: CsvFieldAnalysis;
+  profile: CsvProfile;
   headers: string[];
+  encoding: string;
+  delimiter: string;
+  outputPath: string | null;
   processingTimeMs: number;
 }
 
+export type CsvProcessingPhase = "analyzing" | "formatting" | "complete";
+
+export interface CsvProgressEvent {
+  filePath: string;
+  phase: CsvProcessingPhase;
+  rowsProcessed: number;
+  bytesRead: number;
+  totalBytes: number;
+  percent: number;
+}
+
 export interface CsvPreviewRow {
   index: number;
   content: string;
@@ -45,6 +100,14 @@ export async function preprocessCsvFile(
   return await invoke<CsvPreprocessingResponse>("csv_preprocess_file", { request });
 }
 
+export async function onCsvPreprocessProgress(
+  handler: (event: CsvProgressEvent) => void,
+): Promise<UnlistenFn> {
+  return await listen<CsvProgressEvent>("csv-preprocess-progress", (event) =>
+    handler(event.payload),
+  );
+}
+
 export async function previewCsvRows(
   filePath: string,
   previewCount: number,
@@ -55,6 +118,13 @@ export async function previewCsvRows(
   });
 }
 
-export async function analyzeCsv(filePath: string): Promise<string> {
-  return await invoke<string>("csv_analyze", { filePath });
+export interface CsvAnalysisResponse {
+  report: string;
+  profile: CsvProfile;
+}
+
+export async function analyzeCsv(
+  filePath: string,
+): Promise<CsvAnalysisResponse> {
+  return await invoke<CsvAnalysisResponse>("csv_analyze", { filePath });
 }
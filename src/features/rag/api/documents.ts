@@ -1,10 +1,21 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { RagDocument } from "../types";
+import type {
+  DocumentPreview,
+  ExcelImportOptions,
+  IngestionResumeReport,
+  RagDocument,
+  WebCaptureAuth,
+  WorkbookSheet,
+} from "../types";
 
 export async function getRagDocument(id: number): Promise<RagDocument> {
   return await invoke<RagDocument>("rag_get_document", { id });
 }
 
+export async function getRagDocumentPreview(id: number): Promise<DocumentPreview> {
+  return await invoke<DocumentPreview>("rag_get_document_preview", { id });
+}
+
 export async function listRagDocuments(
   collectionId?: number,
   limit?: number,
@@ -29,6 +40,33 @@ export async function importRagFile(
   });
 }
 
+/** The worksheets of an Excel workbook, to pick the ones to import */
+export async function listWorkbookSheets(filePath: string): Promise<WorkbookSheet[]> {
+  return await invoke<WorkbookSheet[]>("rag_list_workbook_sheets", { filePath });
+}
+
+/** Import sheets of an Excel workbook, one document per sheet */
+export async function importRagWorkbook(
+  filePath: string,
+  collectionId?: number,
+  options?: ExcelImportOptions,
+): Promise<RagDocument[]> {
+  return await invoke<RagDocument[]>("rag_import_workbook", {
+    filePath,
+    collectionId,
+    options,
+  });
+}
+
+/** Resume failed and interrupted imports, of one collection or of all */
+export async function resumeFailedIngestions(
+  collectionId?: number,
+): Promise<IngestionResumeReport> {
+  return await invoke<IngestionResumeReport>("rag_resume_failed_ingestions", {
+    collectionId,
+  });
+}
+
 export type WebCrawlMode = "html" | "ocr";
 
 export async function importRagWeb(
@@ -37,6 +75,7 @@ export async function importRagWeb(
   maxPages?: number,
   maxDepth?: number,
   mode: WebCrawlMode = "html",
+  auth?: WebCaptureAuth,
 ): Promise<RagDocument> {
   return await invoke<RagDocument>("rag_import_web", {
     request: {
@@ -45,6 +84,7 @@ export async function importRagWeb(
       max_pages: maxPages,
       max_depth: maxDepth,
       mode,
+      auth,
     },
   });
 }
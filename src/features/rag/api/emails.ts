@@ -0,0 +1,28 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { EmailFilter, EmailMessageRecord, RagQueryResult } from "../types";
+
+export async function listRagEmailMessages(
+  collectionId: number,
+  filter?: EmailFilter,
+  limit?: number,
+): Promise<EmailMessageRecord[]> {
+  return await invoke<EmailMessageRecord[]>("rag_list_email_messages", {
+    collectionId,
+    filter,
+    limit,
+  });
+}
+
+export async function searchRagEmails(
+  collectionId: number,
+  query: string,
+  filter?: EmailFilter,
+  topK?: number,
+): Promise<RagQueryResult[]> {
+  return await invoke<RagQueryResult[]>("rag_search_emails", {
+    collectionId,
+    query,
+    filter,
+    topK,
+  });
+}
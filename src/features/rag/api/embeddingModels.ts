@@ -0,0 +1,83 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type { EmbeddingExecutionProvider } from "../../settings/api/settings";
+
+/** A local embedding model as listed by the model manager. */
+export interface LocalEmbeddingModel {
+  id: string;
+  /** Hugging Face repository the files come from */
+  modelCode: string;
+  description: string;
+  dimension: number;
+  installed: boolean;
+  active: boolean;
+  /** Size on disk when installed, otherwise the download size when known */
+  sizeBytes: number | null;
+  /** Provider the active model was loaded on; null until first use */
+  executionProvider: EmbeddingExecutionProvider | null;
+}
+
+export interface EmbeddingModelDownloadProgress {
+  modelId: string;
+  file: string;
+  downloadedBytes: number;
+  totalBytes: number;
+  filesDone: number;
+  fileCount: number;
+}
+
+export interface EmbeddingProviderBenchmark {
+  provider: EmbeddingExecutionProvider;
+  /** The provider loaded the model; otherwise `error` says why not */
+  available: boolean;
+  loadMs: number | null;
+  embeddingsPerSec: number | null;
+  error: string | null;
+}
+
+export interface EmbeddingBenchmarkReport {
+  modelId: string;
+  sampleCount: number;
+  batchSize: number;
+  activeProvider: EmbeddingExecutionProvider | null;
+  results: EmbeddingProviderBenchmark[];
+}
+
+export const EMBEDDING_MODEL_DOWNLOAD_PROGRESS_EVENT = "embedding-model-download-progress";
+
+export async function listEmbeddingModels(): Promise<LocalEmbeddingModel[]> {
+  return await invoke<LocalEmbeddingModel[]>("rag_list_embedding_models");
+}
+
+export async function getEmbeddingModelInfo(modelId: string): Promise<LocalEmbeddingModel> {
+  return await invoke<LocalEmbeddingModel>("rag_get_embedding_model_info", { modelId });
+}
+
+/** Download and verify a model; progress arrives through `onEmbeddingModelDownloadProgress`. */
+export async function downloadEmbeddingModel(modelId: string): Promise<LocalEmbeddingModel> {
+  return await invoke<LocalEmbeddingModel>("rag_download_embedding_model", { modelId });
+}
+
+export async function setActiveEmbeddingModel(modelId: string): Promise<LocalEmbeddingModel> {
+  return await invoke<LocalEmbeddingModel>("rag_set_active_embedding_model", { modelId });
+}
+
+/** Embeddings per second on the CPU and each GPU provider; defaults to the active model. */
+export async function benchmarkEmbeddingProviders(
+  modelId?: string,
+  sampleCount?: number,
+): Promise<EmbeddingBenchmarkReport> {
+  return await invoke<EmbeddingBenchmarkReport>("rag_benchmark_embedding_providers", {
+    modelId,
+    sampleCount,
+  });
+}
+
+export async function onEmbeddingModelDownloadProgress(
+  handler: (progress: EmbeddingModelDownloadProgress) => void,
+): Promise<UnlistenFn> {
+  return await listen<EmbeddingModelDownloadProgress>(
+    EMBEDDING_MODEL_DOWNLOAD_PROGRESS_EVENT,
+    (event) => handler(event.payload),
+  );
+}
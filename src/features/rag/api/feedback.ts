@@ -1,5 +1,15 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { FeedbackStats, UserFeedback } from "../types";
+import type {
+  FeedbackStats,
+  QueryRoute,
+  RetrievalExperiment,
+  RetrievalParams,
+  RouteCorrectionReport,
+  RoutingDecisionRecord,
+  TuningOptions,
+  TuningReport,
+  UserFeedback,
+} from "../types";
 
 export async function submitFeedback(feedback: UserFeedback): Promise<string> {
   return await invoke<string>("rag_submit_feedback", { feedback });
@@ -16,3 +26,36 @@ export async function getRecentFeedback(limit?: number): Promise<UserFeedback[]>
 export async function clearFeedback(): Promise<string> {
   return await invoke<string>("rag_clear_feedback");
 }
+
+export async function getTuningReport(options?: TuningOptions): Promise<TuningReport> {
+  return await invoke<TuningReport>("rag_get_tuning_report", { options });
+}
+
+/** Try `candidate`, or the report's proposal when omitted, on half of the queries */
+export async function startTuningExperiment(
+  candidate?: RetrievalParams,
+): Promise<RetrievalExperiment> {
+  return await invoke<RetrievalExperiment>("rag_start_tuning_experiment", { candidate });
+}
+
+export async function finishTuningExperiment(promote: boolean): Promise<string> {
+  return await invoke<string>("rag_finish_tuning_experiment", { promote });
+}
+
+export async function listRoutingDecisions(
+  collectionId?: number,
+  limit?: number,
+): Promise<RoutingDecisionRecord[]> {
+  return await invoke<RoutingDecisionRecord[]>("rag_list_routing_decisions", {
+    collectionId,
+    limit,
+  });
+}
+
+/** Record the route a query should have taken; refits the routing thresholds */
+export async function correctQueryRoute(
+  decisionId: number,
+  route: QueryRoute,
+): Promise<RouteCorrectionReport> {
+  return await invoke<RouteCorrectionReport>("rag_correct_query_route", { decisionId, route });
+}
@@ -0,0 +1,41 @@
+import { invoke } from "@tauri-apps/api/core";
+import type {
+  GitRepoFile,
+  GitRepoInput,
+  GitRepoSource,
+  GitSyncReport,
+} from "../types";
+
+export async function listRagGitRepos(): Promise<GitRepoSource[]> {
+  return await invoke<GitRepoSource[]>("rag_list_git_repos");
+}
+
+export async function createRagGitRepo(
+  input: GitRepoInput,
+): Promise<GitRepoSource> {
+  return await invoke<GitRepoSource>("rag_create_git_repo", { input });
+}
+
+export async function updateRagGitRepo(
+  id: number,
+  input: GitRepoInput,
+): Promise<GitRepoSource> {
+  return await invoke<GitRepoSource>("rag_update_git_repo", { id, input });
+}
+
+export async function deleteRagGitRepo(
+  id: number,
+  deleteDocuments = false,
+): Promise<void> {
+  await invoke("rag_delete_git_repo", { id, deleteDocuments });
+}
+
+export async function listRagGitRepoFiles(
+  repoId: number,
+): Promise<GitRepoFile[]> {
+  return await invoke<GitRepoFile[]>("rag_list_git_repo_files", { repoId });
+}
+
+export async function syncRagGitRepo(id: number): Promise<GitSyncReport> {
+  return await invoke<GitSyncReport>("rag_sync_git_repo", { id });
+}
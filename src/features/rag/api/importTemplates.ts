@@ -0,0 +1,30 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { ImportTemplate, ImportTemplateInput } from "../types";
+
+export async function listRagImportTemplates(
+  collectionId?: number,
+): Promise<ImportTemplate[]> {
+  return await invoke<ImportTemplate[]>("rag_list_import_templates", {
+    collectionId: collectionId ?? null,
+  });
+}
+
+export async function createRagImportTemplate(
+  input: ImportTemplateInput,
+): Promise<ImportTemplate> {
+  return await invoke<ImportTemplate>("rag_create_import_template", { input });
+}
+
+export async function updateRagImportTemplate(
+  id: number,
+  input: ImportTemplateInput,
+): Promise<ImportTemplate> {
+  return await invoke<ImportTemplate>("rag_update_import_template", {
+    id,
+    input,
+  });
+}
+
+export async function deleteRagImportTemplate(id: number): Promise<void> {
+  await invoke("rag_delete_import_template", { id });
+}
@@ -0,0 +1,33 @@
+import { invoke } from "@tauri-apps/api/core";
+
+/** A Tesseract language that is installed or can be downloaded. */
+export interface OcrLanguage {
+  /** Code passed to `tesseract -l`, e.g. "chi_sim" */
+  code: string;
+  name: string | null;
+  installed: boolean;
+  /** Shipped with the app rather than downloaded */
+  bundled: boolean;
+  sizeBytes: number | null;
+}
+
+export async function listOcrLanguages(): Promise<OcrLanguage[]> {
+  return await invoke<OcrLanguage[]>("rag_list_ocr_languages");
+}
+
+/** Download and verify the language data for `code`. */
+export async function downloadOcrLanguage(code: string): Promise<OcrLanguage> {
+  return await invoke<OcrLanguage>("rag_download_ocr_language", { code });
+}
+
+export async function getDocumentOcrLanguages(documentId: number): Promise<string[]> {
+  return await invoke<string[]>("rag_get_document_ocr_languages", { documentId });
+}
+
+/** Store the languages a document is written in; an empty list clears the hint. */
+export async function setDocumentOcrLanguages(
+  documentId: number,
+  languages: string[],
+): Promise<string[]> {
+  return await invoke<string[]>("rag_set_document_ocr_languages", { documentId, languages });
+}
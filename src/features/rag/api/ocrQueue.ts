@@ -0,0 +1,48 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export type OcrPageStatus = "ok" | "queued" | "improved" | "exhausted";
+
+/** Tesseract confidence of one OCR'd page and its place in the reprocessing queue. */
+export interface OcrPageRecord {
+  doc_id: number;
+  page_number: number;
+  /** Mean word confidence, 0-1 */
+  confidence: number;
+  block_confidences: number[];
+  dpi: number;
+  preprocessing: string;
+  status: OcrPageStatus;
+  attempts: number;
+  last_error: string | null;
+  /** Unix seconds */
+  updated_at: number;
+}
+
+export interface OcrQueueReport {
+  processed: number;
+  improved: number;
+  exhausted: number;
+  failed: number;
+  remaining: number;
+  updated_documents: number[];
+}
+
+export async function listOcrPages(
+  documentId?: number,
+  status?: OcrPageStatus,
+  limit?: number,
+): Promise<OcrPageRecord[]> {
+  return await invoke<OcrPageRecord[]>("rag_list_ocr_pages", { documentId, status, limit });
+}
+
+/** OCR the next queued pages again at a higher DPI or with other preprocessing. */
+export async function processOcrQueue(limit?: number): Promise<OcrQueueReport> {
+  return await invoke<OcrQueueReport>("rag_process_ocr_queue", { limit });
+}
+
+export async function requeueOcrPage(
+  documentId: number,
+  pageNumber: number,
+): Promise<OcrPageRecord> {
+  return await invoke<OcrPageRecord>("rag_requeue_ocr_page", { documentId, pageNumber });
+}
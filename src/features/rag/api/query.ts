@@ -1,5 +1,11 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { RagQueryRequest, RagQueryResponse, RagQueryResult } from "../types";
+import type {
+  RagQueryRequest,
+  RagQueryResponse,
+  RagQueryResult,
+  RagToolCallRecord,
+} from "../types";
+import type { LlmConfig } from "../../../shared/api/apiClient";
 
 export async function ragQuery(request: RagQueryRequest): Promise<RagQueryResponse> {
   return await invoke<RagQueryResponse>("rag_query", { request });
@@ -49,6 +55,84 @@ export async function ragChatWithContext(
   return await invoke<ChatWithContextResponse>("rag_chat_with_context", { request });
 }
 
+// ============================================================
+// ANSWER CACHE
+// ============================================================
+
+export interface AnswerCacheRequest {
+  collection_id: number;
+  query: string;
+  top_k: number;
+  provider: string;
+  model: string;
+  language?: string;
+  /** Other options the answer depends on, such as strict mode */
+  variant?: string;
+  /** Skip the cached answer; the new one replaces it */
+  bypass_cache?: boolean;
+}
+
+export interface CachedRagAnswer {
+  answer: string;
+  results: RagQueryResult[];
+  hit_count: number;
+  /** Unix seconds the answer was generated at */
+  cached_at: number;
+}
+
+/** Opaque handle for storing the answer generated after a miss */
+export interface AnswerCacheSlot {
+  collection_id: number;
+  cache_key: string;
+  collection_version: number;
+  query: string;
+}
+
+export interface AnswerCacheLookup {
+  cached: CachedRagAnswer | null;
+  /** Null when the cache is off or the collection is encrypted */
+  slot: AnswerCacheSlot | null;
+}
+
+export async function ragFindCachedAnswer(
+  request: AnswerCacheRequest,
+): Promise<AnswerCacheLookup> {
+  return await invoke<AnswerCacheLookup>("rag_find_cached_answer", { request });
+}
+
+export async function ragCacheAnswer(
+  slot: AnswerCacheSlot,
+  answer: string,
+  results: RagQueryResult[],
+): Promise<void> {
+  await invoke("rag_cache_answer", { slot, answer, results });
+}
+
+// ============================================================
+// TOOL CALLING
+// ============================================================
+
+export interface RagToolAnswerRequest {
+  config: LlmConfig;
+  system_prompt: string;
+  prompt: string;
+  /** Language the answer must be in, over the `outputLanguage` setting */
+  output_language?: string;
+}
+
+export interface RagToolAnswerResponse {
+  answer: string;
+  tool_calls: RagToolCallRecord[];
+  qa_run_id: string | null;
+}
+
+/** Answer a built prompt, letting the model call the whitelisted QA API tool */
+export async function ragAnswerWithTools(
+  request: RagToolAnswerRequest,
+): Promise<RagToolAnswerResponse> {
+  return await invoke<RagToolAnswerResponse>("rag_answer_with_tools", { request });
+}
+
 // ============================================================
 // PHASE 9: Conversation Persistence
 // ============================================================
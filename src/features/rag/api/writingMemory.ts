@@ -0,0 +1,7 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { WritingMemorySyncReport } from "../types";
+
+/** Add translations and enhancements accepted before writing memory was on */
+export async function syncWritingMemory(): Promise<WritingMemorySyncReport> {
+  return await invoke<WritingMemorySyncReport>("rag_sync_writing_memory");
+}
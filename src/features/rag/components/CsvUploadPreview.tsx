@@ -3,6 +3,7 @@ import {
   preprocessCsvFile,
   previewCsvRows,
   analyzeCsv,
+  onCsvPreprocessProgress,
   type CsvPreprocessingResponse,
   type CsvPreviewRow,
 } from "../api";
@@ -23,6 +24,7 @@ export const CsvUploadPreview: React.FC<CsvUploadPreviewProps> = ({
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
   const [showFullPreview, setShowFullPreview] = useState(false);
+  const [progress, setProgress] = useState<number | null>(null);
 
   const handleFileSelect = useCallback(async () => {
     try {
@@ -46,10 +48,19 @@ export const CsvUploadPreview: React.FC<CsvUploadPreviewProps> = ({
 
       // Analyze the CSV first
       const analysis = await analyzeCsv(selected as string);
-      console.log("CSV Analysis:", analysis);
+      console.log("CSV Analysis:", analysis.report);
 
-      // Preprocess the CSV
-      const result = await preprocessCsvFile({ filePath: selected as string });
+      // Preprocess the CSV, tracking progress for large files
+      const unlisten = await onCsvPreprocessProgress((event) => {
+        if (event.filePath === selected) setProgress(event.percent);
+      });
+      let result: CsvPreprocessingResponse;
+      try {
+        result = await preprocessCsvFile({ filePath: selected as string });
+      } finally {
+        unlisten();
+        setProgress(null);
+      }
       setPreprocessingResult(result);
 
       // Get preview rows (first 5)
@@ -111,7 +122,11 @@ export const CsvUploadPreview: React.FC<CsvUploadPreviewProps> = ({
             onClick={handleFileSelect}
             disabled={loading}
             className="px-6 py-3 bg-blue-500 text-white rounded-lg hover:bg-blue-600 disabled:bg-gray-400 transition-colors">
-            {loading ? "Loading..." : "Select CSV File"}
+            {loading
+              ? progress !== null
+                ? `Processing... ${Math.round(progress)}%`
+                : "Loading..."
+              : "Select CSV File"}
           </button>
           {error && <p className="mt-4 text-red-500">{error}</p>}
         </div>
@@ -132,6 +147,9 @@ export const CsvUploadPreview: React.FC<CsvUploadPreviewProps> = ({
                 <span className="px-2 py-1 rounded text-xs font-medium bg-gray-100 text-gray-700">
                   {preprocessingResult.headers.length} columns
                 </span>
+                <span className="px-2 py-1 rounded text-xs font-medium bg-gray-100 text-gray-700">
+                  {preprocessingResult.encoding}
+                </span>
               </div>
             )}
           </div>
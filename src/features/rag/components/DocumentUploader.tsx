@@ -57,11 +57,11 @@ export const DocumentUploader: React.FC<DocumentUploaderProps> = ({
     const files = Array.from(e.dataTransfer.files);
     const supportedFiles = files.filter((f) => {
       const ext = f.name.toLowerCase().split(".").pop();
-      return ["pdf", "docx", "xlsx", "csv", "txt", "md"].includes(ext || "");
+      return ["pdf", "docx", "xlsx", "csv", "txt", "md", "eml", "mbox"].includes(ext || "");
     });
 
     if (supportedFiles.length !== files.length) {
-      setError("Some files were skipped. Supported formats: PDF, DOCX, XLSX, CSV, TXT, MD");
+      setError("Some files were skipped. Supported formats: PDF, DOCX, XLSX, CSV, TXT, MD, EML, MBOX");
     }
 
     setSelectedFiles(supportedFiles);
@@ -220,6 +220,9 @@ export const DocumentUploader: React.FC<DocumentUploaderProps> = ({
         return "📝";
       case "md":
         return "📑";
+      case "eml":
+      case "mbox":
+        return "✉️";
       default:
         return "📄";
     }
@@ -269,7 +272,7 @@ export const DocumentUploader: React.FC<DocumentUploaderProps> = ({
           ref={fileInputRef}
           type="file"
           multiple
-          accept=".pdf,.docx,.xlsx,.csv,.txt,.md"
+          accept=".pdf,.docx,.xlsx,.csv,.txt,.md,.eml,.mbox"
           onChange={handleFileSelect}
           className="hidden"
         />
@@ -278,7 +281,7 @@ export const DocumentUploader: React.FC<DocumentUploaderProps> = ({
           Drag & drop files here or click to browse
         </p>
         <p className="text-neutral-500 text-sm">
-          Supported: PDF, DOCX, XLSX, CSV, TXT, MD
+          Supported: PDF, DOCX, XLSX, CSV, TXT, MD, EML, MBOX
         </p>
       </div>
 
@@ -1,4 +1,4 @@
-import { useState } from "react";
+import { useState, type ReactNode } from "react";
 import {
   Copy,
   RotateCcw,
@@ -15,13 +15,20 @@ import {
 import { cn } from "../../../utils/cn";
 import { MarkdownRenderer } from "./MarkdownRenderer";
 import { TypewriterText } from "./TypewriterText";
-import type { ChatMessage, RagQueryResult, TemplateMatch } from "../types";
+import type {
+  ChatMessage,
+  RagQueryResult,
+  ResultSnippet,
+  TemplateMatch,
+} from "../types";
 // import { isLowConfidenceSources } from "../ragChatUtils";
 
 interface MessageItemProps {
   message: ChatMessage;
   isLatest?: boolean;
   onRegenerate: (query: string) => void;
+  /** Answer a cached message's query again without the answer cache */
+  onRefreshCached?: (query: string) => void;
   /** Regenerate with a specific template (for DB collections) */
   onRegenerateWithTemplate?: (
     query: string,
@@ -37,6 +44,7 @@ export function MessageItem({
   message,
   isLatest,
   onRegenerate,
+  onRefreshCached,
   onRegenerateWithTemplate,
   onCopy,
   copiedId,
@@ -121,6 +129,22 @@ export function MessageItem({
               </button>
             </div>
 
+            {message.cached && (
+              <div className="flex items-center gap-2 pt-3">
+                <span className="px-2 py-1 rounded-md bg-app-card border border-app-border/40 text-[10px] font-bold text-app-subtext uppercase">
+                  Cached answer
+                </span>
+                {message.query && onRefreshCached && (
+                  <button
+                    onClick={() => onRefreshCached(message.query!)}
+                    className="flex items-center gap-1 text-[10px] font-bold text-app-subtext hover:text-app-accent px-2 py-1 rounded-md transition-all border border-transparent hover:border-app-border/40">
+                    <RotateCcw className="w-3 h-3" />
+                    <span>Refresh</span>
+                  </button>
+                )}
+              </div>
+            )}
+
             {/* Telemetry/Metatdata */}
             {message.telemetry && (
               <div className="flex flex-wrap items-center gap-3 pt-4 border-t border-app-border/10">
@@ -367,12 +391,35 @@ function SourceCard({
         </div>
       </div>
       <p className="text-[11px] text-app-subtext italic line-clamp-3 leading-relaxed whitespace-pre-wrap px-2 border-l-2 border-app-border/40">
-        "{source.content}"
+        "{source.snippet ? <SnippetText snippet={source.snippet} /> : source.content}"
       </p>
     </div>
   );
 }
 
+/** Snippet text with its keyword and closest-sentence ranges marked */
+function SnippetText({ snippet }: { snippet: ResultSnippet }) {
+  const parts: ReactNode[] = [];
+  let cursor = 0;
+  snippet.highlights.forEach((highlight, i) => {
+    if (highlight.start < cursor) return;
+    parts.push(snippet.text.slice(cursor, highlight.start));
+    parts.push(
+      <mark
+        key={i}
+        className={cn(
+          "not-italic rounded-sm px-0.5 text-app-text",
+          highlight.kind === "term" ? "bg-amber-400/30" : "bg-primary/15",
+        )}>
+        {snippet.text.slice(highlight.start, highlight.end)}
+      </mark>,
+    );
+    cursor = highlight.end;
+  });
+  parts.push(snippet.text.slice(cursor));
+  return <>{parts}</>;
+}
+
 /** Source Modal for viewing full source content */
 function SourceModal(props: {
   source: RagQueryResult;
@@ -10,6 +10,7 @@ interface RagChatMessagesProps {
   isLoading: boolean;
   selectedCollectionId: number | null;
   onRegenerate: (query: string) => void;
+  onRefreshCached?: (query: string) => void;
   onRegenerateWithTemplate?: (query: string, templateId: number) => void;
 }
 
@@ -19,6 +20,7 @@ export function RagChatMessages({
   isLoading,
   selectedCollectionId,
   onRegenerate,
+  onRefreshCached,
   onRegenerateWithTemplate,
 }: RagChatMessagesProps) {
   const [copiedId, setCopiedId] = useState<string | null>(null);
@@ -102,6 +104,7 @@ export function RagChatMessages({
               message={message}
               isLatest={index === messages.length - 1 && !typedIds.has(message.id)}
               onRegenerate={onRegenerate}
+              onRefreshCached={onRefreshCached}
               onRegenerateWithTemplate={onRegenerateWithTemplate}
               onCopy={handleCopy}
               copiedId={copiedId}
@@ -1,14 +1,76 @@
-import { FileText, Trash2 } from "lucide-react";
-import type { RagDocument } from "../types";
+import { useEffect, useState } from "react";
+import { FileText, Loader2, RotateCw, Trash2 } from "lucide-react";
+import type { DocumentPreview, RagDocument } from "../types";
+import { getRagDocumentPreview } from "../api";
 import { getFileIcon } from "../ragTabUtils";
 import AnimatedContainer from "../../../shared/components/AnimatedContainer";
 
 type Props = {
   documents: RagDocument[];
   onDeleteDocument: (id: number) => void;
+  onResumeImports: () => void;
+  isResumingImports: boolean;
 };
 
-export function RagTabDocumentsGrid({ documents, onDeleteDocument }: Props) {
+function useDocumentPreview(documentId: number) {
+  const [preview, setPreview] = useState<DocumentPreview | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+    getRagDocumentPreview(documentId)
+      .then((result) => {
+        if (!cancelled) setPreview(result);
+      })
+      .catch((err) => console.error("Failed to load document preview:", err));
+    return () => {
+      cancelled = true;
+    };
+  }, [documentId]);
+
+  return preview;
+}
+
+function DocumentCardPreview({ document }: { document: RagDocument }) {
+  const preview = useDocumentPreview(document.id);
+  if (!preview || (!preview.thumbnail && !preview.title && !preview.author)) {
+    return null;
+  }
+
+  return (
+    <div className="mb-4 flex gap-3">
+      {preview.thumbnail && (
+        <img
+          src={preview.thumbnail}
+          alt={`First page of ${document.file_name}`}
+          width={preview.thumbnail_width ?? undefined}
+          height={preview.thumbnail_height ?? undefined}
+          className="w-16 h-auto rounded-md border border-app-border/50 bg-white object-contain shrink-0"
+        />
+      )}
+      <div className="min-w-0 text-sm">
+        {preview.title && (
+          <div className="font-medium text-app-text line-clamp-2" title={preview.title}>
+            {preview.title}
+          </div>
+        )}
+        {preview.author && (
+          <div className="text-xs text-app-text-muted truncate mt-1" title={preview.author}>
+            {preview.author}
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
+
+export function RagTabDocumentsGrid({
+  documents,
+  onDeleteDocument,
+  onResumeImports,
+  isResumingImports,
+}: Props) {
+  const failedImports = documents.filter((document) => document.ingestion_state === "failed").length;
+
   if (documents.length === 0) {
     return (
       <AnimatedContainer animation="fadeIn">
@@ -34,6 +96,19 @@ export function RagTabDocumentsGrid({ documents, onDeleteDocument }: Props) {
             {documents.length} {documents.length === 1 ? "document" : "documents"} stored in this collection
           </p>
         </div>
+        {failedImports > 0 && (
+          <button
+            onClick={() => void onResumeImports()}
+            disabled={isResumingImports}
+            className="inline-flex items-center gap-2 px-3 py-1.5 rounded-md border border-app-border/50 text-sm text-app-text hover:border-app-accent/50 transition-colors disabled:opacity-50">
+            {isResumingImports ? (
+              <Loader2 className="w-4 h-4 animate-spin" />
+            ) : (
+              <RotateCw className="w-4 h-4" />
+            )}
+            Resume {failedImports} failed {failedImports === 1 ? "import" : "imports"}
+          </button>
+        )}
       </div>
       <div className="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-6">
         {documents.map((document) => (
@@ -63,7 +138,21 @@ export function RagTabDocumentsGrid({ documents, onDeleteDocument }: Props) {
               </div>
             </div>
 
+            <DocumentCardPreview document={document} />
+
             <div className="space-y-2.5">
+              {document.ingestion_state !== "complete" && (
+                <div className="flex items-center justify-between text-sm">
+                  <span className="text-app-text-muted/70">Import</span>
+                  <span
+                    className="font-medium text-red-500 capitalize"
+                    title={document.ingestion_error ?? undefined}>
+                    {document.ingestion_state === "failed"
+                      ? `Failed while ${document.ingestion_failed_at ?? "pending"}`
+                      : document.ingestion_state}
+                  </span>
+                </div>
+              )}
               <div className="flex items-center justify-between text-sm">
                 <span className="text-app-text-muted/70">Pages</span>
                 <span className="font-medium text-app-text">{document.total_pages}</span>
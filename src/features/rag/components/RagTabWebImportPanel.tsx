@@ -1,5 +1,6 @@
 import { ArrowRight, Globe, Info, Loader2, X } from "lucide-react";
 import type { WebCrawlMode } from "../api";
+import type { WebCaptureLogin } from "../hooks/ragTab/useRagImports";
 
 type Props = {
   webUrl: string;
@@ -10,6 +11,8 @@ type Props = {
   onChangeMaxDepth: (v: number) => void;
   webCrawlMode: WebCrawlMode;
   onChangeWebCrawlMode: (v: WebCrawlMode) => void;
+  webLogin: WebCaptureLogin;
+  onChangeWebLogin: (v: WebCaptureLogin) => void;
   isCrawling: boolean;
   onStart: () => void;
   onClose: () => void;
@@ -26,6 +29,8 @@ export function RagTabWebImportPanel(props: Props) {
     onChangeMaxDepth,
     webCrawlMode,
     onChangeWebCrawlMode,
+    webLogin,
+    onChangeWebLogin,
     isCrawling,
     onStart,
     onClose,
@@ -101,6 +106,35 @@ export function RagTabWebImportPanel(props: Props) {
         </div>
       </div>
 
+      {webCrawlMode === "ocr" && (
+        <div className="mt-4">
+          <label className="text-[10px] text-app-subtext block mb-2 uppercase tracking-wider">
+            Login (optional)
+          </label>
+          <div className="grid grid-cols-1 md:grid-cols-3 gap-3">
+            <input
+              value={webLogin.qaSessionId}
+              onChange={(e) => onChangeWebLogin({ ...webLogin, qaSessionId: e.target.value })}
+              placeholder="QA session ID (reuse recorder login)"
+              className="w-full bg-app-bg border border-app-border rounded-md px-3 py-2 text-sm outline-none focus:border-app-accent focus:ring-2 focus:ring-app-accent/20 transition-all"
+            />
+            <input
+              value={webLogin.cookies}
+              onChange={(e) => onChangeWebLogin({ ...webLogin, cookies: e.target.value })}
+              placeholder="Cookies: session=abc; theme=dark"
+              className="w-full bg-app-bg border border-app-border rounded-md px-3 py-2 text-sm outline-none focus:border-app-accent focus:ring-2 focus:ring-app-accent/20 transition-all"
+            />
+            <input
+              type="password"
+              value={webLogin.authorization}
+              onChange={(e) => onChangeWebLogin({ ...webLogin, authorization: e.target.value })}
+              placeholder="Authorization header"
+              className="w-full bg-app-bg border border-app-border rounded-md px-3 py-2 text-sm outline-none focus:border-app-accent focus:ring-2 focus:ring-app-accent/20 transition-all"
+            />
+          </div>
+        </div>
+      )}
+
       <div className="mt-4 flex flex-wrap items-center justify-between gap-3">
         <div className="flex items-center gap-2 text-xs text-app-text-muted">
           <Info className="w-3.5 h-3.5" />
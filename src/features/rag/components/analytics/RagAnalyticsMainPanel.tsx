@@ -125,7 +125,7 @@ export function RagAnalyticsMainPanel(props: Props) {
             <div className="text-sm text-app-subtext">
               Select a collection to see per-document diagnostics and retrieval gaps.
             </div>
-            <div className="mt-4 grid grid-cols-2 md:grid-cols-4 gap-4">
+            <div className="mt-4 grid grid-cols-2 md:grid-cols-5 gap-4">
               <div className="bg-app-bg/30 rounded-lg border border-app-border p-4">
                 <div className="text-[10px] font-bold text-app-subtext uppercase tracking-wider">Events</div>
                 <div className="text-2xl font-mono font-bold text-app-text">
@@ -150,6 +150,12 @@ export function RagAnalyticsMainPanel(props: Props) {
                   {analyticsSummary ? fmtPct(analyticsSummary.success_rate) : "—"}
                 </div>
               </div>
+              <div className="bg-app-bg/30 rounded-lg border border-app-border p-4">
+                <div className="text-[10px] font-bold text-app-subtext uppercase tracking-wider">Cached Answers</div>
+                <div className="text-2xl font-mono font-bold text-app-text">
+                  {analyticsSummary ? analyticsSummary.answer_cache_hits : "—"}
+                </div>
+              </div>
             </div>
           </div>
         ) : (
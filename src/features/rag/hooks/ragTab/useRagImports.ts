@@ -2,6 +2,8 @@ import { useCallback, useEffect, useMemo, useState } from "react";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { open } from "@tauri-apps/plugin-dialog";
 import { importRagFile, importRagWeb } from "../../api";
+import type { WebCaptureAuth } from "../../types";
+import { formatAppError } from "../../../../utils/appError";
 import {
   getFileExtension,
   getStatusMessage,
@@ -12,6 +14,34 @@ import {
 
 type WebCrawlMode = "html" | "ocr";
 
+/** Login fields of the web import panel, used by OCR captures */
+export type WebCaptureLogin = {
+  qaSessionId: string;
+  /** `Cookie` header form: `name=value; other=value` */
+  cookies: string;
+  authorization: string;
+};
+
+const EMPTY_WEB_LOGIN: WebCaptureLogin = { qaSessionId: "", cookies: "", authorization: "" };
+
+function toCaptureAuth(login: WebCaptureLogin): WebCaptureAuth | undefined {
+  const cookies = login.cookies
+    .split(";")
+    .map((pair) => pair.trim())
+    .filter((pair) => pair.indexOf("=") > 0)
+    .map((pair) => {
+      const split = pair.indexOf("=");
+      return { name: pair.slice(0, split).trim(), value: pair.slice(split + 1).trim() };
+    });
+  const auth: WebCaptureAuth = {
+    qa_session_id: login.qaSessionId.trim() || undefined,
+    cookies,
+    headers: login.authorization.trim() ? { Authorization: login.authorization.trim() } : {},
+  };
+  const empty = !auth.qa_session_id && cookies.length === 0 && !login.authorization.trim();
+  return empty ? undefined : auth;
+}
+
 type UseRagImportsArgs = {
   selectedCollectionId: number | null;
   loadDocuments: (collectionId: number) => Promise<void>;
@@ -33,6 +63,7 @@ export function useRagImports({
   const [isCrawling, setIsCrawling] = useState(false);
   const [showWebImport, setShowWebImport] = useState(false);
   const [webCrawlMode, setWebCrawlMode] = useState<WebCrawlMode>("html");
+  const [webLogin, setWebLogin] = useState<WebCaptureLogin>(EMPTY_WEB_LOGIN);
 
   const isImporting = useMemo(
     () =>
@@ -145,12 +176,7 @@ export function useRagImports({
           );
         }, 5000);
       } catch (err) {
-        const fallbackMessage =
-          err instanceof Error
-            ? err.message
-            : typeof err === "string"
-              ? err
-              : JSON.stringify(err);
+        const fallbackMessage = formatAppError(err);
         console.error("Failed to import file:", err);
         setImportProgress({
           status: "error",
@@ -238,11 +264,13 @@ export function useRagImports({
         maxPages,
         maxDepth,
         webCrawlMode,
+        webCrawlMode === "ocr" ? toCaptureAuth(webLogin) : undefined,
       );
       setWebUrl("");
       setMaxPages(10);
       setMaxDepth(2);
       setWebCrawlMode("html");
+      setWebLogin(EMPTY_WEB_LOGIN);
       setShowWebImport(false);
       setImportProgress({
         status: "complete",
@@ -260,7 +288,7 @@ export function useRagImports({
     } finally {
       setIsCrawling(false);
     }
-  }, [loadDocuments, maxDepth, maxPages, selectedCollectionId, webCrawlMode, webUrl]);
+  }, [loadDocuments, maxDepth, maxPages, selectedCollectionId, webCrawlMode, webLogin, webUrl]);
 
   useEffect(() => {
     let unlisten: (() => void) | null = null;
@@ -303,6 +331,8 @@ export function useRagImports({
     setMaxDepth,
     webCrawlMode,
     setWebCrawlMode,
+    webLogin,
+    setWebLogin,
     isCrawling,
     handleWebImport,
   };
@@ -222,6 +222,7 @@ export function useFreeChat(input: {
           config: buildConfig({ maxTokens: DEFAULT_MAX_TOKENS, temperature: DEFAULT_TEMPERATURE }),
           content: contentWithHistory,
           system_prompt: DEFAULT_SYSTEM_PROMPT,
+          feature: "ragChat",
         });
 
         const assistantMessage = createAssistantMessage(llmResponse.result, query);
@@ -1,4 +1,5 @@
 import { useEffect, useState } from "react";
+import { onProjectProfilePickedUp } from "../../settings/api/projectProfiles";
 import type { AnswerLanguage } from "../ragChatUtils";
 
 export function useRagPersistedSettings() {
@@ -52,6 +53,16 @@ export function useRagPersistedSettings() {
     }
   }, []);
 
+  useEffect(
+    () =>
+      onProjectProfilePickedUp((profile) => {
+        if (profile.ragCollectionId !== null) {
+          setSelectedCollectionId(profile.ragCollectionId);
+        }
+      }),
+    [],
+  );
+
   useEffect(() => {
     if (selectedCollectionId) {
       localStorage.setItem(
@@ -4,13 +4,21 @@ import {
   dbQueryRagWithTemplate,
   ragQuery,
   ragChatWithContext,
+  ragAnswerWithTools,
+  ragFindCachedAnswer,
+  ragCacheAnswer,
   getConversationMessages,
   recordRetrievalGap,
   submitTemplateFeedback,
 } from "../api";
-import type { ChatMessage, RagQueryResult } from "../types";
+import type { ChatMessage, RagQueryResult, RagToolCallRecord } from "../types";
 import type { ConversationMessage } from "../api";
 import { hashQueryToHex, planRagPrompt } from "../ragChatUtils";
+import {
+  DEFAULT_RAG_TOOL_SETTINGS,
+  getSettings,
+  type RagToolSettings,
+} from "../../settings/api/settings";
 
 import type { LlmConfig, LlmResponse } from "../../../shared/api/apiClient";
 import type { LlmConfigOverrides } from "../../../shared/api/llmConfig";
@@ -32,6 +40,22 @@ const DEFAULT_TEMPERATURE = 0.2;
 // Helper Functions
 // ============================================================================
 
+/**
+ * Loads the tool-calling settings; tools stay off when they cannot be read
+ */
+async function loadRagToolSettings(): Promise<RagToolSettings> {
+  try {
+    const document = await getSettings();
+    return {
+      ...DEFAULT_RAG_TOOL_SETTINGS,
+      ...(document.sections.ragTools as Partial<RagToolSettings>),
+    };
+  } catch (err) {
+    console.error("Failed to load RAG tool settings:", err);
+    return DEFAULT_RAG_TOOL_SETTINGS;
+  }
+}
+
 /**
  * Processes a DB collection query and returns the assistant message with sources
  */
@@ -165,6 +189,7 @@ async function processFileRagQuery(
   enhanceAsync: EnhanceAsync,
   buildConfig: (overrides?: LlmConfigOverrides) => LlmConfig,
   conversationId?: number | null,
+  bypassCache = false,
 ): Promise<{ message: ChatMessage; sourceIds?: number[] }> {
   // Load conversation history if available
   let conversationMessages: ConversationMessage[] | undefined;
@@ -175,9 +200,54 @@ async function processFileRagQuery(
       console.error("Failed to load conversation messages:", err);
     }
   }
+  const hasHistory = !!conversationMessages && conversationMessages.length > 0;
+
+  // Determine effective model
+  const effectiveModel =
+    isLocalProvider && localModels.length > 0
+      ? localModels.includes(model)
+        ? model
+        : localModels[0]
+      : model;
+  const toolSettings = await loadRagToolSettings();
+
+  // Answers that depend on earlier messages or on tool calls are not cached
+  const cacheLookup =
+    hasHistory || toolSettings.enabled
+      ? null
+      : await ragFindCachedAnswer({
+          collection_id: collectionId,
+          query,
+          top_k: Math.max(1, topK),
+          provider,
+          model: effectiveModel,
+          language: answerLanguage,
+          variant: `chat:${strictRagMode ? "strict" : "default"}:${candidateK}:${rerankK}`,
+          bypass_cache: bypassCache,
+        }).catch((err) => {
+          console.error("Failed to read the answer cache:", err);
+          return null;
+        });
+
+  if (cacheLookup?.cached) {
+    const { answer, results } = cacheLookup.cached;
+    const hasSources = results.length > 0;
+    return {
+      message: {
+        id: (Date.now() + 1).toString(),
+        type: "assistant",
+        content: answer,
+        timestamp: new Date(),
+        sources: hasSources ? results : undefined,
+        query,
+        cached: true,
+      },
+      sourceIds: hasSources ? results.map((r: RagQueryResult) => r.source_id) : undefined,
+    };
+  }
 
   // Use chat with context API if we have conversation history
-  const response = conversationMessages && conversationMessages.length > 0
+  const response = hasHistory
     ? await ragChatWithContext({
         collection_id: collectionId,
         query,
@@ -226,31 +296,49 @@ async function processFileRagQuery(
     }).catch((err) => console.error("Failed to record retrieval gap:", err));
   }
 
-  // Determine effective model
-  const effectiveModel =
-    isLocalProvider && localModels.length > 0
-      ? localModels.includes(model)
-        ? model
-        : localModels[0]
-      : model;
-
   const configOverrides: LlmConfigOverrides = {
     maxTokens: DEFAULT_MAX_TOKENS,
     temperature: DEFAULT_TEMPERATURE,
     ...(effectiveModel !== model && { model: effectiveModel }),
   };
 
-  const llmResponse = await enhanceAsync({
-    config: buildConfig(configOverrides),
-    content: plan.promptContent,
-    system_prompt: plan.systemPrompt,
-  });
+  // The backend retries answers that come back in another language
+  const outputLanguage = answerLanguage === "id" ? "Indonesian" : "English";
+
+  // With tools enabled the backend runs the model and any API calls it asks for
+  let answer: string;
+  let toolCalls: RagToolCallRecord[] | undefined;
+  if (toolSettings.enabled) {
+    const toolAnswer = await ragAnswerWithTools({
+      config: buildConfig(configOverrides),
+      system_prompt: plan.systemPrompt,
+      prompt: plan.promptContent,
+      output_language: outputLanguage,
+    });
+    answer = toolAnswer.answer;
+    toolCalls = toolAnswer.tool_calls.length > 0 ? toolAnswer.tool_calls : undefined;
+  } else {
+    const llmResponse = await enhanceAsync({
+      config: buildConfig(configOverrides),
+      content: plan.promptContent,
+      system_prompt: plan.systemPrompt,
+      feature: "ragChat",
+      output_language: outputLanguage,
+    });
+    answer = llmResponse.result;
+  }
 
-  const cleanedAnswer = llmResponse.result
+  const cleanedAnswer = answer
     .replace(/\[Source:[^\]]+\]/g, "")
     .trim();
 
   const results = response.results;
+  if (cacheLookup?.slot) {
+    ragCacheAnswer(cacheLookup.slot, cleanedAnswer, results).catch((err) =>
+      console.error("Failed to cache the answer:", err),
+    );
+  }
+
   const hasSources = results.length > 0;
   const message: ChatMessage = {
     id: (Date.now() + 1).toString(),
@@ -259,6 +347,7 @@ async function processFileRagQuery(
     timestamp: new Date(),
     sources: hasSources ? results : undefined,
     query,
+    toolCalls,
   };
 
   const sourceIds = hasSources ? results.map((r: RagQueryResult) => r.source_id) : undefined;
@@ -339,8 +428,9 @@ export function useRagSend(input: {
 
   const [isLoading, setIsLoading] = useState(false);
 
+  /** `bypassCache` generates a new answer even when a cached one exists */
   const sendMessage = useCallback(
-    async (rawQuery: string) => {
+    async (rawQuery: string, options?: { bypassCache?: boolean }) => {
       const query = rawQuery.trim();
       if (!query || !selectedCollectionId) return;
 
@@ -378,6 +468,7 @@ export function useRagSend(input: {
             enhanceAsync,
             buildConfig,
             conversationId,
+            options?.bypassCache,
           );
           assistantMessage = result.message;
           sourceIds = result.sourceIds;
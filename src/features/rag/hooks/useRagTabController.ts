@@ -12,6 +12,7 @@ import {
   listRagCollections,
   listRagDocuments,
   ragCreateDbCollection,
+  resumeFailedIngestions,
 } from "../api";
 import { useRagImports } from "./ragTab/useRagImports";
 
@@ -195,6 +196,8 @@ export function useRagTabController(navigate: NavigateFunction) {
     setMaxDepth,
     webCrawlMode,
     setWebCrawlMode,
+    webLogin,
+    setWebLogin,
     isCrawling,
     handleWebImport,
   } = useRagImports({ selectedCollectionId, loadDocuments });
@@ -311,6 +314,24 @@ export function useRagTabController(navigate: NavigateFunction) {
     [loadDocuments, selectedCollectionId]
   );
 
+  const [isResumingImports, setIsResumingImports] = useState(false);
+  const handleResumeImports = useCallback(async () => {
+    if (selectedCollectionId === null) return;
+    setIsResumingImports(true);
+    try {
+      const report = await resumeFailedIngestions(selectedCollectionId);
+      if (report.failed.length > 0) {
+        alert(`${report.failed.length} imports failed again; see the logs for details.`);
+      }
+    } catch (err) {
+      console.error("Failed to resume imports:", err);
+      alert("Failed to resume imports: " + (err as Error).message);
+    } finally {
+      setIsResumingImports(false);
+      await loadDocuments(selectedCollectionId);
+    }
+  }, [loadDocuments, selectedCollectionId]);
+
   const gotoDbConnections = useCallback(() => {
     navigate("/database");
   }, [navigate]);
@@ -357,6 +378,8 @@ export function useRagTabController(navigate: NavigateFunction) {
 
     // docs
     handleDeleteDocument,
+    handleResumeImports,
+    isResumingImports,
 
     // imports
     handleFilePicker,
@@ -377,6 +400,8 @@ export function useRagTabController(navigate: NavigateFunction) {
     setMaxDepth,
     webCrawlMode,
     setWebCrawlMode,
+    webLogin,
+    setWebLogin,
     isCrawling,
     handleWebImport,
   };
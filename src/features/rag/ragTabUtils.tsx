@@ -8,7 +8,7 @@ import {
   Loader2,
 } from "lucide-react";
 
-export const SUPPORTED_EXTENSIONS = ["pdf", "docx", "xlsx", "csv", "txt", "web"];
+export const SUPPORTED_EXTENSIONS = ["pdf", "docx", "xlsx", "csv", "txt", "web", "eml", "mbox"];
 
 export type ImportStatus =
   | "idle"
@@ -9,6 +9,126 @@ export interface RagCollection {
   created_at: string;
 }
 
+export interface CollectionEncryptionStatus {
+  collection_id: number;
+  encrypted: boolean;
+  /** Chunks can be read and written; always true for plain collections */
+  unlocked: boolean;
+  encrypted_at: string | null;
+}
+
+export type SimilarityMetric = 'cosine' | 'dot' | 'euclidean';
+
+/** Precision chunk embeddings are stored at */
+export type EmbeddingQuantization = 'float32' | 'float16' | 'int8';
+
+export interface EmbeddingQuantizationReport {
+  collectionId: number;
+  quantization: EmbeddingQuantization;
+  converted: number;
+  unchanged: number;
+  /** Stored blobs that could not be decoded and were left as they are */
+  unreadable: number;
+  bytesBefore: number;
+  bytesAfter: number;
+  /** The database file was compacted to give the space back */
+  compacted: boolean;
+}
+
+/** Which documents a split takes; every condition set must hold */
+export interface DocumentFilter {
+  /** Without the dot, e.g. "pdf" */
+  fileTypes?: string[];
+  languages?: string[];
+  nameContains?: string | null;
+  /** Unix seconds, inclusive */
+  createdAfter?: number | null;
+  createdBefore?: number | null;
+  minQuality?: number | null;
+  maxQuality?: number | null;
+  /** Top-level metadata fields and the values they must equal */
+  meta?: Record<string, unknown>;
+}
+
+export interface CollectionTransferReport {
+  targetCollectionId: number;
+  sourceCollectionIds: number[];
+  movedDocuments: number;
+  movedChunks: number;
+  /** Left in place because a connector or git repository syncs them */
+  skippedSynced: number[];
+  /** Connectors and git repositories handed to the target on a merge */
+  movedSources: number;
+  sourceDeleted: boolean;
+}
+
+export interface CollectionSplit {
+  collection: RagCollection;
+  report: CollectionTransferReport;
+}
+
+export type IngestionStage = 'extract' | 'clean' | 'chunk' | 'embed' | 'index';
+
+export type PipelineHookAction =
+  | { type: 'script'; command: string; args?: string[] }
+  | { type: 'http'; url: string; headers?: Record<string, string> };
+
+export interface PipelineHook {
+  /** Hooks can run after extract, clean or chunk */
+  after: IngestionStage;
+  action: PipelineHookAction;
+  timeoutSecs?: number | null;
+  /** Keep the stage output and go on when the hook fails */
+  continueOnError?: boolean;
+}
+
+/** Ingestion stages and hooks of one files collection */
+export interface IngestionPipelineConfig {
+  clean: {
+    stripControlChars: boolean;
+    normalizeWhitespace: boolean;
+    removeRepeatedLines: boolean;
+    minLineChars: number | null;
+  };
+  chunk: {
+    strategy: 'fixed_size' | 'content_aware' | 'semantic' | null;
+    maxChunkSize: number | null;
+    overlap: number | null;
+    minChunkSize: number | null;
+  };
+  embed: {
+    enabled: boolean;
+    /** Metric the embedding model is trained for; searches compare with it */
+    metric: SimilarityMetric;
+    /** Precision new embeddings are stored at */
+    quantization: EmbeddingQuantization;
+  };
+  hooks: PipelineHook[];
+}
+
+export type AnalyzerLanguage =
+  | 'english'
+  | 'indonesian'
+  | 'spanish'
+  | 'french'
+  | 'german'
+  | 'portuguese';
+
+/** Keyword search settings of one collection */
+export interface CollectionLanguageConfig {
+  /** null keeps keyword search language neutral */
+  language: AnalyzerLanguage | null;
+  /** 'stemmed' needs a language */
+  analyzer: 'standard' | 'stemmed';
+  /** Drop the language's bundled stopwords */
+  stopwords: boolean;
+  extraStopwords: string[];
+  /** Match words with and without accents alike */
+  foldDiacritics: boolean;
+  /** Groups of interchangeable terms or phrases */
+  synonyms: string[][];
+}
+
 export interface RagDocument {
   id: number;
   collection_id: number | null;
@@ -22,6 +142,47 @@ export interface RagDocument {
   chunk_count: number;               // Total number of chunks
   warning_count: number;             // Number of quality warnings
   created_at: string;
+  ingestion_state: IngestionState;
+  ingestion_failed_at: IngestionState | null; // Stage a failed import stopped in
+  ingestion_error: string | null;
+}
+
+export type IngestionState =
+  | "pending"
+  | "extracting"
+  | "chunking"
+  | "embedding"
+  | "complete"
+  | "failed";
+
+export interface IngestionResumeReport {
+  resumed: number[];                 // Documents whose import completed
+  failed: number[];                  // Documents that failed again
+}
+
+export interface WorkbookSheet {
+  name: string;
+  index: number;                     // Position in the workbook, from 0
+  hidden: boolean;
+  rows: number;
+  columns: number;
+  merged_regions: number;            // Filled with their top-left value
+  formula_cells: number;             // Imported as the values Excel computed
+}
+
+export interface ExcelImportOptions {
+  sheets?: string[];                 // Empty imports every visible sheet with data
+  include_hidden?: boolean;
+}
+
+export interface DocumentPreview {
+  document_id: number;
+  title: string | null;
+  author: string | null;
+  page_count: number;
+  thumbnail: string | null;          // data:image/jpeg URL of the first page (PDF only)
+  thumbnail_width: number | null;
+  thumbnail_height: number | null;
 }
 
 export interface RagDocumentChunk {
@@ -70,6 +231,8 @@ export interface RagQueryRequest {
   enable_few_shot?: boolean;
   /** Language for conversational responses ('id', 'en', 'indonesia', 'english', etc.) */
   language?: string;
+  /** Run on this route instead of the one the query analyzer picks */
+  route?: QueryRoute;
 }
 
 export interface RagQueryResult {
@@ -80,17 +243,45 @@ export interface RagQueryResult {
   page_number: number | null;
   page_offset: number | null;
   doc_name: string | null;
+  /** Preview around what matched the query; absent for older results */
+  snippet?: ResultSnippet;
+}
+
+/** `term` marks a query keyword, `sentence` the sentence closest in meaning */
+export type HighlightKind = "term" | "sentence";
+
+export interface SnippetHighlight {
+  /** UTF-16 offsets into `ResultSnippet.text` */
+  start: number;
+  end: number;
+  kind: HighlightKind;
+}
+
+export interface ResultSnippet {
+  text: string;
+  highlights: SnippetHighlight[];
+  truncated: boolean;
 }
 
 export interface RagQueryResponse {
   prompt: string;
   results: RagQueryResult[];
+  /** Route the query ran on; null for column/value lookups */
+  route: QueryRoute | null;
+  /** `route` was forced by the request */
+  route_overridden: boolean;
+  /** Id to correct the route with; null when it could not be recorded */
+  routing_decision_id: number | null;
 }
 
 // Phase 6: Enhanced OCR and chunking
 export interface EnhancedOcrRequest {
   file_path: string;
   config?: OcrConfig;
+  /** Tesseract codes recognized together, e.g. ["eng", "jpn"]; saved as the document's hint */
+  languages?: string[];
+  /** Document whose language hint is used when `languages` is omitted */
+  document_id?: number;
 }
 
 export interface OcrPage {
@@ -124,11 +315,43 @@ export interface HybridRetrievalOptions {
   top_k?: number;
   use_cache?: boolean;
   optimized?: boolean;
+  /** Also return how each candidate scored */
+  explain?: boolean;
+}
+
+export interface CandidateExplanation {
+  source_type: string;
+  source_id: number;
+  doc_name: string | null;
+  keyword_score: number | null;
+  vector_similarity: number | null;
+  /** Expanded query that gave `vector_similarity` */
+  vector_query: string | null;
+  fused_score: number | null;
+  fusion_rank: number | null;
+  rerank_score: number | null;
+  /** Positions the reranker moved the candidate up; negative when down */
+  rerank_delta: number | null;
+  /** Null when the candidate didn't make it into the results */
+  final_rank: number | null;
+}
+
+export interface RetrievalExplanation {
+  route: string | null;
+  cache_hit: boolean | null;
+  keyword_method: 'fts' | 'bm25' | null;
+  expanded_queries: string[];
+  metric: SimilarityMetric | null;
+  keyword_weight: number | null;
+  vector_weight: number | null;
+  reranked: boolean;
+  candidates: CandidateExplanation[];
 }
 
 export interface HybridRetrievalResponse {
   results: RagQueryResult[];
   cache_hit: boolean;
+  explain?: RetrievalExplanation;
 }
 
 // Phase 7: Validation suite
@@ -144,6 +367,8 @@ export interface ValidationOptions {
   top_k: number;
   use_cache: boolean;
   optimized: boolean;
+  /** Also measure how much of each vector top-k survives this precision */
+  quantization?: EmbeddingQuantization | null;
 }
 
 export interface ValidationReport {
@@ -153,6 +378,8 @@ export interface ValidationReport {
   avg_chunking_quality: number;
   avg_extraction_accuracy: number;
   avg_latency_ms: number;
+  quantization: EmbeddingQuantization | null;
+  avg_quantization_recall: number | null;
   results: ValidationResult[];
 }
 
@@ -165,6 +392,7 @@ export interface ValidationResult {
   extraction_accuracy: number;
   latency_ms: number;
   cache_hit: boolean;
+  quantization_recall: number | null;
   issues: string[];
 }
 
@@ -199,6 +427,8 @@ export interface AnalyticsSummary {
   avg_retrieval_ms: number;
   avg_chat_ms: number;
   success_rate: number;
+  /** Chats answered from the answer cache, included in chat_count */
+  answer_cache_hits: number;
 }
 
 export type ChatMessageType = "user" | "assistant" | "system";
@@ -212,6 +442,22 @@ export interface ChatMessage {
   query?: string;
   /** Telemetry for DB collection queries */
   telemetry?: DbQueryTelemetry;
+  /** API calls the model made while answering */
+  toolCalls?: RagToolCallRecord[];
+  /** Served from the answer cache instead of a new retrieval and generation */
+  cached?: boolean;
+}
+
+/** API call the chat model made through the QA API tool */
+export interface RagToolCallRecord {
+  method: string;
+  url: string;
+  status: number | null;
+  duration_ms: number | null;
+  /** Why the call was refused or failed */
+  error: string | null;
+  /** QA API call that recorded the request */
+  api_call_id: string | null;
 }
 
 export interface RagWebImportRequest {
@@ -219,6 +465,229 @@ export interface RagWebImportRequest {
   url: string;
   max_pages?: number;
   max_depth?: number;
+  mode?: "html" | "ocr";
+  auth?: WebCaptureAuth;
+}
+
+export interface WebCaptureCookie {
+  name: string;
+  value: string;
+  /** Defaults to the captured URL's host */
+  domain?: string;
+  path?: string;
+  secure?: boolean;
+  http_only?: boolean;
+}
+
+/** Login for OCR captures of pages behind authentication; all given sources are combined. */
+export interface WebCaptureAuth {
+  /** Reuse the login the QA recorder saved in this session */
+  qa_session_id?: string;
+  /** Recorder run whose login to use; the latest one when omitted */
+  qa_run_id?: string;
+  /** Any other Playwright storage-state file */
+  storage_state_path?: string;
+  cookies?: WebCaptureCookie[];
+  headers?: Record<string, string>;
+}
+
+export type ConnectorKind = "notion" | "confluence";
+
+export interface RagConnector {
+  id: number;
+  name: string;
+  kind: ConnectorKind;
+  collection_id: number;
+  /** Confluence site, e.g. https://example.atlassian.net/wiki */
+  base_url: string | null;
+  /** Confluence Cloud account email; unset for Data Center tokens */
+  username: string | null;
+  /** Notion page to import below (all shared pages when unset), or the Confluence space key */
+  scope: string | null;
+  /** 0 syncs only on demand */
+  sync_interval_minutes: number;
+  enabled: boolean;
+  last_synced_at: number | null;
+  last_sync_status: "ok" | "partial" | "failed" | null;
+  last_sync_error: string | null;
+  created_at: number;
+}
+
+export interface RagConnectorInput {
+  name: string;
+  kind: ConnectorKind;
+  collection_id: number;
+  base_url?: string;
+  username?: string;
+  scope?: string;
+  sync_interval_minutes?: number;
+  enabled?: boolean;
+  /** Required when creating; updates keep the stored token without one */
+  token?: string;
+}
+
+export interface ConnectorPage {
+  connector_id: number;
+  external_id: string;
+  parent_external_id: string | null;
+  title: string;
+  /** Titles from the top of the page tree down to the page, joined by " / " */
+  path: string;
+  url: string | null;
+  last_edited_at: number;
+  doc_id: number | null;
+  synced_at: number;
+}
+
+export interface ConnectorSyncReport {
+  connector_id: number;
+  added: number;
+  updated: number;
+  unchanged: number;
+  removed: number;
+  failed: number;
+  errors: string[];
+  started_at: number;
+  finished_at: number;
+}
+
+export interface WritingMemorySyncReport {
+  collectionId: number;
+  added: number;
+  /** Entries already in the collection */
+  skipped: number;
+  failed: number;
+  errors: string[];
+}
+
+export interface GitRepoSource {
+  id: number;
+  name: string;
+  collection_id: number;
+  /** Remote URL (cloned into app data) or absolute path of a local repository */
+  url: string;
+  /** The remote's default branch or the local HEAD when unset */
+  branch: string | null;
+  /** Only files below this directory are imported */
+  subdirectory: string | null;
+  /** 0 syncs only on demand */
+  sync_interval_minutes: number;
+  enabled: boolean;
+  /** Commit of the last successful sync */
+  last_commit: string | null;
+  last_synced_at: number | null;
+  last_sync_status: "ok" | "partial" | "failed" | null;
+  last_sync_error: string | null;
+  created_at: number;
+}
+
+export interface GitRepoInput {
+  name: string;
+  collection_id: number;
+  url: string;
+  branch?: string;
+  subdirectory?: string;
+  sync_interval_minutes?: number;
+  enabled?: boolean;
+}
+
+export interface GitRepoFile {
+  repo_id: number;
+  path: string;
+  /** Language the file was chunked as, e.g. "rust" or "markdown" */
+  language: string;
+  blob_sha: string;
+  commit_sha: string;
+  doc_id: number | null;
+  chunk_count: number;
+  synced_at: number;
+}
+
+export interface GitSyncReport {
+  repo_id: number;
+  commit_sha: string;
+  previous_commit: string | null;
+  added: number;
+  updated: number;
+  unchanged: number;
+  removed: number;
+  /** Binary, too large, vendored or of an unknown type */
+  skipped: number;
+  failed: number;
+  errors: string[];
+  started_at: number;
+  finished_at: number;
+}
+
+export type ImportTemplateField = "category" | "source" | "title" | "created_at";
+
+export interface ImportColumnMapping {
+  /** Header of the column, as in the file */
+  column: string;
+  field?: ImportTemplateField | null;
+  /** Type the values are coerced to; values stay text when unset */
+  coerce?: "int" | "float" | "date" | "bool" | "text" | null;
+  /** chrono format of a date column, e.g. "%d.%m.%Y"; common formats are tried when unset */
+  date_format?: string | null;
+  /** Left out of the stored rows */
+  skip?: boolean;
+}
+
+export interface ImportTemplate {
+  id: number;
+  name: string;
+  /** Files imported into any collection match when unset */
+  collection_id: number | null;
+  /** Normalized headers a file's header row must have */
+  signature: string[];
+  /** Non-blank rows above the header */
+  skip_rows: number;
+  /** Rows below the data (totals, footers) */
+  skip_footer_rows: number;
+  columns: ImportColumnMapping[];
+  applied_count: number;
+  last_applied_at: number | null;
+  created_at: number;
+  updated_at: number;
+}
+
+export interface ImportTemplateInput {
+  name: string;
+  collection_id?: number | null;
+  skip_rows?: number;
+  skip_footer_rows?: number;
+  columns: ImportColumnMapping[];
+}
+
+export interface EmailMessageRecord {
+  id: number;
+  doc_id: number;
+  /** 1-based position in the file; the page number of its chunks */
+  message_index: number;
+  message_id: string;
+  /** Message id of the first message of the thread */
+  thread_id: string;
+  in_reply_to: string | null;
+  subject: string;
+  from_address: string | null;
+  from_name: string | null;
+  /** To and Cc, comma-separated */
+  recipients: string;
+  /** Unix seconds */
+  sent_at: number | null;
+  /** Attachment file names, comma-separated */
+  attachments: string;
+  chunk_count: number;
+}
+
+export interface EmailFilter {
+  /** Part of the sender's address or name */
+  sender?: string;
+  /** Unix seconds */
+  sent_after?: number;
+  /** Unix seconds */
+  sent_before?: number;
+  thread_id?: string;
 }
 
 export interface LogEntry {
@@ -245,6 +714,16 @@ export interface RetrievalConfig {
   reranking_enabled: boolean;
   min_relevance_score: number;
   query_expansion_enabled: boolean;
+  /** Leave chunks tagged as containing PII out of LLM context */
+  exclude_sensitive_chunks: boolean;
+  /** Return the parent section around each matched chunk instead of the chunk alone */
+  parent_context_enabled: boolean;
+  /** Token budget of the section returned for each matched chunk */
+  parent_context_max_tokens: number;
+  /** Try feedback-driven parameter changes on part of the queries and keep the better ones */
+  auto_tune: boolean;
+  /** Thresholds that route queries, refit from route corrections */
+  routing?: RoutingThresholds;
 }
 
 export interface EmbeddingConfig {
@@ -252,6 +731,10 @@ export interface EmbeddingConfig {
   dimension: number;
   api_endpoint: string;
   batch_size: number;
+  /** Embedding requests in flight at once during ingestion */
+  concurrency: number;
+  /** Request budget per minute; 0 uses the provider's default */
+  requests_per_minute: number;
   timeout_ms: number;
 }
 
@@ -261,6 +744,8 @@ export interface OcrConfig {
   preprocessing_enabled: boolean;
   preprocessing_mode: string;
   min_confidence: number;
+  /** Keep columns in reading order and turn aligned cells into markdown tables */
+  layout_analysis: boolean;
 }
 
 export interface CacheConfig {
@@ -269,6 +754,12 @@ export interface CacheConfig {
   retrieval_cache_size: number;
   retrieval_cache_ttl_secs: number;
   enabled: boolean;
+  /** Keep text retrieval results in rag_sense.db across restarts */
+  persist_retrieval_cache: boolean;
+  persisted_retrieval_cache_ttl_secs: number;
+  /** Reuse generated answers to repeated questions until the collection changes */
+  answer_cache: boolean;
+  answer_cache_ttl_secs: number;
 }
 
 export interface ChatConfig {
@@ -280,6 +771,17 @@ export interface ChatConfig {
   feedback_enabled: boolean;
 }
 
+export type PiiMode = "off" | "tag" | "redact";
+
+export interface PiiConfig {
+  /** What ingestion does with detected PII */
+  mode: PiiMode;
+  detect_emails: boolean;
+  detect_phone_numbers: boolean;
+  detect_national_ids: boolean;
+  detect_credit_cards: boolean;
+}
+
 export interface RagConfig {
   chunking: ChunkingConfig;
   retrieval: RetrievalConfig;
@@ -287,6 +789,7 @@ export interface RagConfig {
   ocr: OcrConfig;
   cache: CacheConfig;
   chat: ChatConfig;
+  pii: PiiConfig;
 }
 
 export interface ConfigValidation {
@@ -326,6 +829,121 @@ export interface FeedbackStats {
   positive_rate: number;
 }
 
+// Retrieval tuning
+export interface RetrievalParams {
+  top_k: number;
+  vector_weight: number;
+  keyword_weight: number;
+  reranking_enabled: boolean;
+}
+
+export interface TuningOptions {
+  /** Ratings a parameter value or experiment arm needs before it is judged */
+  min_samples?: number;
+  /** Difference in negative rate that counts as better or worse */
+  min_improvement?: number;
+}
+
+export type TunedParameter = "top_k" | "fusion_weights" | "reranking";
+
+export interface FeedbackCounts {
+  total: number;
+  negative: number;
+  negative_rate: number;
+}
+
+export interface ParameterBucket extends FeedbackCounts {
+  parameter: TunedParameter;
+  value: string;
+  is_current: boolean;
+}
+
+export interface CollectionFeedback extends FeedbackCounts {
+  collection_id: number;
+  quality: number | null;
+  /** Quality is too low for parameter changes to help */
+  needs_reingest: boolean;
+}
+
+export interface ParameterChange {
+  parameter: TunedParameter;
+  from: string;
+  to: string;
+  reason: string;
+}
+
+export interface TuningProposal {
+  candidate: RetrievalParams;
+  changes: ParameterChange[];
+}
+
+export type ExperimentStatus = "running" | "promoted" | "rolled_back" | "stopped";
+
+export interface RetrievalExperiment {
+  id: number;
+  status: ExperimentStatus;
+  baseline: RetrievalParams;
+  candidate: RetrievalParams;
+  rationale: string;
+  created_at: number;
+  ended_at: number | null;
+}
+
+export interface ExperimentSummary {
+  experiment: RetrievalExperiment;
+  baseline: FeedbackCounts;
+  candidate: FeedbackCounts;
+  verdict: "collecting" | "promote" | "roll_back" | "no_difference";
+}
+
+export interface TuningReport extends FeedbackCounts {
+  current: RetrievalParams;
+  buckets: ParameterBucket[];
+  collections: CollectionFeedback[];
+  proposal: TuningProposal | null;
+  experiment: ExperimentSummary | null;
+}
+
+/** semantic: vector + keyword blend; keyword: full-text only; structured: structured rows */
+export type QueryRoute = "semantic" | "keyword" | "structured";
+
+export interface RoutingThresholds {
+  /** Structured-intent signals a query needs to go to structured rows */
+  structured_min_signals: number;
+  /** Queries with at most this many terms go to keyword search; 0 never */
+  keyword_max_terms: number;
+}
+
+export interface RoutingDecisionRecord {
+  id: number;
+  collection_id: number | null;
+  query_hash: string;
+  query_type: string;
+  auto_route: QueryRoute | null;
+  route: QueryRoute | null;
+  overridden: boolean;
+  structured_signals: number;
+  term_count: number;
+  corrected_route: QueryRoute | null;
+  created_at: number;
+}
+
+export interface RoutingFit {
+  current: RoutingThresholds;
+  proposed: RoutingThresholds;
+  examples: number;
+  misrouted_current: number;
+  misrouted_proposed: number;
+}
+
+export interface RouteCorrectionReport {
+  decision_id: number;
+  /** null until there are enough corrections to refit */
+  fit: RoutingFit | null;
+  /** The proposed thresholds were saved to the retrieval config */
+  applied: boolean;
+}
+
 // Phase 5: System stats
 export interface SystemStats {
   uptime_secs: number;
@@ -337,6 +955,35 @@ export interface SystemStats {
   retrieval_cache_hit_rate: number;
 }
 
+export interface EmbeddingThroughput {
+  texts: number;
+  /** Served from the embedding cache */
+  cached: number;
+  /** Newly computed */
+  embedded: number;
+  failed: number;
+  requests: number;
+  /** Requests repeated after the provider throttled them */
+  retries: number;
+  batch_size: number;
+  concurrency: number;
+  elapsed_ms: number;
+  texts_per_second: number;
+}
+
+export interface EmbeddingThroughputStats {
+  runs: number;
+  texts: number;
+  cached: number;
+  embedded: number;
+  failed: number;
+  requests: number;
+  retries: number;
+  busy_ms: number;
+  texts_per_second: number;
+  last_run: EmbeddingThroughput | null;
+}
+
 // Phase 5: Document quality analysis
 export interface DocumentQualityAnalysis {
   document_id: number;
@@ -0,0 +1,47 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export type ActivitySource =
+  | "qa_run"
+  | "rag_ingestion"
+  | "training"
+  | "mock_server";
+
+export interface ActivityEntry {
+  id: number;
+  /** Unix milliseconds */
+  ts: number;
+  source: ActivitySource;
+  kind: string;
+  level: string;
+  message: string;
+  /** The run, document or route the entry is about */
+  subjectId?: string | null;
+  payloadJson?: string | null;
+}
+
+export interface ActivityQuery {
+  sources?: ActivitySource[];
+  levels?: string[];
+  subjectId?: string;
+  /** Inclusive lower bound in Unix milliseconds */
+  since?: number;
+  /** Exclusive upper bound in Unix milliseconds */
+  until?: number;
+  search?: string;
+  offset?: number;
+  limit?: number;
+}
+
+export interface ActivityPage {
+  entries: ActivityEntry[];
+  total: number;
+  offset: number;
+  limit: number;
+}
+
+/** Pages count back from the newest entry; each page reads oldest first. */
+export async function getActivityFeed(
+  filter: ActivityQuery = {},
+): Promise<ActivityPage> {
+  return await invoke<ActivityPage>("get_activity_feed", { filter });
+}
@@ -0,0 +1,84 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { DatabaseKey } from "./dbMaintenance";
+
+export interface BackupSettings {
+  scheduled: boolean;
+  /** Minimum time between two scheduled backups */
+  intervalHours: number;
+  /** Newest scheduled backup of each of the last N days, weeks and months is kept */
+  keepDaily: number;
+  keepWeekly: number;
+  keepMonthly: number;
+  /** Also archive screenshots and other QA session files */
+  includeQaSessions: boolean;
+  /** Encrypt new backups with a key kept in the OS keychain */
+  encrypt: boolean;
+}
+
+export const DEFAULT_BACKUP_SETTINGS: BackupSettings = {
+  scheduled: true,
+  intervalHours: 24,
+  keepDaily: 7,
+  keepWeekly: 4,
+  keepMonthly: 6,
+  includeQaSessions: true,
+  encrypt: false,
+};
+
+export type BackupReason = "scheduled" | "manual" | "preRestore";
+
+export interface BackedUpDatabase {
+  key: DatabaseKey;
+  label: string;
+  sizeBytes: number;
+}
+
+export interface BackupEntry {
+  formatVersion: number;
+  id: string;
+  createdAt: string;
+  reason: BackupReason;
+  appVersion: string;
+  databases: BackedUpDatabase[];
+  includesQaSessions: boolean;
+  qaSessionFiles: number;
+  encrypted: boolean;
+  path: string;
+  sizeBytes: number;
+}
+
+export interface RestoreRequest {
+  id: string;
+  /** Every database in the backup when empty */
+  databases?: DatabaseKey[];
+  /** Replace the QA session files with the archived ones */
+  qaSessions?: boolean;
+}
+
+export interface RestoreReport {
+  restoredDatabases: DatabaseKey[];
+  restoredQaSessions: boolean;
+  /** Snapshot of the data the restore replaced */
+  safetyBackup: BackupEntry;
+  restartRequired: boolean;
+}
+
+export async function createBackup(): Promise<BackupEntry> {
+  return await invoke<BackupEntry>("backup_create");
+}
+
+/** Newest first. */
+export async function listBackups(): Promise<BackupEntry[]> {
+  return await invoke<BackupEntry[]>("backup_list");
+}
+
+export async function deleteBackup(id: string): Promise<void> {
+  await invoke("backup_delete", { id });
+}
+
+/** The app restarts shortly after this resolves to swap the restored files in. */
+export async function restoreBackup(
+  request: RestoreRequest,
+): Promise<RestoreReport> {
+  return await invoke<RestoreReport>("backup_restore", { request });
+}
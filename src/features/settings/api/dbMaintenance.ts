@@ -0,0 +1,49 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export type DatabaseKey = "app" | "qa" | "rag" | "training";
+
+export interface DbMaintenanceRequest {
+  /** Limit to these databases; all when empty */
+  databases?: DatabaseKey[];
+  vacuum?: boolean;
+  analyze?: boolean;
+  /** Fold the write-ahead log back into the database and truncate it */
+  checkpoint?: boolean;
+  integrityCheck?: boolean;
+}
+
+export interface TableStats {
+  name: string;
+  /** null when the table could not be counted */
+  rowCount: number | null;
+}
+
+export interface DatabaseReport {
+  id: DatabaseKey;
+  label: string;
+  path: string;
+  exists: boolean;
+  sizeBeforeBytes: number;
+  sizeAfterBytes: number;
+  walSizeBytes: number;
+  tables: TableStats[];
+  /** Empty when healthy, absent when the check was not requested */
+  integrityProblems?: string[];
+  operations: string[];
+  error?: string;
+  durationMs: number;
+}
+
+export interface DbMaintenanceReport {
+  generatedAt: string;
+  totalSizeBytes: number;
+  reclaimedBytes: number;
+  databases: DatabaseReport[];
+}
+
+/** Without options only sizes and row counts are reported. */
+export async function runDbMaintenance(
+  request: DbMaintenanceRequest = {},
+): Promise<DbMaintenanceReport> {
+  return await invoke<DbMaintenanceReport>("db_maintenance", { request });
+}
@@ -0,0 +1,35 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export type DiagnosticStatus = "ok" | "warning" | "error";
+
+export type DiagnosticCategory =
+  | "runtime"
+  | "ocr"
+  | "training"
+  | "database"
+  | "storage"
+  | "network";
+
+export interface DiagnosticCheck {
+  id: string;
+  label: string;
+  category: DiagnosticCategory;
+  status: DiagnosticStatus;
+  summary: string;
+  detail?: string;
+  hint?: string;
+  durationMs: number;
+}
+
+export interface DiagnosticsReport {
+  /** Worst status across all checks */
+  status: DiagnosticStatus;
+  generatedAt: string;
+  appVersion: string;
+  platform: string;
+  checks: DiagnosticCheck[];
+}
+
+export async function runSystemDiagnostics(): Promise<DiagnosticsReport> {
+  return await invoke<DiagnosticsReport>("system_diagnostics");
+}
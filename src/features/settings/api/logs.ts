@@ -0,0 +1,58 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export type LogLevel = "trace" | "debug" | "info" | "warn" | "error" | "off";
+
+export interface LogRecord {
+  timestamp: string;
+  level: LogLevel;
+  source: string;
+  message: string;
+  fields?: Record<string, unknown>;
+}
+
+export interface LogQuery {
+  sources?: string[];
+  minLevel?: LogLevel;
+  /** Inclusive RFC 3339 lower bound */
+  since?: string;
+  /** Exclusive RFC 3339 upper bound */
+  until?: string;
+  search?: string;
+  offset?: number;
+  limit?: number;
+}
+
+export interface LogPage {
+  records: LogRecord[];
+  total: number;
+  offset: number;
+  limit: number;
+}
+
+export interface LogSettings {
+  level: LogLevel;
+  sourceLevels: Record<string, LogLevel>;
+  retentionDays: number;
+  maxTotalSizeMb: number;
+  maxFileSizeMb: number;
+}
+
+/** Records are returned newest first. */
+export async function queryLogs(query: LogQuery = {}): Promise<LogPage> {
+  return await invoke<LogPage>("logs_query", { query });
+}
+
+export async function getLogSettings(): Promise<LogSettings> {
+  return await invoke<LogSettings>("logs_get_settings");
+}
+
+export async function updateLogSettings(
+  settings: LogSettings,
+): Promise<LogSettings> {
+  return await invoke<LogSettings>("logs_update_settings", { settings });
+}
+
+/** Returns the number of deleted log files. */
+export async function pruneLogs(): Promise<number> {
+  return await invoke<number>("logs_prune");
+}
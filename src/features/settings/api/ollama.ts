@@ -0,0 +1,66 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+/** A model the Ollama server has pulled. */
+export interface OllamaModel {
+  /** Name with tag, e.g. `llama3:latest` */
+  name: string;
+  sizeBytes: number;
+  modifiedAt: string | null;
+  parameterSize: string | null;
+  quantization: string | null;
+}
+
+export interface OllamaStatus {
+  baseUrl: string;
+  /** The `ollama` binary answered `--version` */
+  installed: boolean;
+  cliVersion: string | null;
+  installError: string | null;
+  running: boolean;
+  serverVersion: string | null;
+  /** Started by the app, so it can be stopped here */
+  managed: boolean;
+  models: OllamaModel[];
+}
+
+export interface OllamaPullProgress {
+  model: string;
+  /** e.g. `pulling manifest`, `verifying sha256 digest`, `success` */
+  status: string;
+  digest: string | null;
+  completedBytes: number | null;
+  totalBytes: number | null;
+}
+
+export const OLLAMA_PULL_PROGRESS_EVENT = "ollama-pull-progress";
+
+export async function getOllamaStatus(baseUrl?: string): Promise<OllamaStatus> {
+  return await invoke<OllamaStatus>("ollama_status", { baseUrl });
+}
+
+export async function listOllamaModels(baseUrl?: string): Promise<OllamaModel[]> {
+  return await invoke<OllamaModel[]>("ollama_list_models", { baseUrl });
+}
+
+export async function startOllama(baseUrl?: string): Promise<OllamaStatus> {
+  return await invoke<OllamaStatus>("ollama_start", { baseUrl });
+}
+
+/** Stops the server only when the app started it. */
+export async function stopOllama(baseUrl?: string): Promise<OllamaStatus> {
+  return await invoke<OllamaStatus>("ollama_stop", { baseUrl });
+}
+
+/** Pull a model; progress arrives through `onOllamaPullProgress`. */
+export async function pullOllamaModel(model: string, baseUrl?: string): Promise<OllamaModel[]> {
+  return await invoke<OllamaModel[]>("ollama_pull_model", { model, baseUrl });
+}
+
+export async function onOllamaPullProgress(
+  handler: (progress: OllamaPullProgress) => void,
+): Promise<UnlistenFn> {
+  return await listen<OllamaPullProgress>(OLLAMA_PULL_PROGRESS_EVENT, (event) =>
+    handler(event.payload),
+  );
+}
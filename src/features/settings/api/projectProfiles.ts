@@ -0,0 +1,74 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type { MockServerStatus } from "../../mock-server/types";
+import {
+  DEFAULT_PROJECT_PROFILE_SETTINGS,
+  getSettings,
+  type ProjectProfile,
+  type ProjectProfileSettings,
+} from "./settings";
+
+export interface ProjectProfileApplied {
+  profile: ProjectProfile;
+  mockServer: MockServerStatus;
+  /** Parts that could not be applied; the rest still were */
+  warnings: string[];
+}
+
+export const PROJECT_PROFILE_APPLIED_EVENT = "project-profile-applied";
+
+/** Switch the mock routes, QA proxy and RAG collection to those of a profile. */
+export async function applyProjectProfile(profileId: string): Promise<ProjectProfileApplied> {
+  return await invoke<ProjectProfileApplied>("project_profile_apply", { profileId });
+}
+
+/** Also fires for profiles applied from a launch argument or a link. */
+export async function onProjectProfileApplied(
+  handler: (applied: ProjectProfileApplied) => void,
+): Promise<UnlistenFn> {
+  return await listen<ProjectProfileApplied>(PROJECT_PROFILE_APPLIED_EVENT, (event) =>
+    handler(event.payload),
+  );
+}
+
+/** Profile applied last, if it still exists */
+export async function getActiveProjectProfile(): Promise<ProjectProfile | null> {
+  const document = await getSettings();
+  const settings: ProjectProfileSettings = {
+    ...DEFAULT_PROJECT_PROFILE_SETTINGS,
+    ...(document.sections.projectProfiles as Partial<ProjectProfileSettings>),
+  };
+  return settings.profiles.find((profile) => profile.id === settings.activeProfileId) ?? null;
+}
+
+/** Link that applies the profile when opened */
+export function projectProfileLink(profileId: string): string {
+  return `gadogado://profile/${encodeURIComponent(profileId)}`;
+}
+
+const PICKED_UP_KEY = "project-profile-picked-up";
+const PICKED_UP_EVENT = "project-profile-picked-up";
+
+/**
+ * Hand an applied profile to the pages that keep their own state (RAG chat's
+ * collection, QA session URLs). Profiles applied before the window loaded
+ * are picked up from the settings once.
+ */
+export function pickUpProjectProfile(profile: ProjectProfile) {
+  localStorage.setItem(PICKED_UP_KEY, profile.id);
+  if (profile.ragCollectionId !== null) {
+    localStorage.setItem("rag-selected-collection", String(profile.ragCollectionId));
+  }
+  window.dispatchEvent(new CustomEvent<ProjectProfile>(PICKED_UP_EVENT, { detail: profile }));
+}
+
+export function lastPickedUpProjectProfile(): string | null {
+  return localStorage.getItem(PICKED_UP_KEY);
+}
+
+/** Profiles applied while the page is open */
+export function onProjectProfilePickedUp(handler: (profile: ProjectProfile) => void): () => void {
+  const listener = (event: Event) => handler((event as CustomEvent<ProjectProfile>).detail);
+  window.addEventListener(PICKED_UP_EVENT, listener);
+  return () => window.removeEventListener(PICKED_UP_EVENT, listener);
+}
@@ -0,0 +1,30 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export type QaAuthSecret = "client_secret" | "password";
+
+export interface QaAuthProfileStatus {
+  id: string;
+  hasClientSecret: boolean;
+  hasPassword: boolean;
+  /** Unix milliseconds the cached token expires at */
+  tokenExpiresAt?: number | null;
+  tokenCached: boolean;
+}
+
+export async function getQaAuthProfileStatuses(): Promise<QaAuthProfileStatus[]> {
+  return await invoke<QaAuthProfileStatus[]>("qa_get_auth_profile_statuses");
+}
+
+/** Stores a secret in the OS keychain; an empty value deletes it */
+export async function saveQaAuthSecret(
+  profileId: string,
+  secret: QaAuthSecret,
+  value: string
+): Promise<void> {
+  await invoke("qa_save_auth_secret", { profileId, secret, value });
+}
+
+/** Fetches a fresh token; resolves to when it expires, if the server said */
+export async function testQaAuthProfile(profileId: string): Promise<number | null> {
+  return await invoke<number | null>("qa_test_auth_profile", { profileId });
+}
@@ -0,0 +1,46 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export type QaPromptKind =
+  | "checkpoint_summary"
+  | "test_cases"
+  | "negative_cases"
+  | "explore"
+  | "visual_analysis";
+
+/** A QA system prompt as the template editor shows it */
+export interface QaPromptTemplateView {
+  kind: QaPromptKind;
+  /** The built-in prompt, with `{language}` where the language goes */
+  defaultTemplate: string;
+  /** The team's template; null while the built-in one is used */
+  template: string | null;
+  /** Bumped on every edit and reset; 0 until the first edit */
+  version: number;
+  updatedAt: number | null;
+}
+
+export async function getQaPromptTemplates(): Promise<QaPromptTemplateView[]> {
+  return await invoke<QaPromptTemplateView[]>("qa_get_prompt_templates");
+}
+
+/**
+ * Replaces the system prompt of `kind`. The generated JSON keys must stay as
+ * the built-in prompt asks for them, or the output can't be read.
+ */
+export async function setQaPromptTemplate(
+  kind: QaPromptKind,
+  template: string,
+): Promise<QaPromptTemplateView[]> {
+  return await invoke<QaPromptTemplateView[]>("qa_set_prompt_template", {
+    kind,
+    template,
+  });
+}
+
+export async function resetQaPromptTemplate(
+  kind: QaPromptKind,
+): Promise<QaPromptTemplateView[]> {
+  return await invoke<QaPromptTemplateView[]>("qa_reset_prompt_template", {
+    kind,
+  });
+}
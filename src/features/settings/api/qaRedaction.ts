@@ -0,0 +1,11 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface QaRedactionReport {
+  apiCalls: number;
+  events: number;
+}
+
+/** Applies the current redaction rules to a session recorded before them */
+export async function redactQaSession(sessionId: string): Promise<QaRedactionReport> {
+  return await invoke<QaRedactionReport>("qa_redact_session", { sessionId });
+}
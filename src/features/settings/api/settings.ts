@@ -0,0 +1,517 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type { LLMProvider } from "../../../store/settings";
+
+export type SettingsSectionKey =
+  | "models"
+  | "languages"
+  | "outputLanguage"
+  | "shortcuts"
+  | "quickAction"
+  | "enhancePresets"
+  | "ollama"
+  | "localEmbedding"
+  | "rag"
+  | "mockServer"
+  | "logging"
+  | "httpServer"
+  | "startup"
+  | "projectProfiles"
+  | "backups"
+  | "storage"
+  | "qaProxy"
+  | "qaSelectors"
+  | "qaRetention"
+  | "qaLlmAudit"
+  | "qaPrompts"
+  | "qaTestManagement"
+  | "qaAuthProfiles"
+  | "qaRedaction"
+  | "qaVision"
+  | "ragTools"
+  | "writingMemory"
+  | "telemetry";
+
+export interface SettingsDocument {
+  schemaVersion: number;
+  updatedAt: string | null;
+  sections: Partial<Record<SettingsSectionKey, unknown>>;
+}
+
+export interface SettingsChange {
+  section: SettingsSectionKey;
+  value: unknown;
+}
+
+export interface LanguageSettings {
+  source: string;
+  target: string;
+}
+
+/** Language the AI features answer in, checked after generation */
+export interface OutputLanguageSettings {
+  /** Name or ISO code, e.g. "Indonesian" or "id"; a request's own language wins */
+  language: string | null;
+  /** Detect the answer's language and ask again when it is another one */
+  verify: boolean;
+  /** Extra attempts for a wrong-language answer, 0-3 */
+  maxRetries: number;
+}
+
+export const DEFAULT_OUTPUT_LANGUAGE_SETTINGS: OutputLanguageSettings = {
+  language: null,
+  verify: true,
+  maxRetries: 1,
+};
+
+export interface ShortcutSettings {
+  enabled: boolean;
+  translate: string;
+  enhance: string;
+  popup: string;
+  terminal: string;
+}
+
+/** Popup shortcut window */
+export interface QuickActionSettings {
+  /** Collection "Ask" answers from when the popup has none picked */
+  ragCollectionId: number | null;
+  /** Sources retrieved for each answer */
+  ragTopK: number;
+}
+
+export const DEFAULT_QUICK_ACTION_SETTINGS: QuickActionSettings = {
+  ragCollectionId: null,
+  ragTopK: 5,
+};
+
+export type EnhanceTone = "formal" | "casual";
+export type EnhanceLength = "shorter" | "longer";
+export type EnhanceFormat = "bullets" | "email" | "commitMessage";
+
+/** Tone, length and format instructions added to the enhancement prompt */
+export interface EnhancePreset {
+  id: string;
+  name: string;
+  tone: EnhanceTone | null;
+  length: EnhanceLength | null;
+  format: EnhanceFormat | null;
+  /** Free-form instructions added after the structured ones */
+  instructions: string | null;
+}
+
+export interface EnhancePresetSettings {
+  presets: EnhancePreset[];
+  /** Used by the enhance shortcut and when no preset is picked */
+  defaultPresetId: string | null;
+}
+
+const preset = (
+  id: string,
+  name: string,
+  tone: EnhanceTone | null,
+  length: EnhanceLength | null,
+  format: EnhanceFormat | null,
+): EnhancePreset => ({ id, name, tone, length, format, instructions: null });
+
+export const DEFAULT_ENHANCE_PRESET_SETTINGS: EnhancePresetSettings = {
+  presets: [
+    preset("formal", "Formal", "formal", null, null),
+    preset("casual", "Casual", "casual", null, null),
+    preset("shorter", "Shorter", null, "shorter", null),
+    preset("longer", "Longer", null, "longer", null),
+    preset("bullets", "Bullet points", null, "shorter", "bullets"),
+    preset("email", "Email", "formal", null, "email"),
+    preset("commit", "Commit message", null, "shorter", "commitMessage"),
+  ],
+  defaultPresetId: null,
+};
+
+export interface StartupSettings {
+  /** Start with the OS session, minimized to the tray */
+  launchAtLogin: boolean;
+  /** Start the mock server on every launch */
+  startMockServer: boolean;
+}
+
+export const DEFAULT_STARTUP_SETTINGS: StartupSettings = {
+  launchAtLogin: false,
+  startMockServer: false,
+};
+
+/** Mock routes, QA environment and RAG collection of one client project */
+export interface ProjectProfile {
+  /** Used in `--profile <id>` and `gadogado://profile/<id>`; letters, digits, '-' or '_' */
+  id: string;
+  name: string;
+  /** Directory the project's mock routes were exported to; null keeps the current routes */
+  mockRoutesDir: string | null;
+  startMockServer: boolean;
+  /** Replaces the QA proxy settings when applied; null keeps the current ones */
+  qaProxy: QaProxySettings | null;
+  /** Prefilled when starting QA sessions */
+  qaTargetUrl: string | null;
+  qaApiBaseUrl: string | null;
+  /** Collection RAG chat selects when applied */
+  ragCollectionId: number | null;
+}
+
+export interface ProjectProfileSettings {
+  profiles: ProjectProfile[];
+  /** Profile applied last */
+  activeProfileId: string | null;
+  /** Apply the active profile again on every launch without `--profile` */
+  applyOnLaunch: boolean;
+}
+
+export const DEFAULT_PROJECT_PROFILE_SETTINGS: ProjectProfileSettings = {
+  profiles: [],
+  activeProfileId: null,
+  applyOnLaunch: false,
+};
+
+/** Local usage stats behind the usage dashboard; nothing is sent anywhere */
+export interface TelemetrySettings {
+  enabled: boolean;
+  /** Days of stats kept, 1-3650 */
+  retentionDays: number;
+}
+
+export const DEFAULT_TELEMETRY_SETTINGS: TelemetrySettings = {
+  enabled: false,
+  retentionDays: 90,
+};
+
+export type OAuthGrant = "client_credentials" | "password";
+
+/** OAuth2 profile the QA API runner signs requests with */
+export interface QaAuthProfile {
+  /** Referenced by requests as `authProfileId`; letters, digits, - and _ */
+  id: string;
+  name: string;
+  grant: OAuthGrant;
+  tokenUrl: string;
+  clientId: string;
+  /** How the client id and secret reach the token endpoint */
+  clientAuth?: "body" | "basic";
+  scope?: string | null;
+  audience?: string | null;
+  /** Password grant only */
+  username?: string | null;
+  /** Defaults to "Authorization" */
+  headerName?: string;
+  /** Defaults to "Bearer"; empty sends the bare token */
+  tokenPrefix?: string;
+}
+
+/** Client secrets and passwords are kept in the OS keychain, not here */
+export interface QaAuthSettings {
+  profiles: QaAuthProfile[];
+}
+
+export const DEFAULT_QA_AUTH_SETTINGS: QaAuthSettings = {
+  profiles: [],
+};
+
+/** Secrets masked in recorded QA API calls and events before they are stored */
+export interface QaRedactionSettings {
+  enabled: boolean;
+  /** Headers whose value is masked, case-insensitive */
+  headerNames: string[];
+  /** Fields whose value is masked; `password` also covers `newPassword` */
+  fieldNames: string[];
+  /** Extra regular expressions; every match is masked */
+  patterns: string[];
+}
+
+export const DEFAULT_QA_REDACTION_SETTINGS: QaRedactionSettings = {
+  enabled: true,
+  headerNames: [
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "x-auth-token",
+  ],
+  fieldNames: ["password", "passwd", "secret", "token", "api_key", "apikey"],
+  patterns: [],
+};
+
+export interface OriginRewrite {
+  from: string;
+  to: string;
+}
+
+export interface InjectedHeader {
+  name: string;
+  value: string;
+}
+
+/** How the QA recorder proxy adapts pages loaded in the preview iframe */
+export interface QaProxySettings {
+  /** Route fetch/XHR calls through the proxy and record them */
+  captureSubresources: boolean;
+  /** Origins only, e.g. `https://api.example.com` -> `http://localhost:8080` */
+  originRewrites: OriginRewrite[];
+  /** Drop `Domain=` from upstream cookies so the browser keeps them */
+  stripCookieDomain: boolean;
+  injectHeaders: InjectedHeader[];
+}
+
+export const DEFAULT_QA_PROXY_SETTINGS: QaProxySettings = {
+  captureSubresources: true,
+  originRewrites: [],
+  stripCookieDomain: true,
+  injectHeaders: [],
+};
+
+/** Attributes QA recorders build selectors from; a session may have its own */
+export interface QaSelectorSettings {
+  /** Tried in order, e.g. `data-qa` or `formcontrolname` first for Angular apps */
+  attributes: string[];
+}
+
+export const DEFAULT_QA_SELECTOR_SETTINGS: QaSelectorSettings = {
+  attributes: ["data-testid", "data-purpose", "id", "name", "aria-label", "role"],
+};
+
+/** Limits applied by `qa_cleanup_storage`; 0 turns a limit off */
+export interface QaRetentionSettings {
+  /** Delete sessions started more than this many days ago */
+  maxSessionAgeDays: number;
+  /** Remove the oldest screenshots once all sessions together hold more */
+  maxScreenshotMb: number;
+  /** Apply the limits when the app starts */
+  cleanupOnStartup: boolean;
+}
+
+export const DEFAULT_QA_RETENTION_SETTINGS: QaRetentionSettings = {
+  maxSessionAgeDays: 0,
+  maxScreenshotMb: 0,
+  cleanupOnStartup: true,
+};
+
+/** USD per million tokens; model `*` prices any model without its own entry */
+export interface QaModelPrice {
+  model: string;
+  inputPerMillion: number;
+  outputPerMillion: number;
+}
+
+/** What QA LLM runs keep for auditing */
+export interface QaLlmAuditSettings {
+  /** Keep the system prompt, user prompt and raw response */
+  storeBodies: boolean;
+  /** Each body is cut to this many characters; 0 keeps it whole */
+  maxBodyChars: number;
+  modelPrices: QaModelPrice[];
+}
+
+export const DEFAULT_QA_LLM_AUDIT_SETTINGS: QaLlmAuditSettings = {
+  storeBodies: true,
+  maxBodyChars: 200000,
+  modelPrices: [],
+};
+
+/** Screenshot review by a vision model during QA exploration */
+export interface QaVisionSettings {
+  enabled: boolean;
+  /** Vision model on the exploration's provider; null uses the exploration's model */
+  model: string | null;
+  /** Screenshots sent per checkpoint, spread over it */
+  maxScreenshots: number;
+  /** Larger screenshot files are skipped */
+  maxImageKb: number;
+}
+
+export const DEFAULT_QA_VISION_SETTINGS: QaVisionSettings = {
+  enabled: false,
+  model: null,
+  maxScreenshots: 4,
+  maxImageKb: 4096,
+};
+
+/** Jira project QA test cases are pushed to as Xray tests */
+export interface XrayTarget {
+  apiUrl: string;
+  /** The client secret is kept in the keychain */
+  clientId: string;
+  projectKey: string;
+  /** Jira priority name per generated priority */
+  priorityMap: Record<string, string>;
+  /** Jira labels per case type */
+  typeLabels: Record<string, string[]>;
+  /** Jira fields set on every test, by field id */
+  extraFields: Record<string, unknown>;
+}
+
+/** TestRail section QA test cases are pushed to */
+export interface TestRailTarget {
+  baseUrl: string;
+  /** The API key is kept in the keychain */
+  username: string;
+  projectId: number | null;
+  suiteId: number | null;
+  sectionId: number | null;
+  priorityMap: Record<string, number>;
+  typeMap: Record<string, number>;
+  extraFields: Record<string, unknown>;
+}
+
+export interface QaTestManagementSettings {
+  xray: XrayTarget;
+  testrail: TestRailTarget;
+}
+
+export const DEFAULT_QA_TEST_MANAGEMENT_SETTINGS: QaTestManagementSettings = {
+  xray: {
+    apiUrl: "https://xray.cloud.getxray.app",
+    clientId: "",
+    projectKey: "",
+    priorityMap: { high: "High", medium: "Medium", low: "Low" },
+    typeLabels: {},
+    extraFields: {},
+  },
+  testrail: {
+    baseUrl: "",
+    username: "",
+    projectId: null,
+    suiteId: null,
+    sectionId: null,
+    priorityMap: { critical: 4, high: 3, medium: 2, low: 1 },
+    typeMap: {},
+    extraFields: {},
+  },
+};
+
+/** Lets the RAG chat call whitelisted APIs through the QA API executor */
+export interface RagToolSettings {
+  enabled: boolean;
+  /** Origins only, e.g. `https://api.example.com` */
+  allowedOrigins: string[];
+  /** QA session the calls are recorded in; required when enabled */
+  qaSessionId: string | null;
+  maxCallsPerAnswer: number;
+  /** Response bodies are cut to this many characters before the model sees them */
+  maxResponseChars: number;
+}
+
+export const DEFAULT_RAG_TOOL_SETTINGS: RagToolSettings = {
+  enabled: false,
+  allowedOrigins: [],
+  qaSessionId: null,
+  maxCallsPerAnswer: 3,
+  maxResponseChars: 8000,
+};
+
+/** Copies accepted translations and enhancements into a RAG collection */
+export interface WritingMemorySettings {
+  enabled: boolean;
+  /** Created on first use */
+  collectionName: string;
+  /** Translations approved in the translation memory or starred in history */
+  includeTranslations: boolean;
+  /** Enhancements starred in history */
+  includeEnhancements: boolean;
+}
+
+export const DEFAULT_WRITING_MEMORY_SETTINGS: WritingMemorySettings = {
+  enabled: false,
+  collectionName: "My writing memory",
+  includeTranslations: true,
+  includeEnhancements: true,
+};
+
+/** ONNX Runtime backend a local embedding model runs on */
+export type EmbeddingExecutionProvider = "cpu" | "cuda" | "directMl" | "coreMl";
+
+/** How in-process embedding models are run */
+export interface LocalEmbeddingSettings {
+  /** Try a GPU execution provider before the CPU */
+  gpuAcceleration: boolean;
+  /** null tries every GPU provider the platform supports */
+  gpuProvider: Exclude<EmbeddingExecutionProvider, "cpu"> | null;
+}
+
+export const DEFAULT_LOCAL_EMBEDDING_SETTINGS: LocalEmbeddingSettings = {
+  gpuAcceleration: false,
+  gpuProvider: null,
+};
+
+/** Local Ollama server managed by the app */
+export interface OllamaSettings {
+  /** Start `ollama serve` when a request finds the server down */
+  autoStart: boolean;
+  /** `ollama` executable; null looks it up on PATH */
+  binaryPath: string | null;
+}
+
+export const DEFAULT_OLLAMA_SETTINGS: OllamaSettings = {
+  autoStart: true,
+  binaryPath: null,
+};
+
+/** Feature that can run on its own model instead of the default one */
+export type LlmFeature =
+  | "translate"
+  | "enhance"
+  | "qaAi"
+  | "ragChat"
+  | "softLabels"
+  | "typegen";
+
+export const LLM_FEATURES: { key: LlmFeature; label: string }[] = [
+  { key: "translate", label: "Translation" },
+  { key: "enhance", label: "Prompt enhancement" },
+  { key: "qaAi", label: "QA AI" },
+  { key: "ragChat", label: "RAG chat" },
+  { key: "softLabels", label: "Soft labels" },
+  { key: "typegen", label: "Type generation" },
+];
+
+/** Provider and model a feature uses; the API key comes from the keychain */
+export interface ModelOverride {
+  provider: LLMProvider;
+  baseUrl: string;
+  model: string;
+}
+
+export async function getSettings(): Promise<SettingsDocument> {
+  return await invoke<SettingsDocument>("settings_get");
+}
+
+export async function updateSettingsSection(
+  section: SettingsSectionKey,
+  value: unknown,
+): Promise<boolean> {
+  return await invoke<boolean>("settings_update_section", { section, value });
+}
+
+/** Returns the number of exported sections. */
+export async function exportSettings(outputPath: string): Promise<number> {
+  return await invoke<number>("settings_export", { outputPath });
+}
+
+/** Returns the sections that changed. */
+export async function importSettings(
+  inputPath: string,
+): Promise<SettingsSectionKey[]> {
+  return await invoke<SettingsSectionKey[]>("settings_import", { inputPath });
+}
+
+export async function onSettingsChanged(
+  handler: (change: SettingsChange) => void,
+): Promise<UnlistenFn> {
+  return await listen<SettingsChange>("settings-changed", (event) =>
+    handler(event.payload),
+  );
+}
+
+/** Pass `null` to put the feature back on the default model. */
+export async function setFeatureModel(
+  feature: LlmFeature,
+  model: ModelOverride | null,
+): Promise<void> {
+  await invoke("sync_feature_model", { feature, model });
+}
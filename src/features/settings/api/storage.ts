@@ -0,0 +1,109 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+export type StorageKind = "qaSessions" | "training";
+
+export interface StorageLocation {
+  kind: StorageKind;
+  path: string;
+  defaultPath: string;
+  isDefault: boolean;
+  sizeBytes: number;
+  fileCount: number;
+  /** Free space on the drive holding `path` */
+  availableBytes?: number;
+}
+
+/**
+ * New locations. A field left out keeps its folder where it is; an empty
+ * string moves it back under the app data dir.
+ */
+export interface StorageMigrationRequest {
+  qaSessionsDir?: string;
+  trainingDir?: string;
+}
+
+export interface StorageMovePlan {
+  kind: StorageKind;
+  from: string;
+  to: string;
+  fileCount: number;
+  totalBytes: number;
+  availableBytes?: number;
+  /** Reasons the move cannot run; empty when it is ready */
+  problems: string[];
+}
+
+export interface StorageMigrationProgress {
+  kind: StorageKind;
+  copiedBytes: number;
+  totalBytes: number;
+  filesDone: number;
+  fileCount: number;
+}
+
+export interface StorageMoveResult {
+  kind: StorageKind;
+  from: string;
+  to: string;
+  filesMoved: number;
+  bytesMoved: number;
+  /** Stored file paths rewritten to the new location */
+  rowsUpdated: number;
+  /** The old folder could not be removed after the move */
+  cleanupError?: string;
+}
+
+export interface QaExpiredSession {
+  sessionId: string;
+  title: string;
+  startedAt: number;
+  eventCount: number;
+  /** Size of the session folder (screenshots and other files) */
+  fileBytes: number;
+}
+
+export interface QaCleanupReport {
+  /** Nothing was deleted; the report shows what would be */
+  dryRun: boolean;
+  expiredSessions: QaExpiredSession[];
+  eventsRemoved: number;
+  /** Screenshots removed to get under the storage cap, besides those of expired sessions */
+  screenshotsRemoved: number;
+  screenshotBytesBefore: number;
+  screenshotBytesAfter: number;
+  bytesFreed: number;
+  errors: string[];
+}
+
+export async function getStorageLocations(): Promise<StorageLocation[]> {
+  return await invoke<StorageLocation[]>("storage_locations");
+}
+
+/** Folders that would not change are left out of the plan. */
+export async function planStorageMigration(
+  request: StorageMigrationRequest,
+): Promise<StorageMovePlan[]> {
+  return await invoke<StorageMovePlan[]>("storage_plan_migration", { request });
+}
+
+/** Fails while a QA session is active or a training run is in progress. */
+export async function migrateStorage(
+  request: StorageMigrationRequest,
+): Promise<StorageMoveResult[]> {
+  return await invoke<StorageMoveResult[]>("storage_migrate", { request });
+}
+
+/** Apply the `qaRetention` limits; the active QA session is never touched. */
+export async function cleanupQaStorage(dryRun: boolean): Promise<QaCleanupReport> {
+  return await invoke<QaCleanupReport>("qa_cleanup_storage", { dryRun });
+}
+
+export async function onStorageMigrationProgress(
+  handler: (event: StorageMigrationProgress) => void,
+): Promise<UnlistenFn> {
+  return await listen<StorageMigrationProgress>(
+    "storage-migration-progress",
+    (event) => handler(event.payload),
+  );
+}
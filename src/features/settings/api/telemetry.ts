@@ -0,0 +1,49 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export type UsageSubsystem = "llm" | "rag" | "qa";
+
+export interface UsageTotals {
+  count: number;
+  errorCount: number;
+  /** Failed share of `count`, 0 to 1 */
+  errorRate: number;
+  avgLatencyMs: number;
+}
+
+export interface SubsystemUsage extends UsageTotals {
+  subsystem: UsageSubsystem;
+}
+
+export interface FeatureUsage extends UsageTotals {
+  subsystem: UsageSubsystem;
+  /** e.g. "enhance" or "checkpoint_summary" */
+  feature: string;
+  maxLatencyMs: number;
+}
+
+export interface DailyUsage extends UsageTotals {
+  /** UTC day, YYYY-MM-DD */
+  day: string;
+}
+
+export interface UsageDashboard {
+  /** Whether calls are recorded now; older stats stay visible after opting out */
+  enabled: boolean;
+  days: number;
+  sinceDay: string;
+  totals: UsageTotals;
+  subsystems: SubsystemUsage[];
+  features: FeatureUsage[];
+  /** Oldest first; days without calls are left out */
+  daily: DailyUsage[];
+}
+
+/** Usage of the last `days` days (default 30), today included */
+export async function getUsageDashboard(days?: number): Promise<UsageDashboard> {
+  return await invoke<UsageDashboard>("get_usage_dashboard", { days });
+}
+
+/** Deletes all recorded stats; resolves to the number of rows removed */
+export async function clearUsageStats(): Promise<number> {
+  return await invoke<number>("clear_usage_stats");
+}
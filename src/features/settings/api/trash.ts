@@ -0,0 +1,36 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export type TrashKind = "qa_session" | "rag_document" | "dataset";
+
+export interface TrashEntry {
+  kind: TrashKind;
+  /** Session or dataset id, or the document id as a string */
+  id: string;
+  name: string;
+  /** Unix ms */
+  deletedAt: number;
+  /** When the entry is purged unless restored first (unix ms) */
+  purgeAt: number;
+}
+
+export interface TrashPurgeReport {
+  qaSessions: number;
+  ragDocuments: number;
+  datasets: number;
+  /** Items that could not be purged; they stay in the trash */
+  errors: string[];
+}
+
+/** Deleted QA sessions, documents and datasets, most recent first. */
+export async function listTrash(): Promise<TrashEntry[]> {
+  return await invoke<TrashEntry[]>("trash_list");
+}
+
+export async function restoreFromTrash(kind: TrashKind, id: string): Promise<void> {
+  await invoke("trash_restore", { kind, id });
+}
+
+/** Purge the expired entries, or every entry with `all`. */
+export async function purgeTrash(all = false): Promise<TrashPurgeReport> {
+  return await invoke<TrashPurgeReport>("trash_purge", { all });
+}
@@ -9,6 +9,7 @@ import {
 } from "lucide-react";
 import type { DbConnection, DbConnectionConfig } from "../../rag/types";
 import { cn } from "../../../utils/cn";
+import { formatAppError } from "../../../utils/appError";
 
 interface DbConnectionConfigModalProps {
   connection: DbConnection | null;
@@ -46,7 +47,7 @@ export function DbConnectionConfigModal({
       setConfig(parsed);
       setDefaultLimit(parsed.default_limit ?? 50);
     } catch (err) {
-      const errorMsg = err instanceof Error ? err.message : JSON.stringify(err);
+      const errorMsg = formatAppError(err);
       setError(`Failed to load config: ${errorMsg}`);
       // Set default values on error
       setConfig({
@@ -85,7 +86,7 @@ export function DbConnectionConfigModal({
         onSave();
       }, 500);
     } catch (err) {
-      const errorMsg = err instanceof Error ? err.message : JSON.stringify(err);
+      const errorMsg = formatAppError(err);
       setError(`Failed to save config: ${errorMsg}`);
     } finally {
       setSaving(false);
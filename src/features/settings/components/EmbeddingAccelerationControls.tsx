@@ -0,0 +1,194 @@
+import { useEffect, useState } from "react";
+import { Gauge, Loader2 } from "lucide-react";
+import { Switch } from "../../../shared/components/Switch";
+import { useToastStore } from "../../../store/toast";
+import { formatAppError } from "../../../utils/appError";
+import { isTauri } from "../../../utils/tauri";
+import {
+  benchmarkEmbeddingProviders,
+  type EmbeddingBenchmarkReport,
+} from "../../rag/api/embeddingModels";
+import {
+  DEFAULT_LOCAL_EMBEDDING_SETTINGS,
+  getSettings,
+  onSettingsChanged,
+  updateSettingsSection,
+  type EmbeddingExecutionProvider,
+  type LocalEmbeddingSettings,
+} from "../api/settings";
+
+export const PROVIDER_LABELS: Record<EmbeddingExecutionProvider, string> = {
+  cpu: "CPU",
+  cuda: "CUDA",
+  directMl: "DirectML",
+  coreMl: "CoreML",
+};
+
+const GPU_PROVIDERS: { value: LocalEmbeddingSettings["gpuProvider"]; label: string }[] = [
+  { value: null, label: "First available" },
+  { value: "cuda", label: "CUDA (NVIDIA)" },
+  { value: "directMl", label: "DirectML (Windows)" },
+  { value: "coreMl", label: "CoreML (macOS)" },
+];
+
+/** GPU execution providers for local embedding models, with a per-provider throughput benchmark. */
+export default function EmbeddingAccelerationControls() {
+  const { addToast } = useToastStore();
+  const [settings, setSettings] = useState<LocalEmbeddingSettings>(
+    DEFAULT_LOCAL_EMBEDDING_SETTINGS,
+  );
+  const [report, setReport] = useState<EmbeddingBenchmarkReport | null>(null);
+  const [benchmarking, setBenchmarking] = useState(false);
+
+  useEffect(() => {
+    if (!isTauri()) return;
+    let disposed = false;
+    let unlisten: (() => void) | undefined;
+
+    const apply = (value: unknown) => {
+      setSettings({
+        ...DEFAULT_LOCAL_EMBEDDING_SETTINGS,
+        ...(value as Partial<LocalEmbeddingSettings>),
+      });
+    };
+
+    getSettings()
+      .then((document) => {
+        if (!disposed) apply(document.sections.localEmbedding);
+      })
+      .catch(console.error);
+
+    onSettingsChanged((change) => {
+      if (change.section === "localEmbedding") apply(change.value);
+    })
+      .then((fn) => {
+        if (disposed) {
+          fn();
+          return;
+        }
+        unlisten = fn;
+      })
+      .catch(console.error);
+
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, []);
+
+  const save = async (next: LocalEmbeddingSettings) => {
+    const previous = settings;
+    setSettings(next);
+    try {
+      await updateSettingsSection("localEmbedding", next);
+    } catch (e) {
+      setSettings(previous);
+      addToast(formatAppError(e, "Failed to save GPU acceleration settings"), "error");
+    }
+  };
+
+  const benchmark = async () => {
+    setBenchmarking(true);
+    try {
+      setReport(await benchmarkEmbeddingProviders());
+    } catch (e) {
+      addToast(formatAppError(e, "Embedding benchmark failed"), "error");
+    } finally {
+      setBenchmarking(false);
+    }
+  };
+
+  const fastest = Math.max(
+    0,
+    ...(report?.results.map((result) => result.embeddingsPerSec ?? 0) ?? []),
+  );
+
+  return (
+    <div className="space-y-2 mt-3 p-2.5 rounded-lg bg-background/40 border border-app-border/30">
+      <div className="flex justify-between items-center gap-4">
+        <div>
+          <div className="text-xs text-app-text font-medium">GPU acceleration</div>
+          <div className="text-[10px] text-app-subtext mt-0.5">
+            Run local models on the GPU when available; falls back to the CPU otherwise.
+          </div>
+        </div>
+        <Switch
+          checked={settings.gpuAcceleration}
+          disabled={!isTauri()}
+          onCheckedChange={(checked) => save({ ...settings, gpuAcceleration: checked })}
+        />
+      </div>
+
+      {settings.gpuAcceleration && (
+        <select
+          className="w-full bg-background border border-app-border rounded-md p-1.5 text-[11px] outline-none hover:border-app-text/30 transition"
+          value={settings.gpuProvider ?? ""}
+          onChange={(e) =>
+            save({
+              ...settings,
+              gpuProvider: (e.target.value || null) as LocalEmbeddingSettings["gpuProvider"],
+            })
+          }>
+          {GPU_PROVIDERS.map((option) => (
+            <option key={option.label} value={option.value ?? ""}>
+              {option.label}
+            </option>
+          ))}
+        </select>
+      )}
+
+      <div className="flex items-center justify-between gap-2">
+        <span className="text-[10px] text-app-subtext">
+          {report
+            ? `${report.modelId}: ${report.sampleCount} texts, batches of ${report.batchSize}`
+            : "Measure embeddings per second on each provider."}
+        </span>
+        <button
+          className="flex items-center gap-1 text-[10px] font-semibold text-app-accent hover:underline shrink-0 disabled:opacity-50"
+          disabled={!isTauri() || benchmarking}
+          onClick={benchmark}>
+          {benchmarking ? (
+            <Loader2 className="w-3.5 h-3.5 animate-spin" />
+          ) : (
+            <Gauge className="w-3.5 h-3.5" />
+          )}
+          Benchmark
+        </button>
+      </div>
+
+      {report && (
+        <div className="space-y-1">
+          {report.results.map((result) => (
+            <div key={result.provider} className="text-[10px]">
+              <div className="flex items-center justify-between gap-3">
+                <span className="text-app-text font-medium">
+                  {PROVIDER_LABELS[result.provider]}
+                  {report.activeProvider === result.provider ? " (in use)" : ""}
+                </span>
+                <span className="text-app-subtext">
+                  {result.embeddingsPerSec !== null
+                    ? `${result.embeddingsPerSec.toFixed(1)} / sec · loaded in ${result.loadMs} ms`
+                    : "unavailable"}
+                </span>
+              </div>
+              {result.embeddingsPerSec !== null ? (
+                <div className="mt-0.5 h-1 w-full rounded bg-app-border/40 overflow-hidden">
+                  <div
+                    className="h-full bg-app-accent"
+                    style={{ width: `${(result.embeddingsPerSec / fastest) * 100}%` }}
+                  />
+                </div>
+              ) : (
+                result.error && (
+                  <div className="text-app-subtext/70 truncate" title={result.error}>
+                    {result.error}
+                  </div>
+                )
+              )}
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
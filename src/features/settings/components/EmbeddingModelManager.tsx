@@ -0,0 +1,141 @@
+import { useCallback, useEffect, useState } from "react";
+import { CheckCircle, Download, Loader2 } from "lucide-react";
+import { useSettingsStore } from "../../../store/settings";
+import { useToastStore } from "../../../store/toast";
+import { isTauri } from "../../../utils/tauri";
+import {
+  downloadEmbeddingModel,
+  listEmbeddingModels,
+  onEmbeddingModelDownloadProgress,
+  setActiveEmbeddingModel,
+  type EmbeddingModelDownloadProgress,
+  type LocalEmbeddingModel,
+} from "../../rag/api/embeddingModels";
+import { PROVIDER_LABELS } from "./EmbeddingAccelerationControls";
+
+function formatSize(bytes: number | null): string {
+  if (bytes === null) return "size unknown";
+  if (bytes < 1024 * 1024) return `${Math.max(1, Math.round(bytes / 1024))} KB`;
+  return `${(bytes / (1024 * 1024)).toFixed(bytes < 100 * 1024 * 1024 ? 1 : 0)} MB`;
+}
+
+/** Local embedding models: download with checksum verification and switch the active one. */
+export default function EmbeddingModelManager() {
+  const { addToast } = useToastStore();
+  const setEmbeddingProvider = useSettingsStore((state) => state.setEmbeddingProvider);
+  const setEmbeddingModel = useSettingsStore((state) => state.setEmbeddingModel);
+  const [models, setModels] = useState<LocalEmbeddingModel[]>([]);
+  const [progress, setProgress] = useState<Record<string, EmbeddingModelDownloadProgress>>({});
+  const [busy, setBusy] = useState<string | null>(null);
+
+  const refresh = useCallback(() => {
+    listEmbeddingModels().then(setModels).catch(console.error);
+  }, []);
+
+  useEffect(() => {
+    if (!isTauri()) return;
+    refresh();
+    let disposed = false;
+    let unlisten: (() => void) | undefined;
+    onEmbeddingModelDownloadProgress((next) => {
+      setProgress((current) => ({ ...current, [next.modelId]: next }));
+    })
+      .then((fn) => {
+        if (disposed) {
+          fn();
+          return;
+        }
+        unlisten = fn;
+      })
+      .catch(console.error);
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, [refresh]);
+
+  const download = async (model: LocalEmbeddingModel) => {
+    setBusy(model.id);
+    try {
+      await downloadEmbeddingModel(model.id);
+      addToast(`${model.id} downloaded and verified`, "success");
+    } catch (e: any) {
+      addToast(typeof e === "string" ? e : e?.message || "Model download failed", "error");
+    } finally {
+      setBusy(null);
+      setProgress(({ [model.id]: _, ...rest }) => rest);
+      refresh();
+    }
+  };
+
+  const activate = async (model: LocalEmbeddingModel) => {
+    setBusy(model.id);
+    try {
+      await setActiveEmbeddingModel(model.id);
+      setEmbeddingProvider("local");
+      setEmbeddingModel(model.id);
+      addToast(`Embedding with ${model.id}; re-index collections built with another model`, "success");
+    } catch (e: any) {
+      addToast(typeof e === "string" ? e : e?.message || "Failed to switch model", "error");
+    } finally {
+      setBusy(null);
+      refresh();
+    }
+  };
+
+  if (models.length === 0) return null;
+
+  return (
+    <div className="space-y-1.5 mt-3">
+      {models.map((model) => {
+        const current = progress[model.id];
+        const percent =
+          current && current.totalBytes > 0
+            ? Math.round((current.downloadedBytes / current.totalBytes) * 100)
+            : 0;
+        return (
+          <div
+            key={model.id}
+            className="flex items-center justify-between gap-4 p-2.5 rounded-lg bg-background/40 border border-app-border/30">
+            <div className="min-w-0">
+              <div className="text-xs text-app-text font-medium flex items-center gap-1.5">
+                {model.id}
+                {model.active && <CheckCircle className="w-3.5 h-3.5 text-app-success" />}
+              </div>
+              <div className="text-[10px] text-app-subtext mt-0.5 truncate">
+                {model.dimension} dim · {model.installed ? formatSize(model.sizeBytes) : "not downloaded"} ·{" "}
+                {model.executionProvider && `on ${PROVIDER_LABELS[model.executionProvider]} · `}
+                {model.description}
+              </div>
+              {current && (
+                <div className="mt-1.5 h-1 w-full rounded bg-app-border/40 overflow-hidden">
+                  <div className="h-full bg-app-accent transition-all" style={{ width: `${percent}%` }} />
+                </div>
+              )}
+            </div>
+            {busy === model.id ? (
+              <Loader2 className="w-4 h-4 animate-spin text-app-subtext shrink-0" />
+            ) : model.installed ? (
+              !model.active && (
+                <button
+                  className="text-[10px] font-semibold text-app-accent hover:underline shrink-0"
+                  disabled={busy !== null}
+                  onClick={() => activate(model)}>
+                  Use
+                </button>
+              )
+            ) : (
+              <button
+                className="flex items-center gap-1 text-[10px] font-semibold text-app-accent hover:underline shrink-0"
+                disabled={busy !== null}
+                onClick={() => download(model)}>
+                <Download className="w-3.5 h-3.5" />
+                Download
+              </button>
+            )}
+          </div>
+        );
+      })}
+    </div>
+  );
+}
@@ -0,0 +1,275 @@
+import { useEffect, useState } from "react";
+import { Plus, RotateCcw, Save, Trash2 } from "lucide-react";
+import { useToastStore } from "../../../store/toast";
+import { formatAppError } from "../../../utils/appError";
+import { isTauri } from "../../../utils/tauri";
+import {
+  DEFAULT_ENHANCE_PRESET_SETTINGS,
+  getSettings,
+  onSettingsChanged,
+  updateSettingsSection,
+  type EnhanceFormat,
+  type EnhanceLength,
+  type EnhancePreset,
+  type EnhancePresetSettings,
+  type EnhanceTone,
+} from "../api/settings";
+
+const TONES: { value: EnhanceTone; label: string }[] = [
+  { value: "formal", label: "Formal" },
+  { value: "casual", label: "Casual" },
+];
+
+const LENGTHS: { value: EnhanceLength; label: string }[] = [
+  { value: "shorter", label: "Shorter" },
+  { value: "longer", label: "Longer" },
+];
+
+const FORMATS: { value: EnhanceFormat; label: string }[] = [
+  { value: "bullets", label: "Bullets" },
+  { value: "email", label: "Email" },
+  { value: "commitMessage", label: "Commit message" },
+];
+
+const SELECT_CLASS =
+  "bg-background border border-app-border rounded-md p-1.5 text-[11px] outline-none hover:border-app-text/30 transition";
+
+function readPresets(value: unknown): EnhancePresetSettings {
+  return {
+    ...DEFAULT_ENHANCE_PRESET_SETTINGS,
+    ...(value as Partial<EnhancePresetSettings>),
+  };
+}
+
+/** Id from the name, made unique among `taken` */
+function presetId(name: string, taken: string[]): string {
+  const base =
+    name
+      .toLowerCase()
+      .replace(/[^a-z0-9]+/g, "-")
+      .replace(/^-+|-+$/g, "") || "preset";
+  let id = base;
+  for (let n = 2; taken.includes(id); n++) id = `${base}-${n}`;
+  return id;
+}
+
+function OptionSelect<T extends string>({
+  value,
+  options,
+  placeholder,
+  onChange,
+}: {
+  value: T | null;
+  options: { value: T; label: string }[];
+  placeholder: string;
+  onChange: (value: T | null) => void;
+}) {
+  return (
+    <select
+      className={SELECT_CLASS}
+      value={value ?? ""}
+      onChange={(e) => onChange((e.target.value || null) as T | null)}>
+      <option value="">{placeholder}</option>
+      {options.map((option) => (
+        <option key={option.value} value={option.value}>
+          {option.label}
+        </option>
+      ))}
+    </select>
+  );
+}
+
+export default function EnhancePresetControls() {
+  const { addToast } = useToastStore();
+  const [saved, setSaved] = useState<EnhancePresetSettings>(
+    DEFAULT_ENHANCE_PRESET_SETTINGS,
+  );
+  const [draft, setDraft] = useState<EnhancePresetSettings>(
+    DEFAULT_ENHANCE_PRESET_SETTINGS,
+  );
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    if (!isTauri()) return;
+    let disposed = false;
+    let unlisten: (() => void) | undefined;
+
+    const apply = (value: unknown) => {
+      const next = readPresets(value);
+      setSaved(next);
+      setDraft(next);
+    };
+
+    getSettings()
+      .then((document) => {
+        if (!disposed) apply(document.sections.enhancePresets);
+      })
+      .catch(console.error);
+
+    onSettingsChanged((change) => {
+      if (change.section === "enhancePresets") apply(change.value);
+    })
+      .then((fn) => {
+        if (disposed) {
+          fn();
+          return;
+        }
+        unlisten = fn;
+      })
+      .catch(console.error);
+
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, []);
+
+  const dirty = JSON.stringify(draft) !== JSON.stringify(saved);
+
+  const updatePreset = (id: string, patch: Partial<EnhancePreset>) => {
+    setDraft((current) => ({
+      ...current,
+      presets: current.presets.map((preset) =>
+        preset.id === id ? { ...preset, ...patch } : preset,
+      ),
+    }));
+  };
+
+  const removePreset = (id: string) => {
+    setDraft((current) => ({
+      presets: current.presets.filter((preset) => preset.id !== id),
+      defaultPresetId:
+        current.defaultPresetId === id ? null : current.defaultPresetId,
+    }));
+  };
+
+  const addPreset = () => {
+    setDraft((current) => {
+      const name = "New preset";
+      const id = presetId(
+        name,
+        current.presets.map((preset) => preset.id),
+      );
+      return {
+        ...current,
+        presets: [
+          ...current.presets,
+          { id, name, tone: "formal", length: null, format: null, instructions: null },
+        ],
+      };
+    });
+  };
+
+  const save = async () => {
+    setSaving(true);
+    try {
+      await updateSettingsSection("enhancePresets", draft);
+      setSaved(draft);
+      addToast("Enhancement presets saved", "success");
+    } catch (e) {
+      addToast(formatAppError(e, "Failed to save enhancement presets"), "error");
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  return (
+    <div className="space-y-3">
+      <div className="space-y-1.5">
+        <label className="text-[9px] text-app-subtext/80 font-bold uppercase tracking-widest block px-0.5">
+          Default preset
+        </label>
+        <select
+          className={`w-full ${SELECT_CLASS}`}
+          value={draft.defaultPresetId ?? ""}
+          onChange={(e) =>
+            setDraft({ ...draft, defaultPresetId: e.target.value || null })
+          }>
+          <option value="">None</option>
+          {draft.presets.map((preset) => (
+            <option key={preset.id} value={preset.id}>
+              {preset.name}
+            </option>
+          ))}
+        </select>
+        <p className="text-[10px] text-app-subtext/60 italic px-0.5">
+          Used by the enhance shortcut and when the popup has no preset picked.
+        </p>
+      </div>
+
+      <div className="space-y-2">
+        {draft.presets.map((preset) => (
+          <div
+            key={preset.id}
+            className="p-2.5 rounded-lg bg-background/40 border border-app-border/30 space-y-2">
+            <div className="flex items-center gap-2">
+              <input
+                className="flex-1 bg-background border border-app-border rounded-md p-1.5 px-2.5 text-xs outline-none focus:border-app-text/30 transition"
+                value={preset.name}
+                onChange={(e) => updatePreset(preset.id, { name: e.target.value })}
+              />
+              <button
+                className="p-1.5 text-app-subtext hover:text-red-400 transition"
+                onClick={() => removePreset(preset.id)}
+                aria-label={`Remove ${preset.name}`}>
+                <Trash2 className="w-3.5 h-3.5" />
+              </button>
+            </div>
+            <div className="grid grid-cols-3 gap-2">
+              <OptionSelect
+                value={preset.tone}
+                options={TONES}
+                placeholder="Any tone"
+                onChange={(tone) => updatePreset(preset.id, { tone })}
+              />
+              <OptionSelect
+                value={preset.length}
+                options={LENGTHS}
+                placeholder="Same length"
+                onChange={(length) => updatePreset(preset.id, { length })}
+              />
+              <OptionSelect
+                value={preset.format}
+                options={FORMATS}
+                placeholder="Any format"
+                onChange={(format) => updatePreset(preset.id, { format })}
+              />
+            </div>
+            <input
+              className="w-full bg-background border border-app-border rounded-md p-1.5 px-2.5 text-[11px] outline-none focus:border-app-text/30 transition"
+              placeholder="Extra instructions (optional)"
+              value={preset.instructions ?? ""}
+              onChange={(e) =>
+                updatePreset(preset.id, { instructions: e.target.value || null })
+              }
+            />
+          </div>
+        ))}
+      </div>
+
+      <div className="flex items-center justify-between gap-2">
+        <div className="flex items-center gap-2">
+          <button
+            className="flex items-center gap-1.5 px-2.5 py-1.5 text-[11px] border border-app-border rounded-md hover:border-app-text/30 transition"
+            onClick={addPreset}>
+            <Plus className="w-3.5 h-3.5" />
+            Add preset
+          </button>
+          <button
+            className="flex items-center gap-1.5 px-2.5 py-1.5 text-[11px] text-app-subtext hover:text-app-text transition"
+            onClick={() => setDraft(DEFAULT_ENHANCE_PRESET_SETTINGS)}>
+            <RotateCcw className="w-3.5 h-3.5" />
+            Restore defaults
+          </button>
+        </div>
+        <button
+          className="flex items-center gap-1.5 px-3 py-1.5 text-[11px] font-semibold rounded-md bg-app-success/10 text-app-success border border-app-success/30 hover:bg-app-success/20 transition disabled:opacity-50"
+          disabled={!isTauri() || !dirty || saving}
+          onClick={save}>
+          <Save className="w-3.5 h-3.5" />
+          Save
+        </button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,174 @@
+import { useEffect, useState } from "react";
+import { Switch } from "../../../shared/components/Switch";
+import { useToastStore } from "../../../store/toast";
+import { useSettingsStore, type LLMProvider } from "../../../store/settings";
+import { formatAppError } from "../../../utils/appError";
+import { isTauri } from "../../../utils/tauri";
+import {
+  LLM_FEATURES,
+  getSettings,
+  onSettingsChanged,
+  setFeatureModel,
+  type LlmFeature,
+  type ModelOverride,
+} from "../api/settings";
+
+const PROVIDERS: { value: LLMProvider; label: string }[] = [
+  { value: "local", label: "Local" },
+  { value: "ollama", label: "Ollama" },
+  { value: "llama_cpp", label: "llama.cpp" },
+  { value: "openai", label: "OpenAI" },
+  { value: "gemini", label: "Gemini" },
+  { value: "openrouter", label: "OpenRouter" },
+  { value: "cli_proxy", label: "CLI Proxy" },
+  { value: "dll", label: "DLL" },
+];
+
+type Overrides = Partial<Record<LlmFeature, ModelOverride>>;
+
+function readOverrides(models: unknown): Overrides {
+  return ((models as { features?: Overrides } | undefined)?.features ?? {});
+}
+
+export default function FeatureModelOverrides() {
+  const { addToast } = useToastStore();
+  const { provider, baseUrl, model } = useSettingsStore();
+  const [overrides, setOverrides] = useState<Overrides>({});
+  const [drafts, setDrafts] = useState<Overrides>({});
+
+  useEffect(() => {
+    if (!isTauri()) return;
+    let disposed = false;
+    let unlisten: (() => void) | undefined;
+
+    getSettings()
+      .then((document) => {
+        if (disposed) return;
+        setOverrides(readOverrides(document.sections.models));
+      })
+      .catch(console.error);
+
+    onSettingsChanged((change) => {
+      if (change.section === "models") {
+        setOverrides(readOverrides(change.value));
+      }
+    })
+      .then((fn) => {
+        if (disposed) {
+          fn();
+          return;
+        }
+        unlisten = fn;
+      })
+      .catch(console.error);
+
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, []);
+
+  const save = async (feature: LlmFeature, next: ModelOverride | null) => {
+    try {
+      await setFeatureModel(feature, next);
+      setOverrides((current) => {
+        const updated = { ...current };
+        if (next) updated[feature] = next;
+        else delete updated[feature];
+        return updated;
+      });
+      setDrafts((current) => {
+        const updated = { ...current };
+        delete updated[feature];
+        return updated;
+      });
+    } catch (e) {
+      addToast(formatAppError(e, "Failed to save the feature model"), "error");
+    }
+  };
+
+  const toggle = (feature: LlmFeature, enabled: boolean) => {
+    if (!enabled) {
+      void save(feature, null);
+      return;
+    }
+    setDrafts((current) => ({
+      ...current,
+      [feature]: { provider, baseUrl, model },
+    }));
+  };
+
+  const editDraft = (feature: LlmFeature, patch: Partial<ModelOverride>) => {
+    setDrafts((current) => {
+      const base = current[feature] ?? overrides[feature];
+      if (!base) return current;
+      return { ...current, [feature]: { ...base, ...patch } };
+    });
+  };
+
+  return (
+    <div className="space-y-2.5">
+      {LLM_FEATURES.map(({ key, label }) => {
+        const saved = overrides[key];
+        const draft = drafts[key];
+        const shown = draft ?? saved;
+        return (
+          <div
+            key={key}
+            className="p-3 rounded-lg bg-background/40 border border-app-border/30 space-y-2">
+            <div className="flex justify-between items-center gap-4">
+              <div>
+                <div className="text-xs text-app-text font-medium">{label}</div>
+                <div className="text-[10px] text-app-subtext mt-0.5">
+                  {saved
+                    ? `${saved.provider} · ${saved.model}`
+                    : "Uses the default model"}
+                </div>
+              </div>
+              <Switch
+                checked={!!shown}
+                disabled={!isTauri()}
+                onCheckedChange={(checked) => toggle(key, checked)}
+              />
+            </div>
+            {shown && (
+              <div className="grid grid-cols-1 md:grid-cols-[8rem_1fr_1fr_auto] gap-2">
+                <select
+                  value={shown.provider}
+                  onChange={(e) =>
+                    editDraft(key, { provider: e.target.value as LLMProvider })
+                  }
+                  className="bg-background/50 border border-app-border/50 rounded-lg p-2 text-[11px] outline-none">
+                  {PROVIDERS.map((option) => (
+                    <option key={option.value} value={option.value}>
+                      {option.label}
+                    </option>
+                  ))}
+                </select>
+                <input
+                  value={shown.baseUrl}
+                  placeholder="Base URL"
+                  onChange={(e) => editDraft(key, { baseUrl: e.target.value })}
+                  className="bg-background/50 border border-app-border/50 rounded-lg p-2 text-[11px] outline-none"
+                />
+                <input
+                  value={shown.model}
+                  placeholder="Model"
+                  onChange={(e) => editDraft(key, { model: e.target.value })}
+                  className="bg-background/50 border border-app-border/50 rounded-lg p-2 text-[11px] outline-none"
+                />
+                <button
+                  type="button"
+                  disabled={!draft || !draft.model.trim()}
+                  onClick={() => draft && save(key, draft)}
+                  className="px-3 py-2 rounded-lg text-[11px] font-medium bg-primary/10 text-primary border border-primary/20 disabled:opacity-40">
+                  Save
+                </button>
+              </div>
+            )}
+          </div>
+        );
+      })}
+    </div>
+  );
+}
@@ -0,0 +1,335 @@
+import { useCallback, useEffect, useState } from "react";
+import { useQueryClient } from "@tanstack/react-query";
+import { CheckCircle, Download, Loader2, Play, RefreshCw, Square } from "lucide-react";
+import { useDebounce } from "../../../hooks/useDebounce";
+import { Switch } from "../../../shared/components/Switch";
+import { useSettingsStore } from "../../../store/settings";
+import { useToastStore } from "../../../store/toast";
+import { formatAppError } from "../../../utils/appError";
+import { isTauri } from "../../../utils/tauri";
+import {
+  getOllamaStatus,
+  onOllamaPullProgress,
+  pullOllamaModel,
+  startOllama,
+  stopOllama,
+  type OllamaModel,
+  type OllamaPullProgress,
+  type OllamaStatus,
+} from "../api/ollama";
+import {
+  DEFAULT_OLLAMA_SETTINGS,
+  getSettings,
+  onSettingsChanged,
+  updateSettingsSection,
+  type OllamaSettings,
+} from "../api/settings";
+
+function formatSize(bytes: number): string {
+  if (bytes < 1024 * 1024 * 1024) return `${Math.max(1, Math.round(bytes / (1024 * 1024)))} MB`;
+  return `${(bytes / (1024 * 1024 * 1024)).toFixed(1)} GB`;
+}
+
+function describeModel(model: OllamaModel): string {
+  return [formatSize(model.sizeBytes), model.parameterSize, model.quantization]
+    .filter(Boolean)
+    .join(" · ");
+}
+
+/** Ollama install and server status, start/stop, and model pulls for the Ollama provider. */
+export default function OllamaControls({ baseUrl: typedBaseUrl }: { baseUrl: string }) {
+  // The base URL field updates on every keystroke
+  const baseUrl = useDebounce(typedBaseUrl, 400);
+  const { addToast } = useToastStore();
+  const queryClient = useQueryClient();
+  const model = useSettingsStore((state) => state.model);
+  const setModel = useSettingsStore((state) => state.setModel);
+  const setLocalModels = useSettingsStore((state) => state.setLocalModels);
+  const [status, setStatus] = useState<OllamaStatus | null>(null);
+  const [settings, setSettings] = useState<OllamaSettings>(DEFAULT_OLLAMA_SETTINGS);
+  const [binaryPath, setBinaryPath] = useState("");
+  const [busy, setBusy] = useState<"refresh" | "start" | "stop" | "pull" | null>(null);
+  const [pullName, setPullName] = useState("");
+  const [progress, setProgress] = useState<OllamaPullProgress | null>(null);
+
+  const apply = useCallback(
+    (next: OllamaStatus) => {
+      setStatus(next);
+      if (next.running) {
+        setLocalModels(next.models.map((entry) => entry.name));
+        queryClient.invalidateQueries({ queryKey: ["models", "ollama"] });
+      }
+    },
+    [queryClient, setLocalModels],
+  );
+
+  const refresh = useCallback(async () => {
+    setBusy("refresh");
+    try {
+      apply(await getOllamaStatus(baseUrl));
+    } catch (e) {
+      addToast(formatAppError(e, "Failed to check Ollama"), "error");
+    } finally {
+      setBusy(null);
+    }
+  }, [addToast, apply, baseUrl]);
+
+  useEffect(() => {
+    if (!isTauri()) return;
+    refresh();
+  }, [refresh]);
+
+  useEffect(() => {
+    if (!isTauri()) return;
+    let disposed = false;
+    const unlisteners: (() => void)[] = [];
+    const keep = (fn: () => void) => {
+      if (disposed) fn();
+      else unlisteners.push(fn);
+    };
+
+    const applySettings = (value: unknown) => {
+      const next = { ...DEFAULT_OLLAMA_SETTINGS, ...(value as Partial<OllamaSettings>) };
+      setSettings(next);
+      setBinaryPath(next.binaryPath ?? "");
+    };
+
+    getSettings()
+      .then((document) => {
+        if (!disposed) applySettings(document.sections.ollama);
+      })
+      .catch(console.error);
+    onSettingsChanged((change) => {
+      if (change.section === "ollama") applySettings(change.value);
+    })
+      .then(keep)
+      .catch(console.error);
+    onOllamaPullProgress(setProgress).then(keep).catch(console.error);
+
+    return () => {
+      disposed = true;
+      unlisteners.forEach((fn) => fn());
+    };
+  }, []);
+
+  const saveSettings = async (next: OllamaSettings) => {
+    const previous = settings;
+    setSettings(next);
+    try {
+      await updateSettingsSection("ollama", next);
+    } catch (e) {
+      setSettings(previous);
+      setBinaryPath(previous.binaryPath ?? "");
+      addToast(formatAppError(e, "Failed to save Ollama settings"), "error");
+    }
+  };
+
+  const saveBinaryPath = () => {
+    const path = binaryPath.trim() || null;
+    if (path === settings.binaryPath) return;
+    saveSettings({ ...settings, binaryPath: path }).then(refresh);
+  };
+
+  const run = async (
+    action: "start" | "stop",
+    call: (baseUrl: string) => Promise<OllamaStatus>,
+  ) => {
+    setBusy(action);
+    try {
+      apply(await call(baseUrl));
+    } catch (e) {
+      addToast(formatAppError(e, `Failed to ${action} Ollama`), "error");
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const pull = async () => {
+    const name = pullName.trim();
+    if (!name) return;
+    setBusy("pull");
+    setProgress(null);
+    try {
+      await pullOllamaModel(name, baseUrl);
+      addToast(`${name} pulled`, "success");
+      setPullName("");
+      apply(await getOllamaStatus(baseUrl));
+    } catch (e) {
+      addToast(formatAppError(e, `Failed to pull ${name}`), "error");
+    } finally {
+      setBusy(null);
+      setProgress(null);
+    }
+  };
+
+  const percent =
+    progress?.totalBytes && progress.completedBytes !== null
+      ? Math.round((progress.completedBytes / progress.totalBytes) * 100)
+      : null;
+
+  return (
+    <div className="space-y-3 p-3 rounded-lg bg-background/40 border border-app-border/30">
+      <div className="flex items-start justify-between gap-3">
+        <div className="min-w-0 text-[11px]">
+          {status === null ? (
+            <span className="text-app-subtext">Checking Ollama…</span>
+          ) : !status.installed && !status.running ? (
+            <>
+              <div className="text-app-text font-medium">Ollama not found</div>
+              <div className="text-[10px] text-app-subtext mt-0.5">
+                {status.installError ??
+                  "Install Ollama from https://ollama.com/download or set its path below."}
+              </div>
+            </>
+          ) : (
+            <>
+              <div className="text-app-text font-medium flex items-center gap-1.5">
+                {status.running && <CheckCircle className="w-3.5 h-3.5 text-app-success" />}
+                {status.running
+                  ? `Running on ${status.baseUrl}`
+                  : `Stopped (${status.baseUrl})`}
+              </div>
+              <div className="text-[10px] text-app-subtext mt-0.5">
+                Ollama {status.serverVersion ?? status.cliVersion}
+                {status.managed ? " · started by this app" : ""}
+              </div>
+            </>
+          )}
+        </div>
+        <div className="flex items-center gap-1.5 shrink-0">
+          {status?.running ? (
+            status.managed && (
+              <button
+                className="flex items-center gap-1 px-2 py-1 text-[10px] border border-app-border rounded-md hover:border-app-text/30 transition disabled:opacity-50"
+                disabled={busy !== null}
+                onClick={() => run("stop", stopOllama)}>
+                {busy === "stop" ? (
+                  <Loader2 className="w-3 h-3 animate-spin" />
+                ) : (
+                  <Square className="w-3 h-3" />
+                )}
+                Stop
+              </button>
+            )
+          ) : (
+            <button
+              className="flex items-center gap-1 px-2 py-1 text-[10px] border border-app-border rounded-md hover:border-app-text/30 transition disabled:opacity-50"
+              disabled={!isTauri() || !status?.installed || busy !== null}
+              onClick={() => run("start", startOllama)}>
+              {busy === "start" ? (
+                <Loader2 className="w-3 h-3 animate-spin" />
+              ) : (
+                <Play className="w-3 h-3" />
+              )}
+              Start
+            </button>
+          )}
+          <button
+            className="p-1 text-app-subtext hover:text-app-text transition disabled:opacity-50"
+            disabled={!isTauri() || busy !== null}
+            onClick={refresh}
+            aria-label="Refresh Ollama status">
+            <RefreshCw className={`w-3.5 h-3.5 ${busy === "refresh" ? "animate-spin" : ""}`} />
+          </button>
+        </div>
+      </div>
+
+      <div className="flex justify-between items-center gap-4">
+        <div>
+          <div className="text-xs text-app-text font-medium">Start automatically</div>
+          <div className="text-[10px] text-app-subtext mt-0.5">
+            Run `ollama serve` when a request finds the server down.
+          </div>
+        </div>
+        <Switch
+          checked={settings.autoStart}
+          disabled={!isTauri()}
+          onCheckedChange={(checked) => saveSettings({ ...settings, autoStart: checked })}
+        />
+      </div>
+
+      <input
+        className="w-full bg-background border border-app-border rounded-md p-1.5 px-2.5 text-[11px] outline-none focus:border-app-text/30 transition"
+        placeholder="ollama executable (default: found on PATH)"
+        value={binaryPath}
+        disabled={!isTauri()}
+        onChange={(e) => setBinaryPath(e.target.value)}
+        onBlur={saveBinaryPath}
+        onKeyDown={(e) => {
+          if (e.key === "Enter") saveBinaryPath();
+        }}
+      />
+
+      {status?.running && status.models.length > 0 && (
+        <div className="space-y-1">
+          {status.models.map((entry) => (
+            <div
+              key={entry.name}
+              className="flex items-center justify-between gap-3 px-2 py-1.5 rounded-md border border-app-border/30">
+              <div className="min-w-0">
+                <div className="text-[11px] text-app-text font-medium flex items-center gap-1.5 truncate">
+                  {entry.name}
+                  {entry.name === model && (
+                    <CheckCircle className="w-3.5 h-3.5 text-app-success shrink-0" />
+                  )}
+                </div>
+                <div className="text-[10px] text-app-subtext">{describeModel(entry)}</div>
+              </div>
+              {entry.name !== model && (
+                <button
+                  className="text-[10px] font-semibold text-app-accent hover:underline shrink-0"
+                  onClick={() => setModel(entry.name)}>
+                  Use
+                </button>
+              )}
+            </div>
+          ))}
+        </div>
+      )}
+
+      <div className="space-y-1.5">
+        <div className="flex items-center gap-2">
+          <input
+            className="flex-1 bg-background border border-app-border rounded-md p-1.5 px-2.5 text-[11px] outline-none focus:border-app-text/30 transition"
+            placeholder="Model to pull, e.g. llama3.2 or qwen2.5:7b"
+            value={pullName}
+            disabled={!isTauri() || busy === "pull"}
+            onChange={(e) => setPullName(e.target.value)}
+            onKeyDown={(e) => {
+              if (e.key === "Enter") pull();
+            }}
+          />
+          <button
+            className="flex items-center gap-1 px-2.5 py-1.5 text-[10px] font-semibold border border-app-border rounded-md hover:border-app-text/30 transition disabled:opacity-50"
+            disabled={
+              !isTauri() ||
+              !(status?.installed || status?.running) ||
+              !pullName.trim() ||
+              busy !== null
+            }
+            onClick={pull}>
+            {busy === "pull" ? (
+              <Loader2 className="w-3.5 h-3.5 animate-spin" />
+            ) : (
+              <Download className="w-3.5 h-3.5" />
+            )}
+            Pull
+          </button>
+        </div>
+        {busy === "pull" && progress && (
+          <div>
+            <div className="text-[10px] text-app-subtext truncate">
+              {progress.status}
+              {percent !== null ? ` · ${percent}%` : ""}
+            </div>
+            {percent !== null && (
+              <div className="mt-1 h-1 w-full rounded bg-app-border/40 overflow-hidden">
+                <div className="h-full bg-app-accent transition-all" style={{ width: `${percent}%` }} />
+              </div>
+            )}
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -4,6 +4,7 @@ import { motion } from "framer-motion";
 import { Plus, Settings, Loader2, ChevronRight } from "lucide-react";
 import type { DbConnection, DbAllowlistProfile } from "../../rag/types";
 import { cn } from "../../../utils/cn";
+import { formatAppError } from "../../../utils/appError";
 
 interface ProfileConfigModalProps {
   connection: DbConnection | null;
@@ -32,7 +33,7 @@ export function ProfileConfigModal({ connection, onClose }: ProfileConfigModalPr
         setSelectedProfile(result[0]);
       }
     } catch (err) {
-      const errorMsg = err instanceof Error ? err.message : String(err);
+      const errorMsg = formatAppError(err);
       setError(`Failed to load profiles: ${errorMsg}`);
     } finally {
       setLoading(false);
@@ -10,6 +10,7 @@ import {
   AlertCircle,
 } from "lucide-react";
 import type { DbAllowlistProfile } from "../../rag/types";
+import { formatAppError } from "../../../utils/appError";
 
 interface ProfileRules {
   allowed_tables: Record<string, string[]>;
@@ -117,18 +118,7 @@ export function ProfileManagementModal({
       setTimeout(() => setSuccess(null), 2000);
     } catch (err) {
       // Handle Tauri errors properly
-      let errorMsg = "Unknown error";
-      if (err) {
-        if (typeof err === "string") {
-          errorMsg = err;
-        } else if (err instanceof Error) {
-          errorMsg = err.message;
-        } else if (typeof err === "object" && "message" in err) {
-          errorMsg = String(err.message);
-        } else {
-          errorMsg = JSON.stringify(err);
-        }
-      }
+      const errorMsg = formatAppError(err);
       setError(`Failed to delete: ${errorMsg}`);
     } finally {
       setLoading(false);
@@ -185,18 +175,7 @@ export function ProfileManagementModal({
       }, 1500);
     } catch (err) {
       // Handle Tauri errors properly
-      let errorMsg = "Unknown error";
-      if (err) {
-        if (typeof err === "string") {
-          errorMsg = err;
-        } else if (err instanceof Error) {
-          errorMsg = err.message;
-        } else if (typeof err === "object" && "message" in err) {
-          errorMsg = String(err.message);
-        } else {
-          errorMsg = JSON.stringify(err);
-        }
-      }
+      const errorMsg = formatAppError(err);
       setError(`Failed to save: ${errorMsg}`);
     } finally {
       setLoading(false);
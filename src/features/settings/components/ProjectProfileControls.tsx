@@ -0,0 +1,353 @@
+import { useEffect, useState } from "react";
+import { Copy, Loader2, Play, Plus, Save, Trash2 } from "lucide-react";
+import { Switch } from "../../../shared/components/Switch";
+import { useToastStore } from "../../../store/toast";
+import { formatAppError } from "../../../utils/appError";
+import { isTauri } from "../../../utils/tauri";
+import { applyProjectProfile, projectProfileLink } from "../api/projectProfiles";
+import {
+  DEFAULT_PROJECT_PROFILE_SETTINGS,
+  DEFAULT_QA_PROXY_SETTINGS,
+  getSettings,
+  onSettingsChanged,
+  updateSettingsSection,
+  type ProjectProfile,
+  type ProjectProfileSettings,
+  type QaProxySettings,
+} from "../api/settings";
+
+const INPUT_CLASS =
+  "w-full bg-background border border-app-border rounded-md p-1.5 px-2.5 text-[11px] outline-none focus:border-app-text/30 transition";
+const BUTTON_CLASS =
+  "flex items-center gap-1 px-2 py-1 text-[10px] border border-app-border rounded-md hover:border-app-text/30 transition disabled:opacity-50";
+
+function newProfile(existing: ProjectProfile[]): ProjectProfile {
+  let index = existing.length + 1;
+  while (existing.some((profile) => profile.id === `project-${index}`)) index += 1;
+  return {
+    id: `project-${index}`,
+    name: `Project ${index}`,
+    mockRoutesDir: null,
+    startMockServer: true,
+    qaProxy: null,
+    qaTargetUrl: null,
+    qaApiBaseUrl: null,
+    ragCollectionId: null,
+  };
+}
+
+const orNull = (value: string) => value.trim() || null;
+
+/** Per-project mock routes, QA proxy and RAG collection, applied together. */
+export default function ProjectProfileControls() {
+  const { addToast } = useToastStore();
+  const [settings, setSettings] = useState<ProjectProfileSettings>(
+    DEFAULT_PROJECT_PROFILE_SETTINGS,
+  );
+  const [qaProxy, setQaProxy] = useState<QaProxySettings>(DEFAULT_QA_PROXY_SETTINGS);
+  const [selectedId, setSelectedId] = useState<string | null>(null);
+  const [draft, setDraft] = useState<ProjectProfile | null>(null);
+  const [busy, setBusy] = useState<"save" | "apply" | null>(null);
+
+  useEffect(() => {
+    if (!isTauri()) return;
+    let disposed = false;
+    let unlisten: (() => void) | undefined;
+
+    const applySettings = (value: unknown) => {
+      setSettings({
+        ...DEFAULT_PROJECT_PROFILE_SETTINGS,
+        ...(value as Partial<ProjectProfileSettings>),
+      });
+    };
+    const applyProxy = (value: unknown) => {
+      setQaProxy({ ...DEFAULT_QA_PROXY_SETTINGS, ...(value as Partial<QaProxySettings>) });
+    };
+
+    getSettings()
+      .then((document) => {
+        if (disposed) return;
+        applySettings(document.sections.projectProfiles);
+        applyProxy(document.sections.qaProxy);
+      })
+      .catch(console.error);
+
+    onSettingsChanged((change) => {
+      if (change.section === "projectProfiles") applySettings(change.value);
+      if (change.section === "qaProxy") applyProxy(change.value);
+    })
+      .then((fn) => {
+        if (disposed) {
+          fn();
+          return;
+        }
+        unlisten = fn;
+      })
+      .catch(console.error);
+
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, []);
+
+  // Keep the draft on the saved profile unless it is being edited
+  useEffect(() => {
+    const saved = settings.profiles.find((profile) => profile.id === selectedId) ?? null;
+    setDraft((current) => (current && current.id === selectedId ? current : saved));
+  }, [settings, selectedId]);
+
+  const save = async (next: ProjectProfileSettings): Promise<boolean> => {
+    const previous = settings;
+    setSettings(next);
+    try {
+      await updateSettingsSection("projectProfiles", next);
+      return true;
+    } catch (e) {
+      setSettings(previous);
+      addToast(formatAppError(e, "Failed to save project profiles"), "error");
+      return false;
+    }
+  };
+
+  const add = async () => {
+    const profile = newProfile(settings.profiles);
+    if (await save({ ...settings, profiles: [...settings.profiles, profile] })) {
+      setSelectedId(profile.id);
+      setDraft(profile);
+    }
+  };
+
+  const remove = async (id: string) => {
+    const next: ProjectProfileSettings = {
+      ...settings,
+      profiles: settings.profiles.filter((profile) => profile.id !== id),
+      activeProfileId: settings.activeProfileId === id ? null : settings.activeProfileId,
+    };
+    if (await save(next)) {
+      setSelectedId(null);
+      setDraft(null);
+    }
+  };
+
+  const saveDraft = async () => {
+    if (!draft || !selectedId) return;
+    const profile: ProjectProfile = {
+      ...draft,
+      id: draft.id.trim(),
+      name: draft.name.trim(),
+      mockRoutesDir: orNull(draft.mockRoutesDir ?? ""),
+      qaTargetUrl: orNull(draft.qaTargetUrl ?? ""),
+      qaApiBaseUrl: orNull(draft.qaApiBaseUrl ?? ""),
+    };
+    setBusy("save");
+    const saved = await save({
+      ...settings,
+      profiles: settings.profiles.map((entry) => (entry.id === selectedId ? profile : entry)),
+      activeProfileId:
+        settings.activeProfileId === selectedId ? profile.id : settings.activeProfileId,
+    });
+    setBusy(null);
+    if (saved) {
+      setSelectedId(profile.id);
+      setDraft(profile);
+    }
+  };
+
+  const apply = async (id: string) => {
+    setBusy("apply");
+    try {
+      const applied = await applyProjectProfile(id);
+      if (applied.warnings.length > 0) {
+        addToast(`Applied ${applied.profile.name}: ${applied.warnings.join("; ")}`, "error");
+      } else {
+        addToast(`Applied ${applied.profile.name}`, "success");
+      }
+    } catch (e) {
+      addToast(formatAppError(e, "Failed to apply profile"), "error");
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const copyLink = async (id: string) => {
+    try {
+      await navigator.clipboard.writeText(projectProfileLink(id));
+      addToast("Profile link copied", "success");
+    } catch {
+      addToast("Failed to copy profile link", "error");
+    }
+  };
+
+  const edit = (patch: Partial<ProjectProfile>) =>
+    setDraft((current) => (current ? { ...current, ...patch } : current));
+
+  return (
+    <div className="space-y-3 p-3 rounded-lg bg-background/40 border border-app-border/30">
+      <div className="flex justify-between items-center gap-4">
+        <div>
+          <div className="text-xs text-app-text font-medium">Apply active profile on launch</div>
+          <div className="text-[10px] text-app-subtext mt-0.5">
+            Launching with `--profile &lt;id&gt;` or a profile link always applies that profile.
+          </div>
+        </div>
+        <Switch
+          checked={settings.applyOnLaunch}
+          disabled={!isTauri()}
+          onCheckedChange={(checked) => save({ ...settings, applyOnLaunch: checked })}
+        />
+      </div>
+
+      <div className="space-y-1.5">
+        {settings.profiles.map((profile) => (
+          <div
+            key={profile.id}
+            className={`flex items-center justify-between gap-2 p-2 rounded-md border cursor-pointer transition ${
+              profile.id === selectedId
+                ? "border-app-text/30 bg-background"
+                : "border-app-border/30 hover:border-app-border"
+            }`}
+            onClick={() => setSelectedId(profile.id === selectedId ? null : profile.id)}>
+            <div className="min-w-0 text-[11px]">
+              <div className="text-app-text font-medium truncate">
+                {profile.name}
+                {profile.id === settings.activeProfileId && (
+                  <span className="ml-1.5 text-[9px] text-app-success">active</span>
+                )}
+              </div>
+              <div className="text-[10px] text-app-subtext truncate">{profile.id}</div>
+            </div>
+            <div className="flex items-center gap-1.5 shrink-0">
+              <button
+                className={BUTTON_CLASS}
+                disabled={!isTauri() || busy !== null}
+                onClick={(e) => {
+                  e.stopPropagation();
+                  apply(profile.id);
+                }}>
+                {busy === "apply" ? (
+                  <Loader2 className="w-3 h-3 animate-spin" />
+                ) : (
+                  <Play className="w-3 h-3" />
+                )}
+                Apply
+              </button>
+              <button
+                className="p-1 text-app-subtext hover:text-app-text transition"
+                onClick={(e) => {
+                  e.stopPropagation();
+                  copyLink(profile.id);
+                }}
+                aria-label={`Copy link for ${profile.name}`}>
+                <Copy className="w-3.5 h-3.5" />
+              </button>
+              <button
+                className="p-1 text-app-subtext hover:text-red-400 transition disabled:opacity-50"
+                disabled={!isTauri() || busy !== null}
+                onClick={(e) => {
+                  e.stopPropagation();
+                  remove(profile.id);
+                }}
+                aria-label={`Delete ${profile.name}`}>
+                <Trash2 className="w-3.5 h-3.5" />
+              </button>
+            </div>
+          </div>
+        ))}
+        <button className={BUTTON_CLASS} disabled={!isTauri() || busy !== null} onClick={add}>
+          <Plus className="w-3 h-3" />
+          Add profile
+        </button>
+      </div>
+
+      {draft && (
+        <div className="space-y-2 pt-2 border-t border-app-border/30">
+          <div className="grid grid-cols-2 gap-2">
+            <input
+              className={INPUT_CLASS}
+              placeholder="id"
+              value={draft.id}
+              onChange={(e) => edit({ id: e.target.value })}
+            />
+            <input
+              className={INPUT_CLASS}
+              placeholder="Name"
+              value={draft.name}
+              onChange={(e) => edit({ name: e.target.value })}
+            />
+          </div>
+          <input
+            className={INPUT_CLASS}
+            placeholder="Mock routes directory (empty keeps the current routes)"
+            value={draft.mockRoutesDir ?? ""}
+            onChange={(e) => edit({ mockRoutesDir: e.target.value })}
+          />
+          <div className="flex justify-between items-center gap-4">
+            <div className="text-[11px] text-app-text">Start mock server</div>
+            <Switch
+              checked={draft.startMockServer}
+              onCheckedChange={(checked) => edit({ startMockServer: checked })}
+            />
+          </div>
+          <div className="grid grid-cols-2 gap-2">
+            <input
+              className={INPUT_CLASS}
+              placeholder="QA target URL"
+              value={draft.qaTargetUrl ?? ""}
+              onChange={(e) => edit({ qaTargetUrl: e.target.value })}
+            />
+            <input
+              className={INPUT_CLASS}
+              placeholder="QA API base URL"
+              value={draft.qaApiBaseUrl ?? ""}
+              onChange={(e) => edit({ qaApiBaseUrl: e.target.value })}
+            />
+          </div>
+          <div className="flex items-center justify-between gap-2 text-[10px] text-app-subtext">
+            <span>
+              {draft.qaProxy
+                ? `QA proxy: ${draft.qaProxy.originRewrites.length} rewrites, ${draft.qaProxy.injectHeaders.length} headers`
+                : "QA proxy settings are left as they are"}
+            </span>
+            <div className="flex items-center gap-1.5">
+              <button className={BUTTON_CLASS} onClick={() => edit({ qaProxy: qaProxy })}>
+                Use current
+              </button>
+              {draft.qaProxy && (
+                <button className={BUTTON_CLASS} onClick={() => edit({ qaProxy: null })}>
+                  Clear
+                </button>
+              )}
+            </div>
+          </div>
+          <input
+            className={INPUT_CLASS}
+            type="number"
+            min={1}
+            placeholder="RAG collection id"
+            value={draft.ragCollectionId ?? ""}
+            onChange={(e) =>
+              edit({ ragCollectionId: e.target.value ? Number(e.target.value) : null })
+            }
+          />
+          <div className="flex items-center justify-between gap-2">
+            <code className="text-[10px] text-app-subtext truncate">
+              {projectProfileLink(draft.id.trim() || selectedId || "")}
+            </code>
+            <button
+              className={BUTTON_CLASS}
+              disabled={!isTauri() || busy !== null}
+              onClick={saveDraft}>
+              {busy === "save" ? (
+                <Loader2 className="w-3 h-3 animate-spin" />
+              ) : (
+                <Save className="w-3 h-3" />
+              )}
+              Save
+            </button>
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -19,6 +19,7 @@ import {
   dbImportQueryTemplatesFromPreview,
   dbPreviewQueryTemplatesImportFromSqlFile,
 } from "../../rag/api";
+import { formatAppError } from "../../../utils/appError";
 
 type Props = {
   isOpen: boolean;
@@ -113,7 +114,7 @@ export function QueryTemplateImportReviewModal({
         setSelectedKeys(next);
       }
     } catch (e) {
-      const msg = e instanceof Error ? e.message : String(e);
+      const msg = formatAppError(e);
       setError(msg);
       addToast?.(`Preview failed: ${msg}`, "error");
     } finally {
@@ -160,7 +161,7 @@ export function QueryTemplateImportReviewModal({
       await onImported();
       onClose();
     } catch (e) {
-      const msg = e instanceof Error ? e.message : String(e);
+      const msg = formatAppError(e);
       setError(msg);
       addToast?.(`Import failed: ${msg}`, "error");
     } finally {
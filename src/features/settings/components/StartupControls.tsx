@@ -0,0 +1,111 @@
+import { useEffect, useState } from "react";
+import { Switch } from "../../../shared/components/Switch";
+import { useToastStore } from "../../../store/toast";
+import { isTauri } from "../../../utils/tauri";
+import {
+  DEFAULT_STARTUP_SETTINGS,
+  getSettings,
+  onSettingsChanged,
+  updateSettingsSection,
+  type StartupSettings,
+} from "../api/settings";
+
+const OPTIONS: {
+  key: keyof StartupSettings;
+  label: string;
+  description: string;
+}[] = [
+  {
+    key: "launchAtLogin",
+    label: "Launch at login",
+    description:
+      "Start minimized to the tray with shortcuts and the HTTP API. RAG and training load when the window opens.",
+  },
+  {
+    key: "startMockServer",
+    label: "Start mock server on launch",
+    description: "Serve the configured mock routes as soon as the app starts.",
+  },
+];
+
+export default function StartupControls() {
+  const { addToast } = useToastStore();
+  const [startup, setStartup] = useState<StartupSettings>(
+    DEFAULT_STARTUP_SETTINGS,
+  );
+
+  useEffect(() => {
+    if (!isTauri()) return;
+    let disposed = false;
+    let unlisten: (() => void) | undefined;
+
+    getSettings()
+      .then((document) => {
+        if (disposed) return;
+        setStartup({
+          ...DEFAULT_STARTUP_SETTINGS,
+          ...(document.sections.startup as Partial<StartupSettings>),
+        });
+      })
+      .catch(console.error);
+
+    onSettingsChanged((change) => {
+      if (change.section === "startup") {
+        setStartup({
+          ...DEFAULT_STARTUP_SETTINGS,
+          ...(change.value as Partial<StartupSettings>),
+        });
+      }
+    })
+      .then((fn) => {
+        if (disposed) {
+          fn();
+          return;
+        }
+        unlisten = fn;
+      })
+      .catch(console.error);
+
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, []);
+
+  const update = async (key: keyof StartupSettings, value: boolean) => {
+    const previous = startup;
+    const next = { ...startup, [key]: value };
+    setStartup(next);
+    try {
+      await updateSettingsSection("startup", next);
+    } catch (e: any) {
+      setStartup(previous);
+      addToast(
+        typeof e === "string" ? e : e?.message || "Failed to save startup settings",
+        "error",
+      );
+    }
+  };
+
+  return (
+    <div className="space-y-2.5 mb-5">
+      {OPTIONS.map((option) => (
+        <div
+          key={option.key}
+          className="flex justify-between items-center gap-4 p-3 rounded-lg bg-background/40 border border-app-border/30">
+          <div>
+            <div className="text-xs text-app-text font-medium">{option.label}</div>
+            <div className="text-[10px] text-app-subtext mt-0.5">
+              {option.description}
+            </div>
+          </div>
+          <Switch
+            checked={startup[option.key]}
+            disabled={!isTauri()}
+            onCheckedChange={(checked) => update(option.key, checked)}
+          />
+        </div>
+      ))}
+    </div>
+  );
+}
@@ -3,6 +3,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { motion, AnimatePresence } from "framer-motion";
 import { Plus, Database, Loader2, ChevronRight, AlertCircle, CheckCircle2, Trash2, RefreshCw } from "lucide-react";
 import type { DbConnection, DbAllowlistProfile, TableInfo } from "../../rag/types";
+import { formatAppError } from "../../../utils/appError";
 
 interface TableAccessModalProps {
   connection: DbConnection | null;
@@ -38,7 +39,7 @@ export function TableAccessModal({ connection, onClose }: TableAccessModalProps)
         await loadAllowedTables(result[0].id);
       }
     } catch (err) {
-      const errorMsg = err instanceof Error ? err.message : String(err);
+      const errorMsg = formatAppError(err);
       setError(`Failed to load profiles: ${errorMsg}`);
     } finally {
       setLoading(false);
@@ -95,7 +96,7 @@ export function TableAccessModal({ connection, onClose }: TableAccessModalProps)
       });
       setAvailableTables(tables);
     } catch (err) {
-      const errorMsg = err instanceof Error ? err.message : String(err);
+      const errorMsg = formatAppError(err);
       setError(`Failed to load tables: ${errorMsg}`);
     } finally {
       setLoadingTables(false);
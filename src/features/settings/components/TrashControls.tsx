@@ -0,0 +1,130 @@
+import { useCallback, useEffect, useState } from "react";
+import { Loader2, RefreshCw, RotateCcw, Trash2 } from "lucide-react";
+import { useToastStore } from "../../../store/toast";
+import { formatAppError } from "../../../utils/appError";
+import { isTauri } from "../../../utils/tauri";
+import { listTrash, purgeTrash, restoreFromTrash, type TrashEntry, type TrashKind } from "../api/trash";
+
+const BUTTON_CLASS =
+  "flex items-center gap-1 px-2 py-1 text-[10px] border border-app-border rounded-md hover:border-app-text/30 transition disabled:opacity-50";
+
+const KIND_LABELS: Record<TrashKind, string> = {
+  qa_session: "QA session",
+  rag_document: "Document",
+  dataset: "Dataset",
+};
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+/** Deleted QA sessions, documents and datasets, kept for 30 days. */
+export default function TrashControls() {
+  const { addToast } = useToastStore();
+  const [entries, setEntries] = useState<TrashEntry[]>([]);
+  const [busy, setBusy] = useState<string | null>(null);
+
+  const load = useCallback(async () => {
+    if (!isTauri()) return;
+    try {
+      setEntries(await listTrash());
+    } catch (e) {
+      addToast(formatAppError(e, "Failed to load the trash"), "error");
+    }
+  }, [addToast]);
+
+  useEffect(() => {
+    load();
+  }, [load]);
+
+  const restore = async (entry: TrashEntry) => {
+    setBusy(`${entry.kind}:${entry.id}`);
+    try {
+      await restoreFromTrash(entry.kind, entry.id);
+      addToast(`Restored ${entry.name}`, "success");
+      await load();
+    } catch (e) {
+      addToast(formatAppError(e, "Failed to restore"), "error");
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const emptyTrash = async () => {
+    setBusy("purge");
+    try {
+      const report = await purgeTrash(true);
+      const purged = report.qaSessions + report.ragDocuments + report.datasets;
+      if (report.errors.length > 0) {
+        addToast(`Purged ${purged} items; failed: ${report.errors.join("; ")}`, "error");
+      } else {
+        addToast(`Purged ${purged} items`, "success");
+      }
+      await load();
+    } catch (e) {
+      addToast(formatAppError(e, "Failed to empty the trash"), "error");
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  return (
+    <div className="space-y-2 p-3 rounded-lg bg-background/40 border border-app-border/30">
+      <div className="flex justify-between items-center gap-4">
+        <div className="text-[10px] text-app-subtext">
+          Deleted items are kept for 30 days before they are purged.
+        </div>
+        <div className="flex items-center gap-1.5 shrink-0">
+          <button className={BUTTON_CLASS} disabled={!isTauri() || busy !== null} onClick={load}>
+            <RefreshCw className="w-3 h-3" />
+            Refresh
+          </button>
+          <button
+            className={BUTTON_CLASS}
+            disabled={!isTauri() || busy !== null || entries.length === 0}
+            onClick={emptyTrash}>
+            {busy === "purge" ? (
+              <Loader2 className="w-3 h-3 animate-spin" />
+            ) : (
+              <Trash2 className="w-3 h-3" />
+            )}
+            Empty trash
+          </button>
+        </div>
+      </div>
+
+      {entries.length === 0 ? (
+        <div className="text-[10px] text-app-subtext">The trash is empty.</div>
+      ) : (
+        <div className="space-y-1.5 max-h-64 overflow-y-auto">
+          {entries.map((entry) => {
+            const key = `${entry.kind}:${entry.id}`;
+            const daysLeft = Math.max(0, Math.ceil((entry.purgeAt - Date.now()) / DAY_MS));
+            return (
+              <div
+                key={key}
+                className="flex items-center justify-between gap-2 p-2 rounded-md border border-app-border/30">
+                <div className="min-w-0 text-[11px]">
+                  <div className="text-app-text font-medium truncate">{entry.name}</div>
+                  <div className="text-[10px] text-app-subtext truncate">
+                    {KIND_LABELS[entry.kind]} · deleted {new Date(entry.deletedAt).toLocaleString()}{" "}
+                    · purged in {daysLeft} {daysLeft === 1 ? "day" : "days"}
+                  </div>
+                </div>
+                <button
+                  className={BUTTON_CLASS}
+                  disabled={!isTauri() || busy !== null}
+                  onClick={() => restore(entry)}>
+                  {busy === key ? (
+                    <Loader2 className="w-3 h-3 animate-spin" />
+                  ) : (
+                    <RotateCcw className="w-3 h-3" />
+                  )}
+                  Restore
+                </button>
+              </div>
+            );
+          })}
+        </div>
+      )}
+    </div>
+  );
+}
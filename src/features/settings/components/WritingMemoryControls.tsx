@@ -0,0 +1,188 @@
+import { useEffect, useState } from "react";
+import { BookHeart, Loader2, RefreshCw } from "lucide-react";
+import { Switch } from "../../../shared/components/Switch";
+import { useToastStore } from "../../../store/toast";
+import { isTauri } from "../../../utils/tauri";
+import { syncWritingMemory } from "../../rag/api/writingMemory";
+import {
+  DEFAULT_WRITING_MEMORY_SETTINGS,
+  getSettings,
+  onSettingsChanged,
+  updateSettingsSection,
+  type WritingMemorySettings,
+} from "../api/settings";
+
+type WritingMemoryToggle = "enabled" | "includeTranslations" | "includeEnhancements";
+
+const OPTIONS: {
+  key: WritingMemoryToggle;
+  label: string;
+  description: string;
+}[] = [
+  {
+    key: "enabled",
+    label: "Keep a writing memory",
+    description:
+      "Add accepted work to a RAG collection so chat and retrieval can use it.",
+  },
+  {
+    key: "includeTranslations",
+    label: "Translations",
+    description:
+      "Approved translation memory entries and starred translations in history.",
+  },
+  {
+    key: "includeEnhancements",
+    label: "Enhancements",
+    description: "Enhanced prompts starred in history.",
+  },
+];
+
+function errorMessage(e: any, fallback: string): string {
+  return typeof e === "string" ? e : e?.message || fallback;
+}
+
+export default function WritingMemoryControls() {
+  const { addToast } = useToastStore();
+  const [memory, setMemory] = useState<WritingMemorySettings>(
+    DEFAULT_WRITING_MEMORY_SETTINGS,
+  );
+  const [collectionName, setCollectionName] = useState(
+    DEFAULT_WRITING_MEMORY_SETTINGS.collectionName,
+  );
+  const [isSyncing, setIsSyncing] = useState(false);
+
+  useEffect(() => {
+    if (!isTauri()) return;
+    let disposed = false;
+    let unlisten: (() => void) | undefined;
+
+    const apply = (value: unknown) => {
+      const next = {
+        ...DEFAULT_WRITING_MEMORY_SETTINGS,
+        ...(value as Partial<WritingMemorySettings>),
+      };
+      setMemory(next);
+      setCollectionName(next.collectionName);
+    };
+
+    getSettings()
+      .then((document) => {
+        if (!disposed) apply(document.sections.writingMemory);
+      })
+      .catch(console.error);
+
+    onSettingsChanged((change) => {
+      if (change.section === "writingMemory") apply(change.value);
+    })
+      .then((fn) => {
+        if (disposed) {
+          fn();
+          return;
+        }
+        unlisten = fn;
+      })
+      .catch(console.error);
+
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, []);
+
+  const save = async (next: WritingMemorySettings) => {
+    const previous = memory;
+    setMemory(next);
+    try {
+      await updateSettingsSection("writingMemory", next);
+    } catch (e: any) {
+      setMemory(previous);
+      setCollectionName(previous.collectionName);
+      addToast(errorMessage(e, "Failed to save writing memory settings"), "error");
+    }
+  };
+
+  const saveCollectionName = () => {
+    const name = collectionName.trim();
+    if (name === memory.collectionName) return;
+    save({ ...memory, collectionName: name });
+  };
+
+  const handleSync = async () => {
+    setIsSyncing(true);
+    try {
+      const report = await syncWritingMemory();
+      addToast(
+        `Writing memory: ${report.added} added, ${report.skipped} already stored` +
+          (report.failed > 0 ? `, ${report.failed} failed` : ""),
+        report.failed > 0 ? "warning" : "success",
+      );
+    } catch (e: any) {
+      addToast(errorMessage(e, "Writing memory sync failed"), "error");
+    } finally {
+      setIsSyncing(false);
+    }
+  };
+
+  return (
+    <div className="bg-app-card rounded-lg border border-app-border p-4">
+      <div className="flex items-center gap-2 mb-4">
+        <BookHeart className="w-4 h-4 text-primary" />
+        <h3 className="font-medium">Writing Memory</h3>
+      </div>
+
+      <div className="space-y-2.5">
+        {OPTIONS.map((option) => (
+          <div
+            key={option.key}
+            className="flex justify-between items-center gap-4 p-3 rounded-lg bg-background/40 border border-app-border/30">
+            <div>
+              <div className="text-xs text-app-text font-medium">{option.label}</div>
+              <div className="text-[10px] text-app-subtext mt-0.5">
+                {option.description}
+              </div>
+            </div>
+            <Switch
+              checked={memory[option.key]}
+              disabled={!isTauri() || (option.key !== "enabled" && !memory.enabled)}
+              onCheckedChange={(checked) => save({ ...memory, [option.key]: checked })}
+            />
+          </div>
+        ))}
+
+        <div>
+          <label className="text-sm text-app-subtext block mb-2">Collection</label>
+          <input
+            type="text"
+            value={collectionName}
+            disabled={!isTauri()}
+            onChange={(e) => setCollectionName(e.target.value)}
+            onBlur={saveCollectionName}
+            onKeyDown={(e) => {
+              if (e.key === "Enter") saveCollectionName();
+            }}
+            className="w-full bg-app-bg border border-app-border rounded px-3 py-2 text-sm"
+          />
+          <p className="text-xs text-app-subtext mt-1">
+            Created on first use. Each entry is stored once, with its
+            languages and date.
+          </p>
+        </div>
+
+        <div className="flex justify-end">
+          <button
+            onClick={handleSync}
+            disabled={!isTauri() || !memory.enabled || isSyncing}
+            className="px-4 py-2 border border-app-border rounded hover:border-primary transition disabled:opacity-50 flex items-center gap-2 text-sm">
+            {isSyncing ? (
+              <Loader2 className="w-4 h-4 animate-spin" />
+            ) : (
+              <RefreshCw className="w-4 h-4" />
+            )}
+            Add past accepted work
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
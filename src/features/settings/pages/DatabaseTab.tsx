@@ -20,6 +20,7 @@ import { DbConnectionForm } from "../components/DbConnectionForm";
 import { DbConnectionConfigModal } from "../components/DbConnectionConfigModal";
 import { ConnectionTable } from "../components/ConnectionTable";
 import { EmptyState } from "../components/EmptyState";
+import { formatAppError } from "../../../utils/appError";
 
 export default function DatabaseTab() {
   const [connections, setConnections] = useState<DbConnection[]>([]);
@@ -45,7 +46,7 @@ export default function DatabaseTab() {
       const result = await invoke<DbConnection[]>("db_list_connections");
       setConnections(result);
     } catch (error) {
-      const errorMsg = error instanceof Error ? error.message : String(error);
+      const errorMsg = formatAppError(error);
       setError(`Failed to load connections: ${errorMsg}`);
       console.error("Failed to load connections:", error);
     } finally {
@@ -69,7 +70,7 @@ export default function DatabaseTab() {
         setError(result.message);
       }
     } catch (error) {
-      const errorMsg = error instanceof Error ? error.message : String(error);
+      const errorMsg = formatAppError(error);
       setError(`Connection test failed: ${errorMsg}`);
     }
   };
@@ -83,7 +84,7 @@ export default function DatabaseTab() {
       setTimeout(() => setSuccessMessage(null), 3000);
       loadConnections();
     } catch (error) {
-      const errorMsg = error instanceof Error ? error.message : String(error);
+      const errorMsg = formatAppError(error);
       setError(`Failed to delete connection: ${errorMsg}`);
     } finally {
       setDeleteConfirm(null);
@@ -9,6 +9,8 @@ import {
   Power,
   CheckCircle,
   ChevronDown,
+  Layers,
+  Sparkles,
 } from "lucide-react";
 import { useSettingsStore, type LLMProvider } from "../../../store/settings";
 import { invoke } from "@tauri-apps/api/core";
@@ -23,6 +25,14 @@ import {
 import { isTauri } from "../../../utils/tauri";
 import { useThemeStore } from "../../theme/themeStore";
 import { LOCAL_LLM_BASE_URL } from "../../../shared/api/llmConfig";
+import EmbeddingModelManager from "../components/EmbeddingModelManager";
+import EmbeddingAccelerationControls from "../components/EmbeddingAccelerationControls";
+import StartupControls from "../components/StartupControls";
+import FeatureModelOverrides from "../components/FeatureModelOverrides";
+import EnhancePresetControls from "../components/EnhancePresetControls";
+import OllamaControls from "../components/OllamaControls";
+import ProjectProfileControls from "../components/ProjectProfileControls";
+import TrashControls from "../components/TrashControls";
 
 const LANGUAGES = [
   "Auto Detect",
@@ -420,12 +430,23 @@ export default function GeneralTab() {
                     className="w-full bg-background border border-app-border rounded-lg p-2.5 px-4 text-xs appearance-none cursor-pointer hover:border-app-text/30 transition outline-none shadow-sm">
                     <option value="all-minilm-l6-v2">all-MiniLM-L6-v2 (384 dim, fast)</option>
                     <option value="nomic-embed-text-v1.5">nomic-embed-text-v1.5 (768 dim, best)</option>
+                    <option value="all-minilm-l12-v2">all-MiniLM-L12-v2 (384 dim)</option>
+                    <option value="nomic-embed-text-v1">nomic-embed-text-v1 (768 dim)</option>
                     <option value="bge-small-en-v1.5">bge-small-en-v1.5 (384 dim)</option>
+                    <option value="bge-base-en-v1.5">bge-base-en-v1.5 (768 dim)</option>
+                    <option value="bge-large-en-v1.5">bge-large-en-v1.5 (1024 dim)</option>
                     <option value="multilingual-e5-small">multilingual-e5-small (384 dim, multi)</option>
+                    <option value="multilingual-e5-base">multilingual-e5-base (768 dim, multi)</option>
                   </select>
                   <ChevronDown className="w-3.5 h-3.5 absolute right-4 top-1/2 -translate-y-1/2 text-app-subtext/60 pointer-events-none" />
                 </div>
               </div>
+              {embeddingProvider === "local" && (
+                <>
+                  <EmbeddingModelManager />
+                  <EmbeddingAccelerationControls />
+                </>
+              )}
             </div>
 
             {/* Provider Specific Status Messages */}
@@ -435,6 +456,7 @@ export default function GeneralTab() {
                 <span className="font-medium tracking-wide">Local LLM provider active (LM Studio, Ollama, or Llama.cpp).</span>
               </div>
             )}
+            {provider === "ollama" && <OllamaControls baseUrl={baseUrl} />}
             {isCliProxyProvider && (
               <div className="bg-app-success-dim border border-app-success/20 text-app-success rounded-lg px-4 py-3 text-[10px] flex items-center gap-3 animate-in fade-in slide-in-from-top-1 duration-300 shadow-sm">
                 <CheckCircle className="w-4 h-4 shrink-0" />
@@ -523,6 +545,36 @@ export default function GeneralTab() {
               <p className="text-[10px] text-app-subtext/60 italic px-0.5">Applied to AI summaries and test cases generated by the assistant.</p>
             </div>
           </section>
+
+          {/* Card: Feature Models */}
+          <section className="bg-app-card rounded-xl border border-app-border/40 p-6 shadow-xl backdrop-blur-sm relative overflow-hidden group">
+            <div className="absolute top-0 left-0 w-1 h-full bg-purple-500/40 group-hover:bg-purple-500 transition-colors duration-300"></div>
+            <div className="flex items-start gap-4 mb-6">
+              <div className="p-3 rounded-lg bg-purple-500/10 text-purple-400 border border-purple-500/20 shadow-inner">
+                <Layers className="w-5 h-5" />
+              </div>
+              <div>
+                <h3 className="text-base font-semibold text-app-text leading-tight">Feature Models</h3>
+                <p className="text-[11px] text-app-subtext mt-1">Run a feature on its own provider and model</p>
+              </div>
+            </div>
+            <FeatureModelOverrides />
+          </section>
+
+          {/* Card: Enhancement Presets */}
+          <section className="bg-app-card rounded-xl border border-app-border/40 p-6 shadow-xl backdrop-blur-sm relative overflow-hidden group">
+            <div className="absolute top-0 left-0 w-1 h-full bg-yellow-500/40 group-hover:bg-yellow-500 transition-colors duration-300"></div>
+            <div className="flex items-start gap-4 mb-6">
+              <div className="p-3 rounded-lg bg-yellow-500/10 text-yellow-400 border border-yellow-500/20 shadow-inner">
+                <Sparkles className="w-5 h-5" />
+              </div>
+              <div>
+                <h3 className="text-base font-semibold text-app-text leading-tight">Enhancement Presets</h3>
+                <p className="text-[11px] text-app-subtext mt-1">Tone, length and format for prompt enhancement</p>
+              </div>
+            </div>
+            <EnhancePresetControls />
+          </section>
         </div>
 
         {/* Right Column */}
@@ -640,6 +692,18 @@ export default function GeneralTab() {
             <Switch checked={shortcutsEnabled} onCheckedChange={setShortcutsEnabled} />
           </div>
 
+          <StartupControls />
+
+          <div className="mb-5">
+            <label className="text-[9px] text-app-subtext/70 font-bold uppercase tracking-wider block mb-1.5">Project Profiles</label>
+            <ProjectProfileControls />
+          </div>
+
+          <div className="mb-5">
+            <label className="text-[9px] text-app-subtext/70 font-bold uppercase tracking-wider block mb-1.5">Trash</label>
+            <TrashControls />
+          </div>
+
           <div className="grid grid-cols-1 md:grid-cols-2 gap-4 mb-5">
             <div className="space-y-1.5">
               <label className="text-[9px] text-app-subtext/70 font-bold uppercase tracking-wider block">Palette</label>
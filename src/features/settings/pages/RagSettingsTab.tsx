@@ -11,6 +11,7 @@ import {
 } from "lucide-react";
 import { useSettingsStore } from "../../../store/settings";
 import { useToastStore } from "../../../store/toast";
+import WritingMemoryControls from "../components/WritingMemoryControls";
 import {
   getRagGlobalSettings,
   updateRagGlobalSettings,
@@ -463,6 +464,8 @@ export default function RagSettingsTab() {
             )}
           </button>
         </div>
+
+        <WritingMemoryControls />
       </aside>
     </div>
   );
@@ -3,11 +3,16 @@ import { TextArea } from "../../../shared/components/TextArea";
 import { Button } from "../../../shared/components/Button";
 import { Select } from "../../../shared/components/Select";
 import { Input } from "../../../shared/components/Input";
+import { Switch } from "../../../shared/components/Switch";
 import { Copy, Check, Code2 } from "lucide-react";
 import { useSettingsStore } from "../../../store/settings";
 import { useHistoryStore } from "../../../store/history";
 import { useLlmConfigBuilder } from "../../../hooks/useLlmConfig";
 import { useTypegenMutation } from "../../../hooks/useLlmApi";
+import type {
+  TypeGenNamingConvention,
+  TypeGenNullableStrategy,
+} from "../../../shared/api/apiClient";
 
 const LANGUAGES = [
   { value: "TypeScript", label: "TypeScript" },
@@ -17,6 +22,11 @@ const LANGUAGES = [
   { value: "Flutter", label: "Flutter" },
   { value: "Java", label: "Java" },
   { value: "PHP", label: "PHP" },
+  { value: "Kotlin", label: "Kotlin" },
+  { value: "Swift", label: "Swift" },
+  { value: "C#", label: "C#" },
+  { value: "Python", label: "Python (pydantic)" },
+  { value: "python-dataclass", label: "Python (dataclass)" },
 ];
 
 type TypeGenMode = "auto" | "offline" | "llm";
@@ -27,13 +37,46 @@ const MODES: Array<{ value: TypeGenMode; label: string }> = [
   { value: "llm", label: "LLM" },
 ];
 
+const NULLABLE_STRATEGIES: Array<{
+  value: TypeGenNullableStrategy;
+  label: string;
+}> = [
+  { value: "native", label: "Native (Option<T>, T | null)" },
+  { value: "default", label: "Defaults (Rust, Go, TypeScript)" },
+  { value: "undefined", label: "Optional props (TypeScript ?:)" },
+  { value: "optional", label: "Optional<T> (Java)" },
+];
+
+type MissingFields = "optional" | "required";
+
+const MISSING_FIELDS: Array<{ value: MissingFields; label: string }> = [
+  { value: "optional", label: "Optional" },
+  { value: "required", label: "Required" },
+];
+
+type FieldNaming = TypeGenNamingConvention | "language";
+
+const FIELD_NAMINGS: Array<{ value: FieldNaming; label: string }> = [
+  { value: "language", label: "Language default" },
+  { value: "camel_case", label: "camelCase" },
+  { value: "pascal_case", label: "PascalCase" },
+  { value: "snake_case", label: "snake_case" },
+];
+
 export default function TypeGenTab() {
   const [input, setInput] = useState("");
+  const [extraSamples, setExtraSamples] = useState("");
   const [output, setOutput] = useState("");
   const [copied, setCopied] = useState(false);
   const [rootName, setRootName] = useState("Root");
   const [language, setLanguage] = useState("TypeScript");
   const [mode, setMode] = useState<TypeGenMode>("auto");
+  const [serializationHelpers, setSerializationHelpers] = useState(false);
+  const [builders, setBuilders] = useState(false);
+  const [nullable, setNullable] = useState<TypeGenNullableStrategy>("native");
+  const [missingFields, setMissingFields] =
+    useState<MissingFields>("optional");
+  const [fieldNaming, setFieldNaming] = useState<FieldNaming>("language");
   const [jsonError, setJsonError] = useState<string | null>(null);
   const { provider, model } = useSettingsStore();
   const { addItem } = useHistoryStore();
@@ -66,6 +109,16 @@ export default function TypeGenTab() {
     }
   }, [input]);
 
+  // Extra samples of the same payload, separated by a line containing only "---".
+  const samples = useMemo(
+    () =>
+      extraSamples
+        .split(/^\s*---\s*$/m)
+        .map((sample) => sample.trim())
+        .filter(Boolean),
+    [extraSamples]
+  );
+
   const handleGenerate = async () => {
     if (!input.trim() || !jsonIsValid || isLoading) return;
 
@@ -77,9 +130,17 @@ export default function TypeGenTab() {
       const result = await typegenMutation.mutateAsync({
         config: buildConfig({ maxTokens: 2000, temperature: 0.2 }),
         json: input,
+        samples,
         language,
         root_name: rootName,
         mode,
+        options: {
+          serialization_helpers: serializationHelpers,
+          builders,
+          nullable,
+          missing_fields: missingFields,
+          field_naming: fieldNaming === "language" ? null : fieldNaming,
+        },
       });
 
       setOutput(result.result);
@@ -127,6 +188,7 @@ export default function TypeGenTab() {
           size="sm"
           onClick={() => {
             setInput("");
+            setExtraSamples("");
             setOutput("");
           }}
           className="text-app-subtext">
@@ -164,6 +226,56 @@ export default function TypeGenTab() {
         </div>
       </div>
 
+      <div className="grid gap-4 md:grid-cols-3">
+        <div className="space-y-2">
+          <label className="text-[10px] uppercase tracking-widest text-app-subtext">
+            Nullable Fields
+          </label>
+          <Select<TypeGenNullableStrategy>
+            options={NULLABLE_STRATEGIES}
+            value={nullable}
+            onChange={(v) => setNullable(typeof v === "string" ? v : v[0])}
+            searchable={false}
+          />
+        </div>
+        <div className="space-y-2">
+          <label className="text-[10px] uppercase tracking-widest text-app-subtext">
+            Fields Missing From Some Samples
+          </label>
+          <Select<MissingFields>
+            options={MISSING_FIELDS}
+            value={missingFields}
+            onChange={(v) => setMissingFields(typeof v === "string" ? v : v[0])}
+            searchable={false}
+          />
+        </div>
+        <div className="space-y-2">
+          <label className="text-[10px] uppercase tracking-widest text-app-subtext">
+            Field Naming
+          </label>
+          <Select<FieldNaming>
+            options={FIELD_NAMINGS}
+            value={fieldNaming}
+            onChange={(v) => setFieldNaming(typeof v === "string" ? v : v[0])}
+            searchable={false}
+          />
+        </div>
+      </div>
+
+      <div className="flex flex-wrap items-center gap-6 px-1">
+        <div className="flex items-center gap-2 text-[11px] text-app-subtext">
+          <Switch
+            checked={serializationHelpers}
+            onCheckedChange={setSerializationHelpers}
+          />
+          JSON helpers (Dart fromJson/toJson, Rust Value conversions)
+        </div>
+        <div className="flex items-center gap-2 text-[11px] text-app-subtext">
+          <Switch checked={builders} onCheckedChange={setBuilders} />
+          Constructors &amp; builders (Java)
+        </div>
+      </div>
+
       <div className="flex justify-center">
         <Button
           size="lg"
@@ -198,6 +310,20 @@ export default function TypeGenTab() {
             onInput={(e: any) => setInput(e.target.value)}
           />
           {jsonError && <p className="text-[11px] text-red-400">{jsonError}</p>}
+          <div className="flex items-center justify-between px-1 pt-2">
+            <span className="text-[10px] uppercase tracking-widest text-app-subtext font-bold">
+              Additional Samples
+            </span>
+            <span className="text-[10px] uppercase tracking-widest text-app-subtext">
+              {samples.length ? `${samples.length} extra` : "Optional"}
+            </span>
+          </div>
+          <TextArea
+            placeholder={"More responses from the same endpoint, separated by a line with ---"}
+            className="min-h-[120px] text-sm font-mono bg-black/20 border-app-border/40"
+            value={extraSamples}
+            onInput={(e: any) => setExtraSamples(e.target.value)}
+          />
         </div>
 
         <div className="space-y-2">
@@ -0,0 +1,46 @@
+import { useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { useToastStore } from "../store/toast";
+import { isTauri } from "../utils/tauri";
+
+/** Outcome of a `gadogado://` link or `--run` command, run without the UI */
+interface AutomationOutcome {
+  command: { action: string; copy: boolean; outputPath: string | null };
+  output: string | null;
+  error: string | null;
+}
+
+export function useAutomationEvents() {
+  const { addToast } = useToastStore();
+
+  useEffect(() => {
+    if (!isTauri()) return;
+    let disposed = false;
+    let unlisten: (() => void) | undefined;
+
+    listen<AutomationOutcome>("automation-outcome", (event) => {
+      const { command, output, error } = event.payload;
+      if (error) {
+        addToast(`${command.action}: ${error}`, "error");
+      } else if (output !== null && command.copy) {
+        addToast(`${command.action}: result copied to clipboard`, "success");
+      } else if (output !== null) {
+        // Commands run with --copy false leave the clipboard alone
+        addToast(`${command.action}: ${output}`, "success");
+      }
+    })
+      .then((fn) => {
+        if (disposed) {
+          fn();
+          return;
+        }
+        unlisten = fn;
+      })
+      .catch(console.error);
+
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, [addToast]);
+}
@@ -5,12 +5,14 @@ import type {
   LlmResponse,
   OpenRouterModel,
   OpenRouterProvider,
+  TypeGenBatchResponse,
 } from "../shared/api/apiClient";
 import {
   llmApi,
   type EnhancePayload,
   type LogEntry,
   type TranslatePayload,
+  type TypeGenBatchPayload,
   type TypeGenPayload,
 } from "../shared/api/apiClient";
 
@@ -41,6 +43,13 @@ export function useTypegenMutation() {
   });
 }
 
+export function useTypegenBatchMutation() {
+  return useMutation<TypeGenBatchResponse, unknown, TypeGenBatchPayload>({
+    mutationFn: (payload) => llmApi.typegenBatch(payload),
+    retry: 0,
+  });
+}
+
 export function useModelsQuery(
   config: LlmConfig,
   enabled: boolean
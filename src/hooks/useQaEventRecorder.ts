@@ -5,17 +5,45 @@ import { useQaSessionStore } from "../store/qaSession";
 import { useToastStore } from "../store/toast";
 import { isTauri } from "../utils/tauri";
 
-import { INPUT_DEBOUNCE_MS } from "../features/qa/recorder/constants";
+import {
+  DEFAULT_SELECTOR_ATTRIBUTES,
+  INPUT_DEBOUNCE_MS,
+  RECORDED_KEYS,
+  SCROLL_DEBOUNCE_MS,
+} from "../features/qa/recorder/constants";
 import {
   buildSelector,
+  describeFiles,
+  elementScope,
+  eventTarget,
   getElementText,
   getElementValue,
   isEditableTargetForManualClick,
+  keyCombo,
   maskValue,
+  selectorAttributes,
   shouldSkipManualClickFromPayload,
   stringifyMeta,
 } from "../features/qa/recorder/dom";
-import type { QaEventPayload } from "../features/qa/recorder/types";
+import type {
+  QaEventPayload,
+  QaNetworkPayload,
+} from "../features/qa/recorder/types";
+
+type RecorderWindow = Window & { __QA_SELECTOR_ATTRIBUTES__?: string[] };
+
+/** Hand the attributes to the recorder script of a same-origin preview frame */
+function shareSelectorAttributes(
+  frame: HTMLIFrameElement | null,
+  attributes: string[],
+) {
+  try {
+    const frameWindow = frame?.contentWindow as RecorderWindow | null | undefined;
+    if (frameWindow) frameWindow.__QA_SELECTOR_ATTRIBUTES__ = attributes;
+  } catch {
+    // Cross-origin frames get them from the proxy instead
+  }
+}
 
 export function useQaEventRecorder() {
   const location = useLocation();
@@ -38,9 +66,32 @@ export function useQaEventRecorder() {
   const sessionRef = useRef<string | null>(null);
   const retryTimeoutsRef = useRef<number[]>([]);
   const recordingDelayTimeoutRef = useRef<number | null>(null);
+  const selectorAttributesRef = useRef<string[]>(DEFAULT_SELECTOR_ATTRIBUTES);
 
   sessionRef.current = recordingSessionId;
 
+  // The session's selector attributes, or the configured ones
+  useEffect(() => {
+    selectorAttributesRef.current = DEFAULT_SELECTOR_ATTRIBUTES;
+    if (!isTauri() || !recordingSessionId) return;
+    let cancelled = false;
+    invoke<string[]>("qa_get_selector_attributes", { sessionId: recordingSessionId })
+      .then((attributes) => {
+        if (cancelled || attributes.length === 0) return;
+        selectorAttributesRef.current = attributes;
+        shareSelectorAttributes(
+          document.querySelector<HTMLIFrameElement>("[data-qa-preview-frame]"),
+          attributes,
+        );
+      })
+      .catch((err) => {
+        console.warn("[QA Recorder] Using default selector attributes", err);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [recordingSessionId]);
+
   useEffect(() => {
     recordingModeRef.current = recordingMode;
   }, [recordingMode]);
@@ -80,7 +131,15 @@ export function useQaEventRecorder() {
       }, delay);
     };
 
-    const allowedEventTypes = new Set(["click", "input", "submit"]);
+    const allowedEventTypes = new Set([
+      "click",
+      "input",
+      "submit",
+      "keydown",
+      "scroll",
+      "navigation",
+      "file_upload",
+    ]);
 
     const recordEvent = (payload: QaEventPayload) => {
       const normalizedType = payload.eventType.toLowerCase();
@@ -162,9 +221,9 @@ export function useQaEventRecorder() {
 
     const handleClick = (event: Event, root?: Element | null) => {
       if (isSyntheticEvent(event)) return;
-      const target = event.target;
-      if (!(target instanceof Element)) return;
-      if (shouldIgnoreTarget(target, root)) return;
+      const target = eventTarget(event);
+      if (!target || !(event.target instanceof Element)) return;
+      if (shouldIgnoreTarget(event.target, root)) return;
       if (!isArmedRef.current) {
         armRecording();
       }
@@ -178,21 +237,23 @@ export function useQaEventRecorder() {
 
       recordEvent({
         eventType: "click",
-        selector: buildSelector(target),
+        selector: buildSelector(target, selectorAttributesRef.current),
         elementText: getElementText(target),
         url: resolveUrl(target),
         metaJson: stringifyMeta({
           tag: target.tagName.toLowerCase(),
           coordinates: getCoordinates(event),
+          ...elementScope(target, selectorAttributesRef.current),
+          ...selectorAttributes(target, selectorAttributesRef.current),
         }),
       });
     };
 
     const handleInput = (event: Event, root?: Element | null) => {
       if (isSyntheticEvent(event)) return;
-      const target = event.target;
-      if (!(target instanceof Element)) return;
-      if (shouldIgnoreTarget(target, root)) return;
+      const target = eventTarget(event);
+      if (!target || !(event.target instanceof Element)) return;
+      if (shouldIgnoreTarget(event.target, root)) return;
       if (
         !(
           target instanceof HTMLInputElement ||
@@ -206,6 +267,26 @@ export function useQaEventRecorder() {
         armRecording();
       }
 
+      if (target instanceof HTMLInputElement && target.type === "file") {
+        const { value, files } = describeFiles(target);
+        recordEvent({
+          eventType: "file_upload",
+          selector: buildSelector(target, selectorAttributesRef.current),
+          elementText: getElementText(target),
+          value,
+          url: resolveUrl(target),
+          metaJson: stringifyMeta({
+            tag: "input",
+            type: "file",
+            multiple: target.multiple || undefined,
+            files,
+            ...elementScope(target, selectorAttributesRef.current),
+            ...selectorAttributes(target, selectorAttributesRef.current),
+          }),
+        });
+        return;
+      }
+
       const inputType = (event as InputEvent).inputType;
       const previousTimer = inputTimersRef.current.get(target);
       if (previousTimer) {
@@ -219,7 +300,7 @@ export function useQaEventRecorder() {
 
         recordEvent({
           eventType: "input",
-          selector: buildSelector(target),
+          selector: buildSelector(target, selectorAttributesRef.current),
           elementText: getElementText(target),
           value: maskedValue,
           url: resolveUrl(target),
@@ -228,6 +309,8 @@ export function useQaEventRecorder() {
             inputType,
             type: target instanceof HTMLInputElement ? target.type : undefined,
             coordinates: getCoordinates(event),
+            ...elementScope(target, selectorAttributesRef.current),
+            ...selectorAttributes(target, selectorAttributesRef.current),
           }),
         });
       }, INPUT_DEBOUNCE_MS);
@@ -237,9 +320,9 @@ export function useQaEventRecorder() {
 
     const handleSubmit = (event: Event, root?: Element | null) => {
       if (isSyntheticEvent(event)) return;
-      const target = event.target;
-      if (!(target instanceof Element)) return;
-      if (shouldIgnoreTarget(target, root)) return;
+      const target = eventTarget(event);
+      if (!target || !(event.target instanceof Element)) return;
+      if (shouldIgnoreTarget(event.target, root)) return;
       if (!isArmedRef.current) {
         armRecording();
       }
@@ -249,7 +332,7 @@ export function useQaEventRecorder() {
 
       recordEvent({
         eventType: "submit",
-        selector: buildSelector(element),
+        selector: buildSelector(element, selectorAttributesRef.current),
         elementText: getElementText(element),
         url: resolveUrl(element),
         metaJson: stringifyMeta({
@@ -257,24 +340,92 @@ export function useQaEventRecorder() {
           action: form?.action,
           method: form?.method,
           coordinates: getCoordinates(event),
+          ...elementScope(element, selectorAttributesRef.current),
+          ...selectorAttributes(element, selectorAttributesRef.current),
+        }),
+      });
+    };
+
+    const handleKeyDown = (event: Event, root?: Element | null) => {
+      if (isSyntheticEvent(event)) return;
+      const keyEvent = event as KeyboardEvent;
+      if (!RECORDED_KEYS.includes(keyEvent.key) || keyEvent.isComposing) return;
+      const target = eventTarget(event);
+      if (!target || !(event.target instanceof Element)) return;
+      if (shouldIgnoreTarget(event.target, root)) return;
+      if (!isArmedRef.current) return;
+
+      recordEvent({
+        eventType: "keydown",
+        selector: buildSelector(target, selectorAttributesRef.current),
+        elementText: getElementText(target),
+        value: keyCombo(keyEvent),
+        url: resolveUrl(target),
+        metaJson: stringifyMeta({
+          tag: target.tagName.toLowerCase(),
+          key: keyEvent.key,
+          ...elementScope(target, selectorAttributesRef.current),
+          ...selectorAttributes(target, selectorAttributesRef.current),
         }),
       });
     };
 
+    // One pending scroll per scrolled element, recorded once it settles
+    const scrollTimers = new Map<EventTarget, number>();
+
+    const handleScroll = (event: Event, root?: Element | null) => {
+      if (!isArmedRef.current) return;
+      const source = event.target;
+      if (!source) return;
+      const element = source instanceof Element ? source : null;
+      if (element && shouldIgnoreTarget(element, root)) return;
+      const view =
+        source instanceof Document
+          ? source.defaultView
+          : element?.ownerDocument?.defaultView;
+
+      const previousTimer = scrollTimers.get(source);
+      if (previousTimer) {
+        window.clearTimeout(previousTimer);
+      }
+      const nextTimer = window.setTimeout(() => {
+        scrollTimers.delete(source);
+        recordEvent({
+          eventType: "scroll",
+          selector: element
+            ? buildSelector(element, selectorAttributesRef.current)
+            : undefined,
+          url: view?.location?.href ?? window.location.href,
+          metaJson: stringifyMeta({
+            target: element ? "element" : "window",
+            scrollX: Math.round(element ? element.scrollLeft : view?.scrollX ?? 0),
+            scrollY: Math.round(element ? element.scrollTop : view?.scrollY ?? 0),
+          }),
+        });
+      }, SCROLL_DEBOUNCE_MS);
+      scrollTimers.set(source, nextTimer);
+    };
+
     const attachListeners = (doc: Document, root?: Element | null) => {
       const onPointerDown = (event: Event) => handlePointerDown(event, root);
       const onClick = (event: Event) => handleClick(event, root);
       const onInput = (event: Event) => handleInput(event, root);
       const onSubmit = (event: Event) => handleSubmit(event, root);
+      const onKeyDown = (event: Event) => handleKeyDown(event, root);
+      const onScroll = (event: Event) => handleScroll(event, root);
       doc.addEventListener("pointerdown", onPointerDown, true);
       doc.addEventListener("click", onClick, true);
       doc.addEventListener("input", onInput, true);
       doc.addEventListener("submit", onSubmit, true);
+      doc.addEventListener("keydown", onKeyDown, true);
+      doc.addEventListener("scroll", onScroll, true);
       return () => {
         doc.removeEventListener("pointerdown", onPointerDown, true);
         doc.removeEventListener("click", onClick, true);
         doc.removeEventListener("input", onInput, true);
         doc.removeEventListener("submit", onSubmit, true);
+        doc.removeEventListener("keydown", onKeyDown, true);
+        doc.removeEventListener("scroll", onScroll, true);
       };
     };
 
@@ -356,6 +507,30 @@ export function useQaEventRecorder() {
       console.warn("[QA Recorder] Frame element not found in DOM");
     }
 
+    // API calls are context for the user's actions, not steps of their own;
+    // manual mode records only the step that was armed
+    const recordNetwork = (payload: QaNetworkPayload) => {
+      const sessionId = sessionRef.current;
+      if (!sessionId || recordingModeRef.current === "manual") return;
+
+      const event: QaEventPayload = {
+        eventType: "api_response",
+        url: payload.url,
+        metaJson: JSON.stringify({
+          method: payload.method,
+          url: payload.url,
+          status: payload.status,
+          timing_ms: payload.timingMs,
+        }),
+        runId: activeRunId ?? undefined,
+        origin: "system",
+        recordingMode: recordingModeRef.current,
+      };
+      invoke("qa_record_event", { event, sessionId }).catch((err) => {
+        console.error("[QA Recorder] Failed to record network call", err);
+      });
+    };
+
     // Listen for postMessage events from iframe
     const handleIframeMessage = (event: MessageEvent) => {
       if (event.data?.type === "qa-recorder-ready") {
@@ -377,6 +552,11 @@ export function useQaEventRecorder() {
           return;
         }
         recordEvent(payload);
+        return;
+      }
+
+      if (event.data?.type === "qa-recorder-network") {
+        recordNetwork(event.data.payload as QaNetworkPayload);
       }
     };
 
@@ -389,6 +569,7 @@ export function useQaEventRecorder() {
         if (frameDoc) {
           // Same-origin: inject directly
           console.log("[QA Recorder] Same-origin iframe, injecting script...");
+          shareSelectorAttributes(targetFrame, selectorAttributesRef.current);
           const script = frameDoc.createElement("script");
           script.src = "/qa-recorder-inject.js";
           frameDoc.head.appendChild(script);
@@ -454,6 +635,8 @@ export function useQaEventRecorder() {
 
       inputTimersRef.current.forEach((timer) => window.clearTimeout(timer));
       inputTimersRef.current.clear();
+      scrollTimers.forEach((timer) => window.clearTimeout(timer));
+      scrollTimers.clear();
     };
   }, [recordingSessionId, addToast]);
 
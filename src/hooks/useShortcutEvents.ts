@@ -2,17 +2,12 @@ import { useEffect } from "react";
 import { listen } from "@tauri-apps/api/event";
 import { isTauri } from "../utils/tauri";
 
-interface ShortcutEventsOptions {
-  onCapture: (payload: string) => void;
-}
-
-export function useShortcutEvents({ onCapture }: ShortcutEventsOptions) {
+export function useShortcutEvents() {
   useEffect(() => {
     if (!isTauri()) return;
     let disposed = false;
     let unlistenStart: (() => void) | undefined;
     let unlistenEnd: (() => void) | undefined;
-    let unlistenCapture: (() => void) | undefined;
 
     listen("shortcut-start", (event) => {
       window.dispatchEvent(
@@ -42,23 +37,10 @@ export function useShortcutEvents({ onCapture }: ShortcutEventsOptions) {
       })
       .catch(console.error);
 
-    listen<string>("shortcut-capture", (event) => {
-      onCapture(event.payload);
-    })
-      .then((unlisten) => {
-        if (disposed) {
-          unlisten();
-          return;
-        }
-        unlistenCapture = unlisten;
-      })
-      .catch(console.error);
-
     return () => {
       disposed = true;
       unlistenStart?.();
       unlistenEnd?.();
-      unlistenCapture?.();
     };
-  }, [onCapture]);
+  }, []);
 }
@@ -0,0 +1,88 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { useSettingsStore, type LLMProvider } from "../store/settings";
+import { isTauri } from "../utils/tauri";
+
+const KEYED_PROVIDERS: LLMProvider[] = [
+  "openai",
+  "gemini",
+  "openrouter",
+  "dll",
+  "cli_proxy",
+];
+const SAVE_DELAY_MS = 500;
+
+/**
+ * API keys live in the OS keychain instead of localStorage. After hydration
+ * this moves keys persisted by older builds into the keychain, loads the
+ * stored keys into memory and writes later edits back.
+ */
+export function useSyncApiKeys() {
+  const [hydrated, setHydrated] = useState(
+    useSettingsStore.persist.hasHydrated()
+  );
+
+  useEffect(() => {
+    return useSettingsStore.persist.onFinishHydration(() => {
+      setHydrated(true);
+    });
+  }, []);
+
+  useEffect(() => {
+    if (!isTauri() || !hydrated) return;
+    let cancelled = false;
+    let timer: ReturnType<typeof setTimeout> | undefined;
+    let unsubscribe = () => {};
+    const saved: Partial<Record<LLMProvider, string>> = {};
+
+    const save = (provider: LLMProvider, key: string) =>
+      invoke("save_api_key", { provider, key })
+        .then(() => {
+          saved[provider] = key;
+        })
+        .catch(console.error);
+
+    (async () => {
+      const legacyKeys = useSettingsStore.getState().apiKeys;
+      for (const [provider, key] of Object.entries(legacyKeys)) {
+        if (key) await save(provider as LLMProvider, key);
+      }
+
+      for (const provider of KEYED_PROVIDERS) {
+        if (legacyKeys[provider]) continue;
+        const key = await invoke<string | null>("get_api_key", {
+          provider,
+        }).catch((err) => {
+          console.error(err);
+          return null;
+        });
+        if (cancelled) return;
+        saved[provider] = key ?? "";
+        if (key) useSettingsStore.getState().setApiKey(provider, key);
+      }
+      if (cancelled) return;
+
+      unsubscribe = useSettingsStore.subscribe((state, prev) => {
+        if (state.apiKeys === prev.apiKeys) return;
+        clearTimeout(timer);
+        timer = setTimeout(() => {
+          const apiKeys = useSettingsStore.getState().apiKeys;
+          const providers = new Set([
+            ...Object.keys(saved),
+            ...Object.keys(apiKeys),
+          ]) as Set<LLMProvider>;
+          for (const provider of providers) {
+            const key = apiKeys[provider] ?? "";
+            if (key !== (saved[provider] ?? "")) void save(provider, key);
+          }
+        }, SAVE_DELAY_MS);
+      });
+    })();
+
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+      unsubscribe();
+    };
+  }, [hydrated]);
+}
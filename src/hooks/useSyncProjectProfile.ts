@@ -0,0 +1,40 @@
+import { useEffect } from "react";
+import {
+  getActiveProjectProfile,
+  lastPickedUpProjectProfile,
+  onProjectProfileApplied,
+  pickUpProjectProfile,
+} from "../features/settings/api/projectProfiles";
+import { isTauri } from "../utils/tauri";
+
+export function useSyncProjectProfile() {
+  useEffect(() => {
+    if (!isTauri()) return;
+    let disposed = false;
+    let unlisten: (() => void) | undefined;
+
+    // A profile applied at launch was applied before anything listened
+    getActiveProjectProfile()
+      .then((active) => {
+        if (!disposed && active && active.id !== lastPickedUpProjectProfile()) {
+          pickUpProjectProfile(active);
+        }
+      })
+      .catch(console.error);
+
+    onProjectProfileApplied((applied) => pickUpProjectProfile(applied.profile))
+      .then((fn) => {
+        if (disposed) {
+          fn();
+          return;
+        }
+        unlisten = fn;
+      })
+      .catch(console.error);
+
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, []);
+}
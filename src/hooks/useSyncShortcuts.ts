@@ -3,8 +3,15 @@ import { invoke } from "@tauri-apps/api/core";
 import { useShallow } from "zustand/shallow";
 import { useSettingsStore } from "../store/settings";
 import { isTauri } from "../utils/tauri";
+import {
+  onSettingsChanged,
+  type ShortcutSettings,
+} from "../features/settings/api/settings";
 
 export function useSyncShortcuts() {
+  const setShortcutsEnabled = useSettingsStore(
+    (state) => state.setShortcutsEnabled,
+  );
   const { shortcutsEnabled, translate, enhance, popup, terminal } =
     useSettingsStore(
       useShallow((state) => ({
@@ -26,4 +33,32 @@ export function useSyncShortcuts() {
       terminal,
     }).catch(console.error);
   }, [shortcutsEnabled, translate, enhance, popup, terminal]);
+
+  // The tray menu toggles shortcuts in the backend; keep the store in step
+  useEffect(() => {
+    if (!isTauri()) return;
+    let disposed = false;
+    let unlisten: (() => void) | undefined;
+
+    onSettingsChanged((change) => {
+      if (change.section !== "shortcuts") return;
+      const value = change.value as ShortcutSettings;
+      if (useSettingsStore.getState().shortcutsEnabled !== value.enabled) {
+        setShortcutsEnabled(value.enabled);
+      }
+    })
+      .then((fn) => {
+        if (disposed) {
+          fn();
+          return;
+        }
+        unlisten = fn;
+      })
+      .catch(console.error);
+
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, [setShortcutsEnabled]);
 }
@@ -5,16 +5,20 @@ import App from "./app/App";
 import { queryClient } from "./app/queryClient";
 import GlobalLoader from "./shared/components/GlobalLoader";
 import DetachedTerminal from "./shared/components/DetachedTerminal";
+import QuickActionWindow from "./features/quick-action";
 
 const isLoader =
   window.location.search.includes("label=loading") || window.name === "loading";
 const isTerminalDetach = window.location.search.includes("label=terminal");
+const isQuickAction = window.location.search.includes("label=quick-action");
 
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
     <QueryClientProvider client={queryClient}>
       {isTerminalDetach ? (
         <DetachedTerminal />
+      ) : isQuickAction ? (
+        <QuickActionWindow />
       ) : isLoader ? (
         <GlobalLoader />
       ) : (
@@ -1,12 +1,26 @@
 import axios from 'axios';
+import { DEFAULT_API_BASE_URL, getHttpServerInfo } from './httpServer';
+import type { AppErrorPayload } from '../../utils/appError';
 
 const apiClient = axios.create({
-  baseURL: 'http://localhost:3001/api',
+  baseURL: DEFAULT_API_BASE_URL,
   headers: {
     'Content-Type': 'application/json',
   },
 });
 
+// The port and token are configurable, so resolve them before each request
+apiClient.interceptors.request.use(async (config) => {
+  const info = await getHttpServerInfo();
+  if (info?.baseUrl) {
+    config.baseURL = info.baseUrl;
+  }
+  if (info?.token) {
+    config.headers.Authorization = `Bearer ${info.token}`;
+  }
+  return config;
+});
+
 export interface LogEntry {
   time: string;
   level: string;
@@ -93,20 +107,59 @@ export interface EnhancePayload {
   config: LlmConfig;
   content: string;
   system_prompt?: string;
+  /** Feature whose model override applies; `enhance` when omitted */
+  feature?: 'enhance' | 'ragChat';
+  /** Language the answer must be in, over the `outputLanguage` setting */
+  output_language?: string;
+  /** Enhancement preset id from the `enhancePresets` settings */
+  preset_id?: string;
 }
 
 export interface TypeGenPayload {
   config: LlmConfig;
   json: string;
+  samples?: string[];
   language: string;
   root_name: string;
   mode: 'auto' | 'offline' | 'llm';
+  options?: TypeGenOptions;
+}
+
+export interface TypeGenBatchPayload extends Omit<TypeGenPayload, 'language'> {
+  /** Target languages; repeats are generated once */
+  languages: string[];
+}
+
+export type TypeGenNullableStrategy =
+  | 'native'
+  | 'default'
+  | 'undefined'
+  | 'optional';
+
+export type TypeGenNamingConvention = 'camel_case' | 'pascal_case' | 'snake_case';
+
+export interface TypeGenOptions {
+  serialization_helpers?: boolean;
+  builders?: boolean;
+  /** How fields that can be null are typed; other languages keep their native nullable type */
+  nullable?: TypeGenNullableStrategy;
+  /** Whether fields missing from some samples are nullable */
+  missing_fields?: 'optional' | 'required';
+  /** Field naming instead of the language's own; the language's when null */
+  field_naming?: TypeGenNamingConvention | null;
 }
 
 export interface LlmResponse {
   result: string;
 }
 
+export interface TypeGenBatchResponse {
+  /** Generated code by language, as named in the request */
+  results: Record<string, string>;
+  /** Languages that failed, with the reason */
+  errors?: Record<string, AppErrorPayload>;
+}
+
 export const llmApi = {
   translate: async (payload: TranslatePayload): Promise<LlmResponse> => {
     const response = await apiClient.post<LlmResponse>('/translate', payload);
@@ -120,6 +173,15 @@ export const llmApi = {
     const response = await apiClient.post<LlmResponse>('/typegen', payload);
     return response.data;
   },
+  typegenBatch: async (
+    payload: TypeGenBatchPayload
+  ): Promise<TypeGenBatchResponse> => {
+    const response = await apiClient.post<TypeGenBatchResponse>(
+      '/typegen/batch',
+      payload
+    );
+    return response.data;
+  },
   getModels: async (config: LlmConfig): Promise<string[]> => {
     const response = await apiClient.post<string[]>('/models', config);
     return response.data;
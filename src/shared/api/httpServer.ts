@@ -0,0 +1,91 @@
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+
+export const DEFAULT_API_BASE_URL = 'http://localhost:3001/api';
+
+export interface HttpServerSettings {
+  bindAddress: string;
+  port: number;
+  /** Pick a free port when `port` is taken and remember it */
+  autoPort: boolean;
+  /** Require `Authorization: Bearer <token>` on every request */
+  requireToken: boolean;
+  /** Token buckets per endpoint; the LLM endpoints are limited when omitted */
+  rateLimits?: HttpRateLimit[];
+}
+
+/** Token bucket shared by every client of one endpoint, e.g. "/api/translate" */
+export interface HttpRateLimit {
+  path: string;
+  requestsPerMinute: number;
+  /** Requests let through at once after a quiet period */
+  burst: number;
+}
+
+/** Requests to a limited endpoint since the server last started */
+export interface RateLimitMetrics extends HttpRateLimit {
+  allowed: number;
+  /** Answered with 429 */
+  limited: number;
+  lastLimitedAt: number | null;
+}
+
+export interface HttpServerInfo {
+  settings: HttpServerSettings;
+  running: boolean;
+  baseUrl: string | null;
+  token: string | null;
+  /** `access_token` for QA proxy iframe URLs; only valid for /api/qa/proxy */
+  proxyToken: string | null;
+  rateLimits: RateLimitMetrics[];
+}
+
+let cachedInfo: HttpServerInfo | null = null;
+let pendingInfo: Promise<HttpServerInfo | null> | null = null;
+
+/** Server info, fetched once and refreshed when the settings change. */
+export function getHttpServerInfo(): Promise<HttpServerInfo | null> {
+  if (!pendingInfo) {
+    pendingInfo = invoke<HttpServerInfo>('http_server_info')
+      .then((info) => {
+        cachedInfo = info;
+        return info;
+      })
+      .catch(() => null);
+  }
+  return pendingInfo;
+}
+
+/** Last known server info, for callers that cannot wait (e.g. iframe URLs). */
+export function getCachedHttpServerInfo(): HttpServerInfo | null {
+  return cachedInfo;
+}
+
+export function invalidateHttpServerInfo() {
+  pendingInfo = null;
+}
+
+function remember(info: HttpServerInfo): HttpServerInfo {
+  cachedInfo = info;
+  pendingInfo = Promise.resolve(info);
+  return info;
+}
+
+export async function updateHttpServerSettings(
+  settings: HttpServerSettings
+): Promise<HttpServerInfo> {
+  return remember(
+    await invoke<HttpServerInfo>('http_server_update_settings', { settings })
+  );
+}
+
+export async function rotateHttpServerToken(): Promise<HttpServerInfo> {
+  return remember(await invoke<HttpServerInfo>('http_server_rotate_token'));
+}
+
+// The server may restart on another port after an import or auto port pick
+listen<{ section: string }>('settings-changed', (event) => {
+  if (event.payload.section === 'httpServer') {
+    invalidateHttpServerInfo();
+  }
+}).catch(() => undefined);
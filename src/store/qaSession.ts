@@ -8,6 +8,8 @@ interface QaSessionState {
   activeSessionId: string | null;
   activeRunId: string | null;
   recordingSessionId: string | null;
+  /** Browser recorder run of each session that is recording */
+  recorderRuns: Record<string, string>;
   recordingMode: RecordingMode;
   screenshotMode: ScreenshotMode;
   captureMode: CaptureMode;
@@ -18,6 +20,7 @@ interface QaSessionState {
   setActiveSessionId: (id: string | null) => void;
   setActiveRunId: (id: string | null) => void;
   setRecordingSessionId: (id: string | null) => void;
+  setRecorderRun: (sessionId: string, runId: string | null) => void;
   setRecordingMode: (mode: RecordingMode) => void;
   setScreenshotMode: (mode: ScreenshotMode) => void;
   setCaptureMode: (mode: CaptureMode) => void;
@@ -31,6 +34,7 @@ export const useQaSessionStore = create<QaSessionState>((set) => ({
   activeSessionId: null,
   activeRunId: null,
   recordingSessionId: null,
+  recorderRuns: {},
   recordingMode: "auto",
   screenshotMode: "auto",
   captureMode: "windowed_frame",
@@ -41,6 +45,13 @@ export const useQaSessionStore = create<QaSessionState>((set) => ({
   setActiveSessionId: (activeSessionId) => set({ activeSessionId }),
   setActiveRunId: (activeRunId) => set({ activeRunId }),
   setRecordingSessionId: (recordingSessionId) => set({ recordingSessionId }),
+  setRecorderRun: (sessionId, runId) =>
+    set((state) => {
+      const recorderRuns = { ...state.recorderRuns };
+      if (runId) recorderRuns[sessionId] = runId;
+      else delete recorderRuns[sessionId];
+      return { recorderRuns };
+    }),
   setRecordingMode: (recordingMode) => set({ recordingMode }),
   setScreenshotMode: (screenshotMode) => set({ screenshotMode }),
   setCaptureMode: (captureMode) => set({ captureMode }),
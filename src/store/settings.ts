@@ -90,7 +90,7 @@ export const DEFAULT_TEMPLATES: PromptTemplate[] = [
 export interface SettingsState {
   provider: LLMProvider;
   model: string;
-  // Per-provider API keys - each provider has its own key storage
+  // Per-provider API keys, loaded from the OS keychain and never persisted here
   apiKeys: Partial<Record<LLMProvider, string>>;
   baseUrl: string;
   localModels: string[];
@@ -421,6 +421,8 @@ export const useSettingsStore = create<SettingsState>()(
     {
       name: "promptbridge-settings",
       version: 3,
+      // API keys are kept in the OS keychain (see useSyncApiKeys)
+      partialize: ({ apiKeys: _apiKeys, ...rest }) => rest,
       migrate: (state) => {
         const persisted = state as SettingsState & {
           mode?: string;
@@ -1,3 +1,5 @@
+import type { DiagnosticCheck } from "../../features/settings/api/diagnostics";
+
 export type QaSessionType = "browser" | "api";
 
 export interface QaSession {
@@ -15,6 +17,16 @@ export interface QaSession {
   started_at: number;
   ended_at?: string | null;
   notes?: string | null;
+  /** Selector attributes in priority order; null uses the `qaSelectors` settings */
+  selector_attributes?: string[] | null;
+}
+
+/** Session started or recorded into during this app run */
+export interface QaActiveSession {
+  session_id: string;
+  session_type: QaSessionType | null;
+  recorder_run_id: string | null;
+  recorder_mode: "manual" | "ai" | null;
 }
 
 export interface QaEvent {
@@ -33,6 +45,7 @@ export interface QaEvent {
   url?: string | null;
   screenshot_id?: string | null;
   screenshot_path?: string | null;
+  annotated_screenshot_path?: string | null;
   meta_json?: string | null;
 }
 
@@ -56,6 +69,16 @@ export type QaCheckpoint = {
   startEventSeq: number;
   endEventSeq: number;
   createdAt: number;
+  /** Tester annotation, passed along to the summary and test case prompts */
+  notes?: string | null;
+  /** When the event range last changed through a merge or split */
+  updatedAt?: number | null;
+};
+
+export type QaCheckpointEdit = {
+  checkpoints: QaCheckpoint[];
+  /** Checkpoints whose summaries and test cases should be regenerated */
+  affectedCheckpointIds: string[];
 };
 
 export type QaCheckpointSummary = {
@@ -65,8 +88,42 @@ export type QaCheckpointSummary = {
   entitiesJson?: string | null;
   risksJson?: string | null;
   createdAt: number;
+  /** JSON `QaActionApiCalls[]`: the checkpoint's actions with the API calls they triggered */
+  apiLinksJson?: string | null;
 };
 
+/** An `api_response` event linked to the click/submit that triggered it */
+export type QaApiLink = {
+  sessionId: string;
+  responseEventId: string;
+  responseSeq: number;
+  triggerEventId: string;
+  triggerSeq: number;
+  /** From the action to the start of the request (ms) */
+  delayMs: number;
+  /** The action was on a page of the API's origin */
+  sameOrigin: boolean;
+};
+
+export type QaLinkedApiCall = {
+  responseSeq: number;
+  method?: string | null;
+  url?: string | null;
+  status?: number | null;
+  delayMs: number;
+};
+
+export type QaActionApiCalls = {
+  triggerSeq: number;
+  triggerType: string;
+  selector?: string | null;
+  elementText?: string | null;
+  calls: QaLinkedApiCall[];
+};
+
+/** `negative` asks only for failure cases built on the recorded fields and API requests */
+export type QaTestGenerationMode = "standard" | "negative";
+
 export type QaTestCase = {
   id: string;
   sessionId: string;
@@ -78,9 +135,65 @@ export type QaTestCase = {
   priority?: string | null;
   status?: string | null;
   dedupHash: string;
+  /** `QaEndpointAssertions[]` suggested for the flow's API calls, as JSON */
+  assertionsJson?: string | null;
   createdAt: number;
 };
 
+/** Cucumber feature file built from the test cases of one checkpoint */
+export type QaGherkinFeature = {
+  /** Null for the feature holding cases outside any checkpoint */
+  checkpointId?: string | null;
+  fileName: string;
+  content: string;
+  scenarioCount: number;
+};
+
+export type TestManagementProvider = "xray" | "testrail";
+
+export type QaTestResultStatus = "passed" | "failed" | "blocked" | "skipped";
+
+export type QaTestResultInput = {
+  testCaseId: string;
+  status: QaTestResultStatus;
+  comment?: string | null;
+};
+
+export type QaTestSyncEntry = {
+  id: string;
+  sessionId: string;
+  provider: TestManagementProvider;
+  /** `case` for a pushed test case, `result` for a pushed run result */
+  action: "case" | "result";
+  testCaseId?: string | null;
+  /** Xray issue key or TestRail case id */
+  externalId?: string | null;
+  status: "created" | "skipped" | "pushed" | "failed";
+  message?: string | null;
+  createdAt: number;
+};
+
+export type QaTestSyncReport = {
+  provider: TestManagementProvider;
+  created: number;
+  skipped: number;
+  failed: number;
+  /** Xray test execution key or TestRail run id */
+  runKey?: string | null;
+  entries: QaTestSyncEntry[];
+};
+
+export type QaTestManagementCredentials = {
+  xray: boolean;
+  testrail: boolean;
+};
+
+export type QaCheckpointRegeneration = {
+  checkpointIds: string[];
+  summaries: QaCheckpointSummary[];
+  testCases: QaTestCase[];
+};
+
 export type QaLlmRun = {
   id: string;
   scope: string;
@@ -91,6 +204,21 @@ export type QaLlmRun = {
   inputSummary?: string | null;
   outputJson: string;
   createdAt: number;
+  promptTokens?: number | null;
+  completionTokens?: number | null;
+  /** No usage reported by the provider; counts are estimated */
+  tokensEstimated?: boolean;
+  latencyMs?: number | null;
+  /** Set when the model has a price in the `qaLlmAudit` settings */
+  costUsd?: number | null;
+};
+
+export type QaLlmRunDetail = QaLlmRun & {
+  systemPrompt?: string | null;
+  userPrompt?: string | null;
+  responseText?: string | null;
+  /** A body was cut to `maxBodyChars` */
+  truncated: boolean;
 };
 
 export type QaSessionRun = {
@@ -118,6 +246,68 @@ export type QaRunStreamEvent = {
   payloadJson?: string | null;
 };
 
+export type QaJsonValueType =
+  | "string"
+  | "number"
+  | "boolean"
+  | "object"
+  | "array"
+  | "null";
+
+/** Header and JSON path checks without a value only require presence */
+export type QaApiAssertion =
+  | { kind: "status"; equals: number }
+  | {
+      kind: "header";
+      name: string;
+      equals?: string | null;
+      contains?: string | null;
+    }
+  | {
+      kind: "jsonPath";
+      /** e.g. `$.data.items[0].id` */
+      path: string;
+      equals?: unknown;
+      type?: QaJsonValueType | null;
+    };
+
+/** Assertions suggested for one endpoint from the responses it gave in a session */
+export type QaEndpointAssertions = {
+  method: string;
+  path: string;
+  callCount: number;
+  assertions: QaApiAssertion[];
+  /** JSON paths whose values changed between responses */
+  changingPaths: string[];
+};
+
+export type QaApiAssertionResult = {
+  assertion: QaApiAssertion;
+  description: string;
+  passed: boolean;
+  actual?: string | null;
+};
+
+export type QaApiTestResult = {
+  testCaseId: string;
+  title: string;
+  status: QaTestResultStatus;
+  responseStatus?: number | null;
+  durationMs?: number | null;
+  assertions: QaApiAssertionResult[];
+  error?: string | null;
+  apiCallId?: string | null;
+};
+
+/** Result of `qa_run_api_tests`, one `api_regression` run */
+export type QaApiTestRunReport = {
+  run: QaSessionRun;
+  results: QaApiTestResult[];
+  passed: number;
+  /** Failed or blocked */
+  failed: number;
+};
+
 export type ExploreResult = {
   checkpoints: QaCheckpoint[];
   summaries: QaCheckpointSummary[];
@@ -125,4 +315,24 @@ export type ExploreResult = {
   llmRuns: QaLlmRun[];
   postSubmitDetected: boolean;
   detectedPatterns: string[];
+  /** UI anomalies the vision model found in the checkpoint's screenshots */
+  visualAnomalies: string[];
+};
+
+export type NodeRuntimeStage = "node" | "scripts" | "playwright" | "browser" | "ready";
+
+/** Emitted on `qa-runtime-progress` while Playwright or Chromium installs. */
+export type NodeRuntimeProgress = {
+  stage: NodeRuntimeStage;
+  message: string;
+};
+
+export type RecorderEnvironment = {
+  /** Everything is installed; recording starts without a download */
+  ready: boolean;
+  /** Nothing is missing that the app cannot install by itself */
+  installable: boolean;
+  runtimeDir: string;
+  playwrightVersion: string;
+  checks: DiagnosticCheck[];
 };
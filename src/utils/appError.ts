@@ -0,0 +1,57 @@
+/** Stable error code from the backend; see `ErrorCode` in `src-tauri/src/domain/error.rs` */
+export type AppErrorCode =
+  | "internal"
+  | "not_found"
+  | "validation"
+  | "parse"
+  | "llm"
+  | "security"
+  | "database"
+  | "io"
+  | "dependency_missing"
+  | "dependency_outdated"
+  | "dependency_install_failed"
+  | "config_missing"
+  | "network_unavailable"
+  | "provider_unauthorized"
+  | "provider_rate_limited";
+
+export type AppErrorCategory =
+  | "validation"
+  | "notFound"
+  | "config"
+  | "network"
+  | "dependency"
+  | "security"
+  | "storage"
+  | "internal";
+
+/** Shape of a rejected Tauri command and of HTTP API error bodies */
+export interface AppErrorPayload {
+  code: AppErrorCode;
+  category: AppErrorCategory;
+  message: string;
+  /** What the user can do about it */
+  hint?: string;
+}
+
+export function isAppError(err: unknown): err is AppErrorPayload {
+  return (
+    typeof err === "object" &&
+    err !== null &&
+    typeof (err as AppErrorPayload).code === "string" &&
+    typeof (err as AppErrorPayload).message === "string"
+  );
+}
+
+/** Message of a failed call, followed by the backend's remediation hint */
+export function formatAppError(err: unknown, fallback = "Unknown error"): string {
+  if (isAppError(err)) {
+    if (!err.hint) return err.message;
+    return `${err.message.replace(/[.\s]+$/, "")}. ${err.hint}`;
+  }
+  if (err instanceof Error) return err.message;
+  if (typeof err === "string") return err || fallback;
+  if (err && typeof err === "object") return JSON.stringify(err);
+  return fallback;
+}